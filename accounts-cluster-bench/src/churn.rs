@@ -0,0 +1,453 @@
+//! Account churn generator.
+//!
+//! Creates accounts of configurable sizes and owners against a cluster and closes them again once
+//! their lifetime has passed, leaving behind the dead accounts that accounts-db clean and shrink
+//! have to deal with. The generator only builds transactions, so it can be driven by anything
+//! that is able to send them; [`run_account_churn`] drives it through an [`RpcClient`].
+use {
+    log::*,
+    rand::{thread_rng, Rng},
+    solana_client::transaction_executor::TransactionExecutor,
+    solana_inline_spl::token,
+    solana_rpc_client::rpc_client::RpcClient,
+    solana_sdk::{
+        hash::Hash,
+        instruction::Instruction,
+        program_pack::Pack,
+        pubkey::Pubkey,
+        rent::Rent,
+        signature::{Keypair, Signer},
+        system_instruction, system_program, sysvar,
+        transaction::Transaction,
+    },
+    spl_token::state::Account,
+    std::{
+        collections::BTreeMap,
+        ops::Range,
+        sync::Arc,
+        thread::sleep,
+        time::{Duration, Instant},
+    },
+};
+
+/// Owner of the accounts created by the churn generator.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChurnOwner {
+    /// Accounts owned by the system program. The system program can only move lamports out of
+    /// accounts without data, so these are always created with zero space.
+    System,
+    /// Token accounts of `mint`, closed through the token program.
+    Token { mint: Pubkey },
+    /// Accounts assigned to an arbitrary program. The generator can not close these again, so
+    /// they are never reclaimed.
+    Program(Pubkey),
+}
+
+impl ChurnOwner {
+    fn program_id(&self) -> Pubkey {
+        match self {
+            Self::System => system_program::id(),
+            Self::Token { .. } => token::id(),
+            Self::Program(program_id) => *program_id,
+        }
+    }
+
+    fn is_closable(&self) -> bool {
+        !matches!(self, Self::Program(_))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AccountChurnConfig {
+    /// Number of accounts created in every round.
+    pub accounts_per_round: usize,
+    /// Data size of `ChurnOwner::Program` accounts, sampled for every account.
+    pub space: Range<u64>,
+    /// Number of rounds an account is kept alive before it is closed, sampled for every account.
+    pub lifetime: Range<u64>,
+    /// Owners to pick from for every new account.
+    pub owners: Vec<ChurnOwner>,
+    /// Maximum number of accounts created or closed by a single transaction.
+    pub accounts_per_transaction: usize,
+}
+
+impl Default for AccountChurnConfig {
+    fn default() -> Self {
+        Self {
+            accounts_per_round: 100,
+            space: 0..1_000,
+            lifetime: 1..10,
+            owners: vec![ChurnOwner::System],
+            accounts_per_transaction: 2,
+        }
+    }
+}
+
+/// Counts of what the generator produced. Transactions can still fail once they reach the
+/// cluster, so these are upper bounds of the churn that actually happened.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AccountChurnStats {
+    pub rounds: u64,
+    pub accounts_created: u64,
+    pub accounts_closed: u64,
+    pub transactions_sent: u64,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct LiveAccount {
+    seed: u64,
+    owner: ChurnOwner,
+    lamports: u64,
+}
+
+/// Builds the create and close transactions of an account churn.
+///
+/// All accounts are derived from a fresh base keypair with increasing seeds, which makes them
+/// easy to tell apart from the rest of the cluster's accounts.
+pub struct AccountChurn {
+    config: AccountChurnConfig,
+    rent: Rent,
+    base_keypair: Keypair,
+    next_seed: u64,
+    round: u64,
+    /// Closable accounts that are still alive, keyed by the round they expire in
+    live_accounts: BTreeMap<u64, Vec<LiveAccount>>,
+    stats: AccountChurnStats,
+}
+
+impl AccountChurn {
+    pub fn new(config: AccountChurnConfig, rent: Rent) -> Self {
+        assert!(!config.owners.is_empty(), "at least one owner is required");
+        assert!(
+            config.accounts_per_transaction > 0,
+            "accounts_per_transaction must be positive"
+        );
+        assert!(
+            config.lifetime.start > 0 && !config.lifetime.is_empty(),
+            "lifetime must be a non-empty range of at least one round"
+        );
+        assert!(!config.space.is_empty(), "space must be a non-empty range");
+        Self {
+            config,
+            rent,
+            base_keypair: Keypair::new(),
+            next_seed: 0,
+            round: 0,
+            live_accounts: BTreeMap::new(),
+            stats: AccountChurnStats::default(),
+        }
+    }
+
+    pub fn base_pubkey(&self) -> Pubkey {
+        self.base_keypair.pubkey()
+    }
+
+    pub fn stats(&self) -> AccountChurnStats {
+        self.stats
+    }
+
+    /// Number of created accounts that will still be closed by the churn.
+    pub fn num_live_accounts(&self) -> usize {
+        self.live_accounts.values().map(Vec::len).sum()
+    }
+
+    /// Address of the account created with `seed` for `owner`.
+    pub fn address(&self, seed: u64, owner: &ChurnOwner) -> Pubkey {
+        Pubkey::create_with_seed(&self.base_pubkey(), &seed.to_string(), &owner.program_id())
+            .unwrap()
+    }
+
+    /// Advances the churn by one round, returning the transactions that close the accounts
+    /// whose lifetime ended followed by the transactions that create the round's new accounts.
+    pub fn next_round(&mut self, payer: &Keypair, blockhash: Hash) -> Vec<Transaction> {
+        self.round += 1;
+        let expired = self.take_expired(self.round);
+        let mut transactions = self.make_close_transactions(payer, &expired, blockhash);
+        transactions.extend(self.make_create_transactions(payer, blockhash));
+        self.stats.rounds += 1;
+        self.stats.transactions_sent += transactions.len() as u64;
+        transactions
+    }
+
+    /// Returns the transactions that close every account which is still alive, regardless of
+    /// its remaining lifetime.
+    pub fn close_all(&mut self, payer: &Keypair, blockhash: Hash) -> Vec<Transaction> {
+        let remaining = self.take_expired(u64::MAX);
+        let transactions = self.make_close_transactions(payer, &remaining, blockhash);
+        self.stats.transactions_sent += transactions.len() as u64;
+        transactions
+    }
+
+    fn take_expired(&mut self, round: u64) -> Vec<LiveAccount> {
+        let mut expired = vec![];
+        while let Some(entry) = self.live_accounts.first_entry() {
+            if *entry.key() > round {
+                break;
+            }
+            expired.extend(entry.remove());
+        }
+        expired
+    }
+
+    fn make_create_transactions(&mut self, payer: &Keypair, blockhash: Hash) -> Vec<Transaction> {
+        let mut rng = thread_rng();
+        let new_accounts: Vec<_> = (0..self.config.accounts_per_round)
+            .map(|_| {
+                let owner = self.config.owners[rng.gen_range(0..self.config.owners.len())];
+                let space = match owner {
+                    ChurnOwner::System => 0,
+                    ChurnOwner::Token { .. } => Account::get_packed_len() as u64,
+                    ChurnOwner::Program(_) => rng.gen_range(self.config.space.clone()),
+                };
+                let account = LiveAccount {
+                    seed: self.next_seed,
+                    owner,
+                    lamports: self.rent.minimum_balance(space as usize),
+                };
+                self.next_seed += 1;
+                if owner.is_closable() {
+                    let expires = self.round + rng.gen_range(self.config.lifetime.clone());
+                    self.live_accounts.entry(expires).or_default().push(account);
+                }
+                (account, space)
+            })
+            .collect();
+        self.stats.accounts_created += new_accounts.len() as u64;
+
+        new_accounts
+            .chunks(self.config.accounts_per_transaction)
+            .map(|chunk| {
+                let instructions: Vec<_> = chunk
+                    .iter()
+                    .flat_map(|(account, space)| {
+                        self.make_create_instructions(payer, account, *space)
+                    })
+                    .collect();
+                self.sign(payer, &instructions, blockhash)
+            })
+            .collect()
+    }
+
+    fn make_create_instructions(
+        &self,
+        payer: &Keypair,
+        account: &LiveAccount,
+        space: u64,
+    ) -> Vec<Instruction> {
+        let base_pubkey = self.base_pubkey();
+        let address = self.address(account.seed, &account.owner);
+        let mut instructions = vec![system_instruction::create_account_with_seed(
+            &payer.pubkey(),
+            &address,
+            &base_pubkey,
+            &account.seed.to_string(),
+            account.lamports,
+            space,
+            &account.owner.program_id(),
+        )];
+        if let ChurnOwner::Token { mint } = account.owner {
+            instructions.push(
+                spl_token::instruction::initialize_account(
+                    &spl_token::id(),
+                    &address,
+                    &mint,
+                    &base_pubkey,
+                )
+                .unwrap(),
+            );
+        }
+        instructions
+    }
+
+    fn make_close_transactions(
+        &mut self,
+        payer: &Keypair,
+        accounts: &[LiveAccount],
+        blockhash: Hash,
+    ) -> Vec<Transaction> {
+        self.stats.accounts_closed += accounts.len() as u64;
+        accounts
+            .chunks(self.config.accounts_per_transaction)
+            .map(|chunk| {
+                let instructions: Vec<_> = chunk
+                    .iter()
+                    .map(|account| self.make_close_instruction(payer, account))
+                    .collect();
+                self.sign(payer, &instructions, blockhash)
+            })
+            .collect()
+    }
+
+    fn make_close_instruction(&self, payer: &Keypair, account: &LiveAccount) -> Instruction {
+        let base_pubkey = self.base_pubkey();
+        let address = self.address(account.seed, &account.owner);
+        match account.owner {
+            ChurnOwner::System => system_instruction::transfer_with_seed(
+                &address,
+                &base_pubkey,
+                account.seed.to_string(),
+                &system_program::id(),
+                &payer.pubkey(),
+                account.lamports,
+            ),
+            ChurnOwner::Token { .. } => spl_token::instruction::close_account(
+                &spl_token::id(),
+                &address,
+                &payer.pubkey(),
+                &base_pubkey,
+                &[],
+            )
+            .unwrap(),
+            ChurnOwner::Program(_) => unreachable!("program owned accounts are never closed"),
+        }
+    }
+
+    fn sign(&self, payer: &Keypair, instructions: &[Instruction], blockhash: Hash) -> Transaction {
+        Transaction::new_signed_with_payer(
+            instructions,
+            Some(&payer.pubkey()),
+            &[payer, &self.base_keypair],
+            blockhash,
+        )
+    }
+}
+
+/// Runs `rounds` rounds of account churn against the cluster behind `client`, with `payer`
+/// funding the new accounts and paying the fees. Each round waits for its transactions to be
+/// confirmed or to time out before the next one starts. With `reclaim_accounts` every account
+/// that is still alive afterwards is closed as well.
+pub fn run_account_churn(
+    client: Arc<RpcClient>,
+    payer: &Keypair,
+    config: AccountChurnConfig,
+    rounds: u64,
+    reclaim_accounts: bool,
+) -> AccountChurnStats {
+    let rent: Rent = client
+        .get_account(&sysvar::rent::id())
+        .expect("rent sysvar")
+        .deserialize_data()
+        .expect("rent");
+    let mut churn = AccountChurn::new(config, rent);
+    info!(
+        "Churning accounts based on {} against {}",
+        churn.base_pubkey(),
+        client.url()
+    );
+
+    let executor = TransactionExecutor::new_with_rpc_client(client.clone());
+    let mut last_log = Instant::now();
+    for _ in 0..rounds {
+        let blockhash = client.get_latest_blockhash().expect("blockhash");
+        executor.push_transactions(churn.next_round(payer, blockhash));
+        wait_for_outstanding(&executor);
+        if last_log.elapsed().as_millis() > 3000 {
+            info!(
+                "{:?} live accounts: {}",
+                churn.stats(),
+                churn.num_live_accounts()
+            );
+            last_log = Instant::now();
+        }
+    }
+    if reclaim_accounts {
+        let blockhash = client.get_latest_blockhash().expect("blockhash");
+        executor.push_transactions(churn.close_all(payer, blockhash));
+        wait_for_outstanding(&executor);
+    }
+    executor.close();
+
+    let stats = churn.stats();
+    info!("{:?} live accounts: {}", stats, churn.num_live_accounts());
+    stats
+}
+
+fn wait_for_outstanding(executor: &TransactionExecutor) {
+    while executor.num_outstanding() > 0 {
+        sleep(Duration::from_millis(100));
+    }
+    let _ = executor.drain_cleared();
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_core::validator::ValidatorConfig,
+        solana_local_cluster::{
+            local_cluster::{ClusterConfig, LocalCluster},
+            validator_configs::make_identical_validator_configs,
+        },
+        solana_sdk::{commitment_config::CommitmentConfig, poh_config::PohConfig},
+        solana_streamer::socket::SocketAddrSpace,
+    };
+
+    #[test]
+    fn test_account_churn_lifetimes() {
+        let program_id = Pubkey::new_unique();
+        let config = AccountChurnConfig {
+            accounts_per_round: 10,
+            space: 100..200,
+            lifetime: 2..3,
+            owners: vec![ChurnOwner::System, ChurnOwner::Program(program_id)],
+            accounts_per_transaction: 4,
+        };
+        let mut churn = AccountChurn::new(config, Rent::default());
+        let payer = Keypair::new();
+        let blockhash = Hash::new_unique();
+
+        // First round only creates accounts
+        let transactions = churn.next_round(&payer, blockhash);
+        assert_eq!(transactions.len(), 3);
+        let live_accounts = churn.num_live_accounts();
+        assert!(live_accounts <= 10);
+        assert!(transactions
+            .iter()
+            .all(|transaction| transaction.verify().is_ok()));
+
+        // Nothing has expired in the second round yet
+        churn.next_round(&payer, blockhash);
+        assert_eq!(churn.stats().accounts_closed, 0);
+
+        // The first round's system accounts expire in the third round
+        churn.next_round(&payer, blockhash);
+        assert_eq!(churn.stats().accounts_closed, live_accounts as u64);
+
+        let remaining = churn.num_live_accounts() as u64;
+        churn.close_all(&payer, blockhash);
+        assert_eq!(churn.num_live_accounts(), 0);
+        let stats = churn.stats();
+        assert_eq!(stats.rounds, 3);
+        assert_eq!(stats.accounts_created, 30);
+        assert_eq!(stats.accounts_closed, live_accounts as u64 + remaining);
+    }
+
+    #[test]
+    fn test_run_account_churn() {
+        solana_logger::setup();
+        let validator_config = ValidatorConfig::default_for_test();
+        let num_nodes = 1;
+        let mut config = ClusterConfig {
+            mint_lamports: 10_000_000_000,
+            poh_config: PohConfig::new_sleep(Duration::from_millis(50)),
+            node_stakes: vec![100; num_nodes],
+            validator_configs: make_identical_validator_configs(&validator_config, num_nodes),
+            ..ClusterConfig::default()
+        };
+        let cluster = LocalCluster::new(&mut config, SocketAddrSpace::Unspecified);
+        let client = Arc::new(RpcClient::new_socket_with_commitment(
+            cluster.entry_point_info.rpc().unwrap(),
+            CommitmentConfig::confirmed(),
+        ));
+
+        let config = AccountChurnConfig {
+            accounts_per_round: 20,
+            lifetime: 1..3,
+            ..AccountChurnConfig::default()
+        };
+        let stats = run_account_churn(client, &cluster.funding_keypair, config, 5, true);
+        assert_eq!(stats.rounds, 5);
+        assert_eq!(stats.accounts_created, 100);
+        assert_eq!(stats.accounts_closed, 100);
+    }
+}
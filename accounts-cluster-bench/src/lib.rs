@@ -0,0 +1,2 @@
+#![allow(clippy::arithmetic_side_effects)]
+pub mod churn;
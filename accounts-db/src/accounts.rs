@@ -28,7 +28,7 @@ use {
     std::{
         cmp::Reverse,
         collections::{BinaryHeap, HashSet},
-        ops::RangeBounds,
+        ops::{Bound, RangeBounds},
         sync::{
             atomic::{AtomicUsize, Ordering},
             Arc, Mutex,
@@ -404,6 +404,50 @@ impl Accounts {
             .map(|_| collector)
     }
 
+    /// Loads up to `limit` accounts owned by `program_id` and matching `filter`,
+    /// in increasing order of pubkey, starting after the pubkey `after`.
+    /// Since the order is stable, the last pubkey of a page can be passed as
+    /// `after` to resume the scan where it left off, even against a later bank.
+    pub fn load_by_program_with_filter_sorted_page<F: Fn(&AccountSharedData) -> bool>(
+        &self,
+        ancestors: &Ancestors,
+        bank_id: BankId,
+        program_id: &Pubkey,
+        filter: F,
+        after: Option<&Pubkey>,
+        limit: usize,
+    ) -> ScanResult<Vec<TransactionAccount>> {
+        if limit == 0 {
+            return Ok(Vec::new());
+        }
+        let config = ScanConfig::new(ScanOrder::Sorted).recreate_with_abort();
+        let range = (
+            after.map_or(Bound::Unbounded, |after| Bound::Excluded(*after)),
+            Bound::Unbounded,
+        );
+        let mut collector = Vec::with_capacity(limit);
+        self.accounts_db
+            .scan_accounts_in_range(
+                ancestors,
+                bank_id,
+                range,
+                |some_account_tuple| {
+                    if collector.len() >= limit {
+                        return;
+                    }
+                    Self::load_while_filtering(&mut collector, some_account_tuple, |account| {
+                        account.owner() == program_id && filter(account)
+                    });
+                    if collector.len() >= limit {
+                        // the page is full, no need to scan the remaining accounts
+                        config.abort();
+                    }
+                },
+                &config,
+            )
+            .map(|_| collector)
+    }
+
     fn calc_scan_result_size(account: &AccountSharedData) -> usize {
         account.data().len()
             + std::mem::size_of::<AccountSharedData>()
@@ -898,6 +942,82 @@ mod tests {
         assert_eq!(loaded, vec![]);
     }
 
+    #[test]
+    fn test_load_by_program_with_filter_sorted_page() {
+        let accounts_db = AccountsDb::new_single_for_tests();
+        let accounts = Accounts::new(Arc::new(accounts_db));
+        let ancestors = Ancestors::from(vec![0]);
+        let program_id = Pubkey::new_unique();
+
+        let mut expected: Vec<_> = (0..10u64)
+            .map(|i| {
+                let pubkey = solana_pubkey::new_rand();
+                let account = AccountSharedData::new(i + 1, 0, &program_id);
+                accounts.store_slow_uncached(0, &pubkey, &account);
+                (pubkey, account)
+            })
+            .collect();
+        // accounts owned by other programs and zero lamport accounts are skipped
+        accounts.store_slow_uncached(
+            0,
+            &solana_pubkey::new_rand(),
+            &AccountSharedData::new(1, 0, &Pubkey::new_unique()),
+        );
+        accounts.store_slow_uncached(
+            0,
+            &solana_pubkey::new_rand(),
+            &AccountSharedData::new(0, 0, &program_id),
+        );
+        expected.sort_unstable_by_key(|(pubkey, _)| *pubkey);
+
+        let load_page = |after: Option<&Pubkey>, limit| {
+            accounts
+                .load_by_program_with_filter_sorted_page(
+                    &ancestors,
+                    0,
+                    &program_id,
+                    |_| true,
+                    after,
+                    limit,
+                )
+                .unwrap()
+        };
+        assert_eq!(load_page(None, 100), expected);
+        assert!(load_page(None, 0).is_empty());
+
+        let mut paged = Vec::new();
+        let mut after = None;
+        loop {
+            let page = load_page(after.as_ref(), 3);
+            assert!(page.len() <= 3);
+            after = page.last().map(|(pubkey, _)| *pubkey);
+            paged.extend(page);
+            if after.is_none() {
+                break;
+            }
+        }
+        assert_eq!(paged, expected);
+
+        let filtered = accounts
+            .load_by_program_with_filter_sorted_page(
+                &ancestors,
+                0,
+                &program_id,
+                |account| account.lamports() % 2 == 0,
+                Some(&expected[0].0),
+                100,
+            )
+            .unwrap();
+        assert_eq!(
+            filtered,
+            expected[1..]
+                .iter()
+                .filter(|(_, account)| account.lamports() % 2 == 0)
+                .cloned()
+                .collect::<Vec<_>>()
+        );
+    }
+
     #[test]
     fn test_lock_accounts_with_duplicates() {
         let accounts_db = AccountsDb::new_single_for_tests();
@@ -18,6 +18,7 @@
 //! tracks the number of commits to the entire data store. So the latest
 //! commit for each slot entry would be indexed.
 
+pub mod duplicate_accounts;
 mod geyser_plugin_utils;
 mod scan_account_storage;
 pub mod stats;
@@ -42,10 +43,10 @@ use {
         },
         accounts_hash::{
             AccountHash, AccountLtHash, AccountsDeltaHash, AccountsHash, AccountsHashKind,
-            AccountsHasher, AccountsLtHash, CalcAccountsHashConfig, CalculateHashIntermediate,
-            HashStats, IncrementalAccountsHash, SerdeAccountsDeltaHash, SerdeAccountsHash,
-            SerdeIncrementalAccountsHash, ZeroLamportAccounts, ZERO_LAMPORT_ACCOUNT_HASH,
-            ZERO_LAMPORT_ACCOUNT_LT_HASH,
+            AccountsHashProof, AccountsHasher, AccountsLtHash, CalcAccountsHashConfig,
+            CalculateHashIntermediate, HashStats, IncrementalAccountsHash, SerdeAccountsDeltaHash,
+            SerdeAccountsHash, SerdeIncrementalAccountsHash, ZeroLamportAccounts, MERKLE_FANOUT,
+            ZERO_LAMPORT_ACCOUNT_HASH, ZERO_LAMPORT_ACCOUNT_LT_HASH,
         },
         accounts_index::{
             in_mem_accounts_index::StartupStats, AccountSecondaryIndexes, AccountsIndex,
@@ -63,6 +64,7 @@ use {
             get_ancient_append_vec_capacity, is_ancient, AccountsToStore, StorageSelector,
         },
         append_vec::{aligned_stored_size, STORE_META_OVERHEAD},
+        background_io_budget::BackgroundIoBudget,
         cache_hash_data::{CacheHashData, DeletionPolicy as CacheHashDeletionPolicy},
         contains::Contains,
         epoch_accounts_hash::EpochAccountsHashManager,
@@ -518,6 +520,8 @@ pub const ACCOUNTS_DB_CONFIG_FOR_TESTING: AccountsDbConfig = AccountsDbConfig {
     num_foreground_threads: None,
     num_hash_threads: None,
     hash_calculation_pubkey_bins: Some(4),
+    background_io_bytes_per_second: None,
+    accounts_file_provider: AccountsFileProvider::AppendVec,
 };
 pub const ACCOUNTS_DB_CONFIG_FOR_BENCHMARKS: AccountsDbConfig = AccountsDbConfig {
     index: Some(ACCOUNTS_INDEX_CONFIG_FOR_BENCHMARKS),
@@ -546,6 +550,8 @@ pub const ACCOUNTS_DB_CONFIG_FOR_BENCHMARKS: AccountsDbConfig = AccountsDbConfig
     num_foreground_threads: None,
     num_hash_threads: None,
     hash_calculation_pubkey_bins: None,
+    background_io_bytes_per_second: None,
+    accounts_file_provider: AccountsFileProvider::AppendVec,
 };
 
 pub type BinnedHashData = Vec<Vec<CalculateHashIntermediate>>;
@@ -680,6 +686,12 @@ pub struct AccountsDbConfig {
     pub num_foreground_threads: Option<NonZeroUsize>,
     /// Number of threads for background accounts hashing (`thread_pool_hash`)
     pub num_hash_threads: Option<NonZeroUsize>,
+    /// Disk bandwidth available to background clean and shrink, in bytes per
+    /// second. If None, background IO is not rate limited.
+    pub background_io_bytes_per_second: Option<u64>,
+    /// The format new account storage files are written in. Storages in
+    /// either format are always readable.
+    pub accounts_file_provider: AccountsFileProvider,
 }
 
 #[cfg(not(test))]
@@ -1492,7 +1504,8 @@ pub struct AccountsDb {
 
     pub accounts_cache: AccountsCache,
 
-    write_cache_limit_bytes: Option<u64>,
+    /// The size of the write cache above which it is flushed aggressively
+    write_cache_limit_bytes: AtomicU64,
 
     sender_bg_hasher: RwLock<Option<Sender<Vec<CachedAccount>>>>,
     read_only_accounts_cache: ReadOnlyAccountsCache,
@@ -1585,6 +1598,9 @@ pub struct AccountsDb {
 
     pub(crate) active_stats: ActiveStats,
 
+    /// Rate limit and pause state for background clean and shrink
+    background_io_budget: BackgroundIoBudget,
+
     pub verify_accounts_hash_in_bg: VerifyAccountsHashInBackground,
 
     /// Used to disable logging dead slots during removal.
@@ -2039,7 +2055,11 @@ impl AccountsDb {
                 read_cache_size.1,
                 read_cache_evict_sample_size,
             ),
-            write_cache_limit_bytes: accounts_db_config.write_cache_limit_bytes,
+            write_cache_limit_bytes: AtomicU64::new(
+                accounts_db_config
+                    .write_cache_limit_bytes
+                    .unwrap_or(WRITE_CACHE_LIMIT_BYTES_DEFAULT),
+            ),
             partitioned_epoch_rewards_config: accounts_db_config.partitioned_epoch_rewards_config,
             exhaustively_verify_refcounts: accounts_db_config.exhaustively_verify_refcounts,
             test_skip_rewrites_but_include_in_bank_hash: accounts_db_config
@@ -2059,6 +2079,9 @@ impl AccountsDb {
             thread_pool_hash,
             verify_accounts_hash_in_bg: VerifyAccountsHashInBackground::default(),
             active_stats: ActiveStats::default(),
+            background_io_budget: BackgroundIoBudget::new(
+                accounts_db_config.background_io_bytes_per_second,
+            ),
             storage: AccountStorage::default(),
             accounts_cache: AccountsCache::default(),
             sender_bg_hasher: RwLock::new(None),
@@ -2084,7 +2107,7 @@ impl AccountsDb {
             dirty_stores: DashMap::default(),
             zero_lamport_accounts_to_purge_after_full_snapshot: DashSet::default(),
             log_dead_slots: AtomicBool::new(true),
-            accounts_file_provider: AccountsFileProvider::default(),
+            accounts_file_provider: accounts_db_config.accounts_file_provider,
             epoch_accounts_hash_manager: EpochAccountsHashManager::new_invalid(),
             latest_full_snapshot_slot: SeqLock::new(None),
             best_ancient_slots_to_shrink: RwLock::default(),
@@ -2616,6 +2639,9 @@ impl AccountsDb {
                         }
                         oldest_dirty_slot = oldest_dirty_slot.min(*slot);
 
+                        if !is_startup {
+                            self.background_io_budget.consume(store.capacity());
+                        }
                         store.accounts.scan_index(|index| {
                             let pubkey = index.index_info.pubkey;
                             let is_zero_lamport = index.index_info.lamports == 0;
@@ -3937,6 +3963,14 @@ impl AccountsDb {
 
     /// Shrinks `store` by rewriting the alive accounts to a new storage
     fn shrink_storage(&self, store: Arc<AccountStorageEntry>) {
+        self.rewrite_storage(store, false);
+    }
+
+    /// Rewrites the alive accounts of `store` to a new storage, even if that
+    /// would not reclaim enough space to be worth it when `force` is set.
+    ///
+    /// Returns true if `store` was rewritten.
+    fn rewrite_storage(&self, store: Arc<AccountStorageEntry>, force: bool) -> bool {
         let slot = store.slot();
         if self.accounts_cache.contains(slot) {
             // It is not correct to shrink a slot while it is in the write cache until flush is complete and the slot is removed from the write cache.
@@ -3950,7 +3984,7 @@ impl AccountsDb {
             // Clean causes us to mark accounts as dead, which causes shrink to later take a look at the slot.
             // This could be an assert, but it could lead to intermittency in tests.
             // It is 'correct' to ignore calls to shrink when a slot is still in the write cache.
-            return;
+            return false;
         }
         let unique_accounts =
             self.get_unique_accounts_from_storage_for_shrink(&store, &self.shrink_stats);
@@ -3960,10 +3994,12 @@ impl AccountsDb {
 
         // This shouldn't happen if alive_bytes is accurate.
         // However, it is possible that the remaining alive bytes could be 0. In that case, the whole slot should be marked dead by clean.
-        if Self::should_not_shrink(
-            shrink_collect.alive_total_bytes as u64,
-            shrink_collect.capacity,
-        ) || shrink_collect.alive_total_bytes == 0
+        if (!force
+            && Self::should_not_shrink(
+                shrink_collect.alive_total_bytes as u64,
+                shrink_collect.capacity,
+            ))
+            || shrink_collect.alive_total_bytes == 0
         {
             if shrink_collect.alive_total_bytes == 0 {
                 // clean needs to take care of this dead slot
@@ -3982,7 +4018,7 @@ impl AccountsDb {
             self.shrink_stats
                 .skipped_shrink
                 .fetch_add(1, Ordering::Relaxed);
-            return;
+            return false;
         }
 
         self.unref_shrunk_dead_accounts(shrink_collect.pubkeys_to_unref.iter().cloned(), slot);
@@ -4032,6 +4068,7 @@ impl AccountsDb {
 
         Self::update_shrink_stats(&self.shrink_stats, stats_sub, true);
         self.shrink_stats.report();
+        true
     }
 
     pub(crate) fn update_shrink_stats(
@@ -4622,6 +4659,21 @@ impl AccountsDb {
         uncleaned_pubkeys.extend(pubkeys);
     }
 
+    /// IO budget applied to background clean and shrink
+    pub fn background_io_budget(&self) -> &BackgroundIoBudget {
+        &self.background_io_budget
+    }
+
+    /// true if clean is currently running
+    pub fn is_clean_active(&self) -> bool {
+        self.active_stats.is_active(ActiveStatItem::Clean)
+    }
+
+    /// true if shrink is currently running
+    pub fn is_shrink_active(&self) -> bool {
+        self.active_stats.is_active(ActiveStatItem::Shrink)
+    }
+
     pub fn shrink_candidate_slots(&self, epoch_schedule: &EpochSchedule) -> usize {
         let oldest_non_ancient_slot = self.get_oldest_non_ancient_slot(epoch_schedule);
 
@@ -4701,6 +4753,12 @@ impl AccountsDb {
                                 .num_ancient_slots_shrunk
                                 .fetch_add(1, Ordering::Relaxed);
                         }
+                        // shrink reads the whole storage and writes back the alive accounts
+                        self.background_io_budget.consume(
+                            slot_shrink_candidate
+                                .capacity()
+                                .saturating_add(slot_shrink_candidate.alive_bytes() as u64),
+                        );
                         self.shrink_storage(slot_shrink_candidate);
                     });
             })
@@ -4817,6 +4875,36 @@ impl AccountsDb {
         Ok(())
     }
 
+    /// Like `scan_accounts`, but only visits the accounts whose pubkey is in `range`
+    pub fn scan_accounts_in_range<F, R>(
+        &self,
+        ancestors: &Ancestors,
+        bank_id: BankId,
+        range: R,
+        mut scan_func: F,
+        config: &ScanConfig,
+    ) -> ScanResult<()>
+    where
+        F: FnMut(Option<(&Pubkey, AccountSharedData, Slot)>),
+        R: RangeBounds<Pubkey> + std::fmt::Debug,
+    {
+        // This can error out if the slots being scanned over are aborted
+        self.accounts_index.scan_accounts_in_range(
+            ancestors,
+            bank_id,
+            range,
+            |pubkey, (account_info, slot)| {
+                let account_slot = self
+                    .get_account_accessor(slot, pubkey, &account_info.storage_location())
+                    .get_loaded_account(|loaded_account| {
+                        (pubkey, loaded_account.take_account(), slot)
+                    });
+                scan_func(account_slot)
+            },
+            config,
+        )
+    }
+
     pub fn unchecked_scan_accounts<F>(
         &self,
         metric_name: &'static str,
@@ -6168,9 +6256,21 @@ impl AccountsDb {
 
     /// true if write cache is too big
     fn should_aggressively_flush_cache(&self) -> bool {
-        self.write_cache_limit_bytes
-            .unwrap_or(WRITE_CACHE_LIMIT_BYTES_DEFAULT)
-            < self.accounts_cache.size()
+        self.write_cache_limit_bytes() < self.accounts_cache.size()
+    }
+
+    /// The size of the write cache above which it is flushed aggressively
+    pub fn write_cache_limit_bytes(&self) -> u64 {
+        self.write_cache_limit_bytes.load(Ordering::Relaxed)
+    }
+
+    /// Sets the size of the write cache above which it is flushed aggressively,
+    /// or restores the default if None.
+    pub fn set_write_cache_limit_bytes(&self, write_cache_limit_bytes: Option<u64>) {
+        self.write_cache_limit_bytes.store(
+            write_cache_limit_bytes.unwrap_or(WRITE_CACHE_LIMIT_BYTES_DEFAULT),
+            Ordering::Relaxed,
+        );
     }
 
     // `force_flush` flushes all the cached roots `<= requested_flush_root`. It also then
@@ -6658,7 +6758,7 @@ impl AccountsDb {
 
         // Pick a chunk size big enough to allow us to produce output vectors that are smaller than the overall size.
         // We'll also accumulate the lamports within each chunk and fewer chunks results in less contention to accumulate the sum.
-        let chunks = crate::accounts_hash::MERKLE_FANOUT.pow(4);
+        let chunks = MERKLE_FANOUT.pow(4);
         let total_lamports = Mutex::<u64>::new(0);
 
         let get_account_hashes = || {
@@ -6668,39 +6768,13 @@ impl AccountsDb {
                     let account_hashes: Vec<Hash> = pubkeys
                         .iter()
                         .filter_map(|pubkey| {
-                            let index_entry = self.accounts_index.get_cloned(pubkey)?;
-                            self.accounts_index
-                                .get_account_info_with_and_then(
-                                    &index_entry,
-                                    config.ancestors,
-                                    Some(max_slot),
-                                    |(slot, account_info)| {
-                                        if account_info.is_zero_lamport() {
-                                            return None;
-                                        }
-                                        self.get_account_accessor(
-                                            slot,
-                                            pubkey,
-                                            &account_info.storage_location(),
-                                        )
-                                        .get_loaded_account(|loaded_account| {
-                                            let mut loaded_hash = loaded_account.loaded_hash();
-                                            let balance = loaded_account.lamports();
-                                            let hash_is_missing =
-                                                loaded_hash == AccountHash(Hash::default());
-                                            if hash_is_missing {
-                                                let computed_hash = Self::hash_account(
-                                                    &loaded_account,
-                                                    loaded_account.pubkey(),
-                                                );
-                                                loaded_hash = computed_hash;
-                                            }
-                                            sum += balance as u128;
-                                            loaded_hash.0
-                                        })
-                                    },
-                                )
-                                .flatten()
+                            let (account_hash, balance) = self.get_account_hash_from_index(
+                                pubkey,
+                                config.ancestors,
+                                max_slot,
+                            )?;
+                            sum += balance as u128;
+                            Some(account_hash.0)
                         })
                         .collect();
                     let mut total = total_lamports.lock().unwrap();
@@ -6732,6 +6806,86 @@ impl AccountsDb {
         (accounts_hash, total_lamports)
     }
 
+    /// Returns the hash and balance of the account at `pubkey` as of `max_slot`, as included
+    /// in the accounts hash, or None if the account does not exist or has zero lamports
+    fn get_account_hash_from_index(
+        &self,
+        pubkey: &Pubkey,
+        ancestors: Option<&Ancestors>,
+        max_slot: Slot,
+    ) -> Option<(AccountHash, u64)> {
+        let index_entry = self.accounts_index.get_cloned(pubkey)?;
+        self.accounts_index
+            .get_account_info_with_and_then(
+                &index_entry,
+                ancestors,
+                Some(max_slot),
+                |(slot, account_info)| {
+                    if account_info.is_zero_lamport() {
+                        return None;
+                    }
+                    self.get_account_accessor(slot, pubkey, &account_info.storage_location())
+                        .get_loaded_account(|loaded_account| {
+                            let mut loaded_hash = loaded_account.loaded_hash();
+                            let hash_is_missing = loaded_hash == AccountHash(Hash::default());
+                            if hash_is_missing {
+                                loaded_hash =
+                                    Self::hash_account(&loaded_account, loaded_account.pubkey());
+                            }
+                            (loaded_hash, loaded_account.lamports())
+                        })
+                },
+            )
+            .flatten()
+    }
+
+    /// Calculates the full accounts hash as of `max_slot`, along with a proof that the account
+    /// at `pubkey` is included in it. Returns None if the account does not exist or has zero
+    /// lamports, as such accounts are left out of the accounts hash.
+    ///
+    /// Scans the whole accounts index, so this is meant for audits rather than for the
+    /// validator's own accounts hash calculation.
+    pub fn calculate_accounts_hash_proof(
+        &self,
+        pubkey: &Pubkey,
+        max_slot: Slot,
+        ancestors: Option<&Ancestors>,
+    ) -> Option<(AccountsHash, AccountsHashProof)> {
+        let keys: Vec<_> = self
+            .accounts_index
+            .account_maps
+            .iter()
+            .flat_map(|map| {
+                let mut keys = map.keys();
+                keys.sort_unstable(); // hashmap is not ordered, but bins are relative to each other
+                keys
+            })
+            .collect();
+        let account_hashes: Vec<(Pubkey, Hash)> = self.thread_pool_clean.install(|| {
+            keys.par_iter()
+                .filter_map(|pubkey| {
+                    let (account_hash, _) =
+                        self.get_account_hash_from_index(pubkey, ancestors, max_slot)?;
+                    Some((*pubkey, account_hash.0))
+                })
+                .collect()
+        });
+        let index = account_hashes
+            .binary_search_by_key(pubkey, |(pubkey, _)| *pubkey)
+            .ok()?;
+        let hashes: Vec<_> = account_hashes.into_iter().map(|(_, hash)| hash).collect();
+        let levels = AccountsHasher::compute_merkle_proof(hashes.clone(), index, MERKLE_FANOUT);
+        let accounts_hash = AccountsHash(AccountsHasher::compute_merkle_root_recurse(
+            hashes,
+            MERKLE_FANOUT,
+        ));
+        let proof = AccountsHashProof {
+            pubkey: *pubkey,
+            levels,
+        };
+        Some((accounts_hash, proof))
+    }
+
     /// Calculates the accounts lt hash
     ///
     /// Only intended to be called at startup (or by tests).
@@ -0,0 +1,123 @@
+//! Detection and repair of accounts stored more than once in the storage of a slot.
+//!
+//! A slot's storage is expected to hold a single entry per pubkey. A crash in
+//! the middle of writing a storage can leave several entries for a pubkey
+//! behind, possibly with divergent contents. Write versions are no longer
+//! stored, so the entry appended last is the highest write and the one kept.
+
+use {
+    super::AccountsDb, log::*, rayon::prelude::*, solana_clock::Slot, solana_measure::measure_us,
+    solana_pubkey::Pubkey, std::collections::HashMap,
+};
+
+/// A pubkey stored more than once in the storage of a slot
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateAccount {
+    pub pubkey: Pubkey,
+    pub slot: Slot,
+    /// Offsets of the entries in the storage, in the order they were appended.
+    /// The last one is kept by a repair.
+    pub offsets: Vec<usize>,
+    /// True if the entries do not all hold the same account
+    pub divergent: bool,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct DuplicateAccountsReport {
+    pub num_storages_scanned: usize,
+    pub duplicates: Vec<DuplicateAccount>,
+}
+
+impl DuplicateAccountsReport {
+    pub fn is_empty(&self) -> bool {
+        self.duplicates.is_empty()
+    }
+
+    /// Number of pubkeys whose entries do not all hold the same account
+    pub fn num_divergent(&self) -> usize {
+        self.duplicates
+            .iter()
+            .filter(|duplicate| duplicate.divergent)
+            .count()
+    }
+
+    /// Slots holding duplicates, sorted and deduplicated
+    pub fn slots(&self) -> Vec<Slot> {
+        let mut slots: Vec<_> = self
+            .duplicates
+            .iter()
+            .map(|duplicate| duplicate.slot)
+            .collect();
+        slots.sort_unstable();
+        slots.dedup();
+        slots
+    }
+}
+
+impl AccountsDb {
+    /// Scan the storage of every slot for pubkeys stored more than once
+    pub fn find_duplicate_accounts(&self) -> DuplicateAccountsReport {
+        let (storages, _slots) = self.get_storages(..);
+        let (mut duplicates, scan_us) = measure_us!(storages
+            .par_iter()
+            .flat_map_iter(|storage| {
+                // Only the entries of duplicated pubkeys are read back in full,
+                // to compare their contents byte for byte
+                let mut offsets = HashMap::<Pubkey, Vec<usize>>::new();
+                storage.accounts.scan_index(|info| {
+                    let info = info.index_info;
+                    offsets.entry(info.pubkey).or_default().push(info.offset);
+                });
+                let slot = storage.slot();
+                offsets
+                    .into_iter()
+                    .filter(|(_pubkey, offsets)| offsets.len() > 1)
+                    .map(move |(pubkey, offsets)| {
+                        let first = storage.accounts.get_account_shared_data(offsets[0]);
+                        let divergent = offsets[1..].iter().any(|offset| {
+                            storage.accounts.get_account_shared_data(*offset) != first
+                        });
+                        DuplicateAccount {
+                            pubkey,
+                            slot,
+                            offsets,
+                            divergent,
+                        }
+                    })
+            })
+            .collect::<Vec<_>>());
+        duplicates.sort_unstable_by_key(|duplicate| (duplicate.slot, duplicate.pubkey));
+
+        datapoint_info!(
+            "accounts_db-find_duplicate_accounts",
+            ("num_storages_scanned", storages.len(), i64),
+            ("num_duplicates", duplicates.len(), i64),
+            ("scan_us", scan_us, i64),
+        );
+        DuplicateAccountsReport {
+            num_storages_scanned: storages.len(),
+            duplicates,
+        }
+    }
+
+    /// Rewrite the storages of the slots in `report` so that they only hold the
+    /// entry appended last for each pubkey.
+    ///
+    /// Returns the number of slots rewritten.
+    pub fn repair_duplicate_accounts(&self, report: &DuplicateAccountsReport) -> usize {
+        report
+            .slots()
+            .into_iter()
+            .filter(|slot| {
+                let Some(storage) = self
+                    .storage
+                    .get_slot_storage_entry_shrinking_in_progress_ok(*slot)
+                else {
+                    warn!("Cannot repair duplicate accounts of slot {slot}: no storage");
+                    return false;
+                };
+                self.rewrite_storage(storage, true)
+            })
+            .count()
+    }
+}
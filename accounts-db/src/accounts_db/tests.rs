@@ -204,6 +204,52 @@ define_accounts_db_test!(test_generate_index_duplicates_within_slot_reverse, |db
     run_generate_index_duplicates_within_slot_test(db, true);
 });
 
+define_accounts_db_test!(test_find_and_repair_duplicate_accounts, |db| {
+    let slot0 = 0;
+    let pubkey = Pubkey::from([1; 32]);
+    let other_pubkey = Pubkey::from([2; 32]);
+    let append_vec = db.create_and_insert_store(slot0, 1000, "test");
+
+    let mut account_old = AccountSharedData::default();
+    account_old.set_data(vec![5; 10]);
+    account_old.set_lamports(2);
+    let mut account_new = AccountSharedData::default();
+    account_new.set_data(vec![1]);
+    account_new.set_lamports(1);
+    let data = [
+        (&pubkey, &account_old),
+        (&other_pubkey, &account_old),
+        (&pubkey, &account_new),
+    ];
+    append_vec.accounts.append_accounts(&(slot0, &data[..]), 0);
+    db.generate_index(None, false, &GenesisConfig::default(), false);
+
+    let report = db.find_duplicate_accounts();
+    assert_eq!(report.num_storages_scanned, 1);
+    assert_eq!(report.duplicates.len(), 1);
+    assert_eq!(report.num_divergent(), 1);
+    let duplicate = &report.duplicates[0];
+    assert_eq!(duplicate.pubkey, pubkey);
+    assert_eq!(duplicate.slot, slot0);
+    assert_eq!(duplicate.offsets.len(), 2);
+    assert!(duplicate.offsets[0] < duplicate.offsets[1]);
+
+    assert_eq!(db.repair_duplicate_accounts(&report), 1);
+    assert!(db.find_duplicate_accounts().is_empty());
+    let storage = db.storage.get_slot_storage_entry(slot0).unwrap();
+    assert_eq!(storage.accounts_count(), 2);
+
+    // the entry appended last is the one kept
+    let (loaded_account, _slot) = db
+        .load_without_fixed_root(&Ancestors::default(), &pubkey)
+        .unwrap();
+    assert!(accounts_equal(&loaded_account, &account_new));
+    let (loaded_account, _slot) = db
+        .load_without_fixed_root(&Ancestors::default(), &other_pubkey)
+        .unwrap();
+    assert!(accounts_equal(&loaded_account, &account_old));
+});
+
 #[test]
 fn test_generate_index_for_single_ref_zero_lamport_slot() {
     let db = AccountsDb::new_single_for_tests();
@@ -3587,8 +3633,8 @@ fn test_flush_accounts_cache_if_needed() {
 }
 
 fn run_test_flush_accounts_cache_if_needed(num_roots: usize, num_unrooted: usize) {
-    let mut db = AccountsDb::new_single_for_tests();
-    db.write_cache_limit_bytes = Some(max_cache_slots() as u64);
+    let db = AccountsDb::new_single_for_tests();
+    db.set_write_cache_limit_bytes(Some(max_cache_slots() as u64));
     let space = 1; // # data bytes per account. write cache counts data len
     let account0 = AccountSharedData::new(1, space, &Pubkey::default());
     let mut keys = vec![];
@@ -4263,8 +4309,8 @@ fn setup_accounts_db_cache_clean(
     scan_slot: Option<Slot>,
     write_cache_limit_bytes: Option<u64>,
 ) -> (Arc<AccountsDb>, Vec<Pubkey>, Vec<Slot>, Option<ScanTracker>) {
-    let mut accounts_db = AccountsDb::new_single_for_tests();
-    accounts_db.write_cache_limit_bytes = write_cache_limit_bytes;
+    let accounts_db = AccountsDb::new_single_for_tests();
+    accounts_db.set_write_cache_limit_bytes(write_cache_limit_bytes);
     let accounts_db = Arc::new(accounts_db);
 
     let slots: Vec<_> = (0..num_slots as Slot).collect();
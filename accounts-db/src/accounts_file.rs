@@ -15,6 +15,7 @@ use {
     std::{
         mem,
         path::{Path, PathBuf},
+        str::FromStr,
     },
     thiserror::Error,
 };
@@ -71,7 +72,8 @@ pub enum AccountsFile {
 }
 
 impl AccountsFile {
-    /// Create an AccountsFile instance from the specified path.
+    /// Create an AccountsFile instance from the specified path, which was
+    /// written by `provider`.
     ///
     /// The second element of the returned tuple is the number of accounts in the
     /// accounts file.
@@ -79,9 +81,35 @@ impl AccountsFile {
         path: impl Into<PathBuf>,
         current_len: usize,
         storage_access: StorageAccess,
+        provider: AccountsFileProvider,
     ) -> Result<(Self, usize)> {
-        let (av, num_accounts) = AppendVec::new_from_file(path, current_len, storage_access)?;
-        Ok((Self::AppendVec(av), num_accounts))
+        match provider {
+            AccountsFileProvider::AppendVec => {
+                let (av, num_accounts) =
+                    AppendVec::new_from_file(path, current_len, storage_access)?;
+                Ok((Self::AppendVec(av), num_accounts))
+            }
+            AccountsFileProvider::HotStorage => {
+                // Tiered storages are always accessed via mmap.
+                let path = path.into();
+                let ts = TieredStorage::new_readonly(&path)?;
+                if ts.len() != current_len {
+                    return Err(
+                        TieredStorageError::IncorrectLength(path, ts.len(), current_len).into(),
+                    );
+                }
+                let num_accounts = ts.reader().map_or(0, |reader| reader.num_accounts());
+                Ok((Self::TieredStorage(ts), num_accounts))
+            }
+        }
+    }
+
+    /// The format this accounts file is written in.
+    pub fn provider(&self) -> AccountsFileProvider {
+        match self {
+            Self::AppendVec(_) => AccountsFileProvider::AppendVec,
+            Self::TieredStorage(_) => AccountsFileProvider::HotStorage,
+        }
     }
 
     /// true if this storage can possibly be appended to (independent of capacity check)
@@ -311,7 +339,8 @@ impl AccountsFile {
 }
 
 /// An enum that creates AccountsFile instance with the specified format.
-#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "frozen-abi", derive(AbiExample, AbiEnumVisitor))]
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Deserialize, Serialize)]
 pub enum AccountsFileProvider {
     #[default]
     AppendVec,
@@ -319,6 +348,11 @@ pub enum AccountsFileProvider {
 }
 
 impl AccountsFileProvider {
+    /// Names of the formats on the command line
+    pub const fn cli_names() -> &'static [&'static str] {
+        &["append-vec", "hot"]
+    }
+
     pub fn new_writable(&self, path: impl Into<PathBuf>, file_size: u64) -> AccountsFile {
         match self {
             Self::AppendVec => {
@@ -329,6 +363,18 @@ impl AccountsFileProvider {
     }
 }
 
+impl FromStr for AccountsFileProvider {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "append-vec" => Ok(Self::AppendVec),
+            "hot" => Ok(Self::HotStorage),
+            _ => Err(format!("unknown account storage format: {s}")),
+        }
+    }
+}
+
 /// The access method to use when archiving an AccountsFile
 #[derive(Debug)]
 pub enum InternalsForArchive<'a> {
@@ -349,7 +395,8 @@ pub struct StoredAccountsInfo {
 
 #[cfg(test)]
 pub mod tests {
-    use crate::accounts_file::AccountsFile;
+    use {super::*, std::mem::ManuallyDrop, tempfile::TempDir, test_case::test_case};
+
     impl AccountsFile {
         pub(crate) fn set_current_len_for_tests(&self, len: usize) {
             match self {
@@ -358,4 +405,49 @@ pub mod tests {
             }
         }
     }
+
+    #[test_case(AccountsFileProvider::AppendVec; "append_vec")]
+    #[test_case(AccountsFileProvider::HotStorage; "hot_storage")]
+    fn test_new_from_file(provider: AccountsFileProvider) {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("0.0");
+        let accounts: Vec<_> = (0..10)
+            .map(|i| {
+                (
+                    Pubkey::new_unique(),
+                    AccountSharedData::new(i + 1, i as usize, &Pubkey::new_unique()),
+                )
+            })
+            .collect();
+        let storage = ManuallyDrop::new(provider.new_writable(&path, 1024 * 1024));
+        storage
+            .append_accounts(&(Slot::MAX, accounts.as_slice()), 0)
+            .unwrap();
+        storage.flush().unwrap();
+
+        let (reopened, num_accounts) =
+            AccountsFile::new_from_file(&path, storage.len(), StorageAccess::default(), provider)
+                .unwrap();
+        assert_eq!(num_accounts, accounts.len());
+        assert_eq!(reopened.provider(), provider);
+        let mut num_scanned = 0;
+        reopened.scan_accounts(|_| num_scanned += 1);
+        assert_eq!(num_scanned, accounts.len());
+    }
+
+    #[test]
+    fn test_accounts_file_provider_from_str() {
+        let providers: Vec<_> = AccountsFileProvider::cli_names()
+            .iter()
+            .map(|name| name.parse().unwrap())
+            .collect();
+        assert_eq!(
+            providers,
+            [
+                AccountsFileProvider::AppendVec,
+                AccountsFileProvider::HotStorage
+            ],
+        );
+        assert!("tiered".parse::<AccountsFileProvider>().is_err());
+    }
 }
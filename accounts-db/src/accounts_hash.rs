@@ -1,6 +1,6 @@
 use {
     crate::{
-        accounts_db::AccountStorageEntry,
+        accounts_db::{AccountStorageEntry, AccountsDb},
         active_stats::{ActiveStatItem, ActiveStats},
         ancestors::Ancestors,
         pubkey_bins::PubkeyBinCalculator24,
@@ -8,6 +8,7 @@ use {
     bytemuck_derive::{Pod, Zeroable},
     log::*,
     rayon::prelude::*,
+    solana_account::ReadableAccount,
     solana_clock::Slot,
     solana_hash::{Hash, HASH_BYTES},
     solana_lattice_hash::lt_hash::LtHash,
@@ -768,6 +769,43 @@ impl AccountsHasher<'_> {
         )
     }
 
+    /// Returns the levels of the proof that `hashes[index]` is included in the merkle root
+    /// computed by `compute_merkle_root_recurse(hashes, fanout)`.
+    pub fn compute_merkle_proof(
+        mut hashes: Vec<Hash>,
+        mut index: usize,
+        fanout: usize,
+    ) -> Vec<AccountsHashProofLevel> {
+        assert!(index < hashes.len());
+        let mut levels = vec![];
+        loop {
+            let start_index = index / fanout * fanout;
+            let end_index = std::cmp::min(start_index + fanout, hashes.len());
+            let mut siblings = hashes[start_index..end_index].to_vec();
+            siblings.remove(index - start_index);
+            levels.push(AccountsHashProofLevel {
+                index: index - start_index,
+                siblings,
+            });
+
+            hashes = hashes
+                .par_chunks(fanout)
+                .map(|chunk| {
+                    let mut hasher = Hasher::default();
+                    for hash in chunk {
+                        hasher.hash(hash.as_ref());
+                    }
+                    hasher.result()
+                })
+                .collect();
+            index /= fanout;
+            // Like the root computation, hash at least once, even a single hash.
+            if hashes.len() == 1 {
+                return levels;
+            }
+        }
+    }
+
     pub fn accumulate_account_hashes(mut hashes: Vec<(Pubkey, AccountHash)>) -> Hash {
         hashes.par_sort_unstable_by(|a, b| a.0.cmp(&b.0));
         Self::compute_merkle_root_loop(hashes, MERKLE_FANOUT, |i| &i.1 .0)
@@ -1283,6 +1321,55 @@ impl From<IncrementalAccountsHash> for AccountsHashKind {
 /// Hash of accounts
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub struct AccountsHash(pub Hash);
+
+/// One level of an `AccountsHashProof`: the position of the proven node among the hashes
+/// combined into its parent, and the other hashes combined with it, in order
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountsHashProofLevel {
+    pub index: usize,
+    pub siblings: Vec<Hash>,
+}
+
+/// Proof that an account is included in an `AccountsHash`
+///
+/// The accounts hash is the root of a merkle tree, with a fanout of `MERKLE_FANOUT`, over the
+/// hashes of all accounts with a non-zero balance, sorted by pubkey. The proof holds the
+/// hashes combined with the account's hash on the way to the root, so that the account can be
+/// checked against an accounts hash, e.g. from a bank hash or snapshot, without the other
+/// accounts.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountsHashProof {
+    pub pubkey: Pubkey,
+    /// The levels of the merkle tree, from the account hashes up to the root
+    pub levels: Vec<AccountsHashProofLevel>,
+}
+
+impl AccountsHashProof {
+    /// Returns the merkle root that the proof leads to from `account_hash`
+    pub fn root(&self, account_hash: &AccountHash) -> Hash {
+        self.levels.iter().fold(account_hash.0, |node, level| {
+            let mut hasher = Hasher::default();
+            for (i, sibling) in level.siblings.iter().enumerate() {
+                if i == level.index {
+                    hasher.hash(node.as_ref());
+                }
+                hasher.hash(sibling.as_ref());
+            }
+            if level.index == level.siblings.len() {
+                hasher.hash(node.as_ref());
+            }
+            hasher.result()
+        })
+    }
+
+    /// Returns whether `account`, stored at the proof's pubkey, is included in `accounts_hash`
+    pub fn verify(&self, account: &impl ReadableAccount, accounts_hash: &AccountsHash) -> bool {
+        // Zero-lamport accounts are left out of the accounts hash.
+        account.lamports() != 0
+            && self.root(&AccountsDb::hash_account(account, &self.pubkey)) == accounts_hash.0
+    }
+}
+
 /// Hash of accounts that includes zero-lamport accounts
 /// Used with incremental snapshots
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
@@ -1343,8 +1430,12 @@ impl From<IncrementalAccountsHash> for SerdeIncrementalAccountsHash {
 #[cfg(test)]
 mod tests {
     use {
-        super::*, crate::accounts_db::DEFAULT_HASH_CALCULATION_PUBKEY_BINS, itertools::Itertools,
-        std::str::FromStr, tempfile::tempdir,
+        super::*,
+        crate::accounts_db::DEFAULT_HASH_CALCULATION_PUBKEY_BINS,
+        itertools::Itertools,
+        solana_account::{AccountSharedData, WritableAccount},
+        std::str::FromStr,
+        tempfile::tempdir,
     };
 
     lazy_static! {
@@ -2342,6 +2433,65 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_accountsdb_compute_merkle_proof() {
+        for fanout in [2, 3, MERKLE_FANOUT] {
+            for count in 1..40 {
+                let hashes: Vec<_> = (0..count).map(|_| Hash::new_unique()).collect();
+                let root = AccountsHasher::compute_merkle_root_recurse(hashes.clone(), fanout);
+                for index in 0..count {
+                    let proof = AccountsHashProof {
+                        pubkey: Pubkey::new_unique(),
+                        levels: AccountsHasher::compute_merkle_proof(hashes.clone(), index, fanout),
+                    };
+                    assert_eq!(proof.root(&AccountHash(hashes[index])), root);
+                    assert_ne!(proof.root(&AccountHash(Hash::new_unique())), root);
+
+                    let mut tampered_proof = proof.clone();
+                    let level = &mut tampered_proof.levels[0];
+                    if let Some(sibling) = level.siblings.first_mut() {
+                        *sibling = Hash::new_unique();
+                    } else {
+                        level.index += 1;
+                    }
+                    assert_ne!(tampered_proof.root(&AccountHash(hashes[index])), root);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_accounts_hash_proof_verify() {
+        let accounts: Vec<_> = (0..20u64)
+            .map(|i| {
+                let account = AccountSharedData::new(i + 1, i as usize, &Pubkey::new_unique());
+                (Pubkey::new_unique(), account)
+            })
+            .sorted_by_key(|(pubkey, _)| *pubkey)
+            .collect();
+        let hashes: Vec<_> = accounts
+            .iter()
+            .map(|(pubkey, account)| AccountsDb::hash_account(account, pubkey).0)
+            .collect();
+        let accounts_hash = AccountsHash(AccountsHasher::compute_merkle_root_recurse(
+            hashes.clone(),
+            MERKLE_FANOUT,
+        ));
+        for (index, (pubkey, account)) in accounts.iter().enumerate() {
+            let proof = AccountsHashProof {
+                pubkey: *pubkey,
+                levels: AccountsHasher::compute_merkle_proof(hashes.clone(), index, MERKLE_FANOUT),
+            };
+            assert!(proof.verify(account, &accounts_hash));
+
+            let mut modified_account = account.clone();
+            modified_account.set_lamports(account.lamports() + 1);
+            assert!(!proof.verify(&modified_account, &accounts_hash));
+            modified_account.set_lamports(0);
+            assert!(!proof.verify(&modified_account, &accounts_hash));
+        }
+    }
+
     #[test]
     fn test_accountsdb_compute_merkle_root() {
         solana_logger::setup();
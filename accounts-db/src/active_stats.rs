@@ -66,9 +66,12 @@ impl ActiveStats {
             item: stat,
         }
     }
-    /// update and log the change to the specified 'item'
-    fn update_and_log(&self, item: ActiveStatItem, modify_stat: impl Fn(&AtomicUsize) -> usize) {
-        let stat = match item {
+    /// true if any thread is currently inside the specified 'item'
+    pub fn is_active(&self, item: ActiveStatItem) -> bool {
+        self.stat(item).load(Ordering::Relaxed) > 0
+    }
+    fn stat(&self, item: ActiveStatItem) -> &AtomicUsize {
+        match item {
             ActiveStatItem::Clean => &self.clean,
             ActiveStatItem::CleanConstructCandidates => &self.clean_construct_candidates,
             ActiveStatItem::CleanScanCandidates => &self.clean_scan_candidates,
@@ -84,7 +87,11 @@ impl ActiveStats {
             ActiveStatItem::HashDeDup => &self.hash_dedup,
             ActiveStatItem::HashMerkleTree => &self.hash_merkle,
             ActiveStatItem::HashScan => &self.hash_scan,
-        };
+        }
+    }
+    /// update and log the change to the specified 'item'
+    fn update_and_log(&self, item: ActiveStatItem, modify_stat: impl Fn(&AtomicUsize) -> usize) {
+        let stat = self.stat(item);
         let value = modify_stat(stat);
         match item {
             ActiveStatItem::Clean => datapoint_info!("accounts_db_active", ("clean", value, i64)),
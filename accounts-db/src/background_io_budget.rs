@@ -0,0 +1,198 @@
+//! IO budget shared by the background clean and shrink passes, so that
+//! cleaning storms do not starve replay of disk bandwidth. The budget is a
+//! token bucket refilled at a configurable number of bytes per second, and the
+//! background passes can additionally be paused altogether.
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Mutex,
+    },
+    thread::sleep,
+    time::{Duration, Instant},
+};
+
+#[derive(Debug)]
+struct TokenBucket {
+    /// Bytes which may be consumed without waiting. Negative when callers have
+    /// consumed ahead of the refill rate.
+    available: i64,
+    last_refill: Instant,
+}
+
+/// Snapshot of the state of a `BackgroundIoBudget`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BackgroundIoBudgetStatus {
+    /// None if background IO is not rate limited
+    pub bytes_per_second: Option<u64>,
+    pub paused: bool,
+    /// Total bytes accounted for since startup
+    pub bytes_consumed: u64,
+    /// Total time background threads spent waiting for the budget
+    pub throttled_us: u64,
+}
+
+#[derive(Debug)]
+pub struct BackgroundIoBudget {
+    /// 0 means unlimited
+    bytes_per_second: AtomicU64,
+    paused: AtomicBool,
+    bucket: Mutex<TokenBucket>,
+    bytes_consumed: AtomicU64,
+    throttled_us: AtomicU64,
+}
+
+impl Default for BackgroundIoBudget {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+impl BackgroundIoBudget {
+    pub fn new(bytes_per_second: Option<u64>) -> Self {
+        let bytes_per_second = bytes_per_second.unwrap_or_default();
+        Self {
+            bytes_per_second: AtomicU64::new(bytes_per_second),
+            paused: AtomicBool::new(false),
+            bucket: Mutex::new(TokenBucket {
+                available: Self::burst(bytes_per_second),
+                last_refill: Instant::now(),
+            }),
+            bytes_consumed: AtomicU64::new(0),
+            throttled_us: AtomicU64::new(0),
+        }
+    }
+
+    // Allow up to one second worth of IO to be consumed at once.
+    fn burst(bytes_per_second: u64) -> i64 {
+        bytes_per_second.min(i64::MAX as u64) as i64
+    }
+
+    /// Sets the number of bytes per second available to background IO, or
+    /// removes the limit if None.
+    pub fn set_limit(&self, bytes_per_second: Option<u64>) {
+        let bytes_per_second = bytes_per_second.unwrap_or_default();
+        let mut bucket = self.bucket.lock().unwrap();
+        self.bytes_per_second
+            .store(bytes_per_second, Ordering::Relaxed);
+        bucket.available = Self::burst(bytes_per_second);
+        bucket.last_refill = Instant::now();
+    }
+
+    pub fn limit(&self) -> Option<u64> {
+        Some(self.bytes_per_second.load(Ordering::Relaxed)).filter(|limit| *limit > 0)
+    }
+
+    /// Stops new background clean and shrink passes from being started,
+    /// including those run before taking a snapshot. Passes already in
+    /// progress run to completion.
+    pub fn pause(&self) {
+        self.paused.store(true, Ordering::Relaxed);
+    }
+
+    pub fn resume(&self) {
+        self.paused.store(false, Ordering::Relaxed);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Relaxed)
+    }
+
+    /// Accounts for `bytes` of background IO, blocking the calling thread for
+    /// as long as needed to stay within the limit.
+    pub fn consume(&self, bytes: u64) {
+        self.bytes_consumed.fetch_add(bytes, Ordering::Relaxed);
+        let Some(wait) = self.reserve(bytes, Instant::now()) else {
+            return;
+        };
+        sleep(wait);
+        self.throttled_us
+            .fetch_add(wait.as_micros() as u64, Ordering::Relaxed);
+    }
+
+    // Takes `bytes` from the bucket and returns how long the caller must wait
+    // for the bucket to be paid back, if at all.
+    fn reserve(&self, bytes: u64, now: Instant) -> Option<Duration> {
+        let bytes_per_second = self.limit()?;
+        let mut bucket = self.bucket.lock().unwrap();
+        let elapsed = now.saturating_duration_since(bucket.last_refill);
+        let refill = (elapsed.as_secs_f64() * bytes_per_second as f64) as i64;
+        bucket.available = bucket
+            .available
+            .saturating_add(refill)
+            .min(Self::burst(bytes_per_second));
+        bucket.last_refill = now;
+        bucket.available = bucket
+            .available
+            .saturating_sub(bytes.min(i64::MAX as u64) as i64);
+        (bucket.available < 0).then(|| {
+            Duration::from_secs_f64(
+                bucket.available.unsigned_abs() as f64 / bytes_per_second as f64,
+            )
+        })
+    }
+
+    pub fn status(&self) -> BackgroundIoBudgetStatus {
+        BackgroundIoBudgetStatus {
+            bytes_per_second: self.limit(),
+            paused: self.is_paused(),
+            bytes_consumed: self.bytes_consumed.load(Ordering::Relaxed),
+            throttled_us: self.throttled_us.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unlimited() {
+        let budget = BackgroundIoBudget::default();
+        assert_eq!(budget.limit(), None);
+        assert_eq!(budget.reserve(u64::MAX, Instant::now()), None);
+        budget.consume(1_000);
+        let status = budget.status();
+        assert_eq!(status.bytes_consumed, 1_000);
+        assert_eq!(status.throttled_us, 0);
+    }
+
+    #[test]
+    fn test_reserve() {
+        let budget = BackgroundIoBudget::new(Some(1_000));
+        let start = budget.bucket.lock().unwrap().last_refill;
+        // the first second worth of bytes is available immediately
+        assert_eq!(budget.reserve(1_000, start), None);
+        assert_eq!(budget.reserve(500, start), Some(Duration::from_millis(500)));
+        // debt accumulates across callers
+        assert_eq!(
+            budget.reserve(500, start),
+            Some(Duration::from_millis(1_000))
+        );
+        // and is paid back over time
+        assert_eq!(
+            budget.reserve(0, start + Duration::from_millis(1_000)),
+            None
+        );
+        // the bucket does not refill past one second worth of bytes
+        assert_eq!(
+            budget.reserve(1_500, start + Duration::from_secs(10)),
+            Some(Duration::from_millis(500))
+        );
+
+        budget.set_limit(None);
+        assert_eq!(budget.reserve(1_000_000, start), None);
+        budget.set_limit(Some(2_000));
+        assert_eq!(budget.limit(), Some(2_000));
+        assert_eq!(budget.reserve(2_000, Instant::now()), None);
+    }
+
+    #[test]
+    fn test_pause_resume() {
+        let budget = BackgroundIoBudget::new(Some(1));
+        assert!(!budget.is_paused());
+        budget.pause();
+        assert!(budget.status().paused);
+        budget.resume();
+        assert!(!budget.status().paused);
+    }
+}
@@ -3,6 +3,9 @@
 use std::os::unix::prelude::FileExt;
 use std::{fs::File, ops::Range};
 
+#[cfg(all(target_os = "linux", feature = "io-uring"))]
+mod uring;
+
 /// `buffer` contains `valid_bytes` of data at its end.
 /// Move those valid bytes to the beginning of `buffer`, then read from `offset` to fill the rest of `buffer`.
 /// Update `offset` for the next read and update `valid_bytes` to specify valid portion of `buffer`.
@@ -49,7 +52,7 @@ pub fn read_into_buffer(
     }
 
     while buffer_offset < buffer.len() {
-        match file.read_at(&mut buffer[buffer_offset..], offset as u64) {
+        match read_at(file, &mut buffer[buffer_offset..], offset as u64) {
             Err(err) => {
                 if err.kind() == std::io::ErrorKind::Interrupted {
                     continue;
@@ -72,6 +75,17 @@ pub fn read_into_buffer(
     Ok(total_bytes_read)
 }
 
+#[cfg(unix)]
+/// Read into `buffer` at `offset`, through io_uring when it is enabled and available.
+/// Falls back to `pread` otherwise.
+fn read_at(file: &File, buffer: &mut [u8], offset: u64) -> std::io::Result<usize> {
+    #[cfg(all(target_os = "linux", feature = "io-uring"))]
+    if let Some(result) = uring::read_at(file, buffer, offset) {
+        return result;
+    }
+    file.read_at(buffer, offset)
+}
+
 #[cfg(not(unix))]
 /// this cannot be supported if we're not on unix-os
 pub fn read_into_buffer(
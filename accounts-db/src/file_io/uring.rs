@@ -0,0 +1,145 @@
+//! io_uring backed reads.
+//!
+//! Each thread submits its reads through its own ring, so a read costs a single `io_uring_enter`
+//! instead of going through the `pread` path. Rings are created lazily, and if io_uring turns
+//! out to be unavailable (old kernel, seccomp filters, locked memory limits, ...) it is disabled
+//! for the whole process and callers fall back to `pread`.
+use {
+    io_uring::{opcode, types, IoUring, Probe},
+    log::*,
+    std::{
+        cell::RefCell,
+        fs::File,
+        io,
+        os::fd::AsRawFd,
+        sync::atomic::{AtomicBool, Ordering},
+    },
+};
+
+// Reads are submitted and waited on one at a time, so a small ring is enough
+const RING_ENTRIES: u32 = 8;
+
+static DISABLED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    static RING: RefCell<Option<IoUring>> = const { RefCell::new(None) };
+}
+
+fn disable(reason: impl std::fmt::Display) {
+    if !DISABLED.swap(true, Ordering::Relaxed) {
+        warn!("io_uring is unavailable, falling back to pread: {reason}");
+    }
+}
+
+fn new_ring() -> io::Result<IoUring> {
+    let ring = IoUring::new(RING_ENTRIES)?;
+    let mut probe = Probe::new();
+    ring.submitter().register_probe(&mut probe)?;
+    if !probe.is_supported(opcode::Read::CODE) {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "IORING_OP_READ is not supported",
+        ));
+    }
+    Ok(ring)
+}
+
+/// Reads into `buffer` at `offset` of `file`, like `pread`.
+/// Returns None if io_uring is not available, in which case the caller should fall back to `pread`.
+pub(super) fn read_at(file: &File, buffer: &mut [u8], offset: u64) -> Option<io::Result<usize>> {
+    if DISABLED.load(Ordering::Relaxed) {
+        return None;
+    }
+    RING.with_borrow_mut(|ring| {
+        if ring.is_none() {
+            match new_ring() {
+                Ok(new_ring) => *ring = Some(new_ring),
+                Err(err) => {
+                    disable(err);
+                    return None;
+                }
+            }
+        }
+        let ring_ref = ring.as_mut().unwrap();
+
+        let len = u32::try_from(buffer.len()).unwrap_or(u32::MAX);
+        let entry = opcode::Read::new(types::Fd(file.as_raw_fd()), buffer.as_mut_ptr(), len)
+            .offset(offset)
+            .build();
+        // SAFETY: `buffer` outlives the read, since we wait for its completion below
+        if unsafe { ring_ref.submission().push(&entry) }.is_err() {
+            // Every read is completed before returning, so the queue can't be full
+            *ring = None;
+            disable("submission queue is full");
+            return None;
+        }
+        // Submit the read and wait for its completion with a single syscall
+        loop {
+            match ring_ref.submit_and_wait(1) {
+                Ok(1) => break,
+                // Interrupted before the entry was taken, so it is still queued for the retry.
+                // An interrupted wait after the entry was taken still reports it as submitted.
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                result => {
+                    // Nothing was handed to the kernel, so it is safe to drop the ring with the
+                    // entry
+                    *ring = None;
+                    disable(format!("failed to submit read: {result:?}"));
+                    return None;
+                }
+            }
+        }
+
+        let completion = loop {
+            if let Some(completion) = ring_ref.completion().next() {
+                break completion;
+            }
+            match ring_ref.submit_and_wait(1) {
+                Ok(_) => {}
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                // The kernel may still be writing into `buffer`, so we can't return
+                Err(err) => panic!("failed to wait for io_uring read: {err}"),
+            }
+        };
+        let result = completion.result();
+        Some(if result < 0 {
+            Err(io::Error::from_raw_os_error(-result))
+        } else {
+            Ok(result as usize)
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        std::{io::Write, os::unix::fs::FileExt},
+        tempfile::tempfile,
+    };
+
+    #[test]
+    fn test_read_at() {
+        let mut file = tempfile().unwrap();
+        let bytes: Vec<u8> = (0..=255).cycle().take(10_000).collect();
+        file.write_all(&bytes).unwrap();
+
+        for (offset, len) in [
+            (0, 10_000),
+            (1, 100),
+            (4_096, 4_096),
+            (9_990, 100),
+            (20_000, 8),
+        ] {
+            let mut expected = vec![0; len];
+            let expected_len = file.read_at(&mut expected, offset).unwrap();
+            let mut buffer = vec![0; len];
+            let Some(result) = read_at(&file, &mut buffer, offset) else {
+                // io_uring is not available where the tests run
+                return;
+            };
+            assert_eq!(result.unwrap(), expected_len);
+            assert_eq!(buffer, expected);
+        }
+    }
+}
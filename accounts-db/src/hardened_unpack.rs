@@ -1,4 +1,5 @@
 use {
+    crate::snapshot_archive_manifest::ManifestVerifier,
     bzip2::bufread::BzDecoder,
     log::*,
     rand::{thread_rng, Rng},
@@ -26,6 +27,8 @@ pub enum UnpackError {
     Io(#[from] std::io::Error),
     #[error("Archive error: {0}")]
     Archive(String),
+    #[error("Archive integrity error: {0}")]
+    Integrity(String),
 }
 
 pub type Result<T> = std::result::Result<T, UnpackError>;
@@ -92,6 +95,7 @@ fn unpack_archive<'a, A, C, D>(
     limit_count: u64,
     mut entry_checker: C, // checks if entry is valid
     entry_processor: D,   // processes entry after setting permissions
+    mut manifest_verifier: Option<&mut ManifestVerifier>,
 ) -> Result<()>
 where
     A: Read,
@@ -132,6 +136,17 @@ where
             )));
         };
 
+        if let Some(verifier) = manifest_verifier.as_deref_mut() {
+            if ManifestVerifier::is_manifest(&parts) && kind == Regular {
+                let size = entry.header().size()?;
+                verifier.load_manifest(&mut entry, size)?;
+                continue;
+            }
+            if matches!(kind, GNUSparse | Regular) {
+                verifier.file_seen();
+            }
+        }
+
         let unpack_dir = match entry_checker(parts.as_slice(), kind) {
             UnpackPath::Invalid => {
                 return Err(UnpackError::Archive(format!(
@@ -175,6 +190,7 @@ where
             continue; // skip it
         };
 
+        let path_in_archive = parts.join("/");
         let unpack = entry.unpack(&entry_path);
         check_unpack_result(unpack.map(|_unpack| true)?, path_str)?;
 
@@ -185,6 +201,14 @@ where
         };
         set_perms(&entry_path, mode)?;
 
+        // Verify the entry before handing it to the processor, so that corrupted files are
+        // never used.
+        if let Some(verifier) = manifest_verifier.as_deref_mut() {
+            if matches!(kind, GNUSparse | Regular) {
+                verifier.verify(path_in_archive, &entry_path)?;
+            }
+        }
+
         // Process entry after setting permissions
         entry_processor(entry_path);
 
@@ -192,6 +216,10 @@ where
     }
     info!("unpacked {} entries total", total_entries);
 
+    if let Some(verifier) = manifest_verifier {
+        std::mem::take(verifier).finish()?;
+    }
+
     return Ok(());
 
     #[cfg(unix)]
@@ -366,6 +394,7 @@ where
 {
     assert!(!account_paths.is_empty());
     let mut i = 0;
+    let mut manifest_verifier = ManifestVerifier::default();
 
     unpack_archive(
         archive,
@@ -401,6 +430,7 @@ where
             }
         },
         entry_processor,
+        Some(&mut manifest_verifier),
     )
 }
 
@@ -521,6 +551,7 @@ fn unpack_genesis<A: Read>(
         MAX_GENESIS_ARCHIVE_UNPACKED_COUNT,
         |p, k| is_valid_genesis_archive_entry(unpack_dir, p, k),
         |_| {},
+        None,
     )
 }
 
@@ -548,6 +579,10 @@ fn is_valid_genesis_archive_entry<'a>(
 mod tests {
     use {
         super::*,
+        crate::snapshot_archive_manifest::{
+            hash_bytes, SnapshotArchiveManifest, SnapshotArchiveManifestEntry,
+            SNAPSHOT_ARCHIVE_MANIFEST_FILENAME,
+        },
         assert_matches::assert_matches,
         tar::{Builder, Header},
     };
@@ -820,6 +855,63 @@ mod tests {
         assert_matches!(result, Ok(()));
     }
 
+    #[test]
+    fn test_archive_unpack_snapshot_manifest() {
+        let files: [(&str, &[u8]); 2] = [("version", b"1.2.0"), ("accounts/123.456", b"acct")];
+        let manifest = SnapshotArchiveManifest::new(
+            "1.2.0".to_string(),
+            123,
+            files
+                .iter()
+                .map(|(path, data)| {
+                    let (size, hash) = hash_bytes(data);
+                    SnapshotArchiveManifestEntry {
+                        path: path.to_string(),
+                        size,
+                        hash,
+                    }
+                })
+                .collect(),
+        )
+        .to_bytes()
+        .unwrap();
+        let make_archive = |files: &[(&str, &[u8])]| {
+            let mut archive = Builder::new(Vec::new());
+            let mut entries = vec![
+                files[0],
+                (SNAPSHOT_ARCHIVE_MANIFEST_FILENAME, manifest.as_slice()),
+            ];
+            entries.extend_from_slice(&files[1..]);
+            for (path, data) in entries {
+                let mut header = Header::new_gnu();
+                header.set_path(path).unwrap();
+                header.set_size(data.len() as u64);
+                header.set_cksum();
+                archive.append(&header, data).unwrap();
+            }
+            archive
+        };
+
+        let result = finalize_and_unpack_snapshot(make_archive(&files));
+        assert_matches!(result, Ok(()));
+
+        // Corrupted files are rejected, naming the file.
+        let corrupted: [(&str, &[u8]); 2] = [files[0], ("accounts/123.456", b"acct!")];
+        let result = finalize_and_unpack_snapshot(make_archive(&corrupted));
+        assert_matches!(
+            result,
+            Err(UnpackError::Integrity(ref message))
+                if message == "\"accounts/123.456\" is 5 bytes, expected 4 bytes"
+        );
+
+        // A truncated archive is missing files listed in the manifest.
+        let result = finalize_and_unpack_snapshot(make_archive(&files[..1]));
+        assert_matches!(
+            result,
+            Err(UnpackError::Integrity(ref message)) if message.contains("truncated")
+        );
+    }
+
     #[test]
     fn test_archive_unpack_genesis_ok() {
         let mut header = Header::new_gnu();
@@ -20,6 +20,7 @@ mod active_stats;
 pub mod ancestors;
 mod ancient_append_vecs;
 pub mod append_vec;
+pub mod background_io_budget;
 pub mod blockhash_queue;
 mod bucket_map_holder;
 mod bucket_map_holder_stats;
@@ -39,6 +40,7 @@ mod read_only_accounts_cache;
 mod rolling_bit_field;
 pub mod secondary_index;
 pub mod shared_buffer_reader;
+pub mod snapshot_archive_manifest;
 pub mod sorted_storages;
 pub mod stake_rewards;
 pub mod storable_accounts;
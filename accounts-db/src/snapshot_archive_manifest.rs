@@ -0,0 +1,292 @@
+//! Integrity manifest stored inside snapshot archives.
+//!
+//! The manifest lists every file in the archive along with its size and BLAKE3 hash. It is
+//! written right after the `version` file, and verified while the archive is unpacked so that a
+//! corrupted or truncated archive is rejected with an error naming the offending file, rather
+//! than surfacing later as an obscure failure while rebuilding the bank.
+use {
+    crate::hardened_unpack::{Result, UnpackError},
+    serde::{Deserialize, Serialize},
+    solana_clock::Slot,
+    std::{
+        collections::HashMap,
+        fmt,
+        fs::File,
+        io::{self, Read},
+        path::{Path, PathBuf},
+    },
+};
+
+/// Name of the manifest file at the root of snapshot archives
+pub const SNAPSHOT_ARCHIVE_MANIFEST_FILENAME: &str = "manifest";
+
+/// Version of the manifest format
+pub const SNAPSHOT_ARCHIVE_MANIFEST_VERSION: u32 = 1;
+
+/// Upper bound on the size of the manifest; guards against allocating an absurd amount of memory
+/// for a malicious archive.
+const MAX_SNAPSHOT_ARCHIVE_MANIFEST_SIZE: u64 = 512 * 1024 * 1024;
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileHash(pub [u8; 32]);
+
+impl fmt::Display for FileHash {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.iter().try_for_each(|byte| write!(f, "{byte:02x}"))
+    }
+}
+
+impl fmt::Debug for FileHash {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotArchiveManifestEntry {
+    /// Path of the file inside the archive, with `/` separators
+    pub path: String,
+    pub size: u64,
+    pub hash: FileHash,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotArchiveManifest {
+    pub manifest_version: u32,
+    /// Contents of the archive's `version` file
+    pub snapshot_version: String,
+    pub slot: Slot,
+    pub files: Vec<SnapshotArchiveManifestEntry>,
+}
+
+impl SnapshotArchiveManifest {
+    pub fn new(
+        snapshot_version: String,
+        slot: Slot,
+        files: Vec<SnapshotArchiveManifestEntry>,
+    ) -> Self {
+        Self {
+            manifest_version: SNAPSHOT_ARCHIVE_MANIFEST_VERSION,
+            snapshot_version,
+            slot,
+            files,
+        }
+    }
+
+    pub fn to_bytes(&self) -> bincode::Result<Vec<u8>> {
+        bincode::serialize(self)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> bincode::Result<Self> {
+        bincode::deserialize(bytes)
+    }
+}
+
+/// Hashes all of `reader`, returning the number of bytes read and their hash
+pub fn hash_reader(mut reader: impl Read) -> io::Result<(u64, FileHash)> {
+    let mut hasher = blake3::Hasher::new();
+    let size = io::copy(&mut reader, &mut hasher)?;
+    Ok((size, FileHash(*hasher.finalize().as_bytes())))
+}
+
+pub fn hash_file(path: impl AsRef<Path>) -> io::Result<(u64, FileHash)> {
+    hash_reader(File::open(path)?)
+}
+
+pub fn hash_bytes(bytes: &[u8]) -> (u64, FileHash) {
+    (
+        bytes.len() as u64,
+        FileHash(*blake3::hash(bytes).as_bytes()),
+    )
+}
+
+/// Verifies unpacked snapshot archive entries against the archive's manifest.
+///
+/// Archives created before the manifest was introduced do not have one, in which case nothing is
+/// verified.
+#[derive(Default)]
+pub(crate) struct ManifestVerifier {
+    manifest: Option<HashMap<String, (u64, FileHash)>>,
+    /// Entries unpacked before the manifest was read
+    pending: Vec<(String, PathBuf)>,
+    /// Number of files seen in the archive, including those skipped by this unpacker
+    num_files_seen: usize,
+}
+
+impl ManifestVerifier {
+    pub(crate) fn is_manifest(parts: &[&str]) -> bool {
+        matches!(parts, [SNAPSHOT_ARCHIVE_MANIFEST_FILENAME])
+    }
+
+    pub(crate) fn load_manifest(&mut self, entry: impl Read, size: u64) -> Result<()> {
+        if self.manifest.is_some() {
+            return Err(UnpackError::Integrity(
+                "archive contains more than one manifest".to_string(),
+            ));
+        }
+        if size > MAX_SNAPSHOT_ARCHIVE_MANIFEST_SIZE {
+            return Err(UnpackError::Integrity(format!(
+                "manifest too large: {size} bytes"
+            )));
+        }
+        let mut bytes = Vec::with_capacity(size as usize);
+        entry.take(size).read_to_end(&mut bytes)?;
+        let manifest = SnapshotArchiveManifest::from_bytes(&bytes)
+            .map_err(|err| UnpackError::Integrity(format!("invalid manifest: {err}")))?;
+        if manifest.manifest_version != SNAPSHOT_ARCHIVE_MANIFEST_VERSION {
+            return Err(UnpackError::Integrity(format!(
+                "unsupported manifest version: {}",
+                manifest.manifest_version
+            )));
+        }
+        let num_files = manifest.files.len();
+        let files: HashMap<_, _> = manifest
+            .files
+            .into_iter()
+            .map(|file| (file.path, (file.size, file.hash)))
+            .collect();
+        if files.len() != num_files {
+            return Err(UnpackError::Integrity(
+                "manifest lists the same file more than once".to_string(),
+            ));
+        }
+        self.manifest = Some(files);
+        for (path, unpacked_path) in std::mem::take(&mut self.pending) {
+            self.verify(path, &unpacked_path)?;
+        }
+        Ok(())
+    }
+
+    /// Called for every file in the archive, whether or not it is unpacked here
+    pub(crate) fn file_seen(&mut self) {
+        self.num_files_seen += 1;
+    }
+
+    /// Verifies the file at `path` in the archive, unpacked to `unpacked_path`
+    pub(crate) fn verify(&mut self, path: String, unpacked_path: &Path) -> Result<()> {
+        let Some(manifest) = &self.manifest else {
+            self.pending.push((path, unpacked_path.to_path_buf()));
+            return Ok(());
+        };
+        let Some(&(expected_size, expected_hash)) = manifest.get(&path) else {
+            return Err(UnpackError::Integrity(format!(
+                "{path:?} is not listed in the manifest"
+            )));
+        };
+        let (size, hash) = hash_file(unpacked_path)?;
+        if size != expected_size {
+            return Err(UnpackError::Integrity(format!(
+                "{path:?} is {size} bytes, expected {expected_size} bytes"
+            )));
+        }
+        if hash != expected_hash {
+            return Err(UnpackError::Integrity(format!(
+                "{path:?} has hash {hash}, expected {expected_hash}"
+            )));
+        }
+        Ok(())
+    }
+
+    /// Checks that every file listed in the manifest was present in the archive
+    pub(crate) fn finish(self) -> Result<()> {
+        match self.manifest {
+            Some(manifest) => {
+                if self.num_files_seen != manifest.len() {
+                    return Err(UnpackError::Integrity(format!(
+                        "archive contains {} files, manifest lists {}; the archive may be \
+                         truncated",
+                        self.num_files_seen,
+                        manifest.len(),
+                    )));
+                }
+                Ok(())
+            }
+            None => {
+                log::info!("snapshot archive has no manifest, skipping integrity verification");
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, assert_matches::assert_matches, std::io::Write, tempfile::TempDir};
+
+    fn write_file(dir: &Path, name: &str, contents: &[u8]) -> PathBuf {
+        let path = dir.join(name);
+        File::create(&path).unwrap().write_all(contents).unwrap();
+        path
+    }
+
+    fn manifest_bytes(files: &[(&str, &[u8])]) -> Vec<u8> {
+        let files = files
+            .iter()
+            .map(|(path, contents)| {
+                let (size, hash) = hash_bytes(contents);
+                SnapshotArchiveManifestEntry {
+                    path: path.to_string(),
+                    size,
+                    hash,
+                }
+            })
+            .collect();
+        SnapshotArchiveManifest::new("1.2.0".to_string(), 42, files)
+            .to_bytes()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_manifest_verifier() {
+        let dir = TempDir::new().unwrap();
+        let version = write_file(dir.path(), "version", b"1.2.0");
+        let storage = write_file(dir.path(), "42.0", b"accounts");
+        let bytes = manifest_bytes(&[("version", b"1.2.0"), ("accounts/42.0", b"accounts")]);
+
+        let mut verifier = ManifestVerifier::default();
+        // Entries unpacked before the manifest is read are verified once it is.
+        verifier.file_seen();
+        verifier.verify("version".to_string(), &version).unwrap();
+        verifier
+            .load_manifest(bytes.as_slice(), bytes.len() as u64)
+            .unwrap();
+        verifier.file_seen();
+        verifier
+            .verify("accounts/42.0".to_string(), &storage)
+            .unwrap();
+        verifier.finish().unwrap();
+
+        // A corrupted file is rejected.
+        let mut verifier = ManifestVerifier::default();
+        verifier
+            .load_manifest(bytes.as_slice(), bytes.len() as u64)
+            .unwrap();
+        let corrupted = write_file(dir.path(), "42.1", b"accountz");
+        assert_matches!(
+            verifier.verify("accounts/42.0".to_string(), &corrupted),
+            Err(UnpackError::Integrity(ref message)) if message.contains("has hash")
+        );
+        // As is a file missing from the manifest.
+        assert_matches!(
+            verifier.verify("accounts/42.1".to_string(), &corrupted),
+            Err(UnpackError::Integrity(ref message)) if message.contains("not listed")
+        );
+
+        // A truncated archive is missing some of the files in the manifest.
+        let mut verifier = ManifestVerifier::default();
+        verifier
+            .load_manifest(bytes.as_slice(), bytes.len() as u64)
+            .unwrap();
+        verifier.file_seen();
+        assert_matches!(
+            verifier.finish(),
+            Err(UnpackError::Integrity(ref message)) if message.contains("truncated")
+        );
+
+        // Archives without a manifest are not verified.
+        let mut verifier = ManifestVerifier::default();
+        verifier.file_seen();
+        verifier.verify("version".to_string(), &version).unwrap();
+        verifier.finish().unwrap();
+    }
+}
@@ -34,4 +34,7 @@ pub enum TieredStorageError {
 
     #[error("failed to flush hot storage writer: {0}")]
     FlushHotWriter(#[source] std::io::Error),
+
+    #[error("tiered storage file {} is {1} bytes, expected {2} bytes", .0.display())]
+    IncorrectLength(PathBuf, usize, usize),
 }
@@ -5,8 +5,12 @@ use {
         ArgMatches, SubCommand,
     },
     rayon::prelude::*,
-    solana_account::ReadableAccount,
-    solana_accounts_db::append_vec::AppendVec,
+    solana_account::{AccountSharedData, ReadableAccount},
+    solana_accounts_db::{
+        accounts_file::{AccountsFile, AccountsFileProvider},
+        append_vec::{aligned_stored_size, AppendVec},
+        tiered_storage::TieredStorage,
+    },
     solana_pubkey::Pubkey,
     solana_system_interface::MAX_PERMITTED_DATA_LENGTH,
     std::{
@@ -19,6 +23,7 @@ use {
 
 const CMD_INSPECT: &str = "inspect";
 const CMD_SEARCH: &str = "search";
+const CMD_CONVERT: &str = "convert";
 
 fn main() {
     let matches = App::new(crate_name!())
@@ -73,6 +78,44 @@ fn main() {
                         .help("Show additional account information"),
                 ),
         )
+        .subcommand(
+            SubCommand::with_name(CMD_CONVERT)
+                .about("Rewrites an account storage file in another format")
+                .arg(
+                    Arg::with_name("path")
+                        .index(1)
+                        .takes_value(true)
+                        .required(true)
+                        .value_name("PATH")
+                        .help("Account storage file to convert"),
+                )
+                .arg(
+                    Arg::with_name("output")
+                        .index(2)
+                        .takes_value(true)
+                        .required(true)
+                        .value_name("OUTPUT")
+                        .help("Path to write the converted account storage file to"),
+                )
+                .arg(
+                    Arg::with_name("input_format")
+                        .long("input-format")
+                        .takes_value(true)
+                        .value_name("FORMAT")
+                        .possible_values(AccountsFileProvider::cli_names())
+                        .default_value("append-vec")
+                        .help("Format of the account storage file to convert"),
+                )
+                .arg(
+                    Arg::with_name("format")
+                        .long("format")
+                        .takes_value(true)
+                        .value_name("FORMAT")
+                        .possible_values(AccountsFileProvider::cli_names())
+                        .default_value("hot")
+                        .help("Format of the converted account storage file"),
+                ),
+        )
         .get_matches();
 
     let subcommand = matches.subcommand();
@@ -80,6 +123,7 @@ fn main() {
     match subcommand {
         (CMD_INSPECT, Some(subcommand_matches)) => cmd_inspect(&matches, subcommand_matches),
         (CMD_SEARCH, Some(subcommand_matches)) => cmd_search(&matches, subcommand_matches),
+        (CMD_CONVERT, Some(subcommand_matches)) => cmd_convert(&matches, subcommand_matches),
         _ => unreachable!(),
     }
     .unwrap_or_else(|err| {
@@ -108,6 +152,17 @@ fn cmd_search(
     do_search(path, addresses, verbose)
 }
 
+fn cmd_convert(
+    _app_matches: &ArgMatches<'_>,
+    subcommand_matches: &ArgMatches<'_>,
+) -> Result<(), String> {
+    let path = value_t_or_exit!(subcommand_matches, "path", String);
+    let output = value_t_or_exit!(subcommand_matches, "output", String);
+    let input_provider = value_t_or_exit!(subcommand_matches, "input_format", AccountsFileProvider);
+    let provider = value_t_or_exit!(subcommand_matches, "format", AccountsFileProvider);
+    do_convert(path, output, input_provider, provider)
+}
+
 fn do_inspect(file: impl AsRef<Path>, verbose: bool) -> Result<(), String> {
     let storage = AppendVec::new_for_store_tool(file.as_ref()).map_err(|err| {
         format!(
@@ -213,6 +268,69 @@ fn do_search(
     Ok(())
 }
 
+fn do_convert(
+    file: impl AsRef<Path>,
+    output: impl AsRef<Path>,
+    input_provider: AccountsFileProvider,
+    provider: AccountsFileProvider,
+) -> Result<(), String> {
+    let file = file.as_ref();
+    let output = output.as_ref();
+    let storage = match input_provider {
+        AccountsFileProvider::AppendVec => AppendVec::new_for_store_tool(file)
+            .map(AccountsFile::AppendVec)
+            .map_err(|err| err.to_string()),
+        AccountsFileProvider::HotStorage => TieredStorage::new_readonly(file)
+            .map(AccountsFile::TieredStorage)
+            .map_err(|err| err.to_string()),
+    }
+    .map_err(|err| {
+        format!(
+            "failed to open account storage file '{}': {err}",
+            file.display(),
+        )
+    })?;
+    // By default, when the storage is dropped, the backing file will be removed.
+    // We do not want to remove the backing file here in the store-tool, so prevent dropping.
+    let storage = ManuallyDrop::new(storage);
+
+    let mut accounts = Vec::new();
+    let mut size = 0;
+    storage.scan_accounts(|account| {
+        size += aligned_stored_size(account.data().len());
+        accounts.push((*account.pubkey(), account.to_account_shared_data()));
+    });
+
+    let converted = ManuallyDrop::new(provider.new_writable(output, size as u64));
+    // The slot is not persisted in the storage file, so any value will do.
+    let slot_ignored = 0_u64;
+    let accounts_to_write: (_, &[(Pubkey, AccountSharedData)]) = (slot_ignored, &accounts);
+    converted
+        .append_accounts(&accounts_to_write, 0)
+        .ok_or_else(|| {
+            format!(
+                "failed to write account storage file '{}'",
+                output.display()
+            )
+        })?;
+    converted.flush().map_err(|err| {
+        format!(
+            "failed to flush account storage file '{}': {err}",
+            output.display(),
+        )
+    })?;
+
+    println!(
+        "converted {} accounts from '{}' into '{}', file size: {} -> {}",
+        accounts.len(),
+        file.display(),
+        output.display(),
+        storage.len(),
+        converted.len(),
+    );
+    Ok(())
+}
+
 /// Returns the number of characters required to print `x` in base-10
 fn width10(x: u64) -> usize {
     (x as f64).log10().ceil() as usize
@@ -9,7 +9,10 @@ use {
     rayon::prelude::*,
     solana_client::connection_cache::ConnectionCache,
     solana_core::{
-        banking_stage::{update_bank_forks_and_poh_recorder_for_new_tpu_bank, BankingStage},
+        banking_stage::{
+            update_bank_forks_and_poh_recorder_for_new_tpu_bank, BankingStage,
+            PrioGraphSchedulerConfig,
+        },
         banking_trace::{BankingTracer, Channels, BANKING_TRACE_DIR_DEFAULT_BYTE_LIMIT},
         validator::{BlockProductionMethod, TransactionStructure},
     },
@@ -496,6 +499,9 @@ fn main() {
         bank_forks.clone(),
         &prioritization_fee_cache,
         false,
+        None,
+        PrioGraphSchedulerConfig::default(),
+        Arc::default(),
     );
 
     // This is so that the signal_receiver does not go out of scope after the closure.
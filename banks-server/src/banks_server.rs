@@ -201,6 +201,8 @@ fn simulate_transaction(
         units_consumed,
         return_data,
         inner_instructions,
+        memory_usage: _,
+        account_privileges: _,
     } = bank.simulate_transaction_unchecked(&sanitized_transaction, true);
 
     let simulation_details = TransactionSimulationDetails {
@@ -28,9 +28,12 @@ use {
     solana_native_token::lamports_to_sol,
     solana_program::stake::state::{Authorized, Lockup},
     solana_pubkey::Pubkey,
-    solana_rpc_client_api::response::{
-        RpcAccountBalance, RpcContactInfo, RpcInflationGovernor, RpcInflationRate, RpcKeyedAccount,
-        RpcSupply, RpcVoteAccountInfo,
+    solana_rpc_client_api::{
+        clock_math::Estimate,
+        response::{
+            RpcAccountBalance, RpcContactInfo, RpcInflationGovernor, RpcInflationRate,
+            RpcKeyedAccount, RpcSupply, RpcVoteAccountInfo,
+        },
     },
     solana_signature::Signature,
     solana_sysvar::stake_history::StakeHistoryEntry,
@@ -149,11 +152,22 @@ impl fmt::Display for CliPrioritizationFee {
 }
 
 #[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct CliAccount {
     #[serde(flatten)]
     pub keyed_account: RpcKeyedAccount,
     #[serde(skip_serializing, skip_deserializing)]
     pub use_lamports_unit: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_hash: Option<CliAccountDataHash>,
+}
+
+/// Hash of the full account data, to check it against a hash computed on-chain
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CliAccountDataHash {
+    pub algorithm: String,
+    pub hash: String,
 }
 
 pub struct CliAccountNewConfig {
@@ -209,6 +223,7 @@ impl CliAccount {
                 ),
             },
             use_lamports_unit,
+            data_hash: None,
         }
     }
 }
@@ -240,6 +255,13 @@ impl fmt::Display for CliAccount {
             "Rent Epoch:",
             &self.keyed_account.account.rent_epoch.to_string(),
         )?;
+        if let Some(data_hash) = &self.data_hash {
+            writeln_name_value(
+                f,
+                &format!("Data Hash ({}):", data_hash.algorithm),
+                &data_hash.hash,
+            )?;
+        }
         Ok(())
     }
 }
@@ -357,6 +379,8 @@ pub struct CliEpochInfo {
     #[serde(skip)]
     pub average_slot_time_ms: u64,
     #[serde(skip)]
+    pub time_remaining: Option<Estimate<Duration>>,
+    #[serde(skip)]
     pub start_block_time: Option<UnixTimestamp>,
     #[serde(skip)]
     pub current_block_time: Option<UnixTimestamp>,
@@ -414,16 +438,33 @@ impl fmt::Display for CliEpochInfo {
                 Some("* estimated based on current slot durations"),
             )
         };
-        let time_remaining = slot_to_duration(remaining_slots_in_epoch, self.average_slot_time_ms);
+        let time_remaining = self.time_remaining.unwrap_or_else(|| {
+            let time_remaining =
+                slot_to_duration(remaining_slots_in_epoch, self.average_slot_time_ms);
+            Estimate {
+                estimate: time_remaining,
+                min: time_remaining,
+                max: time_remaining,
+            }
+        });
+        let time_remaining_range = if time_remaining.min != time_remaining.max {
+            format!(
+                ", between {} and {}",
+                format_duration_secs(time_remaining.min),
+                format_duration_secs(time_remaining.max),
+            )
+        } else {
+            String::new()
+        };
         writeln_name_value(
             f,
             "Epoch Completed Time:",
             &format!(
-                "{}{}/{} ({} remaining)",
+                "{}{}/{} ({} remaining{time_remaining_range})",
                 humantime::format_duration(time_elapsed),
                 if annotation.is_some() { "*" } else { "" },
-                humantime::format_duration(time_elapsed + time_remaining),
-                humantime::format_duration(time_remaining),
+                format_duration_secs(time_elapsed + time_remaining.estimate),
+                format_duration_secs(time_remaining.estimate),
             ),
         )?;
         if let Some(annotation) = annotation {
@@ -438,6 +479,11 @@ fn slot_to_duration(slot: Slot, slot_time_ms: u64) -> Duration {
     Duration::from_secs((slot * slot_time_ms) / 1000)
 }
 
+// Formats `duration` truncated to whole seconds, as the estimates are not more precise
+fn format_duration_secs(duration: Duration) -> humantime::FormattedDuration {
+    humantime::format_duration(Duration::from_secs(duration.as_secs()))
+}
+
 #[derive(Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct CliValidatorsStakeByVersion {
@@ -432,6 +432,7 @@ pub enum CliCommand {
         pubkey: Pubkey,
         output_file: Option<String>,
         use_lamports_unit: bool,
+        data_hash: Option<AccountDataHashAlgorithm>,
     },
     Transfer {
         amount: SpendAmount,
@@ -1667,7 +1668,15 @@ pub fn process_command(config: &CliConfig) -> ProcessResult {
             pubkey,
             output_file,
             use_lamports_unit,
-        } => process_show_account(&rpc_client, config, pubkey, output_file, *use_lamports_unit),
+            data_hash,
+        } => process_show_account(
+            &rpc_client,
+            config,
+            pubkey,
+            output_file,
+            *use_lamports_unit,
+            *data_hash,
+        ),
         CliCommand::Transfer {
             amount,
             to,
@@ -1960,6 +1969,24 @@ mod tests {
             }
         );
 
+        // Test Account Subcommand
+        let test_account = test_commands.clone().get_matches_from(vec![
+            "test",
+            "account",
+            &pubkey_string,
+            "--data-hash",
+            "blake3",
+        ]);
+        assert_eq!(
+            parse_command(&test_account, &default_signer, &mut None).unwrap(),
+            CliCommandInfo::without_signers(CliCommand::ShowAccount {
+                pubkey,
+                output_file: None,
+                use_lamports_unit: false,
+                data_hash: Some(AccountDataHashAlgorithm::Blake3),
+            })
+        );
+
         // Test Confirm Subcommand
         let signature = Signature::from([1; 64]);
         let signature_string = format!("{signature:?}");
@@ -41,6 +41,7 @@ use {
     solana_rpc_client::rpc_client::{GetConfirmedSignaturesForAddress2Config, RpcClient},
     solana_rpc_client_api::{
         client_error::ErrorKind as ClientErrorKind,
+        clock_math::ClockMath,
         config::{
             RpcAccountInfoConfig, RpcBlockConfig, RpcGetVoteAccountsConfig,
             RpcLargestAccountsConfig, RpcLargestAccountsFilter, RpcProgramAccountsConfig,
@@ -48,7 +49,7 @@ use {
         },
         filter::{Memcmp, RpcFilterType},
         request::DELINQUENT_VALIDATOR_SLOT_DISTANCE,
-        response::{RpcPerfSample, RpcPrioritizationFee, SlotInfo},
+        response::{RpcPrioritizationFee, SlotInfo},
     },
     solana_sdk_ids::sysvar::{self, stake_history},
     solana_signature::Signature,
@@ -1122,6 +1123,7 @@ pub fn process_get_epoch_info(rpc_client: &RpcClient, config: &CliConfig) -> Pro
         epoch_info,
         epoch_completed_percent,
         average_slot_time_ms: 0,
+        time_remaining: None,
         start_block_time: None,
         current_block_time: None,
     };
@@ -1129,26 +1131,16 @@ pub fn process_get_epoch_info(rpc_client: &RpcClient, config: &CliConfig) -> Pro
         OutputFormat::Json | OutputFormat::JsonCompact => {}
         _ => {
             let epoch_info = &cli_epoch_info.epoch_info;
-            let average_slot_time_ms = rpc_client
+            let samples = rpc_client
                 .get_recent_performance_samples(Some(60))
+                .unwrap_or_default();
+            let clock_math = rpc_client
+                .get_epoch_schedule()
                 .ok()
-                .and_then(|samples| {
-                    let (slots, secs) = samples.iter().fold(
-                        (0, 0u64),
-                        |(slots, secs): (u64, u64),
-                         RpcPerfSample {
-                             num_slots,
-                             sample_period_secs,
-                             ..
-                         }| {
-                            (
-                                slots.saturating_add(*num_slots),
-                                secs.saturating_add((*sample_period_secs).into()),
-                            )
-                        },
-                    );
-                    secs.saturating_mul(1000).checked_div(slots)
-                })
+                .map(|epoch_schedule| ClockMath::new(epoch_schedule, &samples));
+            let average_slot_time_ms = clock_math
+                .as_ref()
+                .map(|clock_math| clock_math.slot_time().estimate.as_millis() as u64)
                 .unwrap_or(clock::DEFAULT_MS_PER_SLOT);
             let epoch_expected_start_slot = epoch_info
                 .absolute_slot
@@ -1173,6 +1165,8 @@ pub fn process_get_epoch_info(rpc_client: &RpcClient, config: &CliConfig) -> Pro
             let current_block_time = rpc_client.get_block_time(epoch_info.absolute_slot).ok();
 
             cli_epoch_info.average_slot_time_ms = average_slot_time_ms;
+            cli_epoch_info.time_remaining = clock_math
+                .map(|clock_math| clock_math.time_until_epoch_end(epoch_info.absolute_slot));
             cli_epoch_info.start_block_time = start_block_time;
             cli_epoch_info.current_block_time = current_block_time;
         }
@@ -1394,6 +1388,7 @@ pub fn process_largest_accounts(
             commitment: Some(config.commitment),
             filter,
             sort_results: None,
+            min_context_slot: None,
         })?
         .value;
     let largest_accounts = CliAccountBalances { accounts };
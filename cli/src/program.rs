@@ -2164,6 +2164,73 @@ fn close(
     Ok(())
 }
 
+/// Maximum number of buffers closed by a single transaction, keeping the
+/// transaction well within the packet size
+const MAX_BUFFERS_CLOSED_PER_TRANSACTION: usize = 16;
+
+/// Closes the given buffers, several per transaction, and returns the ones
+/// which were closed. If a batch fails, its buffers are closed one at a time
+/// so that one bad buffer does not prevent closing the others.
+fn close_buffers(
+    rpc_client: &RpcClient,
+    config: &CliConfig,
+    buffers: &[CliUpgradeableBuffer],
+    recipient_pubkey: &Pubkey,
+    authority_signer: &dyn Signer,
+) -> Result<Vec<CliUpgradeableBuffer>, Box<dyn std::error::Error>> {
+    let mut closed = vec![];
+    for batch in buffers.chunks(MAX_BUFFERS_CLOSED_PER_TRANSACTION) {
+        let buffer_pubkeys = batch
+            .iter()
+            .map(|buffer| Pubkey::from_str(&buffer.address))
+            .collect::<Result<Vec<_>, _>>()?;
+        let instructions: Vec<_> = buffer_pubkeys
+            .iter()
+            .map(|buffer_pubkey| {
+                bpf_loader_upgradeable::close_any(
+                    buffer_pubkey,
+                    recipient_pubkey,
+                    Some(&authority_signer.pubkey()),
+                    None,
+                )
+            })
+            .collect();
+        let blockhash = rpc_client.get_latest_blockhash()?;
+        let mut tx = Transaction::new_unsigned(Message::new(
+            &instructions,
+            Some(&config.signers[0].pubkey()),
+        ));
+        tx.try_sign(&[config.signers[0], authority_signer], blockhash)?;
+        if rpc_client
+            .send_and_confirm_transaction_with_spinner_and_config(
+                &tx,
+                config.commitment,
+                config.send_transaction_config,
+            )
+            .is_ok()
+        {
+            closed.extend_from_slice(batch);
+            continue;
+        }
+
+        for (buffer, buffer_pubkey) in batch.iter().zip(buffer_pubkeys.iter()) {
+            if close(
+                rpc_client,
+                config,
+                buffer_pubkey,
+                recipient_pubkey,
+                authority_signer,
+                None,
+            )
+            .is_ok()
+            {
+                closed.push(buffer.clone());
+            }
+        }
+    }
+    Ok(closed)
+}
+
 fn process_close(
     rpc_client: &RpcClient,
     config: &CliConfig,
@@ -2272,21 +2339,13 @@ fn process_close(
             use_lamports_unit,
         )?;
 
-        let mut closed = vec![];
-        for buffer in buffers.buffers.iter() {
-            if close(
-                rpc_client,
-                config,
-                &Pubkey::from_str(&buffer.address)?,
-                &recipient_pubkey,
-                authority_signer,
-                None,
-            )
-            .is_ok()
-            {
-                closed.push(buffer.clone());
-            }
-        }
+        let closed = close_buffers(
+            rpc_client,
+            config,
+            &buffers.buffers,
+            &recipient_pubkey,
+            authority_signer,
+        )?;
         Ok(config
             .output_format
             .formatted_string(&CliUpgradeableBuffers {
@@ -24,9 +24,9 @@ use {
     },
     solana_cli_output::{
         display::{build_balance_message, BuildBalanceMessageConfig},
-        return_signers_with_config, CliAccount, CliBalance, CliFindProgramDerivedAddress,
-        CliSignatureVerificationStatus, CliTransaction, CliTransactionConfirmation, OutputFormat,
-        ReturnSignersConfig,
+        return_signers_with_config, CliAccount, CliAccountDataHash, CliBalance,
+        CliFindProgramDerivedAddress, CliSignatureVerificationStatus, CliTransaction,
+        CliTransactionConfirmation, OutputFormat, ReturnSignersConfig,
     },
     solana_commitment_config::CommitmentConfig,
     solana_message::Message,
@@ -77,6 +77,17 @@ impl WalletSubCommands for App<'_, '_> {
                         .long("lamports")
                         .takes_value(false)
                         .help("Display balance in lamports instead of SOL"),
+                )
+                .arg(
+                    Arg::with_name("data_hash")
+                        .long("data-hash")
+                        .value_name("ALGORITHM")
+                        .takes_value(true)
+                        .possible_values(&["sha256", "blake3"])
+                        .help(
+                            "Also display the hash of the account data, to check it against \
+                             a hash computed on-chain",
+                        ),
                 ),
         )
         .subcommand(
@@ -397,6 +408,40 @@ fn resolve_derived_address_program_id(matches: &ArgMatches<'_>, arg_name: &str)
     })
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccountDataHashAlgorithm {
+    Sha256,
+    Blake3,
+}
+
+impl AccountDataHashAlgorithm {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Sha256 => "sha256",
+            Self::Blake3 => "blake3",
+        }
+    }
+
+    fn hash(&self, data: &[u8]) -> String {
+        match self {
+            Self::Sha256 => solana_sha256_hasher::hash(data).to_string(),
+            Self::Blake3 => solana_blake3_hasher::hash(data).to_string(),
+        }
+    }
+}
+
+impl FromStr for AccountDataHashAlgorithm {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "sha256" => Ok(Self::Sha256),
+            "blake3" => Ok(Self::Blake3),
+            _ => Err(format!("unsupported hash algorithm: {s}")),
+        }
+    }
+}
+
 pub fn parse_account(
     matches: &ArgMatches<'_>,
     wallet_manager: &mut Option<Rc<RemoteWalletManager>>,
@@ -404,10 +449,12 @@ pub fn parse_account(
     let account_pubkey = pubkey_of_signer(matches, "account_pubkey", wallet_manager)?.unwrap();
     let output_file = matches.value_of("output_file");
     let use_lamports_unit = matches.is_present("lamports");
+    let data_hash = value_of(matches, "data_hash");
     Ok(CliCommandInfo::without_signers(CliCommand::ShowAccount {
         pubkey: account_pubkey,
         output_file: output_file.map(ToString::to_string),
         use_lamports_unit,
+        data_hash,
     }))
 }
 
@@ -650,10 +697,15 @@ pub fn process_show_account(
     account_pubkey: &Pubkey,
     output_file: &Option<String>,
     use_lamports_unit: bool,
+    data_hash: Option<AccountDataHashAlgorithm>,
 ) -> ProcessResult {
     let account = rpc_client.get_account(account_pubkey)?;
     let data = &account.data;
-    let cli_account = CliAccount::new(account_pubkey, &account, use_lamports_unit);
+    let mut cli_account = CliAccount::new(account_pubkey, &account, use_lamports_unit);
+    cli_account.data_hash = data_hash.map(|algorithm| CliAccountDataHash {
+        algorithm: algorithm.name().to_string(),
+        hash: algorithm.hash(data),
+    });
 
     let mut account_string = config.output_format.formatted_string(&cli_account);
 
@@ -1912,6 +1912,98 @@ fn test_cli_program_write_buffer() {
     );
 }
 
+#[test]
+fn test_cli_program_close_all_buffers() {
+    solana_logger::setup();
+
+    let mut noop_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    noop_path.push("tests");
+    noop_path.push("fixtures");
+    noop_path.push("noop");
+    noop_path.set_extension("so");
+
+    let mint_keypair = Keypair::new();
+    let mint_pubkey = mint_keypair.pubkey();
+    let test_validator = test_validator_genesis(mint_keypair)
+        .start_with_mint_address(mint_pubkey, SocketAddrSpace::Unspecified)
+        .expect("validator start failed");
+
+    let rpc_client =
+        RpcClient::new_with_commitment(test_validator.rpc_url(), CommitmentConfig::processed());
+
+    let mut file = File::open(noop_path.to_str().unwrap()).unwrap();
+    let mut program_data = Vec::new();
+    file.read_to_end(&mut program_data).unwrap();
+    let minimum_balance_for_buffer = rpc_client
+        .get_minimum_balance_for_rent_exemption(UpgradeableLoaderState::size_of_buffer(
+            program_data.len(),
+        ))
+        .unwrap();
+
+    let mut config = CliConfig::recent_for_tests();
+    let keypair = Keypair::new();
+    config.json_rpc_url = test_validator.rpc_url();
+    config.signers = vec![&keypair];
+    config.command = CliCommand::Airdrop {
+        pubkey: None,
+        lamports: 100 * minimum_balance_for_buffer,
+    };
+    process_command(&config).unwrap();
+
+    // Abandon a few buffers
+    let num_buffers = 3;
+    for _ in 0..num_buffers {
+        config.signers = vec![&keypair];
+        config.command = CliCommand::Program(ProgramCliCommand::WriteBuffer {
+            program_location: noop_path.to_str().unwrap().to_string(),
+            fee_payer_signer_index: 0,
+            buffer_signer_index: None,
+            buffer_pubkey: None,
+            buffer_authority_signer_index: 0,
+            max_len: None,
+            skip_fee_check: false,
+            compute_unit_price: None,
+            max_sign_attempts: 5,
+            use_rpc: false,
+            skip_feature_verification: true,
+        });
+        process_command(&config).unwrap();
+    }
+
+    let buffers = rpc_client.get_buffer_accounts(&keypair.pubkey()).unwrap();
+    assert_eq!(buffers.len(), num_buffers);
+    for buffer in &buffers {
+        assert_eq!(buffer.authority, keypair.pubkey().to_string());
+        assert_eq!(buffer.data_len, program_data.len());
+        assert_eq!(buffer.lamports, minimum_balance_for_buffer);
+    }
+
+    // Close all of them at once
+    let recipient_pubkey = Pubkey::new_unique();
+    config.signers = vec![&keypair];
+    config.command = CliCommand::Program(ProgramCliCommand::Close {
+        account_pubkey: None,
+        recipient_pubkey,
+        authority_index: 0,
+        use_lamports_unit: false,
+        bypass_warning: false,
+    });
+    config.output_format = OutputFormat::JsonCompact;
+    let response = process_command(&config).unwrap();
+    let json: Value = serde_json::from_str(&response).unwrap();
+    assert_eq!(json["buffers"].as_array().unwrap().len(), num_buffers);
+
+    assert!(rpc_client
+        .get_buffer_accounts(&keypair.pubkey())
+        .unwrap()
+        .is_empty());
+    let recipient_account = rpc_client.get_account(&recipient_pubkey).unwrap();
+    assert_eq!(
+        num_buffers as u64 * minimum_balance_for_buffer,
+        recipient_account.lamports
+    );
+}
+
 #[test_case(true; "Feature enabled")]
 #[test_case(false; "Feature disabled")]
 fn test_cli_program_write_buffer_feature(enable_feature: bool) {
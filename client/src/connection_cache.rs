@@ -83,8 +83,6 @@ impl ConnectionCache {
         cert_info: Option<(&Keypair, IpAddr)>,
         stake_info: Option<(&Arc<RwLock<StakedNodes>>, &Pubkey)>,
     ) -> Self {
-        // The minimum pool size is 1.
-        let connection_pool_size = 1.max(connection_pool_size);
         let mut config = QuicConfig::new().unwrap();
         if let Some(client_endpoint) = client_endpoint {
             config.update_client_endpoint(client_endpoint);
@@ -95,6 +93,17 @@ impl ConnectionCache {
         if let Some(stake_info) = stake_info {
             config.set_staked_nodes(stake_info.0, stake_info.1);
         }
+        Self::new_with_quic_config(name, connection_pool_size, config)
+    }
+
+    /// Create a quic connection_cache from a complete client config
+    pub fn new_with_quic_config(
+        name: &'static str,
+        connection_pool_size: usize,
+        config: QuicConfig,
+    ) -> Self {
+        // The minimum pool size is 1.
+        let connection_pool_size = 1.max(connection_pool_size);
         let connection_manager = QuicConnectionManager::new_with_connection_config(config);
         let cache =
             BackendConnectionCache::new(name, connection_manager, connection_pool_size).unwrap();
@@ -122,6 +131,14 @@ impl ConnectionCache {
         matches!(self, Self::Quic(_))
     }
 
+    /// Returns false if the circuit breaker of `addr` is open because sends to it keep failing.
+    pub fn is_peer_available(&self, addr: &SocketAddr) -> bool {
+        match self {
+            Self::Quic(cache) => cache.is_peer_available(addr),
+            Self::Udp(cache) => cache.is_peer_available(addr),
+        }
+    }
+
     pub fn get_connection(&self, addr: &SocketAddr) -> BlockingClientConnection {
         match self {
             Self::Quic(cache) => BlockingClientConnection::Quic(cache.get_connection(addr)),
@@ -46,6 +46,11 @@ pub struct ComputeBudget {
     pub sha256_byte_cost: u64,
     /// Maximum number of slices hashed per syscall
     pub sha256_max_slices: u64,
+    /// Base number of compute units consumed to call blake3
+    pub blake3_base_cost: u64,
+    /// Incremental number of units consumed by blake3 for each started
+    /// 1024 byte chunk of a hashed slice
+    pub blake3_chunk_cost: u64,
     /// Maximum SBF to BPF call depth
     pub max_call_depth: usize,
     /// Size of a stack frame in bytes, must match the size specified in the LLVM SBF backend
@@ -160,6 +165,8 @@ impl ComputeBudget {
             sha256_base_cost: 85,
             sha256_byte_cost: 1,
             sha256_max_slices: 20_000,
+            blake3_base_cost: 85,
+            blake3_chunk_cost: 128,
             max_call_depth: MAX_CALL_DEPTH,
             stack_frame_size: STACK_FRAME_SIZE,
             log_pubkey_units: 100,
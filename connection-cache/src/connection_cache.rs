@@ -2,6 +2,7 @@ use {
     crate::{
         client_connection::ClientConnection as BlockingClientConnection,
         connection_cache_stats::{ConnectionCacheStats, CONNECTION_STAT_SUBMISSION_INTERVAL},
+        connection_health::{ConnectionHealth, ConnectionHealthConfig},
         nonblocking::client_connection::ClientConnection as NonblockingClientConnection,
     },
     crossbeam_channel::{Receiver, RecvError, Sender},
@@ -82,6 +83,22 @@ where
         connection_pool_size: usize,
         connection_config: C,
         connection_manager: M,
+    ) -> Self {
+        Self::new_with_health_config(
+            name,
+            connection_pool_size,
+            connection_config,
+            connection_manager,
+            ConnectionHealthConfig::default(),
+        )
+    }
+
+    pub fn new_with_health_config(
+        name: &'static str,
+        connection_pool_size: usize,
+        connection_config: C,
+        connection_manager: M,
+        health_config: ConnectionHealthConfig,
     ) -> Self {
         info!("Creating ConnectionCache {name}, pool size: {connection_pool_size}");
         let (sender, receiver) = crossbeam_channel::unbounded();
@@ -91,7 +108,10 @@ where
         let connection_manager = Arc::new(connection_manager);
         let connection_pool_size = 1.max(connection_pool_size); // The minimum pool size is 1.
 
-        let stats = Arc::new(ConnectionCacheStats::default());
+        let stats = Arc::new(ConnectionCacheStats {
+            connection_health: ConnectionHealth::new(health_config),
+            ..ConnectionCacheStats::default()
+        });
 
         let _async_connection_thread =
             Self::create_connection_async_thread(map.clone(), receiver, stats.clone());
@@ -144,6 +164,17 @@ where
         map.clear();
         self.connection_manager.update_key(key)
     }
+
+    pub fn connection_health(&self) -> &ConnectionHealth {
+        &self.stats.connection_health
+    }
+
+    /// Returns false if sends to `addr` have been failing and its circuit breaker is open, in
+    /// which case callers should skip the peer rather than queue more data on it.
+    pub fn is_peer_available(&self, addr: &SocketAddr) -> bool {
+        self.stats.connection_health.is_peer_available(addr)
+    }
+
     /// Create a lazy connection object under the exclusive lock of the cache map if there is not
     /// enough used connections in the connection pool for the specified address.
     /// Returns CreateConnectionResult.
@@ -264,6 +295,13 @@ where
         &self,
         addr: &SocketAddr,
     ) -> GetConnectionResult<<P as ConnectionPool>::BaseClientConnection> {
+        if self.stats.connection_health.take_reconnect(addr) {
+            // Drop the pool so that fresh connections are made below instead of reusing ones
+            // which are erroring out or stalling.
+            debug!("Re-creating unhealthy connections to {addr}");
+            self.map.write().unwrap().swap_remove(addr);
+        }
+
         let mut get_connection_map_lock_measure = Measure::start("get_connection_map_lock_measure");
         let map = self.map.read().unwrap();
         get_connection_map_lock_measure.stop();
@@ -751,6 +789,49 @@ mod tests {
         let _conn = map.get(&addr_with_quic_port).expect("Address not found");
     }
 
+    #[test]
+    fn test_unhealthy_connection_is_recreated() {
+        let addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 8000);
+        let connection_manager = MockConnectionManager::default();
+        let config = connection_manager.new_connection_config();
+        let connection_cache = ConnectionCache::new_with_health_config(
+            "connection_cache_test",
+            1,
+            config,
+            connection_manager,
+            ConnectionHealthConfig {
+                min_samples: 4,
+                ..ConnectionHealthConfig::default()
+            },
+        );
+        let get_pool_entry = || {
+            connection_cache.get_connection(&addr);
+            connection_cache
+                .map
+                .read()
+                .unwrap()
+                .get(&addr)
+                .unwrap()
+                .get(0)
+                .unwrap()
+        };
+
+        let first = get_pool_entry();
+        let health = connection_cache.connection_health();
+        for _ in 0..4 {
+            health.record_success(addr, None);
+        }
+        assert!(Arc::ptr_eq(&first, &get_pool_entry()));
+
+        for _ in 0..4 {
+            health.record_failure(addr);
+        }
+        let second = get_pool_entry();
+        assert!(!Arc::ptr_eq(&first, &second));
+        assert_eq!(health.reconnects.load(Ordering::Relaxed), 1);
+        assert!(Arc::ptr_eq(&second, &get_pool_entry()));
+    }
+
     // Test that we can get_connection with a connection cache configured
     // on an address with a port that would overflow to
     // an invalid port.
@@ -1,5 +1,5 @@
 use {
-    crate::client_connection::ClientStats,
+    crate::{client_connection::ClientStats, connection_health::ConnectionHealth},
     std::sync::atomic::{AtomicU64, Ordering},
 };
 
@@ -21,6 +21,9 @@ pub struct ConnectionCacheStats {
     // Need to track these separately per-connection
     // because we need to track the base stat value from quinn
     pub total_client_stats: ClientStats,
+
+    // Per-peer health of the cached connections, fed by the send path
+    pub connection_health: ConnectionHealth,
 }
 
 pub const CONNECTION_STAT_SUBMISSION_INTERVAL: u64 = 2000;
@@ -232,6 +235,30 @@ impl ConnectionCacheStats {
                 average_prepare_connection_us,
                 i64
             ),
+            (
+                "health_reconnects",
+                self.connection_health.reconnects.swap(0, Ordering::Relaxed),
+                i64
+            ),
+            (
+                "circuit_breaker_trips",
+                self.connection_health
+                    .circuit_breaker_trips
+                    .swap(0, Ordering::Relaxed),
+                i64
+            ),
+            (
+                "circuit_breaker_rejections",
+                self.connection_health
+                    .circuit_breaker_rejections
+                    .swap(0, Ordering::Relaxed),
+                i64
+            ),
+            (
+                "open_circuits",
+                self.connection_health.num_open_circuits(),
+                i64
+            ),
         );
     }
 }
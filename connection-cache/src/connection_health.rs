@@ -0,0 +1,437 @@
+//! Per-peer health tracking for cached connections.
+//!
+//! Every send through a cached connection reports its outcome (and the round trip time of the
+//! underlying connection when it is known) here. The resulting score is used by the
+//! `ConnectionCache` to drop and re-create connections to peers which have gone bad, and a
+//! per-peer circuit breaker stops the send path from hammering peers which keep failing.
+//!
+//! The state of each peer is kept in atomics, so the map of peers is only locked exclusively
+//! when a peer is first seen.
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicU32, AtomicU64, Ordering},
+        RwLock,
+    },
+    time::{Duration, Instant},
+};
+
+/// Upper bound on the number of peers for which health is tracked.
+const MAX_TRACKED_PEERS: usize = 4096;
+
+/// Weight given to the most recent sample in the moving averages.
+const EWMA_ALPHA: f64 = 0.2;
+
+#[derive(Clone, Debug)]
+pub struct ConnectionHealthConfig {
+    /// Connections to peers scoring below this value (in `[0, 1]`) are re-created.
+    pub min_score: f64,
+    /// Round trip time at which a peer starts being penalized.
+    pub rtt_threshold: Duration,
+    /// Number of samples required before a peer can be scored.
+    pub min_samples: u64,
+    /// Number of consecutive failures which trips the circuit breaker of a peer.
+    pub failure_threshold: u32,
+    /// How long a tripped circuit stays open before a probe is let through.
+    pub circuit_breaker_cooldown: Duration,
+}
+
+impl Default for ConnectionHealthConfig {
+    fn default() -> Self {
+        Self {
+            min_score: 0.5,
+            rtt_threshold: Duration::from_millis(500),
+            min_samples: 8,
+            failure_threshold: 5,
+            circuit_breaker_cooldown: Duration::from_secs(2),
+        }
+    }
+}
+
+// A circuit state is packed into a u64 as the time it refers to, in microseconds since the
+// creation of the `ConnectionHealth`, followed by a two bit tag.
+const CIRCUIT_TAG_BITS: u32 = 2;
+const CIRCUIT_TAG_MASK: u64 = (1 << CIRCUIT_TAG_BITS) - 1;
+const CIRCUIT_CLOSED: u64 = 0;
+const CIRCUIT_OPEN: u64 = 1;
+const CIRCUIT_HALF_OPEN: u64 = 2;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum CircuitState {
+    Closed,
+    Open {
+        until_us: u64,
+    },
+    /// A single probe has been let through and its outcome is pending.
+    HalfOpen {
+        since_us: u64,
+    },
+}
+
+impl CircuitState {
+    fn from_u64(state: u64) -> Self {
+        let time_us = state >> CIRCUIT_TAG_BITS;
+        match state & CIRCUIT_TAG_MASK {
+            CIRCUIT_OPEN => Self::Open { until_us: time_us },
+            CIRCUIT_HALF_OPEN => Self::HalfOpen { since_us: time_us },
+            _ => Self::Closed,
+        }
+    }
+
+    fn to_u64(self) -> u64 {
+        match self {
+            Self::Closed => CIRCUIT_CLOSED,
+            Self::Open { until_us } => (until_us << CIRCUIT_TAG_BITS) | CIRCUIT_OPEN,
+            Self::HalfOpen { since_us } => (since_us << CIRCUIT_TAG_BITS) | CIRCUIT_HALF_OPEN,
+        }
+    }
+}
+
+/// Health of a single peer. Concurrent updates are not serialized, so the moving averages are
+/// approximate under contention, which is fine for scoring.
+#[derive(Debug, Default)]
+struct PeerHealth {
+    samples: AtomicU64,
+    // The moving averages are stored as the bits of an f64.
+    rtt_us: AtomicU64,
+    error_rate: AtomicU64,
+    consecutive_failures: AtomicU32,
+    circuit: AtomicU64,
+}
+
+impl PeerHealth {
+    fn record(&self, error: bool, rtt: Option<Duration>) {
+        let error = if error { 1.0 } else { 0.0 };
+        let first = self.samples.fetch_add(1, Ordering::Relaxed) == 0;
+        update_average(&self.error_rate, error, |_| first);
+        if let Some(rtt) = rtt {
+            update_average(&self.rtt_us, rtt.as_micros() as f64, |rtt_us| rtt_us == 0.0);
+        }
+    }
+
+    fn score(&self, config: &ConnectionHealthConfig) -> f64 {
+        let threshold_us = config.rtt_threshold.as_micros() as f64;
+        let rtt_us = f64::from_bits(self.rtt_us.load(Ordering::Relaxed));
+        let rtt_factor = if rtt_us > threshold_us {
+            threshold_us / rtt_us
+        } else {
+            1.0
+        };
+        (1.0 - f64::from_bits(self.error_rate.load(Ordering::Relaxed))) * rtt_factor
+    }
+
+    fn circuit(&self) -> CircuitState {
+        CircuitState::from_u64(self.circuit.load(Ordering::Relaxed))
+    }
+}
+
+/// Folds `sample` into the moving average stored in `average`, or starts the average over
+/// from it if `start_over` returns true for the current one.
+fn update_average(average: &AtomicU64, sample: f64, start_over: impl Fn(f64) -> bool) {
+    let _ = average.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |bits| {
+        let current = f64::from_bits(bits);
+        let updated = if start_over(current) {
+            sample
+        } else {
+            current + EWMA_ALPHA * (sample - current)
+        };
+        Some(updated.to_bits())
+    });
+}
+
+#[derive(Debug)]
+pub struct ConnectionHealth {
+    config: ConnectionHealthConfig,
+    peers: RwLock<HashMap<SocketAddr, PeerHealth>>,
+    // Reference point of the times stored in the circuit states.
+    epoch: Instant,
+    pub reconnects: AtomicU64,
+    pub circuit_breaker_trips: AtomicU64,
+    pub circuit_breaker_rejections: AtomicU64,
+}
+
+impl Default for ConnectionHealth {
+    fn default() -> Self {
+        Self::new(ConnectionHealthConfig::default())
+    }
+}
+
+impl ConnectionHealth {
+    pub fn new(config: ConnectionHealthConfig) -> Self {
+        Self {
+            config,
+            peers: RwLock::default(),
+            epoch: Instant::now(),
+            reconnects: AtomicU64::default(),
+            circuit_breaker_trips: AtomicU64::default(),
+            circuit_breaker_rejections: AtomicU64::default(),
+        }
+    }
+
+    pub fn config(&self) -> &ConnectionHealthConfig {
+        &self.config
+    }
+
+    /// Record a successful send to `addr`, along with the round trip time of the connection
+    /// used if known.
+    pub fn record_success(&self, addr: SocketAddr, rtt: Option<Duration>) {
+        self.with_peer(addr, |peer| {
+            peer.record(false, rtt);
+            peer.consecutive_failures.store(0, Ordering::Relaxed);
+            peer.circuit.store(CIRCUIT_CLOSED, Ordering::Relaxed);
+        })
+    }
+
+    /// Record a failed send (or connection attempt) to `addr`.
+    pub fn record_failure(&self, addr: SocketAddr) {
+        let until_us = self.now_us().saturating_add(self.cooldown_us());
+        let open = CircuitState::Open { until_us }.to_u64();
+        let tripped = self.with_peer(addr, |peer| {
+            peer.record(true, None);
+            let consecutive_failures = peer
+                .consecutive_failures
+                .fetch_add(1, Ordering::Relaxed)
+                .saturating_add(1);
+            peer.circuit
+                .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |state| {
+                    let trip = match CircuitState::from_u64(state) {
+                        CircuitState::Closed => {
+                            consecutive_failures >= self.config.failure_threshold
+                        }
+                        CircuitState::HalfOpen { .. } => true,
+                        CircuitState::Open { .. } => false,
+                    };
+                    trip.then_some(open)
+                })
+                .is_ok()
+        });
+        if tripped {
+            self.circuit_breaker_trips.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Returns false while the circuit breaker of `addr` is open. Once the cooldown has
+    /// elapsed a single probe is let through; its outcome decides whether the circuit closes
+    /// again or re-opens.
+    pub fn is_peer_available(&self, addr: &SocketAddr) -> bool {
+        let peers = self.peers.read().unwrap();
+        let Some(peer) = peers.get(addr) else {
+            return true;
+        };
+        let now_us = self.now_us();
+        let state = peer.circuit.load(Ordering::Relaxed);
+        let probe = match CircuitState::from_u64(state) {
+            CircuitState::Closed => return true,
+            CircuitState::Open { until_us } => now_us >= until_us,
+            // The outcome of the previous probe never got reported, let another one through.
+            CircuitState::HalfOpen { since_us } => {
+                now_us.saturating_sub(since_us) >= self.cooldown_us()
+            }
+        };
+        // Of the callers racing for the probe, only the one which moves the circuit on gets it.
+        let half_open = CircuitState::HalfOpen { since_us: now_us }.to_u64();
+        let available = probe
+            && peer
+                .circuit
+                .compare_exchange(state, half_open, Ordering::Relaxed, Ordering::Relaxed)
+                .is_ok();
+        if !available {
+            self.circuit_breaker_rejections
+                .fetch_add(1, Ordering::Relaxed);
+        }
+        available
+    }
+
+    /// Returns the health score of `addr` in `[0, 1]`, or None if not enough samples have been
+    /// recorded for the peer yet.
+    pub fn score(&self, addr: &SocketAddr) -> Option<f64> {
+        let peers = self.peers.read().unwrap();
+        peers
+            .get(addr)
+            .filter(|peer| peer.samples.load(Ordering::Relaxed) >= self.config.min_samples)
+            .map(|peer| peer.score(&self.config))
+    }
+
+    /// Returns true if the connections to `addr` should be re-created because the peer scores
+    /// below the configured minimum. The samples of the peer are reset so that the new
+    /// connections start with a clean slate; the circuit breaker state is kept.
+    pub fn take_reconnect(&self, addr: &SocketAddr) -> bool {
+        let peers = self.peers.read().unwrap();
+        let Some(peer) = peers.get(addr) else {
+            return false;
+        };
+        let samples = peer.samples.load(Ordering::Relaxed);
+        if samples < self.config.min_samples || peer.score(&self.config) >= self.config.min_score {
+            return false;
+        }
+        // Only the caller which resets the samples re-creates the connections.
+        if peer
+            .samples
+            .compare_exchange(samples, 0, Ordering::Relaxed, Ordering::Relaxed)
+            .is_err()
+        {
+            return false;
+        }
+        peer.rtt_us.store(0.0f64.to_bits(), Ordering::Relaxed);
+        peer.error_rate.store(0.0f64.to_bits(), Ordering::Relaxed);
+        self.reconnects.fetch_add(1, Ordering::Relaxed);
+        true
+    }
+
+    /// Number of peers whose circuit breaker is currently not closed.
+    pub fn num_open_circuits(&self) -> usize {
+        let peers = self.peers.read().unwrap();
+        peers
+            .values()
+            .filter(|peer| peer.circuit() != CircuitState::Closed)
+            .count()
+    }
+
+    /// Runs `f` on the health of `addr`, which only takes the write lock of the peers map if
+    /// the peer has not been seen before.
+    fn with_peer<R>(&self, addr: SocketAddr, f: impl FnOnce(&PeerHealth) -> R) -> R {
+        if let Some(peer) = self.peers.read().unwrap().get(&addr) {
+            return f(peer);
+        }
+        let mut peers = self.peers.write().unwrap();
+        if peers.len() >= MAX_TRACKED_PEERS && !peers.contains_key(&addr) {
+            // Healthy peers carry no state worth keeping, drop them to make room.
+            peers.retain(|_, peer| peer.circuit() != CircuitState::Closed);
+            if peers.len() >= MAX_TRACKED_PEERS {
+                peers.clear();
+            }
+        }
+        f(peers.entry(addr).or_default())
+    }
+
+    fn now_us(&self) -> u64 {
+        self.epoch.elapsed().as_micros() as u64
+    }
+
+    fn cooldown_us(&self) -> u64 {
+        self.config.circuit_breaker_cooldown.as_micros() as u64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        std::net::{IpAddr, Ipv4Addr},
+    };
+
+    fn test_addr(port: u16) -> SocketAddr {
+        SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), port)
+    }
+
+    #[test]
+    fn test_score_and_reconnect() {
+        let health = ConnectionHealth::new(ConnectionHealthConfig {
+            min_samples: 4,
+            failure_threshold: u32::MAX,
+            ..ConnectionHealthConfig::default()
+        });
+        let addr = test_addr(8000);
+        assert_eq!(health.score(&addr), None);
+
+        for _ in 0..4 {
+            health.record_success(addr, Some(Duration::from_millis(10)));
+        }
+        assert_eq!(health.score(&addr), Some(1.0));
+        assert!(!health.take_reconnect(&addr));
+
+        // A slow peer is penalized proportionally to how far above the threshold it is.
+        let slow = test_addr(8001);
+        for _ in 0..4 {
+            health.record_success(slow, Some(Duration::from_secs(2)));
+        }
+        assert_eq!(health.score(&slow), Some(0.25));
+
+        // Errors drag the score down until the connections get re-created.
+        for _ in 0..4 {
+            health.record_failure(addr);
+        }
+        assert!(health.score(&addr).unwrap() < 0.5);
+        assert!(health.take_reconnect(&addr));
+        assert_eq!(health.reconnects.load(Ordering::Relaxed), 1);
+        // Samples are reset after a reconnect.
+        assert_eq!(health.score(&addr), None);
+        assert!(!health.take_reconnect(&addr));
+    }
+
+    #[test]
+    fn test_circuit_breaker() {
+        let health = ConnectionHealth::new(ConnectionHealthConfig {
+            failure_threshold: 3,
+            circuit_breaker_cooldown: Duration::ZERO,
+            ..ConnectionHealthConfig::default()
+        });
+        let addr = test_addr(8000);
+        assert!(health.is_peer_available(&addr));
+
+        for _ in 0..2 {
+            health.record_failure(addr);
+        }
+        assert!(health.is_peer_available(&addr));
+        assert_eq!(health.num_open_circuits(), 0);
+
+        health.record_failure(addr);
+        assert_eq!(health.circuit_breaker_trips.load(Ordering::Relaxed), 1);
+        assert_eq!(health.num_open_circuits(), 1);
+
+        // The cooldown has elapsed, so a probe is let through. A failing probe re-opens the
+        // circuit straight away.
+        assert!(health.is_peer_available(&addr));
+        health.record_failure(addr);
+        assert_eq!(health.circuit_breaker_trips.load(Ordering::Relaxed), 2);
+
+        // A successful probe closes it.
+        assert!(health.is_peer_available(&addr));
+        health.record_success(addr, None);
+        assert_eq!(health.num_open_circuits(), 0);
+        assert!(health.is_peer_available(&addr));
+    }
+
+    #[test]
+    fn test_circuit_breaker_rejects_during_cooldown() {
+        let health = ConnectionHealth::new(ConnectionHealthConfig {
+            failure_threshold: 1,
+            circuit_breaker_cooldown: Duration::from_secs(3600),
+            ..ConnectionHealthConfig::default()
+        });
+        let addr = test_addr(8000);
+        health.record_failure(addr);
+        assert!(!health.is_peer_available(&addr));
+        assert!(!health.is_peer_available(&addr));
+        assert_eq!(health.circuit_breaker_rejections.load(Ordering::Relaxed), 2);
+        // Other peers are unaffected.
+        assert!(health.is_peer_available(&test_addr(8001)));
+    }
+
+    #[test]
+    fn test_circuit_breaker_single_probe() {
+        let health = ConnectionHealth::new(ConnectionHealthConfig {
+            failure_threshold: 1,
+            circuit_breaker_cooldown: Duration::from_millis(100),
+            ..ConnectionHealthConfig::default()
+        });
+        let addr = test_addr(8000);
+        health.record_failure(addr);
+        std::thread::sleep(Duration::from_millis(150));
+
+        // Once the cooldown has elapsed, only one of the concurrent callers gets the probe.
+        let num_probes = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..8)
+                .map(|_| scope.spawn(|| health.is_peer_available(&addr)))
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .filter(|available| *available)
+                .count()
+        });
+        assert_eq!(num_probes, 1);
+        assert_eq!(health.circuit_breaker_rejections.load(Ordering::Relaxed), 7);
+    }
+}
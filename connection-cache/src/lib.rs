@@ -3,6 +3,7 @@
 pub mod client_connection;
 pub mod connection_cache;
 pub mod connection_cache_stats;
+pub mod connection_health;
 pub mod nonblocking;
 
 #[macro_use]
@@ -27,7 +27,7 @@ use {
             qos_service::QosService,
             unprocessed_packet_batches::*,
             unprocessed_transaction_storage::{ThreadType, UnprocessedTransactionStorage},
-            BankingStage, BankingStageStats,
+            BankingStage, BankingStageStats, PrioGraphSchedulerConfig,
         },
         banking_trace::BankingTracer,
     },
@@ -115,7 +115,13 @@ fn bench_consume_buffered(bencher: &mut Bencher) {
     );
     let (s, _r) = unbounded();
     let committer = Committer::new(None, s, Arc::new(PrioritizationFeeCache::new(0u64)));
-    let consumer = Consumer::new(committer, recorder, QosService::new(1), None);
+    let consumer = Consumer::new(
+        committer,
+        recorder,
+        QosService::new(1),
+        None,
+        Arc::default(),
+    );
     // This tests the performance of buffering packets.
     // If the packet buffers are copied, performance will be poor.
     bencher.iter(move || {
@@ -202,6 +208,24 @@ fn bench_banking(
     tx_type: TransactionType,
     block_production_method: BlockProductionMethod,
     transaction_struct: TransactionStructure,
+) {
+    bench_banking_with_forwarding(
+        bencher,
+        tx_type,
+        block_production_method,
+        transaction_struct,
+        false,
+    );
+}
+
+// With forwarding enabled, buffered packets are kept around to be forwarded,
+// and transactions are cloned out of them instead of being moved.
+fn bench_banking_with_forwarding(
+    bencher: &mut Bencher,
+    tx_type: TransactionType,
+    block_production_method: BlockProductionMethod,
+    transaction_struct: TransactionStructure,
+    enable_forwarding: bool,
 ) {
     solana_logger::setup();
     let num_threads = BankingStage::num_threads() as usize;
@@ -319,7 +343,10 @@ fn bench_banking(
         Arc::new(ConnectionCache::new("connection_cache_test")),
         bank_forks,
         &Arc::new(PrioritizationFeeCache::new(0u64)),
-        false,
+        enable_forwarding,
+        None,
+        PrioGraphSchedulerConfig::default(),
+        Arc::default(),
     );
 
     let chunk_len = verified.len() / CHUNKS;
@@ -420,6 +447,28 @@ fn bench_banking_stage_multi_programs_with_voting(bencher: &mut Bencher) {
     );
 }
 
+#[bench]
+fn bench_banking_stage_multi_accounts_forwarding(bencher: &mut Bencher) {
+    bench_banking_with_forwarding(
+        bencher,
+        TransactionType::Accounts,
+        BlockProductionMethod::CentralScheduler,
+        TransactionStructure::Sdk,
+        true,
+    );
+}
+
+#[bench]
+fn bench_banking_stage_multi_programs_forwarding(bencher: &mut Bencher) {
+    bench_banking_with_forwarding(
+        bencher,
+        TransactionType::Programs,
+        BlockProductionMethod::CentralScheduler,
+        TransactionStructure::Sdk,
+        true,
+    );
+}
+
 #[bench]
 fn bench_banking_stage_multi_accounts_view(bencher: &mut Bencher) {
     bench_banking(
@@ -0,0 +1,93 @@
+#![allow(clippy::arithmetic_side_effects)]
+#![feature(test)]
+
+use {
+    solana_core::banking_stage::{BenchPacketHandling, TransactionStateContainerBench},
+    solana_ledger::genesis_utils::{create_genesis_config, GenesisConfigInfo},
+    solana_perf::packet::Packet,
+    solana_runtime::bank::Bank,
+    solana_sdk::{signature::Keypair, signer::Signer, system_transaction},
+    std::{
+        alloc::{GlobalAlloc, Layout, System},
+        sync::atomic::{AtomicU64, Ordering},
+    },
+    test::Bencher,
+};
+
+extern crate test;
+
+const NUM_TRANSACTIONS: usize = 1024;
+
+/// Counts the allocations made by the benchmarks, on top of the system allocator.
+struct CountingAllocator;
+
+static NUM_ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        NUM_ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        NUM_ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn bench_container(bencher: &mut Bencher, packet_handling: BenchPacketHandling) {
+    let GenesisConfigInfo {
+        genesis_config,
+        mint_keypair,
+        ..
+    } = create_genesis_config(u64::MAX);
+    let bank = Bank::new_for_benches(&genesis_config);
+    let packets: Vec<_> = (0..NUM_TRANSACTIONS)
+        .map(|_| {
+            let transaction = system_transaction::transfer(
+                &mint_keypair,
+                &Keypair::new().pubkey(),
+                1,
+                genesis_config.hash(),
+            );
+            Packet::from_data(None, transaction).unwrap()
+        })
+        .collect();
+    let mut container = TransactionStateContainerBench::with_capacity(NUM_TRANSACTIONS);
+    // Warm up, so that the arena holds the packets of a previous round.
+    container.buffer_and_drain(&bank, &packets, packet_handling);
+
+    let mut num_rounds = 0u64;
+    let num_allocations_before = NUM_ALLOCATIONS.load(Ordering::Relaxed);
+    bencher.iter(|| {
+        container.buffer_and_drain(&bank, &packets, packet_handling);
+        num_rounds += 1;
+    });
+    let num_allocations = NUM_ALLOCATIONS.load(Ordering::Relaxed) - num_allocations_before;
+    eprintln!(
+        "{packet_handling:?}: {:.1} allocations per transaction",
+        num_allocations as f64 / (num_rounds.max(1) * NUM_TRANSACTIONS as u64) as f64
+    );
+}
+
+#[bench]
+fn bench_container_drop_packets(bencher: &mut Bencher) {
+    bench_container(bencher, BenchPacketHandling::Drop);
+}
+
+#[bench]
+fn bench_container_forward_packets_arena(bencher: &mut Bencher) {
+    bench_container(bencher, BenchPacketHandling::Arena);
+}
+
+#[bench]
+fn bench_container_forward_packets_allocate(bencher: &mut Bencher) {
+    bench_container(bencher, BenchPacketHandling::Allocate);
+}
@@ -0,0 +1,908 @@
+//! The `accounts_replication_service` module streams the accounts changed in
+//! each frozen bank to replica nodes over TCP, and applies that stream on the
+//! replica side.
+//!
+//! A replica starts from a snapshot of the validator it follows and keeps a
+//! read-only copy of its accounts, for serving RPC, without replaying the
+//! ledger. Every message is signed with the identity of the validator, which
+//! the replica is configured with, so that the bank hash and capitalization
+//! each delta is checked against come from the validator itself. A delta
+//! failing the check is rejected, along with the slots descending from it.
+//!
+//! The validator keeps the deltas of its unrooted banks, and of its most recent
+//! rooted ones, so that a replica which connects or reconnects is sent the
+//! slots it is missing since its root. Replicas further behind have to restart
+//! from a newer snapshot.
+
+use {
+    bincode::Options,
+    crossbeam_channel::{bounded, Receiver, RecvTimeoutError, Sender, TrySendError},
+    serde::{Deserialize, Serialize},
+    solana_gossip::cluster_info::ClusterInfo,
+    solana_runtime::{
+        accounts_background_service::AbsRequestSender,
+        bank::{accounts_delta::AccountsDelta, Bank},
+        bank_forks::BankForks,
+        commitment::BlockCommitmentCache,
+    },
+    solana_sdk::{
+        clock::Slot,
+        pubkey::Pubkey,
+        signature::{Keypair, Signature, Signer},
+    },
+    std::{
+        collections::{BTreeMap, HashSet},
+        io::{self, BufRead, BufReader, BufWriter, Read, Write},
+        net::{Shutdown, SocketAddr, TcpListener, TcpStream},
+        sync::{
+            atomic::{AtomicBool, AtomicUsize, Ordering},
+            Arc, Mutex, RwLock,
+        },
+        thread::{self, sleep, Builder, JoinHandle},
+        time::{Duration, Instant},
+    },
+    thiserror::Error,
+};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+// Replicas whose connection does not take any data for this long are
+// disconnected
+const WRITE_TIMEOUT: Duration = Duration::from_secs(5);
+// Messages queued for a replica on top of those it is sent to catch up. Each
+// replica is written to by its own thread, and one falling further behind is
+// disconnected rather than holding on to ever more messages
+const MAX_QUEUED_MESSAGES: usize = 256;
+// Bounds the threads serving replicas, including those waiting for the hello
+// of a new connection
+const MAX_REPLICA_CONNECTIONS: usize = 64;
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const READ_TIMEOUT: Duration = Duration::from_secs(1);
+// A validator freezes a bank every slot, so a stream that stays idle this long
+// is assumed to be broken and is reconnected
+const IDLE_TIMEOUT: Duration = Duration::from_secs(30);
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(1);
+// Bounds what a replica reads before checking the signature
+const MAX_MESSAGE_SIZE: u64 = 1 << 30;
+
+/// Number of rooted slots whose deltas are kept for replicas to catch up by default
+pub const DEFAULT_ACCOUNTS_REPLICATION_CATCH_UP_SLOTS: usize = 64;
+
+#[derive(Clone, Debug)]
+pub struct AccountsReplicationConfig {
+    /// Address replicas connect to
+    pub addr: SocketAddr,
+    /// Number of rooted slots whose deltas are kept for replicas to catch up
+    pub catch_up_slots: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct AccountsReplicaConfig {
+    /// Address of the accounts replication service to follow
+    pub source: SocketAddr,
+    /// Identity of the validator running it, which signs the stream
+    pub source_identity: Pubkey,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub enum ReplicationMessage {
+    /// The accounts changed in a newly frozen bank
+    Delta(AccountsDelta),
+    /// The validator set a new root
+    Root(Slot),
+    /// The root of the replica is older than this one, the oldest the
+    /// validator can send the slots following
+    TooFarBehind(Slot),
+}
+
+/// Sent by a replica once connected, so that it is sent the slots it is missing
+#[derive(Debug, Deserialize, Serialize)]
+struct ReplicaHello {
+    root: Slot,
+}
+
+/// A message signed by the identity of the validator
+#[derive(Debug, Deserialize, Serialize)]
+struct SignedReplicationMessage {
+    signature: Signature,
+    /// The serialized ReplicationMessage
+    message: Vec<u8>,
+}
+
+#[derive(Debug, Error)]
+enum ReplicaError {
+    #[error("io error: {0}")]
+    Io(#[from] io::Error),
+
+    #[error("serialization error: {0}")]
+    Serialize(#[from] bincode::Error),
+
+    #[error("message not signed by {0}")]
+    InvalidSignature(Pubkey),
+
+    #[error("root {root} is older than {oldest_root}, restart from a newer snapshot")]
+    TooFarBehind { root: Slot, oldest_root: Slot },
+}
+
+impl SignedReplicationMessage {
+    fn new(message: &ReplicationMessage, keypair: &Keypair) -> Self {
+        let message = bincode::serialize(message).unwrap();
+        Self {
+            signature: keypair.sign_message(&message),
+            message,
+        }
+    }
+
+    fn serialize(&self) -> Arc<Vec<u8>> {
+        Arc::new(bincode::serialize(self).unwrap())
+    }
+
+    fn deserialize_from<R: Read>(reader: R) -> bincode::Result<Self> {
+        bincode::options()
+            .with_limit(MAX_MESSAGE_SIZE)
+            .with_fixint_encoding()
+            .allow_trailing_bytes()
+            .deserialize_from(reader)
+    }
+
+    fn verify(self, identity: &Pubkey) -> Result<ReplicationMessage, ReplicaError> {
+        if !self.signature.verify(identity.as_ref(), &self.message) {
+            return Err(ReplicaError::InvalidSignature(*identity));
+        }
+        Ok(bincode::deserialize(&self.message)?)
+    }
+}
+
+/// The signed deltas of recently frozen banks
+struct RecentDeltas {
+    /// Serialized delta and parent slot, by slot
+    deltas: BTreeMap<Slot, (Slot, Arc<Vec<u8>>)>,
+    root: Slot,
+    /// Replicas with an older root are too far behind to catch up
+    oldest_root: Slot,
+    catch_up_slots: usize,
+}
+
+impl RecentDeltas {
+    fn new(root: Slot, catch_up_slots: usize) -> Self {
+        Self {
+            deltas: BTreeMap::new(),
+            root,
+            oldest_root: root,
+            catch_up_slots,
+        }
+    }
+
+    fn contains(&self, slot: Slot) -> bool {
+        self.deltas.contains_key(&slot)
+    }
+
+    fn insert(&mut self, slot: Slot, parent_slot: Slot, delta: Arc<Vec<u8>>) {
+        self.deltas.insert(slot, (parent_slot, delta));
+    }
+
+    /// Drops the deltas of the slots older than `root` which are not its
+    /// ancestors, and the oldest ancestors beyond `catch_up_slots`
+    fn set_root(&mut self, root: Slot) {
+        self.root = root;
+        self.oldest_root = root;
+        let mut ancestors = HashSet::new();
+        let mut slot = root;
+        while let Some((parent_slot, _)) = self.deltas.get(&slot) {
+            if ancestors.len() == self.catch_up_slots {
+                break;
+            }
+            ancestors.insert(slot);
+            self.oldest_root = *parent_slot;
+            slot = *parent_slot;
+        }
+        self.deltas
+            .retain(|slot, _| *slot > root || ancestors.contains(slot));
+    }
+
+    /// The deltas of the slots following `root`, in slot order
+    fn since(&self, root: Slot) -> impl Iterator<Item = (&Slot, &Arc<Vec<u8>>)> {
+        self.deltas
+            .range(root.saturating_add(1)..)
+            .map(|(slot, (_, delta))| (slot, delta))
+    }
+}
+
+/// A connected replica, as seen by the thread publishing the deltas
+struct Replica {
+    /// Queue of the messages the thread of the replica writes to it
+    sender: Sender<Arc<Vec<u8>>>,
+    /// The connection to the replica, shut down to disconnect it
+    stream: TcpStream,
+    /// Root of the replica when it connected
+    root: Slot,
+    /// Slots whose delta was sent to the replica
+    sent_slots: HashSet<Slot>,
+}
+
+impl Replica {
+    /// Queues `message` for the replica. Returns false if the replica was
+    /// disconnected, because its queue is full or its thread failed to write.
+    fn send(&self, message: Arc<Vec<u8>>) -> bool {
+        match self.sender.try_send(message) {
+            Ok(()) => true,
+            Err(TrySendError::Full(_)) => {
+                let addr = self.stream.peer_addr();
+                warn!("disconnecting accounts replica {addr:?}: too far behind the stream");
+                let _ = self.stream.shutdown(Shutdown::Both);
+                false
+            }
+            // The thread of the replica logged why it stopped
+            Err(TrySendError::Disconnected(_)) => false,
+        }
+    }
+}
+
+/// Streams the deltas of the validator's frozen banks to connected replicas
+pub struct AccountsReplicationService {
+    num_replicas: Arc<AtomicUsize>,
+    thread_hdls: Vec<JoinHandle<()>>,
+}
+
+impl AccountsReplicationService {
+    pub fn new(
+        listener: TcpListener,
+        catch_up_slots: usize,
+        bank_forks: Arc<RwLock<BankForks>>,
+        cluster_info: Arc<ClusterInfo>,
+        exit: Arc<AtomicBool>,
+    ) -> io::Result<Self> {
+        listener.set_nonblocking(true)?;
+        let new_replicas = Arc::<Mutex<Vec<Replica>>>::default();
+        let num_replicas = Arc::<AtomicUsize>::default();
+
+        let accept_thread_hdl = {
+            let new_replicas = new_replicas.clone();
+            let exit = exit.clone();
+            let queue_capacity = catch_up_slots.saturating_add(MAX_QUEUED_MESSAGES);
+            Builder::new()
+                .name("solAcctReplAcpt".to_string())
+                .spawn(move || {
+                    Self::accept_replicas(listener, queue_capacity, &new_replicas, &exit)
+                })
+                .unwrap()
+        };
+        let publish_thread_hdl = {
+            let num_replicas = num_replicas.clone();
+            Builder::new()
+                .name("solAcctReplPub".to_string())
+                .spawn(move || {
+                    info!("AccountsReplicationService has started");
+                    Self::run(
+                        catch_up_slots,
+                        &bank_forks,
+                        &cluster_info,
+                        &new_replicas,
+                        &num_replicas,
+                        &exit,
+                    );
+                    info!("AccountsReplicationService has stopped");
+                })
+                .unwrap()
+        };
+
+        Ok(Self {
+            num_replicas,
+            thread_hdls: vec![accept_thread_hdl, publish_thread_hdl],
+        })
+    }
+
+    /// Returns the number of replicas currently connected
+    pub fn num_replicas(&self) -> usize {
+        self.num_replicas.load(Ordering::Relaxed)
+    }
+
+    fn accept_replicas(
+        listener: TcpListener,
+        queue_capacity: usize,
+        new_replicas: &Arc<Mutex<Vec<Replica>>>,
+        exit: &Arc<AtomicBool>,
+    ) {
+        let mut replica_thread_hdls = Vec::<JoinHandle<()>>::new();
+        while !exit.load(Ordering::Relaxed) {
+            let (finished, running) = std::mem::take(&mut replica_thread_hdls)
+                .into_iter()
+                .partition::<Vec<_>, _>(JoinHandle::is_finished);
+            replica_thread_hdls = running;
+            finished.into_iter().for_each(|thread_hdl| {
+                let _ = thread_hdl.join();
+            });
+
+            let (stream, addr) = match listener.accept() {
+                Ok(accepted) => accepted,
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => {
+                    sleep(POLL_INTERVAL);
+                    continue;
+                }
+                Err(err) => {
+                    warn!("failed to accept accounts replica: {err}");
+                    continue;
+                }
+            };
+            if replica_thread_hdls.len() >= MAX_REPLICA_CONNECTIONS {
+                warn!("refusing accounts replica from {addr}: too many connections");
+                continue;
+            }
+            // The hello is read by the thread of the replica, so that a
+            // connection which never sends it does not hold up the others
+            let new_replicas = new_replicas.clone();
+            let exit = exit.clone();
+            let thread_hdl = Builder::new()
+                .name("solAcctReplConn".to_string())
+                .spawn(move || {
+                    Self::serve_replica(stream, addr, queue_capacity, &new_replicas, &exit)
+                });
+            match thread_hdl {
+                Ok(thread_hdl) => replica_thread_hdls.push(thread_hdl),
+                Err(err) => warn!("failed to spawn accounts replica thread for {addr}: {err}"),
+            }
+        }
+        for thread_hdl in replica_thread_hdls {
+            let _ = thread_hdl.join();
+        }
+    }
+
+    /// Reads the hello of a newly connected replica, hands the replica to the
+    /// publishing thread, then writes the messages queued for it until it is
+    /// disconnected
+    fn serve_replica(
+        stream: TcpStream,
+        addr: SocketAddr,
+        queue_capacity: usize,
+        new_replicas: &Mutex<Vec<Replica>>,
+        exit: &AtomicBool,
+    ) {
+        let hello = stream
+            .set_nonblocking(false)
+            .and_then(|()| stream.set_read_timeout(Some(CONNECT_TIMEOUT)))
+            .and_then(|()| stream.set_write_timeout(Some(WRITE_TIMEOUT)))
+            .map_err(bincode::Error::from)
+            .and_then(|()| bincode::deserialize_from::<_, ReplicaHello>(&stream));
+        let replica = hello.and_then(|ReplicaHello { root }| {
+            let (sender, receiver) = bounded(queue_capacity);
+            let replica = Replica {
+                sender,
+                stream: stream.try_clone()?,
+                root,
+                sent_slots: HashSet::new(),
+            };
+            Ok((replica, receiver))
+        });
+        let receiver = match replica {
+            Ok((replica, receiver)) => {
+                info!(
+                    "accounts replica connected from {addr} with root {}",
+                    replica.root
+                );
+                new_replicas.lock().unwrap().push(replica);
+                receiver
+            }
+            Err(err) => {
+                warn!("failed to set up accounts replica stream from {addr}: {err}");
+                return;
+            }
+        };
+        if let Err(err) = Self::write_messages(BufWriter::new(stream), &receiver, exit) {
+            warn!("disconnecting accounts replica {addr}: {err}");
+        }
+    }
+
+    /// Writes the messages received from `receiver` until the publishing
+    /// thread drops the replica
+    fn write_messages(
+        mut writer: BufWriter<TcpStream>,
+        receiver: &Receiver<Arc<Vec<u8>>>,
+        exit: &AtomicBool,
+    ) -> io::Result<()> {
+        while !exit.load(Ordering::Relaxed) {
+            let message = match receiver.recv_timeout(POLL_INTERVAL) {
+                Ok(message) => message,
+                Err(RecvTimeoutError::Timeout) => continue,
+                Err(RecvTimeoutError::Disconnected) => break,
+            };
+            writer.write_all(&message)?;
+            // Messages queued together are flushed together
+            if receiver.is_empty() {
+                writer.flush()?;
+            }
+        }
+        Ok(())
+    }
+
+    fn run(
+        catch_up_slots: usize,
+        bank_forks: &RwLock<BankForks>,
+        cluster_info: &ClusterInfo,
+        new_replicas: &Mutex<Vec<Replica>>,
+        num_replicas: &AtomicUsize,
+        exit: &AtomicBool,
+    ) {
+        // Replicas start from a snapshot, so nothing at or below the current
+        // root needs to be sent
+        let mut recent_deltas =
+            RecentDeltas::new(bank_forks.read().unwrap().root(), catch_up_slots);
+        let mut replicas = Vec::<Replica>::new();
+
+        while !exit.load(Ordering::Relaxed) {
+            let (root, mut banks) = {
+                let bank_forks = bank_forks.read().unwrap();
+                let root = bank_forks.root();
+                let banks: Vec<_> = bank_forks
+                    .frozen_banks()
+                    .into_values()
+                    .filter(|bank| {
+                        bank.slot() > recent_deltas.root && !recent_deltas.contains(bank.slot())
+                    })
+                    .collect();
+                (root, banks)
+            };
+            // Parents are sent before their children
+            banks.sort_unstable_by_key(|bank| bank.slot());
+            // Deltas are kept whether or not replicas are connected, so that
+            // they can catch up once they are
+            for bank in banks {
+                if let Some(delta) = bank.accounts_delta() {
+                    let message = ReplicationMessage::Delta(delta);
+                    let message = SignedReplicationMessage::new(&message, &cluster_info.keypair());
+                    recent_deltas.insert(bank.slot(), bank.parent_slot(), message.serialize());
+                }
+            }
+            let new_root = root != recent_deltas.root;
+            if new_root {
+                recent_deltas.set_root(root);
+            }
+            let root_message = SignedReplicationMessage::new(
+                &ReplicationMessage::Root(root),
+                &cluster_info.keypair(),
+            )
+            .serialize();
+
+            for mut replica in std::mem::take(&mut *new_replicas.lock().unwrap()) {
+                if replica.root < recent_deltas.oldest_root {
+                    let message = ReplicationMessage::TooFarBehind(recent_deltas.oldest_root);
+                    let message = SignedReplicationMessage::new(&message, &cluster_info.keypair());
+                    // Dropping the replica once the message is queued ends its
+                    // thread after writing it
+                    replica.send(message.serialize());
+                    warn!(
+                        "disconnecting accounts replica with root {}, older than {}",
+                        replica.root, recent_deltas.oldest_root
+                    );
+                    continue;
+                }
+                // Newly connected replicas are sent the current root once
+                // caught up
+                if Self::send_deltas(&mut replica, &recent_deltas, Some(&root_message)) {
+                    replicas.push(replica);
+                }
+            }
+            replicas.retain_mut(|replica| {
+                Self::send_deltas(replica, &recent_deltas, new_root.then_some(&root_message))
+            });
+            num_replicas.store(replicas.len(), Ordering::Relaxed);
+            sleep(POLL_INTERVAL);
+        }
+    }
+
+    /// Queues the deltas `replica` has not been sent yet, then the root
+    /// message if any. Returns false if the replica was disconnected.
+    fn send_deltas(
+        replica: &mut Replica,
+        recent_deltas: &RecentDeltas,
+        root_message: Option<&Arc<Vec<u8>>>,
+    ) -> bool {
+        let mut messages = vec![];
+        for (slot, delta) in recent_deltas.since(replica.root) {
+            if replica.sent_slots.insert(*slot) {
+                messages.push(delta);
+            }
+        }
+        messages.extend(root_message);
+        if messages.is_empty() {
+            return true;
+        }
+        let num_bytes: usize = messages.iter().map(|message| message.len()).sum();
+        datapoint_info!(
+            "accounts_replication_service-send",
+            ("num_messages", messages.len(), i64),
+            ("num_bytes", num_bytes, i64),
+        );
+        replica
+            .sent_slots
+            .retain(|slot| *slot > recent_deltas.root || recent_deltas.contains(*slot));
+        messages
+            .into_iter()
+            .all(|message| replica.send(message.clone()))
+    }
+
+    pub fn join(self) -> thread::Result<()> {
+        for thread_hdl in self.thread_hdls {
+            thread_hdl.join()?;
+        }
+        Ok(())
+    }
+}
+
+/// Follows the stream of an [`AccountsReplicationService`], adding a bank to
+/// the replica's bank forks for every delta that passes the integrity check
+pub struct AccountsReplicaService {
+    thread_hdl: JoinHandle<()>,
+}
+
+impl AccountsReplicaService {
+    pub fn new(
+        config: AccountsReplicaConfig,
+        bank_forks: Arc<RwLock<BankForks>>,
+        block_commitment_cache: Arc<RwLock<BlockCommitmentCache>>,
+        accounts_background_request_sender: AbsRequestSender,
+        exit: Arc<AtomicBool>,
+    ) -> Self {
+        let thread_hdl = Builder::new()
+            .name("solAcctReplica".to_string())
+            .spawn(move || {
+                info!("AccountsReplicaService has started");
+                let source = config.source;
+                while !exit.load(Ordering::Relaxed) {
+                    if let Err(err) = Self::follow(
+                        &config,
+                        &bank_forks,
+                        &block_commitment_cache,
+                        &accounts_background_request_sender,
+                        &exit,
+                    ) {
+                        match err {
+                            ReplicaError::TooFarBehind { .. } => {
+                                error!("accounts replication stream from {source} failed: {err}")
+                            }
+                            _ => warn!("accounts replication stream from {source} failed: {err}"),
+                        }
+                        sleep(RECONNECT_INTERVAL);
+                    }
+                }
+                info!("AccountsReplicaService has stopped");
+            })
+            .unwrap();
+
+        Self { thread_hdl }
+    }
+
+    fn follow(
+        config: &AccountsReplicaConfig,
+        bank_forks: &RwLock<BankForks>,
+        block_commitment_cache: &RwLock<BlockCommitmentCache>,
+        accounts_background_request_sender: &AbsRequestSender,
+        exit: &AtomicBool,
+    ) -> Result<(), ReplicaError> {
+        let stream = TcpStream::connect_timeout(&config.source, CONNECT_TIMEOUT)?;
+        stream.set_read_timeout(Some(READ_TIMEOUT))?;
+        let root = bank_forks.read().unwrap().root();
+        bincode::serialize_into(&stream, &ReplicaHello { root })?;
+        let mut reader = BufReader::new(stream);
+        let mut last_message = Instant::now();
+        while !exit.load(Ordering::Relaxed) {
+            // Wait for the next message without consuming any of it, so that
+            // the exit flag is checked between messages
+            match reader.fill_buf() {
+                Ok([]) => return Err(io::Error::from(io::ErrorKind::UnexpectedEof).into()),
+                Ok(_) => {
+                    let message = SignedReplicationMessage::deserialize_from(&mut reader)?
+                        .verify(&config.source_identity)?;
+                    Self::process_message(
+                        message,
+                        bank_forks,
+                        block_commitment_cache,
+                        accounts_background_request_sender,
+                    )?;
+                    last_message = Instant::now();
+                }
+                Err(err)
+                    if matches!(
+                        err.kind(),
+                        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+                    ) =>
+                {
+                    if last_message.elapsed() > IDLE_TIMEOUT {
+                        return Err(io::Error::from(io::ErrorKind::TimedOut).into());
+                    }
+                }
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => (),
+                Err(err) => return Err(err.into()),
+            }
+        }
+        Ok(())
+    }
+
+    fn process_message(
+        message: ReplicationMessage,
+        bank_forks: &RwLock<BankForks>,
+        block_commitment_cache: &RwLock<BlockCommitmentCache>,
+        accounts_background_request_sender: &AbsRequestSender,
+    ) -> Result<(), ReplicaError> {
+        match message {
+            ReplicationMessage::Delta(delta) => {
+                let parent = {
+                    let bank_forks = bank_forks.read().unwrap();
+                    if delta.slot <= bank_forks.root() || bank_forks.get(delta.slot).is_some() {
+                        return Ok(());
+                    }
+                    bank_forks.get(delta.parent_slot)
+                };
+                // The parent is missing if it was on a pruned fork or failed
+                // its own integrity check
+                let Some(parent) = parent else {
+                    debug!(
+                        "skipping accounts delta for slot {}, parent {} is missing",
+                        delta.slot, delta.parent_slot
+                    );
+                    return Ok(());
+                };
+                let bank = Bank::new_from_parent(parent, &delta.collector_id, delta.slot);
+                match bank.apply_accounts_delta(&delta) {
+                    Ok(()) => {
+                        bank_forks.write().unwrap().insert(bank);
+                        let mut block_commitment_cache = block_commitment_cache.write().unwrap();
+                        if delta.slot > block_commitment_cache.slot() {
+                            let root = block_commitment_cache.root();
+                            block_commitment_cache.initialize_slots(delta.slot, root);
+                        }
+                    }
+                    Err(err) => {
+                        error!("rejecting accounts delta: {err}");
+                        datapoint_error!(
+                            "accounts_replica_service-rejected_delta",
+                            ("slot", delta.slot, i64),
+                            ("error", err.to_string(), String),
+                        );
+                    }
+                }
+            }
+            ReplicationMessage::Root(root) => {
+                let mut bank_forks = bank_forks.write().unwrap();
+                if root <= bank_forks.root() || bank_forks.get(root).is_none() {
+                    return Ok(());
+                }
+                if let Err(err) =
+                    bank_forks.set_root(root, accounts_background_request_sender, None)
+                {
+                    error!("failed to set replica root to {root}: {err}");
+                    return Ok(());
+                }
+                // The replica has no votes to go by, so a slot rooted by the
+                // validator counts as finalized
+                let mut block_commitment_cache = block_commitment_cache.write().unwrap();
+                let slot = block_commitment_cache.slot().max(root);
+                let highest_confirmed_slot =
+                    block_commitment_cache.highest_confirmed_slot().max(root);
+                block_commitment_cache.initialize_slots(slot, root);
+                block_commitment_cache.set_highest_confirmed_slot(highest_confirmed_slot);
+                block_commitment_cache.set_highest_super_majority_root(root);
+            }
+            ReplicationMessage::TooFarBehind(oldest_root) => {
+                let root = bank_forks.read().unwrap().root();
+                return Err(ReplicaError::TooFarBehind { root, oldest_root });
+            }
+        }
+        Ok(())
+    }
+
+    pub fn join(self) -> thread::Result<()> {
+        self.thread_hdl.join()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*, crossbeam_channel::unbounded, solana_gossip::contact_info::ContactInfo,
+        solana_runtime::genesis_utils::create_genesis_config,
+        solana_sdk::native_token::LAMPORTS_PER_SOL, solana_streamer::socket::SocketAddrSpace,
+    };
+
+    fn wait_for<T>(mut f: impl FnMut() -> Option<T>) -> T {
+        let start = Instant::now();
+        loop {
+            if let Some(value) = f() {
+                break value;
+            }
+            assert!(start.elapsed() < Duration::from_secs(10));
+            sleep(POLL_INTERVAL);
+        }
+    }
+
+    #[test]
+    fn test_accounts_replication() {
+        let genesis_config_info = create_genesis_config(LAMPORTS_PER_SOL);
+        let genesis_config = &genesis_config_info.genesis_config;
+        let (bank0, bank_forks) = Bank::new_with_bank_forks_for_tests(genesis_config);
+        let (replica_bank0, replica_bank_forks) =
+            Bank::new_with_bank_forks_for_tests(genesis_config);
+        bank0.freeze();
+        replica_bank0.freeze();
+        assert_eq!(replica_bank0.hash(), bank0.hash());
+
+        let keypair = Arc::new(Keypair::new());
+        let identity = keypair.pubkey();
+        let contact_info = ContactInfo::new_localhost(&identity, 0);
+        let cluster_info = Arc::new(ClusterInfo::new(
+            contact_info,
+            keypair,
+            SocketAddrSpace::Unspecified,
+        ));
+        let exit = Arc::<AtomicBool>::default();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let source = listener.local_addr().unwrap();
+        let service = AccountsReplicationService::new(
+            listener,
+            DEFAULT_ACCOUNTS_REPLICATION_CATCH_UP_SLOTS,
+            bank_forks.clone(),
+            cluster_info,
+            exit.clone(),
+        )
+        .unwrap();
+
+        // Banks frozen before the replica connects are sent once it does
+        let recipient = Pubkey::new_unique();
+        let bank1 = Bank::new_from_parent(bank0, &Pubkey::new_unique(), 1);
+        bank1
+            .transfer(
+                LAMPORTS_PER_SOL / 2,
+                &genesis_config_info.mint_keypair,
+                &recipient,
+            )
+            .unwrap();
+        bank1.freeze();
+        let bank1 = bank_forks
+            .write()
+            .unwrap()
+            .insert(bank1)
+            .clone_without_scheduler();
+
+        // A connection which never sends its hello does not hold up the replica
+        let _silent_connection = TcpStream::connect(source).unwrap();
+        let (snapshot_request_sender, _snapshot_request_receiver) = unbounded();
+        let accounts_background_request_sender = AbsRequestSender::new(snapshot_request_sender);
+        let block_commitment_cache = Arc::<RwLock<BlockCommitmentCache>>::default();
+        let start = Instant::now();
+        let replica_service = AccountsReplicaService::new(
+            AccountsReplicaConfig {
+                source,
+                source_identity: identity,
+            },
+            replica_bank_forks.clone(),
+            block_commitment_cache.clone(),
+            accounts_background_request_sender.clone(),
+            exit.clone(),
+        );
+        wait_for(|| (service.num_replicas() == 1).then_some(()));
+        assert!(start.elapsed() < CONNECT_TIMEOUT);
+
+        let replica_bank1 = wait_for(|| replica_bank_forks.read().unwrap().get(1));
+        assert_eq!(replica_bank1.hash(), bank1.hash());
+        assert_eq!(
+            replica_bank1.get_account(&recipient),
+            bank1.get_account(&recipient)
+        );
+        assert_eq!(block_commitment_cache.read().unwrap().slot(), 1);
+
+        // Banks frozen once connected, and roots, follow
+        let bank2 = Bank::new_from_parent(bank1, &Pubkey::new_unique(), 2);
+        bank2.freeze();
+        let bank2 = bank_forks
+            .write()
+            .unwrap()
+            .insert(bank2)
+            .clone_without_scheduler();
+        bank_forks
+            .write()
+            .unwrap()
+            .set_root(2, &accounts_background_request_sender, None)
+            .unwrap();
+        wait_for(|| (replica_bank_forks.read().unwrap().root() == 2).then_some(()));
+        let replica_bank2 = replica_bank_forks.read().unwrap().get(2).unwrap();
+        assert_eq!(replica_bank2.hash(), bank2.hash());
+        assert_eq!(block_commitment_cache.read().unwrap().root(), 2);
+
+        exit.store(true, Ordering::Relaxed);
+        service.join().unwrap();
+        replica_service.join().unwrap();
+    }
+
+    #[test]
+    fn test_replica_queue_overflow() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let stream = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (mut peer_stream, _) = listener.accept().unwrap();
+        let (sender, receiver) = bounded(2);
+        let mut replica = Replica {
+            sender,
+            stream,
+            root: 10,
+            sent_slots: HashSet::new(),
+        };
+        let mut recent_deltas = RecentDeltas::new(/*root:*/ 10, /*catch_up_slots:*/ 2);
+        recent_deltas.insert(11, 10, Arc::new(vec![11]));
+        assert!(AccountsReplicationService::send_deltas(
+            &mut replica,
+            &recent_deltas,
+            None
+        ));
+        assert_eq!(receiver.len(), 1);
+
+        // The thread of the replica is not keeping up, so the replica is
+        // disconnected instead of queuing more
+        recent_deltas.insert(12, 11, Arc::new(vec![12]));
+        recent_deltas.insert(13, 12, Arc::new(vec![13]));
+        assert!(!AccountsReplicationService::send_deltas(
+            &mut replica,
+            &recent_deltas,
+            None
+        ));
+        assert_eq!(receiver.len(), 2);
+        let mut buf = [0u8; 1];
+        assert_eq!(peer_stream.read(&mut buf).unwrap(), 0);
+
+        // As it is once its thread stops
+        let (sender, receiver) = bounded(2);
+        drop(receiver);
+        replica.sender = sender;
+        recent_deltas.insert(14, 13, Arc::new(vec![14]));
+        assert!(!AccountsReplicationService::send_deltas(
+            &mut replica,
+            &recent_deltas,
+            None
+        ));
+    }
+
+    #[test]
+    fn test_signed_replication_message() {
+        let keypair = Keypair::new();
+        let message = SignedReplicationMessage::new(&ReplicationMessage::Root(42), &keypair);
+        let bytes = message.serialize();
+        let message = SignedReplicationMessage::deserialize_from(bytes.as_slice()).unwrap();
+        assert!(matches!(
+            message.verify(&keypair.pubkey()),
+            Ok(ReplicationMessage::Root(42))
+        ));
+
+        // Messages not signed by the configured identity are rejected
+        let message = SignedReplicationMessage::deserialize_from(bytes.as_slice()).unwrap();
+        assert!(matches!(
+            message.verify(&Pubkey::new_unique()),
+            Err(ReplicaError::InvalidSignature(_))
+        ));
+        let mut message = SignedReplicationMessage::deserialize_from(bytes.as_slice()).unwrap();
+        message.message = bincode::serialize(&ReplicationMessage::Root(43)).unwrap();
+        assert!(matches!(
+            message.verify(&keypair.pubkey()),
+            Err(ReplicaError::InvalidSignature(_))
+        ));
+    }
+
+    #[test]
+    fn test_recent_deltas() {
+        let delta = Arc::new(vec![]);
+        let mut recent_deltas = RecentDeltas::new(/*root:*/ 10, /*catch_up_slots:*/ 2);
+        // Slots 11, 12, 14 and 15 on one fork, 13 on another
+        for (slot, parent_slot) in [(11, 10), (12, 11), (13, 11), (14, 12), (15, 14)] {
+            recent_deltas.insert(slot, parent_slot, delta.clone());
+        }
+        let slots = |recent_deltas: &RecentDeltas, root| -> Vec<Slot> {
+            recent_deltas.since(root).map(|(slot, _)| *slot).collect()
+        };
+        assert_eq!(slots(&recent_deltas, 10), vec![11, 12, 13, 14, 15]);
+        assert_eq!(slots(&recent_deltas, 12), vec![13, 14, 15]);
+
+        // Slots off the rooted fork are dropped, and the ancestors of the root
+        // beyond catch_up_slots
+        recent_deltas.set_root(14);
+        assert_eq!(slots(&recent_deltas, 0), vec![12, 14, 15]);
+        assert_eq!(recent_deltas.oldest_root, 11);
+        recent_deltas.set_root(15);
+        assert_eq!(slots(&recent_deltas, 0), vec![14, 15]);
+        assert_eq!(recent_deltas.oldest_root, 12);
+    }
+}
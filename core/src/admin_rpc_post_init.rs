@@ -4,8 +4,10 @@ use {
         repair::{outstanding_requests::OutstandingRequests, serve_repair::ShredRepairType},
     },
     solana_gossip::cluster_info::ClusterInfo,
-    solana_runtime::bank_forks::BankForks,
+    solana_ledger::blockstore::Blockstore,
+    solana_runtime::{bank_forks::BankForks, snapshot_config::SnapshotConfig},
     solana_sdk::{pubkey::Pubkey, quic::NotifyKeyUpdate},
+    solana_send_transaction_service::inflight_limiter::FeePayerInflightLimiter,
     std::{
         collections::HashSet,
         net::UdpSocket,
@@ -19,8 +21,14 @@ pub struct AdminRpcRequestMetadataPostInit {
     pub bank_forks: Arc<RwLock<BankForks>>,
     pub vote_account: Pubkey,
     pub repair_whitelist: Arc<RwLock<HashSet<Pubkey>>>,
+    pub write_fenced_accounts: Arc<RwLock<HashSet<Pubkey>>>,
     pub notifies: Vec<Arc<dyn NotifyKeyUpdate + Sync + Send>>,
     pub repair_socket: Arc<UdpSocket>,
     pub outstanding_repair_requests: Arc<RwLock<OutstandingRequests<ShredRepairType>>>,
     pub cluster_slots: Arc<ClusterSlots>,
+    pub blockstore: Arc<Blockstore>,
+    /// Snapshot config of the accounts background service, whose intervals may be reloaded
+    pub snapshot_config: Arc<RwLock<SnapshotConfig>>,
+    /// Limiter of the RPC `sendTransaction` transactions pending per fee payer, if enabled
+    pub rpc_fee_payer_inflight_limiter: Option<Arc<FeePayerInflightLimiter>>,
 }
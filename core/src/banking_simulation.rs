@@ -3,6 +3,7 @@ use {
     crate::{
         banking_stage::{
             update_bank_forks_and_poh_recorder_for_new_tpu_bank, BankingStage, LikeClusterInfo,
+            PrioGraphSchedulerConfig,
         },
         banking_trace::{
             BankingTracer, ChannelLabel, Channels, TimedTracedEvent, TracedEvent, TracedSender,
@@ -794,7 +795,6 @@ impl BankingSimulator {
             &genesis_config.hash(),
             Some(&bank_forks.read().unwrap().root_bank().hard_forks()),
         );
-        let (sender, _receiver) = tokio::sync::mpsc::channel(1);
 
         // Create a completely-dummy ClusterInfo for the broadcast stage.
         // We only need it to write shreds into the blockstore and it seems given ClusterInfo is
@@ -816,7 +816,7 @@ impl BankingSimulator {
             blockstore.clone(),
             bank_forks.clone(),
             shred_version,
-            sender,
+            None, // quic_endpoint_sender
         );
 
         info!("Start banking stage!...");
@@ -837,6 +837,9 @@ impl BankingSimulator {
             bank_forks.clone(),
             prioritization_fee_cache,
             false,
+            None,
+            PrioGraphSchedulerConfig::default(),
+            Arc::default(),
         );
 
         let (&_slot, &raw_base_event_time) = freeze_time_by_slot
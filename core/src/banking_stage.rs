@@ -42,7 +42,9 @@ use {
     },
     solana_sdk::{pubkey::Pubkey, timing::AtomicInterval},
     std::{
-        cmp, env,
+        cmp,
+        collections::HashSet,
+        env,
         ops::Deref,
         sync::{
             atomic::{AtomicU64, AtomicUsize, Ordering},
@@ -53,14 +55,22 @@ use {
     },
     transaction_scheduler::{
         greedy_scheduler::{GreedyScheduler, GreedySchedulerConfig},
-        prio_graph_scheduler::PrioGraphSchedulerConfig,
         receive_and_buffer::{
             ReceiveAndBuffer, SanitizedTransactionReceiveAndBuffer, TransactionViewReceiveAndBuffer,
         },
+        transaction_spill::TransactionSpill,
         transaction_state_container::TransactionStateContainer,
     },
 };
 
+#[cfg(feature = "dev-context-only-utils")]
+pub use self::transaction_scheduler::transaction_state_container::{
+    BenchPacketHandling, TransactionStateContainerBench,
+};
+pub use self::transaction_scheduler::{
+    prio_graph_scheduler::PrioGraphSchedulerConfig, transaction_spill::TransactionSpillConfig,
+};
+
 // Below modules are pub to allow use by banking_stage bench
 pub mod committer;
 pub mod consumer;
@@ -369,6 +379,9 @@ impl BankingStage {
         bank_forks: Arc<RwLock<BankForks>>,
         prioritization_fee_cache: &Arc<PrioritizationFeeCache>,
         enable_forwarding: bool,
+        transaction_spill_config: Option<TransactionSpillConfig>,
+        prio_graph_scheduler_config: PrioGraphSchedulerConfig,
+        write_fenced_accounts: Arc<RwLock<HashSet<Pubkey>>>,
     ) -> Self {
         Self::new_num_threads(
             block_production_method,
@@ -386,6 +399,9 @@ impl BankingStage {
             bank_forks,
             prioritization_fee_cache,
             enable_forwarding,
+            transaction_spill_config,
+            prio_graph_scheduler_config,
+            write_fenced_accounts,
         )
     }
 
@@ -406,6 +422,9 @@ impl BankingStage {
         bank_forks: Arc<RwLock<BankForks>>,
         prioritization_fee_cache: &Arc<PrioritizationFeeCache>,
         enable_forwarding: bool,
+        transaction_spill_config: Option<TransactionSpillConfig>,
+        prio_graph_scheduler_config: PrioGraphSchedulerConfig,
+        write_fenced_accounts: Arc<RwLock<HashSet<Pubkey>>>,
     ) -> Self {
         match block_production_method {
             BlockProductionMethod::CentralScheduler
@@ -430,6 +449,9 @@ impl BankingStage {
                     bank_forks,
                     prioritization_fee_cache,
                     enable_forwarding,
+                    transaction_spill_config,
+                    prio_graph_scheduler_config,
+                    write_fenced_accounts,
                 )
             }
         }
@@ -452,6 +474,9 @@ impl BankingStage {
         bank_forks: Arc<RwLock<BankForks>>,
         prioritization_fee_cache: &Arc<PrioritizationFeeCache>,
         enable_forwarding: bool,
+        transaction_spill_config: Option<TransactionSpillConfig>,
+        prio_graph_scheduler_config: PrioGraphSchedulerConfig,
+        write_fenced_accounts: Arc<RwLock<HashSet<Pubkey>>>,
     ) -> Self {
         assert!(num_threads >= MIN_TOTAL_THREADS);
         // Single thread to generate entries from many banks.
@@ -487,6 +512,7 @@ impl BankingStage {
                 committer.clone(),
                 transaction_recorder.clone(),
                 log_messages_bytes_limit,
+                write_fenced_accounts.clone(),
                 Forwarder::new(
                     poh_recorder.clone(),
                     bank_forks.clone(),
@@ -532,6 +558,9 @@ impl BankingStage {
                     bank_forks,
                     enable_forwarding,
                     data_budget,
+                    transaction_spill_config,
+                    prio_graph_scheduler_config,
+                    write_fenced_accounts,
                 );
             }
             TransactionStructure::View => {
@@ -553,6 +582,9 @@ impl BankingStage {
                     bank_forks,
                     enable_forwarding,
                     data_budget,
+                    transaction_spill_config,
+                    prio_graph_scheduler_config,
+                    write_fenced_accounts,
                 );
             }
         }
@@ -575,6 +607,9 @@ impl BankingStage {
         bank_forks: Arc<RwLock<BankForks>>,
         enable_forwarding: bool,
         data_budget: Arc<DataBudget>,
+        transaction_spill_config: Option<TransactionSpillConfig>,
+        prio_graph_scheduler_config: PrioGraphSchedulerConfig,
+        write_fenced_accounts: Arc<RwLock<HashSet<Pubkey>>>,
     ) {
         // Create channels for communication between scheduler and workers
         let num_workers = (num_threads).saturating_sub(NUM_VOTE_PROCESSING_THREADS);
@@ -584,6 +619,9 @@ impl BankingStage {
 
         // Spawn the worker threads
         let mut worker_metrics = Vec::with_capacity(num_workers as usize);
+        // The scheduler keeps the receiving end as well, to take back work
+        // queued for stalled workers.
+        let scheduler_work_receivers = work_receivers.clone();
         for (index, work_receiver) in work_receivers.into_iter().enumerate() {
             let id = (index as u32).saturating_add(NUM_VOTE_PROCESSING_THREADS);
             let consume_worker = ConsumeWorker::new(
@@ -594,6 +632,7 @@ impl BankingStage {
                     poh_recorder.read().unwrap().new_recorder(),
                     QosService::new(id),
                     log_messages_bytes_limit,
+                    write_fenced_accounts.clone(),
                 ),
                 finished_work_sender.clone(),
                 poh_recorder.read().unwrap().new_leader_bank_notifier(),
@@ -620,6 +659,17 @@ impl BankingStage {
             )
         });
 
+        let spill = transaction_spill_config.and_then(|config| {
+            TransactionSpill::new(&config)
+                .inspect_err(|err| {
+                    warn!(
+                        "Unable to create transaction spill in {}: {err}",
+                        config.path.display()
+                    )
+                })
+                .ok()
+        });
+
         // Spawn the central scheduler thread
         if use_greedy_scheduler {
             bank_thread_hdls.push(
@@ -628,6 +678,7 @@ impl BankingStage {
                     .spawn(move || {
                         let scheduler = GreedyScheduler::new(
                             work_senders,
+                            Some(scheduler_work_receivers),
                             finished_work_receiver,
                             GreedySchedulerConfig::default(),
                         );
@@ -638,6 +689,7 @@ impl BankingStage {
                             scheduler,
                             worker_metrics,
                             forwarder,
+                            spill,
                         );
 
                         match scheduler_controller.run() {
@@ -657,8 +709,9 @@ impl BankingStage {
                     .spawn(move || {
                         let scheduler = PrioGraphScheduler::new(
                             work_senders,
+                            Some(scheduler_work_receivers),
                             finished_work_receiver,
-                            PrioGraphSchedulerConfig::default(),
+                            prio_graph_scheduler_config,
                         );
                         let scheduler_controller = SchedulerController::new(
                             decision_maker.clone(),
@@ -667,6 +720,7 @@ impl BankingStage {
                             scheduler,
                             worker_metrics,
                             forwarder,
+                            spill,
                         );
 
                         match scheduler_controller.run() {
@@ -689,6 +743,7 @@ impl BankingStage {
         committer: Committer,
         transaction_recorder: TransactionRecorder,
         log_messages_bytes_limit: Option<usize>,
+        write_fenced_accounts: Arc<RwLock<HashSet<Pubkey>>>,
         mut forwarder: Forwarder<T>,
         unprocessed_transaction_storage: UnprocessedTransactionStorage,
     ) -> JoinHandle<()> {
@@ -698,6 +753,7 @@ impl BankingStage {
             transaction_recorder,
             QosService::new(id),
             log_messages_bytes_limit,
+            write_fenced_accounts,
         );
 
         Builder::new()
@@ -953,6 +1009,9 @@ mod tests {
             bank_forks,
             &Arc::new(PrioritizationFeeCache::new(0u64)),
             false,
+            None,
+            PrioGraphSchedulerConfig::default(),
+            Arc::default(),
         );
         drop(non_vote_sender);
         drop(tpu_vote_sender);
@@ -1012,6 +1071,9 @@ mod tests {
             bank_forks,
             &Arc::new(PrioritizationFeeCache::new(0u64)),
             false,
+            None,
+            PrioGraphSchedulerConfig::default(),
+            Arc::default(),
         );
         trace!("sending bank");
         drop(non_vote_sender);
@@ -1097,6 +1159,9 @@ mod tests {
             bank_forks.clone(), // keep a local-copy of bank-forks so worker threads do not lose weak access to bank-forks
             &Arc::new(PrioritizationFeeCache::new(0u64)),
             false,
+            None,
+            PrioGraphSchedulerConfig::default(),
+            Arc::default(),
         );
 
         // fund another account so we can send 2 good transactions in a single batch.
@@ -1269,6 +1334,9 @@ mod tests {
                 bank_forks,
                 &Arc::new(PrioritizationFeeCache::new(0u64)),
                 false,
+                None,
+                PrioGraphSchedulerConfig::default(),
+                Arc::default(),
             );
 
             // wait for banking_stage to eat the packets
@@ -1459,6 +1527,9 @@ mod tests {
             bank_forks,
             &Arc::new(PrioritizationFeeCache::new(0u64)),
             false,
+            None,
+            PrioGraphSchedulerConfig::default(),
+            Arc::default(),
         );
 
         let keypairs = (0..100).map(|_| Keypair::new()).collect_vec();
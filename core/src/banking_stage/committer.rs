@@ -6,7 +6,7 @@ use {
     },
     solana_measure::measure_us,
     solana_runtime::{
-        bank::{Bank, ProcessedTransactionCounts, TransactionBalancesSet},
+        bank::{Bank, TransactionBalancesSet},
         bank_utils,
         prioritization_fee_cache::PrioritizationFeeCache,
         transaction_batch::TransactionBatch,
@@ -14,11 +14,8 @@ use {
     },
     solana_runtime_transaction::transaction_with_meta::TransactionWithMeta,
     solana_sdk::{pubkey::Pubkey, saturating_add_assign},
-    solana_svm::{
-        transaction_commit_result::{TransactionCommitResult, TransactionCommitResultExtensions},
-        transaction_processing_result::{
-            TransactionProcessingResult, TransactionProcessingResultExtensions,
-        },
+    solana_svm::transaction_commit_result::{
+        TransactionCommitResult, TransactionCommitResultExtensions,
     },
     solana_transaction_status::{
         token_balances::TransactionTokenBalancesSet, TransactionTokenBalance,
@@ -37,7 +34,6 @@ pub enum CommitTransactionDetails {
 
 #[derive(Default)]
 pub(super) struct PreBalanceInfo {
-    pub native: Vec<Vec<u64>>,
     pub token: Vec<Vec<TransactionTokenBalance>>,
     pub mint_decimals: HashMap<Pubkey, u8>,
 }
@@ -66,30 +62,24 @@ impl Committer {
         self.transaction_status_sender.is_some()
     }
 
-    pub(super) fn commit_transactions(
+    /// Reports the results of committing `batch` to the vote listeners, the
+    /// transaction status service and the prioritization fee cache.
+    pub(super) fn handle_commit_results(
         &self,
         batch: &TransactionBatch<impl TransactionWithMeta>,
-        processing_results: Vec<TransactionProcessingResult>,
+        commit_results: Vec<TransactionCommitResult>,
+        balances: TransactionBalancesSet,
         starting_transaction_index: Option<usize>,
         bank: &Arc<Bank>,
         pre_balance_info: &mut PreBalanceInfo,
         execute_and_commit_timings: &mut LeaderExecuteAndCommitTimings,
-        processed_counts: &ProcessedTransactionCounts,
-    ) -> (u64, Vec<CommitTransactionDetails>) {
-        let processed_transactions = processing_results
+    ) -> Vec<CommitTransactionDetails> {
+        let committed_transactions = commit_results
             .iter()
             .zip(batch.sanitized_transactions())
-            .filter_map(|(processing_result, tx)| processing_result.was_processed().then_some(tx))
+            .filter_map(|(commit_result, tx)| commit_result.was_committed().then_some(tx))
             .collect_vec();
 
-        let (commit_results, commit_time_us) = measure_us!(bank.commit_transactions(
-            batch.sanitized_transactions(),
-            processing_results,
-            processed_counts,
-            &mut execute_and_commit_timings.execute_timings,
-        ));
-        execute_and_commit_timings.commit_us = commit_time_us;
-
         let commit_transaction_statuses = commit_results
             .iter()
             .map(|commit_result| match commit_result {
@@ -114,21 +104,23 @@ impl Committer {
             );
             self.collect_balances_and_send_status_batch(
                 commit_results,
+                balances,
                 bank,
                 batch,
                 pre_balance_info,
                 starting_transaction_index,
             );
             self.prioritization_fee_cache
-                .update(bank, processed_transactions.into_iter());
+                .update(bank, committed_transactions.into_iter());
         });
         execute_and_commit_timings.find_and_send_votes_us = find_and_send_votes_us;
-        (commit_time_us, commit_transaction_statuses)
+        commit_transaction_statuses
     }
 
     fn collect_balances_and_send_status_batch(
         &self,
         commit_results: Vec<TransactionCommitResult>,
+        balances: TransactionBalancesSet,
         bank: &Arc<Bank>,
         batch: &TransactionBatch<impl TransactionWithMeta>,
         pre_balance_info: &mut PreBalanceInfo,
@@ -142,7 +134,6 @@ impl Committer {
                 .iter()
                 .map(|tx| tx.as_sanitized_transaction().into_owned())
                 .collect_vec();
-            let post_balances = bank.collect_balances(batch);
             let post_token_balances =
                 collect_token_balances(bank, batch, &mut pre_balance_info.mint_decimals);
             let mut transaction_index = starting_transaction_index.unwrap_or_default();
@@ -162,10 +153,7 @@ impl Committer {
                 bank.slot(),
                 txs,
                 commit_results,
-                TransactionBalancesSet::new(
-                    std::mem::take(&mut pre_balance_info.native),
-                    post_balances,
-                ),
+                balances,
                 TransactionTokenBalancesSet::new(
                     std::mem::take(&mut pre_balance_info.token),
                     post_token_balances,
@@ -343,6 +343,7 @@ impl ConsumeWorkerMetrics {
             invalid_compute_budget,
             not_allowed_during_cluster_maintenance,
             invalid_writable_account,
+            write_fenced_account,
             invalid_rent_paying_account,
             would_exceed_max_block_cost_limit,
             would_exceed_max_account_cost_limit,
@@ -403,6 +404,9 @@ impl ConsumeWorkerMetrics {
         self.error_metrics
             .invalid_writable_account
             .fetch_add(invalid_writable_account.0, Ordering::Relaxed);
+        self.error_metrics
+            .write_fenced_account
+            .fetch_add(write_fenced_account.0, Ordering::Relaxed);
         self.error_metrics
             .invalid_rent_paying_account
             .fetch_add(invalid_rent_paying_account.0, Ordering::Relaxed);
@@ -605,6 +609,7 @@ struct ConsumeWorkerTransactionErrorMetrics {
     invalid_compute_budget: AtomicUsize,
     not_allowed_during_cluster_maintenance: AtomicUsize,
     invalid_writable_account: AtomicUsize,
+    write_fenced_account: AtomicUsize,
     invalid_rent_paying_account: AtomicUsize,
     would_exceed_max_block_cost_limit: AtomicUsize,
     would_exceed_max_account_cost_limit: AtomicUsize,
@@ -703,6 +708,11 @@ impl ConsumeWorkerTransactionErrorMetrics {
                 self.invalid_writable_account.swap(0, Ordering::Relaxed),
                 i64
             ),
+            (
+                "write_fenced_account",
+                self.write_fenced_account.swap(0, Ordering::Relaxed),
+                i64
+            ),
             (
                 "invalid_rent_paying_account",
                 self.invalid_rent_paying_account.swap(0, Ordering::Relaxed),
@@ -835,7 +845,13 @@ mod tests {
             replay_vote_sender,
             Arc::new(PrioritizationFeeCache::new(0u64)),
         );
-        let consumer = Consumer::new(committer, recorder, QosService::new(1), None);
+        let consumer = Consumer::new(
+            committer,
+            recorder,
+            QosService::new(1),
+            None,
+            Arc::default(),
+        );
 
         let (consume_sender, consume_receiver) = unbounded();
         let (consumed_sender, consumed_receiver) = unbounded();
@@ -17,32 +17,35 @@ use {
     solana_ledger::token_balances::collect_token_balances,
     solana_measure::{measure::Measure, measure_us},
     solana_poh::poh_recorder::{
-        BankStart, PohRecorderError, RecordTransactionsSummary, RecordTransactionsTimings,
-        TransactionRecorder,
+        BankStart, PohRecorderError, RecordTransactionsSummary, TransactionRecorder,
     },
     solana_runtime::{
-        bank::{Bank, LoadAndExecuteTransactionsOutput},
+        bank::{Bank, RecordAndCommitTransactionsOutput},
         transaction_batch::TransactionBatch,
+        transaction_recorder::RecordTransactions,
         verify_precompiles::verify_precompiles,
     },
     solana_runtime_transaction::transaction_with_meta::TransactionWithMeta,
     solana_sdk::{
-        clock::{FORWARD_TRANSACTIONS_TO_LEADER_AT_SLOT_OFFSET, MAX_PROCESSING_AGE},
+        clock::{Slot, FORWARD_TRANSACTIONS_TO_LEADER_AT_SLOT_OFFSET, MAX_PROCESSING_AGE},
         fee::FeeBudgetLimits,
+        pubkey::Pubkey,
         saturating_add_assign,
         timing::timestamp,
-        transaction::{self, TransactionError},
+        transaction::{self, TransactionError, VersionedTransaction},
     },
     solana_svm::{
         account_loader::{validate_fee_payer, TransactionCheckResult},
         transaction_error_metrics::TransactionErrorMetrics,
-        transaction_processing_result::TransactionProcessingResultExtensions,
         transaction_processor::{ExecutionRecordingConfig, TransactionProcessingConfig},
     },
+    solana_svm_transaction::svm_message::SVMMessage,
     solana_timings::ExecuteTimings,
     std::{
+        cell::RefCell,
+        collections::HashSet,
         num::Saturating,
-        sync::{atomic::Ordering, Arc},
+        sync::{atomic::Ordering, Arc, RwLock},
         time::Instant,
     },
 };
@@ -86,11 +89,52 @@ pub struct LeaderProcessedTransactionCounts {
     pub(crate) processed_with_successful_result_count: u64,
 }
 
+/// Records the transactions of a bank into PoH on its behalf, keeping the
+/// timings and the result of the recording for the leader metrics.
+struct LeaderTransactionRecorder<'a> {
+    transaction_recorder: &'a TransactionRecorder,
+    start: Instant,
+    // Time spent from the start until asked to record, time spent recording
+    // and the summary of the recording
+    recording: RefCell<Option<(u64, u64, RecordTransactionsSummary)>>,
+}
+
+impl<'a> LeaderTransactionRecorder<'a> {
+    fn new(transaction_recorder: &'a TransactionRecorder) -> Self {
+        Self {
+            transaction_recorder,
+            start: Instant::now(),
+            recording: RefCell::default(),
+        }
+    }
+}
+
+impl RecordTransactions for LeaderTransactionRecorder<'_> {
+    fn record_transactions(
+        &self,
+        bank_slot: Slot,
+        transactions: Vec<VersionedTransaction>,
+    ) -> transaction::Result<Option<usize>> {
+        let elapsed_us = self.start.elapsed().as_micros() as u64;
+        let (summary, record_us) = measure_us!(self
+            .transaction_recorder
+            .record_transactions(bank_slot, transactions));
+        let result = match summary.result {
+            Ok(()) => Ok(summary.starting_transaction_index),
+            Err(_) => Err(TransactionError::CommitCancelled),
+        };
+        *self.recording.borrow_mut() = Some((elapsed_us, record_us, summary));
+        result
+    }
+}
+
 pub struct Consumer {
     committer: Committer,
     transaction_recorder: TransactionRecorder,
     qos_service: QosService,
     log_messages_bytes_limit: Option<usize>,
+    // Accounts this node refuses to write in the blocks it produces
+    write_fenced_accounts: Arc<RwLock<HashSet<Pubkey>>>,
 }
 
 impl Consumer {
@@ -99,12 +143,14 @@ impl Consumer {
         transaction_recorder: TransactionRecorder,
         qos_service: QosService,
         log_messages_bytes_limit: Option<usize>,
+        write_fenced_accounts: Arc<RwLock<HashSet<Pubkey>>>,
     ) -> Self {
         Self {
             committer,
             transaction_recorder,
             qos_service,
             log_messages_bytes_limit,
+            write_fenced_accounts,
         }
     }
 
@@ -477,13 +523,29 @@ impl Consumer {
         chunk_offset: usize,
         pre_results: impl Iterator<Item = Result<(), TransactionError>>,
     ) -> ProcessTransactionBatchOutput {
+        let mut write_fenced_count = Saturating(0);
+        let pre_results: Vec<_> = {
+            let write_fenced_accounts = self.write_fenced_accounts.read().unwrap();
+            txs.iter()
+                .zip(pre_results)
+                .map(|(tx, result)| {
+                    result?;
+                    if Self::writes_fenced_account(tx, &write_fenced_accounts) {
+                        write_fenced_count += 1;
+                        return Err(TransactionError::InvalidWritableAccount);
+                    }
+                    Ok(())
+                })
+                .collect()
+        };
+
         let (
             (transaction_qos_cost_results, cost_model_throttled_transactions_count),
             cost_model_us,
         ) = measure_us!(self.qos_service.select_and_accumulate_transaction_costs(
             bank,
             txs,
-            pre_results
+            pre_results.into_iter()
         ));
 
         // Only lock accounts for those transactions are selected for the block;
@@ -506,6 +568,10 @@ impl Consumer {
         // Once the accounts are new transactions can enter the pipeline to process them
         let (_, unlock_us) = measure_us!(drop(batch));
 
+        execute_and_commit_transactions_output
+            .error_counters
+            .write_fenced_account += write_fenced_count;
+
         let ExecuteAndCommitTransactionsOutput {
             ref mut retryable_transaction_indexes,
             ref execute_and_commit_timings,
@@ -550,6 +616,22 @@ impl Consumer {
         }
     }
 
+    /// Whether `tx` write locks any of the accounts fenced by the operator.
+    /// The fence is a local block production policy: transactions writing
+    /// fenced accounts are dropped instead of included in this node's blocks,
+    /// while blocks produced by other leaders are replayed as usual.
+    fn writes_fenced_account(
+        tx: &impl SVMMessage,
+        write_fenced_accounts: &HashSet<Pubkey>,
+    ) -> bool {
+        !write_fenced_accounts.is_empty()
+            && tx
+                .account_keys()
+                .iter()
+                .enumerate()
+                .any(|(index, key)| tx.is_writable(index) && write_fenced_accounts.contains(key))
+    }
+
     fn execute_and_commit_transactions_locked(
         &self,
         bank: &Arc<Bank>,
@@ -561,9 +643,8 @@ impl Consumer {
         let mut pre_balance_info = PreBalanceInfo::default();
         let (_, collect_balances_us) = measure_us!({
             // If the extra meta-data services are enabled for RPC, collect the
-            // pre-balances for native and token programs.
+            // pre-balances for token programs. The bank collects the native ones.
             if transaction_status_sender_enabled {
-                pre_balance_info.native = bank.collect_balances(batch);
                 pre_balance_info.token =
                     collect_token_balances(bank, batch, &mut pre_balance_info.mint_decimals)
             }
@@ -621,12 +702,12 @@ impl Consumer {
             })
             .collect();
 
-        let (load_and_execute_transactions_output, load_execute_us) = measure_us!(bank
-            .load_and_execute_transactions(
+        let recorder = LeaderTransactionRecorder::new(&self.transaction_recorder);
+        let (record_and_commit_output, load_execute_and_commit_us) = measure_us!(bank
+            .load_execute_and_commit_transactions_with_recorder(
                 batch,
                 MAX_PROCESSING_AGE,
-                &mut execute_and_commit_timings.execute_timings,
-                &mut error_counters,
+                transaction_status_sender_enabled,
                 TransactionProcessingConfig {
                     account_overrides: None,
                     check_program_modification_slot: bank.check_program_modification_slot(),
@@ -637,59 +718,55 @@ impl Consumer {
                         transaction_status_sender_enabled
                     ),
                     transaction_account_lock_limit: Some(bank.get_transaction_account_lock_limit()),
-                }
+                },
+                &mut execute_and_commit_timings.execute_timings,
+                &mut error_counters,
+                &recorder,
             ));
-        execute_and_commit_timings.load_execute_us = load_execute_us;
 
-        let LoadAndExecuteTransactionsOutput {
-            processing_results,
-            processed_counts,
-        } = load_and_execute_transactions_output;
+        let RecordAndCommitTransactionsOutput {
+            processed_transaction_indexes,
+            processed_with_successful_result_count,
+            freeze_lock_us,
+            starting_transaction_index,
+            commit_result,
+        } = record_and_commit_output;
 
         let transaction_counts = LeaderProcessedTransactionCounts {
-            processed_count: processed_counts.processed_transactions_count,
-            processed_with_successful_result_count: processed_counts
-                .processed_with_successful_result_count,
-            attempted_processing_count: processing_results.len() as u64,
+            processed_count: processed_transaction_indexes.len() as u64,
+            processed_with_successful_result_count,
+            attempted_processing_count: batch.sanitized_transactions().len() as u64,
         };
 
-        let (processed_transactions, processing_results_to_transactions_us) =
-            measure_us!(processing_results
-                .iter()
-                .zip(batch.sanitized_transactions())
-                .filter_map(|(processing_result, tx)| {
-                    if processing_result.was_processed() {
-                        Some(tx.to_versioned_transaction())
-                    } else {
-                        None
-                    }
-                })
-                .collect_vec());
-
-        let (freeze_lock, freeze_lock_us) = measure_us!(bank.freeze_lock());
+        // Without anything to record the bank never called the recorder, so
+        // attribute the whole time to loading and executing.
+        let (load_execute_us, record_us, record_transactions_result) =
+            match recorder.recording.into_inner() {
+                Some((
+                    load_execute_us,
+                    record_us,
+                    RecordTransactionsSummary {
+                        result,
+                        record_transactions_timings,
+                        ..
+                    },
+                )) => {
+                    execute_and_commit_timings.record_transactions_timings =
+                        record_transactions_timings;
+                    (
+                        load_execute_us.saturating_sub(freeze_lock_us),
+                        record_us,
+                        result,
+                    )
+                }
+                None => (load_execute_and_commit_us, 0, Ok(())),
+            };
+        execute_and_commit_timings.load_execute_us = load_execute_us;
         execute_and_commit_timings.freeze_lock_us = freeze_lock_us;
-
-        let (record_transactions_summary, record_us) = measure_us!(self
-            .transaction_recorder
-            .record_transactions(bank.slot(), processed_transactions));
         execute_and_commit_timings.record_us = record_us;
 
-        let RecordTransactionsSummary {
-            result: record_transactions_result,
-            record_transactions_timings,
-            starting_transaction_index,
-        } = record_transactions_summary;
-        execute_and_commit_timings.record_transactions_timings = RecordTransactionsTimings {
-            processing_results_to_transactions_us: Saturating(
-                processing_results_to_transactions_us,
-            ),
-            ..record_transactions_timings
-        };
-
         if let Err(recorder_err) = record_transactions_result {
-            retryable_transaction_indexes.extend(processing_results.iter().enumerate().filter_map(
-                |(index, processing_result)| processing_result.was_processed().then_some(index),
-            ));
+            retryable_transaction_indexes.extend(processed_transaction_indexes);
 
             return ExecuteAndCommitTransactionsOutput {
                 transaction_counts,
@@ -702,25 +779,28 @@ impl Consumer {
             };
         }
 
-        let (commit_time_us, commit_transaction_statuses) =
-            if processed_counts.processed_transactions_count != 0 {
-                self.committer.commit_transactions(
-                    batch,
-                    processing_results,
-                    starting_transaction_index,
-                    bank,
-                    &mut pre_balance_info,
-                    &mut execute_and_commit_timings,
-                    &processed_counts,
-                )
-            } else {
-                (
-                    0,
-                    vec![CommitTransactionDetails::NotCommitted; processing_results.len()],
-                )
-            };
-
-        drop(freeze_lock);
+        // The bank fails to commit only if the recorder fails
+        let (commit_results, balances) =
+            commit_result.expect("transactions are committed once recorded");
+        let commit_time_us = load_execute_and_commit_us
+            .saturating_sub(load_execute_us)
+            .saturating_sub(freeze_lock_us)
+            .saturating_sub(record_us);
+        execute_and_commit_timings.commit_us = commit_time_us;
+
+        let commit_transaction_statuses = if processed_transaction_indexes.is_empty() {
+            vec![CommitTransactionDetails::NotCommitted; commit_results.len()]
+        } else {
+            self.committer.handle_commit_results(
+                batch,
+                commit_results,
+                balances,
+                starting_transaction_index,
+                bank,
+                &mut pre_balance_info,
+                &mut execute_and_commit_timings,
+            )
+        };
 
         debug!(
             "bank: {} process_and_record_locked: {}us record: {}us commit: {}us txs_len: {}",
@@ -763,13 +843,15 @@ impl Consumer {
                 .compute_budget_instruction_details()
                 .sanitize_and_convert_to_compute_budget_limits(&bank.feature_set)?,
         );
-        let fee = solana_fee::calculate_fee(
+        let fee = solana_fee::calculate_fee_details_with_model(
             transaction,
             bank.get_lamports_per_signature() == 0,
             bank.fee_structure().lamports_per_signature,
             fee_budget_limits.prioritization_fee,
             FeeFeatures::from(bank.feature_set.as_ref()),
-        );
+            bank.fee_model(),
+        )
+        .total_fee();
         let (mut fee_payer_account, _slot) = bank
             .rc
             .accounts
@@ -946,7 +1028,13 @@ mod tests {
             replay_vote_sender,
             Arc::new(PrioritizationFeeCache::new(0u64)),
         );
-        let consumer = Consumer::new(committer, recorder, QosService::new(1), None);
+        let consumer = Consumer::new(
+            committer,
+            recorder,
+            QosService::new(1),
+            None,
+            Arc::default(),
+        );
         let process_transactions_summary =
             consumer.process_transactions(&bank, &Instant::now(), &transactions);
 
@@ -1120,7 +1208,13 @@ mod tests {
             replay_vote_sender,
             Arc::new(PrioritizationFeeCache::new(0u64)),
         );
-        let consumer = Consumer::new(committer, recorder, QosService::new(1), None);
+        let consumer = Consumer::new(
+            committer,
+            recorder,
+            QosService::new(1),
+            None,
+            Arc::default(),
+        );
 
         let process_transactions_batch_output =
             consumer.process_and_record_transactions(&bank, &transactions, 0);
@@ -1307,7 +1401,13 @@ mod tests {
             replay_vote_sender,
             Arc::new(PrioritizationFeeCache::new(0u64)),
         );
-        let consumer = Consumer::new(committer, recorder, QosService::new(1), None);
+        let consumer = Consumer::new(
+            committer,
+            recorder,
+            QosService::new(1),
+            None,
+            Arc::default(),
+        );
 
         let process_transactions_batch_output =
             consumer.process_and_record_transactions(&bank, &transactions, 0);
@@ -1408,7 +1508,13 @@ mod tests {
             replay_vote_sender,
             Arc::new(PrioritizationFeeCache::new(0u64)),
         );
-        let consumer = Consumer::new(committer, recorder, QosService::new(1), None);
+        let consumer = Consumer::new(
+            committer,
+            recorder,
+            QosService::new(1),
+            None,
+            Arc::default(),
+        );
 
         let process_transactions_batch_output =
             consumer.process_and_record_transactions(&bank, &transactions, 0);
@@ -1442,6 +1548,101 @@ mod tests {
         let _ = poh_simulator.join();
     }
 
+    #[test]
+    fn test_bank_process_and_record_transactions_write_fenced() {
+        solana_logger::setup();
+        let GenesisConfigInfo {
+            genesis_config,
+            mint_keypair,
+            ..
+        } = create_slow_genesis_config(10_000);
+        let (bank, _bank_forks) = Bank::new_no_wallclock_throttle_for_tests(&genesis_config);
+        let fenced_pubkey = solana_pubkey::new_rand();
+        let pubkey = solana_pubkey::new_rand();
+
+        let transactions = sanitize_transactions(vec![
+            system_transaction::transfer(&mint_keypair, &fenced_pubkey, 1, genesis_config.hash()),
+            system_transaction::transfer(&mint_keypair, &pubkey, 1, genesis_config.hash()),
+        ]);
+
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let blockstore = Blockstore::open(ledger_path.path())
+            .expect("Expected to be able to open database ledger");
+        let (poh_recorder, _entry_receiver, record_receiver) = PohRecorder::new(
+            bank.tick_height(),
+            bank.last_blockhash(),
+            bank.clone(),
+            Some((4, 4)),
+            bank.ticks_per_slot(),
+            Arc::new(blockstore),
+            &Arc::new(LeaderScheduleCache::new_from_bank(&bank)),
+            &PohConfig::default(),
+            Arc::new(AtomicBool::default()),
+        );
+        let recorder = poh_recorder.new_recorder();
+        let poh_recorder = Arc::new(RwLock::new(poh_recorder));
+
+        let poh_simulator = simulate_poh(record_receiver, &poh_recorder);
+
+        poh_recorder
+            .write()
+            .unwrap()
+            .set_bank_for_test(bank.clone());
+        let (replay_vote_sender, _replay_vote_receiver) = unbounded();
+        let committer = Committer::new(
+            None,
+            replay_vote_sender,
+            Arc::new(PrioritizationFeeCache::new(0u64)),
+        );
+        let write_fenced_accounts = Arc::new(RwLock::new(HashSet::from([fenced_pubkey])));
+        let consumer = Consumer::new(
+            committer,
+            recorder,
+            QosService::new(1),
+            None,
+            write_fenced_accounts,
+        );
+
+        let process_transactions_batch_output =
+            consumer.process_and_record_transactions(&bank, &transactions, 0);
+
+        let ExecuteAndCommitTransactionsOutput {
+            transaction_counts,
+            retryable_transaction_indexes,
+            commit_transactions_result,
+            error_counters,
+            ..
+        } = process_transactions_batch_output.execute_and_commit_transactions_output;
+
+        // The transfer to the fenced account is dropped, not retried
+        assert_eq!(
+            transaction_counts,
+            LeaderProcessedTransactionCounts {
+                attempted_processing_count: 2,
+                processed_count: 1,
+                processed_with_successful_result_count: 1,
+            }
+        );
+        assert!(retryable_transaction_indexes.is_empty());
+        assert_eq!(error_counters.write_fenced_account.0, 1);
+        assert_matches!(
+            commit_transactions_result.unwrap().as_slice(),
+            [
+                CommitTransactionDetails::NotCommitted,
+                CommitTransactionDetails::Committed { .. }
+            ]
+        );
+        assert_eq!(bank.get_balance(&fenced_pubkey), 0);
+        assert_eq!(bank.get_balance(&pubkey), 1);
+
+        poh_recorder
+            .read()
+            .unwrap()
+            .is_exited
+            .store(true, Ordering::Relaxed);
+        let _ = poh_simulator.join();
+    }
+
     #[test]
     fn test_bank_process_and_record_transactions_cost_tracker() {
         solana_logger::setup();
@@ -1484,7 +1685,13 @@ mod tests {
             replay_vote_sender,
             Arc::new(PrioritizationFeeCache::new(0u64)),
         );
-        let consumer = Consumer::new(committer, recorder, QosService::new(1), None);
+        let consumer = Consumer::new(
+            committer,
+            recorder,
+            QosService::new(1),
+            None,
+            Arc::default(),
+        );
 
         let get_block_cost = || bank.read_cost_tracker().unwrap().block_cost();
         let get_tx_count = || bank.read_cost_tracker().unwrap().transaction_count();
@@ -1643,7 +1850,13 @@ mod tests {
             replay_vote_sender,
             Arc::new(PrioritizationFeeCache::new(0u64)),
         );
-        let consumer = Consumer::new(committer, recorder, QosService::new(1), None);
+        let consumer = Consumer::new(
+            committer,
+            recorder,
+            QosService::new(1),
+            None,
+            Arc::default(),
+        );
 
         let process_transactions_batch_output =
             consumer.process_and_record_transactions(&bank, &transactions, 0);
@@ -1845,7 +2058,13 @@ mod tests {
             replay_vote_sender,
             Arc::new(PrioritizationFeeCache::new(0u64)),
         );
-        let consumer = Consumer::new(committer, recorder.clone(), QosService::new(1), None);
+        let consumer = Consumer::new(
+            committer,
+            recorder.clone(),
+            QosService::new(1),
+            None,
+            Arc::default(),
+        );
 
         let process_transactions_summary =
             consumer.process_transactions(&bank, &Instant::now(), &transactions);
@@ -1971,7 +2190,13 @@ mod tests {
             replay_vote_sender,
             Arc::new(PrioritizationFeeCache::new(0u64)),
         );
-        let consumer = Consumer::new(committer, recorder, QosService::new(1), None);
+        let consumer = Consumer::new(
+            committer,
+            recorder,
+            QosService::new(1),
+            None,
+            Arc::default(),
+        );
 
         let _ = consumer.process_and_record_transactions(&bank, &transactions, 0);
 
@@ -2115,7 +2340,13 @@ mod tests {
             replay_vote_sender,
             Arc::new(PrioritizationFeeCache::new(0u64)),
         );
-        let consumer = Consumer::new(committer, recorder, QosService::new(1), None);
+        let consumer = Consumer::new(
+            committer,
+            recorder,
+            QosService::new(1),
+            None,
+            Arc::default(),
+        );
 
         let _ = consumer.process_and_record_transactions(&bank, &[sanitized_tx.clone()], 0);
 
@@ -2169,7 +2400,13 @@ mod tests {
             replay_vote_sender,
             Arc::new(PrioritizationFeeCache::new(0u64)),
         );
-        let consumer = Consumer::new(committer, recorder, QosService::new(1), None);
+        let consumer = Consumer::new(
+            committer,
+            recorder,
+            QosService::new(1),
+            None,
+            Arc::default(),
+        );
 
         // When the working bank in poh_recorder is None, no packets should be processed (consume will not be called)
         assert!(!poh_recorder.read().unwrap().has_bank());
@@ -2240,7 +2477,13 @@ mod tests {
             replay_vote_sender,
             Arc::new(PrioritizationFeeCache::new(0u64)),
         );
-        let consumer = Consumer::new(committer, recorder, QosService::new(1), None);
+        let consumer = Consumer::new(
+            committer,
+            recorder,
+            QosService::new(1),
+            None,
+            Arc::default(),
+        );
 
         // When the working bank in poh_recorder is None, no packets should be processed
         assert!(!poh_recorder.read().unwrap().has_bank());
@@ -2285,7 +2528,13 @@ mod tests {
             replay_vote_sender,
             Arc::new(PrioritizationFeeCache::new(0u64)),
         );
-        let consumer = Consumer::new(committer, recorder, QosService::new(1), None);
+        let consumer = Consumer::new(
+            committer,
+            recorder,
+            QosService::new(1),
+            None,
+            Arc::default(),
+        );
 
         // When the working bank in poh_recorder is None, no packets should be processed (consume will not be called)
         assert!(!poh_recorder.read().unwrap().has_bank());
@@ -2405,7 +2654,13 @@ mod tests {
             replay_vote_sender,
             Arc::new(PrioritizationFeeCache::new(0u64)),
         );
-        let consumer = Consumer::new(committer, recorder, QosService::new(1), None);
+        let consumer = Consumer::new(
+            committer,
+            recorder,
+            QosService::new(1),
+            None,
+            Arc::default(),
+        );
 
         // When the working bank in poh_recorder is None, no packets should be processed (consume will not be called)
         assert!(!poh_recorder.read().unwrap().has_bank());
@@ -133,15 +133,51 @@ impl ImmutableDeserializedPacket {
         if votes_only && !self.is_simple_vote() {
             return None;
         }
+        Self::sanitize_transaction(
+            self.transaction.clone(),
+            self.message_hash,
+            self.is_simple_vote,
+            bank,
+            reserved_account_keys,
+        )
+    }
+
+    /// Same as `build_sanitized_transaction`, but moves the transaction out of
+    /// the packet rather than cloning it, for callers which do not need the
+    /// packet afterwards.
+    pub fn into_sanitized_transaction(
+        self,
+        votes_only: bool,
+        bank: &Bank,
+        reserved_account_keys: &HashSet<Pubkey>,
+    ) -> Option<(RuntimeTransaction<SanitizedTransaction>, Slot)> {
+        if votes_only && !self.is_simple_vote() {
+            return None;
+        }
+        Self::sanitize_transaction(
+            self.transaction,
+            self.message_hash,
+            self.is_simple_vote,
+            bank,
+            reserved_account_keys,
+        )
+    }
 
+    fn sanitize_transaction(
+        transaction: SanitizedVersionedTransaction,
+        message_hash: Hash,
+        is_simple_vote: bool,
+        bank: &Bank,
+        reserved_account_keys: &HashSet<Pubkey>,
+    ) -> Option<(RuntimeTransaction<SanitizedTransaction>, Slot)> {
         // Resolve the lookup addresses and retrieve the min deactivation slot
         let (loaded_addresses, deactivation_slot) =
-            Self::resolve_addresses_with_deactivation(self.transaction(), bank).ok()?;
+            Self::resolve_addresses_with_deactivation(&transaction, bank).ok()?;
         let address_loader = SimpleAddressLoader::Enabled(loaded_addresses);
         let tx = RuntimeTransaction::<SanitizedVersionedTransaction>::try_from(
-            self.transaction.clone(),
-            MessageHash::Precomputed(self.message_hash),
-            Some(self.is_simple_vote),
+            transaction,
+            MessageHash::Precomputed(message_hash),
+            Some(is_simple_vote),
         )
         .and_then(|tx| {
             RuntimeTransaction::<SanitizedTransaction>::try_from(
@@ -208,6 +244,7 @@ fn packet_message(packet: &Packet) -> Result<&[u8], DeserializedPacketError> {
 mod tests {
     use {
         super::*,
+        solana_ledger::genesis_utils::{create_genesis_config, GenesisConfigInfo},
         solana_sdk::{
             compute_budget, instruction::Instruction, pubkey::Pubkey, signature::Keypair,
             signer::Signer, system_instruction, system_transaction, transaction::Transaction,
@@ -228,6 +265,35 @@ mod tests {
         assert!(deserialized_packet.is_ok());
     }
 
+    #[test]
+    fn into_sanitized_transaction() {
+        let GenesisConfigInfo { genesis_config, .. } = create_genesis_config(10_000);
+        let bank = Bank::new_for_tests(&genesis_config);
+        let reserved_account_keys = bank.get_reserved_account_keys();
+        let tx = system_transaction::transfer(
+            &Keypair::new(),
+            &solana_pubkey::new_rand(),
+            1,
+            genesis_config.hash(),
+        );
+        let packet = Packet::from_data(None, tx).unwrap();
+        let deserialized_packet = ImmutableDeserializedPacket::new(packet).unwrap();
+
+        let (built, built_deactivation_slot) = deserialized_packet
+            .build_sanitized_transaction(false, &bank, reserved_account_keys)
+            .unwrap();
+        assert!(deserialized_packet
+            .build_sanitized_transaction(true, &bank, reserved_account_keys)
+            .is_none());
+        let (moved, moved_deactivation_slot) = deserialized_packet
+            .into_sanitized_transaction(false, &bank, reserved_account_keys)
+            .unwrap();
+        assert_eq!(moved_deactivation_slot, built_deactivation_slot);
+        assert_eq!(moved.message_hash(), built.message_hash());
+        assert_eq!(moved.signatures(), built.signatures());
+        assert_eq!(moved.message(), built.message());
+    }
+
     #[test]
     fn compute_unit_limit_above_static_builtins() {
         // Cases:
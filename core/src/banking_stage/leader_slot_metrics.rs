@@ -452,6 +452,11 @@ fn report_transaction_error_metrics(errors: &TransactionErrorMetrics, id: &str,
             errors.invalid_writable_account.0 as i64,
             i64
         ),
+        (
+            "write_fenced_account",
+            errors.write_fenced_account.0 as i64,
+            i64
+        ),
         (
             "invalid_rent_paying_account",
             errors.invalid_rent_paying_account.0 as i64,
@@ -1,6 +1,6 @@
 use {
     super::{
-        in_flight_tracker::InFlightTracker,
+        in_flight_tracker::{InFlightTracker, DEFAULT_BATCH_DEADLINE},
         prio_graph_scheduler::{
             Batches, PrioGraphScheduler, TransactionSchedulingError, TransactionSchedulingInfo,
         },
@@ -22,12 +22,15 @@ use {
     solana_cost_model::block_cost_limits::MAX_BLOCK_UNITS,
     solana_runtime_transaction::transaction_with_meta::TransactionWithMeta,
     solana_sdk::saturating_add_assign,
+    std::time::Duration,
 };
 
 pub(crate) struct GreedySchedulerConfig {
     pub target_scheduled_cus: u64,
     pub max_scanned_transactions_per_scheduling_pass: usize,
     pub target_transactions_per_batch: usize,
+    /// Batches executing for longer than this mark their thread as stalled.
+    pub batch_deadline: Duration,
 }
 
 impl Default for GreedySchedulerConfig {
@@ -36,6 +39,7 @@ impl Default for GreedySchedulerConfig {
             target_scheduled_cus: MAX_BLOCK_UNITS / 4,
             max_scanned_transactions_per_scheduling_pass: 100_000,
             target_transactions_per_batch: TARGET_NUM_TRANSACTIONS_PER_BATCH,
+            batch_deadline: DEFAULT_BATCH_DEADLINE,
         }
     }
 }
@@ -47,6 +51,8 @@ pub struct GreedyScheduler<Tx: TransactionWithMeta> {
     in_flight_tracker: InFlightTracker,
     account_locks: ThreadAwareAccountLocks,
     consume_work_senders: Vec<Sender<ConsumeWork<Tx>>>,
+    /// Used to take back work queued for stalled threads, if set.
+    consume_work_receivers: Option<Vec<Receiver<ConsumeWork<Tx>>>>,
     finished_consume_work_receiver: Receiver<FinishedConsumeWork<Tx>>,
     working_account_set: ReadWriteAccountSet,
     unschedulables: Vec<TransactionPriorityId>,
@@ -56,11 +62,15 @@ pub struct GreedyScheduler<Tx: TransactionWithMeta> {
 impl<Tx: TransactionWithMeta> GreedyScheduler<Tx> {
     pub(crate) fn new(
         consume_work_senders: Vec<Sender<ConsumeWork<Tx>>>,
+        consume_work_receivers: Option<Vec<Receiver<ConsumeWork<Tx>>>>,
         finished_consume_work_receiver: Receiver<FinishedConsumeWork<Tx>>,
         config: GreedySchedulerConfig,
     ) -> Self {
         let num_threads = consume_work_senders.len();
         assert!(num_threads > 0, "must have at least one worker");
+        if let Some(consume_work_receivers) = &consume_work_receivers {
+            assert_eq!(num_threads, consume_work_receivers.len());
+        }
         assert!(
             num_threads <= MAX_THREADS,
             "cannot have more than {MAX_THREADS} workers"
@@ -69,6 +79,7 @@ impl<Tx: TransactionWithMeta> GreedyScheduler<Tx> {
             in_flight_tracker: InFlightTracker::new(num_threads),
             account_locks: ThreadAwareAccountLocks::new(num_threads),
             consume_work_senders,
+            consume_work_receivers,
             finished_consume_work_receiver,
             working_account_set: ReadWriteAccountSet::default(),
             unschedulables: Vec::with_capacity(config.max_scanned_transactions_per_scheduling_pass),
@@ -90,6 +101,7 @@ impl<Tx: TransactionWithMeta> Scheduler<Tx> for GreedyScheduler<Tx> {
         let mut schedulable_threads = ThreadSet::any(num_threads);
         for thread_id in 0..num_threads {
             if self.in_flight_tracker.cus_in_flight_per_thread()[thread_id] >= target_cu_per_thread
+                || self.in_flight_tracker.is_stalled(thread_id)
             {
                 schedulable_threads.remove(thread_id);
             }
@@ -153,7 +165,8 @@ impl<Tx: TransactionWithMeta> Scheduler<Tx> for GreedyScheduler<Tx> {
                     container.remove_by_id(id.id);
                 }
                 Err(TransactionSchedulingError::UnschedulableConflicts)
-                | Err(TransactionSchedulingError::UnschedulableThread) => {
+                | Err(TransactionSchedulingError::UnschedulableThread)
+                | Err(TransactionSchedulingError::AccountLimitReached) => {
                     num_unschedulable += 1;
                     self.unschedulables.push(id);
                 }
@@ -208,6 +221,7 @@ impl<Tx: TransactionWithMeta> Scheduler<Tx> for GreedyScheduler<Tx> {
             num_scheduled,
             num_unschedulable,
             num_filtered_out,
+            num_account_limited: 0,
             filter_time_us: 0,
         })
     }
@@ -228,6 +242,7 @@ impl<Tx: TransactionWithMeta> Scheduler<Tx> for GreedyScheduler<Tx> {
             saturating_add_assign!(total_num_transactions, num_transactions);
             saturating_add_assign!(total_num_retryable, num_retryable);
         }
+        self.requeue_stalled_batches(container);
         Ok((total_num_transactions, total_num_retryable))
     }
 }
@@ -286,6 +301,47 @@ impl<Tx: TransactionWithMeta> GreedyScheduler<Tx> {
         }
     }
 
+    /// Takes back the batches still queued for threads that have overdue
+    /// batches in flight, and puts their transactions back into the container
+    /// so they can be scheduled on other threads. The overdue batch being
+    /// processed by a stalled thread cannot be taken back, but no more work
+    /// is scheduled on the thread until it completes.
+    fn requeue_stalled_batches(&mut self, container: &mut impl StateContainer<Tx>) {
+        let stalled_threads = self
+            .in_flight_tracker
+            .mark_overdue_batches(self.config.batch_deadline);
+        for thread_id in stalled_threads.contained_threads_iter() {
+            let mut num_requeued: usize = 0;
+            while let Some(ConsumeWork {
+                batch_id,
+                ids,
+                transactions,
+                max_ages,
+            }) = self
+                .consume_work_receivers
+                .as_ref()
+                .and_then(|receivers| receivers[thread_id].try_recv().ok())
+            {
+                self.complete_batch(batch_id, &transactions);
+                saturating_add_assign!(num_requeued, ids.len());
+                for (id, transaction, max_age) in izip!(ids, transactions, max_ages) {
+                    container.retry_transaction(
+                        id,
+                        SanitizedTransactionTTL {
+                            transaction,
+                            max_age,
+                        },
+                    );
+                }
+            }
+            datapoint_warn!(
+                "banking_stage_scheduler_stalled_worker",
+                ("thread_id", thread_id, i64),
+                ("num_requeued", num_requeued, i64)
+            );
+        }
+    }
+
     /// Mark a given `TransactionBatchId` as completed.
     /// This will update the internal tracking, including account locks.
     fn complete_batch(&mut self, batch_id: TransactionBatchId, transactions: &[Tx]) {
@@ -436,8 +492,12 @@ mod test {
         let (consume_work_senders, consume_work_receivers) =
             (0..num_threads).map(|_| unbounded()).unzip();
         let (finished_consume_work_sender, finished_consume_work_receiver) = unbounded();
-        let scheduler =
-            GreedyScheduler::new(consume_work_senders, finished_consume_work_receiver, config);
+        let scheduler = GreedyScheduler::new(
+            consume_work_senders,
+            None,
+            finished_consume_work_receiver,
+            config,
+        );
         (
             scheduler,
             consume_work_receivers,
@@ -496,7 +556,7 @@ mod test {
             const TEST_TRANSACTION_COST: u64 = 5000;
             container.insert_new_transaction(
                 transaction_ttl,
-                packet,
+                Some(packet),
                 compute_unit_price,
                 TEST_TRANSACTION_COST,
             );
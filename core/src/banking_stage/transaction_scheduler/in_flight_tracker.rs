@@ -1,13 +1,28 @@
 use {
-    super::{batch_id_generator::BatchIdGenerator, thread_aware_account_locks::ThreadId},
+    super::{
+        batch_id_generator::BatchIdGenerator,
+        thread_aware_account_locks::{ThreadId, ThreadSet},
+    },
     crate::banking_stage::scheduler_messages::TransactionBatchId,
-    std::collections::HashMap,
+    std::{
+        collections::{HashMap, VecDeque},
+        time::{Duration, Instant},
+    },
 };
 
+/// Batches executing for longer than this are considered overdue, and the
+/// thread they were scheduled on stalled.
+pub(crate) const DEFAULT_BATCH_DEADLINE: Duration = Duration::from_millis(200);
+
 /// Tracks the number of transactions that are in flight for each thread.
 pub struct InFlightTracker {
     num_in_flight_per_thread: Vec<usize>,
     cus_in_flight_per_thread: Vec<u64>,
+    num_overdue_batches_per_thread: Vec<usize>,
+    /// Batches in flight for each thread, in the order they were scheduled.
+    /// Workers process their batches in order, so the first one is the batch
+    /// being executed.
+    batch_queue_per_thread: Vec<VecDeque<TransactionBatchId>>,
     batches: HashMap<TransactionBatchId, BatchEntry>,
     batch_id_generator: BatchIdGenerator,
 }
@@ -16,6 +31,10 @@ struct BatchEntry {
     thread_id: ThreadId,
     num_transactions: usize,
     total_cus: u64,
+    /// When the worker started executing the batch, or None while the batch
+    /// is queued behind others.
+    started_at: Option<Instant>,
+    is_overdue: bool,
 }
 
 impl InFlightTracker {
@@ -23,6 +42,8 @@ impl InFlightTracker {
         Self {
             num_in_flight_per_thread: vec![0; num_threads],
             cus_in_flight_per_thread: vec![0; num_threads],
+            num_overdue_batches_per_thread: vec![0; num_threads],
+            batch_queue_per_thread: vec![VecDeque::new(); num_threads],
             batches: HashMap::new(),
             batch_id_generator: BatchIdGenerator::default(),
         }
@@ -50,20 +71,50 @@ impl InFlightTracker {
         let batch_id = self.batch_id_generator.next();
         self.num_in_flight_per_thread[thread_id] += num_transactions;
         self.cus_in_flight_per_thread[thread_id] += total_cus;
+        let batch_queue = &mut self.batch_queue_per_thread[thread_id];
+        let started_at = batch_queue.is_empty().then(Instant::now);
+        batch_queue.push_back(batch_id);
         self.batches.insert(
             batch_id,
             BatchEntry {
                 thread_id,
                 num_transactions,
                 total_cus,
+                started_at,
+                is_overdue: false,
             },
         );
 
         batch_id
     }
 
+    /// Marks batches executing for longer than `batch_deadline` as overdue.
+    /// Batches queued behind others are not, however long they have waited.
+    /// Returns the threads with newly overdue batches.
+    pub fn mark_overdue_batches(&mut self, batch_deadline: Duration) -> ThreadSet {
+        let mut stalled_threads = ThreadSet::none();
+        for batch in self.batches.values_mut() {
+            let is_overdue = batch
+                .started_at
+                .is_some_and(|started_at| started_at.elapsed() > batch_deadline);
+            if !batch.is_overdue && is_overdue {
+                batch.is_overdue = true;
+                self.num_overdue_batches_per_thread[batch.thread_id] += 1;
+                stalled_threads.insert(batch.thread_id);
+            }
+        }
+        stalled_threads
+    }
+
+    /// Returns true if the thread has overdue batches in flight.
+    pub fn is_stalled(&self, thread_id: ThreadId) -> bool {
+        self.num_overdue_batches_per_thread[thread_id] > 0
+    }
+
     /// Stop tracking the batch with given `batch_id`.
-    /// Removes the number of transactions for the scheduled thread.
+    /// Removes the number of transactions for the scheduled thread. If the
+    /// batch was being executed, the next batch of the thread is considered
+    /// started.
     /// Returns the thread id that the batch was scheduled on.
     ///
     /// # Panics
@@ -73,12 +124,30 @@ impl InFlightTracker {
             thread_id,
             num_transactions,
             total_cus,
+            started_at: _,
+            is_overdue,
         }) = self.batches.remove(&batch_id)
         else {
             panic!("batch id {batch_id} is not being tracked");
         };
         self.num_in_flight_per_thread[thread_id] -= num_transactions;
         self.cus_in_flight_per_thread[thread_id] -= total_cus;
+        if is_overdue {
+            self.num_overdue_batches_per_thread[thread_id] -= 1;
+        }
+
+        let batch_queue = &mut self.batch_queue_per_thread[thread_id];
+        let position = batch_queue
+            .iter()
+            .position(|id| *id == batch_id)
+            .expect("tracked batches are queued");
+        batch_queue.remove(position);
+        if position == 0 {
+            if let Some(next_batch_id) = batch_queue.front() {
+                let next_batch = self.batches.get_mut(next_batch_id).unwrap();
+                next_batch.started_at.get_or_insert_with(Instant::now);
+            }
+        }
 
         thread_id
     }
@@ -120,4 +189,57 @@ mod tests {
         assert_eq!(in_flight_tracker.num_in_flight_per_thread(), &[0, 0]);
         assert_eq!(in_flight_tracker.cus_in_flight_per_thread(), &[0, 0]);
     }
+
+    #[test]
+    fn test_in_flight_tracker_overdue_batches() {
+        let mut in_flight_tracker = InFlightTracker::new(2);
+        let batch_id_0 = in_flight_tracker.track_batch(2, 10_000, 0);
+        assert!(in_flight_tracker
+            .mark_overdue_batches(Duration::from_secs(60))
+            .is_empty());
+        assert!(!in_flight_tracker.is_stalled(0));
+
+        let stalled_threads = in_flight_tracker.mark_overdue_batches(Duration::ZERO);
+        assert!(stalled_threads.contains(0));
+        assert!(!stalled_threads.contains(1));
+        assert!(in_flight_tracker.is_stalled(0));
+        assert!(!in_flight_tracker.is_stalled(1));
+        // Batches are only reported once.
+        assert!(in_flight_tracker
+            .mark_overdue_batches(Duration::ZERO)
+            .is_empty());
+
+        // Completing the overdue batch clears the stall.
+        in_flight_tracker.complete_batch(batch_id_0);
+        assert!(!in_flight_tracker.is_stalled(0));
+    }
+
+    #[test]
+    fn test_in_flight_tracker_queued_batches_not_overdue() {
+        let mut in_flight_tracker = InFlightTracker::new(1);
+        let batch_id_0 = in_flight_tracker.track_batch(1, 1_000, 0);
+        let batch_id_1 = in_flight_tracker.track_batch(1, 1_000, 0);
+        let batch_id_2 = in_flight_tracker.track_batch(1, 1_000, 0);
+
+        // Only the batch being executed can be overdue.
+        assert!(in_flight_tracker
+            .mark_overdue_batches(Duration::ZERO)
+            .contains(0));
+        assert_eq!(in_flight_tracker.num_overdue_batches_per_thread, vec![1]);
+
+        // Taking back a queued batch does not start the next one.
+        in_flight_tracker.complete_batch(batch_id_2);
+        assert!(in_flight_tracker
+            .mark_overdue_batches(Duration::ZERO)
+            .is_empty());
+
+        // The next batch starts once the one being executed completes.
+        in_flight_tracker.complete_batch(batch_id_0);
+        assert!(!in_flight_tracker.is_stalled(0));
+        assert!(in_flight_tracker
+            .mark_overdue_batches(Duration::ZERO)
+            .contains(0));
+        in_flight_tracker.complete_batch(batch_id_1);
+        assert!(!in_flight_tracker.is_stalled(0));
+    }
 }
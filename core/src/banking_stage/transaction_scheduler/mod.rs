@@ -9,5 +9,6 @@ pub(crate) mod scheduler_error;
 mod scheduler_metrics;
 mod thread_aware_account_locks;
 mod transaction_priority_id;
+pub(crate) mod transaction_spill;
 mod transaction_state;
 pub(crate) mod transaction_state_container;
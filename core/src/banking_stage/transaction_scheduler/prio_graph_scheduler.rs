@@ -1,6 +1,6 @@
 use {
     super::{
-        in_flight_tracker::InFlightTracker,
+        in_flight_tracker::{InFlightTracker, DEFAULT_BATCH_DEADLINE},
         scheduler::Scheduler,
         scheduler_error::SchedulerError,
         thread_aware_account_locks::{ThreadAwareAccountLocks, ThreadId, ThreadSet, TryLockError},
@@ -17,6 +17,7 @@ use {
             transaction_state::TransactionState, transaction_state_container::StateContainer,
         },
     },
+    ahash::AHashMap,
     crossbeam_channel::{Receiver, Sender, TryRecvError},
     itertools::izip,
     prio_graph::{AccessKind, GraphNode, PrioGraph},
@@ -25,6 +26,7 @@ use {
     solana_runtime_transaction::transaction_with_meta::TransactionWithMeta,
     solana_sdk::{pubkey::Pubkey, saturating_add_assign},
     solana_svm_transaction::svm_message::SVMMessage,
+    std::time::Duration,
 };
 
 #[inline(always)]
@@ -42,11 +44,26 @@ type SchedulerPrioGraph = PrioGraph<
     fn(&TransactionPriorityId, &GraphNode<TransactionPriorityId>) -> TransactionPriorityId,
 >;
 
-pub(crate) struct PrioGraphSchedulerConfig {
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PrioGraphSchedulerConfig {
     pub max_scheduled_cus: u64,
     pub max_scanned_transactions_per_scheduling_pass: usize,
+    /// Number of transactions taken from the container into the prio-graph
+    /// ahead of scheduling, to detect conflicts before they happen.
     pub look_ahead_window_size: usize,
     pub target_transactions_per_batch: usize,
+    /// Maximum number of transactions writing to the same account within a
+    /// single scheduling pass. Further transactions writing to the account
+    /// are left for a later pass, leaving room for other accounts.
+    /// Conflicting transactions are never in the same batch, so this limits
+    /// the number of consecutive batches a single account can occupy.
+    pub max_transactions_per_account_per_pass: usize,
+    /// Priority added for every millisecond a transaction has been buffered,
+    /// when ordering transactions within the look-ahead window. Zero orders
+    /// transactions by fee priority alone.
+    pub age_priority_weight: u64,
+    /// Batches executing for longer than this mark their thread as stalled.
+    pub batch_deadline: Duration,
 }
 
 impl Default for PrioGraphSchedulerConfig {
@@ -56,6 +73,9 @@ impl Default for PrioGraphSchedulerConfig {
             max_scanned_transactions_per_scheduling_pass: 1000,
             look_ahead_window_size: 256,
             target_transactions_per_batch: TARGET_NUM_TRANSACTIONS_PER_BATCH,
+            max_transactions_per_account_per_pass: usize::MAX,
+            age_priority_weight: 0,
+            batch_deadline: DEFAULT_BATCH_DEADLINE,
         }
     }
 }
@@ -64,6 +84,8 @@ pub(crate) struct PrioGraphScheduler<Tx> {
     in_flight_tracker: InFlightTracker,
     account_locks: ThreadAwareAccountLocks,
     consume_work_senders: Vec<Sender<ConsumeWork<Tx>>>,
+    /// Used to take back work queued for stalled threads, if set.
+    consume_work_receivers: Option<Vec<Receiver<ConsumeWork<Tx>>>>,
     finished_consume_work_receiver: Receiver<FinishedConsumeWork<Tx>>,
     prio_graph: SchedulerPrioGraph,
     config: PrioGraphSchedulerConfig,
@@ -72,14 +94,19 @@ pub(crate) struct PrioGraphScheduler<Tx> {
 impl<Tx: TransactionWithMeta> PrioGraphScheduler<Tx> {
     pub(crate) fn new(
         consume_work_senders: Vec<Sender<ConsumeWork<Tx>>>,
+        consume_work_receivers: Option<Vec<Receiver<ConsumeWork<Tx>>>>,
         finished_consume_work_receiver: Receiver<FinishedConsumeWork<Tx>>,
         config: PrioGraphSchedulerConfig,
     ) -> Self {
         let num_threads = consume_work_senders.len();
+        if let Some(consume_work_receivers) = &consume_work_receivers {
+            assert_eq!(num_threads, consume_work_receivers.len());
+        }
         Self {
             in_flight_tracker: InFlightTracker::new(num_threads),
             account_locks: ThreadAwareAccountLocks::new(num_threads),
             consume_work_senders,
+            consume_work_receivers,
             finished_consume_work_receiver,
             prio_graph: PrioGraph::new(passthrough_priority),
             config,
@@ -114,8 +141,15 @@ impl<Tx: TransactionWithMeta> Scheduler<Tx> for PrioGraphScheduler<Tx> {
         let max_cu_per_thread = self.config.max_scheduled_cus / num_threads as u64;
 
         let mut schedulable_threads = ThreadSet::any(num_threads);
+        // Threads with overdue batches in flight are not given more work.
+        let mut unstalled_threads = ThreadSet::any(num_threads);
         for thread_id in 0..num_threads {
-            if self.in_flight_tracker.cus_in_flight_per_thread()[thread_id] >= max_cu_per_thread {
+            if self.in_flight_tracker.is_stalled(thread_id) {
+                unstalled_threads.remove(thread_id);
+                schedulable_threads.remove(thread_id);
+            } else if self.in_flight_tracker.cus_in_flight_per_thread()[thread_id]
+                >= max_cu_per_thread
+            {
                 schedulable_threads.remove(thread_id);
             }
         }
@@ -124,11 +158,19 @@ impl<Tx: TransactionWithMeta> Scheduler<Tx> for PrioGraphScheduler<Tx> {
                 num_scheduled: 0,
                 num_unschedulable: 0,
                 num_filtered_out: 0,
+                num_account_limited: 0,
                 filter_time_us: 0,
             });
         }
 
         let mut batches = Batches::new(num_threads, self.config.target_transactions_per_batch);
+        // Transactions scheduled per written account are only counted if
+        // the limit can be reached within the pass.
+        let max_transactions_per_account = (self.config.max_transactions_per_account_per_pass
+            < self.config.max_scanned_transactions_per_scheduling_pass)
+            .then_some(self.config.max_transactions_per_account_per_pass);
+        let mut write_account_counts = AHashMap::new();
+        let age_priority_weight = self.config.age_priority_weight;
         // Some transactions may be unschedulable due to multi-thread conflicts.
         // These transactions cannot be scheduled until some conflicting work is completed.
         // However, the scheduler should not allow other transactions that conflict with
@@ -171,9 +213,15 @@ impl<Tx: TransactionWithMeta> Scheduler<Tx> for PrioGraphScheduler<Tx> {
 
                 for (id, filter_result) in ids.iter().zip(&filter_array[..chunk_size]) {
                     if *filter_result {
+                        let id = if age_priority_weight == 0 {
+                            *id
+                        } else {
+                            let age = container.get_mut_transaction_state(id.id).unwrap().age();
+                            age_weighted_priority_id(*id, age, age_priority_weight)
+                        };
                         let transaction = container.get_transaction_ttl(id.id).unwrap();
                         prio_graph.insert_transaction(
-                            *id,
+                            id,
                             Self::get_transaction_account_access(transaction),
                         );
                     } else {
@@ -199,6 +247,7 @@ impl<Tx: TransactionWithMeta> Scheduler<Tx> for PrioGraphScheduler<Tx> {
         let mut num_scheduled: usize = 0;
         let mut num_sent: usize = 0;
         let mut num_unschedulable: usize = 0;
+        let mut num_account_limited: usize = 0;
         while num_scanned < self.config.max_scanned_transactions_per_scheduling_pass {
             // If nothing is in the main-queue of the `PrioGraph` then there's nothing left to schedule.
             if self.prio_graph.is_empty() {
@@ -220,7 +269,16 @@ impl<Tx: TransactionWithMeta> Scheduler<Tx> for PrioGraphScheduler<Tx> {
                     &pre_lock_filter,
                     &mut blocking_locks,
                     &mut self.account_locks,
-                    num_threads,
+                    unstalled_threads,
+                    |transaction| {
+                        max_transactions_per_account.is_some_and(|max_per_account| {
+                            is_write_account_limit_reached(
+                                &write_account_counts,
+                                transaction,
+                                max_per_account,
+                            )
+                        })
+                    },
                     |thread_set| {
                         Self::select_thread(
                             thread_set,
@@ -241,6 +299,10 @@ impl<Tx: TransactionWithMeta> Scheduler<Tx> for PrioGraphScheduler<Tx> {
                         unschedulable_ids.push(id);
                         saturating_add_assign!(num_unschedulable, 1);
                     }
+                    Err(TransactionSchedulingError::AccountLimitReached) => {
+                        unschedulable_ids.push(id);
+                        saturating_add_assign!(num_account_limited, 1);
+                    }
                     Ok(TransactionSchedulingInfo {
                         thread_id,
                         transaction,
@@ -248,6 +310,9 @@ impl<Tx: TransactionWithMeta> Scheduler<Tx> for PrioGraphScheduler<Tx> {
                         cost,
                     }) => {
                         saturating_add_assign!(num_scheduled, 1);
+                        if max_transactions_per_account.is_some() {
+                            count_write_accounts(&mut write_account_counts, &transaction);
+                        }
                         batches.transactions[thread_id].push(transaction);
                         batches.ids[thread_id].push(id.id);
                         batches.max_ages[thread_id].push(max_age);
@@ -298,12 +363,25 @@ impl<Tx: TransactionWithMeta> Scheduler<Tx> for PrioGraphScheduler<Tx> {
         saturating_add_assign!(num_sent, self.send_batches(&mut batches)?);
 
         // Push unschedulable ids back into the container
+        if age_priority_weight != 0 {
+            for id in &mut unschedulable_ids {
+                *id = fee_priority_id(container, *id);
+            }
+        }
         container.push_ids_into_queue(unschedulable_ids.into_iter());
 
         // Push remaining transactions back into the container
-        container.push_ids_into_queue(std::iter::from_fn(|| {
-            self.prio_graph.pop_and_unblock().map(|(id, _)| id)
-        }));
+        if age_priority_weight == 0 {
+            container.push_ids_into_queue(std::iter::from_fn(|| {
+                self.prio_graph.pop_and_unblock().map(|(id, _)| id)
+            }));
+        } else {
+            let remaining_ids: Vec<_> =
+                std::iter::from_fn(|| self.prio_graph.pop_and_unblock().map(|(id, _)| id))
+                    .map(|id| fee_priority_id(container, id))
+                    .collect();
+            container.push_ids_into_queue(remaining_ids.into_iter());
+        }
 
         // No more remaining items in the queue.
         // Clear here to make sure the next scheduling pass starts fresh
@@ -319,6 +397,7 @@ impl<Tx: TransactionWithMeta> Scheduler<Tx> for PrioGraphScheduler<Tx> {
             num_scheduled,
             num_unschedulable,
             num_filtered_out,
+            num_account_limited,
             filter_time_us: total_filter_time_us,
         })
     }
@@ -339,6 +418,7 @@ impl<Tx: TransactionWithMeta> Scheduler<Tx> for PrioGraphScheduler<Tx> {
             saturating_add_assign!(total_num_transactions, num_transactions);
             saturating_add_assign!(total_num_retryable, num_retryable);
         }
+        self.requeue_stalled_batches(container);
         Ok((total_num_transactions, total_num_retryable))
     }
 }
@@ -397,6 +477,47 @@ impl<Tx: TransactionWithMeta> PrioGraphScheduler<Tx> {
         }
     }
 
+    /// Takes back the batches still queued for threads that have overdue
+    /// batches in flight, and puts their transactions back into the container
+    /// so they can be scheduled on other threads. The overdue batch being
+    /// processed by a stalled thread cannot be taken back, but no more work
+    /// is scheduled on the thread until it completes.
+    fn requeue_stalled_batches(&mut self, container: &mut impl StateContainer<Tx>) {
+        let stalled_threads = self
+            .in_flight_tracker
+            .mark_overdue_batches(self.config.batch_deadline);
+        for thread_id in stalled_threads.contained_threads_iter() {
+            let mut num_requeued: usize = 0;
+            while let Some(ConsumeWork {
+                batch_id,
+                ids,
+                transactions,
+                max_ages,
+            }) = self
+                .consume_work_receivers
+                .as_ref()
+                .and_then(|receivers| receivers[thread_id].try_recv().ok())
+            {
+                self.complete_batch(batch_id, &transactions);
+                saturating_add_assign!(num_requeued, ids.len());
+                for (id, transaction, max_age) in izip!(ids, transactions, max_ages) {
+                    container.retry_transaction(
+                        id,
+                        SanitizedTransactionTTL {
+                            transaction,
+                            max_age,
+                        },
+                    );
+                }
+            }
+            datapoint_warn!(
+                "banking_stage_scheduler_stalled_worker",
+                ("thread_id", thread_id, i64),
+                ("num_requeued", num_requeued, i64)
+            );
+        }
+    }
+
     /// Mark a given `TransactionBatchId` as completed.
     /// This will update the internal tracking, including account locks.
     fn complete_batch(&mut self, batch_id: TransactionBatchId, transactions: &[Tx]) {
@@ -548,6 +669,65 @@ impl<Tx> Batches<Tx> {
     }
 }
 
+/// Returns true if any account written by `transaction` has already been
+/// written by `max_per_account` scheduled transactions.
+fn is_write_account_limit_reached(
+    write_account_counts: &AHashMap<Pubkey, usize>,
+    transaction: &impl SVMMessage,
+    max_per_account: usize,
+) -> bool {
+    transaction
+        .account_keys()
+        .iter()
+        .enumerate()
+        .any(|(index, key)| {
+            transaction.is_writable(index)
+                && write_account_counts
+                    .get(key)
+                    .is_some_and(|count| *count >= max_per_account)
+        })
+}
+
+/// Counts the accounts written by a scheduled `transaction`.
+fn count_write_accounts(
+    write_account_counts: &mut AHashMap<Pubkey, usize>,
+    transaction: &impl SVMMessage,
+) {
+    for (index, key) in transaction.account_keys().iter().enumerate() {
+        if transaction.is_writable(index) {
+            *write_account_counts.entry(*key).or_default() += 1;
+        }
+    }
+}
+
+/// Raises the priority of a transaction in the look-ahead window by
+/// `age_priority_weight` for every millisecond it has been buffered.
+fn age_weighted_priority_id(
+    id: TransactionPriorityId,
+    age: Duration,
+    age_priority_weight: u64,
+) -> TransactionPriorityId {
+    let age_ms = u64::try_from(age.as_millis()).unwrap_or(u64::MAX);
+    TransactionPriorityId::new(
+        id.priority
+            .saturating_add(age_ms.saturating_mul(age_priority_weight)),
+        id.id,
+    )
+}
+
+/// Returns the id with the fee priority the transaction is queued by in the
+/// container, undoing any age weighting.
+fn fee_priority_id<Tx: TransactionWithMeta>(
+    container: &mut impl StateContainer<Tx>,
+    id: TransactionPriorityId,
+) -> TransactionPriorityId {
+    let priority = container
+        .get_mut_transaction_state(id.id)
+        .expect("transaction state must exist")
+        .priority();
+    TransactionPriorityId::new(priority, id.id)
+}
+
 /// A transaction has been scheduled to a thread.
 pub(crate) struct TransactionSchedulingInfo<Tx> {
     pub thread_id: ThreadId,
@@ -565,6 +745,9 @@ pub(crate) enum TransactionSchedulingError {
     UnschedulableConflicts,
     /// Thread is not allowed to be scheduled on at this time.
     UnschedulableThread,
+    /// An account written by the transaction has reached its limit of
+    /// transactions per scheduling pass.
+    AccountLimitReached,
 }
 
 fn try_schedule_transaction<Tx: TransactionWithMeta>(
@@ -572,7 +755,8 @@ fn try_schedule_transaction<Tx: TransactionWithMeta>(
     pre_lock_filter: impl Fn(&Tx) -> bool,
     blocking_locks: &mut ReadWriteAccountSet,
     account_locks: &mut ThreadAwareAccountLocks,
-    num_threads: usize,
+    allowed_threads: ThreadSet,
+    account_limit_reached: impl Fn(&Tx) -> bool,
     thread_selector: impl Fn(ThreadSet) -> ThreadId,
 ) -> Result<TransactionSchedulingInfo<Tx>, TransactionSchedulingError> {
     let transaction = &transaction_state.transaction_ttl().transaction;
@@ -586,6 +770,13 @@ fn try_schedule_transaction<Tx: TransactionWithMeta>(
         return Err(TransactionSchedulingError::UnschedulableConflicts);
     }
 
+    // Lower priority transactions writing to the same accounts are blocked as
+    // well, so that they are not scheduled ahead of this one.
+    if account_limit_reached(transaction) {
+        blocking_locks.take_locks(transaction);
+        return Err(TransactionSchedulingError::AccountLimitReached);
+    }
+
     // Schedule the transaction if it can be.
     let account_keys = transaction.account_keys();
     let write_account_locks = account_keys
@@ -600,7 +791,7 @@ fn try_schedule_transaction<Tx: TransactionWithMeta>(
     let thread_id = match account_locks.try_lock_accounts(
         write_account_locks,
         read_account_locks,
-        ThreadSet::any(num_threads),
+        allowed_threads,
         thread_selector,
     ) {
         Ok(thread_id) => thread_id,
@@ -634,7 +825,7 @@ mod tests {
             transaction_scheduler::transaction_state_container::TransactionStateContainer,
         },
         crossbeam_channel::{unbounded, Receiver},
-        itertools::Itertools,
+        itertools::{Itertools, MinMaxResult},
         solana_runtime_transaction::runtime_transaction::RuntimeTransaction,
         solana_sdk::{
             compute_budget::ComputeBudgetInstruction,
@@ -663,6 +854,7 @@ mod tests {
         let (finished_consume_work_sender, finished_consume_work_receiver) = unbounded();
         let scheduler = PrioGraphScheduler::new(
             consume_work_senders,
+            None,
             finished_consume_work_receiver,
             PrioGraphSchedulerConfig::default(),
         );
@@ -719,34 +911,46 @@ mod tests {
     ) -> TransactionStateContainer<RuntimeTransaction<SanitizedTransaction>> {
         let mut container = TransactionStateContainer::with_capacity(capacity);
         for (from_keypair, to_pubkeys, lamports, compute_unit_price) in tx_infos.into_iter() {
-            let transaction = prioritized_tranfers(
+            insert_transaction(
+                &mut container,
                 from_keypair.borrow(),
                 to_pubkeys,
                 lamports,
                 compute_unit_price,
             );
-            let packet = Arc::new(
-                ImmutableDeserializedPacket::new(
-                    Packet::from_data(None, transaction.to_versioned_transaction()).unwrap(),
-                )
-                .unwrap(),
-            );
-            let transaction_ttl = SanitizedTransactionTTL {
-                transaction,
-                max_age: MaxAge::MAX,
-            };
-            const TEST_TRANSACTION_COST: u64 = 5000;
-            container.insert_new_transaction(
-                transaction_ttl,
-                packet,
-                compute_unit_price,
-                TEST_TRANSACTION_COST,
-            );
         }
 
         container
     }
 
+    fn insert_transaction(
+        container: &mut TransactionStateContainer<RuntimeTransaction<SanitizedTransaction>>,
+        from_keypair: &Keypair,
+        to_pubkeys: impl IntoIterator<Item = impl Borrow<Pubkey>>,
+        lamports: u64,
+        compute_unit_price: u64,
+    ) {
+        let transaction =
+            prioritized_tranfers(from_keypair, to_pubkeys, lamports, compute_unit_price);
+        let packet = Arc::new(
+            ImmutableDeserializedPacket::new(
+                Packet::from_data(None, transaction.to_versioned_transaction()).unwrap(),
+            )
+            .unwrap(),
+        );
+        let transaction_ttl = SanitizedTransactionTTL {
+            transaction,
+            max_age: MaxAge::MAX,
+        };
+        const TEST_TRANSACTION_COST: u64 = 5000;
+        container.insert_new_transaction(
+            transaction_ttl,
+            Some(packet),
+            compute_unit_price,
+            TEST_TRANSACTION_COST,
+        );
+    }
+
     fn collect_work(
         receiver: &Receiver<ConsumeWork<RuntimeTransaction<SanitizedTransaction>>>,
     ) -> (
@@ -818,6 +1022,126 @@ mod tests {
         assert_eq!(collect_work(&work_receivers[0]).1, vec![vec![1], vec![0]]);
     }
 
+    #[test]
+    fn test_requeue_stalled_batches() {
+        let (consume_work_senders, consume_work_receivers): (Vec<_>, Vec<_>) =
+            (0..2).map(|_| unbounded()).unzip();
+        let (finished_work_sender, finished_consume_work_receiver) = unbounded();
+        let mut scheduler = PrioGraphScheduler::new(
+            consume_work_senders,
+            Some(consume_work_receivers.clone()),
+            finished_consume_work_receiver,
+            PrioGraphSchedulerConfig {
+                batch_deadline: Duration::ZERO,
+                ..PrioGraphSchedulerConfig::default()
+            },
+        );
+        // Conflicting transactions are scheduled on the same thread, in
+        // separate batches.
+        let pubkey = Pubkey::new_unique();
+        let mut container = create_container([
+            (&Keypair::new(), &[pubkey], 1, 3),
+            (&Keypair::new(), &[pubkey], 1, 2),
+            (&Keypair::new(), &[pubkey], 1, 1),
+        ]);
+        let scheduling_summary = scheduler
+            .schedule(&mut container, test_pre_graph_filter, test_pre_lock_filter)
+            .unwrap();
+        assert_eq!(scheduling_summary.num_scheduled, 3);
+        let thread_id = (0..2)
+            .find(|thread_id| !consume_work_receivers[*thread_id].is_empty())
+            .unwrap();
+        assert_eq!(consume_work_receivers[thread_id].len(), 3);
+
+        // The worker picks up the first batch and stalls on it. The batches
+        // still queued are taken back.
+        let in_progress = consume_work_receivers[thread_id].try_recv().unwrap();
+        assert_eq!(scheduler.receive_completed(&mut container).unwrap(), (0, 0));
+        assert!(consume_work_receivers[thread_id].is_empty());
+        assert!(scheduler.in_flight_tracker.is_stalled(thread_id));
+
+        // The requeued transactions conflict with the stalled batch, and
+        // nothing is scheduled on the stalled thread.
+        let scheduling_summary = scheduler
+            .schedule(&mut container, test_pre_graph_filter, test_pre_lock_filter)
+            .unwrap();
+        assert_eq!(scheduling_summary.num_scheduled, 0);
+        assert_eq!(scheduling_summary.num_unschedulable, 2);
+
+        // Once the stalled batch completes, the requeued transactions are
+        // scheduled again.
+        finished_work_sender
+            .send(FinishedConsumeWork {
+                work: in_progress,
+                retryable_indexes: vec![],
+            })
+            .unwrap();
+        assert_eq!(scheduler.receive_completed(&mut container).unwrap(), (1, 0));
+        let scheduling_summary = scheduler
+            .schedule(&mut container, test_pre_graph_filter, test_pre_lock_filter)
+            .unwrap();
+        assert_eq!(scheduling_summary.num_scheduled, 2);
+    }
+
+    #[test]
+    fn test_schedule_account_limit() {
+        let (mut scheduler, work_receivers, _finished_work_sender) = create_test_frame(1);
+        scheduler.config.max_transactions_per_account_per_pass = 2;
+        let pubkey = Pubkey::new_unique();
+        let mut container = create_container([
+            (&Keypair::new(), &[pubkey], 1, 4),
+            (&Keypair::new(), &[pubkey], 1, 3),
+            (&Keypair::new(), &[pubkey], 1, 2),
+            (&Keypair::new(), &[Pubkey::new_unique()], 1, 1),
+        ]);
+
+        // The third transaction writing to `pubkey` is left for the next pass.
+        let scheduling_summary = scheduler
+            .schedule(&mut container, test_pre_graph_filter, test_pre_lock_filter)
+            .unwrap();
+        assert_eq!(scheduling_summary.num_scheduled, 3);
+        assert_eq!(scheduling_summary.num_unschedulable, 0);
+        assert_eq!(scheduling_summary.num_account_limited, 1);
+        assert_eq!(collect_work(&work_receivers[0]).1, [vec![0, 3], vec![1]]);
+
+        let scheduling_summary = scheduler
+            .schedule(&mut container, test_pre_graph_filter, test_pre_lock_filter)
+            .unwrap();
+        assert_eq!(scheduling_summary.num_scheduled, 1);
+        assert_eq!(scheduling_summary.num_account_limited, 0);
+        assert_eq!(collect_work(&work_receivers[0]).1, [vec![2]]);
+    }
+
+    #[test]
+    fn test_schedule_age_priority_weight() {
+        let (mut scheduler, work_receivers, _finished_work_sender) = create_test_frame(1);
+        scheduler.config.age_priority_weight = 1_000;
+        scheduler
+            .config
+            .max_scanned_transactions_per_scheduling_pass = 1;
+        let mut container = create_container([(&Keypair::new(), &[Pubkey::new_unique()], 1, 1)]);
+        std::thread::sleep(Duration::from_millis(2));
+        insert_transaction(
+            &mut container,
+            &Keypair::new(),
+            [Pubkey::new_unique()],
+            1,
+            2,
+        );
+
+        // The older transaction is scheduled first, despite its lower fee.
+        let scheduling_summary = scheduler
+            .schedule(&mut container, test_pre_graph_filter, test_pre_lock_filter)
+            .unwrap();
+        assert_eq!(scheduling_summary.num_scheduled, 1);
+        assert_eq!(collect_work(&work_receivers[0]).1, [vec![0]]);
+        // The remaining transaction is queued by its fee priority again.
+        assert_eq!(
+            container.get_min_max_priority(),
+            MinMaxResult::OneElement(2)
+        );
+    }
+
     #[test]
     fn test_schedule_consume_single_threaded_multi_batch() {
         let (mut scheduler, work_receivers, _finished_work_sender) = create_test_frame(1);
@@ -27,6 +27,7 @@ use {
     solana_accounts_db::account_locks::validate_account_locks,
     solana_cost_model::cost_model::CostModel,
     solana_measure::measure_us,
+    solana_perf::packet::PacketBatch,
     solana_runtime::{bank::Bank, bank_forks::BankForks},
     solana_runtime_transaction::{
         runtime_transaction::RuntimeTransaction, transaction_meta::StaticMeta,
@@ -47,6 +48,10 @@ use {
     },
 };
 
+/// Maximum number of spilled transactions reloaded into the container per
+/// call to `receive_and_buffer_packets`.
+const MAX_RELOADED_TRANSACTIONS: usize = 1_024;
+
 pub(crate) trait ReceiveAndBuffer {
     type Transaction: TransactionWithMeta + Send + Sync;
     type Container: StateContainer<Self::Transaction> + Send + Sync;
@@ -97,6 +102,10 @@ impl ReceiveAndBuffer for SanitizedTransactionReceiveAndBuffer {
             BufferedPacketsDecision::ForwardAndHold => (MAX_PACKET_RECEIVE_TIME, true),
         };
 
+        if should_buffer {
+            self.reload_spilled(container, timing_metrics, count_metrics);
+        }
+
         let (received_packet_results, receive_time_us) = measure_us!(self
             .packet_receiver
             .receive_packets(recv_timeout, MAX_RECEIVE_PACKETS, |packet| {
@@ -157,6 +166,28 @@ impl SanitizedTransactionReceiveAndBuffer {
         }
     }
 
+    /// Moves spilled transactions back into the container, if it has room.
+    fn reload_spilled(
+        &mut self,
+        container: &mut TransactionStateContainer<RuntimeTransaction<SanitizedTransaction>>,
+        timing_metrics: &mut SchedulerTimingMetrics,
+        count_metrics: &mut SchedulerCountMetrics,
+    ) {
+        let spilled = container.take_spilled(MAX_RELOADED_TRANSACTIONS);
+        if spilled.is_empty() {
+            return;
+        }
+        let num_reloaded = spilled.len();
+        count_metrics.update(|count_metrics| {
+            saturating_add_assign!(count_metrics.num_reloaded_from_spill, num_reloaded);
+        });
+        let packets = spilled
+            .into_iter()
+            .filter_map(|packet| ImmutableDeserializedPacket::new(packet).ok())
+            .collect();
+        self.buffer_packets(container, timing_metrics, count_metrics, packets);
+    }
+
     fn buffer_packets(
         &mut self,
         container: &mut TransactionStateContainer<RuntimeTransaction<SanitizedTransaction>>,
@@ -164,8 +195,6 @@ impl SanitizedTransactionReceiveAndBuffer {
         count_metrics: &mut SchedulerCountMetrics,
         packets: Vec<ImmutableDeserializedPacket>,
     ) {
-        // Convert to Arcs
-        let packets: Vec<_> = packets.into_iter().map(Arc::new).collect();
         // Sanitize packets, generate IDs, and insert into the container.
         let (root_bank, working_bank) = {
             let bank_forks = self.bank_forks.read().unwrap();
@@ -181,24 +210,40 @@ impl SanitizedTransactionReceiveAndBuffer {
         const CHUNK_SIZE: usize = 128;
         let lock_results: [_; CHUNK_SIZE] = core::array::from_fn(|_| Ok(()));
 
-        let mut arc_packets = ArrayVec::<_, CHUNK_SIZE>::new();
+        let mut packets_to_forward = ArrayVec::<_, CHUNK_SIZE>::new();
         let mut transactions = ArrayVec::<_, CHUNK_SIZE>::new();
         let mut max_ages = ArrayVec::<_, CHUNK_SIZE>::new();
         let mut fee_budget_limits_vec = ArrayVec::<_, CHUNK_SIZE>::new();
 
         let mut error_counts = TransactionErrorMetrics::default();
-        for chunk in packets.chunks(CHUNK_SIZE) {
+        let mut packets = packets.into_iter().peekable();
+        while packets.peek().is_some() {
+            let mut chunk_len: usize = 0;
             let mut post_sanitization_count: usize = 0;
-            chunk
-                .iter()
+            packets
+                .by_ref()
+                .take(CHUNK_SIZE)
+                .inspect(|_| saturating_add_assign!(chunk_len, 1))
                 .filter_map(|packet| {
-                    packet
-                        .build_sanitized_transaction(
-                            vote_only,
-                            root_bank.as_ref(),
-                            root_bank.get_reserved_account_keys(),
-                        )
-                        .map(|(tx, deactivation_slot)| (packet.clone(), tx, deactivation_slot))
+                    // Packets are only kept around to be forwarded. Otherwise,
+                    // move the transaction out of the packet, saving a clone.
+                    if self.forwarding_enabled {
+                        packet
+                            .build_sanitized_transaction(
+                                vote_only,
+                                root_bank.as_ref(),
+                                root_bank.get_reserved_account_keys(),
+                            )
+                            .map(|(tx, deactivation_slot)| (Some(packet), tx, deactivation_slot))
+                    } else {
+                        packet
+                            .into_sanitized_transaction(
+                                vote_only,
+                                root_bank.as_ref(),
+                                root_bank.get_reserved_account_keys(),
+                            )
+                            .map(|(tx, deactivation_slot)| (None, tx, deactivation_slot))
+                    }
                 })
                 .inspect(|_| saturating_add_assign!(post_sanitization_count, 1))
                 .filter(|(_packet, tx, _deactivation_slot)| {
@@ -217,7 +262,7 @@ impl SanitizedTransactionReceiveAndBuffer {
                         .ok()
                 })
                 .for_each(|(packet, tx, deactivation_slot, fee_budget_limits)| {
-                    arc_packets.push(packet);
+                    packets_to_forward.push(packet);
                     transactions.push(tx);
                     max_ages.push(calculate_max_age(
                         sanitized_epoch,
@@ -239,7 +284,7 @@ impl SanitizedTransactionReceiveAndBuffer {
             let mut num_dropped_on_capacity: usize = 0;
             let mut num_buffered: usize = 0;
             for ((((packet, transaction), max_age), fee_budget_limits), _check_result) in
-                arc_packets
+                packets_to_forward
                     .drain(..)
                     .zip(transactions.drain(..))
                     .zip(max_ages.drain(..))
@@ -260,14 +305,23 @@ impl SanitizedTransactionReceiveAndBuffer {
                     max_age,
                 };
 
-                if container.insert_new_transaction(transaction_ttl, packet, priority, cost) {
+                let dropped = match packet {
+                    Some(packet) => container.insert_new_transaction_with_packet(
+                        transaction_ttl,
+                        packet,
+                        priority,
+                        cost,
+                    ),
+                    None => container.insert_new_transaction(transaction_ttl, None, priority, cost),
+                };
+                if dropped {
                     saturating_add_assign!(num_dropped_on_capacity, 1);
                 }
                 saturating_add_assign!(num_buffered, 1);
             }
 
             // Update metrics for transactions that were dropped.
-            let num_dropped_on_sanitization = chunk.len().saturating_sub(post_sanitization_count);
+            let num_dropped_on_sanitization = chunk_len.saturating_sub(post_sanitization_count);
             let num_dropped_on_lock_validation =
                 post_sanitization_count.saturating_sub(post_lock_validation_count);
             let num_dropped_on_transaction_checks =
@@ -319,6 +373,17 @@ impl ReceiveAndBuffer for TransactionViewReceiveAndBuffer {
             (root_bank, working_bank)
         };
 
+        if !matches!(decision, BufferedPacketsDecision::Forward) {
+            self.reload_spilled(
+                container,
+                timing_metrics,
+                count_metrics,
+                decision,
+                &root_bank,
+                &working_bank,
+            );
+        }
+
         // Receive packet batches.
         const TIMEOUT: Duration = Duration::from_millis(10);
         let start = Instant::now();
@@ -384,6 +449,36 @@ impl ReceiveAndBuffer for TransactionViewReceiveAndBuffer {
 }
 
 impl TransactionViewReceiveAndBuffer {
+    /// Moves spilled transactions back into the container, if it has room.
+    fn reload_spilled(
+        &mut self,
+        container: &mut TransactionViewStateContainer,
+        timing_metrics: &mut SchedulerTimingMetrics,
+        count_metrics: &mut SchedulerCountMetrics,
+        decision: &BufferedPacketsDecision,
+        root_bank: &Bank,
+        working_bank: &Bank,
+    ) {
+        let spilled = container.take_spilled(MAX_RELOADED_TRANSACTIONS);
+        if spilled.is_empty() {
+            return;
+        }
+        let num_reloaded = spilled.len();
+        count_metrics.update(|count_metrics| {
+            saturating_add_assign!(count_metrics.num_reloaded_from_spill, num_reloaded);
+        });
+        let packet_batch = PacketBatch::new(spilled);
+        self.handle_packet_batch_message(
+            container,
+            timing_metrics,
+            count_metrics,
+            decision,
+            root_bank,
+            working_bank,
+            Arc::new(vec![packet_batch]),
+        );
+    }
+
     /// Return number of received packets.
     fn handle_packet_batch_message(
         &mut self,
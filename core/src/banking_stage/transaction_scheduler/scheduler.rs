@@ -15,6 +15,8 @@ pub(crate) trait Scheduler<Tx: TransactionWithMeta> {
     ) -> Result<SchedulingSummary, SchedulerError>;
 
     /// Receive completed batches of transactions without blocking.
+    /// Batches queued for threads that have stalled on an overdue batch are
+    /// taken back, and their transactions returned to `container`.
     /// Returns (num_transactions, num_retryable_transactions) on success.
     fn receive_completed(
         &mut self,
@@ -31,6 +33,9 @@ pub(crate) struct SchedulingSummary {
     pub num_unschedulable: usize,
     /// Number of transactions that were dropped due to filter.
     pub num_filtered_out: usize,
+    /// Number of transactions that were not scheduled because an account
+    /// they write to reached its limit of transactions per scheduling pass.
+    pub num_account_limited: usize,
     /// Time spent filtering transactions
     pub filter_time_us: u64,
 }
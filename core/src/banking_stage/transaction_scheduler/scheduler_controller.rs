@@ -9,6 +9,7 @@ use {
         scheduler_metrics::{
             SchedulerCountMetrics, SchedulerLeaderDetectionMetrics, SchedulerTimingMetrics,
         },
+        transaction_spill::TransactionSpill,
     },
     crate::banking_stage::{
         consume_worker::ConsumeWorkerMetrics,
@@ -75,12 +76,13 @@ where
         scheduler: S,
         worker_metrics: Vec<Arc<ConsumeWorkerMetrics>>,
         forwarder: Option<Forwarder<C>>,
+        spill: Option<TransactionSpill>,
     ) -> Self {
         Self {
             decision_maker,
             receive_and_buffer,
             bank_forks,
-            container: R::Container::with_capacity(TOTAL_BUFFERED_PACKETS),
+            container: R::Container::with_capacity_and_spill(TOTAL_BUFFERED_PACKETS, spill),
             scheduler,
             leader_detection_metrics: SchedulerLeaderDetectionMetrics::default(),
             count_metrics: SchedulerCountMetrics::default(),
@@ -124,8 +126,10 @@ where
             // Reset intervals when appropriate, regardless of report.
             let should_report = self.count_metrics.interval_has_data();
             let priority_min_max = self.container.get_min_max_priority();
+            let num_spilled = self.container.num_spilled();
             self.count_metrics.update(|count_metrics| {
                 count_metrics.update_priority_stats(priority_min_max);
+                count_metrics.num_spilled = num_spilled;
             });
             self.count_metrics
                 .maybe_report_and_reset_interval(should_report);
@@ -173,6 +177,10 @@ where
                         count_metrics.num_schedule_filtered_out,
                         scheduling_summary.num_filtered_out
                     );
+                    saturating_add_assign!(
+                        count_metrics.num_account_limited,
+                        scheduling_summary.num_account_limited
+                    );
                 });
 
                 self.timing_metrics.update(|timing_metrics| {
@@ -582,6 +590,7 @@ mod tests {
 
         let scheduler = PrioGraphScheduler::new(
             consume_work_senders,
+            None,
             finished_consume_work_receiver,
             PrioGraphSchedulerConfig::default(),
         );
@@ -592,6 +601,7 @@ mod tests {
             scheduler,
             vec![], // no actual workers with metrics to report, this can be empty
             None,
+            None,
         );
 
         (test_frame, scheduler_controller)
@@ -55,6 +55,9 @@ pub struct SchedulerCountMetricsInner {
     pub num_unschedulable: usize,
     /// Number of transactions that were filtered out during scheduling.
     pub num_schedule_filtered_out: usize,
+    /// Number of transactions that were left unscheduled because an account
+    /// they write to reached its per-pass limit.
+    pub num_account_limited: usize,
     /// Number of completed transactions received from workers.
     pub num_finished: usize,
     /// Number of transactions that were retryable.
@@ -77,6 +80,10 @@ pub struct SchedulerCountMetricsInner {
     pub num_dropped_on_age_and_status: usize,
     /// Number of transactions that were dropped due to exceeded capacity.
     pub num_dropped_on_capacity: usize,
+    /// Number of spilled transactions that were reloaded into the container.
+    pub num_reloaded_from_spill: usize,
+    /// Number of transactions in the spill, if any.
+    pub num_spilled: usize,
     /// Min prioritization fees in the transaction container
     pub min_prioritization_fees: u64,
     /// Max prioritization fees in the transaction container
@@ -122,6 +129,7 @@ impl SchedulerCountMetricsInner {
                 self.num_schedule_filtered_out,
                 i64
             ),
+            ("num_account_limited", self.num_account_limited, i64),
             ("num_finished", self.num_finished, i64),
             ("num_retryable", self.num_retryable, i64),
             ("num_forwarded", self.num_forwarded, i64),
@@ -148,6 +156,8 @@ impl SchedulerCountMetricsInner {
                 i64
             ),
             ("num_dropped_on_capacity", self.num_dropped_on_capacity, i64),
+            ("num_reloaded_from_spill", self.num_reloaded_from_spill, i64),
+            ("num_spilled", self.num_spilled, i64),
             ("min_priority", self.get_min_priority(), i64),
             ("max_priority", self.get_max_priority(), i64)
         );
@@ -163,6 +173,7 @@ impl SchedulerCountMetricsInner {
             || self.num_scheduled != 0
             || self.num_unschedulable != 0
             || self.num_schedule_filtered_out != 0
+            || self.num_account_limited != 0
             || self.num_finished != 0
             || self.num_retryable != 0
             || self.num_forwarded != 0
@@ -173,6 +184,7 @@ impl SchedulerCountMetricsInner {
             || self.num_dropped_on_clear != 0
             || self.num_dropped_on_age_and_status != 0
             || self.num_dropped_on_capacity != 0
+            || self.num_reloaded_from_spill != 0
     }
 
     fn reset(&mut self) {
@@ -181,6 +193,7 @@ impl SchedulerCountMetricsInner {
         self.num_scheduled = 0;
         self.num_unschedulable = 0;
         self.num_schedule_filtered_out = 0;
+        self.num_account_limited = 0;
         self.num_finished = 0;
         self.num_retryable = 0;
         self.num_forwarded = 0;
@@ -191,6 +204,8 @@ impl SchedulerCountMetricsInner {
         self.num_dropped_on_clear = 0;
         self.num_dropped_on_age_and_status = 0;
         self.num_dropped_on_capacity = 0;
+        self.num_reloaded_from_spill = 0;
+        self.num_spilled = 0;
         self.min_prioritization_fees = u64::MAX;
         self.max_prioritization_fees = 0;
     }
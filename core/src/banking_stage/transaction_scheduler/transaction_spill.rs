@@ -0,0 +1,548 @@
+//! Disk-backed overflow tier for the transaction state container.
+//!
+//! When the container is full, the lowest priority transactions are written
+//! to a spill file instead of being dropped, and are reloaded in priority
+//! order once the container has room again. Spilled transactions are kept
+//! as serialized wire bytes, along with the flags of the packet they were
+//! received in, and go through sanitization again on reload, since the bank
+//! they were checked against may be long gone by then.
+//!
+//! The spill file is only ever read and written by a background thread, so
+//! that the scheduler thread does not wait on the disk. The scheduler thread
+//! keeps the priority index in memory: it decides right away what is spilled
+//! and what is dropped, and reloads requested in one call are picked up by a
+//! later one. Requests to the background thread are queued in a bounded
+//! channel: while it is full, evicted transactions are dropped instead of
+//! spilled, and reloads are retried on a later call.
+//!
+//! The spill file is truncated on startup; its content does not survive a
+//! restart.
+use {
+    crossbeam_channel::{bounded, unbounded, Receiver, Sender, TrySendError},
+    min_max_heap::MinMaxHeap,
+    solana_sdk::packet::{Packet, PacketFlags},
+    std::{
+        collections::HashMap,
+        fs::{self, File, OpenOptions},
+        io::{self, Read, Seek, SeekFrom, Write},
+        path::PathBuf,
+        thread::{Builder, JoinHandle},
+    },
+};
+
+const SPILL_FILE_NAME: &str = "banking_stage_transactions.spill";
+/// The spill file is compacted once it is this many times larger than the
+/// maximum size of spilled transactions.
+const COMPACTION_FACTOR: u64 = 2;
+/// Each spilled transaction is stored as the bits of its packet flags,
+/// followed by its wire bytes.
+const RECORD_HEADER_LEN: u64 = 1;
+/// Maximum number of requests queued to the spill thread.
+const MAX_QUEUED_SPILL_REQUESTS: usize = 4096;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TransactionSpillConfig {
+    /// Directory the spill file is created in.
+    pub path: PathBuf,
+    /// Maximum number of bytes of spilled transactions kept on disk. Once
+    /// reached, the lowest priority spilled transactions are dropped.
+    pub max_bytes: u64,
+}
+
+/// A spilled transaction in the priority index.
+/// Ordered by priority first, so that the heap pops the highest (or lowest)
+/// priority transaction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct SpilledTransaction {
+    priority: u64,
+    id: u64,
+    len: u32,
+}
+
+enum SpillRequest {
+    Write {
+        id: u64,
+        flags: PacketFlags,
+        data: Vec<u8>,
+    },
+    Discard {
+        ids: Vec<u64>,
+    },
+    Read {
+        ids: Vec<u64>,
+    },
+}
+
+pub(crate) struct TransactionSpill {
+    index: MinMaxHeap<SpilledTransaction>,
+    next_id: u64,
+    /// Number of bytes of the transactions in `index`.
+    num_bytes: u64,
+    max_bytes: u64,
+    /// Number of transactions requested from the spill thread, and not
+    /// received back yet.
+    num_reloading: usize,
+    reloaded: Vec<Packet>,
+    /// Transactions dropped from `index`, to be discarded from the spill file
+    /// once the request channel has room.
+    pending_discards: Vec<u64>,
+    request_sender: Option<Sender<SpillRequest>>,
+    /// The number of transactions requested along with those read back.
+    reloaded_receiver: Receiver<(usize, Vec<Packet>)>,
+    thread_hdl: Option<JoinHandle<()>>,
+}
+
+impl TransactionSpill {
+    pub(crate) fn new(config: &TransactionSpillConfig) -> io::Result<Self> {
+        let spill_file = SpillFile::new(config)?;
+        let (request_sender, request_receiver) = bounded(MAX_QUEUED_SPILL_REQUESTS);
+        let (reloaded_sender, reloaded_receiver) = unbounded();
+        let thread_hdl = Builder::new()
+            .name("solBnkTxSpill".to_string())
+            .spawn(move || spill_file.run(request_receiver, reloaded_sender))?;
+        Ok(Self {
+            index: MinMaxHeap::new(),
+            next_id: 0,
+            num_bytes: 0,
+            max_bytes: config.max_bytes,
+            num_reloading: 0,
+            reloaded: Vec::new(),
+            pending_discards: Vec::new(),
+            request_sender: Some(request_sender),
+            reloaded_receiver,
+            thread_hdl: Some(thread_hdl),
+        })
+    }
+
+    /// Number of transactions currently spilled.
+    pub(crate) fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    /// Number of transactions being reloaded, including those reloaded but
+    /// not taken yet.
+    pub(crate) fn num_reloading(&self) -> usize {
+        self.num_reloading + self.reloaded.len()
+    }
+
+    /// Spills the transaction. If the spill is full, lower priority spilled
+    /// transactions are dropped to make room.
+    /// Returns the number of transactions dropped, including `data` itself
+    /// if it has too low a priority to fit, or if the spill thread is behind.
+    /// In the latter case it is the lowest priority transaction around, since
+    /// it was just evicted from the container.
+    pub(crate) fn push(&mut self, priority: u64, flags: PacketFlags, data: Vec<u8>) -> usize {
+        let len = data.len() as u64;
+        if len > self.max_bytes || self.request_sender().is_full() {
+            return 1;
+        }
+        let mut num_dropped = 0;
+        while self.num_bytes.saturating_add(len) > self.max_bytes {
+            match self.index.peek_min() {
+                Some(lowest) if lowest.priority < priority => {
+                    let lowest = self.index.pop_min().expect("index is not empty");
+                    self.num_bytes -= u64::from(lowest.len);
+                    self.pending_discards.push(lowest.id);
+                    num_dropped += 1;
+                }
+                _ => return num_dropped + 1,
+            }
+        }
+
+        let id = self.next_id;
+        let spilled = SpilledTransaction {
+            priority,
+            id,
+            len: data.len() as u32,
+        };
+        // Only this thread sends requests, so the channel still has room,
+        // unless the spill thread is gone.
+        if self
+            .try_send(SpillRequest::Write { id, flags, data })
+            .is_err()
+        {
+            self.flush_discards();
+            return num_dropped + 1;
+        }
+        self.next_id += 1;
+        self.index.push(spilled);
+        self.num_bytes += len;
+        self.flush_discards();
+        num_dropped
+    }
+
+    /// Requests the `max` highest priority spilled transactions to be read
+    /// back, see `take_reloaded()`.
+    /// If the request channel is full, nothing is requested until a later
+    /// call.
+    pub(crate) fn reload(&mut self, max: usize) {
+        self.flush_discards();
+        let spilled: Vec<_> = std::iter::from_fn(|| self.index.pop_max())
+            .take(max)
+            .collect();
+        if spilled.is_empty() {
+            return;
+        }
+        let ids = spilled.iter().map(|spilled| spilled.id).collect();
+        if self.try_send(SpillRequest::Read { ids }).is_err() {
+            self.index.extend(spilled);
+            return;
+        }
+        self.num_bytes -= spilled
+            .iter()
+            .map(|spilled| u64::from(spilled.len))
+            .sum::<u64>();
+        self.num_reloading += spilled.len();
+    }
+
+    /// Returns the packets of the transactions read back since the last call.
+    pub(crate) fn take_reloaded(&mut self) -> Vec<Packet> {
+        for (num_requested, packets) in self.reloaded_receiver.try_iter() {
+            self.num_reloading -= num_requested;
+            self.reloaded.extend(packets);
+        }
+        std::mem::take(&mut self.reloaded)
+    }
+
+    /// Blocks until all the transactions requested are read back.
+    #[cfg(test)]
+    pub(crate) fn wait_for_reloads(&mut self) {
+        while self.num_reloading > 0 {
+            let (num_requested, packets) = self.reloaded_receiver.recv().unwrap();
+            self.num_reloading -= num_requested;
+            self.reloaded.extend(packets);
+        }
+    }
+
+    /// Requests the spill thread to discard the dropped transactions, unless
+    /// the request channel is full, in which case they are kept for a later
+    /// call.
+    fn flush_discards(&mut self) {
+        if self.pending_discards.is_empty() {
+            return;
+        }
+        let ids = std::mem::take(&mut self.pending_discards);
+        if let Err(SpillRequest::Discard { ids }) = self.try_send(SpillRequest::Discard { ids }) {
+            self.pending_discards = ids;
+        }
+    }
+
+    /// Queues the request to the spill thread, or returns it back if the
+    /// request channel is full.
+    fn try_send(&self, request: SpillRequest) -> Result<(), SpillRequest> {
+        self.request_sender()
+            .try_send(request)
+            .map_err(TrySendError::into_inner)
+    }
+
+    fn request_sender(&self) -> &Sender<SpillRequest> {
+        self.request_sender
+            .as_ref()
+            .expect("sender is only dropped in drop()")
+    }
+}
+
+impl Drop for TransactionSpill {
+    fn drop(&mut self) {
+        drop(self.request_sender.take());
+        if let Some(thread_hdl) = self.thread_hdl.take() {
+            let _ = thread_hdl.join();
+        }
+    }
+}
+
+/// The spill file, owned by the spill thread.
+struct SpillFile {
+    path: PathBuf,
+    file: File,
+    /// Offset and length of the record of each spilled transaction.
+    records: HashMap<u64, (u64, u64)>,
+    /// Number of bytes written to the spill file, live or not.
+    file_len: u64,
+    max_bytes: u64,
+}
+
+impl SpillFile {
+    fn new(config: &TransactionSpillConfig) -> io::Result<Self> {
+        fs::create_dir_all(&config.path)?;
+        let path = config.path.join(SPILL_FILE_NAME);
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&path)?;
+        Ok(Self {
+            path,
+            file,
+            records: HashMap::new(),
+            file_len: 0,
+            max_bytes: config.max_bytes,
+        })
+    }
+
+    fn run(
+        mut self,
+        request_receiver: Receiver<SpillRequest>,
+        reloaded_sender: Sender<(usize, Vec<Packet>)>,
+    ) {
+        for request in request_receiver {
+            match request {
+                SpillRequest::Write { id, flags, data } => {
+                    if let Err(err) = self.write(id, flags, &data) {
+                        warn!("Failed to spill transaction: {err}");
+                    }
+                }
+                SpillRequest::Discard { ids } => ids.into_iter().for_each(|id| self.discard(id)),
+                SpillRequest::Read { ids } => {
+                    let num_requested = ids.len();
+                    let packets = ids
+                        .into_iter()
+                        .filter_map(|id| {
+                            self.read(id)
+                                .inspect_err(|err| {
+                                    warn!("Failed to reload spilled transaction: {err}")
+                                })
+                                .ok()
+                                .flatten()
+                        })
+                        .collect();
+                    if reloaded_sender.send((num_requested, packets)).is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    fn write(&mut self, id: u64, flags: PacketFlags, data: &[u8]) -> io::Result<()> {
+        self.maybe_compact()?;
+        self.file.seek(SeekFrom::Start(self.file_len))?;
+        self.file.write_all(&[flags.bits()])?;
+        self.file.write_all(data)?;
+        let len = RECORD_HEADER_LEN + data.len() as u64;
+        self.records.insert(id, (self.file_len, len));
+        self.file_len += len;
+        Ok(())
+    }
+
+    fn discard(&mut self, id: u64) {
+        self.records.remove(&id);
+    }
+
+    /// Reads back the spilled transaction as a packet, unless it does not
+    /// fit in one.
+    fn read(&mut self, id: u64) -> io::Result<Option<Packet>> {
+        let Some((offset, len)) = self.records.remove(&id) else {
+            return Ok(None);
+        };
+        let mut record = vec![0u8; len as usize];
+        self.file.seek(SeekFrom::Start(offset))?;
+        self.file.read_exact(&mut record)?;
+        if self.records.is_empty() {
+            self.file.set_len(0)?;
+            self.file_len = 0;
+        }
+
+        let (flags, data) = record.split_first().expect("records are never empty");
+        let mut packet = Packet::default();
+        let Some(buffer) = packet.buffer_mut().get_mut(..data.len()) else {
+            return Ok(None);
+        };
+        buffer.copy_from_slice(data);
+        let meta = packet.meta_mut();
+        meta.size = data.len();
+        meta.flags = PacketFlags::from_bits_truncate(*flags);
+        Ok(Some(packet))
+    }
+
+    /// Reclaims the space of reloaded and dropped transactions by moving live
+    /// records towards the start of the file, in offset order, so that data
+    /// is never overwritten before it is moved.
+    fn maybe_compact(&mut self) -> io::Result<()> {
+        if self.file_len <= self.max_bytes.saturating_mul(COMPACTION_FACTOR) {
+            return Ok(());
+        }
+        let mut records: Vec<_> = self.records.iter_mut().collect();
+        records.sort_unstable_by_key(|(_id, (offset, _len))| *offset);
+        let mut buffer = Vec::new();
+        let mut file_len = 0;
+        for (_id, (offset, len)) in records {
+            if *offset != file_len {
+                buffer.resize(*len as usize, 0);
+                self.file.seek(SeekFrom::Start(*offset))?;
+                self.file.read_exact(&mut buffer)?;
+                self.file.seek(SeekFrom::Start(file_len))?;
+                self.file.write_all(&buffer)?;
+                *offset = file_len;
+            }
+            file_len += *len;
+        }
+        self.file.set_len(file_len)?;
+        self.file_len = file_len;
+        Ok(())
+    }
+}
+
+impl Drop for SpillFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, tempfile::TempDir};
+
+    fn new_config(max_bytes: u64) -> (TempDir, TransactionSpillConfig) {
+        let dir = TempDir::new().unwrap();
+        let config = TransactionSpillConfig {
+            path: dir.path().to_path_buf(),
+            max_bytes,
+        };
+        (dir, config)
+    }
+
+    fn reload(spill: &mut TransactionSpill, max: usize) -> Vec<Vec<u8>> {
+        spill.reload(max);
+        spill.wait_for_reloads();
+        spill
+            .take_reloaded()
+            .iter()
+            .map(|packet| packet.data(..).unwrap().to_vec())
+            .collect()
+    }
+
+    #[test]
+    fn test_reload_in_priority_order() {
+        let (_dir, config) = new_config(1024);
+        let mut spill = TransactionSpill::new(&config).unwrap();
+        for priority in [3u64, 7, 1, 5] {
+            let data = vec![priority as u8; 10];
+            assert_eq!(spill.push(priority, PacketFlags::empty(), data), 0);
+        }
+        assert_eq!(spill.len(), 4);
+        assert_eq!(
+            reload(&mut spill, 3),
+            vec![vec![7; 10], vec![5; 10], vec![3; 10]]
+        );
+        assert_eq!(reload(&mut spill, 3), vec![vec![1; 10]]);
+        assert!(spill.is_empty());
+        assert_eq!(reload(&mut spill, 3), Vec::<Vec<u8>>::new());
+        drop(spill);
+        assert!(!config.path.join(SPILL_FILE_NAME).exists());
+    }
+
+    #[test]
+    fn test_persist_packet_flags() {
+        let (_dir, config) = new_config(1024);
+        let mut spill = TransactionSpill::new(&config).unwrap();
+        let flags = PacketFlags::FROM_STAKED_NODE | PacketFlags::SIMPLE_VOTE_TX;
+        assert_eq!(spill.push(1, flags, vec![1; 10]), 0);
+        spill.reload(1);
+        spill.wait_for_reloads();
+        let packets = spill.take_reloaded();
+        assert_eq!(packets.len(), 1);
+        assert_eq!(packets[0].meta().flags, flags);
+        assert_eq!(packets[0].meta().size, 10);
+    }
+
+    #[test]
+    fn test_drop_lowest_priority_when_full() {
+        let (_dir, config) = new_config(30);
+        let mut spill = TransactionSpill::new(&config).unwrap();
+        for priority in [4u64, 2, 6] {
+            let data = vec![priority as u8; 10];
+            assert_eq!(spill.push(priority, PacketFlags::empty(), data), 0);
+        }
+        // A lower priority transaction than everything spilled is dropped.
+        assert_eq!(spill.push(1, PacketFlags::empty(), vec![1; 10]), 1);
+        // A higher priority transaction replaces the lowest spilled ones.
+        assert_eq!(spill.push(5, PacketFlags::empty(), vec![5; 20]), 2);
+        assert_eq!(spill.len(), 2);
+        assert_eq!(reload(&mut spill, 2), vec![vec![6; 10], vec![5; 20]]);
+        // Transactions larger than the spill are never kept.
+        assert_eq!(spill.push(u64::MAX, PacketFlags::empty(), vec![0; 31]), 1);
+        assert!(spill.is_empty());
+    }
+
+    #[test]
+    fn test_full_request_channel() {
+        // No spill thread, so that the test controls when requests are
+        // picked up.
+        let (request_sender, request_receiver) = bounded(2);
+        let (_reloaded_sender, reloaded_receiver) = unbounded();
+        let mut spill = TransactionSpill {
+            index: MinMaxHeap::new(),
+            next_id: 0,
+            num_bytes: 0,
+            max_bytes: 20,
+            num_reloading: 0,
+            reloaded: Vec::new(),
+            pending_discards: Vec::new(),
+            request_sender: Some(request_sender),
+            reloaded_receiver,
+            thread_hdl: None,
+        };
+        assert_eq!(spill.push(1, PacketFlags::empty(), vec![1; 10]), 0);
+        assert_eq!(spill.push(2, PacketFlags::empty(), vec![2; 10]), 0);
+        // The evicted transaction is dropped while the channel is full.
+        assert_eq!(spill.push(3, PacketFlags::empty(), vec![3; 10]), 1);
+        assert_eq!(spill.len(), 2);
+        // Nothing is requested while the channel is full.
+        spill.reload(1);
+        assert_eq!(spill.len(), 2);
+        assert_eq!(spill.num_reloading(), 0);
+
+        assert_eq!(request_receiver.try_iter().count(), 2);
+        // The dropped transaction is discarded once the channel has room.
+        assert_eq!(spill.push(4, PacketFlags::empty(), vec![4; 10]), 1);
+        assert!(spill.pending_discards.is_empty());
+        let requests: Vec<_> = request_receiver.try_iter().collect();
+        assert!(matches!(
+            requests.as_slice(),
+            [
+                SpillRequest::Write { id: 2, .. },
+                SpillRequest::Discard { ids: discarded },
+            ] if discarded == &[0],
+        ));
+        spill.reload(1);
+        assert_eq!(spill.len(), 1);
+        assert_eq!(spill.num_reloading(), 1);
+        assert!(matches!(
+            request_receiver.try_recv(),
+            Ok(SpillRequest::Read { ids }) if ids == [2],
+        ));
+        assert!(request_receiver.is_empty());
+    }
+
+    #[test]
+    fn test_compaction() {
+        let (_dir, config) = new_config(100);
+        let mut spill_file = SpillFile::new(&config).unwrap();
+        for id in 0..50u64 {
+            spill_file
+                .write(2 * id, PacketFlags::empty(), &[id as u8; 10])
+                .unwrap();
+            spill_file
+                .write(2 * id + 1, PacketFlags::empty(), &[id as u8; 10])
+                .unwrap();
+            let packet = spill_file.read(2 * id).unwrap().unwrap();
+            assert_eq!(packet.data(..), Some(&[id as u8; 10][..]));
+            if id >= 5 {
+                spill_file.discard(2 * (id - 5) + 1);
+            }
+            assert!(spill_file.file_len <= 100 * COMPACTION_FACTOR + 2 * 11);
+        }
+        assert_eq!(spill_file.records.len(), 5);
+        for id in 45..50u64 {
+            let packet = spill_file.read(2 * id + 1).unwrap().unwrap();
+            assert_eq!(packet.data(..), Some(&[id as u8; 10][..]));
+        }
+        assert_eq!(spill_file.file.metadata().unwrap().len(), 0);
+    }
+}
@@ -3,7 +3,10 @@ use {
         immutable_deserialized_packet::ImmutableDeserializedPacket, scheduler_messages::MaxAge,
     },
     solana_sdk::packet::{self},
-    std::sync::Arc,
+    std::{
+        sync::Arc,
+        time::{Duration, Instant},
+    },
 };
 
 /// Simple wrapper type to tie a sanitized transaction to max age slot.
@@ -40,6 +43,7 @@ pub(crate) enum TransactionState<Tx> {
         priority: u64,
         cost: u64,
         should_forward: bool,
+        received_at: Instant,
     },
     /// The transaction is currently scheduled or being processed.
     Pending {
@@ -47,6 +51,7 @@ pub(crate) enum TransactionState<Tx> {
         priority: u64,
         cost: u64,
         should_forward: bool,
+        received_at: Instant,
     },
     /// Only used during transition.
     Transitioning,
@@ -70,6 +75,7 @@ impl<Tx> TransactionState<Tx> {
             priority,
             cost,
             should_forward,
+            received_at: Instant::now(),
         }
     }
 
@@ -93,6 +99,15 @@ impl<Tx> TransactionState<Tx> {
         }
     }
 
+    /// Return how long the transaction has been buffered.
+    pub(crate) fn age(&self) -> Duration {
+        match self {
+            Self::Unprocessed { received_at, .. } => received_at.elapsed(),
+            Self::Pending { received_at, .. } => received_at.elapsed(),
+            Self::Transitioning => unreachable!(),
+        }
+    }
+
     /// Return whether packet should be attempted to be forwarded.
     pub(crate) fn should_forward(&self) -> bool {
         match self {
@@ -142,12 +157,14 @@ impl<Tx> TransactionState<Tx> {
                 priority,
                 cost,
                 should_forward: forwarded,
+                received_at,
             } => {
                 *self = TransactionState::Pending {
                     packet,
                     priority,
                     cost,
                     should_forward: forwarded,
+                    received_at,
                 };
                 transaction_ttl
             }
@@ -175,6 +192,7 @@ impl<Tx> TransactionState<Tx> {
                 priority,
                 cost,
                 should_forward: forwarded,
+                received_at,
             } => {
                 *self = Self::Unprocessed {
                     transaction_ttl,
@@ -182,6 +200,7 @@ impl<Tx> TransactionState<Tx> {
                     priority,
                     cost,
                     should_forward: forwarded,
+                    received_at,
                 }
             }
             Self::Transitioning => unreachable!(),
@@ -202,6 +221,15 @@ impl<Tx> TransactionState<Tx> {
         }
     }
 
+    /// Consumes the state, returning the packet of the transaction.
+    pub(crate) fn into_packet(self) -> Option<Arc<ImmutableDeserializedPacket>> {
+        match self {
+            Self::Unprocessed { packet, .. } => packet,
+            Self::Pending { packet, .. } => packet,
+            Self::Transitioning => unreachable!(),
+        }
+    }
+
     /// Internal helper to transitioning between states.
     /// Replaces `self` with a dummy state that will immediately be overwritten in transition.
     fn take(&mut self) -> Self {
@@ -1,6 +1,7 @@
 use {
     super::{
         transaction_priority_id::TransactionPriorityId,
+        transaction_spill::TransactionSpill,
         transaction_state::{SanitizedTransactionTTL, TransactionState},
     },
     crate::banking_stage::{
@@ -14,9 +15,14 @@ use {
     solana_runtime_transaction::{
         runtime_transaction::RuntimeTransaction, transaction_with_meta::TransactionWithMeta,
     },
-    solana_sdk::packet::PACKET_DATA_SIZE,
+    solana_sdk::packet::{Packet, PacketFlags, PACKET_DATA_SIZE},
     std::sync::Arc,
 };
+#[cfg(feature = "dev-context-only-utils")]
+use {
+    crate::banking_stage::scheduler_messages::MaxAge, solana_runtime::bank::Bank,
+    solana_sdk::transaction::SanitizedTransaction,
+};
 
 /// This structure will hold `TransactionState` for the entirety of a
 /// transaction's lifetime in the scheduler and BankingStage as a whole.
@@ -42,16 +48,31 @@ use {
 /// be removed only after the id is removed from the queue.
 ///
 /// The container maintains a fixed capacity. If the queue is full when pushing
-/// a new transaction, the lowest priority transaction will be dropped.
+/// a new transaction, the lowest priority transaction will be dropped, or
+/// written to the optional `TransactionSpill` to be reloaded later.
+///
+/// Packets kept for forwarding are allocated from an arena indexed like the
+/// map: once a transaction is removed, the allocation of its packet is reused
+/// for the next transaction inserted with the same id, unless the packet is
+/// still held elsewhere.
 pub(crate) struct TransactionStateContainer<Tx: TransactionWithMeta> {
     capacity: usize,
     priority_queue: MinMaxHeap<TransactionPriorityId>,
     id_to_transaction_state: Slab<TransactionState<Tx>>,
+    packet_arena: Box<[Option<Arc<ImmutableDeserializedPacket>>]>,
+    spill: Option<TransactionSpill>,
 }
 
 pub(crate) trait StateContainer<Tx: TransactionWithMeta> {
     /// Create a new `TransactionStateContainer` with the given capacity.
-    fn with_capacity(capacity: usize) -> Self;
+    fn with_capacity(capacity: usize) -> Self {
+        Self::with_capacity_and_spill(capacity, None)
+    }
+
+    /// Create a new `TransactionStateContainer` with the given capacity,
+    /// which spills transactions beyond capacity to `spill` instead of
+    /// dropping them.
+    fn with_capacity_and_spill(capacity: usize, spill: Option<TransactionSpill>) -> Self;
 
     /// Returns true if the queue is empty.
     fn is_empty(&self) -> bool;
@@ -84,10 +105,12 @@ pub(crate) trait StateContainer<Tx: TransactionWithMeta> {
 
     /// Pushes transaction ids into the priority queue. If the queue if full,
     /// the lowest priority transactions will be dropped (removed from the
-    /// queue and map) **after** all ids have been pushed.
+    /// queue and map) **after** all ids have been pushed. If the container
+    /// has a spill, they are written to it instead.
     /// To avoid allocating, the caller should not push more than
     /// [`EXTRA_CAPACITY`] ids in a call.
-    /// Returns the number of dropped transactions.
+    /// Returns the number of dropped transactions, not counting those that
+    /// were spilled.
     fn push_ids_into_queue(
         &mut self,
         priority_ids: impl Iterator<Item = TransactionPriorityId>,
@@ -97,6 +120,15 @@ pub(crate) trait StateContainer<Tx: TransactionWithMeta> {
     fn remove_by_id(&mut self, id: TransactionId);
 
     fn get_min_max_priority(&self) -> MinMaxResult<u64>;
+
+    /// Returns the packets of the spilled transactions reloaded since the
+    /// last call, and requests up to `max` more of the highest priority
+    /// spilled transactions, as many as the container has room for. The
+    /// packets need to be sanitized and inserted again by the caller.
+    fn take_spilled(&mut self, max: usize) -> Vec<Packet>;
+
+    /// Returns the number of transactions currently spilled.
+    fn num_spilled(&self) -> usize;
 }
 
 // Extra capacity is added because some additional space is needed when
@@ -104,11 +136,18 @@ pub(crate) trait StateContainer<Tx: TransactionWithMeta> {
 pub(crate) const EXTRA_CAPACITY: usize = 64;
 
 impl<Tx: TransactionWithMeta> StateContainer<Tx> for TransactionStateContainer<Tx> {
-    fn with_capacity(capacity: usize) -> Self {
+    fn with_capacity_and_spill(capacity: usize, spill: Option<TransactionSpill>) -> Self {
+        let id_to_transaction_state = Slab::with_capacity(capacity + EXTRA_CAPACITY);
+        // Ids range up to the capacity of the slab, which may exceed the requested one.
+        let packet_arena = (0..id_to_transaction_state.capacity())
+            .map(|_| None)
+            .collect();
         Self {
             capacity,
             priority_queue: MinMaxHeap::with_capacity(capacity + EXTRA_CAPACITY),
-            id_to_transaction_state: Slab::with_capacity(capacity + EXTRA_CAPACITY),
+            id_to_transaction_state,
+            packet_arena,
+            spill,
         }
     }
 
@@ -145,21 +184,25 @@ impl<Tx: TransactionWithMeta> StateContainer<Tx> for TransactionStateContainer<T
         // greater than or equal to the number of elements in the queue.
         // To avoid the map going over capacity, we use the length of the
         // map here instead of the queue.
-        let num_dropped = self
+        let num_evicted = self
             .id_to_transaction_state
             .len()
             .saturating_sub(self.capacity);
 
-        for _ in 0..num_dropped {
+        let mut num_dropped = 0;
+        for _ in 0..num_evicted {
             let priority_id = self.priority_queue.pop_min().expect("queue is not empty");
-            self.id_to_transaction_state.remove(priority_id.id);
+            let transaction_state = self.id_to_transaction_state.remove(priority_id.id);
+            num_dropped += self.spill_transaction(priority_id.priority, &transaction_state);
+            self.recycle_packet(priority_id.id, transaction_state);
         }
 
         num_dropped
     }
 
     fn remove_by_id(&mut self, id: TransactionId) {
-        self.id_to_transaction_state.remove(id);
+        let transaction_state = self.id_to_transaction_state.remove(id);
+        self.recycle_packet(id, transaction_state);
     }
 
     fn get_min_max_priority(&self) -> MinMaxResult<u64> {
@@ -171,6 +214,28 @@ impl<Tx: TransactionWithMeta> StateContainer<Tx> for TransactionStateContainer<T
             None => MinMaxResult::NoElements,
         }
     }
+
+    fn take_spilled(&mut self, max: usize) -> Vec<Packet> {
+        let Some(spill) = self.spill.as_mut() else {
+            return Vec::new();
+        };
+        let reloaded = spill.take_reloaded();
+        let num_to_reload = self
+            .capacity
+            .saturating_sub(self.id_to_transaction_state.len())
+            .saturating_sub(reloaded.len())
+            .saturating_sub(spill.num_reloading())
+            .min(max);
+        spill.reload(num_to_reload);
+        reloaded
+    }
+
+    fn num_spilled(&self) -> usize {
+        self.spill
+            .as_ref()
+            .map(TransactionSpill::len)
+            .unwrap_or_default()
+    }
 }
 
 impl<Tx: TransactionWithMeta> TransactionStateContainer<Tx> {
@@ -179,7 +244,7 @@ impl<Tx: TransactionWithMeta> TransactionStateContainer<Tx> {
     pub(crate) fn insert_new_transaction(
         &mut self,
         transaction_ttl: SanitizedTransactionTTL<Tx>,
-        packet: Arc<ImmutableDeserializedPacket>,
+        packet: Option<Arc<ImmutableDeserializedPacket>>,
         priority: u64,
         cost: u64,
     ) -> bool {
@@ -188,7 +253,7 @@ impl<Tx: TransactionWithMeta> TransactionStateContainer<Tx> {
             let transaction_id = entry.key();
             entry.insert(TransactionState::new(
                 transaction_ttl,
-                Some(packet),
+                packet,
                 priority,
                 cost,
             ));
@@ -198,6 +263,71 @@ impl<Tx: TransactionWithMeta> TransactionStateContainer<Tx> {
         self.push_ids_into_queue(std::iter::once(priority_id)) > 0
     }
 
+    /// Same as `insert_new_transaction`, but takes the packet by value and
+    /// moves it into the packet arena, which saves allocating it.
+    pub(crate) fn insert_new_transaction_with_packet(
+        &mut self,
+        transaction_ttl: SanitizedTransactionTTL<Tx>,
+        packet: ImmutableDeserializedPacket,
+        priority: u64,
+        cost: u64,
+    ) -> bool {
+        assert!(self.id_to_transaction_state.len() < self.id_to_transaction_state.capacity());
+        let entry = self.id_to_transaction_state.vacant_entry();
+        let transaction_id = entry.key();
+        let packet = match self.packet_arena[transaction_id].take() {
+            Some(mut arena_packet) => match Arc::get_mut(&mut arena_packet) {
+                Some(arena_slot) => {
+                    *arena_slot = packet;
+                    arena_packet
+                }
+                // Still held by the forwarder.
+                None => Arc::new(packet),
+            },
+            None => Arc::new(packet),
+        };
+        entry.insert(TransactionState::new(
+            transaction_ttl,
+            Some(packet),
+            priority,
+            cost,
+        ));
+        let priority_id = TransactionPriorityId::new(priority, transaction_id);
+
+        self.push_ids_into_queue(std::iter::once(priority_id)) > 0
+    }
+
+    /// Returns the packet of a removed transaction to the packet arena.
+    fn recycle_packet(&mut self, id: TransactionId, transaction_state: TransactionState<Tx>) {
+        if let Some(packet) = transaction_state.into_packet() {
+            self.packet_arena[id] = Some(packet);
+        }
+    }
+
+    /// Writes a transaction evicted from the container to the spill, if any.
+    /// Returns the number of transactions dropped in the process.
+    fn spill_transaction(
+        &mut self,
+        priority: u64,
+        transaction_state: &TransactionState<Tx>,
+    ) -> usize {
+        let Some(spill) = self.spill.as_mut() else {
+            return 1;
+        };
+        let transaction = transaction_state
+            .transaction_ttl()
+            .transaction
+            .to_versioned_transaction();
+        let Ok(data) = bincode::serialize(&transaction) else {
+            return 1;
+        };
+        let flags = transaction_state
+            .packet()
+            .map(|packet| packet.original_packet().meta().flags)
+            .unwrap_or_else(PacketFlags::empty);
+        spill.push(priority, flags, data)
+    }
+
     fn get_vacant_map_entry(&mut self) -> VacantEntry<TransactionState<Tx>> {
         assert!(self.id_to_transaction_state.len() < self.id_to_transaction_state.capacity());
         self.id_to_transaction_state.vacant_entry()
@@ -259,8 +389,8 @@ impl TransactionViewStateContainer {
 }
 
 impl StateContainer<RuntimeTransactionView> for TransactionViewStateContainer {
-    fn with_capacity(capacity: usize) -> Self {
-        let inner = TransactionStateContainer::with_capacity(capacity);
+    fn with_capacity_and_spill(capacity: usize, spill: Option<TransactionSpill>) -> Self {
+        let inner = TransactionStateContainer::with_capacity_and_spill(capacity, spill);
         let bytes_buffer = (0..inner.id_to_transaction_state.capacity())
             .map(|_| Arc::new(Vec::with_capacity(PACKET_DATA_SIZE)))
             .collect::<Vec<_>>()
@@ -314,13 +444,115 @@ impl StateContainer<RuntimeTransactionView> for TransactionViewStateContainer {
     fn get_min_max_priority(&self) -> MinMaxResult<u64> {
         self.inner.get_min_max_priority()
     }
+
+    #[inline]
+    fn take_spilled(&mut self, max: usize) -> Vec<Packet> {
+        self.inner.take_spilled(max)
+    }
+
+    #[inline]
+    fn num_spilled(&self) -> usize {
+        self.inner.num_spilled()
+    }
+}
+
+/// How `TransactionStateContainerBench` holds on to the packets of the
+/// transactions it buffers.
+#[cfg(feature = "dev-context-only-utils")]
+#[derive(Clone, Copy, Debug)]
+pub enum BenchPacketHandling {
+    /// The transaction is moved out of the packet, which is dropped, as when
+    /// forwarding is disabled.
+    Drop,
+    /// The packet is kept for forwarding in the packet arena.
+    Arena,
+    /// The packet is kept for forwarding in an allocation of its own.
+    Allocate,
+}
+
+/// Cycles transactions through a `TransactionStateContainer` the way the
+/// scheduler does, so that benchmarks can measure the allocations involved.
+#[cfg(feature = "dev-context-only-utils")]
+pub struct TransactionStateContainerBench {
+    container: TransactionStateContainer<RuntimeTransaction<SanitizedTransaction>>,
+}
+
+#[cfg(feature = "dev-context-only-utils")]
+impl TransactionStateContainerBench {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            container: TransactionStateContainer::with_capacity(capacity),
+        }
+    }
+
+    /// Sanitizes `packets` against `bank` and buffers them, then schedules
+    /// all of the buffered transactions and removes them as processed.
+    pub fn buffer_and_drain(
+        &mut self,
+        bank: &Bank,
+        packets: &[Packet],
+        packet_handling: BenchPacketHandling,
+    ) {
+        let reserved_account_keys = bank.get_reserved_account_keys();
+        for (priority, packet) in packets.iter().enumerate() {
+            let packet = ImmutableDeserializedPacket::new(packet.clone()).unwrap();
+            let (transaction, packet) = match packet_handling {
+                BenchPacketHandling::Drop => {
+                    let (transaction, _) = packet
+                        .into_sanitized_transaction(false, bank, reserved_account_keys)
+                        .unwrap();
+                    (transaction, None)
+                }
+                BenchPacketHandling::Arena | BenchPacketHandling::Allocate => {
+                    let (transaction, _) = packet
+                        .build_sanitized_transaction(false, bank, reserved_account_keys)
+                        .unwrap();
+                    (transaction, Some(packet))
+                }
+            };
+            let transaction_ttl = SanitizedTransactionTTL {
+                transaction,
+                max_age: MaxAge::MAX,
+            };
+            let priority = priority as u64;
+            const BENCH_TRANSACTION_COST: u64 = 5000;
+            match (packet_handling, packet) {
+                (BenchPacketHandling::Arena, Some(packet)) => {
+                    self.container.insert_new_transaction_with_packet(
+                        transaction_ttl,
+                        packet,
+                        priority,
+                        BENCH_TRANSACTION_COST,
+                    )
+                }
+                (_, packet) => self.container.insert_new_transaction(
+                    transaction_ttl,
+                    packet.map(Arc::new),
+                    priority,
+                    BENCH_TRANSACTION_COST,
+                ),
+            };
+        }
+
+        while let Some(priority_id) = self.container.pop() {
+            let transaction_state = self
+                .container
+                .get_mut_transaction_state(priority_id.id)
+                .unwrap();
+            drop(transaction_state.transition_to_pending());
+            self.container.remove_by_id(priority_id.id);
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use {
         super::*,
-        crate::banking_stage::scheduler_messages::MaxAge,
+        crate::banking_stage::{
+            scheduler_messages::MaxAge,
+            transaction_scheduler::transaction_spill::TransactionSpillConfig,
+        },
         agave_transaction_view::transaction_view::SanitizedTransactionView,
         solana_runtime_transaction::runtime_transaction::RuntimeTransaction,
         solana_sdk::{
@@ -331,7 +563,7 @@ mod tests {
             signature::Keypair,
             signer::Signer,
             system_instruction,
-            transaction::{MessageHash, SanitizedTransaction, Transaction},
+            transaction::{MessageHash, SanitizedTransaction, Transaction, VersionedTransaction},
         },
         std::collections::HashSet,
     };
@@ -376,7 +608,7 @@ mod tests {
     ) {
         for priority in 0..num as u64 {
             let (transaction_ttl, packet, priority, cost) = test_transaction(priority);
-            container.insert_new_transaction(transaction_ttl, packet, priority, cost);
+            container.insert_new_transaction(transaction_ttl, Some(packet), priority, cost);
         }
     }
 
@@ -407,6 +639,87 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_spill() {
+        let spill_dir = tempfile::TempDir::new().unwrap();
+        let spill = TransactionSpill::new(&TransactionSpillConfig {
+            path: spill_dir.path().to_path_buf(),
+            max_bytes: 1024 * 1024,
+        })
+        .unwrap();
+        let mut container = TransactionStateContainer::with_capacity_and_spill(2, Some(spill));
+        let (transaction_ttl, _packet, priority, cost) = test_transaction(2);
+        let spilled_transaction = transaction_ttl.transaction.to_versioned_transaction();
+        // The flags of the packet are kept along with the spilled transaction.
+        let mut packet = Packet::from_data(None, &spilled_transaction).unwrap();
+        packet.meta_mut().set_from_staked_node(true);
+        let packet = Arc::new(ImmutableDeserializedPacket::new(packet).unwrap());
+        assert!(!container.insert_new_transaction(transaction_ttl, Some(packet), priority, cost));
+        for priority in [4, 1, 3] {
+            let (transaction_ttl, packet, priority, cost) = test_transaction(priority);
+            // Transactions beyond capacity are spilled, not dropped.
+            assert!(!container.insert_new_transaction(
+                transaction_ttl,
+                Some(packet),
+                priority,
+                cost
+            ));
+        }
+        assert_eq!(container.id_to_transaction_state.len(), 2);
+        assert_eq!(container.num_spilled(), 2);
+        // No room to reload spilled transactions.
+        assert!(container.take_spilled(10).is_empty());
+        assert_eq!(container.num_spilled(), 2);
+
+        let priority_id = container.pop().unwrap();
+        assert_eq!(priority_id.priority, 4);
+        container.remove_by_id(priority_id.id);
+        // The highest priority spilled transaction is reloaded first, once
+        // read back by the spill thread.
+        assert!(container.take_spilled(10).is_empty());
+        assert_eq!(container.num_spilled(), 1);
+        container.spill.as_mut().unwrap().wait_for_reloads();
+        let spilled = container.take_spilled(10);
+        assert_eq!(spilled.len(), 1);
+        assert_eq!(
+            bincode::deserialize::<VersionedTransaction>(spilled[0].data(..).unwrap()).unwrap(),
+            spilled_transaction
+        );
+        assert_eq!(spilled[0].meta().flags, PacketFlags::FROM_STAKED_NODE);
+        assert_eq!(container.num_spilled(), 1);
+    }
+
+    #[test]
+    fn test_packet_arena() {
+        let mut container = TransactionStateContainer::with_capacity(1);
+        let insert = |container: &mut TransactionStateContainer<_>| {
+            let (transaction_ttl, packet, priority, cost) = test_transaction(1);
+            let packet = Arc::try_unwrap(packet).unwrap();
+            container.insert_new_transaction_with_packet(transaction_ttl, packet, priority, cost);
+            let id = container.pop().unwrap().id;
+            let packet = container
+                .get_mut_transaction_state(id)
+                .unwrap()
+                .packet()
+                .unwrap()
+                .clone();
+            (id, packet)
+        };
+
+        // The allocation of a removed packet is reused for the next one.
+        let (id, packet) = insert(&mut container);
+        let packet_ptr = Arc::as_ptr(&packet);
+        drop(packet);
+        container.remove_by_id(id);
+        let (id, packet) = insert(&mut container);
+        assert_eq!(Arc::as_ptr(&packet), packet_ptr);
+
+        // Unless the packet is still held elsewhere.
+        container.remove_by_id(id);
+        let (_id, new_packet) = insert(&mut container);
+        assert_ne!(Arc::as_ptr(&new_packet), Arc::as_ptr(&packet));
+    }
+
     #[test]
     fn test_get_mut_transaction_state() {
         let mut container = TransactionStateContainer::with_capacity(5);
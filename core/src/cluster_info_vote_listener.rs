@@ -6,6 +6,7 @@ use {
         replay_stage::DUPLICATE_THRESHOLD,
         result::{Error, Result},
         sigverify,
+        vote_equivocation_detector::VoteEquivocationDetector,
     },
     agave_banking_stage_ingress_types::BankingPacketBatch,
     crossbeam_channel::{unbounded, Receiver, RecvTimeoutError, Select, Sender},
@@ -21,10 +22,11 @@ use {
     solana_rpc::{
         optimistically_confirmed_bank_tracker::{BankNotification, BankNotificationSender},
         rpc_subscriptions::RpcSubscriptions,
+        vote_latency_tracker::VoteLatencyTracker,
     },
     solana_runtime::{
         bank::Bank,
-        bank_forks::BankForks,
+        bank_forks::{BankForks, HighestConfirmedSlotSender},
         bank_hash_cache::{BankHashCache, DumpedSlotSubscription},
         commitment::VOTE_THRESHOLD_SIZE,
         epoch_stakes::EpochStakes,
@@ -194,6 +196,7 @@ impl ClusterInfoVoteListener {
         cluster_info: Arc<ClusterInfo>,
         verified_packets_sender: BankingPacketSender,
         vote_tracker: Arc<VoteTracker>,
+        vote_latency_tracker: Arc<VoteLatencyTracker>,
         bank_forks: Arc<RwLock<BankForks>>,
         subscriptions: Arc<RpcSubscriptions>,
         verified_vote_sender: VerifiedVoteSender,
@@ -224,12 +227,15 @@ impl ClusterInfoVoteListener {
         let process_thread = Builder::new()
             .name("solCiProcVotes".to_string())
             .spawn(move || {
+                let highest_confirmed_slot_sender =
+                    bank_forks.read().unwrap().highest_confirmed_slot_sender();
                 let mut bank_hash_cache = BankHashCache::new(bank_forks);
                 let dumped_slot_subscription = bank_hash_cache.dumped_slot_subscription();
                 let _ = Self::process_votes_loop(
                     exit,
                     verified_vote_transactions_receiver,
                     vote_tracker,
+                    vote_latency_tracker,
                     &mut bank_hash_cache,
                     dumped_slot_subscription,
                     subscriptions,
@@ -239,6 +245,7 @@ impl ClusterInfoVoteListener {
                     blockstore,
                     bank_notification_sender,
                     duplicate_confirmed_slot_sender,
+                    highest_confirmed_slot_sender,
                 );
             })
             .unwrap();
@@ -318,6 +325,7 @@ impl ClusterInfoVoteListener {
         exit: Arc<AtomicBool>,
         gossip_vote_txs_receiver: VerifiedVoteTransactionsReceiver,
         vote_tracker: Arc<VoteTracker>,
+        vote_latency_tracker: Arc<VoteLatencyTracker>,
         bank_hash_cache: &mut BankHashCache,
         dumped_slot_subscription: DumpedSlotSubscription,
         subscriptions: Arc<RpcSubscriptions>,
@@ -327,8 +335,13 @@ impl ClusterInfoVoteListener {
         blockstore: Arc<Blockstore>,
         bank_notification_sender: Option<BankNotificationSender>,
         duplicate_confirmed_slot_sender: DuplicateConfirmedSlotsSender,
+        highest_confirmed_slot_sender: HighestConfirmedSlotSender,
     ) -> Result<()> {
         let mut confirmation_verifier = OptimisticConfirmationVerifier::new(bank_hash_cache.root());
+        let mut vote_equivocation_detector = Some(VoteEquivocationDetector::new(
+            blockstore.clone(),
+            bank_hash_cache.root(),
+        ));
         let mut latest_vote_slot_per_validator = HashMap::new();
         let mut last_process_root = Instant::now();
         let duplicate_confirmed_slot_sender = Some(duplicate_confirmed_slot_sender);
@@ -351,6 +364,10 @@ impl ClusterInfoVoteListener {
                     &unrooted_optimistic_slots,
                 );
                 vote_tracker.progress_with_new_root_bank(&root_bank);
+                vote_latency_tracker.update_with_root_bank(&root_bank);
+                if let Some(detector) = vote_equivocation_detector.as_mut() {
+                    detector.set_root(root_bank.slot());
+                }
                 last_process_root = Instant::now();
             }
             let confirmed_slots = Self::listen_and_confirm_votes(
@@ -364,12 +381,16 @@ impl ClusterInfoVoteListener {
                 &bank_notification_sender,
                 &duplicate_confirmed_slot_sender,
                 &mut vote_processing_time,
+                &mut vote_equivocation_detector,
                 &mut latest_vote_slot_per_validator,
                 bank_hash_cache,
                 &dumped_slot_subscription,
             );
             match confirmed_slots {
                 Ok(confirmed_slots) => {
+                    if let Some(slot) = confirmed_slots.iter().map(|(slot, _hash)| *slot).max() {
+                        highest_confirmed_slot_sender.send(slot);
+                    }
                     confirmation_verifier
                         .add_new_optimistic_confirmed_slots(confirmed_slots.clone(), &blockstore);
                 }
@@ -398,6 +419,7 @@ impl ClusterInfoVoteListener {
         bank_notification_sender: &Option<BankNotificationSender>,
         duplicate_confirmed_slot_sender: &Option<DuplicateConfirmedSlotsSender>,
         vote_processing_time: &mut Option<VoteProcessingTiming>,
+        vote_equivocation_detector: &mut Option<VoteEquivocationDetector>,
         latest_vote_slot_per_validator: &mut HashMap<Pubkey, Slot>,
         bank_hash_cache: &mut BankHashCache,
         dumped_slot_subscription: &Mutex<bool>,
@@ -416,8 +438,13 @@ impl ClusterInfoVoteListener {
 
             // Should not early return from this point onwards until `process_votes()`
             // returns below to avoid missing any potential `optimistic_confirmed_slots`
-            let gossip_vote_txs: Vec<_> = gossip_vote_txs_receiver.try_iter().flatten().collect();
+            let mut gossip_vote_txs: Vec<_> =
+                gossip_vote_txs_receiver.try_iter().flatten().collect();
             let replay_votes: Vec<_> = replay_votes_receiver.try_iter().collect();
+            if let Some(detector) = vote_equivocation_detector {
+                gossip_vote_txs = detector.process_gossip_votes(gossip_vote_txs);
+                detector.process_replay_votes(&replay_votes);
+            }
             if !gossip_vote_txs.is_empty() || !replay_votes.is_empty() {
                 return Ok(Self::filter_and_confirm_with_new_votes(
                     vote_tracker,
@@ -890,6 +917,7 @@ mod tests {
             &None,
             &None,
             &mut None,
+            &mut None,
             &mut latest_vote_slot_per_validator,
             &mut bank_hash_cache,
             &Mutex::new(false),
@@ -925,6 +953,7 @@ mod tests {
             &None,
             &None,
             &mut None,
+            &mut None,
             &mut latest_vote_slot_per_validator,
             &mut bank_hash_cache,
             &Mutex::new(false),
@@ -1019,6 +1048,7 @@ mod tests {
             &None,
             &None,
             &mut None,
+            &mut None,
             &mut latest_vote_slot_per_validator,
             &mut bank_hash_cache,
             &Mutex::new(false),
@@ -1189,6 +1219,7 @@ mod tests {
             &None,
             &None,
             &mut None,
+            &mut None,
             &mut latest_vote_slot_per_validator,
             &mut bank_hash_cache,
             &Mutex::new(false),
@@ -1302,6 +1333,7 @@ mod tests {
                     &None,
                     &None,
                     &mut None,
+                    &mut None,
                     &mut latest_vote_slot_per_validator,
                     &mut bank_hash_cache,
                     &Mutex::new(false),
@@ -0,0 +1,202 @@
+//! The `leader_prewarm_service` module prepares the node for its upcoming
+//! leader slots. Shortly before the leader window starts, the accounts most
+//! written to in recent blocks are loaded into the accounts cache and the most
+//! used programs are compiled into the program cache, so that the first
+//! transactions of our block do not pay for cold loads and compilation.
+
+use {
+    solana_gossip::cluster_info::ClusterInfo,
+    solana_measure::measure_us,
+    solana_poh::poh_recorder::PohRecorder,
+    solana_runtime::{bank::Bank, bank_forks::BankForks},
+    solana_sdk::{
+        clock::{Slot, NUM_CONSECUTIVE_LEADER_SLOTS},
+        pubkey::Pubkey,
+    },
+    solana_timings::ExecuteTimings,
+    std::{
+        collections::HashMap,
+        iter::successors,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc, RwLock,
+        },
+        thread::{self, sleep, Builder, JoinHandle},
+        time::Duration,
+    },
+};
+
+const LOOP_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Clone, Debug)]
+pub struct LeaderPrewarmConfig {
+    /// Number of slots ahead of the leader window to start pre-warming.
+    pub lead_slots: u64,
+    /// Number of recent blocks from which hot accounts are collected.
+    pub lookback_slots: usize,
+    /// Maximum number of accounts to load ahead of the leader window.
+    pub max_accounts: usize,
+    /// Maximum number of programs to compile ahead of the leader window.
+    pub max_programs: usize,
+}
+
+impl Default for LeaderPrewarmConfig {
+    fn default() -> Self {
+        Self {
+            lead_slots: 4,
+            lookback_slots: 8,
+            max_accounts: 1024,
+            max_programs: 128,
+        }
+    }
+}
+
+pub struct LeaderPrewarmService {
+    thread_hdl: JoinHandle<()>,
+}
+
+impl LeaderPrewarmService {
+    pub fn new(
+        config: LeaderPrewarmConfig,
+        cluster_info: Arc<ClusterInfo>,
+        poh_recorder: Arc<RwLock<PohRecorder>>,
+        bank_forks: Arc<RwLock<BankForks>>,
+        exit: Arc<AtomicBool>,
+    ) -> Self {
+        let thread_hdl = Builder::new()
+            .name("solLdrPrewarm".to_string())
+            .spawn(move || {
+                let mut last_prewarmed_slot = None;
+                while !exit.load(Ordering::Relaxed) {
+                    let leader_slot = poh_recorder
+                        .read()
+                        .unwrap()
+                        .leader_and_slot_after_n_slots(config.lead_slots)
+                        // The identity may change at runtime, so it is looked
+                        // up on every iteration.
+                        .filter(|(leader, _)| *leader == cluster_info.id())
+                        .map(|(_, slot)| slot);
+                    // Pre-warm once per leader window.
+                    if let Some(leader_slot) = leader_slot.filter(|leader_slot| {
+                        last_prewarmed_slot.is_none_or(|last_prewarmed_slot: Slot| {
+                            *leader_slot > last_prewarmed_slot + NUM_CONSECUTIVE_LEADER_SLOTS
+                        })
+                    }) {
+                        last_prewarmed_slot = Some(leader_slot);
+                        let bank = bank_forks.read().unwrap().working_bank();
+                        prewarm_bank(&bank, leader_slot, &config);
+                    }
+                    sleep(LOOP_INTERVAL);
+                }
+            })
+            .unwrap();
+        Self { thread_hdl }
+    }
+
+    pub fn join(self) -> thread::Result<()> {
+        self.thread_hdl.join()
+    }
+}
+
+fn prewarm_bank(bank: &Arc<Bank>, leader_slot: Slot, config: &LeaderPrewarmConfig) {
+    let (hot_accounts, collect_accounts_us) = measure_us!(get_hot_accounts(
+        bank,
+        config.lookback_slots,
+        config.max_accounts
+    ));
+    // Loading the accounts populates the accounts read cache.
+    let (num_loaded_accounts, load_accounts_us) = measure_us!(hot_accounts
+        .iter()
+        .filter(|pubkey| bank.get_account(pubkey).is_some())
+        .count());
+    let mut execute_timings = ExecuteTimings::default();
+    let (num_programs, prewarm_programs_us) =
+        measure_us!(bank.prewarm_program_cache(config.max_programs, &mut execute_timings));
+    let details = &execute_timings.details;
+    datapoint_info!(
+        "leader_prewarm_service",
+        ("leader_slot", leader_slot, i64),
+        ("bank_slot", bank.slot(), i64),
+        ("num_hot_accounts", hot_accounts.len(), i64),
+        ("num_loaded_accounts", num_loaded_accounts, i64),
+        ("collect_accounts_us", collect_accounts_us, i64),
+        ("load_accounts_us", load_accounts_us, i64),
+        ("num_programs", num_programs, i64),
+        ("prewarm_programs_us", prewarm_programs_us, i64),
+        ("load_elf_us", details.create_executor_load_elf_us.0, i64),
+        (
+            "verify_code_us",
+            details.create_executor_verify_code_us.0,
+            i64
+        ),
+        (
+            "jit_compile_us",
+            details.create_executor_jit_compile_us.0,
+            i64
+        ),
+    );
+}
+
+// Returns the accounts with the highest write-lock cost across the given bank
+// and its recent ancestors, in decreasing order of cost.
+fn get_hot_accounts(bank: &Arc<Bank>, lookback_slots: usize, max_accounts: usize) -> Vec<Pubkey> {
+    let mut costs = HashMap::<Pubkey, u64>::new();
+    for bank in successors(Some(bank.clone()), |bank| bank.parent()).take(lookback_slots) {
+        let cost_tracker = bank.read_cost_tracker().unwrap();
+        for (pubkey, cost) in cost_tracker.get_costliest_accounts(max_accounts) {
+            let entry = costs.entry(pubkey).or_default();
+            *entry = entry.saturating_add(cost);
+        }
+    }
+    let mut accounts: Vec<_> = costs.into_iter().collect();
+    accounts.sort_unstable_by(|(_, a), (_, b)| b.cmp(a));
+    accounts
+        .into_iter()
+        .take(max_accounts)
+        .map(|(pubkey, _)| pubkey)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_cost_model::transaction_cost::{
+            TransactionCost, UsageCostDetails, WritableKeysTransaction,
+        },
+        solana_runtime::genesis_utils::create_genesis_config,
+    };
+
+    #[test]
+    fn test_get_hot_accounts() {
+        let genesis_config = create_genesis_config(1).genesis_config;
+        let bank0 = Arc::new(Bank::new_for_tests(&genesis_config));
+        let bank1 = Arc::new(Bank::new_from_parent(bank0.clone(), &Pubkey::default(), 1));
+        let hot = Pubkey::new_unique();
+        let warm = Pubkey::new_unique();
+        let cold = Pubkey::new_unique();
+        for (bank, accounts) in [
+            (&bank0, vec![(hot, 10), (cold, 1)]),
+            (&bank1, vec![(hot, 10), (warm, 15)]),
+        ] {
+            let mut cost_tracker = bank.write_cost_tracker().unwrap();
+            for (pubkey, cost) in accounts {
+                let transaction = WritableKeysTransaction(vec![pubkey]);
+                let tx_cost = TransactionCost::Transaction(UsageCostDetails {
+                    transaction: &transaction,
+                    signature_cost: 0,
+                    write_lock_cost: 0,
+                    data_bytes_cost: 0,
+                    programs_execution_cost: cost,
+                    loaded_accounts_data_size_cost: 0,
+                    allocated_accounts_data_size: 0,
+                });
+                cost_tracker.try_add(&tx_cost).unwrap();
+            }
+        }
+
+        assert_eq!(get_hot_accounts(&bank1, 2, 2), vec![hot, warm]);
+        assert_eq!(get_hot_accounts(&bank1, 2, 3), vec![hot, warm, cold]);
+        assert_eq!(get_hot_accounts(&bank1, 1, 3), vec![warm, hot]);
+    }
+}
@@ -9,6 +9,7 @@
 //!
 
 pub mod accounts_hash_verifier;
+pub mod accounts_replication_service;
 pub mod admin_rpc_post_init;
 pub mod banking_simulation;
 pub mod banking_stage;
@@ -23,6 +24,7 @@ pub mod drop_bank_service;
 pub mod fetch_stage;
 pub mod forwarding_stage;
 pub mod gen_keys;
+pub mod leader_prewarm_service;
 pub mod next_leader;
 pub mod optimistic_confirmation_verifier;
 pub mod poh_timing_report_service;
@@ -43,6 +45,7 @@ mod tpu_entry_notifier;
 pub mod tvu;
 pub mod unfrozen_gossip_verified_vote_hashes;
 pub mod validator;
+pub mod vote_equivocation_detector;
 pub mod vote_simulator;
 pub mod voting_service;
 pub mod warm_quic_cache_service;
@@ -24,8 +24,10 @@ use {
     },
     bytes::Bytes,
     crossbeam_channel::{Receiver as CrossbeamReceiver, Sender as CrossbeamSender},
+    lazy_static::lazy_static,
     lru::LruCache,
     rand::seq::SliceRandom,
+    rayon::{prelude::*, ThreadPool},
     solana_client::connection_cache::Protocol,
     solana_gossip::cluster_info::ClusterInfo,
     solana_ledger::{
@@ -33,6 +35,7 @@ use {
         shred,
     },
     solana_measure::measure::Measure,
+    solana_rayon_threadlimit::get_thread_count,
     solana_runtime::{bank_forks::BankForks, root_bank_cache::RootBankCache},
     solana_sdk::{
         clock::{Slot, DEFAULT_TICKS_PER_SECOND, MS_PER_TICK},
@@ -47,6 +50,7 @@ use {
         collections::{hash_map::Entry, HashMap, HashSet},
         iter::Iterator,
         net::{SocketAddr, UdpSocket},
+        ops::{Range, RangeInclusive},
         sync::{
             atomic::{AtomicBool, Ordering},
             Arc, RwLock,
@@ -72,6 +76,20 @@ const REPAIR_REQUEST_TIMEOUT_MS: u64 = 150;
 // chance of sampling duplicate in the event of cluster partition.
 const NUM_PEERS_TO_SAMPLE_FOR_REPAIRS: usize = 10;
 
+// How often the admin RPC batch repair checks the blockstore for the shreds it
+// requested.
+const PEER_REPAIR_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+lazy_static! {
+    // Admin RPC batch repairs run on their own pool, so that they do not hold
+    // up the global rayon pool shared with replay.
+    static ref PEER_REPAIR_THREAD_POOL: ThreadPool = rayon::ThreadPoolBuilder::new()
+        .num_threads(get_thread_count())
+        .thread_name(|i| format!("solRepairPeer{i:02}"))
+        .build()
+        .unwrap();
+}
+
 pub type AncestorDuplicateSlotsSender = CrossbeamSender<AncestorDuplicateSlotToRepair>;
 pub type AncestorDuplicateSlotsReceiver = CrossbeamReceiver<AncestorDuplicateSlotToRepair>;
 pub type ConfirmedSlotsSender = CrossbeamSender<Vec<Slot>>;
@@ -393,6 +411,18 @@ pub struct RepairSlotRange {
     pub end: Slot,
 }
 
+/// Outcome of the repair of one slot requested through the admin RPC
+#[derive(Debug, PartialEq, Eq)]
+pub struct SlotRepairFromPeer {
+    pub slot: Slot,
+    /// Indexes of the data shreds that were missing and requested from peers
+    pub requested: Vec<u64>,
+    /// Indexes of the requested data shreds still missing when the repair timed out
+    pub missing: Vec<u64>,
+    /// Whether the blockstore holds all of the slot's data shreds
+    pub is_full: bool,
+}
+
 impl Default for RepairSlotRange {
     fn default() -> Self {
         RepairSlotRange {
@@ -921,6 +951,163 @@ impl RepairService {
         repair_socket: &UdpSocket,
         outstanding_repair_requests: Arc<RwLock<OutstandingShredRepairs>>,
     ) {
+        let repair_peers =
+            Self::select_repair_peers(cluster_info.clone(), cluster_slots, pubkey, slot);
+
+        // Send repair request to each peer.
+        for (pubkey, peer_repair_addr) in repair_peers {
+            Self::request_repair_for_shred_from_address(
+                cluster_info.clone(),
+                pubkey,
+                peer_repair_addr,
+                slot,
+                shred_index,
+                repair_socket,
+                outstanding_repair_requests.clone(),
+            );
+        }
+    }
+
+    /// Requests the missing data shreds of each slot in `slots` from the peer
+    /// `pubkey`, or from a sample of peers having the slot, and waits up to
+    /// `timeout` for them to land in the blockstore.
+    ///
+    /// If `shred_indexes` is empty, every data shred known to be missing from
+    /// a slot is requested. Otherwise only the missing shreds within the given
+    /// ranges are. At most `max_shreds` shreds are requested in total, from the
+    /// lowest slots first.
+    #[allow(clippy::too_many_arguments)]
+    pub fn repair_shreds_from_peer(
+        cluster_info: Arc<ClusterInfo>,
+        cluster_slots: Arc<ClusterSlots>,
+        blockstore: &Blockstore,
+        pubkey: Option<Pubkey>,
+        slots: RangeInclusive<Slot>,
+        shred_indexes: &[Range<u64>],
+        repair_socket: &UdpSocket,
+        outstanding_repair_requests: Arc<RwLock<OutstandingShredRepairs>>,
+        max_shreds: usize,
+        timeout: Duration,
+    ) -> Vec<SlotRepairFromPeer> {
+        let mut requests: Vec<(Slot, Vec<u64>)> = PEER_REPAIR_THREAD_POOL.install(|| {
+            slots
+                .into_par_iter()
+                .map(|slot| {
+                    let missing =
+                        Self::find_missing_shreds(blockstore, slot, shred_indexes, max_shreds);
+                    (slot, missing)
+                })
+                .collect()
+        });
+        let mut num_shreds = 0;
+        for (_, requested) in requests.iter_mut() {
+            requested.truncate(max_shreds - num_shreds);
+            num_shreds += requested.len();
+        }
+
+        PEER_REPAIR_THREAD_POOL.install(|| {
+            requests
+                .par_iter()
+                .filter(|(_, requested)| !requested.is_empty())
+                .for_each(|(slot, requested)| {
+                    let repair_peers = Self::select_repair_peers(
+                        cluster_info.clone(),
+                        cluster_slots.clone(),
+                        pubkey,
+                        *slot,
+                    );
+                    for (pubkey, peer_repair_addr) in repair_peers {
+                        for shred_index in requested {
+                            Self::request_repair_for_shred_from_address(
+                                cluster_info.clone(),
+                                pubkey,
+                                peer_repair_addr,
+                                *slot,
+                                *shred_index,
+                                repair_socket,
+                                outstanding_repair_requests.clone(),
+                            );
+                        }
+                    }
+                })
+        });
+
+        let mut missing: Vec<(Slot, Vec<u64>)> = requests.clone();
+        let start = Instant::now();
+        loop {
+            for (slot, shred_indexes) in missing.iter_mut() {
+                shred_indexes.retain(|shred_index| {
+                    !matches!(blockstore.get_data_shred(*slot, *shred_index), Ok(Some(_)))
+                });
+            }
+            if missing
+                .iter()
+                .all(|(_, shred_indexes)| shred_indexes.is_empty())
+                || start.elapsed() >= timeout
+            {
+                break;
+            }
+            sleep(PEER_REPAIR_POLL_INTERVAL);
+        }
+
+        requests
+            .into_iter()
+            .zip(missing)
+            .map(|((slot, requested), (_, missing))| SlotRepairFromPeer {
+                slot,
+                requested,
+                missing,
+                is_full: blockstore.is_full(slot),
+            })
+            .collect()
+    }
+
+    /// Returns the indexes of up to `max_shreds` data shreds missing from
+    /// `slot`, limited to `shred_indexes` unless empty.
+    fn find_missing_shreds(
+        blockstore: &Blockstore,
+        slot: Slot,
+        shred_indexes: &[Range<u64>],
+        max_shreds: usize,
+    ) -> Vec<u64> {
+        if !shred_indexes.is_empty() {
+            return shred_indexes
+                .iter()
+                .flat_map(|range| range.clone())
+                .filter(|shred_index| {
+                    !matches!(blockstore.get_data_shred(slot, *shred_index), Ok(Some(_)))
+                })
+                .take(max_shreds)
+                .collect();
+        }
+        match blockstore.meta(slot) {
+            Ok(Some(slot_meta)) if slot_meta.is_full() => vec![],
+            Ok(Some(slot_meta)) => {
+                // Without the last index, shreds past the highest one received
+                // are unknown, so request the next one as well.
+                let end_index = slot_meta
+                    .last_index
+                    .map(|last_index| last_index + 1)
+                    .unwrap_or(slot_meta.received + 1);
+                blockstore.find_missing_data_indexes(
+                    slot,
+                    0,
+                    0,
+                    slot_meta.consumed,
+                    end_index,
+                    max_shreds,
+                )
+            }
+            _ => vec![0],
+        }
+    }
+
+    fn select_repair_peers(
+        cluster_info: Arc<ClusterInfo>,
+        cluster_slots: Arc<ClusterSlots>,
+        pubkey: Option<Pubkey>,
+        slot: u64,
+    ) -> Vec<(Pubkey, SocketAddr)> {
         let mut repair_peers = vec![];
 
         // Check validity of passed in peer.
@@ -939,21 +1126,9 @@ impl RepairService {
                 "No pubkey was provided or no valid repair socket was found. Sampling a set of \
                  repair peers instead."
             );
-            repair_peers = Self::get_repair_peers(cluster_info.clone(), cluster_slots, slot);
-        }
-
-        // Send repair request to each peer.
-        for (pubkey, peer_repair_addr) in repair_peers {
-            Self::request_repair_for_shred_from_address(
-                cluster_info.clone(),
-                pubkey,
-                peer_repair_addr,
-                slot,
-                shred_index,
-                repair_socket,
-                outstanding_repair_requests.clone(),
-            );
+            repair_peers = Self::get_repair_peers(cluster_info, cluster_slots, slot);
         }
+        repair_peers
     }
 
     fn request_repair_for_shred_from_address(
@@ -1268,6 +1443,122 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_repair_shreds_from_peer() {
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let blockstore = Blockstore::open(ledger_path.path()).unwrap();
+        let num_entries_per_slot = max_ticks_per_n_shreds(1, None) * 4;
+
+        // Slot 1 is full, slot 2 is missing shred 1 and slot 3 is missing entirely.
+        let (shreds, _) = make_slot_entries(1, 0, num_entries_per_slot, true);
+        blockstore.insert_shreds(shreds, None, false).unwrap();
+        let (mut shreds, _) = make_slot_entries(2, 1, num_entries_per_slot, true);
+        assert!(shreds.len() > 2);
+        shreds.remove(1);
+        blockstore.insert_shreds(shreds, None, false).unwrap();
+
+        let cluster_info = Arc::new(new_test_cluster_info());
+        let cluster_slots = Arc::new(ClusterSlots::default());
+        let repair_socket = bind_to_localhost().unwrap();
+        let outstanding_repair_requests = Arc::new(RwLock::new(OutstandingShredRepairs::default()));
+        let repair_with_max_shreds = |slots, shred_indexes: &[Range<u64>], max_shreds| {
+            RepairService::repair_shreds_from_peer(
+                cluster_info.clone(),
+                cluster_slots.clone(),
+                &blockstore,
+                None,
+                slots,
+                shred_indexes,
+                &repair_socket,
+                outstanding_repair_requests.clone(),
+                max_shreds,
+                Duration::from_millis(10),
+            )
+        };
+        let repair = |slots, shred_indexes: &[Range<u64>]| {
+            repair_with_max_shreds(slots, shred_indexes, usize::MAX)
+        };
+
+        // No peer has the slots, so nothing is repaired.
+        assert_eq!(
+            repair(1..=3, &[]),
+            vec![
+                SlotRepairFromPeer {
+                    slot: 1,
+                    requested: vec![],
+                    missing: vec![],
+                    is_full: true,
+                },
+                SlotRepairFromPeer {
+                    slot: 2,
+                    requested: vec![1],
+                    missing: vec![1],
+                    is_full: false,
+                },
+                SlotRepairFromPeer {
+                    slot: 3,
+                    requested: vec![0],
+                    missing: vec![0],
+                    is_full: false,
+                },
+            ]
+        );
+        assert_eq!(
+            repair(2..=2, &[0..1, 2..3]),
+            vec![SlotRepairFromPeer {
+                slot: 2,
+                requested: vec![],
+                missing: vec![],
+                is_full: false,
+            }]
+        );
+        assert_eq!(
+            repair(2..=2, &[0..3]),
+            vec![SlotRepairFromPeer {
+                slot: 2,
+                requested: vec![1],
+                missing: vec![1],
+                is_full: false,
+            }]
+        );
+
+        // Shreds of the lowest slots are requested first.
+        assert_eq!(
+            repair_with_max_shreds(2..=3, &[0..3], 2),
+            vec![
+                SlotRepairFromPeer {
+                    slot: 2,
+                    requested: vec![1],
+                    missing: vec![1],
+                    is_full: false,
+                },
+                SlotRepairFromPeer {
+                    slot: 3,
+                    requested: vec![0],
+                    missing: vec![0],
+                    is_full: false,
+                },
+            ]
+        );
+        assert_eq!(
+            repair_with_max_shreds(2..=3, &[], 1),
+            vec![
+                SlotRepairFromPeer {
+                    slot: 2,
+                    requested: vec![1],
+                    missing: vec![1],
+                    is_full: false,
+                },
+                SlotRepairFromPeer {
+                    slot: 3,
+                    requested: vec![],
+                    missing: vec![],
+                    is_full: false,
+                },
+            ]
+        );
+    }
+
     #[test]
     pub fn test_repair_orphan() {
         let ledger_path = get_tmp_ledger_path_auto_delete!();
@@ -35,7 +35,10 @@ use {
     crossbeam_channel::{Receiver, RecvTimeoutError, Sender},
     rayon::{prelude::*, ThreadPool},
     solana_accounts_db::contains::Contains,
-    solana_entry::entry::VerifyRecyclers,
+    solana_entry::{
+        entry::VerifyRecyclers,
+        entry_verifier::{EntryVerifier, DEFAULT_MAX_QUEUED_BATCHES},
+    },
     solana_geyser_plugin_manager::block_metadata_notifier_interface::BlockMetadataNotifierArc,
     solana_gossip::cluster_info::ClusterInfo,
     solana_ledger::{
@@ -684,11 +687,17 @@ impl ReplayStage {
                 ForkReplayMode::Parallel(pool)
             };
             // Thread pool to replay multiple transactions within one block in parallel
-            let replay_tx_thread_pool = rayon::ThreadPoolBuilder::new()
-                .num_threads(replay_transactions_threads.get())
-                .thread_name(|i| format!("solReplayTx{i:02}"))
-                .build()
-                .expect("new rayon threadpool");
+            let replay_tx_thread_pool = Arc::new(
+                rayon::ThreadPoolBuilder::new()
+                    .num_threads(replay_transactions_threads.get())
+                    .thread_name(|i| format!("solReplayTx{i:02}"))
+                    .build()
+                    .expect("new rayon threadpool"),
+            );
+            // Verifies the proof of history of replayed entries in the background, while the
+            // transactions in them are verified and executed
+            let entry_verifier =
+                EntryVerifier::new(replay_tx_thread_pool.clone(), DEFAULT_MAX_QUEUED_BATCHES);
 
             Self::reset_poh_recorder(
                 &my_pubkey,
@@ -734,6 +743,7 @@ impl ReplayStage {
                     block_meta_sender.as_ref(),
                     entry_notification_sender.as_ref(),
                     &verify_recyclers,
+                    &entry_verifier,
                     &mut heaviest_subtree_fork_choice,
                     &replay_vote_sender,
                     &bank_notification_sender,
@@ -1015,7 +1025,7 @@ impl ReplayStage {
                         wait_to_vote_slot,
                     ) {
                         error!("Unable to set root: {e}");
-                        return;
+                        break;
                     }
                 }
                 voting_time.stop();
@@ -1074,7 +1084,7 @@ impl ReplayStage {
                                         my_old_pubkey, my_pubkey, err
                                     );
                                     // drop(_exit) will set the exit flag, eventually tearing down the entire process
-                                    return;
+                                    break;
                                 }
                             };
                             // Ensure the validator can land votes with the new identity before
@@ -1216,6 +1226,9 @@ impl ReplayStage {
                     retransmit_not_propagated_time.as_us(),
                 );
             }
+            if let Err(err) = entry_verifier.join() {
+                error!("Entry verifier panicked: {err:?}");
+            }
         };
         let t_replay = Builder::new()
             .name("solReplayStage".to_string())
@@ -2239,6 +2252,7 @@ impl ReplayStage {
         entry_notification_sender: Option<&EntryNotifierSender>,
         replay_vote_sender: &ReplayVoteSender,
         verify_recyclers: &VerifyRecyclers,
+        entry_verifier: &EntryVerifier,
         log_messages_bytes_limit: Option<usize>,
         prioritization_fee_cache: &PrioritizationFeeCache,
     ) -> result::Result<usize, BlockstoreProcessorError> {
@@ -2259,6 +2273,7 @@ impl ReplayStage {
             entry_notification_sender,
             Some(replay_vote_sender),
             verify_recyclers,
+            Some(entry_verifier),
             false,
             log_messages_bytes_limit,
             prioritization_fee_cache,
@@ -2917,6 +2932,7 @@ impl ReplayStage {
         transaction_status_sender: Option<&TransactionStatusSender>,
         entry_notification_sender: Option<&EntryNotifierSender>,
         verify_recyclers: &VerifyRecyclers,
+        entry_verifier: &EntryVerifier,
         replay_vote_sender: &ReplayVoteSender,
         replay_timing: &mut ReplayLoopTiming,
         log_messages_bytes_limit: Option<usize>,
@@ -3002,6 +3018,7 @@ impl ReplayStage {
                             entry_notification_sender,
                             &replay_vote_sender.clone(),
                             &verify_recyclers.clone(),
+                            entry_verifier,
                             log_messages_bytes_limit,
                             prioritization_fee_cache,
                         );
@@ -3032,6 +3049,7 @@ impl ReplayStage {
         transaction_status_sender: Option<&TransactionStatusSender>,
         entry_notification_sender: Option<&EntryNotifierSender>,
         verify_recyclers: &VerifyRecyclers,
+        entry_verifier: &EntryVerifier,
         replay_vote_sender: &ReplayVoteSender,
         replay_timing: &mut ReplayLoopTiming,
         log_messages_bytes_limit: Option<usize>,
@@ -3091,6 +3109,7 @@ impl ReplayStage {
                     entry_notification_sender,
                     &replay_vote_sender.clone(),
                     &verify_recyclers.clone(),
+                    entry_verifier,
                     log_messages_bytes_limit,
                     prioritization_fee_cache,
                 );
@@ -3362,17 +3381,28 @@ impl ReplayStage {
                         .parent()
                         .map(|bank| bank.last_blockhash())
                         .unwrap_or_default();
+                    let rewards = bank.get_rewards_and_num_partitions();
                     block_metadata_notifier.notify_block_metadata(
                         bank.parent_slot(),
                         &parent_blockhash.to_string(),
                         bank.slot(),
                         &bank.last_blockhash().to_string(),
-                        &bank.get_rewards_and_num_partitions(),
+                        &rewards,
                         Some(bank.clock().unix_timestamp),
                         Some(bank.block_height()),
                         bank.executed_transaction_count(),
                         r_replay_progress.num_entries as u64,
-                    )
+                    );
+                    // Stake rewards are only calculated in the first block of
+                    // the epoch, the only one with num_partitions
+                    if rewards.num_partitions.is_some()
+                        && block_metadata_notifier.epoch_stake_rewards_notifications_enabled()
+                    {
+                        if let Some(epoch_stake_rewards) = bank.epoch_stake_rewards() {
+                            block_metadata_notifier
+                                .notify_epoch_stake_rewards(bank.slot(), &epoch_stake_rewards);
+                        }
+                    }
                 }
                 bank_complete_time.stop();
 
@@ -3409,6 +3439,7 @@ impl ReplayStage {
         block_meta_sender: Option<&BlockMetaSender>,
         entry_notification_sender: Option<&EntryNotifierSender>,
         verify_recyclers: &VerifyRecyclers,
+        entry_verifier: &EntryVerifier,
         heaviest_subtree_fork_choice: &mut HeaviestSubtreeForkChoice,
         replay_vote_sender: &ReplayVoteSender,
         bank_notification_sender: &Option<BankNotificationSenderConfig>,
@@ -3456,6 +3487,7 @@ impl ReplayStage {
                     transaction_status_sender,
                     entry_notification_sender,
                     verify_recyclers,
+                    entry_verifier,
                     replay_vote_sender,
                     replay_timing,
                     log_messages_bytes_limit,
@@ -3476,6 +3508,7 @@ impl ReplayStage {
                         transaction_status_sender,
                         entry_notification_sender,
                         verify_recyclers,
+                        entry_verifier,
                         replay_vote_sender,
                         replay_timing,
                         log_messages_bytes_limit,
@@ -5047,11 +5080,13 @@ pub(crate) mod tests {
             blockstore.insert_shreds(shreds, None, false).unwrap();
             let block_commitment_cache = Arc::new(RwLock::new(BlockCommitmentCache::default()));
             let exit = Arc::new(AtomicBool::new(false));
-            let replay_tx_thread_pool = rayon::ThreadPoolBuilder::new()
-                .num_threads(1)
-                .thread_name(|i| format!("solReplayTest{i:02}"))
-                .build()
-                .expect("new rayon threadpool");
+            let replay_tx_thread_pool = Arc::new(
+                rayon::ThreadPoolBuilder::new()
+                    .num_threads(1)
+                    .thread_name(|i| format!("solReplayTest{i:02}"))
+                    .build()
+                    .expect("new rayon threadpool"),
+            );
             let res = ReplayStage::replay_blockstore_into_bank(
                 &bank1,
                 &blockstore,
@@ -5062,6 +5097,7 @@ pub(crate) mod tests {
                 None,
                 &replay_vote_sender,
                 &VerifyRecyclers::default(),
+                &EntryVerifier::new(replay_tx_thread_pool.clone(), DEFAULT_MAX_QUEUED_BATCHES),
                 None,
                 &PrioritizationFeeCache::new(0u64),
             );
@@ -10,7 +10,7 @@ pub use solana_sdk::net::DEFAULT_TPU_COALESCE;
 pub use solana_streamer::quic::DEFAULT_MAX_QUIC_CONNECTIONS_PER_PEER as MAX_QUIC_CONNECTIONS_PER_PEER;
 use {
     crate::{
-        banking_stage::BankingStage,
+        banking_stage::{BankingStage, PrioGraphSchedulerConfig, TransactionSpillConfig},
         banking_trace::{Channels, TracerThread},
         cluster_info_vote_listener::{
             ClusterInfoVoteListener, DuplicateConfirmedSlotsSender, GossipVerifiedVoteHashSender,
@@ -34,7 +34,7 @@ use {
     solana_poh::poh_recorder::{PohRecorder, WorkingBankEntry},
     solana_rpc::{
         optimistically_confirmed_bank_tracker::BankNotificationSender,
-        rpc_subscriptions::RpcSubscriptions,
+        rpc_subscriptions::RpcSubscriptions, vote_latency_tracker::VoteLatencyTracker,
     },
     solana_runtime::{
         bank_forks::BankForks,
@@ -48,7 +48,7 @@ use {
     },
     solana_turbine::broadcast_stage::{BroadcastStage, BroadcastStageType},
     std::{
-        collections::HashMap,
+        collections::{HashMap, HashSet},
         net::{SocketAddr, UdpSocket},
         sync::{atomic::AtomicBool, Arc, RwLock},
         thread,
@@ -98,6 +98,7 @@ impl Tpu {
         exit: Arc<AtomicBool>,
         shred_version: u16,
         vote_tracker: Arc<VoteTracker>,
+        vote_latency_tracker: Arc<VoteLatencyTracker>,
         bank_forks: Arc<RwLock<BankForks>>,
         verified_vote_sender: VerifiedVoteSender,
         gossip_verified_vote_hash_sender: GossipVerifiedVoteHashSender,
@@ -107,7 +108,7 @@ impl Tpu {
         tpu_coalesce: Duration,
         duplicate_confirmed_slot_sender: DuplicateConfirmedSlotsSender,
         connection_cache: &Arc<ConnectionCache>,
-        turbine_quic_endpoint_sender: AsyncSender<(SocketAddr, Bytes)>,
+        turbine_quic_endpoint_sender: Option<AsyncSender<(SocketAddr, Bytes)>>,
         keypair: &Keypair,
         log_messages_bytes_limit: Option<usize>,
         staked_nodes: &Arc<RwLock<StakedNodes>>,
@@ -122,6 +123,9 @@ impl Tpu {
         block_production_method: BlockProductionMethod,
         transaction_struct: TransactionStructure,
         enable_block_production_forwarding: bool,
+        transaction_spill_config: Option<TransactionSpillConfig>,
+        prio_graph_scheduler_config: PrioGraphSchedulerConfig,
+        write_fenced_accounts: Arc<RwLock<HashSet<Pubkey>>>,
         _generator_config: Option<GeneratorConfig>, /* vestigial code for replay invalidator */
     ) -> (Self, Vec<Arc<dyn NotifyKeyUpdate + Sync + Send>>) {
         let TpuSockets {
@@ -239,6 +243,7 @@ impl Tpu {
             cluster_info.clone(),
             gossip_vote_sender,
             vote_tracker,
+            vote_latency_tracker,
             bank_forks.clone(),
             subscriptions.clone(),
             verified_vote_sender,
@@ -264,6 +269,9 @@ impl Tpu {
             bank_forks.clone(),
             prioritization_fee_cache,
             enable_block_production_forwarding,
+            transaction_spill_config,
+            prio_graph_scheduler_config,
+            write_fenced_accounts,
         );
 
         let (entry_receiver, tpu_entry_notifier) =
@@ -13,6 +13,7 @@ use {
         consensus::{tower_storage::TowerStorage, Tower},
         cost_update_service::CostUpdateService,
         drop_bank_service::DropBankService,
+        leader_prewarm_service::{LeaderPrewarmConfig, LeaderPrewarmService},
         repair::repair_service::{OutstandingShredRepairs, RepairInfo, RepairServiceChannels},
         replay_stage::{ReplayReceivers, ReplaySenders, ReplayStage, ReplayStageConfig},
         shred_fetch_stage::ShredFetchStage,
@@ -67,6 +68,7 @@ pub struct Tvu {
     cost_update_service: CostUpdateService,
     voting_service: VotingService,
     warm_quic_cache_service: Option<WarmQuicCacheService>,
+    leader_prewarm_service: Option<LeaderPrewarmService>,
     drop_bank_service: DropBankService,
     duplicate_shred_listener: DuplicateShredListener,
 }
@@ -89,6 +91,11 @@ pub struct TvuConfig {
     pub replay_forks_threads: NonZeroUsize,
     pub replay_transactions_threads: NonZeroUsize,
     pub shred_sigverify_threads: NonZeroUsize,
+    // Pre-warms caches ahead of our leader slots if set
+    pub leader_prewarm_config: Option<LeaderPrewarmConfig>,
+    // Accounts replicas take their banks from the validator they follow
+    // instead of replaying them
+    pub disable_replay: bool,
 }
 
 impl Default for TvuConfig {
@@ -102,6 +109,8 @@ impl Default for TvuConfig {
             replay_forks_threads: NonZeroUsize::new(1).expect("1 is non-zero"),
             replay_transactions_threads: NonZeroUsize::new(1).expect("1 is non-zero"),
             shred_sigverify_threads: NonZeroUsize::new(1).expect("1 is non-zero"),
+            leader_prewarm_config: None,
+            disable_replay: false,
         }
     }
 }
@@ -150,7 +159,7 @@ impl Tvu {
         connection_cache: Option<&Arc<ConnectionCache>>,
         prioritization_fee_cache: &Arc<PrioritizationFeeCache>,
         banking_tracer: Arc<BankingTracer>,
-        turbine_quic_endpoint_sender: AsyncSender<(SocketAddr, Bytes)>,
+        turbine_quic_endpoint_sender: Option<AsyncSender<(SocketAddr, Bytes)>>,
         turbine_quic_endpoint_receiver: Receiver<(Pubkey, SocketAddr, Bytes)>,
         repair_response_quic_receiver: Receiver<(Pubkey, SocketAddr, Bytes)>,
         repair_request_quic_sender: AsyncSender<(SocketAddr, Bytes)>,
@@ -346,11 +355,21 @@ impl Tvu {
             &exit,
         );
 
+        let leader_prewarm_service = tvu_config.leader_prewarm_config.map(|config| {
+            LeaderPrewarmService::new(
+                config,
+                cluster_info.clone(),
+                poh_recorder.clone(),
+                bank_forks.clone(),
+                exit.clone(),
+            )
+        });
+
         let cost_update_service = CostUpdateService::new(blockstore.clone(), cost_update_receiver);
 
         let drop_bank_service = DropBankService::new(drop_bank_receiver);
 
-        let replay_stage = if in_wen_restart {
+        let replay_stage = if in_wen_restart || tvu_config.disable_replay {
             None
         } else {
             Some(ReplayStage::new(
@@ -387,6 +406,7 @@ impl Tvu {
             cost_update_service,
             voting_service,
             warm_quic_cache_service,
+            leader_prewarm_service,
             drop_bank_service,
             duplicate_shred_listener,
         })
@@ -409,6 +429,9 @@ impl Tvu {
         if let Some(warmup_service) = self.warm_quic_cache_service {
             warmup_service.join()?;
         }
+        if let Some(leader_prewarm_service) = self.leader_prewarm_service {
+            leader_prewarm_service.join()?;
+        }
         self.drop_bank_service.join()?;
         self.duplicate_shred_listener.join()?;
         Ok(())
@@ -578,7 +601,7 @@ pub mod tests {
             Some(&Arc::new(ConnectionCache::new("connection_cache_test"))),
             &ignored_prioritization_fee_cache,
             BankingTracer::new_disabled(),
-            turbine_quic_endpoint_sender,
+            Some(turbine_quic_endpoint_sender),
             turbine_quic_endpoint_receiver,
             repair_response_quic_receiver,
             repair_quic_async_senders.repair_request_quic_sender,
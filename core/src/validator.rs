@@ -4,7 +4,12 @@ pub use solana_perf::report_target_features;
 use {
     crate::{
         accounts_hash_verifier::AccountsHashVerifier,
+        accounts_replication_service::{
+            AccountsReplicaConfig, AccountsReplicaService, AccountsReplicationConfig,
+            AccountsReplicationService,
+        },
         admin_rpc_post_init::AdminRpcRequestMetadataPostInit,
+        banking_stage::{PrioGraphSchedulerConfig, TransactionSpillConfig},
         banking_trace::{self, BankingTracer, TraceError},
         cluster_info_vote_listener::VoteTracker,
         completed_data_sets_service::CompletedDataSetsService,
@@ -13,6 +18,7 @@ use {
             tower_storage::{NullTowerStorage, TowerStorage},
             ExternalRootSource, Tower,
         },
+        leader_prewarm_service::LeaderPrewarmConfig,
         poh_timing_report_service::PohTimingReportService,
         repair::{
             self,
@@ -55,7 +61,10 @@ use {
         },
         contact_info::ContactInfo,
         crds_gossip_pull::CRDS_GOSSIP_PULL_CRDS_TIMEOUT_MS,
+        egress_budget::GossipBandwidthConfig,
         gossip_service::GossipService,
+        peer_scores::GossipPeerScoresConfig,
+        port_mapping_service::{PortMappingConfig, PortMappingService},
     },
     solana_ledger::{
         bank_forks_utils,
@@ -80,9 +89,11 @@ use {
         poh_recorder::PohRecorder,
         poh_service::{self, PohService},
     },
+    solana_quic_client::QuicConfig,
     solana_rayon_threadlimit::{get_max_thread_count, get_thread_count},
     solana_rpc::{
         block_meta_service::{BlockMetaSender, BlockMetaService},
+        epoch_stake_rewards_cache::EpochStakeRewardsCache,
         max_slots::MaxSlots,
         optimistically_confirmed_bank_tracker::{
             BankNotificationSenderConfig, OptimisticallyConfirmedBank,
@@ -93,15 +104,17 @@ use {
         rpc_pubsub_service::{PubSubConfig, PubSubService},
         rpc_service::JsonRpcService,
         rpc_subscriptions::RpcSubscriptions,
+        slot_status_events::{SlotStatusEventNotifier, SlotStatusEventService, SlotStatusEvents},
         transaction_notifier_interface::TransactionNotifierArc,
         transaction_status_service::TransactionStatusService,
+        vote_latency_tracker::VoteLatencyTracker,
     },
     solana_runtime::{
         accounts_background_service::{
             AbsRequestHandlers, AbsRequestSender, AccountsBackgroundService, DroppedSlotsReceiver,
             PrunedBanksRequestHandler, SnapshotRequestHandler,
         },
-        bank::Bank,
+        bank::{Bank, BankConfig},
         bank_forks::BankForks,
         commitment::BlockCommitmentCache,
         prioritization_fee_cache::PrioritizationFeeCache,
@@ -113,6 +126,7 @@ use {
         snapshot_utils::{self, clean_orphaned_account_snapshot_dirs},
     },
     solana_sdk::{
+        account::from_account,
         clock::Slot,
         epoch_schedule::MAX_LEADER_SCHEDULE_EPOCH_OFFSET,
         exit::Exit,
@@ -122,6 +136,8 @@ use {
         pubkey::Pubkey,
         shred_version::compute_shred_version,
         signature::{Keypair, Signer},
+        slot_hashes::SlotHashes,
+        sysvar,
         timing::timestamp,
     },
     solana_send_transaction_service::send_transaction_service,
@@ -135,8 +151,9 @@ use {
     solana_wen_restart::wen_restart::{wait_for_wen_restart, WenRestartConfig},
     std::{
         borrow::Cow,
-        collections::{HashMap, HashSet},
-        net::SocketAddr,
+        collections::{BTreeMap, HashMap, HashSet},
+        fs, io,
+        net::{SocketAddr, TcpListener},
         num::NonZeroUsize,
         path::{Path, PathBuf},
         sync::{
@@ -153,6 +170,8 @@ use {
 };
 
 const MAX_COMPLETED_DATA_SETS_IN_CHANNEL: usize = 100_000;
+/// Present in the ledger directory while a validator is running
+const UNCLEAN_SHUTDOWN_MARKER_FILE: &str = "unclean-shutdown";
 const WAIT_FOR_SUPERMAJORITY_THRESHOLD_PERCENT: u64 = 80;
 // Right now since we reuse the wait for supermajority code, the
 // following threshold should always greater than or equal to
@@ -248,6 +267,9 @@ pub struct ValidatorConfig {
     pub halt_at_slot: Option<Slot>,
     pub expected_genesis_hash: Option<Hash>,
     pub expected_bank_hash: Option<Hash>,
+    /// Source of the bank hashes which the bank loaded at startup and the ancestors in its
+    /// `SlotHashes` sysvar must match
+    pub snapshot_bank_hash_source: Option<Arc<dyn SnapshotBankHashSource>>,
     pub expected_shred_version: Option<u16>,
     pub voting_disabled: bool,
     pub account_paths: Vec<PathBuf>,
@@ -258,6 +280,10 @@ pub struct ValidatorConfig {
     pub geyser_plugin_always_enabled: bool,
     pub rpc_addrs: Option<(SocketAddr, SocketAddr)>, // (JsonRpc, JsonRpcPubSub)
     pub pubsub_config: PubSubConfig,
+    /// Streams the accounts changed in each frozen bank to replicas
+    pub accounts_replication_config: Option<AccountsReplicationConfig>,
+    /// Follows the accounts of another validator instead of replaying the ledger
+    pub accounts_replica_config: Option<AccountsReplicaConfig>,
     pub snapshot_config: SnapshotConfig,
     pub max_ledger_shreds: Option<u64>,
     pub blockstore_options: BlockstoreOptions,
@@ -280,6 +306,8 @@ pub struct ValidatorConfig {
     pub debug_keys: Option<Arc<HashSet<Pubkey>>>,
     pub contact_debug_interval: u64,
     pub contact_save_interval: u64,
+    pub gossip_bandwidth: GossipBandwidthConfig,
+    pub gossip_peer_scores: GossipPeerScoresConfig,
     pub send_transaction_service_config: send_transaction_service::Config,
     pub no_poh_speed_test: bool,
     pub no_os_memory_stats_reporting: bool,
@@ -300,11 +328,18 @@ pub struct ValidatorConfig {
     pub no_wait_for_vote_to_start_leader: bool,
     pub wait_to_vote_slot: Option<Slot>,
     pub runtime_config: RuntimeConfig,
+    pub bank_config: BankConfig,
     pub banking_trace_dir_byte_limit: banking_trace::DirByteLimit,
     pub block_verification_method: BlockVerificationMethod,
     pub block_production_method: BlockProductionMethod,
     pub transaction_struct: TransactionStructure,
     pub enable_block_production_forwarding: bool,
+    /// Spill buffered transactions beyond the banking stage capacity to disk.
+    pub transaction_spill_config: Option<TransactionSpillConfig>,
+    /// Tuning of the banking stage prio-graph scheduler.
+    pub prio_graph_scheduler_config: PrioGraphSchedulerConfig,
+    /// Accounts whose writes are kept out of the blocks produced by this node.
+    pub write_fenced_accounts: Arc<RwLock<HashSet<Pubkey>>>,
     pub generator_config: Option<GeneratorConfig>,
     pub use_snapshot_archives_at_startup: UseSnapshotArchivesAtStartup,
     pub wen_restart_proto_path: Option<PathBuf>,
@@ -315,8 +350,17 @@ pub struct ValidatorConfig {
     pub replay_forks_threads: NonZeroUsize,
     pub replay_transactions_threads: NonZeroUsize,
     pub tvu_shred_sigverify_threads: NonZeroUsize,
+    /// Send shreds over QUIC to the nodes which advertise a QUIC TVU address,
+    /// falling back to UDP if the QUIC endpoint is congested.
+    pub turbine_use_quic: bool,
     pub thread_manager_config: ThreadManagerConfig,
     pub delay_leader_block_for_pending_fork: bool,
+    /// Forward ports through the NAT gateway and advertise the external
+    /// addresses in gossip. Disabled if None.
+    pub port_mapping_config: Option<PortMappingConfig>,
+    /// Pre-warm the accounts and program caches ahead of our leader slots.
+    /// Disabled if None.
+    pub leader_prewarm_config: Option<LeaderPrewarmConfig>,
 }
 
 impl Default for ValidatorConfig {
@@ -325,6 +369,7 @@ impl Default for ValidatorConfig {
             halt_at_slot: None,
             expected_genesis_hash: None,
             expected_bank_hash: None,
+            snapshot_bank_hash_source: None,
             expected_shred_version: None,
             voting_disabled: false,
             max_ledger_shreds: None,
@@ -336,6 +381,8 @@ impl Default for ValidatorConfig {
             geyser_plugin_always_enabled: false,
             rpc_addrs: None,
             pubsub_config: PubSubConfig::default(),
+            accounts_replication_config: None,
+            accounts_replica_config: None,
             snapshot_config: SnapshotConfig::new_load_only(),
             broadcast_stage_type: BroadcastStageType::Standard,
             turbine_disabled: Arc::<AtomicBool>::default(),
@@ -354,6 +401,8 @@ impl Default for ValidatorConfig {
             debug_keys: None,
             contact_debug_interval: DEFAULT_CONTACT_DEBUG_INTERVAL_MILLIS,
             contact_save_interval: DEFAULT_CONTACT_SAVE_INTERVAL_MILLIS,
+            gossip_bandwidth: GossipBandwidthConfig::default(),
+            gossip_peer_scores: GossipPeerScoresConfig::default(),
             send_transaction_service_config: send_transaction_service::Config::default(),
             no_poh_speed_test: true,
             no_os_memory_stats_reporting: true,
@@ -374,11 +423,15 @@ impl Default for ValidatorConfig {
             accounts_db_config: None,
             wait_to_vote_slot: None,
             runtime_config: RuntimeConfig::default(),
+            bank_config: BankConfig::default(),
             banking_trace_dir_byte_limit: 0,
             block_verification_method: BlockVerificationMethod::default(),
             block_production_method: BlockProductionMethod::default(),
             transaction_struct: TransactionStructure::default(),
             enable_block_production_forwarding: false,
+            transaction_spill_config: None,
+            prio_graph_scheduler_config: PrioGraphSchedulerConfig::default(),
+            write_fenced_accounts: Arc::new(RwLock::new(HashSet::default())),
             generator_config: None,
             use_snapshot_archives_at_startup: UseSnapshotArchivesAtStartup::default(),
             wen_restart_proto_path: None,
@@ -390,7 +443,10 @@ impl Default for ValidatorConfig {
             replay_transactions_threads: NonZeroUsize::new(1).expect("1 is non-zero"),
             thread_manager_config: ThreadManagerConfig::default_for_agave(),
             tvu_shred_sigverify_threads: NonZeroUsize::new(1).expect("1 is non-zero"),
+            turbine_use_quic: false,
             delay_leader_block_for_pending_fork: false,
+            port_mapping_config: None,
+            leader_prewarm_config: None,
         }
     }
 }
@@ -503,6 +559,7 @@ struct TransactionHistoryServices {
     transaction_status_service: Option<TransactionStatusService>,
     max_complete_transaction_status_slot: Arc<AtomicU64>,
     max_complete_rewards_slot: Arc<AtomicU64>,
+    epoch_stake_rewards_cache: Arc<EpochStakeRewardsCache>,
     block_meta_sender: Option<BlockMetaSender>,
     block_meta_service: Option<BlockMetaService>,
 }
@@ -517,6 +574,8 @@ pub struct ValidatorTpuConfig {
     pub tpu_connection_pool_size: usize,
     /// Controls if to enable UDP for TPU tansactions.
     pub tpu_enable_udp: bool,
+    /// Controls if to resume sessions with 0-RTT when sending TPU transactions over QUIC
+    pub tpu_client_zero_rtt: bool,
     /// QUIC server config for regular TPU
     pub tpu_quic_server_config: QuicServerParams,
     /// QUIC server config for TPU forward
@@ -550,6 +609,7 @@ impl ValidatorTpuConfig {
             vote_use_quic: DEFAULT_VOTE_USE_QUIC,
             tpu_connection_pool_size: DEFAULT_TPU_CONNECTION_POOL_SIZE,
             tpu_enable_udp,
+            tpu_client_zero_rtt: false,
             tpu_quic_server_config,
             tpu_fwd_quic_server_config,
             vote_quic_server_config,
@@ -562,15 +622,19 @@ pub struct Validator {
     json_rpc_service: Option<JsonRpcService>,
     pubsub_service: Option<PubSubService>,
     rpc_completed_slots_service: Option<JoinHandle<()>>,
+    slot_status_event_service: Option<JoinHandle<()>>,
     optimistically_confirmed_bank_tracker: Option<OptimisticallyConfirmedBankTracker>,
     transaction_status_service: Option<TransactionStatusService>,
     block_meta_service: Option<BlockMetaService>,
     entry_notifier_service: Option<EntryNotifierService>,
     system_monitor_service: Option<SystemMonitorService>,
     sample_performance_service: Option<SamplePerformanceService>,
+    accounts_replication_service: Option<AccountsReplicationService>,
+    accounts_replica_service: Option<AccountsReplicaService>,
     poh_timing_report_service: PohTimingReportService,
     stats_reporter_service: StatsReporterService,
     gossip_service: GossipService,
+    port_mapping_service: Option<PortMappingService>,
     serve_repair_service: ServeRepairService,
     completed_data_sets_service: Option<CompletedDataSetsService>,
     snapshot_packager_service: Option<SnapshotPackagerService>,
@@ -582,6 +646,8 @@ pub struct Validator {
     pub cluster_info: Arc<ClusterInfo>,
     pub bank_forks: Arc<RwLock<BankForks>>,
     pub blockstore: Arc<Blockstore>,
+    /// The lifecycle transitions of slots, for consumers in the same process
+    pub slot_status_events: Arc<SlotStatusEvents>,
     geyser_plugin_service: Option<GeyserPluginService>,
     blockstore_metric_report_service: BlockstoreMetricReportService,
     accounts_background_service: AccountsBackgroundService,
@@ -593,6 +659,7 @@ pub struct Validator {
     repair_quic_endpoints_runtime: Option<TokioRuntime>,
     repair_quic_endpoints_join_handle: Option<repair::quic_endpoint::AsyncTryJoinHandle>,
     thread_manager: ThreadManager,
+    unclean_shutdown_marker: PathBuf,
 }
 
 impl Validator {
@@ -617,6 +684,7 @@ impl Validator {
             vote_use_quic,
             tpu_connection_pool_size,
             tpu_enable_udp,
+            tpu_client_zero_rtt,
             tpu_quic_server_config,
             tpu_fwd_quic_server_config,
             vote_quic_server_config,
@@ -778,9 +846,15 @@ impl Validator {
             .as_ref()
             .and_then(|geyser_plugin_service| geyser_plugin_service.get_block_metadata_notifier());
 
-        let slot_status_notifier = geyser_plugin_service
-            .as_ref()
-            .and_then(|geyser_plugin_service| geyser_plugin_service.get_slot_status_notifier());
+        let slot_status_events = Arc::<SlotStatusEvents>::default();
+        // Always set, since consumers may only subscribe once the validator is running. Until
+        // then, publishing an event only takes an uncontended read lock.
+        let slot_status_notifier = Some(SlotStatusEventNotifier::new_rw_arc(
+            slot_status_events.clone(),
+            geyser_plugin_service
+                .as_ref()
+                .and_then(|geyser_plugin_service| geyser_plugin_service.get_slot_status_notifier()),
+        ));
 
         info!(
             "Geyser plugin: accounts_update_notifier: {}, transaction_notifier: {}, \
@@ -816,6 +890,7 @@ impl Validator {
                 transaction_status_service,
                 max_complete_transaction_status_slot,
                 max_complete_rewards_slot,
+                epoch_stake_rewards_cache,
                 block_meta_sender,
                 block_meta_service,
             },
@@ -836,6 +911,24 @@ impl Validator {
         )
         .map_err(ValidatorError::Other)?;
 
+        let unclean_shutdown_marker = ledger_path.join(UNCLEAN_SHUTDOWN_MARKER_FILE);
+        if unclean_shutdown_marker.exists() {
+            repair_duplicate_accounts(&bank_forks.read().unwrap().root_bank());
+        }
+        fs::write(&unclean_shutdown_marker, [])
+            .with_context(|| format!("failed to create {unclean_shutdown_marker:?}"))?;
+
+        if let Some(snapshot_bank_hash_source) = &config.snapshot_bank_hash_source {
+            let root_bank = bank_forks.read().unwrap().root_bank();
+            let expected_bank_hashes = snapshot_bank_hash_source
+                .bank_hashes(root_bank.slot())
+                .map_err(|err| ValidatorError::SnapshotBankHashesUnavailable {
+                    slot: root_bank.slot(),
+                    err,
+                })?;
+            verify_snapshot_bank_hashes(&root_bank, &expected_bank_hashes)?;
+        }
+
         if !config.no_poh_speed_test {
             check_poh_speed(&bank_forks.read().unwrap().root_bank(), None)?;
         }
@@ -887,6 +980,8 @@ impl Validator {
             socket_addr_space,
         );
         cluster_info.set_contact_debug_interval(config.contact_debug_interval);
+        cluster_info.set_bandwidth_config(config.gossip_bandwidth);
+        cluster_info.set_peer_scores_config(config.gossip_peer_scores.clone());
         cluster_info.set_entrypoints(cluster_entrypoints);
         cluster_info.restore_contact_info(ledger_path, config.contact_save_interval);
         let cluster_info = Arc::new(cluster_info);
@@ -924,8 +1019,9 @@ impl Validator {
         let (snapshot_request_sender, snapshot_request_receiver) = unbounded();
         let accounts_background_request_sender =
             AbsRequestSender::new(snapshot_request_sender.clone());
+        let snapshot_config = Arc::new(RwLock::new(config.snapshot_config.clone()));
         let snapshot_request_handler = SnapshotRequestHandler {
-            snapshot_config: config.snapshot_config.clone(),
+            snapshot_config: snapshot_config.clone(),
             snapshot_request_sender,
             snapshot_request_receiver,
             accounts_package_sender,
@@ -1064,6 +1160,29 @@ impl Validator {
                 None
             };
 
+        let accounts_replication_service = config
+            .accounts_replication_config
+            .as_ref()
+            .map(|replication_config| {
+                let addr = replication_config.addr;
+                TcpListener::bind(addr)
+                    .and_then(|listener| {
+                        AccountsReplicationService::new(
+                            listener,
+                            replication_config.catch_up_slots,
+                            bank_forks.clone(),
+                            cluster_info.clone(),
+                            exit.clone(),
+                        )
+                    })
+                    .map_err(|err| {
+                        ValidatorError::Other(format!(
+                            "Failed to start accounts replication service on {addr}: {err}"
+                        ))
+                    })
+            })
+            .transpose()?;
+
         let mut block_commitment_cache = BlockCommitmentCache::default();
         let bank_forks_guard = bank_forks.read().unwrap();
         block_commitment_cache.initialize_slots(
@@ -1073,6 +1192,24 @@ impl Validator {
         drop(bank_forks_guard);
         let block_commitment_cache = Arc::new(RwLock::new(block_commitment_cache));
 
+        let accounts_replica_service =
+            config
+                .accounts_replica_config
+                .clone()
+                .map(|replica_config| {
+                    info!(
+                        "Following the accounts of {} at {}",
+                        replica_config.source_identity, replica_config.source
+                    );
+                    AccountsReplicaService::new(
+                        replica_config,
+                        bank_forks.clone(),
+                        block_commitment_cache.clone(),
+                        accounts_background_request_sender.clone(),
+                        exit.clone(),
+                    )
+                });
+
         let optimistically_confirmed_bank =
             OptimisticallyConfirmedBank::locked_from_bank_forks_root(&bank_forks);
 
@@ -1089,24 +1226,27 @@ impl Validator {
         ));
 
         let max_slots = Arc::new(MaxSlots::default());
+        let vote_latency_tracker = Arc::new(VoteLatencyTracker::new(Some(*vote_account)));
 
         let staked_nodes = Arc::new(RwLock::new(StakedNodes::default()));
 
         let connection_cache = if use_quic {
-            let connection_cache = ConnectionCache::new_with_client_options(
+            let mut quic_config = QuicConfig::new().unwrap();
+            quic_config.update_client_certificate(
+                &identity_keypair,
+                node.info
+                    .tpu(Protocol::UDP)
+                    .ok_or_else(|| {
+                        ValidatorError::Other(String::from("Invalid UDP address for TPU"))
+                    })?
+                    .ip(),
+            );
+            quic_config.set_staked_nodes(&staked_nodes, &identity_keypair.pubkey());
+            quic_config.set_zero_rtt(tpu_client_zero_rtt);
+            let connection_cache = ConnectionCache::new_with_quic_config(
                 "connection_cache_tpu_quic",
                 tpu_connection_pool_size,
-                None,
-                Some((
-                    &identity_keypair,
-                    node.info
-                        .tpu(Protocol::UDP)
-                        .ok_or_else(|| {
-                            ValidatorError::Other(String::from("Invalid UDP address for TPU"))
-                        })?
-                        .ip(),
-                )),
-                Some((&staked_nodes, &identity_keypair.pubkey())),
+                quic_config,
             );
             Arc::new(connection_cache)
         } else {
@@ -1150,6 +1290,7 @@ impl Validator {
             rpc_completed_slots_service,
             optimistically_confirmed_bank_tracker,
             bank_notification_sender,
+            slot_status_event_service,
         ) = if let Some((rpc_addr, rpc_pubsub_addr)) = config.rpc_addrs {
             assert_eq!(
                 node.info.rpc().map(|addr| socket_addr_space.check(&addr)),
@@ -1158,11 +1299,14 @@ impl Validator {
                     .map(|addr| socket_addr_space.check(&addr))
             );
             let (bank_notification_sender, bank_notification_receiver) = unbounded();
-            let confirmed_bank_subscribers = if !bank_notification_senders.is_empty() {
-                Some(Arc::new(RwLock::new(bank_notification_senders)))
-            } else {
-                None
-            };
+            let (slot_notification_sender, slot_notification_receiver) = unbounded();
+            bank_notification_senders.push(slot_notification_sender);
+            let slot_status_event_service = SlotStatusEventService::spawn(
+                slot_notification_receiver,
+                slot_status_events.clone(),
+                exit.clone(),
+            );
+            let confirmed_bank_subscribers = Some(Arc::new(RwLock::new(bank_notification_senders)));
 
             let json_rpc_service = JsonRpcService::new(
                 rpc_addr,
@@ -1182,10 +1326,12 @@ impl Validator {
                 optimistically_confirmed_bank.clone(),
                 config.send_transaction_service_config.clone(),
                 max_slots.clone(),
+                vote_latency_tracker.clone(),
                 leader_schedule_cache.clone(),
                 connection_cache.clone(),
                 max_complete_transaction_status_slot,
                 max_complete_rewards_slot,
+                epoch_stake_rewards_cache,
                 prioritization_fee_cache.clone(),
             )
             .map_err(ValidatorError::Other)?;
@@ -1264,9 +1410,10 @@ impl Validator {
                 rpc_completed_slots_service,
                 optimistically_confirmed_bank_tracker,
                 bank_notification_sender_config,
+                Some(slot_status_event_service),
             )
         } else {
-            (None, None, None, None, None, None, None)
+            (None, None, None, None, None, None, None, None)
         };
 
         if config.halt_at_slot.is_some() {
@@ -1305,6 +1452,12 @@ impl Validator {
             Some(stats_reporter_sender.clone()),
             exit.clone(),
         );
+        let port_mapping_service = config
+            .port_mapping_config
+            .clone()
+            .map(|port_mapping_config| {
+                PortMappingService::new(cluster_info.clone(), port_mapping_config, exit.clone())
+            });
         let serve_repair = ServeRepair::new(
             cluster_info.clone(),
             bank_forks.clone(),
@@ -1393,6 +1546,10 @@ impl Validator {
             .map(|(endpoint, sender, join_handle)| (Some(endpoint), sender, Some(join_handle)))
             .unwrap()
         };
+        // Shreds are received over QUIC regardless, but only sent over QUIC
+        // if enabled; otherwise they are sent over UDP.
+        let turbine_use_quic = config.turbine_use_quic && turbine_quic_endpoint.is_some();
+        let turbine_quic_endpoint_sender = turbine_use_quic.then_some(turbine_quic_endpoint_sender);
 
         // Repair quic endpoint.
         let repair_quic_endpoints_runtime = (current_runtime_handle.is_err()
@@ -1514,6 +1671,8 @@ impl Validator {
                 replay_forks_threads: config.replay_forks_threads,
                 replay_transactions_threads: config.replay_transactions_threads,
                 shred_sigverify_threads: config.tvu_shred_sigverify_threads,
+                leader_prewarm_config: config.leader_prewarm_config.clone(),
+                disable_replay: config.accounts_replica_config.is_some(),
             },
             &max_slots,
             block_metadata_notifier,
@@ -1580,6 +1739,7 @@ impl Validator {
             exit,
             node.info.shred_version(),
             vote_tracker,
+            vote_latency_tracker,
             bank_forks.clone(),
             verified_vote_sender,
             gossip_verified_vote_hash_sender,
@@ -1604,6 +1764,9 @@ impl Validator {
             config.block_production_method.clone(),
             config.transaction_struct.clone(),
             config.enable_block_production_forwarding,
+            config.transaction_spill_config.clone(),
+            config.prio_graph_scheduler_config.clone(),
+            config.write_fenced_accounts.clone(),
             config.generator_config.clone(),
         );
 
@@ -1625,25 +1788,36 @@ impl Validator {
             cluster_info: cluster_info.clone(),
             vote_account: *vote_account,
             repair_whitelist: config.repair_whitelist.clone(),
+            write_fenced_accounts: config.write_fenced_accounts.clone(),
             notifies: key_notifies,
             repair_socket: Arc::new(node.sockets.repair),
             outstanding_repair_requests,
             cluster_slots,
+            blockstore: blockstore.clone(),
+            snapshot_config,
+            rpc_fee_payer_inflight_limiter: json_rpc_service
+                .as_ref()
+                .and_then(JsonRpcService::fee_payer_inflight_limiter)
+                .cloned(),
         });
 
         Ok(Self {
             stats_reporter_service,
             gossip_service,
+            port_mapping_service,
             serve_repair_service,
             json_rpc_service,
             pubsub_service,
             rpc_completed_slots_service,
+            slot_status_event_service,
             optimistically_confirmed_bank_tracker,
             transaction_status_service,
             block_meta_service,
             entry_notifier_service,
             system_monitor_service,
             sample_performance_service,
+            accounts_replication_service,
+            accounts_replica_service,
             poh_timing_report_service,
             snapshot_packager_service,
             completed_data_sets_service,
@@ -1656,6 +1830,7 @@ impl Validator {
             cluster_info,
             bank_forks,
             blockstore,
+            slot_status_events,
             geyser_plugin_service,
             blockstore_metric_report_service,
             accounts_background_service,
@@ -1667,6 +1842,7 @@ impl Validator {
             repair_quic_endpoints_runtime,
             repair_quic_endpoints_join_handle,
             thread_manager,
+            unclean_shutdown_marker,
         })
     }
 
@@ -1729,6 +1905,12 @@ impl Validator {
                 .expect("rpc_completed_slots_service");
         }
 
+        if let Some(slot_status_event_service) = self.slot_status_event_service {
+            slot_status_event_service
+                .join()
+                .expect("slot_status_event_service");
+        }
+
         if let Some(optimistically_confirmed_bank_tracker) =
             self.optimistically_confirmed_bank_tracker
         {
@@ -1759,6 +1941,18 @@ impl Validator {
                 .expect("sample_performance_service");
         }
 
+        if let Some(accounts_replication_service) = self.accounts_replication_service {
+            accounts_replication_service
+                .join()
+                .expect("accounts_replication_service");
+        }
+
+        if let Some(accounts_replica_service) = self.accounts_replica_service {
+            accounts_replica_service
+                .join()
+                .expect("accounts_replica_service");
+        }
+
         if let Some(entry_notifier_service) = self.entry_notifier_service {
             entry_notifier_service
                 .join()
@@ -1770,6 +1964,9 @@ impl Validator {
         }
 
         self.gossip_service.join().expect("gossip_service");
+        if let Some(port_mapping_service) = self.port_mapping_service {
+            port_mapping_service.join().expect("port_mapping_service");
+        }
         self.repair_quic_endpoints
             .iter()
             .flatten()
@@ -1823,7 +2020,57 @@ impl Validator {
             .join()
             .expect("poh_timing_report_service");
         self.thread_manager.destroy();
+
+        remove_unclean_shutdown_marker(&self.unclean_shutdown_marker);
+    }
+}
+
+fn remove_unclean_shutdown_marker(unclean_shutdown_marker: &Path) {
+    if let Err(err) = fs::remove_file(unclean_shutdown_marker) {
+        if err.kind() != io::ErrorKind::NotFound {
+            warn!("Failed to remove {unclean_shutdown_marker:?}: {err}");
+        }
+    }
+}
+
+/// Records that the validator in `ledger_path` shut down cleanly, for exit paths
+/// that end the process without returning from [`Validator::join`]
+pub fn mark_clean_shutdown(ledger_path: &Path) {
+    remove_unclean_shutdown_marker(&ledger_path.join(UNCLEAN_SHUTDOWN_MARKER_FILE));
+}
+
+/// A crash while writing a storage can leave several entries for a pubkey in it.
+/// Rewrite such storages so that only the newest entry remains.
+fn repair_duplicate_accounts(bank: &Bank) {
+    info!("Previous shutdown was unclean, scanning storages for duplicate accounts..");
+    let accounts_db = &bank.rc.accounts.accounts_db;
+    let report = accounts_db.find_duplicate_accounts();
+    if report.is_empty() {
+        info!(
+            "No duplicate accounts found in {} storages",
+            report.num_storages_scanned
+        );
+        return;
+    }
+    for duplicate in &report.duplicates {
+        warn!(
+            "Account {} stored {} times in slot {}{}",
+            duplicate.pubkey,
+            duplicate.offsets.len(),
+            duplicate.slot,
+            if duplicate.divergent {
+                " with divergent contents"
+            } else {
+                ""
+            },
+        );
     }
+    let num_slots_repaired = accounts_db.repair_duplicate_accounts(&report);
+    warn!(
+        "Repaired {num_slots_repaired} slots holding {} duplicate accounts, {} of them divergent",
+        report.duplicates.len(),
+        report.num_divergent(),
+    );
 }
 
 fn active_vote_account_exists_in_bank(bank: &Bank, vote_account: &Pubkey) -> bool {
@@ -2047,6 +2294,7 @@ fn load_blockstore(
         accounts_db_skip_shrink: config.accounts_db_skip_shrink,
         accounts_db_force_initial_clean: config.accounts_db_force_initial_clean,
         runtime_config: config.runtime_config.clone(),
+        bank_config: config.bank_config.clone(),
         use_snapshot_archives_at_startup: config.use_snapshot_archives_at_startup,
         ..blockstore_processor::ProcessOptions::default()
     };
@@ -2510,12 +2758,14 @@ fn initialize_rpc_transaction_history_services(
     ));
 
     let max_complete_rewards_slot = Arc::new(AtomicU64::new(blockstore.max_root()));
+    let epoch_stake_rewards_cache = Arc::<EpochStakeRewardsCache>::default();
     let (block_meta_sender, block_meta_receiver) = unbounded();
     let block_meta_sender = Some(block_meta_sender);
     let block_meta_service = Some(BlockMetaService::new(
         block_meta_receiver,
         blockstore,
         max_complete_rewards_slot.clone(),
+        epoch_stake_rewards_cache.clone(),
         exit,
     ));
     TransactionHistoryServices {
@@ -2523,6 +2773,7 @@ fn initialize_rpc_transaction_history_services(
         transaction_status_service,
         max_complete_transaction_status_slot,
         max_complete_rewards_slot,
+        epoch_stake_rewards_cache,
         block_meta_sender,
         block_meta_service,
     }
@@ -2556,6 +2807,19 @@ pub enum ValidatorError {
     #[error("shred version mismatch: actual {actual}, expected {expected}")]
     ShredVersionMismatch { actual: u16, expected: u16 },
 
+    #[error("snapshot bank hash mismatch at slot {slot}: actual {actual}, expected {expected}")]
+    SnapshotBankHashMismatch {
+        slot: Slot,
+        actual: Hash,
+        expected: Hash,
+    },
+
+    #[error("no expected bank hash for snapshot slot {0}")]
+    SnapshotBankHashUnknown(Slot),
+
+    #[error("failed to get the expected bank hashes for snapshot slot {slot}: {err}")]
+    SnapshotBankHashesUnavailable { slot: Slot, err: String },
+
     #[error(transparent)]
     TraceError(#[from] TraceError),
 
@@ -2563,6 +2827,51 @@ pub enum ValidatorError {
     WenRestartFinished,
 }
 
+/// Provides the bank hashes that the bank loaded at startup is verified against, such as those
+/// agreed on by a quorum of RPC nodes
+pub trait SnapshotBankHashSource: Send + Sync {
+    /// Returns the expected bank hashes of `slot`, the slot of the bank actually loaded, and of
+    /// any of its ancestors
+    fn bank_hashes(&self, slot: Slot) -> Result<BTreeMap<Slot, Hash>, String>;
+}
+
+/// Checks the bank loaded at startup, and the ancestors in its `SlotHashes` sysvar, against the
+/// expected bank hashes. Ancestors without an expected hash are not checked.
+fn verify_snapshot_bank_hashes(
+    bank: &Bank,
+    expected_bank_hashes: &BTreeMap<Slot, Hash>,
+) -> Result<(), ValidatorError> {
+    if !expected_bank_hashes.contains_key(&bank.slot()) {
+        return Err(ValidatorError::SnapshotBankHashUnknown(bank.slot()));
+    }
+    let slot_hashes = bank
+        .get_account(&sysvar::slot_hashes::id())
+        .and_then(|account| from_account::<SlotHashes, _>(&account))
+        .unwrap_or_default();
+    let mut num_verified = 0;
+    for (slot, actual) in
+        std::iter::once((bank.slot(), bank.hash())).chain(slot_hashes.iter().copied())
+    {
+        let Some(expected) = expected_bank_hashes.get(&slot).copied() else {
+            continue;
+        };
+        if actual != expected {
+            error!("Snapshot bank hash of slot {slot} is {actual}, expected {expected}");
+            return Err(ValidatorError::SnapshotBankHashMismatch {
+                slot,
+                actual,
+                expected,
+            });
+        }
+        num_verified += 1;
+    }
+    info!(
+        "Snapshot bank {} verified against {num_verified} expected bank hashes",
+        bank.slot()
+    );
+    Ok(())
+}
+
 // Return if the validator waited on other nodes to start. In this case
 // it should not wait for one of it's votes to land to produce blocks
 // because if the whole network is waiting, then it will stall.
@@ -3157,6 +3466,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_verify_snapshot_bank_hashes() {
+        let (genesis_config, _mint_keypair) = create_genesis_config(100);
+        let bank0 = Arc::new(Bank::new_for_tests(&genesis_config));
+        let bank1 = Bank::new_from_parent(bank0.clone(), &Pubkey::default(), 1);
+        bank1.freeze();
+
+        let expected = BTreeMap::from([(0, bank0.hash()), (1, bank1.hash())]);
+        assert!(verify_snapshot_bank_hashes(&bank1, &expected).is_ok());
+
+        // Ancestors without an expected hash are skipped
+        let expected = BTreeMap::from([(1, bank1.hash())]);
+        assert!(verify_snapshot_bank_hashes(&bank1, &expected).is_ok());
+
+        // The snapshot slot itself must have an expected hash
+        let expected = BTreeMap::from([(0, bank0.hash())]);
+        assert!(matches!(
+            verify_snapshot_bank_hashes(&bank1, &expected),
+            Err(ValidatorError::SnapshotBankHashUnknown(1)),
+        ));
+
+        let expected = BTreeMap::from([(1, Hash::new_unique())]);
+        assert!(matches!(
+            verify_snapshot_bank_hashes(&bank1, &expected),
+            Err(ValidatorError::SnapshotBankHashMismatch { slot: 1, .. }),
+        ));
+
+        // A mismatching ancestor fails too
+        let expected = BTreeMap::from([(0, Hash::new_unique()), (1, bank1.hash())]);
+        assert!(matches!(
+            verify_snapshot_bank_hashes(&bank1, &expected),
+            Err(ValidatorError::SnapshotBankHashMismatch { slot: 0, .. }),
+        ));
+    }
+
     #[test]
     fn test_interval_check() {
         fn new_snapshot_config(
@@ -0,0 +1,301 @@
+//! The `vote_equivocation_detector` module cross-checks the votes received
+//! through gossip against the votes included in replayed blocks, and records in
+//! blockstore any vote account which voted for two different bank hashes of the
+//! same slot. It complements the duplicate shred detection, which catches
+//! leaders producing two versions of a block, by catching validators voting for
+//! both.
+
+use {
+    solana_ledger::{
+        blockstore::Blockstore,
+        blockstore_meta::{DuplicateVote, DuplicateVoteProof},
+    },
+    solana_sdk::{clock::Slot, pubkey::Pubkey, timing::AtomicInterval, transaction::Transaction},
+    solana_vote::vote_parser::{self, ParsedVote},
+    std::{
+        collections::{hash_map::Entry, BTreeMap, HashMap},
+        sync::Arc,
+    },
+};
+
+// Votes for slots further ahead of the root are ignored, bounding the number of
+// slots tracked if a validator signs votes for arbitrary future slots.
+const MAX_VOTE_SLOT_DISTANCE: Slot = 1_000;
+const STATS_REPORT_INTERVAL_MS: u64 = 10_000;
+
+#[derive(Default)]
+struct VoteEquivocationStats {
+    gossip_votes: u64,
+    repeated_gossip_votes: u64,
+    replay_votes: u64,
+    out_of_range_votes: u64,
+    duplicate_votes: u64,
+    last_report: AtomicInterval,
+}
+
+impl VoteEquivocationStats {
+    fn maybe_report(&mut self, num_tracked_slots: usize) {
+        if !self.last_report.should_update(STATS_REPORT_INTERVAL_MS) {
+            return;
+        }
+        datapoint_info!(
+            "vote-equivocation-detector",
+            ("gossip_votes", self.gossip_votes, i64),
+            ("repeated_gossip_votes", self.repeated_gossip_votes, i64),
+            ("replay_votes", self.replay_votes, i64),
+            ("out_of_range_votes", self.out_of_range_votes, i64),
+            ("duplicate_votes", self.duplicate_votes, i64),
+            ("tracked_slots", num_tracked_slots, i64),
+        );
+        *self = Self {
+            last_report: std::mem::take(&mut self.last_report),
+            ..Self::default()
+        };
+    }
+}
+
+pub struct VoteEquivocationDetector {
+    blockstore: Arc<Blockstore>,
+    root: Slot,
+    // The first vote seen from each vote account, by voted slot
+    votes: BTreeMap<Slot, HashMap<Pubkey, DuplicateVote>>,
+    stats: VoteEquivocationStats,
+}
+
+impl VoteEquivocationDetector {
+    pub fn new(blockstore: Arc<Blockstore>, root: Slot) -> Self {
+        Self {
+            blockstore,
+            root,
+            votes: BTreeMap::default(),
+            stats: VoteEquivocationStats::default(),
+        }
+    }
+
+    /// Forgets the votes for slots up to `root`
+    pub fn set_root(&mut self, root: Slot) {
+        if root > self.root {
+            self.root = root;
+            self.votes = self.votes.split_off(&root.saturating_add(1));
+        }
+    }
+
+    /// Checks the vote transactions received through gossip, returning those
+    /// which were not received before.
+    pub fn process_gossip_votes(&mut self, vote_txs: Vec<Transaction>) -> Vec<Transaction> {
+        let vote_txs = vote_txs
+            .into_iter()
+            .filter(|tx| {
+                self.stats.gossip_votes += 1;
+                let Some((vote_pubkey, vote, _switch_proof, signature)) =
+                    vote_parser::parse_vote_transaction(tx)
+                else {
+                    return true;
+                };
+                let Some((slot, hash)) = vote.last_voted_slot_hash() else {
+                    return true;
+                };
+                let vote = DuplicateVote {
+                    hash,
+                    signature,
+                    transaction: Some(tx.clone()),
+                };
+                let is_repeated = self.process_vote(vote_pubkey, slot, vote);
+                if is_repeated {
+                    self.stats.repeated_gossip_votes += 1;
+                }
+                !is_repeated
+            })
+            .collect();
+        self.stats.maybe_report(self.votes.len());
+        vote_txs
+    }
+
+    /// Checks the votes included in replayed blocks
+    pub fn process_replay_votes(&mut self, votes: &[ParsedVote]) {
+        for (vote_pubkey, vote, _switch_proof, signature) in votes {
+            self.stats.replay_votes += 1;
+            if let Some((slot, hash)) = vote.last_voted_slot_hash() {
+                let vote = DuplicateVote {
+                    hash,
+                    signature: *signature,
+                    transaction: None,
+                };
+                self.process_vote(*vote_pubkey, slot, vote);
+            }
+        }
+        self.stats.maybe_report(self.votes.len());
+    }
+
+    // Returns true if the same vote transaction was seen before
+    fn process_vote(&mut self, vote_pubkey: Pubkey, slot: Slot, vote: DuplicateVote) -> bool {
+        if slot <= self.root || slot > self.root.saturating_add(MAX_VOTE_SLOT_DISTANCE) {
+            self.stats.out_of_range_votes += 1;
+            return false;
+        }
+        let first_vote = match self.votes.entry(slot).or_default().entry(vote_pubkey) {
+            Entry::Vacant(entry) => {
+                entry.insert(vote);
+                return false;
+            }
+            Entry::Occupied(entry) => entry.into_mut(),
+        };
+        if first_vote.signature == vote.signature {
+            return true;
+        }
+        if first_vote.hash == vote.hash || self.blockstore.has_duplicate_vote(slot, vote_pubkey) {
+            return false;
+        }
+
+        warn!(
+            "Vote account {vote_pubkey} voted for slot {slot} with bank hashes {} and {}",
+            first_vote.hash, vote.hash
+        );
+        datapoint_warn!(
+            "vote-equivocation",
+            ("slot", slot, i64),
+            ("vote_pubkey", vote_pubkey.to_string(), String),
+        );
+        self.stats.duplicate_votes += 1;
+        let proof = DuplicateVoteProof {
+            vote1: first_vote.clone(),
+            vote2: vote,
+        };
+        if let Err(err) = self
+            .blockstore
+            .store_duplicate_vote(slot, vote_pubkey, &proof)
+        {
+            error!("Failed to store duplicate vote of {vote_pubkey} for slot {slot}: {err:?}");
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_ledger::get_tmp_ledger_path_auto_delete,
+        solana_sdk::{
+            hash::Hash,
+            signature::{Keypair, Signature, Signer},
+        },
+        solana_vote::vote_transaction::{self, VoteTransaction},
+        solana_vote_program::vote_state::Vote,
+    };
+
+    fn vote_tx(vote_keypair: &Keypair, slot: Slot, hash: Hash, blockhash: Hash) -> Transaction {
+        vote_transaction::new_vote_transaction(
+            vec![slot],
+            hash,
+            blockhash,
+            vote_keypair,
+            vote_keypair,
+            vote_keypair,
+            None,
+        )
+    }
+
+    #[test]
+    fn test_gossip_vote_dedup() {
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let blockstore = Arc::new(Blockstore::open(ledger_path.path()).unwrap());
+        let mut detector = VoteEquivocationDetector::new(blockstore.clone(), 0);
+
+        let vote_keypair = Keypair::new();
+        let hash = Hash::new_unique();
+        let tx = vote_tx(&vote_keypair, 1, hash, Hash::default());
+        // A refreshed vote for the same hash is signed over a different blockhash
+        let refreshed_tx = vote_tx(&vote_keypair, 1, hash, Hash::new_unique());
+
+        let vote_txs = detector.process_gossip_votes(vec![tx.clone(), tx.clone()]);
+        assert_eq!(vote_txs, vec![tx.clone()]);
+        let vote_txs = detector.process_gossip_votes(vec![tx, refreshed_tx.clone()]);
+        assert_eq!(vote_txs, vec![refreshed_tx]);
+        assert!(!blockstore.has_duplicate_vote(1, vote_keypair.pubkey()));
+
+        // Votes at or below the root are let through untracked
+        detector.set_root(1);
+        assert!(detector.votes.is_empty());
+        let tx = vote_tx(&vote_keypair, 1, Hash::new_unique(), Hash::default());
+        let vote_txs = detector.process_gossip_votes(vec![tx.clone(), tx.clone()]);
+        assert_eq!(vote_txs.len(), 2);
+        assert!(detector.votes.is_empty());
+        assert!(!blockstore.has_duplicate_vote(1, vote_keypair.pubkey()));
+    }
+
+    #[test]
+    fn test_duplicate_vote_detection() {
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let blockstore = Arc::new(Blockstore::open(ledger_path.path()).unwrap());
+        let mut detector = VoteEquivocationDetector::new(blockstore.clone(), 0);
+
+        let vote_keypair = Keypair::new();
+        let vote_pubkey = vote_keypair.pubkey();
+        let (hash1, hash2, hash3) = (Hash::new_unique(), Hash::new_unique(), Hash::new_unique());
+        let replay_vote = |slot, hash, signature| {
+            (
+                vote_pubkey,
+                VoteTransaction::from(Vote::new(vec![slot], hash)),
+                None,
+                signature,
+            )
+        };
+
+        // The vote in the block agrees with the vote in gossip
+        let gossip_tx = vote_tx(&vote_keypair, 2, hash1, Hash::default());
+        detector.process_gossip_votes(vec![gossip_tx.clone()]);
+        detector.process_replay_votes(&[replay_vote(2, hash1, gossip_tx.signatures[0])]);
+        assert!(!blockstore.has_duplicate_vote(2, vote_pubkey));
+
+        // A block contains a vote for another hash of the slot
+        let replay_signature = Signature::new_unique();
+        detector.process_replay_votes(&[replay_vote(2, hash2, replay_signature)]);
+        let proof = blockstore.get_duplicate_vote(2, vote_pubkey).unwrap();
+        assert_eq!(
+            proof,
+            DuplicateVoteProof {
+                vote1: DuplicateVote {
+                    hash: hash1,
+                    signature: gossip_tx.signatures[0],
+                    transaction: Some(gossip_tx),
+                },
+                vote2: DuplicateVote {
+                    hash: hash2,
+                    signature: replay_signature,
+                    transaction: None,
+                },
+            }
+        );
+
+        // Only the first proof is kept
+        let gossip_tx = vote_tx(&vote_keypair, 2, hash3, Hash::default());
+        detector.process_gossip_votes(vec![gossip_tx]);
+        assert_eq!(
+            blockstore.get_duplicate_vote(2, vote_pubkey).unwrap(),
+            proof
+        );
+
+        // Two gossip votes for different hashes of a slot
+        let gossip_tx1 = vote_tx(&vote_keypair, 3, hash1, Hash::default());
+        let gossip_tx2 = vote_tx(&vote_keypair, 3, hash2, Hash::default());
+        let vote_txs = detector.process_gossip_votes(vec![gossip_tx1.clone(), gossip_tx2.clone()]);
+        assert_eq!(vote_txs, vec![gossip_tx1.clone(), gossip_tx2.clone()]);
+        let proof = blockstore.get_duplicate_vote(3, vote_pubkey).unwrap();
+        assert_eq!(proof.vote1.transaction, Some(gossip_tx1));
+        assert_eq!(proof.vote2.transaction, Some(gossip_tx2));
+
+        // Other vote accounts voting for other hashes are not duplicates
+        let other_keypair = Keypair::new();
+        detector.process_gossip_votes(vec![vote_tx(&other_keypair, 3, hash3, Hash::default())]);
+        assert!(!blockstore.has_duplicate_vote(3, other_keypair.pubkey()));
+
+        // Votes too far ahead of the root are not tracked
+        let slot = MAX_VOTE_SLOT_DISTANCE + 1;
+        detector.process_gossip_votes(vec![
+            vote_tx(&vote_keypair, slot, hash1, Hash::default()),
+            vote_tx(&vote_keypair, slot, hash2, Hash::default()),
+        ]);
+        assert!(!blockstore.has_duplicate_vote(slot, vote_pubkey));
+    }
+}
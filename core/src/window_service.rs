@@ -1,6 +1,13 @@
 //! `window_service` handles the data plane incoming shreds, storing them in
 //!   blockstore and retransmitting where required
 //!
+//! Verified shreds flow through a pipeline of stages, each running on its own
+//! thread:
+//!   shred-sigverify -> dedup (deserialize, discard stale and duplicate shreds)
+//!   -> insert (write to blockstore) -> check-duplicate
+//! so that deserializing the next batch overlaps with inserting the current
+//! one. The channel from shred-sigverify is unbounded so that a slow
+//! blockstore never stalls signature verification, and with it retransmit.
 
 use {
     crate::{
@@ -11,22 +18,26 @@ use {
         result::{Error, Result},
     },
     assert_matches::debug_assert_matches,
-    crossbeam_channel::{unbounded, Receiver, RecvTimeoutError, Sender},
+    crossbeam_channel::{bounded, unbounded, Receiver, RecvTimeoutError, Sender},
     rayon::{prelude::*, ThreadPool},
     solana_feature_set as feature_set,
     solana_gossip::cluster_info::ClusterInfo,
     solana_ledger::{
         blockstore::{Blockstore, BlockstoreInsertionMetrics, PossibleDuplicateShred},
         leader_schedule_cache::LeaderScheduleCache,
-        shred::{self, ReedSolomonCache, Shred},
+        shred::{self, ReedSolomonCache, Shred, ShredId},
     },
     solana_measure::measure::Measure,
     solana_metrics::inc_new_counter_error,
     solana_rayon_threadlimit::get_thread_count,
     solana_runtime::bank_forks::BankForks,
-    solana_sdk::clock::{Slot, DEFAULT_MS_PER_SLOT},
+    solana_sdk::{
+        clock::{Slot, DEFAULT_MS_PER_SLOT},
+        signature::Signature,
+    },
     solana_turbine::cluster_nodes,
     std::{
+        collections::HashSet,
         net::UdpSocket,
         sync::{
             atomic::{AtomicBool, AtomicUsize, Ordering},
@@ -39,33 +50,102 @@ use {
 
 type DuplicateSlotSender = Sender<Slot>;
 pub(crate) type DuplicateSlotReceiver = Receiver<Slot>;
+type DedupedShredsSender = Sender<Vec<(Shred, /*is_repaired:*/ bool)>>;
+type DedupedShredsReceiver = Receiver<Vec<(Shred, /*is_repaired:*/ bool)>>;
+
+/// Capacity, in batches, of the channel from the dedup to the insert stage.
+const DEDUPED_SHREDS_CHANNEL_CAPACITY: usize = 1024;
 
 #[derive(Default)]
-struct WindowServiceMetrics {
-    run_insert_count: u64,
+struct WindowDedupMetrics {
+    run_dedup_count: u64,
     num_repairs: AtomicUsize,
     num_shreds_received: usize,
+    num_shreds_sent: usize,
+    num_deserialize_failures: usize,
+    num_discarded_below_root: usize,
+    num_discarded_not_repaired: usize,
+    num_duplicates: usize,
+    max_queue_len: usize,
     handle_packets_elapsed_us: u64,
-    shred_receiver_elapsed_us: u64,
+    send_elapsed_us: u64,
     num_errors: u64,
-    num_errors_blockstore: u64,
     num_errors_cross_beam_recv_timeout: u64,
     num_errors_other: u64,
-    num_errors_try_crossbeam_send: u64,
 }
 
-impl WindowServiceMetrics {
+impl WindowDedupMetrics {
     fn report_metrics(&self, metric_name: &'static str) {
         datapoint_info!(
             metric_name,
+            ("run_dedup_count", self.run_dedup_count as i64, i64),
+            ("num_repairs", self.num_repairs.load(Ordering::Relaxed), i64),
+            ("num_shreds_received", self.num_shreds_received, i64),
+            ("num_shreds_sent", self.num_shreds_sent, i64),
+            (
+                "num_deserialize_failures",
+                self.num_deserialize_failures,
+                i64
+            ),
+            (
+                "num_discarded_below_root",
+                self.num_discarded_below_root,
+                i64
+            ),
+            (
+                "num_discarded_not_repaired",
+                self.num_discarded_not_repaired,
+                i64
+            ),
+            ("num_duplicates", self.num_duplicates, i64),
+            ("max_queue_len", self.max_queue_len, i64),
             (
                 "handle_packets_elapsed_us",
                 self.handle_packets_elapsed_us,
                 i64
             ),
+            ("send_elapsed_us", self.send_elapsed_us, i64),
+            ("num_errors", self.num_errors, i64),
+            ("num_errors_other", self.num_errors_other, i64),
+            (
+                "num_errors_cross_beam_recv_timeout",
+                self.num_errors_cross_beam_recv_timeout,
+                i64
+            ),
+        );
+    }
+
+    fn record_error(&mut self, err: &Error) {
+        self.num_errors += 1;
+        match err {
+            Error::RecvTimeout(_) => self.num_errors_cross_beam_recv_timeout += 1,
+            _ => self.num_errors_other += 1,
+        }
+    }
+}
+
+#[derive(Default)]
+struct WindowServiceMetrics {
+    run_insert_count: u64,
+    num_shreds_received: usize,
+    max_queue_len: usize,
+    insert_elapsed_us: u64,
+    shred_receiver_elapsed_us: u64,
+    num_errors: u64,
+    num_errors_blockstore: u64,
+    num_errors_cross_beam_recv_timeout: u64,
+    num_errors_other: u64,
+    num_errors_try_crossbeam_send: u64,
+}
+
+impl WindowServiceMetrics {
+    fn report_metrics(&self, metric_name: &'static str) {
+        datapoint_info!(
+            metric_name,
+            ("insert_elapsed_us", self.insert_elapsed_us, i64),
             ("run_insert_count", self.run_insert_count as i64, i64),
-            ("num_repairs", self.num_repairs.load(Ordering::Relaxed), i64),
             ("num_shreds_received", self.num_shreds_received, i64),
+            ("max_queue_len", self.max_queue_len, i64),
             (
                 "shred_receiver_elapsed_us",
                 self.shred_receiver_elapsed_us as i64,
@@ -176,53 +256,119 @@ fn run_check_duplicate(
         .try_for_each(check_duplicate)
 }
 
-#[allow(clippy::too_many_arguments)]
-fn run_insert<F>(
+/// Deserializes verified shreds and discards the ones which the insert stage
+/// would only spend time rejecting: shreds which fail to deserialize, shreds
+/// for slots at or below the blockstore root, and identical copies of a shred
+/// within the batch (e.g. the same shred received both from turbine and
+/// repair). Copies of a shred with a different signature are kept, since the
+/// insert stage needs both to generate duplicate proofs.
+fn run_dedup(
     thread_pool: &ThreadPool,
     verified_receiver: &Receiver<Vec<(shred::Payload, /*is_repaired:*/ bool)>>,
+    deduped_sender: &DedupedShredsSender,
     blockstore: &Blockstore,
-    leader_schedule_cache: &LeaderScheduleCache,
-    handle_duplicate: F,
-    metrics: &mut BlockstoreInsertionMetrics,
-    ws_metrics: &mut WindowServiceMetrics,
-    completed_data_sets_sender: Option<&CompletedDataSetsSender>,
-    retransmit_sender: &Sender<Vec<shred::Payload>>,
-    reed_solomon_cache: &ReedSolomonCache,
+    metrics: &mut WindowDedupMetrics,
     accept_repairs_only: bool,
-) -> Result<()>
-where
-    F: Fn(PossibleDuplicateShred),
-{
+) -> Result<()> {
     const RECV_TIMEOUT: Duration = Duration::from_millis(200);
-    let mut shred_receiver_elapsed = Measure::start("shred_receiver_elapsed");
+    metrics.max_queue_len = metrics.max_queue_len.max(verified_receiver.len());
     let mut shreds = verified_receiver.recv_timeout(RECV_TIMEOUT)?;
     shreds.extend(verified_receiver.try_iter().flatten());
-    shred_receiver_elapsed.stop();
-    ws_metrics.shred_receiver_elapsed_us += shred_receiver_elapsed.as_us();
-    ws_metrics.run_insert_count += 1;
+    metrics.run_dedup_count += 1;
+    metrics.num_shreds_received += shreds.len();
+    let num_repairs = &metrics.num_repairs;
     let handle_shred = |(shred, repair): (shred::Payload, bool)| {
         if accept_repairs_only && !repair {
-            return None;
+            return Err(DedupDiscard::NotRepaired);
         }
         if repair {
-            ws_metrics.num_repairs.fetch_add(1, Ordering::Relaxed);
+            num_repairs.fetch_add(1, Ordering::Relaxed);
             debug_assert_matches!(shred, shred::Payload::Unique(_));
         } else {
             debug_assert_matches!(shred, shred::Payload::Shared(_));
         }
-        let shred = Shred::new_from_serialized_shred(shred).ok()?;
-        Some((shred, repair))
+        let shred =
+            Shred::new_from_serialized_shred(shred).map_err(|_| DedupDiscard::Deserialize)?;
+        Ok((shred, repair))
     };
     let now = Instant::now();
+    let root = blockstore.max_root();
     let shreds: Vec<_> = thread_pool.install(|| {
         shreds
             .into_par_iter()
             .with_min_len(32)
-            .filter_map(handle_shred)
+            .map(handle_shred)
             .collect()
     });
-    ws_metrics.handle_packets_elapsed_us += now.elapsed().as_micros() as u64;
+    let mut seen = HashSet::<(ShredId, Signature)>::with_capacity(shreds.len());
+    let shreds: Vec<_> = shreds
+        .into_iter()
+        .filter_map(|shred| match shred {
+            Err(DedupDiscard::NotRepaired) => {
+                metrics.num_discarded_not_repaired += 1;
+                None
+            }
+            Err(DedupDiscard::Deserialize) => {
+                metrics.num_deserialize_failures += 1;
+                None
+            }
+            // Blockstore only accepts writes at or below the root for the genesis slot.
+            Ok((shred, _)) if root > 0 && shred.slot() <= root => {
+                metrics.num_discarded_below_root += 1;
+                None
+            }
+            Ok((shred, repair)) => {
+                if seen.insert((shred.id(), *shred.signature())) {
+                    Some((shred, repair))
+                } else {
+                    metrics.num_duplicates += 1;
+                    None
+                }
+            }
+        })
+        .collect();
+    metrics.handle_packets_elapsed_us += now.elapsed().as_micros() as u64;
+    if shreds.is_empty() {
+        return Ok(());
+    }
+    metrics.num_shreds_sent += shreds.len();
+    // Blocks while the insert stage is backed up.
+    let now = Instant::now();
+    deduped_sender.send(shreds)?;
+    metrics.send_elapsed_us += now.elapsed().as_micros() as u64;
+    Ok(())
+}
+
+enum DedupDiscard {
+    NotRepaired,
+    Deserialize,
+}
+
+#[allow(clippy::too_many_arguments)]
+fn run_insert<F>(
+    deduped_receiver: &DedupedShredsReceiver,
+    blockstore: &Blockstore,
+    leader_schedule_cache: &LeaderScheduleCache,
+    handle_duplicate: F,
+    metrics: &mut BlockstoreInsertionMetrics,
+    ws_metrics: &mut WindowServiceMetrics,
+    completed_data_sets_sender: Option<&CompletedDataSetsSender>,
+    retransmit_sender: &Sender<Vec<shred::Payload>>,
+    reed_solomon_cache: &ReedSolomonCache,
+) -> Result<()>
+where
+    F: Fn(PossibleDuplicateShred),
+{
+    const RECV_TIMEOUT: Duration = Duration::from_millis(200);
+    ws_metrics.max_queue_len = ws_metrics.max_queue_len.max(deduped_receiver.len());
+    let mut shred_receiver_elapsed = Measure::start("shred_receiver_elapsed");
+    let mut shreds = deduped_receiver.recv_timeout(RECV_TIMEOUT)?;
+    shreds.extend(deduped_receiver.try_iter().flatten());
+    shred_receiver_elapsed.stop();
+    ws_metrics.shred_receiver_elapsed_us += shred_receiver_elapsed.as_us();
+    ws_metrics.run_insert_count += 1;
     ws_metrics.num_shreds_received += shreds.len();
+    let now = Instant::now();
     let completed_data_sets = blockstore.insert_shreds_handle_duplicate(
         shreds,
         Some(leader_schedule_cache),
@@ -232,6 +378,7 @@ where
         reed_solomon_cache,
         metrics,
     )?;
+    ws_metrics.insert_elapsed_us += now.elapsed().as_micros() as u64;
 
     if let Some(sender) = completed_data_sets_sender {
         sender.try_send(completed_data_sets)?;
@@ -267,6 +414,7 @@ impl WindowServiceChannels {
 }
 
 pub(crate) struct WindowService {
+    t_dedup: JoinHandle<()>,
     t_insert: JoinHandle<()>,
     t_check_duplicate: JoinHandle<()>,
     repair_service: RepairService,
@@ -309,6 +457,7 @@ impl WindowService {
         );
 
         let (duplicate_sender, duplicate_receiver) = unbounded();
+        let (deduped_sender, deduped_receiver) = bounded(DEDUPED_SHREDS_CHANNEL_CAPACITY);
 
         let t_check_duplicate = Self::start_check_duplicate_thread(
             cluster_info,
@@ -319,18 +468,26 @@ impl WindowService {
             bank_forks,
         );
 
+        let t_dedup = Self::start_window_dedup_thread(
+            exit.clone(),
+            blockstore.clone(),
+            verified_receiver,
+            deduped_sender,
+            accept_repairs_only,
+        );
+
         let t_insert = Self::start_window_insert_thread(
             exit,
             blockstore,
             leader_schedule_cache,
-            verified_receiver,
+            deduped_receiver,
             duplicate_sender,
             completed_data_sets_sender,
             retransmit_sender,
-            accept_repairs_only,
         );
 
         WindowService {
+            t_dedup,
             t_insert,
             t_check_duplicate,
             repair_service,
@@ -368,22 +525,18 @@ impl WindowService {
             .unwrap()
     }
 
-    fn start_window_insert_thread(
+    fn start_window_dedup_thread(
         exit: Arc<AtomicBool>,
         blockstore: Arc<Blockstore>,
-        leader_schedule_cache: Arc<LeaderScheduleCache>,
         verified_receiver: Receiver<Vec<(shred::Payload, /*is_repaired:*/ bool)>>,
-        check_duplicate_sender: Sender<PossibleDuplicateShred>,
-        completed_data_sets_sender: Option<CompletedDataSetsSender>,
-        retransmit_sender: Sender<Vec<shred::Payload>>,
+        deduped_sender: DedupedShredsSender,
         accept_repairs_only: bool,
     ) -> JoinHandle<()> {
         let handle_error = || {
-            inc_new_counter_error!("solana-window-insert-error", 1, 1);
+            inc_new_counter_error!("solana-window-dedup-error", 1, 1);
         };
-        let reed_solomon_cache = ReedSolomonCache::default();
         Builder::new()
-            .name("solWinInsert".to_string())
+            .name("solWinDedup".to_string())
             .spawn(move || {
                 let thread_pool = rayon::ThreadPoolBuilder::new()
                     .num_threads(get_thread_count().min(8))
@@ -391,9 +544,52 @@ impl WindowService {
                     // pool is used to process a small number of shreds, since they'll be processed
                     // directly on the current thread.
                     .use_current_thread()
-                    .thread_name(|i| format!("solWinInsert{i:02}"))
+                    .thread_name(|i| format!("solWinDedup{i:02}"))
                     .build()
                     .unwrap();
+                let mut metrics = WindowDedupMetrics::default();
+                let mut last_print = Instant::now();
+                while !exit.load(Ordering::Relaxed) {
+                    if let Err(e) = run_dedup(
+                        &thread_pool,
+                        &verified_receiver,
+                        &deduped_sender,
+                        &blockstore,
+                        &mut metrics,
+                        accept_repairs_only,
+                    ) {
+                        metrics.record_error(&e);
+                        if Self::should_exit_on_error(e, &handle_error) {
+                            break;
+                        }
+                    }
+
+                    if last_print.elapsed().as_secs() > 2 {
+                        metrics.report_metrics("recv-window-dedup-shreds");
+                        metrics = WindowDedupMetrics::default();
+                        last_print = Instant::now();
+                    }
+                }
+            })
+            .unwrap()
+    }
+
+    fn start_window_insert_thread(
+        exit: Arc<AtomicBool>,
+        blockstore: Arc<Blockstore>,
+        leader_schedule_cache: Arc<LeaderScheduleCache>,
+        deduped_receiver: DedupedShredsReceiver,
+        check_duplicate_sender: Sender<PossibleDuplicateShred>,
+        completed_data_sets_sender: Option<CompletedDataSetsSender>,
+        retransmit_sender: Sender<Vec<shred::Payload>>,
+    ) -> JoinHandle<()> {
+        let handle_error = || {
+            inc_new_counter_error!("solana-window-insert-error", 1, 1);
+        };
+        let reed_solomon_cache = ReedSolomonCache::default();
+        Builder::new()
+            .name("solWinInsert".to_string())
+            .spawn(move || {
                 let handle_duplicate = |possible_duplicate_shred| {
                     let _ = check_duplicate_sender.send(possible_duplicate_shred);
                 };
@@ -402,8 +598,7 @@ impl WindowService {
                 let mut last_print = Instant::now();
                 while !exit.load(Ordering::Relaxed) {
                     if let Err(e) = run_insert(
-                        &thread_pool,
-                        &verified_receiver,
+                        &deduped_receiver,
                         &blockstore,
                         &leader_schedule_cache,
                         handle_duplicate,
@@ -412,7 +607,6 @@ impl WindowService {
                         completed_data_sets_sender.as_ref(),
                         &retransmit_sender,
                         &reed_solomon_cache,
-                        accept_repairs_only,
                     ) {
                         ws_metrics.record_error(&e);
                         if Self::should_exit_on_error(e, &handle_error) {
@@ -449,6 +643,7 @@ impl WindowService {
     }
 
     pub(crate) fn join(self) -> thread::Result<()> {
+        self.t_dedup.join()?;
         self.t_insert.join()?;
         self.t_check_duplicate.join()?;
         self.repair_service.join()
@@ -514,6 +709,96 @@ mod test {
         assert_eq!(blockstore.get_slot_entries(0, 0).unwrap(), original_entries);
     }
 
+    #[test]
+    fn test_run_dedup() {
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let blockstore = Blockstore::open(ledger_path.path()).unwrap();
+        blockstore.set_roots(std::iter::once(&3)).unwrap();
+        let thread_pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(2)
+            .build()
+            .unwrap();
+        let (verified_sender, verified_receiver) = unbounded();
+        let (deduped_sender, deduped_receiver) = bounded(DEDUPED_SHREDS_CHANNEL_CAPACITY);
+        let (stale_shreds, _) = make_many_slot_entries(2, 1, 10);
+        let (shreds, _) = make_many_slot_entries(5, 1, 10);
+        // Same shred id as shreds[0], but signed by a different leader.
+        let conflicting_shred = {
+            let (mut shreds, _) = make_many_slot_entries(5, 1, 10);
+            shreds.swap_remove(0)
+        };
+        let turbine = |shred: &Shred| {
+            let payload = shred::Payload::from(Arc::new(shred.payload().to_vec()));
+            (payload, /*is_repaired:*/ false)
+        };
+        let repair = |shred: &Shred| {
+            let payload = shred::Payload::from(shred.payload().to_vec());
+            (payload, /*is_repaired:*/ true)
+        };
+        verified_sender
+            .send(shreds.iter().chain(&stale_shreds).map(turbine).collect())
+            .unwrap();
+        verified_sender
+            .send(
+                shreds
+                    .iter()
+                    .map(repair)
+                    .chain([turbine(&conflicting_shred)])
+                    .chain([(shred::Payload::from(vec![0u8; 16]), true)])
+                    .collect(),
+            )
+            .unwrap();
+
+        let mut metrics = WindowDedupMetrics::default();
+        run_dedup(
+            &thread_pool,
+            &verified_receiver,
+            &deduped_sender,
+            &blockstore,
+            &mut metrics,
+            false, // accept_repairs_only
+        )
+        .unwrap();
+        assert_eq!(
+            metrics.num_shreds_received,
+            2 * shreds.len() + stale_shreds.len() + 2
+        );
+        assert_eq!(metrics.num_discarded_below_root, stale_shreds.len());
+        assert_eq!(metrics.num_duplicates, shreds.len());
+        assert_eq!(metrics.num_deserialize_failures, 1);
+        assert_eq!(
+            metrics.num_repairs.load(Ordering::Relaxed),
+            shreds.len() + 1
+        );
+
+        // Turbine copies arrived first and are kept; the conflicting shred is
+        // passed on to the insert stage so that a duplicate proof is generated.
+        let deduped = deduped_receiver.try_recv().unwrap();
+        assert!(deduped_receiver.try_recv().is_err());
+        assert_eq!(deduped.len(), shreds.len() + 1);
+        assert!(deduped.iter().all(|(_, repair)| !repair));
+        let (last, _) = deduped.last().unwrap();
+        assert_eq!(last.id(), shreds[0].id());
+        assert_eq!(last.payload(), conflicting_shred.payload());
+
+        // In wen_restart only repaired shreds are passed on.
+        verified_sender
+            .send(shreds.iter().map(turbine).collect())
+            .unwrap();
+        let mut metrics = WindowDedupMetrics::default();
+        run_dedup(
+            &thread_pool,
+            &verified_receiver,
+            &deduped_sender,
+            &blockstore,
+            &mut metrics,
+            true, // accept_repairs_only
+        )
+        .unwrap();
+        assert_eq!(metrics.num_discarded_not_repaired, shreds.len());
+        assert!(deduped_receiver.try_recv().is_err());
+    }
+
     #[test]
     fn test_run_check_duplicate() {
         let ledger_path = get_tmp_ledger_path_auto_delete!();
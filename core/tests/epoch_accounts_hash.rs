@@ -209,7 +209,7 @@ impl BackgroundServices {
         let accounts_background_request_sender =
             AbsRequestSender::new(snapshot_request_sender.clone());
         let snapshot_request_handler = SnapshotRequestHandler {
-            snapshot_config: snapshot_config.clone(),
+            snapshot_config: Arc::new(RwLock::new(snapshot_config.clone())),
             snapshot_request_sender,
             snapshot_request_receiver,
             accounts_package_sender,
@@ -204,7 +204,7 @@ fn run_bank_forks_snapshot_n<F>(
     let (snapshot_request_sender, snapshot_request_receiver) = unbounded();
     let request_sender = AbsRequestSender::new(snapshot_request_sender.clone());
     let snapshot_request_handler = SnapshotRequestHandler {
-        snapshot_config: snapshot_test_config.snapshot_config.clone(),
+        snapshot_config: Arc::new(RwLock::new(snapshot_test_config.snapshot_config.clone())),
         snapshot_request_sender,
         snapshot_request_receiver,
         accounts_package_sender,
@@ -468,7 +468,7 @@ fn test_bank_forks_incremental_snapshot(
     let (snapshot_request_sender, snapshot_request_receiver) = unbounded();
     let request_sender = AbsRequestSender::new(snapshot_request_sender.clone());
     let snapshot_request_handler = SnapshotRequestHandler {
-        snapshot_config: snapshot_test_config.snapshot_config.clone(),
+        snapshot_config: Arc::new(RwLock::new(snapshot_test_config.snapshot_config.clone())),
         snapshot_request_sender,
         snapshot_request_receiver,
         accounts_package_sender,
@@ -712,7 +712,7 @@ fn test_snapshots_with_background_services(
 
     let abs_request_sender = AbsRequestSender::new(snapshot_request_sender.clone());
     let snapshot_request_handler = SnapshotRequestHandler {
-        snapshot_config: snapshot_test_config.snapshot_config.clone(),
+        snapshot_config: Arc::new(RwLock::new(snapshot_test_config.snapshot_config.clone())),
         snapshot_request_sender,
         snapshot_request_receiver,
         accounts_package_sender: accounts_package_sender.clone(),
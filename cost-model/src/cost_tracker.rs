@@ -266,6 +266,23 @@ impl CostTracker {
         );
     }
 
+    /// Returns up to `count` writable accounts with the highest accumulated
+    /// cost, in decreasing order of cost.
+    pub fn get_costliest_accounts(&self, count: usize) -> Vec<(Pubkey, u64)> {
+        let mut accounts: Vec<_> = self
+            .cost_by_writable_accounts
+            .iter()
+            .filter(|(_, &cost)| cost > 0)
+            .map(|(&pubkey, &cost)| (pubkey, cost))
+            .collect();
+        if accounts.len() > count {
+            accounts.select_nth_unstable_by(count, |(_, a), (_, b)| b.cmp(a));
+            accounts.truncate(count);
+        }
+        accounts.sort_unstable_by(|(_, a), (_, b)| b.cmp(a));
+        accounts
+    }
+
     fn find_costliest_account(&self) -> (Pubkey, u64) {
         self.cost_by_writable_accounts
             .iter()
@@ -582,6 +599,25 @@ mod tests {
         assert_eq!(std::cmp::max(cost1, cost2), costliest_account_cost);
     }
 
+    #[test]
+    fn test_cost_tracker_get_costliest_accounts() {
+        let keypairs: Vec<_> = (0..3).map(|_| Keypair::new()).collect();
+        let txs: Vec<_> = keypairs.iter().map(build_simple_transaction).collect();
+        let mut testee = CostTracker::default();
+        assert!(testee.get_costliest_accounts(2).is_empty());
+
+        for (tx, programs_execution_cost) in txs.iter().zip([5, 20, 10]) {
+            let tx_cost = simple_transaction_cost(tx, programs_execution_cost);
+            testee.add_transaction_cost(&tx_cost);
+        }
+        assert_eq!(
+            testee.get_costliest_accounts(2),
+            vec![(keypairs[1].pubkey(), 20), (keypairs[2].pubkey(), 10)]
+        );
+        assert_eq!(testee.get_costliest_accounts(5).len(), 3);
+        assert!(testee.get_costliest_accounts(0).is_empty());
+    }
+
     #[test]
     fn test_cost_tracker_chain_reach_limit() {
         let mint_keypair = test_setup();
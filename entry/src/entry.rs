@@ -249,7 +249,7 @@ pub fn next_hash(
 }
 
 /// Last action required to verify an entry
-enum VerifyAction {
+pub(crate) enum VerifyAction {
     /// Mixin a hash before computing the last hash for a transaction entry
     Mixin(Hash),
     /// Compute one last hash for a tick entry
@@ -258,6 +258,137 @@ enum VerifyAction {
     None,
 }
 
+impl VerifyAction {
+    /// Returns the entry hash expected after the entry's plain hashes end at `hash`.
+    pub(crate) fn apply(self, hash: Hash) -> Hash {
+        match self {
+            VerifyAction::Mixin(mixin) => Poh::new(hash, None).record(mixin).unwrap().hash,
+            VerifyAction::Tick => Poh::new(hash, None).tick().unwrap().hash,
+            VerifyAction::None => hash,
+        }
+    }
+
+    pub(crate) fn for_entry(entry: &Entry) -> Self {
+        if entry.transactions.is_empty() {
+            if entry.num_hashes == 0 {
+                VerifyAction::None
+            } else {
+                VerifyAction::Tick
+            }
+        } else {
+            VerifyAction::Mixin(hash_transactions(&entry.transactions))
+        }
+    }
+}
+
+/// The Proof of History of a batch of entries, copied out of the entries so
+/// that it can be verified while they move on to transaction verification
+pub(crate) struct PohVerificationData {
+    /// The hash preceding each entry, which `hash` turns into the hash before
+    /// each entry's final hash or mixin
+    hashes: PinnedVec<Hash>,
+    /// The number of plain hashes from the preceding hash to each entry's
+    /// final hash or mixin
+    num_hashes: PinnedVec<u64>,
+    verifications: Vec<(VerifyAction, Hash)>,
+}
+
+impl PohVerificationData {
+    pub(crate) fn new(
+        entries: &[Entry],
+        start_hash: &Hash,
+        recyclers: &VerifyRecyclers,
+        thread_pool: &ThreadPool,
+    ) -> Self {
+        let genesis = [Entry {
+            num_hashes: 0,
+            hash: *start_hash,
+            transactions: vec![],
+        }];
+
+        let hashes: Vec<Hash> = genesis
+            .iter()
+            .chain(entries)
+            .map(|entry| entry.hash)
+            .take(entries.len())
+            .collect();
+
+        let mut hashes_pinned = recyclers.hash_recycler.allocate("poh_verify_hash");
+        hashes_pinned.set_pinnable();
+        hashes_pinned.resize(hashes.len(), Hash::default());
+        hashes_pinned.copy_from_slice(&hashes);
+
+        let mut num_hashes = recyclers
+            .tick_count_recycler
+            .allocate("poh_verify_num_hashes");
+        num_hashes.reserve_and_pin(cmp::max(1, entries.len()));
+        for entry in entries {
+            num_hashes.push(entry.num_hashes.saturating_sub(1));
+        }
+
+        let verifications = thread_pool.install(|| {
+            entries
+                .into_par_iter()
+                .map(|entry| (VerifyAction::for_entry(entry), entry.hash))
+                .collect()
+        });
+        Self {
+            hashes: hashes_pinned,
+            num_hashes,
+            verifications,
+        }
+    }
+
+    /// Computes the plain hashes of the entries, on the GPU if perf-libs are
+    /// loaded and on `thread_pool` otherwise
+    pub(crate) fn hash(&mut self, thread_pool: &ThreadPool) {
+        match perf_libs::api() {
+            Some(api) => gpu_poh_verify_many(api, &mut self.hashes[..], &self.num_hashes[..]),
+            None => thread_pool.install(|| {
+                self.hashes
+                    .par_iter_mut()
+                    .zip(self.num_hashes.par_iter())
+                    .for_each(|(hash_out, num_hashes)| {
+                        for _ in 0..*num_hashes {
+                            *hash_out = solana_sha256_hasher::hash(hash_out.as_ref());
+                        }
+                    })
+            }),
+        }
+    }
+
+    /// Returns whether the entries, once hashed, end with their hashes
+    pub(crate) fn verify(self, thread_pool: &ThreadPool) -> bool {
+        verify_hashed_entries(&self.hashes[..], self.verifications, thread_pool)
+    }
+}
+
+fn gpu_poh_verify_many(api: &perf_libs::Api, hashes: &mut [Hash], num_hashes: &[u64]) {
+    let res = unsafe {
+        (api.poh_verify_many)(
+            hashes.as_mut_ptr() as *mut u8,
+            num_hashes.as_ptr(),
+            hashes.len(),
+            1,
+        )
+    };
+    assert!(res == 0, "GPU PoH verify many failed");
+}
+
+fn verify_hashed_entries(
+    hashes: &[Hash],
+    verifications: Vec<(VerifyAction, Hash)>,
+    thread_pool: &ThreadPool,
+) -> bool {
+    thread_pool.install(|| {
+        hashes
+            .par_iter()
+            .copied()
+            .zip(verifications)
+            .all(|(hash, (action, expected))| action.apply(hash) == expected)
+    })
+}
+
 pub struct GpuVerificationData {
     thread_h: Option<JoinHandle<u64>>,
     hashes: Option<Arc<Mutex<PinnedVec<Hash>>>>,
@@ -267,12 +398,15 @@ pub struct GpuVerificationData {
 pub enum DeviceVerificationData {
     Cpu(),
     Gpu(GpuVerificationData),
+    /// Queued on an `EntryVerifier`, which sends whether the entries verified
+    /// and how long that took in microseconds.
+    Queued(Receiver<(bool, u64)>),
 }
 
 pub struct EntryVerificationState {
-    verification_status: EntryVerificationStatus,
-    poh_duration_us: u64,
-    device_verification_data: DeviceVerificationData,
+    pub(crate) verification_status: EntryVerificationStatus,
+    pub(crate) poh_duration_us: u64,
+    pub(crate) device_verification_data: DeviceVerificationData,
 }
 
 pub struct GpuSigVerificationData {
@@ -357,22 +491,11 @@ impl EntryVerificationState {
                     .expect("unwrap Arc")
                     .into_inner()
                     .expect("into_inner");
-                let res = thread_pool.install(|| {
-                    hashes
-                        .into_par_iter()
-                        .cloned()
-                        .zip(verification_state.verifications.take().unwrap())
-                        .all(|(hash, (action, expected))| {
-                            let actual = match action {
-                                VerifyAction::Mixin(mixin) => {
-                                    Poh::new(hash, None).record(mixin).unwrap().hash
-                                }
-                                VerifyAction::Tick => Poh::new(hash, None).tick().unwrap().hash,
-                                VerifyAction::None => hash,
-                            };
-                            actual == expected
-                        })
-                });
+                let res = verify_hashed_entries(
+                    &hashes[..],
+                    verification_state.verifications.take().unwrap(),
+                    thread_pool,
+                );
                 verify_check_time.stop();
                 self.poh_duration_us += gpu_time_us + verify_check_time.as_us();
 
@@ -383,6 +506,18 @@ impl EntryVerificationState {
                 };
                 res
             }
+            DeviceVerificationData::Queued(result_receiver) => {
+                let (res, poh_duration_us) = result_receiver
+                    .recv()
+                    .expect("entry verifier drains its queue before exiting");
+                self.poh_duration_us += poh_duration_us;
+                self.verification_status = if res {
+                    EntryVerificationStatus::Success
+                } else {
+                    EntryVerificationStatus::Failure
+                };
+                res
+            }
             DeviceVerificationData::Cpu() => {
                 self.verification_status == EntryVerificationStatus::Success
             }
@@ -798,34 +933,12 @@ impl EntrySlice for [Entry] {
         };
         inc_new_counter_info!("entry_verify-num_entries", self.len());
 
-        let genesis = [Entry {
-            num_hashes: 0,
-            hash: *start_hash,
-            transactions: vec![],
-        }];
-
-        let hashes: Vec<Hash> = genesis
-            .iter()
-            .chain(self)
-            .map(|entry| entry.hash)
-            .take(self.len())
-            .collect();
-
-        let mut hashes_pinned = recyclers.hash_recycler.allocate("poh_verify_hash");
-        hashes_pinned.set_pinnable();
-        hashes_pinned.resize(hashes.len(), Hash::default());
-        hashes_pinned.copy_from_slice(&hashes);
-
-        let mut num_hashes_vec = recyclers
-            .tick_count_recycler
-            .allocate("poh_verify_num_hashes");
-        num_hashes_vec.reserve_and_pin(cmp::max(1, self.len()));
-        for entry in self {
-            num_hashes_vec.push(entry.num_hashes.saturating_sub(1));
-        }
-
-        let length = self.len();
-        let hashes = Arc::new(Mutex::new(hashes_pinned));
+        let PohVerificationData {
+            hashes,
+            num_hashes,
+            verifications,
+        } = PohVerificationData::new(self, start_hash, &recyclers, thread_pool);
+        let hashes = Arc::new(Mutex::new(hashes));
         let hashes_clone = hashes.clone();
 
         let gpu_verify_thread = thread::Builder::new()
@@ -833,16 +946,7 @@ impl EntrySlice for [Entry] {
             .spawn(move || {
                 let mut hashes = hashes_clone.lock().unwrap();
                 let gpu_wait = Instant::now();
-                let res;
-                unsafe {
-                    res = (api.poh_verify_many)(
-                        hashes.as_mut_ptr() as *mut u8,
-                        num_hashes_vec.as_ptr(),
-                        length,
-                        1,
-                    );
-                }
-                assert!(res == 0, "GPU PoH verify many failed");
+                gpu_poh_verify_many(api, &mut hashes[..], &num_hashes[..]);
                 inc_new_counter_info!(
                     "entry_verify-gpu_thread",
                     gpu_wait.elapsed().as_micros() as usize
@@ -851,23 +955,6 @@ impl EntrySlice for [Entry] {
             })
             .unwrap();
 
-        let verifications = thread_pool.install(|| {
-            self.into_par_iter()
-                .map(|entry| {
-                    let answer = entry.hash;
-                    let action = if entry.transactions.is_empty() {
-                        if entry.num_hashes == 0 {
-                            VerifyAction::None
-                        } else {
-                            VerifyAction::Tick
-                        }
-                    } else {
-                        VerifyAction::Mixin(hash_transactions(&entry.transactions))
-                    };
-                    (action, answer)
-                })
-                .collect()
-        });
         let device_verification_data = DeviceVerificationData::Gpu(GpuVerificationData {
             thread_h: Some(gpu_verify_thread),
             verifications: Some(verifications),
@@ -0,0 +1,182 @@
+//! The `entry_verifier` module provides a service that verifies the Proof of
+//! History of entry batches in the background. Callers queue a batch, move on
+//! to the next one, and collect the result later through
+//! `EntryVerificationState::finish_verify`, so that verifying one batch
+//! overlaps with fetching and replaying the next.
+use {
+    crate::entry::{
+        DeviceVerificationData, Entry, EntryVerificationState, EntryVerificationStatus,
+        PohVerificationData, VerifyRecyclers,
+    },
+    crossbeam_channel::{bounded, Receiver, Sender},
+    log::*,
+    rayon::ThreadPool,
+    solana_hash::Hash,
+    solana_metrics::inc_new_counter_info,
+    std::{
+        sync::Arc,
+        thread::{self, Builder, JoinHandle},
+        time::Instant,
+    },
+};
+
+/// Default number of batches that may wait for verification before
+/// `EntryVerifier::start_verify` blocks the caller.
+pub const DEFAULT_MAX_QUEUED_BATCHES: usize = 32;
+
+struct PohBatch {
+    poh_verification_data: PohVerificationData,
+    result_sender: Sender<(bool, u64)>,
+}
+
+pub struct EntryVerifier {
+    batch_sender: Sender<PohBatch>,
+    thread_pool: Arc<ThreadPool>,
+    recyclers: VerifyRecyclers,
+    thread_hdl: JoinHandle<()>,
+}
+
+impl EntryVerifier {
+    /// Spawns the verifier. Batches are hashed on the GPU if perf-libs are
+    /// loaded, and on `thread_pool` otherwise, which the verifier shares with
+    /// the caller. At most `max_queued_batches` batches wait for verification
+    /// at a time.
+    pub fn new(thread_pool: Arc<ThreadPool>, max_queued_batches: usize) -> Self {
+        let (batch_sender, batch_receiver) = bounded(max_queued_batches);
+        let thread_hdl = {
+            let thread_pool = thread_pool.clone();
+            Builder::new()
+                .name("solEntryVerify".to_string())
+                .spawn(move || Self::run(batch_receiver, &thread_pool))
+                .unwrap()
+        };
+        Self {
+            batch_sender,
+            thread_pool,
+            recyclers: VerifyRecyclers::default(),
+            thread_hdl,
+        }
+    }
+
+    /// Queues Proof of History verification of `entries`, which must follow
+    /// `start_hash`, blocking while the queue is full. The returned state
+    /// stays pending until finished with `finish_verify`.
+    pub fn start_verify(&self, entries: &[Entry], start_hash: &Hash) -> EntryVerificationState {
+        let start = Instant::now();
+        let (result_sender, result_receiver) = bounded(1);
+        if entries.is_empty() {
+            result_sender.send((true, 0)).unwrap();
+        } else {
+            let batch = PohBatch {
+                poh_verification_data: PohVerificationData::new(
+                    entries,
+                    start_hash,
+                    &self.recyclers,
+                    &self.thread_pool,
+                ),
+                result_sender,
+            };
+            if self.batch_sender.is_full() {
+                inc_new_counter_info!("entry_verifier-queue_full", 1);
+            }
+            self.batch_sender
+                .send(batch)
+                .expect("entry verifier thread exited");
+        }
+        EntryVerificationState {
+            verification_status: EntryVerificationStatus::Pending,
+            poh_duration_us: start.elapsed().as_micros() as u64,
+            device_verification_data: DeviceVerificationData::Queued(result_receiver),
+        }
+    }
+
+    /// Stops the verifier once every queued batch has been verified.
+    pub fn join(self) -> thread::Result<()> {
+        let Self {
+            batch_sender,
+            thread_hdl,
+            ..
+        } = self;
+        drop(batch_sender);
+        thread_hdl.join()
+    }
+
+    fn run(batch_receiver: Receiver<PohBatch>, thread_pool: &ThreadPool) {
+        // Exits once the verifier, and with it the sender, is dropped.
+        for batch in batch_receiver.iter() {
+            let start = Instant::now();
+            let PohBatch {
+                mut poh_verification_data,
+                result_sender,
+            } = batch;
+            poh_verification_data.hash(thread_pool);
+            let verified = poh_verification_data.verify(thread_pool);
+            if !verified {
+                warn!("entry verifier: proof of history mismatch");
+            }
+            // Replay may have given up on the slot and dropped the receiver.
+            let _ = result_sender.send((verified, start.elapsed().as_micros() as u64));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::entry::{create_random_ticks, next_entry, EntrySlice},
+        solana_keypair::Keypair,
+        solana_sha256_hasher::hash,
+        solana_signer::Signer,
+        solana_system_transaction as system_transaction,
+    };
+
+    #[test]
+    fn test_entry_verifier() {
+        let thread_pool = Arc::new(crate::entry::thread_pool_for_tests());
+        let entry_verifier = EntryVerifier::new(thread_pool.clone(), 2);
+
+        let zero = Hash::default();
+        let keypair = Keypair::new();
+        let tx = system_transaction::transfer(&keypair, &keypair.pubkey(), 1, zero);
+        let mut entries = create_random_ticks(8, 100, zero);
+        let last_hash = entries.last().unwrap().hash;
+        entries.push(next_entry(&last_hash, 10, vec![tx]));
+        entries.push(next_entry(&entries.last().unwrap().hash, 0, vec![]));
+
+        let mut bad_entries = entries.clone();
+        bad_entries[3].hash = hash(&[1, 2, 3]);
+        let mut bad_start_hash_entries = entries.clone();
+        bad_start_hash_entries.truncate(1);
+
+        // Queue more batches than the queue holds before collecting results.
+        let mut states: Vec<_> = [
+            (&entries[..], zero, true),
+            (&bad_entries[..], zero, false),
+            (&bad_start_hash_entries[..], last_hash, false),
+            (&[][..], zero, true),
+        ]
+        .into_iter()
+        .map(|(entries, start_hash, expected)| {
+            assert_eq!(entries.verify(&start_hash, &thread_pool), expected);
+            let state = entry_verifier.start_verify(entries, &start_hash);
+            assert_eq!(state.status(), EntryVerificationStatus::Pending);
+            (state, expected)
+        })
+        .collect();
+        for (state, expected) in &mut states {
+            assert_eq!(state.finish_verify(&thread_pool), *expected);
+            let expected_status = if *expected {
+                EntryVerificationStatus::Success
+            } else {
+                EntryVerificationStatus::Failure
+            };
+            assert_eq!(state.status(), expected_status);
+        }
+
+        // Results of batches queued before join are still delivered.
+        let mut state = entry_verifier.start_verify(&entries, &zero);
+        entry_verifier.join().unwrap();
+        assert!(state.finish_verify(&thread_pool));
+    }
+}
@@ -1,5 +1,6 @@
 #![allow(clippy::arithmetic_side_effects)]
 pub mod entry;
+pub mod entry_verifier;
 pub mod poh;
 
 extern crate log;
@@ -3,11 +3,17 @@ use {
     log::*,
     solana_hash::Hash,
     solana_sha256_hasher::{hash, hashv},
-    std::time::{Duration, Instant},
+    std::{
+        sync::OnceLock,
+        time::{Duration, Instant},
+    },
 };
 
 const LOW_POWER_MODE: u64 = u64::MAX;
 
+/// Number of hashes timed to measure the machine's hash rate.
+pub const CALIBRATION_HASHES_SAMPLE_SIZE: u64 = 1_000_000;
+
 pub struct Poh {
     pub hash: Hash,
     num_hashes: u64,
@@ -122,7 +128,25 @@ pub fn compute_hash_time(hashes_sample_size: u64) -> Duration {
 
 pub fn compute_hashes_per_tick(duration: Duration, hashes_sample_size: u64) -> u64 {
     let elapsed_ms = compute_hash_time(hashes_sample_size).as_millis() as u64;
-    duration.as_millis() as u64 * hashes_sample_size / elapsed_ms
+    duration.as_millis() as u64 * hashes_sample_size / elapsed_ms.max(1)
+}
+
+/// Returns the hashes per tick at which PoH uses half of this machine's hash
+/// rate for ticks of `target_tick_duration`, leaving headroom for the rest of
+/// the validator. The hash rate is measured on the first call and reused, so
+/// that all callers in a process agree.
+pub fn calibrate_hashes_per_tick(target_tick_duration: Duration) -> u64 {
+    static HASHES_PER_SECOND: OnceLock<u64> = OnceLock::new();
+    let hashes_per_second = *HASHES_PER_SECOND.get_or_init(|| {
+        let hashes_per_second =
+            compute_hashes_per_tick(Duration::from_secs(1), CALIBRATION_HASHES_SAMPLE_SIZE);
+        info!("PoH calibration: {hashes_per_second} hashes per second");
+        hashes_per_second
+    });
+    let hashes_per_tick =
+        u128::from(hashes_per_second) * target_tick_duration.as_nanos() / 1_000_000_000 / 2;
+    // Poh requires at least two hashes per tick.
+    u64::try_from(hashes_per_tick).unwrap_or(u64::MAX).max(2)
 }
 
 #[cfg(test)]
@@ -192,6 +216,16 @@ mod tests {
         poh.target_poh_time(target_ns_per_tick);
     }
 
+    #[test]
+    fn test_calibrate_hashes_per_tick() {
+        let hashes_per_tick = calibrate_hashes_per_tick(Duration::from_millis(10));
+        assert!(hashes_per_tick >= 2);
+        // The hash rate is measured once, so longer ticks scale proportionally.
+        let hashes_per_double_tick = calibrate_hashes_per_tick(Duration::from_millis(20));
+        assert!(hashes_per_double_tick.abs_diff(2 * hashes_per_tick) <= 1);
+        assert_eq!(calibrate_hashes_per_tick(Duration::ZERO), 2);
+    }
+
     #[test]
     fn test_poh_verify() {
         let zero = Hash::default();
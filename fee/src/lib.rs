@@ -1,7 +1,9 @@
 use {
     solana_feature_set::{enable_secp256r1_precompile, FeatureSet},
     solana_fee_structure::FeeDetails,
+    solana_pubkey::Pubkey,
     solana_svm_transaction::svm_message::SVMMessage,
+    std::fmt::Debug,
 };
 
 /// Bools indicating the activation of features relevant
@@ -23,6 +25,59 @@ impl From<&FeatureSet> for FeeFeatures {
     }
 }
 
+/// Hooks deciding how much a transaction is charged, so that a cluster can
+/// experiment with its fee market without changing how fees are collected.
+/// Every hook defaults to the current fee schedule.
+pub trait FeeModel: Debug + Send + Sync {
+    /// Lamports charged to every transaction on top of its signature fee
+    fn base_fee(&self) -> u64 {
+        0
+    }
+
+    /// Lamports charged for the transaction and precompile signatures
+    fn signature_fee(&self, signature_count: u64, lamports_per_signature: u64) -> u64 {
+        signature_count.saturating_mul(lamports_per_signature)
+    }
+
+    /// Lamports charged for priority, given the fee derived from the requested
+    /// compute unit limit and price
+    fn prioritization_fee(&self, prioritization_fee: u64) -> u64 {
+        prioritization_fee
+    }
+}
+
+/// The current fee schedule
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DefaultFeeModel;
+
+impl FeeModel for DefaultFeeModel {}
+
+/// A fee model which charges transactions once its feature is active, so that
+/// every validator of a cluster charges the same fees
+pub struct FeeModelPrototype {
+    pub feature_id: Pubkey,
+    pub fee_model: &'static dyn FeeModel,
+}
+
+/// Fee models replacing the current fee schedule, the last active one winning
+pub static FEE_MODELS: &[FeeModelPrototype] = &[];
+
+/// The fee model charging transactions under `feature_set`
+pub fn active_fee_model(feature_set: &FeatureSet) -> &'static dyn FeeModel {
+    select_fee_model(FEE_MODELS, feature_set)
+}
+
+fn select_fee_model(
+    fee_models: &[FeeModelPrototype],
+    feature_set: &FeatureSet,
+) -> &'static dyn FeeModel {
+    fee_models
+        .iter()
+        .rev()
+        .find(|prototype| feature_set.is_active(&prototype.feature_id))
+        .map_or(&DefaultFeeModel, |prototype| prototype.fee_model)
+}
+
 /// Calculate fee for `SanitizedMessage`
 pub fn calculate_fee(
     message: &impl SVMMessage,
@@ -47,39 +102,71 @@ pub fn calculate_fee_details(
     lamports_per_signature: u64,
     prioritization_fee: u64,
     fee_features: FeeFeatures,
+) -> FeeDetails {
+    calculate_fee_details_with_model(
+        message,
+        zero_fees_for_test,
+        lamports_per_signature,
+        prioritization_fee,
+        fee_features,
+        &DefaultFeeModel,
+    )
+}
+
+/// Calculate fee details for `SanitizedMessage` according to `fee_model`
+pub fn calculate_fee_details_with_model(
+    message: &impl SVMMessage,
+    zero_fees_for_test: bool,
+    lamports_per_signature: u64,
+    prioritization_fee: u64,
+    fee_features: FeeFeatures,
+    fee_model: &dyn FeeModel,
 ) -> FeeDetails {
     if zero_fees_for_test {
         return FeeDetails::default();
     }
 
+    let signature_count = calculate_signature_count(
+        SignatureCounts::from(message),
+        fee_features.enable_secp256r1_precompile,
+    );
     FeeDetails::new(
-        calculate_signature_fee(
-            SignatureCounts::from(message),
-            lamports_per_signature,
-            fee_features.enable_secp256r1_precompile,
-        ),
-        prioritization_fee,
+        fee_model
+            .base_fee()
+            .saturating_add(fee_model.signature_fee(signature_count, lamports_per_signature)),
+        fee_model.prioritization_fee(prioritization_fee),
     )
 }
 
 /// Calculate fees from signatures.
+#[cfg(test)]
 fn calculate_signature_fee(
+    signature_counts: SignatureCounts,
+    lamports_per_signature: u64,
+    enable_secp256r1_precompile: bool,
+) -> u64 {
+    DefaultFeeModel.signature_fee(
+        calculate_signature_count(signature_counts, enable_secp256r1_precompile),
+        lamports_per_signature,
+    )
+}
+
+/// Count the signatures a transaction is charged for.
+fn calculate_signature_count(
     SignatureCounts {
         num_transaction_signatures,
         num_ed25519_signatures,
         num_secp256k1_signatures,
         num_secp256r1_signatures,
     }: SignatureCounts,
-    lamports_per_signature: u64,
     enable_secp256r1_precompile: bool,
 ) -> u64 {
-    let signature_count = num_transaction_signatures
+    num_transaction_signatures
         .saturating_add(num_ed25519_signatures)
         .saturating_add(num_secp256k1_signatures)
         .saturating_add(
             u64::from(enable_secp256r1_precompile).wrapping_mul(num_secp256r1_signatures),
-        );
-    signature_count.saturating_mul(lamports_per_signature)
+        )
 }
 
 struct SignatureCounts {
@@ -168,4 +255,36 @@ mod tests {
             6 * LAMPORTS_PER_SIGNATURE
         );
     }
+
+    #[derive(Debug)]
+    struct FlatFeeModel(u64);
+
+    impl FeeModel for FlatFeeModel {
+        fn base_fee(&self) -> u64 {
+            self.0
+        }
+    }
+
+    #[test]
+    fn test_select_fee_model() {
+        let first_feature_id = Pubkey::new_unique();
+        let second_feature_id = Pubkey::new_unique();
+        let fee_models = [
+            FeeModelPrototype {
+                feature_id: first_feature_id,
+                fee_model: &FlatFeeModel(1),
+            },
+            FeeModelPrototype {
+                feature_id: second_feature_id,
+                fee_model: &FlatFeeModel(2),
+            },
+        ];
+
+        let mut feature_set = FeatureSet::default();
+        assert_eq!(select_fee_model(&fee_models, &feature_set).base_fee(), 0);
+        feature_set.activate(&second_feature_id, 0);
+        assert_eq!(select_fee_model(&fee_models, &feature_set).base_fee(), 2);
+        feature_set.activate(&first_feature_id, 0);
+        assert_eq!(select_fee_model(&fee_models, &feature_set).base_fee(), 2);
+    }
 }
@@ -0,0 +1,223 @@
+//! Loading genesis accounts and programs from a directory of fixtures.
+//!
+//! A fixtures directory may contain, named by the address they are installed at:
+//! * `<ADDRESS>.json`: an account, in the format written by `solana account --output json`
+//! * `<ADDRESS>.so`: a program ELF, deployed with the upgradeable loader. Its upgrade
+//!   authority is read from `<ADDRESS>.authority`, holding a pubkey, if present; otherwise
+//!   the program is immutable.
+//!
+//! Other files are ignored.
+use {
+    solana_rpc_client_api::response::RpcKeyedAccount,
+    solana_sdk::{
+        account::{Account, AccountSharedData},
+        bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+        genesis_config::GenesisConfig,
+        pubkey::Pubkey,
+    },
+    std::{
+        fs,
+        io::{self, ErrorKind},
+        path::Path,
+        str::FromStr,
+    },
+};
+
+/// Adds a program deployed with the upgradeable loader `loader` at `address`, along with its
+/// program data account.
+pub fn add_upgradeable_program(
+    genesis_config: &mut GenesisConfig,
+    address: Pubkey,
+    loader: &Pubkey,
+    elf: &[u8],
+    upgrade_authority_address: Option<Pubkey>,
+) {
+    let (programdata_address, _) = Pubkey::find_program_address(&[address.as_ref()], loader);
+    let mut program_data = bincode::serialize(&UpgradeableLoaderState::ProgramData {
+        slot: 0,
+        upgrade_authority_address,
+    })
+    .unwrap();
+    program_data.extend_from_slice(elf);
+    genesis_config.add_account(
+        programdata_address,
+        AccountSharedData::from(Account {
+            lamports: genesis_config.rent.minimum_balance(program_data.len()),
+            data: program_data,
+            owner: *loader,
+            executable: false,
+            rent_epoch: 0,
+        }),
+    );
+
+    let program_data = bincode::serialize(&UpgradeableLoaderState::Program {
+        programdata_address,
+    })
+    .unwrap();
+    genesis_config.add_account(
+        address,
+        AccountSharedData::from(Account {
+            lamports: genesis_config.rent.minimum_balance(program_data.len()),
+            data: program_data,
+            owner: *loader,
+            executable: true,
+            rent_epoch: 0,
+        }),
+    );
+}
+
+/// Adds the accounts and programs in the fixtures directory `dir` to `genesis_config`,
+/// returning the number of fixtures loaded.
+pub fn load_fixtures_dir(dir: &Path, genesis_config: &mut GenesisConfig) -> io::Result<usize> {
+    let invalid = |path: &Path, err: String| {
+        io::Error::new(ErrorKind::Other, format!("{}: {err}", path.display()))
+    };
+
+    let mut paths = fs::read_dir(dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<io::Result<Vec<_>>>()?;
+    // Load in a deterministic order, so that conflicting fixtures always resolve the same way.
+    paths.sort();
+
+    let mut num_fixtures = 0;
+    for path in paths {
+        let (Some(address), Some(extension)) = (
+            path.file_stem().and_then(|stem| stem.to_str()),
+            path.extension().and_then(|extension| extension.to_str()),
+        ) else {
+            continue;
+        };
+        if !path.is_file() || !matches!(extension, "json" | "so") {
+            continue;
+        }
+        let address = Pubkey::from_str(address)
+            .map_err(|err| invalid(&path, format!("invalid address: {err}")))?;
+
+        if extension == "json" {
+            let keyed_account: RpcKeyedAccount = serde_json::from_slice(&fs::read(&path)?)
+                .map_err(|err| invalid(&path, format!("invalid account: {err}")))?;
+            let account = keyed_account
+                .account
+                .decode::<AccountSharedData>()
+                .ok_or_else(|| invalid(&path, "unable to decode account data".to_string()))?;
+            genesis_config.add_account(address, account);
+        } else {
+            let authority_path = path.with_extension("authority");
+            let upgrade_authority_address = if authority_path.is_file() {
+                let authority = fs::read_to_string(&authority_path)?;
+                Some(Pubkey::from_str(authority.trim()).map_err(|err| {
+                    invalid(&authority_path, format!("invalid upgrade authority: {err}"))
+                })?)
+            } else {
+                None
+            };
+            add_upgradeable_program(
+                genesis_config,
+                address,
+                &bpf_loader_upgradeable::id(),
+                &fs::read(&path)?,
+                upgrade_authority_address,
+            );
+        }
+        num_fixtures += 1;
+    }
+    Ok(num_fixtures)
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_account_decoder::{encode_ui_account, UiAccountEncoding},
+        solana_sdk::account::ReadableAccount,
+    };
+
+    #[test]
+    fn test_load_fixtures_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut genesis_config = GenesisConfig::default();
+        assert_eq!(
+            load_fixtures_dir(dir.path(), &mut genesis_config).unwrap(),
+            0
+        );
+
+        let account_address = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let account = AccountSharedData::from(Account {
+            lamports: 42,
+            data: vec![1, 2, 3],
+            owner,
+            executable: false,
+            rent_epoch: 0,
+        });
+        // The address in the file name takes precedence over the one in the file.
+        let keyed_account = RpcKeyedAccount {
+            pubkey: Pubkey::new_unique().to_string(),
+            account: encode_ui_account(
+                &account_address,
+                &account,
+                UiAccountEncoding::Base64,
+                None,
+                None,
+            ),
+        };
+        fs::write(
+            dir.path().join(format!("{account_address}.json")),
+            serde_json::to_vec(&keyed_account).unwrap(),
+        )
+        .unwrap();
+
+        let immutable_program = Pubkey::new_unique();
+        fs::write(dir.path().join(format!("{immutable_program}.so")), b"elf").unwrap();
+        let upgradeable_program = Pubkey::new_unique();
+        let upgrade_authority = Pubkey::new_unique();
+        fs::write(dir.path().join(format!("{upgradeable_program}.so")), b"elf").unwrap();
+        fs::write(
+            dir.path().join(format!("{upgradeable_program}.authority")),
+            format!("{upgrade_authority}\n"),
+        )
+        .unwrap();
+        fs::write(dir.path().join("README.md"), b"ignored").unwrap();
+
+        assert_eq!(
+            load_fixtures_dir(dir.path(), &mut genesis_config).unwrap(),
+            3
+        );
+        // One account, plus a program and a program data account for each program.
+        assert_eq!(genesis_config.accounts.len(), 5);
+        assert_eq!(
+            AccountSharedData::from(genesis_config.accounts[&account_address].clone()),
+            account
+        );
+        for (program, authority) in [
+            (immutable_program, None),
+            (upgradeable_program, Some(upgrade_authority)),
+        ] {
+            let program_account = &genesis_config.accounts[&program];
+            assert!(program_account.executable);
+            assert_eq!(program_account.owner, bpf_loader_upgradeable::id());
+            let UpgradeableLoaderState::Program {
+                programdata_address,
+            } = bincode::deserialize(program_account.data()).unwrap()
+            else {
+                panic!("not a program account");
+            };
+            let programdata_account = &genesis_config.accounts[&programdata_address];
+            let metadata_len = UpgradeableLoaderState::size_of_programdata_metadata();
+            assert_eq!(
+                bincode::deserialize::<UpgradeableLoaderState>(
+                    &programdata_account.data()[..metadata_len]
+                )
+                .unwrap(),
+                UpgradeableLoaderState::ProgramData {
+                    slot: 0,
+                    upgrade_authority_address: authority,
+                }
+            );
+            assert_eq!(&programdata_account.data()[metadata_len..], b"elf");
+        }
+
+        fs::write(dir.path().join("not-an-address.so"), b"elf").unwrap();
+        assert!(load_fixtures_dir(dir.path(), &mut genesis_config).is_err());
+    }
+}
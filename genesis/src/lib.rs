@@ -1,5 +1,6 @@
 #![allow(clippy::arithmetic_side_effects)]
 pub mod address_generator;
+pub mod fixtures;
 pub mod genesis_accounts;
 pub mod stakes;
 pub mod unlocks;
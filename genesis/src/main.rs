@@ -15,17 +15,17 @@ use {
             is_valid_percentage, normalize_to_url_if_moniker,
         },
     },
-    solana_entry::poh::compute_hashes_per_tick,
+    solana_entry::poh::calibrate_hashes_per_tick,
     solana_genesis::{
-        genesis_accounts::add_genesis_accounts, Base64Account, StakedValidatorAccountInfo,
-        ValidatorAccountsFile,
+        fixtures::{add_upgradeable_program, load_fixtures_dir},
+        genesis_accounts::add_genesis_accounts,
+        Base64Account, StakedValidatorAccountInfo, ValidatorAccountsFile,
     },
     solana_ledger::{blockstore::create_new_ledger, blockstore_options::LedgerColumnOptions},
     solana_rpc_client::rpc_client::RpcClient,
     solana_rpc_client_api::request::MAX_MULTIPLE_ACCOUNTS,
     solana_sdk::{
         account::{Account, AccountSharedData, ReadableAccount, WritableAccount},
-        bpf_loader_upgradeable::UpgradeableLoaderState,
         clock,
         commitment_config::CommitmentConfig,
         epoch_schedule::EpochSchedule,
@@ -50,7 +50,7 @@ use {
         error,
         fs::File,
         io::{self, Read},
-        path::PathBuf,
+        path::{Path, PathBuf},
         process,
         slice::Iter,
         str::FromStr,
@@ -600,6 +600,20 @@ fn main() -> Result<(), Box<dyn error::Error>> {
                 .multiple(true)
                 .help("Install an upgradeable SBF program at the given address with the given upgrade authority (or \"none\")"),
         )
+        .arg(
+            Arg::with_name("fixtures_dir")
+                .long("fixtures-dir")
+                .value_name("DIR")
+                .takes_value(true)
+                .multiple(true)
+                .help(
+                    "Install the accounts and programs in DIR. Accounts are read from \
+                     ADDRESS.json files, in the format of `solana account --output json`, \
+                     and programs from ADDRESS.so files, deployed with the upgradeable \
+                     loader. A program's upgrade authority is read from an ADDRESS.authority \
+                     file, if any; otherwise the program is immutable",
+                ),
+        )
         .arg(
             Arg::with_name("inflation")
                 .required(false)
@@ -686,9 +700,8 @@ fn main() -> Result<(), Box<dyn error::Error>> {
     match matches.value_of("hashes_per_tick").unwrap() {
         "auto" => match cluster_type {
             ClusterType::Development => {
-                let hashes_per_tick =
-                    compute_hashes_per_tick(poh_config.target_tick_duration, 1_000_000);
-                poh_config.hashes_per_tick = Some(hashes_per_tick / 2); // use 50% of peak ability
+                poh_config.hashes_per_tick =
+                    Some(calibrate_hashes_per_tick(poh_config.target_tick_duration));
             }
             ClusterType::Devnet | ClusterType::Testnet | ClusterType::MainnetBeta => {
                 poh_config.hashes_per_tick = Some(clock::DEFAULT_HASHES_PER_TICK);
@@ -851,42 +864,27 @@ fn main() -> Result<(), Box<dyn error::Error>> {
                 })
             };
 
-            let (programdata_address, _) =
-                Pubkey::find_program_address(&[address.as_ref()], &loader);
-            let mut program_data = bincode::serialize(&UpgradeableLoaderState::ProgramData {
-                slot: 0,
-                upgrade_authority_address: Some(upgrade_authority_address),
-            })
-            .unwrap();
-            program_data.extend_from_slice(&program_data_elf);
-            genesis_config.add_account(
-                programdata_address,
-                AccountSharedData::from(Account {
-                    lamports: genesis_config.rent.minimum_balance(program_data.len()),
-                    data: program_data,
-                    owner: loader,
-                    executable: false,
-                    rent_epoch: 0,
-                }),
-            );
-
-            let program_data = bincode::serialize(&UpgradeableLoaderState::Program {
-                programdata_address,
-            })
-            .unwrap();
-            genesis_config.add_account(
+            add_upgradeable_program(
+                &mut genesis_config,
                 address,
-                AccountSharedData::from(Account {
-                    lamports: genesis_config.rent.minimum_balance(program_data.len()),
-                    data: program_data,
-                    owner: loader,
-                    executable: true,
-                    rent_epoch: 0,
-                }),
+                &loader,
+                &program_data_elf,
+                Some(upgrade_authority_address),
             );
         }
     }
 
+    if let Some(dirs) = matches.values_of("fixtures_dir") {
+        for dir in dirs {
+            let num_fixtures = load_fixtures_dir(Path::new(dir), &mut genesis_config)
+                .unwrap_or_else(|err| {
+                    eprintln!("Error: failed to load fixtures from {dir}: {err}");
+                    process::exit(1);
+                });
+            println!("Loaded {num_fixtures} fixtures from {dir}");
+        }
+    }
+
     solana_logger::setup();
     create_new_ledger(
         &ledger_path,
@@ -272,6 +272,42 @@ pub enum ReplicaBlockInfoVersions<'a> {
     V0_0_4(&'a ReplicaBlockInfoV4<'a>),
 }
 
+/// Reward of a stake account for an epoch
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct ReplicaStakeRewardInfo<'a> {
+    /// The Pubkey of the stake account
+    pub stake_pubkey: &'a [u8],
+    /// The Pubkey of the vote account the stake is delegated to
+    pub vote_pubkey: &'a [u8],
+    /// Lamports credited to the stake account
+    pub lamports: u64,
+    /// Commission of the vote account when the rewards were calculated
+    pub commission: Option<u8>,
+    /// Effective stake of the account in the rewarded epoch
+    pub effective_stake: u64,
+}
+
+/// Stake rewards of an epoch, calculated in the first block of the next epoch
+#[derive(Clone, Debug)]
+#[repr(C)]
+pub struct ReplicaEpochStakeRewardsInfo<'a> {
+    /// The slot of the block the rewards were calculated in
+    pub slot: Slot,
+    /// The epoch the rewards were earned in
+    pub rewarded_epoch: u64,
+    /// Rewards of the stake accounts, sorted by stake account
+    pub rewards: &'a [ReplicaStakeRewardInfo<'a>],
+}
+
+/// A wrapper to future-proof ReplicaEpochStakeRewardsInfo handling. To make a change to the
+/// structure of ReplicaEpochStakeRewardsInfo, add an new enum variant wrapping a newer version,
+/// which will force plugin implementations to handle the change.
+#[repr(u32)]
+pub enum ReplicaEpochStakeRewardsInfoVersions<'a> {
+    V0_0_1(&'a ReplicaEpochStakeRewardsInfo<'a>),
+}
+
 /// Errors returned by plugin calls
 #[derive(Error, Debug)]
 #[repr(u32)]
@@ -347,6 +383,25 @@ impl SlotStatus {
 
 pub type Result<T> = std::result::Result<T, GeyserPluginError>;
 
+/// The version of the Geyser plugin interface this crate implements.
+///
+/// Plugins may report the interface version they were built against by exporting
+/// [`GEYSER_PLUGIN_INTERFACE_VERSION_SYMBOL`], in which case the validator refuses to load
+/// plugins built against a different version:
+///
+/// ```
+/// use agave_geyser_plugin_interface::geyser_plugin_interface::GEYSER_PLUGIN_INTERFACE_VERSION;
+///
+/// #[no_mangle]
+/// pub extern "C" fn _geyser_plugin_interface_version() -> u32 {
+///     GEYSER_PLUGIN_INTERFACE_VERSION
+/// }
+/// ```
+pub const GEYSER_PLUGIN_INTERFACE_VERSION: u32 = 2;
+
+/// The name of the symbol a plugin library exports to report its interface version.
+pub const GEYSER_PLUGIN_INTERFACE_VERSION_SYMBOL: &str = "_geyser_plugin_interface_version";
+
 /// Defines a Geyser plugin, to stream data from the runtime.
 /// Geyser plugins must describe desired behavior for load and unload,
 /// as well as how they will handle streamed data.
@@ -476,4 +531,21 @@ pub trait GeyserPlugin: Any + Send + Sync + std::fmt::Debug {
     fn entry_notifications_enabled(&self) -> bool {
         false
     }
+
+    /// Check if the plugin is interested in the stake rewards of each epoch
+    /// Default is false -- if the plugin is interested in
+    /// epoch stake rewards, return true.
+    fn epoch_stake_rewards_notifications_enabled(&self) -> bool {
+        false
+    }
+
+    /// Called when the stake rewards of an epoch are calculated, in the first
+    /// block of the next epoch.
+    #[allow(unused_variables)]
+    fn notify_epoch_stake_rewards(
+        &self,
+        rewards: ReplicaEpochStakeRewardsInfoVersions,
+    ) -> Result<()> {
+        Ok(())
+    }
 }
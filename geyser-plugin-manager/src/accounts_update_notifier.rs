@@ -73,9 +73,9 @@ impl AccountsUpdateNotifierInterface for AccountsUpdateNotifierImpl {
             return;
         }
 
-        for plugin in plugin_manager.plugins.iter() {
+        for plugin in plugin_manager.active_plugins() {
             let mut measure = Measure::start("geyser-plugin-end-of-restore-from-snapshot");
-            match plugin.notify_end_of_startup() {
+            match plugin.invoke(|plugin| plugin.notify_end_of_startup()) {
                 Err(err) => {
                     error!(
                         "Failed to notify the end of restore from snapshot, error: {} to plugin {}",
@@ -164,13 +164,15 @@ impl AccountsUpdateNotifierImpl {
         if plugin_manager.plugins.is_empty() {
             return;
         }
-        for plugin in plugin_manager.plugins.iter() {
+        for plugin in plugin_manager.active_plugins() {
             let mut measure = Measure::start("geyser-plugin-update-account");
-            match plugin.update_account(
-                ReplicaAccountInfoVersions::V0_0_3(&account),
-                slot,
-                is_startup,
-            ) {
+            match plugin.invoke(|plugin| {
+                plugin.update_account(
+                    ReplicaAccountInfoVersions::V0_0_3(&account),
+                    slot,
+                    is_startup,
+                )
+            }) {
                 Err(err) => {
                     error!(
                         "Failed to update account {} at slot {}, error: {} to plugin {}",
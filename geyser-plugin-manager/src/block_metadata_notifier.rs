@@ -4,13 +4,14 @@ use {
         geyser_plugin_manager::GeyserPluginManager,
     },
     agave_geyser_plugin_interface::geyser_plugin_interface::{
-        ReplicaBlockInfoV4, ReplicaBlockInfoVersions,
+        ReplicaBlockInfoV4, ReplicaBlockInfoVersions, ReplicaEpochStakeRewardsInfo,
+        ReplicaEpochStakeRewardsInfoVersions, ReplicaStakeRewardInfo,
     },
     log::*,
-    solana_clock::UnixTimestamp,
+    solana_clock::{Slot, UnixTimestamp},
     solana_measure::measure::Measure,
     solana_metrics::*,
-    solana_runtime::bank::KeyedRewardsAndNumPartitions,
+    solana_runtime::bank::{EpochStakeRewards, KeyedRewardsAndNumPartitions},
     solana_transaction_status::{Reward, RewardsAndNumPartitions},
     std::sync::{Arc, RwLock},
 };
@@ -51,10 +52,10 @@ impl BlockMetadataNotifier for BlockMetadataNotifierImpl {
             entry_count,
         );
 
-        for plugin in plugin_manager.plugins.iter() {
+        for plugin in plugin_manager.active_plugins() {
             let mut measure = Measure::start("geyser-plugin-update-slot");
             let block_info = ReplicaBlockInfoVersions::V0_0_4(&block_info);
-            match plugin.notify_block_metadata(block_info) {
+            match plugin.invoke(|plugin| plugin.notify_block_metadata(block_info)) {
                 Err(err) => {
                     error!(
                         "Failed to update block metadata at slot {}, error: {} to plugin {}",
@@ -80,6 +81,74 @@ impl BlockMetadataNotifier for BlockMetadataNotifierImpl {
             );
         }
     }
+
+    fn epoch_stake_rewards_notifications_enabled(&self) -> bool {
+        self.plugin_manager
+            .read()
+            .unwrap()
+            .epoch_stake_rewards_notifications_enabled()
+    }
+
+    fn notify_epoch_stake_rewards(&self, slot: Slot, rewards: &EpochStakeRewards) {
+        let plugin_manager = self.plugin_manager.read().unwrap();
+        let mut plugins = plugin_manager
+            .active_plugins()
+            .filter(|plugin| {
+                plugin.notifications_enabled(|plugin| {
+                    plugin.epoch_stake_rewards_notifications_enabled()
+                })
+            })
+            .peekable();
+        if plugins.peek().is_none() {
+            return;
+        }
+
+        let stake_rewards: Vec<_> = rewards
+            .rewards
+            .iter()
+            .map(|reward| ReplicaStakeRewardInfo {
+                stake_pubkey: reward.stake_pubkey.as_ref(),
+                vote_pubkey: reward.vote_pubkey.as_ref(),
+                lamports: reward.lamports,
+                commission: reward.commission,
+                effective_stake: reward.effective_stake,
+            })
+            .collect();
+        let rewards_info = ReplicaEpochStakeRewardsInfo {
+            slot,
+            rewarded_epoch: rewards.rewarded_epoch,
+            rewards: &stake_rewards,
+        };
+
+        for plugin in plugins {
+            let mut measure = Measure::start("geyser-plugin-notify-epoch-stake-rewards");
+            let rewards_info = ReplicaEpochStakeRewardsInfoVersions::V0_0_1(&rewards_info);
+            match plugin.invoke(|plugin| plugin.notify_epoch_stake_rewards(rewards_info)) {
+                Err(err) => {
+                    error!(
+                        "Failed to notify the stake rewards of epoch {} to plugin {}, error: {}",
+                        rewards.rewarded_epoch,
+                        plugin.name(),
+                        err,
+                    )
+                }
+                Ok(_) => {
+                    trace!(
+                        "Successfully notified the stake rewards of epoch {} to plugin {}",
+                        rewards.rewarded_epoch,
+                        plugin.name()
+                    );
+                }
+            }
+            measure.stop();
+            inc_new_counter_debug!(
+                "geyser-plugin-notify-epoch-stake-rewards-us",
+                measure.as_us() as usize,
+                1000,
+                1000
+            );
+        }
+    }
 }
 
 impl BlockMetadataNotifierImpl {
@@ -1,5 +1,7 @@
 use {
-    solana_clock::UnixTimestamp, solana_runtime::bank::KeyedRewardsAndNumPartitions, std::sync::Arc,
+    solana_clock::{Slot, UnixTimestamp},
+    solana_runtime::bank::{EpochStakeRewards, KeyedRewardsAndNumPartitions},
+    std::sync::Arc,
 };
 
 /// Interface for notifying block metadata changes
@@ -18,6 +20,13 @@ pub trait BlockMetadataNotifier {
         executed_transaction_count: u64,
         entry_count: u64,
     );
+
+    /// Check if there is any plugin interested in epoch stake rewards, which
+    /// are expensive to collect
+    fn epoch_stake_rewards_notifications_enabled(&self) -> bool;
+
+    /// Notify the stake rewards of an epoch, calculated in the block at `slot`
+    fn notify_epoch_stake_rewards(&self, slot: Slot, rewards: &EpochStakeRewards);
 }
 
 pub type BlockMetadataNotifierArc = Arc<dyn BlockMetadataNotifier + Sync + Send>;
@@ -35,11 +35,13 @@ impl EntryNotifier for EntryNotifierImpl {
         let entry_info =
             Self::build_replica_entry_info(slot, index, entry, starting_transaction_index);
 
-        for plugin in plugin_manager.plugins.iter() {
-            if !plugin.entry_notifications_enabled() {
+        for plugin in plugin_manager.active_plugins() {
+            if !plugin.notifications_enabled(|plugin| plugin.entry_notifications_enabled()) {
                 continue;
             }
-            match plugin.notify_entry(ReplicaEntryInfoVersions::V0_0_2(&entry_info)) {
+            match plugin
+                .invoke(|plugin| plugin.notify_entry(ReplicaEntryInfoVersions::V0_0_2(&entry_info)))
+            {
                 Err(err) => {
                     error!(
                         "Failed to notify entry, error: ({}) to plugin {}",
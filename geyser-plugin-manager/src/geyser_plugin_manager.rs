@@ -1,11 +1,18 @@
 use {
-    agave_geyser_plugin_interface::geyser_plugin_interface::GeyserPlugin,
+    agave_geyser_plugin_interface::geyser_plugin_interface::{
+        GeyserPlugin, GeyserPluginError, Result as PluginResult, GEYSER_PLUGIN_INTERFACE_VERSION,
+        GEYSER_PLUGIN_INTERFACE_VERSION_SYMBOL,
+    },
     jsonrpc_core::{ErrorCode, Result as JsonRpcResult},
     libloading::Library,
     log::*,
+    serde_derive::{Deserialize, Serialize},
     std::{
+        any::Any,
         ops::{Deref, DerefMut},
+        panic::AssertUnwindSafe,
         path::Path,
+        sync::OnceLock,
     },
     tokio::sync::oneshot::Sender as OneShotSender,
 };
@@ -14,6 +21,10 @@ use {
 pub struct LoadedGeyserPlugin {
     name: String,
     plugin: Box<dyn GeyserPlugin>,
+    // The interface version reported by the plugin library, if it exports one
+    interface_version: Option<u32>,
+    // Set once the plugin panics. A failed plugin receives no further notifications.
+    failure: OnceLock<String>,
     // NOTE: While we do not access the library, the plugin we have loaded most
     // certainly does. To ensure we don't SIGSEGV we must declare the library
     // after the plugin so the plugin is dropped first.
@@ -31,6 +42,8 @@ impl LoadedGeyserPlugin {
         Self {
             name: name.unwrap_or_else(|| plugin.name().to_owned()),
             plugin,
+            interface_version: None,
+            failure: OnceLock::new(),
             library,
         }
     }
@@ -38,6 +51,76 @@ impl LoadedGeyserPlugin {
     pub fn name(&self) -> &str {
         &self.name
     }
+
+    pub fn interface_version(&self) -> Option<u32> {
+        self.interface_version
+    }
+
+    /// Returns the reason the plugin was disabled, if it has failed
+    pub fn failure(&self) -> Option<&str> {
+        self.failure.get().map(String::as_str)
+    }
+
+    pub fn is_failed(&self) -> bool {
+        self.failure.get().is_some()
+    }
+
+    /// Invokes `f` on the plugin, isolating the validator from a panic within the plugin.
+    ///
+    /// A panicking plugin is marked as failed and the panic is returned as an error.
+    pub fn invoke<T>(
+        &self,
+        f: impl FnOnce(&dyn GeyserPlugin) -> PluginResult<T>,
+    ) -> PluginResult<T> {
+        catch_plugin_panic(|| f(self.plugin.as_ref())).unwrap_or_else(|message| {
+            let message = format!("plugin panicked: {message}");
+            error!("Disabling plugin {}, {message}", self.name);
+            let _ = self.failure.set(message.clone());
+            Err(GeyserPluginError::Custom(message.into()))
+        })
+    }
+
+    /// Returns whether the plugin is interested in the notifications checked by `f`, isolating
+    /// the validator from a panic within the plugin like `invoke`.
+    ///
+    /// A panicking plugin is marked as failed and treated as not interested.
+    pub fn notifications_enabled(&self, f: impl FnOnce(&dyn GeyserPlugin) -> bool) -> bool {
+        self.invoke(|plugin| Ok(f(plugin))).unwrap_or(false)
+    }
+
+    fn status(&self) -> GeyserPluginStatus {
+        GeyserPluginStatus {
+            name: self.name.clone(),
+            interface_version: self.interface_version,
+            failure: self.failure().map(str::to_owned),
+        }
+    }
+}
+
+/// The status of a loaded plugin, as reported over the admin RPC
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeyserPluginStatus {
+    pub name: String,
+    /// The interface version reported by the plugin library, if any
+    pub interface_version: Option<u32>,
+    /// The reason the plugin was disabled, if it has failed
+    pub failure: Option<String>,
+}
+
+// Runs `f`, converting a panic into an error carrying the panic message
+fn catch_plugin_panic<T>(f: impl FnOnce() -> T) -> Result<T, String> {
+    solana_metrics::catch_unwind_without_exit(AssertUnwindSafe(f)).map_err(panic_message)
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic payload".to_string()
+    }
 }
 
 impl Deref for LoadedGeyserPlugin {
@@ -71,48 +154,51 @@ impl GeyserPluginManager {
     pub fn unload(&mut self) {
         for mut plugin in self.plugins.drain(..) {
             info!("Unloading plugin for {:?}", plugin.name());
-            plugin.on_unload();
+            unload_plugin_isolated(&mut plugin);
         }
     }
 
     /// Check if there is any plugin interested in account data
     pub fn account_data_notifications_enabled(&self) -> bool {
-        for plugin in &self.plugins {
-            if plugin.account_data_notifications_enabled() {
-                return true;
-            }
-        }
-        false
+        self.active_plugins().any(|plugin| {
+            plugin.notifications_enabled(|plugin| plugin.account_data_notifications_enabled())
+        })
     }
 
     /// Check if there is any plugin interested in account data from snapshot
     pub fn account_data_snapshot_notifications_enabled(&self) -> bool {
-        for plugin in &self.plugins {
-            if plugin.account_data_snapshot_notifications_enabled() {
-                return true;
-            }
-        }
-        false
+        self.active_plugins().any(|plugin| {
+            plugin.notifications_enabled(|plugin| {
+                plugin.account_data_snapshot_notifications_enabled()
+            })
+        })
     }
 
     /// Check if there is any plugin interested in transaction data
     pub fn transaction_notifications_enabled(&self) -> bool {
-        for plugin in &self.plugins {
-            if plugin.transaction_notifications_enabled() {
-                return true;
-            }
-        }
-        false
+        self.active_plugins().any(|plugin| {
+            plugin.notifications_enabled(|plugin| plugin.transaction_notifications_enabled())
+        })
     }
 
     /// Check if there is any plugin interested in entry data
     pub fn entry_notifications_enabled(&self) -> bool {
-        for plugin in &self.plugins {
-            if plugin.entry_notifications_enabled() {
-                return true;
-            }
-        }
-        false
+        self.active_plugins().any(|plugin| {
+            plugin.notifications_enabled(|plugin| plugin.entry_notifications_enabled())
+        })
+    }
+
+    /// Check if there is any plugin interested in epoch stake rewards
+    pub fn epoch_stake_rewards_notifications_enabled(&self) -> bool {
+        self.active_plugins().any(|plugin| {
+            plugin
+                .notifications_enabled(|plugin| plugin.epoch_stake_rewards_notifications_enabled())
+        })
+    }
+
+    /// Returns the plugins which have not been disabled due to a failure
+    pub fn active_plugins(&self) -> impl Iterator<Item = &LoadedGeyserPlugin> {
+        self.plugins.iter().filter(|plugin| !plugin.is_failed())
     }
 
     /// Admin RPC request handler
@@ -120,6 +206,15 @@ impl GeyserPluginManager {
         Ok(self.plugins.iter().map(|p| p.name().to_owned()).collect())
     }
 
+    /// Admin RPC request handler
+    pub(crate) fn plugin_statuses(&self) -> JsonRpcResult<Vec<GeyserPluginStatus>> {
+        Ok(self
+            .plugins
+            .iter()
+            .map(LoadedGeyserPlugin::status)
+            .collect())
+    }
+
     /// Admin RPC request handler
     /// # Safety
     ///
@@ -161,16 +256,16 @@ impl GeyserPluginManager {
         setup_logger_for_plugin(&*new_plugin.plugin)?;
 
         // Call on_load and push plugin
-        new_plugin
-            .on_load(new_config_file, false)
-            .map_err(|on_load_err| jsonrpc_core::Error {
+        call_plugin_isolated(|| new_plugin.on_load(new_config_file, false)).map_err(
+            |on_load_err| jsonrpc_core::Error {
                 code: ErrorCode::InvalidRequest,
                 message: format!(
                     "on_load method of plugin {} failed: {on_load_err}",
                     new_plugin.name()
                 ),
                 data: None,
-            })?;
+            },
+        )?;
         let name = new_plugin.name().to_string();
         self.plugins.push(new_plugin);
 
@@ -248,7 +343,7 @@ impl GeyserPluginManager {
         setup_logger_for_plugin(&*new_plugin.plugin)?;
 
         // Attempt to on_load with new plugin
-        match new_plugin.on_load(new_parsed_config_file, true) {
+        match call_plugin_isolated(|| new_plugin.on_load(new_parsed_config_file, true)) {
             // On success, push plugin and library
             Ok(()) => {
                 self.plugins.push(new_plugin);
@@ -272,23 +367,42 @@ impl GeyserPluginManager {
     fn _drop_plugin(&mut self, idx: usize) {
         let mut current_plugin = self.plugins.remove(idx);
         let name = current_plugin.name().to_string();
-        current_plugin.on_unload();
+        unload_plugin_isolated(&mut current_plugin);
         info!("Unloaded plugin {name} at idx {idx}");
     }
 }
 
+// Runs a fallible plugin callback, converting a panic into a plugin error
+fn call_plugin_isolated<T>(f: impl FnOnce() -> PluginResult<T>) -> PluginResult<T> {
+    catch_plugin_panic(f).unwrap_or_else(|message| {
+        Err(GeyserPluginError::Custom(
+            format!("plugin panicked: {message}").into(),
+        ))
+    })
+}
+
+// Call the plugin's on_unload method, logging rather than propagating a panic
+fn unload_plugin_isolated(plugin: &mut LoadedGeyserPlugin) {
+    if let Err(message) = catch_plugin_panic(|| plugin.on_unload()) {
+        error!(
+            "Plugin {} panicked while unloading: {message}",
+            plugin.name()
+        );
+    }
+}
+
 // Initialize logging for the plugin
 fn setup_logger_for_plugin(new_plugin: &dyn GeyserPlugin) -> Result<(), jsonrpc_core::Error> {
-    new_plugin
-        .setup_logger(log::logger(), log::max_level())
-        .map_err(|setup_logger_err| jsonrpc_core::Error {
+    call_plugin_isolated(|| new_plugin.setup_logger(log::logger(), log::max_level())).map_err(
+        |setup_logger_err| jsonrpc_core::Error {
             code: ErrorCode::InvalidRequest,
             message: format!(
                 "setup_logger method of plugin {} failed: {setup_logger_err}",
                 new_plugin.name()
             ),
             data: None,
-        })
+        },
+    )
 }
 
 #[derive(Debug)]
@@ -309,6 +423,9 @@ pub enum GeyserPluginManagerRequest {
     ListPlugins {
         response_sender: OneShotSender<JsonRpcResult<Vec<String>>>,
     },
+    PluginStatuses {
+        response_sender: OneShotSender<JsonRpcResult<Vec<GeyserPluginStatus>>>,
+    },
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -336,6 +453,15 @@ pub enum GeyserPluginManagerError {
 
     #[error("The GeyserPlugin on_load method failed (error: {0})")]
     PluginStartError(String),
+
+    #[error(
+        "The geyser plugin was built against interface version {plugin_version}, but the \
+         validator requires version {validator_version}"
+    )]
+    IncompatibleInterfaceVersion {
+        plugin_version: u32,
+        validator_version: u32,
+    },
 }
 
 /// # Safety
@@ -351,6 +477,7 @@ pub(crate) fn load_plugin_from_config(
 ) -> Result<(LoadedGeyserPlugin, &str), GeyserPluginManagerError> {
     use std::{fs::File, io::Read, path::PathBuf};
     type PluginConstructor = unsafe fn() -> *mut dyn GeyserPlugin;
+    type InterfaceVersionFn = unsafe extern "C" fn() -> u32;
     use libloading::Symbol;
 
     let mut file = match File::open(geyser_plugin_config_file) {
@@ -398,19 +525,43 @@ pub(crate) fn load_plugin_from_config(
         .to_str()
         .ok_or(GeyserPluginManagerError::InvalidPluginPath)?;
 
-    let (plugin, lib) = unsafe {
+    let (plugin, lib, interface_version) = unsafe {
         let lib = Library::new(libpath)
             .map_err(|e| GeyserPluginManagerError::PluginLoadError(e.to_string()))?;
+        // Plugins predating the version symbol are loaded without a compatibility check
+        let interface_version = lib
+            .get::<InterfaceVersionFn>(GEYSER_PLUGIN_INTERFACE_VERSION_SYMBOL.as_bytes())
+            .ok()
+            .map(|interface_version| interface_version());
+        check_interface_version(interface_version)?;
         let constructor: Symbol<PluginConstructor> = lib
             .get(b"_create_plugin")
             .map_err(|e| GeyserPluginManagerError::PluginLoadError(e.to_string()))?;
         let plugin_raw = constructor();
-        (Box::from_raw(plugin_raw), lib)
+        (Box::from_raw(plugin_raw), lib, interface_version)
     };
-    Ok((
-        LoadedGeyserPlugin::new(lib, plugin, plugin_name),
-        config_file,
-    ))
+    let mut plugin = LoadedGeyserPlugin::new(lib, plugin, plugin_name);
+    plugin.interface_version = interface_version;
+    Ok((plugin, config_file))
+}
+
+fn check_interface_version(interface_version: Option<u32>) -> Result<(), GeyserPluginManagerError> {
+    match interface_version {
+        None => {
+            warn!(
+                "Geyser plugin does not export {GEYSER_PLUGIN_INTERFACE_VERSION_SYMBOL}, \
+                 skipping interface version check"
+            );
+            Ok(())
+        }
+        Some(plugin_version) if plugin_version != GEYSER_PLUGIN_INTERFACE_VERSION => {
+            Err(GeyserPluginManagerError::IncompatibleInterfaceVersion {
+                plugin_version,
+                validator_version: GEYSER_PLUGIN_INTERFACE_VERSION,
+            })
+        }
+        Some(_) => Ok(()),
+    }
 }
 
 #[cfg(test)]
@@ -448,10 +599,14 @@ pub(crate) fn load_plugin_from_config(
 mod tests {
     use {
         crate::geyser_plugin_manager::{
-            GeyserPluginManager, LoadedGeyserPlugin, TESTPLUGIN2_CONFIG, TESTPLUGIN_CONFIG,
+            check_interface_version, GeyserPluginManager, GeyserPluginManagerError,
+            GeyserPluginStatus, LoadedGeyserPlugin, TESTPLUGIN2_CONFIG, TESTPLUGIN_CONFIG,
+        },
+        agave_geyser_plugin_interface::geyser_plugin_interface::{
+            GeyserPlugin, Result as PluginResult, SlotStatus, GEYSER_PLUGIN_INTERFACE_VERSION,
         },
-        agave_geyser_plugin_interface::geyser_plugin_interface::GeyserPlugin,
         libloading::Library,
+        solana_clock::Slot,
         std::sync::{Arc, RwLock},
     };
 
@@ -491,6 +646,119 @@ mod tests {
         }
     }
 
+    #[derive(Clone, Copy, Debug)]
+    struct PanickingPlugin;
+
+    impl GeyserPlugin for PanickingPlugin {
+        fn name(&self) -> &'static str {
+            "panicking"
+        }
+
+        fn update_slot_status(
+            &self,
+            _slot: Slot,
+            _parent: Option<Slot>,
+            _status: &SlotStatus,
+        ) -> PluginResult<()> {
+            panic!("slot status failure");
+        }
+
+        fn epoch_stake_rewards_notifications_enabled(&self) -> bool {
+            panic!("notifications enabled failure");
+        }
+    }
+
+    #[test]
+    fn test_plugin_panic_is_isolated() {
+        let mut plugin_manager = GeyserPluginManager::new();
+        let (plugin, _config) = dummy_plugin_and_library(PanickingPlugin, DUMMY_CONFIG);
+        plugin_manager.plugins.push(plugin);
+        let (plugin, _config) = dummy_plugin_and_library(TestPlugin, DUMMY_CONFIG);
+        plugin_manager.plugins.push(plugin);
+        assert_eq!(plugin_manager.active_plugins().count(), 2);
+
+        // The panic is surfaced as an error and the plugin is disabled
+        let result = plugin_manager.plugins[0]
+            .invoke(|plugin| plugin.update_slot_status(0, None, &SlotStatus::Processed));
+        assert!(result.is_err());
+        assert!(plugin_manager.plugins[0].is_failed());
+
+        // The remaining plugin keeps receiving notifications
+        let active_plugins: Vec<_> = plugin_manager
+            .active_plugins()
+            .map(|plugin| plugin.name())
+            .collect();
+        assert_eq!(active_plugins, vec![DUMMY_NAME]);
+        assert!(plugin_manager.plugins[1]
+            .invoke(|plugin| plugin.update_slot_status(0, None, &SlotStatus::Processed))
+            .is_ok());
+
+        let statuses = plugin_manager.plugin_statuses().unwrap();
+        assert_eq!(
+            statuses,
+            vec![
+                GeyserPluginStatus {
+                    name: "panicking".to_string(),
+                    interface_version: None,
+                    failure: Some("plugin panicked: slot status failure".to_string()),
+                },
+                GeyserPluginStatus {
+                    name: DUMMY_NAME.to_string(),
+                    interface_version: None,
+                    failure: None,
+                },
+            ]
+        );
+
+        // A failed plugin can still be unloaded
+        assert!(plugin_manager.unload_plugin("panicking").is_ok());
+        assert_eq!(plugin_manager.plugins.len(), 1);
+    }
+
+    #[test]
+    fn test_plugin_panic_in_notifications_enabled_is_isolated() {
+        let mut plugin_manager = GeyserPluginManager::new();
+        let (plugin, _config) = dummy_plugin_and_library(PanickingPlugin, DUMMY_CONFIG);
+        plugin_manager.plugins.push(plugin);
+        let (plugin, _config) = dummy_plugin_and_library(TestPlugin, DUMMY_CONFIG);
+        plugin_manager.plugins.push(plugin);
+
+        // The panicking plugin is treated as not interested, and disabled
+        assert!(!plugin_manager.epoch_stake_rewards_notifications_enabled());
+        assert_eq!(
+            plugin_manager.plugins[0].failure(),
+            Some("plugin panicked: notifications enabled failure")
+        );
+        let active_plugins: Vec<_> = plugin_manager
+            .active_plugins()
+            .map(|plugin| plugin.name())
+            .collect();
+        assert_eq!(active_plugins, vec![DUMMY_NAME]);
+        assert!(!plugin_manager.epoch_stake_rewards_notifications_enabled());
+    }
+
+    #[test]
+    fn test_check_interface_version() {
+        assert!(check_interface_version(None).is_ok());
+        assert!(check_interface_version(Some(GEYSER_PLUGIN_INTERFACE_VERSION)).is_ok());
+        // Plugins built before the epoch stake rewards methods were added
+        assert_eq!(GEYSER_PLUGIN_INTERFACE_VERSION, 2);
+        assert!(matches!(
+            check_interface_version(Some(1)),
+            Err(GeyserPluginManagerError::IncompatibleInterfaceVersion {
+                plugin_version: 1,
+                validator_version: 2,
+            })
+        ));
+        assert!(matches!(
+            check_interface_version(Some(GEYSER_PLUGIN_INTERFACE_VERSION + 1)),
+            Err(GeyserPluginManagerError::IncompatibleInterfaceVersion {
+                plugin_version,
+                validator_version: GEYSER_PLUGIN_INTERFACE_VERSION,
+            }) if plugin_version == GEYSER_PLUGIN_INTERFACE_VERSION + 1
+        ));
+    }
+
     #[test]
     fn test_geyser_reload() {
         // Initialize empty manager
@@ -221,6 +221,13 @@ impl GeyserPluginService {
                                 .expect("Admin rpc service will be waiting for response");
                         }
 
+                        GeyserPluginManagerRequest::PluginStatuses { response_sender } => {
+                            let plugin_statuses = plugin_manager.read().unwrap().plugin_statuses();
+                            response_sender
+                                .send(plugin_statuses)
+                                .expect("Admin rpc service will be waiting for response");
+                        }
+
                         GeyserPluginManagerRequest::ReloadPlugin {
                             ref name,
                             ref config_file,
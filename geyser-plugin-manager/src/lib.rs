@@ -8,4 +8,4 @@ pub mod slot_status_notifier;
 pub mod slot_status_observer;
 pub mod transaction_notifier;
 
-pub use geyser_plugin_manager::GeyserPluginManagerRequest;
+pub use geyser_plugin_manager::{GeyserPluginManagerRequest, GeyserPluginStatus};
@@ -54,9 +54,9 @@ impl SlotStatusNotifierImpl {
             return;
         }
 
-        for plugin in plugin_manager.plugins.iter() {
+        for plugin in plugin_manager.active_plugins() {
             let mut measure = Measure::start("geyser-plugin-update-slot");
-            match plugin.update_slot_status(slot, parent, &slot_status) {
+            match plugin.invoke(|plugin| plugin.update_slot_status(slot, parent, &slot_status)) {
                 Err(err) => {
                     error!(
                         "Failed to update slot status at slot {}, error: {} to plugin {}",
@@ -46,14 +46,16 @@ impl TransactionNotifier for TransactionNotifierImpl {
             return;
         }
 
-        for plugin in plugin_manager.plugins.iter() {
-            if !plugin.transaction_notifications_enabled() {
+        for plugin in plugin_manager.active_plugins() {
+            if !plugin.notifications_enabled(|plugin| plugin.transaction_notifications_enabled()) {
                 continue;
             }
-            match plugin.notify_transaction(
-                ReplicaTransactionInfoVersions::V0_0_2(&transaction_log_info),
-                slot,
-            ) {
+            match plugin.invoke(|plugin| {
+                plugin.notify_transaction(
+                    ReplicaTransactionInfoVersions::V0_0_2(&transaction_log_info),
+                    slot,
+                )
+            }) {
                 Err(err) => {
                     error!(
                         "Failed to notify transaction, error: ({}) to plugin {}",
@@ -28,10 +28,16 @@ use {
             CRDS_GOSSIP_PULL_CRDS_TIMEOUT_MS,
         },
         crds_value::{CrdsValue, CrdsValueLabel},
+        custom_crds_value::{
+            accepts_custom_values, is_valid_namespace, CustomValue, MAX_CUSTOM_VALUES,
+            MAX_CUSTOM_VALUE_DATA_SIZE,
+        },
         duplicate_shred::DuplicateShred,
+        egress_budget::GossipBandwidthConfig,
         epoch_slots::EpochSlots,
         epoch_specs::EpochSpecs,
         gossip_error::GossipError,
+        peer_scores::{BlacklistedPeer, GossipPeerScores, GossipPeerScoresConfig, PeerOffense},
         ping_pong::Pong,
         protocol::{
             split_gossip_messages, Ping, PingCache, Protocol, PruneData,
@@ -140,6 +146,8 @@ pub enum ClusterInfoError {
     BadGossipAddress,
     #[error("TooManyIncrementalSnapshotHashes")]
     TooManyIncrementalSnapshotHashes,
+    #[error("InvalidCustomValue")]
+    InvalidCustomValue,
 }
 
 pub struct ClusterInfo {
@@ -158,6 +166,8 @@ pub struct ClusterInfo {
     contact_save_interval: u64,  // milliseconds, 0 = disabled
     contact_info_path: PathBuf,
     socket_addr_space: SocketAddrSpace,
+    bandwidth_config: GossipBandwidthConfig,
+    peer_scores: GossipPeerScores,
 }
 
 // Returns false if the CRDS value should be discarded.
@@ -186,6 +196,7 @@ fn should_retain_crds_value(
         | CrdsData::LegacyVersion(_)
         | CrdsData::DuplicateShred(_, _)
         | CrdsData::RestartHeaviestFork(_)
+        | CrdsData::CustomValue(_, _)
         | CrdsData::RestartLastVotedForkSlots(_)
         | CrdsData::NodeInstance(_) => {
             stakes.len() < MIN_NUM_STAKED_NODES || {
@@ -196,6 +207,11 @@ fn should_retain_crds_value(
     }
 }
 
+#[inline]
+fn is_custom_value(value: &CrdsValue) -> bool {
+    matches!(value.data(), CrdsData::CustomValue(..))
+}
+
 impl ClusterInfo {
     pub fn new(
         contact_info: ContactInfo,
@@ -222,6 +238,8 @@ impl ClusterInfo {
             contact_info_path: PathBuf::default(),
             contact_save_interval: 0, // disabled
             socket_addr_space,
+            bandwidth_config: GossipBandwidthConfig::default(),
+            peer_scores: GossipPeerScores::default(),
         };
         me.refresh_my_gossip_contact_info();
         me
@@ -231,6 +249,31 @@ impl ClusterInfo {
         self.contact_debug_interval = new;
     }
 
+    /// Caps the bandwidth used by gossip egress, once the gossip service starts
+    pub fn set_bandwidth_config(&mut self, config: GossipBandwidthConfig) {
+        self.bandwidth_config = config;
+    }
+
+    pub(crate) fn bandwidth_config(&self) -> GossipBandwidthConfig {
+        self.bandwidth_config
+    }
+
+    /// Blacklists the peers misbehaving by the thresholds of `config`
+    pub fn set_peer_scores_config(&mut self, config: GossipPeerScoresConfig) {
+        self.peer_scores = GossipPeerScores::new(config);
+    }
+
+    /// Peers whose gossip packets are dropped, or were recently
+    pub fn gossip_blacklist(&self) -> Vec<BlacklistedPeer> {
+        self.peer_scores.blacklist(Instant::now())
+    }
+
+    /// Stops dropping the gossip packets of the peer, returning false if it
+    /// was not on the blacklist
+    pub fn gossip_unban(&self, pubkey: &Pubkey) -> bool {
+        self.peer_scores.unban(pubkey)
+    }
+
     pub fn socket_addr_space(&self) -> &SocketAddrSpace {
         &self.socket_addr_space
     }
@@ -428,6 +471,28 @@ impl ClusterInfo {
         Ok(())
     }
 
+    pub fn set_rpc(&self, rpc_addr: SocketAddr) -> Result<(), ContactInfoError> {
+        self.my_contact_info.write().unwrap().set_rpc(rpc_addr)?;
+        self.refresh_my_gossip_contact_info();
+        Ok(())
+    }
+
+    // Overrides the given sockets in this node's contact-info, keyed by
+    // socket tag, and pushes the updated contact-info to gossip.
+    pub(crate) fn set_my_sockets(
+        &self,
+        sockets: impl IntoIterator<Item = (/*key:*/ u8, SocketAddr)>,
+    ) -> Result<(), ContactInfoError> {
+        {
+            let mut node = self.my_contact_info.write().unwrap();
+            for (key, socket) in sockets {
+                node.set_socket(key, socket)?;
+            }
+        }
+        self.refresh_my_gossip_contact_info();
+        Ok(())
+    }
+
     pub fn lookup_contact_info<R>(
         &self,
         id: &Pubkey,
@@ -772,6 +837,54 @@ impl ClusterInfo {
         Ok(())
     }
 
+    /// Publishes `data` under `namespace`, replacing this node's previous
+    /// value in the same namespace, if any. Once this node has
+    /// `MAX_CUSTOM_VALUES` values in other namespaces, the oldest is evicted.
+    pub fn push_custom_value(
+        &self,
+        namespace: &str,
+        data: Vec<u8>,
+    ) -> Result<(), ClusterInfoError> {
+        if !is_valid_namespace(namespace) || data.len() > MAX_CUSTOM_VALUE_DATA_SIZE {
+            return Err(ClusterInfoError::InvalidCustomValue);
+        }
+        let self_pubkey = self.id();
+        let now = timestamp();
+        let mut gossip_crds = self.gossip.crds.write().unwrap();
+        // Pick the index holding the same namespace, or else a free index, or
+        // else the index of the oldest value.
+        let (_, index, wallclock) = (0..MAX_CUSTOM_VALUES)
+            .map(|ix| {
+                let label = CrdsValueLabel::CustomValue(ix, self_pubkey);
+                let value = match gossip_crds.get::<&CrdsValue>(&label).map(CrdsValue::data) {
+                    Some(CrdsData::CustomValue(_, value)) => Some(value),
+                    _ => None,
+                };
+                match value {
+                    Some(value) if value.namespace == namespace => (0, ix, value.wallclock),
+                    Some(value) => (2, ix, value.wallclock),
+                    None => (1, ix, 0),
+                }
+            })
+            .min_by_key(|&(rank, ix, wallclock)| (rank, wallclock, ix))
+            .unwrap_or_default();
+        let value = CrdsData::CustomValue(
+            index,
+            CustomValue {
+                from: self_pubkey,
+                // The new value must be newer than the one it overrides.
+                wallclock: now.max(wallclock + 1),
+                namespace: namespace.to_string(),
+                data,
+            },
+        );
+        let value = CrdsValue::new(value, &self.keypair());
+        if let Err(err) = gossip_crds.insert(value, now, GossipRoute::LocalMessage) {
+            error!("push_custom_value failed: {:?}", err);
+        }
+        Ok(())
+    }
+
     pub fn push_vote_at_index(&self, vote: Transaction, vote_index: u8) {
         assert!((vote_index as usize) < MAX_LOCKOUT_HISTORY);
         let self_pubkey = self.id();
@@ -1004,6 +1117,25 @@ impl ClusterInfo {
             .collect()
     }
 
+    /// Returns custom values in `namespace` inserted since the given cursor.
+    /// Excludes entries from nodes with unknown or different shred version.
+    pub fn get_custom_values(&self, namespace: &str, cursor: &mut Cursor) -> Vec<CustomValue> {
+        let self_shred_version = Some(self.my_shred_version());
+        let gossip_crds = self.gossip.crds.read().unwrap();
+        gossip_crds
+            .get_entries(cursor)
+            .filter_map(|entry| {
+                let CrdsData::CustomValue(_, value) = entry.value.data() else {
+                    return None;
+                };
+                (value.namespace == namespace
+                    && gossip_crds.get_shred_version(&value.from) == self_shred_version)
+                    .then_some(value)
+            })
+            .cloned()
+            .collect()
+    }
+
     /// Returns duplicate-shreds inserted since the given cursor.
     pub(crate) fn get_duplicate_shreds(&self, cursor: &mut Cursor) -> Vec<DuplicateShred> {
         let gossip_crds = self.gossip.crds.read().unwrap();
@@ -1283,14 +1415,20 @@ impl ClusterInfo {
                 self.time_gossip_read_lock("push_req_lookup", &self.stats.new_push_requests2);
             push_messages
                 .into_iter()
-                .filter_map(|(pubkey, messages)| {
+                .filter_map(|(pubkey, mut messages)| {
                     let addr = get_node_addr(
                         pubkey,
                         ContactInfo::gossip,
                         &gossip_crds,
                         &self.socket_addr_space,
                     )?;
-                    Some((addr, messages))
+                    if !gossip_crds
+                        .get::<&ContactInfo>(pubkey)
+                        .is_some_and(accepts_custom_values)
+                    {
+                        messages.retain(|&k| !is_custom_value(&entries[k]));
+                    }
+                    (!messages.is_empty()).then_some((addr, messages))
                 })
                 .collect()
         };
@@ -1687,6 +1825,23 @@ impl ClusterInfo {
                 &self.stats,
             )
         };
+        // Custom values would make older nodes drop the whole packet.
+        let pull_responses: Vec<_> = {
+            let gossip_crds = self.gossip.crds.read().unwrap();
+            requests
+                .iter()
+                .zip(pull_responses)
+                .map(|(PullRequest { pubkey, .. }, mut values)| {
+                    if !gossip_crds
+                        .get::<&ContactInfo>(*pubkey)
+                        .is_some_and(accepts_custom_values)
+                    {
+                        values.retain(|value| !is_custom_value(value));
+                    }
+                    values
+                })
+                .collect()
+        };
         // Prioritize more recent values, staked values and ContactInfos.
         let get_score = |value: &CrdsValue| -> u64 {
             let age = now.saturating_sub(value.wallclock());
@@ -2121,55 +2276,80 @@ impl ClusterInfo {
         self.stats
             .packets_received_count
             .add_relaxed(num_packets as u64 + num_packets_dropped);
-        fn verify_packet(
-            packet: &Packet,
-            stakes: &HashMap<Pubkey, u64>,
-            stats: &GossipStats,
-        ) -> Option<(SocketAddr, Protocol)> {
-            let mut protocol: Protocol =
-                stats.record_received_packet(packet.deserialize_slice::<Protocol, _>(..))?;
-            protocol.sanitize().ok()?;
-            if let Protocol::PullResponse(_, values) | Protocol::PushMessage(_, values) =
-                &mut protocol
-            {
-                values.retain(|value| {
-                    should_retain_crds_value(
-                        value, stakes, /*drop_unstaked_node_instance:*/ false,
-                    )
-                });
-                if values.is_empty() {
-                    return None;
-                }
-            }
-            protocol.par_verify().then(|| {
-                stats.packets_received_verified_count.add_relaxed(1);
-                (packet.meta().socket_addr(), protocol)
-            })
-        }
+        let now = Instant::now();
         let stakes = epoch_specs
             .map(EpochSpecs::current_epoch_staked_nodes)
             .cloned()
             .unwrap_or_default();
+        let verify_packet = |packet: &Packet| {
+            verify_gossip_packet(packet, &stakes, &self.stats, &self.peer_scores, now)
+        };
         let packets: Vec<_> = {
             let _st = ScopedTimer::from(&self.stats.verify_gossip_packets_time);
             thread_pool.install(|| {
                 if packets.len() == 1 {
-                    packets[0]
-                        .par_iter()
-                        .filter_map(|packet| verify_packet(packet, &stakes, &self.stats))
-                        .collect()
+                    packets[0].par_iter().map(verify_packet).collect()
                 } else {
-                    packets
-                        .par_iter()
-                        .flatten()
-                        .filter_map(|packet| verify_packet(packet, &stakes, &self.stats))
-                        .collect()
+                    packets.par_iter().flatten().map(verify_packet).collect()
                 }
             })
         };
+        let (packets, offenses): (Vec<_>, Vec<_>) = packets.into_iter().partition_result();
+        if self.peer_scores.is_enabled() {
+            self.score_peers(&packets, offenses.into_iter().flatten(), &stakes, now);
+        }
         Ok(sender.send(packets)?)
     }
 
+    // Records the offenses and the number of packets of the nodes packets
+    // were received from. Packets are only attributed to the node they claim
+    // to be from if it answered a ping sent to the address they came from, and
+    // staked nodes and entrypoints are never penalized.
+    fn score_peers(
+        &self,
+        packets: &[(SocketAddr, Protocol)],
+        offenses: impl IntoIterator<Item = (Pubkey, SocketAddr, PeerOffense)>,
+        stakes: &HashMap<Pubkey, u64>,
+        now: Instant,
+    ) {
+        let mut num_peer_packets = HashMap::<(Pubkey, SocketAddr), u64>::new();
+        for (addr, protocol) in packets {
+            *num_peer_packets
+                .entry((protocol.sender(), *addr))
+                .or_default() += 1;
+        }
+        let entrypoints: HashSet<IpAddr> = self
+            .entrypoints
+            .read()
+            .unwrap()
+            .iter()
+            .filter_map(|entrypoint| entrypoint.gossip())
+            .map(|addr| addr.ip())
+            .collect();
+        let is_accountable = {
+            let ping_cache = self.ping_cache.lock().unwrap();
+            move |node: &(Pubkey, SocketAddr)| {
+                stakes.get(&node.0).copied().unwrap_or_default() == 0
+                    && !entrypoints.contains(&node.1.ip())
+                    && ping_cache.is_verified(node, now)
+            }
+        };
+        let mut num_banned = 0;
+        for (pubkey, addr, offense) in offenses {
+            if is_accountable(&(pubkey, addr))
+                && self.peer_scores.record_offense(pubkey, offense, now)
+            {
+                num_banned += 1;
+            }
+        }
+        for (node, num_packets) in num_peer_packets {
+            if is_accountable(&node) && self.peer_scores.record_packets(node.0, num_packets, now) {
+                num_banned += 1;
+            }
+        }
+        self.stats.peers_blacklisted.add_relaxed(num_banned);
+    }
+
     /// Process messages from the network
     fn run_listen(
         &self,
@@ -3024,6 +3204,47 @@ fn make_gossip_packet(
     }
 }
 
+// Returns the packet if it verifies, otherwise the offense of the node it
+// claims to be from, if any. Packets whose signatures do not verify may be
+// forged by anyone, so they are never attributed to the node they claim to be
+// from.
+fn verify_gossip_packet(
+    packet: &Packet,
+    stakes: &HashMap<Pubkey, u64>,
+    stats: &GossipStats,
+    peer_scores: &GossipPeerScores,
+    now: Instant,
+) -> Result<(SocketAddr, Protocol), Option<(Pubkey, SocketAddr, PeerOffense)>> {
+    let protocol = packet.deserialize_slice::<Protocol, _>(..);
+    let Some(mut protocol) = stats.record_received_packet(protocol) else {
+        return Err(None);
+    };
+    let sender = protocol.sender();
+    let addr = packet.meta().socket_addr();
+    if peer_scores.is_enabled() && peer_scores.is_banned(&sender, now) {
+        stats.packets_dropped_from_blacklisted_peers.add_relaxed(1);
+        return Err(None);
+    }
+    if protocol.sanitize().is_err() {
+        // Signatures are only verified for the sake of the peer scores.
+        let is_signed = peer_scores.is_enabled() && protocol.par_verify();
+        return Err(is_signed.then_some((sender, addr, PeerOffense::ProtocolViolation)));
+    }
+    if let Protocol::PullResponse(_, values) | Protocol::PushMessage(_, values) = &mut protocol {
+        values.retain(|value| {
+            should_retain_crds_value(value, stakes, /*drop_unstaked_node_instance:*/ false)
+        });
+        if values.is_empty() {
+            return Err(None);
+        }
+    }
+    if !protocol.par_verify() {
+        return Err(None);
+    }
+    stats.packets_received_verified_count.add_relaxed(1);
+    Ok((addr, protocol))
+}
+
 #[cfg(test)]
 mod tests {
     use {
@@ -4278,6 +4499,187 @@ mod tests {
         assert_eq!(heaviest_forks[0].from, pubkey2);
     }
 
+    #[test]
+    fn test_push_custom_value() {
+        solana_logger::setup();
+        let keypair = Arc::new(Keypair::new());
+        let pubkey = keypair.pubkey();
+        let contact_info = ContactInfo::new_localhost(&pubkey, 0);
+        let cluster_info = ClusterInfo::new(contact_info, keypair, SocketAddrSpace::Unspecified);
+
+        let mut cursor = Cursor::default();
+        assert_eq!(cluster_info.get_custom_values("ns0", &mut cursor), vec![]);
+        assert_eq!(
+            cluster_info.push_custom_value("not valid", vec![]),
+            Err(ClusterInfoError::InvalidCustomValue)
+        );
+        assert_eq!(
+            cluster_info.push_custom_value("ns0", vec![0; MAX_CUSTOM_VALUE_DATA_SIZE + 1]),
+            Err(ClusterInfoError::InvalidCustomValue)
+        );
+
+        // Publishing again in the same namespace replaces the previous value.
+        cluster_info.push_custom_value("ns0", vec![1]).unwrap();
+        cluster_info.push_custom_value("ns0", vec![2]).unwrap();
+        let values = cluster_info.get_custom_values("ns0", &mut cursor);
+        assert_eq!(values.len(), 1);
+        assert_eq!(values[0].from, pubkey);
+        assert_eq!(values[0].data, vec![2]);
+        assert_eq!(cluster_info.get_custom_values("ns0", &mut cursor), vec![]);
+
+        // Once all indexes are taken, one of the oldest values is evicted.
+        for ns in 1..=MAX_CUSTOM_VALUES {
+            cluster_info
+                .push_custom_value(&format!("ns{ns}"), vec![ns])
+                .unwrap();
+        }
+        let num_namespaces = (0..=MAX_CUSTOM_VALUES)
+            .filter(|ns| {
+                !cluster_info
+                    .get_custom_values(&format!("ns{ns}"), &mut Cursor::default())
+                    .is_empty()
+            })
+            .count();
+        assert_eq!(num_namespaces, usize::from(MAX_CUSTOM_VALUES));
+        let values = cluster_info
+            .get_custom_values(&format!("ns{MAX_CUSTOM_VALUES}"), &mut Cursor::default());
+        assert_eq!(values.len(), 1);
+        assert_eq!(values[0].data, vec![MAX_CUSTOM_VALUES]);
+
+        // Values from nodes with a different shred version are excluded.
+        let mut rng = rand::thread_rng();
+        let pubkey2 = Pubkey::new_unique();
+        let mut node = ContactInfo::new_rand(&mut rng, Some(pubkey2));
+        node.set_shred_version(42);
+        let entries = vec![
+            CrdsValue::new_unsigned(CrdsData::from(node)),
+            CrdsValue::new_unsigned(CrdsData::CustomValue(
+                0,
+                CustomValue {
+                    from: pubkey2,
+                    wallclock: timestamp(),
+                    namespace: "ns1".to_string(),
+                    data: vec![42],
+                },
+            )),
+        ];
+        {
+            let mut gossip_crds = cluster_info.gossip.crds.write().unwrap();
+            for entry in entries {
+                assert!(gossip_crds
+                    .insert(entry, /*now=*/ 0, GossipRoute::LocalMessage)
+                    .is_ok());
+            }
+        }
+        let values = cluster_info.get_custom_values("ns1", &mut Cursor::default());
+        assert_eq!(values.len(), 1);
+        assert_eq!(values[0].from, pubkey);
+    }
+
+    #[test]
+    fn test_push_custom_values_to_accepting_nodes() {
+        let mut rng = rand::thread_rng();
+        let keypair = Arc::new(Keypair::new());
+        let contact_info = ContactInfo::new_localhost(&keypair.pubkey(), 0);
+        let cluster_info = ClusterInfo::new(contact_info, keypair, SocketAddrSpace::Unspecified);
+        let stakes = HashMap::<Pubkey, u64>::default();
+        let (major, minor, patch) = crate::custom_crds_value::MIN_CUSTOM_VALUE_PEER_VERSION;
+        let peers: Vec<_> = [minor - 1, minor]
+            .into_iter()
+            .map(|minor| {
+                let mut peer = ContactInfo::new_rand(&mut rng, None);
+                peer.set_wallclock(timestamp());
+                peer.set_version(solana_version::Version {
+                    major,
+                    minor,
+                    patch,
+                    ..solana_version::Version::default()
+                });
+                cluster_info.ping_cache.lock().unwrap().mock_pong(
+                    *peer.pubkey(),
+                    peer.gossip().unwrap(),
+                    Instant::now(),
+                );
+                cluster_info.insert_info(peer.clone());
+                peer.gossip().unwrap()
+            })
+            .collect();
+        cluster_info.gossip.refresh_push_active_set(
+            &cluster_info.keypair(),
+            cluster_info.my_shred_version(),
+            &stakes,
+            None, // gossip validators
+            &cluster_info.ping_cache,
+            &mut Vec::new(), // pings
+            &SocketAddrSpace::Unspecified,
+        );
+        cluster_info.push_custom_value("ns0", vec![1]).unwrap();
+        let requests: Vec<_> = cluster_info.new_push_requests(&stakes).collect();
+        let pushes_custom_value = |peer: &SocketAddr| {
+            requests.iter().any(|(addr, protocol)| match protocol {
+                Protocol::PushMessage(_, values) => {
+                    addr == peer && values.iter().any(is_custom_value)
+                }
+                _ => false,
+            })
+        };
+        // Older nodes would drop the whole packet.
+        assert!(!pushes_custom_value(&peers[0]));
+        assert!(pushes_custom_value(&peers[1]));
+    }
+
+    #[test]
+    fn test_exchange_custom_values_at_current_version() {
+        let new_cluster_info = || {
+            let keypair = Arc::new(Keypair::new());
+            let contact_info = ContactInfo::new_localhost(&keypair.pubkey(), timestamp());
+            assert_eq!(contact_info.version(), &solana_version::Version::default());
+            ClusterInfo::new(contact_info, keypair, SocketAddrSpace::Unspecified)
+        };
+        let (node, peer) = (new_cluster_info(), new_cluster_info());
+        node.ping_cache.lock().unwrap().mock_pong(
+            peer.id(),
+            peer.my_contact_info().gossip().unwrap(),
+            Instant::now(),
+        );
+        node.insert_info(peer.my_contact_info());
+        peer.insert_info(node.my_contact_info());
+        let stakes = HashMap::<Pubkey, u64>::default();
+        node.gossip.refresh_push_active_set(
+            &node.keypair(),
+            node.my_shred_version(),
+            &stakes,
+            None, // gossip validators
+            &node.ping_cache,
+            &mut Vec::new(), // pings
+            &SocketAddrSpace::Unspecified,
+        );
+        node.push_custom_value("ns0", vec![1, 2, 3]).unwrap();
+
+        let peer_addr = peer.my_contact_info().gossip().unwrap();
+        let values: Vec<CrdsValue> = node
+            .new_push_requests(&stakes)
+            .filter(|(addr, _)| *addr == peer_addr)
+            .filter_map(|(_, protocol)| {
+                // Round trip through the wire format
+                let protocol: Protocol =
+                    bincode::deserialize(&bincode::serialize(&protocol).unwrap()).unwrap();
+                match protocol {
+                    Protocol::PushMessage(_, values) => Some(values),
+                    _ => None,
+                }
+            })
+            .flatten()
+            .collect();
+        assert!(values.iter().any(is_custom_value));
+        peer.gossip
+            .process_push_message(vec![(node.id(), values)], timestamp());
+        let custom_values = peer.get_custom_values("ns0", &mut Cursor::default());
+        assert_eq!(custom_values.len(), 1);
+        assert_eq!(custom_values[0].from, node.id());
+        assert_eq!(custom_values[0].data, vec![1, 2, 3]);
+    }
+
     #[test]
     fn test_contact_trace() {
         solana_logger::setup();
@@ -4335,4 +4737,107 @@ mod tests {
         info!("rpc:\n{}", trace);
         assert_eq!(trace.len(), 335);
     }
+
+    #[test]
+    fn test_score_peers() {
+        let keypair = Arc::new(Keypair::new());
+        let mut cluster_info = ClusterInfo::new(
+            ContactInfo::new_localhost(&keypair.pubkey(), timestamp()),
+            keypair,
+            SocketAddrSpace::Unspecified,
+        );
+        cluster_info.set_peer_scores_config(GossipPeerScoresConfig {
+            ban_threshold: Some(1),
+            ..GossipPeerScoresConfig::default()
+        });
+        let mut entrypoint = ContactInfo::new_localhost(&Pubkey::new_unique(), timestamp());
+        entrypoint
+            .set_gossip(socketaddr!("127.0.0.2:1234"))
+            .unwrap();
+        cluster_info.set_entrypoint(entrypoint);
+        let staked_node = Pubkey::new_unique();
+        let stakes = HashMap::from([(staked_node, 1)]);
+        let now = Instant::now();
+        let record_offense = |pubkey, addr| {
+            let offense = (pubkey, addr, PeerOffense::ProtocolViolation);
+            cluster_info.score_peers(&[], [offense], &stakes, now);
+            cluster_info.peer_scores.is_banned(&pubkey, now)
+        };
+
+        // Offenses are not attributed to nodes which have not answered a
+        // ping at the address the packet came from
+        let node = Pubkey::new_unique();
+        let addr = socketaddr!("127.0.0.3:1234");
+        assert!(!record_offense(node, addr));
+        let mock_pong = |pubkey, addr| {
+            let mut ping_cache = cluster_info.ping_cache.lock().unwrap();
+            ping_cache.mock_pong(pubkey, addr, now);
+        };
+        mock_pong(node, socketaddr!("127.0.0.4:1234"));
+        assert!(!record_offense(node, addr));
+        mock_pong(node, addr);
+        assert!(record_offense(node, addr));
+
+        // Staked nodes and entrypoints are never blacklisted
+        mock_pong(staked_node, addr);
+        assert!(!record_offense(staked_node, addr));
+        let node = Pubkey::new_unique();
+        let entrypoint_addr = socketaddr!("127.0.0.2:1235");
+        mock_pong(node, entrypoint_addr);
+        assert!(!record_offense(node, entrypoint_addr));
+
+        assert_eq!(cluster_info.gossip_blacklist().len(), 1);
+    }
+
+    #[test]
+    fn test_invalid_signature_not_penalized() {
+        let keypair = Arc::new(Keypair::new());
+        let mut cluster_info = ClusterInfo::new(
+            ContactInfo::new_localhost(&keypair.pubkey(), timestamp()),
+            keypair,
+            SocketAddrSpace::Unspecified,
+        );
+        cluster_info.set_peer_scores_config(GossipPeerScoresConfig {
+            ban_threshold: Some(1),
+            ..GossipPeerScoresConfig::default()
+        });
+        // An unstaked node which answered a ping at its address
+        let node = Keypair::new();
+        let addr = socketaddr!("127.0.0.3:1234");
+        let now = Instant::now();
+        cluster_info
+            .ping_cache
+            .lock()
+            .unwrap()
+            .mock_pong(node.pubkey(), addr, now);
+        let stakes = HashMap::new();
+        let verify_packet = |packet: &Packet| {
+            verify_gossip_packet(
+                packet,
+                &stakes,
+                &cluster_info.stats,
+                &cluster_info.peer_scores,
+                now,
+            )
+        };
+
+        let value = CrdsValue::new(
+            CrdsData::ContactInfo(ContactInfo::new_localhost(&node.pubkey(), timestamp())),
+            &node,
+        );
+        let protocol = Protocol::PushMessage(node.pubkey(), vec![value]);
+        let mut packet = Packet::from_data(Some(&addr), &protocol).unwrap();
+        assert!(verify_packet(&packet).is_ok());
+
+        // Anyone can send the packet from the node's address with a corrupted
+        // signature, which follows the enum tag, sender and number of values.
+        packet.buffer_mut()[4 + 32 + 8] ^= 1;
+        for _ in 0..10 {
+            let offense = verify_packet(&packet).unwrap_err();
+            assert_eq!(offense, None);
+            cluster_info.score_peers(&[], offense, &stakes, now);
+        }
+        assert!(!cluster_info.peer_scores.is_banned(&node.pubkey(), now));
+        assert_eq!(cluster_info.gossip_blacklist(), vec![]);
+    }
 }
@@ -120,6 +120,7 @@ pub struct GossipStats {
     pub(crate) new_push_requests2: Counter,
     pub(crate) new_push_requests: Counter,
     pub(crate) num_unverifed_gossip_addrs: Counter,
+    pub(crate) packets_dropped_from_blacklisted_peers: Counter,
     pub(crate) packets_received_count: Counter,
     packets_received_ping_messages_count: Counter,
     packets_received_pong_messages_count: Counter,
@@ -135,6 +136,7 @@ pub struct GossipStats {
     packets_sent_pull_requests_count: Counter,
     packets_sent_pull_responses_count: Counter,
     packets_sent_push_messages_count: Counter,
+    pub(crate) peers_blacklisted: Counter,
     pub(crate) process_gossip_packets_time: Counter,
     pub(crate) process_prune: Counter,
     pub(crate) process_pull_response: Counter,
@@ -512,6 +514,12 @@ pub(crate) fn submit_gossip_stats(
             stats.num_unverifed_gossip_addrs.clear(),
             i64
         ),
+        (
+            "packets_dropped_from_blacklisted_peers",
+            stats.packets_dropped_from_blacklisted_peers.clear(),
+            i64
+        ),
+        ("peers_blacklisted", stats.peers_blacklisted.clear(), i64),
         (
             "packets_received_count",
             stats.packets_received_count.clear(),
@@ -642,6 +650,8 @@ pub(crate) fn submit_gossip_stats(
         ),
         ("RestartHeaviestFork-push", crds_stats.push.counts[13], i64),
         ("RestartHeaviestFork-pull", crds_stats.pull.counts[13], i64),
+        ("CustomValue-push", crds_stats.push.counts[14], i64),
+        ("CustomValue-pull", crds_stats.pull.counts[14], i64),
         (
             "all-push",
             crds_stats.push.counts.iter().sum::<usize>(),
@@ -691,6 +701,8 @@ pub(crate) fn submit_gossip_stats(
         ),
         ("RestartHeaviestFork-push", crds_stats.push.fails[13], i64),
         ("RestartHeaviestFork-pull", crds_stats.pull.fails[13], i64),
+        ("CustomValue-push", crds_stats.push.fails[14], i64),
+        ("CustomValue-pull", crds_stats.pull.fails[14], i64),
         ("all-push", crds_stats.push.fails.iter().sum::<usize>(), i64),
         ("all-pull", crds_stats.pull.fails.iter().sum::<usize>(), i64),
     );
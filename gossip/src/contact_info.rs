@@ -233,7 +233,7 @@ impl ContactInfo {
     }
 
     #[inline]
-    pub(crate) fn version(&self) -> &solana_version::Version {
+    pub fn version(&self) -> &solana_version::Version {
         &self.version
     }
 
@@ -252,6 +252,11 @@ impl ContactInfo {
         self.shred_version = shred_version
     }
 
+    #[cfg(test)]
+    pub(crate) fn set_version(&mut self, version: solana_version::Version) {
+        self.version = version;
+    }
+
     get_socket!(gossip, SOCKET_TAG_GOSSIP);
     get_socket!(rpc, SOCKET_TAG_RPC);
     get_socket!(rpc_pubsub, SOCKET_TAG_RPC_PUBSUB);
@@ -390,6 +395,15 @@ impl ContactInfo {
         }
     }
 
+    // Returns all sockets advertised by this node along with their keys.
+    pub(crate) fn sockets(&self) -> impl Iterator<Item = (/*key:*/ u8, SocketAddr)> + '_ {
+        self.cache
+            .iter()
+            .enumerate()
+            .filter(|(_, socket)| *socket != &SOCKET_ADDR_UNSPECIFIED)
+            .map(|(key, socket)| (key as u8, *socket))
+    }
+
     pub fn is_valid_address(addr: &SocketAddr, socket_addr_space: &SocketAddrSpace) -> bool {
         LegacyContactInfo::is_valid_address(addr, socket_addr_space)
     }
@@ -492,6 +506,15 @@ impl ContactInfo {
     }
 }
 
+// Returns true if the socket with the given key also serves TCP traffic on
+// the same port, e.g. ip-echo server on the gossip port or json-rpc.
+pub(crate) fn is_tcp_socket_key(key: u8) -> bool {
+    matches!(
+        key,
+        SOCKET_TAG_GOSSIP | SOCKET_TAG_RPC | SOCKET_TAG_RPC_PUBSUB
+    )
+}
+
 fn get_node_outset() -> u64 {
     let now = SystemTime::now();
     let elapsed = now.duration_since(UNIX_EPOCH).unwrap();
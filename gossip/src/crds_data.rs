@@ -1,6 +1,7 @@
 use {
     crate::{
         contact_info::ContactInfo,
+        custom_crds_value::{CustomValue, CustomValueIndex, MAX_CUSTOM_VALUES},
         deprecated,
         duplicate_shred::{DuplicateShred, DuplicateShredIndex, MAX_DUPLICATE_SHREDS},
         epoch_slots::EpochSlots,
@@ -63,6 +64,7 @@ pub enum CrdsData {
     ContactInfo(ContactInfo),
     RestartLastVotedForkSlots(RestartLastVotedForkSlots),
     RestartHeaviestFork(RestartHeaviestFork),
+    CustomValue(CustomValueIndex, CustomValue),
 }
 
 impl Sanitize for CrdsData {
@@ -103,6 +105,13 @@ impl Sanitize for CrdsData {
             CrdsData::ContactInfo(node) => node.sanitize(),
             CrdsData::RestartLastVotedForkSlots(slots) => slots.sanitize(),
             CrdsData::RestartHeaviestFork(fork) => fork.sanitize(),
+            CrdsData::CustomValue(ix, value) => {
+                if *ix >= MAX_CUSTOM_VALUES {
+                    Err(SanitizeError::ValueOutOfBounds)
+                } else {
+                    value.sanitize()
+                }
+            }
         }
     }
 }
@@ -116,7 +125,7 @@ pub(crate) fn new_rand_timestamp<R: Rng>(rng: &mut R) -> u64 {
 impl CrdsData {
     /// New random CrdsData for tests and benchmarks.
     pub(crate) fn new_rand<R: Rng>(rng: &mut R, pubkey: Option<Pubkey>) -> CrdsData {
-        let kind = rng.gen_range(0..8);
+        let kind = rng.gen_range(0..9);
         // TODO: Implement other kinds of CrdsData here.
         // TODO: Assign ranges to each arm proportional to their frequency in
         // the mainnet crds table.
@@ -131,6 +140,10 @@ impl CrdsData {
                 rng, pubkey,
             )),
             6 => CrdsData::RestartHeaviestFork(RestartHeaviestFork::new_rand(rng, pubkey)),
+            7 => CrdsData::CustomValue(
+                rng.gen_range(0..MAX_CUSTOM_VALUES),
+                CustomValue::new_rand(rng, pubkey),
+            ),
             _ => CrdsData::EpochSlots(
                 rng.gen_range(0..MAX_EPOCH_SLOTS),
                 EpochSlots::new_rand(rng, pubkey),
@@ -154,6 +167,7 @@ impl CrdsData {
             CrdsData::ContactInfo(node) => node.wallclock(),
             CrdsData::RestartLastVotedForkSlots(slots) => slots.wallclock,
             CrdsData::RestartHeaviestFork(fork) => fork.wallclock,
+            CrdsData::CustomValue(_, value) => value.wallclock,
         }
     }
 
@@ -173,6 +187,7 @@ impl CrdsData {
             CrdsData::ContactInfo(node) => *node.pubkey(),
             CrdsData::RestartLastVotedForkSlots(slots) => slots.from,
             CrdsData::RestartHeaviestFork(fork) => fork.from,
+            CrdsData::CustomValue(_, value) => value.from,
         }
     }
 
@@ -195,6 +210,7 @@ impl CrdsData {
             Self::ContactInfo(_) => false,
             Self::RestartLastVotedForkSlots(_) => false,
             Self::RestartHeaviestFork(_) => false,
+            Self::CustomValue(..) => false,
         }
     }
 }
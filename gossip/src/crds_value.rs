@@ -2,6 +2,7 @@ use {
     crate::{
         contact_info::ContactInfo,
         crds_data::{CrdsData, EpochSlotsIndex, VoteIndex},
+        custom_crds_value::CustomValueIndex,
         duplicate_shred::DuplicateShredIndex,
         epoch_slots::EpochSlots,
     },
@@ -75,6 +76,7 @@ pub enum CrdsValueLabel {
     ContactInfo(Pubkey),
     RestartLastVotedForkSlots(Pubkey),
     RestartHeaviestFork(Pubkey),
+    CustomValue(CustomValueIndex, Pubkey),
 }
 
 impl CrdsValueLabel {
@@ -94,6 +96,7 @@ impl CrdsValueLabel {
             CrdsValueLabel::ContactInfo(pubkey) => *pubkey,
             CrdsValueLabel::RestartLastVotedForkSlots(p) => *p,
             CrdsValueLabel::RestartHeaviestFork(p) => *p,
+            CrdsValueLabel::CustomValue(_, p) => *p,
         }
     }
 }
@@ -182,6 +185,7 @@ impl CrdsValue {
                 CrdsValueLabel::RestartLastVotedForkSlots(pubkey)
             }
             CrdsData::RestartHeaviestFork(_) => CrdsValueLabel::RestartHeaviestFork(pubkey),
+            CrdsData::CustomValue(ix, _) => CrdsValueLabel::CustomValue(ix, pubkey),
         }
     }
 
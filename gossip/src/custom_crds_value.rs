@@ -0,0 +1,162 @@
+//! Application-defined values published through gossip.
+//!
+//! Operators may publish small opaque blobs, e.g. maintenance announcements or
+//! restart coordination data, under a namespace of their choosing. Each node
+//! holds at most `MAX_CUSTOM_VALUES` of them in the crds table at a time, and,
+//! as with other non-essential values, only those from staked nodes are
+//! propagated once the cluster has enough staked nodes.
+//!
+//! Nodes which do not know the `CrdsData::CustomValue` variant fail to
+//! deserialize the whole packet carrying one, dropping the other values in it,
+//! so custom values are only sent to nodes running at least
+//! `MIN_CUSTOM_VALUE_PEER_VERSION`, the first release shipping the variant.
+//! Nodes running older releases neither receive nor propagate them.
+use {
+    crate::{
+        contact_info::ContactInfo,
+        crds_data::{new_rand_timestamp, sanitize_wallclock},
+    },
+    rand::Rng,
+    solana_sanitize::{Sanitize, SanitizeError},
+    solana_sdk::pubkey::{self, Pubkey},
+};
+
+pub(crate) type CustomValueIndex = u8;
+/// Maximum number of custom values a node may have in the crds table.
+pub const MAX_CUSTOM_VALUES: CustomValueIndex = 8;
+/// Maximum length of a custom value's namespace, in bytes.
+pub const MAX_CUSTOM_VALUE_NAMESPACE_LEN: usize = 32;
+/// Maximum size of a custom value's data, so that the serialized value stays
+/// well below MAX_CRDS_OBJECT_SIZE.
+pub const MAX_CUSTOM_VALUE_DATA_SIZE: usize = 512;
+/// Minimum (major, minor, patch) version of the nodes custom values are sent
+/// to, which is the first release that deserializes them.
+pub(crate) const MIN_CUSTOM_VALUE_PEER_VERSION: (u16, u16, u16) = (2, 2, 0);
+
+#[cfg_attr(feature = "frozen-abi", derive(AbiExample))]
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct CustomValue {
+    pub from: Pubkey,
+    pub wallclock: u64,
+    pub namespace: String,
+    pub data: Vec<u8>,
+}
+
+/// Returns true if `namespace` is non-empty, at most
+/// MAX_CUSTOM_VALUE_NAMESPACE_LEN bytes long, and only made of ASCII
+/// alphanumeric characters and `-`, `_`, `.` or `/`.
+pub fn is_valid_namespace(namespace: &str) -> bool {
+    !namespace.is_empty()
+        && namespace.len() <= MAX_CUSTOM_VALUE_NAMESPACE_LEN
+        && namespace
+            .bytes()
+            .all(|byte| byte.is_ascii_alphanumeric() || b"-_./".contains(&byte))
+}
+
+/// Returns true if `node` runs a version which deserializes custom values.
+pub(crate) fn accepts_custom_values(node: &ContactInfo) -> bool {
+    let version = node.version();
+    (version.major, version.minor, version.patch) >= MIN_CUSTOM_VALUE_PEER_VERSION
+}
+
+impl Sanitize for CustomValue {
+    fn sanitize(&self) -> Result<(), SanitizeError> {
+        sanitize_wallclock(self.wallclock)?;
+        if !is_valid_namespace(&self.namespace) {
+            return Err(SanitizeError::InvalidValue);
+        }
+        if self.data.len() > MAX_CUSTOM_VALUE_DATA_SIZE {
+            return Err(SanitizeError::ValueOutOfBounds);
+        }
+        self.from.sanitize()
+    }
+}
+
+impl CustomValue {
+    /// New random CustomValue for tests and benchmarks.
+    pub(crate) fn new_rand<R: Rng>(rng: &mut R, pubkey: Option<Pubkey>) -> Self {
+        let size = rng.gen_range(0..MAX_CUSTOM_VALUE_DATA_SIZE);
+        Self {
+            from: pubkey.unwrap_or_else(pubkey::new_rand),
+            wallclock: new_rand_timestamp(rng),
+            namespace: format!("test/{}", rng.gen_range(0..4)),
+            data: std::iter::repeat_with(|| rng.gen()).take(size).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            crds_data::{CrdsData, MAX_WALLCLOCK},
+            crds_value::CrdsValue,
+            protocol::MAX_CRDS_OBJECT_SIZE,
+        },
+        bincode::serialized_size,
+        solana_sdk::{signature::Keypair, signer::Signer, timing::timestamp},
+    };
+
+    #[test]
+    fn test_is_valid_namespace() {
+        assert!(is_valid_namespace("maintenance"));
+        assert!(is_valid_namespace("acme/restart-v1.2_rc"));
+        assert!(is_valid_namespace(
+            &"a".repeat(MAX_CUSTOM_VALUE_NAMESPACE_LEN)
+        ));
+        assert!(!is_valid_namespace(""));
+        assert!(!is_valid_namespace(
+            &"a".repeat(MAX_CUSTOM_VALUE_NAMESPACE_LEN + 1)
+        ));
+        assert!(!is_valid_namespace("with space"));
+        assert!(!is_valid_namespace("ünïcode"));
+    }
+
+    #[test]
+    fn test_accepts_custom_values() {
+        let (major, minor, patch) = MIN_CUSTOM_VALUE_PEER_VERSION;
+        let mut node = ContactInfo::new_localhost(&Pubkey::new_unique(), timestamp());
+        for (version, accepts) in [
+            ((major, minor, patch), true),
+            ((major, minor + 1, 0), true),
+            ((major, minor - 1, 99), false),
+            ((0, 0, 0), false),
+        ] {
+            node.set_version(solana_version::Version {
+                major: version.0,
+                minor: version.1,
+                patch: version.2,
+                ..solana_version::Version::default()
+            });
+            assert_eq!(accepts_custom_values(&node), accepts, "{version:?}");
+        }
+    }
+
+    #[test]
+    fn test_custom_value_sanitize() {
+        let keypair = Keypair::new();
+        let mut value = CustomValue {
+            from: keypair.pubkey(),
+            wallclock: timestamp(),
+            namespace: "maintenance".to_string(),
+            data: vec![7u8; MAX_CUSTOM_VALUE_DATA_SIZE],
+        };
+        assert_eq!(value.sanitize(), Ok(()));
+        // The largest custom value fits in a crds object.
+        let crds_value = CrdsValue::new(
+            CrdsData::CustomValue(MAX_CUSTOM_VALUES - 1, value.clone()),
+            &keypair,
+        );
+        assert!(serialized_size(&crds_value).unwrap() <= MAX_CRDS_OBJECT_SIZE as u64);
+
+        value.data.push(7);
+        assert_eq!(value.sanitize(), Err(SanitizeError::ValueOutOfBounds));
+        value.data.clear();
+        value.namespace = "not valid".to_string();
+        assert_eq!(value.sanitize(), Err(SanitizeError::InvalidValue));
+        value.namespace = "maintenance".to_string();
+        value.wallclock = MAX_WALLCLOCK;
+        assert_eq!(value.sanitize(), Err(SanitizeError::ValueOutOfBounds));
+    }
+}
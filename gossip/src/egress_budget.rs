@@ -0,0 +1,418 @@
+//! The `egress_budget` module caps the bandwidth used by gossip egress.
+//!
+//! When limits are configured, packets on their way to the gossip socket pass
+//! through a `GossipEgressBudget`, which holds a token bucket for all peers
+//! combined and one for each destination address. Packets exceeding either
+//! budget are deferred until the buckets refill, and dropped if too many are
+//! already deferred or if they have waited for too long.
+//!
+//! Pings and pongs are never held back, since peers drop the traffic of nodes
+//! which fail to answer their pings in time. They still count against the
+//! budgets.
+use {
+    crossbeam_channel::RecvTimeoutError,
+    lru::LruCache,
+    solana_perf::packet::{Packet, PacketBatch, PACKET_DATA_SIZE},
+    solana_streamer::streamer::{PacketBatchReceiver, PacketBatchSender},
+    std::{
+        collections::VecDeque,
+        net::SocketAddr,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        },
+        thread::{Builder, JoinHandle},
+        time::{Duration, Instant},
+    },
+};
+
+// Maximum number of packets waiting for the budgets to refill.
+const MAX_DEFERRED_PACKETS: usize = 16_384;
+// Deferred packets are dropped after this long, as their content is likely
+// stale by then, e.g. pings would fail the ping cache's deadline.
+const MAX_DEFERRAL: Duration = Duration::from_secs(1);
+// Number of destination addresses whose budget is tracked. The least recently
+// used address is evicted when full, restarting with a full budget.
+const PEER_BUDGETS_CAPACITY: usize = 8_192;
+// How often deferred packets are retried when no new packets arrive.
+const RETRY_INTERVAL: Duration = Duration::from_millis(10);
+const STATS_REPORT_INTERVAL: Duration = Duration::from_secs(2);
+// Bincode serializes the variant index of `Protocol` as a leading u32.
+const PING_MESSAGE_VARIANT_INDEX: u32 = 4;
+const PONG_MESSAGE_VARIANT_INDEX: u32 = 5;
+
+/// Caps on the bandwidth used by gossip egress
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GossipBandwidthConfig {
+    /// Bytes per second sent to all peers combined, unlimited if None
+    pub max_bytes_per_second: Option<u64>,
+    /// Bytes per second sent to each peer, unlimited if None
+    pub max_peer_bytes_per_second: Option<u64>,
+}
+
+impl GossipBandwidthConfig {
+    pub fn is_unlimited(&self) -> bool {
+        self.max_bytes_per_second.is_none() && self.max_peer_bytes_per_second.is_none()
+    }
+}
+
+/// Token bucket holding up to one second worth of bytes, and at least enough
+/// for a full packet so that limits below the packet size still let packets
+/// through.
+struct TokenBucket {
+    bytes_per_second: u64,
+    capacity: u64,
+    bytes: u64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(bytes_per_second: u64, now: Instant) -> Self {
+        let capacity = bytes_per_second.max(PACKET_DATA_SIZE as u64);
+        Self {
+            bytes_per_second,
+            capacity,
+            bytes: capacity,
+            last_refill: now,
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        let refill = (u128::from(self.bytes_per_second) * elapsed.as_micros() / 1_000_000) as u64;
+        // Only move the refill time forward by whole bytes, so that frequent
+        // refills do not round the budget away.
+        if refill > 0 {
+            self.bytes = self.bytes.saturating_add(refill).min(self.capacity);
+            self.last_refill = now;
+        }
+    }
+}
+
+#[derive(Default)]
+struct GossipEgressStats {
+    packets_sent: u64,
+    bytes_sent: u64,
+    packets_deferred: u64,
+    packets_dropped_queue_full: u64,
+    packets_dropped_expired: u64,
+}
+
+impl GossipEgressStats {
+    fn report(&mut self, num_deferred: usize) {
+        datapoint_info!(
+            "gossip_egress_budget",
+            ("packets_sent", self.packets_sent, i64),
+            ("bytes_sent", self.bytes_sent, i64),
+            ("packets_deferred", self.packets_deferred, i64),
+            (
+                "packets_dropped_queue_full",
+                self.packets_dropped_queue_full,
+                i64
+            ),
+            ("packets_dropped_expired", self.packets_dropped_expired, i64),
+            ("deferred_queue_len", num_deferred, i64),
+        );
+        *self = Self::default();
+    }
+}
+
+pub(crate) struct GossipEgressBudget {
+    global: Option<TokenBucket>,
+    max_peer_bytes_per_second: Option<u64>,
+    peers: LruCache<SocketAddr, TokenBucket>,
+    // Packets waiting for the budgets to refill, along with when they were
+    // first deferred.
+    deferred: VecDeque<(Instant, Packet)>,
+    stats: GossipEgressStats,
+}
+
+impl GossipEgressBudget {
+    pub(crate) fn new(config: &GossipBandwidthConfig, now: Instant) -> Self {
+        Self {
+            global: config
+                .max_bytes_per_second
+                .map(|bytes_per_second| TokenBucket::new(bytes_per_second, now)),
+            max_peer_bytes_per_second: config.max_peer_bytes_per_second,
+            peers: LruCache::new(PEER_BUDGETS_CAPACITY),
+            deferred: VecDeque::new(),
+            stats: GossipEgressStats::default(),
+        }
+    }
+
+    /// Returns the deferred packets, followed by the packets of `batches`,
+    /// which fit within the budgets. The others are deferred, or dropped.
+    pub(crate) fn release(&mut self, batches: Vec<PacketBatch>, now: Instant) -> Vec<Packet> {
+        if let Some(global) = &mut self.global {
+            global.refill(now);
+        }
+        let mut packets = vec![];
+        let deferred = std::mem::take(&mut self.deferred);
+        for (deferred_at, packet) in deferred {
+            if now.saturating_duration_since(deferred_at) > MAX_DEFERRAL {
+                self.stats.packets_dropped_expired += 1;
+            } else if self.take(&packet, now) {
+                packets.push(packet);
+            } else {
+                self.deferred.push_back((deferred_at, packet));
+            }
+        }
+        for packet in batches.iter().flatten() {
+            if packet.meta().discard() {
+                continue;
+            }
+            if self.take(packet, now) {
+                packets.push(packet.clone());
+            } else if self.deferred.len() < MAX_DEFERRED_PACKETS {
+                self.stats.packets_deferred += 1;
+                self.deferred.push_back((now, packet.clone()));
+            } else {
+                self.stats.packets_dropped_queue_full += 1;
+            }
+        }
+        self.stats.packets_sent += packets.len() as u64;
+        packets
+    }
+
+    // Consumes the packet's size from the global budget and from its
+    // destination's budget, if both have enough bytes left. Pings and pongs
+    // are always let through, drawing the budgets down as far as zero.
+    fn take(&mut self, packet: &Packet, now: Instant) -> bool {
+        let size = packet.meta().size as u64;
+        let exempt = is_ping_or_pong(packet);
+        if !exempt && matches!(&self.global, Some(global) if global.bytes < size) {
+            return false;
+        }
+        if let Some(max_peer_bytes_per_second) = self.max_peer_bytes_per_second {
+            let addr = packet.meta().socket_addr();
+            if !self.peers.contains(&addr) {
+                self.peers
+                    .put(addr, TokenBucket::new(max_peer_bytes_per_second, now));
+            }
+            let peer = self.peers.get_mut(&addr).unwrap();
+            peer.refill(now);
+            if !exempt && peer.bytes < size {
+                return false;
+            }
+            peer.bytes = peer.bytes.saturating_sub(size);
+        }
+        if let Some(global) = &mut self.global {
+            global.bytes = global.bytes.saturating_sub(size);
+        }
+        self.stats.bytes_sent += size;
+        true
+    }
+}
+
+fn is_ping_or_pong(packet: &Packet) -> bool {
+    matches!(
+        packet.deserialize_slice::<u32, _>(..4),
+        Ok(PING_MESSAGE_VARIANT_INDEX | PONG_MESSAGE_VARIANT_INDEX)
+    )
+}
+
+/// Spawns a thread forwarding packet batches from `receiver` to `sender`
+/// within the bandwidth caps of `config`
+pub(crate) fn spawn_egress_budget_thread(
+    config: GossipBandwidthConfig,
+    receiver: PacketBatchReceiver,
+    sender: PacketBatchSender,
+    exit: Arc<AtomicBool>,
+) -> JoinHandle<()> {
+    Builder::new()
+        .name("solGossipEgress".to_string())
+        .spawn(move || {
+            let mut budget = GossipEgressBudget::new(&config, Instant::now());
+            let mut last_report = Instant::now();
+            while !exit.load(Ordering::Relaxed) {
+                let batches = match receiver.recv_timeout(RETRY_INTERVAL) {
+                    Ok(batch) => std::iter::once(batch).chain(receiver.try_iter()).collect(),
+                    Err(RecvTimeoutError::Timeout) => vec![],
+                    Err(RecvTimeoutError::Disconnected) => break,
+                };
+                let packets = budget.release(batches, Instant::now());
+                if !packets.is_empty() && sender.send(PacketBatch::new(packets)).is_err() {
+                    break;
+                }
+                if last_report.elapsed() >= STATS_REPORT_INTERVAL {
+                    budget.stats.report(budget.deferred.len());
+                    last_report = Instant::now();
+                }
+            }
+        })
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{
+            ping_pong::Pong,
+            protocol::{Ping, Protocol},
+        },
+        solana_sdk::signature::Keypair,
+    };
+
+    fn new_packet(addr: SocketAddr, size: usize) -> Packet {
+        let mut packet = Packet::default();
+        packet.meta_mut().set_socket_addr(&addr);
+        packet.meta_mut().size = size;
+        packet
+    }
+
+    fn destinations(packets: &[Packet]) -> Vec<SocketAddr> {
+        packets
+            .iter()
+            .map(|packet| packet.meta().socket_addr())
+            .collect()
+    }
+
+    #[test]
+    fn test_egress_budget_unlimited() {
+        let now = Instant::now();
+        let mut budget = GossipEgressBudget::new(&GossipBandwidthConfig::default(), now);
+        let addr = SocketAddr::from(([127, 0, 0, 1], 8001));
+        let batch = PacketBatch::new(vec![new_packet(addr, 1_000); 100]);
+        assert_eq!(budget.release(vec![batch], now).len(), 100);
+        assert!(budget.deferred.is_empty());
+    }
+
+    #[test]
+    fn test_egress_budget_peer_limit() {
+        let now = Instant::now();
+        let config = GossipBandwidthConfig {
+            max_bytes_per_second: None,
+            max_peer_bytes_per_second: Some(2_000),
+        };
+        let mut budget = GossipEgressBudget::new(&config, now);
+        let addr1 = SocketAddr::from(([127, 0, 0, 1], 8001));
+        let addr2 = SocketAddr::from(([127, 0, 0, 1], 8002));
+        let batch = PacketBatch::new(vec![
+            new_packet(addr1, 1_200),
+            new_packet(addr2, 1_200),
+            new_packet(addr1, 1_200),
+        ]);
+        // The second packet to addr1 exceeds its budget and is deferred.
+        assert_eq!(
+            destinations(&budget.release(vec![batch], now)),
+            vec![addr1, addr2]
+        );
+        assert_eq!(budget.deferred.len(), 1);
+        assert_eq!(budget.stats.packets_deferred, 1);
+
+        // Not enough has been refilled yet.
+        let now = now + Duration::from_millis(100);
+        assert!(budget.release(vec![], now).is_empty());
+        // Deferred packets go before new ones.
+        let now = now + Duration::from_millis(500);
+        let batch = PacketBatch::new(vec![new_packet(addr2, 100)]);
+        assert_eq!(
+            destinations(&budget.release(vec![batch], now)),
+            vec![addr1, addr2]
+        );
+        assert!(budget.deferred.is_empty());
+    }
+
+    #[test]
+    fn test_egress_budget_global_limit() {
+        let now = Instant::now();
+        let config = GossipBandwidthConfig {
+            max_bytes_per_second: Some(2_000),
+            max_peer_bytes_per_second: Some(10_000),
+        };
+        let mut budget = GossipEgressBudget::new(&config, now);
+        let packets: Vec<_> = (0..4)
+            .map(|i| new_packet(SocketAddr::from(([127, 0, 0, 1], 8000 + i)), 800))
+            .collect();
+        let released = budget.release(vec![PacketBatch::new(packets)], now);
+        assert_eq!(released.len(), 2);
+        assert_eq!(budget.deferred.len(), 2);
+
+        // Packets deferred for too long are dropped.
+        let now = now + MAX_DEFERRAL + Duration::from_millis(1);
+        assert!(budget.release(vec![], now).is_empty());
+        assert!(budget.deferred.is_empty());
+        assert_eq!(budget.stats.packets_dropped_expired, 2);
+    }
+
+    #[test]
+    fn test_egress_budget_limit_below_packet_size() {
+        let now = Instant::now();
+        let config = GossipBandwidthConfig {
+            max_bytes_per_second: Some(100),
+            max_peer_bytes_per_second: Some(100),
+        };
+        let mut budget = GossipEgressBudget::new(&config, now);
+        let addr = SocketAddr::from(([127, 0, 0, 1], 8001));
+        let batch = PacketBatch::new(vec![new_packet(addr, PACKET_DATA_SIZE)]);
+        assert_eq!(budget.release(vec![batch], now).len(), 1);
+
+        // It takes PACKET_DATA_SIZE / 100 seconds to refill for the next full
+        // packet.
+        let now = now + Duration::from_millis(10 * PACKET_DATA_SIZE as u64 - 10);
+        let batch = PacketBatch::new(vec![new_packet(addr, PACKET_DATA_SIZE)]);
+        assert!(budget.release(vec![batch], now).is_empty());
+        assert_eq!(budget.deferred.len(), 1);
+        let now = now + Duration::from_millis(10);
+        assert_eq!(budget.release(vec![], now).len(), 1);
+        assert!(budget.deferred.is_empty());
+    }
+
+    #[test]
+    fn test_egress_budget_ping_pong_exempt() {
+        let now = Instant::now();
+        let config = GossipBandwidthConfig {
+            max_bytes_per_second: Some(0),
+            max_peer_bytes_per_second: Some(0),
+        };
+        let mut budget = GossipEgressBudget::new(&config, now);
+        let addr = SocketAddr::from(([127, 0, 0, 1], 8001));
+        let keypair = Keypair::new();
+        let ping = Ping::new([0x5a; 32], &keypair);
+        let pong = Pong::new(&ping, &keypair);
+        let packets: Vec<_> = std::iter::once(new_packet(addr, PACKET_DATA_SIZE))
+            .chain(
+                [
+                    Protocol::PingMessage(ping),
+                    Protocol::PongMessage(pong),
+                    Protocol::PingMessage(Ping::new([0xa5; 32], &keypair)),
+                ]
+                .iter()
+                .map(|protocol| Packet::from_data(Some(&addr), protocol).unwrap()),
+            )
+            .collect();
+        // The full packet uses the budget up, but pings and pongs still go
+        // through.
+        let released = budget.release(vec![PacketBatch::new(packets)], now);
+        assert_eq!(released.len(), 4);
+        let batch = PacketBatch::new(vec![new_packet(addr, 10)]);
+        assert!(budget.release(vec![batch], now).is_empty());
+        let batch = PacketBatch::new(vec![Packet::from_data(
+            Some(&addr),
+            Protocol::PingMessage(Ping::new([0x00; 32], &keypair)),
+        )
+        .unwrap()]);
+        assert_eq!(budget.release(vec![batch], now).len(), 1);
+        assert_eq!(budget.deferred.len(), 1);
+    }
+
+    #[test]
+    fn test_egress_budget_deferred_queue_full() {
+        let now = Instant::now();
+        let config = GossipBandwidthConfig {
+            max_bytes_per_second: Some(0),
+            max_peer_bytes_per_second: None,
+        };
+        let mut budget = GossipEgressBudget::new(&config, now);
+        let addr = SocketAddr::from(([127, 0, 0, 1], 8001));
+        // The first packet uses up the initial budget.
+        let batch = PacketBatch::new(vec![
+            new_packet(addr, PACKET_DATA_SIZE);
+            MAX_DEFERRED_PACKETS + 6
+        ]);
+        assert_eq!(budget.release(vec![batch], now).len(), 1);
+        assert_eq!(budget.deferred.len(), MAX_DEFERRED_PACKETS);
+        assert_eq!(budget.stats.packets_dropped_queue_full, 5);
+    }
+}
@@ -1,7 +1,10 @@
 //! The `gossip_service` module implements the network control plane.
 
 use {
-    crate::{cluster_info::ClusterInfo, contact_info::ContactInfo},
+    crate::{
+        cluster_info::ClusterInfo, contact_info::ContactInfo,
+        egress_budget::spawn_egress_budget_thread,
+    },
     crossbeam_channel::{unbounded, Sender},
     rand::{thread_rng, Rng},
     solana_client::{connection_cache::ConnectionCache, tpu_client::TpuClientWrapper},
@@ -79,10 +82,27 @@ impl GossipService {
             should_check_duplicate_instance,
             exit.clone(),
         );
-        let t_gossip =
-            cluster_info
-                .clone()
-                .gossip(bank_forks, response_sender, gossip_validators, exit);
+        let t_gossip = cluster_info.clone().gossip(
+            bank_forks,
+            response_sender,
+            gossip_validators,
+            exit.clone(),
+        );
+        // With bandwidth caps, responses go through the egress budget before
+        // reaching the responder.
+        let bandwidth_config = cluster_info.bandwidth_config();
+        let (t_egress_budget, response_receiver) = if bandwidth_config.is_unlimited() {
+            (None, response_receiver)
+        } else {
+            let (budgeted_sender, budgeted_receiver) = unbounded();
+            let t_egress_budget = spawn_egress_budget_thread(
+                bandwidth_config,
+                response_receiver,
+                budgeted_sender,
+                exit,
+            );
+            (Some(t_egress_budget), budgeted_receiver)
+        };
         let t_responder = streamer::responder(
             "Gossip",
             gossip_socket,
@@ -90,13 +110,14 @@ impl GossipService {
             socket_addr_space,
             stats_reporter_sender,
         );
-        let thread_hdls = vec![
+        let mut thread_hdls = vec![
             t_receiver,
             t_responder,
             t_socket_consume,
             t_listen,
             t_gossip,
         ];
+        thread_hdls.extend(t_egress_budget);
         Self { thread_hdls }
     }
 
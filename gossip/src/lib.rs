@@ -13,17 +13,21 @@ pub mod crds_gossip_pull;
 pub mod crds_gossip_push;
 pub mod crds_shards;
 pub mod crds_value;
+pub mod custom_crds_value;
 mod deprecated;
 pub mod duplicate_shred;
 pub mod duplicate_shred_handler;
 pub mod duplicate_shred_listener;
+pub mod egress_budget;
 pub mod epoch_slots;
 mod epoch_specs;
 pub mod gossip_error;
 pub mod gossip_service;
 #[macro_use]
 mod legacy_contact_info;
+pub mod peer_scores;
 pub mod ping_pong;
+pub mod port_mapping_service;
 mod protocol;
 mod push_active_set;
 mod received_cache;
@@ -0,0 +1,369 @@
+//! The `peer_scores` module blacklists misbehaving gossip peers.
+//!
+//! Peers are identified by their node pubkey. UDP source addresses can be
+//! spoofed, so an offense is only attributed to the node a packet claims to be
+//! from if the packet's signatures verify and that node proved, by answering a
+//! ping, that it receives packets at the address the packet came from. Anyone
+//! can forge a badly signed packet claiming to be from another node, so packets
+//! failing signature verification, like those which do not even deserialize,
+//! are dropped without penalizing any node.
+//!
+//! Every offense adds a penalty to the score of the peer, and a peer whose
+//! score reaches the threshold is blacklisted: its packets are dropped until
+//! the ban expires. Each ban lasts twice as long as the previous one, up to a
+//! maximum, until the peer behaves for that long. Scores are forgotten after a
+//! while without offenses. Staked nodes, entrypoints and allowlisted peers are
+//! never blacklisted, which is up to the caller to check for the first two.
+use {
+    lru::LruCache,
+    solana_sdk::pubkey::Pubkey,
+    std::{
+        collections::{HashMap, HashSet},
+        sync::{Mutex, RwLock},
+        time::{Duration, Instant},
+    },
+};
+
+// Number of peers whose score is tracked. The least recently scored peer is
+// evicted when full, restarting from a clean score.
+const PEER_SCORES_CAPACITY: usize = 8_192;
+// Scores of peers without offenses for this long are reset.
+const SCORE_TTL: Duration = Duration::from_secs(60);
+// Packets are counted against the bandwidth limit over windows this long.
+const BANDWIDTH_WINDOW: Duration = Duration::from_secs(1);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PeerOffense {
+    /// Packet which fails sanitization
+    ProtocolViolation,
+    /// More packets than allowed within a second
+    ExcessiveBandwidth,
+}
+
+impl PeerOffense {
+    fn penalty(self) -> u64 {
+        match self {
+            Self::ProtocolViolation => 5,
+            Self::ExcessiveBandwidth => 10,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GossipPeerScoresConfig {
+    /// Score at which a peer is blacklisted, never if None
+    pub ban_threshold: Option<u64>,
+    /// Duration of the first ban of a peer
+    pub ban_duration: Duration,
+    /// Duration bans stop doubling at
+    pub max_ban_duration: Duration,
+    /// Packets per second a peer may send before it is penalized, unlimited if None
+    pub max_packets_per_second: Option<u64>,
+    /// Peers which are never blacklisted
+    pub allowlist: HashSet<Pubkey>,
+}
+
+impl Default for GossipPeerScoresConfig {
+    fn default() -> Self {
+        Self {
+            ban_threshold: None,
+            ban_duration: Duration::from_secs(60),
+            max_ban_duration: Duration::from_secs(3_600),
+            max_packets_per_second: None,
+            allowlist: HashSet::default(),
+        }
+    }
+}
+
+/// A peer on the blacklist, or recently so
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BlacklistedPeer {
+    pub pubkey: Pubkey,
+    /// Time left before the ban expires, zero if it has already expired
+    pub remaining: Duration,
+    /// Number of times the peer has been banned in a row
+    pub num_bans: u32,
+}
+
+struct PeerScore {
+    score: u64,
+    last_offense: Instant,
+    window_start: Instant,
+    window_packets: u64,
+}
+
+impl PeerScore {
+    fn new(now: Instant) -> Self {
+        Self {
+            score: 0,
+            last_offense: now,
+            window_start: now,
+            window_packets: 0,
+        }
+    }
+}
+
+fn get_or_insert_peer_score(
+    scores: &mut LruCache<Pubkey, PeerScore>,
+    pubkey: Pubkey,
+    now: Instant,
+) -> &mut PeerScore {
+    if !scores.contains(&pubkey) {
+        scores.put(pubkey, PeerScore::new(now));
+    }
+    scores.get_mut(&pubkey).unwrap()
+}
+
+struct Ban {
+    banned_until: Instant,
+    num_bans: u32,
+}
+
+pub struct GossipPeerScores {
+    config: GossipPeerScoresConfig,
+    scores: Mutex<LruCache<Pubkey, PeerScore>>,
+    // Kept after they expire, until the peer behaves for max_ban_duration, so
+    // that repeat offenders are banned for longer.
+    bans: RwLock<HashMap<Pubkey, Ban>>,
+}
+
+impl Default for GossipPeerScores {
+    fn default() -> Self {
+        Self::new(GossipPeerScoresConfig::default())
+    }
+}
+
+impl GossipPeerScores {
+    pub fn new(config: GossipPeerScoresConfig) -> Self {
+        Self {
+            config,
+            scores: Mutex::new(LruCache::new(PEER_SCORES_CAPACITY)),
+            bans: RwLock::default(),
+        }
+    }
+
+    pub fn config(&self) -> &GossipPeerScoresConfig {
+        &self.config
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.config.ban_threshold.is_some()
+    }
+
+    pub fn is_banned(&self, pubkey: &Pubkey, now: Instant) -> bool {
+        let bans = self.bans.read().unwrap();
+        bans.get(pubkey).is_some_and(|ban| now < ban.banned_until)
+    }
+
+    /// Penalizes the peer for `offense`. Returns true if the peer got banned.
+    pub fn record_offense(&self, pubkey: Pubkey, offense: PeerOffense, now: Instant) -> bool {
+        let Some(ban_threshold) = self.config.ban_threshold else {
+            return false;
+        };
+        if self.config.allowlist.contains(&pubkey) {
+            return false;
+        }
+        {
+            let mut scores = self.scores.lock().unwrap();
+            let score = get_or_insert_peer_score(&mut scores, pubkey, now);
+            if now.saturating_duration_since(score.last_offense) > SCORE_TTL {
+                score.score = 0;
+            }
+            score.score = score.score.saturating_add(offense.penalty());
+            score.last_offense = now;
+            if score.score < ban_threshold {
+                return false;
+            }
+            score.score = 0;
+        }
+        self.ban(pubkey, now);
+        true
+    }
+
+    /// Counts packets received from the peer against the bandwidth limit.
+    /// Returns true if the peer got banned.
+    pub fn record_packets(&self, pubkey: Pubkey, num_packets: u64, now: Instant) -> bool {
+        let Some(max_packets_per_second) = self.config.max_packets_per_second else {
+            return false;
+        };
+        if self.config.ban_threshold.is_none() || self.config.allowlist.contains(&pubkey) {
+            return false;
+        }
+        let exceeded = {
+            let mut scores = self.scores.lock().unwrap();
+            let score = get_or_insert_peer_score(&mut scores, pubkey, now);
+            if now.saturating_duration_since(score.window_start) >= BANDWIDTH_WINDOW {
+                score.window_start = now;
+                score.window_packets = 0;
+            }
+            let was_exceeded = score.window_packets > max_packets_per_second;
+            score.window_packets = score.window_packets.saturating_add(num_packets);
+            // Penalized once per window.
+            !was_exceeded && score.window_packets > max_packets_per_second
+        };
+        exceeded && self.record_offense(pubkey, PeerOffense::ExcessiveBandwidth, now)
+    }
+
+    fn ban(&self, pubkey: Pubkey, now: Instant) {
+        let mut bans = self.bans.write().unwrap();
+        self.remove_stale_bans(&mut bans, now);
+        let num_bans = bans
+            .get(&pubkey)
+            .map(|ban| ban.num_bans)
+            .unwrap_or_default();
+        let duration = self
+            .config
+            .ban_duration
+            .saturating_mul(1u32.checked_shl(num_bans).unwrap_or(u32::MAX))
+            .min(self.config.max_ban_duration);
+        bans.insert(
+            pubkey,
+            Ban {
+                banned_until: now + duration,
+                num_bans: num_bans.saturating_add(1),
+            },
+        );
+        warn!("Blacklisting gossip peer {pubkey} for {duration:?}");
+    }
+
+    fn remove_stale_bans(&self, bans: &mut HashMap<Pubkey, Ban>, now: Instant) {
+        bans.retain(|_, ban| {
+            now.saturating_duration_since(ban.banned_until) < self.config.max_ban_duration
+        });
+    }
+
+    /// Peers which are on the blacklist or were recently, sorted by pubkey
+    pub fn blacklist(&self, now: Instant) -> Vec<BlacklistedPeer> {
+        let mut bans = self.bans.write().unwrap();
+        self.remove_stale_bans(&mut bans, now);
+        let mut blacklist: Vec<_> = bans
+            .iter()
+            .map(|(pubkey, ban)| BlacklistedPeer {
+                pubkey: *pubkey,
+                remaining: ban.banned_until.saturating_duration_since(now),
+                num_bans: ban.num_bans,
+            })
+            .collect();
+        blacklist.sort_unstable_by_key(|peer| peer.pubkey);
+        blacklist
+    }
+
+    /// Lifts the ban of the peer and clears its score, returning false if it
+    /// was not on the blacklist.
+    pub fn unban(&self, pubkey: &Pubkey) -> bool {
+        self.scores.lock().unwrap().pop(pubkey);
+        self.bans.write().unwrap().remove(pubkey).is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> GossipPeerScoresConfig {
+        GossipPeerScoresConfig {
+            ban_threshold: Some(10),
+            ban_duration: Duration::from_secs(10),
+            max_ban_duration: Duration::from_secs(30),
+            max_packets_per_second: Some(100),
+            allowlist: HashSet::from([Pubkey::from([1; 32])]),
+        }
+    }
+
+    #[test]
+    fn test_ban_backoff() {
+        let peer_scores = GossipPeerScores::new(config());
+        let pubkey = Pubkey::new_unique();
+        let now = Instant::now();
+
+        assert!(!peer_scores.record_offense(pubkey, PeerOffense::ProtocolViolation, now));
+        assert!(!peer_scores.is_banned(&pubkey, now));
+        assert!(peer_scores.record_offense(pubkey, PeerOffense::ProtocolViolation, now));
+        assert!(peer_scores.is_banned(&pubkey, now));
+        assert!(!peer_scores.is_banned(&pubkey, now + Duration::from_secs(10)));
+
+        // Repeat offenders are banned for twice as long, up to the maximum
+        let now = now + Duration::from_secs(10);
+        assert!(peer_scores.record_offense(pubkey, PeerOffense::ExcessiveBandwidth, now));
+        assert_eq!(
+            peer_scores.blacklist(now),
+            vec![BlacklistedPeer {
+                pubkey,
+                remaining: Duration::from_secs(20),
+                num_bans: 2,
+            }]
+        );
+        let now = now + Duration::from_secs(20);
+        assert!(peer_scores.record_offense(pubkey, PeerOffense::ExcessiveBandwidth, now));
+        assert_eq!(
+            peer_scores.blacklist(now)[0].remaining,
+            Duration::from_secs(30)
+        );
+
+        // Bans are forgotten once the peer behaves for the maximum duration
+        let now = now + Duration::from_secs(60);
+        assert_eq!(peer_scores.blacklist(now), vec![]);
+    }
+
+    #[test]
+    fn test_unban() {
+        let peer_scores = GossipPeerScores::new(config());
+        let pubkey = Pubkey::new_unique();
+        let now = Instant::now();
+        assert!(!peer_scores.record_offense(pubkey, PeerOffense::ProtocolViolation, now));
+        assert!(peer_scores.record_offense(pubkey, PeerOffense::ProtocolViolation, now));
+        assert!(peer_scores.unban(&pubkey));
+        assert!(!peer_scores.is_banned(&pubkey, now));
+        assert_eq!(peer_scores.blacklist(now), vec![]);
+
+        // Unbanning also clears the score
+        assert!(!peer_scores.record_offense(pubkey, PeerOffense::ProtocolViolation, now));
+        assert!(!peer_scores.unban(&pubkey));
+        assert!(!peer_scores.record_offense(pubkey, PeerOffense::ProtocolViolation, now));
+        assert!(!peer_scores.is_banned(&pubkey, now));
+    }
+
+    #[test]
+    fn test_score_ttl() {
+        let peer_scores = GossipPeerScores::new(config());
+        let pubkey = Pubkey::new_unique();
+        let now = Instant::now();
+        assert!(!peer_scores.record_offense(pubkey, PeerOffense::ProtocolViolation, now));
+        let now = now + SCORE_TTL + Duration::from_secs(1);
+        assert!(!peer_scores.record_offense(pubkey, PeerOffense::ProtocolViolation, now));
+        assert!(!peer_scores.is_banned(&pubkey, now));
+    }
+
+    #[test]
+    fn test_allowlist_and_disabled() {
+        let peer_scores = GossipPeerScores::new(config());
+        let pubkey = Pubkey::from([1; 32]);
+        let now = Instant::now();
+        assert!(!peer_scores.record_offense(pubkey, PeerOffense::ExcessiveBandwidth, now));
+        assert!(!peer_scores.record_packets(pubkey, 1_000, now));
+        assert!(!peer_scores.is_banned(&pubkey, now));
+
+        let peer_scores = GossipPeerScores::default();
+        assert!(!peer_scores.is_enabled());
+        let pubkey = Pubkey::new_unique();
+        assert!(!peer_scores.record_offense(pubkey, PeerOffense::ExcessiveBandwidth, now));
+        assert!(!peer_scores.is_banned(&pubkey, now));
+    }
+
+    #[test]
+    fn test_record_packets() {
+        let peer_scores = GossipPeerScores::new(config());
+        let pubkey = Pubkey::new_unique();
+        let now = Instant::now();
+        assert!(!peer_scores.record_packets(pubkey, 100, now));
+        // Exceeding the limit is penalized once per window
+        assert!(peer_scores.record_packets(pubkey, 1, now));
+        assert!(peer_scores.unban(&pubkey));
+        assert!(!peer_scores.record_packets(pubkey, 100, now));
+        assert!(!peer_scores.is_banned(&pubkey, now));
+        // The count restarts with every window
+        let now = now + BANDWIDTH_WINDOW;
+        assert!(!peer_scores.record_packets(pubkey, 100, now));
+        assert!(!peer_scores.unban(&pubkey));
+    }
+}
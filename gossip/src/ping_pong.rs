@@ -236,6 +236,14 @@ impl<const N: usize> PingCache<N> {
         (check, ping)
     }
 
+    /// Returns true if the remote node has responded to a ping message which
+    /// has not expired yet, without extending its verification.
+    pub fn is_verified(&self, remote_node: &(Pubkey, SocketAddr), now: Instant) -> bool {
+        self.pongs
+            .peek(remote_node)
+            .is_some_and(|t| now.saturating_duration_since(*t) <= self.ttl)
+    }
+
     fn maybe_refresh_key<R: Rng + CryptoRng>(&mut self, rng: &mut R, now: Instant) {
         if now.checked_duration_since(self.key_refresh) > Some(KEY_REFRESH_CADENCE) {
             let hasher = SipHasher24::new_with_key(&rng.gen());
@@ -329,6 +337,7 @@ mod tests {
                 let node = (keypair.pubkey(), *socket);
                 let (check, ping) = cache.check(&mut rng, &this_node, now, node);
                 assert!(!check);
+                assert!(!cache.is_verified(&node, now));
                 assert_eq!(seen_nodes.insert(node), ping.is_some());
                 ping
             })
@@ -356,6 +365,7 @@ mod tests {
         // All nodes now have a recent pong packet.
         for (keypair, socket) in &remote_nodes {
             let node = (keypair.pubkey(), *socket);
+            assert!(cache.is_verified(&node, now));
             let (check, ping) = cache.check(&mut rng, &this_node, now, node);
             assert!(check);
             assert!(ping.is_none());
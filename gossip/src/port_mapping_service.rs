@@ -0,0 +1,214 @@
+//! The `port_mapping_service` module keeps NAT port mappings for this node's
+//! sockets alive and advertises the externally reachable addresses in gossip,
+//! so that nodes behind a home router do not need manual port forwarding.
+
+use {
+    crate::{cluster_info::ClusterInfo, contact_info},
+    solana_net_utils::nat::{
+        NatTraversalMethod, PortMapper, PortMapping, PortMappingProtocol,
+        DEFAULT_PORT_MAPPING_LEASE_DURATION,
+    },
+    std::{
+        collections::HashMap,
+        net::{IpAddr, Ipv4Addr, SocketAddr},
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        },
+        thread::{self, sleep, Builder, JoinHandle},
+        time::{Duration, Instant},
+    },
+};
+
+// How often the exit flag is checked between refreshes.
+const EXIT_CHECK_INTERVAL: Duration = Duration::from_millis(500);
+// Delay before retrying after the gateway could not be reached.
+const RETRY_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Clone, Debug)]
+pub struct PortMappingConfig {
+    pub method: NatTraversalMethod,
+    /// Lifetime requested for each mapping. Mappings are renewed at half of
+    /// the lifetime granted by the gateway.
+    pub lease_duration: Duration,
+}
+
+impl Default for PortMappingConfig {
+    fn default() -> Self {
+        Self {
+            method: NatTraversalMethod::default(),
+            lease_duration: DEFAULT_PORT_MAPPING_LEASE_DURATION,
+        }
+    }
+}
+
+pub struct PortMappingService {
+    thread_hdl: JoinHandle<()>,
+}
+
+impl PortMappingService {
+    pub fn new(
+        cluster_info: Arc<ClusterInfo>,
+        config: PortMappingConfig,
+        exit: Arc<AtomicBool>,
+    ) -> Self {
+        // Snapshot of the locally bound sockets, taken before any of them are
+        // overridden with the external addresses.
+        let node = cluster_info.my_contact_info();
+        let local_sockets: Vec<_> = node.sockets().collect();
+        // Only sockets bound on this host are mapped, and not for example a
+        // --public-tpu-address pointing to another machine.
+        let local_ip = node.gossip().map(|socket| socket.ip());
+        let thread_hdl = Builder::new()
+            .name("solNatPortMap".to_string())
+            .spawn(move || {
+                let mut mapper = None;
+                let mut mappings = HashMap::new();
+                let mut next_refresh = Instant::now();
+                while !exit.load(Ordering::Relaxed) {
+                    if Instant::now() >= next_refresh {
+                        let refresh_interval = refresh_port_mappings(
+                            &cluster_info,
+                            &config,
+                            local_ip,
+                            &local_sockets,
+                            &mut mapper,
+                            &mut mappings,
+                        );
+                        next_refresh = Instant::now() + refresh_interval;
+                    }
+                    sleep(EXIT_CHECK_INTERVAL);
+                }
+                if let Some(mapper) = mapper {
+                    for mapping in mappings.values() {
+                        if let Err(err) = mapper.remove_port_mapping(mapping) {
+                            debug!("failed to remove port mapping {mapping:?}: {err}");
+                        }
+                    }
+                }
+            })
+            .unwrap();
+        Self { thread_hdl }
+    }
+
+    pub fn join(self) -> thread::Result<()> {
+        self.thread_hdl.join()
+    }
+}
+
+// Renews the port mappings for all local sockets and updates the node's
+// contact-info with the external addresses. Returns the delay until the next
+// refresh.
+fn refresh_port_mappings(
+    cluster_info: &ClusterInfo,
+    config: &PortMappingConfig,
+    local_ip: Option<IpAddr>,
+    local_sockets: &[(/*key:*/ u8, SocketAddr)],
+    mapper: &mut Option<PortMapper>,
+    mappings: &mut HashMap<(PortMappingProtocol, /*internal port:*/ u16), PortMapping>,
+) -> Duration {
+    if mapper.is_none() {
+        match PortMapper::discover(config.method) {
+            Ok(discovered) => {
+                info!("discovered NAT gateway: {discovered:?}");
+                *mapper = Some(discovered);
+            }
+            Err(err) => {
+                warn!("unable to discover NAT gateway: {err}");
+                return RETRY_INTERVAL;
+            }
+        }
+    }
+    let port_mapper = mapper.as_ref().unwrap();
+    let external_ip = match port_mapper.external_address() {
+        Ok(external_ip) => external_ip,
+        Err(err) => {
+            warn!("unable to query external address from NAT gateway: {err}");
+            // The gateway may have gone away, rediscover on the next attempt.
+            *mapper = None;
+            return RETRY_INTERVAL;
+        }
+    };
+    if !is_routable(&external_ip) {
+        warn!("NAT gateway external address {external_ip} is not publicly routable");
+        return RETRY_INTERVAL;
+    }
+    let mut num_failed = 0;
+    let mut sockets = Vec::with_capacity(local_sockets.len());
+    for &(key, socket) in local_sockets {
+        if local_ip.is_some_and(|local_ip| local_ip != socket.ip()) {
+            continue;
+        }
+        let protocols: &[PortMappingProtocol] = if contact_info::is_tcp_socket_key(key) {
+            &[PortMappingProtocol::Tcp, PortMappingProtocol::Udp]
+        } else {
+            &[PortMappingProtocol::Udp]
+        };
+        let mut external_port = None;
+        for &protocol in protocols {
+            match port_mapper.add_port_mapping(protocol, socket.port(), config.lease_duration) {
+                Ok(mapping) => {
+                    if external_port.is_some_and(|port| port != mapping.external_port) {
+                        warn!(
+                            "NAT gateway mapped {protocol} port {} to {}, advertising {:?}",
+                            socket.port(),
+                            mapping.external_port,
+                            external_port,
+                        );
+                    }
+                    external_port.get_or_insert(mapping.external_port);
+                    mappings.insert((protocol, socket.port()), mapping);
+                }
+                Err(err) => {
+                    warn!("failed to map {protocol} port {}: {err}", socket.port());
+                    num_failed += 1;
+                }
+            }
+        }
+        if let Some(external_port) = external_port {
+            sockets.push((key, SocketAddr::new(IpAddr::V4(external_ip), external_port)));
+        }
+    }
+    let num_mapped = sockets.len();
+    if let Err(err) = cluster_info.set_my_sockets(sockets) {
+        error!("failed to advertise NAT port mappings: {err:?}");
+    }
+    datapoint_info!(
+        "port_mapping_service",
+        ("num_mapped", num_mapped, i64),
+        ("num_failed", num_failed, i64),
+    );
+    // Renew at half of the shortest lease granted by the gateway.
+    mappings
+        .values()
+        .map(|mapping| mapping.lease_duration)
+        .min()
+        .filter(|lease_duration| !lease_duration.is_zero())
+        .map(|lease_duration| lease_duration / 2)
+        .unwrap_or(RETRY_INTERVAL)
+}
+
+// Returns false for addresses which indicate that the gateway is itself behind
+// another NAT, in which case port mappings are not reachable from outside.
+fn is_routable(ip: &Ipv4Addr) -> bool {
+    let [a, b, ..] = ip.octets();
+    // 100.64.0.0/10 is reserved for carrier-grade NAT.
+    let is_shared = a == 100 && (b & 0b1100_0000) == 0b0100_0000;
+    !(ip.is_private() || ip.is_loopback() || ip.is_unspecified() || is_shared)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_routable() {
+        assert!(is_routable(&Ipv4Addr::new(203, 0, 113, 7)));
+        assert!(!is_routable(&Ipv4Addr::new(192, 168, 1, 1)));
+        assert!(!is_routable(&Ipv4Addr::new(10, 0, 0, 1)));
+        assert!(!is_routable(&Ipv4Addr::new(100, 64, 0, 1)));
+        assert!(!is_routable(&Ipv4Addr::new(100, 127, 255, 254)));
+        assert!(is_routable(&Ipv4Addr::new(100, 128, 0, 1)));
+        assert!(!is_routable(&Ipv4Addr::UNSPECIFIED));
+    }
+}
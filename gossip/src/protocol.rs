@@ -90,6 +90,18 @@ impl Protocol {
             .unwrap()
     }
 
+    /// Returns the node the message claims to be from, which is not
+    /// authenticated by the signatures of push messages and pull responses.
+    pub(crate) fn sender(&self) -> Pubkey {
+        match self {
+            Self::PullRequest(_, caller) => caller.pubkey(),
+            Self::PullResponse(from, _) | Self::PushMessage(from, _) => *from,
+            Self::PruneMessage(_, data) => data.pubkey,
+            Self::PingMessage(ping) => ping.pubkey(),
+            Self::PongMessage(pong) => pong.pubkey(),
+        }
+    }
+
     // Returns true if all signatures verify.
     #[must_use]
     pub(crate) fn par_verify(&self) -> bool {
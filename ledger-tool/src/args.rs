@@ -3,7 +3,7 @@ use {
     clap::{value_t, value_t_or_exit, values_t, values_t_or_exit, Arg, ArgMatches},
     solana_accounts_db::{
         accounts_db::{AccountsDb, AccountsDbConfig, CreateAncientStorage},
-        accounts_file::StorageAccess,
+        accounts_file::{AccountsFileProvider, StorageAccess},
         accounts_index::{AccountsIndexConfig, IndexLimitMb, ScanFilter},
         utils::create_and_canonicalize_directories,
     },
@@ -126,6 +126,16 @@ pub fn accounts_db_args<'a, 'b>() -> Box<[Arg<'a, 'b>]> {
             .takes_value(true)
             .possible_values(&["mmap", "file"])
             .help("Access account storages using this method"),
+        Arg::with_name("accounts_db_storage_format")
+            .long("accounts-db-storage-format")
+            .value_name("FORMAT")
+            .takes_value(true)
+            .possible_values(AccountsFileProvider::cli_names())
+            .help(
+                "Write new account storage files in this format. Existing storages in either \
+                 format are always readable.",
+            )
+            .hidden(hidden_unless_forced()),
         Arg::with_name("accounts_db_experimental_accumulator_hash")
             .long("accounts-db-experimental-accumulator-hash")
             .help("Enables the experimental accumulator hash")
@@ -343,6 +353,12 @@ pub fn get_accounts_db_config(
             }
         })
         .unwrap_or_default();
+    let accounts_file_provider = value_t!(
+        arg_matches,
+        "accounts_db_storage_format",
+        AccountsFileProvider
+    )
+    .unwrap_or_default();
 
     let scan_filter_for_shrinking = arg_matches
         .value_of("accounts_db_scan_filter_for_shrinking")
@@ -386,6 +402,7 @@ pub fn get_accounts_db_config(
             .is_present("accounts_db_test_skip_rewrites"),
         create_ancient_storage,
         storage_access,
+        accounts_file_provider,
         scan_filter_for_shrinking,
         enable_experimental_accumulator_hash: arg_matches
             .is_present("accounts_db_experimental_accumulator_hash"),
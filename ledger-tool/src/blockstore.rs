@@ -510,6 +510,17 @@ pub fn blockstore_subcommands<'a, 'b>(hidden: bool) -> Vec<App<'a, 'b>> {
                     .required(false)
                     .takes_value(false)
                     .help("Limit purging to dead slots only"),
+            )
+            .arg(
+                Arg::with_name("dry_run")
+                    .long("dry-run")
+                    .required(false)
+                    .takes_value(false)
+                    .conflicts_with("dead_slots_only")
+                    .help(
+                        "Report how many entries and bytes would be deleted from each column \
+                         family, without deleting anything",
+                    ),
             ),
         SubCommand::with_name("remove-dead-slot")
             .about("Remove the dead flag for a slot")
@@ -857,13 +868,15 @@ fn do_blockstore_process_command(ledger_path: &Path, matches: &ArgMatches<'_>) -
                 warn!("--no-compaction is deprecated and is now the default behavior.");
             }
             let dead_slots_only = arg_matches.is_present("dead_slots_only");
+            let dry_run = arg_matches.is_present("dry_run");
             let batch_size = value_t_or_exit!(arg_matches, "batch_size", usize);
 
-            let blockstore = crate::open_blockstore(
-                &ledger_path,
-                arg_matches,
-                AccessType::PrimaryForMaintenance,
-            );
+            let access_type = if dry_run {
+                AccessType::Secondary
+            } else {
+                AccessType::PrimaryForMaintenance
+            };
+            let blockstore = crate::open_blockstore(&ledger_path, arg_matches, access_type);
 
             let Some(highest_slot) = blockstore.highest_slot()? else {
                 return Err(LedgerToolError::BadArgument(
@@ -883,6 +896,23 @@ fn do_blockstore_process_command(ledger_path: &Path, matches: &ArgMatches<'_>) -
                 )));
             }
 
+            if dry_run {
+                let estimate = blockstore.estimate_purge(start_slot, end_slot)?;
+                println!("Purging slots {start_slot} to {end_slot} would delete:");
+                for column in &estimate.columns {
+                    println!(
+                        "  {:<32} {:>12} entries {:>16} bytes",
+                        column.name, column.num_entries, column.num_bytes
+                    );
+                }
+                println!(
+                    "Total: {} shreds, {} bytes before compression",
+                    estimate.num_shreds(),
+                    estimate.num_bytes()
+                );
+                return Ok(());
+            }
+
             info!(
                 "Purging data from slots {} to {} ({} slots) (do compaction: {}) \
                 (dead slot only: {})",
@@ -320,12 +320,14 @@ pub fn load_and_process_ledger(
         );
 
         let (block_meta_sender, block_meta_receiver) = unbounded();
-        // Nothing else will be interacting with max_complete_rewards_slot
+        // Nothing else will be interacting with max_complete_rewards_slot or
+        // the epoch stake rewards cache
         let max_complete_rewards_slot = Arc::default();
         let block_meta_service = BlockMetaService::new(
             block_meta_receiver,
             write_blockstore,
             max_complete_rewards_slot,
+            Arc::default(),
             exit.clone(),
         );
 
@@ -404,7 +406,7 @@ pub fn load_and_process_ledger(
     let (snapshot_request_sender, snapshot_request_receiver) = crossbeam_channel::unbounded();
     let accounts_background_request_sender = AbsRequestSender::new(snapshot_request_sender.clone());
     let snapshot_request_handler = SnapshotRequestHandler {
-        snapshot_config: SnapshotConfig::new_load_only(),
+        snapshot_config: Arc::new(RwLock::new(SnapshotConfig::new_load_only())),
         snapshot_request_sender,
         snapshot_request_receiver,
         accounts_package_sender,
@@ -46,6 +46,7 @@ use {
         blockstore_processor::{
             ProcessSlotCallback, TransactionStatusMessage, TransactionStatusSender,
         },
+        leader_schedule_utils::{self, LeaderScheduleByIdentity},
     },
     solana_measure::{measure::Measure, measure_time},
     solana_runtime::{
@@ -1611,6 +1612,36 @@ fn main() {
                         .help("Output file in the csv format"),
                 ),
         )
+        .subcommand(
+            SubCommand::with_name("leader-schedule")
+                .about(
+                    "Print the leader schedule of an epoch, and the inputs it is derived from, \
+                     as of the working bank",
+                )
+                .arg(&load_genesis_config_arg)
+                .args(&accounts_db_config_args)
+                .args(&snapshot_config_args)
+                .arg(&halt_at_slot_arg)
+                .arg(&hard_forks_arg)
+                .arg(
+                    Arg::with_name("epoch")
+                        .long("epoch")
+                        .takes_value(true)
+                        .value_name("EPOCH")
+                        .validator(is_parsable::<Epoch>)
+                        .help("Epoch to print the schedule of [default: the working bank's epoch]"),
+                )
+                .arg(
+                    Arg::with_name("verify")
+                        .long("verify")
+                        .takes_value(true)
+                        .value_name("FILE")
+                        .help(
+                            "Instead of printing the schedule, check it against the one in FILE, \
+                             in the JSON format returned by the getLeaderSchedule RPC method",
+                        ),
+                ),
+        )
         .subcommand(
             SubCommand::with_name("compute-slot-cost")
                 .about(
@@ -3106,6 +3137,104 @@ fn main() {
                         println!("Capitalization: {}", Sol(bank.capitalization()));
                     }
                 }
+                ("leader-schedule", Some(arg_matches)) => {
+                    let mut process_options = parse_process_options(&ledger_path, arg_matches);
+                    // Respect a user-set --halt-at-slot; otherwise, set Some(0) to avoid
+                    // processing any additional banks and just use the snapshot bank
+                    if process_options.halt_at_slot.is_none() {
+                        process_options.halt_at_slot = Some(0);
+                    }
+                    let genesis_config = open_genesis_config_by(&ledger_path, arg_matches);
+                    let blockstore = open_blockstore(
+                        &ledger_path,
+                        arg_matches,
+                        get_access_type(&process_options),
+                    );
+                    let LoadAndProcessLedgerOutput { bank_forks, .. } =
+                        load_and_process_ledger_or_exit(
+                            arg_matches,
+                            &genesis_config,
+                            Arc::new(blockstore),
+                            process_options,
+                            None,
+                        );
+                    let bank = bank_forks.read().unwrap().working_bank();
+                    let epoch = value_t!(arg_matches, "epoch", Epoch).unwrap_or(bank.epoch());
+                    let inputs = leader_schedule_utils::leader_schedule_inputs(epoch, &bank)
+                        .unwrap_or_else(|| {
+                            eprintln!(
+                                "Error: Bank at slot {} has no stakes for epoch {epoch}",
+                                bank.slot()
+                            );
+                            exit(1);
+                        });
+
+                    if let Some(path) = arg_matches.value_of("verify") {
+                        let file = File::open(path).unwrap_or_else(|err| {
+                            eprintln!("Error: Unable to open {path}: {err}");
+                            exit(1);
+                        });
+                        let by_identity: LeaderScheduleByIdentity = serde_json::from_reader(file)
+                            .unwrap_or_else(|err| {
+                                eprintln!("Error: Unable to parse {path}: {err}");
+                                exit(1);
+                            });
+                        let mut slot_leaders =
+                            vec![Pubkey::default(); inputs.slots_in_epoch as usize];
+                        for (identity, slot_indices) in by_identity {
+                            let identity = Pubkey::from_str(&identity).unwrap_or_else(|err| {
+                                eprintln!("Error: Invalid identity {identity}: {err}");
+                                exit(1);
+                            });
+                            for slot_index in slot_indices {
+                                let Some(slot_leader) = slot_leaders.get_mut(slot_index) else {
+                                    eprintln!(
+                                        "Error: Leader schedule for epoch {epoch} differs at slot \
+                                         index {slot_index}, beyond the {} slots of the epoch",
+                                        inputs.slots_in_epoch
+                                    );
+                                    exit(1);
+                                };
+                                *slot_leader = identity;
+                            }
+                        }
+                        match inputs.find_mismatch(&slot_leaders) {
+                            None => println!("Leader schedule for epoch {epoch} matches"),
+                            Some(slot_index) => {
+                                eprintln!(
+                                    "Error: Leader schedule for epoch {epoch} differs at slot \
+                                     index {slot_index}"
+                                );
+                                exit(1);
+                            }
+                        }
+                    } else {
+                        let mut staked_nodes: Vec<_> = inputs.staked_nodes.iter().collect();
+                        staked_nodes.sort_by(|(a_pubkey, a_stake), (b_pubkey, b_stake)| {
+                            b_stake.cmp(a_stake).then(a_pubkey.cmp(b_pubkey))
+                        });
+                        println!("Epoch: {epoch}");
+                        let seed: String =
+                            inputs.seed().iter().map(|b| format!("{b:02x}")).collect();
+                        println!("Seed: {seed}");
+                        println!("Slots in epoch: {}", inputs.slots_in_epoch);
+                        println!("Consecutive leader slots: {}", inputs.repeat);
+                        println!("Staked nodes:");
+                        for (pubkey, stake) in staked_nodes {
+                            println!("  {pubkey:<44} {stake}");
+                        }
+                        println!("Leader schedule:");
+                        let first_slot = bank.epoch_schedule().get_first_slot_in_epoch(epoch);
+                        for (slot_index, leader) in inputs
+                            .leader_schedule()
+                            .get_slot_leaders()
+                            .iter()
+                            .enumerate()
+                        {
+                            println!("  {:<15} {leader}", first_slot + slot_index as u64);
+                        }
+                    }
+                }
                 ("compute-slot-cost", Some(arg_matches)) => {
                     let blockstore =
                         open_blockstore(&ledger_path, arg_matches, AccessType::Secondary);
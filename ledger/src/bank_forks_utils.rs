@@ -277,7 +277,7 @@ fn bank_forks_from_snapshot(
         }
     };
 
-    let bank = if let Some(fastboot_snapshot) = fastboot_snapshot {
+    let mut bank = if let Some(fastboot_snapshot) = fastboot_snapshot {
         let (bank, _) = snapshot_bank_utils::bank_from_snapshot_dir(
             &account_paths,
             &fastboot_snapshot,
@@ -367,5 +367,7 @@ fn bank_forks_from_snapshot(
         incremental: incremental_snapshot_hash,
     };
 
+    bank.set_config(&process_options.bank_config);
+
     Ok((BankForks::new_rw_arc(bank), starting_snapshot_hashes))
 }
@@ -253,11 +253,14 @@ pub struct Blockstore {
     address_signatures_cf: LedgerColumn<cf::AddressSignatures>,
     bank_hash_cf: LedgerColumn<cf::BankHash>,
     block_height_cf: LedgerColumn<cf::BlockHeight>,
+    block_height_index_cf: LedgerColumn<cf::BlockHeightIndex>,
     blocktime_cf: LedgerColumn<cf::Blocktime>,
+    blocktime_index_cf: LedgerColumn<cf::BlocktimeIndex>,
     code_shred_cf: LedgerColumn<cf::ShredCode>,
     data_shred_cf: LedgerColumn<cf::ShredData>,
     dead_slots_cf: LedgerColumn<cf::DeadSlots>,
     duplicate_slots_cf: LedgerColumn<cf::DuplicateSlots>,
+    duplicate_votes_cf: LedgerColumn<cf::DuplicateVotes>,
     erasure_meta_cf: LedgerColumn<cf::ErasureMeta>,
     index_cf: LedgerColumn<cf::Index>,
     merkle_root_meta_cf: LedgerColumn<cf::MerkleRootMeta>,
@@ -402,11 +405,14 @@ impl Blockstore {
         let address_signatures_cf = db.column();
         let bank_hash_cf = db.column();
         let block_height_cf = db.column();
+        let block_height_index_cf = db.column();
         let blocktime_cf = db.column();
+        let blocktime_index_cf = db.column();
         let code_shred_cf = db.column();
         let data_shred_cf = db.column();
         let dead_slots_cf = db.column();
         let duplicate_slots_cf = db.column();
+        let duplicate_votes_cf = db.column();
         let erasure_meta_cf = db.column();
         let index_cf = db.column();
         let merkle_root_meta_cf = db.column();
@@ -437,11 +443,14 @@ impl Blockstore {
             address_signatures_cf,
             bank_hash_cf,
             block_height_cf,
+            block_height_index_cf,
             blocktime_cf,
+            blocktime_index_cf,
             code_shred_cf,
             data_shred_cf,
             dead_slots_cf,
             duplicate_slots_cf,
+            duplicate_votes_cf,
             erasure_meta_cf,
             index_cf,
             merkle_root_meta_cf,
@@ -875,6 +884,9 @@ impl Blockstore {
         self.bank_hash_cf.submit_rocksdb_cf_metrics();
         self.optimistic_slots_cf.submit_rocksdb_cf_metrics();
         self.merkle_root_meta_cf.submit_rocksdb_cf_metrics();
+        self.block_height_index_cf.submit_rocksdb_cf_metrics();
+        self.blocktime_index_cf.submit_rocksdb_cf_metrics();
+        self.duplicate_votes_cf.submit_rocksdb_cf_metrics();
     }
 
     /// Report the accumulated RPC API metrics
@@ -2600,7 +2612,36 @@ impl Blockstore {
     }
 
     pub fn set_block_time(&self, slot: Slot, timestamp: UnixTimestamp) -> Result<()> {
-        self.blocktime_cf.put(slot, &timestamp)
+        let mut write_batch = self.get_write_batch()?;
+        if let Some(previous_timestamp) = self.blocktime_cf.get(slot)? {
+            self.blocktime_index_cf
+                .delete_in_batch(&mut write_batch, (previous_timestamp, slot))?;
+        }
+        self.blocktime_cf
+            .put_in_batch(&mut write_batch, slot, &timestamp)?;
+        self.blocktime_index_cf
+            .put_in_batch(&mut write_batch, (timestamp, slot), &true)?;
+        self.write_batch(write_batch)
+    }
+
+    /// Returns the rooted slot with the earliest block time at or after
+    /// `timestamp`, using the blocktime index rather than scanning slots.
+    ///
+    /// Only slots whose block time has been cached (see
+    /// [`Blockstore::set_block_time`]) are considered.
+    pub fn get_first_rooted_slot_at_or_after_time(
+        &self,
+        timestamp: UnixTimestamp,
+    ) -> Result<Option<Slot>> {
+        let slot = self
+            .blocktime_index_cf
+            .iter(IteratorMode::From(
+                (timestamp, 0),
+                IteratorDirection::Forward,
+            ))?
+            .map(|((_timestamp, slot), _)| slot)
+            .find(|&slot| self.is_root(slot));
+        Ok(slot)
     }
 
     pub fn get_block_height(&self, slot: Slot) -> Result<Option<u64>> {
@@ -2613,7 +2654,35 @@ impl Blockstore {
     }
 
     pub fn set_block_height(&self, slot: Slot, block_height: u64) -> Result<()> {
-        self.block_height_cf.put(slot, &block_height)
+        let mut write_batch = self.get_write_batch()?;
+        if let Some(previous_block_height) = self.block_height_cf.get(slot)? {
+            self.block_height_index_cf
+                .delete_in_batch(&mut write_batch, (previous_block_height, slot))?;
+        }
+        self.block_height_cf
+            .put_in_batch(&mut write_batch, slot, &block_height)?;
+        self.block_height_index_cf
+            .put_in_batch(&mut write_batch, (block_height, slot), &true)?;
+        self.write_batch(write_batch)
+    }
+
+    /// Returns the rooted slot at `block_height`, using the block height index
+    /// rather than scanning slots.
+    ///
+    /// Only slots whose block height has been cached (see
+    /// [`Blockstore::set_block_height`]) are considered.
+    pub fn get_rooted_slot_by_block_height(&self, block_height: u64) -> Result<Option<Slot>> {
+        let slot = self
+            .block_height_index_cf
+            .iter(IteratorMode::From(
+                (block_height, 0),
+                IteratorDirection::Forward,
+            ))?
+            .map(|(index, _)| index)
+            .take_while(|&(height, _slot)| height == block_height)
+            .map(|(_height, slot)| slot)
+            .find(|&slot| self.is_root(slot));
+        Ok(slot)
     }
 
     /// The first complete block that is available in the Blockstore ledger
@@ -3965,6 +4034,28 @@ impl Blockstore {
             .map(|versioned| versioned.frozen_hash())
     }
 
+    /// Returns up to `limit` rooted slots in `start_slot..=end_slot`, along with their bank
+    /// hashes, i.e. the entries the `SlotHashes` sysvar held for them before they expired.
+    /// Rooted slots whose bank hash is unknown, as when they were rooted from a snapshot, are
+    /// skipped.
+    pub fn get_rooted_slot_hashes(
+        &self,
+        start_slot: Slot,
+        end_slot: Slot,
+        limit: usize,
+    ) -> Result<Vec<(Slot, Hash)>> {
+        let mut slot_hashes = vec![];
+        for slot in self.rooted_slot_iterator(start_slot)? {
+            if slot > end_slot || slot_hashes.len() >= limit {
+                break;
+            }
+            if let Some(bank_hash) = self.bank_hash_cf.get(slot)? {
+                slot_hashes.push((slot, bank_hash.frozen_hash()));
+            }
+        }
+        Ok(slot_hashes)
+    }
+
     pub fn is_duplicate_confirmed(&self, slot: Slot) -> bool {
         self.bank_hash_cf
             .get(slot)
@@ -4113,6 +4204,47 @@ impl Blockstore {
         (other != **shred.payload()).then_some(other)
     }
 
+    /// Stores evidence of `vote_pubkey` voting for two different bank hashes
+    /// of `slot`. Only the first proof for a vote account and slot is kept.
+    pub fn store_duplicate_vote(
+        &self,
+        slot: Slot,
+        vote_pubkey: Pubkey,
+        proof: &DuplicateVoteProof,
+    ) -> Result<()> {
+        if self.has_duplicate_vote(slot, vote_pubkey) {
+            return Ok(());
+        }
+        self.duplicate_votes_cf.put((slot, vote_pubkey), proof)
+    }
+
+    pub fn get_duplicate_vote(
+        &self,
+        slot: Slot,
+        vote_pubkey: Pubkey,
+    ) -> Option<DuplicateVoteProof> {
+        self.duplicate_votes_cf
+            .get((slot, vote_pubkey))
+            .expect("fetch from DuplicateVotes column family failed")
+    }
+
+    pub fn has_duplicate_vote(&self, slot: Slot, vote_pubkey: Pubkey) -> bool {
+        self.get_duplicate_vote(slot, vote_pubkey).is_some()
+    }
+
+    /// Returns the duplicate vote proofs of slots `slot` and later, in slot order
+    pub fn duplicate_votes_iterator(
+        &self,
+        slot: Slot,
+    ) -> Result<impl Iterator<Item = ((Slot, Pubkey), DuplicateVoteProof)> + '_> {
+        let duplicate_votes_iterator = self.duplicate_votes_cf.iter(IteratorMode::From(
+            (slot, Pubkey::default()),
+            IteratorDirection::Forward,
+        ))?;
+        Ok(duplicate_votes_iterator
+            .map(|(index, proof_bytes)| (index, deserialize(&proof_bytes).unwrap())))
+    }
+
     pub fn has_duplicate_shreds_in_slot(&self, slot: Slot) -> bool {
         self.duplicate_slots_cf
             .get(slot)
@@ -8062,6 +8194,48 @@ pub mod tests {
         }
     }
 
+    #[test]
+    fn test_get_rooted_slot_hashes() {
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let blockstore = Blockstore::open(ledger_path.path()).unwrap();
+        let roots = [2, 4, 7, 12, 15];
+        blockstore.set_roots(roots.iter()).unwrap();
+        let bank_hashes: HashMap<_, _> = [1, 2, 3, 7, 12, 15]
+            .into_iter()
+            .map(|slot| (slot, Hash::new_unique()))
+            .collect();
+        for (slot, bank_hash) in &bank_hashes {
+            blockstore.insert_bank_hash(*slot, *bank_hash, false);
+        }
+
+        // Slots 1 and 3 are not rooted, and the hash of root 4 is unknown.
+        let expected: Vec<_> = [2, 7, 12, 15]
+            .into_iter()
+            .map(|slot| (slot, bank_hashes[&slot]))
+            .collect();
+        assert_eq!(
+            blockstore.get_rooted_slot_hashes(0, 16, 10).unwrap(),
+            expected
+        );
+        assert_eq!(
+            blockstore.get_rooted_slot_hashes(3, 16, 2).unwrap(),
+            expected[1..3]
+        );
+        // The scan stops at the end slot.
+        assert_eq!(
+            blockstore.get_rooted_slot_hashes(0, 11, 10).unwrap(),
+            expected[..2]
+        );
+        assert_eq!(
+            blockstore.get_rooted_slot_hashes(12, 16, 0).unwrap(),
+            vec![]
+        );
+        assert_eq!(
+            blockstore.get_rooted_slot_hashes(16, 20, 10).unwrap(),
+            vec![]
+        );
+    }
+
     #[test]
     fn test_is_skipped() {
         let ledger_path = get_tmp_ledger_path_auto_delete!();
@@ -8501,6 +8675,93 @@ pub mod tests {
         assert_eq!(complete_block, expected_complete_block);
     }
 
+    #[test]
+    fn test_get_rooted_slot_by_block_height_and_time() {
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let blockstore = Blockstore::open(ledger_path.path()).unwrap();
+
+        // Slots 1 and 2 are competing forks at the same block height.
+        for (slot, block_height, timestamp) in [(1, 1, 100), (2, 1, 105), (3, 2, 110)] {
+            blockstore.set_block_height(slot, block_height).unwrap();
+            blockstore.set_block_time(slot, timestamp).unwrap();
+        }
+        blockstore.set_roots([2, 3].iter()).unwrap();
+
+        assert_eq!(
+            blockstore.get_rooted_slot_by_block_height(1).unwrap(),
+            Some(2)
+        );
+        assert_eq!(
+            blockstore.get_rooted_slot_by_block_height(2).unwrap(),
+            Some(3)
+        );
+        assert_eq!(blockstore.get_rooted_slot_by_block_height(3).unwrap(), None);
+        assert_eq!(
+            blockstore
+                .get_first_rooted_slot_at_or_after_time(0)
+                .unwrap(),
+            Some(2)
+        );
+        assert_eq!(
+            blockstore
+                .get_first_rooted_slot_at_or_after_time(105)
+                .unwrap(),
+            Some(2)
+        );
+        assert_eq!(
+            blockstore
+                .get_first_rooted_slot_at_or_after_time(106)
+                .unwrap(),
+            Some(3)
+        );
+        assert_eq!(
+            blockstore
+                .get_first_rooted_slot_at_or_after_time(111)
+                .unwrap(),
+            None
+        );
+
+        // Negative timestamps sort before positive ones.
+        assert!(cf::BlocktimeIndex::key(&(-1, 0)) < cf::BlocktimeIndex::key(&(0, 0)));
+        assert_eq!(
+            cf::BlocktimeIndex::index(&cf::BlocktimeIndex::key(&(-1, 7))),
+            (-1, 7)
+        );
+        assert_eq!(
+            blockstore
+                .get_first_rooted_slot_at_or_after_time(-1)
+                .unwrap(),
+            Some(2)
+        );
+
+        // Overwriting a value moves its index entry.
+        blockstore.set_block_time(3, 120).unwrap();
+        assert_eq!(blockstore.blocktime_index_cf.get((110, 3)).unwrap(), None);
+        assert_eq!(
+            blockstore
+                .get_first_rooted_slot_at_or_after_time(111)
+                .unwrap(),
+            Some(3)
+        );
+
+        // An exact purge removes the index entries of the purged slots.
+        blockstore.run_purge(0, 2, PurgeType::Exact).unwrap();
+        assert_eq!(blockstore.block_height_index_cf.get((1, 1)).unwrap(), None);
+        assert_eq!(blockstore.block_height_index_cf.get((1, 2)).unwrap(), None);
+        assert_eq!(blockstore.blocktime_index_cf.get((105, 2)).unwrap(), None);
+        assert_eq!(blockstore.get_rooted_slot_by_block_height(1).unwrap(), None);
+        assert_eq!(
+            blockstore.get_rooted_slot_by_block_height(2).unwrap(),
+            Some(3)
+        );
+        assert_eq!(
+            blockstore
+                .get_first_rooted_slot_at_or_after_time(0)
+                .unwrap(),
+            Some(3)
+        );
+    }
+
     #[test]
     fn test_persist_transaction_status() {
         let ledger_path = get_tmp_ledger_path_auto_delete!();
@@ -10600,6 +10861,68 @@ pub mod tests {
         assert_eq!(duplicate_proof.shred2, *duplicate_shred.payload());
     }
 
+    #[test]
+    fn test_duplicate_votes() {
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let blockstore = Blockstore::open(ledger_path.path()).unwrap();
+
+        let vote_pubkey = Pubkey::new_unique();
+        let make_proof = |hash1, hash2| DuplicateVoteProof {
+            vote1: DuplicateVote {
+                hash: hash1,
+                signature: Signature::new_unique(),
+                transaction: None,
+            },
+            vote2: DuplicateVote {
+                hash: hash2,
+                signature: Signature::new_unique(),
+                transaction: Some(Transaction::default()),
+            },
+        };
+        let proof = make_proof(Hash::new_unique(), Hash::new_unique());
+
+        assert!(!blockstore.has_duplicate_vote(5, vote_pubkey));
+        blockstore
+            .store_duplicate_vote(5, vote_pubkey, &proof)
+            .unwrap();
+        assert!(blockstore.has_duplicate_vote(5, vote_pubkey));
+        assert!(!blockstore.has_duplicate_vote(6, vote_pubkey));
+        assert!(!blockstore.has_duplicate_vote(5, Pubkey::new_unique()));
+
+        // Only the first proof for a vote account and slot is kept
+        blockstore
+            .store_duplicate_vote(
+                5,
+                vote_pubkey,
+                &make_proof(Hash::default(), Hash::default()),
+            )
+            .unwrap();
+        assert_eq!(
+            blockstore.get_duplicate_vote(5, vote_pubkey),
+            Some(proof.clone())
+        );
+
+        let other_proof = make_proof(Hash::new_unique(), Hash::new_unique());
+        blockstore
+            .store_duplicate_vote(7, vote_pubkey, &other_proof)
+            .unwrap();
+        let duplicate_votes: Vec<_> = blockstore.duplicate_votes_iterator(0).unwrap().collect();
+        assert_eq!(
+            duplicate_votes,
+            vec![
+                ((5, vote_pubkey), proof),
+                ((7, vote_pubkey), other_proof.clone())
+            ]
+        );
+        let duplicate_votes: Vec<_> = blockstore.duplicate_votes_iterator(6).unwrap().collect();
+        assert_eq!(duplicate_votes, vec![((7, vote_pubkey), other_proof)]);
+
+        // Duplicate votes are purged with their slot
+        blockstore.purge_slots(0, 5, PurgeType::Exact);
+        assert!(!blockstore.has_duplicate_vote(5, vote_pubkey));
+        assert!(blockstore.has_duplicate_vote(7, vote_pubkey));
+    }
+
     #[test]
     fn test_clear_unconfirmed_slot() {
         let ledger_path = get_tmp_ledger_path_auto_delete!();
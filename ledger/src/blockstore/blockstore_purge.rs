@@ -1,7 +1,12 @@
 use {
     super::*,
+    crate::blockstore::column::{ColumnName, TypedColumn},
     solana_sdk::message::AccountKeys,
-    std::{cmp::max, time::Instant},
+    std::{
+        cmp::max,
+        thread::{self, JoinHandle},
+        time::Instant,
+    },
 };
 
 #[derive(Default)]
@@ -22,6 +27,83 @@ pub enum PurgeType {
     CompactionFilter,
 }
 
+/// What purging a range of slots would delete from one column family
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PurgeColumnEstimate {
+    pub name: &'static str,
+    pub num_entries: u64,
+    /// The size of the keys and values, before compression
+    pub num_bytes: u64,
+}
+
+/// What purging a range of slots would delete, see `Blockstore::estimate_purge()`
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PurgeEstimate {
+    pub columns: Vec<PurgeColumnEstimate>,
+}
+
+impl PurgeEstimate {
+    pub fn num_shreds(&self) -> u64 {
+        self.columns
+            .iter()
+            .filter(|column| {
+                column.name == cf::ShredData::NAME || column.name == cf::ShredCode::NAME
+            })
+            .map(|column| column.num_entries)
+            .sum()
+    }
+
+    pub fn num_bytes(&self) -> u64 {
+        self.columns.iter().map(|column| column.num_bytes).sum()
+    }
+}
+
+/// The progress of a purge started by `Blockstore::purge_slots_in_background()`
+#[derive(Debug)]
+pub struct PurgeProgress {
+    from_slot: Slot,
+    to_slot: Slot,
+    /// The lowest slot not purged yet
+    next_slot: AtomicU64,
+    cancelled: AtomicBool,
+}
+
+impl PurgeProgress {
+    pub fn num_slots(&self) -> u64 {
+        self.to_slot
+            .saturating_sub(self.from_slot)
+            .saturating_add(1)
+    }
+
+    pub fn num_slots_purged(&self) -> u64 {
+        self.next_slot.load(Ordering::Relaxed) - self.from_slot
+    }
+
+    /// Stops the purge once the batch being purged is done
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+}
+
+pub struct PurgeHandle {
+    progress: Arc<PurgeProgress>,
+    thread_hdl: JoinHandle<Result<()>>,
+}
+
+impl PurgeHandle {
+    pub fn progress(&self) -> &Arc<PurgeProgress> {
+        &self.progress
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.thread_hdl.is_finished()
+    }
+
+    pub fn join(self) -> Result<()> {
+        self.thread_hdl.join().expect("blockstore purge thread")
+    }
+}
+
 impl Blockstore {
     /// Performs cleanup based on the specified deletion range.  After this
     /// function call, entries within \[`from_slot`, `to_slot`\] will become
@@ -63,6 +145,107 @@ impl Blockstore {
         }
     }
 
+    /// Reports what `purge_slots()` would delete from each column family keyed by slot within
+    /// \[`from_slot`, `to_slot`\], without deleting anything.
+    ///
+    /// The transaction status and address signature column families are not included: they
+    /// are not keyed by slot, and are only purged right away by `PurgeType::Exact`.
+    pub fn estimate_purge(&self, from_slot: Slot, to_slot: Slot) -> Result<PurgeEstimate> {
+        fn estimate_column<C: Column + ColumnName>(
+            column: &LedgerColumn<C>,
+            from_slot: Slot,
+            to_slot: Slot,
+        ) -> Result<PurgeColumnEstimate> {
+            let (num_entries, num_bytes) = column.range_size(from_slot, to_slot)?;
+            Ok(PurgeColumnEstimate {
+                name: C::NAME,
+                num_entries,
+                num_bytes,
+            })
+        }
+
+        let columns = vec![
+            estimate_column(&self.meta_cf, from_slot, to_slot)?,
+            estimate_column(&self.bank_hash_cf, from_slot, to_slot)?,
+            estimate_column(&self.roots_cf, from_slot, to_slot)?,
+            estimate_column(&self.data_shred_cf, from_slot, to_slot)?,
+            estimate_column(&self.code_shred_cf, from_slot, to_slot)?,
+            estimate_column(&self.dead_slots_cf, from_slot, to_slot)?,
+            estimate_column(&self.duplicate_slots_cf, from_slot, to_slot)?,
+            estimate_column(&self.duplicate_votes_cf, from_slot, to_slot)?,
+            estimate_column(&self.erasure_meta_cf, from_slot, to_slot)?,
+            estimate_column(&self.orphans_cf, from_slot, to_slot)?,
+            estimate_column(&self.index_cf, from_slot, to_slot)?,
+            estimate_column(&self.rewards_cf, from_slot, to_slot)?,
+            estimate_column(&self.blocktime_cf, from_slot, to_slot)?,
+            estimate_column(&self.perf_samples_cf, from_slot, to_slot)?,
+            estimate_column(&self.block_height_cf, from_slot, to_slot)?,
+            estimate_column(&self.optimistic_slots_cf, from_slot, to_slot)?,
+            estimate_column(&self.merkle_root_meta_cf, from_slot, to_slot)?,
+        ];
+        Ok(PurgeEstimate { columns })
+    }
+
+    /// Purges \[`from_slot`, `to_slot`\] like `purge_slots()`, `batch_size` slots at a time on
+    /// a background thread, so that callers can follow the progress of a long purge and cancel
+    /// it between batches.
+    pub fn purge_slots_in_background(
+        self: &Arc<Self>,
+        from_slot: Slot,
+        to_slot: Slot,
+        purge_type: PurgeType,
+        batch_size: u64,
+    ) -> PurgeHandle {
+        let progress = Arc::new(PurgeProgress {
+            from_slot,
+            to_slot,
+            next_slot: AtomicU64::new(from_slot),
+            cancelled: AtomicBool::default(),
+        });
+        let blockstore = self.clone();
+        let thread_progress = progress.clone();
+        let thread_hdl = thread::Builder::new()
+            .name("solBstorePurge".to_string())
+            .spawn(move || {
+                let progress = thread_progress;
+                let mut purge_stats = PurgeStats::default();
+                let mut batch_start = from_slot;
+                while batch_start <= to_slot && !progress.cancelled.load(Ordering::Relaxed) {
+                    let batch_end = batch_start
+                        .saturating_add(batch_size.max(1) - 1)
+                        .min(to_slot);
+                    blockstore.run_purge_with_stats(
+                        batch_start,
+                        batch_end,
+                        purge_type,
+                        &mut purge_stats,
+                    )?;
+                    progress
+                        .next_slot
+                        .store(batch_end.saturating_add(1), Ordering::Relaxed);
+                    if batch_end == to_slot {
+                        break;
+                    }
+                    batch_start = batch_end + 1;
+                }
+                info!(
+                    "Purged {} of {} slots from slot {from_slot}, delete_range: {}us, \
+                     write_batch: {}us, delete_file_in_range: {}us",
+                    progress.num_slots_purged(),
+                    progress.num_slots(),
+                    purge_stats.delete_range,
+                    purge_stats.write_batch,
+                    purge_stats.delete_file_in_range,
+                );
+                Ok(())
+            })
+            .unwrap();
+        PurgeHandle {
+            progress,
+            thread_hdl,
+        }
+    }
+
     /// Usually this is paired with .purge_slots() but we can't internally call this in
     /// that function unconditionally. That's because set_max_expired_slot()
     /// expects to purge older slots by the successive chronological order, while .purge_slots()
@@ -281,6 +464,10 @@ impl Blockstore {
                 .duplicate_slots_cf
                 .delete_range_in_batch(write_batch, from_slot, to_slot)
                 .is_ok()
+            & self
+                .duplicate_votes_cf
+                .delete_range_in_batch(write_batch, from_slot, to_slot)
+                .is_ok()
             & self
                 .erasure_meta_cf
                 .delete_range_in_batch(write_batch, from_slot, to_slot)
@@ -321,6 +508,7 @@ impl Blockstore {
         match purge_type {
             PurgeType::Exact => {
                 self.purge_special_columns_exact(write_batch, from_slot, to_slot)?;
+                self.purge_slot_index_columns_exact(write_batch, from_slot, to_slot)?;
             }
             PurgeType::CompactionFilter => {
                 // No explicit action is required here because this purge type completely and
@@ -361,6 +549,10 @@ impl Blockstore {
                 .duplicate_slots_cf
                 .delete_file_in_range(from_slot, to_slot)
                 .is_ok()
+            & self
+                .duplicate_votes_cf
+                .delete_file_in_range(from_slot, to_slot)
+                .is_ok()
             & self
                 .erasure_meta_cf
                 .delete_file_in_range(from_slot, to_slot)
@@ -419,6 +611,37 @@ impl Blockstore {
         Ok(transaction_status_empty && address_signatures_empty)
     }
 
+    /// Purges the reverse indexes of the BlockHeight and Blocktime columns
+    /// exactly, by reading the values being purged from the forward columns.
+    ///
+    /// The purge range applies to \[`from_slot`, `to_slot`\].
+    fn purge_slot_index_columns_exact(
+        &self,
+        batch: &mut WriteBatch,
+        from_slot: Slot,
+        to_slot: Slot,
+    ) -> Result<()> {
+        let block_heights = self
+            .block_height_cf
+            .iter(IteratorMode::From(from_slot, IteratorDirection::Forward))?
+            .take_while(|(slot, _)| *slot <= to_slot);
+        for (slot, value) in block_heights {
+            let block_height = cf::BlockHeight::deserialize(&value)?;
+            self.block_height_index_cf
+                .delete_in_batch(batch, (block_height, slot))?;
+        }
+        let block_times = self
+            .blocktime_cf
+            .iter(IteratorMode::From(from_slot, IteratorDirection::Forward))?
+            .take_while(|(slot, _)| *slot <= to_slot);
+        for (slot, value) in block_times {
+            let timestamp = cf::Blocktime::deserialize(&value)?;
+            self.blocktime_index_cf
+                .delete_in_batch(batch, (timestamp, slot))?;
+        }
+        Ok(())
+    }
+
     /// Purges special columns (using a non-Slot primary-index) exactly, by
     /// deserializing each slot being purged and iterating through all
     /// transactions to determine the keys of individual records.
@@ -563,6 +786,92 @@ pub mod tests {
             });
     }
 
+    #[test]
+    fn test_estimate_purge() {
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let blockstore = Blockstore::open(ledger_path.path()).unwrap();
+
+        let (shreds, _) = make_many_slot_entries(0, 50, 5);
+        let num_data_shreds = |from_slot, to_slot| {
+            shreds
+                .iter()
+                .filter(|shred| shred.is_data() && (from_slot..=to_slot).contains(&shred.slot()))
+                .count() as u64
+        };
+        let num_code_shreds = |from_slot, to_slot| {
+            shreds
+                .iter()
+                .filter(|shred| shred.is_code() && (from_slot..=to_slot).contains(&shred.slot()))
+                .count() as u64
+        };
+        let (expected_data_shreds, expected_code_shreds) =
+            (num_data_shreds(10, 19), num_code_shreds(10, 19));
+        blockstore
+            .insert_shreds(shreds.clone(), None, false)
+            .unwrap();
+
+        let estimate = blockstore.estimate_purge(10, 19).unwrap();
+        let column = |name| {
+            estimate
+                .columns
+                .iter()
+                .find(|column| column.name == name)
+                .unwrap()
+                .clone()
+        };
+        assert_eq!(
+            column(cf::ShredData::NAME).num_entries,
+            expected_data_shreds
+        );
+        assert_eq!(
+            column(cf::ShredCode::NAME).num_entries,
+            expected_code_shreds
+        );
+        assert_eq!(column(cf::SlotMeta::NAME).num_entries, 10);
+        assert_eq!(
+            estimate.num_shreds(),
+            expected_data_shreds + expected_code_shreds
+        );
+        assert!(estimate.num_bytes() > 0);
+
+        // The estimate does not delete anything, and matches what is purged
+        let num_bytes = blockstore.estimate_purge(0, 49).unwrap().num_bytes();
+        assert_eq!(blockstore.estimate_purge(10, 19).unwrap(), estimate);
+        blockstore.purge_slots(10, 19, PurgeType::Exact);
+        assert_eq!(
+            blockstore.estimate_purge(0, 49).unwrap().num_bytes(),
+            num_bytes - estimate.num_bytes()
+        );
+        let estimate = blockstore.estimate_purge(10, 19).unwrap();
+        assert_eq!(estimate.num_shreds(), 0);
+        assert_eq!(estimate.num_bytes(), 0);
+    }
+
+    #[test]
+    fn test_purge_slots_in_background() {
+        let ledger_path = get_tmp_ledger_path_auto_delete!();
+        let blockstore = Arc::new(Blockstore::open(ledger_path.path()).unwrap());
+
+        let (shreds, _) = make_many_slot_entries(0, 50, 5);
+        blockstore.insert_shreds(shreds, None, false).unwrap();
+
+        let purge_handle = blockstore.purge_slots_in_background(0, 29, PurgeType::Exact, 7);
+        let progress = purge_handle.progress().clone();
+        purge_handle.join().unwrap();
+        assert_eq!(progress.num_slots(), 30);
+        assert_eq!(progress.num_slots_purged(), 30);
+        test_all_empty_or_min(&blockstore, 30);
+
+        // A cancelled purge stops between batches
+        let purge_handle = blockstore.purge_slots_in_background(30, 49, PurgeType::Exact, 5);
+        purge_handle.progress().cancel();
+        let progress = purge_handle.progress().clone();
+        purge_handle.join().unwrap();
+        assert_eq!(progress.num_slots(), 20);
+        assert_eq!(progress.num_slots_purged() % 5, 0);
+        test_all_empty_or_min(&blockstore, 30 + progress.num_slots_purged());
+    }
+
     #[test]
     fn test_purge_front_of_ledger() {
         let ledger_path = get_tmp_ledger_path_auto_delete!();
@@ -215,6 +215,37 @@ pub mod columns {
     /// * index type: `crate::shred::ErasureSetId` `(Slot, fec_set_index: u32)`
     /// * value type: [`blockstore_meta::MerkleRootMeta`]`
     pub struct MerkleRootMeta;
+
+    #[derive(Debug)]
+    /// The block height index column
+    ///
+    /// Reverse index of the [`BlockHeight`] column, used to look up the slot of
+    /// a block by its height without scanning slots. Multiple slots may share a
+    /// block height if they are on different forks.
+    ///
+    /// * index type: `(block_height: u64, Slot)`
+    /// * value type: `bool`
+    pub struct BlockHeightIndex;
+
+    #[derive(Debug)]
+    /// The blocktime index column
+    ///
+    /// Reverse index of the [`Blocktime`] column, used to look up slots by
+    /// approximate unix timestamp without scanning slots.
+    ///
+    /// * index type: `(`[`UnixTimestamp`]`, Slot)`
+    /// * value type: `bool`
+    pub struct BlocktimeIndex;
+
+    #[derive(Debug)]
+    /// The duplicate votes column
+    ///
+    /// Evidence of a vote account signing votes for different bank hashes of
+    /// the same slot, one entry per equivocating vote account and slot.
+    ///
+    /// * index type: `(Slot, `[`Pubkey`]`)`
+    /// * value type: [`blockstore_meta::DuplicateVoteProof`]
+    pub struct DuplicateVotes;
 }
 
 macro_rules! convert_column_index_to_key_bytes {
@@ -848,3 +879,123 @@ impl ColumnName for columns::MerkleRootMeta {
 impl TypedColumn for columns::MerkleRootMeta {
     type Type = blockstore_meta::MerkleRootMeta;
 }
+
+impl Column for columns::BlockHeightIndex {
+    type Index = (/*block_height:*/ u64, Slot);
+    type Key = [u8; std::mem::size_of::<u64>() + std::mem::size_of::<Slot>()];
+
+    #[inline]
+    fn key((block_height, slot): &Self::Index) -> Self::Key {
+        convert_column_index_to_key_bytes!(Key,
+            ..8 => &block_height.to_be_bytes(),
+            8.. => &slot.to_be_bytes(),
+        )
+    }
+
+    fn index(key: &[u8]) -> Self::Index {
+        convert_column_key_bytes_to_index!(key,
+            0..8  => u64::from_be_bytes, // block_height
+            8..16 => Slot::from_be_bytes,
+        )
+    }
+
+    fn slot((_block_height, slot): Self::Index) -> Slot {
+        slot
+    }
+
+    // The BlockHeightIndex column is not keyed by slot so this method is meaningless
+    // See Column::as_index() declaration for more details
+    fn as_index(_index: u64) -> Self::Index {
+        (0, 0)
+    }
+}
+impl ColumnName for columns::BlockHeightIndex {
+    const NAME: &'static str = "block_height_index";
+}
+impl TypedColumn for columns::BlockHeightIndex {
+    type Type = bool;
+}
+
+impl columns::BlocktimeIndex {
+    // Flipping the sign bit maps i64 onto u64 preserving order, so that
+    // big-endian keys of negative timestamps sort before positive ones.
+    const SIGN_BIT: u64 = 1 << 63;
+
+    fn encode_timestamp(timestamp: UnixTimestamp) -> [u8; 8] {
+        ((timestamp as u64) ^ Self::SIGN_BIT).to_be_bytes()
+    }
+
+    fn decode_timestamp(bytes: [u8; 8]) -> UnixTimestamp {
+        (u64::from_be_bytes(bytes) ^ Self::SIGN_BIT) as UnixTimestamp
+    }
+}
+
+impl Column for columns::BlocktimeIndex {
+    type Index = (UnixTimestamp, Slot);
+    type Key = [u8; std::mem::size_of::<UnixTimestamp>() + std::mem::size_of::<Slot>()];
+
+    #[inline]
+    fn key((timestamp, slot): &Self::Index) -> Self::Key {
+        convert_column_index_to_key_bytes!(Key,
+            ..8 => &Self::encode_timestamp(*timestamp),
+            8.. => &slot.to_be_bytes(),
+        )
+    }
+
+    fn index(key: &[u8]) -> Self::Index {
+        convert_column_key_bytes_to_index!(key,
+            0..8  => Self::decode_timestamp,
+            8..16 => Slot::from_be_bytes,
+        )
+    }
+
+    fn slot((_timestamp, slot): Self::Index) -> Slot {
+        slot
+    }
+
+    // The BlocktimeIndex column is not keyed by slot so this method is meaningless
+    // See Column::as_index() declaration for more details
+    fn as_index(_index: u64) -> Self::Index {
+        (UnixTimestamp::MIN, 0)
+    }
+}
+impl ColumnName for columns::BlocktimeIndex {
+    const NAME: &'static str = "blocktime_index";
+}
+impl TypedColumn for columns::BlocktimeIndex {
+    type Type = bool;
+}
+
+impl Column for columns::DuplicateVotes {
+    type Index = (Slot, /*vote account:*/ Pubkey);
+    type Key = [u8; std::mem::size_of::<Slot>() + PUBKEY_BYTES];
+
+    #[inline]
+    fn key((slot, pubkey): &Self::Index) -> Self::Key {
+        convert_column_index_to_key_bytes!(Key,
+            ..8 => &slot.to_be_bytes(),
+            8.. => pubkey.as_ref(),
+        )
+    }
+
+    fn index(key: &[u8]) -> Self::Index {
+        convert_column_key_bytes_to_index!(key,
+            0..8  => Slot::from_be_bytes,
+            8..40 => Pubkey::from,
+        )
+    }
+
+    fn slot((slot, _pubkey): Self::Index) -> Slot {
+        slot
+    }
+
+    fn as_index(slot: Slot) -> Self::Index {
+        (slot, Pubkey::default())
+    }
+}
+impl ColumnName for columns::DuplicateVotes {
+    const NAME: &'static str = "duplicate_votes";
+}
+impl TypedColumn for columns::DuplicateVotes {
+    type Type = blockstore_meta::DuplicateVoteProof;
+}
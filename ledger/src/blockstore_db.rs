@@ -188,6 +188,9 @@ impl Rocks {
             new_cf_descriptor::<columns::ProgramCosts>(options, oldest_slot),
             new_cf_descriptor::<columns::OptimisticSlots>(options, oldest_slot),
             new_cf_descriptor::<columns::MerkleRootMeta>(options, oldest_slot),
+            new_cf_descriptor::<columns::BlockHeightIndex>(options, oldest_slot),
+            new_cf_descriptor::<columns::BlocktimeIndex>(options, oldest_slot),
+            new_cf_descriptor::<columns::DuplicateVotes>(options, oldest_slot),
         ];
 
         // If the access type is Secondary, we don't need to open all of the
@@ -236,7 +239,7 @@ impl Rocks {
         cf_descriptors
     }
 
-    const fn columns() -> [&'static str; 21] {
+    const fn columns() -> [&'static str; 24] {
         [
             columns::ErasureMeta::NAME,
             columns::DeadSlots::NAME,
@@ -259,6 +262,9 @@ impl Rocks {
             columns::ProgramCosts::NAME,
             columns::OptimisticSlots::NAME,
             columns::MerkleRootMeta::NAME,
+            columns::BlockHeightIndex::NAME,
+            columns::BlocktimeIndex::NAME,
+            columns::DuplicateVotes::NAME,
         ]
     }
 
@@ -758,6 +764,29 @@ where
         batch.delete_range_cf(self.handle(), from_key, to_key)
     }
 
+    /// Returns the number of entries within \[`from`, `to`\] and the total size in bytes of
+    /// their keys and values, i.e. what `delete_range_in_batch()` would delete.
+    pub(crate) fn range_size(&self, from: Slot, to: Slot) -> Result<(u64, u64)>
+    where
+        C: Column + ColumnName,
+    {
+        let from_key = <C as Column>::key(&C::as_index(from));
+        let to_key = <C as Column>::key(&C::as_index(to.saturating_add(1)));
+        let mut iter = self.backend.raw_iterator_cf(self.handle())?;
+        iter.seek(from_key);
+        let (mut num_entries, mut num_bytes) = (0, 0);
+        while let (Some(key), Some(value)) = (iter.key(), iter.value()) {
+            if key >= to_key.as_ref() {
+                break;
+            }
+            num_entries += 1;
+            num_bytes += (key.len() + value.len()) as u64;
+            iter.next();
+        }
+        iter.status()?;
+        Ok((num_entries, num_bytes))
+    }
+
     /// Delete files whose slot range is within \[`from`, `to`\].
     pub fn delete_file_in_range(&self, from: Slot, to: Slot) -> Result<()>
     where
@@ -1219,6 +1248,8 @@ fn should_enable_cf_compaction(cf_name: &str) -> bool {
         columns::TransactionStatus::NAME
             | columns::TransactionMemos::NAME
             | columns::AddressSignatures::NAME
+            | columns::BlockHeightIndex::NAME
+            | columns::BlocktimeIndex::NAME
     )
 }
 
@@ -1310,6 +1341,8 @@ pub mod tests {
         let columns_to_compact = [
             columns::TransactionStatus::NAME,
             columns::AddressSignatures::NAME,
+            columns::BlockHeightIndex::NAME,
+            columns::BlocktimeIndex::NAME,
         ];
         columns_to_compact.iter().for_each(|cf_name| {
             assert!(should_enable_cf_compaction(cf_name));
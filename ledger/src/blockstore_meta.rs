@@ -8,6 +8,8 @@ use {
     solana_sdk::{
         clock::{Slot, UnixTimestamp},
         hash::Hash,
+        signature::Signature,
+        transaction::Transaction,
     },
     std::{
         collections::BTreeSet,
@@ -230,6 +232,24 @@ pub struct DuplicateSlotProof {
     pub shred2: shred::Payload,
 }
 
+/// Two votes signed by the same vote account for different bank hashes of the
+/// same slot
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct DuplicateVoteProof {
+    pub vote1: DuplicateVote,
+    pub vote2: DuplicateVote,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct DuplicateVote {
+    /// The bank hash voted for
+    pub hash: Hash,
+    pub signature: Signature,
+    /// The vote transaction, for votes received through gossip. Votes seen in
+    /// a block can be read from the ledger by their signature.
+    pub transaction: Option<Transaction>,
+}
+
 #[derive(Deserialize, Serialize, Debug, PartialEq, Eq)]
 pub enum FrozenHashVersioned {
     Current(FrozenHashStatus),
@@ -19,15 +19,19 @@ use {
         epoch_accounts_hash::EpochAccountsHash,
     },
     solana_cost_model::cost_model::CostModel,
-    solana_entry::entry::{
-        self, create_ticks, Entry, EntrySlice, EntryType, EntryVerificationStatus, VerifyRecyclers,
+    solana_entry::{
+        entry::{
+            self, create_ticks, Entry, EntrySlice, EntryType, EntryVerificationStatus,
+            VerifyRecyclers,
+        },
+        entry_verifier::EntryVerifier,
     },
     solana_measure::{measure::Measure, measure_us},
     solana_metrics::datapoint_error,
     solana_rayon_threadlimit::{get_max_thread_count, get_thread_count},
     solana_runtime::{
         accounts_background_service::{AbsRequestSender, SnapshotRequestKind},
-        bank::{Bank, PreCommitResult, TransactionBalancesSet},
+        bank::{Bank, BankConfig, PreCommitResult, TransactionBalancesSet},
         bank_forks::{BankForks, SetRootError},
         bank_utils,
         commitment::VOTE_THRESHOLD_SIZE,
@@ -905,6 +909,7 @@ pub struct ProcessOptions {
     pub accounts_db_config: Option<AccountsDbConfig>,
     pub verify_index: bool,
     pub runtime_config: RuntimeConfig,
+    pub bank_config: BankConfig,
     pub on_halt_store_hash_raw_data_for_debug: bool,
     /// true if after processing the contents of the blockstore at startup, we should run an accounts hash calc
     /// This is useful for debugging.
@@ -996,7 +1001,7 @@ pub(crate) fn process_blockstore_for_bank_0(
     exit: Arc<AtomicBool>,
 ) -> Arc<RwLock<BankForks>> {
     // Setup bank for slot 0
-    let bank0 = Bank::new_with_paths(
+    let mut bank0 = Bank::new_with_paths(
         genesis_config,
         Arc::new(opts.runtime_config.clone()),
         account_paths,
@@ -1010,6 +1015,7 @@ pub(crate) fn process_blockstore_for_bank_0(
         None,
         None,
     );
+    bank0.set_config(&opts.bank_config);
     let bank0_slot = bank0.slot();
     let bank_forks = BankForks::new_rw_arc(bank0);
 
@@ -1234,6 +1240,7 @@ fn confirm_full_slot(
         entry_notification_sender,
         replay_vote_sender,
         recyclers,
+        None,
         opts.allow_dead_slots,
         opts.runtime_config.log_messages_bytes_limit,
         &ignored_prioritization_fee_cache,
@@ -1569,6 +1576,7 @@ pub fn confirm_slot(
     entry_notification_sender: Option<&EntryNotifierSender>,
     replay_vote_sender: Option<&ReplayVoteSender>,
     recyclers: &VerifyRecyclers,
+    entry_verifier: Option<&EntryVerifier>,
     allow_dead_slots: bool,
     log_messages_bytes_limit: Option<usize>,
     prioritization_fee_cache: &PrioritizationFeeCache,
@@ -1600,6 +1608,7 @@ pub fn confirm_slot(
         entry_notification_sender,
         replay_vote_sender,
         recyclers,
+        entry_verifier,
         log_messages_bytes_limit,
         prioritization_fee_cache,
     )
@@ -1617,6 +1626,7 @@ fn confirm_slot_entries(
     entry_notification_sender: Option<&EntryNotifierSender>,
     replay_vote_sender: Option<&ReplayVoteSender>,
     recyclers: &VerifyRecyclers,
+    entry_verifier: Option<&EntryVerifier>,
     log_messages_bytes_limit: Option<usize>,
     prioritization_fee_cache: &PrioritizationFeeCache,
 ) -> result::Result<(), BlockstoreProcessorError> {
@@ -1695,11 +1705,16 @@ fn confirm_slot_entries(
     let last_entry_hash = entries.last().map(|e| e.hash);
     let verifier = if !skip_verification {
         datapoint_debug!("verify-batch-size", ("size", num_entries as i64, i64));
-        let entry_state = entries.start_verify(
-            &progress.last_entry,
-            replay_tx_thread_pool,
-            recyclers.clone(),
-        );
+        // With an entry verifier, PoH is verified in the background, overlapping with
+        // transaction verification and execution below.
+        let entry_state = match entry_verifier {
+            Some(entry_verifier) => entry_verifier.start_verify(&entries, &progress.last_entry),
+            None => entries.start_verify(
+                &progress.last_entry,
+                replay_tx_thread_pool,
+                recyclers.clone(),
+            ),
+        };
         if entry_state.status() == EntryVerificationStatus::Failure {
             warn!("Ledger proof of history failed at slot: {}", slot);
             return Err(BlockError::InvalidEntryHash.into());
@@ -4847,6 +4862,7 @@ pub mod tests {
             None,
             &VerifyRecyclers::default(),
             None,
+            None,
             &PrioritizationFeeCache::new(0u64),
         )
     }
@@ -4940,6 +4956,7 @@ pub mod tests {
             None,
             &VerifyRecyclers::default(),
             None,
+            None,
             &PrioritizationFeeCache::new(0u64),
         )
         .unwrap();
@@ -4985,6 +5002,7 @@ pub mod tests {
             None,
             &VerifyRecyclers::default(),
             None,
+            None,
             &PrioritizationFeeCache::new(0u64),
         )
         .unwrap();
@@ -5243,6 +5261,53 @@ pub mod tests {
         }
     }
 
+    #[test]
+    fn test_confirm_slot_entries_with_entry_verifier() {
+        const HASHES_PER_TICK: u64 = 10;
+
+        let GenesisConfigInfo {
+            mut genesis_config, ..
+        } = create_genesis_config(10_000);
+        genesis_config.poh_config.hashes_per_tick = Some(HASHES_PER_TICK);
+        let genesis_hash = genesis_config.hash();
+        let replay_tx_thread_pool = Arc::new(create_thread_pool(1));
+        let entry_verifier = EntryVerifier::new(
+            replay_tx_thread_pool.clone(),
+            solana_entry::entry_verifier::DEFAULT_MAX_QUEUED_BATCHES,
+        );
+        let confirm = |entries: Vec<Entry>| {
+            let bank = Arc::new(Bank::new_for_tests(&genesis_config));
+            confirm_slot_entries(
+                &BankWithScheduler::new_without_scheduler(bank.clone()),
+                &replay_tx_thread_pool,
+                (entries, 0, false),
+                &mut ConfirmationTiming::default(),
+                &mut ConfirmationProgress::new(genesis_hash),
+                false,
+                None,
+                None,
+                None,
+                &VerifyRecyclers::default(),
+                Some(&entry_verifier),
+                None,
+                &PrioritizationFeeCache::new(0u64),
+            )
+            .map(|()| bank.tick_height())
+        };
+
+        let mut entries = create_ticks(2, HASHES_PER_TICK, genesis_hash);
+        assert_eq!(confirm(entries.clone()).unwrap(), 2);
+
+        entries[1].hash = Hash::new_unique();
+        assert_matches!(
+            confirm(entries),
+            Err(BlockstoreProcessorError::InvalidBlock(
+                BlockError::InvalidEntryHash
+            ))
+        );
+        entry_verifier.join().unwrap();
+    }
+
     #[test]
     fn test_confirm_slot_entries_with_fix() {
         const HASHES_PER_TICK: u64 = 10;
@@ -5410,6 +5475,8 @@ pub mod tests {
                         return_data: None,
                         executed_units: actual_execution_cu,
                         accounts_data_len_delta: 0,
+                        memory_usage: None,
+                        account_privileges: None,
                     },
                     loaded_transaction: LoadedTransaction {
                         loaded_accounts_data_size: actual_loaded_accounts_data_size,
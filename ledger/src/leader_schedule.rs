@@ -7,6 +7,65 @@ use {
     std::{collections::HashMap, convert::identity, ops::Index, sync::Arc},
 };
 
+/// Returns the seed of the ChaCha RNG which samples the leaders of `epoch`.
+///
+/// The seed is the little-endian encoding of the epoch number, zero padded to
+/// 32 bytes. It does not depend on any other bank state, so anyone who knows
+/// the epoch's staked nodes can reproduce the schedule.
+pub fn leader_schedule_seed(epoch: Epoch) -> [u8; 32] {
+    let mut seed = [0u8; 32];
+    seed[0..8].copy_from_slice(&epoch.to_le_bytes());
+    seed
+}
+
+/// Everything needed to derive the leader schedule of one epoch.
+///
+/// The inputs are read from a bank at or after the epoch boundary which
+/// determines the stakes for `epoch` (see `leader_schedule_utils`). Third
+/// parties can obtain them from a snapshot and rebuild or check a schedule
+/// without running a validator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LeaderScheduleInputs {
+    pub epoch: Epoch,
+    /// Stake delegated to each validator identity.
+    pub staked_nodes: Arc<HashMap<Pubkey, u64>>,
+    pub slots_in_epoch: u64,
+    /// Number of consecutive slots assigned to each sampled leader.
+    pub repeat: u64,
+}
+
+impl LeaderScheduleInputs {
+    pub fn seed(&self) -> [u8; 32] {
+        leader_schedule_seed(self.epoch)
+    }
+
+    // Note: zero staked nodes will cause a panic.
+    pub fn leader_schedule(&self) -> LeaderSchedule {
+        LeaderSchedule::new_keyed_by_validator_identity(
+            &self.staked_nodes,
+            self.epoch,
+            self.slots_in_epoch,
+            self.repeat,
+        )
+    }
+
+    /// Returns the first slot index, relative to the start of the epoch, where
+    /// `slot_leaders` deviates from the schedule derived from these inputs,
+    /// or None if they match.
+    pub fn find_mismatch(&self, slot_leaders: &[Pubkey]) -> Option<usize> {
+        let expected = self.leader_schedule();
+        let expected = expected.get_slot_leaders();
+        expected
+            .iter()
+            .zip(slot_leaders)
+            .position(|(expected, actual)| expected != actual)
+            .or_else(|| {
+                (expected.len() != slot_leaders.len())
+                    .then(|| expected.len().min(slot_leaders.len()))
+            })
+    }
+}
+
 // Used for testing
 #[derive(Clone, Debug)]
 pub struct FixedSchedule {
@@ -47,9 +106,7 @@ impl LeaderSchedule {
         sort_stakes(&mut keyed_stakes);
         let (keys, stakes): (Vec<_>, Vec<_>) = keyed_stakes.into_iter().unzip();
         let weighted_index = WeightedIndex::new(stakes).unwrap();
-        let mut seed = [0u8; 32];
-        seed[0..8].copy_from_slice(&epoch.to_le_bytes());
-        let rng = &mut ChaChaRng::from_seed(seed);
+        let rng = &mut ChaChaRng::from_seed(leader_schedule_seed(epoch));
         let mut current_slot_leader = Pubkey::default();
         (0..len)
             .map(|i| {
@@ -236,6 +293,34 @@ mod tests {
         assert_eq!(leaders2, leaders2_expected);
     }
 
+    #[test]
+    fn test_leader_schedule_inputs() {
+        let stakes: HashMap<_, _> = repeat_with(Pubkey::new_unique).zip(1..=10).collect();
+        let inputs = LeaderScheduleInputs {
+            epoch: 7,
+            staked_nodes: Arc::new(stakes.clone()),
+            slots_in_epoch: 64,
+            repeat: 4,
+        };
+        let mut seed = [0u8; 32];
+        seed[0] = 7;
+        assert_eq!(inputs.seed(), seed);
+
+        let leader_schedule = LeaderSchedule::new_keyed_by_validator_identity(&stakes, 7, 64, 4);
+        assert_eq!(inputs.leader_schedule(), leader_schedule);
+        let mut slot_leaders = leader_schedule.get_slot_leaders().to_vec();
+        assert_eq!(inputs.find_mismatch(&slot_leaders), None);
+
+        // Truncated schedule.
+        assert_eq!(inputs.find_mismatch(&slot_leaders[..60]), Some(60));
+        // Different leader.
+        slot_leaders[13] = Pubkey::new_unique();
+        assert_eq!(inputs.find_mismatch(&slot_leaders), Some(13));
+        // Different epoch yields a different schedule.
+        let inputs = LeaderScheduleInputs { epoch: 8, ..inputs };
+        assert_ne!(inputs.leader_schedule(), leader_schedule);
+    }
+
     #[test]
     fn test_get_indices() {
         const NUM_SLOTS: usize = 97;
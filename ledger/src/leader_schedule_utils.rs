@@ -1,5 +1,5 @@
 use {
-    crate::leader_schedule::LeaderSchedule,
+    crate::leader_schedule::{LeaderSchedule, LeaderScheduleInputs},
     solana_runtime::bank::Bank,
     solana_sdk::{
         clock::{Epoch, Slot, NUM_CONSECUTIVE_LEADER_SLOTS},
@@ -8,16 +8,21 @@ use {
     std::collections::HashMap,
 };
 
+/// Return the inputs which determine the leader schedule for the given epoch,
+/// or None if the bank does not hold the stakes for that epoch.
+pub fn leader_schedule_inputs(epoch: Epoch, bank: &Bank) -> Option<LeaderScheduleInputs> {
+    bank.epoch_staked_nodes(epoch)
+        .map(|staked_nodes| LeaderScheduleInputs {
+            epoch,
+            staked_nodes,
+            slots_in_epoch: bank.get_slots_in_epoch(epoch),
+            repeat: NUM_CONSECUTIVE_LEADER_SLOTS,
+        })
+}
+
 /// Return the leader schedule for the given epoch.
 pub fn leader_schedule(epoch: Epoch, bank: &Bank) -> Option<LeaderSchedule> {
-    bank.epoch_staked_nodes(epoch).map(|stakes| {
-        LeaderSchedule::new_keyed_by_validator_identity(
-            &stakes,
-            epoch,
-            bank.get_slots_in_epoch(epoch),
-            NUM_CONSECUTIVE_LEADER_SLOTS,
-        )
-    })
+    leader_schedule_inputs(epoch, bank).map(|inputs| inputs.leader_schedule())
 }
 
 /// Map of leader base58 identity pubkeys to the slot indices relative to the first epoch slot
@@ -101,4 +106,32 @@ mod tests {
         let bank = Bank::new_for_tests(&genesis_config);
         assert_eq!(slot_leader_at(bank.slot(), &bank).unwrap(), pubkey);
     }
+
+    #[test]
+    fn test_leader_schedule_inputs() {
+        let pubkey = solana_pubkey::new_rand();
+        let genesis_config =
+            create_genesis_config_with_leader(42, &pubkey, bootstrap_validator_stake_lamports())
+                .genesis_config;
+        let bank = Bank::new_for_tests(&genesis_config);
+        let epoch = bank.epoch();
+
+        let inputs = leader_schedule_inputs(epoch, &bank).unwrap();
+        assert_eq!(inputs.epoch, epoch);
+        assert_eq!(inputs.slots_in_epoch, bank.get_slots_in_epoch(epoch));
+        assert_eq!(
+            *inputs.staked_nodes,
+            HashMap::from([(pubkey, bootstrap_validator_stake_lamports())])
+        );
+        let leader_schedule = leader_schedule(epoch, &bank).unwrap();
+        assert_eq!(inputs.leader_schedule(), leader_schedule);
+        assert_eq!(
+            inputs.find_mismatch(leader_schedule.get_slot_leaders()),
+            None
+        );
+
+        // The bank only holds stakes up to the leader schedule epoch.
+        let beyond = bank.get_leader_schedule_epoch(bank.slot()) + 1;
+        assert!(leader_schedule_inputs(beyond, &bank).is_none());
+    }
 }
@@ -0,0 +1,278 @@
+//! Declarative chaos scenarios against a [`LocalCluster`].
+//!
+//! A [`ChaosScenario`] is a list of actions, each triggered once the cluster reaches a slot, a
+//! number of slots after the previous action, or a delay after it. Actions run in order:
+//!
+//! ```ignore
+//! ChaosScenario::new("kill and partition")
+//!     .at_slot(20)
+//!     .kill(node_a)
+//!     .after_slots(30)
+//!     .restart(node_a)
+//!     .partition(&[node_b])
+//!     .after(Duration::from_secs(10))
+//!     .heal(&[node_b])
+//!     .check_new_roots(16)
+//!     .run(&mut cluster);
+//! ```
+//!
+//! Partitions stop the partitioned nodes from receiving turbine shreds by setting their
+//! `ValidatorConfig::turbine_disabled` flag. Nodes sharing a flag are partitioned together,
+//! while `make_identical_validator_configs` gives each node a flag of its own.
+use {
+    crate::{
+        cluster::{Cluster, ClusterValidatorInfo},
+        local_cluster::LocalCluster,
+    },
+    log::*,
+    solana_rpc_client::rpc_client::RpcClient,
+    solana_sdk::{clock::Slot, commitment_config::CommitmentConfig, pubkey::Pubkey},
+    solana_streamer::socket::SocketAddrSpace,
+    std::{
+        collections::HashMap,
+        sync::atomic::Ordering,
+        thread::sleep,
+        time::{Duration, Instant},
+    },
+};
+
+const DEFAULT_TRIGGER_TIMEOUT: Duration = Duration::from_secs(180);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChaosTrigger {
+    /// As soon as the previous action is done
+    Immediately,
+    /// Once the cluster reaches the slot
+    Slot(Slot),
+    /// Once the cluster advances by this many slots after the previous action
+    AfterSlots(u64),
+    /// After a delay following the previous action
+    After(Duration),
+}
+
+pub enum ChaosAction {
+    /// Stop the node, keeping its ledger for a later restart
+    Kill(Pubkey),
+    /// Restart a node stopped by a previous `Kill`
+    Restart(Pubkey),
+    /// Stop the nodes from receiving turbine shreds
+    Partition(Vec<Pubkey>),
+    /// Let partitioned nodes receive turbine shreds again
+    Heal(Vec<Pubkey>),
+    /// Wait for the running nodes to make this many new roots
+    CheckNewRoots(usize),
+    /// Check that the running nodes make no new roots for this many slots
+    CheckNoNewRoots(usize),
+    /// Wait for the running nodes to root the slot
+    CheckMinRoot(Slot),
+    /// Run arbitrary code against the cluster and the nodes killed so far
+    Custom(Box<dyn FnOnce(&mut LocalCluster, &mut KilledNodes)>),
+}
+
+impl std::fmt::Debug for ChaosAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Kill(pubkey) => write!(f, "Kill({pubkey})"),
+            Self::Restart(pubkey) => write!(f, "Restart({pubkey})"),
+            Self::Partition(pubkeys) => write!(f, "Partition({pubkeys:?})"),
+            Self::Heal(pubkeys) => write!(f, "Heal({pubkeys:?})"),
+            Self::CheckNewRoots(num_roots) => write!(f, "CheckNewRoots({num_roots})"),
+            Self::CheckNoNewRoots(num_slots) => write!(f, "CheckNoNewRoots({num_slots})"),
+            Self::CheckMinRoot(slot) => write!(f, "CheckMinRoot({slot})"),
+            Self::Custom(_) => write!(f, "Custom"),
+        }
+    }
+}
+
+/// The nodes stopped by `Kill` actions and not restarted yet
+pub type KilledNodes = HashMap<Pubkey, ClusterValidatorInfo>;
+
+pub struct ChaosScenario {
+    name: String,
+    steps: Vec<(ChaosTrigger, ChaosAction)>,
+    next_trigger: ChaosTrigger,
+    trigger_timeout: Duration,
+    socket_addr_space: SocketAddrSpace,
+}
+
+impl ChaosScenario {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            steps: vec![],
+            next_trigger: ChaosTrigger::Immediately,
+            trigger_timeout: DEFAULT_TRIGGER_TIMEOUT,
+            socket_addr_space: SocketAddrSpace::Unspecified,
+        }
+    }
+
+    /// How long to wait for a slot trigger before failing the scenario
+    pub fn trigger_timeout(mut self, trigger_timeout: Duration) -> Self {
+        self.trigger_timeout = trigger_timeout;
+        self
+    }
+
+    pub fn socket_addr_space(mut self, socket_addr_space: SocketAddrSpace) -> Self {
+        self.socket_addr_space = socket_addr_space;
+        self
+    }
+
+    /// Run the next action once the cluster reaches `slot`
+    pub fn at_slot(self, slot: Slot) -> Self {
+        self.trigger(ChaosTrigger::Slot(slot))
+    }
+
+    /// Run the next action once the cluster advances by `num_slots` after the previous action
+    pub fn after_slots(self, num_slots: u64) -> Self {
+        self.trigger(ChaosTrigger::AfterSlots(num_slots))
+    }
+
+    /// Run the next action `delay` after the previous action
+    pub fn after(self, delay: Duration) -> Self {
+        self.trigger(ChaosTrigger::After(delay))
+    }
+
+    pub fn trigger(mut self, trigger: ChaosTrigger) -> Self {
+        self.next_trigger = trigger;
+        self
+    }
+
+    pub fn kill(self, pubkey: Pubkey) -> Self {
+        self.action(ChaosAction::Kill(pubkey))
+    }
+
+    pub fn restart(self, pubkey: Pubkey) -> Self {
+        self.action(ChaosAction::Restart(pubkey))
+    }
+
+    pub fn partition(self, pubkeys: &[Pubkey]) -> Self {
+        self.action(ChaosAction::Partition(pubkeys.to_vec()))
+    }
+
+    pub fn heal(self, pubkeys: &[Pubkey]) -> Self {
+        self.action(ChaosAction::Heal(pubkeys.to_vec()))
+    }
+
+    pub fn check_new_roots(self, num_new_roots: usize) -> Self {
+        self.action(ChaosAction::CheckNewRoots(num_new_roots))
+    }
+
+    pub fn check_no_new_roots(self, num_slots_to_wait: usize) -> Self {
+        self.action(ChaosAction::CheckNoNewRoots(num_slots_to_wait))
+    }
+
+    pub fn check_min_root(self, min_root: Slot) -> Self {
+        self.action(ChaosAction::CheckMinRoot(min_root))
+    }
+
+    pub fn custom(
+        self,
+        action: impl FnOnce(&mut LocalCluster, &mut KilledNodes) + 'static,
+    ) -> Self {
+        self.action(ChaosAction::Custom(Box::new(action)))
+    }
+
+    /// Add `action`, triggered by the last trigger set. Triggers apply to a single action, the
+    /// ones after it run immediately unless given their own trigger.
+    pub fn action(mut self, action: ChaosAction) -> Self {
+        let trigger = std::mem::replace(&mut self.next_trigger, ChaosTrigger::Immediately);
+        self.steps.push((trigger, action));
+        self
+    }
+
+    /// Runs the scenario against `cluster`, panicking if a trigger times out or a check fails.
+    /// Returns the nodes that were killed and not restarted.
+    pub fn run(self, cluster: &mut LocalCluster) -> KilledNodes {
+        let Self {
+            name,
+            steps,
+            next_trigger: _,
+            trigger_timeout,
+            socket_addr_space,
+        } = self;
+        let mut killed_nodes = KilledNodes::new();
+        let mut previous_slot = current_slot(cluster);
+        for (index, (trigger, action)) in steps.into_iter().enumerate() {
+            match trigger {
+                ChaosTrigger::Immediately => {}
+                ChaosTrigger::Slot(slot) => {
+                    wait_for_slot(cluster, slot, trigger_timeout, &name);
+                }
+                ChaosTrigger::AfterSlots(num_slots) => {
+                    let slot = previous_slot.saturating_add(num_slots);
+                    wait_for_slot(cluster, slot, trigger_timeout, &name);
+                }
+                ChaosTrigger::After(delay) => sleep(delay),
+            }
+            info!("{name} step {index}: {action:?}");
+            match action {
+                ChaosAction::Kill(pubkey) => {
+                    let node = cluster.exit_node(&pubkey);
+                    killed_nodes.insert(pubkey, node);
+                }
+                ChaosAction::Restart(pubkey) => {
+                    let node = killed_nodes
+                        .remove(&pubkey)
+                        .unwrap_or_else(|| panic!("{name}: {pubkey} was not killed"));
+                    cluster.restart_node(&pubkey, node, socket_addr_space);
+                }
+                ChaosAction::Partition(pubkeys) => set_partitioned(cluster, &pubkeys, true),
+                ChaosAction::Heal(pubkeys) => set_partitioned(cluster, &pubkeys, false),
+                ChaosAction::CheckNewRoots(num_new_roots) => {
+                    cluster.check_for_new_roots(num_new_roots, &name, socket_addr_space);
+                }
+                ChaosAction::CheckNoNewRoots(num_slots_to_wait) => {
+                    cluster.check_no_new_roots(num_slots_to_wait, &name, socket_addr_space);
+                }
+                ChaosAction::CheckMinRoot(min_root) => {
+                    cluster.check_min_slot_is_rooted(min_root, &name, socket_addr_space);
+                }
+                ChaosAction::Custom(action) => action(cluster, &mut killed_nodes),
+            }
+            previous_slot = current_slot(cluster);
+        }
+        killed_nodes
+    }
+}
+
+fn set_partitioned(cluster: &LocalCluster, pubkeys: &[Pubkey], partitioned: bool) {
+    for pubkey in pubkeys {
+        let node = cluster
+            .validators
+            .get(pubkey)
+            .unwrap_or_else(|| panic!("{pubkey} is not running"));
+        node.config
+            .turbine_disabled
+            .store(partitioned, Ordering::Relaxed);
+    }
+}
+
+/// The highest processed slot of the running nodes, or 0 if none answers
+fn current_slot(cluster: &LocalCluster) -> Slot {
+    cluster
+        .get_node_pubkeys()
+        .iter()
+        .filter_map(|pubkey| cluster.get_contact_info(pubkey)?.rpc())
+        .filter_map(|rpc_addr| {
+            RpcClient::new_socket(rpc_addr)
+                .get_slot_with_commitment(CommitmentConfig::processed())
+                .ok()
+        })
+        .max()
+        .unwrap_or_default()
+}
+
+fn wait_for_slot(cluster: &LocalCluster, slot: Slot, timeout: Duration, name: &str) {
+    let start = Instant::now();
+    loop {
+        let current_slot = current_slot(cluster);
+        if current_slot >= slot {
+            return;
+        }
+        assert!(
+            start.elapsed() < timeout,
+            "{name}: timed out waiting for slot {slot}, cluster is at {current_slot}"
+        );
+        sleep(Duration::from_millis(100));
+    }
+}
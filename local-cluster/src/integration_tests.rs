@@ -50,10 +50,7 @@ use {
         fs, iter,
         num::NonZeroUsize,
         path::{Path, PathBuf},
-        sync::{
-            atomic::{AtomicBool, Ordering},
-            Arc,
-        },
+        sync::{atomic::Ordering, Arc},
         thread::sleep,
         time::Duration,
     },
@@ -291,6 +288,16 @@ pub fn create_custom_leader_schedule_with_random_keys(
     (leader_schedule, validator_keys)
 }
 
+/// Stops or resumes the delivery of turbine shreds to all the running nodes of `cluster`
+fn set_turbine_disabled(cluster: &LocalCluster, disabled: bool) {
+    for validator in cluster.validators.values() {
+        validator
+            .config
+            .turbine_disabled
+            .store(disabled, Ordering::Relaxed);
+    }
+}
+
 /// This function runs a network, initiates a partition based on a
 /// configuration, resolve the partition, then checks that the network
 /// continues to achieve consensus
@@ -319,11 +326,7 @@ pub fn run_cluster_partition<C>(
         .collect();
     assert_eq!(node_stakes.len(), num_nodes);
     let mint_lamports = node_stakes.iter().sum::<u64>() * 2;
-    let turbine_disabled = Arc::new(AtomicBool::new(false));
-    let mut validator_config = ValidatorConfig {
-        turbine_disabled: turbine_disabled.clone(),
-        ..ValidatorConfig::default_for_test()
-    };
+    let mut validator_config = ValidatorConfig::default_for_test();
 
     let (validator_keys, partition_duration): (Vec<_>, Duration) = {
         if let Some((leader_schedule, validator_keys)) = leader_schedule {
@@ -401,13 +404,13 @@ pub fn run_cluster_partition<C>(
 
     info!("PARTITION_TEST start partition");
     on_partition_start(&mut cluster, &mut context);
-    turbine_disabled.store(true, Ordering::Relaxed);
+    set_turbine_disabled(&cluster, true);
 
     sleep(partition_duration);
 
     on_before_partition_resolved(&mut cluster, &mut context);
     info!("PARTITION_TEST remove partition");
-    turbine_disabled.store(false, Ordering::Relaxed);
+    set_turbine_disabled(&cluster, false);
 
     // Give partitions time to propagate their blocks from during the partition
     // after the partition resolves
@@ -1,7 +1,9 @@
 #![allow(clippy::arithmetic_side_effects)]
+pub mod chaos;
 pub mod cluster;
 pub mod cluster_tests;
 pub mod integration_tests;
 pub mod local_cluster;
 mod local_cluster_snapshot_utils;
+pub mod log_capture;
 pub mod validator_configs;
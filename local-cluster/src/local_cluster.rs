@@ -1,3 +1,7 @@
+#[cfg(feature = "dev-context-only-utils")]
+use solana_streamer::network_conditions::{
+    condition_socket, remove_socket_conditioner, NetworkConditioner, NetworkConditions,
+};
 use {
     crate::{
         cluster::{Cluster, ClusterValidatorInfo, QuicTpuClient, ValidatorInfo},
@@ -13,12 +17,14 @@ use {
         consensus::tower_storage::FileTowerStorage,
         validator::{Validator, ValidatorConfig, ValidatorStartProgress, ValidatorTpuConfig},
     },
+    solana_entry::poh::calibrate_hashes_per_tick,
+    solana_genesis::fixtures::load_fixtures_dir,
     solana_gossip::{
         cluster_info::Node,
         contact_info::{ContactInfo, Protocol},
         gossip_service::discover_cluster,
     },
-    solana_ledger::{create_new_tmp_ledger_with_size, shred::Shred},
+    solana_ledger::{create_new_tmp_ledger_with_size, get_tmp_ledger_path, shred::Shred},
     solana_net_utils::bind_to_unspecified,
     solana_rpc_client::rpc_client::RpcClient,
     solana_runtime::{
@@ -60,6 +66,7 @@ use {
     },
     std::{
         collections::HashMap,
+        fs,
         io::{Error, ErrorKind, Result},
         iter,
         net::{IpAddr, Ipv4Addr, SocketAddr},
@@ -96,10 +103,23 @@ pub struct ClusterConfig {
     pub native_instruction_processors: Vec<(String, Pubkey)>,
     pub cluster_type: ClusterType,
     pub poh_config: PohConfig,
+    /// If `poh_config` leaves `hashes_per_tick` unset, set it to what this
+    /// machine can sustain instead of sleeping between ticks, so that slot
+    /// timing resembles a real cluster. Off by default, as hashing keeps a
+    /// core busy for each node of the cluster
+    pub calibrate_poh: bool,
     pub additional_accounts: Vec<(Pubkey, AccountSharedData)>,
+    /// Directories of account and program fixtures to install in genesis, see
+    /// `solana_genesis::fixtures`
+    pub genesis_fixtures_dirs: Vec<PathBuf>,
     pub tpu_use_quic: bool,
     pub tpu_connection_pool_size: usize,
     pub vote_use_quic: bool,
+    /// Simulated network conditions of the gossip, turbine and UDP TPU sockets of each node
+    /// when it starts, which can be changed with `LocalCluster::set_network_conditions`. If
+    /// `None`, the sockets are not conditioned and can't be later.
+    #[cfg(feature = "dev-context-only-utils")]
+    pub network_conditions: Option<NetworkConditions>,
 }
 
 impl ClusterConfig {
@@ -135,11 +155,84 @@ impl Default for ClusterConfig {
             native_instruction_processors: vec![],
             cluster_type: ClusterType::Development,
             poh_config: PohConfig::default(),
+            calibrate_poh: false,
             skip_warmup_slots: false,
             additional_accounts: vec![],
+            genesis_fixtures_dirs: vec![],
             tpu_use_quic: DEFAULT_TPU_USE_QUIC,
             tpu_connection_pool_size: DEFAULT_TPU_CONNECTION_POOL_SIZE,
             vote_use_quic: DEFAULT_VOTE_USE_QUIC,
+            #[cfg(feature = "dev-context-only-utils")]
+            network_conditions: None,
+        }
+    }
+}
+
+/// The simulated network conditions of each node, see `ClusterConfig::network_conditions`
+#[cfg(feature = "dev-context-only-utils")]
+#[derive(Default)]
+struct NetworkConditioners {
+    initial_conditions: Option<NetworkConditions>,
+    // The conditioner of each node, and the addresses of its conditioned sockets
+    nodes: HashMap<Pubkey, (Arc<NetworkConditioner>, Vec<SocketAddr>)>,
+}
+
+#[cfg(feature = "dev-context-only-utils")]
+impl NetworkConditioners {
+    fn new(initial_conditions: Option<NetworkConditions>) -> Self {
+        Self {
+            initial_conditions,
+            nodes: HashMap::new(),
+        }
+    }
+
+    /// Conditions the sockets of `node`, which must be done before the validator starts. A
+    /// restarted node keeps the conditions it had.
+    fn condition_node(&mut self, node: &Node) {
+        let Some(initial_conditions) = self.initial_conditions else {
+            return;
+        };
+        let pubkey = *node.info.pubkey();
+        let (conditioner, addrs) = self.nodes.entry(pubkey).or_insert_with(|| {
+            let seed = u64::from_le_bytes(pubkey.to_bytes()[..8].try_into().unwrap());
+            (
+                Arc::new(NetworkConditioner::new(initial_conditions, seed)),
+                vec![],
+            )
+        });
+        for addr in addrs.drain(..) {
+            remove_socket_conditioner(&addr);
+        }
+        let sockets = &node.sockets;
+        addrs.extend(
+            iter::once(&sockets.gossip)
+                .chain(&sockets.tvu)
+                .chain(&sockets.tpu)
+                .chain(&sockets.tpu_forwards)
+                .chain(&sockets.tpu_vote)
+                .map(|socket| socket.local_addr().unwrap()),
+        );
+        for addr in addrs.iter() {
+            condition_socket(*addr, conditioner.clone());
+        }
+    }
+
+    fn conditioner(&self, pubkey: &Pubkey) -> &NetworkConditioner {
+        let (conditioner, _) = self
+            .nodes
+            .get(pubkey)
+            .expect("network conditions are enabled by ClusterConfig::network_conditions");
+        conditioner
+    }
+}
+
+#[cfg(feature = "dev-context-only-utils")]
+impl Drop for NetworkConditioners {
+    fn drop(&mut self) {
+        for (_, addrs) in self.nodes.values() {
+            for addr in addrs {
+                remove_socket_conditioner(addr);
+            }
         }
     }
 }
@@ -149,6 +242,43 @@ struct QuicConnectionCacheConfig {
     staked_nodes: Arc<RwLock<StakedNodes>>,
 }
 
+impl Clone for QuicConnectionCacheConfig {
+    fn clone(&self) -> Self {
+        Self {
+            client_keypair: self.client_keypair.insecure_clone(),
+            staked_nodes: self.staked_nodes.clone(),
+        }
+    }
+}
+
+/// A copy of the ledger directories of the validators of a `LocalCluster`, taken while they
+/// were stopped, see `LocalCluster::checkpoint()`
+pub struct ClusterCheckpoint {
+    dir: PathBuf,
+    // The entry point first
+    validators: Vec<Pubkey>,
+    // The identity and vote keypairs, and the config of each validator, in the same order, to
+    // start new clusters from the checkpoint
+    validator_keys: Vec<(Arc<Keypair>, Arc<Keypair>, ValidatorConfig)>,
+    funding_keypair: Keypair,
+    genesis_config: GenesisConfig,
+    quic_connection_cache_config: Option<QuicConnectionCacheConfig>,
+    tpu_connection_pool_size: usize,
+    #[cfg(feature = "dev-context-only-utils")]
+    network_conditions: Option<NetworkConditions>,
+}
+
+impl ClusterCheckpoint {
+    pub fn validators(&self) -> &[Pubkey] {
+        &self.validators
+    }
+
+    /// The copy of the ledger directory of `validator_pubkey`
+    pub fn ledger_path(&self, validator_pubkey: &Pubkey) -> PathBuf {
+        self.dir.join(validator_pubkey.to_string())
+    }
+}
+
 pub struct LocalCluster {
     /// Keypair with funding to participate in the network
     pub funding_keypair: Keypair,
@@ -159,6 +289,8 @@ pub struct LocalCluster {
     pub connection_cache: Arc<ConnectionCache>,
     quic_connection_cache_config: Option<QuicConnectionCacheConfig>,
     tpu_connection_pool_size: usize,
+    #[cfg(feature = "dev-context-only-utils")]
+    network_conditioners: NetworkConditioners,
 }
 
 impl LocalCluster {
@@ -295,6 +427,10 @@ impl LocalCluster {
         let leader_vote_keypair = &keys_in_genesis[0].vote_keypair;
         let leader_pubkey = leader_keypair.pubkey();
         let leader_node = Node::new_localhost_with_pubkey(&leader_pubkey);
+        #[cfg(feature = "dev-context-only-utils")]
+        let mut network_conditioners = NetworkConditioners::new(config.network_conditions);
+        #[cfg(feature = "dev-context-only-utils")]
+        network_conditioners.condition_node(&leader_node);
 
         let GenesisConfigInfo {
             mut genesis_config,
@@ -319,9 +455,22 @@ impl LocalCluster {
             !config.skip_warmup_slots,
         );
         genesis_config.poh_config = config.poh_config.clone();
+        if config.calibrate_poh && genesis_config.poh_config.hashes_per_tick.is_none() {
+            genesis_config.poh_config.hashes_per_tick = Some(calibrate_hashes_per_tick(
+                genesis_config.poh_config.target_tick_duration,
+            ));
+        }
         genesis_config
             .native_instruction_processors
             .extend_from_slice(&config.native_instruction_processors);
+        for dir in &config.genesis_fixtures_dirs {
+            load_fixtures_dir(dir, &mut genesis_config).unwrap_or_else(|err| {
+                panic!(
+                    "failed to load genesis fixtures from {}: {err}",
+                    dir.display()
+                )
+            });
+        }
 
         let mut leader_config = safe_clone_config(&config.validator_configs[0]);
         let (leader_ledger_path, _blockhash) = create_new_tmp_ledger_with_size!(
@@ -380,6 +529,8 @@ impl LocalCluster {
             connection_cache,
             quic_connection_cache_config,
             tpu_connection_pool_size: config.tpu_connection_pool_size,
+            #[cfg(feature = "dev-context-only-utils")]
+            network_conditioners,
         };
 
         let node_pubkey_to_vote_key: HashMap<Pubkey, Arc<Keypair>> = keys_in_genesis
@@ -451,6 +602,152 @@ impl LocalCluster {
         }
     }
 
+    /// Stops all the validators, copies their ledger directories into `dir`, and restarts them.
+    ///
+    /// The ledger directory of a validator holds its blockstore, tower, accounts and snapshots,
+    /// so `restore()` can later restart every validator from the exact state it stopped in. This
+    /// lets a test replay a scenario from a known point, instead of relying on sleeps to reach
+    /// it. Validators taken out of the cluster with `exit_node()` are not part of the checkpoint.
+    pub fn checkpoint(
+        &mut self,
+        dir: &Path,
+        socket_addr_space: SocketAddrSpace,
+    ) -> Result<ClusterCheckpoint> {
+        let nodes = self.stop_all_nodes();
+        let checkpoint = ClusterCheckpoint {
+            dir: dir.to_path_buf(),
+            validators: nodes.iter().map(|(pubkey, _)| *pubkey).collect(),
+            validator_keys: nodes
+                .iter()
+                .map(|(_, node)| {
+                    (
+                        node.info.keypair.clone(),
+                        node.info.voting_keypair.clone(),
+                        safe_clone_config(&node.config),
+                    )
+                })
+                .collect(),
+            funding_keypair: self.funding_keypair.insecure_clone(),
+            genesis_config: self.genesis_config.clone(),
+            quic_connection_cache_config: self.quic_connection_cache_config.clone(),
+            tpu_connection_pool_size: self.tpu_connection_pool_size,
+            #[cfg(feature = "dev-context-only-utils")]
+            network_conditions: self.network_conditioners.initial_conditions,
+        };
+        let result = nodes.iter().try_for_each(|(pubkey, node)| {
+            info!("Checkpointing the ledger of {pubkey}");
+            copy_dir(&node.info.ledger_path, &checkpoint.ledger_path(pubkey))
+        });
+        self.restart_nodes(nodes, socket_addr_space);
+        result.map(|()| checkpoint)
+    }
+
+    /// Stops all the validators, replaces the ledger directories of those in `checkpoint` with
+    /// their copy, and restarts them.
+    pub fn restore(
+        &mut self,
+        checkpoint: &ClusterCheckpoint,
+        socket_addr_space: SocketAddrSpace,
+    ) -> Result<()> {
+        if let Some(pubkey) = checkpoint
+            .validators
+            .iter()
+            .find(|pubkey| !self.validators.contains_key(pubkey))
+        {
+            return Err(Error::new(
+                ErrorKind::NotFound,
+                format!("validator {pubkey} of the checkpoint is not part of the cluster"),
+            ));
+        }
+        let nodes = self.stop_all_nodes();
+        let result = nodes
+            .iter()
+            .filter(|(pubkey, _)| checkpoint.validators.contains(pubkey))
+            .try_for_each(|(pubkey, node)| {
+                info!("Restoring the ledger of {pubkey}");
+                let ledger_path = &node.info.ledger_path;
+                fs::remove_dir_all(ledger_path)?;
+                copy_dir(&checkpoint.ledger_path(pubkey), ledger_path)
+            });
+        self.restart_nodes(nodes, socket_addr_space);
+        result
+    }
+
+    /// Starts a new cluster whose validators pick up from copies of their ledger directories in
+    /// `checkpoint`, which is left untouched so that other clusters can start from it too.
+    pub fn from_checkpoint(
+        checkpoint: &ClusterCheckpoint,
+        socket_addr_space: SocketAddrSpace,
+    ) -> Result<Self> {
+        let entry_point = *checkpoint
+            .validators
+            .first()
+            .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "checkpoint has no validators"))?;
+        let mut cluster = Self {
+            funding_keypair: checkpoint.funding_keypair.insecure_clone(),
+            // Replaced once the entry point starts
+            entry_point_info: ContactInfo::new_localhost(&entry_point, 0),
+            validators: HashMap::new(),
+            genesis_config: checkpoint.genesis_config.clone(),
+            connection_cache: create_connection_cache(
+                &checkpoint.quic_connection_cache_config,
+                checkpoint.tpu_connection_pool_size,
+            ),
+            quic_connection_cache_config: checkpoint.quic_connection_cache_config.clone(),
+            tpu_connection_pool_size: checkpoint.tpu_connection_pool_size,
+            #[cfg(feature = "dev-context-only-utils")]
+            network_conditioners: NetworkConditioners::new(checkpoint.network_conditions),
+        };
+        for (pubkey, (keypair, voting_keypair, config)) in
+            checkpoint.validators.iter().zip(&checkpoint.validator_keys)
+        {
+            let ledger_path = get_tmp_ledger_path!();
+            info!(
+                "Starting {pubkey} from its checkpointed ledger in {}",
+                ledger_path.display()
+            );
+            copy_dir(&checkpoint.ledger_path(pubkey), &ledger_path)?;
+            let node = ClusterValidatorInfo {
+                info: ValidatorInfo {
+                    keypair: keypair.clone(),
+                    voting_keypair: voting_keypair.clone(),
+                    ledger_path,
+                    // Replaced once the validator starts
+                    contact_info: ContactInfo::new_localhost(pubkey, 0),
+                },
+                config: safe_clone_config(config),
+                validator: None,
+            };
+            cluster.restart_node(pubkey, node, socket_addr_space);
+        }
+        Ok(cluster)
+    }
+
+    /// Stops all the validators and takes them out of the cluster, the entry point first
+    fn stop_all_nodes(&mut self) -> Vec<(Pubkey, ClusterValidatorInfo)> {
+        self.exit();
+        let mut nodes: Vec<_> = self.validators.drain().collect();
+        for (_, node) in nodes.iter_mut() {
+            if let Some(validator) = node.validator.take() {
+                validator.join();
+            }
+        }
+        // Restarting the entry point first lets the other validators find it
+        let entry_point = *self.entry_point_info.pubkey();
+        nodes.sort_by_key(|(pubkey, _)| (*pubkey != entry_point, *pubkey));
+        nodes
+    }
+
+    fn restart_nodes(
+        &mut self,
+        nodes: Vec<(Pubkey, ClusterValidatorInfo)>,
+        socket_addr_space: SocketAddrSpace,
+    ) {
+        for (pubkey, node) in nodes {
+            self.restart_node(&pubkey, node, socket_addr_space);
+        }
+    }
+
     /// Set up validator without voting or staking accounts
     pub fn add_validator_listener(
         &mut self,
@@ -509,6 +806,8 @@ impl LocalCluster {
         }
         let validator_pubkey = validator_keypair.pubkey();
         let validator_node = Node::new_localhost_with_pubkey(&validator_keypair.pubkey());
+        #[cfg(feature = "dev-context-only-utils")]
+        self.network_conditioners.condition_node(&validator_node);
         let contact_info = validator_node.info.clone();
         let (ledger_path, _blockhash) = create_new_tmp_ledger_with_size!(
             &self.genesis_config,
@@ -593,6 +892,20 @@ impl LocalCluster {
             .clone()
     }
 
+    /// Changes the simulated network conditions of the node's gossip, turbine and UDP TPU
+    /// sockets. Requires `ClusterConfig::network_conditions` to be set.
+    #[cfg(feature = "dev-context-only-utils")]
+    pub fn set_network_conditions(&self, pubkey: &Pubkey, conditions: NetworkConditions) {
+        self.network_conditioners
+            .conditioner(pubkey)
+            .set_conditions(conditions);
+    }
+
+    #[cfg(feature = "dev-context-only-utils")]
+    pub fn network_conditions(&self, pubkey: &Pubkey) -> NetworkConditions {
+        self.network_conditioners.conditioner(pubkey).conditions()
+    }
+
     fn close(&mut self) {
         self.close_preserve_ledgers();
     }
@@ -1037,6 +1350,8 @@ impl Cluster for LocalCluster {
     ) -> (Node, Vec<ContactInfo>) {
         // Update the stored ContactInfo for this node
         let node = Node::new_localhost_with_pubkey(pubkey);
+        #[cfg(feature = "dev-context-only-utils")]
+        self.network_conditioners.condition_node(&node);
         cluster_validator_info.info.contact_info = node.info.clone();
         cluster_validator_info.config.rpc_addrs =
             Some((node.info.rpc().unwrap(), node.info.rpc_pubsub().unwrap()));
@@ -1158,6 +1473,50 @@ impl Cluster for LocalCluster {
     }
 }
 
+/// Copies the directory `src` to `dst`. Sockets and other special files are skipped.
+///
+/// The targets of symlinks are copied too, so that the copy does not share any state with
+/// `src`. A symlink to a path within `src`, such as one from a bank snapshot to its account
+/// storages, is recreated pointing to the copy of that path. The target of any other symlink
+/// is copied in place of the symlink.
+fn copy_dir(src: &Path, dst: &Path) -> Result<()> {
+    copy_dir_within(src, dst, src, dst)
+}
+
+fn copy_dir_within(src: &Path, dst: &Path, src_root: &Path, dst_root: &Path) -> Result<()> {
+    fs::create_dir_all(dst)?;
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        let dst = dst.join(entry.file_name());
+        if file_type.is_dir() {
+            copy_dir_within(&entry.path(), &dst, src_root, dst_root)?;
+        } else if file_type.is_symlink() {
+            let target = src.join(fs::read_link(entry.path())?);
+            match target.strip_prefix(src_root) {
+                #[cfg(unix)]
+                Ok(relative_target) => {
+                    std::os::unix::fs::symlink(dst_root.join(relative_target), &dst)?
+                }
+                #[cfg(not(unix))]
+                Ok(_) => {
+                    return Err(Error::new(
+                        ErrorKind::Unsupported,
+                        format!("cannot copy symlink {}", entry.path().display()),
+                    ))
+                }
+                Err(_) if target.is_dir() => copy_dir(&target, &dst)?,
+                Err(_) => {
+                    fs::copy(&target, &dst)?;
+                }
+            }
+        } else if file_type.is_file() {
+            fs::copy(entry.path(), &dst)?;
+        }
+    }
+    Ok(())
+}
+
 impl Drop for LocalCluster {
     fn drop(&mut self) {
         self.close();
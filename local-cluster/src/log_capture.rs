@@ -0,0 +1,267 @@
+//! Capture of the log records emitted while a test runs, so that tests can
+//! assert on expected log events instead of redirecting and searching stderr.
+//!
+//! The validators of a [`LocalCluster`](crate::local_cluster::LocalCluster)
+//! run in the test process and share its logger, so a capture receives the
+//! matching records of every validator in the cluster.
+//!
+//! While a [`LogCapture`] is alive, `solana_logger` logs to a file owned by
+//! the capture, which is tailed by a background thread: each line is echoed to
+//! stderr, and matching records are handed to the capture. Once dropped,
+//! logging goes back to stderr. Only one capture is active at a time, as
+//! starting another one, or setting up logging again, redirects the logger.
+
+use {
+    crossbeam_channel::{unbounded, Receiver, RecvTimeoutError, Sender},
+    log::Level,
+    std::{
+        fs::File,
+        io::{self, BufRead, BufReader, Write},
+        str::FromStr,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        },
+        thread::{self, Builder, JoinHandle},
+        time::{Duration, Instant},
+    },
+    tempfile::TempDir,
+};
+
+const LOG_FILE_NAME: &str = "capture.log";
+// How often the log file is checked for new records once fully read.
+const TAIL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// A log record captured by a [`LogCapture`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LogEvent {
+    pub level: Level,
+    pub target: String,
+    pub message: String,
+}
+
+/// The records logged with a given target and level while alive
+pub struct LogCapture {
+    filter: String,
+    receiver: Receiver<LogEvent>,
+    exit: Arc<AtomicBool>,
+    thread_hdl: Option<JoinHandle<()>>,
+    // Removed once the tailing thread is done with the log file.
+    _log_dir: TempDir,
+}
+
+impl LogCapture {
+    /// Logs with `filter` as the default filter, overridden by `RUST_LOG`, as
+    /// `solana_logger::setup_with_default` does, and captures the records
+    /// logged from now on with `target`, or one of its submodules, at `level`
+    /// or more severe. An empty `target` matches all records.
+    ///
+    /// Records of `target` at `level` are logged regardless of `filter`,
+    /// unless `RUST_LOG` is set.
+    pub fn start(filter: &str, target: &str, level: Level) -> io::Result<Self> {
+        let log_dir = tempfile::tempdir()?;
+        let log_path = log_dir.path().join(LOG_FILE_NAME);
+        let log_file = File::create(&log_path)?;
+        let capture_filter = if target.is_empty() {
+            format!("{filter},{level}")
+        } else {
+            format!("{filter},{target}={level}")
+        };
+        solana_logger::setup_file_with_default(log_path.to_str().unwrap(), &capture_filter);
+
+        let (sender, receiver) = unbounded();
+        let exit = Arc::new(AtomicBool::new(false));
+        let matcher = Matcher {
+            target: target.to_string(),
+            level,
+        };
+        let thread_hdl = Builder::new().name("solLogCapture".to_string()).spawn({
+            let exit = exit.clone();
+            move || tail_log_file(log_file, matcher, sender, exit)
+        })?;
+        Ok(Self {
+            filter: filter.to_string(),
+            receiver,
+            exit,
+            thread_hdl: Some(thread_hdl),
+            _log_dir: log_dir,
+        })
+    }
+
+    /// Returns the records received and not yet returned by this capture
+    pub fn events(&self) -> Vec<LogEvent> {
+        self.receiver.try_iter().collect()
+    }
+
+    /// Waits up to `timeout` for a record matching `predicate`, consuming the
+    /// records received before it
+    pub fn wait_for(
+        &self,
+        timeout: Duration,
+        predicate: impl Fn(&LogEvent) -> bool,
+    ) -> Option<LogEvent> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            match self.receiver.recv_deadline(deadline) {
+                Ok(event) if predicate(&event) => return Some(event),
+                Ok(_) => (),
+                Err(RecvTimeoutError::Timeout | RecvTimeoutError::Disconnected) => return None,
+            }
+        }
+    }
+
+    /// Panics unless a record whose message contains `expected` is received
+    /// within `timeout`
+    pub fn assert_logged(&self, expected: &str, timeout: Duration) -> LogEvent {
+        self.wait_for(timeout, |event| event.message.contains(expected))
+            .unwrap_or_else(|| {
+                panic!("expected log message not found within {timeout:?}: {expected}")
+            })
+    }
+
+    /// Panics if a record whose message contains `unexpected` was received
+    /// and not yet consumed
+    pub fn assert_not_logged(&self, unexpected: &str) {
+        if let Some(event) = self
+            .events()
+            .into_iter()
+            .find(|event| event.message.contains(unexpected))
+        {
+            panic!("unexpected log message: {event:?}");
+        }
+    }
+}
+
+impl Drop for LogCapture {
+    fn drop(&mut self) {
+        solana_logger::setup_with_default(&self.filter);
+        self.exit.store(true, Ordering::Relaxed);
+        if let Some(thread_hdl) = self.thread_hdl.take() {
+            let _ = thread_hdl.join();
+        }
+    }
+}
+
+struct Matcher {
+    target: String,
+    level: Level,
+}
+
+impl Matcher {
+    fn matches(&self, event: &LogEvent) -> bool {
+        event.level <= self.level
+            && (self.target.is_empty()
+                || event
+                    .target
+                    .strip_prefix(&self.target)
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with("::")))
+    }
+}
+
+/// Reads the records logged to `log_file` until `exit` is set and the file is
+/// fully read, echoing them to stderr and sending the matching ones
+fn tail_log_file(
+    log_file: File,
+    matcher: Matcher,
+    sender: Sender<LogEvent>,
+    exit: Arc<AtomicBool>,
+) {
+    let mut reader = BufReader::new(log_file);
+    let mut line = String::new();
+    // Messages may span several lines, so a record is only complete once the
+    // next one starts, or once the file is fully read.
+    let mut pending: Option<LogEvent> = None;
+    let send_pending = |pending: &mut Option<LogEvent>| {
+        if let Some(event) = pending.take().filter(|event| matcher.matches(event)) {
+            let _ = sender.send(event);
+        }
+    };
+    loop {
+        match reader.read_line(&mut line) {
+            Ok(_) if line.ends_with('\n') => {
+                let _ = io::stderr().write_all(line.as_bytes());
+                match parse_record_header(&line) {
+                    Some(event) => {
+                        send_pending(&mut pending);
+                        pending = Some(event);
+                    }
+                    None => {
+                        if let Some(event) = &mut pending {
+                            event.message.push('\n');
+                            event.message.push_str(line.trim_end_matches('\n'));
+                        }
+                    }
+                }
+                line.clear();
+            }
+            // Fully read, keep any partial line until the rest is written.
+            Ok(_) => {
+                send_pending(&mut pending);
+                if exit.load(Ordering::Relaxed) {
+                    break;
+                }
+                thread::sleep(TAIL_INTERVAL);
+            }
+            Err(_) => break,
+        }
+    }
+}
+
+/// Parses the first line of a record logged by `solana_logger`, such as
+/// `[2025-01-01T00:00:00.000000000Z INFO  solana_core::validator] message`
+fn parse_record_header(line: &str) -> Option<LogEvent> {
+    let (header, message) = line.strip_prefix('[')?.split_once("] ")?;
+    let mut fields = header.split_whitespace();
+    let _timestamp = fields.next()?;
+    let level = Level::from_str(fields.next()?).ok()?;
+    let target = fields.next()?;
+    if fields.next().is_some() {
+        return None;
+    }
+    Some(LogEvent {
+        level,
+        target: target.to_string(),
+        message: message.trim_end_matches('\n').to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, log::*};
+
+    #[test]
+    fn test_parse_record_header() {
+        assert_eq!(
+            parse_record_header(
+                "[2025-01-01T00:00:00.000000000Z INFO  solana_core::validator] started [1/2]\n"
+            ),
+            Some(LogEvent {
+                level: Level::Info,
+                target: "solana_core::validator".to_string(),
+                message: "started [1/2]".to_string(),
+            })
+        );
+        assert_eq!(parse_record_header("continued message\n"), None);
+        assert_eq!(parse_record_header("[not a header] message\n"), None);
+    }
+
+    #[test]
+    fn test_log_capture() {
+        let capture = LogCapture::start("error", module_path!(), Level::Info).unwrap();
+
+        info!("expected {}", 1);
+        debug!("too verbose");
+        log!(target: "solana_local_cluster_other", Level::Error, "other target");
+        info!("multi\nline");
+
+        let event = capture.assert_logged("expected", Duration::from_secs(5));
+        assert_eq!(event.level, Level::Info);
+        assert_eq!(event.target, module_path!());
+        assert_eq!(event.message, "expected 1");
+        let event = capture.assert_logged("multi", Duration::from_secs(5));
+        assert_eq!(event.message, "multi\nline");
+        assert_eq!(capture.events(), vec![]);
+        assert_eq!(capture.wait_for(Duration::from_millis(10), |_| true), None);
+        capture.assert_not_logged("too verbose");
+    }
+}
@@ -1,7 +1,10 @@
 use {
     solana_core::validator::ValidatorConfig,
     solana_sdk::exit::Exit,
-    std::sync::{Arc, RwLock},
+    std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, RwLock,
+    },
 };
 
 pub fn safe_clone_config(config: &ValidatorConfig) -> ValidatorConfig {
@@ -9,6 +12,7 @@ pub fn safe_clone_config(config: &ValidatorConfig) -> ValidatorConfig {
         halt_at_slot: config.halt_at_slot,
         expected_genesis_hash: config.expected_genesis_hash,
         expected_bank_hash: config.expected_bank_hash,
+        snapshot_bank_hash_source: config.snapshot_bank_hash_source.clone(),
         expected_shred_version: config.expected_shred_version,
         voting_disabled: config.voting_disabled,
         account_paths: config.account_paths.clone(),
@@ -18,6 +22,8 @@ pub fn safe_clone_config(config: &ValidatorConfig) -> ValidatorConfig {
         geyser_plugin_always_enabled: config.geyser_plugin_always_enabled,
         rpc_addrs: config.rpc_addrs,
         pubsub_config: config.pubsub_config.clone(),
+        accounts_replication_config: config.accounts_replication_config.clone(),
+        accounts_replica_config: config.accounts_replica_config.clone(),
         snapshot_config: config.snapshot_config.clone(),
         max_ledger_shreds: config.max_ledger_shreds,
         blockstore_options: config.blockstore_options.clone(),
@@ -38,6 +44,8 @@ pub fn safe_clone_config(config: &ValidatorConfig) -> ValidatorConfig {
         debug_keys: config.debug_keys.clone(),
         contact_debug_interval: config.contact_debug_interval,
         contact_save_interval: config.contact_save_interval,
+        gossip_bandwidth: config.gossip_bandwidth,
+        gossip_peer_scores: config.gossip_peer_scores.clone(),
         send_transaction_service_config: config.send_transaction_service_config.clone(),
         no_poh_speed_test: config.no_poh_speed_test,
         no_os_memory_stats_reporting: config.no_os_memory_stats_reporting,
@@ -58,11 +66,15 @@ pub fn safe_clone_config(config: &ValidatorConfig) -> ValidatorConfig {
         accounts_db_config: config.accounts_db_config.clone(),
         wait_to_vote_slot: config.wait_to_vote_slot,
         runtime_config: config.runtime_config.clone(),
+        bank_config: config.bank_config.clone(),
         banking_trace_dir_byte_limit: config.banking_trace_dir_byte_limit,
         block_verification_method: config.block_verification_method.clone(),
         block_production_method: config.block_production_method.clone(),
         transaction_struct: config.transaction_struct.clone(),
         enable_block_production_forwarding: config.enable_block_production_forwarding,
+        transaction_spill_config: config.transaction_spill_config.clone(),
+        prio_graph_scheduler_config: config.prio_graph_scheduler_config.clone(),
+        write_fenced_accounts: config.write_fenced_accounts.clone(),
         generator_config: config.generator_config.clone(),
         use_snapshot_archives_at_startup: config.use_snapshot_archives_at_startup,
         wen_restart_proto_path: config.wen_restart_proto_path.clone(),
@@ -73,8 +85,11 @@ pub fn safe_clone_config(config: &ValidatorConfig) -> ValidatorConfig {
         replay_forks_threads: config.replay_forks_threads,
         replay_transactions_threads: config.replay_transactions_threads,
         tvu_shred_sigverify_threads: config.tvu_shred_sigverify_threads,
+        turbine_use_quic: config.turbine_use_quic,
         thread_manager_config: config.thread_manager_config.clone(),
         delay_leader_block_for_pending_fork: config.delay_leader_block_for_pending_fork,
+        port_mapping_config: config.port_mapping_config.clone(),
+        leader_prewarm_config: config.leader_prewarm_config.clone(),
     }
 }
 
@@ -84,7 +99,12 @@ pub fn make_identical_validator_configs(
 ) -> Vec<ValidatorConfig> {
     let mut configs = vec![];
     for _ in 0..num {
-        configs.push(safe_clone_config(config));
+        let mut config = safe_clone_config(config);
+        // Each node gets its own flag, so that nodes can be partitioned individually
+        config.turbine_disabled = Arc::new(AtomicBool::new(
+            config.turbine_disabled.load(Ordering::Relaxed),
+        ));
+        configs.push(config);
     }
     configs
 }
@@ -2,7 +2,6 @@
 use {
     assert_matches::assert_matches,
     crossbeam_channel::{unbounded, Receiver},
-    gag::BufferRedirect,
     itertools::Itertools,
     log::*,
     rand::seq::SliceRandom,
@@ -11,6 +10,7 @@ use {
         hardened_unpack::open_genesis_config, utils::create_accounts_run_and_snapshot_dirs,
     },
     solana_core::{
+        accounts_replication_service::{AccountsReplicaConfig, AccountsReplicationConfig},
         consensus::{
             tower_storage::FileTowerStorage, Tower, SWITCH_FORK_THRESHOLD, VOTE_THRESHOLD_DEPTH,
         },
@@ -31,6 +31,7 @@ use {
         use_snapshot_archives_at_startup::UseSnapshotArchivesAtStartup,
     },
     solana_local_cluster::{
+        chaos::ChaosScenario,
         cluster::{Cluster, ClusterValidatorInfo, QuicTpuClient},
         cluster_tests,
         integration_tests::{
@@ -44,6 +45,7 @@ use {
             ValidatorTestConfig, DEFAULT_NODE_STAKE, RUST_LOG_FILTER,
         },
         local_cluster::{ClusterConfig, LocalCluster, DEFAULT_MINT_LAMPORTS},
+        log_capture::LogCapture,
         validator_configs::*,
     },
     solana_pubsub_client::pubsub_client::PubsubClient,
@@ -71,6 +73,7 @@ use {
         genesis_config::ClusterType,
         hard_forks::HardForks,
         hash::Hash,
+        native_token::LAMPORTS_PER_SOL,
         poh_config::PohConfig,
         pubkey::Pubkey,
         signature::{Keypair, Signer},
@@ -78,7 +81,7 @@ use {
         vote::state::TowerSync,
     },
     solana_stake_program::stake_state::NEW_WARMUP_COOLDOWN_RATE,
-    solana_streamer::socket::SocketAddrSpace,
+    solana_streamer::{network_conditions::NetworkConditions, socket::SocketAddrSpace},
     solana_turbine::broadcast_stage::{
         broadcast_duplicates_run::{BroadcastDuplicatesConfig, ClusterPartition},
         BroadcastStageType,
@@ -87,13 +90,12 @@ use {
     solana_vote_program::vote_state::MAX_LOCKOUT_HISTORY,
     std::{
         collections::{BTreeSet, HashMap, HashSet},
-        fs,
-        io::Read,
-        iter,
+        fs, iter,
+        net::TcpListener,
         path::Path,
         sync::{
             atomic::{AtomicBool, AtomicUsize, Ordering},
-            Arc, Mutex,
+            Arc,
         },
         thread::{sleep, Builder, JoinHandle},
         time::{Duration, Instant},
@@ -201,6 +203,89 @@ fn test_spend_and_verify_all_nodes_3() {
     );
 }
 
+fn run_spend_and_verify_all_nodes_with_turbine_transport(turbine_use_quic: bool) {
+    let num_nodes = 3;
+    let validator_config = ValidatorConfig {
+        turbine_use_quic,
+        ..ValidatorConfig::default_for_test()
+    };
+    let mut config = ClusterConfig {
+        validator_configs: make_identical_validator_configs(&validator_config, num_nodes),
+        ..ClusterConfig::new_with_equal_stakes(num_nodes, DEFAULT_MINT_LAMPORTS, DEFAULT_NODE_STAKE)
+    };
+    let local = LocalCluster::new(&mut config, SocketAddrSpace::Unspecified);
+    cluster_tests::spend_and_verify_all_nodes(
+        &local.entry_point_info,
+        &local.funding_keypair,
+        num_nodes,
+        HashSet::new(),
+        SocketAddrSpace::Unspecified,
+        &local.connection_cache,
+    );
+    local.check_for_new_roots(16, "turbine_transport", SocketAddrSpace::Unspecified);
+}
+
+#[test]
+#[serial]
+fn test_checkpoint_restore() {
+    solana_logger::setup_with_default(RUST_LOG_FILTER);
+    let mut cluster = LocalCluster::new_with_equal_stakes(
+        2,
+        DEFAULT_MINT_LAMPORTS,
+        DEFAULT_NODE_STAKE,
+        SocketAddrSpace::Unspecified,
+    );
+    cluster.check_for_new_roots(8, "checkpoint_restore", SocketAddrSpace::Unspecified);
+
+    let checkpoint_dir = tempfile::tempdir_in(farf_dir()).unwrap();
+    let checkpoint = cluster
+        .checkpoint(checkpoint_dir.path(), SocketAddrSpace::Unspecified)
+        .unwrap();
+    assert_eq!(checkpoint.validators().len(), 2);
+    let checkpoint_last_votes: HashMap<_, _> = checkpoint
+        .validators()
+        .iter()
+        .map(|pubkey| {
+            let (last_vote, _) =
+                last_vote_in_tower(&checkpoint.ledger_path(pubkey), pubkey).unwrap();
+            (*pubkey, last_vote)
+        })
+        .collect();
+
+    // The cluster keeps going after the checkpoint
+    cluster.check_for_new_roots(8, "checkpoint_restore", SocketAddrSpace::Unspecified);
+    for (pubkey, checkpoint_last_vote) in &checkpoint_last_votes {
+        let (last_vote, _) = last_vote_in_tower(&cluster.ledger_path(pubkey), pubkey).unwrap();
+        assert!(last_vote > *checkpoint_last_vote);
+    }
+
+    // And makes progress again once rewound to it
+    cluster
+        .restore(&checkpoint, SocketAddrSpace::Unspecified)
+        .unwrap();
+    cluster.check_for_new_roots(8, "checkpoint_restore", SocketAddrSpace::Unspecified);
+
+    // A new cluster can start from it as well
+    drop(cluster);
+    let mut cluster =
+        LocalCluster::from_checkpoint(&checkpoint, SocketAddrSpace::Unspecified).unwrap();
+    cluster.check_for_new_roots(8, "checkpoint_restore", SocketAddrSpace::Unspecified);
+}
+
+#[test]
+#[serial]
+fn test_spend_and_verify_all_nodes_turbine_udp() {
+    solana_logger::setup_with_default(RUST_LOG_FILTER);
+    run_spend_and_verify_all_nodes_with_turbine_transport(/*turbine_use_quic:*/ false);
+}
+
+#[test]
+#[serial]
+fn test_spend_and_verify_all_nodes_turbine_quic() {
+    solana_logger::setup_with_default(RUST_LOG_FILTER);
+    run_spend_and_verify_all_nodes_with_turbine_transport(/*turbine_use_quic:*/ true);
+}
+
 #[test]
 #[serial]
 fn test_local_cluster_signature_subscribe() {
@@ -550,6 +635,124 @@ fn test_snapshot_download() {
     );
 }
 
+#[test]
+#[serial]
+fn test_accounts_replica() {
+    solana_logger::setup_with_default(RUST_LOG_FILTER);
+    let snapshot_interval_slots = 50;
+    let num_account_paths = 3;
+
+    // Pick a free port for the leader to stream its accounts from
+    let replication_addr = TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap();
+    let mut leader_snapshot_test_config =
+        setup_snapshot_validator_config(snapshot_interval_slots, num_account_paths);
+    leader_snapshot_test_config
+        .validator_config
+        .accounts_replication_config = Some(AccountsReplicationConfig {
+        addr: replication_addr,
+        // Enough for the replica to catch up from the snapshot it starts from
+        catch_up_slots: 10_000,
+    });
+    let mut replica_snapshot_test_config =
+        setup_snapshot_validator_config(snapshot_interval_slots, num_account_paths);
+
+    let mut config = ClusterConfig {
+        node_stakes: vec![DEFAULT_NODE_STAKE],
+        validator_configs: make_identical_validator_configs(
+            &leader_snapshot_test_config.validator_config,
+            1,
+        ),
+        ..ClusterConfig::default()
+    };
+    let mut cluster = LocalCluster::new(&mut config, SocketAddrSpace::Unspecified);
+
+    let full_snapshot_archive_info = cluster.wait_for_next_full_snapshot(
+        &leader_snapshot_test_config
+            .validator_config
+            .snapshot_config
+            .full_snapshot_archives_dir,
+        Some(Duration::from_secs(5 * 60)),
+    );
+    let replica_snapshot_config = &replica_snapshot_test_config
+        .validator_config
+        .snapshot_config;
+    download_snapshot_archive(
+        &cluster.entry_point_info.rpc().unwrap(),
+        &replica_snapshot_config.full_snapshot_archives_dir,
+        &replica_snapshot_config.incremental_snapshot_archives_dir,
+        (
+            full_snapshot_archive_info.slot(),
+            *full_snapshot_archive_info.hash(),
+        ),
+        SnapshotKind::FullSnapshot,
+        replica_snapshot_config.maximum_full_snapshot_archives_to_retain,
+        replica_snapshot_config.maximum_incremental_snapshot_archives_to_retain,
+        false,
+        &mut None,
+    )
+    .unwrap();
+
+    // Boot a replica of the leader from that snapshot, which takes its banks
+    // from the leader's stream rather than replaying the ledger
+    let replica_config = &mut replica_snapshot_test_config.validator_config;
+    replica_config.voting_disabled = true;
+    replica_config.accounts_replica_config = Some(AccountsReplicaConfig {
+        source: replication_addr,
+        source_identity: *cluster.entry_point_info.pubkey(),
+    });
+    let replica_pubkey = cluster.add_validator_listener(
+        replica_config,
+        0,
+        Arc::new(Keypair::new()),
+        None,
+        SocketAddrSpace::Unspecified,
+    );
+    let replica_rpc_client = RpcClient::new_socket(
+        cluster.validators[&replica_pubkey]
+            .info
+            .contact_info
+            .rpc()
+            .unwrap(),
+    );
+
+    // Accounts changed by the leader after the snapshot show up on the replica
+    let recipient = Pubkey::new_unique();
+    let lamports = 42 * LAMPORTS_PER_SOL;
+    cluster.transfer(&cluster.funding_keypair, &recipient, lamports);
+    let start = Instant::now();
+    loop {
+        let balance = replica_rpc_client
+            .get_balance_with_commitment(&recipient, CommitmentConfig::processed())
+            .map(|response| response.value);
+        if balance.as_ref().ok() == Some(&lamports) {
+            break;
+        }
+        assert!(
+            start.elapsed() < Duration::from_secs(60),
+            "replica balance of {recipient}: {balance:?}"
+        );
+        sleep(Duration::from_millis(500));
+    }
+
+    // And the replica roots what the leader roots
+    let leader_rpc_client = RpcClient::new_socket(cluster.entry_point_info.rpc().unwrap());
+    let leader_root = leader_rpc_client
+        .get_slot_with_commitment(CommitmentConfig::finalized())
+        .unwrap();
+    let start = Instant::now();
+    while replica_rpc_client
+        .get_slot_with_commitment(CommitmentConfig::finalized())
+        .unwrap()
+        < leader_root
+    {
+        assert!(start.elapsed() < Duration::from_secs(60));
+        sleep(Duration::from_millis(500));
+    }
+}
+
 #[test]
 #[serial]
 fn test_incremental_snapshot_download() {
@@ -1744,10 +1947,13 @@ fn test_optimistic_confirmation_violation_detection() {
     };
 
     {
-        // Buffer stderr to detect optimistic slot violation log
-        let buf = std::env::var("OPTIMISTIC_CONF_TEST_DUMP_LOG")
-            .err()
-            .map(|_| BufferRedirect::stderr().unwrap());
+        // Capture logs to detect optimistic slot violation log
+        let violation_logs = LogCapture::start(
+            RUST_LOG_FILTER,
+            "solana_core::optimistic_confirmation_verifier",
+            Level::Error,
+        )
+        .unwrap();
 
         // In order to prevent the node from voting on a slot it's already voted on
         // which can potentially cause a panic in gossip, start up the validator as a
@@ -1836,24 +2042,8 @@ fn test_optimistic_confirmation_violation_detection() {
             OptimisticConfirmationVerifier::format_optimistic_confirmed_slot_violation_log(
                 optimistically_confirmed_slot,
             );
-        // Violation detection thread can be behind so poll logs up to 10 seconds
-        if let Some(mut buf) = buf {
-            let start = Instant::now();
-            let mut success = false;
-            let mut output = String::new();
-            while start.elapsed().as_secs() < 10 {
-                buf.read_to_string(&mut output).unwrap();
-                if output.contains(&expected_log) {
-                    success = true;
-                    break;
-                }
-                sleep(Duration::from_millis(10));
-            }
-            print!("{output}");
-            assert!(success);
-        } else {
-            panic!("dumped log and disabled testing");
-        }
+        // Violation detection thread can be behind so wait for the log up to 10 seconds
+        violation_logs.assert_logged(&expected_log, Duration::from_secs(10));
     }
 
     // Make sure validator still makes progress
@@ -2136,31 +2326,24 @@ fn test_future_tower_master_slave() {
 }
 
 fn restart_whole_cluster_after_hard_fork(
-    cluster: &Arc<Mutex<LocalCluster>>,
+    cluster: &mut LocalCluster,
     validator_a_pubkey: Pubkey,
     validator_b_pubkey: Pubkey,
     mut validator_a_info: ClusterValidatorInfo,
     validator_b_info: ClusterValidatorInfo,
 ) {
     // restart validator A first
-    let cluster_for_a = cluster.clone();
     let val_a_ledger_path = validator_a_info.info.ledger_path.clone();
+    let restart_context =
+        cluster.create_restart_context(&validator_a_pubkey, &mut validator_a_info);
 
     // Spawn a thread because wait_for_supermajority blocks in Validator::new()!
     let thread = std::thread::spawn(move || {
-        let restart_context = cluster_for_a
-            .lock()
-            .unwrap()
-            .create_restart_context(&validator_a_pubkey, &mut validator_a_info);
-        let restarted_validator_info = LocalCluster::restart_node_with_context(
+        LocalCluster::restart_node_with_context(
             validator_a_info,
             restart_context,
             SocketAddrSpace::Unspecified,
-        );
-        cluster_for_a
-            .lock()
-            .unwrap()
-            .add_node(&validator_a_pubkey, restarted_validator_info);
+        )
     });
 
     // test validator A actually to wait for supermajority
@@ -2178,14 +2361,15 @@ fn restart_whole_cluster_after_hard_fork(
     }
 
     // restart validator B normally
-    cluster.lock().unwrap().restart_node(
+    cluster.restart_node(
         &validator_b_pubkey,
         validator_b_info,
         SocketAddrSpace::Unspecified,
     );
 
     // validator A should now start so join its thread here
-    thread.join().unwrap();
+    let restarted_validator_info = thread.join().unwrap();
+    cluster.add_node(&validator_a_pubkey, restarted_validator_info);
 }
 
 #[test]
@@ -2226,72 +2410,130 @@ fn test_hard_fork_invalidates_tower() {
         skip_warmup_slots: true,
         ..ClusterConfig::default()
     };
-    let cluster = std::sync::Arc::new(std::sync::Mutex::new(LocalCluster::new(
-        &mut config,
-        SocketAddrSpace::Unspecified,
-    )));
-
-    let val_a_ledger_path = cluster.lock().unwrap().ledger_path(&validator_a_pubkey);
-
-    let min_root = 15;
-    loop {
-        sleep(Duration::from_millis(100));
-
-        if let Some(root) = root_in_tower(&val_a_ledger_path, &validator_a_pubkey) {
-            if root >= min_root {
-                break;
-            }
-        }
-    }
-
-    let mut validator_a_info = cluster.lock().unwrap().exit_node(&validator_a_pubkey);
-    let mut validator_b_info = cluster.lock().unwrap().exit_node(&validator_b_pubkey);
+    let mut cluster = LocalCluster::new(&mut config, SocketAddrSpace::Unspecified);
 
     // setup hard fork at slot < a previously rooted slot!
     // hard fork earlier than root is very unrealistic in the wild, but it's handy for
     // persistent tower's lockout behavior...
+    let min_root = 15;
     let hard_fork_slot = min_root - 5;
     let hard_fork_slots = Some(vec![hard_fork_slot]);
     let mut hard_forks = solana_sdk::hard_forks::HardForks::default();
     hard_forks.register(hard_fork_slot);
 
     let expected_shred_version = solana_sdk::shred_version::compute_shred_version(
-        &cluster.lock().unwrap().genesis_config.hash(),
+        &cluster.genesis_config.hash(),
         Some(&hard_forks),
     );
 
-    validator_a_info
-        .config
-        .new_hard_forks
-        .clone_from(&hard_fork_slots);
-    validator_a_info.config.wait_for_supermajority = Some(hard_fork_slot);
-    validator_a_info.config.expected_shred_version = Some(expected_shred_version);
+    ChaosScenario::new("test_hard_fork_invalidates_tower")
+        .check_min_root(min_root)
+        .kill(validator_a_pubkey)
+        .kill(validator_b_pubkey)
+        .custom(move |cluster, killed_nodes| {
+            let mut validator_a_info = killed_nodes.remove(&validator_a_pubkey).unwrap();
+            let mut validator_b_info = killed_nodes.remove(&validator_b_pubkey).unwrap();
 
-    validator_b_info.config.new_hard_forks = hard_fork_slots;
-    validator_b_info.config.wait_for_supermajority = Some(hard_fork_slot);
-    validator_b_info.config.expected_shred_version = Some(expected_shred_version);
+            validator_a_info
+                .config
+                .new_hard_forks
+                .clone_from(&hard_fork_slots);
+            validator_a_info.config.wait_for_supermajority = Some(hard_fork_slot);
+            validator_a_info.config.expected_shred_version = Some(expected_shred_version);
 
-    // Clear ledger of all slots post hard fork
-    {
-        let blockstore_a = open_blockstore(&validator_a_info.info.ledger_path);
-        let blockstore_b = open_blockstore(&validator_b_info.info.ledger_path);
-        purge_slots_with_count(&blockstore_a, hard_fork_slot + 1, 100);
-        purge_slots_with_count(&blockstore_b, hard_fork_slot + 1, 100);
-    }
+            validator_b_info.config.new_hard_forks = hard_fork_slots;
+            validator_b_info.config.wait_for_supermajority = Some(hard_fork_slot);
+            validator_b_info.config.expected_shred_version = Some(expected_shred_version);
 
-    restart_whole_cluster_after_hard_fork(
-        &cluster,
-        validator_a_pubkey,
-        validator_b_pubkey,
-        validator_a_info,
-        validator_b_info,
-    );
+            // Clear ledger of all slots post hard fork
+            {
+                let blockstore_a = open_blockstore(&validator_a_info.info.ledger_path);
+                let blockstore_b = open_blockstore(&validator_b_info.info.ledger_path);
+                purge_slots_with_count(&blockstore_a, hard_fork_slot + 1, 100);
+                purge_slots_with_count(&blockstore_b, hard_fork_slot + 1, 100);
+            }
 
-    // new slots should be rooted after hard-fork cluster relaunch
-    cluster
-        .lock()
-        .unwrap()
-        .check_for_new_roots(16, "hard fork", SocketAddrSpace::Unspecified);
+            restart_whole_cluster_after_hard_fork(
+                cluster,
+                validator_a_pubkey,
+                validator_b_pubkey,
+                validator_a_info,
+                validator_b_info,
+            );
+        })
+        // new slots should be rooted after hard-fork cluster relaunch
+        .check_new_roots(16)
+        .run(&mut cluster);
+}
+
+#[test]
+#[serial]
+fn test_chaos_scenario_kill_restart_partition() {
+    solana_logger::setup_with_default(RUST_LOG_FILTER);
+    let node_stakes = vec![
+        40 * DEFAULT_NODE_STAKE,
+        40 * DEFAULT_NODE_STAKE,
+        20 * DEFAULT_NODE_STAKE,
+    ];
+    let validator_keys: Vec<_> = iter::repeat_with(|| (Arc::new(Keypair::new()), true))
+        .take(node_stakes.len())
+        .collect();
+    let validators: Vec<_> = validator_keys
+        .iter()
+        .map(|(keypair, _)| keypair.pubkey())
+        .collect();
+    let validator_configs =
+        make_identical_validator_configs(&ValidatorConfig::default_for_test(), node_stakes.len());
+    let mut config = ClusterConfig {
+        mint_lamports: DEFAULT_MINT_LAMPORTS + node_stakes.iter().sum::<u64>(),
+        node_stakes,
+        validator_configs,
+        validator_keys: Some(validator_keys),
+        ..ClusterConfig::default()
+    };
+    let mut cluster = LocalCluster::new(&mut config, SocketAddrSpace::Unspecified);
+
+    let killed_nodes = ChaosScenario::new("test_chaos_scenario_kill_restart_partition")
+        .at_slot(10)
+        .kill(validators[2])
+        // The remaining 80% of the stake keeps rooting
+        .check_new_roots(8)
+        .after_slots(10)
+        .restart(validators[2])
+        .partition(&[validators[1]])
+        .after(Duration::from_secs(10))
+        .heal(&[validators[1]])
+        .check_new_roots(16)
+        .run(&mut cluster);
+    assert!(killed_nodes.is_empty());
+}
+
+#[test]
+#[serial]
+fn test_degraded_network_conditions() {
+    solana_logger::setup_with_default(RUST_LOG_FILTER);
+    let num_nodes = 3;
+    let degraded = NetworkConditions {
+        latency: Duration::from_millis(50),
+        jitter: Duration::from_millis(20),
+        packet_loss_percent: 5.0,
+    };
+    let mut config =
+        ClusterConfig::new_with_equal_stakes(num_nodes, DEFAULT_MINT_LAMPORTS, DEFAULT_NODE_STAKE);
+    config.network_conditions = Some(degraded);
+    let mut cluster = LocalCluster::new(&mut config, SocketAddrSpace::Unspecified);
+    cluster.check_for_new_roots(16, "degraded network", SocketAddrSpace::Unspecified);
+
+    // Consensus survives a single node on a much worse link
+    let node = *cluster.get_node_pubkeys().first().unwrap();
+    let worse = NetworkConditions {
+        latency: Duration::from_millis(200),
+        packet_loss_percent: 20.0,
+        ..degraded
+    };
+    cluster.set_network_conditions(&node, worse);
+    assert_eq!(cluster.network_conditions(&node), worse);
+    cluster.check_for_new_roots(16, "degraded network", SocketAddrSpace::Unspecified);
 }
 
 #[test]
@@ -2397,13 +2639,10 @@ fn test_hard_fork_with_gap_in_roots() {
         skip_warmup_slots: true,
         ..ClusterConfig::default()
     };
-    let cluster = std::sync::Arc::new(std::sync::Mutex::new(LocalCluster::new(
-        &mut config,
-        SocketAddrSpace::Unspecified,
-    )));
+    let mut cluster = LocalCluster::new(&mut config, SocketAddrSpace::Unspecified);
 
-    let val_a_ledger_path = cluster.lock().unwrap().ledger_path(&validator_a_pubkey);
-    let val_b_ledger_path = cluster.lock().unwrap().ledger_path(&validator_b_pubkey);
+    let val_a_ledger_path = cluster.ledger_path(&validator_a_pubkey);
+    let val_b_ledger_path = cluster.ledger_path(&validator_b_pubkey);
 
     let min_last_vote = 45;
     let min_root = 10;
@@ -2420,8 +2659,8 @@ fn test_hard_fork_with_gap_in_roots() {
     }
 
     // stop all nodes of the cluster
-    let mut validator_a_info = cluster.lock().unwrap().exit_node(&validator_a_pubkey);
-    let mut validator_b_info = cluster.lock().unwrap().exit_node(&validator_b_pubkey);
+    let mut validator_a_info = cluster.exit_node(&validator_a_pubkey);
+    let mut validator_b_info = cluster.exit_node(&validator_b_pubkey);
 
     // hard fork slot is effectively a (possibly skipping) new root.
     // assert that the precondition of validator a to test gap between
@@ -2434,7 +2673,7 @@ fn test_hard_fork_with_gap_in_roots() {
     hard_forks.register(hard_fork_slot);
 
     let expected_shred_version = solana_sdk::shred_version::compute_shred_version(
-        &cluster.lock().unwrap().genesis_config.hash(),
+        &cluster.genesis_config.hash(),
         Some(&hard_forks),
     );
 
@@ -2473,17 +2712,14 @@ fn test_hard_fork_with_gap_in_roots() {
     validator_b_info.config.expected_shred_version = Some(expected_shred_version);
 
     restart_whole_cluster_after_hard_fork(
-        &cluster,
+        &mut cluster,
         validator_a_pubkey,
         validator_b_pubkey,
         validator_a_info,
         validator_b_info,
     );
     // new slots should be rooted after hard-fork cluster relaunch
-    cluster
-        .lock()
-        .unwrap()
-        .check_for_new_roots(16, "hard fork", SocketAddrSpace::Unspecified);
+    cluster.check_for_new_roots(16, "hard fork", SocketAddrSpace::Unspecified);
 
     // drop everything to open blockstores below
     drop(cluster);
@@ -3,7 +3,9 @@ pub mod counter;
 pub mod datapoint;
 pub mod metrics;
 pub mod poh_timing_point;
-pub use crate::metrics::{flush, query, set_host_id, set_panic_hook, submit};
+pub use crate::metrics::{
+    catch_unwind_without_exit, flush, query, set_host_id, set_panic_hook, submit,
+};
 use std::sync::{
     atomic::{AtomicU64, Ordering},
     Arc,
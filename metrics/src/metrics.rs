@@ -9,11 +9,13 @@ use {
     solana_cluster_type::ClusterType,
     solana_sha256_hasher::hash,
     std::{
+        cell::Cell,
         cmp,
         collections::HashMap,
         convert::Into,
         env,
         fmt::Write,
+        panic::{self, UnwindSafe},
         sync::{Arc, Barrier, Mutex, Once, RwLock},
         thread,
         time::{Duration, Instant, UNIX_EPOCH},
@@ -23,6 +25,11 @@ use {
 
 type CounterMap = HashMap<(&'static str, u64), CounterPoint>;
 
+thread_local! {
+    // Set while running code whose panics are caught by `catch_unwind_without_exit()`
+    static PANIC_EXIT_SUPPRESSED: Cell<bool> = const { Cell::new(false) };
+}
+
 #[derive(Debug, Error)]
 pub enum MetricsError {
     #[error(transparent)]
@@ -536,12 +543,28 @@ pub fn set_panic_hook(program: &'static str, version: Option<String>) {
             // Flush metrics immediately
             flush();
 
+            if PANIC_EXIT_SUPPRESSED.with(Cell::get) {
+                // The caller is catching this panic and will handle it
+                return;
+            }
+
             // Exit cleanly so the process don't limp along in a half-dead state
             std::process::exit(1);
         }));
     });
 }
 
+/// Invokes `f`, catching any panic it raises.
+///
+/// Unlike `std::panic::catch_unwind()`, this prevents the hook installed by `set_panic_hook()`
+/// from exiting the process, so the panic is still reported but can be handled by the caller.
+pub fn catch_unwind_without_exit<R>(f: impl FnOnce() -> R + UnwindSafe) -> thread::Result<R> {
+    let previous = PANIC_EXIT_SUPPRESSED.with(|suppressed| suppressed.replace(true));
+    let result = panic::catch_unwind(f);
+    PANIC_EXIT_SUPPRESSED.with(|suppressed| suppressed.set(previous));
+    result
+}
+
 pub mod test_mocks {
     use super::*;
 
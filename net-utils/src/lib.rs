@@ -1,6 +1,7 @@
 //! The `net_utils` module assists with networking
 mod ip_echo_client;
 mod ip_echo_server;
+pub mod nat;
 
 pub use ip_echo_server::{
     ip_echo_server, IpEchoServer, DEFAULT_IP_ECHO_SERVER_THREADS, MAX_PORT_COUNT_PER_MESSAGE,
@@ -0,0 +1,671 @@
+//! NAT traversal helpers.
+//!
+//! Allows a node running behind a home router to ask the gateway to forward
+//! its ports, either with NAT-PMP (RFC 6886) or with UPnP IGD, and to learn
+//! the external address that should be advertised to the rest of the cluster.
+use {
+    anyhow::{anyhow, bail},
+    log::*,
+    std::{
+        fmt,
+        io::{Read, Write},
+        net::{IpAddr, Ipv4Addr, SocketAddr, SocketAddrV4, TcpStream, UdpSocket},
+        str::FromStr,
+        time::Duration,
+    },
+    url::Url,
+};
+
+/// Port the NAT-PMP server listens on at the gateway.
+pub const NAT_PMP_PORT: u16 = 5351;
+/// Default lifetime requested for port mappings.
+pub const DEFAULT_PORT_MAPPING_LEASE_DURATION: Duration = Duration::from_secs(3600);
+/// Description attached to UPnP port mappings.
+const PORT_MAPPING_DESCRIPTION: &str = "solana-validator";
+
+const NAT_PMP_VERSION: u8 = 0;
+const NAT_PMP_OP_EXTERNAL_ADDRESS: u8 = 0;
+const NAT_PMP_OP_MAP_UDP: u8 = 1;
+const NAT_PMP_OP_MAP_TCP: u8 = 2;
+const NAT_PMP_RESPONSE_FLAG: u8 = 128;
+// RFC 6886 section 3.1: initial retransmission after 250ms, doubling after
+// each attempt.
+const NAT_PMP_INITIAL_TIMEOUT: Duration = Duration::from_millis(250);
+const NAT_PMP_MAX_ATTEMPTS: usize = 5;
+
+const SSDP_MULTICAST_ADDR: SocketAddrV4 =
+    SocketAddrV4::new(Ipv4Addr::new(239, 255, 255, 250), 1900);
+const UPNP_TIMEOUT: Duration = Duration::from_secs(3);
+const UPNP_SEARCH_TARGET: &str = "urn:schemas-upnp-org:device:InternetGatewayDevice:1";
+const UPNP_WAN_SERVICE_TYPES: [&str; 3] = [
+    "urn:schemas-upnp-org:service:WANIPConnection:2",
+    "urn:schemas-upnp-org:service:WANIPConnection:1",
+    "urn:schemas-upnp-org:service:WANPPPConnection:1",
+];
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum PortMappingProtocol {
+    Udp,
+    Tcp,
+}
+
+impl PortMappingProtocol {
+    fn nat_pmp_opcode(&self) -> u8 {
+        match self {
+            Self::Udp => NAT_PMP_OP_MAP_UDP,
+            Self::Tcp => NAT_PMP_OP_MAP_TCP,
+        }
+    }
+}
+
+impl fmt::Display for PortMappingProtocol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Udp => write!(f, "UDP"),
+            Self::Tcp => write!(f, "TCP"),
+        }
+    }
+}
+
+/// How the gateway should be asked to forward ports.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum NatTraversalMethod {
+    /// Try NAT-PMP against the default gateway first, then UPnP.
+    #[default]
+    Auto,
+    /// NAT-PMP against the given gateway, or the default gateway if none.
+    NatPmp { gateway: Option<Ipv4Addr> },
+    /// UPnP IGD discovered through SSDP.
+    Upnp,
+}
+
+impl FromStr for NatTraversalMethod {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(Self::Auto),
+            "nat-pmp" => Ok(Self::NatPmp { gateway: None }),
+            "upnp" => Ok(Self::Upnp),
+            _ => Err(format!("invalid NAT traversal method: {s}")),
+        }
+    }
+}
+
+/// A port mapping granted by the gateway.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PortMapping {
+    pub protocol: PortMappingProtocol,
+    pub internal_port: u16,
+    pub external_port: u16,
+    pub lease_duration: Duration,
+}
+
+/// Client for the NAT-PMP protocol, RFC 6886.
+#[derive(Clone, Debug)]
+pub struct NatPmpClient {
+    gateway: SocketAddrV4,
+}
+
+impl NatPmpClient {
+    pub fn new(gateway: Ipv4Addr) -> Self {
+        Self {
+            gateway: SocketAddrV4::new(gateway, NAT_PMP_PORT),
+        }
+    }
+
+    pub fn gateway(&self) -> Ipv4Addr {
+        *self.gateway.ip()
+    }
+
+    /// Asks the gateway for its external IPv4 address.
+    pub fn external_address(&self) -> anyhow::Result<Ipv4Addr> {
+        let request = [NAT_PMP_VERSION, NAT_PMP_OP_EXTERNAL_ADDRESS];
+        let response = self.send_request(&request, 12)?;
+        parse_nat_pmp_external_address_response(&response)
+    }
+
+    /// Requests a mapping for `internal_port`. The gateway is free to assign
+    /// a different external port than the suggested one.
+    pub fn add_port_mapping(
+        &self,
+        protocol: PortMappingProtocol,
+        internal_port: u16,
+        suggested_external_port: u16,
+        lease_duration: Duration,
+    ) -> anyhow::Result<PortMapping> {
+        let request = encode_nat_pmp_mapping_request(
+            protocol,
+            internal_port,
+            suggested_external_port,
+            lease_duration,
+        );
+        let response = self.send_request(&request, 16)?;
+        parse_nat_pmp_mapping_response(protocol, &response)
+    }
+
+    /// Removes the mapping for `internal_port` by requesting a zero lifetime.
+    pub fn remove_port_mapping(
+        &self,
+        protocol: PortMappingProtocol,
+        internal_port: u16,
+    ) -> anyhow::Result<()> {
+        let request = encode_nat_pmp_mapping_request(protocol, internal_port, 0, Duration::ZERO);
+        self.send_request(&request, 16)?;
+        Ok(())
+    }
+
+    fn send_request(&self, request: &[u8], response_len: usize) -> anyhow::Result<Vec<u8>> {
+        let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))?;
+        socket.connect(self.gateway)?;
+        let mut timeout = NAT_PMP_INITIAL_TIMEOUT;
+        let mut buf = [0u8; 16];
+        for _ in 0..NAT_PMP_MAX_ATTEMPTS {
+            socket.send(request)?;
+            socket.set_read_timeout(Some(timeout))?;
+            match socket.recv(&mut buf) {
+                Ok(size) if size >= response_len => return Ok(buf[..response_len].to_vec()),
+                Ok(size) => bail!("short NAT-PMP response: {size} bytes"),
+                Err(err)
+                    if matches!(
+                        err.kind(),
+                        std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut
+                    ) =>
+                {
+                    timeout = timeout.saturating_mul(2);
+                }
+                Err(err) => return Err(err.into()),
+            }
+        }
+        bail!("no NAT-PMP response from gateway {}", self.gateway)
+    }
+}
+
+fn encode_nat_pmp_mapping_request(
+    protocol: PortMappingProtocol,
+    internal_port: u16,
+    suggested_external_port: u16,
+    lease_duration: Duration,
+) -> [u8; 12] {
+    let lifetime = u32::try_from(lease_duration.as_secs()).unwrap_or(u32::MAX);
+    let mut request = [0u8; 12];
+    request[0] = NAT_PMP_VERSION;
+    request[1] = protocol.nat_pmp_opcode();
+    // request[2..4] is reserved and must be zero.
+    request[4..6].copy_from_slice(&internal_port.to_be_bytes());
+    request[6..8].copy_from_slice(&suggested_external_port.to_be_bytes());
+    request[8..12].copy_from_slice(&lifetime.to_be_bytes());
+    request
+}
+
+fn check_nat_pmp_response_header(response: &[u8], opcode: u8) -> anyhow::Result<()> {
+    if response.len() < 4 {
+        bail!("short NAT-PMP response: {} bytes", response.len());
+    }
+    if response[0] != NAT_PMP_VERSION {
+        bail!("unsupported NAT-PMP version: {}", response[0]);
+    }
+    if response[1] != NAT_PMP_RESPONSE_FLAG | opcode {
+        bail!("unexpected NAT-PMP opcode: {}", response[1]);
+    }
+    match u16::from_be_bytes([response[2], response[3]]) {
+        0 => Ok(()),
+        1 => bail!("NAT-PMP: unsupported version"),
+        2 => bail!("NAT-PMP: not authorized or refused"),
+        3 => bail!("NAT-PMP: network failure"),
+        4 => bail!("NAT-PMP: out of resources"),
+        5 => bail!("NAT-PMP: unsupported opcode"),
+        code => bail!("NAT-PMP: result code {code}"),
+    }
+}
+
+fn parse_nat_pmp_external_address_response(response: &[u8]) -> anyhow::Result<Ipv4Addr> {
+    check_nat_pmp_response_header(response, NAT_PMP_OP_EXTERNAL_ADDRESS)?;
+    let octets: [u8; 4] = response
+        .get(8..12)
+        .ok_or_else(|| anyhow!("short NAT-PMP response: {} bytes", response.len()))?
+        .try_into()
+        .unwrap();
+    Ok(Ipv4Addr::from(octets))
+}
+
+fn parse_nat_pmp_mapping_response(
+    protocol: PortMappingProtocol,
+    response: &[u8],
+) -> anyhow::Result<PortMapping> {
+    check_nat_pmp_response_header(response, protocol.nat_pmp_opcode())?;
+    if response.len() < 16 {
+        bail!("short NAT-PMP response: {} bytes", response.len());
+    }
+    let internal_port = u16::from_be_bytes([response[8], response[9]]);
+    let external_port = u16::from_be_bytes([response[10], response[11]]);
+    let lifetime = u32::from_be_bytes([response[12], response[13], response[14], response[15]]);
+    Ok(PortMapping {
+        protocol,
+        internal_port,
+        external_port,
+        lease_duration: Duration::from_secs(u64::from(lifetime)),
+    })
+}
+
+/// Client for the WAN connection service of a UPnP Internet Gateway Device.
+#[derive(Clone, Debug)]
+pub struct UpnpClient {
+    control_url: Url,
+    service_type: String,
+    local_ip: Ipv4Addr,
+}
+
+impl UpnpClient {
+    /// Discovers a gateway on the local network via SSDP and resolves its
+    /// WAN connection control endpoint.
+    pub fn discover() -> anyhow::Result<Self> {
+        let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))?;
+        socket.set_read_timeout(Some(UPNP_TIMEOUT))?;
+        let request = format!(
+            "M-SEARCH * HTTP/1.1\r\nHOST: {SSDP_MULTICAST_ADDR}\r\nST: \
+             {UPNP_SEARCH_TARGET}\r\nMAN: \"ssdp:discover\"\r\nMX: 2\r\n\r\n"
+        );
+        socket.send_to(request.as_bytes(), SSDP_MULTICAST_ADDR)?;
+        let mut buf = [0u8; 2048];
+        let (size, _) = socket.recv_from(&mut buf)?;
+        let response = String::from_utf8_lossy(&buf[..size]);
+        let location = parse_http_header(&response, "location")
+            .ok_or_else(|| anyhow!("SSDP response without location"))?;
+        let location = Url::parse(location)?;
+        let (description, local_ip) = http_request(&location, "GET", &[], "")?;
+        let (service_type, control_path) = parse_upnp_wan_service(&description)
+            .ok_or_else(|| anyhow!("gateway at {location} has no WAN connection service"))?;
+        let control_url = location.join(control_path)?;
+        Ok(Self {
+            control_url,
+            service_type: service_type.to_string(),
+            local_ip,
+        })
+    }
+
+    pub fn external_address(&self) -> anyhow::Result<Ipv4Addr> {
+        let response = self.soap_request("GetExternalIPAddress", "")?;
+        let address = parse_xml_element(&response, "NewExternalIPAddress")
+            .ok_or_else(|| anyhow!("missing external address in UPnP response"))?;
+        Ok(address.trim().parse()?)
+    }
+
+    /// Requests a mapping for `internal_port`. UPnP gateways never reassign
+    /// ports, so the returned external port is always the requested one.
+    pub fn add_port_mapping(
+        &self,
+        protocol: PortMappingProtocol,
+        internal_port: u16,
+        external_port: u16,
+        lease_duration: Duration,
+    ) -> anyhow::Result<PortMapping> {
+        let arguments = format!(
+            "<NewRemoteHost></NewRemoteHost><NewExternalPort>{external_port}</NewExternalPort>\
+             <NewProtocol>{protocol}</NewProtocol><NewInternalPort>{internal_port}</NewInternalPort>\
+             <NewInternalClient>{}</NewInternalClient><NewEnabled>1</NewEnabled>\
+             <NewPortMappingDescription>{PORT_MAPPING_DESCRIPTION}</NewPortMappingDescription>\
+             <NewLeaseDuration>{}</NewLeaseDuration>",
+            self.local_ip,
+            lease_duration.as_secs(),
+        );
+        self.soap_request("AddPortMapping", &arguments)?;
+        Ok(PortMapping {
+            protocol,
+            internal_port,
+            external_port,
+            lease_duration,
+        })
+    }
+
+    pub fn remove_port_mapping(
+        &self,
+        protocol: PortMappingProtocol,
+        external_port: u16,
+    ) -> anyhow::Result<()> {
+        let arguments = format!(
+            "<NewRemoteHost></NewRemoteHost><NewExternalPort>{external_port}</NewExternalPort>\
+             <NewProtocol>{protocol}</NewProtocol>"
+        );
+        self.soap_request("DeletePortMapping", &arguments)?;
+        Ok(())
+    }
+
+    fn soap_request(&self, action: &str, arguments: &str) -> anyhow::Result<String> {
+        let body = format!(
+            "<?xml version=\"1.0\"?>\
+             <s:Envelope xmlns:s=\"http://schemas.xmlsoap.org/soap/envelope/\" \
+             s:encodingStyle=\"http://schemas.xmlsoap.org/soap/encoding/\">\
+             <s:Body><u:{action} xmlns:u=\"{}\">{arguments}</u:{action}></s:Body></s:Envelope>",
+            self.service_type,
+        );
+        let soap_action = format!("\"{}#{action}\"", self.service_type);
+        let headers = [
+            ("Content-Type", "text/xml; charset=\"utf-8\""),
+            ("SOAPAction", soap_action.as_str()),
+        ];
+        let (response, _) = http_request(&self.control_url, "POST", &headers, &body)?;
+        if let Some(code) = parse_xml_element(&response, "errorCode") {
+            let description = parse_xml_element(&response, "errorDescription").unwrap_or("");
+            bail!("UPnP {action} failed: {code} {description}");
+        }
+        Ok(response)
+    }
+}
+
+/// A gateway able to forward ports to this host.
+#[derive(Clone, Debug)]
+pub enum PortMapper {
+    NatPmp(NatPmpClient),
+    Upnp(UpnpClient),
+}
+
+impl PortMapper {
+    /// Locates a gateway supporting the requested method.
+    pub fn discover(method: NatTraversalMethod) -> anyhow::Result<Self> {
+        match method {
+            NatTraversalMethod::NatPmp { gateway } => {
+                let gateway = match gateway {
+                    Some(gateway) => gateway,
+                    None => default_gateway()?,
+                };
+                let client = NatPmpClient::new(gateway);
+                // Make sure the gateway actually speaks NAT-PMP.
+                client.external_address()?;
+                Ok(Self::NatPmp(client))
+            }
+            NatTraversalMethod::Upnp => Ok(Self::Upnp(UpnpClient::discover()?)),
+            NatTraversalMethod::Auto => {
+                Self::discover(NatTraversalMethod::NatPmp { gateway: None }).or_else(|err| {
+                    debug!("NAT-PMP unavailable, falling back to UPnP: {err}");
+                    Self::discover(NatTraversalMethod::Upnp)
+                })
+            }
+        }
+    }
+
+    pub fn external_address(&self) -> anyhow::Result<Ipv4Addr> {
+        match self {
+            Self::NatPmp(client) => client.external_address(),
+            Self::Upnp(client) => client.external_address(),
+        }
+    }
+
+    pub fn add_port_mapping(
+        &self,
+        protocol: PortMappingProtocol,
+        internal_port: u16,
+        lease_duration: Duration,
+    ) -> anyhow::Result<PortMapping> {
+        // Ask for the same external port so that the advertised port layout
+        // stays intact whenever the gateway allows it.
+        match self {
+            Self::NatPmp(client) => {
+                client.add_port_mapping(protocol, internal_port, internal_port, lease_duration)
+            }
+            Self::Upnp(client) => {
+                client.add_port_mapping(protocol, internal_port, internal_port, lease_duration)
+            }
+        }
+    }
+
+    pub fn remove_port_mapping(&self, mapping: &PortMapping) -> anyhow::Result<()> {
+        match self {
+            Self::NatPmp(client) => {
+                client.remove_port_mapping(mapping.protocol, mapping.internal_port)
+            }
+            Self::Upnp(client) => {
+                client.remove_port_mapping(mapping.protocol, mapping.external_port)
+            }
+        }
+    }
+}
+
+/// Returns the IPv4 default gateway of this host.
+#[cfg(target_os = "linux")]
+pub fn default_gateway() -> anyhow::Result<Ipv4Addr> {
+    let routes = std::fs::read_to_string("/proc/net/route")?;
+    parse_default_gateway(&routes).ok_or_else(|| anyhow!("no IPv4 default route"))
+}
+
+/// Returns the IPv4 default gateway of this host.
+#[cfg(not(target_os = "linux"))]
+pub fn default_gateway() -> anyhow::Result<Ipv4Addr> {
+    bail!("default gateway discovery is not supported on this platform")
+}
+
+// Parses the contents of /proc/net/route. Addresses are hex encoded in host
+// byte order of the network-order bytes.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_default_gateway(routes: &str) -> Option<Ipv4Addr> {
+    routes.lines().skip(1).find_map(|line| {
+        let mut fields = line.split_whitespace();
+        let _iface = fields.next()?;
+        let destination = fields.next()?;
+        let gateway = fields.next()?;
+        if destination != "00000000" {
+            return None;
+        }
+        let gateway = u32::from_str_radix(gateway, 16).ok()?;
+        (gateway != 0).then(|| Ipv4Addr::from(gateway.to_le_bytes()))
+    })
+}
+
+fn parse_http_header<'a>(response: &'a str, name: &str) -> Option<&'a str> {
+    response.lines().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        key.trim()
+            .eq_ignore_ascii_case(name)
+            .then_some(value.trim())
+    })
+}
+
+fn parse_xml_element<'a>(xml: &'a str, name: &str) -> Option<&'a str> {
+    let start_tag = format!("<{name}>");
+    let end_tag = format!("</{name}>");
+    let start = xml.find(&start_tag)? + start_tag.len();
+    let end = start + xml[start..].find(&end_tag)?;
+    Some(&xml[start..end])
+}
+
+// Returns the service type and control URL of the first WAN connection
+// service listed in an IGD device description.
+fn parse_upnp_wan_service(description: &str) -> Option<(&'static str, &str)> {
+    UPNP_WAN_SERVICE_TYPES.iter().find_map(|service_type| {
+        description.split("<service>").skip(1).find_map(|service| {
+            let service = service.split("</service>").next()?;
+            (parse_xml_element(service, "serviceType")?.trim() == *service_type)
+                .then(|| parse_xml_element(service, "controlURL"))
+                .flatten()
+                .map(|control_url| (*service_type, control_url.trim()))
+        })
+    })
+}
+
+// Minimal HTTP/1.1 client used to talk to the gateway. Returns the response
+// body along with the local address the request was sent from.
+fn http_request(
+    url: &Url,
+    method: &str,
+    headers: &[(&str, &str)],
+    body: &str,
+) -> anyhow::Result<(String, Ipv4Addr)> {
+    let host = url
+        .host_str()
+        .ok_or_else(|| anyhow!("missing host in {url}"))?;
+    let port = url
+        .port_or_known_default()
+        .ok_or_else(|| anyhow!("missing port in {url}"))?;
+    let addr: SocketAddr = (host.parse::<IpAddr>()?, port).into();
+    let mut stream = TcpStream::connect_timeout(&addr, UPNP_TIMEOUT)?;
+    stream.set_read_timeout(Some(UPNP_TIMEOUT))?;
+    stream.set_write_timeout(Some(UPNP_TIMEOUT))?;
+    let local_ip = match stream.local_addr()?.ip() {
+        IpAddr::V4(ip) => ip,
+        IpAddr::V6(ip) => bail!("unexpected IPv6 local address {ip}"),
+    };
+    let mut request = format!(
+        "{method} {} HTTP/1.1\r\nHost: {host}:{port}\r\nConnection: close\r\nContent-Length: {}\r\n",
+        url.path(),
+        body.len(),
+    );
+    for (name, value) in headers {
+        request.push_str(&format!("{name}: {value}\r\n"));
+    }
+    request.push_str("\r\n");
+    request.push_str(body);
+    stream.write_all(request.as_bytes())?;
+    let mut response = Vec::new();
+    stream.read_to_end(&mut response)?;
+    let response = String::from_utf8_lossy(&response);
+    let (head, body) = response
+        .split_once("\r\n\r\n")
+        .ok_or_else(|| anyhow!("malformed HTTP response from {url}"))?;
+    let body = match parse_http_header(head, "transfer-encoding") {
+        Some(encoding) if encoding.eq_ignore_ascii_case("chunked") => decode_chunked_body(body)?,
+        _ => body.to_string(),
+    };
+    // SOAP faults are returned with a 500 status and parsed by the caller.
+    let status = head.split_whitespace().nth(1).unwrap_or_default();
+    if status != "200" && status != "500" {
+        bail!("HTTP {status} from {url}");
+    }
+    Ok((body, local_ip))
+}
+
+fn decode_chunked_body(mut body: &str) -> anyhow::Result<String> {
+    let mut decoded = String::new();
+    loop {
+        let (size, rest) = body
+            .split_once("\r\n")
+            .ok_or_else(|| anyhow!("malformed chunked body"))?;
+        let size = usize::from_str_radix(size.split(';').next().unwrap_or_default().trim(), 16)?;
+        if size == 0 {
+            return Ok(decoded);
+        }
+        let chunk = rest
+            .get(..size)
+            .ok_or_else(|| anyhow!("truncated chunked body"))?;
+        decoded.push_str(chunk);
+        body = rest[size..].trim_start_matches("\r\n");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_default_gateway() {
+        let routes = "Iface\tDestination\tGateway \tFlags\tRefCnt\tUse\tMetric\tMask\n\
+                      eth0\t0001A8C0\t00000000\t0001\t0\t0\t0\t00FFFFFF\n\
+                      eth0\t00000000\t0101A8C0\t0003\t0\t0\t0\t00000000\n";
+        assert_eq!(
+            parse_default_gateway(routes),
+            Some(Ipv4Addr::new(192, 168, 1, 1))
+        );
+        assert_eq!(parse_default_gateway("Iface\tDestination\tGateway\n"), None);
+    }
+
+    #[test]
+    fn test_nat_pmp_mapping_round_trip() {
+        let request = encode_nat_pmp_mapping_request(
+            PortMappingProtocol::Udp,
+            8001,
+            8001,
+            Duration::from_secs(3600),
+        );
+        assert_eq!(
+            request,
+            [0, 1, 0, 0, 0x1f, 0x41, 0x1f, 0x41, 0, 0, 0x0e, 0x10]
+        );
+
+        let response = [
+            0, 129, 0, 0, 0, 0, 0, 42, 0x1f, 0x41, 0x23, 0x29, 0, 0, 0x07, 0x08,
+        ];
+        assert_eq!(
+            parse_nat_pmp_mapping_response(PortMappingProtocol::Udp, &response).unwrap(),
+            PortMapping {
+                protocol: PortMappingProtocol::Udp,
+                internal_port: 8001,
+                external_port: 9001,
+                lease_duration: Duration::from_secs(1800),
+            }
+        );
+        // Opcode mismatch.
+        assert!(parse_nat_pmp_mapping_response(PortMappingProtocol::Tcp, &response).is_err());
+        // Non-zero result code.
+        let mut refused = response;
+        refused[3] = 2;
+        assert!(parse_nat_pmp_mapping_response(PortMappingProtocol::Udp, &refused).is_err());
+    }
+
+    #[test]
+    fn test_nat_pmp_external_address_response() {
+        let response = [0, 128, 0, 0, 0, 0, 0, 42, 203, 0, 113, 7];
+        assert_eq!(
+            parse_nat_pmp_external_address_response(&response).unwrap(),
+            Ipv4Addr::new(203, 0, 113, 7)
+        );
+        assert!(parse_nat_pmp_external_address_response(&response[..8]).is_err());
+    }
+
+    #[test]
+    fn test_parse_upnp_responses() {
+        let ssdp = "HTTP/1.1 200 OK\r\nCACHE-CONTROL: max-age=120\r\n\
+                    Location: http://192.168.1.1:5000/rootDesc.xml\r\n\r\n";
+        assert_eq!(
+            parse_http_header(ssdp, "LOCATION"),
+            Some("http://192.168.1.1:5000/rootDesc.xml")
+        );
+
+        let description = "<root><device><serviceList>\
+            <service><serviceType>urn:schemas-upnp-org:service:Layer3Forwarding:1</serviceType>\
+            <controlURL>/ctl/L3F</controlURL></service>\
+            <service><serviceType>urn:schemas-upnp-org:service:WANIPConnection:1</serviceType>\
+            <controlURL>/ctl/IPConn</controlURL></service>\
+            </serviceList></device></root>";
+        assert_eq!(
+            parse_upnp_wan_service(description),
+            Some((
+                "urn:schemas-upnp-org:service:WANIPConnection:1",
+                "/ctl/IPConn"
+            ))
+        );
+        assert_eq!(parse_upnp_wan_service("<root></root>"), None);
+
+        assert_eq!(
+            parse_xml_element(
+                "<u:Resp><NewExternalIPAddress>203.0.113.7</NewExternalIPAddress></u:Resp>",
+                "NewExternalIPAddress"
+            ),
+            Some("203.0.113.7")
+        );
+    }
+
+    #[test]
+    fn test_decode_chunked_body() {
+        assert_eq!(
+            decode_chunked_body("5\r\nhello\r\n6\r\n world\r\n0\r\n\r\n").unwrap(),
+            "hello world"
+        );
+        assert!(decode_chunked_body("5\r\nhel").is_err());
+    }
+
+    #[test]
+    fn test_nat_traversal_method_from_str() {
+        assert_eq!(
+            "auto".parse::<NatTraversalMethod>(),
+            Ok(NatTraversalMethod::Auto)
+        );
+        assert_eq!(
+            "nat-pmp".parse::<NatTraversalMethod>(),
+            Ok(NatTraversalMethod::NatPmp { gateway: None })
+        );
+        assert_eq!(
+            "upnp".parse::<NatTraversalMethod>(),
+            Ok(NatTraversalMethod::Upnp)
+        );
+        assert!("pcp".parse::<NatTraversalMethod>().is_err());
+    }
+}
@@ -9,10 +9,11 @@ use {
     solana_keypair::{write_keypair_file, Keypair},
     std::{
         borrow::Cow,
-        collections::{HashMap, HashSet},
+        collections::{hash_map::DefaultHasher, HashMap, HashSet},
         env,
         ffi::OsStr,
         fs::{self, File},
+        hash::{Hash, Hasher},
         io::{prelude::*, BufReader, BufWriter},
         path::{Path, PathBuf},
         process::{exit, Command, Stdio},
@@ -40,6 +41,7 @@ struct Config<'a> {
     offline: bool,
     remap_cwd: bool,
     debug: bool,
+    incremental: bool,
     verbose: bool,
     workspace: bool,
     jobs: Option<String>,
@@ -70,6 +72,7 @@ impl Default for Config<'_> {
             offline: false,
             remap_cwd: true,
             debug: false,
+            incremental: false,
             verbose: false,
             workspace: false,
             jobs: None,
@@ -767,6 +770,12 @@ fn build_solana_package(
         );
     }
 
+    if config.incremental {
+        // rustc keeps the object files of each crate's codegen units in the incremental
+        // directory of the target, and only recompiles those affected by a change
+        env::set_var("CARGO_PROFILE_RELEASE_INCREMENTAL", "true");
+    }
+
     let cargo_build = PathBuf::from("cargo");
     let mut cargo_build_args = vec![];
     if !config.no_rustup_override {
@@ -813,6 +822,8 @@ fn build_solana_package(
         let program_so = sbf_out_dir.join(format!("{program_name}.so"));
         let program_debug = sbf_out_dir.join(format!("{program_name}.debug"));
         let program_keypair = sbf_out_dir.join(format!("{program_name}-keypair.json"));
+        let program_fingerprint =
+            target_build_directory.join(format!("{program_name}.so.fingerprint"));
 
         fn file_older_or_missing(prerequisite_file: &Path, target_file: &Path) -> bool {
             let prerequisite_metadata = fs::metadata(prerequisite_file).unwrap_or_else(|err| {
@@ -833,6 +844,27 @@ fn build_solana_package(
             }
         }
 
+        // In incremental mode, cargo relinks the program whenever one of its crates is
+        // recompiled, even if the linked program doesn't change. Only process the program
+        // again if its content changed, or if an output is missing.
+        let fingerprint = config
+            .incremental
+            .then(|| program_fingerprint_of(&program_unstripped_so));
+        let program_unchanged = fingerprint.is_some_and(|fingerprint| {
+            fs::read_to_string(&program_fingerprint)
+                .is_ok_and(|previous| previous.trim() == fingerprint.to_string())
+        });
+        let needs_update = |target_file: &Path| {
+            if program_unchanged {
+                !target_file.exists()
+            } else {
+                file_older_or_missing(&program_unstripped_so, target_file)
+            }
+        };
+        if program_unchanged {
+            info!("{program_name}.so is unchanged, skipping post-processing");
+        }
+
         if !program_keypair.exists() {
             write_keypair_file(&Keypair::new(), &program_keypair).unwrap_or_else(|err| {
                 error!(
@@ -844,7 +876,7 @@ fn build_solana_package(
             });
         }
 
-        if file_older_or_missing(&program_unstripped_so, &program_so) {
+        if needs_update(&program_so) {
             #[cfg(windows)]
             let output = spawn(
                 &llvm_bin.join("llvm-objcopy"),
@@ -866,7 +898,7 @@ fn build_solana_package(
             }
         }
 
-        if config.dump && file_older_or_missing(&program_unstripped_so, &program_dump) {
+        if config.dump && needs_update(&program_dump) {
             let dump_script = config.sbf_sdk.join("scripts").join("dump.sh");
             #[cfg(windows)]
             {
@@ -891,7 +923,7 @@ fn build_solana_package(
             postprocess_dump(&program_dump);
         }
 
-        if config.debug && file_older_or_missing(&program_unstripped_so, &program_debug) {
+        if config.debug && needs_update(&program_debug) {
             #[cfg(windows)]
             let llvm_objcopy = &llvm_bin.join("llvm-objcopy");
             #[cfg(not(windows))]
@@ -911,6 +943,16 @@ fn build_solana_package(
             }
         }
 
+        if let Some(fingerprint) = fingerprint {
+            fs::write(&program_fingerprint, fingerprint.to_string()).unwrap_or_else(|err| {
+                error!("Unable to write {}: {}", program_fingerprint.display(), err);
+                exit(1);
+            });
+        } else {
+            // The outputs of a non-incremental build may not match the recorded fingerprint
+            let _ = fs::remove_file(&program_fingerprint);
+        }
+
         check_undefined_symbols(config, &program_so);
 
         info!("To deploy this program:");
@@ -922,6 +964,17 @@ fn build_solana_package(
     }
 }
 
+// Fingerprint of the content of a linked program, to tell whether relinking changed it
+fn program_fingerprint_of(program: &Path) -> u64 {
+    let content = fs::read(program).unwrap_or_else(|err| {
+        error!("Unable to read {}: {}", program.display(), err);
+        exit(1);
+    });
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+    hasher.finish()
+}
+
 // allow user to set proper `rustc` into RUSTC or into PATH
 fn check_solana_target_installed(target: &str) {
     let rustc = env::var("RUSTC").unwrap_or("rustc".to_owned());
@@ -1041,6 +1094,16 @@ fn main() {
                 .takes_value(false)
                 .help("Enable debug symbols"),
         )
+        .arg(
+            Arg::new("incremental")
+                .long("incremental")
+                .takes_value(false)
+                .help(
+                    "Compile incrementally, caching the object files of each crate between \
+                     builds, and only strip and dump the program again when relinking changed \
+                     it",
+                ),
+        )
         .arg(
             Arg::new("dump")
                 .long("dump")
@@ -1206,6 +1269,7 @@ fn main() {
         no_default_features: matches.is_present("no_default_features"),
         remap_cwd: !matches.is_present("remap_cwd"),
         debug: matches.is_present("debug"),
+        incremental: matches.is_present("incremental"),
         offline: matches.is_present("offline"),
         verbose: matches.is_present("verbose"),
         workspace: matches.is_present("workspace"),
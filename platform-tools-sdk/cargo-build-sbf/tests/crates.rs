@@ -81,6 +81,38 @@ fn test_dump() {
     clean_target("noop");
 }
 
+#[test]
+#[serial]
+fn test_incremental() {
+    run_cargo_build("noop", &["--incremental"], false);
+    let cwd = env::current_dir().expect("Unable to get current working directory");
+    let release = cwd
+        .join("tests")
+        .join("crates")
+        .join("noop")
+        .join("target")
+        .join("sbf-solana-solana")
+        .join("release");
+    assert!(release.join("incremental").exists());
+    assert!(release.join("noop.so.fingerprint").exists());
+
+    // An unchanged program is not processed again
+    let program_so = cwd
+        .join("tests")
+        .join("crates")
+        .join("noop")
+        .join("target")
+        .join("deploy")
+        .join("noop.so");
+    let modified = fs::metadata(&program_so).unwrap().modified().unwrap();
+    run_cargo_build("noop", &["--incremental"], false);
+    assert_eq!(
+        fs::metadata(&program_so).unwrap().modified().unwrap(),
+        modified
+    );
+    clean_target("noop");
+}
+
 #[test]
 #[serial]
 fn test_out_dir() {
@@ -27,8 +27,12 @@ use {
     solana_metrics::poh_timing_point::{send_poh_timing_point, PohTimingSender, SlotPohTimingInfo},
     solana_poh_config::PohConfig,
     solana_pubkey::Pubkey,
-    solana_runtime::{bank::Bank, installed_scheduler_pool::BankWithScheduler},
+    solana_runtime::{
+        bank::Bank, installed_scheduler_pool::BankWithScheduler,
+        transaction_recorder::RecordTransactions,
+    },
     solana_transaction::versioned::VersionedTransaction,
+    solana_transaction_error::{TransactionError, TransactionResult},
     std::{
         cmp,
         num::Saturating,
@@ -228,6 +232,25 @@ impl TransactionRecorder {
     }
 }
 
+impl RecordTransactions for TransactionRecorder {
+    /// Records `transactions` into the PoH stream, failing with
+    /// `CommitCancelled` if the bank is no longer the working bank.
+    fn record_transactions(
+        &self,
+        bank_slot: Slot,
+        transactions: Vec<VersionedTransaction>,
+    ) -> TransactionResult<Option<usize>> {
+        let RecordTransactionsSummary {
+            result,
+            starting_transaction_index,
+            ..
+        } = TransactionRecorder::record_transactions(self, bank_slot, transactions);
+        result
+            .map(|()| starting_transaction_index)
+            .map_err(|_| TransactionError::CommitCancelled)
+    }
+}
+
 pub struct WorkingBank {
     pub bank: BankWithScheduler,
     pub start: Arc<Instant>,
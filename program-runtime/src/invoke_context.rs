@@ -39,6 +39,7 @@ use {
         alloc::Layout,
         cell::RefCell,
         fmt::{self, Debug},
+        mem,
         rc::Rc,
     },
 };
@@ -185,6 +186,78 @@ pub struct SerializedAccountMetadata {
     pub vm_owner_addr: u64,
 }
 
+/// The stack and heap memory used by an SBF program invocation.
+///
+/// The memory used is measured as the span of bytes that changed between a
+/// copy of the stack and heap taken before the program ran and their state
+/// when it returned, and for the heap at least the bytes handed out by the
+/// `sol_alloc_free_` syscall allocator.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProgramMemoryUsage {
+    pub program_id: Pubkey,
+    /// The instruction stack height of the invocation, 1 for instructions of
+    /// the transaction itself
+    pub stack_height: usize,
+    pub stack_bytes: u64,
+    pub stack_size: u64,
+    pub heap_bytes: u64,
+    pub heap_size: u64,
+}
+
+impl ProgramMemoryUsage {
+    /// Measures the memory used in the `stack` and `heap` of an invocation
+    /// that returned, against the `initial_stack` and `initial_heap` it
+    /// started with. `dynamic_stack_frames` tells whether the stack grows
+    /// downwards from its end, rather than in fixed frames from its start.
+    #[allow(clippy::too_many_arguments)]
+    pub fn measure(
+        program_id: Pubkey,
+        stack_height: usize,
+        initial_stack: &[u8],
+        stack: &[u8],
+        initial_heap: &[u8],
+        heap: &[u8],
+        syscall_allocator: &BpfAllocator,
+        dynamic_stack_frames: bool,
+    ) -> Self {
+        let changed = |initial: &[u8], memory: &[u8]| {
+            let mut changed = initial
+                .iter()
+                .zip(memory)
+                .enumerate()
+                .filter(|(_, (initial, byte))| initial != byte)
+                .map(|(offset, _)| offset);
+            let first = changed.next()?;
+            let last = changed.last().unwrap_or(first);
+            Some(first..last.saturating_add(1))
+        };
+        let stack_bytes = changed(initial_stack, stack).map_or(0, |span| {
+            if dynamic_stack_frames {
+                stack.len().saturating_sub(span.start)
+            } else {
+                span.end
+            }
+        });
+        // The default allocator of programs keeps its position in the first
+        // bytes of the heap and allocates downwards from the end of its region
+        let position = mem::size_of::<u64>();
+        let heap_bytes = changed(
+            initial_heap.get(position..).unwrap_or_default(),
+            heap.get(position..).unwrap_or_default(),
+        )
+        .map_or(0, |span| span.len() as u64)
+        .max(syscall_allocator.pos);
+        Self {
+            program_id,
+            stack_height,
+            stack_bytes: stack_bytes as u64,
+            stack_size: stack.len() as u64,
+            heap_bytes,
+            heap_size: heap.len() as u64,
+        }
+    }
+}
+
 /// Main pipeline from runtime to program execution.
 pub struct InvokeContext<'a> {
     /// Information about the currently executing transaction.
@@ -204,6 +277,8 @@ pub struct InvokeContext<'a> {
     pub timings: ExecuteDetailsTimings,
     pub syscall_context: Vec<Option<SyscallContext>>,
     traces: Vec<Vec<[u64; 12]>>,
+    memory_usage: Option<Vec<ProgramMemoryUsage>>,
+    account_privileges: Option<Vec<InstructionAccountPrivileges>>,
 }
 
 impl<'a> InvokeContext<'a> {
@@ -226,6 +301,8 @@ impl<'a> InvokeContext<'a> {
             timings: ExecuteDetailsTimings::default(),
             syscall_context: Vec::new(),
             traces: Vec::new(),
+            memory_usage: None,
+            account_privileges: None,
         }
     }
 
@@ -472,10 +549,94 @@ impl<'a> InvokeContext<'a> {
             .get_next_instruction_context()?
             .configure(program_indices, instruction_accounts, instruction_data);
         self.push()?;
-        self.process_executable_chain(compute_units_consumed, timings)
-            // MUST pop if and only if `push` succeeded, independent of `result`.
-            // Thus, the `.and()` instead of an `.and_then()`.
-            .and(self.pop())
+        let privileges_recording = self
+            .account_privileges
+            .is_some()
+            .then(|| self.start_account_privileges_recording(instruction_accounts));
+        let result = self.process_executable_chain(compute_units_consumed, timings);
+        if let Some((index, pre_accounts)) = privileges_recording {
+            self.finish_account_privileges_recording(index, pre_accounts);
+        }
+        // MUST pop if and only if `push` succeeded, independent of `result`.
+        // Thus, the `.and()` instead of an `.and_then()`.
+        result.and(self.pop())
+    }
+
+    /// Records the privileges of the instruction being pushed, and returns
+    /// the index of the record and the accounts the instruction can modify,
+    /// as they were before the instruction
+    fn start_account_privileges_recording(
+        &mut self,
+        instruction_accounts: &[InstructionAccount],
+    ) -> (usize, Vec<Option<(IndexOfAccount, AccountSharedData)>>) {
+        let transaction_context = &self.transaction_context;
+        let program_id = transaction_context
+            .get_current_instruction_context()
+            .and_then(|instruction_context| {
+                instruction_context.get_last_program_key(transaction_context)
+            })
+            .copied()
+            .unwrap_or_default();
+        let (accounts, pre_accounts) = instruction_accounts
+            .iter()
+            .enumerate()
+            .filter(|(index_in_instruction, instruction_account)| {
+                instruction_account.index_in_callee as usize == *index_in_instruction
+            })
+            .map(|(_, instruction_account)| {
+                let index = instruction_account.index_in_transaction;
+                let pubkey = transaction_context
+                    .get_key_of_account_at_index(index)
+                    .copied()
+                    .unwrap_or_default();
+                let pre_account = instruction_account
+                    .is_writable
+                    .then(|| transaction_context.get_account_at_index(index).ok())
+                    .flatten()
+                    .and_then(|account| account.try_borrow().ok().map(|account| account.clone()))
+                    .map(|account| (index, account));
+                let usage = AccountPrivilegeUsage {
+                    pubkey,
+                    is_signer: instruction_account.is_signer,
+                    is_writable: instruction_account.is_writable,
+                    modified: false,
+                };
+                (usage, pre_account)
+            })
+            .unzip();
+        let stack_height = self.get_stack_height();
+        let recorded = self.account_privileges.get_or_insert_with(Vec::new);
+        recorded.push(InstructionAccountPrivileges {
+            program_id,
+            stack_height,
+            accounts,
+        });
+        (recorded.len().saturating_sub(1), pre_accounts)
+    }
+
+    fn finish_account_privileges_recording(
+        &mut self,
+        index: usize,
+        pre_accounts: Vec<Option<(IndexOfAccount, AccountSharedData)>>,
+    ) {
+        let transaction_context = &self.transaction_context;
+        let Some(privileges) = self
+            .account_privileges
+            .as_mut()
+            .and_then(|recorded| recorded.get_mut(index))
+        else {
+            return;
+        };
+        for (usage, pre_account) in privileges.accounts.iter_mut().zip(pre_accounts) {
+            let Some((index, pre_account)) = pre_account else {
+                continue;
+            };
+            usage.modified = transaction_context
+                .get_account_at_index(index)
+                .ok()
+                .and_then(|account| account.try_borrow().ok().map(|post| *post != pre_account))
+                .unwrap_or(false);
+        }
     }
 
     /// Processes a precompile instruction
@@ -706,6 +867,39 @@ impl<'a> InvokeContext<'a> {
     pub fn get_traces(&self) -> &Vec<Vec<[u64; 12]>> {
         &self.traces
     }
+
+    /// Records the memory used by each SBF program invocation from now on
+    pub fn enable_memory_usage_recording(&mut self) {
+        self.memory_usage.get_or_insert_with(Vec::new);
+    }
+
+    pub fn is_memory_usage_recording_enabled(&self) -> bool {
+        self.memory_usage.is_some()
+    }
+
+    pub fn record_memory_usage(&mut self, memory_usage: ProgramMemoryUsage) {
+        if let Some(recorded) = self.memory_usage.as_mut() {
+            recorded.push(memory_usage);
+        }
+    }
+
+    /// Returns the memory usage recorded so far, in the order in which the
+    /// invocations returned, and stops recording
+    pub fn take_memory_usage(&mut self) -> Option<Vec<ProgramMemoryUsage>> {
+        self.memory_usage.take()
+    }
+
+    /// Records the account privileges of each instruction processed from now
+    /// on, including cross-program invocations
+    pub fn enable_account_privileges_recording(&mut self) {
+        self.account_privileges.get_or_insert_with(Vec::new);
+    }
+
+    /// Returns the account privileges recorded so far, in the order in which
+    /// the instructions were invoked, and stops recording
+    pub fn take_account_privileges(&mut self) -> Option<Vec<InstructionAccountPrivileges>> {
+        self.account_privileges.take()
+    }
 }
 
 #[macro_export]
@@ -1176,6 +1370,100 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_account_privileges_recording() {
+        let callee_program_id = solana_pubkey::new_rand();
+        let owned_account = AccountSharedData::new(42, 1, &callee_program_id);
+        let not_owned_account = AccountSharedData::new(84, 1, &solana_pubkey::new_rand());
+        let readonly_account = AccountSharedData::new(168, 1, &solana_pubkey::new_rand());
+        let loader_account = AccountSharedData::new(0, 1, &native_loader::id());
+        let mut program_account = AccountSharedData::new(1, 1, &native_loader::id());
+        program_account.set_executable(true);
+        let transaction_accounts = vec![
+            (solana_pubkey::new_rand(), owned_account),
+            (solana_pubkey::new_rand(), not_owned_account),
+            (solana_pubkey::new_rand(), readonly_account),
+            (callee_program_id, program_account),
+            (solana_pubkey::new_rand(), loader_account),
+        ];
+        let owned_key = transaction_accounts.first().unwrap().0;
+        let not_owned_key = transaction_accounts.get(1).unwrap().0;
+        let readonly_key = transaction_accounts.get(2).unwrap().0;
+        let metas = vec![
+            AccountMeta::new(owned_key, false),
+            AccountMeta::new(not_owned_key, false),
+            AccountMeta::new_readonly(readonly_key, true),
+            // Duplicates are reported once
+            AccountMeta::new(owned_key, false),
+        ];
+        let instruction_accounts = (0..4)
+            .map(|instruction_account_index| InstructionAccount {
+                index_in_transaction: instruction_account_index,
+                index_in_caller: instruction_account_index,
+                index_in_callee: instruction_account_index,
+                is_signer: instruction_account_index == 2,
+                is_writable: instruction_account_index < 2,
+            })
+            .collect::<Vec<_>>();
+        with_mock_invoke_context!(invoke_context, transaction_context, transaction_accounts);
+        let mut program_cache_for_tx_batch = ProgramCacheForTxBatch::default();
+        program_cache_for_tx_batch.replenish(
+            callee_program_id,
+            Arc::new(ProgramCacheEntry::new_builtin(0, 1, MockBuiltin::vm)),
+        );
+        invoke_context.program_cache_for_tx_batch = &mut program_cache_for_tx_batch;
+        invoke_context.enable_account_privileges_recording();
+
+        invoke_context
+            .transaction_context
+            .get_next_instruction_context()
+            .unwrap()
+            .configure(&[4], &instruction_accounts, &[]);
+        invoke_context.push().unwrap();
+        // Writing the same data the second time doesn't modify the account
+        for _ in 0..2 {
+            let inner_instruction = Instruction::new_with_bincode(
+                callee_program_id,
+                &MockInstruction::ModifyOwned,
+                metas.clone(),
+            );
+            invoke_context
+                .native_invoke(inner_instruction.into(), &[])
+                .unwrap();
+        }
+        invoke_context.pop().unwrap();
+
+        let expected_privileges = |modified| InstructionAccountPrivileges {
+            program_id: callee_program_id,
+            stack_height: 2,
+            accounts: vec![
+                AccountPrivilegeUsage {
+                    pubkey: owned_key,
+                    is_signer: false,
+                    is_writable: true,
+                    modified,
+                },
+                AccountPrivilegeUsage {
+                    pubkey: not_owned_key,
+                    is_signer: false,
+                    is_writable: true,
+                    modified: false,
+                },
+                AccountPrivilegeUsage {
+                    pubkey: readonly_key,
+                    is_signer: true,
+                    is_writable: false,
+                    modified: false,
+                },
+            ],
+        };
+        assert_eq!(
+            invoke_context.take_account_privileges(),
+            Some(vec![expected_privileges(true), expected_privileges(false)])
+        );
+        assert_eq!(invoke_context.take_account_privileges(), None);
+    }
+
     #[test]
     fn test_invoke_context_compute_budget() {
         let transaction_accounts = vec![(solana_pubkey::new_rand(), AccountSharedData::default())];
@@ -1200,6 +1488,83 @@ mod tests {
         invoke_context.pop().unwrap();
     }
 
+    #[test]
+    fn test_program_memory_usage() {
+        let program_id = Pubkey::new_unique();
+        // 16 bytes written in the second of four 16 byte stack frames
+        let stack = [
+            [0; 16],
+            [0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2, 0],
+            [0; 16],
+            [0; 16],
+        ]
+        .concat();
+        // The allocator position, then 12 bytes allocated at the end
+        let heap = [[0, 0, 0, 0, 1, 0, 0, 0], [0; 8], [0; 4], [3; 12]].concat();
+
+        let syscall_allocator = BpfAllocator::new(32);
+        let initial_stack = [0; 64];
+        let initial_heap = [0; 32];
+        let measure = |initial_stack: &[u8], initial_heap: &[u8], dynamic_stack_frames| {
+            ProgramMemoryUsage::measure(
+                program_id,
+                2,
+                initial_stack,
+                &stack,
+                initial_heap,
+                &heap,
+                &syscall_allocator,
+                dynamic_stack_frames,
+            )
+        };
+
+        let memory_usage = measure(&initial_stack, &initial_heap, false);
+        assert_eq!(
+            memory_usage,
+            ProgramMemoryUsage {
+                program_id,
+                stack_height: 2,
+                stack_bytes: 31,
+                stack_size: 64,
+                heap_bytes: 12,
+                heap_size: 32,
+            }
+        );
+        assert_eq!(measure(&initial_stack, &initial_heap, true).stack_bytes, 47);
+        // Memory is compared to its initial state, which need not be zeroed
+        let memory_usage = measure(&stack, &heap, true);
+        assert_eq!((memory_usage.stack_bytes, memory_usage.heap_bytes), (0, 0));
+        let initial_heap = [[0; 8], [0; 8], [0; 4], [3; 12]].concat();
+        assert_eq!(measure(&initial_stack, &initial_heap, false).heap_bytes, 0);
+
+        // Bytes handed out by the syscall allocator count as used
+        let mut syscall_allocator = BpfAllocator::new(32);
+        syscall_allocator
+            .alloc(Layout::from_size_align(20, 1).unwrap())
+            .unwrap();
+        let memory_usage = ProgramMemoryUsage::measure(
+            program_id,
+            1,
+            &stack,
+            &stack,
+            &heap,
+            &heap,
+            &syscall_allocator,
+            false,
+        );
+        assert_eq!(memory_usage.heap_bytes, 20);
+
+        let transaction_accounts = vec![(program_id, AccountSharedData::default())];
+        with_mock_invoke_context!(invoke_context, transaction_context, transaction_accounts);
+        invoke_context.record_memory_usage(memory_usage.clone());
+        assert!(!invoke_context.is_memory_usage_recording_enabled());
+        assert_eq!(invoke_context.take_memory_usage(), None);
+        invoke_context.enable_memory_usage_recording();
+        invoke_context.record_memory_usage(memory_usage.clone());
+        assert_eq!(invoke_context.take_memory_usage(), Some(vec![memory_usage]));
+        assert!(!invoke_context.is_memory_usage_recording_enabled());
+    }
+
     #[test]
     fn test_process_instruction_accounts_resize_delta() {
         let program_key = Pubkey::new_unique();
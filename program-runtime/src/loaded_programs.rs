@@ -1217,6 +1217,35 @@ impl<FG: ForkGraph> ProgramCache<FG> {
         }
     }
 
+    /// Returns up to `count` program ids ordered by decreasing usage as of
+    /// slot `now`. Programs which were unloaded are included, as they are the
+    /// ones which would have to be compiled again on their next use.
+    pub fn get_most_used_programs(&self, count: usize, now: Slot) -> Vec<Pubkey> {
+        let mut programs: Vec<(Pubkey, u64)> = match &self.index {
+            IndexImplementation::V1 { entries, .. } => entries
+                .iter()
+                .filter_map(|(id, second_level)| {
+                    second_level
+                        .iter()
+                        .filter(|program| {
+                            matches!(
+                                program.program,
+                                ProgramCacheEntryType::Loaded(_)
+                                    | ProgramCacheEntryType::Unloaded(_)
+                            )
+                        })
+                        .map(|program| program.decayed_usage_counter(now))
+                        .max()
+                        .filter(|usage_counter| *usage_counter > 0)
+                        .map(|usage_counter| (*id, usage_counter))
+                })
+                .collect(),
+        };
+        programs.sort_unstable_by(|(_, a), (_, b)| b.cmp(a));
+        programs.truncate(count);
+        programs.into_iter().map(|(id, _)| id).collect()
+    }
+
     /// Returns the list of all entries in the cache.
     pub fn get_flattened_entries_for_tests(&self) -> Vec<(Pubkey, Arc<ProgramCacheEntry>)> {
         match &self.index {
@@ -1818,6 +1847,42 @@ mod tests {
             });
     }
 
+    #[test]
+    fn test_get_most_used_programs() {
+        let mut cache = new_mock_cache::<TestForkGraph>();
+
+        let unused = Pubkey::new_unique();
+        cache.assign_program(unused, new_test_entry(0, 1));
+        let loaded = Pubkey::new_unique();
+        cache.assign_program(loaded, new_test_entry_with_usage(0, 1, AtomicU64::new(8)));
+        let unloaded = Pubkey::new_unique();
+        let entry = new_test_entry_with_usage(0, 1, AtomicU64::new(32));
+        cache.assign_program(unloaded, Arc::new(entry.to_unloaded().unwrap()));
+        let decayed = Pubkey::new_unique();
+        let entry = new_test_entry_with_usage(0, 1, AtomicU64::new(64));
+        entry.update_access_slot(0);
+        cache.assign_program(decayed, entry);
+        let builtin = Pubkey::new_unique();
+        cache.assign_program(builtin, new_test_builtin_entry(0, 0));
+        cache
+            .get_slot_versions_for_tests(&builtin)
+            .iter()
+            .for_each(|entry| entry.tx_usage_counter.store(100, Ordering::Relaxed));
+        [loaded, unloaded].iter().for_each(|key| {
+            cache
+                .get_slot_versions_for_tests(key)
+                .iter()
+                .for_each(|entry| entry.update_access_slot(4));
+        });
+
+        assert_eq!(
+            cache.get_most_used_programs(4, 4),
+            vec![unloaded, loaded, decayed]
+        );
+        assert_eq!(cache.get_most_used_programs(1, 4), vec![unloaded]);
+        assert!(cache.get_most_used_programs(0, 4).is_empty());
+    }
+
     #[test]
     fn test_fuzz_assign_program_order() {
         use rand::prelude::SliceRandom;
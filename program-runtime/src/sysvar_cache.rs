@@ -43,6 +43,12 @@ pub struct SysvarCache {
     slot_hashes_obj: Option<Arc<SlotHashes>>,
     stake_history_obj: Option<Arc<StakeHistory>>,
 
+    // object representations of the sysvars read by most transactions
+    // these are deserialized once when the entry is filled rather than on every read
+    clock_obj: Option<Arc<Clock>>,
+    epoch_schedule_obj: Option<Arc<EpochSchedule>>,
+    rent_obj: Option<Arc<Rent>>,
+
     // deprecated sysvars, these should be removed once practical
     #[allow(deprecated)]
     fees: Option<Fees>,
@@ -64,13 +70,19 @@ impl SysvarCache {
         let sysvar_id = T::id();
         match sysvar_id {
             sysvar::clock::ID => {
+                let clock: Clock =
+                    bincode::deserialize(&data).expect("Failed to deserialize Clock sysvar.");
                 self.clock = Some(data);
+                self.clock_obj = Some(Arc::new(clock));
             }
             sysvar::epoch_rewards::ID => {
                 self.epoch_rewards = Some(data);
             }
             sysvar::epoch_schedule::ID => {
+                let epoch_schedule: EpochSchedule = bincode::deserialize(&data)
+                    .expect("Failed to deserialize EpochSchedule sysvar.");
                 self.epoch_schedule = Some(data);
+                self.epoch_schedule_obj = Some(Arc::new(epoch_schedule));
             }
             FEES_ID => {
                 let fees: Fees =
@@ -86,7 +98,10 @@ impl SysvarCache {
                 self.recent_blockhashes = Some(recent_blockhashes);
             }
             sysvar::rent::ID => {
+                let rent: Rent =
+                    bincode::deserialize(&data).expect("Failed to deserialize Rent sysvar.");
                 self.rent = Some(data);
+                self.rent_obj = Some(Arc::new(rent));
             }
             sysvar::slot_hashes::ID => {
                 let slot_hashes: SlotHashes =
@@ -141,11 +156,15 @@ impl SysvarCache {
     }
 
     pub fn get_clock(&self) -> Result<Arc<Clock>, InstructionError> {
-        self.get_sysvar_obj(&Clock::id())
+        self.clock_obj
+            .clone()
+            .ok_or(InstructionError::UnsupportedSysvar)
     }
 
     pub fn get_epoch_schedule(&self) -> Result<Arc<EpochSchedule>, InstructionError> {
-        self.get_sysvar_obj(&EpochSchedule::id())
+        self.epoch_schedule_obj
+            .clone()
+            .ok_or(InstructionError::UnsupportedSysvar)
     }
 
     pub fn get_epoch_rewards(&self) -> Result<Arc<EpochRewards>, InstructionError> {
@@ -153,7 +172,9 @@ impl SysvarCache {
     }
 
     pub fn get_rent(&self) -> Result<Arc<Rent>, InstructionError> {
-        self.get_sysvar_obj(&Rent::id())
+        self.rent_obj
+            .clone()
+            .ok_or(InstructionError::UnsupportedSysvar)
     }
 
     pub fn get_last_restart_slot(&self) -> Result<Arc<LastRestartSlot>, InstructionError> {
@@ -196,16 +217,18 @@ impl SysvarCache {
     ) {
         if self.clock.is_none() {
             get_account_data(&Clock::id(), &mut |data: &[u8]| {
-                if bincode::deserialize::<Clock>(data).is_ok() {
+                if let Ok(obj) = bincode::deserialize::<Clock>(data) {
                     self.clock = Some(data.to_vec());
+                    self.clock_obj = Some(Arc::new(obj));
                 }
             });
         }
 
         if self.epoch_schedule.is_none() {
             get_account_data(&EpochSchedule::id(), &mut |data: &[u8]| {
-                if bincode::deserialize::<EpochSchedule>(data).is_ok() {
+                if let Ok(obj) = bincode::deserialize::<EpochSchedule>(data) {
                     self.epoch_schedule = Some(data.to_vec());
+                    self.epoch_schedule_obj = Some(Arc::new(obj));
                 }
             });
         }
@@ -220,8 +243,9 @@ impl SysvarCache {
 
         if self.rent.is_none() {
             get_account_data(&Rent::id(), &mut |data: &[u8]| {
-                if bincode::deserialize::<Rent>(data).is_ok() {
+                if let Ok(obj) = bincode::deserialize::<Rent>(data) {
                     self.rent = Some(data.to_vec());
+                    self.rent_obj = Some(Arc::new(obj));
                 }
             });
         }
@@ -274,6 +298,53 @@ impl SysvarCache {
     pub fn reset(&mut self) {
         *self = Self::default();
     }
+
+    /// Drop the cached entry of a sysvar, so that the next `fill_missing_entries` loads it again.
+    /// Used to keep the cache in sync when the sysvar account is updated.
+    #[allow(deprecated)]
+    pub fn invalidate(&mut self, sysvar_id: &Pubkey) {
+        match *sysvar_id {
+            sysvar::clock::ID => {
+                self.clock = None;
+                self.clock_obj = None;
+            }
+            sysvar::epoch_rewards::ID => self.epoch_rewards = None,
+            sysvar::epoch_schedule::ID => {
+                self.epoch_schedule = None;
+                self.epoch_schedule_obj = None;
+            }
+            FEES_ID => self.fees = None,
+            sysvar::last_restart_slot::ID => self.last_restart_slot = None,
+            RECENT_BLOCKHASHES_ID => self.recent_blockhashes = None,
+            sysvar::rent::ID => {
+                self.rent = None;
+                self.rent_obj = None;
+            }
+            sysvar::slot_hashes::ID => {
+                self.slot_hashes = None;
+                self.slot_hashes_obj = None;
+            }
+            sysvar::stake_history::ID => {
+                self.stake_history = None;
+                self.stake_history_obj = None;
+            }
+            _ => {}
+        }
+    }
+
+    /// Whether no sysvar has been filled in yet
+    #[allow(deprecated)]
+    pub fn is_empty(&self) -> bool {
+        self.clock.is_none()
+            && self.epoch_schedule.is_none()
+            && self.epoch_rewards.is_none()
+            && self.rent.is_none()
+            && self.slot_hashes.is_none()
+            && self.stake_history.is_none()
+            && self.last_restart_slot.is_none()
+            && self.fees.is_none()
+            && self.recent_blockhashes.is_none()
+    }
 }
 
 /// These methods facilitate a transition from fetching sysvars from keyed
@@ -412,4 +483,42 @@ mod tests {
 
         assert_eq!(out_buf, in_buf);
     }
+
+    #[test]
+    fn test_sysvar_cache_invalidate() {
+        let clock = Clock {
+            slot: 42,
+            ..Clock::default()
+        };
+        let rent = Rent::default();
+        let mut sysvar_cache = SysvarCache::default();
+        assert!(sysvar_cache.is_empty());
+        sysvar_cache.fill_missing_entries(|pubkey, callback| {
+            if *pubkey == Clock::id() {
+                callback(&bincode::serialize(&clock).unwrap());
+            } else if *pubkey == Rent::id() {
+                callback(&bincode::serialize(&rent).unwrap());
+            }
+        });
+        assert!(!sysvar_cache.is_empty());
+        assert_eq!(*sysvar_cache.get_clock().unwrap(), clock);
+        let cached_rent = sysvar_cache.get_rent().unwrap();
+
+        // Only the invalidated entry is reloaded
+        sysvar_cache.invalidate(&Clock::id());
+        assert!(sysvar_cache.get_clock().is_err());
+        assert!(sysvar_cache.sysvar_id_to_buffer(&Clock::id()).is_none());
+        let new_clock = Clock {
+            slot: 43,
+            ..Clock::default()
+        };
+        sysvar_cache.fill_missing_entries(|pubkey, callback| {
+            assert_ne!(*pubkey, Rent::id());
+            if *pubkey == Clock::id() {
+                callback(&bincode::serialize(&new_clock).unwrap());
+            }
+        });
+        assert_eq!(*sysvar_cache.get_clock().unwrap(), new_clock);
+        assert!(Arc::ptr_eq(&sysvar_cache.get_rent().unwrap(), &cached_rent));
+    }
 }
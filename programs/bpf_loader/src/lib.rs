@@ -24,7 +24,10 @@ use {
     solana_measure::measure::Measure,
     solana_program_entrypoint::{MAX_PERMITTED_DATA_INCREASE, SUCCESS},
     solana_program_runtime::{
-        invoke_context::{BpfAllocator, InvokeContext, SerializedAccountMetadata, SyscallContext},
+        invoke_context::{
+            BpfAllocator, InvokeContext, ProgramMemoryUsage, SerializedAccountMetadata,
+            SyscallContext,
+        },
         loaded_programs::{
             LoadProgramMetrics, ProgramCacheEntry, ProgramCacheEntryOwner, ProgramCacheEntryType,
             ProgramCacheForTxBatch, ProgramRuntimeEnvironment, DELAY_VISIBILITY_SLOT_OFFSET,
@@ -1622,8 +1625,38 @@ fn execute<'a, 'b: 'a>(
         };
         create_vm_time.stop();
 
+        let stack_size = executable.get_config().stack_size();
+        let heap_size = vm.context_object_pointer.get_compute_budget().heap_size as usize;
+        let initial_memory = vm
+            .context_object_pointer
+            .is_memory_usage_recording_enabled()
+            .then(|| {
+                (
+                    stack.as_slice().get(..stack_size).map(<[u8]>::to_vec),
+                    heap.as_slice().get(..heap_size).map(<[u8]>::to_vec),
+                )
+            });
+
         vm.context_object_pointer.execute_time = Some(Measure::start("execute"));
         let (compute_units_consumed, result) = vm.execute_program(executable, !use_jit);
+        let memory_usage = initial_memory.map(|(initial_stack, initial_heap)| {
+            ProgramMemoryUsage::measure(
+                program_id,
+                vm.context_object_pointer.get_stack_height(),
+                &initial_stack.expect("invalid stack size"),
+                stack
+                    .as_slice()
+                    .get(..stack_size)
+                    .expect("invalid stack size"),
+                &initial_heap.expect("invalid heap size"),
+                heap.as_slice().get(..heap_size).expect("invalid heap size"),
+                &vm.context_object_pointer
+                    .get_syscall_context()
+                    .expect("syscall context")
+                    .allocator,
+                executable.get_sbpf_version().dynamic_stack_frames(),
+            )
+        });
         MEMORY_POOL.with_borrow_mut(|memory_pool| {
             memory_pool.put_stack(stack);
             memory_pool.put_heap(heap);
@@ -1631,6 +1664,18 @@ fn execute<'a, 'b: 'a>(
             debug_assert!(memory_pool.heap_len() <= MAX_INSTRUCTION_STACK_DEPTH);
         });
         drop(vm);
+        if let Some(memory_usage) = memory_usage {
+            ic_logger_msg!(
+                log_collector,
+                "Program {} used {} of {} stack bytes and {} of {} heap bytes",
+                &program_id,
+                memory_usage.stack_bytes,
+                memory_usage.stack_size,
+                memory_usage.heap_bytes,
+                memory_usage.heap_size
+            );
+            invoke_context.record_memory_usage(memory_usage);
+        }
         if let Some(execute_time) = invoke_context.execute_time.as_mut() {
             execute_time.stop();
             invoke_context.timings.execute_us += execute_time.as_us();
@@ -141,10 +141,22 @@ trait HasherImpl {
     fn hash(&mut self, val: &[u8]);
     fn result(self) -> Self::Output;
     fn get_base_cost(compute_budget: &ComputeBudget) -> u64;
-    fn get_byte_cost(compute_budget: &ComputeBudget) -> u64;
+    /// Cost of hashing one slice of `len` bytes
+    fn get_slice_cost(compute_budget: &ComputeBudget, len: u64) -> u64;
     fn get_max_slices(compute_budget: &ComputeBudget) -> u64;
 }
 
+fn get_sha256_slice_cost(compute_budget: &ComputeBudget, len: u64) -> u64 {
+    compute_budget.mem_op_base_cost.max(
+        compute_budget
+            .sha256_byte_cost
+            .saturating_mul(len.checked_div(2).expect("div by non-zero literal")),
+    )
+}
+
+/// Blake3 processes its input in chunks of this many bytes
+const BLAKE3_CHUNK_LEN: u64 = 1024;
+
 struct Sha256Hasher(Hasher);
 struct Blake3Hasher(blake3::Hasher);
 struct Keccak256Hasher(keccak::Hasher);
@@ -168,8 +180,8 @@ impl HasherImpl for Sha256Hasher {
     fn get_base_cost(compute_budget: &ComputeBudget) -> u64 {
         compute_budget.sha256_base_cost
     }
-    fn get_byte_cost(compute_budget: &ComputeBudget) -> u64 {
-        compute_budget.sha256_byte_cost
+    fn get_slice_cost(compute_budget: &ComputeBudget, len: u64) -> u64 {
+        get_sha256_slice_cost(compute_budget, len)
     }
     fn get_max_slices(compute_budget: &ComputeBudget) -> u64 {
         compute_budget.sha256_max_slices
@@ -193,10 +205,13 @@ impl HasherImpl for Blake3Hasher {
     }
 
     fn get_base_cost(compute_budget: &ComputeBudget) -> u64 {
-        compute_budget.sha256_base_cost
+        compute_budget.blake3_base_cost
     }
-    fn get_byte_cost(compute_budget: &ComputeBudget) -> u64 {
-        compute_budget.sha256_byte_cost
+    fn get_slice_cost(compute_budget: &ComputeBudget, len: u64) -> u64 {
+        let chunks = len.div_ceil(BLAKE3_CHUNK_LEN);
+        compute_budget
+            .mem_op_base_cost
+            .max(compute_budget.blake3_chunk_cost.saturating_mul(chunks))
     }
     fn get_max_slices(compute_budget: &ComputeBudget) -> u64 {
         compute_budget.sha256_max_slices
@@ -222,8 +237,8 @@ impl HasherImpl for Keccak256Hasher {
     fn get_base_cost(compute_budget: &ComputeBudget) -> u64 {
         compute_budget.sha256_base_cost
     }
-    fn get_byte_cost(compute_budget: &ComputeBudget) -> u64 {
-        compute_budget.sha256_byte_cost
+    fn get_slice_cost(compute_budget: &ComputeBudget, len: u64) -> u64 {
+        get_sha256_slice_cost(compute_budget, len)
     }
     fn get_max_slices(compute_budget: &ComputeBudget) -> u64 {
         compute_budget.sha256_max_slices
@@ -2117,7 +2132,6 @@ declare_builtin_function!(
     ) -> Result<u64, Error> {
         let compute_budget = invoke_context.get_compute_budget();
         let hash_base_cost = H::get_base_cost(compute_budget);
-        let hash_byte_cost = H::get_byte_cost(compute_budget);
         let hash_max_slices = H::get_max_slices(compute_budget);
         if hash_max_slices < vals_len {
             ic_msg!(
@@ -2149,14 +2163,10 @@ declare_builtin_function!(
 
             for val in vals.iter() {
                 let bytes = val.translate(memory_mapping, invoke_context.get_check_aligned())?;
-                let cost = compute_budget.mem_op_base_cost.max(
-                    hash_byte_cost.saturating_mul(
-                        val.len()
-                            .checked_div(2)
-                            .expect("div by non-zero literal"),
-                    ),
-                );
-                consume_compute_meter(invoke_context, cost)?;
+                consume_compute_meter(
+                    invoke_context,
+                    H::get_slice_cost(compute_budget, val.len()),
+                )?;
                 hasher.hash(bytes);
             }
         }
@@ -2897,6 +2907,80 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_syscall_blake3() {
+        let config = Config::default();
+        prepare_mockup!(invoke_context, program_id, bpf_loader_deprecated::id());
+
+        // spans three chunks
+        let bytes1 = [7u8; 2 * BLAKE3_CHUNK_LEN as usize + 1];
+        // shorter than a chunk
+        let bytes2 = b"flurbos";
+
+        let mock_slice1 = MockSlice {
+            vm_addr: 0x300000000,
+            len: bytes1.len(),
+        };
+        let mock_slice2 = MockSlice {
+            vm_addr: 0x400000000,
+            len: bytes2.len(),
+        };
+        let bytes_to_hash = [mock_slice1, mock_slice2];
+        let mut hash_result = [0; HASH_BYTES];
+        let ro_len = bytes_to_hash.len() as u64;
+        let ro_va = 0x100000000;
+        let rw_va = 0x200000000;
+        let mut memory_mapping = MemoryMapping::new(
+            vec![
+                MemoryRegion::new_readonly(bytes_of_slice(&bytes_to_hash), ro_va),
+                MemoryRegion::new_writable(bytes_of_slice_mut(&mut hash_result), rw_va),
+                MemoryRegion::new_readonly(&bytes1, bytes_to_hash[0].vm_addr),
+                MemoryRegion::new_readonly(bytes2, bytes_to_hash[1].vm_addr),
+            ],
+            &config,
+            SBPFVersion::V3,
+        )
+        .unwrap();
+
+        let compute_budget = invoke_context.get_compute_budget();
+        let cost = compute_budget.blake3_base_cost + compute_budget.blake3_chunk_cost * 4;
+        assert_eq!(
+            Blake3Hasher::get_slice_cost(compute_budget, bytes1.len() as u64),
+            compute_budget.blake3_chunk_cost * 3
+        );
+        invoke_context.mock_set_remaining(cost);
+
+        let result = SyscallHash::rust::<Blake3Hasher>(
+            &mut invoke_context,
+            ro_va,
+            ro_len,
+            rw_va,
+            0,
+            0,
+            &mut memory_mapping,
+        );
+        result.unwrap();
+        assert_eq!(invoke_context.get_remaining(), 0);
+
+        let hash_local = blake3::hashv(&[bytes1.as_ref(), bytes2.as_ref()]).0;
+        assert_eq!(hash_result, hash_local);
+
+        invoke_context.mock_set_remaining(cost - 1);
+        let result = SyscallHash::rust::<Blake3Hasher>(
+            &mut invoke_context,
+            ro_va,
+            ro_len,
+            rw_va,
+            0,
+            0,
+            &mut memory_mapping,
+        );
+        assert_matches!(
+            result,
+            Result::Err(error) if error.downcast_ref::<InstructionError>().unwrap() == &InstructionError::ComputationalBudgetExceeded
+        );
+    }
+
     #[test]
     fn test_syscall_edwards_curve_point_validation() {
         use solana_curve25519::curve_syscall_traits::CURVE25519_EDWARDS;
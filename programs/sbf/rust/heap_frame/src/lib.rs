@@ -0,0 +1,124 @@
+//! Example Rust-based SBF program that uses the heap requested with the
+//! `RequestHeapFrame` compute budget instruction, rather than assuming the
+//! default heap length
+
+#![allow(clippy::arithmetic_side_effects)]
+
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::{ProgramResult, HEAP_LENGTH},
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar::instructions,
+};
+
+const COMPUTE_BUDGET_PROGRAM_ID: Pubkey =
+    solana_program::pubkey!("ComputeBudget111111111111111111111111111111");
+/// The tag of `ComputeBudgetInstruction::RequestHeapFrame`
+const REQUEST_HEAP_FRAME: u8 = 1;
+
+/// A bump allocator that allocates upwards, so that its limit can be raised
+/// once the heap length is known without moving the allocations made before.
+/// Its position and limit are kept at the start of the heap, as programs
+/// can't have writable statics.
+#[cfg(target_os = "solana")]
+mod allocator {
+    use {
+        solana_program::entrypoint::{HEAP_LENGTH, HEAP_START_ADDRESS},
+        std::{
+            alloc::{GlobalAlloc, Layout},
+            mem::size_of,
+            ptr::null_mut,
+        },
+    };
+
+    const POS_PTR: *mut usize = HEAP_START_ADDRESS as *mut usize;
+    const LEN_PTR: *mut usize = (HEAP_START_ADDRESS as usize + size_of::<usize>()) as *mut usize;
+    const BOTTOM_ADDRESS: usize = HEAP_START_ADDRESS as usize + 2 * size_of::<usize>();
+
+    /// Lets the allocator use `heap_len` bytes of heap, which must not
+    /// exceed the heap length granted to the transaction
+    pub fn set_heap_len(heap_len: usize) {
+        unsafe { *LEN_PTR = heap_len };
+    }
+
+    struct GrowableBumpAllocator;
+
+    unsafe impl GlobalAlloc for GrowableBumpAllocator {
+        #[inline]
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let pos = match *POS_PTR {
+                0 => BOTTOM_ADDRESS,
+                pos => pos,
+            };
+            let heap_len = match *LEN_PTR {
+                0 => HEAP_LENGTH,
+                heap_len => heap_len,
+            };
+            let start = (pos + layout.align() - 1) & !(layout.align() - 1);
+            let end = start.saturating_add(layout.size());
+            if end > HEAP_START_ADDRESS as usize + heap_len {
+                return null_mut();
+            }
+            *POS_PTR = end;
+            start as *mut u8
+        }
+
+        #[inline]
+        unsafe fn dealloc(&self, _: *mut u8, _: Layout) {
+            // I'm a bump allocator, I don't free
+        }
+    }
+
+    #[global_allocator]
+    static A: GrowableBumpAllocator = GrowableBumpAllocator;
+}
+
+/// Returns the heap length requested by the transaction, found through the
+/// instructions sysvar
+fn requested_heap_len(instructions_account: &AccountInfo) -> Result<Option<usize>, ProgramError> {
+    let mut index = 0;
+    while let Ok(instruction) =
+        instructions::load_instruction_at_checked(index, instructions_account)
+    {
+        if instruction.program_id == COMPUTE_BUDGET_PROGRAM_ID
+            && instruction.data.first() == Some(&REQUEST_HEAP_FRAME)
+        {
+            let bytes = instruction
+                .data
+                .get(1..5)
+                .and_then(|bytes| bytes.try_into().ok())
+                .ok_or(ProgramError::InvalidInstructionData)?;
+            return Ok(Some(u32::from_le_bytes(bytes) as usize));
+        }
+        index += 1;
+    }
+    Ok(None)
+}
+
+solana_program::entrypoint!(process_instruction);
+fn process_instruction(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let instructions_account = accounts.first().ok_or(ProgramError::NotEnoughAccountKeys)?;
+    assert_eq!(*instructions_account.key, instructions::id());
+    let heap_len = requested_heap_len(instructions_account)?.unwrap_or(HEAP_LENGTH);
+    #[cfg(target_os = "solana")]
+    allocator::set_heap_len(heap_len);
+    msg!("Heap length: {}", heap_len);
+
+    // Allocate and touch the number of bytes in the instruction data
+    let len = instruction_data
+        .try_into()
+        .map(u32::from_le_bytes)
+        .map_err(|_| ProgramError::InvalidInstructionData)? as usize;
+    let mut buffer = vec![0u8; len];
+    buffer.fill(1);
+    assert_eq!(buffer.last(), len.checked_sub(1).map(|_| &1));
+    msg!("Allocated {} bytes", len);
+
+    Ok(())
+}
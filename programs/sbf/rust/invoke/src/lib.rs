@@ -7,10 +7,13 @@ use {
     solana_program::{
         account_info::AccountInfo,
         bpf_loader_deprecated,
+        compute_units::sol_remaining_compute_units,
         entrypoint::{ProgramResult, MAX_PERMITTED_DATA_INCREASE},
         instruction::Instruction,
         msg,
-        program::{get_return_data, invoke, invoke_signed, set_return_data},
+        program::{
+            get_return_data, invoke, invoke_signed, invoke_signed_unchecked, set_return_data,
+        },
         program_error::ProgramError,
         pubkey::{Pubkey, PubkeyError},
         syscalls::{
@@ -1594,6 +1597,47 @@ fn process_instruction<'a>(
             )
             .unwrap();
         }
+        TEST_INVOKE_SIGNED_UNCHECKED => {
+            msg!("TEST_INVOKE_SIGNED_UNCHECKED");
+
+            // accounts[3] is derived from b"unchecked" and signs the invocations
+            let signer_seeds: &[&[u8]] = &[b"unchecked", &[bump_seed1]];
+            let write_account_instruction = |value: u8| {
+                let mut instruction_data = vec![TEST_WRITE_ACCOUNT, 1];
+                instruction_data.extend_from_slice(&0usize.to_le_bytes());
+                instruction_data.push(value);
+                create_instruction(
+                    *program_id,
+                    &[
+                        (program_id, false, false),
+                        (accounts[1].key, true, false),
+                        (accounts[3].key, false, true),
+                    ],
+                    instruction_data,
+                )
+            };
+
+            let instruction = write_account_instruction(1);
+            let remaining = sol_remaining_compute_units();
+            invoke_signed(&instruction, accounts, &[signer_seeds])?;
+            let checked_units = remaining - sol_remaining_compute_units();
+            assert_eq!(accounts[1].data.borrow()[0], 1);
+
+            // Unlike invoke_signed, doesn't check that none of the account
+            // infos is borrowed, which holds here
+            let instruction = write_account_instruction(2);
+            let remaining = sol_remaining_compute_units();
+            invoke_signed_unchecked(&instruction, accounts, &[signer_seeds])?;
+            let unchecked_units = remaining - sol_remaining_compute_units();
+            assert_eq!(accounts[1].data.borrow()[0], 2);
+
+            msg!(
+                "invoke_signed consumed {} compute units, invoke_signed_unchecked {}",
+                checked_units,
+                unchecked_units
+            );
+            assert!(unchecked_units < checked_units);
+        }
         _ => panic!("unexpected program data"),
     }
 
@@ -1,5 +1,7 @@
 //! Example Rust-based SBF program that issues a cross-program-invocation
 
+use solana_program::instruction::InstructionError;
+
 pub const TEST_SUCCESS: u8 = 1;
 pub const TEST_PRIVILEGE_ESCALATION_SIGNER: u8 = 2;
 pub const TEST_PRIVILEGE_ESCALATION_WRITABLE: u8 = 3;
@@ -45,6 +47,87 @@ pub const TEST_STACK_HEAP_ZEROED: u8 = 42;
 pub const TEST_ACCOUNT_INFO_IN_ACCOUNT: u8 = 43;
 pub const TEST_ACCOUNT_INFO_LAMPORTS_RC: u8 = 44;
 pub const TEST_ACCOUNT_INFO_DATA_RC: u8 = 45;
+pub const TEST_INVOKE_SIGNED_UNCHECKED: u8 = 46;
+
+/// The error each failure test case of the Rust and C invoke programs fails the invoking
+/// instruction with. Tests assert against this table, so that a new failure case must be given
+/// its expected error here.
+pub const EXPECTED_INSTRUCTION_ERRORS: &[(u8, InstructionError)] = &[
+    (
+        TEST_PRIVILEGE_ESCALATION_SIGNER,
+        InstructionError::PrivilegeEscalation,
+    ),
+    (
+        TEST_PRIVILEGE_ESCALATION_WRITABLE,
+        InstructionError::PrivilegeEscalation,
+    ),
+    (
+        TEST_PPROGRAM_NOT_OWNED_BY_LOADER,
+        InstructionError::UnsupportedProgramId,
+    ),
+    (
+        TEST_PPROGRAM_NOT_EXECUTABLE,
+        InstructionError::UnsupportedProgramId,
+    ),
+    (TEST_EMPTY_ACCOUNTS_SLICE, InstructionError::MissingAccount),
+    (TEST_CAP_SEEDS, InstructionError::MaxSeedLengthExceeded),
+    (TEST_CAP_SIGNERS, InstructionError::ProgramFailedToComplete),
+    (
+        TEST_ALLOC_ACCESS_VIOLATION,
+        InstructionError::ProgramFailedToComplete,
+    ),
+    (
+        TEST_MAX_INSTRUCTION_DATA_LEN_EXCEEDED,
+        InstructionError::ProgramFailedToComplete,
+    ),
+    (
+        TEST_MAX_INSTRUCTION_ACCOUNTS_EXCEEDED,
+        InstructionError::ProgramFailedToComplete,
+    ),
+    (TEST_RETURN_ERROR, InstructionError::Custom(42)),
+    (
+        TEST_PRIVILEGE_DEESCALATION_ESCALATION_SIGNER,
+        InstructionError::PrivilegeEscalation,
+    ),
+    (
+        TEST_PRIVILEGE_DEESCALATION_ESCALATION_WRITABLE,
+        InstructionError::PrivilegeEscalation,
+    ),
+    (
+        TEST_WRITABLE_DEESCALATION_WRITABLE,
+        InstructionError::ReadonlyDataModified,
+    ),
+    (TEST_NESTED_INVOKE_TOO_DEEP, InstructionError::CallDepth),
+    (
+        TEST_CALL_PRECOMPILE,
+        InstructionError::ProgramFailedToComplete,
+    ),
+    (
+        TEST_RETURN_DATA_TOO_LARGE,
+        InstructionError::ProgramFailedToComplete,
+    ),
+    (
+        TEST_DUPLICATE_PRIVILEGE_ESCALATION_SIGNER,
+        InstructionError::PrivilegeEscalation,
+    ),
+    (
+        TEST_DUPLICATE_PRIVILEGE_ESCALATION_WRITABLE,
+        InstructionError::PrivilegeEscalation,
+    ),
+    (
+        TEST_MAX_ACCOUNT_INFOS_EXCEEDED,
+        InstructionError::ProgramFailedToComplete,
+    ),
+];
+
+/// The error the failure test case `test` is expected to fail with, see
+/// [`EXPECTED_INSTRUCTION_ERRORS`]
+pub fn expected_instruction_error(test: u8) -> Option<InstructionError> {
+    EXPECTED_INSTRUCTION_ERRORS
+        .iter()
+        .find(|(expected_test, _)| *expected_test == test)
+        .map(|(_, error)| error.clone())
+}
 
 pub const MINT_INDEX: usize = 0;
 pub const ARGUMENT_INDEX: usize = 1;
@@ -60,3 +143,22 @@ pub const FROM_INDEX: usize = 10;
 pub const ED25519_PROGRAM_INDEX: usize = 11;
 pub const INVOKE_PROGRAM_INDEX: usize = 12;
 pub const UNEXECUTABLE_PROGRAM_INDEX: usize = 13;
+
+#[cfg(test)]
+mod tests {
+    use {super::*, std::collections::HashSet};
+
+    #[test]
+    fn test_expected_instruction_errors() {
+        let mut tests = HashSet::new();
+        for (test, _) in EXPECTED_INSTRUCTION_ERRORS {
+            assert!(tests.insert(*test), "duplicate entry for test #{test}");
+        }
+        assert!(!tests.contains(&TEST_SUCCESS));
+        assert_eq!(
+            expected_instruction_error(TEST_RETURN_ERROR),
+            Some(InstructionError::Custom(42))
+        );
+        assert_eq!(expected_instruction_error(TEST_SUCCESS), None);
+    }
+}
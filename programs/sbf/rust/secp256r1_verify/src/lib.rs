@@ -0,0 +1,82 @@
+//! Example Rust-based SBF program that checks a secp256r1 signature verified by
+//! the secp256r1 precompile, as done by passkey (WebAuthn) based wallets
+
+extern crate solana_program;
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError, pubkey,
+    pubkey::Pubkey, sysvar::instructions,
+};
+
+const SECP256R1_PROGRAM_ID: Pubkey = pubkey!("Secp256r1SigVerify1111111111111111111111111");
+const COMPRESSED_PUBKEY_SERIALIZED_SIZE: usize = 33;
+const SIGNATURE_OFFSETS_START: usize = 2;
+const SIGNATURE_OFFSETS_SERIALIZED_SIZE: usize = 14;
+
+fn read_u16(data: &[u8], offset: usize) -> Result<u16, ProgramError> {
+    data.get(offset..offset + 2)
+        .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]))
+        .ok_or(ProgramError::InvalidInstructionData)
+}
+
+fn read_slice(data: &[u8], offset: u16, len: usize) -> Result<&[u8], ProgramError> {
+    let offset = offset as usize;
+    data.get(offset..offset + len)
+        .ok_or(ProgramError::InvalidInstructionData)
+}
+
+solana_program::entrypoint_no_alloc!(process_instruction);
+/// Instruction data is the index of the secp256r1 instruction, followed by the
+/// compressed public key of the expected signer and the expected message.
+fn process_instruction(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let (secp_instruction_index, rest) = instruction_data
+        .split_first()
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    if rest.len() < COMPRESSED_PUBKEY_SERIALIZED_SIZE {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let (expected_pubkey, expected_message) = rest.split_at(COMPRESSED_PUBKEY_SERIALIZED_SIZE);
+
+    let instructions_account = accounts.last().ok_or(ProgramError::NotEnoughAccountKeys)?;
+    let instruction = instructions::load_instruction_at_checked(
+        *secp_instruction_index as usize,
+        instructions_account,
+    )?;
+    if instruction.program_id != SECP256R1_PROGRAM_ID {
+        msg!("not a secp256r1 instruction");
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // The precompile has already verified every signature in the instruction,
+    // so only check that exactly one was verified, over the expected public key
+    // and message, with all data contained in the precompile instruction itself.
+    let data = &instruction.data;
+    if data.first() != Some(&1) {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+    let offsets = data
+        .get(SIGNATURE_OFFSETS_START..SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_SERIALIZED_SIZE)
+        .ok_or(ProgramError::InvalidInstructionData)?;
+    let public_key_offset = read_u16(offsets, 4)?;
+    let message_data_offset = read_u16(offsets, 8)?;
+    let message_data_size = read_u16(offsets, 10)?;
+    for instruction_index_offset in [2, 6, 12] {
+        if read_u16(offsets, instruction_index_offset)? != u16::MAX {
+            msg!("signature data is not in the secp256r1 instruction");
+            return Err(ProgramError::InvalidInstructionData);
+        }
+    }
+
+    let pubkey = read_slice(data, public_key_offset, COMPRESSED_PUBKEY_SERIALIZED_SIZE)?;
+    let message = read_slice(data, message_data_offset, message_data_size as usize)?;
+    if pubkey != expected_pubkey || message != expected_message {
+        msg!("unexpected signer or message");
+        return Err(ProgramError::MissingRequiredSignature);
+    }
+
+    msg!("verified secp256r1 signature");
+    Ok(())
+}
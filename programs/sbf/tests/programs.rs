@@ -10,6 +10,11 @@
 #[cfg(feature = "sbf_rust")]
 use {
     borsh::{from_slice, to_vec, BorshDeserialize, BorshSerialize},
+    openssl::{
+        bn::BigNumContext,
+        ec::{EcGroup, EcKey, PointConversionForm},
+        nid::Nid,
+    },
     solana_compute_budget::compute_budget::ComputeBudget,
     solana_compute_budget_instruction::instructions_processor::process_compute_budget_instructions,
     solana_feature_set::{self as feature_set, FeatureSet},
@@ -98,11 +103,7 @@ fn load_execute_and_commit_transaction(bank: &Bank, tx: Transaction) -> Transact
             &tx_batch,
             MAX_PROCESSING_AGE,
             false,
-            ExecutionRecordingConfig {
-                enable_cpi_recording: true,
-                enable_log_recording: true,
-                enable_return_data_recording: false,
-            },
+            ExecutionRecordingConfig::new(true, true, false),
             &mut ExecuteTimings::default(),
             None,
         )
@@ -564,6 +565,62 @@ fn test_return_data_and_log_data_syscall() {
     }
 }
 
+#[test]
+#[cfg(feature = "sbf_rust")]
+fn test_program_sbf_memory_usage() {
+    solana_logger::setup();
+
+    let GenesisConfigInfo {
+        genesis_config,
+        mint_keypair,
+        ..
+    } = create_genesis_config(50);
+    let (bank, bank_forks) = Bank::new_with_bank_forks_for_tests(&genesis_config);
+    let mut bank_client = BankClient::new_shared(bank.clone());
+    let authority_keypair = Keypair::new();
+    let (bank, program_id) = load_program_of_loader_v4(
+        &mut bank_client,
+        &bank_forks,
+        &mint_keypair,
+        &authority_keypair,
+        "solana_sbf_rust_alloc",
+    );
+    bank.freeze();
+
+    let instruction = Instruction::new_with_bytes(program_id, &[], vec![]);
+    let message = Message::new(&[instruction], Some(&mint_keypair.pubkey()));
+    let transaction = Transaction::new(&[&mint_keypair], message, bank.last_blockhash());
+    let sanitized_tx = RuntimeTransaction::from_transaction_for_tests(transaction);
+
+    // Memory usage is only recorded when requested
+    let result = bank.simulate_transaction(&sanitized_tx, false);
+    assert!(result.result.is_ok());
+    assert!(result.memory_usage.is_empty());
+
+    let mut recording_config = ExecutionRecordingConfig::new(false, true, true);
+    recording_config.enable_memory_usage_recording = true;
+    let result = bank.simulate_transaction_with_recording_config(&sanitized_tx, recording_config);
+    assert!(result.result.is_ok());
+    let [memory_usage] = result.memory_usage.as_slice() else {
+        panic!("expected one invocation, got {:?}", result.memory_usage);
+    };
+    assert_eq!(memory_usage.program_id, program_id);
+    assert_eq!(memory_usage.stack_height, 1);
+    assert!(memory_usage.stack_bytes > 0);
+    assert!(memory_usage.stack_bytes <= memory_usage.stack_size);
+    // The program allocates at least a 100 element vector of usize
+    assert!(memory_usage.heap_bytes >= 800);
+    assert!(memory_usage.heap_bytes <= memory_usage.heap_size);
+    assert!(result.logs.contains(&format!(
+        "Program {} used {} of {} stack bytes and {} of {} heap bytes",
+        program_id,
+        memory_usage.stack_bytes,
+        memory_usage.stack_size,
+        memory_usage.heap_bytes,
+        memory_usage.heap_size
+    )));
+}
+
 #[test]
 #[cfg(feature = "sbf_rust")]
 fn test_program_sbf_invoke_sanity() {
@@ -754,11 +811,15 @@ fn test_program_sbf_invoke_sanity() {
 
         let do_invoke_failure_test_local_with_compute_check =
             |test: u8,
-             expected_error: TransactionError,
              expected_invoked_programs: &[Pubkey],
              expected_log_messages: Option<Vec<String>>,
              should_deplete_compute_meter: bool| {
                 println!("Running failure test #{:?}", test);
+                let expected_error = TransactionError::InstructionError(
+                    0,
+                    expected_instruction_error(test)
+                        .unwrap_or_else(|| panic!("no expected error for test #{test}")),
+                );
                 let instruction_data = &[test, bump_seed1, bump_seed2, bump_seed3];
                 let signers = vec![
                     &mint_keypair,
@@ -809,12 +870,10 @@ fn test_program_sbf_invoke_sanity() {
 
         let do_invoke_failure_test_local =
             |test: u8,
-             expected_error: TransactionError,
              expected_invoked_programs: &[Pubkey],
              expected_log_messages: Option<Vec<String>>| {
                 do_invoke_failure_test_local_with_compute_check(
                     test,
-                    expected_error,
                     expected_invoked_programs,
                     expected_log_messages,
                     false, // should_deplete_compute_meter
@@ -828,56 +887,36 @@ fn test_program_sbf_invoke_sanity() {
 
         do_invoke_failure_test_local(
             TEST_PRIVILEGE_ESCALATION_SIGNER,
-            TransactionError::InstructionError(0, InstructionError::PrivilegeEscalation),
             &[invoked_program_id.clone()],
             None,
         );
 
         do_invoke_failure_test_local(
             TEST_PRIVILEGE_ESCALATION_WRITABLE,
-            TransactionError::InstructionError(0, InstructionError::PrivilegeEscalation),
             &[invoked_program_id.clone()],
             None,
         );
 
         do_invoke_failure_test_local(
             TEST_PPROGRAM_NOT_OWNED_BY_LOADER,
-            TransactionError::InstructionError(0, InstructionError::UnsupportedProgramId),
             &[argument_keypair.pubkey()],
             None,
         );
 
         do_invoke_failure_test_local(
             TEST_PPROGRAM_NOT_EXECUTABLE,
-            TransactionError::InstructionError(0, InstructionError::UnsupportedProgramId),
             &[unexecutable_program_keypair.pubkey()],
             None,
         );
 
-        do_invoke_failure_test_local(
-            TEST_EMPTY_ACCOUNTS_SLICE,
-            TransactionError::InstructionError(0, InstructionError::MissingAccount),
-            &[],
-            None,
-        );
+        do_invoke_failure_test_local(TEST_EMPTY_ACCOUNTS_SLICE, &[], None);
 
-        do_invoke_failure_test_local(
-            TEST_CAP_SEEDS,
-            TransactionError::InstructionError(0, InstructionError::MaxSeedLengthExceeded),
-            &[],
-            None,
-        );
+        do_invoke_failure_test_local(TEST_CAP_SEEDS, &[], None);
 
-        do_invoke_failure_test_local(
-            TEST_CAP_SIGNERS,
-            TransactionError::InstructionError(0, InstructionError::ProgramFailedToComplete),
-            &[],
-            None,
-        );
+        do_invoke_failure_test_local(TEST_CAP_SIGNERS, &[], None);
 
         do_invoke_failure_test_local(
             TEST_MAX_INSTRUCTION_DATA_LEN_EXCEEDED,
-            TransactionError::InstructionError(0, InstructionError::ProgramFailedToComplete),
             &[],
             Some(vec![
                 format!("Program {invoke_program_id} invoke [1]"),
@@ -890,7 +929,6 @@ fn test_program_sbf_invoke_sanity() {
 
         do_invoke_failure_test_local(
             TEST_MAX_INSTRUCTION_ACCOUNTS_EXCEEDED,
-            TransactionError::InstructionError(0, InstructionError::ProgramFailedToComplete),
             &[],
             Some(vec![
                 format!("Program {invoke_program_id} invoke [1]"),
@@ -903,7 +941,6 @@ fn test_program_sbf_invoke_sanity() {
 
         do_invoke_failure_test_local(
             TEST_MAX_ACCOUNT_INFOS_EXCEEDED,
-            TransactionError::InstructionError(0, InstructionError::ProgramFailedToComplete),
             &[],
             Some(vec![
                 format!("Program {invoke_program_id} invoke [1]"),
@@ -914,30 +951,22 @@ fn test_program_sbf_invoke_sanity() {
             ]),
         );
 
-        do_invoke_failure_test_local(
-            TEST_RETURN_ERROR,
-            TransactionError::InstructionError(0, InstructionError::Custom(42)),
-            &[invoked_program_id.clone()],
-            None,
-        );
+        do_invoke_failure_test_local(TEST_RETURN_ERROR, &[invoked_program_id.clone()], None);
 
         do_invoke_failure_test_local(
             TEST_PRIVILEGE_DEESCALATION_ESCALATION_SIGNER,
-            TransactionError::InstructionError(0, InstructionError::PrivilegeEscalation),
             &[invoked_program_id.clone()],
             None,
         );
 
         do_invoke_failure_test_local(
             TEST_PRIVILEGE_DEESCALATION_ESCALATION_WRITABLE,
-            TransactionError::InstructionError(0, InstructionError::PrivilegeEscalation),
             &[invoked_program_id.clone()],
             None,
         );
 
         do_invoke_failure_test_local_with_compute_check(
             TEST_WRITABLE_DEESCALATION_WRITABLE,
-            TransactionError::InstructionError(0, InstructionError::ReadonlyDataModified),
             &[invoked_program_id.clone()],
             None,
             true, // should_deplete_compute_meter
@@ -945,7 +974,6 @@ fn test_program_sbf_invoke_sanity() {
 
         do_invoke_failure_test_local(
             TEST_NESTED_INVOKE_TOO_DEEP,
-            TransactionError::InstructionError(0, InstructionError::CallDepth),
             &[
                 invoked_program_id.clone(),
                 invoked_program_id.clone(),
@@ -956,30 +984,18 @@ fn test_program_sbf_invoke_sanity() {
             None,
         );
 
-        do_invoke_failure_test_local(
-            TEST_CALL_PRECOMPILE,
-            TransactionError::InstructionError(0, InstructionError::ProgramFailedToComplete),
-            &[],
-            None,
-        );
+        do_invoke_failure_test_local(TEST_CALL_PRECOMPILE, &[], None);
 
-        do_invoke_failure_test_local(
-            TEST_RETURN_DATA_TOO_LARGE,
-            TransactionError::InstructionError(0, InstructionError::ProgramFailedToComplete),
-            &[],
-            None,
-        );
+        do_invoke_failure_test_local(TEST_RETURN_DATA_TOO_LARGE, &[], None);
 
         do_invoke_failure_test_local(
             TEST_DUPLICATE_PRIVILEGE_ESCALATION_SIGNER,
-            TransactionError::InstructionError(0, InstructionError::PrivilegeEscalation),
             &[invoked_program_id.clone()],
             None,
         );
 
         do_invoke_failure_test_local(
             TEST_DUPLICATE_PRIVILEGE_ESCALATION_WRITABLE,
-            TransactionError::InstructionError(0, InstructionError::PrivilegeEscalation),
             &[invoked_program_id.clone()],
             None,
         );
@@ -1032,7 +1048,10 @@ fn test_program_sbf_invoke_sanity() {
         assert_eq!(invoked_programs, vec![]);
         assert_eq!(
             result.unwrap_err(),
-            TransactionError::InstructionError(0, InstructionError::ProgramFailedToComplete)
+            TransactionError::InstructionError(
+                0,
+                expected_instruction_error(TEST_ALLOC_ACCESS_VIOLATION).unwrap()
+            )
         );
     }
 }
@@ -1276,7 +1295,7 @@ fn assert_instruction_count() {
             ("sanity", 2377),
             ("sanity++", 2277),
             ("secp256k1_recover", 25383),
-            ("sha", 1355),
+            ("sha", 1591),
             ("struct_pass", 108),
             ("struct_ret", 122),
         ]);
@@ -1297,7 +1316,7 @@ fn assert_instruction_count() {
             ("solana_sbf_rust_rand", 378),
             ("solana_sbf_rust_sanity", 51953),
             ("solana_sbf_rust_secp256k1_recover", 91185),
-            ("solana_sbf_rust_sha", 24059),
+            ("solana_sbf_rust_sha", 24295),
         ]);
     }
 
@@ -1413,6 +1432,100 @@ fn test_program_sbf_instruction_introspection() {
     assert!(bank.get_account(&sysvar::instructions::id()).is_none());
 }
 
+#[test]
+#[cfg(feature = "sbf_rust")]
+fn test_program_sbf_secp256r1_verify() {
+    solana_logger::setup();
+
+    let GenesisConfigInfo {
+        genesis_config,
+        mint_keypair,
+        ..
+    } = create_genesis_config(50_000);
+
+    let (bank, bank_forks) = Bank::new_with_bank_forks_for_tests(&genesis_config);
+    let mut bank_client = BankClient::new_shared(bank);
+    let authority_keypair = Keypair::new();
+
+    let (_bank, program_id) = load_program_of_loader_v4(
+        &mut bank_client,
+        &bank_forks,
+        &mint_keypair,
+        &authority_keypair,
+        "solana_sbf_rust_secp256r1_verify",
+    );
+
+    let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).unwrap();
+    let signing_key = EcKey::generate(&group).unwrap();
+    let pubkey = signing_key
+        .public_key()
+        .to_bytes(
+            &group,
+            PointConversionForm::COMPRESSED,
+            &mut BigNumContext::new().unwrap(),
+        )
+        .unwrap();
+    let message = b"hello passkey";
+    let secp256r1_instruction =
+        solana_secp256r1_program::new_secp256r1_instruction(message, signing_key).unwrap();
+    let verify_instruction = |secp_instruction_index: u8, message: &[u8]| {
+        let mut data = vec![secp_instruction_index];
+        data.extend_from_slice(&pubkey);
+        data.extend_from_slice(message);
+        Instruction::new_with_bytes(
+            program_id,
+            &data,
+            vec![AccountMeta::new_readonly(sysvar::instructions::id(), false)],
+        )
+    };
+
+    // Signature verified by the precompile for the expected signer and message
+    let message_with_secp = |verify_instruction: Instruction| {
+        Message::new(
+            &[secp256r1_instruction.clone(), verify_instruction],
+            Some(&mint_keypair.pubkey()),
+        )
+    };
+    let result = bank_client.send_and_confirm_message(
+        &[&mint_keypair],
+        message_with_secp(verify_instruction(0, message)),
+    );
+    assert!(result.is_ok());
+
+    // Signature over a different message
+    let result = bank_client.send_and_confirm_message(
+        &[&mint_keypair],
+        message_with_secp(verify_instruction(0, b"goodbye passkey")),
+    );
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(1, InstructionError::MissingRequiredSignature)
+    );
+
+    // Pointing at an instruction which is not a secp256r1 instruction
+    let result = bank_client.send_and_confirm_message(
+        &[&mint_keypair],
+        message_with_secp(verify_instruction(1, message)),
+    );
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(1, InstructionError::InvalidArgument)
+    );
+
+    // A tampered signature is rejected by the precompile before the program runs
+    let mut tampered_instruction = secp256r1_instruction.clone();
+    let last = tampered_instruction.data.len() - 1;
+    tampered_instruction.data[last] ^= 0xff;
+    let result = bank_client.send_and_confirm_message(
+        &[&mint_keypair],
+        Message::new(
+            &[tampered_instruction, verify_instruction(0, &message[..])],
+            Some(&mint_keypair.pubkey()),
+        ),
+    );
+    assert!(result.is_err());
+}
+
 fn get_stable_genesis_config() -> GenesisConfigInfo {
     let validator_pubkey =
         Pubkey::from_str("GLh546CXmtZdvpEzL8sxzqhhUf7KPvmGaRpFHB5W1sjV").unwrap();
@@ -4158,6 +4271,60 @@ fn test_cpi_invalid_account_info_pointers() {
     }
 }
 
+#[test]
+#[cfg(feature = "sbf_rust")]
+fn test_program_sbf_invoke_signed_unchecked() {
+    solana_logger::setup();
+
+    let GenesisConfigInfo {
+        genesis_config,
+        mint_keypair,
+        ..
+    } = create_genesis_config(100_123_456_789);
+
+    let (bank, bank_forks) = Bank::new_with_bank_forks_for_tests(&genesis_config);
+    let mut bank_client = BankClient::new_shared(bank);
+    let authority_keypair = Keypair::new();
+
+    let (bank, invoke_program_id) = load_program_of_loader_v4(
+        &mut bank_client,
+        &bank_forks,
+        &mint_keypair,
+        &authority_keypair,
+        "solana_sbf_rust_invoke",
+    );
+
+    let account_keypair = Keypair::new();
+    let account = AccountSharedData::new(
+        bank.get_minimum_balance_for_rent_exemption(1),
+        1,
+        &invoke_program_id,
+    );
+    bank.store_account(&account_keypair.pubkey(), &account);
+    let (derived_key, bump_seed) =
+        Pubkey::find_program_address(&[b"unchecked"], &invoke_program_id);
+
+    let instruction = Instruction::new_with_bytes(
+        invoke_program_id,
+        &[TEST_INVOKE_SIGNED_UNCHECKED, bump_seed, 0, 0],
+        vec![
+            AccountMeta::new(mint_keypair.pubkey(), true),
+            AccountMeta::new(account_keypair.pubkey(), false),
+            AccountMeta::new_readonly(invoke_program_id, false),
+            AccountMeta::new_readonly(derived_key, false),
+        ],
+    );
+    let message = Message::new(&[instruction], Some(&mint_keypair.pubkey()));
+    let tx = Transaction::new(&[&mint_keypair], message, bank.last_blockhash());
+    let (result, inner_instructions, logs, _) = process_transaction_and_record_inner(&bank, tx);
+    assert_eq!(result, Ok(()), "{logs:?}");
+    assert_eq!(inner_instructions[0].len(), 2);
+    assert_eq!(
+        bank.get_account(&account_keypair.pubkey()).unwrap().data(),
+        &[2]
+    );
+}
+
 #[test]
 #[cfg(feature = "sbf_rust")]
 fn test_deplete_cost_meter_with_access_violation() {
@@ -4978,6 +5145,76 @@ fn test_stack_heap_zeroed() {
     }
 }
 
+#[test]
+#[cfg(feature = "sbf_rust")]
+fn test_program_sbf_heap_frame() {
+    solana_logger::setup();
+
+    let GenesisConfigInfo {
+        genesis_config,
+        mint_keypair,
+        ..
+    } = create_genesis_config(100_123_456_789);
+
+    let (bank, bank_forks) = Bank::new_with_bank_forks_for_tests(&genesis_config);
+    let mut bank_client = BankClient::new_shared(bank);
+    let authority_keypair = Keypair::new();
+
+    let (bank, program_id) = load_program_of_loader_v4(
+        &mut bank_client,
+        &bank_forks,
+        &mint_keypair,
+        &authority_keypair,
+        "solana_sbf_rust_heap_frame",
+    );
+
+    let heap_len = 256 * 1024;
+    // Most of the heap, leaving room for the allocations of the entrypoint
+    let len = heap_len - 16 * 1024;
+    let instruction = Instruction::new_with_bytes(
+        program_id,
+        &len.to_le_bytes(),
+        vec![AccountMeta::new_readonly(sysvar::instructions::id(), false)],
+    );
+
+    // The default heap is too small
+    let message = Message::new(
+        &[
+            ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+            instruction.clone(),
+        ],
+        Some(&mint_keypair.pubkey()),
+    );
+    let tx = Transaction::new(&[&mint_keypair], message, bank.last_blockhash());
+    let (result, _, logs, _) = process_transaction_and_record_inner(&bank, tx);
+    assert!(result.is_err(), "{result:?}");
+    assert!(
+        logs.contains(&"Program log: Heap length: 32768".to_string()),
+        "{logs:?}"
+    );
+
+    // The allocator uses the whole of the requested heap
+    let message = Message::new(
+        &[
+            ComputeBudgetInstruction::set_compute_unit_limit(1_400_000),
+            ComputeBudgetInstruction::request_heap_frame(heap_len),
+            instruction,
+        ],
+        Some(&mint_keypair.pubkey()),
+    );
+    let tx = Transaction::new(&[&mint_keypair], message, bank.last_blockhash());
+    let (result, _, logs, _) = process_transaction_and_record_inner(&bank, tx);
+    assert!(result.is_ok(), "{result:?}");
+    assert!(
+        logs.contains(&format!("Program log: Heap length: {heap_len}")),
+        "{logs:?}"
+    );
+    assert!(
+        logs.contains(&format!("Program log: Allocated {len} bytes")),
+        "{logs:?}"
+    );
+}
+
 #[test]
 fn test_function_call_args() {
     // This function tests edge compiler edge cases when calling functions with more than five
@@ -5062,11 +5299,7 @@ fn test_function_call_args() {
             &tx_batch,
             MAX_PROCESSING_AGE,
             false,
-            ExecutionRecordingConfig {
-                enable_cpi_recording: false,
-                enable_log_recording: false,
-                enable_return_data_recording: true,
-            },
+            ExecutionRecordingConfig::new(false, false, true),
             &mut ExecuteTimings::default(),
             None,
         )
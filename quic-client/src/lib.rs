@@ -15,6 +15,7 @@ use {
         quic_client::QuicClientConnection as BlockingQuicClientConnection,
     },
     quinn::Endpoint,
+    rustls::client::{ClientSessionMemoryCache, ClientSessionStore},
     solana_connection_cache::{
         connection_cache::{
             BaseClientConnection, ClientError, ConnectionCache, ConnectionManager, ConnectionPool,
@@ -71,6 +72,9 @@ impl ConnectionPool for QuicPool {
     }
 }
 
+/// Number of servers for which TLS session tickets are kept when 0-RTT is enabled
+const ZERO_RTT_SESSION_CACHE_SIZE: usize = 1024;
+
 pub struct QuicConfig {
     // Arc to prevent having to copy the struct
     client_certificate: RwLock<Arc<QuicClientCertificate>>,
@@ -80,6 +84,9 @@ pub struct QuicConfig {
     // The optional specified endpoint for the quic based client connections
     // If not specified, the connection cache will create as needed.
     client_endpoint: Option<Endpoint>,
+
+    // Session tickets shared by the endpoints of all pools when 0-RTT is enabled
+    zero_rtt_session_store: Option<Arc<dyn ClientSessionStore>>,
 }
 
 impl Clone for QuicConfig {
@@ -90,6 +97,7 @@ impl Clone for QuicConfig {
             maybe_staked_nodes: self.maybe_staked_nodes.clone(),
             maybe_client_pubkey: self.maybe_client_pubkey,
             client_endpoint: self.client_endpoint.clone(),
+            zero_rtt_session_store: self.zero_rtt_session_store.clone(),
         }
     }
 }
@@ -105,6 +113,7 @@ impl NewConnectionConfig for QuicConfig {
             maybe_staked_nodes: None,
             maybe_client_pubkey: None,
             client_endpoint: None,
+            zero_rtt_session_store: None,
         })
     }
 }
@@ -112,7 +121,14 @@ impl NewConnectionConfig for QuicConfig {
 impl QuicConfig {
     fn create_endpoint(&self) -> QuicLazyInitializedEndpoint {
         let cert_guard = self.client_certificate.read().unwrap();
-        QuicLazyInitializedEndpoint::new(cert_guard.clone(), self.client_endpoint.as_ref().cloned())
+        let endpoint = QuicLazyInitializedEndpoint::new(
+            cert_guard.clone(),
+            self.client_endpoint.as_ref().cloned(),
+        );
+        match &self.zero_rtt_session_store {
+            Some(session_store) => endpoint.with_zero_rtt(session_store.clone()),
+            None => endpoint,
+        }
     }
 
     pub fn update_client_certificate(&mut self, keypair: &Keypair, _ipaddr: IpAddr) {
@@ -149,6 +165,21 @@ impl QuicConfig {
     pub fn update_client_endpoint(&mut self, client_endpoint: Endpoint) {
         self.client_endpoint = Some(client_endpoint);
     }
+
+    /// Resume TLS sessions across all connections of the cache and send data as 0-RTT early
+    /// data when reconnecting to a server seen before, e.g. a leader coming back around.
+    ///
+    /// Disabled by default, as early data can be replayed by an attacker.
+    pub fn set_zero_rtt(&mut self, enabled: bool) {
+        self.zero_rtt_session_store = enabled.then(|| {
+            Arc::new(ClientSessionMemoryCache::new(ZERO_RTT_SESSION_CACHE_SIZE))
+                as Arc<dyn ClientSessionStore>
+        });
+    }
+
+    pub fn zero_rtt_enabled(&self) -> bool {
+        self.zero_rtt_session_store.is_some()
+    }
 }
 
 pub struct Quic(Arc<QuicClient>);
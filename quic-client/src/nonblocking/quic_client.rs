@@ -11,6 +11,7 @@ use {
         ConnectionError, Endpoint, EndpointConfig, IdleTimeout, TokioRuntime, TransportConfig,
         WriteError,
     },
+    rustls::client::{ClientSessionStore, Resumption},
     solana_connection_cache::{
         client_connection::ClientStats, connection_cache_stats::ConnectionCacheStats,
         nonblocking::client_connection::ClientConnection,
@@ -41,6 +42,9 @@ pub struct QuicLazyInitializedEndpoint {
     endpoint: OnceCell<Arc<Endpoint>>,
     client_certificate: Arc<QuicClientCertificate>,
     client_endpoint: Option<Endpoint>,
+    // When set, TLS session tickets are kept here instead of in a cache private to the endpoint,
+    // and new connections to servers with a ticket send their data as 0-RTT early data.
+    zero_rtt_session_store: Option<Arc<dyn ClientSessionStore>>,
 }
 
 #[derive(Error, Debug)]
@@ -70,9 +74,25 @@ impl QuicLazyInitializedEndpoint {
             endpoint: OnceCell::<Arc<Endpoint>>::new(),
             client_certificate,
             client_endpoint,
+            zero_rtt_session_store: None,
         }
     }
 
+    /// Enable 0-RTT for new connections, resuming TLS sessions from `session_store`. The store
+    /// can be shared between endpoints so that a server's ticket outlives the endpoint it was
+    /// obtained through.
+    ///
+    /// Early data can be replayed by an attacker, so this should only be enabled when the data
+    /// sent is safe to be received more than once, as is the case for signed transactions.
+    pub fn with_zero_rtt(mut self, session_store: Arc<dyn ClientSessionStore>) -> Self {
+        self.zero_rtt_session_store = Some(session_store);
+        self
+    }
+
+    fn zero_rtt_enabled(&self) -> bool {
+        self.zero_rtt_session_store.is_some()
+    }
+
     fn create_endpoint(&self) -> Endpoint {
         let mut endpoint = if let Some(endpoint) = &self.client_endpoint {
             endpoint.clone()
@@ -98,6 +118,9 @@ impl QuicLazyInitializedEndpoint {
             .expect("Failed to set QUIC client certificates");
         crypto.enable_early_data = true;
         crypto.alpn_protocols = vec![ALPN_TPU_PROTOCOL_ID.to_vec()];
+        if let Some(session_store) = &self.zero_rtt_session_store {
+            crypto.resumption = Resumption::store(session_store.clone());
+        }
 
         let mut config = ClientConfig::new(Arc::new(QuicClientConfig::try_from(crypto).unwrap()));
         let mut transport_config = TransportConfig::default();
@@ -140,6 +163,7 @@ impl Default for QuicLazyInitializedEndpoint {
 struct QuicNewConnection {
     endpoint: Arc<Endpoint>,
     connection: Arc<Connection>,
+    server_name: String,
 }
 
 impl QuicNewConnection {
@@ -148,12 +172,51 @@ impl QuicNewConnection {
         endpoint: Arc<QuicLazyInitializedEndpoint>,
         addr: SocketAddr,
         stats: &ClientStats,
+        connection_stats: &Arc<ConnectionCacheStats>,
     ) -> Result<Self, QuicError> {
         let mut make_connection_measure = Measure::start("make_connection_measure");
+        let zero_rtt = endpoint.zero_rtt_enabled();
         let endpoint = endpoint.get_endpoint().await;
 
-        let connecting = endpoint.connect(addr, "connect")?;
+        let server_name = server_name(&addr, zero_rtt);
+        let connecting = endpoint.connect(addr, &server_name)?;
         stats.total_connections.fetch_add(1, Ordering::Relaxed);
+        let connecting = if zero_rtt {
+            match connecting.into_0rtt() {
+                Ok((connection, zero_rtt_accepted)) => {
+                    // Don't wait for the handshake, the data goes out as early data. Should the
+                    // server reject it, the writes fail with ZeroRttRejected and are retried
+                    // once the handshake completes, see `_send_buffer_using_conn`. Whether it
+                    // was accepted is only known then, so it is recorded in the background.
+                    let connection_stats = connection_stats.clone();
+                    tokio::spawn(async move {
+                        let client_stats = &connection_stats.total_client_stats;
+                        if zero_rtt_accepted.await {
+                            client_stats
+                                .zero_rtt_accepts
+                                .fetch_add(1, Ordering::Relaxed);
+                        } else {
+                            client_stats
+                                .zero_rtt_rejects
+                                .fetch_add(1, Ordering::Relaxed);
+                        }
+                    });
+                    make_connection_measure.stop();
+                    stats
+                        .make_connection_ms
+                        .fetch_add(make_connection_measure.as_ms(), Ordering::Relaxed);
+                    return Ok(Self {
+                        endpoint,
+                        connection: Arc::new(connection),
+                        server_name,
+                    });
+                }
+                // No ticket for this server yet.
+                Err(connecting) => connecting,
+            }
+        } else {
+            connecting
+        };
         if let Ok(connecting_result) = timeout(QUIC_CONNECTION_HANDSHAKE_TIMEOUT, connecting).await
         {
             if connecting_result.is_err() {
@@ -169,6 +232,7 @@ impl QuicNewConnection {
             Ok(Self {
                 endpoint,
                 connection: Arc::new(connection),
+                server_name,
             })
         } else {
             Err(ConnectionError::TimedOut.into())
@@ -187,7 +251,7 @@ impl QuicNewConnection {
         addr: SocketAddr,
         stats: &ClientStats,
     ) -> Result<Arc<Connection>, QuicError> {
-        let connecting = self.endpoint.connect(addr, "connect")?;
+        let connecting = self.endpoint.connect(addr, &self.server_name)?;
         stats.total_connections.fetch_add(1, Ordering::Relaxed);
         let connection = match connecting.into_0rtt() {
             Ok((connection, zero_rtt)) => {
@@ -219,6 +283,18 @@ impl QuicNewConnection {
     }
 }
 
+/// The server name presented when connecting to `addr`. Session tickets are stored per server
+/// name, so with 0-RTT enabled each address gets its own name in order not to offer a server
+/// the ticket of another one.
+fn server_name(addr: &SocketAddr, zero_rtt: bool) -> String {
+    if zero_rtt {
+        let ip = addr.ip().to_string().replace(['.', ':'], "-");
+        format!("p{ip}-{}", addr.port())
+    } else {
+        "connect".to_string()
+    }
+}
+
 pub struct QuicClient {
     endpoint: Arc<QuicLazyInitializedEndpoint>,
     connection: Arc<Mutex<Option<QuicNewConnection>>>,
@@ -240,6 +316,17 @@ impl QuicClient {
         data: &[u8],
         connection: &Connection,
     ) -> Result<(), QuicError> {
+        match Self::write_uni(data, connection).await {
+            Err(QuicError::WriteError(WriteError::ZeroRttRejected)) => {
+                // The server did not accept the early data. The connection carries on as a
+                // regular one, so the data only has to be sent again.
+                Self::write_uni(data, connection).await
+            }
+            result => result,
+        }
+    }
+
+    async fn write_uni(data: &[u8], connection: &Connection) -> Result<(), QuicError> {
         let mut send_stream = connection.open_uni().await?;
         send_stream.write_all(data).await?;
         Ok(())
@@ -299,6 +386,7 @@ impl QuicClient {
                             self.endpoint.clone(),
                             self.addr,
                             stats,
+                            &connection_stats,
                         )
                         .await;
                         match conn {
@@ -419,9 +507,11 @@ impl QuicClient {
     where
         T: AsRef<[u8]>,
     {
-        self._send_buffer(data.as_ref(), stats, connection_stats)
-            .await
-            .map_err(Into::<ClientErrorKind>::into)?;
+        let result = self
+            ._send_buffer(data.as_ref(), stats, connection_stats.clone())
+            .await;
+        self.record_send_result(&result, &connection_stats);
+        result.map_err(Into::<ClientErrorKind>::into)?;
         Ok(())
     }
 
@@ -448,17 +538,36 @@ impl QuicClient {
         if buffers.is_empty() {
             return Ok(());
         }
-        let connection = self
-            ._send_buffer(buffers[0].as_ref(), stats, connection_stats)
-            .await
-            .map_err(Into::<ClientErrorKind>::into)?;
+        let result = self
+            ._send_buffer(buffers[0].as_ref(), stats, connection_stats.clone())
+            .await;
+        self.record_send_result(&result, &connection_stats);
+        let connection = result.map_err(Into::<ClientErrorKind>::into)?;
 
         for data in buffers[1..buffers.len()].iter() {
-            Self::_send_buffer_using_conn(data.as_ref(), &connection).await?;
+            if let Err(err) = Self::_send_buffer_using_conn(data.as_ref(), &connection).await {
+                connection_stats.connection_health.record_failure(self.addr);
+                return Err(err.into());
+            }
         }
         Ok(())
     }
 
+    /// Feed the outcome of a send into the health tracking of the connection cache, which
+    /// re-creates connections and trips the circuit breaker of peers that keep misbehaving.
+    fn record_send_result(
+        &self,
+        result: &Result<Arc<Connection>, QuicError>,
+        connection_stats: &ConnectionCacheStats,
+    ) {
+        match result {
+            Ok(connection) => connection_stats
+                .connection_health
+                .record_success(self.addr, Some(connection.rtt())),
+            Err(_) => connection_stats.connection_health.record_failure(self.addr),
+        }
+    }
+
     pub fn server_addr(&self) -> &SocketAddr {
         &self.addr
     }
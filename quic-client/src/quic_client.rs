@@ -111,6 +111,9 @@ fn handle_send_result(
             client_stats.send_timeout.fetch_add(1, Ordering::Relaxed);
             let stats = connection.connection_stats();
             stats.add_client_stats(&client_stats, 0, false);
+            stats
+                .connection_health
+                .record_failure(*connection.server_addr());
             info!("Timedout sending data {:?}", connection.server_addr());
             Err(TransportError::Custom("Timedout sending data".to_string()))
         }
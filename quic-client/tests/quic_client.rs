@@ -200,6 +200,114 @@ mod tests {
         t.await.unwrap();
     }
 
+    #[tokio::test]
+    async fn test_nonblocking_quic_client_zero_rtt() {
+        use {
+            quinn::{
+                crypto::rustls::QuicServerConfig, Endpoint, EndpointConfig, ServerConfig,
+                TokioRuntime,
+            },
+            rustls::client::{ClientSessionMemoryCache, ClientSessionStore},
+            solana_connection_cache::nonblocking::client_connection::ClientConnection,
+            solana_quic_client::nonblocking::quic_client::QuicClientConnection,
+            solana_streamer::nonblocking::quic::ALPN_TPU_PROTOCOL_ID,
+            solana_tls_utils::tls_server_config_builder,
+            tokio::{sync::mpsc::unbounded_channel, time::timeout},
+        };
+        solana_logger::setup();
+
+        // The TPU server does not take early data, so the client is pointed at a bare server
+        // which does, and which reports the size of every stream it receives.
+        let (cert, priv_key) = new_dummy_x509_certificate(&Keypair::new());
+        let mut server_tls_config = tls_server_config_builder()
+            .with_single_cert(vec![cert], priv_key)
+            .unwrap();
+        server_tls_config.alpn_protocols = vec![ALPN_TPU_PROTOCOL_ID.to_vec()];
+        server_tls_config.max_early_data_size = u32::MAX;
+        let server_config = ServerConfig::with_crypto(Arc::new(
+            QuicServerConfig::try_from(server_tls_config).unwrap(),
+        ));
+        let server_endpoint = Endpoint::new(
+            EndpointConfig::default(),
+            Some(server_config),
+            bind_to_localhost().unwrap(),
+            Arc::new(TokioRuntime),
+        )
+        .unwrap();
+        let server_addr = server_endpoint.local_addr().unwrap();
+        let (sender, mut receiver) = unbounded_channel();
+        let server = tokio::spawn({
+            let server_endpoint = server_endpoint.clone();
+            async move {
+                while let Some(incoming) = server_endpoint.accept().await {
+                    let Ok(connection) = incoming.await else {
+                        continue;
+                    };
+                    let sender = sender.clone();
+                    tokio::spawn(async move {
+                        while let Ok(mut stream) = connection.accept_uni().await {
+                            let data = stream.read_to_end(PACKET_DATA_SIZE).await.unwrap();
+                            let _ = sender.send(data.len());
+                        }
+                    });
+                }
+            }
+        });
+
+        let session_store: Arc<dyn ClientSessionStore> = Arc::new(ClientSessionMemoryCache::new(8));
+        let num_packets_per_client: usize = 10;
+
+        // The first client does a full handshake and gets a session ticket. The second one has
+        // its own endpoint but shares the session store, so it sends as 0-RTT early data.
+        let mut client_stats = vec![];
+        for _ in 0..2 {
+            let endpoint =
+                QuicLazyInitializedEndpoint::default().with_zero_rtt(session_store.clone());
+            let connection_stats = Arc::new(ConnectionCacheStats::default());
+            let client = QuicClientConnection::new(
+                Arc::new(endpoint),
+                server_addr,
+                connection_stats.clone(),
+            );
+            for _ in 0..num_packets_per_client {
+                client.send_data(&[0u8; PACKET_DATA_SIZE]).await.unwrap();
+            }
+            for _ in 0..num_packets_per_client {
+                let num_bytes = timeout(Duration::from_secs(10), receiver.recv())
+                    .await
+                    .unwrap()
+                    .unwrap();
+                assert_eq!(num_bytes, PACKET_DATA_SIZE);
+            }
+            client_stats.push(connection_stats);
+        }
+
+        // Whether the early data was accepted is recorded once the handshake completes.
+        let zero_rtt_accepts = |stats: &ConnectionCacheStats| {
+            stats
+                .total_client_stats
+                .zero_rtt_accepts
+                .load(Ordering::Relaxed)
+        };
+        let zero_rtt_rejects = |stats: &ConnectionCacheStats| {
+            stats
+                .total_client_stats
+                .zero_rtt_rejects
+                .load(Ordering::Relaxed)
+        };
+        let now = Instant::now();
+        while zero_rtt_accepts(&client_stats[1]) == 0 && now.elapsed() < Duration::from_secs(10) {
+            sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(zero_rtt_accepts(&client_stats[0]), 0);
+        assert_eq!(zero_rtt_rejects(&client_stats[0]), 0);
+        assert_eq!(zero_rtt_accepts(&client_stats[1]), 1);
+        assert_eq!(zero_rtt_rejects(&client_stats[1]), 0);
+
+        server_endpoint.close(0u32.into(), b"");
+        server.await.unwrap();
+    }
+
     #[test]
     fn test_quic_bi_direction() {
         /// This tests bi-directional quic communication. There are the following components
@@ -0,0 +1,339 @@
+#![allow(clippy::arithmetic_side_effects)]
+//! Replays a captured ledger segment, a snapshot archive followed by the shreds of the slots after
+//! it, through the replay path in isolation, and reports the slots/s along with the time spent in
+//! each stage of replay.  Since the segment is fixed, the results can be compared across builds.
+
+use {
+    clap::{crate_description, crate_name, value_t, value_t_or_exit, App, Arg},
+    log::*,
+    solana_accounts_db::{
+        hardened_unpack::{open_genesis_config, MAX_GENESIS_ARCHIVE_UNPACKED_SIZE},
+        utils::{create_all_accounts_run_and_snapshot_dirs, move_and_async_delete_path_contents},
+    },
+    solana_core::{
+        accounts_hash_verifier::AccountsHashVerifier,
+        snapshot_packager_service::PendingSnapshotPackages,
+    },
+    solana_entry::entry::VerifyRecyclers,
+    solana_ledger::{
+        bank_forks_utils,
+        blockstore::Blockstore,
+        blockstore_options::{AccessType, BlockstoreOptions},
+        blockstore_processor::{
+            confirm_slot, ConfirmationProgress, ConfirmationTiming, ProcessOptions,
+        },
+    },
+    solana_measure::{measure::Measure, measure_us},
+    solana_rayon_threadlimit::get_max_thread_count,
+    solana_runtime::{
+        accounts_background_service::{
+            AbsRequestHandlers, AbsRequestSender, AccountsBackgroundService,
+            PrunedBanksRequestHandler, SnapshotRequestHandler,
+        },
+        bank::Bank,
+        prioritization_fee_cache::PrioritizationFeeCache,
+        snapshot_config::SnapshotConfig,
+    },
+    solana_sdk::clock::Slot,
+    solana_timings::ExecuteTimingType,
+    std::{
+        fmt::Display,
+        path::PathBuf,
+        process::exit,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc, Mutex, RwLock,
+        },
+    },
+};
+
+/// Directory within the ledger holding the accounts and bank snapshots of the bench, so that a
+/// validator using the same ledger is left undisturbed
+const REPLAY_BENCH_DIRECTORY: &str = "replay-bench";
+
+fn exit_with_error(message: impl Display) -> ! {
+    eprintln!("{message}");
+    exit(1);
+}
+
+fn main() {
+    solana_logger::setup_with_default("solana=info");
+
+    let matches = App::new(crate_name!())
+        .about(crate_description!())
+        .version(solana_version::version!())
+        .arg(
+            Arg::with_name("ledger_path")
+                .long("ledger")
+                .value_name("DIR")
+                .takes_value(true)
+                .required(true)
+                .help(
+                    "Use DIR as ledger location. It must hold the genesis config and the shreds \
+                     of the slots to replay",
+                ),
+        )
+        .arg(
+            Arg::with_name("snapshots")
+                .long("snapshots")
+                .value_name("DIR")
+                .takes_value(true)
+                .help("Load the snapshot archives from DIR [default: --ledger value]"),
+        )
+        .arg(
+            Arg::with_name("num_slots")
+                .long("num-slots")
+                .value_name("NUM")
+                .takes_value(true)
+                .help(
+                    "Replay at most NUM rooted slots after the snapshot slot [default: all \
+                     rooted slots in the ledger]",
+                ),
+        )
+        .arg(
+            Arg::with_name("replay_threads")
+                .long("replay-threads")
+                .value_name("NUM")
+                .takes_value(true)
+                .help("Number of threads executing transactions [default: number of cores]"),
+        )
+        .arg(
+            Arg::with_name("skip_verification")
+                .long("skip-verification")
+                .takes_value(false)
+                .help("Skip the PoH and transaction signature verification of the entries"),
+        )
+        .get_matches();
+
+    let ledger_path = PathBuf::from(value_t_or_exit!(matches, "ledger_path", String));
+    let snapshot_archives_dir = value_t!(matches, "snapshots", String)
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| ledger_path.clone());
+    let num_slots = value_t!(matches, "num_slots", usize).unwrap_or(usize::MAX);
+    let replay_threads =
+        value_t!(matches, "replay_threads", usize).unwrap_or_else(|_| get_max_thread_count());
+    let skip_verification = matches.is_present("skip_verification");
+
+    let genesis_config = open_genesis_config(&ledger_path, MAX_GENESIS_ARCHIVE_UNPACKED_SIZE)
+        .unwrap_or_else(|err| exit_with_error(format!("Failed to open genesis config: {err}")));
+    // Secondary access leaves the ledger untouched, so that the same segment can be replayed
+    // again, even while a validator is using it
+    let blockstore = Blockstore::open_with_options(
+        &ledger_path,
+        BlockstoreOptions {
+            access_type: AccessType::Secondary,
+            ..BlockstoreOptions::default()
+        },
+    )
+    .unwrap_or_else(|err| exit_with_error(format!("Failed to open blockstore: {err:?}")));
+
+    let bench_path = ledger_path.join(REPLAY_BENCH_DIRECTORY);
+    if bench_path.exists() {
+        move_and_async_delete_path_contents(&bench_path);
+    }
+    let bank_snapshots_dir = bench_path.join("snapshot");
+    let (account_paths, _account_snapshot_paths) =
+        create_all_accounts_run_and_snapshot_dirs(&[bench_path.join("accounts")]).unwrap_or_else(
+            |err| exit_with_error(format!("Failed to create accounts directories: {err}")),
+        );
+    let snapshot_config = SnapshotConfig {
+        full_snapshot_archives_dir: snapshot_archives_dir.clone(),
+        incremental_snapshot_archives_dir: snapshot_archives_dir,
+        bank_snapshots_dir,
+        ..SnapshotConfig::new_load_only()
+    };
+    let process_options = ProcessOptions {
+        run_verification: !skip_verification,
+        ..ProcessOptions::default()
+    };
+
+    let exit = Arc::new(AtomicBool::new(false));
+    let (bank_forks, leader_schedule_cache, ..) = bank_forks_utils::load_bank_forks(
+        &genesis_config,
+        &blockstore,
+        account_paths,
+        Some(&snapshot_config),
+        &process_options,
+        None,
+        None,
+        None,
+        exit.clone(),
+    )
+    .unwrap_or_else(|err| exit_with_error(format!("Failed to load snapshot: {err}")));
+
+    // Rooting the replayed banks requests epoch accounts hash calculations, which must be served
+    // for later banks to freeze
+    let (accounts_package_sender, accounts_package_receiver) = crossbeam_channel::unbounded();
+    let accounts_hash_verifier = AccountsHashVerifier::new(
+        accounts_package_sender.clone(),
+        accounts_package_receiver,
+        Arc::new(Mutex::new(PendingSnapshotPackages::default())),
+        exit.clone(),
+        SnapshotConfig::new_load_only(),
+    );
+    let (snapshot_request_sender, snapshot_request_receiver) = crossbeam_channel::unbounded();
+    let accounts_background_request_sender = AbsRequestSender::new(snapshot_request_sender.clone());
+    let pruned_banks_receiver =
+        AccountsBackgroundService::setup_bank_drop_callback(bank_forks.clone());
+    let accounts_background_service = AccountsBackgroundService::new(
+        bank_forks.clone(),
+        exit.clone(),
+        AbsRequestHandlers {
+            snapshot_request_handler: SnapshotRequestHandler {
+                snapshot_config: Arc::new(RwLock::new(SnapshotConfig::new_load_only())),
+                snapshot_request_sender,
+                snapshot_request_receiver,
+                accounts_package_sender,
+            },
+            pruned_banks_request_handler: PrunedBanksRequestHandler {
+                pruned_banks_receiver,
+            },
+        },
+        false,
+    );
+
+    let start_slot = bank_forks.read().unwrap().root();
+    let slots: Vec<Slot> = blockstore
+        .rooted_slot_iterator(start_slot)
+        .unwrap_or_else(|err| exit_with_error(format!("Failed to read roots: {err:?}")))
+        .skip_while(|slot| *slot <= start_slot)
+        .take(num_slots)
+        .collect();
+    if slots.is_empty() {
+        exit_with_error(format!(
+            "The ledger holds no rooted slots after the snapshot slot {start_slot}"
+        ));
+    }
+    info!(
+        "Replaying {} slots from slot {start_slot} with {replay_threads} threads",
+        slots.len()
+    );
+
+    let replay_tx_thread_pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(replay_threads)
+        .thread_name(|i| format!("solReplayTx{i:02}"))
+        .build()
+        .expect("new rayon threadpool");
+    let recyclers = VerifyRecyclers::default();
+    let ignored_prioritization_fee_cache = PrioritizationFeeCache::new(0u64);
+    let mut confirmation_timing = ConfirmationTiming::default();
+    let mut freeze_us = 0;
+    let mut set_root_us = 0;
+    let mut num_txs = 0;
+    let mut num_entries = 0;
+    let mut num_mismatched_bank_hashes = 0;
+
+    let mut replay_time = Measure::start("replay");
+    for &slot in &slots {
+        let parent = bank_forks.read().unwrap().root_bank();
+        let parent_slot = blockstore
+            .meta(slot)
+            .ok()
+            .flatten()
+            .filter(|meta| meta.is_full())
+            .and_then(|meta| meta.parent_slot);
+        if parent_slot != Some(parent.slot()) {
+            exit_with_error(format!(
+                "Slot {slot} is incomplete or not a child of slot {}",
+                parent.slot()
+            ));
+        }
+
+        let leader = leader_schedule_cache
+            .slot_leader_at(slot, Some(&parent))
+            .unwrap_or_else(|| exit_with_error(format!("No leader for slot {slot}")));
+        let bank = bank_forks.write().unwrap().insert(Bank::new_from_parent(
+            parent.clone(),
+            &leader,
+            slot,
+        ));
+        let mut progress = ConfirmationProgress::new(parent.last_blockhash());
+        confirm_slot(
+            &blockstore,
+            &bank,
+            &replay_tx_thread_pool,
+            &mut confirmation_timing,
+            &mut progress,
+            skip_verification,
+            None,
+            None,
+            None,
+            &recyclers,
+            None,
+            false,
+            None,
+            &ignored_prioritization_fee_cache,
+        )
+        .unwrap_or_else(|err| exit_with_error(format!("Failed to replay slot {slot}: {err}")));
+        if !bank.is_complete() {
+            exit_with_error(format!("Slot {slot} has fewer ticks than expected"));
+        }
+
+        let ((), us) = measure_us!(bank.freeze());
+        freeze_us += us;
+        if let Some(expected_hash) = blockstore.get_bank_hash(slot) {
+            if bank.hash() != expected_hash {
+                warn!(
+                    "Bank hash mismatch for slot {slot}: {}, expected {expected_hash}",
+                    bank.hash()
+                );
+                num_mismatched_bank_hashes += 1;
+            }
+        }
+        num_txs += progress.num_txs;
+        num_entries += progress.num_entries;
+
+        let (result, us) = measure_us!(bank_forks.write().unwrap().set_root(
+            slot,
+            &accounts_background_request_sender,
+            None,
+        ));
+        result.unwrap_or_else(|err| exit_with_error(format!("Failed to root slot {slot}: {err}")));
+        set_root_us += us;
+    }
+    replay_time.stop();
+
+    exit.store(true, Ordering::Relaxed);
+    accounts_background_service.join().unwrap();
+    accounts_hash_verifier.join().unwrap();
+
+    let num_slots = slots.len() as u64;
+    let secs = replay_time.as_s() as f64;
+    println!(
+        "Replayed {num_slots} slots, {}..={}, with {num_entries} entries and {num_txs} \
+         transactions in {secs:.2}s",
+        slots.first().unwrap(),
+        slots.last().unwrap(),
+    );
+    println!(
+        "slots/s: {:.2}, txs/s: {:.2}",
+        num_slots as f64 / secs,
+        num_txs as f64 / secs
+    );
+    if num_mismatched_bank_hashes > 0 {
+        println!("WARNING: {num_mismatched_bank_hashes} slots replayed to unexpected bank hashes");
+    }
+
+    // The breakdown of replay is summed over the replay threads, so it may exceed the wall clock
+    // time of replay
+    let execute_timings = &confirmation_timing.batch_execute.totals.metrics;
+    println!("{:<24}{:>16}{:>16}", "stage", "total(us)", "per slot(us)");
+    for (stage, us) in [
+        ("fetch_entries", confirmation_timing.fetch_elapsed),
+        ("poh_verify", confirmation_timing.poh_verify_elapsed),
+        (
+            "transaction_verify",
+            confirmation_timing.transaction_verify_elapsed,
+        ),
+        ("replay", confirmation_timing.replay_elapsed),
+        ("  check", execute_timings[ExecuteTimingType::CheckUs].0),
+        ("  load", execute_timings[ExecuteTimingType::LoadUs].0),
+        ("  execute", execute_timings[ExecuteTimingType::ExecuteUs].0),
+        ("  store", execute_timings[ExecuteTimingType::StoreUs].0),
+        ("confirmation", confirmation_timing.confirmation_elapsed),
+        ("freeze", freeze_us),
+        ("set_root", set_root_us),
+    ] {
+        println!("{stage:<24}{us:>16}{:>16}", us / num_slots);
+    }
+}
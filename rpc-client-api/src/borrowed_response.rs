@@ -0,0 +1,298 @@
+//! Borrowed variants of the account response types.
+//!
+//! Strings in these types borrow from the JSON they are deserialized from,
+//! instead of being copied into new allocations. This matters for clients
+//! deserializing large volumes of account data, such as indexers calling
+//! `getProgramAccounts`. The JSON must outlive the deserialized response,
+//! so it must be deserialized with [`serde_json::from_str`] or
+//! [`serde_json::from_slice`] rather than from a reader.
+//!
+//! ```
+//! # use solana_rpc_client_api::{borrowed_response::UiAccountRef, response::Response};
+//! let json = r#"{
+//!     "context": {"slot": 1},
+//!     "value": {
+//!         "lamports": 1,
+//!         "data": ["AQID", "base64"],
+//!         "owner": "11111111111111111111111111111111",
+//!         "executable": false,
+//!         "rentEpoch": 0,
+//!         "space": 3
+//!     }
+//! }"#;
+//! let response: Response<Option<UiAccountRef>> = serde_json::from_str(json).unwrap();
+//! assert_eq!(response.value.unwrap().data.decode().unwrap(), vec![1, 2, 3]);
+//! ```
+use {
+    base64::{prelude::BASE64_STANDARD, Engine},
+    serde_json::Value,
+    solana_account::WritableAccount,
+    solana_account_decoder_client_types::{
+        ParsedAccount, UiAccount, UiAccountData, UiAccountEncoding,
+    },
+    solana_pubkey::{ParsePubkeyError, Pubkey},
+    std::{borrow::Cow, str::FromStr},
+    thiserror::Error,
+};
+
+#[derive(Error, Debug)]
+#[non_exhaustive]
+pub enum AccountDecodeError {
+    #[error("account data is not binary encoded")]
+    NotBinary,
+    #[error("base58 decode error")]
+    Base58DecodeError(#[from] bs58::decode::Error),
+    #[error("base64 decode error")]
+    Base64DecodeError(#[from] base64::DecodeError),
+    #[error("zstd decompress error")]
+    ZstdDecompressError(#[source] std::io::Error),
+    #[error("base64+zstd account data requires the zstd feature")]
+    ZstdUnsupported,
+    #[error("invalid pubkey")]
+    InvalidPubkey(#[from] ParsePubkeyError),
+}
+
+/// Borrowed variant of [`UiAccount`].
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct UiAccountRef<'a> {
+    pub lamports: u64,
+    #[serde(borrow)]
+    pub data: UiAccountDataRef<'a>,
+    #[serde(borrow)]
+    pub owner: Cow<'a, str>,
+    pub executable: bool,
+    pub rent_epoch: u64,
+    pub space: Option<u64>,
+}
+
+impl UiAccountRef<'_> {
+    pub fn decode<T: WritableAccount>(&self) -> Result<T, AccountDecodeError> {
+        let data = self.data.decode()?;
+        Ok(T::create(
+            self.lamports,
+            data,
+            Pubkey::from_str(&self.owner)?,
+            self.executable,
+            self.rent_epoch,
+        ))
+    }
+}
+
+impl From<UiAccountRef<'_>> for UiAccount {
+    fn from(account: UiAccountRef<'_>) -> Self {
+        Self {
+            lamports: account.lamports,
+            data: account.data.into(),
+            owner: account.owner.into_owned(),
+            executable: account.executable,
+            rent_epoch: account.rent_epoch,
+            space: account.space,
+        }
+    }
+}
+
+/// Borrowed variant of [`UiAccountData`].
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", untagged)]
+pub enum UiAccountDataRef<'a> {
+    LegacyBinary(#[serde(borrow)] Cow<'a, str>), // Legacy. Retained for RPC backwards compatibility
+    Json(#[serde(borrow)] ParsedAccountRef<'a>),
+    Binary(#[serde(borrow)] Cow<'a, str>, UiAccountEncoding),
+}
+
+impl UiAccountDataRef<'_> {
+    /// Returns decoded account data in binary format
+    pub fn decode(&self) -> Result<Vec<u8>, AccountDecodeError> {
+        match self {
+            Self::Json(_) => Err(AccountDecodeError::NotBinary),
+            Self::LegacyBinary(blob) => Ok(bs58::decode(blob.as_ref()).into_vec()?),
+            Self::Binary(blob, encoding) => match encoding {
+                UiAccountEncoding::Base58 => Ok(bs58::decode(blob.as_ref()).into_vec()?),
+                UiAccountEncoding::Base64 => Ok(BASE64_STANDARD.decode(blob.as_ref())?),
+                UiAccountEncoding::Base64Zstd => {
+                    zstd_decompress(&BASE64_STANDARD.decode(blob.as_ref())?)
+                }
+                UiAccountEncoding::Binary | UiAccountEncoding::JsonParsed => {
+                    Err(AccountDecodeError::NotBinary)
+                }
+            },
+        }
+    }
+}
+
+#[cfg(feature = "zstd")]
+fn zstd_decompress(data: &[u8]) -> Result<Vec<u8>, AccountDecodeError> {
+    zstd::decode_all(data).map_err(AccountDecodeError::ZstdDecompressError)
+}
+
+#[cfg(not(feature = "zstd"))]
+fn zstd_decompress(_data: &[u8]) -> Result<Vec<u8>, AccountDecodeError> {
+    Err(AccountDecodeError::ZstdUnsupported)
+}
+
+impl From<UiAccountDataRef<'_>> for UiAccountData {
+    fn from(data: UiAccountDataRef<'_>) -> Self {
+        match data {
+            UiAccountDataRef::LegacyBinary(blob) => Self::LegacyBinary(blob.into_owned()),
+            UiAccountDataRef::Json(parsed) => Self::Json(parsed.into()),
+            UiAccountDataRef::Binary(blob, encoding) => Self::Binary(blob.into_owned(), encoding),
+        }
+    }
+}
+
+/// Borrowed variant of [`ParsedAccount`]. The parsed account is not
+/// borrowed, since its layout depends on the program.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ParsedAccountRef<'a> {
+    #[serde(borrow)]
+    pub program: Cow<'a, str>,
+    pub parsed: Value,
+    pub space: u64,
+}
+
+impl From<ParsedAccountRef<'_>> for ParsedAccount {
+    fn from(account: ParsedAccountRef<'_>) -> Self {
+        Self {
+            program: account.program.into_owned(),
+            parsed: account.parsed,
+            space: account.space,
+        }
+    }
+}
+
+/// Borrowed variant of [`RpcKeyedAccount`](crate::response::RpcKeyedAccount).
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RpcKeyedAccountRef<'a> {
+    #[serde(borrow)]
+    pub pubkey: Cow<'a, str>,
+    #[serde(borrow)]
+    pub account: UiAccountRef<'a>,
+}
+
+impl RpcKeyedAccountRef<'_> {
+    /// Returns the decoded pubkey and account
+    pub fn decode<T: WritableAccount>(&self) -> Result<(Pubkey, T), AccountDecodeError> {
+        Ok((Pubkey::from_str(&self.pubkey)?, self.account.decode()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::response::{OptionalContext, RpcKeyedAccount},
+        solana_account::Account,
+    };
+
+    const PROGRAM_ACCOUNTS: &str = r#"{
+        "context": {"slot": 5, "apiVersion": "2.3.0"},
+        "value": [
+            {
+                "pubkey": "4Nd1mBQtrMJVYVfKf2PJy9NZUZdTAsp7D4xWLs4gDB4T",
+                "account": {
+                    "lamports": 10,
+                    "data": ["AQID", "base64"],
+                    "owner": "11111111111111111111111111111111",
+                    "executable": false,
+                    "rentEpoch": 18446744073709551615,
+                    "space": 3
+                }
+            },
+            {
+                "pubkey": "8opHzTAnfzRpPEx21XtnrVTX28YQuCpAjcn1PczScKh",
+                "account": {
+                    "lamports": 20,
+                    "data": {"program": "nonce", "parsed": {"type": "uninitialized"}, "space": 80},
+                    "owner": "11111111111111111111111111111111",
+                    "executable": false,
+                    "rentEpoch": 0,
+                    "space": 80
+                }
+            }
+        ]
+    }"#;
+
+    #[test]
+    fn test_program_accounts_borrowed() {
+        let response: OptionalContext<Vec<RpcKeyedAccountRef>> =
+            serde_json::from_str(PROGRAM_ACCOUNTS).unwrap();
+        let accounts = response.parse_value();
+        assert_eq!(accounts.len(), 2);
+        assert!(matches!(accounts[0].pubkey, Cow::Borrowed(_)));
+        assert!(matches!(accounts[0].account.owner, Cow::Borrowed(_)));
+        assert!(matches!(
+            accounts[0].account.data,
+            UiAccountDataRef::Binary(Cow::Borrowed("AQID"), UiAccountEncoding::Base64)
+        ));
+        assert_eq!(accounts[0].account.data.decode().unwrap(), vec![1, 2, 3]);
+        let (pubkey, account) = accounts[0].decode::<Account>().unwrap();
+        assert_eq!(
+            pubkey.to_string(),
+            "4Nd1mBQtrMJVYVfKf2PJy9NZUZdTAsp7D4xWLs4gDB4T"
+        );
+        assert_eq!(account.lamports, 10);
+        assert_eq!(account.data, vec![1, 2, 3]);
+        assert!(matches!(
+            &accounts[1].account.data,
+            UiAccountDataRef::Json(ParsedAccountRef {
+                program: Cow::Borrowed("nonce"),
+                ..
+            })
+        ));
+        assert!(matches!(
+            accounts[1].account.data.decode(),
+            Err(AccountDecodeError::NotBinary)
+        ));
+        assert!(matches!(
+            accounts[1].decode::<Account>(),
+            Err(AccountDecodeError::NotBinary)
+        ));
+
+        // Converts into the same owned types as deserialized directly
+        let owned: OptionalContext<Vec<RpcKeyedAccount>> =
+            serde_json::from_str(PROGRAM_ACCOUNTS).unwrap();
+        let owned_accounts: Vec<UiAccount> = accounts
+            .into_iter()
+            .map(|keyed_account| keyed_account.account.into())
+            .collect();
+        assert_eq!(
+            owned_accounts,
+            owned
+                .parse_value()
+                .into_iter()
+                .map(|keyed_account| keyed_account.account)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_decode_base64_zstd() {
+        fn data(blob: &str) -> UiAccountDataRef<'_> {
+            UiAccountDataRef::Binary(Cow::Borrowed(blob), UiAccountEncoding::Base64Zstd)
+        }
+
+        // Malformed base64
+        assert!(matches!(
+            data("!!!").decode(),
+            Err(AccountDecodeError::Base64DecodeError(_))
+        ));
+
+        // Valid base64 that is not a zstd frame
+        let result = data("AQID").decode();
+        #[cfg(feature = "zstd")]
+        assert!(matches!(
+            result,
+            Err(AccountDecodeError::ZstdDecompressError(_))
+        ));
+        #[cfg(not(feature = "zstd"))]
+        assert!(matches!(result, Err(AccountDecodeError::ZstdUnsupported)));
+
+        #[cfg(feature = "zstd")]
+        {
+            let compressed = BASE64_STANDARD.encode(zstd::encode_all(&[1u8, 2, 3][..], 0).unwrap());
+            assert_eq!(data(&compressed).decode().unwrap(), vec![1, 2, 3]);
+        }
+    }
+}
@@ -0,0 +1,251 @@
+//! Conversions between slots, epochs and wall clock time.
+//!
+//! Slots are not produced at exactly [`DEFAULT_MS_PER_SLOT`], and their pace drifts with the
+//! cluster's load and skip rate. [`ClockMath`] estimates slot times from recent performance
+//! samples instead, and bounds its estimates by the fastest and slowest of those samples.
+
+use {
+    crate::response::RpcPerfSample,
+    solana_clock::{Epoch, Slot, DEFAULT_MS_PER_SLOT},
+    solana_epoch_schedule::EpochSchedule,
+    std::time::Duration,
+};
+
+/// An estimated value along with the range it is expected to fall in
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Estimate<T> {
+    pub estimate: T,
+    pub min: T,
+    pub max: T,
+}
+
+impl<T: Copy> Estimate<T> {
+    fn exact(value: T) -> Self {
+        Self {
+            estimate: value,
+            min: value,
+            max: value,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ClockMath {
+    epoch_schedule: EpochSchedule,
+    // Average, shortest and longest slot time of the samples, in microseconds
+    slot_time_us: Estimate<u64>,
+}
+
+impl ClockMath {
+    /// Creates a `ClockMath` estimating slot times from `samples`, as returned by
+    /// `getRecentPerformanceSamples`. Without usable samples the nominal slot time of
+    /// [`DEFAULT_MS_PER_SLOT`] is assumed.
+    pub fn new(epoch_schedule: EpochSchedule, samples: &[RpcPerfSample]) -> Self {
+        let samples: Vec<_> = samples
+            .iter()
+            .filter(|sample| sample.num_slots > 0 && sample.sample_period_secs > 0)
+            .map(|sample| {
+                (
+                    sample.num_slots,
+                    u64::from(sample.sample_period_secs) * 1_000_000,
+                )
+            })
+            .collect();
+        let (total_slots, total_us) =
+            samples
+                .iter()
+                .fold((0u64, 0u64), |(slots, us), (num_slots, period_us)| {
+                    (
+                        slots.saturating_add(*num_slots),
+                        us.saturating_add(*period_us),
+                    )
+                });
+        let slot_times_us = samples
+            .iter()
+            .map(|(num_slots, period_us)| period_us / num_slots);
+        let slot_time_us = match (
+            total_us.checked_div(total_slots),
+            slot_times_us.clone().min(),
+            slot_times_us.max(),
+        ) {
+            (Some(estimate), Some(min), Some(max)) => Estimate { estimate, min, max },
+            _ => Estimate::exact(DEFAULT_MS_PER_SLOT * 1_000),
+        };
+        Self {
+            epoch_schedule,
+            slot_time_us,
+        }
+    }
+
+    pub fn epoch_schedule(&self) -> &EpochSchedule {
+        &self.epoch_schedule
+    }
+
+    pub fn slot_time(&self) -> Estimate<Duration> {
+        self.slots_to_duration(1)
+    }
+
+    /// Estimates how long the cluster takes to advance by `num_slots`
+    pub fn slots_to_duration(&self, num_slots: u64) -> Estimate<Duration> {
+        let duration =
+            |slot_time_us: u64| Duration::from_micros(slot_time_us.saturating_mul(num_slots));
+        Estimate {
+            estimate: duration(self.slot_time_us.estimate),
+            min: duration(self.slot_time_us.min),
+            max: duration(self.slot_time_us.max),
+        }
+    }
+
+    /// Estimates how many slots the cluster advances by in `duration`
+    pub fn duration_to_slots(&self, duration: Duration) -> Estimate<u64> {
+        let duration_us = u64::try_from(duration.as_micros()).unwrap_or(u64::MAX);
+        let slots = |slot_time_us: u64| duration_us / slot_time_us.max(1);
+        Estimate {
+            estimate: slots(self.slot_time_us.estimate),
+            min: slots(self.slot_time_us.max),
+            max: slots(self.slot_time_us.min),
+        }
+    }
+
+    /// Estimates the slot the cluster will be at `duration` after `current_slot`
+    pub fn slot_after(&self, current_slot: Slot, duration: Duration) -> Estimate<Slot> {
+        let slots = self.duration_to_slots(duration);
+        Estimate {
+            estimate: current_slot.saturating_add(slots.estimate),
+            min: current_slot.saturating_add(slots.min),
+            max: current_slot.saturating_add(slots.max),
+        }
+    }
+
+    /// Estimates the epoch the cluster will be in `duration` after `current_slot`
+    pub fn epoch_after(&self, current_slot: Slot, duration: Duration) -> Estimate<Epoch> {
+        let slot = self.slot_after(current_slot, duration);
+        Estimate {
+            estimate: self.epoch_schedule.get_epoch(slot.estimate),
+            min: self.epoch_schedule.get_epoch(slot.min),
+            max: self.epoch_schedule.get_epoch(slot.max),
+        }
+    }
+
+    /// Estimates the time until the cluster reaches `slot`, which is zero if `slot` is not
+    /// ahead of `current_slot`
+    pub fn time_until_slot(&self, current_slot: Slot, slot: Slot) -> Estimate<Duration> {
+        self.slots_to_duration(slot.saturating_sub(current_slot))
+    }
+
+    /// Estimates the time until the first slot of `epoch`
+    pub fn time_until_epoch(&self, current_slot: Slot, epoch: Epoch) -> Estimate<Duration> {
+        self.time_until_slot(
+            current_slot,
+            self.epoch_schedule.get_first_slot_in_epoch(epoch),
+        )
+    }
+
+    /// Estimates the time until the end of the epoch `current_slot` is in
+    pub fn time_until_epoch_end(&self, current_slot: Slot) -> Estimate<Duration> {
+        let epoch = self.epoch_schedule.get_epoch(current_slot);
+        self.time_until_epoch(current_slot, epoch.saturating_add(1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(num_slots: u64, sample_period_secs: u16) -> RpcPerfSample {
+        RpcPerfSample {
+            slot: 0,
+            num_transactions: 0,
+            num_non_vote_transactions: None,
+            num_slots,
+            sample_period_secs,
+        }
+    }
+
+    #[test]
+    fn test_slot_time() {
+        let epoch_schedule = EpochSchedule::without_warmup();
+
+        // Without samples, the nominal slot time is assumed
+        let clock_math = ClockMath::new(epoch_schedule.clone(), &[]);
+        assert_eq!(
+            clock_math.slot_time(),
+            Estimate::exact(Duration::from_millis(DEFAULT_MS_PER_SLOT))
+        );
+
+        // Empty samples are ignored
+        let clock_math = ClockMath::new(
+            epoch_schedule,
+            &[
+                sample(150, 60),
+                sample(100, 60),
+                sample(0, 60),
+                sample(10, 0),
+            ],
+        );
+        assert_eq!(
+            clock_math.slot_time(),
+            Estimate {
+                estimate: Duration::from_millis(480),
+                min: Duration::from_millis(400),
+                max: Duration::from_millis(600),
+            }
+        );
+        assert_eq!(
+            clock_math.slots_to_duration(1_000),
+            Estimate {
+                estimate: Duration::from_secs(480),
+                min: Duration::from_secs(400),
+                max: Duration::from_secs(600),
+            }
+        );
+        assert_eq!(
+            clock_math.duration_to_slots(Duration::from_secs(120)),
+            Estimate {
+                estimate: 250,
+                min: 200,
+                max: 300,
+            }
+        );
+        assert_eq!(
+            clock_math.slot_after(1_000, Duration::from_secs(120)),
+            Estimate {
+                estimate: 1_250,
+                min: 1_200,
+                max: 1_300,
+            }
+        );
+    }
+
+    #[test]
+    fn test_epoch_times() {
+        let epoch_schedule = EpochSchedule::custom(1_000, 1_000, false);
+        let clock_math = ClockMath::new(epoch_schedule, &[sample(150, 60), sample(100, 60)]);
+
+        assert_eq!(
+            clock_math.time_until_epoch_end(2_500),
+            Estimate {
+                estimate: Duration::from_secs(240),
+                min: Duration::from_secs(200),
+                max: Duration::from_secs(300),
+            }
+        );
+        assert_eq!(
+            clock_math.time_until_epoch(2_500, 5).estimate,
+            Duration::from_millis(2_500 * 480)
+        );
+        // Past epochs are already reached
+        assert_eq!(
+            clock_math.time_until_epoch(2_500, 1),
+            Estimate::exact(Duration::ZERO)
+        );
+        assert_eq!(
+            clock_math.epoch_after(2_500, Duration::from_secs(270)),
+            Estimate {
+                estimate: 3,
+                min: 2,
+                max: 3,
+            }
+        );
+    }
+}
@@ -44,6 +44,13 @@ pub struct RpcSimulateTransactionConfig {
     pub min_context_slot: Option<Slot>,
     #[serde(default)]
     pub inner_instructions: bool,
+    /// Return the stack and heap memory used by each program invocation
+    #[serde(default)]
+    pub memory_usage: bool,
+    /// Return the account privileges of each instruction and whether it
+    /// modified its writable accounts
+    #[serde(default)]
+    pub account_privileges: bool,
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -76,6 +83,8 @@ pub struct RpcBlockProductionConfig {
     pub range: Option<RpcBlockProductionConfigRange>, // current epoch if `None`
     #[serde(flatten)]
     pub commitment: Option<CommitmentConfig>,
+    /// Report the skipped slots of each leader, with the reason each was skipped
+    pub skipped_slot_reasons: Option<bool>,
 }
 
 #[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -88,6 +97,12 @@ pub struct RpcGetVoteAccountsConfig {
     pub delinquent_slot_distance: Option<u64>,
 }
 
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcVoteLatencyConfig {
+    pub vote_pubkey: Option<String>, // validator vote address, as a base-58 encoded string
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum RpcLeaderScheduleConfigWrapper {
@@ -118,6 +133,7 @@ pub struct RpcLargestAccountsConfig {
     pub commitment: Option<CommitmentConfig>,
     pub filter: Option<RpcLargestAccountsFilter>,
     pub sort_results: Option<bool>,
+    pub min_context_slot: Option<Slot>,
 }
 
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
@@ -127,6 +143,7 @@ pub struct RpcSupplyConfig {
     pub commitment: Option<CommitmentConfig>,
     #[serde(default)]
     pub exclude_non_circulating_accounts_list: bool,
+    pub min_context_slot: Option<Slot>,
 }
 
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
@@ -156,6 +173,17 @@ pub struct RpcAccountInfoConfig {
     pub min_context_slot: Option<Slot>,
 }
 
+/// Body of a `POST /v0/multiple-accounts` request, which streams the accounts back in chunks
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcMultipleAccountsStreamRequest {
+    pub pubkeys: Vec<String>,
+    #[serde(flatten)]
+    pub config: RpcAccountInfoConfig,
+    /// Number of accounts per chunk, capped by the node's `getMultipleAccounts` limit
+    pub chunk_size: Option<usize>,
+}
+
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct RpcProgramAccountsConfig {
@@ -164,6 +192,28 @@ pub struct RpcProgramAccountsConfig {
     pub account_config: RpcAccountInfoConfig,
     pub with_context: Option<bool>,
     pub sort_results: Option<bool>,
+    /// Return a single page of accounts, in increasing order of pubkey
+    pub pagination: Option<RpcPaginationConfig>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcPaginationConfig {
+    /// Maximum number of accounts in the page
+    pub limit: usize,
+    /// Base-58 encoded pubkey of the last account of the previous page, if any.
+    /// A page with fewer than `limit` accounts is the last one.
+    pub after: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcEpochStakeRewardsConfig {
+    /// Epoch the rewards were earned in, the latest one available if None
+    pub epoch: Option<Epoch>,
+    /// Return a single page of rewards, in increasing order of stake account.
+    /// Without it, the first page of the maximum size is returned.
+    pub pagination: Option<RpcPaginationConfig>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -3,6 +3,7 @@ use {
     crate::response::RpcSimulateTransactionResult,
     jsonrpc_core::{Error, ErrorCode},
     solana_clock::Slot,
+    solana_pubkey::Pubkey,
     solana_transaction_status_client_types::EncodeError,
     thiserror::Error,
 };
@@ -27,6 +28,7 @@ pub const JSON_RPC_SERVER_ERROR_MIN_CONTEXT_SLOT_NOT_REACHED: i64 = -32016;
 pub const JSON_RPC_SERVER_ERROR_EPOCH_REWARDS_PERIOD_ACTIVE: i64 = -32017;
 pub const JSON_RPC_SERVER_ERROR_SLOT_NOT_EPOCH_BOUNDARY: i64 = -32018;
 pub const JSON_RPC_SERVER_ERROR_LONG_TERM_STORAGE_UNREACHABLE: i64 = -32019;
+pub const JSON_RPC_SERVER_ERROR_FEE_PAYER_INFLIGHT_LIMIT_EXCEEDED: i64 = -32020;
 
 #[derive(Error, Debug)]
 pub enum RpcCustomError {
@@ -78,6 +80,11 @@ pub enum RpcCustomError {
     SlotNotEpochBoundary { slot: Slot },
     #[error("LongTermStorageUnreachable")]
     LongTermStorageUnreachable,
+    #[error("FeePayerInflightLimitExceeded")]
+    FeePayerInflightLimitExceeded {
+        fee_payer: Pubkey,
+        max_inflight: usize,
+    },
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -92,6 +99,13 @@ pub struct MinContextSlotNotReachedErrorData {
     pub context_slot: Slot,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeePayerInflightLimitExceededErrorData {
+    pub fee_payer: String,
+    pub max_inflight: usize,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct EpochRewardsPeriodActiveErrorData {
@@ -252,6 +266,22 @@ impl From<RpcCustomError> for Error {
                 message: "Failed to query long-term storage; please try again".to_string(),
                 data: None,
             },
+            RpcCustomError::FeePayerInflightLimitExceeded {
+                fee_payer,
+                max_inflight,
+            } => Self {
+                code: ErrorCode::ServerError(
+                    JSON_RPC_SERVER_ERROR_FEE_PAYER_INFLIGHT_LIMIT_EXCEEDED,
+                ),
+                message: format!(
+                    "Fee payer {fee_payer} already has {max_inflight} transactions being sent; \
+                     please retry once some of them land or expire"
+                ),
+                data: Some(serde_json::json!(FeePayerInflightLimitExceededErrorData {
+                    fee_payer: fee_payer.to_string(),
+                    max_inflight,
+                })),
+            },
         }
     }
 }
@@ -10,6 +10,11 @@ use {
 const MAX_DATA_SIZE: usize = 128;
 const MAX_DATA_BASE58_SIZE: usize = 175;
 const MAX_DATA_BASE64_SIZE: usize = 172;
+// Leaves room for the zstd frame header and incompressible blocks
+const MAX_DATA_BASE64_ZSTD_SIZE: usize = 256;
+// Bytes compared with the zstd decompressed account data must end within this many bytes, which
+// bounds how much of each account is decompressed
+const MAX_ZSTD_DECOMPRESSED_SIZE: usize = 64 * 1024;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -48,6 +53,19 @@ impl RpcFilterType {
                             Ok(())
                         }
                     }
+                    Base64Zstd(bytes) => {
+                        if bytes.len() > MAX_DATA_BASE64_ZSTD_SIZE {
+                            return Err(RpcFilterError::DataTooLarge);
+                        }
+                        let _compressed_bytes = BASE64_STANDARD.decode(bytes)?;
+                        #[cfg(feature = "zstd")]
+                        {
+                            let bytes = zstd_decompress(&_compressed_bytes, MAX_DATA_SIZE + 1)?;
+                            verify_zstd_bytes(compare.offset, &bytes)?;
+                        }
+                        Ok(())
+                    }
+                    ZstdBytes(bytes) => verify_zstd_bytes(compare.offset, bytes),
                     Bytes(bytes) => {
                         if bytes.len() > MAX_DATA_SIZE {
                             return Err(RpcFilterError::DataTooLarge);
@@ -74,21 +92,74 @@ impl RpcFilterType {
 }
 
 #[derive(Error, PartialEq, Eq, Debug)]
+#[non_exhaustive]
 pub enum RpcFilterError {
     #[error("encoded binary data should be less than 129 bytes")]
     DataTooLarge,
+    #[error("bytes compared with zstd decompressed data should end within the first 65536 bytes")]
+    DecompressedDataTooLarge,
     #[error("base58 decode error")]
     Base58DecodeError(#[from] bs58::decode::Error),
     #[error("base64 decode error")]
     Base64DecodeError(#[from] base64::DecodeError),
+    #[error("zstd decompress error")]
+    ZstdDecompressError,
+}
+
+fn verify_zstd_bytes(offset: usize, bytes: &[u8]) -> Result<(), RpcFilterError> {
+    if bytes.len() > MAX_DATA_SIZE {
+        return Err(RpcFilterError::DataTooLarge);
+    }
+    if offset.saturating_add(bytes.len()) > MAX_ZSTD_DECOMPRESSED_SIZE {
+        return Err(RpcFilterError::DecompressedDataTooLarge);
+    }
+    Ok(())
+}
+
+/// Decompresses at most `max_len` bytes of the zstd frame `data`
+#[cfg(feature = "zstd")]
+fn zstd_decompress(data: &[u8], max_len: usize) -> Result<Vec<u8>, RpcFilterError> {
+    use std::io::Read;
+
+    let mut decompressed = vec![];
+    zstd::stream::read::Decoder::new(data)
+        .and_then(|reader| reader.take(max_len as u64).read_to_end(&mut decompressed))
+        .map_err(|_| RpcFilterError::ZstdDecompressError)?;
+    Ok(decompressed)
+}
+
+/// Serializes the bytes of a converted `base64+zstd` filter the way the filter was received
+fn serialize_zstd_bytes<S: serde::Serializer>(
+    bytes: &[u8],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    #[cfg(feature = "zstd")]
+    {
+        let compressed_bytes = zstd::encode_all(bytes, 0).map_err(serde::ser::Error::custom)?;
+        serializer.serialize_str(&BASE64_STANDARD.encode(compressed_bytes))
+    }
+    #[cfg(not(feature = "zstd"))]
+    {
+        let _ = (bytes, serializer);
+        Err(serde::ser::Error::custom("zstd support is not enabled"))
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
 #[serde(rename_all = "camelCase", tag = "encoding", content = "bytes")]
+#[non_exhaustive]
 pub enum MemcmpEncodedBytes {
     Base58(String),
     Base64(String),
+    /// Base64 encoded zstd frame. The decompressed bytes are compared with the zstd decompressed
+    /// account data, so that accounts storing compressed data can be filtered on their content
+    #[serde(rename = "base64+zstd")]
+    Base64Zstd(String),
     Bytes(Vec<u8>),
+    /// The decompressed bytes of a `Base64Zstd` filter, as converted by
+    /// `Memcmp::convert_to_raw_bytes`, which are compared with the zstd decompressed account data
+    #[serde(rename = "base64+zstd", serialize_with = "serialize_zstd_bytes")]
+    ZstdBytes(Vec<u8>),
 }
 
 impl<'de> Deserialize<'de> for MemcmpEncodedBytes {
@@ -108,6 +179,8 @@ impl<'de> Deserialize<'de> for MemcmpEncodedBytes {
         enum RpcMemcmpEncoding {
             Base58,
             Base64,
+            #[serde(rename = "base64+zstd")]
+            Base64Zstd,
             Bytes,
         }
 
@@ -125,6 +198,7 @@ impl<'de> Deserialize<'de> for MemcmpEncodedBytes {
                     MemcmpEncodedBytes::Base58(bytes)
                 }
                 RpcMemcmpEncoding::Base64 => MemcmpEncodedBytes::Base64(bytes),
+                RpcMemcmpEncoding::Base64Zstd => MemcmpEncodedBytes::Base64Zstd(bytes),
             },
             DataType::Raw(bytes) => MemcmpEncodedBytes::Bytes(bytes),
         };
@@ -164,6 +238,16 @@ impl Memcmp {
         }
     }
 
+    /// Matches `bytes` with the zstd decompressed account data
+    #[cfg(feature = "zstd")]
+    pub fn new_base64_zstd_encoded(offset: usize, bytes: &[u8]) -> Self {
+        let compressed_bytes = zstd::encode_all(bytes, 0).unwrap();
+        Self {
+            offset,
+            bytes: MemcmpEncodedBytes::Base64Zstd(BASE64_STANDARD.encode(compressed_bytes)),
+        }
+    }
+
     pub fn offset(&self) -> usize {
         self.offset
     }
@@ -173,7 +257,15 @@ impl Memcmp {
         match &self.bytes {
             Base58(bytes) => bs58::decode(bytes).into_vec().ok().map(Cow::Owned),
             Base64(bytes) => BASE64_STANDARD.decode(bytes).ok().map(Cow::Owned),
-            Bytes(bytes) => Some(Cow::Borrowed(bytes)),
+            #[cfg(feature = "zstd")]
+            Base64Zstd(bytes) => BASE64_STANDARD
+                .decode(bytes)
+                .ok()
+                .and_then(|bytes| zstd_decompress(&bytes, MAX_DATA_SIZE).ok())
+                .map(Cow::Owned),
+            #[cfg(not(feature = "zstd"))]
+            Base64Zstd(_) => None,
+            Bytes(bytes) | ZstdBytes(bytes) => Some(Cow::Borrowed(bytes)),
         }
     }
 
@@ -190,11 +282,34 @@ impl Memcmp {
                 self.bytes = Bytes(bytes);
                 Ok(())
             }
-            _ => Ok(()),
+            // Decompressed once here rather than for every account compared with
+            #[cfg(feature = "zstd")]
+            Base64Zstd(bytes) => {
+                let bytes = zstd_decompress(&BASE64_STANDARD.decode(bytes)?, MAX_DATA_SIZE + 1)?;
+                verify_zstd_bytes(self.offset, &bytes)?;
+                self.bytes = ZstdBytes(bytes);
+                Ok(())
+            }
+            #[cfg(not(feature = "zstd"))]
+            Base64Zstd(_) => Ok(()),
+            Bytes(_) | ZstdBytes(_) => Ok(()),
         }
     }
 
     pub fn bytes_match(&self, data: &[u8]) -> bool {
+        #[cfg(feature = "zstd")]
+        if let MemcmpEncodedBytes::Base64Zstd(_) | MemcmpEncodedBytes::ZstdBytes(_) = &self.bytes {
+            let Some(bytes) = self.bytes() else {
+                return false;
+            };
+            // Only the compared prefix of the account data is decompressed
+            let len = self.offset.saturating_add(bytes.len());
+            if len > MAX_ZSTD_DECOMPRESSED_SIZE {
+                return false;
+            }
+            return zstd_decompress(data, len)
+                .is_ok_and(|data| data.len() == len && data[self.offset..] == bytes[..]);
+        }
         match self.bytes() {
             Some(bytes) => {
                 if self.offset > data.len() {
@@ -335,6 +450,8 @@ mod tests {
         formatcp!(r#"{{"bytes":"{BASE58_STR}","offset":{OFFSET},"encoding":"base58"}}"#);
     const BASE64_FILTER: &str =
         formatcp!(r#"{{"bytes":"{BASE64_STR}","offset":{OFFSET},"encoding":"base64"}}"#);
+    const BASE64_ZSTD_FILTER: &str =
+        formatcp!(r#"{{"bytes":"{BASE64_STR}","offset":{OFFSET},"encoding":"base64+zstd"}}"#);
     const MISMATCHED_BASE64_FILTER: &str =
         formatcp!(r#"{{"bytes":[0, 1, 2, 3],"offset":{OFFSET},"encoding":"base64"}}"#);
     const BYTES_FILTER: &str =
@@ -380,6 +497,16 @@ mod tests {
             }
         );
 
+        // Base64+zstd input
+        let base64_zstd_filter: Memcmp = serde_json::from_str(BASE64_ZSTD_FILTER).unwrap();
+        assert_eq!(
+            base64_zstd_filter,
+            Memcmp {
+                offset: OFFSET,
+                bytes: MemcmpEncodedBytes::Base64Zstd(BASE64_STR.to_string()),
+            }
+        );
+
         // Raw bytes input
         let bytes_filter: Memcmp = serde_json::from_str(BYTES_FILTER).unwrap();
         assert_eq!(
@@ -444,6 +571,17 @@ mod tests {
             serde_json::from_str::<Value>(BASE64_FILTER).unwrap()
         );
 
+        // Base64+zstd
+        let base64_zstd = Memcmp {
+            offset: OFFSET,
+            bytes: MemcmpEncodedBytes::Base64Zstd(BASE64_STR.to_string()),
+        };
+        let serialized_json = json!(base64_zstd);
+        assert_eq!(
+            serialized_json,
+            serde_json::from_str::<Value>(BASE64_ZSTD_FILTER).unwrap()
+        );
+
         // Bytes
         let bytes = Memcmp {
             offset: OFFSET,
@@ -455,4 +593,94 @@ mod tests {
             serde_json::from_str::<Value>(BYTES_FILTER_WITH_ENCODING).unwrap()
         );
     }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_zstd_bytes_match() {
+        let data: Vec<u8> = (0..=255).collect();
+        let compressed_data = zstd::encode_all(data.as_slice(), 0).unwrap();
+
+        let zstd_bytes_match = |offset, bytes: &[u8], data: &[u8]| {
+            Memcmp::new_base64_zstd_encoded(offset, bytes).bytes_match(data)
+        };
+
+        // Matches the decompressed data
+        assert!(zstd_bytes_match(0, &[0, 1, 2], &compressed_data));
+        assert!(zstd_bytes_match(253, &[253, 254, 255], &compressed_data));
+        assert!(!zstd_bytes_match(1, &[0, 1, 2], &compressed_data));
+        // Bytes overrun decompressed data fails
+        assert!(!zstd_bytes_match(254, &[254, 255, 0], &compressed_data));
+        // Uncompressed data fails
+        assert!(!zstd_bytes_match(0, &[0, 1, 2], &data));
+        // Other encodings still match the compressed data
+        assert!(
+            Memcmp::new_raw_bytes(0, compressed_data[..4].to_vec()).bytes_match(&compressed_data)
+        );
+
+        // Conversion decompresses the bytes once, and they are still compared with the
+        // decompressed data
+        let mut memcmp = Memcmp::new_base64_zstd_encoded(253, &[253, 254, 255]);
+        let serialized_json = json!(memcmp);
+        memcmp.convert_to_raw_bytes().unwrap();
+        assert_eq!(
+            memcmp.bytes,
+            MemcmpEncodedBytes::ZstdBytes(vec![253, 254, 255])
+        );
+        assert_eq!(memcmp.raw_bytes_as_ref(), None);
+        assert!(memcmp.bytes_match(&compressed_data));
+        assert!(!memcmp.bytes_match(&data));
+        // and serialized as received
+        assert_eq!(json!(memcmp), serialized_json);
+        assert_eq!(
+            serde_json::from_value::<Memcmp>(serialized_json)
+                .unwrap()
+                .bytes(),
+            memcmp.bytes()
+        );
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_verify_memcmp_zstd() {
+        let verify =
+            |bytes: MemcmpEncodedBytes| RpcFilterType::Memcmp(Memcmp { offset: 0, bytes }).verify();
+        assert_eq!(
+            verify(Memcmp::new_base64_zstd_encoded(0, &[0xff; MAX_DATA_SIZE]).bytes),
+            Ok(())
+        );
+        assert_eq!(
+            verify(Memcmp::new_base64_zstd_encoded(0, &[0xff; MAX_DATA_SIZE + 1]).bytes),
+            Err(RpcFilterError::DataTooLarge)
+        );
+        assert_eq!(
+            verify(MemcmpEncodedBytes::Base64Zstd(BASE64_STR.to_string())),
+            Err(RpcFilterError::ZstdDecompressError)
+        );
+        assert_eq!(
+            verify(MemcmpEncodedBytes::Base64Zstd(
+                "A".repeat(MAX_DATA_BASE64_ZSTD_SIZE + 4)
+            )),
+            Err(RpcFilterError::DataTooLarge)
+        );
+
+        // The compared bytes must end within the decompressed size limit
+        let verify_at = |offset| {
+            let mut memcmp = Memcmp::new_base64_zstd_encoded(offset, &[0xff; MAX_DATA_SIZE]);
+            let result = RpcFilterType::Memcmp(memcmp.clone()).verify();
+            assert_eq!(memcmp.convert_to_raw_bytes(), result);
+            result
+        };
+        assert_eq!(
+            verify_at(MAX_ZSTD_DECOMPRESSED_SIZE - MAX_DATA_SIZE),
+            Ok(())
+        );
+        assert_eq!(
+            verify_at(MAX_ZSTD_DECOMPRESSED_SIZE - MAX_DATA_SIZE + 1),
+            Err(RpcFilterError::DecompressedDataTooLarge)
+        );
+        assert_eq!(
+            verify_at(usize::MAX),
+            Err(RpcFilterError::DecompressedDataTooLarge)
+        );
+    }
 }
@@ -1,12 +1,16 @@
 #![allow(clippy::arithmetic_side_effects)]
 
+#[cfg(feature = "borrowed-responses")]
+pub mod borrowed_response;
 pub mod client_error;
+pub mod clock_math;
 pub mod config;
 pub mod custom_error;
 pub mod error_object;
 pub mod filter;
 pub mod request;
 pub mod response;
+pub mod typed_request;
 
 #[macro_use]
 extern crate serde_derive;
@@ -19,9 +19,12 @@ pub enum RpcRequest {
     GetBlocks,
     GetBlocksWithLimit,
     GetBlockTime,
+    GetBufferAccounts,
     GetClusterNodes,
+    GetDuplicateVotes,
     GetEpochInfo,
     GetEpochSchedule,
+    GetEpochStakeRewards,
     GetFeeForMessage,
     GetFirstAvailableBlock,
     GetGenesisHash,
@@ -44,6 +47,7 @@ pub enum RpcRequest {
     GetSignaturesForAddress,
     GetSignatureStatuses,
     GetSlot,
+    GetSlotHashes,
     GetSlotLeader,
     GetSlotLeaders,
     GetStorageTurn,
@@ -60,7 +64,9 @@ pub enum RpcRequest {
     GetTransaction,
     GetTransactionCount,
     GetVersion,
+    GetVoteAccountEpochStakes,
     GetVoteAccounts,
+    GetVoteLatency,
     IsBlockhashValid,
     MinimumLedgerSlot,
     RegisterNode,
@@ -84,9 +90,12 @@ impl fmt::Display for RpcRequest {
             RpcRequest::GetBlocks => "getBlocks",
             RpcRequest::GetBlocksWithLimit => "getBlocksWithLimit",
             RpcRequest::GetBlockTime => "getBlockTime",
+            RpcRequest::GetBufferAccounts => "getBufferAccounts",
             RpcRequest::GetClusterNodes => "getClusterNodes",
+            RpcRequest::GetDuplicateVotes => "getDuplicateVotes",
             RpcRequest::GetEpochInfo => "getEpochInfo",
             RpcRequest::GetEpochSchedule => "getEpochSchedule",
+            RpcRequest::GetEpochStakeRewards => "getEpochStakeRewards",
             RpcRequest::GetFeeForMessage => "getFeeForMessage",
             RpcRequest::GetFirstAvailableBlock => "getFirstAvailableBlock",
             RpcRequest::GetGenesisHash => "getGenesisHash",
@@ -109,6 +118,7 @@ impl fmt::Display for RpcRequest {
             RpcRequest::GetSignaturesForAddress => "getSignaturesForAddress",
             RpcRequest::GetSignatureStatuses => "getSignatureStatuses",
             RpcRequest::GetSlot => "getSlot",
+            RpcRequest::GetSlotHashes => "getSlotHashes",
             RpcRequest::GetSlotLeader => "getSlotLeader",
             RpcRequest::GetSlotLeaders => "getSlotLeaders",
             RpcRequest::GetStakeMinimumDelegation => "getStakeMinimumDelegation",
@@ -125,7 +135,9 @@ impl fmt::Display for RpcRequest {
             RpcRequest::GetTransaction => "getTransaction",
             RpcRequest::GetTransactionCount => "getTransactionCount",
             RpcRequest::GetVersion => "getVersion",
+            RpcRequest::GetVoteAccountEpochStakes => "getVoteAccountEpochStakes",
             RpcRequest::GetVoteAccounts => "getVoteAccounts",
+            RpcRequest::GetVoteLatency => "getVoteLatency",
             RpcRequest::IsBlockhashValid => "isBlockhashValid",
             RpcRequest::MinimumLedgerSlot => "minimumLedgerSlot",
             RpcRequest::RegisterNode => "registerNode",
@@ -145,9 +157,15 @@ pub const MAX_GET_CONFIRMED_SIGNATURES_FOR_ADDRESS_SLOT_RANGE: u64 = 10_000;
 pub const MAX_GET_CONFIRMED_BLOCKS_RANGE: u64 = 500_000;
 pub const MAX_GET_CONFIRMED_SIGNATURES_FOR_ADDRESS2_LIMIT: usize = 1_000;
 pub const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+pub const MAX_MULTIPLE_ACCOUNTS_STREAM: usize = 10_000;
 pub const NUM_LARGEST_ACCOUNTS: usize = 20;
 pub const MAX_GET_PROGRAM_ACCOUNT_FILTERS: usize = 4;
+pub const MAX_GET_PROGRAM_ACCOUNTS_PAGE_LIMIT: usize = 10_000;
+pub const MAX_GET_EPOCH_STAKE_REWARDS_PAGE_LIMIT: usize = 10_000;
 pub const MAX_GET_SLOT_LEADERS: usize = 5000;
+pub const MAX_GET_SLOT_HASHES_LIMIT: usize = 10_000;
+pub const MAX_GET_SLOT_HASHES_SLOT_RANGE: u64 = 100_000;
+pub const MAX_GET_DUPLICATE_VOTES_LIMIT: usize = 1_000;
 
 // Limit the length of the `epoch_credits` array for each validator in a `get_vote_accounts`
 // response
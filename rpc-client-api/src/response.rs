@@ -116,6 +116,36 @@ pub struct RpcBlockhash {
     pub last_valid_block_height: u64,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcSlotHash {
+    pub slot: Slot,
+    pub hash: String,
+}
+
+/// Two votes signed by the same vote account for different bank hashes of the same slot
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcDuplicateVote {
+    pub slot: Slot,
+    /// Vote account address, as base-58 encoded string
+    pub vote_pubkey: String,
+    /// The conflicting votes, in the order they were seen
+    pub votes: Vec<RpcDuplicateVoteEvidence>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcDuplicateVoteEvidence {
+    /// The bank hash voted for
+    pub hash: String,
+    /// Signature of the vote transaction, as base-58 encoded string
+    pub signature: String,
+    /// The signed vote transaction, as base-64 encoded wire transaction, for votes seen in
+    /// gossip. Votes seen in a block can be looked up by their signature.
+    pub transaction: Option<String>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct RpcFeeCalculator {
@@ -166,6 +196,28 @@ pub struct RpcKeyedAccount {
     pub account: UiAccount,
 }
 
+/// One line of a `POST /v0/multiple-accounts` response. Each chunk is read from its own bank, so
+/// `context` may differ between chunks. A chunk that could not be served carries `error` instead
+/// of `context` and `value`, and the chunks after it are still sent.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcMultipleAccountsChunk {
+    /// Index of the chunk's first account in the requested pubkeys
+    pub offset: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context: Option<RpcResponseContext>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<Vec<Option<UiAccount>>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<RpcMultipleAccountsChunkError>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct RpcMultipleAccountsChunkError {
+    pub code: i64,
+    pub message: String,
+}
+
 #[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
 pub struct SlotInfo {
     pub slot: Slot,
@@ -308,6 +360,33 @@ pub struct RpcBlockProduction {
     /// Map of leader base58 identity pubkeys to a tuple of `(number of leader slots, number of blocks produced)`
     pub by_identity: HashMap<String, (usize, usize)>,
     pub range: RpcBlockProductionRange,
+    /// Map of leader base58 identity pubkeys to their skipped slots, if requested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skipped_slots: Option<HashMap<String, Vec<RpcSkippedSlot>>>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcSkippedSlot {
+    pub slot: Slot,
+    pub reason: RpcSkippedSlotReason,
+}
+
+/// Why a leader slot is not on the rooted fork, as seen by the node serving the request
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum RpcSkippedSlotReason {
+    /// No shred of the block reached the node, the leader was most likely offline
+    Offline,
+    /// The block was incomplete, or only started arriving after the next block of the rooted
+    /// fork, which was built without it
+    Late,
+    /// The block was complete and arrived in time, but the cluster rooted a fork without it
+    ForkedOff,
+    /// The block failed to replay
+    Dead,
+    /// The node has no record of the slot, e.g. it is older than its ledger
+    Unknown,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, Eq)]
@@ -390,6 +469,57 @@ pub struct RpcVoteAccountInfo {
     pub root_slot: Slot,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcVoteLatency {
+    /// Vote account address, as base-58 encoded string
+    pub vote_pubkey: String,
+
+    /// The validator identity, as base-58 encoded string
+    pub node_pubkey: String,
+
+    /// Number of rooted votes which landed in the current epoch
+    pub num_votes: u64,
+
+    /// Mean number of slots between the voted slot and the slot the vote landed in
+    pub mean_latency: f64,
+
+    /// Median vote latency, in slots
+    pub median_latency: u8,
+
+    /// 90th percentile vote latency, in slots
+    pub p90_latency: u8,
+
+    /// Maximum vote latency, in slots
+    pub max_latency: u8,
+
+    /// Number of votes per latency in slots; the last entry also counts all
+    /// votes with a higher latency
+    pub histogram: Vec<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcVoteLatencyStatus {
+    /// Epoch the latencies were collected in
+    pub epoch: Epoch,
+    pub validators: Vec<RpcVoteLatency>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcVoteAccountEpochStake {
+    /// Leader schedule epoch the stakes were used for. They are the stakes that were active
+    /// during the previous epoch.
+    pub epoch: Epoch,
+
+    /// Stake delegated to the vote account in the epoch, in lamports
+    pub activated_stake: u64,
+
+    /// Total stake delegated to all vote accounts in the epoch, in lamports
+    pub total_activated_stake: u64,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct RpcSignatureConfirmation {
@@ -407,6 +537,43 @@ pub struct RpcSimulateTransactionResult {
     pub return_data: Option<UiTransactionReturnData>,
     pub inner_instructions: Option<Vec<UiInnerInstructions>>,
     pub replacement_blockhash: Option<RpcBlockhash>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory_usage: Option<Vec<RpcProgramMemoryUsage>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account_privileges: Option<Vec<RpcInstructionAccountPrivileges>>,
+}
+
+/// The stack and heap memory used by a program invocation, measured as the
+/// span of bytes written with non-zero values
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcProgramMemoryUsage {
+    pub program_id: String,
+    pub stack_height: usize,
+    pub stack_bytes: u64,
+    pub stack_size: u64,
+    pub heap_bytes: u64,
+    pub heap_size: u64,
+}
+
+/// The privileges an instruction was given over its accounts. Writable
+/// accounts the instruction didn't modify, and signers it didn't need, could
+/// have been passed without these privileges.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcInstructionAccountPrivileges {
+    pub program_id: String,
+    pub stack_height: usize,
+    pub accounts: Vec<RpcAccountPrivilegeUsage>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcAccountPrivilegeUsage {
+    pub pubkey: String,
+    pub signer: bool,
+    pub writable: bool,
+    pub modified: bool,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
@@ -423,6 +590,19 @@ pub struct RpcAccountBalance {
     pub lamports: u64,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcBufferAccount {
+    /// Program deploy buffer address, as base-58 encoded string
+    pub pubkey: String,
+    /// Buffer authority, as base-58 encoded string
+    pub authority: String,
+    /// Length of the buffer data, excluding the buffer metadata
+    pub data_len: usize,
+    /// Lamports held by the buffer, recovered when it is closed
+    pub lamports: u64,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct RpcSupply {
@@ -480,6 +660,26 @@ pub struct RpcInflationReward {
     pub commission: Option<u8>, // Vote account commission when the reward was credited
 }
 
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcEpochStakeRewards {
+    /// Epoch the rewards were earned in
+    pub epoch: Epoch,
+    /// Slot of the block the rewards were calculated in
+    pub effective_slot: Slot,
+    pub rewards: Vec<RpcEpochStakeReward>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcEpochStakeReward {
+    pub stake_account: String,
+    pub vote_account: String,
+    pub amount: u64,            // lamports
+    pub commission: Option<u8>, // Vote account commission when the reward was calculated
+    pub effective_stake: u64,   // lamports, in the rewarded epoch
+}
+
 #[derive(Clone, Deserialize, Serialize, Debug, Error, Eq, PartialEq)]
 pub enum RpcBlockUpdateError {
     #[error("block store error")]
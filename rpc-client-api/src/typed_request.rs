@@ -0,0 +1,384 @@
+//! Typed JSON-RPC requests.
+//!
+//! Each request is built from its required parameters and takes its optional
+//! ones through builder methods, so that malformed parameters are caught at
+//! compile time instead of being rejected by the node with an "Invalid params"
+//! error:
+//!
+//! ```
+//! # use {
+//! #     solana_account_decoder_client_types::UiAccountEncoding,
+//! #     solana_pubkey::Pubkey,
+//! #     solana_rpc_client_api::typed_request::{GetAccountInfo, TypedRpcRequest},
+//! # };
+//! let request = GetAccountInfo::new(Pubkey::new_unique())
+//!     .encoding(UiAccountEncoding::Base64)
+//!     .min_context_slot(42);
+//! let json = request.build_request_json(1);
+//! assert_eq!(json["method"], "getAccountInfo");
+//! ```
+//!
+//! The requests serialize to the same wire format as their [`RpcRequest`].
+
+use {
+    crate::{
+        config::{RpcAccountInfoConfig, RpcContextConfig},
+        request::RpcRequest,
+        response::{Response, RpcBlockhash},
+    },
+    serde::de::DeserializeOwned,
+    serde_json::{json, Value},
+    solana_account_decoder_client_types::{UiAccount, UiAccountEncoding, UiDataSliceConfig},
+    solana_clock::Slot,
+    solana_commitment_config::CommitmentConfig,
+    solana_pubkey::Pubkey,
+};
+
+pub trait TypedRpcRequest {
+    /// Type the `result` of the response deserializes into
+    type Response: DeserializeOwned;
+
+    fn request(&self) -> RpcRequest;
+
+    /// Positional parameters of the request
+    fn params(&self) -> Value;
+
+    /// Sets the commitment of the request to `commitment`, unless it was set
+    /// explicitly
+    fn apply_default_commitment(&mut self, commitment: CommitmentConfig);
+
+    fn build_request_json(&self, id: u64) -> Value {
+        self.request().build_request_json(id, self.params())
+    }
+}
+
+/// `getAccountInfo` request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GetAccountInfo {
+    pubkey: Pubkey,
+    config: RpcAccountInfoConfig,
+}
+
+impl GetAccountInfo {
+    pub fn new(pubkey: Pubkey) -> Self {
+        Self {
+            pubkey,
+            config: RpcAccountInfoConfig::default(),
+        }
+    }
+
+    pub fn encoding(mut self, encoding: UiAccountEncoding) -> Self {
+        self.config.encoding = Some(encoding);
+        self
+    }
+
+    pub fn data_slice(mut self, data_slice: UiDataSliceConfig) -> Self {
+        self.config.data_slice = Some(data_slice);
+        self
+    }
+
+    pub fn commitment(mut self, commitment: CommitmentConfig) -> Self {
+        self.config.commitment = Some(commitment);
+        self
+    }
+
+    pub fn min_context_slot(mut self, min_context_slot: Slot) -> Self {
+        self.config.min_context_slot = Some(min_context_slot);
+        self
+    }
+}
+
+impl TypedRpcRequest for GetAccountInfo {
+    type Response = Response<Option<UiAccount>>;
+
+    fn request(&self) -> RpcRequest {
+        RpcRequest::GetAccountInfo
+    }
+
+    fn params(&self) -> Value {
+        json!([self.pubkey.to_string(), self.config])
+    }
+
+    fn apply_default_commitment(&mut self, commitment: CommitmentConfig) {
+        self.config.commitment.get_or_insert(commitment);
+    }
+}
+
+/// `getMultipleAccounts` request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GetMultipleAccounts {
+    pubkeys: Vec<Pubkey>,
+    config: RpcAccountInfoConfig,
+}
+
+impl GetMultipleAccounts {
+    /// At most [`MAX_MULTIPLE_ACCOUNTS`] accounts can be requested at once.
+    ///
+    /// [`MAX_MULTIPLE_ACCOUNTS`]: crate::request::MAX_MULTIPLE_ACCOUNTS
+    pub fn new(pubkeys: Vec<Pubkey>) -> Self {
+        Self {
+            pubkeys,
+            config: RpcAccountInfoConfig::default(),
+        }
+    }
+
+    pub fn encoding(mut self, encoding: UiAccountEncoding) -> Self {
+        self.config.encoding = Some(encoding);
+        self
+    }
+
+    pub fn data_slice(mut self, data_slice: UiDataSliceConfig) -> Self {
+        self.config.data_slice = Some(data_slice);
+        self
+    }
+
+    pub fn commitment(mut self, commitment: CommitmentConfig) -> Self {
+        self.config.commitment = Some(commitment);
+        self
+    }
+
+    pub fn min_context_slot(mut self, min_context_slot: Slot) -> Self {
+        self.config.min_context_slot = Some(min_context_slot);
+        self
+    }
+}
+
+impl TypedRpcRequest for GetMultipleAccounts {
+    type Response = Response<Vec<Option<UiAccount>>>;
+
+    fn request(&self) -> RpcRequest {
+        RpcRequest::GetMultipleAccounts
+    }
+
+    fn params(&self) -> Value {
+        let pubkeys: Vec<_> = self.pubkeys.iter().map(Pubkey::to_string).collect();
+        json!([pubkeys, self.config])
+    }
+
+    fn apply_default_commitment(&mut self, commitment: CommitmentConfig) {
+        self.config.commitment.get_or_insert(commitment);
+    }
+}
+
+/// `getBalance` request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GetBalance {
+    pubkey: Pubkey,
+    config: RpcContextConfig,
+}
+
+impl GetBalance {
+    pub fn new(pubkey: Pubkey) -> Self {
+        Self {
+            pubkey,
+            config: RpcContextConfig::default(),
+        }
+    }
+
+    pub fn commitment(mut self, commitment: CommitmentConfig) -> Self {
+        self.config.commitment = Some(commitment);
+        self
+    }
+
+    pub fn min_context_slot(mut self, min_context_slot: Slot) -> Self {
+        self.config.min_context_slot = Some(min_context_slot);
+        self
+    }
+}
+
+impl TypedRpcRequest for GetBalance {
+    type Response = Response<u64>;
+
+    fn request(&self) -> RpcRequest {
+        RpcRequest::GetBalance
+    }
+
+    fn params(&self) -> Value {
+        json!([self.pubkey.to_string(), self.config])
+    }
+
+    fn apply_default_commitment(&mut self, commitment: CommitmentConfig) {
+        self.config.commitment.get_or_insert(commitment);
+    }
+}
+
+/// `getSlot` request
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GetSlot {
+    config: RpcContextConfig,
+}
+
+impl GetSlot {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn commitment(mut self, commitment: CommitmentConfig) -> Self {
+        self.config.commitment = Some(commitment);
+        self
+    }
+
+    pub fn min_context_slot(mut self, min_context_slot: Slot) -> Self {
+        self.config.min_context_slot = Some(min_context_slot);
+        self
+    }
+}
+
+impl TypedRpcRequest for GetSlot {
+    type Response = Slot;
+
+    fn request(&self) -> RpcRequest {
+        RpcRequest::GetSlot
+    }
+
+    fn params(&self) -> Value {
+        json!([self.config])
+    }
+
+    fn apply_default_commitment(&mut self, commitment: CommitmentConfig) {
+        self.config.commitment.get_or_insert(commitment);
+    }
+}
+
+/// `getLatestBlockhash` request
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GetLatestBlockhash {
+    config: RpcContextConfig,
+}
+
+impl GetLatestBlockhash {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn commitment(mut self, commitment: CommitmentConfig) -> Self {
+        self.config.commitment = Some(commitment);
+        self
+    }
+
+    pub fn min_context_slot(mut self, min_context_slot: Slot) -> Self {
+        self.config.min_context_slot = Some(min_context_slot);
+        self
+    }
+}
+
+impl TypedRpcRequest for GetLatestBlockhash {
+    type Response = Response<RpcBlockhash>;
+
+    fn request(&self) -> RpcRequest {
+        RpcRequest::GetLatestBlockhash
+    }
+
+    fn params(&self) -> Value {
+        json!([self.config])
+    }
+
+    fn apply_default_commitment(&mut self, commitment: CommitmentConfig) {
+        self.config.commitment.get_or_insert(commitment);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, solana_commitment_config::CommitmentLevel};
+
+    #[test]
+    fn test_get_account_info() {
+        let pubkey = Pubkey::new_unique();
+        let request = GetAccountInfo::new(pubkey)
+            .encoding(UiAccountEncoding::Base64)
+            .data_slice(UiDataSliceConfig {
+                offset: 1,
+                length: 2,
+            })
+            .commitment(CommitmentConfig::confirmed())
+            .min_context_slot(42);
+        let json = request.build_request_json(7);
+        assert_eq!(json["id"], 7);
+        assert_eq!(json["method"], "getAccountInfo");
+        assert_eq!(
+            json["params"],
+            json!([
+                pubkey.to_string(),
+                {
+                    "encoding": "base64",
+                    "dataSlice": { "offset": 1, "length": 2 },
+                    "commitment": "confirmed",
+                    "minContextSlot": 42,
+                }
+            ])
+        );
+
+        // Unset options serialize like the default config
+        assert_eq!(
+            GetAccountInfo::new(pubkey).params(),
+            json!([pubkey.to_string(), RpcAccountInfoConfig::default()])
+        );
+    }
+
+    #[test]
+    fn test_get_multiple_accounts() {
+        let pubkeys = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+        let request = GetMultipleAccounts::new(pubkeys.clone()).encoding(UiAccountEncoding::Base58);
+        assert_eq!(request.request(), RpcRequest::GetMultipleAccounts);
+        assert_eq!(
+            request.params(),
+            json!([
+                [pubkeys[0].to_string(), pubkeys[1].to_string()],
+                RpcAccountInfoConfig {
+                    encoding: Some(UiAccountEncoding::Base58),
+                    ..RpcAccountInfoConfig::default()
+                }
+            ])
+        );
+    }
+
+    #[test]
+    fn test_context_requests() {
+        let pubkey = Pubkey::new_unique();
+        let commitment = CommitmentConfig {
+            commitment: CommitmentLevel::Finalized,
+        };
+        assert_eq!(
+            GetBalance::new(pubkey).commitment(commitment).params(),
+            json!([pubkey.to_string(), { "commitment": "finalized", "minContextSlot": null }])
+        );
+        assert_eq!(
+            GetSlot::new().min_context_slot(3).params(),
+            json!([{ "minContextSlot": 3 }])
+        );
+        assert_eq!(
+            GetLatestBlockhash::new().build_request_json(1)["method"],
+            "getLatestBlockhash"
+        );
+    }
+
+    #[test]
+    fn test_apply_default_commitment() {
+        let mut request = GetSlot::new();
+        request.apply_default_commitment(CommitmentConfig::processed());
+        assert_eq!(request.params()[0]["commitment"], "processed");
+
+        // An explicit commitment is kept
+        let mut request =
+            GetBalance::new(Pubkey::new_unique()).commitment(CommitmentConfig::finalized());
+        request.apply_default_commitment(CommitmentConfig::processed());
+        assert_eq!(request.params()[1]["commitment"], "finalized");
+    }
+
+    #[test]
+    fn test_responses() {
+        let response: <GetLatestBlockhash as TypedRpcRequest>::Response =
+            serde_json::from_value(json!({
+                "context": { "slot": 5 },
+                "value": { "blockhash": "deadbeef", "lastValidBlockHeight": 10 },
+            }))
+            .unwrap();
+        assert_eq!(response.context.slot, 5);
+        assert_eq!(response.value.last_valid_block_height, 10);
+
+        let slot: <GetSlot as TypedRpcRequest>::Response =
+            serde_json::from_value(json!(12)).unwrap();
+        assert_eq!(slot, 12);
+    }
+}
@@ -3,6 +3,7 @@
 pub mod http_sender;
 pub mod mock_sender;
 pub mod nonblocking;
+pub mod pooled_http_sender;
 pub mod rpc_client;
 pub mod rpc_sender;
 pub mod spinner;
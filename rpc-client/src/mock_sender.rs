@@ -18,11 +18,12 @@ use {
         request::RpcRequest,
         response::{
             Response, RpcAccountBalance, RpcBlockProduction, RpcBlockProductionRange, RpcBlockhash,
-            RpcConfirmedTransactionStatusWithSignature, RpcContactInfo, RpcIdentity,
-            RpcInflationGovernor, RpcInflationRate, RpcInflationReward, RpcKeyedAccount,
-            RpcPerfSample, RpcPrioritizationFee, RpcResponseContext, RpcSimulateTransactionResult,
-            RpcSnapshotSlotInfo, RpcSupply, RpcVersionInfo, RpcVoteAccountInfo,
-            RpcVoteAccountStatus,
+            RpcBufferAccount, RpcConfirmedTransactionStatusWithSignature, RpcContactInfo,
+            RpcDuplicateVote, RpcIdentity, RpcInflationGovernor, RpcInflationRate,
+            RpcInflationReward, RpcKeyedAccount, RpcPerfSample, RpcPrioritizationFee,
+            RpcResponseContext, RpcSimulateTransactionResult, RpcSlotHash, RpcSnapshotSlotInfo,
+            RpcSupply, RpcVersionInfo, RpcVoteAccountEpochStake, RpcVoteAccountInfo,
+            RpcVoteAccountStatus, RpcVoteLatencyStatus,
         },
     },
     solana_signature::Signature,
@@ -181,6 +182,7 @@ impl RpcSender for MockSender {
                                         accounts: vec![0, 1],
                                         data: "3Bxs49DitAvXtoDR".to_string(),
                                         stack_height: None,
+                                        program_id: None,
                                     }],
                                     address_table_lookups: None,
                                 })
@@ -205,6 +207,11 @@ impl RpcSender for MockSender {
             })?,
             "getTransactionCount" => json![1234],
             "getSlot" => json![0],
+            "getSlotHashes" => serde_json::to_value(vec![RpcSlotHash {
+                slot: 0,
+                hash: "D37n3BSG71oUWcWjbZ37jZP7UfsxG2QMKeuALJ1PYvM6".to_string(),
+            }])?,
+            "getDuplicateVotes" => serde_json::to_value(Vec::<RpcDuplicateVote>::new())?,
             "getMaxShredInsertSlot" => json![0],
             "requestAirdrop" => Value::String(Signature::from([8; 64]).to_string()),
             "getHighestSnapshotSlot" => json!(RpcSnapshotSlotInfo {
@@ -223,6 +230,7 @@ impl RpcSender for MockSender {
                                 first_slot: 1,
                                 last_slot: 2,
                             },
+                            skipped_slots: None,
                         },
                     })
                 } else {
@@ -243,6 +251,7 @@ impl RpcSender for MockSender {
                                     config_range.last_slot.unwrap_or(2)
                                 },
                             },
+                            skipped_slots: None,
                         },
                     })
                 }
@@ -286,6 +295,25 @@ impl RpcSender for MockSender {
                     }],
                 })
             }
+            "getBufferAccounts" => serde_json::to_value(Response {
+                context: RpcResponseContext { slot: 1, api_version: None },
+                value: Vec::<RpcBufferAccount>::new(),
+            })?,
+            "getVoteAccountEpochStakes" => serde_json::to_value(Response {
+                context: RpcResponseContext { slot: 1, api_version: None },
+                value: vec![RpcVoteAccountEpochStake {
+                    epoch: 1,
+                    activated_stake: 42,
+                    total_activated_stake: 100,
+                }],
+            })?,
+            "getVoteLatency" => serde_json::to_value(Response {
+                context: RpcResponseContext { slot: 1, api_version: None },
+                value: RpcVoteLatencyStatus {
+                    epoch: 1,
+                    validators: vec![],
+                },
+            })?,
             "sendTransaction" => {
                 let signature = if self.url == "malicious" {
                     Signature::from([8; 64]).to_string()
@@ -306,7 +334,9 @@ impl RpcSender for MockSender {
                     units_consumed: None,
                     return_data: None,
                     inner_instructions: None,
-                    replacement_blockhash: None
+                    replacement_blockhash: None,
+                    memory_usage: None,
+                    account_privileges: None,
                 },
             })?,
             "getMinimumBalanceForRentExemption" => json![20],
@@ -41,6 +41,7 @@ use {
         config::{RpcAccountInfoConfig, *},
         request::{RpcError, RpcRequest, RpcResponseErrorData, TokenAccountsFilter},
         response::*,
+        typed_request::TypedRpcRequest,
     },
     solana_signature::Signature,
     solana_transaction_error::TransactionResult,
@@ -1914,6 +1915,7 @@ impl RpcClient {
     ///     identity: Some(leader.to_string()),
     ///     range: Some(range),
     ///     commitment: Some(CommitmentConfig::processed()),
+    ///     skipped_slot_reasons: None,
     /// };
     /// let production = rpc_client.get_block_production_with_config(
     ///     config
@@ -2016,6 +2018,7 @@ impl RpcClient {
     ///     commitment: Some(commitment_config),
     ///     filter: Some(RpcLargestAccountsFilter::Circulating),
     ///     sort_results: None,
+    ///     min_context_slot: None,
     /// };
     /// let accounts = rpc_client.get_largest_accounts_with_config(
     ///     config,
@@ -2152,6 +2155,157 @@ impl RpcClient {
             .await
     }
 
+    /// Returns the distribution of vote latencies in the current epoch for
+    /// the given vote account, or for all vote accounts if none is specified.
+    ///
+    /// Latencies are tracked by the RPC node from its rooted bank, starting
+    /// from when the node was started.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `getVoteLatency` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::{
+    /// #     client_error::Error,
+    /// #     config::RpcVoteLatencyConfig,
+    /// # };
+    /// # use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+    /// # use solana_keypair::Keypair;
+    /// # use solana_signer::Signer;
+    /// # futures::executor::block_on(async {
+    /// #     let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// #     let vote_keypair = Keypair::new();
+    /// let vote_pubkey = vote_keypair.pubkey();
+    /// let config = RpcVoteLatencyConfig {
+    ///     vote_pubkey: Some(vote_pubkey.to_string()),
+    /// };
+    /// let latency = rpc_client.get_vote_latency_with_config(config).await?;
+    /// #     Ok::<(), Error>(())
+    /// # })?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub async fn get_vote_latency_with_config(
+        &self,
+        config: RpcVoteLatencyConfig,
+    ) -> RpcResult<RpcVoteLatencyStatus> {
+        self.send(RpcRequest::GetVoteLatency, json!([config])).await
+    }
+
+    /// Returns the stake of the given vote account, and the total stake, in
+    /// each epoch whose stakes are retained by the RPC node.
+    ///
+    /// Epochs are leader schedule epochs: the stakes returned for epoch `E`
+    /// were active during epoch `E - 1`. Nodes retain the stakes of a few
+    /// epochs by default, more with `--epoch-stakes-retention`.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `getVoteAccountEpochStakes` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::client_error::Error;
+    /// # use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+    /// # use solana_keypair::Keypair;
+    /// # use solana_signer::Signer;
+    /// # futures::executor::block_on(async {
+    /// #     let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// #     let vote_keypair = Keypair::new();
+    /// let vote_pubkey = vote_keypair.pubkey();
+    /// let epoch_stakes = rpc_client.get_vote_account_epoch_stakes(&vote_pubkey).await?;
+    /// #     Ok::<(), Error>(())
+    /// # })?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub async fn get_vote_account_epoch_stakes(
+        &self,
+        vote_pubkey: &Pubkey,
+    ) -> RpcResult<Vec<RpcVoteAccountEpochStake>> {
+        self.send(
+            RpcRequest::GetVoteAccountEpochStakes,
+            json!([vote_pubkey.to_string()]),
+        )
+        .await
+    }
+
+    /// Returns the bank hashes of up to `limit` finalized slots starting at
+    /// `start_slot`.
+    ///
+    /// The `SlotHashes` sysvar only holds the hashes of the most recent 512
+    /// slots. This method also reaches the hashes of older slots, for as long
+    /// as the RPC node retains them in its ledger. Slots whose hash the node
+    /// does not know, such as slots before the snapshot it started from, are
+    /// skipped.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the limit is greater than 10,000 slots.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `getSlotHashes` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::client_error::Error;
+    /// # use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+    /// # futures::executor::block_on(async {
+    /// #     let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// let slot_hashes = rpc_client.get_slot_hashes(0, 10).await?;
+    /// #     Ok::<(), Error>(())
+    /// # })?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub async fn get_slot_hashes(
+        &self,
+        start_slot: Slot,
+        limit: usize,
+    ) -> ClientResult<Vec<RpcSlotHash>> {
+        self.send(RpcRequest::GetSlotHashes, json!([start_slot, limit]))
+            .await
+    }
+
+    /// Returns up to `limit` proofs of vote accounts voting for two different
+    /// bank hashes of the same slot, starting at `start_slot`.
+    ///
+    /// The RPC node records these when it sees conflicting votes in gossip or
+    /// in replayed blocks, and keeps them for as long as the slots remain in
+    /// its ledger.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the limit is greater than 1,000 proofs.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `getDuplicateVotes` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::client_error::Error;
+    /// # use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+    /// # futures::executor::block_on(async {
+    /// #     let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// let duplicate_votes = rpc_client.get_duplicate_votes(0, 10).await?;
+    /// #     Ok::<(), Error>(())
+    /// # })?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub async fn get_duplicate_votes(
+        &self,
+        start_slot: Slot,
+        limit: usize,
+    ) -> ClientResult<Vec<RpcDuplicateVote>> {
+        self.send(RpcRequest::GetDuplicateVotes, json!([start_slot, limit]))
+            .await
+    }
+
     pub async fn wait_for_max_stake(
         &self,
         commitment: CommitmentConfig,
@@ -3963,6 +4117,7 @@ impl RpcClient {
     ///     },
     ///     with_context: Some(false),
     ///     sort_results: Some(true),
+    ///     pagination: None,
     /// };
     /// let accounts = rpc_client.get_program_accounts_with_config(
     ///     &alice.pubkey(),
@@ -3993,6 +4148,89 @@ impl RpcClient {
         parse_keyed_accounts(accounts, RpcRequest::GetProgramAccounts)
     }
 
+    /// Returns the program deploy buffers whose authority is the provided
+    /// pubkey, in increasing order of buffer address.
+    ///
+    /// Buffers left behind by failed or abandoned deployments keep their
+    /// lamports until they are closed by their authority.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `getBufferAccounts` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::client_error::Error;
+    /// # use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+    /// # use solana_keypair::Keypair;
+    /// # use solana_signer::Signer;
+    /// # futures::executor::block_on(async {
+    /// #     let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// #     let authority = Keypair::new();
+    /// let buffers = rpc_client.get_buffer_accounts(&authority.pubkey()).await?;
+    /// let stranded_lamports: u64 = buffers.iter().map(|buffer| buffer.lamports).sum();
+    /// #     Ok::<(), Error>(())
+    /// # })?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub async fn get_buffer_accounts(
+        &self,
+        authority: &Pubkey,
+    ) -> ClientResult<Vec<RpcBufferAccount>> {
+        Ok(self
+            .get_buffer_accounts_with_config(
+                authority,
+                RpcContextConfig {
+                    commitment: Some(self.commitment()),
+                    min_context_slot: None,
+                },
+            )
+            .await?
+            .value)
+    }
+
+    /// Returns the program deploy buffers whose authority is the provided
+    /// pubkey, in increasing order of buffer address.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `getBufferAccounts` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::{client_error::Error, config::RpcContextConfig};
+    /// # use solana_rpc_client::nonblocking::rpc_client::RpcClient;
+    /// # use solana_commitment_config::CommitmentConfig;
+    /// # use solana_keypair::Keypair;
+    /// # use solana_signer::Signer;
+    /// # futures::executor::block_on(async {
+    /// #     let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// #     let authority = Keypair::new();
+    /// let config = RpcContextConfig {
+    ///     commitment: Some(CommitmentConfig::finalized()),
+    ///     min_context_slot: None,
+    /// };
+    /// let buffers = rpc_client
+    ///     .get_buffer_accounts_with_config(&authority.pubkey(), config)
+    ///     .await?;
+    /// #     Ok::<(), Error>(())
+    /// # })?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub async fn get_buffer_accounts_with_config(
+        &self,
+        authority: &Pubkey,
+        config: RpcContextConfig,
+    ) -> RpcResult<Vec<RpcBufferAccount>> {
+        self.send(
+            RpcRequest::GetBufferAccounts,
+            json!([authority.to_string(), config]),
+        )
+        .await
+    }
+
     /// Returns the stake minimum delegation, in lamports.
     ///
     /// # RPC Reference
@@ -4634,6 +4872,19 @@ impl RpcClient {
             .map_err(|err| ClientError::new_with_request(err.into(), request))
     }
 
+    /// Send a typed request, see [`solana_rpc_client_api::typed_request`].
+    ///
+    /// Requests without an explicit commitment use the configured [commitment level][cl].
+    ///
+    /// [cl]: https://solana.com/docs/rpc#configuring-state-commitment
+    pub async fn send_typed<R: TypedRpcRequest>(
+        &self,
+        mut request: R,
+    ) -> ClientResult<R::Response> {
+        request.apply_default_commitment(self.commitment());
+        self.send(request.request(), request.params()).await
+    }
+
     pub fn get_transport_stats(&self) -> RpcTransportStats {
         self.sender.get_transport_stats()
     }
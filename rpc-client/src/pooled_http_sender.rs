@@ -0,0 +1,371 @@
+//! Nonblocking [`RpcSender`] spreading requests over a pool of HTTP endpoints.
+//!
+//! Every endpoint has a limit on the number of requests in flight to it.
+//! Requests are sent to the endpoint with the most spare capacity, and wait in
+//! a queue shared by all the endpoints once they are all at their limit, so
+//! that a queued request goes to whichever endpoint frees up first.
+
+use {
+    crate::{http_sender::HttpSender, rpc_sender::*},
+    async_trait::async_trait,
+    solana_rpc_client_api::{
+        client_error::Result,
+        request::{RpcError, RpcRequest},
+    },
+    std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc, RwLock,
+        },
+        time::{Duration, Instant},
+    },
+    tokio::sync::{OwnedSemaphorePermit, Semaphore},
+};
+
+/// An endpoint of a [`PooledHttpSender`].
+#[derive(Debug, Clone)]
+pub struct PoolEndpointConfig {
+    /// HTTP URL of the endpoint, as in "http://localhost:8899"
+    pub url: String,
+
+    /// Max number of requests in flight to the endpoint
+    pub max_concurrent_requests: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct PooledHttpSenderConfig {
+    pub endpoints: Vec<PoolEndpointConfig>,
+
+    /// Timeout of every request, not counting the time spent in the queue
+    pub timeout: Duration,
+
+    /// Max number of requests waiting for an endpoint, further requests failing
+    /// immediately, or `None` to let the queue grow unbounded
+    pub max_queued_requests: Option<usize>,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct PoolEndpointStats {
+    pub url: String,
+
+    /// Number of requests currently in flight to the endpoint
+    pub in_flight_requests: usize,
+
+    /// Total amount of time requests sent to the endpoint spent in the queue
+    pub queued_time: Duration,
+
+    /// Longest amount of time a request sent to the endpoint spent in the queue
+    pub max_queued_time: Duration,
+
+    /// Stats of the requests sent to the endpoint
+    pub transport_stats: RpcTransportStats,
+}
+
+#[derive(Default)]
+struct QueueStats {
+    queued_time: Duration,
+    max_queued_time: Duration,
+}
+
+struct PoolEndpoint {
+    sender: HttpSender,
+    max_concurrent_requests: usize,
+    permits: Arc<Semaphore>,
+    queue_stats: RwLock<QueueStats>,
+}
+
+impl PoolEndpoint {
+    fn new(config: PoolEndpointConfig, timeout: Duration) -> Self {
+        assert!(
+            config.max_concurrent_requests > 0,
+            "max_concurrent_requests of {} must be positive",
+            config.url
+        );
+        Self {
+            sender: HttpSender::new_with_timeout(config.url, timeout),
+            max_concurrent_requests: config.max_concurrent_requests,
+            permits: Arc::new(Semaphore::new(config.max_concurrent_requests)),
+            queue_stats: RwLock::default(),
+        }
+    }
+
+    fn add_queued_time(&self, duration: Duration) {
+        let mut queue_stats = self.queue_stats.write().unwrap();
+        queue_stats.queued_time += duration;
+        queue_stats.max_queued_time = queue_stats.max_queued_time.max(duration);
+    }
+
+    fn stats(&self) -> PoolEndpointStats {
+        let queue_stats = self.queue_stats.read().unwrap();
+        PoolEndpointStats {
+            url: self.sender.url(),
+            in_flight_requests: self
+                .max_concurrent_requests
+                .saturating_sub(self.permits.available_permits()),
+            queued_time: queue_stats.queued_time,
+            max_queued_time: queue_stats.max_queued_time,
+            transport_stats: self.sender.get_transport_stats(),
+        }
+    }
+}
+
+/// A request waiting for an endpoint, leaving the queue when dropped even if
+/// the request is cancelled.
+struct QueuedRequest<'a> {
+    queued_requests: &'a AtomicUsize,
+}
+
+impl Drop for QueuedRequest<'_> {
+    fn drop(&mut self) {
+        self.queued_requests.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Nonblocking [`RpcSender`] over a pool of HTTP endpoints.
+pub struct PooledHttpSender {
+    endpoints: Vec<PoolEndpoint>,
+    /// As many permits as the endpoints together, so that holding one
+    /// guarantees an endpoint has room for the request
+    permits: Semaphore,
+    max_queued_requests: Option<usize>,
+    queued_requests: AtomicUsize,
+}
+
+impl PooledHttpSender {
+    /// Create a pooled HTTP RPC sender.
+    ///
+    /// Every endpoint takes up to `max_concurrent_requests` at a time, and the
+    /// queue is unbounded. Requests have a default timeout of 30 seconds.
+    pub fn new<U: ToString>(
+        urls: impl IntoIterator<Item = U>,
+        max_concurrent_requests: usize,
+    ) -> Self {
+        Self::new_with_config(PooledHttpSenderConfig {
+            endpoints: urls
+                .into_iter()
+                .map(|url| PoolEndpointConfig {
+                    url: url.to_string(),
+                    max_concurrent_requests,
+                })
+                .collect(),
+            timeout: Duration::from_secs(30),
+            max_queued_requests: None,
+        })
+    }
+
+    /// Create a pooled HTTP RPC sender.
+    pub fn new_with_config(config: PooledHttpSenderConfig) -> Self {
+        assert!(
+            !config.endpoints.is_empty(),
+            "a pooled sender needs at least one endpoint"
+        );
+        let endpoints: Vec<_> = config
+            .endpoints
+            .into_iter()
+            .map(|endpoint| PoolEndpoint::new(endpoint, config.timeout))
+            .collect();
+        let max_concurrent_requests = endpoints
+            .iter()
+            .map(|endpoint| endpoint.max_concurrent_requests)
+            .sum();
+        Self {
+            endpoints,
+            permits: Semaphore::new(max_concurrent_requests),
+            max_queued_requests: config.max_queued_requests,
+            queued_requests: AtomicUsize::new(0),
+        }
+    }
+
+    /// Stats of every endpoint, in the order of the configuration
+    pub fn get_endpoint_stats(&self) -> Vec<PoolEndpointStats> {
+        self.endpoints.iter().map(PoolEndpoint::stats).collect()
+    }
+
+    /// Number of requests currently waiting for an endpoint
+    pub fn queued_requests(&self) -> usize {
+        self.queued_requests.load(Ordering::Relaxed)
+    }
+
+    /// Takes a permit of the endpoint with the most spare capacity. The caller
+    /// holds a pool permit, so an endpoint has room for the request, although
+    /// concurrent requests may take it from under an endpoint just picked.
+    async fn acquire_endpoint(&self) -> (&PoolEndpoint, OwnedSemaphorePermit) {
+        loop {
+            let mut endpoints: Vec<_> = self.endpoints.iter().collect();
+            // The sort is stable, favoring the earlier of equally loaded
+            // endpoints
+            endpoints
+                .sort_by_key(|endpoint| std::cmp::Reverse(endpoint.permits.available_permits()));
+            for endpoint in endpoints {
+                if let Ok(permit) = endpoint.permits.clone().try_acquire_owned() {
+                    return (endpoint, permit);
+                }
+            }
+            tokio::task::yield_now().await;
+        }
+    }
+}
+
+#[async_trait]
+impl RpcSender for PooledHttpSender {
+    /// Sum of the stats of all the endpoints
+    fn get_transport_stats(&self) -> RpcTransportStats {
+        self.endpoints
+            .iter()
+            .map(|endpoint| endpoint.sender.get_transport_stats())
+            .fold(RpcTransportStats::default(), |mut total, stats| {
+                total.request_count += stats.request_count;
+                total.elapsed_time += stats.elapsed_time;
+                total.rate_limited_time += stats.rate_limited_time;
+                total
+            })
+    }
+
+    async fn send(
+        &self,
+        request: RpcRequest,
+        params: serde_json::Value,
+    ) -> Result<serde_json::Value> {
+        let queue_start_time = Instant::now();
+        let (_pool_permit, queued_time) = match self.permits.try_acquire() {
+            Ok(permit) => (permit, None),
+            Err(_) => {
+                let queued_requests = self.queued_requests.fetch_add(1, Ordering::Relaxed);
+                let queued_request = QueuedRequest {
+                    queued_requests: &self.queued_requests,
+                };
+                if self
+                    .max_queued_requests
+                    .is_some_and(|max_queued_requests| queued_requests >= max_queued_requests)
+                {
+                    return Err(RpcError::RpcRequestError(format!(
+                        "RPC request queue is full: {queued_requests} requests waiting"
+                    ))
+                    .into());
+                }
+                let permit = self
+                    .permits
+                    .acquire()
+                    .await
+                    .expect("pool semaphore is never closed");
+                drop(queued_request);
+                (permit, Some(queue_start_time.elapsed()))
+            }
+        };
+        let (endpoint, _endpoint_permit) = self.acquire_endpoint().await;
+        if let Some(queued_time) = queued_time {
+            endpoint.add_queued_time(queued_time);
+        }
+        endpoint.sender.send(request, params).await
+    }
+
+    /// URL of the first endpoint of the pool
+    fn url(&self) -> String {
+        self.endpoints[0].sender.url()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crossbeam_channel::unbounded,
+        futures::future::join_all,
+        jsonrpc_core::{futures::future, IoHandler, Params},
+        jsonrpc_http_server::{AccessControlAllowOrigin, DomainsValidation, ServerBuilder},
+        serde_json::{Number, Value},
+        std::{net::SocketAddr, thread},
+    };
+
+    fn start_rpc_server(delay: Duration) -> SocketAddr {
+        let (sender, receiver) = unbounded();
+        thread::spawn(move || {
+            let rpc_addr = "0.0.0.0:0".parse().unwrap();
+            let mut io = IoHandler::default();
+            io.add_method("getBalance", move |_params: Params| {
+                thread::sleep(delay);
+                future::ok(Value::Number(Number::from(50)))
+            });
+
+            let server = ServerBuilder::new(io)
+                .threads(1)
+                .cors(DomainsValidation::AllowOnly(vec![
+                    AccessControlAllowOrigin::Any,
+                ]))
+                .start_http(&rpc_addr)
+                .expect("Unable to start RPC server");
+            sender.send(*server.address()).unwrap();
+            server.wait();
+        });
+        receiver.recv().unwrap()
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_pooled_http_sender_spreads_requests() {
+        let urls: Vec<_> = (0..2)
+            .map(|_| format!("http://{}", start_rpc_server(Duration::ZERO)))
+            .collect();
+        let pooled_sender = PooledHttpSender::new(urls.clone(), 1);
+        assert_eq!(pooled_sender.url(), urls[0]);
+
+        let results =
+            join_all((0..4).map(|_| pooled_sender.send(RpcRequest::GetBalance, Value::Null))).await;
+        for result in results {
+            assert_eq!(result.unwrap(), Value::Number(Number::from(50)));
+        }
+
+        let endpoint_stats = pooled_sender.get_endpoint_stats();
+        assert_eq!(endpoint_stats.len(), 2);
+        for (stats, url) in endpoint_stats.iter().zip(&urls) {
+            assert_eq!(&stats.url, url);
+            assert_eq!(stats.in_flight_requests, 0);
+            assert!(stats.transport_stats.request_count > 0);
+        }
+        assert_eq!(pooled_sender.queued_requests(), 0);
+        assert_eq!(pooled_sender.get_transport_stats().request_count, 4);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_pooled_http_sender_shared_queue() {
+        let slow_url = format!("http://{}", start_rpc_server(Duration::from_secs(1)));
+        let fast_url = format!("http://{}", start_rpc_server(Duration::ZERO));
+        let pooled_sender = PooledHttpSender::new([slow_url, fast_url], 1);
+
+        // The queued request goes to the fast endpoint once it is done, rather
+        // than waiting for the slow one
+        let results =
+            join_all((0..3).map(|_| pooled_sender.send(RpcRequest::GetBalance, Value::Null))).await;
+        for result in results {
+            assert_eq!(result.unwrap(), Value::Number(Number::from(50)));
+        }
+        let endpoint_stats = pooled_sender.get_endpoint_stats();
+        assert_eq!(endpoint_stats[0].transport_stats.request_count, 1);
+        assert_eq!(endpoint_stats[1].transport_stats.request_count, 2);
+        assert_eq!(endpoint_stats[0].max_queued_time, Duration::ZERO);
+        assert!(endpoint_stats[1].max_queued_time > Duration::ZERO);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_pooled_http_sender_queue_limit() {
+        let url = format!("http://{}", start_rpc_server(Duration::from_millis(200)));
+        let pooled_sender = PooledHttpSender::new_with_config(PooledHttpSenderConfig {
+            endpoints: vec![PoolEndpointConfig {
+                url,
+                max_concurrent_requests: 1,
+            }],
+            timeout: Duration::from_secs(30),
+            max_queued_requests: Some(1),
+        });
+
+        let results =
+            join_all((0..3).map(|_| pooled_sender.send(RpcRequest::GetBalance, Value::Null))).await;
+        assert!(results[0].is_ok());
+        assert!(results[1].is_ok());
+        assert!(results[2].is_err());
+
+        let endpoint_stats = &pooled_sender.get_endpoint_stats()[0];
+        assert_eq!(endpoint_stats.transport_stats.request_count, 2);
+        assert!(endpoint_stats.max_queued_time > Duration::ZERO);
+        assert_eq!(endpoint_stats.queued_time, endpoint_stats.max_queued_time);
+    }
+}
@@ -34,6 +34,7 @@ use {
         config::{RpcAccountInfoConfig, *},
         request::{RpcRequest, TokenAccountsFilter},
         response::*,
+        typed_request::TypedRpcRequest,
     },
     solana_signature::Signature,
     solana_transaction::{uses_durable_nonce, versioned::VersionedTransaction, Transaction},
@@ -865,6 +866,15 @@ impl RpcClient {
         self.invoke((self.rpc_client.as_ref()).send(request, params))
     }
 
+    /// Send a typed request, see [`solana_rpc_client_api::typed_request`].
+    ///
+    /// Requests without an explicit commitment use the configured [commitment level][cl].
+    ///
+    /// [cl]: https://solana.com/docs/rpc#configuring-state-commitment
+    pub fn send_typed<R: TypedRpcRequest>(&self, request: R) -> ClientResult<R::Response> {
+        self.invoke((self.rpc_client.as_ref()).send_typed(request))
+    }
+
     /// Check the confirmation status of a transaction.
     ///
     /// Returns `true` if the given transaction succeeded and has been committed
@@ -1661,6 +1671,7 @@ impl RpcClient {
     ///     identity: Some(leader.to_string()),
     ///     range: Some(range),
     ///     commitment: Some(CommitmentConfig::processed()),
+    ///     skipped_slot_reasons: None,
     /// };
     /// let production = rpc_client.get_block_production_with_config(
     ///     config
@@ -1751,6 +1762,7 @@ impl RpcClient {
     ///     commitment: Some(commitment_config),
     ///     filter: Some(RpcLargestAccountsFilter::Circulating),
     ///     sort_results: None,
+    ///     min_context_slot: None,
     /// };
     /// let accounts = rpc_client.get_largest_accounts_with_config(
     ///     config,
@@ -1864,6 +1876,139 @@ impl RpcClient {
         self.invoke((self.rpc_client.as_ref()).get_vote_accounts_with_config(config))
     }
 
+    /// Returns the distribution of vote latencies in the current epoch for
+    /// the given vote account, or for all vote accounts if none is specified.
+    ///
+    /// Latencies are tracked by the RPC node from its rooted bank, starting
+    /// from when the node was started.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `getVoteLatency` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::{
+    /// #     client_error::Error,
+    /// #     config::RpcVoteLatencyConfig,
+    /// # };
+    /// # use solana_rpc_client::rpc_client::RpcClient;
+    /// # use solana_keypair::Keypair;
+    /// # use solana_signer::Signer;
+    /// # let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// # let vote_keypair = Keypair::new();
+    /// let vote_pubkey = vote_keypair.pubkey();
+    /// let config = RpcVoteLatencyConfig {
+    ///     vote_pubkey: Some(vote_pubkey.to_string()),
+    /// };
+    /// let latency = rpc_client.get_vote_latency_with_config(config)?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn get_vote_latency_with_config(
+        &self,
+        config: RpcVoteLatencyConfig,
+    ) -> RpcResult<RpcVoteLatencyStatus> {
+        self.invoke((self.rpc_client.as_ref()).get_vote_latency_with_config(config))
+    }
+
+    /// Returns the stake of the given vote account, and the total stake, in
+    /// each epoch whose stakes are retained by the RPC node.
+    ///
+    /// Epochs are leader schedule epochs: the stakes returned for epoch `E`
+    /// were active during epoch `E - 1`. Nodes retain the stakes of a few
+    /// epochs by default, more with `--epoch-stakes-retention`.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `getVoteAccountEpochStakes` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::client_error::Error;
+    /// # use solana_rpc_client::rpc_client::RpcClient;
+    /// # use solana_keypair::Keypair;
+    /// # use solana_signer::Signer;
+    /// # let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// # let vote_keypair = Keypair::new();
+    /// let vote_pubkey = vote_keypair.pubkey();
+    /// let epoch_stakes = rpc_client.get_vote_account_epoch_stakes(&vote_pubkey)?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn get_vote_account_epoch_stakes(
+        &self,
+        vote_pubkey: &Pubkey,
+    ) -> RpcResult<Vec<RpcVoteAccountEpochStake>> {
+        self.invoke((self.rpc_client.as_ref()).get_vote_account_epoch_stakes(vote_pubkey))
+    }
+
+    /// Returns the bank hashes of up to `limit` finalized slots starting at
+    /// `start_slot`.
+    ///
+    /// The `SlotHashes` sysvar only holds the hashes of the most recent 512
+    /// slots. This method also reaches the hashes of older slots, for as long
+    /// as the RPC node retains them in its ledger. Slots whose hash the node
+    /// does not know, such as slots before the snapshot it started from, are
+    /// skipped.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the limit is greater than 10,000 slots.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `getSlotHashes` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::client_error::Error;
+    /// # use solana_rpc_client::rpc_client::RpcClient;
+    /// # let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// let slot_hashes = rpc_client.get_slot_hashes(0, 10)?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn get_slot_hashes(
+        &self,
+        start_slot: Slot,
+        limit: usize,
+    ) -> ClientResult<Vec<RpcSlotHash>> {
+        self.invoke((self.rpc_client.as_ref()).get_slot_hashes(start_slot, limit))
+    }
+
+    /// Returns up to `limit` proofs of vote accounts voting for two different
+    /// bank hashes of the same slot, starting at `start_slot`.
+    ///
+    /// The RPC node records these when it sees conflicting votes in gossip or
+    /// in replayed blocks, and keeps them for as long as the slots remain in
+    /// its ledger.
+    ///
+    /// # Errors
+    ///
+    /// This method returns an error if the limit is greater than 1,000 proofs.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `getDuplicateVotes` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::client_error::Error;
+    /// # use solana_rpc_client::rpc_client::RpcClient;
+    /// # let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// let duplicate_votes = rpc_client.get_duplicate_votes(0, 10)?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn get_duplicate_votes(
+        &self,
+        start_slot: Slot,
+        limit: usize,
+    ) -> ClientResult<Vec<RpcDuplicateVote>> {
+        self.invoke((self.rpc_client.as_ref()).get_duplicate_votes(start_slot, limit))
+    }
+
     pub fn wait_for_max_stake(
         &self,
         commitment: CommitmentConfig,
@@ -3315,6 +3460,7 @@ impl RpcClient {
     ///     },
     ///     with_context: Some(false),
     ///     sort_results: Some(true),
+    ///     pagination: None,
     /// };
     /// let accounts = rpc_client.get_program_accounts_with_config(
     ///     &alice.pubkey(),
@@ -3330,6 +3476,65 @@ impl RpcClient {
         self.invoke((self.rpc_client.as_ref()).get_program_accounts_with_config(pubkey, config))
     }
 
+    /// Returns the program deploy buffers whose authority is the provided
+    /// pubkey, in increasing order of buffer address.
+    ///
+    /// Buffers left behind by failed or abandoned deployments keep their
+    /// lamports until they are closed by their authority.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `getBufferAccounts` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::client_error::Error;
+    /// # use solana_rpc_client::rpc_client::RpcClient;
+    /// # use solana_keypair::Keypair;
+    /// # use solana_signer::Signer;
+    /// # let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// # let authority = Keypair::new();
+    /// let buffers = rpc_client.get_buffer_accounts(&authority.pubkey())?;
+    /// let stranded_lamports: u64 = buffers.iter().map(|buffer| buffer.lamports).sum();
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn get_buffer_accounts(&self, authority: &Pubkey) -> ClientResult<Vec<RpcBufferAccount>> {
+        self.invoke((self.rpc_client.as_ref()).get_buffer_accounts(authority))
+    }
+
+    /// Returns the program deploy buffers whose authority is the provided
+    /// pubkey, in increasing order of buffer address.
+    ///
+    /// # RPC Reference
+    ///
+    /// This method corresponds directly to the `getBufferAccounts` RPC method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use solana_rpc_client_api::{client_error::Error, config::RpcContextConfig};
+    /// # use solana_rpc_client::rpc_client::RpcClient;
+    /// # use solana_commitment_config::CommitmentConfig;
+    /// # use solana_keypair::Keypair;
+    /// # use solana_signer::Signer;
+    /// # let rpc_client = RpcClient::new_mock("succeeds".to_string());
+    /// # let authority = Keypair::new();
+    /// let config = RpcContextConfig {
+    ///     commitment: Some(CommitmentConfig::finalized()),
+    ///     min_context_slot: None,
+    /// };
+    /// let buffers = rpc_client.get_buffer_accounts_with_config(&authority.pubkey(), config)?;
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn get_buffer_accounts_with_config(
+        &self,
+        authority: &Pubkey,
+        config: RpcContextConfig,
+    ) -> RpcResult<Vec<RpcBufferAccount>> {
+        self.invoke((self.rpc_client.as_ref()).get_buffer_accounts_with_config(authority, config))
+    }
+
     /// Returns the stake minimum delegation, in lamports.
     ///
     /// # RPC Reference
@@ -3711,7 +3916,10 @@ mod tests {
         solana_account_decoder_client_types::UiAccountEncoding,
         solana_instruction::error::InstructionError,
         solana_keypair::Keypair,
-        solana_rpc_client_api::client_error::ErrorKind,
+        solana_rpc_client_api::{
+            client_error::ErrorKind,
+            typed_request::{GetBalance, GetSlot},
+        },
         solana_signer::Signer,
         solana_system_transaction as system_transaction,
         solana_transaction_error::TransactionError,
@@ -3787,6 +3995,21 @@ mod tests {
         assert!(blockhash.is_err());
     }
 
+    #[test]
+    fn test_send_typed() {
+        let rpc_client = RpcClient::new_mock("succeeds".to_string());
+        let balance = rpc_client
+            .send_typed(GetBalance::new(Pubkey::new_unique()).min_context_slot(1))
+            .unwrap();
+        assert_eq!(balance.context.slot, 1);
+        assert_eq!(balance.value, 50);
+
+        let slot = rpc_client
+            .send_typed(GetSlot::new().commitment(CommitmentConfig::finalized()))
+            .unwrap();
+        assert_eq!(slot, 0);
+    }
+
     #[test]
     fn test_send_transaction() {
         let rpc_client = RpcClient::new_mock("succeeds".to_string());
@@ -3889,6 +4112,7 @@ mod tests {
             identity: Some(Keypair::new().pubkey().to_string()),
             range: None,
             commitment: None,
+            skipped_slot_reasons: None,
         };
 
         let prod = rpc_client.get_block_production_with_config(config)?.value;
@@ -3972,6 +4196,7 @@ mod tests {
                         },
                         with_context: None,
                         sort_results: None,
+                        pagination: None,
                     },
                 )
                 .unwrap();
@@ -4007,6 +4232,7 @@ mod tests {
                         },
                         with_context: Some(true),
                         sort_results: None,
+                        pagination: None,
                     },
                 )
                 .unwrap();
@@ -1,8 +1,10 @@
 //! The `BlockMetaService` is responsible for persisting block metadata from
-//! banks into the `Blockstore`
+//! banks into the `Blockstore`, and for caching the stake rewards calculated
+//! at epoch boundaries
 
 pub use solana_ledger::blockstore_processor::BlockMetaSender;
 use {
+    crate::epoch_stake_rewards_cache::EpochStakeRewardsCache,
     crossbeam_channel::{Receiver, RecvTimeoutError},
     solana_ledger::blockstore::{Blockstore, BlockstoreError},
     solana_runtime::bank::{Bank, KeyedRewardsAndNumPartitions},
@@ -28,6 +30,7 @@ impl BlockMetaService {
         block_meta_receiver: BlockMetaReceiver,
         blockstore: Arc<Blockstore>,
         max_complete_rewards_slot: Arc<AtomicU64>,
+        epoch_stake_rewards_cache: Arc<EpochStakeRewardsCache>,
         exit: Arc<AtomicBool>,
     ) -> Self {
         let thread_hdl = Builder::new()
@@ -48,9 +51,12 @@ impl BlockMetaService {
                         }
                     };
 
-                    if let Err(err) =
-                        Self::write_block_meta(&bank, &blockstore, &max_complete_rewards_slot)
-                    {
+                    if let Err(err) = Self::write_block_meta(
+                        &bank,
+                        &blockstore,
+                        &max_complete_rewards_slot,
+                        &epoch_stake_rewards_cache,
+                    ) {
                         error!("BlockMetaService is stopping because: {err}");
                         // Set the exit flag to allow other services to gracefully stop
                         exit.store(true, Ordering::Relaxed);
@@ -67,6 +73,7 @@ impl BlockMetaService {
         bank: &Bank,
         blockstore: &Blockstore,
         max_complete_rewards_slot: &Arc<AtomicU64>,
+        epoch_stake_rewards_cache: &EpochStakeRewardsCache,
     ) -> Result<(), BlockstoreError> {
         let slot = bank.slot();
 
@@ -74,6 +81,13 @@ impl BlockMetaService {
         blockstore.set_block_height(slot, bank.block_height())?;
 
         let rewards = bank.get_rewards_and_num_partitions();
+        // Stake rewards are only calculated in the first block of the epoch,
+        // the only one with num_partitions
+        if rewards.num_partitions.is_some() {
+            if let Some(epoch_stake_rewards) = bank.epoch_stake_rewards() {
+                epoch_stake_rewards_cache.insert(slot, epoch_stake_rewards);
+            }
+        }
         if rewards.should_record() {
             let KeyedRewardsAndNumPartitions {
                 keyed_rewards,
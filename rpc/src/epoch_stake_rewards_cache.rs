@@ -0,0 +1,160 @@
+//! The `epoch_stake_rewards_cache` module keeps the stake rewards of the most
+//! recent epochs, so that `getEpochStakeRewards` can serve them after they
+//! have been distributed. Rewards are cached from the first block of each
+//! epoch when its bank is frozen, keyed by the slot of that block, as several
+//! forks may calculate them. Only the rewards calculated in a rooted block are
+//! served.
+
+use {
+    solana_runtime::bank::{EpochStakeReward, EpochStakeRewards},
+    solana_sdk::{
+        clock::{Epoch, Slot},
+        pubkey::Pubkey,
+    },
+    std::{
+        collections::BTreeMap,
+        sync::{Arc, RwLock},
+    },
+};
+
+/// Number of epochs whose rewards are kept
+const MAX_CACHED_EPOCHS: usize = 2;
+/// Number of forks whose rewards are kept for each epoch
+const MAX_CACHED_FORKS_PER_EPOCH: usize = 4;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct CachedEpochStakeRewards {
+    /// Slot of the block the rewards were calculated in
+    pub slot: Slot,
+    pub rewards: EpochStakeRewards,
+}
+
+impl CachedEpochStakeRewards {
+    /// Up to `limit` rewards of the stake accounts following `after`, in
+    /// increasing order of stake account
+    pub fn page(&self, after: Option<&Pubkey>, limit: usize) -> &[EpochStakeReward] {
+        let rewards = &self.rewards.rewards;
+        let start = after
+            .map(|after| rewards.partition_point(|reward| reward.stake_pubkey <= *after))
+            .unwrap_or_default();
+        let end = start.saturating_add(limit).min(rewards.len());
+        &rewards[start..end]
+    }
+}
+
+#[derive(Default)]
+pub struct EpochStakeRewardsCache {
+    epochs: RwLock<BTreeMap<Epoch, BTreeMap<Slot, Arc<CachedEpochStakeRewards>>>>,
+}
+
+impl EpochStakeRewardsCache {
+    /// Caches the rewards calculated in the block at `slot`, evicting the
+    /// oldest epoch, and the lowest slots of an epoch, when full
+    pub fn insert(&self, slot: Slot, rewards: EpochStakeRewards) {
+        let mut epochs = self.epochs.write().unwrap();
+        let forks = epochs.entry(rewards.rewarded_epoch).or_default();
+        forks.insert(slot, Arc::new(CachedEpochStakeRewards { slot, rewards }));
+        while forks.len() > MAX_CACHED_FORKS_PER_EPOCH {
+            forks.pop_first();
+        }
+        while epochs.len() > MAX_CACHED_EPOCHS {
+            epochs.pop_first();
+        }
+    }
+
+    /// Rewards of `rewarded_epoch`, or of the latest epoch with rewards
+    /// calculated in a rooted block if None. Only the rewards calculated in a
+    /// block for which `is_root` holds are returned.
+    pub fn get(
+        &self,
+        rewarded_epoch: Option<Epoch>,
+        is_root: impl Fn(Slot) -> bool,
+    ) -> Option<Arc<CachedEpochStakeRewards>> {
+        let epochs = self.epochs.read().unwrap();
+        let rooted = |forks: &BTreeMap<Slot, Arc<CachedEpochStakeRewards>>| {
+            forks
+                .iter()
+                .find(|(slot, _)| is_root(**slot))
+                .map(|(_, cached)| cached.clone())
+        };
+        match rewarded_epoch {
+            Some(epoch) => epochs.get(&epoch).and_then(rooted),
+            None => epochs.values().rev().find_map(rooted),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_rewards(rewarded_epoch: Epoch, num_rewards: usize) -> EpochStakeRewards {
+        let mut rewards: Vec<_> = (0..num_rewards)
+            .map(|_| EpochStakeReward {
+                stake_pubkey: Pubkey::new_unique(),
+                vote_pubkey: Pubkey::new_unique(),
+                lamports: 42,
+                commission: Some(10),
+                effective_stake: 1_000,
+            })
+            .collect();
+        rewards.sort_unstable_by_key(|reward| reward.stake_pubkey);
+        EpochStakeRewards {
+            rewarded_epoch,
+            rewards,
+        }
+    }
+
+    #[test]
+    fn test_insert_and_get() {
+        let cache = EpochStakeRewardsCache::default();
+        let all_rooted = |_| true;
+        assert_eq!(cache.get(None, all_rooted), None);
+
+        cache.insert(32, new_rewards(0, 1));
+        cache.insert(64, new_rewards(1, 1));
+        assert_eq!(cache.get(None, all_rooted).unwrap().slot, 64);
+        assert_eq!(cache.get(Some(0), all_rooted).unwrap().slot, 32);
+
+        // Rewards calculated on another fork are kept apart, and only those
+        // calculated in a rooted block are returned
+        cache.insert(65, new_rewards(1, 1));
+        assert_eq!(cache.get(Some(1), |slot| slot == 65).unwrap().slot, 65);
+        assert_eq!(cache.get(Some(1), |slot| slot != 65).unwrap().slot, 64);
+        assert_eq!(cache.get(None, |slot| slot == 32).unwrap().slot, 32);
+        assert_eq!(cache.get(Some(1), |slot| slot == 32), None);
+
+        // The lowest slots of an epoch are evicted
+        for slot in 66..=68 {
+            cache.insert(slot, new_rewards(1, 1));
+        }
+        assert_eq!(cache.get(Some(1), |slot| slot == 64), None);
+        assert_eq!(cache.get(Some(1), |slot| slot == 65).unwrap().slot, 65);
+
+        // The oldest epoch is evicted
+        cache.insert(96, new_rewards(2, 1));
+        assert_eq!(cache.get(Some(0), all_rooted), None);
+        assert_eq!(cache.get(Some(1), |slot| slot == 65).unwrap().slot, 65);
+        assert_eq!(cache.get(None, all_rooted).unwrap().slot, 96);
+    }
+
+    #[test]
+    fn test_page() {
+        let cache = EpochStakeRewardsCache::default();
+        cache.insert(32, new_rewards(0, 5));
+        let cached = cache.get(Some(0), |_| true).unwrap();
+        let rewards = &cached.rewards.rewards;
+
+        assert_eq!(cached.page(None, 2), &rewards[..2]);
+        assert_eq!(
+            cached.page(Some(&rewards[1].stake_pubkey), 2),
+            &rewards[2..4]
+        );
+        assert_eq!(
+            cached.page(Some(&rewards[3].stake_pubkey), 2),
+            &rewards[4..]
+        );
+        assert!(cached.page(Some(&rewards[4].stake_pubkey), 2).is_empty());
+        assert_eq!(cached.page(Some(&Pubkey::default()), 10), &rewards[..]);
+    }
+}
@@ -1,6 +1,7 @@
 #![allow(clippy::arithmetic_side_effects)]
 pub mod block_meta_service;
 mod cluster_tpu_info;
+pub mod epoch_stake_rewards_cache;
 pub mod filter;
 pub mod max_slots;
 pub mod optimistically_confirmed_bank_tracker;
@@ -14,9 +15,12 @@ pub mod rpc_pubsub_service;
 pub mod rpc_service;
 pub mod rpc_subscription_tracker;
 pub mod rpc_subscriptions;
+pub mod sanitize_detailed;
+pub mod slot_status_events;
 pub mod slot_status_notifier;
 pub mod transaction_notifier_interface;
 pub mod transaction_status_service;
+pub mod vote_latency_tracker;
 
 #[macro_use]
 extern crate log;
@@ -3,15 +3,24 @@
 use solana_runtime::installed_scheduler_pool::BankWithScheduler;
 use {
     crate::{
-        filter::filter_allows, max_slots::MaxSlots,
+        epoch_stake_rewards_cache::EpochStakeRewardsCache,
+        filter::filter_allows,
+        max_slots::MaxSlots,
         optimistically_confirmed_bank_tracker::OptimisticallyConfirmedBank,
-        parsed_token_accounts::*, rpc_cache::LargestAccountsCache, rpc_health::*,
+        parsed_token_accounts::*,
+        rpc_cache::{LargestAccountsCache, PreflightCache, PreflightCacheKey, PreflightFailure},
+        rpc_health::*,
+        sanitize_detailed::SanitizeDetailed,
+        vote_latency_tracker::VoteLatencyTracker,
     },
     base64::{prelude::BASE64_STANDARD, Engine},
     bincode::{config::Options, serialize},
     crossbeam_channel::{unbounded, Receiver, Sender},
     jsonrpc_core::{
-        futures::future::{self, FutureExt, OptionFuture},
+        futures::{
+            future::{self, FutureExt, OptionFuture},
+            stream::{self, Stream, StreamExt},
+        },
         types::error,
         BoxFuture, Error, Metadata, Result,
     },
@@ -51,9 +60,12 @@ use {
         request::{
             TokenAccountsFilter, DELINQUENT_VALIDATOR_SLOT_DISTANCE,
             MAX_GET_CONFIRMED_BLOCKS_RANGE, MAX_GET_CONFIRMED_SIGNATURES_FOR_ADDRESS2_LIMIT,
-            MAX_GET_PROGRAM_ACCOUNT_FILTERS, MAX_GET_SIGNATURE_STATUSES_QUERY_ITEMS,
-            MAX_GET_SLOT_LEADERS, MAX_MULTIPLE_ACCOUNTS,
-            MAX_RPC_VOTE_ACCOUNT_INFO_EPOCH_CREDITS_HISTORY, NUM_LARGEST_ACCOUNTS,
+            MAX_GET_DUPLICATE_VOTES_LIMIT, MAX_GET_EPOCH_STAKE_REWARDS_PAGE_LIMIT,
+            MAX_GET_PROGRAM_ACCOUNTS_PAGE_LIMIT, MAX_GET_PROGRAM_ACCOUNT_FILTERS,
+            MAX_GET_SIGNATURE_STATUSES_QUERY_ITEMS, MAX_GET_SLOT_HASHES_LIMIT,
+            MAX_GET_SLOT_HASHES_SLOT_RANGE, MAX_GET_SLOT_LEADERS, MAX_MULTIPLE_ACCOUNTS,
+            MAX_MULTIPLE_ACCOUNTS_STREAM, MAX_RPC_VOTE_ACCOUNT_INFO_EPOCH_CREDITS_HISTORY,
+            NUM_LARGEST_ACCOUNTS,
         },
         response::{Response as RpcResponse, *},
     },
@@ -61,7 +73,7 @@ use {
         bank::{Bank, TransactionSimulationResult},
         bank_forks::BankForks,
         commitment::{BlockCommitmentArray, BlockCommitmentCache},
-        non_circulating_supply::{calculate_non_circulating_supply, NonCirculatingSupply},
+        non_circulating_supply::{NonCirculatingAccounts, Supply},
         prioritization_fee_cache::PrioritizationFeeCache,
         snapshot_config::SnapshotConfig,
         snapshot_utils,
@@ -70,7 +82,8 @@ use {
     solana_runtime_transaction::runtime_transaction::RuntimeTransaction,
     solana_sdk::{
         account::{AccountSharedData, ReadableAccount},
-        clock::{Slot, UnixTimestamp, MAX_PROCESSING_AGE},
+        bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+        clock::{Epoch, Slot, UnixTimestamp, MAX_PROCESSING_AGE},
         commitment_config::{CommitmentConfig, CommitmentLevel},
         epoch_info::EpochInfo,
         epoch_rewards_hasher::EpochRewardsHasher,
@@ -79,7 +92,9 @@ use {
         hash::Hash,
         message::SanitizedMessage,
         pubkey::{Pubkey, PUBKEY_BYTES},
+        sanitize::Sanitize,
         signature::{Keypair, Signature, Signer},
+        slot_history::{Check, SlotHistory},
         system_instruction,
         transaction::{
             self, AddressLoader, MessageHash, SanitizedTransaction, TransactionError,
@@ -87,9 +102,13 @@ use {
         },
         transaction_context::TransactionAccount,
     },
-    solana_send_transaction_service::send_transaction_service::TransactionInfo,
+    solana_send_transaction_service::{
+        inflight_limiter::{FeePayerInflightLimiter, InflightPermit},
+        send_transaction_service::TransactionInfo,
+    },
     solana_stake_program,
     solana_storage_bigtable::Error as StorageError,
+    solana_svm::transaction_processor::ExecutionRecordingConfig,
     solana_transaction_status::{
         map_inner_instructions, BlockEncodingOptions, ConfirmedBlock,
         ConfirmedTransactionStatusWithSignature, ConfirmedTransactionWithStatusMeta,
@@ -138,6 +157,8 @@ pub mod account_resolver;
 type RpcCustomResult<T> = std::result::Result<T, RpcCustomError>;
 
 pub const MAX_REQUEST_BODY_SIZE: usize = 50 * (1 << 10); // 50kB
+pub const DEFAULT_PREFLIGHT_CACHE_TTL_MS: u64 = 1_000;
+pub const DEFAULT_PREFLIGHT_CACHE_CAPACITY: usize = 10_000;
 pub const PERFORMANCE_SAMPLES_LIMIT: usize = 720;
 
 fn new_response<T>(bank: &Bank, value: T) -> RpcResponse<T> {
@@ -157,6 +178,70 @@ fn is_finalized(
         && (blockstore.is_root(slot) || bank.status_cache_ancestors().contains(&slot))
 }
 
+/// Classifies why each of `skipped_slots`, which are not on the rooted fork, was skipped
+fn get_skipped_slot_reasons(
+    blockstore: &Blockstore,
+    slot_history: &SlotHistory,
+    mut skipped_slots: Vec<(Slot, Pubkey)>,
+) -> HashMap<String, Vec<RpcSkippedSlot>> {
+    let lowest_slot = blockstore.lowest_slot();
+    let mut next_block = None;
+    let mut skipped_slot_reasons: HashMap<_, Vec<_>> = HashMap::new();
+    skipped_slots.sort_unstable();
+    for (slot, identity) in skipped_slots {
+        // The next block of the rooted fork
+        if next_block.is_none_or(|next_block| next_block <= slot) {
+            next_block = (slot.saturating_add(1)..=slot_history.newest())
+                .find(|slot| slot_history.check(*slot) == Check::Found);
+        }
+        skipped_slot_reasons
+            .entry(identity.to_string())
+            .or_default()
+            .push(RpcSkippedSlot {
+                slot,
+                reason: get_skipped_slot_reason(blockstore, slot, lowest_slot, next_block),
+            });
+    }
+    skipped_slot_reasons
+}
+
+fn get_skipped_slot_reason(
+    blockstore: &Blockstore,
+    slot: Slot,
+    lowest_slot: Slot,
+    next_block: Option<Slot>,
+) -> RpcSkippedSlotReason {
+    if slot < lowest_slot {
+        return RpcSkippedSlotReason::Unknown;
+    }
+    let received_meta = |slot| match blockstore.meta(slot) {
+        Ok(meta) => Ok(meta.filter(|meta| meta.received > 0)),
+        Err(err) => {
+            warn!("failed to get slot meta of {slot}: {err:?}");
+            Err(())
+        }
+    };
+    let meta = match received_meta(slot) {
+        Ok(Some(meta)) => meta,
+        Ok(None) => return RpcSkippedSlotReason::Offline,
+        Err(()) => return RpcSkippedSlotReason::Unknown,
+    };
+    if blockstore.is_dead(slot) {
+        return RpcSkippedSlotReason::Dead;
+    }
+    let next_block_first_shred_timestamp = next_block
+        .and_then(|next_block| received_meta(next_block).ok().flatten())
+        .map(|next_block_meta| next_block_meta.first_shred_timestamp);
+    if !meta.is_full()
+        || next_block_first_shred_timestamp
+            .is_some_and(|timestamp| meta.first_shred_timestamp > timestamp)
+    {
+        RpcSkippedSlotReason::Late
+    } else {
+        RpcSkippedSlotReason::ForkedOff
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct JsonRpcConfig {
     pub enable_rpc_transaction_history: bool,
@@ -175,6 +260,16 @@ pub struct JsonRpcConfig {
     pub max_request_body_size: Option<usize>,
     /// Disable the health check, used for tests and TestValidator
     pub disable_health_check: bool,
+    /// How long `sendTransaction` preflight results are reused for identical transactions.
+    /// Zero disables the preflight cache.
+    pub preflight_cache_ttl_ms: u64,
+    /// The maximum number of cached `sendTransaction` preflight results
+    pub preflight_cache_capacity: usize,
+    /// Accounts reported as non-circulating supply
+    pub non_circulating_accounts: NonCirculatingAccounts,
+    /// The maximum number of `sendTransaction` transactions of a single fee payer that may be
+    /// pending in the send transaction service. None means no limit.
+    pub max_inflight_transactions_per_fee_payer: Option<usize>,
 }
 
 impl Default for JsonRpcConfig {
@@ -195,6 +290,10 @@ impl Default for JsonRpcConfig {
             rpc_scan_and_fix_roots: Default::default(),
             max_request_body_size: Option::default(),
             disable_health_check: Default::default(),
+            preflight_cache_ttl_ms: Default::default(),
+            preflight_cache_capacity: Default::default(),
+            non_circulating_accounts: NonCirculatingAccounts::default(),
+            max_inflight_transactions_per_fee_payer: Option::default(),
         }
     }
 }
@@ -249,10 +348,14 @@ pub struct JsonRpcRequestProcessor {
     optimistically_confirmed_bank: Arc<RwLock<OptimisticallyConfirmedBank>>,
     largest_accounts_cache: Arc<RwLock<LargestAccountsCache>>,
     max_slots: Arc<MaxSlots>,
+    vote_latency_tracker: Arc<VoteLatencyTracker>,
     leader_schedule_cache: Arc<LeaderScheduleCache>,
     max_complete_transaction_status_slot: Arc<AtomicU64>,
     max_complete_rewards_slot: Arc<AtomicU64>,
+    epoch_stake_rewards_cache: Arc<EpochStakeRewardsCache>,
     prioritization_fee_cache: Arc<PrioritizationFeeCache>,
+    preflight_cache: Arc<PreflightCache>,
+    fee_payer_inflight_limiter: Option<Arc<FeePayerInflightLimiter>>,
     runtime: Arc<Runtime>,
 }
 impl Metadata for JsonRpcRequestProcessor {}
@@ -273,24 +376,15 @@ impl JsonRpcRequestProcessor {
             min_context_slot,
         } = config;
         let bank = self.bank(commitment);
-        if let Some(min_context_slot) = min_context_slot {
-            if bank.slot() < min_context_slot {
-                return Err(RpcCustomError::MinContextSlotNotReached {
-                    context_slot: bank.slot(),
-                }
-                .into());
-            }
-        }
+        verify_context_slot(&bank, min_context_slot)?;
         Ok(bank)
     }
 
-    async fn calculate_non_circulating_supply(
-        &self,
-        bank: &Arc<Bank>,
-    ) -> ScanResult<NonCirculatingSupply> {
+    async fn calculate_supply(&self, bank: &Arc<Bank>) -> ScanResult<Arc<Supply>> {
         let bank = Arc::clone(bank);
+        let non_circulating_accounts = self.config.non_circulating_accounts.clone();
         self.runtime
-            .spawn_blocking(move || calculate_non_circulating_supply(&bank))
+            .spawn_blocking(move || bank.supply(&non_circulating_accounts))
             .await
             .expect("Failed to spawn blocking task")
     }
@@ -387,6 +481,26 @@ impl JsonRpcRequestProcessor {
         })
     }
 
+    pub(crate) fn fee_payer_inflight_limiter(&self) -> Option<&Arc<FeePayerInflightLimiter>> {
+        self.fee_payer_inflight_limiter.as_ref()
+    }
+
+    /// Counts a transaction of `fee_payer` as pending in the send transaction service, failing
+    /// if the fee payer already has the maximum number of transactions pending
+    fn acquire_inflight_permit(&self, fee_payer: &Pubkey) -> Result<Option<InflightPermit>> {
+        let Some(limiter) = &self.fee_payer_inflight_limiter else {
+            return Ok(None);
+        };
+        limiter.try_acquire(*fee_payer).map(Some).ok_or_else(|| {
+            inc_new_counter_info!("rpc-send-tx_fee-payer-inflight-limit", 1);
+            RpcCustomError::FeePayerInflightLimitExceeded {
+                fee_payer: *fee_payer,
+                max_inflight: limiter.max_inflight_per_fee_payer(),
+            }
+            .into()
+        })
+    }
+
     fn genesis_creation_time(&self) -> UnixTimestamp {
         self.bank(None).genesis_creation_time()
     }
@@ -406,13 +520,22 @@ impl JsonRpcRequestProcessor {
         optimistically_confirmed_bank: Arc<RwLock<OptimisticallyConfirmedBank>>,
         largest_accounts_cache: Arc<RwLock<LargestAccountsCache>>,
         max_slots: Arc<MaxSlots>,
+        vote_latency_tracker: Arc<VoteLatencyTracker>,
         leader_schedule_cache: Arc<LeaderScheduleCache>,
         max_complete_transaction_status_slot: Arc<AtomicU64>,
         max_complete_rewards_slot: Arc<AtomicU64>,
+        epoch_stake_rewards_cache: Arc<EpochStakeRewardsCache>,
         prioritization_fee_cache: Arc<PrioritizationFeeCache>,
         runtime: Arc<Runtime>,
     ) -> (Self, Receiver<TransactionInfo>) {
         let (transaction_sender, transaction_receiver) = unbounded();
+        let preflight_cache = Arc::new(PreflightCache::new(
+            Duration::from_millis(config.preflight_cache_ttl_ms),
+            config.preflight_cache_capacity,
+        ));
+        let fee_payer_inflight_limiter = config
+            .max_inflight_transactions_per_fee_payer
+            .map(|max_inflight| Arc::new(FeePayerInflightLimiter::new(max_inflight)));
         (
             Self {
                 config,
@@ -429,10 +552,14 @@ impl JsonRpcRequestProcessor {
                 optimistically_confirmed_bank,
                 largest_accounts_cache,
                 max_slots,
+                vote_latency_tracker,
                 leader_schedule_cache,
                 max_complete_transaction_status_slot,
                 max_complete_rewards_slot,
+                epoch_stake_rewards_cache,
                 prioritization_fee_cache,
+                preflight_cache,
+                fee_payer_inflight_limiter,
                 runtime,
             },
             transaction_receiver,
@@ -513,10 +640,14 @@ impl JsonRpcRequestProcessor {
             optimistically_confirmed_bank,
             largest_accounts_cache: Arc::new(RwLock::new(LargestAccountsCache::new(30))),
             max_slots: Arc::new(MaxSlots::default()),
+            vote_latency_tracker: Arc::new(VoteLatencyTracker::default()),
             leader_schedule_cache,
             max_complete_transaction_status_slot: Arc::new(AtomicU64::default()),
             max_complete_rewards_slot: Arc::new(AtomicU64::default()),
+            epoch_stake_rewards_cache: Arc::new(EpochStakeRewardsCache::default()),
             prioritization_fee_cache: Arc::new(PrioritizationFeeCache::default()),
+            preflight_cache: Arc::new(PreflightCache::new(Duration::ZERO, 0)),
+            fee_payer_inflight_limiter: None,
             runtime: service_runtime(rpc_threads, rpc_blocking_threads, rpc_niceness_adj),
         }
     }
@@ -581,6 +712,79 @@ impl JsonRpcRequestProcessor {
         Ok(new_response(&bank, accounts))
     }
 
+    /// Validates a streaming multiple accounts request, returning the pubkeys, account config and
+    /// chunk size to pass to `get_multiple_accounts_stream()`
+    pub fn verify_multiple_accounts_stream_request(
+        &self,
+        request: RpcMultipleAccountsStreamRequest,
+    ) -> Result<(Vec<Pubkey>, RpcAccountInfoConfig, usize)> {
+        let RpcMultipleAccountsStreamRequest {
+            pubkeys,
+            config,
+            chunk_size,
+        } = request;
+        if pubkeys.len() > MAX_MULTIPLE_ACCOUNTS_STREAM {
+            return Err(Error::invalid_params(format!(
+                "Too many inputs provided; max {MAX_MULTIPLE_ACCOUNTS_STREAM}"
+            )));
+        }
+        let max_multiple_accounts = self
+            .config
+            .max_multiple_accounts
+            .unwrap_or(MAX_MULTIPLE_ACCOUNTS);
+        let chunk_size = chunk_size.unwrap_or(max_multiple_accounts);
+        if chunk_size == 0 || chunk_size > max_multiple_accounts {
+            return Err(Error::invalid_params(format!(
+                "Invalid chunk size; must be between 1 and {max_multiple_accounts}"
+            )));
+        }
+        let pubkeys = pubkeys
+            .iter()
+            .map(|pubkey_str| verify_pubkey(pubkey_str))
+            .collect::<Result<Vec<_>>>()?;
+        Ok((pubkeys, config, chunk_size))
+    }
+
+    /// Fetches `pubkeys` in chunks of `chunk_size` accounts, each chunk from the bank selected by
+    /// `config` at the time it is read. A chunk that fails is reported in place and does not end
+    /// the stream.
+    pub fn get_multiple_accounts_stream(
+        &self,
+        pubkeys: Vec<Pubkey>,
+        config: RpcAccountInfoConfig,
+        chunk_size: usize,
+    ) -> impl Stream<Item = RpcMultipleAccountsChunk> + Send + 'static {
+        let chunks = pubkeys
+            .chunks(chunk_size)
+            .enumerate()
+            .map(|(i, chunk)| (i.saturating_mul(chunk_size), chunk.to_vec()))
+            .collect::<Vec<_>>();
+        let meta = self.clone();
+        stream::iter(chunks).then(move |(offset, chunk)| {
+            let meta = meta.clone();
+            let config = config.clone();
+            async move {
+                match meta.get_multiple_accounts(chunk, Some(config)).await {
+                    Ok(response) => RpcMultipleAccountsChunk {
+                        offset,
+                        context: Some(response.context),
+                        value: Some(response.value),
+                        error: None,
+                    },
+                    Err(err) => RpcMultipleAccountsChunk {
+                        offset,
+                        context: None,
+                        value: None,
+                        error: Some(RpcMultipleAccountsChunkError {
+                            code: err.code.code(),
+                            message: err.message,
+                        }),
+                    },
+                }
+            }
+        })
+    }
+
     pub fn get_minimum_balance_for_rent_exemption(
         &self,
         data_len: usize,
@@ -597,6 +801,8 @@ impl JsonRpcRequestProcessor {
         mut filters: Vec<RpcFilterType>,
         with_context: bool,
         sort_results: bool,
+        // (after, limit) of the requested page, if any
+        page: Option<(Option<Pubkey>, usize)>,
     ) -> Result<OptionalContext<Vec<RpcKeyedAccount>>> {
         let RpcAccountInfoConfig {
             encoding,
@@ -610,6 +816,8 @@ impl JsonRpcRequestProcessor {
         })?;
         let encoding = encoding.unwrap_or(UiAccountEncoding::Binary);
         optimize_filters(&mut filters);
+        // Pages are always in increasing order of pubkey
+        let sort_results = sort_results || page.is_some();
         let keyed_accounts = {
             if let Some(owner) = get_spl_token_owner_filter(&program_id, &filters) {
                 self.get_filtered_spl_token_accounts_by_owner(
@@ -629,6 +837,20 @@ impl JsonRpcRequestProcessor {
                     sort_results,
                 )
                 .await?
+            } else if let Some((after, limit)) = page.filter(|_| {
+                !self
+                    .config
+                    .account_indexes
+                    .contains(&AccountIndex::ProgramId)
+            }) {
+                self.get_filtered_program_accounts_sorted_page(
+                    Arc::clone(&bank),
+                    program_id,
+                    filters,
+                    after,
+                    limit,
+                )
+                .await?
             } else {
                 self.get_filtered_program_accounts(
                     Arc::clone(&bank),
@@ -639,6 +861,15 @@ impl JsonRpcRequestProcessor {
                 .await?
             }
         };
+        // Secondary index scans return every match, cut the page out of them
+        let keyed_accounts = match page {
+            Some((after, limit)) => keyed_accounts
+                .into_iter()
+                .filter(|(pubkey, _)| after.is_none_or(|after| *pubkey > after))
+                .take(limit)
+                .collect(),
+            None => keyed_accounts,
+        };
         let accounts = if is_known_spl_token_id(&program_id)
             && encoding == UiAccountEncoding::JsonParsed
         {
@@ -660,6 +891,39 @@ impl JsonRpcRequestProcessor {
         })
     }
 
+    pub async fn get_buffer_accounts(
+        &self,
+        authority: Pubkey,
+        config: Option<RpcContextConfig>,
+    ) -> Result<RpcResponse<Vec<RpcBufferAccount>>> {
+        let bank = self.get_bank_with_config(config.unwrap_or_default())?;
+        // Buffers have a 4 byte state discriminant of 1, followed by an
+        // optional authority
+        let filters = vec![
+            RpcFilterType::Memcmp(Memcmp::new_raw_bytes(0, vec![1, 0, 0, 0])),
+            RpcFilterType::Memcmp(Memcmp::new_raw_bytes(4, vec![1])),
+            RpcFilterType::Memcmp(Memcmp::new_raw_bytes(5, authority.to_bytes().to_vec())),
+        ];
+        let buffer_metadata_len = UpgradeableLoaderState::size_of_buffer_metadata();
+        let buffers = self
+            .get_filtered_program_accounts(
+                Arc::clone(&bank),
+                bpf_loader_upgradeable::id(),
+                filters,
+                true,
+            )
+            .await?
+            .into_iter()
+            .map(|(pubkey, account)| RpcBufferAccount {
+                pubkey: pubkey.to_string(),
+                authority: authority.to_string(),
+                data_len: account.data().len().saturating_sub(buffer_metadata_len),
+                lamports: account.lamports(),
+            })
+            .collect();
+        Ok(new_response(&bank, buffers))
+    }
+
     fn filter_map_rewards<'a, F>(
         rewards: &'a Option<Rewards>,
         slot: Slot,
@@ -881,6 +1145,41 @@ impl JsonRpcRequestProcessor {
         Ok(rewards)
     }
 
+    /// Stake rewards of `epoch`, or of the latest epoch available, for up to
+    /// `limit` stake accounts following `after`
+    pub fn get_epoch_stake_rewards(
+        &self,
+        epoch: Option<Epoch>,
+        after: Option<Pubkey>,
+        limit: usize,
+    ) -> Result<RpcEpochStakeRewards> {
+        let cached = self
+            .epoch_stake_rewards_cache
+            .get(epoch, |slot| self.blockstore.is_root(slot))
+            .ok_or_else(|| {
+                Error::invalid_params(match epoch {
+                    Some(epoch) => format!("Stake rewards of epoch {epoch} are not available"),
+                    None => "No stake rewards are available".to_string(),
+                })
+            })?;
+        let rewards = cached
+            .page(after.as_ref(), limit)
+            .iter()
+            .map(|reward| RpcEpochStakeReward {
+                stake_account: reward.stake_pubkey.to_string(),
+                vote_account: reward.vote_pubkey.to_string(),
+                amount: reward.lamports,
+                commission: reward.commission,
+                effective_stake: reward.effective_stake,
+            })
+            .collect();
+        Ok(RpcEpochStakeRewards {
+            epoch: cached.rewards.rewarded_epoch,
+            effective_slot: cached.slot,
+            rewards,
+        })
+    }
+
     pub fn get_inflation_governor(
         &self,
         commitment: Option<CommitmentConfig>,
@@ -1000,6 +1299,21 @@ impl JsonRpcRequestProcessor {
         Ok(slot_leaders)
     }
 
+    /// Classifies why each of `skipped_slots`, which are not on the rooted fork, was skipped
+    async fn get_skipped_slot_reasons(
+        &self,
+        slot_history: SlotHistory,
+        skipped_slots: Vec<(Slot, Pubkey)>,
+    ) -> HashMap<String, Vec<RpcSkippedSlot>> {
+        let blockstore = Arc::clone(&self.blockstore);
+        self.runtime
+            .spawn_blocking(move || {
+                get_skipped_slot_reasons(&blockstore, &slot_history, skipped_slots)
+            })
+            .await
+            .expect("Failed to spawn blocking task")
+    }
+
     fn minimum_ledger_slot(&self) -> Result<Slot> {
         match self.blockstore.slot_meta_iterator(0) {
             Ok(mut metas) => match metas.next() {
@@ -1042,22 +1356,26 @@ impl JsonRpcRequestProcessor {
     ) -> RpcCustomResult<RpcResponse<Vec<RpcAccountBalance>>> {
         let config = config.unwrap_or_default();
         let bank = self.bank(config.commitment);
+        verify_context_slot(&bank, config.min_context_slot)?;
         let sort_results = config.sort_results.unwrap_or(true);
 
-        if let Some((slot, accounts)) = self.get_cached_largest_accounts(&config.filter) {
+        if let Some((slot, accounts)) = self
+            .get_cached_largest_accounts(&config.filter)
+            .filter(|(slot, _)| *slot >= config.min_context_slot.unwrap_or_default())
+        {
             Ok(RpcResponse {
                 context: RpcResponseContext::new(slot),
                 value: accounts,
             })
         } else {
             let (addresses, address_filter) = if let Some(filter) = config.clone().filter {
-                let non_circulating_supply = self
-                    .calculate_non_circulating_supply(&bank)
-                    .await
-                    .map_err(|e| RpcCustomError::ScanError {
-                        message: e.to_string(),
-                    })?;
-                let addresses = non_circulating_supply.accounts.into_iter().collect();
+                let supply =
+                    self.calculate_supply(&bank)
+                        .await
+                        .map_err(|e| RpcCustomError::ScanError {
+                            message: e.to_string(),
+                        })?;
+                let addresses = supply.non_circulating_accounts.iter().copied().collect();
                 let address_filter = match filter {
                     RpcLargestAccountsFilter::Circulating => AccountAddressFilter::Exclude,
                     RpcLargestAccountsFilter::NonCirculating => AccountAddressFilter::Include,
@@ -1102,31 +1420,16 @@ impl JsonRpcRequestProcessor {
     ) -> RpcCustomResult<RpcResponse<RpcSupply>> {
         let config = config.unwrap_or_default();
         let bank = self.bank(config.commitment);
-        let non_circulating_supply =
-            self.calculate_non_circulating_supply(&bank)
-                .await
-                .map_err(|e| RpcCustomError::ScanError {
-                    message: e.to_string(),
-                })?;
-        let total_supply = bank.capitalization();
-        let non_circulating_accounts = if config.exclude_non_circulating_accounts_list {
-            vec![]
-        } else {
-            non_circulating_supply
-                .accounts
-                .iter()
-                .map(|pubkey| pubkey.to_string())
-                .collect()
-        };
-
+        verify_context_slot(&bank, config.min_context_slot)?;
+        let supply = self
+            .calculate_supply(&bank)
+            .await
+            .map_err(|e| RpcCustomError::ScanError {
+                message: e.to_string(),
+            })?;
         Ok(new_response(
             &bank,
-            RpcSupply {
-                total: total_supply,
-                circulating: total_supply - non_circulating_supply.lamports,
-                non_circulating: non_circulating_supply.lamports,
-                non_circulating_accounts,
-            },
+            rpc_supply(&supply, config.exclude_non_circulating_accounts_list),
         ))
     }
 
@@ -1217,6 +1520,55 @@ impl JsonRpcRequestProcessor {
         })
     }
 
+    fn get_vote_latency(
+        &self,
+        config: Option<RpcVoteLatencyConfig>,
+    ) -> Result<RpcResponse<RpcVoteLatencyStatus>> {
+        let config = config.unwrap_or_default();
+        let vote_pubkey = config
+            .vote_pubkey
+            .as_deref()
+            .map(verify_pubkey)
+            .transpose()?;
+        let (slot, epoch, stats) = self.vote_latency_tracker.get_stats(vote_pubkey.as_ref());
+        let validators = stats
+            .into_iter()
+            .map(|(vote_pubkey, stats)| RpcVoteLatency {
+                vote_pubkey: vote_pubkey.to_string(),
+                node_pubkey: stats.node_pubkey.to_string(),
+                num_votes: stats.num_votes(),
+                mean_latency: stats.mean(),
+                median_latency: stats.percentile(50),
+                p90_latency: stats.percentile(90),
+                max_latency: stats.max(),
+                histogram: stats.histogram.to_vec(),
+            })
+            .collect();
+        Ok(RpcResponse {
+            context: RpcResponseContext::new(slot),
+            value: RpcVoteLatencyStatus { epoch, validators },
+        })
+    }
+
+    fn get_vote_account_epoch_stakes(
+        &self,
+        vote_pubkey: &Pubkey,
+        config: RpcContextConfig,
+    ) -> Result<RpcResponse<Vec<RpcVoteAccountEpochStake>>> {
+        let bank = self.get_bank_with_config(config)?;
+        let mut epoch_stakes: Vec<_> = bank
+            .epoch_stakes_map()
+            .iter()
+            .map(|(epoch, epoch_stakes)| RpcVoteAccountEpochStake {
+                epoch: *epoch,
+                activated_stake: epoch_stakes.vote_account_stake(vote_pubkey),
+                total_activated_stake: epoch_stakes.total_stake(),
+            })
+            .collect();
+        epoch_stakes.sort_unstable_by_key(|epoch_stake| epoch_stake.epoch);
+        Ok(new_response(&bank, epoch_stakes))
+    }
+
     fn check_blockstore_root<T>(
         &self,
         result: &std::result::Result<T, BlockstoreError>,
@@ -1580,6 +1932,112 @@ impl JsonRpcRequestProcessor {
         Ok(blocks)
     }
 
+    /// Returns the bank hashes of up to `limit` finalized slots from `start_slot` onwards, as
+    /// kept in the blockstore after they expire from the `SlotHashes` sysvar. At most
+    /// `MAX_GET_SLOT_HASHES_SLOT_RANGE` slots are scanned, so fewer than `limit` hashes may be
+    /// returned even though later slots have some.
+    pub async fn get_slot_hashes(
+        &self,
+        start_slot: Slot,
+        limit: usize,
+    ) -> Result<Vec<RpcSlotHash>> {
+        if limit > MAX_GET_SLOT_HASHES_LIMIT {
+            return Err(Error::invalid_params(format!(
+                "Limit too large; max {MAX_GET_SLOT_HASHES_LIMIT}"
+            )));
+        }
+
+        let highest_super_majority_root = self
+            .block_commitment_cache
+            .read()
+            .unwrap()
+            .highest_super_majority_root();
+        let end_slot = min(
+            start_slot.saturating_add(MAX_GET_SLOT_HASHES_SLOT_RANGE - 1),
+            highest_super_majority_root,
+        );
+        let blockstore = Arc::clone(&self.blockstore);
+        let slot_hashes = self
+            .runtime
+            .spawn_blocking(move || blockstore.get_rooted_slot_hashes(start_slot, end_slot, limit))
+            .await
+            .expect("Failed to spawn blocking task")
+            .map_err(|_| Error::internal_error())?;
+        Ok(slot_hashes
+            .into_iter()
+            .map(|(slot, hash)| RpcSlotHash {
+                slot,
+                hash: hash.to_string(),
+            })
+            .collect())
+    }
+
+    /// Returns the finalized slot at `block_height`, looked up in the blockstore's block height
+    /// index
+    pub fn get_slot_by_block_height(&self, block_height: u64) -> Result<Option<Slot>> {
+        let highest_super_majority_root = self
+            .block_commitment_cache
+            .read()
+            .unwrap()
+            .highest_super_majority_root();
+        Ok(self
+            .blockstore
+            .get_rooted_slot_by_block_height(block_height)
+            .map_err(|_| Error::internal_error())?
+            .filter(|slot| *slot <= highest_super_majority_root))
+    }
+
+    /// Returns the earliest finalized slot with a block time at or after `timestamp`, looked up
+    /// in the blockstore's block time index
+    pub fn get_slot_by_block_time(&self, timestamp: UnixTimestamp) -> Result<Option<Slot>> {
+        let highest_super_majority_root = self
+            .block_commitment_cache
+            .read()
+            .unwrap()
+            .highest_super_majority_root();
+        Ok(self
+            .blockstore
+            .get_first_rooted_slot_at_or_after_time(timestamp)
+            .map_err(|_| Error::internal_error())?
+            .filter(|slot| *slot <= highest_super_majority_root))
+    }
+
+    /// Returns up to `limit` proofs of vote accounts voting for two different bank hashes of the
+    /// same slot, for slots from `start_slot` onwards
+    pub fn get_duplicate_votes(
+        &self,
+        start_slot: Slot,
+        limit: usize,
+    ) -> Result<Vec<RpcDuplicateVote>> {
+        if limit > MAX_GET_DUPLICATE_VOTES_LIMIT {
+            return Err(Error::invalid_params(format!(
+                "Limit too large; max {MAX_GET_DUPLICATE_VOTES_LIMIT}"
+            )));
+        }
+
+        Ok(self
+            .blockstore
+            .duplicate_votes_iterator(start_slot)
+            .map_err(|_| Error::internal_error())?
+            .take(limit)
+            .map(|((slot, vote_pubkey), proof)| RpcDuplicateVote {
+                slot,
+                vote_pubkey: vote_pubkey.to_string(),
+                votes: [proof.vote1, proof.vote2]
+                    .into_iter()
+                    .map(|vote| RpcDuplicateVoteEvidence {
+                        hash: vote.hash.to_string(),
+                        signature: vote.signature.to_string(),
+                        transaction: vote
+                            .transaction
+                            .and_then(|tx| serialize(&tx).ok())
+                            .map(|wire_tx| BASE64_STANDARD.encode(wire_tx)),
+                    })
+                    .collect(),
+            })
+            .collect())
+    }
+
     pub async fn get_block_time(&self, slot: Slot) -> Result<Option<UnixTimestamp>> {
         if slot == 0 {
             return Ok(Some(self.genesis_creation_time()));
@@ -1969,9 +2427,9 @@ impl JsonRpcRequestProcessor {
     pub fn get_token_account_balance(
         &self,
         pubkey: &Pubkey,
-        commitment: Option<CommitmentConfig>,
+        config: Option<RpcContextConfig>,
     ) -> Result<RpcResponse<UiTokenAmount>> {
-        let bank = self.bank(commitment);
+        let bank = self.get_bank_with_config(config.unwrap_or_default())?;
         let account = bank.get_account(pubkey).ok_or_else(|| {
             Error::invalid_params("Invalid param: could not find account".to_string())
         })?;
@@ -1993,9 +2451,9 @@ impl JsonRpcRequestProcessor {
     pub fn get_token_supply(
         &self,
         mint: &Pubkey,
-        commitment: Option<CommitmentConfig>,
+        config: Option<RpcContextConfig>,
     ) -> Result<RpcResponse<UiTokenAmount>> {
-        let bank = self.bank(commitment);
+        let bank = self.get_bank_with_config(config.unwrap_or_default())?;
         let mint_account = bank.get_account(mint).ok_or_else(|| {
             Error::invalid_params("Invalid param: could not find account".to_string())
         })?;
@@ -2032,9 +2490,9 @@ impl JsonRpcRequestProcessor {
     pub async fn get_token_largest_accounts(
         &self,
         mint: Pubkey,
-        commitment: Option<CommitmentConfig>,
+        config: Option<RpcContextConfig>,
     ) -> Result<RpcResponse<Vec<RpcTokenAccountBalance>>> {
-        let bank = self.bank(commitment);
+        let bank = self.get_bank_with_config(config.unwrap_or_default())?;
         let (mint_owner, data) = get_mint_owner_and_additional_data(&bank, &mint)?;
         if !is_known_spl_token_id(&mint_owner) {
             return Err(Error::invalid_params(
@@ -2262,6 +2720,35 @@ impl JsonRpcRequestProcessor {
         }
     }
 
+    /// Get a page of program accounts, sorted by pubkey
+    async fn get_filtered_program_accounts_sorted_page(
+        &self,
+        bank: Arc<Bank>,
+        program_id: Pubkey,
+        filters: Vec<RpcFilterType>,
+        after: Option<Pubkey>,
+        limit: usize,
+    ) -> RpcCustomResult<Vec<(Pubkey, AccountSharedData)>> {
+        self.runtime
+            .spawn_blocking(move || {
+                bank.get_filtered_program_accounts_sorted_page(
+                    &program_id,
+                    |account: &AccountSharedData| {
+                        filters
+                            .iter()
+                            .all(|filter_type| filter_allows(filter_type, account))
+                    },
+                    after.as_ref(),
+                    limit,
+                )
+                .map_err(|e| RpcCustomError::ScanError {
+                    message: e.to_string(),
+                })
+            })
+            .await
+            .expect("Failed to spawn blocking task")
+    }
+
     /// Get an iterator of spl-token accounts by owner address
     async fn get_filtered_spl_token_accounts_by_owner(
         &self,
@@ -2438,6 +2925,18 @@ fn verify_transaction(
     Ok(())
 }
 
+/// Fails if `bank` is older than the `min_context_slot` requested by the client.
+fn verify_context_slot(bank: &Bank, min_context_slot: Option<Slot>) -> RpcCustomResult<()> {
+    match min_context_slot {
+        Some(min_context_slot) if bank.slot() < min_context_slot => {
+            Err(RpcCustomError::MinContextSlotNotReached {
+                context_slot: bank.slot(),
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
 fn verify_filter(input: &RpcFilterType) -> Result<()> {
     input
         .verify()
@@ -2692,8 +3191,9 @@ fn _send_transaction(
     last_valid_block_height: u64,
     durable_nonce_info: Option<(Pubkey, Hash)>,
     max_retries: Option<usize>,
+    inflight_permit: Option<InflightPermit>,
 ) -> Result<String> {
-    let transaction_info = TransactionInfo::new(
+    let mut transaction_info = TransactionInfo::new(
         signature,
         wire_transaction,
         last_valid_block_height,
@@ -2701,6 +3201,9 @@ fn _send_transaction(
         max_retries,
         None,
     );
+    if let Some(inflight_permit) = inflight_permit {
+        transaction_info = transaction_info.with_inflight_permit(inflight_permit);
+    }
     meta.transaction_sender
         .send(transaction_info)
         .unwrap_or_else(|err| warn!("Failed to enqueue transaction: {}", err));
@@ -2995,7 +3498,15 @@ pub mod rpc_bank {
             &self,
             meta: Self::Metadata,
             config: Option<RpcBlockProductionConfig>,
-        ) -> Result<RpcResponse<RpcBlockProduction>>;
+        ) -> BoxFuture<Result<RpcResponse<RpcBlockProduction>>>;
+
+        #[rpc(meta, name = "getVoteAccountEpochStakes")]
+        fn get_vote_account_epoch_stakes(
+            &self,
+            meta: Self::Metadata,
+            vote_pubkey_str: String,
+            config: Option<RpcContextConfig>,
+        ) -> Result<RpcResponse<Vec<RpcVoteAccountEpochStake>>>;
     }
 
     pub struct BankDataImpl;
@@ -3075,88 +3586,125 @@ pub mod rpc_bank {
             &self,
             meta: Self::Metadata,
             config: Option<RpcBlockProductionConfig>,
-        ) -> Result<RpcResponse<RpcBlockProduction>> {
+        ) -> BoxFuture<Result<RpcResponse<RpcBlockProduction>>> {
             debug!("get_block_production rpc request received");
 
-            let config = config.unwrap_or_default();
-            let filter_by_identity = if let Some(ref identity) = config.identity {
-                Some(verify_pubkey(identity)?)
-            } else {
-                None
-            };
+            async move {
+                let config = config.unwrap_or_default();
+                let filter_by_identity = if let Some(ref identity) = config.identity {
+                    Some(verify_pubkey(identity)?)
+                } else {
+                    None
+                };
 
-            let bank = meta.bank(config.commitment);
-            let (first_slot, last_slot) = match config.range {
-                None => (
-                    bank.epoch_schedule().get_first_slot_in_epoch(bank.epoch()),
-                    bank.slot(),
-                ),
-                Some(range) => {
-                    let first_slot = range.first_slot;
-                    let last_slot = range.last_slot.unwrap_or_else(|| bank.slot());
-                    if last_slot < first_slot {
-                        return Err(Error::invalid_params(format!(
-                            "lastSlot, {last_slot}, cannot be less than firstSlot, {first_slot}"
-                        )));
+                let bank = meta.bank(config.commitment);
+                let (first_slot, last_slot) = match config.range {
+                    None => (
+                        bank.epoch_schedule().get_first_slot_in_epoch(bank.epoch()),
+                        bank.slot(),
+                    ),
+                    Some(range) => {
+                        let first_slot = range.first_slot;
+                        let last_slot = range.last_slot.unwrap_or_else(|| bank.slot());
+                        if last_slot < first_slot {
+                            return Err(Error::invalid_params(format!(
+                                "lastSlot, {last_slot}, cannot be less than firstSlot, {first_slot}"
+                            )));
+                        }
+                        (first_slot, last_slot)
                     }
-                    (first_slot, last_slot)
+                };
+
+                let slot_history = bank.get_slot_history();
+                if first_slot < slot_history.oldest() {
+                    return Err(Error::invalid_params(format!(
+                        "firstSlot, {}, is too small; min {}",
+                        first_slot,
+                        slot_history.oldest()
+                    )));
+                }
+                if last_slot > slot_history.newest() {
+                    return Err(Error::invalid_params(format!(
+                        "lastSlot, {}, is too large; max {}",
+                        last_slot,
+                        slot_history.newest()
+                    )));
+                }
+                let skipped_slot_reasons = config.skipped_slot_reasons.unwrap_or_default();
+                // Classifying skipped slots reads the slot meta of each of them
+                if skipped_slot_reasons && last_slot - first_slot > MAX_GET_CONFIRMED_BLOCKS_RANGE {
+                    return Err(Error::invalid_params(format!(
+                        "Slot range too large for skippedSlotReasons; max {MAX_GET_CONFIRMED_BLOCKS_RANGE}"
+                    )));
                 }
-            };
 
-            let slot_history = bank.get_slot_history();
-            if first_slot < slot_history.oldest() {
-                return Err(Error::invalid_params(format!(
-                    "firstSlot, {}, is too small; min {}",
+                let slot_leaders = meta.get_slot_leaders(
+                    config.commitment,
                     first_slot,
-                    slot_history.oldest()
-                )));
-            }
-            if last_slot > slot_history.newest() {
-                return Err(Error::invalid_params(format!(
-                    "lastSlot, {}, is too large; max {}",
-                    last_slot,
-                    slot_history.newest()
-                )));
-            }
+                    last_slot.saturating_sub(first_slot) as usize + 1, // +1 because last_slot is inclusive
+                )?;
 
-            let slot_leaders = meta.get_slot_leaders(
-                config.commitment,
-                first_slot,
-                last_slot.saturating_sub(first_slot) as usize + 1, // +1 because last_slot is inclusive
-            )?;
+                let mut block_production: HashMap<_, (usize, usize)> = HashMap::new();
+                let mut skipped_slots = skipped_slot_reasons.then(Vec::new);
 
-            let mut block_production: HashMap<_, (usize, usize)> = HashMap::new();
+                let mut slot = first_slot;
+                for identity in slot_leaders {
+                    if let Some(ref filter_by_identity) = filter_by_identity {
+                        if identity != *filter_by_identity {
+                            slot += 1;
+                            continue;
+                        }
+                    }
 
-            let mut slot = first_slot;
-            for identity in slot_leaders {
-                if let Some(ref filter_by_identity) = filter_by_identity {
-                    if identity != *filter_by_identity {
-                        slot += 1;
-                        continue;
+                    let entry = block_production.entry(identity).or_default();
+                    if slot_history.check(slot) == Check::Found {
+                        entry.1 += 1; // Increment blocks_produced
+                    } else if let Some(skipped_slots) = skipped_slots.as_mut() {
+                        skipped_slots.push((slot, identity));
                     }
+                    entry.0 += 1; // Increment leader_slots
+                    slot += 1;
                 }
 
-                let entry = block_production.entry(identity).or_default();
-                if slot_history.check(slot) == solana_sdk::slot_history::Check::Found {
-                    entry.1 += 1; // Increment blocks_produced
-                }
-                entry.0 += 1; // Increment leader_slots
-                slot += 1;
-            }
+                let skipped_slots = if let Some(skipped_slots) = skipped_slots {
+                    Some(
+                        meta.get_skipped_slot_reasons(slot_history, skipped_slots)
+                            .await,
+                    )
+                } else {
+                    None
+                };
 
-            Ok(new_response(
-                &bank,
-                RpcBlockProduction {
-                    by_identity: block_production
-                        .into_iter()
-                        .map(|(k, v)| (k.to_string(), v))
-                        .collect(),
-                    range: RpcBlockProductionRange {
-                        first_slot,
-                        last_slot,
+                Ok(new_response(
+                    &bank,
+                    RpcBlockProduction {
+                        by_identity: block_production
+                            .into_iter()
+                            .map(|(k, v)| (k.to_string(), v))
+                            .collect(),
+                        range: RpcBlockProductionRange {
+                            first_slot,
+                            last_slot,
+                        },
+                        skipped_slots,
                     },
-                },
-            ))
+                ))
+            }
+            .boxed()
+        }
+
+        fn get_vote_account_epoch_stakes(
+            &self,
+            meta: Self::Metadata,
+            vote_pubkey_str: String,
+            config: Option<RpcContextConfig>,
+        ) -> Result<RpcResponse<Vec<RpcVoteAccountEpochStake>>> {
+            debug!(
+                "get_vote_account_epoch_stakes rpc request received: {:?}",
+                vote_pubkey_str
+            );
+            let vote_pubkey = verify_pubkey(&vote_pubkey_str)?;
+            meta.get_vote_account_epoch_stakes(&vote_pubkey, config.unwrap_or_default())
         }
     }
 }
@@ -3201,7 +3749,7 @@ pub mod rpc_accounts {
             &self,
             meta: Self::Metadata,
             pubkey_str: String,
-            commitment: Option<CommitmentConfig>,
+            config: Option<RpcContextConfig>,
         ) -> Result<RpcResponse<UiTokenAmount>>;
 
         #[rpc(meta, name = "getTokenSupply")]
@@ -3209,7 +3757,7 @@ pub mod rpc_accounts {
             &self,
             meta: Self::Metadata,
             mint_str: String,
-            commitment: Option<CommitmentConfig>,
+            config: Option<RpcContextConfig>,
         ) -> Result<RpcResponse<UiTokenAmount>>;
     }
 
@@ -3273,25 +3821,25 @@ pub mod rpc_accounts {
             &self,
             meta: Self::Metadata,
             pubkey_str: String,
-            commitment: Option<CommitmentConfig>,
+            config: Option<RpcContextConfig>,
         ) -> Result<RpcResponse<UiTokenAmount>> {
             debug!(
                 "get_token_account_balance rpc request received: {:?}",
                 pubkey_str
             );
             let pubkey = verify_pubkey(&pubkey_str)?;
-            meta.get_token_account_balance(&pubkey, commitment)
+            meta.get_token_account_balance(&pubkey, config)
         }
 
         fn get_token_supply(
             &self,
             meta: Self::Metadata,
             mint_str: String,
-            commitment: Option<CommitmentConfig>,
+            config: Option<RpcContextConfig>,
         ) -> Result<RpcResponse<UiTokenAmount>> {
             debug!("get_token_supply rpc request received: {:?}", mint_str);
             let mint = verify_pubkey(&mint_str)?;
-            meta.get_token_supply(&mint, commitment)
+            meta.get_token_supply(&mint, config)
         }
     }
 }
@@ -3313,6 +3861,14 @@ pub mod rpc_accounts_scan {
             config: Option<RpcProgramAccountsConfig>,
         ) -> BoxFuture<Result<OptionalContext<Vec<RpcKeyedAccount>>>>;
 
+        #[rpc(meta, name = "getBufferAccounts")]
+        fn get_buffer_accounts(
+            &self,
+            meta: Self::Metadata,
+            authority_str: String,
+            config: Option<RpcContextConfig>,
+        ) -> BoxFuture<Result<RpcResponse<Vec<RpcBufferAccount>>>>;
+
         #[rpc(meta, name = "getLargestAccounts")]
         fn get_largest_accounts(
             &self,
@@ -3336,7 +3892,7 @@ pub mod rpc_accounts_scan {
             &self,
             meta: Self::Metadata,
             mint_str: String,
-            commitment: Option<CommitmentConfig>,
+            config: Option<RpcContextConfig>,
         ) -> BoxFuture<Result<RpcResponse<Vec<RpcTokenAccountBalance>>>>;
 
         #[rpc(meta, name = "getTokenAccountsByOwner")]
@@ -3374,16 +3930,18 @@ pub mod rpc_accounts_scan {
             );
             async move {
                 let program_id = verify_pubkey(&program_id_str)?;
-                let (config, filters, with_context, sort_results) = if let Some(config) = config {
-                    (
-                        Some(config.account_config),
-                        config.filters.unwrap_or_default(),
-                        config.with_context.unwrap_or_default(),
-                        config.sort_results.unwrap_or(true),
-                    )
-                } else {
-                    (None, vec![], false, true)
-                };
+                let (config, filters, with_context, sort_results, pagination) =
+                    if let Some(config) = config {
+                        (
+                            Some(config.account_config),
+                            config.filters.unwrap_or_default(),
+                            config.with_context.unwrap_or_default(),
+                            config.sort_results.unwrap_or(true),
+                            config.pagination,
+                        )
+                    } else {
+                        (None, vec![], false, true, None)
+                    };
                 if filters.len() > MAX_GET_PROGRAM_ACCOUNT_FILTERS {
                     return Err(Error::invalid_params(format!(
                         "Too many filters provided; max {MAX_GET_PROGRAM_ACCOUNT_FILTERS}"
@@ -3392,8 +3950,43 @@ pub mod rpc_accounts_scan {
                 for filter in &filters {
                     verify_filter(filter)?;
                 }
-                meta.get_program_accounts(program_id, config, filters, with_context, sort_results)
-                    .await
+                let page = pagination
+                    .map(|RpcPaginationConfig { limit, after }| {
+                        if limit == 0 || limit > MAX_GET_PROGRAM_ACCOUNTS_PAGE_LIMIT {
+                            return Err(Error::invalid_params(format!(
+                                "Invalid page limit; max {MAX_GET_PROGRAM_ACCOUNTS_PAGE_LIMIT}"
+                            )));
+                        }
+                        let after = after.as_deref().map(verify_pubkey).transpose()?;
+                        Ok((after, limit))
+                    })
+                    .transpose()?;
+                meta.get_program_accounts(
+                    program_id,
+                    config,
+                    filters,
+                    with_context,
+                    sort_results,
+                    page,
+                )
+                .await
+            }
+            .boxed()
+        }
+
+        fn get_buffer_accounts(
+            &self,
+            meta: Self::Metadata,
+            authority_str: String,
+            config: Option<RpcContextConfig>,
+        ) -> BoxFuture<Result<RpcResponse<Vec<RpcBufferAccount>>>> {
+            debug!(
+                "get_buffer_accounts rpc request received: {:?}",
+                authority_str
+            );
+            async move {
+                let authority = verify_pubkey(&authority_str)?;
+                meta.get_buffer_accounts(authority, config).await
             }
             .boxed()
         }
@@ -3420,7 +4013,7 @@ pub mod rpc_accounts_scan {
             &self,
             meta: Self::Metadata,
             mint_str: String,
-            commitment: Option<CommitmentConfig>,
+            config: Option<RpcContextConfig>,
         ) -> BoxFuture<Result<RpcResponse<Vec<RpcTokenAccountBalance>>>> {
             debug!(
                 "get_token_largest_accounts rpc request received: {:?}",
@@ -3428,7 +4021,7 @@ pub mod rpc_accounts_scan {
             );
             async move {
                 let mint = verify_pubkey(&mint_str)?;
-                meta.get_token_largest_accounts(mint, commitment).await
+                meta.get_token_largest_accounts(mint, config).await
             }
             .boxed()
         }
@@ -3495,6 +4088,13 @@ pub mod rpc_full {
             config: Option<RpcEpochConfig>,
         ) -> BoxFuture<Result<Vec<Option<RpcInflationReward>>>>;
 
+        #[rpc(meta, name = "getEpochStakeRewards")]
+        fn get_epoch_stake_rewards(
+            &self,
+            meta: Self::Metadata,
+            config: Option<RpcEpochStakeRewardsConfig>,
+        ) -> Result<RpcEpochStakeRewards>;
+
         #[rpc(meta, name = "getClusterNodes")]
         fn get_cluster_nodes(&self, meta: Self::Metadata) -> Result<Vec<RpcContactInfo>>;
 
@@ -3519,6 +4119,13 @@ pub mod rpc_full {
         #[rpc(meta, name = "getMaxShredInsertSlot")]
         fn get_max_shred_insert_slot(&self, meta: Self::Metadata) -> Result<Slot>;
 
+        #[rpc(meta, name = "getVoteLatency")]
+        fn get_vote_latency(
+            &self,
+            meta: Self::Metadata,
+            config: Option<RpcVoteLatencyConfig>,
+        ) -> Result<RpcResponse<RpcVoteLatencyStatus>>;
+
         #[rpc(meta, name = "requestAirdrop")]
         fn request_airdrop(
             &self,
@@ -3599,6 +4206,36 @@ pub mod rpc_full {
         #[rpc(meta, name = "getFirstAvailableBlock")]
         fn get_first_available_block(&self, meta: Self::Metadata) -> BoxFuture<Result<Slot>>;
 
+        #[rpc(meta, name = "getSlotHashes")]
+        fn get_slot_hashes(
+            &self,
+            meta: Self::Metadata,
+            start_slot: Slot,
+            limit: usize,
+        ) -> BoxFuture<Result<Vec<RpcSlotHash>>>;
+
+        #[rpc(meta, name = "getSlotByBlockHeight")]
+        fn get_slot_by_block_height(
+            &self,
+            meta: Self::Metadata,
+            block_height: u64,
+        ) -> Result<Option<Slot>>;
+
+        #[rpc(meta, name = "getSlotByBlockTime")]
+        fn get_slot_by_block_time(
+            &self,
+            meta: Self::Metadata,
+            timestamp: UnixTimestamp,
+        ) -> Result<Option<Slot>>;
+
+        #[rpc(meta, name = "getDuplicateVotes")]
+        fn get_duplicate_votes(
+            &self,
+            meta: Self::Metadata,
+            start_slot: Slot,
+            limit: usize,
+        ) -> Result<Vec<RpcDuplicateVote>>;
+
         #[rpc(meta, name = "getLatestBlockhash")]
         fn get_latest_blockhash(
             &self,
@@ -3768,6 +4405,15 @@ pub mod rpc_full {
             Ok(meta.get_max_shred_insert_slot())
         }
 
+        fn get_vote_latency(
+            &self,
+            meta: Self::Metadata,
+            config: Option<RpcVoteLatencyConfig>,
+        ) -> Result<RpcResponse<RpcVoteLatencyStatus>> {
+            debug!("get_vote_latency rpc request received");
+            meta.get_vote_latency(config)
+        }
+
         fn request_airdrop(
             &self,
             meta: Self::Metadata,
@@ -3824,6 +4470,7 @@ pub mod rpc_full {
                 last_valid_block_height,
                 None,
                 None,
+                None,
             )
         }
 
@@ -3866,6 +4513,8 @@ pub mod rpc_full {
                 preflight_bank.get_reserved_account_keys(),
             )?;
             let signature = *transaction.signature();
+            let inflight_permit =
+                meta.acquire_inflight_permit(transaction.message().fee_payer())?;
 
             let mut last_valid_block_height = preflight_bank
                 .get_blockhash_last_valid_block_height(transaction.message().recent_blockhash())
@@ -3905,14 +4554,52 @@ pub mod rpc_full {
                     }
                 }
 
-                if let TransactionSimulationResult {
-                    result: Err(err),
+                // Identical transactions resubmitted in quick succession reuse the preflight
+                // result of the same bank. Signatures are still verified above, since they are not
+                // covered by the message hash.
+                let preflight_cache_key = PreflightCacheKey {
+                    message_hash: *transaction.message_hash(),
+                    slot: preflight_bank.slot(),
+                    commitment: preflight_commitment.unwrap_or_default().commitment,
+                };
+                let preflight_result = match meta.preflight_cache.get(&preflight_cache_key) {
+                    Some(preflight_result) => {
+                        inc_new_counter_info!("rpc-send-tx_preflight-cache-hit", 1);
+                        preflight_result
+                    }
+                    None => {
+                        if meta.preflight_cache.is_enabled() {
+                            inc_new_counter_info!("rpc-send-tx_preflight-cache-miss", 1);
+                        }
+                        let TransactionSimulationResult {
+                            result,
+                            logs,
+                            post_simulation_accounts: _,
+                            units_consumed,
+                            return_data,
+                            // Always `None` due to `enable_cpi_recording = false`
+                            inner_instructions: _,
+                            memory_usage: _,
+                            account_privileges: _,
+                        } = preflight_bank.simulate_transaction(&transaction, false);
+                        let preflight_result = result.map_err(|err| PreflightFailure {
+                            err,
+                            logs,
+                            units_consumed,
+                            return_data,
+                        });
+                        meta.preflight_cache
+                            .insert(preflight_cache_key, preflight_result.clone());
+                        preflight_result
+                    }
+                };
+
+                if let Err(PreflightFailure {
+                    err,
                     logs,
-                    post_simulation_accounts: _,
                     units_consumed,
                     return_data,
-                    inner_instructions: _, // Always `None` due to `enable_cpi_recording = false`
-                } = preflight_bank.simulate_transaction(&transaction, false)
+                }) = preflight_result
                 {
                     match err {
                         TransactionError::BlockhashNotFound => {
@@ -3932,6 +4619,8 @@ pub mod rpc_full {
                             return_data: return_data.map(|return_data| return_data.into()),
                             inner_instructions: None,
                             replacement_blockhash: None,
+                            memory_usage: None,
+                            account_privileges: None,
                         },
                     }
                     .into());
@@ -3945,6 +4634,7 @@ pub mod rpc_full {
                 last_valid_block_height,
                 durable_nonce_info,
                 max_retries,
+                inflight_permit,
             )
         }
 
@@ -3963,6 +4653,8 @@ pub mod rpc_full {
                 accounts: config_accounts,
                 min_context_slot,
                 inner_instructions: enable_cpi_recording,
+                memory_usage: enable_memory_usage,
+                account_privileges: enable_account_privileges,
             } = config.unwrap_or_default();
             let tx_encoding = encoding.unwrap_or(UiTransactionEncoding::Base58);
             let binary_encoding = tx_encoding.into_binary_encoding().ok_or_else(|| {
@@ -4003,6 +4695,10 @@ pub mod rpc_full {
                 verify_transaction(&transaction, &bank.feature_set)?;
             }
 
+            let mut recording_config =
+                ExecutionRecordingConfig::new(enable_cpi_recording, true, true);
+            recording_config.enable_memory_usage_recording = enable_memory_usage;
+            recording_config.enable_account_privileges_recording = enable_account_privileges;
             let TransactionSimulationResult {
                 result,
                 logs,
@@ -4010,7 +4706,9 @@ pub mod rpc_full {
                 units_consumed,
                 return_data,
                 inner_instructions,
-            } = bank.simulate_transaction(&transaction, enable_cpi_recording);
+                memory_usage,
+                account_privileges,
+            } = bank.simulate_transaction_with_recording_config(&transaction, recording_config);
 
             let account_keys = transaction.message().account_keys();
             let number_of_accounts = account_keys.len();
@@ -4066,6 +4764,38 @@ pub mod rpc_full {
                     .map(|converted| parse_ui_inner_instructions(converted, &account_keys))
                     .collect()
             });
+            let memory_usage = enable_memory_usage.then(|| {
+                memory_usage
+                    .into_iter()
+                    .map(|usage| RpcProgramMemoryUsage {
+                        program_id: usage.program_id.to_string(),
+                        stack_height: usage.stack_height,
+                        stack_bytes: usage.stack_bytes,
+                        stack_size: usage.stack_size,
+                        heap_bytes: usage.heap_bytes,
+                        heap_size: usage.heap_size,
+                    })
+                    .collect()
+            });
+            let account_privileges = enable_account_privileges.then(|| {
+                account_privileges
+                    .into_iter()
+                    .map(|privileges| RpcInstructionAccountPrivileges {
+                        program_id: privileges.program_id.to_string(),
+                        stack_height: privileges.stack_height,
+                        accounts: privileges
+                            .accounts
+                            .into_iter()
+                            .map(|usage| RpcAccountPrivilegeUsage {
+                                pubkey: usage.pubkey.to_string(),
+                                signer: usage.is_signer,
+                                writable: usage.is_writable,
+                                modified: usage.modified,
+                            })
+                            .collect(),
+                    })
+                    .collect()
+            });
 
             Ok(new_response(
                 bank,
@@ -4077,6 +4807,8 @@ pub mod rpc_full {
                     return_data: return_data.map(|return_data| return_data.into()),
                     inner_instructions,
                     replacement_blockhash: blockhash,
+                    memory_usage,
+                    account_privileges,
                 },
             ))
         }
@@ -4190,6 +4922,50 @@ pub mod rpc_full {
             Box::pin(async move { Ok(meta.get_first_available_block().await) })
         }
 
+        fn get_slot_hashes(
+            &self,
+            meta: Self::Metadata,
+            start_slot: Slot,
+            limit: usize,
+        ) -> BoxFuture<Result<Vec<RpcSlotHash>>> {
+            debug!(
+                "get_slot_hashes rpc request received: {}-{}",
+                start_slot, limit,
+            );
+            Box::pin(async move { meta.get_slot_hashes(start_slot, limit).await })
+        }
+
+        fn get_slot_by_block_height(
+            &self,
+            meta: Self::Metadata,
+            block_height: u64,
+        ) -> Result<Option<Slot>> {
+            debug!("get_slot_by_block_height rpc request received: {block_height}");
+            meta.get_slot_by_block_height(block_height)
+        }
+
+        fn get_slot_by_block_time(
+            &self,
+            meta: Self::Metadata,
+            timestamp: UnixTimestamp,
+        ) -> Result<Option<Slot>> {
+            debug!("get_slot_by_block_time rpc request received: {timestamp}");
+            meta.get_slot_by_block_time(timestamp)
+        }
+
+        fn get_duplicate_votes(
+            &self,
+            meta: Self::Metadata,
+            start_slot: Slot,
+            limit: usize,
+        ) -> Result<Vec<RpcDuplicateVote>> {
+            debug!(
+                "get_duplicate_votes rpc request received: {}-{}",
+                start_slot, limit,
+            );
+            meta.get_duplicate_votes(start_slot, limit)
+        }
+
         fn get_inflation_reward(
             &self,
             meta: Self::Metadata,
@@ -4214,6 +4990,27 @@ pub mod rpc_full {
             Box::pin(async move { meta.get_inflation_reward(addresses, config).await })
         }
 
+        fn get_epoch_stake_rewards(
+            &self,
+            meta: Self::Metadata,
+            config: Option<RpcEpochStakeRewardsConfig>,
+        ) -> Result<RpcEpochStakeRewards> {
+            debug!("get_epoch_stake_rewards rpc request received: {:?}", config);
+            let RpcEpochStakeRewardsConfig { epoch, pagination } = config.unwrap_or_default();
+            let (after, limit) = match pagination {
+                Some(RpcPaginationConfig { limit, after }) => {
+                    if limit == 0 || limit > MAX_GET_EPOCH_STAKE_REWARDS_PAGE_LIMIT {
+                        return Err(Error::invalid_params(format!(
+                            "Invalid page limit; max {MAX_GET_EPOCH_STAKE_REWARDS_PAGE_LIMIT}"
+                        )));
+                    }
+                    (after.as_deref().map(verify_pubkey).transpose()?, limit)
+                }
+                None => (None, MAX_GET_EPOCH_STAKE_REWARDS_PAGE_LIMIT),
+            };
+            meta.get_epoch_stake_rewards(epoch, after, limit)
+        }
+
         fn get_latest_blockhash(
             &self,
             meta: Self::Metadata,
@@ -4321,6 +5118,24 @@ fn rpc_perf_sample_from_perf_sample(slot: u64, sample: PerfSample) -> RpcPerfSam
     }
 }
 
+fn rpc_supply(supply: &Supply, exclude_non_circulating_accounts_list: bool) -> RpcSupply {
+    let non_circulating_accounts = if exclude_non_circulating_accounts_list {
+        vec![]
+    } else {
+        supply
+            .non_circulating_accounts
+            .iter()
+            .map(|pubkey| pubkey.to_string())
+            .collect()
+    };
+    RpcSupply {
+        total: supply.total,
+        circulating: supply.circulating,
+        non_circulating: supply.non_circulating,
+        non_circulating_accounts,
+    }
+}
+
 const MAX_BASE58_SIZE: usize = 1683; // Golden, bump if PACKET_DATA_SIZE changes
 const MAX_BASE64_SIZE: usize = 1644; // Golden, bump if PACKET_DATA_SIZE changes
 fn decode_and_deserialize<T>(
@@ -4390,6 +5205,16 @@ fn sanitize_transaction(
     address_loader: impl AddressLoader,
     reserved_account_keys: &HashSet<Pubkey>,
 ) -> Result<RuntimeTransaction<SanitizedTransaction>> {
+    if let Err(err) = transaction.sanitize() {
+        // Only transactions failing sanitization pay for the detailed checks
+        let err = TransactionError::from(err);
+        let mut error = Error::invalid_params(format!("invalid transaction: {err}"));
+        let violations = transaction.sanitize_detailed();
+        if !violations.is_empty() {
+            error.data = Some(serde_json::json!({ "violations": violations }));
+        }
+        return Err(error);
+    }
     RuntimeTransaction::try_create(
         transaction,
         MessageHash::Compute,
@@ -4514,7 +5339,7 @@ pub mod tests {
         solana_entry::entry::next_versioned_entry,
         solana_gossip::{contact_info::ContactInfo, socketaddr},
         solana_ledger::{
-            blockstore_meta::PerfSampleV2,
+            blockstore_meta::{DuplicateVote, DuplicateVoteProof, PerfSampleV2},
             blockstore_processor::fill_blockstore_slot_with_ticks,
             genesis_utils::{create_genesis_config, GenesisConfigInfo},
             get_tmp_ledger_path,
@@ -4522,6 +5347,8 @@ pub mod tests {
         solana_rpc_client_api::{
             custom_error::{
                 JSON_RPC_SERVER_ERROR_BLOCK_NOT_AVAILABLE,
+                JSON_RPC_SERVER_ERROR_FEE_PAYER_INFLIGHT_LIMIT_EXCEEDED,
+                JSON_RPC_SERVER_ERROR_MIN_CONTEXT_SLOT_NOT_REACHED,
                 JSON_RPC_SERVER_ERROR_TRANSACTION_HISTORY_NOT_AVAILABLE,
                 JSON_RPC_SERVER_ERROR_UNSUPPORTED_TRANSACTION_VERSION,
             },
@@ -4529,7 +5356,7 @@ pub mod tests {
         },
         solana_runtime::{
             accounts_background_service::AbsRequestSender,
-            bank::BankTestConfig,
+            bank::{BankTestConfig, EpochStakeReward, EpochStakeRewards},
             commitment::{BlockCommitment, CommitmentSlots},
             non_circulating_supply::non_circulating_accounts,
         },
@@ -4639,7 +5466,9 @@ pub mod tests {
         blockstore: Arc<Blockstore>,
         bank_forks: Arc<RwLock<BankForks>>,
         max_slots: Arc<MaxSlots>,
+        vote_latency_tracker: Arc<VoteLatencyTracker>,
         max_complete_transaction_status_slot: Arc<AtomicU64>,
+        epoch_stake_rewards_cache: Arc<EpochStakeRewardsCache>,
         block_commitment_cache: Arc<RwLock<BlockCommitmentCache>>,
     }
 
@@ -4675,9 +5504,11 @@ pub mod tests {
                 &socketaddr!(Ipv4Addr::LOCALHOST, 1234),
             ));
             let max_slots = Arc::new(MaxSlots::default());
+            let vote_latency_tracker = Arc::new(VoteLatencyTracker::default());
             // note that this means that slot 0 will always be considered complete
             let max_complete_transaction_status_slot = Arc::new(AtomicU64::new(0));
             let max_complete_rewards_slot = Arc::new(AtomicU64::new(0));
+            let epoch_stake_rewards_cache = Arc::new(EpochStakeRewardsCache::default());
             let optimistically_confirmed_bank =
                 OptimisticallyConfirmedBank::locked_from_bank_forks_root(&bank_forks);
 
@@ -4701,9 +5532,11 @@ pub mod tests {
                 optimistically_confirmed_bank,
                 Arc::new(RwLock::new(LargestAccountsCache::new(30))),
                 max_slots.clone(),
+                vote_latency_tracker.clone(),
                 Arc::new(LeaderScheduleCache::new_from_bank(&bank)),
                 max_complete_transaction_status_slot.clone(),
                 max_complete_rewards_slot,
+                epoch_stake_rewards_cache.clone(),
                 Arc::new(PrioritizationFeeCache::default()),
                 service_runtime(rpc_threads, rpc_blocking_threads, rpc_niceness_adj),
             )
@@ -4724,7 +5557,9 @@ pub mod tests {
                 bank_forks,
                 blockstore,
                 max_slots,
+                vote_latency_tracker,
                 max_complete_transaction_status_slot,
+                epoch_stake_rewards_cache,
                 block_commitment_cache,
             }
         }
@@ -5223,6 +6058,35 @@ pub mod tests {
         assert_eq!(result.value, expected);
     }
 
+    #[test]
+    fn test_account_reads_min_context_slot() {
+        let rpc = RpcHandler::start();
+        let min_context_slot = rpc.working_bank().slot() + 1;
+        let config = json!({"minContextSlot": min_context_slot});
+        let pubkey = solana_pubkey::new_rand().to_string();
+        for (method, params) in [
+            ("getAccountInfo", json!([pubkey, config])),
+            ("getMultipleAccounts", json!([[pubkey], config])),
+            ("getBalance", json!([pubkey, config])),
+            ("getTokenAccountBalance", json!([pubkey, config])),
+            ("getTokenSupply", json!([pubkey, config])),
+            ("getTokenLargestAccounts", json!([pubkey, config])),
+            ("getLargestAccounts", json!([config])),
+            ("getSupply", json!([config])),
+        ] {
+            let request = create_test_request(method, Some(params));
+            let (code, message) = parse_failure_response(rpc.handle_request_sync(request));
+            assert_eq!(
+                code, JSON_RPC_SERVER_ERROR_MIN_CONTEXT_SLOT_NOT_REACHED,
+                "{method}: {message}"
+            );
+        }
+
+        // Older slots have been reached.
+        let request = create_test_request("getSupply", Some(json!([{"minContextSlot": 0}])));
+        let _: RpcResponse<RpcSupply> = parse_success_result(rpc.handle_request_sync(request));
+    }
+
     #[test]
     fn test_get_largest_accounts() {
         let rpc = RpcHandler::start();
@@ -5485,6 +6349,45 @@ pub mod tests {
         );
     }
 
+    #[test]
+    fn test_rpc_get_account_info_base64_zstd() {
+        let rpc = RpcHandler::start();
+        let bank = rpc.working_bank();
+
+        // Large, mostly empty accounts such as order books compress well
+        let pubkey = Pubkey::new_unique();
+        let address = pubkey.to_string();
+        let mut data = vec![0; 1024 * 1024];
+        data[..4].copy_from_slice(&[1, 2, 3, 4]);
+        let account = AccountSharedData::create(42, data.clone(), Pubkey::default(), false, 0);
+        bank.store_account(&pubkey, &account);
+
+        let request = create_test_request(
+            "getAccountInfo",
+            Some(json!([address, {"encoding": "base64+zstd"}])),
+        );
+        let result: Value = parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result["value"]["data"][1], "base64+zstd");
+        assert!(result["value"]["data"][0].as_str().unwrap().len() < data.len() / 100);
+        let ui_account: UiAccount = serde_json::from_value(result["value"].clone()).unwrap();
+        assert_eq!(ui_account.data.decode(), Some(data.clone()));
+        assert_eq!(ui_account.space, Some(data.len() as u64));
+
+        let config = json!({"encoding": "base64+zstd", "dataSlice": {"length": 2, "offset": 1}});
+        let request = create_test_request("getAccountInfo", Some(json!([address, config])));
+        let result: Value = parse_success_result(rpc.handle_request_sync(request));
+        let ui_account: UiAccount = serde_json::from_value(result["value"].clone()).unwrap();
+        assert_eq!(ui_account.data.decode(), Some(data[1..3].to_vec()));
+
+        let request = create_test_request(
+            "getMultipleAccounts",
+            Some(json!([[address], {"encoding": "base64+zstd"}])),
+        );
+        let result: Value = parse_success_result(rpc.handle_request_sync(request));
+        let ui_account: UiAccount = serde_json::from_value(result["value"][0].clone()).unwrap();
+        assert_eq!(ui_account.data.decode(), Some(data));
+    }
+
     #[test]
     fn test_encode_account_does_not_throw_when_slice_larger_than_account() {
         let data = vec![42; 5];
@@ -5678,6 +6581,100 @@ pub mod tests {
         );
     }
 
+    #[test]
+    fn test_get_multiple_accounts_stream() {
+        let rpc = RpcHandler::start();
+        let bank = rpc.working_bank();
+
+        let pubkeys = (0..5).map(|_| Pubkey::new_unique()).collect::<Vec<_>>();
+        for (i, pubkey) in pubkeys.iter().enumerate().skip(1) {
+            let account = AccountSharedData::new(i as u64, 0, &Pubkey::default());
+            bank.store_account(pubkey, &account);
+        }
+        let pubkey_strs = pubkeys.iter().map(|p| p.to_string()).collect::<Vec<_>>();
+
+        let (verified_pubkeys, config, chunk_size) = rpc
+            .meta
+            .verify_multiple_accounts_stream_request(RpcMultipleAccountsStreamRequest {
+                pubkeys: pubkey_strs.clone(),
+                config: RpcAccountInfoConfig::default(),
+                chunk_size: Some(2),
+            })
+            .unwrap();
+        assert_eq!(verified_pubkeys, pubkeys);
+        assert_eq!(chunk_size, 2);
+
+        let chunks: Vec<_> = futures::executor::block_on(
+            rpc.meta
+                .get_multiple_accounts_stream(verified_pubkeys, config, chunk_size)
+                .collect(),
+        );
+        assert_eq!(
+            chunks.iter().map(|chunk| chunk.offset).collect::<Vec<_>>(),
+            vec![0, 2, 4]
+        );
+        let accounts = chunks
+            .into_iter()
+            .flat_map(|chunk| {
+                assert_eq!(chunk.context.unwrap().slot, bank.slot());
+                assert!(chunk.error.is_none());
+                chunk.value.unwrap()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(accounts.len(), pubkeys.len());
+        assert!(accounts[0].is_none());
+        for (i, account) in accounts.iter().enumerate().skip(1) {
+            assert_eq!(account.as_ref().unwrap().lamports, i as u64);
+        }
+
+        // A chunk that cannot be served is reported in place of its accounts
+        let config = RpcAccountInfoConfig {
+            min_context_slot: Some(bank.slot() + 100),
+            ..RpcAccountInfoConfig::default()
+        };
+        let chunks: Vec<_> = futures::executor::block_on(
+            rpc.meta
+                .get_multiple_accounts_stream(pubkeys.clone(), config, 3)
+                .collect(),
+        );
+        assert_eq!(chunks.len(), 2);
+        for (chunk, offset) in chunks.into_iter().zip([0, 3]) {
+            assert_eq!(chunk.offset, offset);
+            assert!(chunk.context.is_none());
+            assert!(chunk.value.is_none());
+            assert_eq!(
+                chunk.error.unwrap().code,
+                JSON_RPC_SERVER_ERROR_MIN_CONTEXT_SLOT_NOT_REACHED
+            );
+        }
+
+        // Chunks larger than getMultipleAccounts allows are refused
+        assert!(rpc
+            .meta
+            .verify_multiple_accounts_stream_request(RpcMultipleAccountsStreamRequest {
+                pubkeys: pubkey_strs.clone(),
+                config: RpcAccountInfoConfig::default(),
+                chunk_size: Some(MAX_MULTIPLE_ACCOUNTS + 1),
+            })
+            .is_err());
+        assert!(rpc
+            .meta
+            .verify_multiple_accounts_stream_request(RpcMultipleAccountsStreamRequest {
+                pubkeys: vec!["invalid".to_string()],
+                config: RpcAccountInfoConfig::default(),
+                chunk_size: None,
+            })
+            .is_err());
+        assert!(rpc
+            .meta
+            .verify_multiple_accounts_stream_request(RpcMultipleAccountsStreamRequest {
+                pubkeys: vec![pubkey_strs[0].clone(); MAX_MULTIPLE_ACCOUNTS_STREAM + 1],
+                config: RpcAccountInfoConfig::default(),
+                chunk_size: None,
+            })
+            .is_err());
+    }
+
     #[test]
     fn test_rpc_get_program_accounts() {
         let rpc = RpcHandler::start();
@@ -5828,8 +6825,116 @@ pub mod tests {
                 }]}, // Filter on Initialized and non-matching data size
             ])),
         );
-        let result: Vec<RpcKeyedAccount> = parse_success_result(rpc.handle_request_sync(request));
-        assert_eq!(result.len(), 0);
+        let result: Vec<RpcKeyedAccount> = parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result.len(), 0);
+    }
+
+    #[test]
+    fn test_rpc_get_program_accounts_paginated() {
+        let rpc = RpcHandler::start();
+        let bank = rpc.working_bank();
+
+        let program_id = Pubkey::new_unique();
+        let mut expected: Vec<_> = (0..5)
+            .map(|_| {
+                let pubkey = solana_pubkey::new_rand();
+                bank.store_account(&pubkey, &AccountSharedData::new(42, 0, &program_id));
+                pubkey.to_string()
+            })
+            .collect();
+        expected.sort_unstable_by_key(|pubkey| pubkey.parse::<Pubkey>().unwrap());
+
+        let mut paged = vec![];
+        let mut after = None;
+        loop {
+            let request = create_test_request(
+                "getProgramAccounts",
+                Some(json!([
+                    program_id.to_string(),
+                    {"pagination": {"limit": 2, "after": after}},
+                ])),
+            );
+            let result: Vec<RpcKeyedAccount> =
+                parse_success_result(rpc.handle_request_sync(request));
+            assert!(result.len() <= 2);
+            let is_last_page = result.len() < 2;
+            after = result.last().map(|account| account.pubkey.clone());
+            paged.extend(result.into_iter().map(|account| account.pubkey));
+            if is_last_page {
+                break;
+            }
+        }
+        assert_eq!(paged, expected);
+
+        // Invalid page limit
+        let request = create_test_request(
+            "getProgramAccounts",
+            Some(json!([
+                program_id.to_string(),
+                {"pagination": {"limit": 0}},
+            ])),
+        );
+        let (code, _) = parse_failure_response(rpc.handle_request_sync(request));
+        assert_eq!(code, ErrorCode::InvalidParams.code());
+
+        // Invalid pagination token
+        let request = create_test_request(
+            "getProgramAccounts",
+            Some(json!([
+                program_id.to_string(),
+                {"pagination": {"limit": 2, "after": "not a pubkey"}},
+            ])),
+        );
+        let (code, _) = parse_failure_response(rpc.handle_request_sync(request));
+        assert_eq!(code, ErrorCode::InvalidParams.code());
+    }
+
+    #[test]
+    fn test_rpc_get_buffer_accounts() {
+        let rpc = RpcHandler::start();
+        let bank = rpc.working_bank();
+
+        let authority = Pubkey::new_unique();
+        let store_buffer = |authority_address: Option<Pubkey>, data_len: usize| {
+            let pubkey = Pubkey::new_unique();
+            let account = AccountSharedData::new_data_with_space(
+                42,
+                &UpgradeableLoaderState::Buffer { authority_address },
+                UpgradeableLoaderState::size_of_buffer(data_len),
+                &bpf_loader_upgradeable::id(),
+            )
+            .unwrap();
+            bank.store_account(&pubkey, &account);
+            pubkey
+        };
+        let mut expected = vec![
+            RpcBufferAccount {
+                pubkey: store_buffer(Some(authority), 100).to_string(),
+                authority: authority.to_string(),
+                data_len: 100,
+                lamports: 42,
+            },
+            RpcBufferAccount {
+                pubkey: store_buffer(Some(authority), 0).to_string(),
+                authority: authority.to_string(),
+                data_len: 0,
+                lamports: 42,
+            },
+        ];
+        expected.sort_unstable_by_key(|buffer| buffer.pubkey.parse::<Pubkey>().unwrap());
+        // Buffers of other authorities, or without one, are not listed
+        store_buffer(Some(Pubkey::new_unique()), 100);
+        store_buffer(None, 100);
+
+        let request =
+            create_test_request("getBufferAccounts", Some(json!([authority.to_string()])));
+        let result: RpcResponse<Vec<RpcBufferAccount>> =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result.value, expected);
+
+        let request = create_test_request("getBufferAccounts", Some(json!(["not a pubkey"])));
+        let (code, _) = parse_failure_response(rpc.handle_request_sync(request));
+        assert_eq!(code, ErrorCode::InvalidParams.code());
     }
 
     #[test]
@@ -6478,6 +7583,103 @@ pub mod tests {
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn test_rpc_simulate_transaction_with_memory_usage() {
+        let rpc = RpcHandler::start();
+        let bank = rpc.working_bank();
+        let rent_exempt_amount = bank.get_minimum_balance_for_rent_exemption(0);
+        let recent_blockhash = bank.confirmed_last_blockhash();
+        let RpcHandler {
+            ref meta, ref io, ..
+        } = rpc;
+
+        let tx = system_transaction::transfer(
+            &rpc.mint_keypair,
+            &solana_pubkey::new_rand(),
+            rent_exempt_amount,
+            recent_blockhash,
+        );
+        let tx_serialized_encoded = bs58::encode(serialize(&tx).unwrap()).into_string();
+
+        // Simulation bank must be frozen
+        bank.freeze();
+
+        let simulate = |config: &str| {
+            let req = format!(
+                r#"{{"jsonrpc":"2.0",
+                     "id":1,
+                     "method":"simulateTransaction",
+                     "params":["{tx_serialized_encoded}", {config}]
+                }}"#,
+            );
+            let res = io.handle_request_sync(&req, meta.clone());
+            let result: Value = serde_json::from_str(&res.expect("actual response"))
+                .expect("actual response deserialization");
+            result["result"]["value"].clone()
+        };
+
+        // `memoryUsage` not requested, should not be in response
+        let value = simulate("{}");
+        assert_eq!(value["err"], Value::Null);
+        assert!(value.get("memoryUsage").is_none());
+
+        // Builtin programs run outside the VM, so there's no memory usage to report
+        let value = simulate(r#"{"memoryUsage": true}"#);
+        assert_eq!(value["err"], Value::Null);
+        assert_eq!(value["memoryUsage"], json!([]));
+    }
+
+    #[test]
+    fn test_rpc_simulate_transaction_with_account_privileges() {
+        let rpc = RpcHandler::start();
+        let bank = rpc.working_bank();
+        let recent_blockhash = bank.confirmed_last_blockhash();
+        let RpcHandler {
+            ref meta, ref io, ..
+        } = rpc;
+
+        // Transfer nothing, so that the recipient is not modified
+        let bob_pubkey = solana_pubkey::new_rand();
+        let tx = system_transaction::transfer(&rpc.mint_keypair, &bob_pubkey, 0, recent_blockhash);
+        let tx_serialized_encoded = bs58::encode(serialize(&tx).unwrap()).into_string();
+
+        // Simulation bank must be frozen
+        bank.freeze();
+
+        let req = format!(
+            r#"{{"jsonrpc":"2.0",
+                 "id":1,
+                 "method":"simulateTransaction",
+                 "params":["{tx_serialized_encoded}", {{"accountPrivileges": true}}]
+            }}"#,
+        );
+        let res = io.handle_request_sync(&req, meta.clone());
+        let result: Value = serde_json::from_str(&res.expect("actual response"))
+            .expect("actual response deserialization");
+        assert_eq!(result["result"]["value"]["err"], Value::Null);
+        assert_eq!(
+            result["result"]["value"]["accountPrivileges"],
+            json!([{
+                "programId": system_program::id().to_string(),
+                "stackHeight": 1,
+                "accounts": [
+                    {
+                        "pubkey": rpc.mint_keypair.pubkey().to_string(),
+                        "signer": true,
+                        "writable": true,
+                        "modified": false,
+                    },
+                    {
+                        "pubkey": bob_pubkey.to_string(),
+                        "signer": false,
+                        "writable": true,
+                        "modified": false,
+                    },
+                ],
+            }])
+        );
+    }
+
     #[test]
     #[should_panic(expected = "simulation bank must be frozen")]
     fn test_rpc_simulate_transaction_panic_on_unfrozen_bank() {
@@ -6675,9 +7877,11 @@ pub mod tests {
             optimistically_confirmed_bank,
             Arc::new(RwLock::new(LargestAccountsCache::new(30))),
             Arc::new(MaxSlots::default()),
+            Arc::new(VoteLatencyTracker::default()),
             Arc::new(LeaderScheduleCache::default()),
             Arc::new(AtomicU64::default()),
             Arc::new(AtomicU64::default()),
+            Arc::new(EpochStakeRewardsCache::default()),
             Arc::new(PrioritizationFeeCache::default()),
             service_runtime(rpc_threads, rpc_blocking_threads, rpc_niceness_adj),
         );
@@ -6724,7 +7928,7 @@ pub mod tests {
         assert_eq!(
             res,
             Some(
-                r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"invalid transaction: Transaction failed to sanitize accounts offsets correctly"},"id":1}"#.to_string(),
+                r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"invalid transaction: Transaction failed to sanitize accounts offsets correctly","data":{"violations":[{"kind":"programIdIsFeePayer","instruction":0}]}},"id":1}"#.to_string(),
             )
         );
         let mut bad_transaction = system_transaction::transfer(
@@ -6788,9 +7992,110 @@ pub mod tests {
         assert_eq!(
             res,
             Some(
-                r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"invalid transaction: Transaction failed to sanitize accounts offsets correctly"},"id":1}"#.to_string(),
+                r#"{"jsonrpc":"2.0","error":{"code":-32602,"message":"invalid transaction: Transaction failed to sanitize accounts offsets correctly","data":{"violations":[{"kind":"signatureCountMismatch","numSignatures":0,"numRequiredSignatures":1}]}},"id":1}"#.to_string(),
+            )
+        );
+    }
+
+    #[test]
+    fn test_rpc_send_transaction_preflight_cache() {
+        let rpc = RpcHandler::start_with_config(JsonRpcConfig {
+            enable_rpc_transaction_history: true,
+            skip_preflight_health_check: true,
+            preflight_cache_ttl_ms: 60_000,
+            preflight_cache_capacity: 16,
+            ..JsonRpcConfig::default()
+        });
+        let bank = rpc.working_bank();
+        let recent_blockhash = bank.last_blockhash();
+        // Simulation bank must be frozen
+        bank.freeze();
+
+        // The payer account doesn't exist, so the preflight simulation fails
+        let payer = Keypair::new();
+        let tx = system_transaction::transfer(&payer, &Pubkey::new_unique(), 42, recent_blockhash);
+        let cache_key = PreflightCacheKey {
+            message_hash: tx.message.hash(),
+            slot: bank.slot(),
+            commitment: CommitmentLevel::Processed,
+        };
+        let request = create_test_request(
+            "sendTransaction",
+            Some(json!([
+                bs58::encode(serialize(&tx).unwrap()).into_string(),
+                {"preflightCommitment": "processed"},
+            ])),
+        );
+        assert_eq!(rpc.meta.preflight_cache.get(&cache_key), None);
+        let (code, message) = parse_failure_response(rpc.handle_request_sync(request.clone()));
+        assert_eq!(code, -32002);
+        assert!(message.starts_with("Transaction simulation failed"));
+        assert!(matches!(
+            rpc.meta.preflight_cache.get(&cache_key),
+            Some(Err(PreflightFailure {
+                err: TransactionError::AccountNotFound,
+                ..
+            }))
+        ));
+
+        // Resubmitting the same transaction returns the cached failure
+        assert_eq!(
+            parse_failure_response(rpc.handle_request_sync(request)),
+            (code, message)
+        );
+    }
+
+    #[test]
+    fn test_rpc_send_transaction_fee_payer_inflight_limit() {
+        let rpc = RpcHandler::start_with_config(JsonRpcConfig {
+            max_inflight_transactions_per_fee_payer: Some(2),
+            ..JsonRpcConfig::default()
+        });
+        let limiter = rpc.meta.fee_payer_inflight_limiter.clone().unwrap();
+        let recent_blockhash = rpc.working_bank().last_blockhash();
+        let payer = Keypair::new();
+        let send_request = |payer: &Keypair| {
+            let tx =
+                system_transaction::transfer(payer, &Pubkey::new_unique(), 42, recent_blockhash);
+            create_test_request(
+                "sendTransaction",
+                Some(json!([
+                    bs58::encode(serialize(&tx).unwrap()).into_string(),
+                    {"skipPreflight": true},
+                ])),
             )
+        };
+
+        // The transactions of the fee payer still being sent count against its limit
+        let permits = [
+            limiter.try_acquire(payer.pubkey()).unwrap(),
+            limiter.try_acquire(payer.pubkey()).unwrap(),
+        ];
+        let response = rpc.handle_request_sync(send_request(&payer));
+        let (code, message) = parse_failure_response(response.clone());
+        assert_eq!(
+            code,
+            JSON_RPC_SERVER_ERROR_FEE_PAYER_INFLIGHT_LIMIT_EXCEEDED
+        );
+        assert!(message.contains(&payer.pubkey().to_string()), "{message}");
+        let response = serde_json::to_value(response).unwrap();
+        assert_eq!(
+            response["error"]["data"],
+            json!({"feePayer": payer.pubkey().to_string(), "maxInflight": 2})
         );
+
+        // Other fee payers are not affected
+        let result: String =
+            parse_success_result(rpc.handle_request_sync(send_request(&Keypair::new())));
+        assert!(!result.is_empty());
+
+        // The fee payer can send again once its transactions are done
+        drop(permits);
+        let result: String = parse_success_result(rpc.handle_request_sync(send_request(&payer)));
+        assert!(!result.is_empty());
+        // The send transaction service of the test isn't running, so the transaction is dropped
+        // right away
+        assert_eq!(limiter.inflight(&payer.pubkey()), 0);
     }
 
     #[test]
@@ -6875,6 +8180,144 @@ pub mod tests {
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn test_rpc_get_vote_latency() {
+        let rpc = RpcHandler::start();
+        rpc.vote_latency_tracker
+            .update_with_root_bank(&rpc.working_bank());
+
+        let request = create_test_request("getVoteLatency", None);
+        let result: RpcResponse<RpcVoteLatencyStatus> =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result.value.epoch, 0);
+        assert!(result.value.validators.is_empty());
+
+        let request = create_test_request(
+            "getVoteLatency",
+            Some(json!([{ "votePubkey": rpc.leader_vote_keypair.pubkey().to_string() }])),
+        );
+        let result: RpcResponse<RpcVoteLatencyStatus> =
+            parse_success_result(rpc.handle_request_sync(request));
+        assert!(result.value.validators.is_empty());
+
+        let request = create_test_request(
+            "getVoteLatency",
+            Some(json!([{ "votePubkey": "not a pubkey" }])),
+        );
+        let (code, _message) = parse_failure_response(rpc.handle_request_sync(request));
+        assert_eq!(code, ErrorCode::InvalidParams.code());
+    }
+
+    #[test]
+    fn test_rpc_get_epoch_stake_rewards() {
+        let rpc = RpcHandler::start();
+        let request = create_test_request("getEpochStakeRewards", None);
+        let (code, _message) = parse_failure_response(rpc.handle_request_sync(request));
+        assert_eq!(code, ErrorCode::InvalidParams.code());
+
+        let mut rewards: Vec<_> = (0..3)
+            .map(|i| EpochStakeReward {
+                stake_pubkey: Pubkey::new_unique(),
+                vote_pubkey: Pubkey::new_unique(),
+                lamports: 10 + i,
+                commission: Some(5),
+                effective_stake: 1_000,
+            })
+            .collect();
+        rewards.sort_unstable_by_key(|reward| reward.stake_pubkey);
+        rpc.epoch_stake_rewards_cache.insert(
+            32,
+            EpochStakeRewards {
+                rewarded_epoch: 0,
+                rewards: rewards.clone(),
+            },
+        );
+        // Rewards calculated in a block which is not rooted are not served
+        let request = create_test_request("getEpochStakeRewards", None);
+        let (code, _message) = parse_failure_response(rpc.handle_request_sync(request));
+        assert_eq!(code, ErrorCode::InvalidParams.code());
+        rpc.blockstore.set_roots(std::iter::once(&32)).unwrap();
+        let expected_reward = |reward: &EpochStakeReward| RpcEpochStakeReward {
+            stake_account: reward.stake_pubkey.to_string(),
+            vote_account: reward.vote_pubkey.to_string(),
+            amount: reward.lamports,
+            commission: reward.commission,
+            effective_stake: reward.effective_stake,
+        };
+
+        let request = create_test_request("getEpochStakeRewards", None);
+        let result: RpcEpochStakeRewards = parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result.epoch, 0);
+        assert_eq!(result.effective_slot, 32);
+        assert_eq!(
+            result.rewards,
+            rewards.iter().map(expected_reward).collect::<Vec<_>>()
+        );
+
+        let request = create_test_request(
+            "getEpochStakeRewards",
+            Some(json!([{
+                "epoch": 0,
+                "pagination": { "limit": 2, "after": rewards[0].stake_pubkey.to_string() },
+            }])),
+        );
+        let result: RpcEpochStakeRewards = parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(
+            result.rewards,
+            rewards[1..].iter().map(expected_reward).collect::<Vec<_>>()
+        );
+
+        for params in [
+            json!([{ "epoch": 1 }]),
+            json!([{ "pagination": { "limit": 0 } }]),
+            json!([{ "pagination": { "limit": MAX_GET_EPOCH_STAKE_REWARDS_PAGE_LIMIT + 1 } }]),
+            json!([{ "pagination": { "limit": 1, "after": "not a pubkey" } }]),
+        ] {
+            let request = create_test_request("getEpochStakeRewards", Some(params));
+            let (code, _message) = parse_failure_response(rpc.handle_request_sync(request));
+            assert_eq!(code, ErrorCode::InvalidParams.code());
+        }
+    }
+
+    #[test]
+    fn test_rpc_get_vote_account_epoch_stakes() {
+        let rpc = RpcHandler::start();
+        let bank = rpc.working_bank();
+        let vote_pubkey = rpc.leader_vote_keypair.pubkey();
+
+        let request = create_test_request(
+            "getVoteAccountEpochStakes",
+            Some(json!([vote_pubkey.to_string()])),
+        );
+        let result: RpcResponse<Vec<RpcVoteAccountEpochStake>> =
+            parse_success_result(rpc.handle_request_sync(request));
+        let mut expected_epochs: Vec<_> = bank.epoch_stakes_map().keys().copied().collect();
+        expected_epochs.sort_unstable();
+        assert_eq!(
+            result
+                .value
+                .iter()
+                .map(|epoch_stake| epoch_stake.epoch)
+                .collect::<Vec<_>>(),
+            expected_epochs
+        );
+        for epoch_stake in &result.value {
+            assert_eq!(
+                Some(epoch_stake.activated_stake),
+                bank.vote_account_stake_in_epoch(epoch_stake.epoch, &vote_pubkey)
+            );
+            assert_eq!(
+                Some(epoch_stake.total_activated_stake),
+                bank.epoch_total_stake(epoch_stake.epoch)
+            );
+        }
+
+        let request =
+            create_test_request("getVoteAccountEpochStakes", Some(json!(["not a pubkey"])));
+        let (code, _message) = parse_failure_response(rpc.handle_request_sync(request));
+        assert_eq!(code, ErrorCode::InvalidParams.code());
+    }
+
     #[test]
     fn test_rpc_get_max_slots() {
         let rpc = RpcHandler::start();
@@ -6957,9 +8400,11 @@ pub mod tests {
             optimistically_confirmed_bank,
             Arc::new(RwLock::new(LargestAccountsCache::new(30))),
             Arc::new(MaxSlots::default()),
+            Arc::new(VoteLatencyTracker::default()),
             Arc::new(LeaderScheduleCache::default()),
             Arc::new(AtomicU64::default()),
             Arc::new(AtomicU64::default()),
+            Arc::new(EpochStakeRewardsCache::default()),
             Arc::new(PrioritizationFeeCache::default()),
             service_runtime(rpc_threads, rpc_blocking_threads, rpc_niceness_adj),
         );
@@ -7249,6 +8694,7 @@ pub mod tests {
                 first_slot: 0,
                 last_slot: 8,
             },
+            skipped_slots: None,
         };
         assert_eq!(result.value, expected);
 
@@ -7280,10 +8726,48 @@ pub mod tests {
                 first_slot: 0,
                 last_slot: 4,
             },
+            skipped_slots: None,
         };
         assert_eq!(result.value, expected);
     }
 
+    #[test]
+    fn test_get_block_production_skipped_slot_reasons() {
+        let rpc = RpcHandler::start();
+        // Complete blocks, received before or after the next block of the rooted fork
+        fill_blockstore_slot_with_ticks(&rpc.blockstore, 5, 7, 4, Hash::default());
+        rpc.add_roots_to_blockstore(vec![0, 1, 3, 4, 8]);
+        std::thread::sleep(Duration::from_millis(10));
+        fill_blockstore_slot_with_ticks(&rpc.blockstore, 5, 5, 4, Hash::default());
+        // A block that failed to replay
+        fill_blockstore_slot_with_ticks(&rpc.blockstore, 5, 6, 4, Hash::default());
+        rpc.blockstore.set_dead_slot(6).unwrap();
+        rpc.block_commitment_cache
+            .write()
+            .unwrap()
+            .set_highest_super_majority_root(8);
+
+        let request = create_test_request(
+            "getBlockProduction",
+            Some(json!([{ "skippedSlotReasons": true }])),
+        );
+        let result: RpcResponse<RpcBlockProduction> =
+            parse_success_result(rpc.handle_request_sync(request));
+        let skipped_slot = |slot, reason| RpcSkippedSlot { slot, reason };
+        assert_eq!(
+            result.value.skipped_slots,
+            Some(HashMap::from([(
+                rpc.leader_pubkey().to_string(),
+                vec![
+                    skipped_slot(2, RpcSkippedSlotReason::Offline),
+                    skipped_slot(5, RpcSkippedSlotReason::Late),
+                    skipped_slot(6, RpcSkippedSlotReason::Dead),
+                    skipped_slot(7, RpcSkippedSlotReason::ForkedOff),
+                ],
+            )])),
+        );
+    }
+
     #[test]
     fn test_get_blocks() {
         let rpc = RpcHandler::start();
@@ -7376,6 +8860,146 @@ pub mod tests {
         assert_eq!(result, Vec::<Slot>::new());
     }
 
+    #[test]
+    fn test_get_slot_hashes() {
+        let rpc = RpcHandler::start();
+        rpc.add_roots_to_blockstore(vec![1, 3, 4, 8]);
+        rpc.block_commitment_cache
+            .write()
+            .unwrap()
+            .set_highest_super_majority_root(4);
+        let bank_hashes: HashMap<Slot, Hash> = [1, 4, 8]
+            .into_iter()
+            .map(|slot| (slot, Hash::new_unique()))
+            .collect();
+        for (slot, bank_hash) in &bank_hashes {
+            rpc.blockstore.insert_bank_hash(*slot, *bank_hash, false);
+        }
+        let expected = |slots: &[Slot]| -> Vec<RpcSlotHash> {
+            slots
+                .iter()
+                .map(|slot| RpcSlotHash {
+                    slot: *slot,
+                    hash: bank_hashes[slot].to_string(),
+                })
+                .collect()
+        };
+
+        let request = create_test_request("getSlotHashes", Some(json!([0u64, 10_001u64])));
+        let response = parse_failure_response(rpc.handle_request_sync(request));
+        let expected_error = (
+            ErrorCode::InvalidParams.code(),
+            String::from("Limit too large; max 10000"),
+        );
+        assert_eq!(response, expected_error);
+
+        // The hash of root 3 is unknown, and root 8 is not finalized yet.
+        let request = create_test_request("getSlotHashes", Some(json!([0u64, 10u64])));
+        let result: Vec<RpcSlotHash> = parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result, expected(&[1, 4]));
+
+        let request = create_test_request("getSlotHashes", Some(json!([1u64, 1u64])));
+        let result: Vec<RpcSlotHash> = parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result, expected(&[1]));
+
+        let request = create_test_request("getSlotHashes", Some(json!([2u64, 10u64])));
+        let result: Vec<RpcSlotHash> = parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result, expected(&[4]));
+    }
+
+    #[test]
+    fn test_get_slot_by_block_height_and_time() {
+        let rpc = RpcHandler::start();
+        rpc.add_roots_to_blockstore(vec![1, 3, 4, 8]);
+        rpc.block_commitment_cache
+            .write()
+            .unwrap()
+            .set_highest_super_majority_root(4);
+        for (slot, block_height, block_time) in [(1, 1, 100), (3, 2, 300), (4, 3, 400), (8, 4, 800)]
+        {
+            rpc.blockstore.set_block_height(slot, block_height).unwrap();
+            rpc.blockstore.set_block_time(slot, block_time).unwrap();
+        }
+
+        let request = create_test_request("getSlotByBlockHeight", Some(json!([2u64])));
+        let result: Option<Slot> = parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result, Some(3));
+
+        // Root 8 is not finalized yet
+        let request = create_test_request("getSlotByBlockHeight", Some(json!([4u64])));
+        let result: Option<Slot> = parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result, None);
+
+        let request = create_test_request("getSlotByBlockTime", Some(json!([301i64])));
+        let result: Option<Slot> = parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result, Some(4));
+
+        let request = create_test_request("getSlotByBlockTime", Some(json!([401i64])));
+        let result: Option<Slot> = parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_get_duplicate_votes() {
+        let rpc = RpcHandler::start();
+
+        let vote_pubkey = Pubkey::new_unique();
+        let gossip_tx = Transaction::new_with_payer(&[], Some(&vote_pubkey));
+        let proof = DuplicateVoteProof {
+            vote1: DuplicateVote {
+                hash: Hash::new_unique(),
+                signature: Signature::new_unique(),
+                transaction: None,
+            },
+            vote2: DuplicateVote {
+                hash: Hash::new_unique(),
+                signature: Signature::new_unique(),
+                transaction: Some(gossip_tx.clone()),
+            },
+        };
+        for slot in [3, 5] {
+            rpc.blockstore
+                .store_duplicate_vote(slot, vote_pubkey, &proof)
+                .unwrap();
+        }
+
+        let request = create_test_request("getDuplicateVotes", Some(json!([0u64, 1_001u64])));
+        let response = parse_failure_response(rpc.handle_request_sync(request));
+        let expected_error = (
+            ErrorCode::InvalidParams.code(),
+            String::from("Limit too large; max 1000"),
+        );
+        assert_eq!(response, expected_error);
+
+        let expected = |slot| RpcDuplicateVote {
+            slot,
+            vote_pubkey: vote_pubkey.to_string(),
+            votes: vec![
+                RpcDuplicateVoteEvidence {
+                    hash: proof.vote1.hash.to_string(),
+                    signature: proof.vote1.signature.to_string(),
+                    transaction: None,
+                },
+                RpcDuplicateVoteEvidence {
+                    hash: proof.vote2.hash.to_string(),
+                    signature: proof.vote2.signature.to_string(),
+                    transaction: Some(BASE64_STANDARD.encode(serialize(&gossip_tx).unwrap())),
+                },
+            ],
+        };
+        let request = create_test_request("getDuplicateVotes", Some(json!([0u64, 10u64])));
+        let result: Vec<RpcDuplicateVote> = parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result, vec![expected(3), expected(5)]);
+
+        let request = create_test_request("getDuplicateVotes", Some(json!([0u64, 1u64])));
+        let result: Vec<RpcDuplicateVote> = parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result, vec![expected(3)]);
+
+        let request = create_test_request("getDuplicateVotes", Some(json!([4u64, 10u64])));
+        let result: Vec<RpcDuplicateVote> = parse_success_result(rpc.handle_request_sync(request));
+        assert_eq!(result, vec![expected(5)]);
+    }
+
     #[test]
     fn test_get_block_time() {
         let rpc = RpcHandler::start();
@@ -8647,9 +10271,11 @@ pub mod tests {
             optimistically_confirmed_bank.clone(),
             Arc::new(RwLock::new(LargestAccountsCache::new(30))),
             Arc::new(MaxSlots::default()),
+            Arc::new(VoteLatencyTracker::default()),
             Arc::new(LeaderScheduleCache::default()),
             max_complete_transaction_status_slot,
             max_complete_rewards_slot,
+            Arc::new(EpochStakeRewardsCache::default()),
             Arc::new(PrioritizationFeeCache::default()),
             service_runtime(rpc_threads, rpc_blocking_threads, rpc_niceness_adj),
         );
@@ -8859,10 +10485,18 @@ pub mod tests {
         )
         .unwrap()
         .1;
-        let expect58 = Error::invalid_params(
+        let mut expect58 = Error::invalid_params(
             "invalid transaction: Transaction failed to sanitize accounts offsets correctly"
                 .to_string(),
         );
+        expect58.data = Some(json!({
+            "violations": [{
+                "kind": "programIdIndexOutOfBounds",
+                "instruction": 0,
+                "programIdIndex": 255,
+                "numStaticAccountKeys": 2,
+            }]
+        }));
         assert_eq!(
             sanitize_transaction(
                 unsanitary_versioned_tx,
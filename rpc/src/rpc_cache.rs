@@ -1,8 +1,13 @@
 use {
     solana_rpc_client_api::{config::RpcLargestAccountsFilter, response::RpcAccountBalance},
+    solana_sdk::{
+        clock::Slot, commitment_config::CommitmentLevel, hash::Hash, transaction::TransactionError,
+        transaction_context::TransactionReturnData,
+    },
     std::{
         collections::HashMap,
-        time::{Duration, SystemTime},
+        sync::RwLock,
+        time::{Duration, Instant, SystemTime},
     },
 };
 
@@ -58,6 +63,85 @@ impl LargestAccountsCache {
     }
 }
 
+/// The outcome of a failed `sendTransaction` preflight simulation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PreflightFailure {
+    pub err: TransactionError,
+    pub logs: Vec<String>,
+    pub units_consumed: u64,
+    pub return_data: Option<TransactionReturnData>,
+}
+
+pub(crate) type PreflightResult = Result<(), PreflightFailure>;
+
+/// A preflight result only holds for the bank it was simulated against, so the slot and
+/// commitment of that bank are part of the key along with the message hash.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct PreflightCacheKey {
+    pub message_hash: Hash,
+    pub slot: Slot,
+    pub commitment: CommitmentLevel,
+}
+
+/// Caches `sendTransaction` preflight results for a short time, so that identical
+/// transactions resubmitted in quick succession are not simulated again.
+#[derive(Debug)]
+pub struct PreflightCache {
+    ttl: Duration,
+    capacity: usize,
+    cache: RwLock<HashMap<PreflightCacheKey, PreflightCacheValue>>,
+}
+
+#[derive(Debug)]
+struct PreflightCacheValue {
+    result: PreflightResult,
+    cached_time: Instant,
+}
+
+impl PreflightCache {
+    pub(crate) fn new(ttl: Duration, capacity: usize) -> Self {
+        Self {
+            ttl,
+            capacity,
+            cache: RwLock::default(),
+        }
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        !self.ttl.is_zero() && self.capacity > 0
+    }
+
+    pub(crate) fn get(&self, key: &PreflightCacheKey) -> Option<PreflightResult> {
+        self.cache
+            .read()
+            .unwrap()
+            .get(key)
+            .filter(|value| value.cached_time.elapsed() < self.ttl)
+            .map(|value| value.result.clone())
+    }
+
+    pub(crate) fn insert(&self, key: PreflightCacheKey, result: PreflightResult) {
+        if !self.is_enabled() {
+            return;
+        }
+        let mut cache = self.cache.write().unwrap();
+        if cache.len() >= self.capacity && !cache.contains_key(&key) {
+            cache.retain(|_, value| value.cached_time.elapsed() < self.ttl);
+            if cache.len() >= self.capacity {
+                inc_new_counter_info!("rpc-send-tx_preflight-cache-full", 1);
+                return;
+            }
+        }
+        cache.insert(
+            key,
+            PreflightCacheValue {
+                result,
+                cached_time: Instant::now(),
+            },
+        );
+    }
+}
+
 #[cfg(test)]
 pub mod test {
     use super::*;
@@ -74,4 +158,60 @@ pub mod test {
         std::thread::sleep(Duration::from_secs(1));
         assert_eq!(cache.get_largest_accounts(&filter), None);
     }
+
+    #[test]
+    fn test_preflight_cache() {
+        let cache = PreflightCache::new(Duration::from_millis(200), 2);
+        let failure = PreflightFailure {
+            err: TransactionError::AccountNotFound,
+            logs: vec!["log".to_string()],
+            units_consumed: 42,
+            return_data: None,
+        };
+        let new_key = |slot| PreflightCacheKey {
+            message_hash: Hash::new_unique(),
+            slot,
+            commitment: CommitmentLevel::Confirmed,
+        };
+        let (hash0, hash1, hash2) = (new_key(0), new_key(0), new_key(0));
+
+        assert_eq!(cache.get(&hash0), None);
+        cache.insert(hash0, Ok(()));
+        cache.insert(hash1, Err(failure.clone()));
+        assert_eq!(cache.get(&hash0), Some(Ok(())));
+        assert_eq!(cache.get(&hash1), Some(Err(failure)));
+
+        // The same message simulated against another bank is not a hit
+        for key in [
+            PreflightCacheKey { slot: 1, ..hash0 },
+            PreflightCacheKey {
+                commitment: CommitmentLevel::Processed,
+                ..hash0
+            },
+        ] {
+            assert_eq!(cache.get(&key), None);
+        }
+
+        // No room for another entry until the existing ones expire
+        cache.insert(hash2, Ok(()));
+        assert_eq!(cache.get(&hash2), None);
+
+        std::thread::sleep(Duration::from_millis(200));
+        assert_eq!(cache.get(&hash0), None);
+        cache.insert(hash2, Ok(()));
+        assert_eq!(cache.get(&hash2), Some(Ok(())));
+    }
+
+    #[test]
+    fn test_preflight_cache_disabled() {
+        let cache = PreflightCache::new(Duration::ZERO, 2);
+        assert!(!cache.is_enabled());
+        let hash = PreflightCacheKey {
+            message_hash: Hash::new_unique(),
+            slot: 0,
+            commitment: CommitmentLevel::Finalized,
+        };
+        cache.insert(hash, Ok(()));
+        assert_eq!(cache.get(&hash), None);
+    }
 }
@@ -3,16 +3,19 @@
 use {
     crate::{
         cluster_tpu_info::ClusterTpuInfo,
+        epoch_stake_rewards_cache::EpochStakeRewardsCache,
         max_slots::MaxSlots,
         optimistically_confirmed_bank_tracker::OptimisticallyConfirmedBank,
         rpc::{rpc_accounts::*, rpc_accounts_scan::*, rpc_bank::*, rpc_full::*, rpc_minimal::*, *},
         rpc_cache::LargestAccountsCache,
         rpc_health::*,
+        vote_latency_tracker::VoteLatencyTracker,
     },
     crossbeam_channel::unbounded,
     jsonrpc_core::{futures::prelude::*, MetaIoHandler},
     jsonrpc_http_server::{
-        hyper, AccessControlAllowOrigin, CloseHandle, DomainsValidation, RequestMiddleware,
+        hyper::{self, body::HttpBody},
+        AccessControlAllowOrigin, CloseHandle, DomainsValidation, RequestMiddleware,
         RequestMiddlewareAction, ServerBuilder,
     },
     regex::Regex,
@@ -28,6 +31,7 @@ use {
     solana_poh::poh_recorder::PohRecorder,
     solana_runtime::{
         bank_forks::BankForks, commitment::BlockCommitmentCache,
+        non_circulating_supply::NonCirculatingAccounts,
         prioritization_fee_cache::PrioritizationFeeCache,
         snapshot_archive_info::SnapshotArchiveInfoGetter, snapshot_config::SnapshotConfig,
         snapshot_utils,
@@ -36,7 +40,10 @@ use {
         exit::Exit, genesis_config::DEFAULT_GENESIS_DOWNLOAD_PATH, hash::Hash,
         native_token::lamports_to_sol,
     },
-    solana_send_transaction_service::send_transaction_service::{self, SendTransactionService},
+    solana_send_transaction_service::{
+        inflight_limiter::FeePayerInflightLimiter,
+        send_transaction_service::{self, SendTransactionService},
+    },
     solana_storage_bigtable::CredentialType,
     std::{
         net::SocketAddr,
@@ -53,6 +60,9 @@ use {
 const FULL_SNAPSHOT_REQUEST_PATH: &str = "/snapshot.tar.bz2";
 const INCREMENTAL_SNAPSHOT_REQUEST_PATH: &str = "/incremental-snapshot.tar.bz2";
 const LARGEST_ACCOUNTS_CACHE_DURATION: u64 = 60 * 60 * 2;
+const MULTIPLE_ACCOUNTS_STREAM_PATH: &str = "/v0/multiple-accounts";
+// Large enough for MAX_MULTIPLE_ACCOUNTS_STREAM base58 pubkeys and the account config
+const MAX_MULTIPLE_ACCOUNTS_STREAM_BODY_SIZE: usize = 1 << 20;
 
 pub struct JsonRpcService {
     thread_hdl: JoinHandle<()>,
@@ -60,6 +70,7 @@ pub struct JsonRpcService {
     #[cfg(test)]
     pub request_processor: JsonRpcRequestProcessor, // Used only by test_rpc_new()...
 
+    fee_payer_inflight_limiter: Option<Arc<FeePayerInflightLimiter>>,
     close_handle: Option<CloseHandle>,
 }
 
@@ -70,6 +81,9 @@ struct RpcRequestMiddleware {
     snapshot_config: Option<SnapshotConfig>,
     bank_forks: Arc<RwLock<BankForks>>,
     health: Arc<RpcHealth>,
+    non_circulating_accounts: NonCirculatingAccounts,
+    /// Serves `MULTIPLE_ACCOUNTS_STREAM_PATH`; only set when the full API is enabled
+    request_processor: Option<JsonRpcRequestProcessor>,
 }
 
 impl RpcRequestMiddleware {
@@ -78,6 +92,8 @@ impl RpcRequestMiddleware {
         snapshot_config: Option<SnapshotConfig>,
         bank_forks: Arc<RwLock<BankForks>>,
         health: Arc<RpcHealth>,
+        non_circulating_accounts: NonCirculatingAccounts,
+        request_processor: Option<JsonRpcRequestProcessor>,
     ) -> Self {
         Self {
             ledger_path,
@@ -92,6 +108,8 @@ impl RpcRequestMiddleware {
             snapshot_config,
             bank_forks,
             health,
+            non_circulating_accounts,
+            request_processor,
         }
     }
 
@@ -228,6 +246,74 @@ impl RpcRequestMiddleware {
         }
     }
 
+    fn bad_request(message: String) -> hyper::Response<hyper::Body> {
+        hyper::Response::builder()
+            .status(hyper::StatusCode::BAD_REQUEST)
+            .header(hyper::header::CONTENT_TYPE, "application/json")
+            .body(hyper::Body::from(
+                serde_json::json!({ "error": message }).to_string(),
+            ))
+            .unwrap()
+    }
+
+    async fn read_body(mut body: hyper::Body, limit: usize) -> Result<Vec<u8>, String> {
+        let mut bytes = vec![];
+        while let Some(chunk) = body.data().await {
+            let chunk = chunk.map_err(|err| err.to_string())?;
+            if bytes.len().saturating_add(chunk.len()) > limit {
+                return Err(format!("Request body too large; max {limit} bytes"));
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+        Ok(bytes)
+    }
+
+    /// Streams the requested accounts back as newline-delimited JSON, one
+    /// `RpcMultipleAccountsChunk` per line
+    fn process_multiple_accounts_stream(
+        request_processor: JsonRpcRequestProcessor,
+        request: hyper::Request<hyper::Body>,
+    ) -> RequestMiddlewareAction {
+        inc_new_counter_info!("rpc-get_multiple_accounts_stream", 1);
+        RequestMiddlewareAction::Respond {
+            should_validate_hosts: true,
+            response: Box::pin(async move {
+                let body = match Self::read_body(
+                    request.into_body(),
+                    MAX_MULTIPLE_ACCOUNTS_STREAM_BODY_SIZE,
+                )
+                .await
+                {
+                    Ok(body) => body,
+                    Err(err) => return Ok(Self::bad_request(err)),
+                };
+                let stream_request = match serde_json::from_slice(&body) {
+                    Ok(stream_request) => stream_request,
+                    Err(err) => return Ok(Self::bad_request(format!("Invalid request: {err}"))),
+                };
+                let (pubkeys, config, chunk_size) = match request_processor
+                    .verify_multiple_accounts_stream_request(stream_request)
+                {
+                    Ok(verified) => verified,
+                    Err(err) => return Ok(Self::bad_request(err.message)),
+                };
+                let stream = request_processor
+                    .get_multiple_accounts_stream(pubkeys, config, chunk_size)
+                    .map(|chunk| {
+                        let mut line =
+                            serde_json::to_string(&chunk).map_err(std::io::Error::from)?;
+                        line.push('\n');
+                        Ok::<_, std::io::Error>(line)
+                    });
+                Ok(hyper::Response::builder()
+                    .status(hyper::StatusCode::OK)
+                    .header(hyper::header::CONTENT_TYPE, "application/x-ndjson")
+                    .body(hyper::Body::wrap_stream(stream))
+                    .unwrap())
+            }),
+        }
+    }
+
     fn health_check(&self) -> &'static str {
         let response = match self.health.check() {
             RpcHealthStatus::Ok => "ok",
@@ -287,7 +373,19 @@ impl RequestMiddleware for RpcRequestMiddleware {
             }
         }
 
-        if let Some(result) = process_rest(&self.bank_forks, request.uri().path()) {
+        if request.method() == hyper::Method::POST
+            && request.uri().path() == MULTIPLE_ACCOUNTS_STREAM_PATH
+        {
+            if let Some(request_processor) = &self.request_processor {
+                return Self::process_multiple_accounts_stream(request_processor.clone(), request);
+            }
+        }
+
+        if let Some(result) = process_rest(
+            &self.bank_forks,
+            &self.non_circulating_accounts,
+            request.uri().path(),
+        ) {
             hyper::Response::builder()
                 .status(hyper::StatusCode::OK)
                 .body(hyper::Body::from(result))
@@ -307,19 +405,18 @@ impl RequestMiddleware for RpcRequestMiddleware {
     }
 }
 
-fn process_rest(bank_forks: &Arc<RwLock<BankForks>>, path: &str) -> Option<String> {
+fn process_rest(
+    bank_forks: &Arc<RwLock<BankForks>>,
+    non_circulating_accounts: &NonCirculatingAccounts,
+    path: &str,
+) -> Option<String> {
     match path {
         "/v0/circulating-supply" => {
             let bank = bank_forks.read().unwrap().root_bank();
-            let total_supply = bank.capitalization();
-            let non_circulating_supply =
-                solana_runtime::non_circulating_supply::calculate_non_circulating_supply(&bank)
-                    .expect("Scan should not error on root banks")
-                    .lamports;
-            Some(format!(
-                "{}",
-                lamports_to_sol(total_supply - non_circulating_supply)
-            ))
+            let supply = bank
+                .supply(non_circulating_accounts)
+                .expect("Scan should not error on root banks");
+            Some(format!("{}", lamports_to_sol(supply.circulating)))
         }
         "/v0/total-supply" => {
             let bank = bank_forks.read().unwrap().root_bank();
@@ -350,10 +447,12 @@ impl JsonRpcService {
         optimistically_confirmed_bank: Arc<RwLock<OptimisticallyConfirmedBank>>,
         send_transaction_service_config: send_transaction_service::Config,
         max_slots: Arc<MaxSlots>,
+        vote_latency_tracker: Arc<VoteLatencyTracker>,
         leader_schedule_cache: Arc<LeaderScheduleCache>,
         connection_cache: Arc<ConnectionCache>,
         max_complete_transaction_status_slot: Arc<AtomicU64>,
         max_complete_rewards_slot: Arc<AtomicU64>,
+        epoch_stake_rewards_cache: Arc<EpochStakeRewardsCache>,
         prioritization_fee_cache: Arc<PrioritizationFeeCache>,
     ) -> Result<Self, String> {
         info!("rpc bound to {:?}", rpc_addr);
@@ -441,6 +540,7 @@ impl JsonRpcService {
             };
 
         let full_api = config.full_api;
+        let non_circulating_accounts = config.non_circulating_accounts.clone();
         let max_request_body_size = config
             .max_request_body_size
             .unwrap_or(MAX_REQUEST_BODY_SIZE);
@@ -458,9 +558,11 @@ impl JsonRpcService {
             optimistically_confirmed_bank,
             largest_accounts_cache,
             max_slots,
+            vote_latency_tracker,
             leader_schedule_cache,
             max_complete_transaction_status_slot,
             max_complete_rewards_slot,
+            epoch_stake_rewards_cache,
             prioritization_fee_cache,
             Arc::clone(&runtime),
         );
@@ -479,6 +581,8 @@ impl JsonRpcService {
 
         #[cfg(test)]
         let test_request_processor = request_processor.clone();
+        let fee_payer_inflight_limiter = request_processor.fee_payer_inflight_limiter().cloned();
+        let middleware_request_processor = full_api.then(|| request_processor.clone());
 
         let ledger_path = ledger_path.to_path_buf();
 
@@ -503,6 +607,8 @@ impl JsonRpcService {
                     snapshot_config,
                     bank_forks.clone(),
                     health.clone(),
+                    non_circulating_accounts,
+                    middleware_request_processor,
                 );
                 let server = ServerBuilder::with_meta_extractor(
                     io,
@@ -555,10 +661,17 @@ impl JsonRpcService {
             thread_hdl,
             #[cfg(test)]
             request_processor: test_request_processor,
+            fee_payer_inflight_limiter,
             close_handle: Some(close_handle),
         })
     }
 
+    /// Returns the limiter of the `sendTransaction` transactions of each fee payer pending in the
+    /// send transaction service, if `max_inflight_transactions_per_fee_payer` is configured
+    pub fn fee_payer_inflight_limiter(&self) -> Option<&Arc<FeePayerInflightLimiter>> {
+        self.fee_payer_inflight_limiter.as_ref()
+    }
+
     pub fn exit(&mut self) {
         if let Some(c) = self.close_handle.take() {
             c.close()
@@ -614,12 +727,14 @@ mod tests {
             genesis_utils::{create_genesis_config, GenesisConfigInfo},
             get_tmp_ledger_path_auto_delete,
         },
-        solana_rpc_client_api::config::RpcContextConfig,
+        solana_pubkey::Pubkey,
+        solana_rpc_client_api::{config::RpcContextConfig, response::RpcMultipleAccountsChunk},
         solana_runtime::bank::Bank,
         solana_sdk::{
             genesis_config::{ClusterType, DEFAULT_GENESIS_ARCHIVE},
             signature::Signer,
         },
+        solana_streamer::socket::SocketAddrSpace,
         std::{
             io::Write,
             net::{IpAddr, Ipv4Addr},
@@ -672,10 +787,12 @@ mod tests {
                 ..send_transaction_service::Config::default()
             },
             Arc::new(MaxSlots::default()),
+            Arc::new(VoteLatencyTracker::default()),
             Arc::new(LeaderScheduleCache::default()),
             connection_cache,
             Arc::new(AtomicU64::default()),
             Arc::new(AtomicU64::default()),
+            Arc::new(EpochStakeRewardsCache::default()),
             Arc::new(PrioritizationFeeCache::default()),
         )
         .expect("assume successful JsonRpcService start");
@@ -707,10 +824,22 @@ mod tests {
     fn test_process_rest_api() {
         let bank_forks = create_bank_forks();
 
-        assert_eq!(None, process_rest(&bank_forks, "not-a-supported-rest-api"));
+        let non_circulating_accounts = NonCirculatingAccounts::default();
+        assert_eq!(
+            None,
+            process_rest(
+                &bank_forks,
+                &non_circulating_accounts,
+                "not-a-supported-rest-api"
+            )
+        );
         assert_eq!(
-            process_rest(&bank_forks, "/v0/circulating-supply"),
-            process_rest(&bank_forks, "/v0/total-supply")
+            process_rest(
+                &bank_forks,
+                &non_circulating_accounts,
+                "/v0/circulating-supply"
+            ),
+            process_rest(&bank_forks, &non_circulating_accounts, "/v0/total-supply")
         );
     }
 
@@ -758,12 +887,16 @@ mod tests {
             None,
             bank_forks.clone(),
             health.clone(),
+            NonCirculatingAccounts::default(),
+            None,
         );
         let rrm_with_snapshot_config = RpcRequestMiddleware::new(
             ledger_path.path().to_path_buf(),
             Some(SnapshotConfig::default()),
             bank_forks,
             health,
+            NonCirculatingAccounts::default(),
+            None,
         );
 
         assert!(rrm.is_file_get_path(DEFAULT_GENESIS_DOWNLOAD_PATH));
@@ -851,6 +984,67 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_process_multiple_accounts_stream() {
+        let runtime = Runtime::new().unwrap();
+        let GenesisConfigInfo {
+            genesis_config,
+            mint_keypair,
+            ..
+        } = create_genesis_config(10_000);
+        let request_processor = JsonRpcRequestProcessor::new_from_bank(
+            Bank::new_for_tests(&genesis_config),
+            SocketAddrSpace::Unspecified,
+            Arc::new(ConnectionCache::new("connection_cache_test")),
+        );
+        let pubkeys = [
+            mint_keypair.pubkey(),
+            Pubkey::new_unique(),
+            Pubkey::new_unique(),
+        ]
+        .iter()
+        .map(|pubkey| pubkey.to_string())
+        .collect::<Vec<_>>();
+
+        let send = |body: serde_json::Value| {
+            let request = hyper::Request::post(MULTIPLE_ACCOUNTS_STREAM_PATH)
+                .body(hyper::Body::from(body.to_string()))
+                .unwrap();
+            let action = RpcRequestMiddleware::process_multiple_accounts_stream(
+                request_processor.clone(),
+                request,
+            );
+            let RequestMiddlewareAction::Respond { response, .. } = action else {
+                panic!("Unexpected RequestMiddlewareAction variant");
+            };
+            runtime.block_on(async {
+                let response = response.await.unwrap();
+                let status = response.status();
+                let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+                (status, String::from_utf8(body.to_vec()).unwrap())
+            })
+        };
+
+        let (status, body) = send(serde_json::json!({"pubkeys": pubkeys, "chunkSize": 2}));
+        assert_eq!(status, hyper::StatusCode::OK);
+        let chunks = body
+            .lines()
+            .map(|line| serde_json::from_str::<RpcMultipleAccountsChunk>(line).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].offset, 0);
+        assert_eq!(chunks[1].offset, 2);
+        let accounts = chunks[0].value.as_ref().unwrap();
+        assert_eq!(accounts[0].as_ref().unwrap().lamports, 10_000);
+        assert!(accounts[1].is_none());
+        assert_eq!(chunks[1].value.as_ref().unwrap().len(), 1);
+
+        let (status, _) = send(serde_json::json!({"pubkeys": ["invalid"]}));
+        assert_eq!(status, hyper::StatusCode::BAD_REQUEST);
+        let (status, _) = send(serde_json::json!({"keys": pubkeys}));
+        assert_eq!(status, hyper::StatusCode::BAD_REQUEST);
+    }
+
     #[test]
     fn test_process_file_get() {
         let runtime = Runtime::new().unwrap();
@@ -866,6 +1060,8 @@ mod tests {
             None,
             bank_forks,
             RpcHealth::stub(optimistically_confirmed_bank, blockstore),
+            NonCirculatingAccounts::default(),
+            None,
         );
 
         // File does not exist => request should fail.
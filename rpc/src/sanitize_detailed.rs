@@ -0,0 +1,387 @@
+//! Detailed transaction sanitization diagnostics.
+//!
+//! `VersionedTransaction::sanitize` stops at the first violated constraint and reports it as an
+//! opaque `SanitizeError`. [`SanitizeDetailed`] checks the same constraints, plus duplicate
+//! account keys, and reports every violation, so that RPC clients are told what to fix when
+//! preflight rejects a transaction.
+use {
+    solana_sdk::{
+        message::{MessageHeader, VersionedMessage},
+        transaction::VersionedTransaction,
+    },
+    std::{collections::HashSet, fmt},
+};
+
+#[derive(Serialize, Clone, Debug, PartialEq, Eq)]
+#[serde(
+    tag = "kind",
+    rename_all = "camelCase",
+    rename_all_fields = "camelCase"
+)]
+pub enum SanitizeViolation {
+    /// The header requires more signed and readonly unsigned accounts than there are keys
+    NotEnoughAccountKeys {
+        num_required_signatures: u8,
+        num_readonly_unsigned_accounts: u8,
+        num_account_keys: usize,
+    },
+    /// Every signer is readonly, so there is no writable fee payer
+    NoWritableFeePayer {
+        num_required_signatures: u8,
+        num_readonly_signed_accounts: u8,
+    },
+    /// The static and looked up account keys can't all be indexed by a u8
+    TooManyAccountKeys { num_account_keys: usize },
+    /// An address table lookup loads no account
+    EmptyAddressTableLookup { lookup: usize },
+    /// An account key is listed more than once
+    DuplicateAccountKey { pubkey: String },
+    /// An instruction's program id index doesn't refer to a static account key
+    ProgramIdIndexOutOfBounds {
+        instruction: usize,
+        program_id_index: u8,
+        num_static_account_keys: usize,
+    },
+    /// An instruction uses the fee payer as its program
+    ProgramIdIsFeePayer { instruction: usize },
+    /// An instruction's account index doesn't refer to an account key
+    AccountIndexOutOfBounds {
+        instruction: usize,
+        account_index: u8,
+        num_account_keys: usize,
+    },
+    /// The number of signatures doesn't match the number of required signatures
+    SignatureCountMismatch {
+        num_signatures: usize,
+        num_required_signatures: u8,
+    },
+    /// There are more signatures than static account keys to sign with
+    TooManySignatures {
+        num_signatures: usize,
+        num_static_account_keys: usize,
+    },
+}
+
+impl fmt::Display for SanitizeViolation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NotEnoughAccountKeys {
+                num_required_signatures,
+                num_readonly_unsigned_accounts,
+                num_account_keys,
+            } => write!(
+                f,
+                "{num_required_signatures} signed and {num_readonly_unsigned_accounts} readonly \
+                 unsigned accounts required, but only {num_account_keys} account keys given"
+            ),
+            Self::NoWritableFeePayer {
+                num_required_signatures,
+                num_readonly_signed_accounts,
+            } => write!(
+                f,
+                "{num_readonly_signed_accounts} of {num_required_signatures} signed accounts are \
+                 readonly, leaving no writable fee payer"
+            ),
+            Self::TooManyAccountKeys { num_account_keys } => {
+                write!(
+                    f,
+                    "{num_account_keys} account keys, more than the 256 allowed"
+                )
+            }
+            Self::EmptyAddressTableLookup { lookup } => {
+                write!(f, "address table lookup {lookup} loads no account")
+            }
+            Self::DuplicateAccountKey { pubkey } => write!(f, "account key {pubkey} is duplicated"),
+            Self::ProgramIdIndexOutOfBounds {
+                instruction,
+                program_id_index,
+                num_static_account_keys,
+            } => write!(
+                f,
+                "instruction {instruction} program id index {program_id_index} is out of bounds \
+                 of the {num_static_account_keys} static account keys"
+            ),
+            Self::ProgramIdIsFeePayer { instruction } => {
+                write!(
+                    f,
+                    "instruction {instruction} uses the fee payer as its program id"
+                )
+            }
+            Self::AccountIndexOutOfBounds {
+                instruction,
+                account_index,
+                num_account_keys,
+            } => write!(
+                f,
+                "instruction {instruction} account index {account_index} is out of bounds of the \
+                 {num_account_keys} account keys"
+            ),
+            Self::SignatureCountMismatch {
+                num_signatures,
+                num_required_signatures,
+            } => write!(
+                f,
+                "{num_signatures} signatures given, but {num_required_signatures} required"
+            ),
+            Self::TooManySignatures {
+                num_signatures,
+                num_static_account_keys,
+            } => write!(
+                f,
+                "{num_signatures} signatures given, but only {num_static_account_keys} static \
+                 account keys"
+            ),
+        }
+    }
+}
+
+/// Sanitization reporting every violated constraint rather than the first one
+pub trait SanitizeDetailed {
+    /// Returns the violated constraints, empty if sanitization passes
+    fn sanitize_detailed(&self) -> Vec<SanitizeViolation>;
+}
+
+impl SanitizeDetailed for VersionedMessage {
+    fn sanitize_detailed(&self) -> Vec<SanitizeViolation> {
+        let mut violations = vec![];
+        let header = self.header();
+        let static_account_keys = self.static_account_keys();
+        let num_static_account_keys = static_account_keys.len();
+        check_header(header, num_static_account_keys, &mut violations);
+
+        let num_account_keys = match self {
+            VersionedMessage::Legacy(_) => num_static_account_keys,
+            VersionedMessage::V0(message) => {
+                let mut num_account_keys = num_static_account_keys;
+                for (lookup, address_table_lookup) in
+                    message.address_table_lookups.iter().enumerate()
+                {
+                    let num_lookup_keys = address_table_lookup
+                        .writable_indexes
+                        .len()
+                        .saturating_add(address_table_lookup.readonly_indexes.len());
+                    if num_lookup_keys == 0 {
+                        violations.push(SanitizeViolation::EmptyAddressTableLookup { lookup });
+                    }
+                    num_account_keys = num_account_keys.saturating_add(num_lookup_keys);
+                }
+                if num_account_keys > 256 {
+                    violations.push(SanitizeViolation::TooManyAccountKeys { num_account_keys });
+                }
+                num_account_keys
+            }
+        };
+
+        let mut unique_keys = HashSet::with_capacity(num_static_account_keys);
+        let mut duplicate_keys = HashSet::new();
+        for pubkey in static_account_keys {
+            if !unique_keys.insert(pubkey) && duplicate_keys.insert(pubkey) {
+                violations.push(SanitizeViolation::DuplicateAccountKey {
+                    pubkey: pubkey.to_string(),
+                });
+            }
+        }
+
+        for (instruction, compiled_instruction) in self.instructions().iter().enumerate() {
+            let program_id_index = compiled_instruction.program_id_index;
+            if usize::from(program_id_index) >= num_static_account_keys {
+                violations.push(SanitizeViolation::ProgramIdIndexOutOfBounds {
+                    instruction,
+                    program_id_index,
+                    num_static_account_keys,
+                });
+            } else if program_id_index == 0 {
+                violations.push(SanitizeViolation::ProgramIdIsFeePayer { instruction });
+            }
+            for &account_index in &compiled_instruction.accounts {
+                if usize::from(account_index) >= num_account_keys {
+                    violations.push(SanitizeViolation::AccountIndexOutOfBounds {
+                        instruction,
+                        account_index,
+                        num_account_keys,
+                    });
+                }
+            }
+        }
+        violations
+    }
+}
+
+impl SanitizeDetailed for VersionedTransaction {
+    fn sanitize_detailed(&self) -> Vec<SanitizeViolation> {
+        let mut violations = self.message.sanitize_detailed();
+        let num_signatures = self.signatures.len();
+        let num_required_signatures = self.message.header().num_required_signatures;
+        if num_signatures != usize::from(num_required_signatures) {
+            violations.push(SanitizeViolation::SignatureCountMismatch {
+                num_signatures,
+                num_required_signatures,
+            });
+        }
+        let num_static_account_keys = self.message.static_account_keys().len();
+        if num_signatures > num_static_account_keys {
+            violations.push(SanitizeViolation::TooManySignatures {
+                num_signatures,
+                num_static_account_keys,
+            });
+        }
+        violations
+    }
+}
+
+fn check_header(
+    header: &MessageHeader,
+    num_static_account_keys: usize,
+    violations: &mut Vec<SanitizeViolation>,
+) {
+    if usize::from(header.num_required_signatures)
+        .saturating_add(usize::from(header.num_readonly_unsigned_accounts))
+        > num_static_account_keys
+    {
+        violations.push(SanitizeViolation::NotEnoughAccountKeys {
+            num_required_signatures: header.num_required_signatures,
+            num_readonly_unsigned_accounts: header.num_readonly_unsigned_accounts,
+            num_account_keys: num_static_account_keys,
+        });
+    }
+    if header.num_readonly_signed_accounts >= header.num_required_signatures {
+        violations.push(SanitizeViolation::NoWritableFeePayer {
+            num_required_signatures: header.num_required_signatures,
+            num_readonly_signed_accounts: header.num_readonly_signed_accounts,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_sdk::{
+            instruction::CompiledInstruction,
+            message::{v0, v0::MessageAddressTableLookup, Message},
+            pubkey::Pubkey,
+            signature::Signature,
+        },
+    };
+
+    #[test]
+    fn test_sanitize_detailed_legacy() {
+        let fee_payer = Pubkey::new_unique();
+        let program_id = Pubkey::new_unique();
+        let mut message = Message {
+            header: MessageHeader {
+                num_required_signatures: 1,
+                num_readonly_signed_accounts: 0,
+                num_readonly_unsigned_accounts: 1,
+            },
+            account_keys: vec![fee_payer, program_id],
+            instructions: vec![CompiledInstruction::new_from_raw_parts(1, vec![], vec![0])],
+            ..Message::default()
+        };
+        let transaction = VersionedTransaction {
+            signatures: vec![Signature::default()],
+            message: VersionedMessage::Legacy(message.clone()),
+        };
+        assert_eq!(transaction.sanitize_detailed(), vec![]);
+
+        // Every violation is reported, not only the first one
+        message.header.num_readonly_signed_accounts = 1;
+        message.account_keys.push(fee_payer);
+        message.instructions = vec![
+            CompiledInstruction::new_from_raw_parts(0, vec![], vec![3]),
+            CompiledInstruction::new_from_raw_parts(255, vec![], vec![]),
+        ];
+        let transaction = VersionedTransaction {
+            signatures: vec![Signature::default(); 4],
+            message: VersionedMessage::Legacy(message),
+        };
+        assert_eq!(
+            transaction.sanitize_detailed(),
+            vec![
+                SanitizeViolation::NoWritableFeePayer {
+                    num_required_signatures: 1,
+                    num_readonly_signed_accounts: 1,
+                },
+                SanitizeViolation::DuplicateAccountKey {
+                    pubkey: fee_payer.to_string(),
+                },
+                SanitizeViolation::ProgramIdIsFeePayer { instruction: 0 },
+                SanitizeViolation::AccountIndexOutOfBounds {
+                    instruction: 0,
+                    account_index: 3,
+                    num_account_keys: 3,
+                },
+                SanitizeViolation::ProgramIdIndexOutOfBounds {
+                    instruction: 1,
+                    program_id_index: 255,
+                    num_static_account_keys: 3,
+                },
+                SanitizeViolation::SignatureCountMismatch {
+                    num_signatures: 4,
+                    num_required_signatures: 1,
+                },
+                SanitizeViolation::TooManySignatures {
+                    num_signatures: 4,
+                    num_static_account_keys: 3,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sanitize_detailed_v0() {
+        let mut message = v0::Message {
+            header: MessageHeader {
+                num_required_signatures: 1,
+                num_readonly_signed_accounts: 0,
+                num_readonly_unsigned_accounts: 1,
+            },
+            account_keys: vec![Pubkey::new_unique(), Pubkey::new_unique()],
+            instructions: vec![CompiledInstruction::new_from_raw_parts(
+                1,
+                vec![],
+                vec![0, 2],
+            )],
+            address_table_lookups: vec![MessageAddressTableLookup {
+                account_key: Pubkey::new_unique(),
+                writable_indexes: vec![0],
+                readonly_indexes: vec![],
+            }],
+            ..v0::Message::default()
+        };
+        assert_eq!(
+            VersionedMessage::V0(message.clone()).sanitize_detailed(),
+            vec![]
+        );
+
+        // Looked up accounts can't be programs, and lookups must load accounts
+        message.instructions = vec![CompiledInstruction::new_from_raw_parts(2, vec![], vec![])];
+        message
+            .address_table_lookups
+            .push(MessageAddressTableLookup {
+                account_key: Pubkey::new_unique(),
+                writable_indexes: vec![],
+                readonly_indexes: vec![],
+            });
+        assert_eq!(
+            VersionedMessage::V0(message).sanitize_detailed(),
+            vec![
+                SanitizeViolation::EmptyAddressTableLookup { lookup: 1 },
+                SanitizeViolation::ProgramIdIndexOutOfBounds {
+                    instruction: 0,
+                    program_id_index: 2,
+                    num_static_account_keys: 2,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sanitize_violation_serialization() {
+        assert_eq!(
+            serde_json::to_value(SanitizeViolation::ProgramIdIsFeePayer { instruction: 2 })
+                .unwrap(),
+            json!({"kind": "programIdIsFeePayer", "instruction": 2}),
+        );
+    }
+}
@@ -0,0 +1,364 @@
+//! The `slot_status_events` module publishes the lifecycle transitions of slots as a typed event
+//! stream, for consumers inside the process (tools, tests, services) which would otherwise poll
+//! the blockstore slot metas or the bank forks.
+//!
+//! The events are produced in two places:
+//! * `SlotStatusEventNotifier`, a `SlotStatusNotifierInterface` passed to the stages which
+//!   receive shreds and replay slots: first shred received, bank created and dead. It is also
+//!   passed to the `RpcCompletedSlotsService`, the only producer of completed events, which
+//!   only runs with the full RPC API or geyser plugins enabled.
+//! * `SlotStatusEventService`, which consumes the `SlotNotification`s of the
+//!   `OptimisticallyConfirmedBankTracker`: processed (frozen), confirmed and rooted. These are
+//!   only available when the RPC services are running.
+
+use {
+    crate::{
+        optimistically_confirmed_bank_tracker::{SlotNotification, SlotNotificationReceiver},
+        slot_status_notifier::{SlotStatusNotifier, SlotStatusNotifierInterface},
+    },
+    crossbeam_channel::{bounded, Receiver, RecvTimeoutError, Sender},
+    solana_sdk::clock::Slot,
+    std::{
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc, RwLock,
+        },
+        thread::{Builder, JoinHandle},
+        time::Duration,
+    },
+};
+
+const SLOT_NOTIFICATION_RECV_TIMEOUT: Duration = Duration::from_millis(100);
+/// Maximum number of events queued to a subscriber before it is unsubscribed
+pub const MAX_QUEUED_SLOT_STATUS_EVENTS: usize = 10_000;
+
+/// A transition in the lifecycle of a slot, mirroring the `slotsUpdatesSubscribe` updates
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SlotStatusEvent {
+    FirstShredReceived {
+        slot: Slot,
+    },
+    Completed {
+        slot: Slot,
+    },
+    CreatedBank {
+        slot: Slot,
+        parent: Slot,
+    },
+    /// The bank of the slot was frozen after replaying all its entries
+    Processed {
+        slot: Slot,
+        parent: Slot,
+    },
+    OptimisticallyConfirmed {
+        slot: Slot,
+    },
+    Rooted {
+        slot: Slot,
+        parent: Slot,
+    },
+    Dead {
+        slot: Slot,
+        error: String,
+    },
+}
+
+impl SlotStatusEvent {
+    pub fn slot(&self) -> Slot {
+        match self {
+            Self::FirstShredReceived { slot }
+            | Self::Completed { slot }
+            | Self::CreatedBank { slot, .. }
+            | Self::Processed { slot, .. }
+            | Self::OptimisticallyConfirmed { slot }
+            | Self::Rooted { slot, .. }
+            | Self::Dead { slot, .. } => *slot,
+        }
+    }
+}
+
+pub type SlotStatusEventReceiver = Receiver<SlotStatusEvent>;
+
+/// Fans the slot status events out to every subscriber
+#[derive(Debug, Default)]
+pub struct SlotStatusEvents {
+    subscribers: RwLock<Vec<Sender<SlotStatusEvent>>>,
+}
+
+impl SlotStatusEvents {
+    /// Returns a receiver of all the events published from now on. The subscription ends
+    /// when the receiver is dropped, or once it falls `MAX_QUEUED_SLOT_STATUS_EVENTS` events
+    /// behind, in which case the receiver is disconnected after the queued events.
+    pub fn subscribe(&self) -> SlotStatusEventReceiver {
+        let (sender, receiver) = bounded(MAX_QUEUED_SLOT_STATUS_EVENTS);
+        self.subscribers.write().unwrap().push(sender);
+        receiver
+    }
+
+    pub fn num_subscribers(&self) -> usize {
+        self.subscribers.read().unwrap().len()
+    }
+
+    /// Sends the event to every subscriber, dropping those whose receiver is gone or full
+    pub fn publish(&self, event: SlotStatusEvent) {
+        let unsubscribed: Vec<_> = self
+            .subscribers
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|subscriber| subscriber.try_send(event.clone()).is_err())
+            .cloned()
+            .collect();
+        if !unsubscribed.is_empty() {
+            self.subscribers.write().unwrap().retain(|subscriber| {
+                !unsubscribed
+                    .iter()
+                    .any(|unsubscribed| unsubscribed.same_channel(subscriber))
+            });
+        }
+    }
+}
+
+/// Publishes the notifications it receives as events, and forwards them to the wrapped
+/// notifier, if any (e.g. the geyser plugins)
+pub struct SlotStatusEventNotifier {
+    events: Arc<SlotStatusEvents>,
+    notifier: Option<SlotStatusNotifier>,
+}
+
+impl SlotStatusEventNotifier {
+    pub fn new_rw_arc(
+        events: Arc<SlotStatusEvents>,
+        notifier: Option<SlotStatusNotifier>,
+    ) -> SlotStatusNotifier {
+        Arc::new(RwLock::new(Self { events, notifier }))
+    }
+
+    fn notify(&self, f: impl FnOnce(&dyn SlotStatusNotifierInterface), event: SlotStatusEvent) {
+        if let Some(notifier) = &self.notifier {
+            f(&*notifier.read().unwrap());
+        }
+        self.events.publish(event);
+    }
+}
+
+impl SlotStatusNotifierInterface for SlotStatusEventNotifier {
+    fn notify_slot_confirmed(&self, slot: Slot, parent: Option<Slot>) {
+        self.notify(
+            |notifier| notifier.notify_slot_confirmed(slot, parent),
+            SlotStatusEvent::OptimisticallyConfirmed { slot },
+        );
+    }
+
+    fn notify_slot_processed(&self, slot: Slot, parent: Option<Slot>) {
+        self.notify(
+            |notifier| notifier.notify_slot_processed(slot, parent),
+            SlotStatusEvent::Processed {
+                slot,
+                parent: parent.unwrap_or_default(),
+            },
+        );
+    }
+
+    fn notify_slot_rooted(&self, slot: Slot, parent: Option<Slot>) {
+        self.notify(
+            |notifier| notifier.notify_slot_rooted(slot, parent),
+            SlotStatusEvent::Rooted {
+                slot,
+                parent: parent.unwrap_or_default(),
+            },
+        );
+    }
+
+    fn notify_first_shred_received(&self, slot: Slot) {
+        self.notify(
+            |notifier| notifier.notify_first_shred_received(slot),
+            SlotStatusEvent::FirstShredReceived { slot },
+        );
+    }
+
+    fn notify_completed(&self, slot: Slot) {
+        self.notify(
+            |notifier| notifier.notify_completed(slot),
+            SlotStatusEvent::Completed { slot },
+        );
+    }
+
+    fn notify_created_bank(&self, slot: Slot, parent: Slot) {
+        self.notify(
+            |notifier| notifier.notify_created_bank(slot, parent),
+            SlotStatusEvent::CreatedBank { slot, parent },
+        );
+    }
+
+    fn notify_slot_dead(&self, slot: Slot, error: String) {
+        self.notify(
+            |notifier| notifier.notify_slot_dead(slot, error.clone()),
+            SlotStatusEvent::Dead { slot, error },
+        );
+    }
+}
+
+/// Publishes the slot notifications of the `OptimisticallyConfirmedBankTracker` as events.
+///
+/// These are published here rather than through `SlotStatusEventNotifier` because the geyser
+/// plugins receive them through their own `SlotNotification` subscription.
+pub struct SlotStatusEventService;
+impl SlotStatusEventService {
+    pub fn spawn(
+        slot_notification_receiver: SlotNotificationReceiver,
+        events: Arc<SlotStatusEvents>,
+        exit: Arc<AtomicBool>,
+    ) -> JoinHandle<()> {
+        Builder::new()
+            .name("solSlotStatusEv".to_string())
+            .spawn(move || {
+                while !exit.load(Ordering::Relaxed) {
+                    match slot_notification_receiver.recv_timeout(SLOT_NOTIFICATION_RECV_TIMEOUT) {
+                        Err(RecvTimeoutError::Timeout) => {}
+                        Err(RecvTimeoutError::Disconnected) => break,
+                        Ok(notification) => events.publish(SlotStatusEvent::from(notification)),
+                    }
+                }
+            })
+            .unwrap()
+    }
+}
+
+impl From<SlotNotification> for SlotStatusEvent {
+    fn from(notification: SlotNotification) -> Self {
+        match notification {
+            SlotNotification::OptimisticallyConfirmed(slot) => {
+                Self::OptimisticallyConfirmed { slot }
+            }
+            SlotNotification::Frozen((slot, parent)) => Self::Processed { slot, parent },
+            SlotNotification::Root((slot, parent)) => Self::Rooted { slot, parent },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crossbeam_channel::unbounded, std::sync::Mutex};
+
+    #[derive(Default)]
+    struct TestNotifier {
+        completed: Mutex<Vec<Slot>>,
+    }
+
+    impl SlotStatusNotifierInterface for TestNotifier {
+        fn notify_slot_confirmed(&self, _slot: Slot, _parent: Option<Slot>) {}
+        fn notify_slot_processed(&self, _slot: Slot, _parent: Option<Slot>) {}
+        fn notify_slot_rooted(&self, _slot: Slot, _parent: Option<Slot>) {}
+        fn notify_first_shred_received(&self, _slot: Slot) {}
+        fn notify_completed(&self, slot: Slot) {
+            self.completed.lock().unwrap().push(slot);
+        }
+        fn notify_created_bank(&self, _slot: Slot, _parent: Slot) {}
+        fn notify_slot_dead(&self, _slot: Slot, _error: String) {}
+    }
+
+    #[test]
+    fn test_slot_status_event_notifier() {
+        let events = Arc::<SlotStatusEvents>::default();
+        let test_notifier = Arc::new(RwLock::new(TestNotifier::default()));
+        let notifier = SlotStatusEventNotifier::new_rw_arc(
+            events.clone(),
+            Some(test_notifier.clone() as SlotStatusNotifier),
+        );
+
+        // Events published before subscribing are not received
+        notifier.read().unwrap().notify_first_shred_received(1);
+        let receiver = events.subscribe();
+        let other_receiver = events.subscribe();
+        assert_eq!(events.num_subscribers(), 2);
+
+        let notifier = notifier.read().unwrap();
+        notifier.notify_first_shred_received(2);
+        notifier.notify_created_bank(2, 1);
+        notifier.notify_completed(2);
+        notifier.notify_slot_dead(3, "error".to_string());
+        let expected = vec![
+            SlotStatusEvent::FirstShredReceived { slot: 2 },
+            SlotStatusEvent::CreatedBank { slot: 2, parent: 1 },
+            SlotStatusEvent::Completed { slot: 2 },
+            SlotStatusEvent::Dead {
+                slot: 3,
+                error: "error".to_string(),
+            },
+        ];
+        assert_eq!(receiver.try_iter().collect::<Vec<_>>(), expected);
+        assert_eq!(other_receiver.try_iter().collect::<Vec<_>>(), expected);
+        assert_eq!(
+            *test_notifier.read().unwrap().completed.lock().unwrap(),
+            [2]
+        );
+
+        // Dropped receivers are unsubscribed
+        drop(other_receiver);
+        notifier.notify_completed(4);
+        assert_eq!(events.num_subscribers(), 1);
+        assert_eq!(
+            receiver.try_iter().collect::<Vec<_>>(),
+            [SlotStatusEvent::Completed { slot: 4 }]
+        );
+    }
+
+    #[test]
+    fn test_unsubscribe_full_receiver() {
+        let events = SlotStatusEvents::default();
+        let receiver = events.subscribe();
+        let other_receiver = events.subscribe();
+        for slot in 0..MAX_QUEUED_SLOT_STATUS_EVENTS as Slot {
+            events.publish(SlotStatusEvent::Completed { slot });
+        }
+        assert_eq!(
+            other_receiver.try_iter().count(),
+            MAX_QUEUED_SLOT_STATUS_EVENTS
+        );
+        assert_eq!(events.num_subscribers(), 2);
+
+        // The receiver which fell behind is disconnected after its queued events
+        events.publish(SlotStatusEvent::Completed { slot: 1_000_000 });
+        assert_eq!(events.num_subscribers(), 1);
+        assert_eq!(
+            other_receiver.try_recv(),
+            Ok(SlotStatusEvent::Completed { slot: 1_000_000 })
+        );
+        assert_eq!(receiver.try_iter().count(), MAX_QUEUED_SLOT_STATUS_EVENTS);
+        assert!(receiver.recv().is_err());
+    }
+
+    #[test]
+    fn test_slot_status_event_service() {
+        let events = Arc::<SlotStatusEvents>::default();
+        let receiver = events.subscribe();
+        let exit = Arc::new(AtomicBool::new(false));
+        let (slot_notification_sender, slot_notification_receiver) = unbounded();
+        let thread_hdl =
+            SlotStatusEventService::spawn(slot_notification_receiver, events, exit.clone());
+
+        for notification in [
+            SlotNotification::Frozen((2, 1)),
+            SlotNotification::OptimisticallyConfirmed(2),
+            SlotNotification::Root((2, 1)),
+        ] {
+            slot_notification_sender.send(notification).unwrap();
+        }
+        let timeout = Duration::from_secs(5);
+        assert_eq!(
+            receiver.recv_timeout(timeout).unwrap(),
+            SlotStatusEvent::Processed { slot: 2, parent: 1 }
+        );
+        assert_eq!(
+            receiver.recv_timeout(timeout).unwrap(),
+            SlotStatusEvent::OptimisticallyConfirmed { slot: 2 }
+        );
+        let event = receiver.recv_timeout(timeout).unwrap();
+        assert_eq!(event, SlotStatusEvent::Rooted { slot: 2, parent: 1 });
+        assert_eq!(event.slot(), 2);
+
+        exit.store(true, Ordering::Relaxed);
+        thread_hdl.join().unwrap();
+    }
+}
@@ -0,0 +1,245 @@
+//! The `vote_latency_tracker` module tracks, for each vote account, the
+//! distribution of vote latencies within the current epoch. The latency of a
+//! vote is the number of slots between the voted slot and the slot in which
+//! the vote landed, as recorded in the rooted vote state.
+
+use {
+    solana_runtime::bank::Bank,
+    solana_sdk::{
+        clock::{Epoch, Slot},
+        pubkey::Pubkey,
+        timing::AtomicInterval,
+    },
+    std::{collections::HashMap, sync::RwLock},
+};
+
+/// Latencies at or above this value are counted in the last histogram bucket.
+pub const MAX_TRACKED_VOTE_LATENCY: u8 = 32;
+/// Size of the latency histogram, indexed by latency in slots.
+pub const VOTE_LATENCY_HISTOGRAM_SIZE: usize = MAX_TRACKED_VOTE_LATENCY as usize + 1;
+
+const VOTE_LATENCY_REPORT_INTERVAL_MS: u64 = 10_000;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VoteLatencyStats {
+    pub node_pubkey: Pubkey,
+    /// Number of votes per latency in slots. Latency is always at least one
+    /// slot, so the first bucket is unused.
+    pub histogram: [u64; VOTE_LATENCY_HISTOGRAM_SIZE],
+    // Sum of the unclamped latencies, so that outliers beyond the last
+    // histogram bucket are fully accounted for in the mean.
+    total_latency: u64,
+}
+
+impl VoteLatencyStats {
+    fn new(node_pubkey: Pubkey) -> Self {
+        Self {
+            node_pubkey,
+            histogram: [0; VOTE_LATENCY_HISTOGRAM_SIZE],
+            total_latency: 0,
+        }
+    }
+
+    fn record(&mut self, latency: u8) {
+        let bucket = latency.min(MAX_TRACKED_VOTE_LATENCY);
+        self.histogram[usize::from(bucket)] += 1;
+        self.total_latency += u64::from(latency);
+    }
+
+    fn merge(&mut self, other: &Self) {
+        for (count, other) in self.histogram.iter_mut().zip(&other.histogram) {
+            *count += other;
+        }
+        self.total_latency += other.total_latency;
+    }
+
+    pub fn num_votes(&self) -> u64 {
+        self.histogram.iter().sum()
+    }
+
+    pub fn mean(&self) -> f64 {
+        let num_votes = self.num_votes();
+        if num_votes == 0 {
+            return 0.0;
+        }
+        self.total_latency as f64 / num_votes as f64
+    }
+
+    /// Returns the smallest latency such that at least `percentile` percent
+    /// of the votes landed within it.
+    pub fn percentile(&self, percentile: u8) -> u8 {
+        let num_votes = self.num_votes();
+        let target = (num_votes * u64::from(percentile.min(100))).div_ceil(100);
+        let mut seen = 0;
+        for (latency, count) in self.histogram.iter().enumerate() {
+            seen += count;
+            if seen >= target && seen > 0 {
+                return latency as u8;
+            }
+        }
+        0
+    }
+
+    pub fn max(&self) -> u8 {
+        self.histogram
+            .iter()
+            .rposition(|count| *count > 0)
+            .unwrap_or_default() as u8
+    }
+}
+
+#[derive(Default)]
+struct VoteLatencyTrackerInner {
+    epoch: Epoch,
+    root: Slot,
+    // Most recent voted slot already accounted for, per vote account.
+    last_tracked_vote: HashMap<Pubkey, Slot>,
+    stats: HashMap<Pubkey, VoteLatencyStats>,
+}
+
+#[derive(Default)]
+pub struct VoteLatencyTracker {
+    // This node's vote account, reported in metrics along with cluster-wide
+    // latencies.
+    vote_pubkey: Option<Pubkey>,
+    inner: RwLock<VoteLatencyTrackerInner>,
+    last_report: AtomicInterval,
+}
+
+impl VoteLatencyTracker {
+    pub fn new(vote_pubkey: Option<Pubkey>) -> Self {
+        Self {
+            vote_pubkey,
+            ..Self::default()
+        }
+    }
+
+    /// Accounts for all votes which landed since the previous root. Stats are
+    /// reset at epoch boundaries.
+    pub fn update_with_root_bank(&self, root_bank: &Bank) {
+        let mut inner = self.inner.write().unwrap();
+        if root_bank.slot() <= inner.root {
+            return;
+        }
+        inner.root = root_bank.slot();
+        let epoch = root_bank.epoch();
+        if epoch != inner.epoch {
+            inner.epoch = epoch;
+            inner.stats.clear();
+        }
+        let first_slot_in_epoch = root_bank.epoch_schedule().get_first_slot_in_epoch(epoch);
+        let vote_accounts = root_bank.vote_accounts();
+        let VoteLatencyTrackerInner {
+            last_tracked_vote,
+            stats,
+            ..
+        } = &mut *inner;
+        last_tracked_vote.retain(|vote_pubkey, _| vote_accounts.contains_key(vote_pubkey));
+        for (vote_pubkey, (_stake, vote_account)) in vote_accounts.iter() {
+            let vote_state = vote_account.vote_state();
+            let last_tracked_vote = last_tracked_vote.entry(*vote_pubkey).or_default();
+            for vote in &vote_state.votes {
+                // Zero latency indicates a vote cast before latencies were
+                // recorded in the vote state.
+                if vote.slot() <= *last_tracked_vote
+                    || vote.latency == 0
+                    || vote.slot() + u64::from(vote.latency) < first_slot_in_epoch
+                {
+                    continue;
+                }
+                stats
+                    .entry(*vote_pubkey)
+                    .or_insert_with(|| VoteLatencyStats::new(vote_state.node_pubkey))
+                    .record(vote.latency);
+            }
+            if let Some(vote) = vote_state.votes.back() {
+                *last_tracked_vote = (*last_tracked_vote).max(vote.slot());
+            }
+        }
+        if self
+            .last_report
+            .should_update(VOTE_LATENCY_REPORT_INTERVAL_MS)
+        {
+            Self::report_metrics(self.vote_pubkey.as_ref(), stats);
+        }
+    }
+
+    fn report_metrics(vote_pubkey: Option<&Pubkey>, stats: &HashMap<Pubkey, VoteLatencyStats>) {
+        let mut cluster_stats = VoteLatencyStats::new(Pubkey::default());
+        stats.values().for_each(|stats| cluster_stats.merge(stats));
+        let node_stats = vote_pubkey
+            .and_then(|vote_pubkey| stats.get(vote_pubkey))
+            .cloned()
+            .unwrap_or_else(|| VoteLatencyStats::new(Pubkey::default()));
+        datapoint_info!(
+            "vote-latency",
+            ("num_votes", node_stats.num_votes(), i64),
+            ("mean", node_stats.mean(), f64),
+            ("p50", node_stats.percentile(50), i64),
+            ("p90", node_stats.percentile(90), i64),
+            ("max", node_stats.max(), i64),
+            ("cluster_num_votes", cluster_stats.num_votes(), i64),
+            ("cluster_mean", cluster_stats.mean(), f64),
+            ("cluster_p50", cluster_stats.percentile(50), i64),
+            ("cluster_p90", cluster_stats.percentile(90), i64),
+        );
+    }
+
+    /// Returns the root slot and epoch the stats were last updated at, along
+    /// with the stats of the given vote account, or of all vote accounts if
+    /// none is specified.
+    pub fn get_stats(
+        &self,
+        vote_pubkey: Option<&Pubkey>,
+    ) -> (Slot, Epoch, Vec<(Pubkey, VoteLatencyStats)>) {
+        let inner = self.inner.read().unwrap();
+        let stats = match vote_pubkey {
+            Some(vote_pubkey) => inner
+                .stats
+                .get(vote_pubkey)
+                .map(|stats| vec![(*vote_pubkey, stats.clone())])
+                .unwrap_or_default(),
+            None => inner
+                .stats
+                .iter()
+                .map(|(vote_pubkey, stats)| (*vote_pubkey, stats.clone()))
+                .collect(),
+        };
+        (inner.root, inner.epoch, stats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vote_latency_stats() {
+        let mut stats = VoteLatencyStats::new(Pubkey::new_unique());
+        assert_eq!(stats.num_votes(), 0);
+        assert_eq!(stats.mean(), 0.0);
+        assert_eq!(stats.percentile(50), 0);
+        assert_eq!(stats.max(), 0);
+
+        for latency in [1, 1, 1, 1, 1, 2, 2, 3, 5, 200] {
+            stats.record(latency);
+        }
+        assert_eq!(stats.num_votes(), 10);
+        assert_eq!(stats.histogram[1], 5);
+        assert_eq!(stats.histogram[usize::from(MAX_TRACKED_VOTE_LATENCY)], 1);
+        assert_eq!(stats.percentile(50), 1);
+        assert_eq!(stats.percentile(60), 2);
+        assert_eq!(stats.percentile(90), 5);
+        assert_eq!(stats.percentile(100), MAX_TRACKED_VOTE_LATENCY);
+        assert_eq!(stats.max(), MAX_TRACKED_VOTE_LATENCY);
+        // Outliers are clamped in the histogram but not in the mean.
+        assert_eq!(stats.mean(), 21.7);
+
+        let mut merged = VoteLatencyStats::new(Pubkey::default());
+        merged.merge(&stats);
+        merged.merge(&stats);
+        assert_eq!(merged.num_votes(), 20);
+        assert_eq!(merged.percentile(50), 1);
+        assert_eq!(merged.mean(), 21.7);
+    }
+}
@@ -5,6 +5,7 @@ extern crate test;
 
 use {
     log::*,
+    rayon::ThreadPoolBuilder,
     solana_program_runtime::declare_process_instruction,
     solana_runtime::{
         bank::{test_utils::goto_end_of_slot, *},
@@ -18,6 +19,7 @@ use {
         message::Message,
         pubkey::Pubkey,
         signature::{Keypair, Signer},
+        system_transaction,
         transaction::Transaction,
     },
     solana_svm::transaction_processing_callback::TransactionProcessingCallback,
@@ -202,3 +204,49 @@ fn bench_bank_update_recent_blockhashes(bencher: &mut Bencher) {
         bank.update_recent_blockhashes();
     });
 }
+
+fn bench_bank_process_transfer_batch(
+    bencher: &mut Bencher,
+    intra_batch_execution_threads: Option<usize>,
+) {
+    let (genesis_config, mint_keypair) = create_genesis_config(100_000_000_000_000);
+    let mut bank = Bank::new_for_benches(&genesis_config);
+    bank.set_config(&BankConfig {
+        intra_batch_execution_threads,
+        ..BankConfig::default()
+    });
+
+    // Transfers between distinct accounts, which do not conflict with each other
+    let transactions: Vec<_> = (0..64)
+        .map(|_| {
+            let payer = Keypair::new();
+            let recipient = Pubkey::new_unique();
+            bank.transfer(1_000_000_000, &mint_keypair, &payer.pubkey())
+                .unwrap();
+            bank.transfer(1_000_000_000, &mint_keypair, &recipient)
+                .unwrap();
+            system_transaction::transfer(&payer, &recipient, 1, bank.last_blockhash())
+        })
+        .collect();
+
+    // Batches are only split when executed on a rayon thread pool, as in replay
+    let thread_pool = ThreadPoolBuilder::new().num_threads(8).build().unwrap();
+    bencher.iter(|| {
+        // Since bencher runs this multiple times, we need to clear the signatures.
+        bank.clear_signatures();
+        let results = thread_pool.install(|| bank.process_transactions(transactions.iter()));
+        assert!(results.iter().all(Result::is_ok));
+    });
+}
+
+#[bench]
+#[ignore]
+fn bench_bank_process_transfer_batch_serially(bencher: &mut Bencher) {
+    bench_bank_process_transfer_batch(bencher, None);
+}
+
+#[bench]
+#[ignore]
+fn bench_bank_process_transfer_batch_in_parallel(bencher: &mut Bencher) {
+    bench_bank_process_transfer_batch(bencher, Some(8));
+}
@@ -0,0 +1,65 @@
+#![feature(test)]
+extern crate test;
+
+use {
+    solana_runtime::{
+        bank::Bank,
+        genesis_utils::{create_genesis_config, GenesisConfigInfo},
+    },
+    solana_sdk::{
+        account::from_account, clock::Clock, epoch_schedule::EpochSchedule, pubkey::Pubkey,
+        rent::Rent, sysvar,
+    },
+    std::sync::Arc,
+    test::Bencher,
+};
+
+fn new_bank() -> Bank {
+    let GenesisConfigInfo { genesis_config, .. } = create_genesis_config(1_000_000);
+    let bank0 = Arc::new(Bank::new_for_benches(&genesis_config));
+    Bank::new_from_parent(bank0, &Pubkey::default(), 1)
+}
+
+#[bench]
+fn bench_sysvar_cache_get_sysvars(bencher: &mut Bencher) {
+    let bank = new_bank();
+    let transaction_processor = bank.get_transaction_processor();
+    bencher.iter(|| {
+        let sysvar_cache = transaction_processor.sysvar_cache();
+        test::black_box(sysvar_cache.get_clock().unwrap());
+        test::black_box(sysvar_cache.get_rent().unwrap());
+        test::black_box(sysvar_cache.get_epoch_schedule().unwrap());
+    });
+}
+
+#[bench]
+fn bench_deserialize_sysvar_accounts(bencher: &mut Bencher) {
+    let bank = new_bank();
+    let clock_account = bank.get_account(&sysvar::clock::id()).unwrap();
+    let rent_account = bank.get_account(&sysvar::rent::id()).unwrap();
+    let epoch_schedule_account = bank.get_account(&sysvar::epoch_schedule::id()).unwrap();
+    bencher.iter(|| {
+        test::black_box(from_account::<Clock, _>(&clock_account).unwrap());
+        test::black_box(from_account::<Rent, _>(&rent_account).unwrap());
+        test::black_box(from_account::<EpochSchedule, _>(&epoch_schedule_account).unwrap());
+    });
+}
+
+#[bench]
+fn bench_sysvar_cache_refresh_entry(bencher: &mut Bencher) {
+    let bank = new_bank();
+    let transaction_processor = bank.get_transaction_processor();
+    bencher.iter(|| {
+        transaction_processor.refresh_sysvar_cache_entry(&bank, &sysvar::clock::id());
+    });
+}
+
+#[bench]
+fn bench_sysvar_cache_fill(bencher: &mut Bencher) {
+    let bank = new_bank();
+    let transaction_processor = bank.get_transaction_processor();
+    bencher.iter(|| {
+        transaction_processor.reset_sysvar_cache();
+        transaction_processor.fill_missing_sysvar_cache_entries(&bank);
+    });
+}
@@ -228,6 +228,8 @@ mod tests {
                     return_data: None,
                     executed_units: 0,
                     accounts_data_len_delta: 0,
+                    memory_usage: None,
+                    account_privileges: None,
                 },
                 loaded_transaction,
                 programs_modified_by_tx: HashMap::new(),
@@ -134,7 +134,8 @@ pub enum SnapshotRequestKind {
 }
 
 pub struct SnapshotRequestHandler {
-    pub snapshot_config: SnapshotConfig,
+    /// Shared so that the snapshot intervals can be changed while the validator runs
+    pub snapshot_config: Arc<RwLock<SnapshotConfig>>,
     pub snapshot_request_sender: SnapshotRequestSender,
     pub snapshot_request_receiver: SnapshotRequestReceiver,
     pub accounts_package_sender: Sender<AccountsPackage>,
@@ -198,7 +199,7 @@ impl SnapshotRequestHandler {
             .try_iter()
             .map(|request| {
                 let accounts_package_kind =
-                    new_accounts_package_kind(&request, &self.snapshot_config);
+                    new_accounts_package_kind(&request, &self.snapshot_config.read().unwrap());
                 (request, accounts_package_kind)
             })
             .collect();
@@ -364,14 +365,29 @@ impl SnapshotRequestHandler {
             this_accounts_hash
         });
 
+        // Clean and shrink are skipped while background maintenance is paused by the
+        // operator; the snapshot is still taken, only with more dead accounts in it.
+        let is_maintenance_paused = snapshot_root_bank
+            .rc
+            .accounts
+            .accounts_db
+            .background_io_budget()
+            .is_paused();
+
         let mut clean_time = Measure::start("clean_time");
-        snapshot_root_bank.clean_accounts();
+        if !is_maintenance_paused {
+            snapshot_root_bank.clean_accounts();
+        }
         clean_time.stop();
 
-        let (_, shrink_ancient_time_us) = measure_us!(snapshot_root_bank.shrink_ancient_slots());
+        let (_, shrink_ancient_time_us) = measure_us!(if !is_maintenance_paused {
+            snapshot_root_bank.shrink_ancient_slots();
+        });
 
         let mut shrink_time = Measure::start("shrink_time");
-        snapshot_root_bank.shrink_candidate_slots();
+        if !is_maintenance_paused {
+            snapshot_root_bank.shrink_candidate_slots();
+        }
         shrink_time.stop();
 
         // Snapshot the bank and send over an accounts package
@@ -668,6 +684,15 @@ impl AccountsBackgroundService {
                                     break;
                                 }
                             }
+                        } else if bank
+                            .rc
+                            .accounts
+                            .accounts_db
+                            .background_io_budget()
+                            .is_paused()
+                        {
+                            // Background maintenance was paused by the operator. Clean
+                            // will catch up on everything skipped once resumed.
                         } else {
                             if bank.block_height() - last_cleaned_block_height
                                 > (CLEAN_INTERVAL_BLOCKS + thread_rng().gen_range(0..10))
@@ -896,7 +921,7 @@ mod test {
         let (accounts_package_sender, _accounts_package_receiver) = crossbeam_channel::unbounded();
         let (snapshot_request_sender, snapshot_request_receiver) = crossbeam_channel::unbounded();
         let snapshot_request_handler = SnapshotRequestHandler {
-            snapshot_config,
+            snapshot_config: Arc::new(RwLock::new(snapshot_config)),
             snapshot_request_sender: snapshot_request_sender.clone(),
             snapshot_request_receiver,
             accounts_package_sender,
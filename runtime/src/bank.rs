@@ -37,6 +37,9 @@ use {
     crate::{
         account_saver::collect_accounts_to_store,
         bank::{
+            account_observers::{
+                AccountObserverFilter, AccountObserverId, AccountObservers, AccountWrite,
+            },
             metrics::*,
             partitioned_epoch_rewards::{EpochRewardStatus, StakeRewards, VoteRewardsAccounts},
         },
@@ -44,9 +47,12 @@ use {
         epoch_stakes::{split_epoch_stakes, EpochStakes, NodeVoteAccounts, VersionedEpochStakes},
         inflation_rewards::points::InflationPointCalculationEvent,
         installed_scheduler_pool::{BankWithScheduler, InstalledSchedulerRwLock},
+        non_circulating_supply::{
+            calculate_non_circulating_supply_with_accounts, NonCirculatingAccounts, Supply,
+        },
         rent_collector::RentCollectorWithMetrics,
         runtime_config::RuntimeConfig,
-        serde_snapshot::BankIncrementalSnapshotPersistence,
+        serde_snapshot::{BankIncrementalSnapshotPersistence, SerializedAccountsFileId},
         snapshot_hash::SnapshotHash,
         stake_account::StakeAccount,
         stake_weighted_timestamp::{
@@ -56,6 +62,7 @@ use {
         stakes::{Stakes, StakesCache, StakesEnum},
         status_cache::{SlotDelta, StatusCache},
         transaction_batch::{OwnedOrBorrowed, TransactionBatch},
+        transaction_recorder::RecordTransactions,
         verify_precompiles::verify_precompiles,
     },
     accounts_lt_hash::{CacheValue as AccountsLtHashCacheValue, Stats as AccountsLtHashStats},
@@ -75,9 +82,10 @@ use {
             DuplicatesLtHash, OldStoragesPolicy, PubkeyHashAccount,
             VerifyAccountsHashAndLamportsConfig,
         },
+        accounts_file::AccountsFileProvider,
         accounts_hash::{
-            AccountHash, AccountsHash, AccountsLtHash, CalcAccountsHashConfig, HashStats,
-            IncrementalAccountsHash, MerkleOrLatticeAccountsHash,
+            AccountHash, AccountsHash, AccountsHashProof, AccountsLtHash, CalcAccountsHashConfig,
+            HashStats, IncrementalAccountsHash, MerkleOrLatticeAccountsHash,
         },
         accounts_index::{IndexKey, ScanConfig, ScanResult},
         accounts_partition::{self, Partition, PartitionIndex},
@@ -96,11 +104,14 @@ use {
     solana_compute_budget_instruction::instructions_processor::process_compute_budget_instructions,
     solana_cost_model::{block_cost_limits::simd_0207_block_limits, cost_tracker::CostTracker},
     solana_feature_set::{self as feature_set, FeatureSet},
-    solana_fee::FeeFeatures,
+    solana_fee::{DefaultFeeModel, FeeFeatures, FeeModel},
     solana_lattice_hash::lt_hash::LtHash,
     solana_measure::{meas_dur, measure::Measure, measure_time, measure_us},
     solana_program_runtime::{
-        invoke_context::BuiltinFunctionWithContext, loaded_programs::ProgramCacheEntry,
+        invoke_context::{
+            BuiltinFunctionWithContext, InstructionAccountPrivileges, ProgramMemoryUsage,
+        },
+        loaded_programs::ProgramCacheEntry,
     },
     solana_runtime_transaction::{
         runtime_transaction::RuntimeTransaction, transaction_with_meta::TransactionWithMeta,
@@ -192,7 +203,10 @@ use {
     },
 };
 pub use {
-    partitioned_epoch_rewards::KeyedRewardsAndNumPartitions, solana_sdk::reward_type::RewardType,
+    partitioned_epoch_rewards::{
+        EpochStakeReward, EpochStakeRewards, KeyedRewardsAndNumPartitions,
+    },
+    solana_sdk::reward_type::RewardType,
 };
 #[cfg(feature = "dev-context-only-utils")]
 use {
@@ -214,6 +228,8 @@ struct VerifyAccountsHashConfig {
     store_hash_raw_data_for_debug: bool,
 }
 
+pub mod account_observers;
+pub mod accounts_delta;
 mod accounts_lt_hash;
 mod address_lookup_table;
 pub mod bank_hash_details;
@@ -223,6 +239,7 @@ mod check_transactions;
 pub mod epoch_accounts_hash_utils;
 mod fee_distribution;
 mod metrics;
+mod parallel_execution;
 pub(crate) mod partitioned_epoch_rewards;
 mod recent_blockhashes_account;
 mod serde_snapshot;
@@ -233,6 +250,10 @@ pub const SECONDS_PER_YEAR: f64 = 365.25 * 24.0 * 60.0 * 60.0;
 
 pub const MAX_LEADER_SCHEDULE_STAKES: Epoch = 5;
 
+/// Max number of accounts a transaction may lock before `increase_tx_account_lock_limit`
+/// raises it to `MAX_TX_ACCOUNT_LOCKS`
+pub const LEGACY_TX_ACCOUNT_LOCK_LIMIT: usize = 64;
+
 #[derive(Default)]
 struct RentMetrics {
     hold_range_us: AtomicU64,
@@ -330,6 +351,22 @@ pub struct LoadAndExecuteTransactionsOutput {
     pub processed_counts: ProcessedTransactionCounts,
 }
 
+pub struct RecordAndCommitTransactionsOutput {
+    // Indexes in the batch of the processed transactions, which are the ones
+    // handed to the recorder.
+    pub processed_transaction_indexes: Vec<usize>,
+    // Number of the processed transactions which executed successfully.
+    pub processed_with_successful_result_count: u64,
+    // Time spent waiting for the freeze lock before recording.
+    pub freeze_lock_us: u64,
+    // Index in the slot of the first transaction recorded, if the recorder
+    // keeps track of it.
+    pub starting_transaction_index: Option<usize>,
+    // Commit results and balances of the batch, or the error of the recorder
+    // in which case nothing was committed.
+    pub commit_result: Result<(Vec<TransactionCommitResult>, TransactionBalancesSet)>,
+}
+
 #[derive(Debug, PartialEq)]
 pub struct TransactionSimulationResult {
     pub result: Result<()>,
@@ -338,6 +375,30 @@ pub struct TransactionSimulationResult {
     pub units_consumed: u64,
     pub return_data: Option<TransactionReturnData>,
     pub inner_instructions: Option<Vec<InnerInstructions>>,
+    /// The memory used by each SBF program invocation, in the order in which
+    /// the invocations returned
+    pub memory_usage: Vec<ProgramMemoryUsage>,
+    /// The account privileges of each instruction, in the order in which the
+    /// instructions were invoked
+    pub account_privileges: Vec<InstructionAccountPrivileges>,
+}
+
+/// The state of an account write locked by a simulated transaction, before and after the
+/// transaction
+#[derive(Debug, PartialEq)]
+pub struct SimulatedAccountDiff {
+    pub pubkey: Pubkey,
+    /// `None` if the account did not exist before the transaction
+    pub pre: Option<AccountSharedData>,
+    /// The account as it would be committed; `None` if it would not exist afterwards
+    pub post: Option<AccountSharedData>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct TransactionSimulationWithDiffs {
+    pub simulation: TransactionSimulationResult,
+    /// The accounts write locked by the transaction, in the order of its account keys
+    pub account_diffs: Vec<SimulatedAccountDiff>,
 }
 
 #[derive(Clone, Debug)]
@@ -373,6 +434,19 @@ impl Default for TransactionLogCollectorFilter {
     }
 }
 
+/// Encapsulates flags that can be used to tweak the behavior of a bank, and of
+/// the banks descending from it.
+#[derive(Debug, Default, Clone)]
+pub struct BankConfig {
+    /// Max number of threads of the replay thread pool executing the
+    /// transactions of a batch which do not conflict with each other in
+    /// parallel, batches being executed serially if not more than one
+    pub intra_batch_execution_threads: Option<usize>,
+    /// Number of past epochs whose stakes are kept, if more than the leader
+    /// schedule needs
+    pub epoch_stakes_retention: Option<Epoch>,
+}
+
 #[derive(Debug, Default)]
 pub struct TransactionLogCollectorConfig {
     pub mentioned_addresses: HashSet<Pubkey>,
@@ -460,6 +534,8 @@ pub struct BankFieldsToDeserialize {
     // When removing the accounts lt hash featurization code, also remove this Option wrapper
     pub(crate) accounts_lt_hash: Option<AccountsLtHash>,
     pub(crate) bank_hash_stats: BankHashStats,
+    /// Format of each storage which is not an append vec
+    pub(crate) storage_formats: HashMap<(Slot, SerializedAccountsFileId), AccountsFileProvider>,
 }
 
 /// Bank's common fields shared by all supported snapshot versions for serialization.
@@ -553,6 +629,7 @@ impl PartialEq for Bank {
             inflation,
             stakes_cache,
             epoch_stakes,
+            epoch_stakes_retention: _,
             is_delta,
             #[cfg(feature = "dev-context-only-utils")]
             hash_overrides,
@@ -580,11 +657,15 @@ impl PartialEq for Bank {
             collector_fee_details: _,
             compute_budget: _,
             transaction_account_lock_limit: _,
+            intra_batch_execution_threads: _,
             fee_structure: _,
+            fee_model: _,
+            account_observers: _,
             cache_for_accounts_lt_hash: _,
             stats_for_accounts_lt_hash: _,
             block_id,
             bank_hash_stats: _,
+            supply_cache: _,
             // Ignore new fields explicitly if they do not impact PartialEq.
             // Adding ".." will remove compile-time checks that if a new field
             // is added to the struct, this PartialEq is accordingly updated.
@@ -849,6 +930,9 @@ pub struct Bank {
     ///   a leader schedule calculation boundary
     epoch_stakes: HashMap<Epoch, EpochStakes>,
 
+    /// Number of epochs before the leader schedule epoch whose stakes are kept in `epoch_stakes`
+    epoch_stakes_retention: Epoch,
+
     /// A boolean reflecting whether any entries were recorded into the PoH
     /// stream for the slot == self.slot
     is_delta: AtomicBool,
@@ -912,9 +996,19 @@ pub struct Bank {
     /// The max number of accounts that a transaction may lock.
     transaction_account_lock_limit: Option<usize>,
 
+    /// Max number of threads executing the non-conflicting transactions of a batch.
+    intra_batch_execution_threads: usize,
+
     /// Fee structure to use for assessing transaction fees.
     fee_structure: FeeStructure,
 
+    /// Fee model deciding how much each transaction is charged, which follows
+    /// the active feature set.
+    fee_model: &'static dyn FeeModel,
+
+    /// Observers of the accounts written by committed transactions, shared with child banks.
+    account_observers: Arc<AccountObservers>,
+
     /// blockhash and bank_hash overrides keyed by slot for simulated block production.
     /// This _field_ was needed to be DCOU-ed to avoid 2 locks per bank freezing...
     #[cfg(feature = "dev-context-only-utils")]
@@ -944,6 +1038,10 @@ pub struct Bank {
 
     /// Accounts stats for computing the bank hash
     bank_hash_stats: AtomicBankHashStats,
+
+    /// The supply of this bank, and the non-circulating accounts it was computed with.
+    /// Only populated once the bank is frozen, as the supply cannot change after.
+    supply_cache: Mutex<Option<(NonCirculatingAccounts, Arc<Supply>)>>,
 }
 
 #[derive(Debug)]
@@ -1112,6 +1210,7 @@ impl Bank {
             inflation: Arc::<RwLock<Inflation>>::default(),
             stakes_cache: StakesCache::default(),
             epoch_stakes: HashMap::<Epoch, EpochStakes>::default(),
+            epoch_stakes_retention: MAX_LEADER_SCHEDULE_STAKES,
             is_delta: AtomicBool::default(),
             rewards: RwLock::<Vec<(Pubkey, RewardInfo)>>::default(),
             cluster_type: Option::<ClusterType>::default(),
@@ -1136,7 +1235,10 @@ impl Bank {
             collector_fee_details: RwLock::new(CollectorFeeDetails::default()),
             compute_budget: None,
             transaction_account_lock_limit: None,
+            intra_batch_execution_threads: 1,
             fee_structure: FeeStructure::default(),
+            fee_model: &DefaultFeeModel,
+            account_observers: Arc::default(),
             #[cfg(feature = "dev-context-only-utils")]
             hash_overrides: Arc::new(Mutex::new(HashOverrides::default())),
             accounts_lt_hash: Mutex::new(AccountsLtHash(LtHash::identity())),
@@ -1144,6 +1246,7 @@ impl Bank {
             stats_for_accounts_lt_hash: AccountsLtHashStats::default(),
             block_id: RwLock::new(None),
             bank_hash_stats: AtomicBankHashStats::default(),
+            supply_cache: Mutex::default(),
         };
 
         bank.transaction_processor =
@@ -1353,6 +1456,7 @@ impl Bank {
             // we will .clone_with_epoch() this soon after stake data update; so just .clone() for now
             stakes_cache,
             epoch_stakes,
+            epoch_stakes_retention: parent.epoch_stakes_retention,
             parent_hash: parent.hash(),
             parent_slot: parent.slot(),
             collector_id: *collector_id,
@@ -1392,7 +1496,10 @@ impl Bank {
             collector_fee_details: RwLock::new(CollectorFeeDetails::default()),
             compute_budget: parent.compute_budget,
             transaction_account_lock_limit: parent.transaction_account_lock_limit,
+            intra_batch_execution_threads: parent.intra_batch_execution_threads,
             fee_structure: parent.fee_structure.clone(),
+            fee_model: parent.fee_model,
+            account_observers: parent.account_observers.clone(),
             #[cfg(feature = "dev-context-only-utils")]
             hash_overrides: parent.hash_overrides.clone(),
             accounts_lt_hash: Mutex::new(parent.accounts_lt_hash.lock().unwrap().clone()),
@@ -1400,6 +1507,7 @@ impl Bank {
             stats_for_accounts_lt_hash: AccountsLtHashStats::default(),
             block_id: RwLock::new(None),
             bank_hash_stats: AtomicBankHashStats::default(),
+            supply_cache: Mutex::default(),
         };
 
         let (_, ancestors_time_us) = measure_us!({
@@ -1766,6 +1874,7 @@ impl Bank {
             inflation: Arc::new(RwLock::new(fields.inflation)),
             stakes_cache: StakesCache::new(stakes),
             epoch_stakes: fields.epoch_stakes,
+            epoch_stakes_retention: MAX_LEADER_SCHEDULE_STAKES,
             is_delta: AtomicBool::new(fields.is_delta),
             rewards: RwLock::new(vec![]),
             cluster_type: Some(genesis_config.cluster_type),
@@ -1791,7 +1900,10 @@ impl Bank {
             collector_fee_details: RwLock::new(CollectorFeeDetails::default()),
             compute_budget: runtime_config.compute_budget,
             transaction_account_lock_limit: runtime_config.transaction_account_lock_limit,
+            intra_batch_execution_threads: 1,
             fee_structure: FeeStructure::default(),
+            fee_model: &DefaultFeeModel,
+            account_observers: Arc::default(),
             #[cfg(feature = "dev-context-only-utils")]
             hash_overrides: Arc::new(Mutex::new(HashOverrides::default())),
             accounts_lt_hash: Mutex::new(AccountsLtHash(LtHash([0xBAD1; LtHash::NUM_ELEMENTS]))),
@@ -1799,6 +1911,7 @@ impl Bank {
             stats_for_accounts_lt_hash: AccountsLtHashStats::default(),
             block_id: RwLock::new(None),
             bank_hash_stats: AtomicBankHashStats::new(&fields.bank_hash_stats),
+            supply_cache: Mutex::default(),
         };
 
         bank.transaction_processor =
@@ -2045,6 +2158,8 @@ impl Bank {
         // although there is no such sysvars currently.
         self.adjust_sysvar_balance_for_rent(&mut new_account);
         self.store_account_and_update_capitalization(pubkey, &new_account);
+        self.transaction_processor
+            .refresh_sysvar_cache_entry(self, pubkey);
     }
 
     fn inherit_specially_retained_account_fields(
@@ -2226,7 +2341,7 @@ impl Bank {
         //  crossed a boundary
         if !self.epoch_stakes.contains_key(&leader_schedule_epoch) {
             self.epoch_stakes.retain(|&epoch, _| {
-                epoch >= leader_schedule_epoch.saturating_sub(MAX_LEADER_SCHEDULE_STAKES)
+                epoch >= leader_schedule_epoch.saturating_sub(self.epoch_stakes_retention)
             });
             let stakes = self.stakes_cache.stakes().clone();
             let stakes = Arc::new(StakesEnum::from(stakes));
@@ -2913,13 +3028,15 @@ impl Bank {
             )
             .unwrap_or_default(),
         );
-        solana_fee::calculate_fee(
+        solana_fee::calculate_fee_details_with_model(
             message,
             lamports_per_signature == 0,
             self.fee_structure().lamports_per_signature,
             fee_budget_limits.prioritization_fee,
             FeeFeatures::from(self.feature_set.as_ref()),
+            self.fee_model,
         )
+        .total_fee()
     }
 
     pub fn get_blockhash_last_valid_block_height(&self, blockhash: &Hash) -> Option<Slot> {
@@ -3103,7 +3220,7 @@ impl Bank {
         {
             MAX_TX_ACCOUNT_LOCKS
         } else {
-            64
+            LEGACY_TX_ACCOUNT_LOCK_LIMIT
         }
     }
 
@@ -3195,10 +3312,34 @@ impl Bank {
         &self,
         transaction: &impl TransactionWithMeta,
         enable_cpi_recording: bool,
+    ) -> TransactionSimulationResult {
+        self.simulate_transaction_with_recording_config(
+            transaction,
+            Self::simulation_recording_config(enable_cpi_recording),
+        )
+    }
+
+    /// Run transactions against a frozen bank without committing the results, recording what
+    /// `recording_config` enables
+    pub fn simulate_transaction_with_recording_config(
+        &self,
+        transaction: &impl TransactionWithMeta,
+        recording_config: ExecutionRecordingConfig,
     ) -> TransactionSimulationResult {
         assert!(self.is_frozen(), "simulation bank must be frozen");
 
-        self.simulate_transaction_unchecked(transaction, enable_cpi_recording)
+        let (processing_result, units_consumed) =
+            self.process_transaction_for_simulation(transaction, recording_config);
+        Self::simulation_result(
+            processing_result,
+            transaction.account_keys().len(),
+            units_consumed,
+        )
+    }
+
+    /// Logs and return data are always recorded in simulation
+    fn simulation_recording_config(enable_cpi_recording: bool) -> ExecutionRecordingConfig {
+        ExecutionRecordingConfig::new(enable_cpi_recording, true, true)
     }
 
     /// Run transactions against a bank without committing the results; does not check if the bank
@@ -3208,36 +3349,88 @@ impl Bank {
         transaction: &impl TransactionWithMeta,
         enable_cpi_recording: bool,
     ) -> TransactionSimulationResult {
+        let (processing_result, units_consumed) = self.process_transaction_for_simulation(
+            transaction,
+            Self::simulation_recording_config(enable_cpi_recording),
+        );
+        Self::simulation_result(
+            processing_result,
+            transaction.account_keys().len(),
+            units_consumed,
+        )
+    }
+
+    /// Run a transaction against a frozen bank without committing the results, and report the
+    /// state of each account it write locks before the transaction and as it would be committed
+    /// after it. A failed transaction only changes its fee payer and nonce accounts.
+    pub fn simulate_transaction_with_diffs(
+        &self,
+        transaction: &impl TransactionWithMeta,
+        enable_cpi_recording: bool,
+    ) -> TransactionSimulationWithDiffs {
+        assert!(self.is_frozen(), "simulation bank must be frozen");
+
+        let (processing_result, units_consumed) = self.process_transaction_for_simulation(
+            transaction,
+            Self::simulation_recording_config(enable_cpi_recording),
+        );
+
+        let processing_results = [processing_result];
+        let (committed_accounts, _) = collect_accounts_to_store(
+            slice::from_ref(transaction),
+            &None::<Vec<&SanitizedTransaction>>,
+            &processing_results,
+        );
+        let account_diffs = transaction
+            .account_keys()
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| transaction.is_writable(*index))
+            .map(|(_, pubkey)| {
+                let pre = self.get_account(pubkey);
+                let post = match committed_accounts
+                    .iter()
+                    .find(|(committed_pubkey, _)| *committed_pubkey == pubkey)
+                {
+                    Some((_, account)) => (account.lamports() > 0).then(|| (*account).clone()),
+                    None => pre.clone(),
+                };
+                SimulatedAccountDiff {
+                    pubkey: *pubkey,
+                    pre,
+                    post,
+                }
+            })
+            .collect();
+
+        let [processing_result] = processing_results;
+        TransactionSimulationWithDiffs {
+            simulation: Self::simulation_result(
+                processing_result,
+                transaction.account_keys().len(),
+                units_consumed,
+            ),
+            account_diffs,
+        }
+    }
+
+    /// Load and execute a transaction for simulation, returning its processing result and the
+    /// compute units it consumed
+    fn process_transaction_for_simulation(
+        &self,
+        transaction: &impl TransactionWithMeta,
+        recording_config: ExecutionRecordingConfig,
+    ) -> (TransactionProcessingResult, u64) {
         let account_keys = transaction.account_keys();
-        let number_of_accounts = account_keys.len();
         let account_overrides = self.get_account_overrides_for_simulation(&account_keys);
         let batch = self.prepare_unlocked_batch_from_single_tx(transaction);
         let mut timings = ExecuteTimings::default();
 
-        let LoadAndExecuteTransactionsOutput {
-            mut processing_results,
-            ..
-        } = self.load_and_execute_transactions(
+        let mut processing_results = self.load_and_execute_transactions_for_simulation(
             &batch,
-            // After simulation, transactions will need to be forwarded to the leader
-            // for processing. During forwarding, the transaction could expire if the
-            // delay is not accounted for.
-            MAX_PROCESSING_AGE - MAX_TRANSACTION_FORWARDING_DELAY,
+            &account_overrides,
+            recording_config,
             &mut timings,
-            &mut TransactionErrorMetrics::default(),
-            TransactionProcessingConfig {
-                account_overrides: Some(&account_overrides),
-                check_program_modification_slot: self.check_program_modification_slot,
-                compute_budget: self.compute_budget(),
-                log_messages_bytes_limit: None,
-                limit_to_load_programs: true,
-                recording_config: ExecutionRecordingConfig {
-                    enable_cpi_recording,
-                    enable_log_recording: true,
-                    enable_return_data_recording: true,
-                },
-                transaction_account_lock_limit: Some(self.get_transaction_account_lock_limit()),
-            },
         );
 
         let units_consumed =
@@ -3257,32 +3450,158 @@ impl Bank {
         let processing_result = processing_results
             .pop()
             .unwrap_or(Err(TransactionError::InvalidProgramForExecution));
-        let (post_simulation_accounts, result, logs, return_data, inner_instructions) =
-            match processing_result {
-                Ok(processed_tx) => match processed_tx {
-                    ProcessedTransaction::Executed(executed_tx) => {
-                        let details = executed_tx.execution_details;
-                        let post_simulation_accounts = executed_tx
-                            .loaded_transaction
-                            .accounts
-                            .into_iter()
-                            .take(number_of_accounts)
-                            .collect::<Vec<_>>();
-                        (
-                            post_simulation_accounts,
-                            details.status,
-                            details.log_messages,
-                            details.return_data,
-                            details.inner_instructions,
-                        )
-                    }
-                    ProcessedTransaction::FeesOnly(fees_only_tx) => {
-                        (vec![], Err(fees_only_tx.load_error), None, None, None)
+        (processing_result, units_consumed)
+    }
+
+    /// Run a batch of transactions against a frozen bank without committing the results, with
+    /// the accounts in `account_overrides` loaded in place of the bank's. Each transaction sees
+    /// the accounts as left by the transactions before it in the batch.
+    ///
+    /// This allows asking what the transactions would do if accounts, such as an oracle's price
+    /// account, held different state. See [`AccountOverrides`] for the accounts that cannot be
+    /// overridden.
+    pub fn simulate_transactions_with_account_overrides<Tx: TransactionWithMeta>(
+        &self,
+        transactions: &[Tx],
+        mut account_overrides: AccountOverrides,
+        enable_cpi_recording: bool,
+    ) -> Vec<TransactionSimulationResult> {
+        assert!(self.is_frozen(), "simulation bank must be frozen");
+
+        let slot_history_id = sysvar::slot_history::id();
+        if account_overrides.get(&slot_history_id).is_none()
+            && transactions.iter().any(|transaction| {
+                transaction
+                    .account_keys()
+                    .iter()
+                    .any(|key| *key == slot_history_id)
+            })
+        {
+            account_overrides.set_slot_history(self.get_slot_history_for_simulation());
+        }
+
+        // Transactions are executed one after another in simulation, so they may lock the same
+        // accounts
+        let tx_account_lock_limit = self.get_transaction_account_lock_limit();
+        let lock_results = transactions
+            .iter()
+            .map(|transaction| {
+                validate_account_locks(transaction.account_keys(), tx_account_lock_limit)
+            })
+            .collect();
+        let mut batch =
+            TransactionBatch::new(lock_results, self, OwnedOrBorrowed::Borrowed(transactions));
+        batch.set_needs_unlock(false);
+
+        let mut timings = ExecuteTimings::default();
+        let processing_results = self.load_and_execute_transactions_for_simulation(
+            &batch,
+            &account_overrides,
+            Self::simulation_recording_config(enable_cpi_recording),
+            &mut timings,
+        );
+        debug!(
+            "simulate_transactions_with_account_overrides: {:?}",
+            timings
+        );
+
+        transactions
+            .iter()
+            .zip(processing_results)
+            .map(|(transaction, processing_result)| {
+                let units_consumed = match &processing_result {
+                    Ok(ProcessedTransaction::Executed(executed_tx)) => {
+                        executed_tx.execution_details.executed_units
                     }
-                },
-                Err(error) => (vec![], Err(error), None, None, None),
-            };
+                    _ => 0,
+                };
+                Self::simulation_result(
+                    processing_result,
+                    transaction.account_keys().len(),
+                    units_consumed,
+                )
+            })
+            .collect()
+    }
+
+    fn load_and_execute_transactions_for_simulation(
+        &self,
+        batch: &TransactionBatch<impl TransactionWithMeta>,
+        account_overrides: &AccountOverrides,
+        recording_config: ExecutionRecordingConfig,
+        timings: &mut ExecuteTimings,
+    ) -> Vec<TransactionProcessingResult> {
+        let LoadAndExecuteTransactionsOutput {
+            processing_results, ..
+        } = self.load_and_execute_transactions(
+            batch,
+            // After simulation, transactions will need to be forwarded to the leader
+            // for processing. During forwarding, the transaction could expire if the
+            // delay is not accounted for.
+            MAX_PROCESSING_AGE - MAX_TRANSACTION_FORWARDING_DELAY,
+            timings,
+            &mut TransactionErrorMetrics::default(),
+            TransactionProcessingConfig {
+                account_overrides: Some(account_overrides),
+                check_program_modification_slot: self.check_program_modification_slot,
+                compute_budget: self.compute_budget(),
+                log_messages_bytes_limit: None,
+                limit_to_load_programs: true,
+                recording_config,
+                transaction_account_lock_limit: Some(self.get_transaction_account_lock_limit()),
+            },
+        );
+        processing_results
+    }
+
+    fn simulation_result(
+        processing_result: TransactionProcessingResult,
+        number_of_accounts: usize,
+        units_consumed: u64,
+    ) -> TransactionSimulationResult {
+        let (
+            post_simulation_accounts,
+            result,
+            logs,
+            return_data,
+            inner_instructions,
+            memory_usage,
+            account_privileges,
+        ) = match processing_result {
+            Ok(processed_tx) => match processed_tx {
+                ProcessedTransaction::Executed(executed_tx) => {
+                    let details = executed_tx.execution_details;
+                    let post_simulation_accounts = executed_tx
+                        .loaded_transaction
+                        .accounts
+                        .into_iter()
+                        .take(number_of_accounts)
+                        .collect::<Vec<_>>();
+                    (
+                        post_simulation_accounts,
+                        details.status,
+                        details.log_messages,
+                        details.return_data,
+                        details.inner_instructions,
+                        details.memory_usage,
+                        details.account_privileges,
+                    )
+                }
+                ProcessedTransaction::FeesOnly(fees_only_tx) => (
+                    vec![],
+                    Err(fees_only_tx.load_error),
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                ),
+            },
+            Err(error) => (vec![], Err(error), None, None, None, None, None),
+        };
         let logs = logs.unwrap_or_default();
+        let memory_usage = memory_usage.unwrap_or_default();
+        let account_privileges = account_privileges.unwrap_or_default();
 
         TransactionSimulationResult {
             result,
@@ -3291,6 +3610,8 @@ impl Bank {
             units_consumed,
             return_data,
             inner_instructions,
+            memory_usage,
+            account_privileges,
         }
     }
 
@@ -3298,23 +3619,28 @@ impl Bank {
         let mut account_overrides = AccountOverrides::default();
         let slot_history_id = sysvar::slot_history::id();
         if account_keys.iter().any(|pubkey| *pubkey == slot_history_id) {
-            let current_account = self.get_account_with_fixed_root(&slot_history_id);
-            let slot_history = current_account
-                .as_ref()
-                .map(|account| from_account::<SlotHistory, _>(account).unwrap())
-                .unwrap_or_default();
-            if slot_history.check(self.slot()) == Check::Found {
-                let ancestors = Ancestors::from(self.proper_ancestors().collect::<Vec<_>>());
-                if let Some((account, _)) =
-                    self.load_slow_with_fixed_root(&ancestors, &slot_history_id)
-                {
-                    account_overrides.set_slot_history(Some(account));
-                }
-            }
+            account_overrides.set_slot_history(self.get_slot_history_for_simulation());
         }
         account_overrides
     }
 
+    /// The SlotHistory as of the parent bank, if the current one already includes this slot
+    fn get_slot_history_for_simulation(&self) -> Option<AccountSharedData> {
+        let slot_history_id = sysvar::slot_history::id();
+        let current_account = self.get_account_with_fixed_root(&slot_history_id);
+        let slot_history = current_account
+            .as_ref()
+            .map(|account| from_account::<SlotHistory, _>(account).unwrap())
+            .unwrap_or_default();
+        if slot_history.check(self.slot()) == Check::Found {
+            let ancestors = Ancestors::from(self.proper_ancestors().collect::<Vec<_>>());
+            self.load_slow_with_fixed_root(&ancestors, &slot_history_id)
+                .map(|(account, _)| account)
+        } else {
+            None
+        }
+    }
+
     pub fn unlock_accounts<'a, Tx: SVMMessage + 'a>(
         &self,
         txs_and_results: impl Iterator<Item = (&'a Tx, &'a Result<()>)> + Clone,
@@ -3383,15 +3709,13 @@ impl Bank {
             rent_collector: Some(&rent_collector_with_metrics),
         };
 
-        let sanitized_output = self
-            .transaction_processor
-            .load_and_execute_sanitized_transactions(
-                self,
-                sanitized_txs,
-                check_results,
-                &processing_environment,
-                &processing_config,
-            );
+        let sanitized_output = self.load_and_execute_sanitized_transactions(
+            sanitized_txs,
+            check_results,
+            &processing_environment,
+            &rent_collector_with_metrics,
+            &processing_config,
+        );
 
         // Accumulate the errors returned by the batch processor.
         error_counters.accumulate(&sanitized_output.error_metrics);
@@ -3640,6 +3964,21 @@ impl Bank {
         self.bank_hash_stats.accumulate(&stats);
     }
 
+    /// Registers `callback` to be called for every account matching `filter`
+    /// written by the transactions committed to this bank or its descendants.
+    pub fn register_account_observer(
+        &self,
+        filter: AccountObserverFilter,
+        callback: impl Fn(&AccountWrite) + Send + Sync + 'static,
+    ) -> AccountObserverId {
+        self.account_observers.register(filter, Box::new(callback))
+    }
+
+    /// Returns false if there is no observer registered as `id`.
+    pub fn unregister_account_observer(&self, id: AccountObserverId) -> bool {
+        self.account_observers.unregister(id)
+    }
+
     pub fn commit_transactions(
         &self,
         sanitized_txs: &[impl TransactionWithMeta],
@@ -3678,19 +4017,18 @@ impl Bank {
         }
 
         let ((), store_accounts_us) = measure_us!({
-            // If geyser is present, we must collect `SanitizedTransaction`
-            // references in order to comply with that interface - until it
-            // is changed.
-            let maybe_transaction_refs = self
-                .accounts()
-                .accounts_db
-                .has_accounts_update_notifier()
-                .then(|| {
-                    sanitized_txs
-                        .iter()
-                        .map(|tx| tx.as_sanitized_transaction())
-                        .collect::<Vec<_>>()
-                });
+            // If geyser or account observers are present, we must collect
+            // `SanitizedTransaction` references in order to comply with that
+            // interface - until it is changed.
+            let has_account_observers = !self.account_observers.is_empty();
+            let collect_transaction_refs =
+                self.accounts().accounts_db.has_accounts_update_notifier() || has_account_observers;
+            let maybe_transaction_refs = collect_transaction_refs.then(|| {
+                sanitized_txs
+                    .iter()
+                    .map(|tx| tx.as_sanitized_transaction())
+                    .collect::<Vec<_>>()
+            });
 
             let (accounts_to_store, transactions) = collect_accounts_to_store(
                 sanitized_txs,
@@ -3703,6 +4041,16 @@ impl Bank {
             self.rc
                 .accounts
                 .store_cached(to_store, transactions.as_deref());
+
+            if has_account_observers {
+                let signatures: Vec<_> = transactions
+                    .iter()
+                    .flatten()
+                    .map(|tx| tx.signature())
+                    .collect();
+                self.account_observers
+                    .notify(self.slot(), &accounts_to_store, &signatures);
+            }
         });
 
         self.collect_rent(&processing_results);
@@ -4540,6 +4888,90 @@ impl Bank {
         )
     }
 
+    /// Process a batch of transactions, handing the processed ones to `recorder`
+    /// before committing them.
+    ///
+    /// The bank is kept from freezing from the time the transactions are
+    /// recorded until they are committed. Nothing is committed if `recorder`
+    /// fails.
+    pub fn load_execute_and_commit_transactions_with_recorder(
+        &self,
+        batch: &TransactionBatch<impl TransactionWithMeta>,
+        max_age: usize,
+        collect_balances: bool,
+        processing_config: TransactionProcessingConfig,
+        timings: &mut ExecuteTimings,
+        error_counters: &mut TransactionErrorMetrics,
+        recorder: &dyn RecordTransactions,
+    ) -> RecordAndCommitTransactionsOutput {
+        let pre_balances = if collect_balances {
+            self.collect_balances(batch)
+        } else {
+            vec![]
+        };
+
+        let LoadAndExecuteTransactionsOutput {
+            processing_results,
+            processed_counts,
+        } = self.load_and_execute_transactions(
+            batch,
+            max_age,
+            timings,
+            error_counters,
+            processing_config,
+        );
+
+        let (processed_transaction_indexes, processed_transactions): (Vec<_>, Vec<_>) =
+            processing_results
+                .iter()
+                .zip(batch.sanitized_transactions())
+                .enumerate()
+                .filter(|(_index, (processing_result, _tx))| processing_result.was_processed())
+                .map(|(index, (_processing_result, tx))| (index, tx.to_versioned_transaction()))
+                .unzip();
+
+        let mut freeze_lock_us = 0;
+        let record_result = if processed_transactions.is_empty() {
+            Ok((None, None))
+        } else {
+            let (freeze_lock, us) = measure_us!(self.freeze_lock());
+            freeze_lock_us = us;
+            recorder
+                .record_transactions(self.slot(), processed_transactions)
+                .map(|starting_transaction_index| (Some(freeze_lock), starting_transaction_index))
+        };
+
+        let mut starting_transaction_index = None;
+        let commit_result = record_result.map(|(freeze_lock, transaction_index)| {
+            starting_transaction_index = transaction_index;
+            let commit_results = self.commit_transactions(
+                batch.sanitized_transactions(),
+                processing_results,
+                &processed_counts,
+                timings,
+            );
+            drop(freeze_lock);
+            let post_balances = if collect_balances {
+                self.collect_balances(batch)
+            } else {
+                vec![]
+            };
+            (
+                commit_results,
+                TransactionBalancesSet::new(pre_balances, post_balances),
+            )
+        });
+
+        RecordAndCommitTransactionsOutput {
+            processed_transaction_indexes,
+            processed_with_successful_result_count: processed_counts
+                .processed_with_successful_result_count,
+            freeze_lock_us,
+            starting_transaction_index,
+            commit_result,
+        }
+    }
+
     fn do_load_execute_and_commit_transactions_with_pre_commit_callback<'a>(
         &'a self,
         batch: &TransactionBatch<impl TransactionWithMeta>,
@@ -4626,11 +5058,7 @@ impl Bank {
             &batch,
             MAX_PROCESSING_AGE,
             false, // collect_balances
-            ExecutionRecordingConfig {
-                enable_cpi_recording: false,
-                enable_log_recording: true,
-                enable_return_data_recording: true,
-            },
+            ExecutionRecordingConfig::new(false, true, true),
             &mut ExecuteTimings::default(),
             Some(1000 * 1000),
         );
@@ -5029,6 +5457,27 @@ impl Bank {
         )
     }
 
+    /// Returns up to `limit` accounts owned by `program_id` and matching
+    /// `filter`, in increasing order of pubkey and starting after `after`.
+    /// The order does not depend on the state of the accounts index, so the
+    /// last pubkey of a page can be used to fetch the next one.
+    pub fn get_filtered_program_accounts_sorted_page<F: Fn(&AccountSharedData) -> bool>(
+        &self,
+        program_id: &Pubkey,
+        filter: F,
+        after: Option<&Pubkey>,
+        limit: usize,
+    ) -> ScanResult<Vec<TransactionAccount>> {
+        self.rc.accounts.load_by_program_with_filter_sorted_page(
+            &self.ancestors,
+            self.bank_id,
+            program_id,
+            filter,
+            after,
+            limit,
+        )
+    }
+
     pub fn get_filtered_indexed_accounts<F: Fn(&AccountSharedData) -> bool>(
         &self,
         index_key: &IndexKey,
@@ -5198,6 +5647,16 @@ impl Bank {
         rcache.get_status_any_blockhash(signature, &self.ancestors)
     }
 
+    /// Looks up the slot and status of each of `signatures`, in the same order, holding the
+    /// status cache lock once for the whole batch.
+    pub fn get_signature_statuses_slot(
+        &self,
+        signatures: &[Signature],
+    ) -> Vec<Option<(Slot, Result<()>)>> {
+        let rcache = self.status_cache.read().unwrap();
+        rcache.get_statuses_any_blockhash(signatures, &self.ancestors)
+    }
+
     pub fn get_signature_status(&self, signature: &Signature) -> Option<Result<()>> {
         self.get_signature_status_slot(signature).map(|v| v.1)
     }
@@ -5772,6 +6231,22 @@ impl Bank {
             .map(|(accounts_hash, _)| accounts_hash)
     }
 
+    /// Calculates the full `AccountsHash` of this bank's accounts, along with a proof that the
+    /// account at `pubkey` is included in it
+    ///
+    /// Returns None if the account does not exist or has zero lamports. This scans the whole
+    /// accounts index, so it is only meant for audits, e.g. by ledger-tool.
+    pub fn accounts_hash_proof(
+        &self,
+        pubkey: &Pubkey,
+    ) -> Option<(AccountsHash, AccountsHashProof)> {
+        self.rc.accounts.accounts_db.calculate_accounts_hash_proof(
+            pubkey,
+            self.slot(),
+            Some(&self.ancestors),
+        )
+    }
+
     /// Returns the `IncrementalAccountsHash` that was calculated for this bank's slot
     ///
     /// This fn is used when creating an incremental snapshot with ledger-tool, or when
@@ -6057,6 +6532,37 @@ impl Bank {
         self.capitalization.load(Relaxed)
     }
 
+    /// Return the total supply of the Bank, split into circulating and non-circulating supply
+    ///
+    /// Computing the non-circulating supply scans all stake accounts, so the result is cached
+    /// once the bank is frozen.
+    pub fn supply(
+        &self,
+        non_circulating_accounts: &NonCirculatingAccounts,
+    ) -> ScanResult<Arc<Supply>> {
+        if let Some((cached_accounts, supply)) = self.supply_cache.lock().unwrap().as_ref() {
+            if cached_accounts == non_circulating_accounts {
+                return Ok(Arc::clone(supply));
+            }
+        }
+
+        let is_frozen = self.is_frozen();
+        let non_circulating_supply =
+            calculate_non_circulating_supply_with_accounts(self, non_circulating_accounts)?;
+        let total = self.capitalization();
+        let supply = Arc::new(Supply {
+            total,
+            circulating: total.saturating_sub(non_circulating_supply.lamports),
+            non_circulating: non_circulating_supply.lamports,
+            non_circulating_accounts: non_circulating_supply.accounts,
+        });
+        if is_frozen {
+            *self.supply_cache.lock().unwrap() =
+                Some((non_circulating_accounts.clone(), Arc::clone(&supply)));
+        }
+        Ok(supply)
+    }
+
     /// Return this bank's max_tick_height
     pub fn max_tick_height(&self) -> u64 {
         self.max_tick_height
@@ -6216,6 +6722,14 @@ impl Bank {
             .total_stake()
     }
 
+    /// Get the stake of the given vote account for the leader schedule of the given epoch, or None
+    /// if the stakes of that epoch are not retained. Like `epoch_stakes`, this is keyed by leader
+    /// schedule epoch, so the stake delegated during epoch E is returned for epoch E + 1.
+    pub fn vote_account_stake_in_epoch(&self, epoch: Epoch, vote_account: &Pubkey) -> Option<u64> {
+        self.epoch_stakes(epoch)
+            .map(|epoch_stakes| epoch_stakes.vote_account_stake(vote_account))
+    }
+
     /// Get the fixed stake of the given vote account for the current epoch
     pub fn epoch_vote_account_stake(&self, vote_account: &Pubkey) -> u64 {
         *self
@@ -6407,6 +6921,7 @@ impl Bank {
         let (feature_set, new_feature_activations) =
             self.compute_active_feature_set(allow_new_activations);
         self.feature_set = Arc::new(feature_set);
+        self.fee_model = solana_fee::active_fee_model(&self.feature_set);
 
         // Update activation slot of features in `new_feature_activations`
         for feature_id in new_feature_activations.iter() {
@@ -6798,6 +7313,17 @@ impl Bank {
         self.check_program_modification_slot
     }
 
+    /// Applies `config` to this bank, and so to the banks descending from it
+    pub fn set_config(&mut self, config: &BankConfig) {
+        self.intra_batch_execution_threads = config.intra_batch_execution_threads.unwrap_or(1);
+        // The retention never drops below what the leader schedule needs
+        self.epoch_stakes_retention = config
+            .epoch_stakes_retention
+            .map_or(MAX_LEADER_SCHEDULE_STAKES, |retention| {
+                retention.max(MAX_LEADER_SCHEDULE_STAKES)
+            });
+    }
+
     pub fn set_check_program_modification_slot(&mut self, check: bool) {
         self.check_program_modification_slot = check;
     }
@@ -6806,6 +7332,10 @@ impl Bank {
         &self.fee_structure
     }
 
+    pub fn fee_model(&self) -> &dyn FeeModel {
+        self.fee_model
+    }
+
     pub fn block_id(&self) -> Option<Hash> {
         *self.block_id.read().unwrap()
     }
@@ -6903,12 +7433,13 @@ impl TransactionProcessingCallback for Bank {
         prioritization_fee: u64,
         feature_set: &FeatureSet,
     ) -> FeeDetails {
-        solana_fee::calculate_fee_details(
+        solana_fee::calculate_fee_details_with_model(
             message,
             false, /* zero_fees_for_test */
             lamports_per_signature,
             prioritization_fee,
             FeeFeatures::from(feature_set),
+            self.fee_model,
         )
     }
 }
@@ -7114,6 +7645,10 @@ impl Bank {
         self.fee_structure = fee_structure.clone();
     }
 
+    pub fn set_fee_model(&mut self, fee_model: &'static dyn FeeModel) {
+        self.fee_model = fee_model;
+    }
+
     pub fn load_program(
         &self,
         pubkey: &Pubkey,
@@ -7133,6 +7668,29 @@ impl Bank {
         )
     }
 
+    /// Loads and compiles the `max_programs` most used programs into the
+    /// program cache, if they were unloaded or never loaded on this fork.
+    /// Returns the number of programs considered.
+    pub fn prewarm_program_cache(
+        &self,
+        max_programs: usize,
+        execute_timings: &mut ExecuteTimings,
+    ) -> usize {
+        let program_ids = self
+            .transaction_processor
+            .program_cache
+            .read()
+            .unwrap()
+            .get_most_used_programs(max_programs, self.slot());
+        self.transaction_processor.prewarm_program_cache(
+            self,
+            &program_ids,
+            execute_timings,
+            self.check_program_modification_slot,
+        );
+        program_ids.len()
+    }
+
     pub fn withdraw(&self, pubkey: &Pubkey, lamports: u64) -> Result<()> {
         match self.get_account_with_fixed_root(pubkey) {
             Some(mut account) => {
@@ -0,0 +1,154 @@
+//! Observers of the accounts written by the transactions a bank commits.
+//!
+//! Observers are called from `Bank::commit_transactions()`, right after the
+//! accounts are stored, for every written account matching their filter. They
+//! let code embedded with the runtime, such as tests and local tooling, follow
+//! account changes without a geyser plugin or scanning all the accounts.
+
+use {
+    solana_sdk::{
+        account::{AccountSharedData, ReadableAccount},
+        clock::Slot,
+        pubkey::Pubkey,
+        signature::Signature,
+    },
+    std::{
+        collections::HashSet,
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            Arc, RwLock,
+        },
+    },
+};
+
+/// Accounts an observer is called for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AccountObserverFilter {
+    All,
+    Pubkeys(HashSet<Pubkey>),
+    /// Accounts owned by any of these programs after the write
+    Owners(HashSet<Pubkey>),
+}
+
+impl AccountObserverFilter {
+    fn matches(&self, pubkey: &Pubkey, account: &AccountSharedData) -> bool {
+        match self {
+            Self::All => true,
+            Self::Pubkeys(pubkeys) => pubkeys.contains(pubkey),
+            Self::Owners(owners) => owners.contains(account.owner()),
+        }
+    }
+}
+
+/// An account written by a committed transaction
+#[derive(Debug)]
+pub struct AccountWrite<'a> {
+    pub slot: Slot,
+    pub pubkey: &'a Pubkey,
+    /// The account after the write
+    pub account: &'a AccountSharedData,
+    /// First signature of the transaction writing the account
+    pub signature: &'a Signature,
+}
+
+pub type AccountObserverCallback = Box<dyn Fn(&AccountWrite) + Send + Sync>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AccountObserverId(u64);
+
+struct AccountObserver {
+    id: AccountObserverId,
+    filter: AccountObserverFilter,
+    callback: AccountObserverCallback,
+}
+
+/// Observers registered with a bank, shared with its descendants
+#[derive(Default)]
+pub(super) struct AccountObservers {
+    next_id: AtomicU64,
+    observers: RwLock<Vec<Arc<AccountObserver>>>,
+}
+
+impl AccountObservers {
+    pub(super) fn register(
+        &self,
+        filter: AccountObserverFilter,
+        callback: AccountObserverCallback,
+    ) -> AccountObserverId {
+        let id = AccountObserverId(self.next_id.fetch_add(1, Ordering::Relaxed));
+        let observer = AccountObserver {
+            id,
+            filter,
+            callback,
+        };
+        self.observers.write().unwrap().push(Arc::new(observer));
+        id
+    }
+
+    pub(super) fn unregister(&self, id: AccountObserverId) -> bool {
+        let mut observers = self.observers.write().unwrap();
+        let num_observers = observers.len();
+        observers.retain(|observer| observer.id != id);
+        observers.len() != num_observers
+    }
+
+    pub(super) fn is_empty(&self) -> bool {
+        self.observers.read().unwrap().is_empty()
+    }
+
+    /// Calls the matching observers for each of `accounts`, which were written
+    /// by the transaction with the same index in `signatures`. The observers
+    /// are called without holding the lock, so they may register or unregister
+    /// observers themselves.
+    pub(super) fn notify(
+        &self,
+        slot: Slot,
+        accounts: &[(&Pubkey, &AccountSharedData)],
+        signatures: &[&Signature],
+    ) {
+        debug_assert_eq!(accounts.len(), signatures.len());
+        let observers = self.observers.read().unwrap().clone();
+        for ((pubkey, account), signature) in accounts.iter().zip(signatures) {
+            let account_write = AccountWrite {
+                slot,
+                pubkey,
+                account,
+                signature,
+            };
+            observers
+                .iter()
+                .filter(|observer| observer.filter.matches(pubkey, account))
+                .for_each(|observer| (observer.callback)(&account_write));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, std::sync::atomic::AtomicUsize};
+
+    #[test]
+    fn test_register_from_observer() {
+        let observers = Arc::new(AccountObservers::default());
+        let calls = Arc::new(AtomicUsize::new(0));
+        let id = observers.register(AccountObserverFilter::All, {
+            let observers = Arc::downgrade(&observers);
+            let calls = calls.clone();
+            Box::new(move |_account_write: &AccountWrite| {
+                calls.fetch_add(1, Ordering::Relaxed);
+                // Would deadlock if called while notify() holds the lock
+                let observers = observers.upgrade().unwrap();
+                let id = observers.register(AccountObserverFilter::All, Box::new(|_| ()));
+                assert!(observers.unregister(id));
+            })
+        });
+
+        let pubkey = Pubkey::new_unique();
+        let account = AccountSharedData::default();
+        let signature = Signature::default();
+        observers.notify(0, &[(&pubkey, &account)], &[&signature]);
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+        assert!(observers.unregister(id));
+        assert!(observers.is_empty());
+    }
+}
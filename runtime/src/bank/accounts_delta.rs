@@ -0,0 +1,356 @@
+//! Export of the accounts changed in a frozen bank
+//!
+//! A replica that starts from the same snapshot as the validator it follows
+//! can reproduce that validator's account state by storing the accounts of
+//! each slot's delta, in slot order, without executing any transactions.
+//! Freezing the replica's bank then recomputes the bank hash, which must
+//! match the one in the delta. The bank hash does not cover the
+//! capitalization, so the replica also tracks it from the lamports of the
+//! accounts it stores and checks it against the delta's.
+
+use {
+    super::Bank,
+    serde::{Deserialize, Serialize},
+    solana_sdk::{
+        account::{AccountSharedData, ReadableAccount},
+        clock::Slot,
+        hash::Hash,
+        pubkey::Pubkey,
+    },
+    std::{
+        io::{self, BufRead, Write},
+        sync::atomic::Ordering::Relaxed,
+    },
+    thiserror::Error,
+};
+
+/// The accounts changed in a single frozen bank
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct AccountsDelta {
+    pub slot: Slot,
+    pub parent_slot: Slot,
+    pub parent_hash: Hash,
+    pub bank_hash: Hash,
+    pub collector_id: Pubkey,
+    pub signature_count: u64,
+    pub last_blockhash: Hash,
+    pub capitalization: u64,
+    /// The final state of every account stored in this slot, sorted by pubkey.
+    /// Accounts with zero lamports were closed in this slot.
+    pub accounts: Vec<(Pubkey, AccountSharedData)>,
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum AccountsDeltaError {
+    #[error("bank {0} is already frozen")]
+    BankFrozen(Slot),
+
+    #[error(
+        "delta for slot {delta_slot} with parent {delta_parent_slot} does not apply to bank \
+         {slot} with parent {parent_slot}"
+    )]
+    SlotMismatch {
+        slot: Slot,
+        parent_slot: Slot,
+        delta_slot: Slot,
+        delta_parent_slot: Slot,
+    },
+
+    #[error("parent hash mismatch for slot {slot}: bank has {expected}, delta has {actual}")]
+    ParentHashMismatch {
+        slot: Slot,
+        expected: Hash,
+        actual: Hash,
+    },
+
+    #[error("bank hash mismatch for slot {slot}: computed {computed}, delta has {expected}")]
+    BankHashMismatch {
+        slot: Slot,
+        expected: Hash,
+        computed: Hash,
+    },
+
+    #[error("capitalization mismatch for slot {slot}: computed {computed}, delta has {expected}")]
+    CapitalizationMismatch {
+        slot: Slot,
+        expected: u64,
+        computed: u64,
+    },
+}
+
+impl AccountsDelta {
+    /// Appends this delta to a stream of deltas
+    pub fn serialize_into<W: Write>(&self, writer: W) -> bincode::Result<()> {
+        bincode::serialize_into(writer, self)
+    }
+}
+
+/// Reads the deltas written with [`AccountsDelta::serialize_into`] back from a stream
+pub struct AccountsDeltaReader<R> {
+    reader: R,
+}
+
+impl<R: BufRead> AccountsDeltaReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self { reader }
+    }
+}
+
+impl<R: BufRead> Iterator for AccountsDeltaReader<R> {
+    type Item = bincode::Result<AccountsDelta>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // A stream that ends between deltas is complete, whereas one that
+        // ends within a delta is truncated and reported as an error
+        match self.reader.fill_buf() {
+            Ok([]) => None,
+            Ok(_) => Some(bincode::deserialize_from(&mut self.reader)),
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => self.next(),
+            Err(err) => Some(Err(err.into())),
+        }
+    }
+}
+
+impl Bank {
+    /// Returns the accounts changed in this bank, or None if it is not frozen yet
+    pub fn accounts_delta(&self) -> Option<AccountsDelta> {
+        if !self.is_frozen() {
+            return None;
+        }
+        let mut accounts = self.get_all_accounts_modified_since_parent();
+        accounts.sort_unstable_by(|(a, _), (b, _)| a.cmp(b));
+        Some(AccountsDelta {
+            slot: self.slot(),
+            parent_slot: self.parent_slot(),
+            parent_hash: self.parent_hash(),
+            bank_hash: self.hash(),
+            collector_id: *self.collector_id(),
+            signature_count: self.signature_count(),
+            last_blockhash: self.last_blockhash(),
+            capitalization: self.capitalization(),
+            accounts,
+        })
+    }
+
+    /// Replaces executing this bank's transactions with storing the accounts
+    /// of `delta`, then freezes the bank and checks that its hash matches.
+    ///
+    /// The bank must be a new, unfrozen child of the bank the delta's parent
+    /// was applied to, created with the delta's collector id.
+    pub fn apply_accounts_delta(&self, delta: &AccountsDelta) -> Result<(), AccountsDeltaError> {
+        if self.freeze_started() {
+            return Err(AccountsDeltaError::BankFrozen(self.slot()));
+        }
+        if delta.slot != self.slot() || delta.parent_slot != self.parent_slot() {
+            return Err(AccountsDeltaError::SlotMismatch {
+                slot: self.slot(),
+                parent_slot: self.parent_slot(),
+                delta_slot: delta.slot,
+                delta_parent_slot: delta.parent_slot,
+            });
+        }
+        if delta.parent_hash != self.parent_hash() {
+            return Err(AccountsDeltaError::ParentHashMismatch {
+                slot: self.slot(),
+                expected: self.parent_hash(),
+                actual: delta.parent_hash,
+            });
+        }
+
+        self.tick_height.store(self.max_tick_height, Relaxed);
+        if delta.last_blockhash != self.last_blockhash() {
+            let mut w_blockhash_queue = self.blockhash_queue.write().unwrap();
+            w_blockhash_queue.register_hash(
+                &delta.last_blockhash,
+                self.fee_rate_governor.lamports_per_signature,
+            );
+            self.update_recent_blockhashes_locked(&w_blockhash_queue);
+        }
+        let capitalization = self.capitalization_after_accounts_delta(delta);
+        let accounts: Vec<_> = delta
+            .accounts
+            .iter()
+            .map(|(pubkey, account)| (pubkey, account))
+            .collect();
+        self.store_accounts((self.slot(), accounts.as_slice()));
+        self.signature_count.store(delta.signature_count, Relaxed);
+        self.capitalization
+            .store(capitalization.unwrap_or_default(), Relaxed);
+
+        self.freeze();
+        if self.hash() != delta.bank_hash {
+            return Err(AccountsDeltaError::BankHashMismatch {
+                slot: self.slot(),
+                expected: delta.bank_hash,
+                computed: self.hash(),
+            });
+        }
+        match capitalization {
+            Some(_) if self.capitalization() == delta.capitalization => Ok(()),
+            _ => Err(AccountsDeltaError::CapitalizationMismatch {
+                slot: self.slot(),
+                expected: delta.capitalization,
+                computed: self.capitalization(),
+            }),
+        }
+    }
+
+    /// Returns the capitalization of this bank once the accounts of `delta`
+    /// are stored, or None if it overflows
+    fn capitalization_after_accounts_delta(&self, delta: &AccountsDelta) -> Option<u64> {
+        let mut added = 0u128;
+        let mut removed = 0u128;
+        for (pubkey, account) in &delta.accounts {
+            added += u128::from(account.lamports());
+            removed += self
+                .get_account(pubkey)
+                .map(|account| u128::from(account.lamports()))
+                .unwrap_or_default();
+        }
+        (u128::from(self.capitalization()) + added)
+            .checked_sub(removed)
+            .and_then(|capitalization| u64::try_from(capitalization).ok())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::{bank::tests::new_bank_from_parent_with_bank_forks, genesis_utils},
+        solana_sdk::{
+            account::WritableAccount as _, native_token::LAMPORTS_PER_SOL, signature::Signer as _,
+            signer::keypair::Keypair, sysvar,
+        },
+    };
+
+    #[test]
+    fn test_accounts_delta() {
+        let genesis_config_info = genesis_utils::create_genesis_config(LAMPORTS_PER_SOL);
+        let mint_keypair = genesis_config_info.mint_keypair;
+        let (bank0, bank_forks) =
+            Bank::new_with_bank_forks_for_tests(&genesis_config_info.genesis_config);
+        bank0.freeze();
+        let delta0 = bank0.accounts_delta().unwrap();
+
+        let bank1 = new_bank_from_parent_with_bank_forks(&bank_forks, bank0, &Pubkey::default(), 1);
+        let recipient = Keypair::new().pubkey();
+        bank1
+            .transfer(LAMPORTS_PER_SOL / 2, &mint_keypair, &recipient)
+            .unwrap();
+        assert_eq!(bank1.accounts_delta(), None);
+
+        bank1.freeze();
+        let delta1 = bank1.accounts_delta().unwrap();
+        assert_eq!(delta1.slot, 1);
+        assert_eq!(delta1.parent_slot, 0);
+        assert_eq!(delta1.parent_hash, delta0.bank_hash);
+        assert_eq!(delta1.bank_hash, bank1.hash());
+        assert!(delta1.accounts.is_sorted_by_key(|(pubkey, _)| *pubkey));
+        for pubkey in [mint_keypair.pubkey(), recipient, sysvar::clock::id()] {
+            let (_, account) = delta1
+                .accounts
+                .iter()
+                .find(|(address, _)| *address == pubkey)
+                .unwrap();
+            assert_eq!(Some(account), bank1.get_account(&pubkey).as_ref());
+        }
+        let (_, account) = delta1
+            .accounts
+            .iter()
+            .find(|(address, _)| *address == recipient)
+            .unwrap();
+        assert_eq!(account.lamports(), LAMPORTS_PER_SOL / 2);
+
+        // Deltas round trip through a stream
+        let mut stream = vec![];
+        delta0.serialize_into(&mut stream).unwrap();
+        delta1.serialize_into(&mut stream).unwrap();
+        let deltas = AccountsDeltaReader::new(stream.as_slice())
+            .collect::<bincode::Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(deltas, vec![delta0, delta1]);
+
+        // A truncated stream is an error
+        let mut deltas = AccountsDeltaReader::new(&stream[..stream.len() - 1]);
+        assert!(deltas.next().unwrap().is_ok());
+        assert!(deltas.next().unwrap().is_err());
+    }
+
+    #[test]
+    fn test_apply_accounts_delta() {
+        let genesis_config_info = genesis_utils::create_genesis_config(LAMPORTS_PER_SOL);
+        let genesis_config = &genesis_config_info.genesis_config;
+        let mint_keypair = genesis_config_info.mint_keypair;
+        let (bank0, bank_forks) = Bank::new_with_bank_forks_for_tests(genesis_config);
+        let collector_id = Pubkey::new_unique();
+        let bank1 = new_bank_from_parent_with_bank_forks(&bank_forks, bank0, &collector_id, 1);
+        let recipient = Keypair::new().pubkey();
+        bank1
+            .transfer(LAMPORTS_PER_SOL / 2, &mint_keypair, &recipient)
+            .unwrap();
+        bank1.freeze();
+        let delta = bank1.accounts_delta().unwrap();
+
+        // A replica started from the same state reproduces the bank without the transaction
+        let new_replica_bank = || {
+            let (replica_bank0, replica_bank_forks) =
+                Bank::new_with_bank_forks_for_tests(genesis_config);
+            let replica_bank1 = new_bank_from_parent_with_bank_forks(
+                &replica_bank_forks,
+                replica_bank0,
+                &delta.collector_id,
+                1,
+            );
+            (replica_bank1, replica_bank_forks)
+        };
+        let (replica_bank1, _replica_bank_forks) = new_replica_bank();
+        assert_eq!(replica_bank1.apply_accounts_delta(&delta), Ok(()));
+        assert_eq!(replica_bank1.hash(), bank1.hash());
+        assert_eq!(replica_bank1.capitalization(), bank1.capitalization());
+        assert_eq!(
+            replica_bank1.get_account(&recipient),
+            bank1.get_account(&recipient)
+        );
+        assert_eq!(
+            replica_bank1.apply_accounts_delta(&delta),
+            Err(AccountsDeltaError::BankFrozen(1))
+        );
+
+        // Tampered account state fails the integrity check
+        let mut tampered_delta = delta.clone();
+        let (_, account) = tampered_delta
+            .accounts
+            .iter_mut()
+            .find(|(address, _)| *address == recipient)
+            .unwrap();
+        account.checked_add_lamports(1).unwrap();
+        let (replica_bank1, _replica_bank_forks) = new_replica_bank();
+        assert!(matches!(
+            replica_bank1.apply_accounts_delta(&tampered_delta),
+            Err(AccountsDeltaError::BankHashMismatch { slot: 1, .. })
+        ));
+
+        // So does a capitalization that does not add up, which the bank hash does not cover
+        let mut tampered_delta = delta.clone();
+        tampered_delta.capitalization += 1;
+        let (replica_bank1, _replica_bank_forks) = new_replica_bank();
+        assert_eq!(
+            replica_bank1.apply_accounts_delta(&tampered_delta),
+            Err(AccountsDeltaError::CapitalizationMismatch {
+                slot: 1,
+                expected: bank1.capitalization() + 1,
+                computed: bank1.capitalization(),
+            })
+        );
+
+        // Deltas only apply to the bank they were exported from
+        let (replica_bank1, _replica_bank_forks) = new_replica_bank();
+        let mut wrong_parent_delta = delta;
+        wrong_parent_delta.parent_hash = Hash::new_unique();
+        assert!(matches!(
+            replica_bank1.apply_accounts_delta(&wrong_parent_delta),
+            Err(AccountsDeltaError::ParentHashMismatch { slot: 1, .. })
+        ));
+    }
+}
@@ -63,12 +63,13 @@ impl Bank {
         transaction: &impl TransactionWithMeta,
         fee_budget_limits: &FeeBudgetLimits,
     ) -> u64 {
-        let fee_details = solana_fee::calculate_fee_details(
+        let fee_details = solana_fee::calculate_fee_details_with_model(
             transaction,
             self.get_lamports_per_signature() == 0,
             self.fee_structure().lamports_per_signature,
             fee_budget_limits.prioritization_fee,
             FeeFeatures::from(self.feature_set.as_ref()),
+            self.fee_model(),
         );
         let (reward, _burn) =
             self.calculate_reward_and_burn_fee_details(&CollectorFeeDetails::from(fee_details));
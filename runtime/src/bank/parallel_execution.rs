@@ -0,0 +1,280 @@
+//! Execution of the transactions of a batch on several threads.
+//!
+//! Transactions which lock none of the same accounts for writing are split into groups, which
+//! are executed by the transaction processor concurrently. Transactions of a group keep their
+//! relative order, and the results of all the groups are put back in the order of the batch, so
+//! the outcome is the same as when the batch is executed serially.
+
+use {
+    super::Bank,
+    crate::rent_collector::RentCollectorWithMetrics,
+    rayon::iter::{IntoParallelIterator, ParallelIterator},
+    solana_runtime_transaction::transaction_with_meta::TransactionWithMeta,
+    solana_sdk::pubkey::Pubkey,
+    solana_svm::{
+        account_loader::TransactionCheckResult,
+        transaction_error_metrics::TransactionErrorMetrics,
+        transaction_processing_result::TransactionProcessingResult,
+        transaction_processor::{
+            LoadAndExecuteSanitizedTransactionsOutput, TransactionProcessingConfig,
+            TransactionProcessingEnvironment,
+        },
+    },
+    solana_svm_transaction::svm_message::SVMMessage,
+    solana_timings::ExecuteTimings,
+    std::{collections::HashMap, sync::Arc},
+};
+
+/// Splits the indexes of `transactions` into at most `max_partitions` partitions, so that
+/// transactions locking a same account, with at least one of them for writing, end up in the
+/// same partition. Indexes are sorted within each partition, and partitions by their first
+/// index.
+///
+/// Transactions which failed their checks are not executed, and are spread as the others.
+fn partition_transactions(
+    transactions: &[impl SVMMessage],
+    check_results: &[TransactionCheckResult],
+    max_partitions: usize,
+) -> Vec<Vec<usize>> {
+    let mut conflict_sets = DisjointSets::new(transactions.len());
+    // Transactions locking each account, and whether any of them write locks it
+    let mut account_locks = HashMap::<Pubkey, (Vec<usize>, bool)>::new();
+    for (index, (transaction, check_result)) in transactions.iter().zip(check_results).enumerate() {
+        if check_result.is_err() {
+            continue;
+        }
+        for (key_index, key) in transaction.account_keys().iter().enumerate() {
+            let (indexes, is_write_locked) = account_locks.entry(*key).or_default();
+            indexes.push(index);
+            *is_write_locked |= transaction.is_writable(key_index);
+        }
+    }
+    for (indexes, is_write_locked) in account_locks.values() {
+        if *is_write_locked {
+            for index in &indexes[1..] {
+                conflict_sets.union(indexes[0], *index);
+            }
+        }
+    }
+
+    let mut groups = HashMap::<usize, Vec<usize>>::new();
+    for index in 0..transactions.len() {
+        groups
+            .entry(conflict_sets.find(index))
+            .or_default()
+            .push(index);
+    }
+    let mut groups: Vec<_> = groups.into_values().collect();
+    // Biggest groups first, ties broken by position in the batch to stay deterministic
+    groups.sort_unstable_by_key(|group| (std::cmp::Reverse(group.len()), group[0]));
+
+    let mut partitions = vec![Vec::new(); max_partitions.clamp(1, groups.len().max(1))];
+    for group in groups {
+        let partition = partitions
+            .iter_mut()
+            .min_by_key(|partition| partition.len())
+            .unwrap();
+        partition.extend(group);
+    }
+    for partition in &mut partitions {
+        partition.sort_unstable();
+    }
+    partitions.retain(|partition| !partition.is_empty());
+    partitions.sort_unstable_by_key(|partition| partition[0]);
+    partitions
+}
+
+struct DisjointSets {
+    parents: Vec<usize>,
+}
+
+impl DisjointSets {
+    fn new(len: usize) -> Self {
+        Self {
+            parents: (0..len).collect(),
+        }
+    }
+
+    fn find(&mut self, mut index: usize) -> usize {
+        while self.parents[index] != index {
+            self.parents[index] = self.parents[self.parents[index]];
+            index = self.parents[index];
+        }
+        index
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let (a, b) = (self.find(a), self.find(b));
+        // The smallest index is the root, so that roots do not depend on the union order
+        self.parents[a.max(b)] = a.min(b);
+    }
+}
+
+impl Bank {
+    /// Loads and executes `sanitized_txs`, on several threads if the bank is configured to and
+    /// is called from a thread of a rayon thread pool.
+    ///
+    /// Only batches executed on a rayon thread pool, as in replay, are split, so that the other
+    /// threads of the same pool pick up the partitions when idle rather than competing with it.
+    pub(super) fn load_and_execute_sanitized_transactions(
+        &self,
+        sanitized_txs: &[impl TransactionWithMeta],
+        check_results: Vec<TransactionCheckResult>,
+        environment: &TransactionProcessingEnvironment,
+        rent_collector: &RentCollectorWithMetrics,
+        config: &TransactionProcessingConfig,
+    ) -> LoadAndExecuteSanitizedTransactionsOutput {
+        let max_partitions = self
+            .intra_batch_execution_threads
+            .min(rayon::current_num_threads());
+        let partitions = if max_partitions > 1 && rayon::current_thread_index().is_some() {
+            partition_transactions(sanitized_txs, &check_results, max_partitions)
+        } else {
+            vec![]
+        };
+        if partitions.len() <= 1 {
+            return self
+                .transaction_processor
+                .load_and_execute_sanitized_transactions(
+                    self,
+                    sanitized_txs,
+                    check_results,
+                    environment,
+                    config,
+                );
+        }
+
+        // Transactions are handed to the other threads in their sanitized form, which replayed
+        // transactions merely borrow
+        let sanitized_txs: Vec<_> = sanitized_txs
+            .iter()
+            .map(|tx| tx.as_sanitized_transaction())
+            .collect();
+        let mut check_results: Vec<_> = check_results.into_iter().map(Some).collect();
+        let partitions: Vec<_> = partitions
+            .into_iter()
+            .map(|indexes| {
+                let transactions: Vec<_> =
+                    indexes.iter().map(|i| sanitized_txs[*i].as_ref()).collect();
+                let check_results: Vec<_> = indexes
+                    .iter()
+                    .map(|i| check_results[*i].take().unwrap())
+                    .collect();
+                (indexes, transactions, check_results)
+            })
+            .collect();
+
+        let TransactionProcessingEnvironment {
+            blockhash,
+            blockhash_lamports_per_signature,
+            epoch_total_stake,
+            ref feature_set,
+            fee_lamports_per_signature,
+            rent_collector: _,
+        } = *environment;
+        let outputs: Vec<_> = partitions
+            .into_par_iter()
+            .map(|(indexes, transactions, check_results)| {
+                let environment = TransactionProcessingEnvironment {
+                    blockhash,
+                    blockhash_lamports_per_signature,
+                    epoch_total_stake,
+                    feature_set: Arc::clone(feature_set),
+                    fee_lamports_per_signature,
+                    rent_collector: Some(rent_collector),
+                };
+                let output = self
+                    .transaction_processor
+                    .load_and_execute_sanitized_transactions(
+                        self,
+                        &transactions,
+                        check_results,
+                        &environment,
+                        config,
+                    );
+                (indexes, output)
+            })
+            .collect();
+
+        let mut error_metrics = TransactionErrorMetrics::default();
+        let mut execute_timings = ExecuteTimings::default();
+        let mut processing_results: Vec<Option<TransactionProcessingResult>> =
+            (0..sanitized_txs.len()).map(|_| None).collect();
+        for (indexes, output) in outputs {
+            error_metrics.accumulate(&output.error_metrics);
+            execute_timings.accumulate(&output.execute_timings);
+            for (index, processing_result) in indexes.into_iter().zip(output.processing_results) {
+                processing_results[index] = Some(processing_result);
+            }
+        }
+
+        LoadAndExecuteSanitizedTransactionsOutput {
+            error_metrics,
+            execute_timings,
+            processing_results: processing_results
+                .into_iter()
+                .map(|processing_result| processing_result.unwrap())
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        solana_sdk::{
+            hash::Hash,
+            signature::{Keypair, Signer},
+            system_transaction,
+            transaction::{SanitizedTransaction, TransactionError},
+        },
+    };
+
+    fn transfer(from: &Keypair, to: &Pubkey) -> SanitizedTransaction {
+        SanitizedTransaction::from_transaction_for_tests(system_transaction::transfer(
+            from,
+            to,
+            1,
+            Hash::default(),
+        ))
+    }
+
+    #[test]
+    fn test_partition_transactions() {
+        let payers: Vec<_> = (0..4).map(|_| Keypair::new()).collect();
+        let recipient = Pubkey::new_unique();
+        let transactions = vec![
+            transfer(&payers[0], &Pubkey::new_unique()),
+            transfer(&payers[1], &recipient),
+            transfer(&payers[2], &Pubkey::new_unique()),
+            transfer(&payers[3], &recipient),
+            transfer(&payers[0], &Pubkey::new_unique()),
+        ];
+        let check_results = vec![Ok(Default::default()); transactions.len()];
+
+        // Transactions 0 and 4 share a payer, 1 and 3 a recipient
+        assert_eq!(
+            partition_transactions(&transactions, &check_results, 8),
+            vec![vec![0, 4], vec![1, 3], vec![2]]
+        );
+        assert_eq!(
+            partition_transactions(&transactions, &check_results, 2),
+            vec![vec![0, 2, 4], vec![1, 3]]
+        );
+        assert_eq!(
+            partition_transactions(&transactions, &check_results, 1),
+            vec![vec![0, 1, 2, 3, 4]]
+        );
+
+        // Transactions which are not executed do not conflict with the others
+        let mut check_results = check_results;
+        check_results[4] = Err(TransactionError::AlreadyProcessed);
+        assert_eq!(
+            partition_transactions(&transactions, &check_results, 8),
+            vec![vec![0], vec![1, 3], vec![2], vec![4]]
+        );
+
+        assert!(partition_transactions(&[] as &[SanitizedTransaction], &[], 8).is_empty());
+    }
+}
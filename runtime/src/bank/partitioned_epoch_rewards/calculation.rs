@@ -373,6 +373,11 @@ impl Bank {
                     let stake_account = (*stake_account).to_owned();
 
                     let vote_pubkey = stake_account.delegation().voter_pubkey;
+                    let effective_stake = stake_account.delegation().stake(
+                        rewarded_epoch,
+                        stake_history,
+                        new_warmup_cooldown_rate_epoch,
+                    );
                     let (mut stake_account, stake_state) =
                         <(AccountSharedData, StakeStateV2)>::from(stake_account);
                     let vote_account = get_vote_account(&vote_pubkey)?;
@@ -436,6 +441,7 @@ impl Bank {
                                 commission: Some(commission),
                             },
                             stake,
+                            effective_stake,
                         });
                     } else {
                         debug!(
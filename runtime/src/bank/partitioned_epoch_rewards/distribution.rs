@@ -541,6 +541,7 @@ mod tests {
             stake_pubkey: nonexistent_account,
             stake: new_stake,
             stake_reward_info,
+            effective_stake: new_stake.delegation.stake,
         };
         let stakes_cache = bank.stakes_cache.stakes();
         let stakes_cache_accounts = stakes_cache.stake_delegations();
@@ -571,6 +572,7 @@ mod tests {
             stake_pubkey: overflowing_account,
             stake: new_stake,
             stake_reward_info,
+            effective_stake: new_stake.delegation.stake,
         };
         let stakes_cache = bank.stakes_cache.stakes();
         let stakes_cache_accounts = stakes_cache.stake_delegations();
@@ -609,6 +611,7 @@ mod tests {
             stake_pubkey: successful_account,
             stake: new_stake,
             stake_reward_info,
+            effective_stake: new_stake.delegation.stake,
         };
         let stakes_cache = bank.stakes_cache.stakes();
         let stakes_cache_accounts = stakes_cache.stake_delegations();
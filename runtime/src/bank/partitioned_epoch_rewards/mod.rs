@@ -14,6 +14,7 @@ use {
     },
     solana_sdk::{
         account::AccountSharedData,
+        clock::Epoch,
         pubkey::Pubkey,
         reward_info::RewardInfo,
         stake::state::{Delegation, Stake},
@@ -36,6 +37,8 @@ pub(crate) struct PartitionedStakeReward {
     /// fields are available on calculation, but RewardInfo::post_balance must
     /// be updated based on current account state before recording.
     pub stake_reward_info: RewardInfo,
+    /// Effective stake of the account in the rewarded epoch
+    pub effective_stake: u64,
 }
 
 type PartitionedStakeRewards = Vec<PartitionedStakeReward>;
@@ -156,6 +159,27 @@ impl KeyedRewardsAndNumPartitions {
     }
 }
 
+/// Reward of a stake account for an epoch
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EpochStakeReward {
+    pub stake_pubkey: Pubkey,
+    pub vote_pubkey: Pubkey,
+    /// Lamports credited to the stake account
+    pub lamports: u64,
+    /// Commission of the vote account when the rewards were calculated
+    pub commission: Option<u8>,
+    /// Effective stake of the account in the rewarded epoch
+    pub effective_stake: u64,
+}
+
+/// Stake rewards of an epoch, sorted by stake account
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EpochStakeRewards {
+    /// Epoch the rewards were earned in, the one before they are paid
+    pub rewarded_epoch: Epoch,
+    pub rewards: Vec<EpochStakeReward>,
+}
+
 impl Bank {
     pub fn get_rewards_and_num_partitions(&self) -> KeyedRewardsAndNumPartitions {
         let keyed_rewards = self.rewards.read().unwrap().clone();
@@ -174,6 +198,31 @@ impl Bank {
         }
     }
 
+    /// Stake rewards calculated at the start of this epoch, or None if they
+    /// have all been distributed
+    pub fn epoch_stake_rewards(&self) -> Option<EpochStakeRewards> {
+        let EpochRewardStatus::Active(status) = &self.epoch_reward_status else {
+            return None;
+        };
+        let mut rewards: Vec<_> = status
+            .stake_rewards_by_partition
+            .iter()
+            .flatten()
+            .map(|reward| EpochStakeReward {
+                stake_pubkey: reward.stake_pubkey,
+                vote_pubkey: reward.stake.delegation.voter_pubkey,
+                lamports: u64::try_from(reward.stake_reward_info.lamports).unwrap_or_default(),
+                commission: reward.stake_reward_info.commission,
+                effective_stake: reward.effective_stake,
+            })
+            .collect();
+        rewards.sort_unstable_by_key(|reward| reward.stake_pubkey);
+        Some(EpochStakeRewards {
+            rewarded_epoch: self.epoch().saturating_sub(1),
+            rewards,
+        })
+    }
+
     pub(crate) fn set_epoch_reward_status_active(
         &mut self,
         distribution_starting_block_height: u64,
@@ -267,6 +316,7 @@ mod tests {
                     stake_pubkey: stake_reward.stake_pubkey,
                     stake,
                     stake_reward_info: stake_reward.stake_reward_info,
+                    effective_stake: stake.delegation.stake,
                 })
             } else {
                 None
@@ -556,6 +606,17 @@ mod tests {
                 if slot == SLOTS_PER_EPOCH {
                     // cap should increase because of new epoch rewards
                     assert!(post_cap > pre_cap);
+
+                    let epoch_stake_rewards = curr_bank.epoch_stake_rewards().unwrap();
+                    assert_eq!(epoch_stake_rewards.rewarded_epoch, 0);
+                    assert_eq!(epoch_stake_rewards.rewards.len(), 100);
+                    assert!(epoch_stake_rewards
+                        .rewards
+                        .windows(2)
+                        .all(|rewards| rewards[0].stake_pubkey < rewards[1].stake_pubkey));
+                    assert!(epoch_stake_rewards.rewards.iter().all(|reward| {
+                        reward.effective_stake == 2_000_000_000 && reward.commission.is_some()
+                    }));
                 } else {
                     assert_eq!(post_cap, pre_cap);
                 }
@@ -569,6 +630,7 @@ mod tests {
                     curr_bank.get_reward_interval(),
                     RewardInterval::OutsideInterval
                 );
+                assert_eq!(curr_bank.epoch_stake_rewards(), None);
 
                 let account = curr_bank
                     .get_account(&solana_sdk::sysvar::epoch_rewards::id())
@@ -71,6 +71,7 @@ mod tests {
                 output_path,
                 storage_entry.accounts.len(),
                 storage_access,
+                storage_entry.accounts.provider(),
             )?;
             let new_storage_entry = AccountStorageEntry::new_existing(
                 storage_entry.slot(),
@@ -217,13 +218,17 @@ mod tests {
 
                 let versioned_epoch_stakes = mem::take(&mut bank_fields.versioned_epoch_stakes);
                 let accounts_lt_hash = bank_fields.accounts_lt_hash.clone().map(Into::into);
+                let storages_to_serialize =
+                    get_storages_to_serialize(&bank2.get_snapshot_storages(None));
+                let storage_formats =
+                    serde_snapshot::storage_formats_to_serialize(&storages_to_serialize);
                 serde_snapshot::serialize_bank_snapshot_into(
                     &mut writer,
                     bank_fields,
                     bank2.get_bank_hash_stats(),
                     accounts_db.get_accounts_delta_hash(bank2_slot).unwrap(),
                     expected_accounts_hash,
-                    &get_storages_to_serialize(&bank2.get_snapshot_storages(None)),
+                    &storages_to_serialize,
                     ExtraFieldsToSerialize {
                         lamports_per_signature: bank2.fee_rate_governor.lamports_per_signature,
                         incremental_snapshot_persistence: expected_incremental_snapshot_persistence
@@ -231,6 +236,7 @@ mod tests {
                         epoch_accounts_hash: expected_epoch_accounts_hash,
                         versioned_epoch_stakes,
                         accounts_lt_hash,
+                        storage_formats,
                     },
                     accounts_db.write_version.load(Ordering::Acquire),
                 )
@@ -608,6 +614,7 @@ mod tests {
                     epoch_accounts_hash: Some(EpochAccountsHash::new(Hash::new_unique())),
                     versioned_epoch_stakes,
                     accounts_lt_hash: Some(AccountsLtHash(LtHash::identity()).into()),
+                    storage_formats: Vec::default(),
                 },
                 StoredMetaWriteVersion::default(),
             )
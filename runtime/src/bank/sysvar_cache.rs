@@ -7,8 +7,10 @@ mod tests {
         super::*,
         crate::inflation_rewards::points::PointValue,
         solana_sdk::{
-            genesis_config::create_genesis_config, pubkey::Pubkey,
-            sysvar::epoch_rewards::EpochRewards,
+            account::create_account_shared_data_with_fields as create_account,
+            genesis_config::create_genesis_config,
+            pubkey::Pubkey,
+            sysvar::{self, clock::Clock, epoch_rewards::EpochRewards},
         },
         std::sync::Arc,
     };
@@ -152,4 +154,45 @@ mod tests {
             expected_epoch_rewards,
         );
     }
+
+    #[test]
+    fn test_sysvar_cache_refreshed_on_sysvar_update() {
+        let (genesis_config, _mint_keypair) = create_genesis_config(100_000);
+        let bank0 = Arc::new(Bank::new_for_tests(&genesis_config));
+        let bank1_slot = bank0.slot() + 1;
+        let bank1 = Bank::new_from_parent(bank0, &Pubkey::default(), bank1_slot);
+
+        let sysvar_cache = bank1.transaction_processor.sysvar_cache();
+        let cached_clock = sysvar_cache.get_clock().unwrap();
+        let cached_rent = sysvar_cache.get_rent().unwrap();
+        assert_eq!(cached_clock.slot, bank1_slot);
+        drop(sysvar_cache);
+
+        let new_clock = Clock {
+            unix_timestamp: cached_clock.unix_timestamp + 1,
+            ..*cached_clock
+        };
+        bank1.update_sysvar_account(&sysvar::clock::id(), |account| {
+            create_account(
+                &new_clock,
+                bank1.inherit_specially_retained_account_fields(account),
+            )
+        });
+
+        // The updated sysvar is reloaded, the others are kept as they are
+        let sysvar_cache = bank1.transaction_processor.sysvar_cache();
+        assert_eq!(*sysvar_cache.get_clock().unwrap(), new_clock);
+        assert!(Arc::ptr_eq(&sysvar_cache.get_rent().unwrap(), &cached_rent));
+        drop(sysvar_cache);
+
+        // Sysvars missing from the cache are loaded when it is filled, not on update
+        bank1.transaction_processor.reset_sysvar_cache();
+        bank1.update_sysvar_account(&sysvar::clock::id(), |account| {
+            create_account(
+                &new_clock,
+                bank1.inherit_specially_retained_account_fields(account),
+            )
+        });
+        assert!(bank1.transaction_processor.sysvar_cache().is_empty());
+    }
 }
@@ -17,6 +17,7 @@ use {
         stake_history::StakeHistory,
         stakes::InvalidCacheEntryReason,
         status_cache::MAX_CACHE_ENTRIES,
+        transaction_recorder::NoopTransactionRecorder,
     },
     agave_transaction_view::static_account_keys_frame::MAX_STATIC_ACCOUNTS_PER_PACKET,
     assert_matches::assert_matches,
@@ -106,6 +107,7 @@ use {
     solana_stake_program::stake_state::{self, StakeStateV2},
     solana_svm::{
         account_loader::{FeesOnlyTransaction, LoadedTransaction},
+        account_overrides::AccountOverrides,
         rollback_accounts::RollbackAccounts,
         transaction_commit_result::TransactionCommitResultExtensions,
         transaction_execution_result::ExecutedTransaction,
@@ -256,6 +258,8 @@ fn new_executed_processing_result(
                 return_data: None,
                 executed_units: 0,
                 accounts_data_len_delta: 0,
+                memory_usage: None,
+                account_privileges: None,
             },
             programs_modified_by_tx: HashMap::new(),
         },
@@ -405,6 +409,55 @@ fn test_bank_update_epoch_stakes() {
     );
 }
 
+#[test]
+fn test_bank_epoch_stakes_retention() {
+    let mut bank = create_simple_test_bank(100_000);
+    let retention = MAX_LEADER_SCHEDULE_STAKES * 2;
+    bank.set_config(&BankConfig {
+        epoch_stakes_retention: Some(retention),
+        ..BankConfig::default()
+    });
+    assert_eq!(bank.epoch_stakes_retention, retention);
+
+    let leader_schedule_epoch = retention * 2;
+    for epoch in 2..=leader_schedule_epoch {
+        bank.update_epoch_stakes(epoch);
+    }
+    let mut epochs: Vec<_> = bank.epoch_stakes_map().keys().copied().collect();
+    epochs.sort_unstable();
+    assert_eq!(
+        epochs,
+        (leader_schedule_epoch - retention..=leader_schedule_epoch).collect::<Vec<_>>()
+    );
+
+    let (vote_pubkey, (stake, _)) = bank
+        .epoch_vote_accounts(leader_schedule_epoch)
+        .unwrap()
+        .iter()
+        .next()
+        .map(|(pubkey, entry)| (*pubkey, entry.clone()))
+        .unwrap();
+    assert_eq!(
+        bank.vote_account_stake_in_epoch(leader_schedule_epoch - retention, &vote_pubkey),
+        Some(stake)
+    );
+    assert_eq!(
+        bank.vote_account_stake_in_epoch(leader_schedule_epoch - retention - 1, &vote_pubkey),
+        None
+    );
+    assert_eq!(
+        bank.vote_account_stake_in_epoch(leader_schedule_epoch, &Pubkey::new_unique()),
+        Some(0)
+    );
+
+    // The retention never drops below what the leader schedule needs.
+    bank.set_config(&BankConfig {
+        epoch_stakes_retention: Some(1),
+        ..BankConfig::default()
+    });
+    assert_eq!(bank.epoch_stakes_retention, MAX_LEADER_SCHEDULE_STAKES);
+}
+
 fn bank0_sysvar_delta() -> u64 {
     const SLOT_HISTORY_SYSVAR_MIN_BALANCE: u64 = 913_326_000;
     SLOT_HISTORY_SYSVAR_MIN_BALANCE
@@ -2800,6 +2853,65 @@ fn test_bank_tx_fee() {
     );
 }
 
+#[derive(Debug)]
+struct FlatPlusDoubleSignatureFeeModel;
+
+impl FeeModel for FlatPlusDoubleSignatureFeeModel {
+    fn base_fee(&self) -> u64 {
+        1_000
+    }
+
+    fn signature_fee(&self, signature_count: u64, lamports_per_signature: u64) -> u64 {
+        signature_count
+            .saturating_mul(lamports_per_signature)
+            .saturating_mul(2)
+    }
+}
+
+#[test]
+fn test_bank_tx_fee_with_fee_model() {
+    let arbitrary_transfer_amount = 42_000;
+    let mint = arbitrary_transfer_amount * 100;
+    let leader = solana_pubkey::new_rand();
+    let GenesisConfigInfo {
+        mut genesis_config,
+        mint_keypair,
+        ..
+    } = create_genesis_config_with_leader(mint, &leader, 3);
+    genesis_config.fee_rate_governor = FeeRateGovernor::new(5000, 0);
+
+    let mut bank = Bank::new_for_tests(&genesis_config);
+    bank.set_fee_model(&FlatPlusDoubleSignatureFeeModel);
+    let (bank, bank_forks) = bank.wrap_with_bank_forks_for_tests();
+    let expected_fee_paid = 1_000 + 2 * bank.fee_structure().lamports_per_signature;
+
+    let key = solana_pubkey::new_rand();
+    let tx = system_transaction::transfer(
+        &mint_keypair,
+        &key,
+        arbitrary_transfer_amount,
+        bank.last_blockhash(),
+    );
+    let message = new_sanitized_message(tx.message().clone());
+    assert_eq!(bank.get_fee_for_message(&message), Some(expected_fee_paid));
+
+    assert_eq!(bank.process_transaction(&tx), Ok(()));
+    assert_eq!(
+        bank.get_balance(&mint_keypair.pubkey()),
+        mint - arbitrary_transfer_amount - expected_fee_paid
+    );
+
+    // The fee model carries over to child banks
+    goto_end_of_slot(bank.clone());
+    let bank = new_bank_from_parent_with_bank_forks(bank_forks.as_ref(), bank, &leader, 1);
+    let tx = system_transaction::transfer(&mint_keypair, &key, 1, bank.last_blockhash());
+    assert_eq!(bank.process_transaction(&tx), Ok(()));
+    assert_eq!(
+        bank.get_balance(&mint_keypair.pubkey()),
+        mint - arbitrary_transfer_amount - 1 - 2 * expected_fee_paid
+    );
+}
+
 #[test]
 fn test_bank_tx_compute_unit_fee() {
     solana_logger::setup();
@@ -3282,6 +3394,75 @@ fn test_load_and_execute_commit_transactions_fees_only(enable_fees_only_txs: boo
     }
 }
 
+#[test]
+fn test_intra_batch_parallel_execution() {
+    let (genesis_config, mint_keypair) = create_genesis_config(sol_to_lamports(1.));
+    let payers: Vec<_> = (0..8).map(|_| Keypair::new()).collect();
+    let recipients: Vec<_> = (0..6).map(|_| Pubkey::new_unique()).collect();
+    // Batches are only split when executed on a rayon thread pool, as in replay
+    let thread_pool = ThreadPoolBuilder::new().num_threads(4).build().unwrap();
+
+    let process_batch = |intra_batch_execution_threads: Option<usize>| {
+        let mut bank = Bank::new_for_tests(&genesis_config);
+        bank.set_config(&BankConfig {
+            intra_batch_execution_threads,
+            ..BankConfig::default()
+        });
+        for payer in &payers {
+            bank.transfer(LAMPORTS_PER_SOL / 100, &mint_keypair, &payer.pubkey())
+                .unwrap();
+        }
+
+        let transactions: Vec<_> = payers
+            .iter()
+            .enumerate()
+            .map(|(i, payer)| {
+                // The last payer can't afford its transfer
+                let lamports = if i == payers.len() - 1 {
+                    LAMPORTS_PER_SOL
+                } else {
+                    1_000_000 + i as u64
+                };
+                // The first two pairs of payers transfer to the same recipient, and conflict
+                let recipient = if i < 4 {
+                    recipients[i / 2]
+                } else {
+                    recipients[i - 2]
+                };
+                system_transaction::transfer(payer, &recipient, lamports, bank.last_blockhash())
+            })
+            .collect();
+        let results = thread_pool.install(|| bank.process_transactions(transactions.iter()));
+        let balances: Vec<_> = payers
+            .iter()
+            .map(Keypair::pubkey)
+            .chain(recipients.iter().copied())
+            .map(|pubkey| bank.get_balance(&pubkey))
+            .collect();
+        (results, balances, bank.transaction_count())
+    };
+
+    let serial = process_batch(None);
+    let parallel = process_batch(Some(4));
+    assert_eq!(parallel, serial);
+    assert_eq!(
+        serial.0,
+        vec![
+            Ok(()),
+            Err(TransactionError::AccountInUse),
+            Ok(()),
+            Err(TransactionError::AccountInUse),
+            Ok(()),
+            Ok(()),
+            Ok(()),
+            Err(TransactionError::InstructionError(
+                0,
+                SystemError::ResultWithNegativeLamports.into()
+            )),
+        ]
+    );
+}
+
 #[test]
 fn test_readonly_relaxed_locks() {
     let (genesis_config, _) = create_genesis_config(3);
@@ -3527,6 +3708,32 @@ fn test_bank_hash_internal_state(is_accounts_lt_hash_enabled: bool) {
     assert!(bank2.verify_accounts_hash(None, VerifyAccountsHashConfig::default_for_test(), None,));
 }
 
+#[test]
+fn test_bank_accounts_hash_proof() {
+    let (genesis_config, mint_keypair) = create_genesis_config(sol_to_lamports(1.));
+    let (bank0, bank_forks) = Bank::new_with_bank_forks_for_tests(&genesis_config);
+    let bank1 = new_from_parent_with_fork_next_slot(bank0, &bank_forks);
+    let amount = genesis_config.rent.minimum_balance(0);
+    let pubkey = solana_pubkey::new_rand();
+    bank1.transfer(amount, &mint_keypair, &pubkey).unwrap();
+    bank1.freeze();
+    bank1.squash();
+    bank1.force_flush_accounts_cache();
+    let accounts_hash = bank1.update_accounts_hash_for_tests();
+
+    for address in [pubkey, mint_keypair.pubkey()] {
+        let (proof_accounts_hash, proof) = bank1.accounts_hash_proof(&address).unwrap();
+        assert_eq!(proof_accounts_hash, accounts_hash);
+        assert_eq!(proof.pubkey, address);
+        let account = bank1.get_account(&address).unwrap();
+        assert!(proof.verify(&account, &accounts_hash));
+        assert!(!proof.verify(&account, &AccountsHash(Hash::new_unique())));
+    }
+    assert!(bank1
+        .accounts_hash_proof(&solana_pubkey::new_rand())
+        .is_none());
+}
+
 #[test_case(false; "accounts lt hash disabled")]
 #[test_case(true; "accounts lt hash enabled")]
 fn test_bank_hash_internal_state_verify(is_accounts_lt_hash_enabled: bool) {
@@ -6253,6 +6460,55 @@ fn test_process_transaction_with_too_many_account_locks() {
     assert_eq!(result, Err(TransactionError::TooManyAccountLocks));
 }
 
+#[test]
+fn test_transaction_account_lock_limit_feature_gate() {
+    let (genesis_config, mint_keypair) = create_genesis_config(500);
+    let mut bank = Bank::new_for_tests(&genesis_config);
+
+    let transaction_with_num_accounts = |num_accounts: usize| {
+        let mut tx = system_transaction::transfer(
+            &mint_keypair,
+            &solana_pubkey::new_rand(),
+            1,
+            genesis_config.hash(),
+        );
+        while tx.message.account_keys.len() < num_accounts {
+            tx.message.account_keys.push(solana_pubkey::new_rand());
+        }
+        RuntimeTransaction::from_transaction_for_tests(tx)
+    };
+
+    for (is_active, expected_limit) in [
+        (false, LEGACY_TX_ACCOUNT_LOCK_LIMIT),
+        (true, MAX_TX_ACCOUNT_LOCKS),
+    ] {
+        if is_active {
+            bank.activate_feature(&feature_set::increase_tx_account_lock_limit::id());
+        } else {
+            bank.deactivate_feature(&feature_set::increase_tx_account_lock_limit::id());
+        }
+        assert_eq!(bank.get_transaction_account_lock_limit(), expected_limit);
+
+        let txs = [
+            transaction_with_num_accounts(expected_limit),
+            transaction_with_num_accounts(expected_limit + 1),
+        ];
+        let results = bank.try_lock_accounts(&txs);
+        assert_eq!(
+            results,
+            vec![Ok(()), Err(TransactionError::TooManyAccountLocks)]
+        );
+        bank.unlock_accounts(txs.iter().zip(results.iter()));
+    }
+
+    // An explicit runtime override takes precedence over the feature gate
+    bank.transaction_account_lock_limit = Some(LEGACY_TX_ACCOUNT_LOCK_LIMIT);
+    assert_eq!(
+        bank.get_transaction_account_lock_limit(),
+        LEGACY_TX_ACCOUNT_LOCK_LIMIT
+    );
+}
+
 #[test]
 fn test_program_id_as_payer() {
     solana_logger::setup();
@@ -9420,11 +9676,7 @@ fn test_tx_log_order() {
             &batch,
             MAX_PROCESSING_AGE,
             false,
-            ExecutionRecordingConfig {
-                enable_cpi_recording: false,
-                enable_log_recording: true,
-                enable_return_data_recording: false,
-            },
+            ExecutionRecordingConfig::new(false, true, false),
             &mut ExecuteTimings::default(),
             None,
         )
@@ -9528,11 +9780,7 @@ fn test_tx_return_data() {
                 &batch,
                 MAX_PROCESSING_AGE,
                 false,
-                ExecutionRecordingConfig {
-                    enable_cpi_recording: false,
-                    enable_log_recording: false,
-                    enable_return_data_recording: true,
-                },
+                ExecutionRecordingConfig::new(false, false, true),
                 &mut ExecuteTimings::default(),
                 None,
             )
@@ -9616,6 +9864,152 @@ fn test_load_and_execute_commit_transactions_rent_debits() {
     }
 }
 
+#[derive(Default)]
+struct TestTransactionRecorder {
+    recorded: Mutex<Vec<VersionedTransaction>>,
+    fail: bool,
+}
+
+impl RecordTransactions for TestTransactionRecorder {
+    fn record_transactions(
+        &self,
+        _bank_slot: Slot,
+        transactions: Vec<VersionedTransaction>,
+    ) -> Result<Option<usize>> {
+        if self.fail {
+            return Err(TransactionError::CommitCancelled);
+        }
+        let mut recorded = self.recorded.lock().unwrap();
+        let starting_transaction_index = recorded.len();
+        recorded.extend(transactions);
+        Ok(Some(starting_transaction_index))
+    }
+}
+
+#[test]
+fn test_load_execute_and_commit_transactions_with_recorder() {
+    let (genesis_config, mint_keypair) = create_genesis_config(sol_to_lamports(1.));
+    let (bank, _bank_forks) = Bank::new_with_bank_forks_for_tests(&genesis_config);
+    let amount = genesis_config.rent.minimum_balance(0);
+    let alice = Keypair::new();
+    let bob = Keypair::new();
+    let load_execute_and_commit = |txs: Vec<Transaction>, recorder: &dyn RecordTransactions| {
+        let batch = bank.prepare_batch_for_tests(txs);
+        bank.load_execute_and_commit_transactions_with_recorder(
+            &batch,
+            MAX_PROCESSING_AGE,
+            false,
+            TransactionProcessingConfig {
+                compute_budget: bank.compute_budget(),
+                transaction_account_lock_limit: Some(bank.get_transaction_account_lock_limit()),
+                ..TransactionProcessingConfig::default()
+            },
+            &mut ExecuteTimings::default(),
+            &mut TransactionErrorMetrics::default(),
+            recorder,
+        )
+    };
+
+    // Transactions which are not processed are not recorded
+    let recorder = TestTransactionRecorder::default();
+    let tx_to_alice = system_transaction::transfer(
+        &mint_keypair,
+        &alice.pubkey(),
+        amount,
+        bank.last_blockhash(),
+    );
+    let tx_unprocessed =
+        system_transaction::transfer(&alice, &bob.pubkey(), amount, bank.last_blockhash());
+    let output = load_execute_and_commit(vec![tx_to_alice.clone(), tx_unprocessed], &recorder);
+    assert_eq!(output.processed_transaction_indexes, vec![0]);
+    assert_eq!(output.processed_with_successful_result_count, 1);
+    assert_eq!(output.starting_transaction_index, Some(0));
+    let (commit_results, _balances) = output.commit_result.unwrap();
+    assert!(commit_results[0].is_ok());
+    assert!(commit_results[1].is_err());
+    assert_eq!(
+        *recorder.recorded.lock().unwrap(),
+        vec![VersionedTransaction::from(tx_to_alice)]
+    );
+    assert_eq!(bank.get_balance(&alice.pubkey()), amount);
+
+    // Nothing is committed if the recorder fails
+    let recorder = TestTransactionRecorder {
+        fail: true,
+        ..TestTransactionRecorder::default()
+    };
+    let tx_to_bob =
+        system_transaction::transfer(&mint_keypair, &bob.pubkey(), amount, bank.last_blockhash());
+    let output = load_execute_and_commit(vec![tx_to_bob.clone()], &recorder);
+    assert_eq!(output.processed_transaction_indexes, vec![0]);
+    assert_eq!(
+        output.commit_result.unwrap_err(),
+        TransactionError::CommitCancelled
+    );
+    assert_eq!(bank.get_balance(&bob.pubkey()), 0);
+
+    // The no-op recorder lets everything through
+    let output = load_execute_and_commit(vec![tx_to_bob], &NoopTransactionRecorder);
+    assert_eq!(output.starting_transaction_index, None);
+    let (commit_results, _balances) = output.commit_result.unwrap();
+    assert!(commit_results[0].is_ok());
+    assert_eq!(bank.get_balance(&bob.pubkey()), amount);
+}
+
+#[test]
+fn test_account_observers() {
+    let (genesis_config, mint_keypair) = create_genesis_config(sol_to_lamports(1.));
+    let (bank, bank_forks) = Bank::new_with_bank_forks_for_tests(&genesis_config);
+    let amount = genesis_config.rent.minimum_balance(0);
+    let alice = Pubkey::new_unique();
+    let bob = Pubkey::new_unique();
+
+    let alice_writes = Arc::new(Mutex::new(Vec::new()));
+    let alice_observer =
+        bank.register_account_observer(AccountObserverFilter::Pubkeys(HashSet::from([alice])), {
+            let alice_writes = alice_writes.clone();
+            move |account_write| {
+                alice_writes.lock().unwrap().push((
+                    account_write.slot,
+                    *account_write.signature,
+                    account_write.account.lamports(),
+                ))
+            }
+        });
+    let system_writes = Arc::new(AtomicUsize::new(0));
+    bank.register_account_observer(
+        AccountObserverFilter::Owners(HashSet::from([system_program::id()])),
+        {
+            let system_writes = system_writes.clone();
+            move |_account_write| {
+                system_writes.fetch_add(1, Relaxed);
+            }
+        },
+    );
+
+    let tx = system_transaction::transfer(&mint_keypair, &alice, amount, bank.last_blockhash());
+    assert_eq!(bank.process_transaction(&tx), Ok(()));
+    assert_eq!(
+        *alice_writes.lock().unwrap(),
+        vec![(bank.slot(), tx.signatures[0], amount)]
+    );
+    // The mint and alice
+    assert_eq!(system_writes.load(Relaxed), 2);
+
+    // Observers carry over to child banks, until unregistered
+    goto_end_of_slot(bank.clone());
+    let bank =
+        new_bank_from_parent_with_bank_forks(bank_forks.as_ref(), bank, &Pubkey::default(), 1);
+    assert!(bank.unregister_account_observer(alice_observer));
+    assert!(!bank.unregister_account_observer(alice_observer));
+    let tx = system_transaction::transfer(&mint_keypair, &alice, amount, bank.last_blockhash());
+    assert_eq!(bank.process_transaction(&tx), Ok(()));
+    let tx = system_transaction::transfer(&mint_keypair, &bob, amount, bank.last_blockhash());
+    assert_eq!(bank.process_transaction(&tx), Ok(()));
+    assert_eq!(alice_writes.lock().unwrap().len(), 1);
+    assert_eq!(system_writes.load(Relaxed), 6);
+}
+
 #[test]
 fn test_get_largest_accounts() {
     let GenesisConfigInfo { genesis_config, .. } =
@@ -13142,10 +13536,124 @@ fn test_failed_simulation_load_error() {
             units_consumed: 0,
             return_data: None,
             inner_instructions: None,
+            memory_usage: vec![],
+            account_privileges: vec![],
         }
     );
 }
 
+#[test]
+fn test_simulate_transactions_with_account_overrides() {
+    let (genesis_config, _mint_keypair) = create_genesis_config(LAMPORTS_PER_SOL);
+    let (bank, _bank_forks) = Bank::new_with_bank_forks_for_tests(&genesis_config);
+    let payer = Keypair::new();
+    let recipient = Keypair::new();
+    let other = Pubkey::new_unique();
+    let blockhash = bank.last_blockhash();
+    let transactions: Vec<_> = [
+        system_transaction::transfer(&payer, &recipient.pubkey(), LAMPORTS_PER_SOL / 2, blockhash),
+        // Only funded by the first transfer
+        system_transaction::transfer(&recipient, &other, LAMPORTS_PER_SOL / 4, blockhash),
+    ]
+    .into_iter()
+    .map(RuntimeTransaction::from_transaction_for_tests)
+    .collect();
+    bank.freeze();
+
+    let results = bank.simulate_transactions_with_account_overrides(
+        &transactions,
+        AccountOverrides::default(),
+        false,
+    );
+    assert_eq!(results[0].result, Err(TransactionError::AccountNotFound));
+    assert_eq!(results[1].result, Err(TransactionError::AccountNotFound));
+
+    let mut account_overrides = AccountOverrides::default();
+    account_overrides.set_account(
+        &payer.pubkey(),
+        Some(AccountSharedData::new(
+            LAMPORTS_PER_SOL,
+            0,
+            &system_program::id(),
+        )),
+    );
+    let results =
+        bank.simulate_transactions_with_account_overrides(&transactions, account_overrides, false);
+    assert_eq!(results[0].result, Ok(()));
+    assert_eq!(results[1].result, Ok(()));
+    assert!(results[1].units_consumed > 0);
+    let (pubkey, account) = &results[1].post_simulation_accounts[1];
+    assert_eq!(*pubkey, other);
+    assert_eq!(account.lamports(), LAMPORTS_PER_SOL / 4);
+
+    // Nothing is committed
+    assert_eq!(bank.get_balance(&payer.pubkey()), 0);
+    assert_eq!(bank.get_balance(&recipient.pubkey()), 0);
+    assert_eq!(bank.get_balance(&other), 0);
+}
+
+#[test]
+fn test_simulate_transaction_with_diffs() {
+    let (genesis_config, mint_keypair) = create_genesis_config(LAMPORTS_PER_SOL);
+    let (bank, _bank_forks) = Bank::new_with_bank_forks_for_tests(&genesis_config);
+    let recipient = Pubkey::new_unique();
+    let blockhash = bank.last_blockhash();
+    bank.freeze();
+    let mint_balance = bank.get_balance(&mint_keypair.pubkey());
+
+    let transaction = RuntimeTransaction::from_transaction_for_tests(system_transaction::transfer(
+        &mint_keypair,
+        &recipient,
+        LAMPORTS_PER_SOL / 2,
+        blockhash,
+    ));
+    let TransactionSimulationWithDiffs {
+        simulation,
+        account_diffs,
+    } = bank.simulate_transaction_with_diffs(&transaction, false);
+    assert_eq!(simulation.result, Ok(()));
+    assert!(simulation.units_consumed > 0);
+    assert!(!simulation.logs.is_empty());
+    // The system program is not write locked
+    assert_eq!(account_diffs.len(), 2);
+    assert_eq!(account_diffs[0].pubkey, mint_keypair.pubkey());
+    assert_eq!(
+        account_diffs[0].pre.as_ref().unwrap().lamports(),
+        mint_balance
+    );
+    let fee =
+        mint_balance - LAMPORTS_PER_SOL / 2 - account_diffs[0].post.as_ref().unwrap().lamports();
+    assert_eq!(account_diffs[1].pubkey, recipient);
+    assert_eq!(account_diffs[1].pre, None);
+    assert_eq!(
+        account_diffs[1].post.as_ref().unwrap().lamports(),
+        LAMPORTS_PER_SOL / 2
+    );
+
+    // A failed transaction only charges its fee
+    let transaction = RuntimeTransaction::from_transaction_for_tests(system_transaction::transfer(
+        &mint_keypair,
+        &recipient,
+        2 * LAMPORTS_PER_SOL,
+        blockhash,
+    ));
+    let TransactionSimulationWithDiffs {
+        simulation,
+        account_diffs,
+    } = bank.simulate_transaction_with_diffs(&transaction, false);
+    assert!(simulation.result.is_err());
+    assert_eq!(
+        account_diffs[0].post.as_ref().unwrap().lamports(),
+        mint_balance - fee
+    );
+    assert_eq!(account_diffs[1].pre, None);
+    assert_eq!(account_diffs[1].post, None);
+
+    // Nothing is committed
+    assert_eq!(bank.get_balance(&mint_keypair.pubkey()), mint_balance);
+    assert_eq!(bank.get_balance(&recipient), 0);
+}
+
 #[test]
 fn test_filter_program_errors_and_collect_fee_details() {
     // TX  | PROCESSING RESULT           | COLLECT            | COLLECT
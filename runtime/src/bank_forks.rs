@@ -29,6 +29,7 @@ use {
         time::Instant,
     },
     thiserror::Error,
+    tokio::sync::watch,
 };
 
 pub const MAX_ROOT_DISTANCE_FOR_VOTE_ONLY: Slot = 400;
@@ -47,6 +48,27 @@ impl ReadOnlyAtomicSlot {
     }
 }
 
+/// Reports newly optimistically confirmed slots to the receivers returned by
+/// `BankForks::highest_confirmed_slot_receiver()`.
+#[derive(Clone)]
+pub struct HighestConfirmedSlotSender {
+    sender: Arc<watch::Sender<Slot>>,
+}
+
+impl HighestConfirmedSlotSender {
+    /// Notify receivers of `slot` if it is higher than any slot previously sent
+    pub fn send(&self, slot: Slot) {
+        self.sender.send_if_modified(|highest_confirmed_slot| {
+            if slot > *highest_confirmed_slot {
+                *highest_confirmed_slot = slot;
+                true
+            } else {
+                false
+            }
+        });
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum SetRootError {
     #[error("failed to send epoch accounts hash request for bank {0}: {1}")]
@@ -76,6 +98,8 @@ pub struct BankForks {
     banks: HashMap<Slot, BankWithScheduler>,
     descendants: HashMap<Slot, HashSet<Slot>>,
     root: Arc<AtomicSlot>,
+    root_sender: watch::Sender<Slot>,
+    highest_confirmed_slot_sender: HighestConfirmedSlotSender,
 
     pub snapshot_config: Option<SnapshotConfig>,
 
@@ -128,6 +152,10 @@ impl BankForks {
 
         let bank_forks = Arc::new(RwLock::new(Self {
             root: Arc::new(AtomicSlot::new(root_slot)),
+            root_sender: watch::Sender::new(root_slot),
+            highest_confirmed_slot_sender: HighestConfirmedSlotSender {
+                sender: Arc::new(watch::Sender::new(root_slot)),
+            },
             banks,
             descendants,
             snapshot_config: None,
@@ -538,6 +566,11 @@ impl BankForks {
             accounts_background_request_sender,
             highest_super_majority_root,
         )?;
+        self.root_sender.send_if_modified(|old_root| {
+            let modified = *old_root != root;
+            *old_root = root;
+            modified
+        });
         datapoint_info!(
             "bank-forks_set_root",
             (
@@ -637,6 +670,24 @@ impl BankForks {
         }
     }
 
+    /// Returns a receiver which is notified whenever a new root is set.
+    ///
+    /// Unlike `get_atomic_root()`, this allows waiting for the root to advance.
+    pub fn root_receiver(&self) -> watch::Receiver<Slot> {
+        self.root_sender.subscribe()
+    }
+
+    /// Returns a receiver which is notified whenever a higher slot is optimistically confirmed.
+    pub fn highest_confirmed_slot_receiver(&self) -> watch::Receiver<Slot> {
+        self.highest_confirmed_slot_sender.sender.subscribe()
+    }
+
+    /// Returns a sender for reporting optimistically confirmed slots to
+    /// `highest_confirmed_slot_receiver()`.
+    pub fn highest_confirmed_slot_sender(&self) -> HighestConfirmedSlotSender {
+        self.highest_confirmed_slot_sender.clone()
+    }
+
     /// After setting a new root, prune the banks that are no longer on rooted paths
     ///
     /// Given the following banks and slots...
@@ -1064,6 +1115,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_bank_forks_root_and_confirmed_slot_receivers() {
+        let GenesisConfigInfo { genesis_config, .. } = create_genesis_config(10_000);
+        let bank = Bank::new_for_tests(&genesis_config);
+        let bank_forks = BankForks::new_rw_arc(bank);
+        let mut root_receiver = bank_forks.read().unwrap().root_receiver();
+        let mut confirmed_receiver = bank_forks.read().unwrap().highest_confirmed_slot_receiver();
+        let confirmed_sender = bank_forks.read().unwrap().highest_confirmed_slot_sender();
+        assert_eq!(*root_receiver.borrow_and_update(), 0);
+        assert_eq!(*confirmed_receiver.borrow_and_update(), 0);
+
+        extend_bank_forks(bank_forks.clone(), &[(0, 1), (1, 2)]);
+        confirmed_sender.send(2);
+        assert!(confirmed_receiver.has_changed().unwrap());
+        assert_eq!(*confirmed_receiver.borrow_and_update(), 2);
+
+        // Lower confirmed slots do not notify receivers
+        confirmed_sender.send(1);
+        assert!(!confirmed_receiver.has_changed().unwrap());
+        assert_eq!(*confirmed_receiver.borrow(), 2);
+
+        assert!(!root_receiver.has_changed().unwrap());
+        bank_forks
+            .write()
+            .unwrap()
+            .set_root(1, &AbsRequestSender::default(), None)
+            .unwrap();
+        assert!(root_receiver.has_changed().unwrap());
+        assert_eq!(*root_receiver.borrow_and_update(), 1);
+    }
+
     #[test]
     fn test_bank_forks_with_highest_super_majority_root() {
         let GenesisConfigInfo { genesis_config, .. } = create_genesis_config(10_000);
@@ -37,6 +37,7 @@ pub mod stakes;
 pub mod static_ids;
 pub mod status_cache;
 pub mod transaction_batch;
+pub mod transaction_recorder;
 pub mod verify_precompiles;
 pub mod vote_sender_types;
 
@@ -16,14 +16,60 @@ pub struct NonCirculatingSupply {
     pub accounts: Vec<Pubkey>,
 }
 
-pub fn calculate_non_circulating_supply(bank: &Bank) -> ScanResult<NonCirculatingSupply> {
-    debug!("Updating Bank supply, epoch: {}", bank.epoch());
-    let mut non_circulating_accounts_set: HashSet<Pubkey> = HashSet::new();
+/// The total supply of a bank, split into its circulating and non-circulating parts
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Supply {
+    pub total: u64,
+    pub circulating: u64,
+    pub non_circulating: u64,
+    pub non_circulating_accounts: Vec<Pubkey>,
+}
+
+/// Which accounts are considered non-circulating
+///
+/// Besides the listed accounts, stake accounts that are locked up or whose withdraw authority is
+/// one of the listed withdraw authorities are non-circulating.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NonCirculatingAccounts {
+    accounts: HashSet<Pubkey>,
+    withdraw_authorities: HashSet<Pubkey>,
+}
 
-    for key in non_circulating_accounts() {
-        non_circulating_accounts_set.insert(key);
+impl Default for NonCirculatingAccounts {
+    /// The mainnet-beta non-circulating accounts
+    fn default() -> Self {
+        Self::new(non_circulating_accounts(), withdraw_authority())
+    }
+}
+
+impl NonCirculatingAccounts {
+    pub fn new(
+        accounts: impl IntoIterator<Item = Pubkey>,
+        withdraw_authorities: impl IntoIterator<Item = Pubkey>,
+    ) -> Self {
+        Self {
+            accounts: accounts.into_iter().collect(),
+            withdraw_authorities: withdraw_authorities.into_iter().collect(),
+        }
     }
-    let withdraw_authority_list = withdraw_authority();
+
+    /// Adds `accounts` to the non-circulating accounts
+    pub fn extend(&mut self, accounts: impl IntoIterator<Item = Pubkey>) {
+        self.accounts.extend(accounts);
+    }
+}
+
+pub fn calculate_non_circulating_supply(bank: &Bank) -> ScanResult<NonCirculatingSupply> {
+    calculate_non_circulating_supply_with_accounts(bank, &NonCirculatingAccounts::default())
+}
+
+pub fn calculate_non_circulating_supply_with_accounts(
+    bank: &Bank,
+    non_circulating_accounts: &NonCirculatingAccounts,
+) -> ScanResult<NonCirculatingSupply> {
+    debug!("Updating Bank supply, epoch: {}", bank.epoch());
+    let mut non_circulating_accounts_set = non_circulating_accounts.accounts.clone();
+    let withdraw_authority_list = &non_circulating_accounts.withdraw_authorities;
 
     let clock = bank.clock();
     let config = &ScanConfig::default();
@@ -329,4 +375,65 @@ mod tests {
             num_non_circulating_accounts as usize
         );
     }
+
+    #[test]
+    fn test_bank_supply() {
+        let balance = 10;
+        let non_circulating_key = solana_pubkey::new_rand();
+        let accounts = (0..3)
+            .map(|_| solana_pubkey::new_rand())
+            .chain([non_circulating_key])
+            .map(|key| (key, Account::new(balance, 0, &Pubkey::default())))
+            .collect();
+        let genesis_config = GenesisConfig {
+            accounts,
+            ..GenesisConfig::default()
+        };
+        let bank = Arc::new(Bank::new_for_tests(&genesis_config));
+        let bank = new_from_parent(bank);
+        let total = bank.capitalization();
+
+        // None of the genesis accounts are on the default list.
+        let supply = bank.supply(&NonCirculatingAccounts::default()).unwrap();
+        assert_eq!(supply.total, total);
+        assert_eq!(supply.circulating, total);
+        assert_eq!(supply.non_circulating, 0);
+        let mut accounts = supply.non_circulating_accounts.clone();
+        accounts.sort();
+        let mut expected_accounts = non_circulating_accounts();
+        expected_accounts.sort();
+        expected_accounts.dedup();
+        assert_eq!(accounts, expected_accounts);
+
+        let custom_accounts = NonCirculatingAccounts::new([non_circulating_key], []);
+        let supply = bank.supply(&custom_accounts).unwrap();
+        assert_eq!(
+            *supply,
+            Supply {
+                total,
+                circulating: total - balance,
+                non_circulating: balance,
+                non_circulating_accounts: vec![non_circulating_key],
+            }
+        );
+        // The supply is not cached until the bank is frozen.
+        assert!(!Arc::ptr_eq(
+            &supply,
+            &bank.supply(&custom_accounts).unwrap()
+        ));
+
+        bank.freeze();
+        let supply = bank.supply(&custom_accounts).unwrap();
+        assert!(Arc::ptr_eq(
+            &supply,
+            &bank.supply(&custom_accounts).unwrap()
+        ));
+        // A different list of accounts is not served from the cache.
+        assert_eq!(
+            bank.supply(&NonCirculatingAccounts::default())
+                .unwrap()
+                .non_circulating,
+            0
+        );
+    }
 }
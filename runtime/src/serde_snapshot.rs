@@ -19,7 +19,7 @@ use {
             AccountStorageEntry, AccountsDb, AccountsDbConfig, AccountsFileId,
             AtomicAccountsFileId, DuplicatesLtHash, IndexGenerationInfo,
         },
-        accounts_file::{AccountsFile, StorageAccess},
+        accounts_file::{AccountsFile, AccountsFileProvider, StorageAccess},
         accounts_hash::{AccountsDeltaHash, AccountsHash},
         accounts_update_notifier_interface::AccountsUpdateNotifier,
         ancestors::AncestorsForSerialization,
@@ -201,6 +201,7 @@ impl From<DeserializableVersionedBank> for BankFieldsToDeserialize {
             incremental_snapshot_persistence: None,
             epoch_accounts_hash: None,
             accounts_lt_hash: None, // populated from ExtraFieldsToDeserialize
+            storage_formats: HashMap::default(), // populated from ExtraFieldsToDeserialize
             bank_hash_stats: BankHashStats::default(), // populated from AccountsDbFields
         }
     }
@@ -403,6 +404,8 @@ struct ExtraFieldsToDeserialize {
     versioned_epoch_stakes: HashMap<u64, VersionedEpochStakes>,
     #[serde(deserialize_with = "default_on_eof")]
     accounts_lt_hash: Option<SerdeAccountsLtHash>,
+    #[serde(deserialize_with = "default_on_eof")]
+    storage_formats: Vec<(Slot, SerializedAccountsFileId, AccountsFileProvider)>,
 }
 
 /// Extra fields that are serialized at the end of snapshots.
@@ -420,6 +423,26 @@ pub struct ExtraFieldsToSerialize<'a> {
     pub epoch_accounts_hash: Option<EpochAccountsHash>,
     pub versioned_epoch_stakes: HashMap<u64, VersionedEpochStakes>,
     pub accounts_lt_hash: Option<SerdeAccountsLtHash>,
+    pub storage_formats: Vec<(Slot, SerializedAccountsFileId, AccountsFileProvider)>,
+}
+
+/// Formats of the storages which are not append vecs, so that they are opened
+/// as such when the snapshot is loaded
+pub(crate) fn storage_formats_to_serialize(
+    account_storage_entries: &[Vec<Arc<AccountStorageEntry>>],
+) -> Vec<(Slot, SerializedAccountsFileId, AccountsFileProvider)> {
+    account_storage_entries
+        .iter()
+        .flatten()
+        .filter(|storage| storage.accounts.provider() != AccountsFileProvider::AppendVec)
+        .map(|storage| {
+            (
+                storage.slot(),
+                storage.id() as SerializedAccountsFileId,
+                storage.accounts.provider(),
+            )
+        })
+        .collect()
 }
 
 fn deserialize_bank_fields<R>(
@@ -446,6 +469,7 @@ where
         epoch_accounts_hash,
         versioned_epoch_stakes,
         accounts_lt_hash,
+        storage_formats,
     } = extra_fields;
 
     bank_fields.fee_rate_governor = bank_fields
@@ -463,6 +487,10 @@ where
     );
 
     bank_fields.accounts_lt_hash = accounts_lt_hash.map(Into::into);
+    bank_fields.storage_formats = storage_formats
+        .into_iter()
+        .map(|(slot, id, provider)| ((slot, id), provider))
+        .collect();
 
     Ok((bank_fields, accounts_db_fields))
 }
@@ -709,6 +737,7 @@ impl Serialize for SerializableBankAndStorage<'_> {
         let lamports_per_signature = bank_fields.fee_rate_governor.lamports_per_signature;
         let versioned_epoch_stakes = std::mem::take(&mut bank_fields.versioned_epoch_stakes);
         let accounts_lt_hash = bank_fields.accounts_lt_hash.clone().map(Into::into);
+        let storage_formats = storage_formats_to_serialize(self.snapshot_storages);
         let bank_fields_to_serialize = (
             SerializableVersionedBank::from(bank_fields),
             SerializableAccountsDb::<'_> {
@@ -725,6 +754,7 @@ impl Serialize for SerializableBankAndStorage<'_> {
                 epoch_accounts_hash: self.bank.get_epoch_accounts_hash_to_serialize(),
                 versioned_epoch_stakes,
                 accounts_lt_hash,
+                storage_formats,
             },
         );
         bank_fields_to_serialize.serialize(serializer)
@@ -918,9 +948,10 @@ pub(crate) fn reconstruct_single_storage(
     current_len: usize,
     append_vec_id: AccountsFileId,
     storage_access: StorageAccess,
+    provider: AccountsFileProvider,
 ) -> Result<Arc<AccountStorageEntry>, SnapshotError> {
     let (accounts_file, num_accounts) =
-        AccountsFile::new_from_file(append_vec_path, current_len, storage_access)?;
+        AccountsFile::new_from_file(append_vec_path, current_len, storage_access, provider)?;
     Ok(Arc::new(AccountStorageEntry::new_existing(
         *slot,
         append_vec_id,
@@ -1010,6 +1041,7 @@ pub(crate) fn remap_and_reconstruct_single_storage(
     next_append_vec_id: &AtomicAccountsFileId,
     num_collisions: &AtomicUsize,
     storage_access: StorageAccess,
+    provider: AccountsFileProvider,
 ) -> Result<Arc<AccountStorageEntry>, SnapshotError> {
     let (remapped_append_vec_id, remapped_append_vec_path) = remap_append_vec_file(
         slot,
@@ -1024,6 +1056,7 @@ pub(crate) fn remap_and_reconstruct_single_storage(
         current_len,
         remapped_append_vec_id,
         storage_access,
+        provider,
     )?;
     Ok(storage)
 }
@@ -5,7 +5,8 @@ mod serde_snapshot_tests {
             bank::BankHashStats,
             serde_snapshot::{
                 deserialize_accounts_db_fields, reconstruct_accountsdb_from_fields,
-                remap_append_vec_file, SerializableAccountsDb, SnapshotAccountsDbFields,
+                reconstruct_single_storage, remap_append_vec_file, storage_formats_to_serialize,
+                SerializableAccountsDb, SnapshotAccountsDbFields,
             },
             snapshot_utils::{get_storages_to_serialize, StorageAndNextAccountsFileId},
         },
@@ -19,7 +20,7 @@ mod serde_snapshot_tests {
                 get_temp_accounts_paths, test_utils::create_test_accounts, AccountStorageEntry,
                 AccountsDb, AtomicAccountsFileId, VerifyAccountsHashAndLamportsConfig,
             },
-            accounts_file::{AccountsFile, AccountsFileError, StorageAccess},
+            accounts_file::{AccountsFile, AccountsFileError, AccountsFileProvider, StorageAccess},
             accounts_hash::AccountsHash,
             ancestors::Ancestors,
         },
@@ -151,6 +152,7 @@ mod serde_snapshot_tests {
                 output_path,
                 storage_entry.accounts.len(),
                 storage_access,
+                storage_entry.accounts.provider(),
             )?;
             let new_storage_entry = AccountStorageEntry::new_existing(
                 storage_entry.slot(),
@@ -906,4 +908,60 @@ mod serde_snapshot_tests {
         )
         .unwrap();
     }
+
+    #[test]
+    fn test_storage_formats() {
+        let tmp = tempfile::tempdir().unwrap();
+        let accounts: Vec<_> = (0..3)
+            .map(|i| {
+                (
+                    Pubkey::new_unique(),
+                    AccountSharedData::new(i + 1, 0, &Pubkey::default()),
+                )
+            })
+            .collect();
+        let storages: Vec<_> = [
+            (1, AccountsFileProvider::AppendVec),
+            (2, AccountsFileProvider::HotStorage),
+        ]
+        .into_iter()
+        .map(|(slot, provider)| {
+            let accounts_file = provider.new_writable(
+                tmp.path().join(AccountsFile::file_name(slot, 0)),
+                1024 * 1024,
+            );
+            accounts_file
+                .append_accounts(&(slot, accounts.as_slice()), 0)
+                .unwrap();
+            accounts_file.flush().unwrap();
+            Arc::new(AccountStorageEntry::new_existing(
+                slot,
+                0,
+                accounts_file,
+                accounts.len(),
+            ))
+        })
+        .collect();
+
+        let storage_formats = storage_formats_to_serialize(&get_storages_to_serialize(&storages));
+        assert_eq!(storage_formats, [(2, 0, AccountsFileProvider::HotStorage)]);
+
+        // Each storage is reopened in the format it was written in
+        for storage in storages {
+            let provider = storage.accounts.provider();
+            let path = tmp.path().join(format!("{}.copy", storage.slot()));
+            std::fs::copy(storage.path(), &path).unwrap();
+            let reconstructed = reconstruct_single_storage(
+                &storage.slot(),
+                &path,
+                storage.accounts.len(),
+                1,
+                StorageAccess::default(),
+                provider,
+            )
+            .unwrap();
+            assert_eq!(reconstructed.accounts.provider(), provider);
+            assert_eq!(reconstructed.count(), accounts.len());
+        }
+    }
 }
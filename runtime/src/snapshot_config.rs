@@ -46,6 +46,12 @@ pub struct SnapshotConfig {
 
     // Thread niceness adjustment for snapshot packager service
     pub packager_thread_niceness_adj: i8,
+
+    /// Whether to include an integrity manifest in generated snapshot archives
+    ///
+    /// Older versions refuse to unpack archives containing a manifest, so this stays off until
+    /// they no longer need to load snapshots from this node.
+    pub archive_manifest: bool,
 }
 
 impl Default for SnapshotConfig {
@@ -69,6 +75,7 @@ impl Default for SnapshotConfig {
                 snapshot_utils::DEFAULT_MAX_INCREMENTAL_SNAPSHOT_ARCHIVES_TO_RETAIN,
             accounts_hash_debug_verify: false,
             packager_thread_niceness_adj: 0,
+            archive_manifest: false,
         }
     }
 }
@@ -21,6 +21,7 @@ use {
     flate2::read::GzDecoder,
     lazy_static::lazy_static,
     log::*,
+    rayon::prelude::*,
     regex::Regex,
     solana_accounts_db::{
         account_storage::{meta::StoredMetaWriteVersion, AccountStorageMap},
@@ -30,6 +31,10 @@ use {
         epoch_accounts_hash::EpochAccountsHash,
         hardened_unpack::{self, ParallelSelector, UnpackError},
         shared_buffer_reader::{SharedBuffer, SharedBufferReader},
+        snapshot_archive_manifest::{
+            self, SnapshotArchiveManifest, SnapshotArchiveManifestEntry,
+            SNAPSHOT_ARCHIVE_MANIFEST_FILENAME,
+        },
         utils::{move_and_async_delete_path, ACCOUNTS_RUN_DIR, ACCOUNTS_SNAPSHOT_DIR},
     },
     solana_measure::{measure::Measure, measure_time, measure_us},
@@ -57,7 +62,7 @@ use {
 };
 #[cfg(feature = "dev-context-only-utils")]
 use {
-    hardened_unpack::UnpackedAppendVecMap, rayon::prelude::*,
+    hardened_unpack::UnpackedAppendVecMap,
     solana_accounts_db::utils::create_accounts_run_and_snapshot_dirs,
 };
 
@@ -68,6 +73,9 @@ pub use archive_format::*;
 pub const SNAPSHOT_STATUS_CACHE_FILENAME: &str = "status_cache";
 pub const SNAPSHOT_VERSION_FILENAME: &str = "version";
 pub const SNAPSHOT_STATE_COMPLETE_FILENAME: &str = "state_complete";
+// Directories inside snapshot archives
+const SNAPSHOT_ARCHIVE_SNAPSHOTS_DIR: &str = "snapshots";
+const SNAPSHOT_ARCHIVE_ACCOUNTS_DIR: &str = "accounts";
 pub const SNAPSHOT_ACCOUNTS_HARDLINKS: &str = "accounts_hardlinks";
 pub const SNAPSHOT_ARCHIVE_DOWNLOAD_DIR: &str = "remote";
 pub const SNAPSHOT_FULL_SNAPSHOT_SLOT_FILENAME: &str = "full_snapshot_slot";
@@ -495,6 +503,15 @@ pub enum ArchiveSnapshotPackageError {
     #[error("failed to archive version file: {0}")]
     ArchiveVersionFile(#[source] IoError),
 
+    #[error("failed to hash '{1}' for the archive manifest: {0}")]
+    HashArchiveFile(#[source] IoError, PathBuf),
+
+    #[error("failed to serialize archive manifest: {0}")]
+    SerializeManifest(#[source] bincode::Error),
+
+    #[error("failed to archive manifest: {0}")]
+    ArchiveManifest(#[source] IoError),
+
     #[error("failed to archive snapshots dir: {0}")]
     ArchiveSnapshotsDir(#[source] IoError),
 
@@ -818,6 +835,7 @@ pub fn serialize_and_archive_snapshot_package(
         &bank_snapshot_info.snapshot_dir,
         snapshot_archive_path,
         snapshot_config.archive_format,
+        snapshot_config.archive_manifest,
     )?;
 
     Ok(snapshot_archive_info)
@@ -882,12 +900,16 @@ fn serialize_snapshot(
 
         let bank_snapshot_serializer = move |stream: &mut BufWriter<fs::File>| -> Result<()> {
             let versioned_epoch_stakes = mem::take(&mut bank_fields.versioned_epoch_stakes);
+            let storages_to_serialize = get_storages_to_serialize(snapshot_storages);
             let extra_fields = ExtraFieldsToSerialize {
                 lamports_per_signature: bank_fields.fee_rate_governor.lamports_per_signature,
                 incremental_snapshot_persistence: bank_incremental_snapshot_persistence,
                 epoch_accounts_hash,
                 versioned_epoch_stakes,
                 accounts_lt_hash: bank_fields.accounts_lt_hash.clone().map(Into::into),
+                storage_formats: serde_snapshot::storage_formats_to_serialize(
+                    &storages_to_serialize,
+                ),
             };
             serde_snapshot::serialize_bank_snapshot_into(
                 stream,
@@ -895,7 +917,7 @@ fn serialize_snapshot(
                 bank_hash_stats,
                 accounts_delta_hash,
                 accounts_hash,
-                &get_storages_to_serialize(snapshot_storages),
+                &storages_to_serialize,
                 extra_fields,
                 write_version,
             )?;
@@ -966,6 +988,66 @@ fn serialize_snapshot(
     do_serialize_snapshot().map_err(|err| SnapshotError::AddBankSnapshot(err, slot))
 }
 
+/// Builds the integrity manifest of a snapshot archive, by hashing every file that goes into it
+fn build_snapshot_archive_manifest(
+    snapshot_slot: Slot,
+    src_snapshot_dir: &Path,
+    snapshot_storages: &[Arc<AccountStorageEntry>],
+) -> std::result::Result<SnapshotArchiveManifest, ArchiveSnapshotPackageError> {
+    use ArchiveSnapshotPackageError as E;
+    let hash_file = |path_in_archive: String, path: &Path| {
+        snapshot_archive_manifest::hash_file(path)
+            .map(|(size, hash)| SnapshotArchiveManifestEntry {
+                path: path_in_archive,
+                size,
+                hash,
+            })
+            .map_err(|err| E::HashArchiveFile(err, path.to_path_buf()))
+    };
+
+    let version_path = src_snapshot_dir.join(SNAPSHOT_VERSION_FILENAME);
+    let snapshot_version = fs::read_to_string(&version_path)
+        .map_err(|err| E::HashArchiveFile(err, version_path.clone()))?;
+    let slot_str = snapshot_slot.to_string();
+    let mut files = vec![
+        hash_file(SNAPSHOT_VERSION_FILENAME.to_string(), &version_path)?,
+        hash_file(
+            format!("{SNAPSHOT_ARCHIVE_SNAPSHOTS_DIR}/{slot_str}/{slot_str}"),
+            &src_snapshot_dir.join(&slot_str),
+        )?,
+        hash_file(
+            format!("{SNAPSHOT_ARCHIVE_SNAPSHOTS_DIR}/{SNAPSHOT_STATUS_CACHE_FILENAME}"),
+            &src_snapshot_dir.join(SNAPSHOT_STATUS_CACHE_FILENAME),
+        )?,
+    ];
+    let storages = snapshot_storages
+        .par_iter()
+        .map(|storage| {
+            let path_in_archive = format!(
+                "{SNAPSHOT_ARCHIVE_ACCOUNTS_DIR}/{}",
+                AccountsFile::file_name(storage.slot(), storage.id())
+            );
+            match storage.accounts.internals_for_archive() {
+                InternalsForArchive::Mmap(data) => {
+                    let (size, hash) = snapshot_archive_manifest::hash_bytes(data);
+                    Ok(SnapshotArchiveManifestEntry {
+                        path: path_in_archive,
+                        size,
+                        hash,
+                    })
+                }
+                InternalsForArchive::FileIo(path) => hash_file(path_in_archive, path),
+            }
+        })
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    files.extend(storages);
+    Ok(SnapshotArchiveManifest::new(
+        snapshot_version,
+        snapshot_slot,
+        files,
+    ))
+}
+
 /// Archives a snapshot into `archive_path`
 fn archive_snapshot(
     snapshot_kind: SnapshotKind,
@@ -975,10 +1057,9 @@ fn archive_snapshot(
     bank_snapshot_dir: impl AsRef<Path>,
     archive_path: impl AsRef<Path>,
     archive_format: ArchiveFormat,
+    write_manifest: bool,
 ) -> Result<SnapshotArchiveInfo> {
     use ArchiveSnapshotPackageError as E;
-    const SNAPSHOTS_DIR: &str = "snapshots";
-    const ACCOUNTS_DIR: &str = "accounts";
     info!("Generating snapshot archive for slot {snapshot_slot}, kind: {snapshot_kind:?}");
 
     let mut timer = Measure::start("snapshot_package-package_snapshots");
@@ -995,7 +1076,7 @@ fn archive_snapshot(
         .prefix(&format!("{}{}-", staging_dir_prefix, snapshot_slot))
         .tempdir_in(tar_dir)
         .map_err(|err| E::CreateStagingDir(err, tar_dir.to_path_buf()))?;
-    let staging_snapshots_dir = staging_dir.path().join(SNAPSHOTS_DIR);
+    let staging_snapshots_dir = staging_dir.path().join(SNAPSHOT_ARCHIVE_SNAPSHOTS_DIR);
 
     let slot_str = snapshot_slot.to_string();
     let staging_snapshot_dir = staging_snapshots_dir.join(&slot_str);
@@ -1007,6 +1088,14 @@ fn archive_snapshot(
     let src_snapshot_dir = bank_snapshot_dir.as_ref().canonicalize().map_err(|err| {
         E::CanonicalizeSnapshotSourceDir(err, bank_snapshot_dir.as_ref().to_path_buf())
     })?;
+    let manifest = write_manifest
+        .then(|| {
+            build_snapshot_archive_manifest(snapshot_slot, &src_snapshot_dir, snapshot_storages)?
+                .to_bytes()
+                .map_err(E::SerializeManifest)
+        })
+        .transpose()?;
+
     let staging_snapshot_file = staging_snapshot_dir.join(&slot_str);
     let src_snapshot_file = src_snapshot_dir.join(slot_str);
     symlink::symlink_file(&src_snapshot_file, &staging_snapshot_file)
@@ -1045,12 +1134,26 @@ fn archive_snapshot(
             archive
                 .append_path_with_name(&staging_version_file, SNAPSHOT_VERSION_FILENAME)
                 .map_err(E::ArchiveVersionFile)?;
+            // The manifest goes right after the version file, so that unpacking can verify
+            // every file as it is unpacked.
+            if let Some(manifest) = &manifest {
+                let mut header = tar::Header::new_gnu();
+                header
+                    .set_path(SNAPSHOT_ARCHIVE_MANIFEST_FILENAME)
+                    .map_err(E::ArchiveManifest)?;
+                header.set_size(manifest.len() as u64);
+                header.set_mode(0o644);
+                header.set_cksum();
+                archive
+                    .append(&header, manifest.as_slice())
+                    .map_err(E::ArchiveManifest)?;
+            }
             archive
-                .append_dir_all(SNAPSHOTS_DIR, &staging_snapshots_dir)
+                .append_dir_all(SNAPSHOT_ARCHIVE_SNAPSHOTS_DIR, &staging_snapshots_dir)
                 .map_err(E::ArchiveSnapshotsDir)?;
 
             for storage in snapshot_storages {
-                let path_in_archive = Path::new(ACCOUNTS_DIR)
+                let path_in_archive = Path::new(SNAPSHOT_ARCHIVE_ACCOUNTS_DIR)
                     .join(AccountsFile::file_name(storage.slot(), storage.id()));
                 match storage.accounts.internals_for_archive() {
                     InternalsForArchive::Mmap(data) => {
@@ -17,7 +17,7 @@ use {
     solana_accounts_db::{
         account_storage::AccountStorageMap,
         accounts_db::{AccountsFileId, AtomicAccountsFileId},
-        accounts_file::StorageAccess,
+        accounts_file::{AccountsFileProvider, StorageAccess},
     },
     solana_nohash_hasher::BuildNoHashHasher,
     solana_sdk::clock::Slot,
@@ -57,6 +57,8 @@ pub(crate) struct SnapshotStorageRebuilder {
     num_threads: usize,
     /// Snapshot storage lengths - from the snapshot file
     snapshot_storage_lengths: HashMap<Slot, HashMap<SerializedAccountsFileId, usize>>,
+    /// Format of each storage which is not an append vec - from the snapshot file
+    snapshot_storage_formats: HashMap<(Slot, SerializedAccountsFileId), AccountsFileProvider>,
     /// Container for storing snapshot file paths
     storage_paths: DashMap<Slot, Mutex<Vec<PathBuf>>>,
     /// Container for storing rebuilt snapshot storages
@@ -90,7 +92,7 @@ impl SnapshotStorageRebuilder {
                 "unsupported snapshot version '{snapshot_version_str}': {err}",
             ))
         })?;
-        let snapshot_storage_lengths =
+        let (snapshot_storage_lengths, snapshot_storage_formats) =
             Self::process_snapshot_file(snapshot_version, snapshot_file_path)?;
 
         let account_storage_map = Self::spawn_rebuilder_threads(
@@ -98,6 +100,7 @@ impl SnapshotStorageRebuilder {
             num_threads,
             next_append_vec_id,
             snapshot_storage_lengths,
+            snapshot_storage_formats,
             append_vec_files,
             snapshot_from,
             storage_access,
@@ -116,6 +119,7 @@ impl SnapshotStorageRebuilder {
         num_threads: usize,
         next_append_vec_id: Arc<AtomicAccountsFileId>,
         snapshot_storage_lengths: HashMap<Slot, HashMap<usize, usize>>,
+        snapshot_storage_formats: HashMap<(Slot, usize), AccountsFileProvider>,
         snapshot_from: SnapshotFrom,
         storage_access: StorageAccess,
     ) -> Self {
@@ -133,6 +137,7 @@ impl SnapshotStorageRebuilder {
             file_receiver,
             num_threads,
             snapshot_storage_lengths,
+            snapshot_storage_formats,
             storage_paths,
             storage,
             next_append_vec_id,
@@ -188,19 +193,29 @@ impl SnapshotStorageRebuilder {
         (snapshot_version_path, snapshot_file_path, append_vec_files)
     }
 
-    /// Process the snapshot file to get the size of each snapshot storage file
+    /// Process the snapshot file to get the size and format of each snapshot storage file
+    #[allow(clippy::type_complexity)]
     fn process_snapshot_file(
         snapshot_version: SnapshotVersion,
         snapshot_file_path: PathBuf,
-    ) -> Result<HashMap<Slot, HashMap<usize, usize>>, bincode::Error> {
+    ) -> Result<
+        (
+            HashMap<Slot, HashMap<usize, usize>>,
+            HashMap<(Slot, usize), AccountsFileProvider>,
+        ),
+        bincode::Error,
+    > {
         let snapshot_file = File::open(snapshot_file_path).unwrap();
         let mut snapshot_stream = BufReader::new(snapshot_file);
         match snapshot_version {
             SnapshotVersion::V1_2_0 => {
-                let (_bank_fields, accounts_fields) =
+                let (bank_fields, accounts_fields) =
                     serde_snapshot::fields_from_stream(&mut snapshot_stream)?;
 
-                Ok(snapshot_storage_lengths_from_fields(&accounts_fields))
+                Ok((
+                    snapshot_storage_lengths_from_fields(&accounts_fields),
+                    bank_fields.storage_formats,
+                ))
             }
         }
     }
@@ -211,6 +226,7 @@ impl SnapshotStorageRebuilder {
         num_threads: usize,
         next_append_vec_id: Arc<AtomicAccountsFileId>,
         snapshot_storage_lengths: HashMap<Slot, HashMap<usize, usize>>,
+        snapshot_storage_formats: HashMap<(Slot, usize), AccountsFileProvider>,
         append_vec_files: Vec<PathBuf>,
         snapshot_from: SnapshotFrom,
         storage_access: StorageAccess,
@@ -220,6 +236,7 @@ impl SnapshotStorageRebuilder {
             num_threads,
             next_append_vec_id,
             snapshot_storage_lengths,
+            snapshot_storage_formats,
             snapshot_from,
             storage_access,
         ));
@@ -322,6 +339,11 @@ impl SnapshotStorageRebuilder {
                     .unwrap()
                     .get(&old_append_vec_id)
                     .unwrap();
+                let provider = self
+                    .snapshot_storage_formats
+                    .get(&(slot, old_append_vec_id))
+                    .copied()
+                    .unwrap_or_default();
 
                 let storage_entry = match &self.snapshot_from {
                     SnapshotFrom::Archive => remap_and_reconstruct_single_storage(
@@ -332,6 +354,7 @@ impl SnapshotStorageRebuilder {
                         &self.next_append_vec_id,
                         &self.num_collisions,
                         self.storage_access,
+                        provider,
                     )?,
                     SnapshotFrom::Dir => reconstruct_single_storage(
                         &slot,
@@ -339,6 +362,7 @@ impl SnapshotStorageRebuilder {
                         current_len,
                         old_append_vec_id as AccountsFileId,
                         self.storage_access,
+                        provider,
                     )?,
                 };
 
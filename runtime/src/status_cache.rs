@@ -163,6 +163,24 @@ impl<T: Serialize + Clone> StatusCache<T> {
         None
     }
 
+    /// Search for each of `keys` with any blockhash, returning the statuses in the same order.
+    /// Cheaper than calling `get_status_any_blockhash` once per key, since the blockhashes are
+    /// only collected once.
+    pub fn get_statuses_any_blockhash<K: AsRef<[u8]>>(
+        &self,
+        keys: &[K],
+        ancestors: &Ancestors,
+    ) -> Vec<Option<(Slot, T)>> {
+        let blockhashes: Vec<_> = self.cache.keys().collect();
+        keys.iter()
+            .map(|key| {
+                blockhashes
+                    .iter()
+                    .find_map(|blockhash| self.get_status(key, blockhash, ancestors))
+            })
+            .collect()
+    }
+
     /// Add a known root fork.  Roots are always valid ancestors.
     /// After MAX_CACHE_ENTRIES, roots are removed, and any old keys are cleared.
     pub fn add_root(&mut self, fork: Slot) {
@@ -346,6 +364,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_get_statuses_any_blockhash() {
+        let sig = Signature::from([1; 64]);
+        let other_sig = Signature::from([2; 64]);
+        let missing_sig = Signature::from([3; 64]);
+        let mut status_cache = BankStatusCache::default();
+        let blockhash = hash(Hash::default().as_ref());
+        let other_blockhash = hash(blockhash.as_ref());
+        let ancestors = vec![(0, 1), (1, 1)].into_iter().collect();
+        status_cache.insert(&blockhash, sig, 0, ());
+        status_cache.insert(&other_blockhash, other_sig, 1, ());
+        assert_eq!(
+            status_cache.get_statuses_any_blockhash(&[missing_sig, other_sig, sig], &ancestors),
+            vec![None, Some((1, ())), Some((0, ()))]
+        );
+        assert_eq!(
+            status_cache.get_statuses_any_blockhash::<Signature>(&[], &ancestors),
+            vec![]
+        );
+    }
+
     #[test]
     fn test_find_sig_without_ancestor_fork() {
         let sig = Signature::default();
@@ -0,0 +1,38 @@
+//! Recording of the transactions a bank is about to commit.
+//!
+//! A block producer must record its transactions into the PoH stream before
+//! committing them, while tests and simulators have nothing to record into.
+//! [`RecordTransactions`] lets the caller of
+//! `Bank::load_execute_and_commit_transactions_with_recorder()` choose.
+
+use solana_sdk::{
+    clock::Slot,
+    transaction::{Result, VersionedTransaction},
+};
+
+pub trait RecordTransactions {
+    /// Records the processed `transactions` of the bank at `bank_slot`, which
+    /// are committed only if this succeeds.
+    ///
+    /// Returns the index in the slot of the first transaction recorded, if the
+    /// recorder keeps track of it.
+    fn record_transactions(
+        &self,
+        bank_slot: Slot,
+        transactions: Vec<VersionedTransaction>,
+    ) -> Result<Option<usize>>;
+}
+
+/// Recorder accepting every transaction without recording it anywhere
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoopTransactionRecorder;
+
+impl RecordTransactions for NoopTransactionRecorder {
+    fn record_transactions(
+        &self,
+        _bank_slot: Slot,
+        _transactions: Vec<VersionedTransaction>,
+    ) -> Result<Option<usize>> {
+        Ok(None)
+    }
+}
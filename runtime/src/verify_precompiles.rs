@@ -37,7 +37,12 @@ pub fn verify_precompiles(message: &impl SVMMessage, feature_set: &FeatureSet) -
 mod tests {
     use {
         super::*,
+        openssl::{
+            ec::{EcGroup, EcKey},
+            nid::Nid,
+        },
         rand0_7::{thread_rng, Rng},
+        solana_feature_set::enable_secp256r1_precompile,
         solana_sdk::{
             ed25519_instruction::new_ed25519_instruction,
             hash::Hash,
@@ -48,6 +53,7 @@ mod tests {
             system_instruction, system_transaction,
             transaction::{SanitizedTransaction, Transaction},
         },
+        solana_secp256r1_program::new_secp256r1_instruction,
     };
 
     #[test]
@@ -129,6 +135,40 @@ mod tests {
         assert!(verify_precompiles(&tx, &feature_set).is_err());
     }
 
+    #[test]
+    fn test_verify_precompiles_secp256r1() {
+        let group = EcGroup::from_curve_name(Nid::X9_62_PRIME256V1).unwrap();
+        let secret_key = EcKey::generate(&group).unwrap();
+        let mut instruction = new_secp256r1_instruction(b"hello", secret_key).unwrap();
+        let mint_keypair = Keypair::new();
+        let mut feature_set = FeatureSet::all_enabled();
+
+        let tx =
+            SanitizedTransaction::from_transaction_for_tests(Transaction::new_signed_with_payer(
+                &[instruction.clone()],
+                Some(&mint_keypair.pubkey()),
+                &[&mint_keypair],
+                Hash::default(),
+            ));
+        assert!(verify_precompiles(&tx, &feature_set).is_ok());
+
+        // tamper with the message
+        let last = instruction.data.len() - 1;
+        instruction.data[last] = instruction.data[last].wrapping_add(12);
+        let tx =
+            SanitizedTransaction::from_transaction_for_tests(Transaction::new_signed_with_payer(
+                &[instruction],
+                Some(&mint_keypair.pubkey()),
+                &[&mint_keypair],
+                Hash::default(),
+            ));
+        assert!(verify_precompiles(&tx, &feature_set).is_err());
+
+        // the precompile is not verified until the feature is activated
+        feature_set.deactivate(&enable_secp256r1_precompile::id());
+        assert!(verify_precompiles(&tx, &feature_set).is_ok());
+    }
+
     #[test]
     fn test_verify_precompiles_mixed() {
         let message_arr = b"hello";
@@ -0,0 +1,143 @@
+//! Caps the number of transactions of each fee payer that the send
+//! transaction service holds at once, so that a single key can't fill its
+//! queue and retry pool.
+
+use {
+    solana_sdk::pubkey::Pubkey,
+    std::{
+        collections::{hash_map::Entry, HashMap},
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc, Mutex,
+        },
+    },
+};
+
+#[derive(Debug)]
+pub struct FeePayerInflightLimiter {
+    max_inflight_per_fee_payer: AtomicUsize,
+    inflight: Mutex<HashMap<Pubkey, usize>>,
+}
+
+impl FeePayerInflightLimiter {
+    pub fn new(max_inflight_per_fee_payer: usize) -> Self {
+        Self {
+            max_inflight_per_fee_payer: AtomicUsize::new(max_inflight_per_fee_payer),
+            inflight: Mutex::default(),
+        }
+    }
+
+    pub fn max_inflight_per_fee_payer(&self) -> usize {
+        self.max_inflight_per_fee_payer.load(Ordering::Relaxed)
+    }
+
+    /// Changes the maximum number of transactions of each fee payer in flight.
+    /// Transactions already in flight are not affected.
+    pub fn set_max_inflight_per_fee_payer(&self, max_inflight_per_fee_payer: usize) {
+        self.max_inflight_per_fee_payer
+            .store(max_inflight_per_fee_payer, Ordering::Relaxed);
+    }
+
+    /// Returns a permit for one more transaction of `fee_payer`, or None if
+    /// the fee payer already has the maximum number of transactions in flight
+    pub fn try_acquire(self: &Arc<Self>, fee_payer: Pubkey) -> Option<InflightPermit> {
+        let mut inflight = self.inflight.lock().unwrap();
+        let count = inflight.get(&fee_payer).copied().unwrap_or_default();
+        if count >= self.max_inflight_per_fee_payer() {
+            return None;
+        }
+        inflight.insert(fee_payer, count + 1);
+        Some(InflightPermit {
+            limiter: Arc::clone(self),
+            fee_payer,
+        })
+    }
+
+    /// Returns the number of transactions of `fee_payer` in flight
+    pub fn inflight(&self, fee_payer: &Pubkey) -> usize {
+        self.inflight
+            .lock()
+            .unwrap()
+            .get(fee_payer)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    fn release(&self, fee_payer: &Pubkey) {
+        let mut inflight = self.inflight.lock().unwrap();
+        if let Entry::Occupied(mut entry) = inflight.entry(*fee_payer) {
+            *entry.get_mut() -= 1;
+            if *entry.get() == 0 {
+                entry.remove();
+            }
+        }
+    }
+}
+
+/// Counts a transaction as in flight for its fee payer until dropped, which
+/// happens when the send transaction service is done with the transaction
+#[derive(Debug)]
+pub struct InflightPermit {
+    limiter: Arc<FeePayerInflightLimiter>,
+    fee_payer: Pubkey,
+}
+
+impl Drop for InflightPermit {
+    fn drop(&mut self) {
+        self.limiter.release(&self.fee_payer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fee_payer_inflight_limiter() {
+        let limiter = Arc::new(FeePayerInflightLimiter::new(2));
+        let fee_payer = Pubkey::new_unique();
+        let other_fee_payer = Pubkey::new_unique();
+
+        let permit0 = limiter.try_acquire(fee_payer).unwrap();
+        let permit1 = limiter.try_acquire(fee_payer).unwrap();
+        assert!(limiter.try_acquire(fee_payer).is_none());
+        assert_eq!(limiter.inflight(&fee_payer), 2);
+
+        // Other fee payers are not affected
+        let other_permit = limiter.try_acquire(other_fee_payer).unwrap();
+        assert_eq!(limiter.inflight(&other_fee_payer), 1);
+
+        drop(permit0);
+        assert_eq!(limiter.inflight(&fee_payer), 1);
+        let permit2 = limiter.try_acquire(fee_payer).unwrap();
+
+        drop((permit1, permit2, other_permit));
+        assert_eq!(limiter.inflight(&fee_payer), 0);
+        assert_eq!(limiter.inflight(&other_fee_payer), 0);
+        assert!(limiter.inflight.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_set_max_inflight_per_fee_payer() {
+        let limiter = Arc::new(FeePayerInflightLimiter::new(2));
+        let fee_payer = Pubkey::new_unique();
+        let permits: Vec<_> = (0..2)
+            .map(|_| limiter.try_acquire(fee_payer).unwrap())
+            .collect();
+        assert!(limiter.try_acquire(fee_payer).is_none());
+
+        limiter.set_max_inflight_per_fee_payer(3);
+        assert_eq!(limiter.max_inflight_per_fee_payer(), 3);
+        let permit = limiter.try_acquire(fee_payer).unwrap();
+        assert!(limiter.try_acquire(fee_payer).is_none());
+
+        // Lowering the limit does not revoke permits, but no more are handed out
+        // until enough of them are released
+        limiter.set_max_inflight_per_fee_payer(1);
+        assert_eq!(limiter.inflight(&fee_payer), 3);
+        drop(permit);
+        assert!(limiter.try_acquire(fee_payer).is_none());
+        drop(permits);
+        assert!(limiter.try_acquire(fee_payer).is_some());
+    }
+}
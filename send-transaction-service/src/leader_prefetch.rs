@@ -0,0 +1,234 @@
+//! Opens the connections to the upcoming leaders ahead of their slots, so that
+//! the first transactions sent to a new leader don't wait for a handshake, and
+//! tracks how many batches are queued for each leader.
+
+use {
+    log::warn,
+    solana_client::connection_cache::ConnectionCache,
+    solana_connection_cache::client_connection::ClientConnection as TpuConnection,
+    solana_sdk::timing::AtomicInterval,
+    std::{
+        collections::{hash_map::Entry, HashMap},
+        net::SocketAddr,
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            Mutex,
+        },
+        time::{Duration, Instant},
+    },
+};
+
+/// How often the upcoming leaders are looked up
+pub const LEADER_PREFETCH_INTERVAL_MS: u64 = 200;
+
+/// A connection warmed this recently is not warmed again
+const REWARM_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Report the leader prefetch metrics for every 5 seconds.
+const LEADER_PREFETCH_METRICS_REPORT_RATE_MS: u64 = 5000;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct LeaderSendCounts {
+    /// Count of batches queued on the connection of the leader. The batches
+    /// are sent asynchronously, so they may still fail to reach the leader.
+    pub queued: u64,
+    /// Count of batches the connection of the leader failed to queue
+    pub rejected: u64,
+}
+
+/// Metrics of the leader prefetch.
+#[derive(Default)]
+pub struct LeaderPrefetchStats {
+    /// Count of connections to upcoming leaders warmed
+    pub warmed_connections: AtomicU64,
+
+    /// Count of connections to upcoming leaders which failed to warm
+    pub warm_failures: AtomicU64,
+
+    /// Sends to each leader since the last report
+    leader_sends: Mutex<HashMap<SocketAddr, LeaderSendCounts>>,
+
+    last_report: AtomicInterval,
+}
+
+impl LeaderPrefetchStats {
+    pub fn record_send(&self, leader: &SocketAddr, queued: bool) {
+        let mut leader_sends = self.leader_sends.lock().unwrap();
+        let counts = leader_sends.entry(*leader).or_default();
+        if queued {
+            counts.queued += 1;
+        } else {
+            counts.rejected += 1;
+        }
+    }
+
+    /// Sends to each leader since the last report
+    pub fn leader_sends(&self) -> HashMap<SocketAddr, LeaderSendCounts> {
+        self.leader_sends.lock().unwrap().clone()
+    }
+
+    /// report metrics of the leader prefetch
+    pub(crate) fn report(&self) {
+        if !self
+            .last_report
+            .should_update(LEADER_PREFETCH_METRICS_REPORT_RATE_MS)
+        {
+            return;
+        }
+        datapoint_info!(
+            "send_transaction_service-leader_prefetch",
+            (
+                "warmed-connections",
+                self.warmed_connections.swap(0, Ordering::Relaxed),
+                i64
+            ),
+            (
+                "warm-failures",
+                self.warm_failures.swap(0, Ordering::Relaxed),
+                i64
+            ),
+        );
+        let leader_sends = std::mem::take(&mut *self.leader_sends.lock().unwrap());
+        for (leader, LeaderSendCounts { queued, rejected }) in leader_sends {
+            datapoint_info!(
+                "send_transaction_service-leader_sends",
+                ("leader", leader.to_string(), String),
+                ("queued", queued, i64),
+                ("rejected", rejected, i64),
+            );
+        }
+    }
+}
+
+/// Keeps the connections to the upcoming leaders warm.
+pub struct LeaderPrefetcher {
+    /// When the connection to each upcoming leader was last warmed
+    warmed: HashMap<SocketAddr, Instant>,
+}
+
+impl Default for LeaderPrefetcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LeaderPrefetcher {
+    pub fn new() -> Self {
+        Self {
+            warmed: HashMap::new(),
+        }
+    }
+
+    /// Warms the connections to those of `upcoming_leaders` which have not been
+    /// warmed recently
+    pub fn prefetch(
+        &mut self,
+        connection_cache: &ConnectionCache,
+        upcoming_leaders: &[SocketAddr],
+        stats: &LeaderPrefetchStats,
+    ) {
+        for leader in self.leaders_to_warm(upcoming_leaders, Instant::now()) {
+            let conn = connection_cache.get_connection(&leader);
+            match conn.send_data(&[]) {
+                Ok(()) => {
+                    stats.warmed_connections.fetch_add(1, Ordering::Relaxed);
+                }
+                Err(err) => {
+                    warn!("Failed to warm up the connection to the leader at {leader}: {err:?}");
+                    stats.warm_failures.fetch_add(1, Ordering::Relaxed);
+                    // try again on the next lookup
+                    self.warmed.remove(&leader);
+                }
+            }
+        }
+    }
+
+    /// Returns the leaders whose connections need warming, and forgets about
+    /// the leaders which are no longer upcoming
+    fn leaders_to_warm(
+        &mut self,
+        upcoming_leaders: &[SocketAddr],
+        now: Instant,
+    ) -> Vec<SocketAddr> {
+        self.warmed
+            .retain(|leader, _warmed_at| upcoming_leaders.contains(leader));
+        upcoming_leaders
+            .iter()
+            .filter(|leader| match self.warmed.entry(**leader) {
+                Entry::Occupied(mut entry) => {
+                    if now.duration_since(*entry.get()) < REWARM_INTERVAL {
+                        return false;
+                    }
+                    entry.insert(now);
+                    true
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(now);
+                    true
+                }
+            })
+            .copied()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, std::net::Ipv4Addr};
+
+    fn leader(port: u16) -> SocketAddr {
+        SocketAddr::from((Ipv4Addr::LOCALHOST, port))
+    }
+
+    #[test]
+    fn test_leaders_to_warm() {
+        let mut prefetcher = LeaderPrefetcher::new();
+        let now = Instant::now();
+
+        assert_eq!(
+            prefetcher.leaders_to_warm(&[leader(1), leader(2)], now),
+            vec![leader(1), leader(2)]
+        );
+        // Recently warmed leaders are skipped
+        assert_eq!(
+            prefetcher.leaders_to_warm(&[leader(2), leader(3)], now),
+            vec![leader(3)]
+        );
+        // Leaders which are no longer upcoming are forgotten
+        assert!(!prefetcher.warmed.contains_key(&leader(1)));
+        assert_eq!(
+            prefetcher.leaders_to_warm(&[leader(1)], now),
+            vec![leader(1)]
+        );
+        // Connections are warmed again once the rewarm interval elapses
+        assert_eq!(
+            prefetcher.leaders_to_warm(&[leader(1)], now + REWARM_INTERVAL),
+            vec![leader(1)]
+        );
+    }
+
+    #[test]
+    fn test_record_send() {
+        let stats = LeaderPrefetchStats::default();
+        stats.record_send(&leader(1), true);
+        stats.record_send(&leader(1), true);
+        stats.record_send(&leader(1), false);
+        stats.record_send(&leader(2), false);
+
+        let leader_sends = stats.leader_sends();
+        assert_eq!(
+            leader_sends[&leader(1)],
+            LeaderSendCounts {
+                queued: 2,
+                rejected: 1
+            }
+        );
+        assert_eq!(
+            leader_sends[&leader(2)],
+            LeaderSendCounts {
+                queued: 0,
+                rejected: 1
+            }
+        );
+    }
+}
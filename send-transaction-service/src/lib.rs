@@ -1,4 +1,6 @@
 #![allow(clippy::arithmetic_side_effects)]
+pub mod inflight_limiter;
+pub mod leader_prefetch;
 pub mod send_transaction_service;
 pub mod send_transaction_service_stats;
 #[cfg(any(test, feature = "dev-context-only-utils"))]
@@ -8,17 +8,25 @@ pub use crate::{
 };
 use {
     crate::{
+        inflight_limiter::InflightPermit,
+        leader_prefetch::{LeaderPrefetchStats, LeaderPrefetcher, LEADER_PREFETCH_INTERVAL_MS},
         send_transaction_service_stats::SendTransactionServiceStatsReport,
         tpu_info::TpuInfo,
         transaction_client::{ConnectionCacheClient, TransactionClient},
     },
-    crossbeam_channel::{Receiver, RecvTimeoutError},
+    crossbeam_channel::{Receiver, RecvTimeoutError, Sender},
     itertools::Itertools,
     log::*,
     solana_client::connection_cache::ConnectionCache,
     solana_runtime::{bank::Bank, bank_forks::BankForks},
     solana_sdk::{
-        hash::Hash, nonce_account, pubkey::Pubkey, saturating_add_assign, signature::Signature,
+        clock::Slot,
+        hash::Hash,
+        nonce_account,
+        pubkey::Pubkey,
+        saturating_add_assign,
+        signature::Signature,
+        transaction::{self, TransactionError},
     },
     std::{
         collections::{
@@ -66,6 +74,7 @@ pub const MAX_BATCH_SEND_RATE_MS: usize = 100_000;
 pub struct SendTransactionService {
     receive_txn_thread: JoinHandle<()>,
     retry_thread: JoinHandle<()>,
+    leader_prefetch_thread: Option<JoinHandle<()>>,
     exit: Arc<AtomicBool>,
 }
 
@@ -78,6 +87,8 @@ pub struct TransactionInfo {
     retries: usize,
     /// Last time the transaction was sent
     last_sent_time: Option<Instant>,
+    /// Released once the service is done with the transaction
+    inflight_permit: Option<InflightPermit>,
 }
 
 impl TransactionInfo {
@@ -97,8 +108,54 @@ impl TransactionInfo {
             max_retries,
             retries: 0,
             last_sent_time,
+            inflight_permit: None,
         }
     }
+
+    /// Holds `inflight_permit` until the service is done with the transaction
+    pub fn with_inflight_permit(mut self, inflight_permit: InflightPermit) -> Self {
+        self.inflight_permit = Some(inflight_permit);
+        self
+    }
+}
+
+/// A change in the state of a transaction handled by the service
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TransactionState {
+    /// Broadcast for the first time
+    Sent,
+    /// Broadcast again, for the `retries`th time, because no bank has processed it yet
+    Retried { retries: usize },
+    /// Dropped after its first broadcast because the retry pool is full
+    RetryPoolFull,
+    /// Processed in a rooted slot, successfully or not
+    Rooted {
+        slot: Slot,
+        status: transaction::Result<()>,
+    },
+    /// Dropped because its blockhash or durable nonce is no longer valid
+    Expired,
+    /// Dropped after being retried `max_retries` times
+    MaxRetriesElapsed { max_retries: usize },
+    /// Dropped because it failed in a slot that is not rooted yet
+    Failed { slot: Slot, error: TransactionError },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TransactionStatusEvent {
+    pub signature: Signature,
+    pub state: TransactionState,
+}
+
+fn send_status_event(
+    status_sender: &Option<Sender<TransactionStatusEvent>>,
+    signature: Signature,
+    state: TransactionState,
+) {
+    if let Some(status_sender) = status_sender {
+        // Don't hold up the service on a slow or gone consumer
+        let _ = status_sender.try_send(TransactionStatusEvent { signature, state });
+    }
 }
 
 #[derive(Default, Debug, PartialEq, Eq)]
@@ -124,6 +181,12 @@ pub struct Config {
     /// When the retry pool exceeds this max size, new transactions are dropped after their first broadcast attempt
     pub retry_pool_max_size: usize,
     pub tpu_peers: Option<Vec<SocketAddr>>,
+    /// Receives every change in the state of the transactions handled by the service. Events
+    /// are dropped when the channel is full, so it should be unbounded or drained promptly.
+    pub status_sender: Option<Sender<TransactionStatusEvent>>,
+    /// Number of leaders after the ones transactions are forwarded to whose QUIC connections
+    /// are opened ahead of their slots, none if 0
+    pub leader_prefetch_count: u64,
 }
 
 impl Default for Config {
@@ -137,6 +200,8 @@ impl Default for Config {
             batch_send_rate_ms: DEFAULT_BATCH_SEND_RATE_MS,
             retry_pool_max_size: MAX_TRANSACTION_RETRY_POOL_SIZE,
             tpu_peers: None,
+            status_sender: None,
+            leader_prefetch_count: 0,
         }
     }
 }
@@ -189,7 +254,20 @@ impl SendTransactionService {
             config.leader_forward_count,
         );
 
-        Self::new_with_client(bank_forks, receiver, client, config, exit)
+        if config.leader_prefetch_count == 0 || !connection_cache.use_quic() {
+            return Self::new_with_client(bank_forks, receiver, client, config, exit);
+        }
+        let leader_prefetch_stats = Arc::new(LeaderPrefetchStats::default());
+        let client = client.with_leader_prefetch_stats(leader_prefetch_stats.clone());
+        let leader_prefetch_thread = Self::leader_prefetch_thread(
+            client.clone(),
+            config.leader_forward_count + config.leader_prefetch_count,
+            leader_prefetch_stats,
+            exit.clone(),
+        );
+        let mut service = Self::new_with_client(bank_forks, receiver, client, config, exit);
+        service.leader_prefetch_thread = Some(leader_prefetch_thread);
+        service
     }
 
     pub fn new_with_client<Client: TransactionClient + Clone + std::marker::Send + 'static>(
@@ -223,10 +301,37 @@ impl SendTransactionService {
         Self {
             receive_txn_thread,
             retry_thread,
+            leader_prefetch_thread: None,
             exit,
         }
     }
 
+    /// Thread keeping the connections to the upcoming `leader_count` leaders warm.
+    fn leader_prefetch_thread<T: TpuInfo + std::marker::Send + 'static>(
+        client: ConnectionCacheClient<T>,
+        leader_count: u64,
+        leader_prefetch_stats: Arc<LeaderPrefetchStats>,
+        exit: Arc<AtomicBool>,
+    ) -> JoinHandle<()> {
+        debug!("Starting send-transaction-service::leader_prefetch_thread");
+        Builder::new()
+            .name("solStxPrefetch".to_string())
+            .spawn(move || {
+                let mut prefetcher = LeaderPrefetcher::new();
+                while !exit.load(Ordering::Relaxed) {
+                    let upcoming_leaders = client.upcoming_leader_tpus(leader_count);
+                    prefetcher.prefetch(
+                        client.connection_cache(),
+                        &upcoming_leaders,
+                        &leader_prefetch_stats,
+                    );
+                    leader_prefetch_stats.report();
+                    sleep(Duration::from_millis(LEADER_PREFETCH_INTERVAL_MS));
+                }
+            })
+            .unwrap()
+    }
+
     /// Thread responsible for receiving transactions from RPC clients.
     fn receive_txn_thread<Client: TransactionClient + std::marker::Send + 'static>(
         receiver: Receiver<TransactionInfo>,
@@ -236,6 +341,7 @@ impl SendTransactionService {
             batch_send_rate_ms,
             batch_size,
             retry_pool_max_size,
+            status_sender,
             ..
         }: Config,
         stats_report: Arc<SendTransactionServiceStatsReport>,
@@ -294,6 +400,9 @@ impl SendTransactionService {
                         .map(|transaction_info| transaction_info.wire_transaction.clone())
                         .collect::<Vec<Vec<u8>>>();
                     client.send_transactions_in_batch(wire_transactions, stats);
+                    for signature in transactions.keys() {
+                        send_status_event(&status_sender, *signature, TransactionState::Sent);
+                    }
                     let last_sent_time = Instant::now();
                     {
                         // take a lock of retry_transactions and move the batch to the retry set.
@@ -305,7 +414,11 @@ impl SendTransactionService {
                             let entry = retry_transactions.entry(signature);
                             if let Entry::Vacant(_) = entry {
                                 if retry_len >= retry_pool_max_size {
-                                    break;
+                                    send_status_event(
+                                        &status_sender,
+                                        signature,
+                                        TransactionState::RetryPoolFull,
+                                    );
                                 } else {
                                     transaction_info.last_sent_time = Some(last_sent_time);
                                     saturating_add_assign!(transactions_added_to_retry, 1);
@@ -341,34 +454,52 @@ impl SendTransactionService {
         debug!("Starting send-transaction-service::retry_thread.");
         Builder::new()
             .name("solStxRetry".to_string())
-            .spawn(move || loop {
-                let retry_interval_ms = config.retry_rate_ms;
-                let stats = &stats_report.stats;
-                sleep(Duration::from_millis(
-                    MAX_RETRY_SLEEP_MS.min(retry_interval_ms),
-                ));
-                if exit.load(Ordering::Relaxed) {
-                    break;
-                }
-                let mut transactions = retry_transactions.lock().unwrap();
-                if !transactions.is_empty() {
-                    stats
-                        .retry_queue_size
-                        .store(transactions.len() as u64, Ordering::Relaxed);
-                    let (root_bank, working_bank) = {
-                        let bank_forks = bank_forks.read().unwrap();
-                        (bank_forks.root_bank(), bank_forks.working_bank())
-                    };
-
-                    let _result = Self::process_transactions(
-                        &working_bank,
-                        &root_bank,
-                        &mut transactions,
-                        &client,
-                        &config,
-                        stats,
-                    );
-                    stats_report.report();
+            .spawn(move || {
+                let (mut root_receiver, mut confirmed_slot_receiver) = {
+                    let bank_forks = bank_forks.read().unwrap();
+                    (
+                        bank_forks.root_receiver(),
+                        bank_forks.highest_confirmed_slot_receiver(),
+                    )
+                };
+                let mut banks = None;
+                loop {
+                    let retry_interval_ms = config.retry_rate_ms;
+                    let stats = &stats_report.stats;
+                    sleep(Duration::from_millis(
+                        MAX_RETRY_SLEEP_MS.min(retry_interval_ms),
+                    ));
+                    if exit.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    let mut transactions = retry_transactions.lock().unwrap();
+                    if !transactions.is_empty() {
+                        stats
+                            .retry_queue_size
+                            .store(transactions.len() as u64, Ordering::Relaxed);
+                        // Only take the bank_forks lock once the root or the highest optimistically
+                        // confirmed slot has moved, rather than on every retry tick
+                        let root_changed = root_receiver.has_changed().unwrap_or(true);
+                        let confirmed_slot_changed =
+                            confirmed_slot_receiver.has_changed().unwrap_or(true);
+                        if banks.is_none() || root_changed || confirmed_slot_changed {
+                            root_receiver.borrow_and_update();
+                            confirmed_slot_receiver.borrow_and_update();
+                            let bank_forks = bank_forks.read().unwrap();
+                            banks = Some((bank_forks.root_bank(), bank_forks.working_bank()));
+                        }
+                        let (root_bank, working_bank) = banks.as_ref().unwrap();
+
+                        let _result = Self::process_transactions(
+                            working_bank,
+                            root_bank,
+                            &mut transactions,
+                            &client,
+                            &config,
+                            stats,
+                        );
+                        stats_report.report();
+                    }
                 }
             })
             .unwrap()
@@ -385,6 +516,7 @@ impl SendTransactionService {
             service_max_retries,
             default_max_retries,
             batch_size,
+            ref status_sender,
             ..
         }: &Config,
         stats: &SendTransactionServiceStats,
@@ -394,17 +526,47 @@ impl SendTransactionService {
         let mut batched_transactions = HashSet::new();
         let retry_rate = Duration::from_millis(retry_rate_ms);
 
+        // Look up the statuses of all pending transactions in one pass over each status cache
+        let signatures: Vec<_> = transactions.keys().copied().collect();
+        let (mut rooted_statuses, unrooted_signatures): (HashMap<_, _>, Vec<_>) = {
+            let root_statuses = root_bank.get_signature_statuses_slot(&signatures);
+            let (rooted, unrooted): (Vec<_>, Vec<_>) = signatures
+                .into_iter()
+                .zip(root_statuses)
+                .partition(|(_signature, status)| status.is_some());
+            (
+                rooted
+                    .into_iter()
+                    .map(|(signature, status)| (signature, status.unwrap()))
+                    .collect(),
+                unrooted
+                    .into_iter()
+                    .map(|(signature, _)| signature)
+                    .collect(),
+            )
+        };
+        let working_statuses = working_bank.get_signature_statuses_slot(&unrooted_signatures);
+        let mut signature_statuses: HashMap<_, _> = unrooted_signatures
+            .into_iter()
+            .zip(working_statuses)
+            .collect();
+
         transactions.retain(|signature, transaction_info| {
             if transaction_info.durable_nonce_info.is_some() {
                 stats.nonced_transactions.fetch_add(1, Ordering::Relaxed);
             }
-            if root_bank.has_signature(signature) {
+            if let Some((slot, status)) = rooted_statuses.remove(signature) {
                 info!("Transaction is rooted: {}", signature);
                 result.rooted += 1;
                 stats.rooted_transactions.fetch_add(1, Ordering::Relaxed);
+                send_status_event(
+                    status_sender,
+                    *signature,
+                    TransactionState::Rooted { slot, status },
+                );
                 return false;
             }
-            let signature_status = working_bank.get_signature_status_slot(signature);
+            let signature_status = signature_statuses.remove(signature).flatten();
             if let Some((nonce_pubkey, durable_nonce)) = transaction_info.durable_nonce_info {
                 let nonce_account = working_bank.get_account(&nonce_pubkey).unwrap_or_default();
                 let now = Instant::now();
@@ -418,6 +580,7 @@ impl SendTransactionService {
                     info!("Dropping expired durable-nonce transaction: {}", signature);
                     result.expired += 1;
                     stats.expired_transactions.fetch_add(1, Ordering::Relaxed);
+                    send_status_event(status_sender, *signature, TransactionState::Expired);
                     return false;
                 }
             }
@@ -425,6 +588,7 @@ impl SendTransactionService {
                 info!("Dropping expired transaction: {}", signature);
                 result.expired += 1;
                 stats.expired_transactions.fetch_add(1, Ordering::Relaxed);
+                send_status_event(status_sender, *signature, TransactionState::Expired);
                 return false;
             }
 
@@ -440,6 +604,11 @@ impl SendTransactionService {
                     stats
                         .transactions_exceeding_max_retries
                         .fetch_add(1, Ordering::Relaxed);
+                    send_status_event(
+                        status_sender,
+                        *signature,
+                        TransactionState::MaxRetriesElapsed { max_retries },
+                    );
                     return false;
                 }
             }
@@ -460,6 +629,15 @@ impl SendTransactionService {
                             result.retried += 1;
                             transaction_info.retries += 1;
                             stats.retries.fetch_add(1, Ordering::Relaxed);
+                            send_status_event(
+                                status_sender,
+                                *signature,
+                                TransactionState::Retried {
+                                    retries: transaction_info.retries,
+                                },
+                            );
+                        } else {
+                            send_status_event(status_sender, *signature, TransactionState::Sent);
                         }
 
                         batched_transactions.insert(*signature);
@@ -467,11 +645,16 @@ impl SendTransactionService {
                     }
                     true
                 }
-                Some((_slot, status)) => {
-                    if status.is_err() {
+                Some((slot, status)) => {
+                    if let Err(error) = status {
                         info!("Dropping failed transaction: {}", signature);
                         result.failed += 1;
                         stats.failed_transactions.fetch_add(1, Ordering::Relaxed);
+                        send_status_event(
+                            status_sender,
+                            *signature,
+                            TransactionState::Failed { slot, error },
+                        );
                         false
                     } else {
                         result.retained += 1;
@@ -500,6 +683,9 @@ impl SendTransactionService {
     pub fn join(self) -> thread::Result<()> {
         self.receive_txn_thread.join()?;
         self.exit.store(true, Ordering::Relaxed);
+        if let Some(leader_prefetch_thread) = self.leader_prefetch_thread {
+            leader_prefetch_thread.join()?;
+        }
         self.retry_thread.join()
     }
 }
@@ -562,6 +748,7 @@ mod test {
             max_retries: None,
             retries: 0,
             last_sent_time: None,
+            inflight_permit: None,
         };
 
         let exit = Arc::new(AtomicBool::new(false));
@@ -603,7 +790,11 @@ mod test {
         let (_, bank_forks) = Bank::new_with_bank_forks_for_tests(&genesis_config);
 
         let leader_forward_count = 1;
-        let config = Config::default();
+        let (status_sender, status_receiver) = unbounded();
+        let config = Config {
+            status_sender: Some(status_sender),
+            ..Config::default()
+        };
 
         let root_bank = Bank::new_from_parent(
             bank_forks.read().unwrap().working_bank(),
@@ -681,6 +872,13 @@ mod test {
                 ..ProcessTransactionsResult::default()
             }
         );
+        assert_eq!(
+            status_receiver.try_iter().collect::<Vec<_>>(),
+            vec![TransactionStatusEvent {
+                signature: Signature::default(),
+                state: TransactionState::Expired,
+            }]
+        );
 
         info!("Rooted transactions are dropped...");
         transactions.insert(
@@ -710,6 +908,16 @@ mod test {
                 ..ProcessTransactionsResult::default()
             }
         );
+        assert_eq!(
+            status_receiver.try_iter().collect::<Vec<_>>(),
+            vec![TransactionStatusEvent {
+                signature: rooted_signature,
+                state: TransactionState::Rooted {
+                    slot: root_bank.slot(),
+                    status: Ok(()),
+                },
+            }]
+        );
 
         info!("Failed transactions are dropped...");
         transactions.insert(
@@ -739,6 +947,13 @@ mod test {
                 ..ProcessTransactionsResult::default()
             }
         );
+        let events: Vec<_> = status_receiver.try_iter().collect();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].signature, failed_signature);
+        assert!(matches!(
+            events[0].state,
+            TransactionState::Failed { slot, .. } if slot == working_bank.slot()
+        ));
 
         info!("Non-rooted transactions are kept...");
         transactions.insert(
@@ -768,6 +983,7 @@ mod test {
                 ..ProcessTransactionsResult::default()
             }
         );
+        assert!(status_receiver.try_recv().is_err());
         transactions.clear();
 
         info!("Unknown transactions are retried...");
@@ -799,6 +1015,13 @@ mod test {
                 ..ProcessTransactionsResult::default()
             }
         );
+        assert_eq!(
+            status_receiver.try_iter().collect::<Vec<_>>(),
+            vec![TransactionStatusEvent {
+                signature: Signature::default(),
+                state: TransactionState::Retried { retries: 1 },
+            }]
+        );
         transactions.clear();
 
         info!("Transactions are only retried until max_retries");
@@ -51,6 +51,9 @@ pub struct SendTransactionServiceStats {
 
     /// Count of transactions failed
     pub failed_transactions: AtomicU64,
+
+    /// Count of sends skipped because the circuit breaker of the peer was open
+    pub skipped_unavailable_peers: AtomicU64,
 }
 
 #[derive(Default)]
@@ -141,6 +144,13 @@ impl SendTransactionServiceStatsReport {
                     "failed-tx",
                     self.stats.failed_transactions.swap(0, Ordering::Relaxed),
                     i64
+                ),
+                (
+                    "skipped-unavailable-peer",
+                    self.stats
+                        .skipped_unavailable_peers
+                        .swap(0, Ordering::Relaxed),
+                    i64
                 )
             );
         }
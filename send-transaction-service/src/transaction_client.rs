@@ -1,5 +1,8 @@
 use {
-    crate::{send_transaction_service_stats::SendTransactionServiceStats, tpu_info::TpuInfo},
+    crate::{
+        leader_prefetch::LeaderPrefetchStats,
+        send_transaction_service_stats::SendTransactionServiceStats, tpu_info::TpuInfo,
+    },
     log::warn,
     solana_client::connection_cache::ConnectionCache,
     solana_connection_cache::client_connection::ClientConnection as TpuConnection,
@@ -29,6 +32,7 @@ pub struct ConnectionCacheClient<T: TpuInfoWithSendStatic> {
     tpu_peers: Option<Vec<SocketAddr>>,
     leader_info_provider: Arc<Mutex<CurrentLeaderInfo<T>>>,
     leader_forward_count: u64,
+    leader_prefetch_stats: Option<Arc<LeaderPrefetchStats>>,
 }
 
 // Manual implementation of Clone without requiring T to be Clone
@@ -43,6 +47,7 @@ where
             tpu_peers: self.tpu_peers.clone(),
             leader_info_provider: Arc::clone(&self.leader_info_provider),
             leader_forward_count: self.leader_forward_count,
+            leader_prefetch_stats: self.leader_prefetch_stats.clone(),
         }
     }
 }
@@ -65,9 +70,39 @@ where
             tpu_peers,
             leader_info_provider,
             leader_forward_count,
+            leader_prefetch_stats: None,
         }
     }
 
+    /// Records the outcome of the sends to each leader in `leader_prefetch_stats`
+    pub fn with_leader_prefetch_stats(
+        mut self,
+        leader_prefetch_stats: Arc<LeaderPrefetchStats>,
+    ) -> Self {
+        self.leader_prefetch_stats = Some(leader_prefetch_stats);
+        self
+    }
+
+    pub fn connection_cache(&self) -> &Arc<ConnectionCache> {
+        &self.connection_cache
+    }
+
+    /// Returns the TPU addresses of the leaders of the next
+    /// `leader_count * NUM_CONSECUTIVE_LEADER_SLOTS` slots
+    pub fn upcoming_leader_tpus(&self, leader_count: u64) -> Vec<SocketAddr> {
+        let mut leader_info_provider = self.leader_info_provider.lock().unwrap();
+        leader_info_provider
+            .get_leader_info()
+            .map(|leader_info| {
+                leader_info
+                    .get_leader_tpus(leader_count, self.connection_cache.protocol())
+                    .into_iter()
+                    .copied()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
     fn get_tpu_addresses<'a>(&'a self, leader_info: Option<&'a T>) -> Vec<&'a SocketAddr> {
         leader_info
             .map(|leader_info| {
@@ -84,10 +119,22 @@ where
         wire_transactions: Vec<Vec<u8>>,
         stats: &SendTransactionServiceStats,
     ) {
+        if !self.connection_cache.is_peer_available(peer) {
+            // Sends to this peer keep failing, don't queue more on a dead connection until the
+            // connection cache lets a probe through again.
+            stats
+                .skipped_unavailable_peers
+                .fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+
         let mut measure = Measure::start("send-us");
         let conn = self.connection_cache.get_connection(peer);
         let result = conn.send_data_batch_async(wire_transactions);
 
+        if let Some(leader_prefetch_stats) = &self.leader_prefetch_stats {
+            leader_prefetch_stats.record_send(peer, result.is_ok());
+        }
         if let Err(err) = result {
             warn!(
                 "Failed to send transaction transaction to {}: {:?}",
@@ -0,0 +1,71 @@
+#![allow(clippy::arithmetic_side_effects)]
+#![feature(test)]
+
+extern crate test;
+
+use {
+    solana_net_utils::bind_to_localhost,
+    solana_packet::PACKET_DATA_SIZE,
+    solana_streamer::sendmmsg::{batch_send, multi_target_send},
+    std::net::{SocketAddr, UdpSocket},
+    test::Bencher,
+};
+
+const NUM_PACKETS: usize = 1024;
+// Roughly the fanout of a node in turbine
+const NUM_DESTINATIONS: usize = 200;
+
+fn bind_receivers(num_receivers: usize) -> (Vec<UdpSocket>, Vec<SocketAddr>) {
+    let receivers: Vec<_> = (0..num_receivers)
+        .map(|_| bind_to_localhost().unwrap())
+        .collect();
+    let addrs = receivers
+        .iter()
+        .map(|socket| socket.local_addr().unwrap())
+        .collect();
+    (receivers, addrs)
+}
+
+#[bench]
+fn bench_send_to_loop(bencher: &mut Bencher) {
+    let sender = bind_to_localhost().unwrap();
+    let (_receivers, addrs) = bind_receivers(1);
+    let packets = vec![vec![0u8; PACKET_DATA_SIZE]; NUM_PACKETS];
+    bencher.iter(|| {
+        for packet in &packets {
+            let _ = sender.send_to(packet, addrs[0]);
+        }
+    });
+}
+
+#[bench]
+fn bench_batch_send(bencher: &mut Bencher) {
+    let sender = bind_to_localhost().unwrap();
+    let (_receivers, addrs) = bind_receivers(1);
+    let packets = vec![vec![0u8; PACKET_DATA_SIZE]; NUM_PACKETS];
+    bencher.iter(|| {
+        let _ = batch_send(&sender, packets.iter().map(|packet| (packet, &addrs[0])));
+    });
+}
+
+#[bench]
+fn bench_send_to_loop_multi_target(bencher: &mut Bencher) {
+    let sender = bind_to_localhost().unwrap();
+    let (_receivers, addrs) = bind_receivers(NUM_DESTINATIONS);
+    let packet = vec![0u8; PACKET_DATA_SIZE];
+    bencher.iter(|| {
+        for addr in &addrs {
+            let _ = sender.send_to(&packet, addr);
+        }
+    });
+}
+
+#[bench]
+fn bench_multi_target_send(bencher: &mut Bencher) {
+    let sender = bind_to_localhost().unwrap();
+    let (_receivers, addrs) = bind_receivers(NUM_DESTINATIONS);
+    let packet = vec![0u8; PACKET_DATA_SIZE];
+    bencher.iter(|| {
+        let _ = multi_target_send(&sender, &packet, &addrs);
+    });
+}
@@ -1,11 +1,14 @@
 #![allow(clippy::arithmetic_side_effects)]
 pub mod msghdr;
+#[cfg(feature = "dev-context-only-utils")]
+pub mod network_conditions;
 pub mod nonblocking;
 pub mod packet;
 pub mod quic;
 pub mod recvmmsg;
 pub mod sendmmsg;
 pub mod socket;
+pub mod socket_stats;
 pub mod streamer;
 
 #[macro_use]
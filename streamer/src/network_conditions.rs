@@ -0,0 +1,237 @@
+//! Simulated network conditions, for tests.
+//!
+//! Packets read by [`crate::streamer::receiver`] from a socket registered with
+//! [`condition_socket`] are dropped and delayed by a [`NetworkConditioner`] before being handed
+//! to the receiver's consumer, so that local clusters can reproduce lossy, high latency links
+//! between their nodes. Sockets are looked up by local address when the receiver starts, and
+//! QUIC endpoints are not affected.
+
+use {
+    crate::{packet::PacketBatch, streamer::PacketBatchSender},
+    crossbeam_channel::{Receiver, RecvTimeoutError},
+    rand::{rngs::StdRng, Rng, SeedableRng},
+    std::{
+        cmp::Ordering,
+        collections::{BinaryHeap, HashMap},
+        net::SocketAddr,
+        sync::{Arc, LazyLock, Mutex, RwLock},
+        thread::{Builder, JoinHandle},
+        time::{Duration, Instant},
+    },
+};
+
+// How long to wait for packets when none are delayed
+const RECV_TIMEOUT: Duration = Duration::from_millis(100);
+
+static SOCKET_CONDITIONERS: LazyLock<RwLock<HashMap<SocketAddr, Arc<NetworkConditioner>>>> =
+    LazyLock::new(RwLock::default);
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct NetworkConditions {
+    /// Delay added to every received packet batch
+    pub latency: Duration,
+    /// Up to this much additional delay, picked uniformly at random for each batch
+    pub jitter: Duration,
+    /// Percentage of received packets dropped, from 0 to 100
+    pub packet_loss_percent: f64,
+}
+
+/// Applies [`NetworkConditions`], which can be changed at any time, to received packets
+pub struct NetworkConditioner {
+    conditions: RwLock<NetworkConditions>,
+    rng: Mutex<StdRng>,
+}
+
+impl NetworkConditioner {
+    /// Packets are dropped and delayed at random, drawing from an RNG seeded with `seed`
+    pub fn new(conditions: NetworkConditions, seed: u64) -> Self {
+        Self {
+            conditions: RwLock::new(conditions),
+            rng: Mutex::new(StdRng::seed_from_u64(seed)),
+        }
+    }
+
+    pub fn conditions(&self) -> NetworkConditions {
+        *self.conditions.read().unwrap()
+    }
+
+    pub fn set_conditions(&self, conditions: NetworkConditions) {
+        *self.conditions.write().unwrap() = conditions;
+    }
+
+    /// Drops packets of `packet_batch`, and returns the rest with the time they are delivered
+    /// at, if any
+    fn condition(&self, packet_batch: PacketBatch) -> Option<(Instant, PacketBatch)> {
+        let conditions = self.conditions();
+        let mut rng = self.rng.lock().unwrap();
+        let packet_batch = if conditions.packet_loss_percent > 0.0 {
+            let loss_probability = (conditions.packet_loss_percent / 100.0).clamp(0.0, 1.0);
+            PacketBatch::new(
+                packet_batch
+                    .iter()
+                    .filter(|_| !rng.gen_bool(loss_probability))
+                    .cloned()
+                    .collect(),
+            )
+        } else {
+            packet_batch
+        };
+        if packet_batch.is_empty() {
+            return None;
+        }
+        let jitter = if conditions.jitter.is_zero() {
+            Duration::ZERO
+        } else {
+            rng.gen_range(Duration::ZERO..=conditions.jitter)
+        };
+        Some((Instant::now() + conditions.latency + jitter, packet_batch))
+    }
+}
+
+/// Applies `conditioner` to the packets received on the socket bound to `addr`, by receivers
+/// started from now on
+pub fn condition_socket(addr: SocketAddr, conditioner: Arc<NetworkConditioner>) {
+    SOCKET_CONDITIONERS
+        .write()
+        .unwrap()
+        .insert(addr, conditioner);
+}
+
+pub fn remove_socket_conditioner(addr: &SocketAddr) {
+    SOCKET_CONDITIONERS.write().unwrap().remove(addr);
+}
+
+pub(crate) fn socket_conditioner(addr: &SocketAddr) -> Option<Arc<NetworkConditioner>> {
+    SOCKET_CONDITIONERS.read().unwrap().get(addr).cloned()
+}
+
+struct DelayedBatch {
+    deliver_at: Instant,
+    // Keeps batches delivered at the same time in order
+    index: u64,
+    packet_batch: PacketBatch,
+}
+
+impl PartialEq for DelayedBatch {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for DelayedBatch {}
+
+impl PartialOrd for DelayedBatch {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for DelayedBatch {
+    // Reversed, so that the max-heap pops the earliest batch first
+    fn cmp(&self, other: &Self) -> Ordering {
+        (other.deliver_at, other.index).cmp(&(self.deliver_at, self.index))
+    }
+}
+
+/// Forwards the packet batches of `receiver` to `sender` through `conditioner`, until
+/// `receiver` disconnects
+pub(crate) fn spawn_conditioner(
+    thread_name: String,
+    conditioner: Arc<NetworkConditioner>,
+    receiver: Receiver<PacketBatch>,
+    sender: PacketBatchSender,
+) -> JoinHandle<()> {
+    Builder::new()
+        .name(thread_name)
+        .spawn(move || {
+            let mut delayed_batches = BinaryHeap::new();
+            let mut index = 0;
+            loop {
+                let timeout = delayed_batches
+                    .peek()
+                    .map(|batch: &DelayedBatch| {
+                        batch.deliver_at.saturating_duration_since(Instant::now())
+                    })
+                    .unwrap_or(RECV_TIMEOUT);
+                match receiver.recv_timeout(timeout) {
+                    Ok(packet_batch) => {
+                        if let Some((deliver_at, packet_batch)) =
+                            conditioner.condition(packet_batch)
+                        {
+                            delayed_batches.push(DelayedBatch {
+                                deliver_at,
+                                index,
+                                packet_batch,
+                            });
+                            index += 1;
+                        }
+                    }
+                    Err(RecvTimeoutError::Timeout) => {}
+                    Err(RecvTimeoutError::Disconnected) => return,
+                }
+                let now = Instant::now();
+                while delayed_batches
+                    .peek()
+                    .is_some_and(|batch| batch.deliver_at <= now)
+                {
+                    let batch = delayed_batches.pop().unwrap();
+                    if sender.send(batch.packet_batch).is_err() {
+                        return;
+                    }
+                }
+            }
+        })
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, crossbeam_channel::unbounded, solana_packet::Packet};
+
+    fn packet_batch(num_packets: usize) -> PacketBatch {
+        PacketBatch::new(vec![Packet::default(); num_packets])
+    }
+
+    #[test]
+    fn test_network_conditioner() {
+        let conditioner = Arc::new(NetworkConditioner::new(NetworkConditions::default(), 0));
+        let (sender, receiver) = unbounded();
+        let (conditioned_sender, conditioned_receiver) = unbounded();
+        let t_conditioner = spawn_conditioner(
+            "solCondTest".to_string(),
+            conditioner.clone(),
+            receiver,
+            conditioned_sender,
+        );
+
+        // Unconditioned packets go through
+        sender.send(packet_batch(10)).unwrap();
+        assert_eq!(conditioned_receiver.recv().unwrap().len(), 10);
+
+        // Lost packets are dropped, and the rest is delayed
+        conditioner.set_conditions(NetworkConditions {
+            latency: Duration::from_millis(200),
+            jitter: Duration::from_millis(50),
+            packet_loss_percent: 50.0,
+        });
+        let start = Instant::now();
+        sender.send(packet_batch(1_000)).unwrap();
+        let num_packets = conditioned_receiver.recv().unwrap().len();
+        assert!(start.elapsed() >= Duration::from_millis(200));
+        assert!((400..600).contains(&num_packets), "{num_packets}");
+
+        // Fully lost batches are not delivered
+        conditioner.set_conditions(NetworkConditions {
+            packet_loss_percent: 100.0,
+            ..NetworkConditions::default()
+        });
+        sender.send(packet_batch(10)).unwrap();
+        assert!(matches!(
+            conditioned_receiver.recv_timeout(Duration::from_millis(500)),
+            Err(RecvTimeoutError::Timeout)
+        ));
+
+        drop(sender);
+        t_conditioner.join().unwrap();
+    }
+}
@@ -8,6 +8,7 @@ use {
             },
         },
         quic::{configure_server, QuicServerError, QuicServerParams, StreamerStats},
+        socket_stats,
         streamer::StakedNodes,
     },
     async_channel::{bounded as async_bounded, Receiver as AsyncReceiver, Sender as AsyncSender},
@@ -207,7 +208,15 @@ pub fn spawn_server_multi(
             .map_err(QuicServerError::EndpointFailed)
         })
         .collect::<Result<Vec<_>, _>>()?;
-    let stats = Arc::<StreamerStats>::default();
+    // The sockets of a server are all bound to the same address, with SO_REUSEPORT
+    let socket_stats = endpoints
+        .first()
+        .and_then(|endpoint| endpoint.local_addr().ok())
+        .map(|addr| socket_stats::register_socket(addr, name));
+    let stats = Arc::new(StreamerStats {
+        socket_stats,
+        ..StreamerStats::default()
+    });
     let handle = tokio::spawn(run_server(
         name,
         endpoints.clone(),
@@ -927,8 +936,16 @@ async fn packet_batch_sender(
                     stats
                         .total_packet_batch_send_err
                         .fetch_add(1, Ordering::Relaxed);
+                    stats.record_dropped_in_process(len);
                     trace!("Send error: {}", e);
                 } else {
+                    if let Some(socket_stats) = &stats.socket_stats {
+                        socket_stats.record_batch(
+                            len,
+                            batch_start_time.elapsed(),
+                            packet_sender.len(),
+                        );
+                    }
                     stats
                         .total_packet_batches_sent
                         .fetch_add(1, Ordering::Relaxed);
@@ -1141,6 +1158,7 @@ async fn handle_connection(
                     stats
                         .total_stream_read_errors
                         .fetch_add(1, Ordering::Relaxed);
+                    stats.record_dropped_in_process(1);
                     break;
                 }
                 // timeout elapsed
@@ -1149,6 +1167,7 @@ async fn handle_connection(
                     stats
                         .total_stream_read_timeouts
                         .fetch_add(1, Ordering::Relaxed);
+                    stats.record_dropped_in_process(1);
                     break;
                 }
             };
@@ -1231,6 +1250,7 @@ async fn handle_chunks(
             // never exceed this size. A peer can send two chunks that together exceed the size
             // tho, in which case we report the error.
             stats.invalid_stream_size.fetch_add(1, Ordering::Relaxed);
+            stats.record_dropped_in_process(1);
             debug!("invalid stream size {}", accum.meta.size);
             return Err(());
         }
@@ -1267,6 +1287,7 @@ async fn handle_chunks(
         stats
             .total_handle_chunk_to_packet_batcher_send_err
             .fetch_add(1, Ordering::Relaxed);
+        stats.record_dropped_in_process(1);
         trace!("packet batch send error {:?}", err);
     } else {
         stats
@@ -1756,6 +1777,12 @@ pub mod test {
         // Test that the stream was created, but timed out in read
         assert_eq!(stats.total_streams.load(Ordering::Relaxed), 0);
         assert_ne!(stats.total_stream_read_timeouts.load(Ordering::Relaxed), 0);
+        // The abandoned stream is accounted for in the statistics of the server's socket
+        let socket_stats = socket_stats::socket_receive_stats()
+            .into_iter()
+            .find(|socket_stats| socket_stats.addr == server_address)
+            .unwrap();
+        assert_ne!(socket_stats.packets_dropped_in_process, 0);
 
         // Test that more writes to the stream will fail (i.e. the stream is no longer writable
         // after the timeouts)
@@ -1,7 +1,7 @@
 //! The `packet` module defines data structures and methods to pull data from the network.
 use {
     crate::{
-        recvmmsg::{recv_mmsg, NUM_RCVMMSGS},
+        recvmmsg::{recv_mmsg, recv_mmsg_with_drop_count, NUM_RCVMMSGS},
         socket::SocketAddrSpace,
     },
     std::{
@@ -18,7 +18,30 @@ pub use {
 };
 
 pub fn recv_from(batch: &mut PacketBatch, socket: &UdpSocket, max_wait: Duration) -> Result<usize> {
+    recv_from_impl(batch, socket, max_wait, |socket, packets| {
+        recv_mmsg(socket, packets).map(|npkts| (npkts, None))
+    })
+    .map(|(npkts, _)| npkts)
+}
+
+/// Like [`recv_from`], also returning the last count of packets dropped by the kernel reported
+/// by [`recv_mmsg_with_drop_count`], if any
+pub fn recv_from_with_drop_count(
+    batch: &mut PacketBatch,
+    socket: &UdpSocket,
+    max_wait: Duration,
+) -> Result<(usize, Option<u32>)> {
+    recv_from_impl(batch, socket, max_wait, recv_mmsg_with_drop_count)
+}
+
+fn recv_from_impl(
+    batch: &mut PacketBatch,
+    socket: &UdpSocket,
+    max_wait: Duration,
+    recv_mmsg: impl Fn(&UdpSocket, &mut [Packet]) -> Result<(usize, Option<u32>)>,
+) -> Result<(usize, Option<u32>)> {
     let mut i = 0;
+    let mut drop_count = None;
     //DOCUMENTED SIDE-EFFECT
     //Performance out of the IO without poll
     //  * block on the socket until it's readable
@@ -43,7 +66,8 @@ pub fn recv_from(batch: &mut PacketBatch, socket: &UdpSocket, max_wait: Duration
                 trace!("recv_from err {:?}", e);
                 return Err(e);
             }
-            Ok(npkts) => {
+            Ok((npkts, new_drop_count)) => {
+                drop_count = new_drop_count.or(drop_count);
                 if i == 0 {
                     socket.set_nonblocking(true)?;
                 }
@@ -58,7 +82,7 @@ pub fn recv_from(batch: &mut PacketBatch, socket: &UdpSocket, max_wait: Duration
         }
     }
     batch.truncate(i);
-    Ok(i)
+    Ok((i, drop_count))
 }
 
 pub fn send_to(
@@ -1,6 +1,7 @@
 use {
     crate::{
         nonblocking::quic::{ALPN_TPU_PROTOCOL_ID, DEFAULT_WAIT_FOR_CHUNK_TIMEOUT},
+        socket_stats::SocketReceiveStats,
         streamer::StakedNodes,
     },
     crossbeam_channel::Sender,
@@ -211,9 +212,18 @@ pub struct StreamerStats {
     pub(crate) outstanding_incoming_connection_attempts: AtomicUsize,
     pub(crate) total_incoming_connection_attempts: AtomicUsize,
     pub(crate) quic_endpoints_count: AtomicUsize,
+    // Receive statistics of the address the server listens on, kept for the lifetime of the process
+    pub(crate) socket_stats: Option<Arc<SocketReceiveStats>>,
 }
 
 impl StreamerStats {
+    /// Accounts for `num_packets` packets sent by peers which are not handed over to the consumer
+    pub(crate) fn record_dropped_in_process(&self, num_packets: usize) {
+        if let Some(socket_stats) = &self.socket_stats {
+            socket_stats.record_dropped_in_process(num_packets);
+        }
+    }
+
     pub fn report(&self, name: &'static str) {
         let process_sampled_packets_us_hist = {
             let mut metrics = self.process_sampled_packets_us_hist.lock().unwrap();
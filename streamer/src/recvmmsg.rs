@@ -10,6 +10,7 @@ use {
         mem::{self, MaybeUninit},
         net::{Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6},
         os::unix::io::AsRawFd,
+        ptr,
     },
 };
 use {
@@ -17,6 +18,44 @@ use {
     std::{cmp, io, net::UdpSocket},
 };
 
+// Room for a control message holding a u32, as needed by SO_RXQ_OVFL, in units of u64 to keep
+// the control messages aligned
+#[cfg(target_os = "linux")]
+const CONTROL_LEN: usize = 4;
+
+/// Has the kernel report, along with packets received on `sock`, how many packets it dropped
+/// because the socket's receive buffer was full
+#[cfg(target_os = "linux")]
+pub fn enable_drop_count(sock: &UdpSocket) -> io::Result<()> {
+    let enable: libc::c_int = 1;
+    let ret = unsafe {
+        libc::setsockopt(
+            sock.as_raw_fd(),
+            libc::SOL_SOCKET,
+            libc::SO_RXQ_OVFL,
+            &enable as *const _ as *const libc::c_void,
+            mem::size_of_val(&enable) as socklen_t,
+        )
+    };
+    if ret < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn enable_drop_count(_sock: &UdpSocket) -> io::Result<()> {
+    Err(io::Error::from(io::ErrorKind::Unsupported))
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn recv_mmsg_with_drop_count(
+    socket: &UdpSocket,
+    packets: &mut [Packet],
+) -> io::Result<(usize, Option<u32>)> {
+    recv_mmsg(socket, packets).map(|nrecv| (nrecv, None))
+}
+
 #[cfg(not(target_os = "linux"))]
 pub fn recv_mmsg(socket: &UdpSocket, packets: &mut [Packet]) -> io::Result</*num packets:*/ usize> {
     debug_assert!(packets.iter().all(|pkt| pkt.meta() == &Meta::default()));
@@ -78,8 +117,47 @@ fn cast_socket_addr(addr: &sockaddr_storage, hdr: &mmsghdr) -> Option<SocketAddr
     None
 }
 
+/// Returns the SO_RXQ_OVFL drop count carried by the control messages of `hdr`, if any
+#[cfg(target_os = "linux")]
+fn cast_drop_count(hdr: &mmsghdr) -> Option<u32> {
+    // SAFETY: msg_control points to the control buffer given to recvmmsg(), and msg_controllen
+    // was updated by recvmmsg() to the length of the control messages it wrote
+    let mut cmsg = unsafe { libc::CMSG_FIRSTHDR(&hdr.msg_hdr) };
+    while !cmsg.is_null() {
+        let cmsg_ref = unsafe { &*cmsg };
+        if cmsg_ref.cmsg_level == libc::SOL_SOCKET && cmsg_ref.cmsg_type == libc::SO_RXQ_OVFL {
+            return Some(unsafe { ptr::read_unaligned(libc::CMSG_DATA(cmsg) as *const u32) });
+        }
+        cmsg = unsafe { libc::CMSG_NXTHDR(&hdr.msg_hdr, cmsg) };
+    }
+    None
+}
+
 #[cfg(target_os = "linux")]
 pub fn recv_mmsg(sock: &UdpSocket, packets: &mut [Packet]) -> io::Result</*num packets:*/ usize> {
+    recv_mmsg_with_controls(sock, packets, None)
+}
+
+/// Like [`recv_mmsg`], also returning the number of packets the kernel dropped on `sock` since
+/// it was created, as of the last packet received. The count is only reported once enabled with
+/// [`enable_drop_count`] and non zero, and wraps around at `u32::MAX`.
+#[cfg(target_os = "linux")]
+pub fn recv_mmsg_with_drop_count(
+    sock: &UdpSocket,
+    packets: &mut [Packet],
+) -> io::Result<(usize, Option<u32>)> {
+    let mut controls = [[0u64; CONTROL_LEN]; NUM_RCVMMSGS];
+    let mut drop_count = None;
+    let nrecv = recv_mmsg_with_controls(sock, packets, Some((&mut controls, &mut drop_count)))?;
+    Ok((nrecv, drop_count))
+}
+
+#[cfg(target_os = "linux")]
+fn recv_mmsg_with_controls(
+    sock: &UdpSocket,
+    packets: &mut [Packet],
+    mut controls: Option<(&mut [[u64; CONTROL_LEN]; NUM_RCVMMSGS], &mut Option<u32>)>,
+) -> io::Result</*num packets:*/ usize> {
     // Should never hit this, but bail if the caller didn't provide any Packets
     // to receive into
     if packets.is_empty() {
@@ -96,8 +174,10 @@ pub fn recv_mmsg(sock: &UdpSocket, packets: &mut [Packet]) -> io::Result</*num p
     let sock_fd = sock.as_raw_fd();
     let count = cmp::min(iovs.len(), packets.len());
 
-    for (packet, hdr, iov, addr) in
-        izip!(packets.iter_mut(), &mut hdrs, &mut iovs, &mut addrs).take(count)
+    for (index, (packet, hdr, iov, addr)) in
+        izip!(packets.iter_mut(), &mut hdrs, &mut iovs, &mut addrs)
+            .take(count)
+            .enumerate()
     {
         let buffer = packet.buffer_mut();
         iov.write(iovec {
@@ -105,7 +185,12 @@ pub fn recv_mmsg(sock: &UdpSocket, packets: &mut [Packet]) -> io::Result</*num p
             iov_len: buffer.len(),
         });
 
-        let msg_hdr = create_msghdr(addr, SOCKADDR_STORAGE_SIZE, iov);
+        let mut msg_hdr = create_msghdr(addr, SOCKADDR_STORAGE_SIZE, iov);
+        if let Some((controls, _)) = controls.as_mut() {
+            let control = &mut controls[index];
+            msg_hdr.msg_control = control.as_mut_ptr() as *mut libc::c_void;
+            msg_hdr.msg_controllen = mem::size_of_val(control) as _;
+        }
 
         hdr.write(mmsghdr {
             msg_len: 0,
@@ -146,6 +231,11 @@ pub fn recv_mmsg(sock: &UdpSocket, packets: &mut [Packet]) -> io::Result</*num p
         if let Some(addr) = cast_socket_addr(addr_ref, hdr_ref) {
             pkt.meta_mut().set_socket_addr(&addr);
         }
+        if let Some((_, drop_count)) = controls.as_mut() {
+            if let Some(count) = cast_drop_count(hdr_ref) {
+                **drop_count = Some(count);
+            }
+        }
     }
 
     for (iov, addr, hdr) in izip!(&mut iovs, &mut addrs, &mut hdrs).take(count) {
@@ -330,4 +420,43 @@ mod tests {
             assert_eq!(packet.meta().socket_addr(), saddr2);
         }
     }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    pub fn test_recv_mmsg_with_drop_count() {
+        let reader = bind_to_localhost().expect("bind");
+        let addr = reader.local_addr().unwrap();
+        // Leave room for only a few packets in the receive buffer
+        socket2::SockRef::from(&reader)
+            .set_recv_buffer_size(4096)
+            .unwrap();
+        let sender = bind_to_localhost().expect("bind");
+
+        let mut packets = vec![Packet::default(); TEST_NUM_MSGS];
+        sender.send_to(&[0; PACKET_DATA_SIZE], addr).unwrap();
+        // The drop count is only reported once enabled
+        assert_eq!(
+            recv_mmsg_with_drop_count(&reader, &mut packets[..]).unwrap(),
+            (1, None)
+        );
+
+        enable_drop_count(&reader).unwrap();
+        for _ in 0..TEST_NUM_MSGS {
+            sender.send_to(&[0; PACKET_DATA_SIZE], addr).unwrap();
+        }
+        packets
+            .iter_mut()
+            .for_each(|pkt| *pkt.meta_mut() = Meta::default());
+        let (recv, _) = recv_mmsg_with_drop_count(&reader, &mut packets[..]).unwrap();
+        assert!(recv < TEST_NUM_MSGS);
+
+        // Packets carry the count of drops which happened before they were queued
+        sender.send_to(&[0; PACKET_DATA_SIZE], addr).unwrap();
+        packets
+            .iter_mut()
+            .for_each(|pkt| *pkt.meta_mut() = Meta::default());
+        let (new_recv, drop_count) = recv_mmsg_with_drop_count(&reader, &mut packets[..]).unwrap();
+        assert_eq!(new_recv, 1);
+        assert_eq!(recv + drop_count.unwrap() as usize, TEST_NUM_MSGS);
+    }
 }
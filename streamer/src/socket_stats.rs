@@ -0,0 +1,191 @@
+//! Receive statistics of the UDP sockets read by [`crate::streamer::receiver`] and by the QUIC
+//! servers.
+//!
+//! Unlike [`crate::streamer::StreamerReceiveStats`], which are reset each time they are reported
+//! as metrics, these statistics accumulate for the lifetime of the process, and are kept per
+//! local socket address, so that operators can tell whether packets were dropped by the OS
+//! because a socket's receive buffer was full or by the validator itself. Sockets bound to the
+//! same address with `SO_REUSEPORT` share the statistics of that address.
+
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        Arc, LazyLock, RwLock,
+    },
+    time::Duration,
+};
+
+static SOCKET_RECEIVE_STATS: LazyLock<RwLock<HashMap<SocketAddr, Arc<SocketReceiveStats>>>> =
+    LazyLock::new(RwLock::default);
+
+#[derive(Debug, Default)]
+pub struct SocketReceiveStats {
+    name: &'static str,
+    packets_received: AtomicU64,
+    packet_batches_received: AtomicU64,
+    // Whether the OS reports the packets it drops for any of the sockets bound to the address
+    os_drop_count_enabled: AtomicBool,
+    packets_dropped_by_os: AtomicU64,
+    packets_dropped_in_process: AtomicU64,
+    processing_time_us: AtomicU64,
+    max_processing_time_us: AtomicU64,
+    channel_len: AtomicUsize,
+}
+
+/// The statistics of a socket address at some point in time
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SocketReceiveStatsSnapshot {
+    pub addr: SocketAddr,
+    /// Name of the receiver reading from the socket
+    pub name: &'static str,
+    pub packets_received: u64,
+    pub packet_batches_received: u64,
+    /// Packets dropped by the OS because the receive buffer was full, if the OS reports them.
+    /// Drops are only accounted for once the next packet is received.
+    pub packets_dropped_by_os: Option<u64>,
+    /// Packets read from the socket but not handed over to the receiver's consumer. For QUIC
+    /// servers, these are the streams which were abandoned or could not be handed over.
+    pub packets_dropped_in_process: u64,
+    /// Mean time between a packet batch being read and it being handed over
+    pub mean_processing_time_us: u64,
+    pub max_processing_time_us: u64,
+    /// Packet batches waiting to be processed by the receiver's consumer, as of the last batch
+    pub channel_len: usize,
+}
+
+impl SocketReceiveStats {
+    pub(crate) fn record_os_drop_count_enabled(&self) {
+        self.os_drop_count_enabled.store(true, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_batch(
+        &self,
+        num_packets: usize,
+        processing_time: Duration,
+        channel_len: usize,
+    ) {
+        let processing_time_us = processing_time.as_micros() as u64;
+        self.packets_received
+            .fetch_add(num_packets as u64, Ordering::Relaxed);
+        self.packet_batches_received.fetch_add(1, Ordering::Relaxed);
+        self.processing_time_us
+            .fetch_add(processing_time_us, Ordering::Relaxed);
+        self.max_processing_time_us
+            .fetch_max(processing_time_us, Ordering::Relaxed);
+        self.channel_len.store(channel_len, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_dropped_by_os(&self, num_packets: u64) {
+        self.packets_dropped_by_os
+            .fetch_add(num_packets, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_dropped_in_process(&self, num_packets: usize) {
+        self.packets_dropped_in_process
+            .fetch_add(num_packets as u64, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self, addr: SocketAddr) -> SocketReceiveStatsSnapshot {
+        let packet_batches_received = self.packet_batches_received.load(Ordering::Relaxed);
+        SocketReceiveStatsSnapshot {
+            addr,
+            name: self.name,
+            packets_received: self.packets_received.load(Ordering::Relaxed),
+            packet_batches_received,
+            packets_dropped_by_os: self
+                .os_drop_count_enabled
+                .load(Ordering::Relaxed)
+                .then(|| self.packets_dropped_by_os.load(Ordering::Relaxed)),
+            packets_dropped_in_process: self.packets_dropped_in_process.load(Ordering::Relaxed),
+            mean_processing_time_us: self
+                .processing_time_us
+                .load(Ordering::Relaxed)
+                .checked_div(packet_batches_received)
+                .unwrap_or_default(),
+            max_processing_time_us: self.max_processing_time_us.load(Ordering::Relaxed),
+            channel_len: self.channel_len.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Returns the statistics of the socket bound to `addr`, registering them under `name` if the
+/// socket was not read from before
+pub(crate) fn register_socket(addr: SocketAddr, name: &'static str) -> Arc<SocketReceiveStats> {
+    SOCKET_RECEIVE_STATS
+        .write()
+        .unwrap()
+        .entry(addr)
+        .or_insert_with(|| {
+            Arc::new(SocketReceiveStats {
+                name,
+                ..SocketReceiveStats::default()
+            })
+        })
+        .clone()
+}
+
+/// Returns the statistics of all the sockets read from so far, sorted by address
+pub fn socket_receive_stats() -> Vec<SocketReceiveStatsSnapshot> {
+    let mut snapshots: Vec<_> = SOCKET_RECEIVE_STATS
+        .read()
+        .unwrap()
+        .iter()
+        .map(|(addr, stats)| stats.snapshot(*addr))
+        .collect();
+    snapshots.sort_unstable_by_key(|snapshot| snapshot.addr);
+    snapshots
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_socket_receive_stats() {
+        let addr = SocketAddr::from(([127, 0, 0, 1], 1));
+        let stats = register_socket(addr, "test");
+        assert!(Arc::ptr_eq(&stats, &register_socket(addr, "other")));
+        let snapshot = || {
+            socket_receive_stats()
+                .into_iter()
+                .find(|snapshot| snapshot.addr == addr)
+                .unwrap()
+        };
+        assert_eq!(
+            snapshot(),
+            SocketReceiveStatsSnapshot {
+                addr,
+                name: "test",
+                packets_received: 0,
+                packet_batches_received: 0,
+                packets_dropped_by_os: None,
+                packets_dropped_in_process: 0,
+                mean_processing_time_us: 0,
+                max_processing_time_us: 0,
+                channel_len: 0,
+            }
+        );
+
+        stats.record_os_drop_count_enabled();
+        stats.record_batch(10, Duration::from_micros(100), 2);
+        stats.record_batch(20, Duration::from_micros(300), 1);
+        stats.record_dropped_by_os(5);
+        stats.record_dropped_in_process(20);
+        assert_eq!(
+            snapshot(),
+            SocketReceiveStatsSnapshot {
+                addr,
+                name: "test",
+                packets_received: 30,
+                packet_batches_received: 2,
+                packets_dropped_by_os: Some(5),
+                packets_dropped_in_process: 20,
+                mean_processing_time_us: 200,
+                max_processing_time_us: 300,
+                channel_len: 1,
+            }
+        );
+    }
+}
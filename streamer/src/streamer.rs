@@ -4,8 +4,10 @@
 use {
     crate::{
         packet::{self, PacketBatch, PacketBatchRecycler, PACKETS_PER_BATCH},
+        recvmmsg::enable_drop_count,
         sendmmsg::{batch_send, SendPktsError},
         socket::SocketAddrSpace,
+        socket_stats::{self, SocketReceiveStats},
     },
     crossbeam_channel::{Receiver, RecvTimeoutError, SendError, Sender},
     histogram::Histogram,
@@ -109,11 +111,14 @@ fn recv_loop(
     packet_batch_sender: &PacketBatchSender,
     recycler: &PacketBatchRecycler,
     stats: &StreamerReceiveStats,
+    socket_stats: Option<&SocketReceiveStats>,
     coalesce: Duration,
     use_pinned_memory: bool,
     in_vote_only_mode: Option<Arc<AtomicBool>>,
     is_staked_service: bool,
 ) -> Result<()> {
+    // Packets dropped by the OS since the socket was created, as last reported
+    let mut os_drop_count = 0u32;
     loop {
         let mut packet_batch = if use_pinned_memory {
             PacketBatch::new_with_recycler(recycler, PACKETS_PER_BATCH, stats.name)
@@ -134,7 +139,18 @@ fn recv_loop(
                 }
             }
 
-            if let Ok(len) = packet::recv_from(&mut packet_batch, socket, coalesce) {
+            if let Ok((len, new_os_drop_count)) =
+                packet::recv_from_with_drop_count(&mut packet_batch, socket, coalesce)
+            {
+                let received = Instant::now();
+                if let (Some(socket_stats), Some(new_os_drop_count)) =
+                    (socket_stats, new_os_drop_count)
+                {
+                    socket_stats.record_dropped_by_os(u64::from(
+                        new_os_drop_count.wrapping_sub(os_drop_count),
+                    ));
+                    os_drop_count = new_os_drop_count;
+                }
                 if len > 0 {
                     let StreamerReceiveStats {
                         packets_count,
@@ -153,7 +169,19 @@ fn recv_loop(
                     packet_batch
                         .iter_mut()
                         .for_each(|p| p.meta_mut().set_from_staked_node(is_staked_service));
-                    packet_batch_sender.send(packet_batch)?;
+                    if let Err(err) = packet_batch_sender.send(packet_batch) {
+                        if let Some(socket_stats) = socket_stats {
+                            socket_stats.record_dropped_in_process(len);
+                        }
+                        return Err(err.into());
+                    }
+                    if let Some(socket_stats) = socket_stats {
+                        socket_stats.record_batch(
+                            len,
+                            received.elapsed(),
+                            packet_batch_sender.len(),
+                        );
+                    }
                 }
                 break;
             }
@@ -176,6 +204,32 @@ pub fn receiver(
 ) -> JoinHandle<()> {
     let res = socket.set_read_timeout(Some(Duration::new(1, 0)));
     assert!(res.is_ok(), "streamer::receiver set_read_timeout error");
+    let socket_stats = socket.local_addr().ok().map(|addr| {
+        let socket_stats = socket_stats::register_socket(addr, stats.name);
+        if enable_drop_count(&socket).is_ok() {
+            socket_stats.record_os_drop_count_enabled();
+        }
+        socket_stats
+    });
+    // Simulated network conditions, if any, are applied on their own thread
+    #[cfg(feature = "dev-context-only-utils")]
+    let (packet_batch_sender, t_conditioner) = match socket
+        .local_addr()
+        .ok()
+        .and_then(|addr| crate::network_conditions::socket_conditioner(&addr))
+    {
+        Some(conditioner) => {
+            let (sender, receiver) = crossbeam_channel::unbounded();
+            let t_conditioner = crate::network_conditions::spawn_conditioner(
+                "solNetCondition".to_string(),
+                conditioner,
+                receiver,
+                packet_batch_sender,
+            );
+            (sender, Some(t_conditioner))
+        }
+        None => (packet_batch_sender, None),
+    };
     Builder::new()
         .name(thread_name)
         .spawn(move || {
@@ -185,11 +239,17 @@ pub fn receiver(
                 &packet_batch_sender,
                 &recycler,
                 &stats,
+                socket_stats.as_deref(),
                 coalesce,
                 use_pinned_memory,
                 in_vote_only_mode,
                 is_staked_service,
             );
+            drop(packet_batch_sender);
+            #[cfg(feature = "dev-context-only-utils")]
+            if let Some(t_conditioner) = t_conditioner {
+                t_conditioner.join().unwrap();
+            }
         })
         .unwrap()
 }
@@ -9,6 +9,7 @@ use {
     solana_sdk_ids::{ed25519_program, secp256k1_program, secp256r1_program, system_program},
 };
 
+mod reference;
 mod sanitized_message;
 mod sanitized_transaction;
 // inlined to avoid solana-nonce dep
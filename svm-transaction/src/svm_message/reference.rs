@@ -0,0 +1,88 @@
+use {
+    crate::{
+        instruction::SVMInstruction, message_address_table_lookup::SVMMessageAddressTableLookup,
+        svm_message::SVMMessage,
+    },
+    solana_hash::Hash,
+    solana_message::AccountKeys,
+    solana_pubkey::Pubkey,
+};
+
+// Allows processing a subset of a batch of transactions without cloning them
+impl<T: SVMMessage> SVMMessage for &T {
+    fn num_transaction_signatures(&self) -> u64 {
+        T::num_transaction_signatures(self)
+    }
+
+    fn num_ed25519_signatures(&self) -> u64 {
+        T::num_ed25519_signatures(self)
+    }
+
+    fn num_secp256k1_signatures(&self) -> u64 {
+        T::num_secp256k1_signatures(self)
+    }
+
+    fn num_secp256r1_signatures(&self) -> u64 {
+        T::num_secp256r1_signatures(self)
+    }
+
+    fn num_write_locks(&self) -> u64 {
+        T::num_write_locks(self)
+    }
+
+    fn recent_blockhash(&self) -> &Hash {
+        T::recent_blockhash(self)
+    }
+
+    fn num_instructions(&self) -> usize {
+        T::num_instructions(self)
+    }
+
+    fn instructions_iter(&self) -> impl Iterator<Item = SVMInstruction> {
+        T::instructions_iter(self)
+    }
+
+    fn program_instructions_iter(&self) -> impl Iterator<Item = (&Pubkey, SVMInstruction)> + Clone {
+        T::program_instructions_iter(self)
+    }
+
+    fn account_keys(&self) -> AccountKeys {
+        T::account_keys(self)
+    }
+
+    fn fee_payer(&self) -> &Pubkey {
+        T::fee_payer(self)
+    }
+
+    fn is_writable(&self, index: usize) -> bool {
+        T::is_writable(self, index)
+    }
+
+    fn is_signer(&self, index: usize) -> bool {
+        T::is_signer(self, index)
+    }
+
+    fn is_invoked(&self, key_index: usize) -> bool {
+        T::is_invoked(self, key_index)
+    }
+
+    fn is_instruction_account(&self, key_index: usize) -> bool {
+        T::is_instruction_account(self, key_index)
+    }
+
+    fn get_durable_nonce(&self) -> Option<&Pubkey> {
+        T::get_durable_nonce(self)
+    }
+
+    fn get_ix_signers(&self, index: usize) -> impl Iterator<Item = &Pubkey> {
+        T::get_ix_signers(self, index)
+    }
+
+    fn num_lookup_tables(&self) -> usize {
+        T::num_lookup_tables(self)
+    }
+
+    fn message_address_table_lookups(&self) -> impl Iterator<Item = SVMMessageAddressTableLookup> {
+        T::message_address_table_lookups(self)
+    }
+}
@@ -1,5 +1,6 @@
 use {crate::svm_message::SVMMessage, solana_signature::Signature};
 
+mod reference;
 mod sanitized_transaction;
 
 pub trait SVMTransaction: SVMMessage {
@@ -0,0 +1,11 @@
+use {crate::svm_transaction::SVMTransaction, solana_signature::Signature};
+
+impl<T: SVMTransaction> SVMTransaction for &T {
+    fn signature(&self) -> &Signature {
+        T::signature(self)
+    }
+
+    fn signatures(&self) -> &[Signature] {
+        T::signatures(self)
+    }
+}
@@ -329,11 +329,7 @@ impl JsonRpcRequestProcessor {
                 compute_budget: Some(ComputeBudget::default()),
                 log_messages_bytes_limit: None,
                 limit_to_load_programs: true,
-                recording_config: ExecutionRecordingConfig {
-                    enable_cpi_recording,
-                    enable_log_recording: true,
-                    enable_return_data_recording: true,
-                },
+                recording_config: ExecutionRecordingConfig::new(enable_cpi_recording, true, true),
                 transaction_account_lock_limit: Some(64),
             },
         );
@@ -667,6 +663,8 @@ pub mod rpc {
                 accounts: config_accounts,
                 min_context_slot: _,
                 inner_instructions: enable_cpi_recording,
+                memory_usage: _,
+                account_privileges: _,
             } = config.unwrap_or_default();
             let tx_encoding = encoding.unwrap_or(UiTransactionEncoding::Base58);
             let binary_encoding = tx_encoding.into_binary_encoding().ok_or_else(|| {
@@ -757,6 +755,8 @@ pub mod rpc {
                     return_data: return_data.map(|return_data| return_data.into()),
                     inner_instructions,
                     replacement_blockhash: None,
+                    memory_usage: None,
+                    account_privileges: None,
                 },
             ))
         }
@@ -24,10 +24,7 @@ use {
     solana_rent::RentDue,
     solana_rent_debits::RentDebits,
     solana_sdk::rent_collector::{CollectedInfo, RENT_EXEMPT_RENT_EPOCH},
-    solana_sdk_ids::{
-        native_loader,
-        sysvar::{self, slot_history},
-    },
+    solana_sdk_ids::{native_loader, sysvar},
     solana_svm_rent_collector::svm_rent_collector::SVMRentCollector,
     solana_svm_transaction::svm_message::SVMMessage,
     solana_transaction_context::{IndexOfAccount, TransactionAccount},
@@ -153,12 +150,14 @@ impl<'a, CB: TransactionProcessingCallback> AccountLoader<'a, CB> {
     ) -> AccountLoader<'a, CB> {
         let mut account_cache = AHashMap::with_capacity(capacity);
 
-        // SlotHistory may be overridden for simulation.
-        // No other uses of AccountOverrides are expected.
-        if let Some(slot_history) =
-            account_overrides.and_then(|overrides| overrides.get(&slot_history::id()))
-        {
-            account_cache.insert(slot_history::id(), slot_history.clone());
+        // Overridden accounts, such as the SlotHistory for simulation, are
+        // loaded in place of the stored ones
+        if let Some(account_overrides) = account_overrides {
+            account_cache.extend(
+                account_overrides
+                    .iter()
+                    .map(|(pubkey, account)| (*pubkey, account.clone())),
+            );
         }
 
         Self {
@@ -1097,14 +1096,18 @@ mod tests {
         let slot_history_id = sysvar::slot_history::id();
         let account = AccountSharedData::new(42, 0, &Pubkey::default());
         account_overrides.set_slot_history(Some(account));
+        // Accounts missing from the store can be overridden too
+        let other_id = Pubkey::new_unique();
+        let account = AccountSharedData::new(7, 0, &Pubkey::default());
+        account_overrides.set_account(&other_id, Some(account));
 
         let keypair = Keypair::new();
         let account = AccountSharedData::new(1_000_000, 0, &Pubkey::default());
 
-        let instructions = vec![CompiledInstruction::new(2, &(), vec![0])];
+        let instructions = vec![CompiledInstruction::new(3, &(), vec![0])];
         let tx = Transaction::new_with_compiled_instructions(
             &[&keypair],
-            &[slot_history_id],
+            &[slot_history_id, other_id],
             Hash::default(),
             vec![native_loader::id()],
             instructions,
@@ -1117,6 +1120,8 @@ mod tests {
                 assert_eq!(loaded_transaction.accounts[0].0, keypair.pubkey());
                 assert_eq!(loaded_transaction.accounts[1].0, slot_history_id);
                 assert_eq!(loaded_transaction.accounts[1].1.lamports(), 42);
+                assert_eq!(loaded_transaction.accounts[2].0, other_id);
+                assert_eq!(loaded_transaction.accounts[2].1.lamports(), 7);
             }
             TransactionLoadResult::FeesOnly(fees_only_tx) => panic!("{}", fees_only_tx.load_error),
             TransactionLoadResult::NotLoaded(e) => panic!("{e}"),
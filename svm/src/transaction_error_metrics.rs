@@ -19,6 +19,7 @@ pub struct TransactionErrorMetrics {
     pub invalid_compute_budget: Saturating<usize>,
     pub not_allowed_during_cluster_maintenance: Saturating<usize>,
     pub invalid_writable_account: Saturating<usize>,
+    pub write_fenced_account: Saturating<usize>,
     pub invalid_rent_paying_account: Saturating<usize>,
     pub would_exceed_max_block_cost_limit: Saturating<usize>,
     pub would_exceed_max_account_cost_limit: Saturating<usize>,
@@ -51,6 +52,7 @@ impl TransactionErrorMetrics {
         self.invalid_compute_budget += other.invalid_compute_budget;
         self.not_allowed_during_cluster_maintenance += other.not_allowed_during_cluster_maintenance;
         self.invalid_writable_account += other.invalid_writable_account;
+        self.write_fenced_account += other.write_fenced_account;
         self.invalid_rent_paying_account += other.invalid_rent_paying_account;
         self.would_exceed_max_block_cost_limit += other.would_exceed_max_block_cost_limit;
         self.would_exceed_max_account_cost_limit += other.would_exceed_max_account_cost_limit;
@@ -6,7 +6,10 @@
 pub use solana_sdk::inner_instruction::{InnerInstruction, InnerInstructionsList};
 use {
     crate::account_loader::LoadedTransaction,
-    solana_program_runtime::loaded_programs::ProgramCacheEntry,
+    solana_program_runtime::{
+        invoke_context::{InstructionAccountPrivileges, ProgramMemoryUsage},
+        loaded_programs::ProgramCacheEntry,
+    },
     solana_pubkey::Pubkey,
     solana_transaction_context::TransactionReturnData,
     solana_transaction_error::TransactionResult,
@@ -42,6 +45,10 @@ pub struct TransactionExecutionDetails {
     /// The change in accounts data len for this transaction.
     /// NOTE: This value is valid IFF `status` is `Ok`.
     pub accounts_data_len_delta: i64,
+    /// The memory used by each SBF program invocation, if recorded
+    pub memory_usage: Option<Vec<ProgramMemoryUsage>>,
+    /// The account privileges of each instruction, if recorded
+    pub account_privileges: Option<Vec<InstructionAccountPrivileges>>,
 }
 
 impl TransactionExecutionDetails {
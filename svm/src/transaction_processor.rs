@@ -86,18 +86,44 @@ pub struct LoadAndExecuteSanitizedTransactionsOutput {
 
 /// Configuration of the recording capabilities for transaction execution
 #[derive(Copy, Clone, Default)]
+#[non_exhaustive]
 pub struct ExecutionRecordingConfig {
     pub enable_cpi_recording: bool,
     pub enable_log_recording: bool,
     pub enable_return_data_recording: bool,
+    /// Record the stack and heap memory used by each SBF program invocation
+    pub enable_memory_usage_recording: bool,
+    /// Record the account privileges of each instruction and whether it
+    /// modified its writable accounts
+    pub enable_account_privileges_recording: bool,
 }
 
 impl ExecutionRecordingConfig {
+    /// Memory usage and account privileges recording are left disabled, to be
+    /// enabled on the returned config where needed
+    pub fn new(
+        enable_cpi_recording: bool,
+        enable_log_recording: bool,
+        enable_return_data_recording: bool,
+    ) -> Self {
+        ExecutionRecordingConfig {
+            enable_cpi_recording,
+            enable_log_recording,
+            enable_return_data_recording,
+            enable_memory_usage_recording: false,
+            enable_account_privileges_recording: false,
+        }
+    }
+
+    /// Memory usage and account privileges recording are left disabled, as
+    /// they inspect the memory and accounts of every invocation
     pub fn new_single_setting(option: bool) -> Self {
         ExecutionRecordingConfig {
             enable_return_data_recording: option,
             enable_log_recording: option,
             enable_cpi_recording: option,
+            enable_memory_usage_recording: false,
+            enable_account_privileges_recording: false,
         }
     }
 }
@@ -818,6 +844,34 @@ impl<FG: ForkGraph> TransactionBatchProcessor<FG> {
         loaded_programs_for_txs.unwrap()
     }
 
+    /// Loads, verifies and compiles the given programs into the global
+    /// program cache if they are not already present, so that the first
+    /// transactions invoking them do not have to wait for compilation.
+    /// Program ids which are not owned by one of the loaders are ignored.
+    pub fn prewarm_program_cache<CB: TransactionProcessingCallback>(
+        &self,
+        callbacks: &CB,
+        program_ids: &[Pubkey],
+        execute_timings: &mut ExecuteTimings,
+        check_program_modification_slot: bool,
+    ) {
+        let program_accounts_map: HashMap<Pubkey, (&Pubkey, u64)> = program_ids
+            .iter()
+            .filter_map(|program_id| {
+                let index = callbacks.account_matches_owners(program_id, PROGRAM_OWNERS)?;
+                // Usage is only accounted for once the programs are invoked.
+                Some((*program_id, (PROGRAM_OWNERS.get(index)?, 0)))
+            })
+            .collect();
+        self.replenish_program_cache(
+            callbacks,
+            &program_accounts_map,
+            execute_timings,
+            check_program_modification_slot,
+            false, // limit_to_load_programs
+        );
+    }
+
     pub fn prepare_program_cache_for_upcoming_feature_set<CB: TransactionProcessingCallback>(
         &self,
         callbacks: &CB,
@@ -992,6 +1046,12 @@ impl<FG: ForkGraph> TransactionBatchProcessor<FG> {
             log_collector.clone(),
             compute_budget,
         );
+        if config.recording_config.enable_memory_usage_recording {
+            invoke_context.enable_memory_usage_recording();
+        }
+        if config.recording_config.enable_account_privileges_recording {
+            invoke_context.enable_account_privileges_recording();
+        }
 
         let mut process_message_time = Measure::start("process_message_time");
         let process_result = process_message(
@@ -1003,6 +1063,8 @@ impl<FG: ForkGraph> TransactionBatchProcessor<FG> {
         );
         process_message_time.stop();
 
+        let memory_usage = invoke_context.take_memory_usage();
+        let account_privileges = invoke_context.take_account_privileges();
         drop(invoke_context);
 
         execute_timings.execute_accessories.process_message_us += process_message_time.as_us();
@@ -1086,6 +1148,8 @@ impl<FG: ForkGraph> TransactionBatchProcessor<FG> {
                 return_data,
                 executed_units,
                 accounts_data_len_delta,
+                memory_usage,
+                account_privileges,
             },
             loaded_transaction,
             programs_modified_by_tx: program_cache_for_tx_batch.drain_modified_entries(),
@@ -1156,6 +1220,28 @@ impl<FG: ForkGraph> TransactionBatchProcessor<FG> {
         });
     }
 
+    /// Reloads a sysvar from `callbacks` after its account was updated. A cache that has not
+    /// been filled yet is left alone, `fill_missing_sysvar_cache_entries` loads the sysvar along
+    /// with the others when it fills the cache.
+    pub fn refresh_sysvar_cache_entry<CB: TransactionProcessingCallback>(
+        &self,
+        callbacks: &CB,
+        sysvar_id: &Pubkey,
+    ) {
+        let mut sysvar_cache = self.sysvar_cache.write().unwrap();
+        if sysvar_cache.is_empty() {
+            return;
+        }
+        sysvar_cache.invalidate(sysvar_id);
+        sysvar_cache.fill_missing_entries(|pubkey, set_sysvar| {
+            if pubkey == sysvar_id {
+                if let Some(account) = callbacks.get_account_shared_data(pubkey) {
+                    set_sysvar(account.data());
+                }
+            }
+        });
+    }
+
     pub fn reset_sysvar_cache(&self) {
         let mut sysvar_cache = self.sysvar_cache.write().unwrap();
         sysvar_cache.reset();
@@ -1640,6 +1726,53 @@ mod tests {
         assert!(loaded_missing > 0);
     }
 
+    #[test]
+    fn test_prewarm_program_cache() {
+        let mock_bank = MockBankCallback::default();
+        let fork_graph = Arc::new(RwLock::new(TestForkGraph {}));
+        let batch_processor =
+            TransactionBatchProcessor::new(0, 0, Arc::downgrade(&fork_graph), None, None);
+        let program = Pubkey::new_unique();
+        let not_a_program = Pubkey::new_unique();
+        let missing = Pubkey::new_unique();
+
+        let mut account_data = AccountSharedData::default();
+        account_data.set_owner(bpf_loader::id());
+        mock_bank
+            .account_shared_data
+            .write()
+            .unwrap()
+            .insert(program, account_data);
+        let mut account_data = AccountSharedData::default();
+        account_data.set_owner(Pubkey::new_unique());
+        mock_bank
+            .account_shared_data
+            .write()
+            .unwrap()
+            .insert(not_a_program, account_data);
+
+        batch_processor.prewarm_program_cache(
+            &mock_bank,
+            &[program, not_a_program, missing],
+            &mut ExecuteTimings::default(),
+            false,
+        );
+
+        let entries = batch_processor
+            .program_cache
+            .read()
+            .unwrap()
+            .get_flattened_entries_for_tests();
+        assert_eq!(entries.len(), 1);
+        let (key, entry) = &entries[0];
+        assert_eq!(*key, program);
+        assert!(matches!(
+            entry.program,
+            ProgramCacheEntryType::FailedVerification(_)
+        ));
+        assert_eq!(entry.tx_usage_counter.load(Ordering::Relaxed), 0);
+    }
+
     #[test]
     fn test_filter_executable_program_accounts() {
         let mock_bank = MockBankCallback::default();
@@ -248,11 +248,7 @@ fn svm_concurrent() {
                 TRANSACTIONS_PER_THREAD
             ];
             let processing_config = TransactionProcessingConfig {
-                recording_config: ExecutionRecordingConfig {
-                    enable_log_recording: true,
-                    enable_return_data_recording: false,
-                    enable_cpi_recording: false,
-                },
+                recording_config: ExecutionRecordingConfig::new(false, true, false),
                 ..Default::default()
             };
             let check_tx_data = std::mem::take(&mut check_data[idx]);
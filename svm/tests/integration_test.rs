@@ -99,11 +99,7 @@ impl SvmTestEnvironment<'_> {
         register_builtins(&mock_bank, &batch_processor, test_entry.with_loader_v4);
 
         let processing_config = TransactionProcessingConfig {
-            recording_config: ExecutionRecordingConfig {
-                enable_log_recording: true,
-                enable_return_data_recording: true,
-                enable_cpi_recording: false,
-            },
+            recording_config: ExecutionRecordingConfig::new(false, true, true),
             ..Default::default()
         };
 
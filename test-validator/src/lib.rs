@@ -10,6 +10,7 @@ use {
     },
     solana_cli_output::CliAccount,
     solana_compute_budget::compute_budget::ComputeBudget,
+    solana_connection_cache::connection_cache::NewConnectionConfig,
     solana_core::{
         admin_rpc_post_init::AdminRpcRequestMetadataPostInit,
         consensus::tower_storage::TowerStorage,
@@ -30,6 +31,7 @@ use {
         create_new_tmp_ledger,
     },
     solana_net_utils::PortRange,
+    solana_quic_client::{QuicConfig, QuicConnectionManager, QuicPool},
     solana_rpc::{rpc::JsonRpcConfig, rpc_pubsub_service::PubSubConfig},
     solana_rpc_client::{nonblocking, rpc_client::RpcClient},
     solana_rpc_client_api::request::MAX_MULTIPLE_ACCOUNTS,
@@ -56,7 +58,9 @@ use {
         signature::{read_keypair_file, write_keypair_file, Keypair, Signer},
     },
     solana_streamer::socket::SocketAddrSpace,
-    solana_tpu_client::tpu_client::DEFAULT_TPU_ENABLE_UDP,
+    solana_tpu_client::tpu_client::{
+        TpuClient, TpuClientConfig, TpuSenderError, DEFAULT_TPU_ENABLE_UDP,
+    },
     std::{
         collections::{HashMap, HashSet},
         ffi::OsStr,
@@ -581,12 +585,21 @@ impl TestValidatorGenesis {
     pub fn add_program(&mut self, program_name: &str, program_id: Pubkey) -> &mut Self {
         let program_path = solana_program_test::find_file(&format!("{program_name}.so"))
             .unwrap_or_else(|| panic!("Unable to locate program {program_name}"));
+        self.add_program_with_path(program_id, program_path)
+    }
 
+    /// Add a SBF program to the test environment, loading the shared object from
+    /// `program_path`.
+    pub fn add_program_with_path(
+        &mut self,
+        program_id: Pubkey,
+        program_path: impl Into<PathBuf>,
+    ) -> &mut Self {
         self.upgradeable_programs.push(UpgradeableProgramInfo {
             program_id,
             loader: solana_sdk::bpf_loader_upgradeable::id(),
             upgrade_authority: Pubkey::default(),
-            program_path,
+            program_path: program_path.into(),
         });
         self
     }
@@ -1159,6 +1172,24 @@ impl TestValidator {
         )
     }
 
+    /// Return a QUIC TpuClient for the validator.
+    ///
+    /// Must not be called from within a current_thread tokio runtime.
+    pub fn get_tpu_client(
+        &self,
+    ) -> Result<TpuClient<QuicPool, QuicConnectionManager, QuicConfig>, TpuSenderError> {
+        let connection_config = QuicConfig::new().map_err(|err| {
+            TpuSenderError::Custom(format!("Failed to create QUIC config: {err}"))
+        })?;
+        TpuClient::new(
+            "test_validator_tpu_client",
+            Arc::new(self.get_rpc_client()),
+            &self.rpc_pubsub_url,
+            TpuClientConfig::default(),
+            QuicConnectionManager::new_with_connection_config(connection_config),
+        )
+    }
+
     pub fn join(mut self) {
         if let Some(validator) = self.validator.take() {
             validator.join();
@@ -1207,6 +1238,34 @@ mod test {
         rpc_client.get_health().expect("health");
     }
 
+    #[test]
+    fn get_tpu_client() {
+        let (test_validator, payer) = TestValidatorGenesis::default().start();
+        let rpc_client = test_validator.get_rpc_client();
+        let tpu_client = test_validator.get_tpu_client().expect("tpu client");
+
+        let recipient = Pubkey::new_unique();
+        let lamports = sol_to_lamports(1.0);
+        let recent_blockhash = rpc_client.get_latest_blockhash().unwrap();
+        let transaction = solana_sdk::system_transaction::transfer(
+            &payer,
+            &recipient,
+            lamports,
+            recent_blockhash,
+        );
+        assert!(tpu_client.send_transaction(&transaction));
+
+        let mut balance = 0;
+        for _ in 0..20 {
+            balance = rpc_client.get_balance(&recipient).unwrap();
+            if balance == lamports {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(DEFAULT_MS_PER_SLOT));
+        }
+        assert_eq!(balance, lamports);
+    }
+
     #[tokio::test]
     async fn nonblocking_get_health() {
         let (test_validator, _payer) = TestValidatorGenesis::default().start_async().await;
@@ -336,6 +336,20 @@ impl From<TransactionReturnData> for UiTransactionReturnData {
     }
 }
 
+impl UiTransactionReturnData {
+    /// Decodes the program id and data, such as those returned by a
+    /// simulation, for the data to be deserialized into the type the program
+    /// returned
+    pub fn decode(&self) -> Option<TransactionReturnData> {
+        let program_id = self.program_id.parse().ok()?;
+        let (data, encoding) = &self.data;
+        let data = match encoding {
+            UiReturnDataEncoding::Base64 => BASE64_STANDARD.decode(data).ok()?,
+        };
+        Some(TransactionReturnData { program_id, data })
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, Hash, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub enum UiReturnDataEncoding {
@@ -441,6 +455,10 @@ pub struct UiCompiledInstruction {
     pub accounts: Vec<u8>,
     pub data: String,
     pub stack_height: Option<u32>,
+    /// Program id resolved from `program_id_index`, including addresses loaded
+    /// from lookup tables. Only provided for inner instructions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub program_id: Option<String>,
 }
 
 impl UiCompiledInstruction {
@@ -450,6 +468,7 @@ impl UiCompiledInstruction {
             accounts: instruction.accounts.clone(),
             data: bs58::encode(&instruction.data).into_string(),
             stack_height,
+            program_id: None,
         }
     }
 }
@@ -668,6 +687,27 @@ mod test {
         assert!(unsanitary_transaction.decode().is_none());
     }
 
+    #[test]
+    fn test_decode_return_data() {
+        let return_data = TransactionReturnData {
+            program_id: solana_pubkey::Pubkey::new_unique(),
+            data: vec![1, 2, 3],
+        };
+        let ui_return_data = UiTransactionReturnData::from(return_data.clone());
+        assert_eq!(ui_return_data.decode(), Some(return_data));
+
+        let invalid_program_id = UiTransactionReturnData {
+            program_id: "invalid".to_string(),
+            ..ui_return_data.clone()
+        };
+        assert_eq!(invalid_program_id.decode(), None);
+        let invalid_data = UiTransactionReturnData {
+            data: ("!".to_string(), UiReturnDataEncoding::Base64),
+            ..ui_return_data
+        };
+        assert_eq!(invalid_data.decode(), None);
+    }
+
     #[test]
     fn test_satisfies_commitment() {
         let status = TransactionStatus {
@@ -159,6 +159,24 @@ pub fn parse_ui_inner_instructions(
     }
 }
 
+/// Fills in the program id of compiled inner instructions, so that nested
+/// invocations can be traced without resolving lookup table addresses.
+fn resolve_inner_instruction_program_ids(
+    inner_instructions: &mut [UiInnerInstructions],
+    account_keys: &AccountKeys,
+) {
+    for instruction in inner_instructions
+        .iter_mut()
+        .flat_map(|inner_instructions| inner_instructions.instructions.iter_mut())
+    {
+        if let UiInstruction::Compiled(instruction) = instruction {
+            instruction.program_id = account_keys
+                .get(usize::from(instruction.program_id_index))
+                .map(ToString::to_string);
+        }
+    }
+}
+
 fn build_simple_ui_transaction_status_meta(
     meta: TransactionStatusMeta,
     show_rewards: bool,
@@ -526,10 +544,19 @@ impl VersionedTransactionWithStatusMeta {
                     show_rewards,
                 ),
                 _ => {
+                    let loaded_addresses = self.meta.loaded_addresses.clone();
                     let mut meta = UiTransactionStatusMeta::from(self.meta);
                     if !show_rewards {
                         meta.rewards = OptionSerializer::None;
                     }
+                    if let OptionSerializer::Some(inner_instructions) = &mut meta.inner_instructions
+                    {
+                        let account_keys = AccountKeys::new(
+                            self.transaction.message.static_account_keys(),
+                            Some(&loaded_addresses),
+                        );
+                        resolve_inner_instruction_program_ids(inner_instructions, &account_keys);
+                    }
                     meta
                 }
             }),
@@ -929,4 +956,64 @@ mod test {
             expected_json_output_value
         );
     }
+
+    #[test]
+    fn test_encode_resolves_inner_instruction_program_ids() {
+        let static_keys = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+        let loaded_program_id = Pubkey::new_unique();
+        let transaction = VersionedTransaction {
+            signatures: vec![Signature::default()],
+            message: VersionedMessage::V0(v0::Message {
+                header: solana_message::MessageHeader {
+                    num_required_signatures: 1,
+                    num_readonly_signed_accounts: 0,
+                    num_readonly_unsigned_accounts: 1,
+                },
+                account_keys: static_keys.clone(),
+                recent_blockhash: Hash::default(),
+                instructions: vec![CompiledInstruction::new_from_raw_parts(1, vec![], vec![0])],
+                address_table_lookups: vec![],
+            }),
+        };
+        let inner_instruction = |program_id_index, stack_height| InnerInstruction {
+            instruction: CompiledInstruction::new_from_raw_parts(program_id_index, vec![], vec![]),
+            stack_height: Some(stack_height),
+        };
+        let meta = TransactionStatusMeta {
+            inner_instructions: Some(vec![InnerInstructions {
+                index: 0,
+                instructions: vec![inner_instruction(1, 2), inner_instruction(2, 3)],
+            }]),
+            loaded_addresses: LoadedAddresses {
+                writable: vec![],
+                readonly: vec![loaded_program_id],
+            },
+            ..TransactionStatusMeta::default()
+        };
+
+        let encoded = VersionedTransactionWithStatusMeta { transaction, meta }
+            .encode(UiTransactionEncoding::Base64, Some(0), false)
+            .unwrap();
+        let OptionSerializer::Some(inner_instructions) = encoded.meta.unwrap().inner_instructions
+        else {
+            panic!("missing inner instructions");
+        };
+        let resolved: Vec<_> = inner_instructions[0]
+            .instructions
+            .iter()
+            .map(|instruction| match instruction {
+                UiInstruction::Compiled(instruction) => {
+                    (instruction.program_id.clone(), instruction.stack_height)
+                }
+                _ => panic!("expected compiled instruction"),
+            })
+            .collect();
+        assert_eq!(
+            resolved,
+            vec![
+                (Some(static_keys[1].to_string()), Some(2)),
+                (Some(loaded_program_id.to_string()), Some(3)),
+            ]
+        );
+    }
 }
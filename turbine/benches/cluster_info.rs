@@ -76,7 +76,7 @@ fn broadcast_shreds_bench(bencher: &mut Bencher) {
             &cluster_info,
             &bank_forks,
             &SocketAddrSpace::Unspecified,
-            &quic_endpoint_sender,
+            Some(&quic_endpoint_sender),
         )
         .unwrap();
     });
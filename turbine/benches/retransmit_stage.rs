@@ -100,8 +100,6 @@ fn bench_retransmitter(bencher: &mut Bencher) {
         .collect();
 
     let keypair = Keypair::new();
-    let (quic_endpoint_sender, _quic_endpoint_receiver) =
-        tokio::sync::mpsc::channel(/*capacity:*/ 128);
     let slot = 0;
     let parent = 0;
     let shredder = Shredder::new(slot, parent, 0, 0).unwrap();
@@ -125,7 +123,7 @@ fn bench_retransmitter(bencher: &mut Bencher) {
         leader_schedule_cache,
         cluster_info,
         Arc::new(sockets),
-        quic_endpoint_sender,
+        None, // quic_endpoint_sender
         shreds_receiver,
         Arc::new(solana_rpc::max_slots::MaxSlots::default()),
         None,
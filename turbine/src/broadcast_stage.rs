@@ -8,7 +8,7 @@ use {
         fail_entry_verification_broadcast_run::FailEntryVerificationBroadcastRun,
         standard_broadcast_run::StandardBroadcastRun,
     },
-    crate::cluster_nodes::{self, ClusterNodes, ClusterNodesCache},
+    crate::cluster_nodes::{ClusterNodes, ClusterNodesCache},
     bytes::Bytes,
     crossbeam_channel::{unbounded, Receiver, RecvError, RecvTimeoutError, Sender},
     itertools::{Either, Itertools},
@@ -46,7 +46,7 @@ use {
         time::{Duration, Instant},
     },
     thiserror::Error,
-    tokio::sync::mpsc::Sender as AsyncSender,
+    tokio::sync::mpsc::{error::TrySendError, Sender as AsyncSender},
 };
 
 pub mod broadcast_duplicates_run;
@@ -120,7 +120,7 @@ impl BroadcastStageType {
         blockstore: Arc<Blockstore>,
         bank_forks: Arc<RwLock<BankForks>>,
         shred_version: u16,
-        quic_endpoint_sender: AsyncSender<(SocketAddr, Bytes)>,
+        quic_endpoint_sender: Option<AsyncSender<(SocketAddr, Bytes)>>,
     ) -> BroadcastStage {
         match self {
             BroadcastStageType::Standard => BroadcastStage::new(
@@ -189,7 +189,7 @@ trait BroadcastRun {
         cluster_info: &ClusterInfo,
         sock: &UdpSocket,
         bank_forks: &RwLock<BankForks>,
-        quic_endpoint_sender: &AsyncSender<(SocketAddr, Bytes)>,
+        quic_endpoint_sender: Option<&AsyncSender<(SocketAddr, Bytes)>>,
     ) -> Result<()>;
     fn record(&mut self, receiver: &RecordReceiver, blockstore: &Blockstore) -> Result<()>;
 }
@@ -284,7 +284,7 @@ impl BroadcastStage {
         exit: Arc<AtomicBool>,
         blockstore: Arc<Blockstore>,
         bank_forks: Arc<RwLock<BankForks>>,
-        quic_endpoint_sender: AsyncSender<(SocketAddr, Bytes)>,
+        quic_endpoint_sender: Option<AsyncSender<(SocketAddr, Bytes)>>,
         mut broadcast_stage_run: impl BroadcastRun + Send + 'static + Clone,
     ) -> Self {
         let (socket_sender, socket_receiver) = unbounded();
@@ -323,7 +323,7 @@ impl BroadcastStage {
                     &cluster_info,
                     &sock,
                     &bank_forks,
-                    &quic_endpoint_sender,
+                    quic_endpoint_sender.as_ref(),
                 );
                 let res = Self::handle_error(res, "solana-broadcaster-transmit");
                 if let Some(res) = res {
@@ -442,7 +442,7 @@ pub fn broadcast_shreds(
     cluster_info: &ClusterInfo,
     bank_forks: &RwLock<BankForks>,
     socket_addr_space: &SocketAddrSpace,
-    quic_endpoint_sender: &AsyncSender<(SocketAddr, Bytes)>,
+    quic_endpoint_sender: Option<&AsyncSender<(SocketAddr, Bytes)>>,
 ) -> Result<()> {
     let mut result = Ok(());
     // Compute destinations & transmission protocols for each of the shreds to be sent
@@ -451,7 +451,7 @@ pub fn broadcast_shreds(
         let bank_forks = bank_forks.read().unwrap();
         (bank_forks.root_bank(), bank_forks.working_bank())
     };
-    let (packets, quic_packets): (Vec<_>, Vec<_>) = shreds
+    let (mut packets, quic_packets): (Vec<_>, Vec<_>) = shreds
         .iter()
         .group_by(|shred| shred.slot())
         .into_iter()
@@ -461,23 +461,41 @@ pub fn broadcast_shreds(
             update_peer_stats(&cluster_nodes, last_datapoint_submit);
             shreds.filter_map(move |shred| {
                 let key = shred.id();
-                let protocol = cluster_nodes::get_broadcast_protocol(&key);
-                cluster_nodes
-                    .get_broadcast_peer(&key)?
-                    .tvu(protocol)
-                    .filter(|addr| socket_addr_space.check(addr))
-                    .map(|addr| {
-                        (match protocol {
-                            Protocol::QUIC => Either::Right,
-                            Protocol::UDP => Either::Left,
-                        })((shred.payload(), addr))
-                    })
+                let node = cluster_nodes.get_broadcast_peer(&key)?;
+                match node.tvu_shred_addrs(quic_endpoint_sender.is_some(), socket_addr_space) {
+                    (Some(tvu_quic), tvu_udp) => {
+                        Some(Either::Right((shred.payload(), tvu_quic, tvu_udp)))
+                    }
+                    (None, Some(tvu_udp)) => Some(Either::Left((shred.payload(), tvu_udp))),
+                    (None, None) => None,
+                }
             })
         })
         .partition_map(std::convert::identity);
     shred_select.stop();
     transmit_stats.shred_select += shred_select.as_us();
-    let num_udp_packets = packets.len();
+    transmit_stats.total_packets += packets.len() + quic_packets.len();
+    let mut quic_send_time = Measure::start("send shreds via quic");
+    if let Some(quic_endpoint_sender) = quic_endpoint_sender {
+        for (shred, addr, tvu_udp) in quic_packets {
+            let payload = Bytes::from(shred::Payload::unwrap_or_clone(shred.clone()));
+            match quic_endpoint_sender.try_send((addr, payload)) {
+                Ok(()) => (),
+                // If the QUIC endpoint can not keep up, fall back to UDP
+                // instead of blocking the broadcast.
+                Err(TrySendError::Full(_)) if tvu_udp.is_some() => {
+                    transmit_stats.quic_fallback_packets += 1;
+                    packets.extend(tvu_udp.map(|addr| (shred, addr)));
+                }
+                Err(err) => {
+                    transmit_stats.dropped_packets_quic += 1;
+                    result = Err(Error::from(err));
+                }
+            }
+        }
+    }
+    quic_send_time.stop();
+    transmit_stats.send_quic_elapsed = quic_send_time.as_us();
     let mut send_mmsg_time = Measure::start("send_mmsg");
     match batch_send(s, packets) {
         Ok(()) => (),
@@ -488,17 +506,6 @@ pub fn broadcast_shreds(
     }
     send_mmsg_time.stop();
     transmit_stats.send_mmsg_elapsed += send_mmsg_time.as_us();
-    let mut quic_send_time = Measure::start("send shreds via quic");
-    transmit_stats.total_packets += num_udp_packets + quic_packets.len();
-    for (shred, addr) in quic_packets {
-        let shred = Bytes::from(shred::Payload::unwrap_or_clone(shred.clone()));
-        if let Err(err) = quic_endpoint_sender.blocking_send((addr, shred)) {
-            transmit_stats.dropped_packets_quic += 1;
-            result = Err(Error::from(err));
-        }
-    }
-    quic_send_time.stop();
-    transmit_stats.send_quic_elapsed = quic_send_time.as_us();
     result
 }
 
@@ -508,8 +515,8 @@ impl<T> From<crossbeam_channel::SendError<T>> for Error {
     }
 }
 
-impl<T> From<tokio::sync::mpsc::error::SendError<T>> for Error {
-    fn from(_: tokio::sync::mpsc::error::SendError<T>) -> Error {
+impl<T> From<TrySendError<T>> for Error {
+    fn from(_: TrySendError<T>) -> Error {
         Error::Send
     }
 }
@@ -702,7 +709,7 @@ pub mod test {
             exit_sender,
             blockstore.clone(),
             bank_forks,
-            quic_endpoint_sender,
+            Some(quic_endpoint_sender),
             StandardBroadcastRun::new(0),
         );
 
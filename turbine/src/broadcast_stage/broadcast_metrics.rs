@@ -27,6 +27,8 @@ pub struct TransmitShredsStats {
     pub total_packets: usize,
     pub(crate) dropped_packets_udp: usize,
     pub(crate) dropped_packets_quic: usize,
+    /// Packets sent over UDP because the QUIC endpoint was congested
+    pub(crate) quic_fallback_packets: usize,
 }
 
 impl BroadcastStats for TransmitShredsStats {
@@ -39,6 +41,7 @@ impl BroadcastStats for TransmitShredsStats {
         self.total_packets += new_stats.total_packets;
         self.dropped_packets_udp += new_stats.dropped_packets_udp;
         self.dropped_packets_quic += new_stats.dropped_packets_quic;
+        self.quic_fallback_packets += new_stats.quic_fallback_packets;
     }
     fn report_stats(&mut self, slot: Slot, slot_start: Instant, was_interrupted: bool) {
         if was_interrupted {
@@ -57,6 +60,11 @@ impl BroadcastStats for TransmitShredsStats {
                     self.dropped_packets_quic as i64,
                     i64
                 ),
+                (
+                    "quic_fallback_packets",
+                    self.quic_fallback_packets as i64,
+                    i64
+                ),
             );
         } else {
             datapoint_info!(
@@ -81,6 +89,11 @@ impl BroadcastStats for TransmitShredsStats {
                     self.dropped_packets_quic as i64,
                     i64
                 ),
+                (
+                    "quic_fallback_packets",
+                    self.quic_fallback_packets as i64,
+                    i64
+                ),
             );
         }
     }
@@ -228,6 +241,7 @@ mod test {
                 total_packets: 6,
                 dropped_packets_udp: 7,
                 dropped_packets_quic: 8,
+                quic_fallback_packets: 9,
             },
             &Some(BroadcastShredBatchInfo {
                 slot: 0,
@@ -249,6 +263,7 @@ mod test {
         assert_eq!(slot_0_stats.broadcast_shred_stats.total_packets, 6);
         assert_eq!(slot_0_stats.broadcast_shred_stats.dropped_packets_udp, 7);
         assert_eq!(slot_0_stats.broadcast_shred_stats.dropped_packets_quic, 8);
+        assert_eq!(slot_0_stats.broadcast_shred_stats.quic_fallback_packets, 9);
 
         slot_broadcast_stats.update(
             &TransmitShredsStats {
@@ -260,6 +275,7 @@ mod test {
                 total_packets: 16,
                 dropped_packets_udp: 17,
                 dropped_packets_quic: 18,
+                quic_fallback_packets: 19,
             },
             &None,
         );
@@ -276,6 +292,7 @@ mod test {
         assert_eq!(slot_0_stats.broadcast_shred_stats.total_packets, 6);
         assert_eq!(slot_0_stats.broadcast_shred_stats.dropped_packets_udp, 7);
         assert_eq!(slot_0_stats.broadcast_shred_stats.dropped_packets_quic, 8);
+        assert_eq!(slot_0_stats.broadcast_shred_stats.quic_fallback_packets, 9);
 
         // If another batch is given, then total number of batches == num_expected_batches == 2,
         // so the batch should be purged from the HashMap
@@ -289,6 +306,7 @@ mod test {
                 total_packets: 1,
                 dropped_packets_udp: 1,
                 dropped_packets_quic: 1,
+                quic_fallback_packets: 1,
             },
             &Some(BroadcastShredBatchInfo {
                 slot: 0,
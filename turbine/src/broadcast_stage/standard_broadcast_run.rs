@@ -164,7 +164,7 @@ impl StandardBroadcastRun {
         blockstore: &Blockstore,
         receive_results: ReceiveResults,
         bank_forks: &RwLock<BankForks>,
-        quic_endpoint_sender: &AsyncSender<(SocketAddr, Bytes)>,
+        quic_endpoint_sender: Option<&AsyncSender<(SocketAddr, Bytes)>>,
     ) -> Result<()> {
         let (bsend, brecv) = unbounded();
         let (ssend, srecv) = unbounded();
@@ -381,7 +381,7 @@ impl StandardBroadcastRun {
         shreds: Arc<Vec<Shred>>,
         broadcast_shred_batch_info: Option<BroadcastShredBatchInfo>,
         bank_forks: &RwLock<BankForks>,
-        quic_endpoint_sender: &AsyncSender<(SocketAddr, Bytes)>,
+        quic_endpoint_sender: Option<&AsyncSender<(SocketAddr, Bytes)>>,
     ) -> Result<()> {
         trace!("Broadcasting {:?} shreds", shreds.len());
         let mut transmit_stats = TransmitShredsStats::default();
@@ -464,7 +464,7 @@ impl BroadcastRun for StandardBroadcastRun {
         cluster_info: &ClusterInfo,
         sock: &UdpSocket,
         bank_forks: &RwLock<BankForks>,
-        quic_endpoint_sender: &AsyncSender<(SocketAddr, Bytes)>,
+        quic_endpoint_sender: Option<&AsyncSender<(SocketAddr, Bytes)>>,
     ) -> Result<()> {
         let (shreds, batch_info) = receiver.recv()?;
         self.broadcast(
@@ -625,7 +625,7 @@ mod test {
                 &blockstore,
                 receive_results,
                 &bank_forks,
-                &quic_endpoint_sender,
+                Some(&quic_endpoint_sender),
             )
             .unwrap();
         assert_eq!(
@@ -694,7 +694,7 @@ mod test {
                 &blockstore,
                 receive_results,
                 &bank_forks,
-                &quic_endpoint_sender,
+                Some(&quic_endpoint_sender),
             )
             .unwrap();
 
@@ -807,7 +807,7 @@ mod test {
                 &blockstore,
                 receive_results,
                 &bank_forks,
-                &quic_endpoint_sender,
+                Some(&quic_endpoint_sender),
             )
             .unwrap();
         assert!(standard_broadcast_run.completed)
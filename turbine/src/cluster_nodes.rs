@@ -80,6 +80,19 @@ pub struct Node {
     stake: u64,
 }
 
+// Position of a node in the turbine retransmit tree of a shred.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RetransmitTree {
+    // Index of the node within the shuffled nodes; the root of the tree is 0.
+    pub index: usize,
+    pub root_distance: usize,
+    // None if the node is the root of the tree.
+    pub parent: Option<Pubkey>,
+    // Nodes the shred is retransmitted to, including any staked nodes which
+    // have no contact-info in gossip and so do not actually get the shred.
+    pub children: Vec<Pubkey>,
+}
+
 pub struct ClusterNodes<T> {
     pubkey: Pubkey, // The local node itself.
     // All staked nodes + other known tvu-peers + the node itself;
@@ -146,6 +159,26 @@ impl ContactInfo {
         }
     }
 
+    // Returns the TVU address shreds are sent to the node at over QUIC, if
+    // sending shreds over QUIC is enabled and the node advertises one, and the
+    // UDP TVU address, which is used otherwise or if the QUIC endpoint is
+    // congested.
+    #[inline]
+    pub(crate) fn tvu_shred_addrs(
+        &self,
+        use_quic: bool,
+        socket_addr_space: &SocketAddrSpace,
+    ) -> (
+        Option<SocketAddr>, // QUIC
+        Option<SocketAddr>, // UDP
+    ) {
+        let tvu_quic = self
+            .tvu_quic
+            .filter(|addr| use_quic && socket_addr_space.check(addr));
+        let tvu_udp = self.tvu_udp.filter(|addr| socket_addr_space.check(addr));
+        (tvu_quic, tvu_udp)
+    }
+
     // Removes respective TVU address from the ContactInfo so that no more
     // shreds are sent to that socket address.
     #[inline]
@@ -227,6 +260,23 @@ impl ClusterNodes<RetransmitStage> {
         fanout: usize,
         socket_addr_space: &SocketAddrSpace,
     ) -> Result<(/*root_distance:*/ usize, Vec<SocketAddr>), Error> {
+        let (root_distance, nodes) = self.get_retransmit_nodes(slot_leader, shred, fanout)?;
+        let addrs = nodes
+            .into_iter()
+            .filter_map(|node| node.tvu(Protocol::UDP))
+            .filter(|addr| socket_addr_space.check(addr))
+            .collect();
+        Ok((root_distance, addrs))
+    }
+
+    // Returns the nodes this node retransmits the shred to, along with this
+    // node's distance from the root of the turbine broadcast tree.
+    pub(crate) fn get_retransmit_nodes(
+        &self,
+        slot_leader: &Pubkey,
+        shred: &ShredId,
+        fanout: usize,
+    ) -> Result<(/*root_distance:*/ usize, Vec<&ContactInfo>), Error> {
         // Exclude slot leader from list of nodes.
         if slot_leader == &self.pubkey {
             return Err(Error::Loopback {
@@ -245,11 +295,7 @@ impl ClusterNodes<RetransmitStage> {
                 |k| self.nodes[k].pubkey() == &self.pubkey,
                 weighted_shuffle.shuffle(&mut rng),
             );
-            let protocol = get_broadcast_protocol(shred);
-            let peers = peers
-                .filter_map(|k| self.nodes[k].contact_info()?.tvu(protocol))
-                .filter(|addr| socket_addr_space.check(addr))
-                .collect();
+            let peers = peers.filter_map(|k| self.nodes[k].contact_info()).collect();
             let root_distance = get_root_distance(index, fanout);
             Ok((root_distance, peers))
         })
@@ -290,6 +336,49 @@ impl ClusterNodes<RetransmitStage> {
         let parent = get_retransmit_parent(fanout, nodes.len(), &nodes);
         Ok(parent.map(Node::pubkey).copied())
     }
+
+    // Computes the retransmit tree parent and children of an arbitrary node
+    // for the shred, as seen by the local node. This is the same computation
+    // as get_retransmit_addrs and get_retransmit_parent, and is meant for
+    // debugging whether missing shreds are due to the tree or the network.
+    // Returns None if the node is not known to the local node.
+    // Unstaked nodes' position depends on the gossip view of each node, so
+    // other nodes may place them differently.
+    pub fn get_retransmit_tree(
+        &self,
+        slot_leader: &Pubkey,
+        shred: &ShredId,
+        fanout: usize,
+        node: &Pubkey,
+    ) -> Result<Option<RetransmitTree>, Error> {
+        if slot_leader == node {
+            return Err(Error::Loopback {
+                leader: *slot_leader,
+                shred: *shred,
+            });
+        }
+        let Some(&node_index) = self.index.get(node) else {
+            return Ok(None);
+        };
+        let mut weighted_shuffle = self.weighted_shuffle.clone();
+        if let Some(index) = self.index.get(slot_leader) {
+            weighted_shuffle.remove_index(*index);
+        }
+        let mut rng = get_seeded_rng(slot_leader, shred);
+        let shuffled: Vec<usize> = weighted_shuffle.shuffle(&mut rng).collect();
+        let Some(index) = shuffled.iter().position(|&k| k == node_index) else {
+            return Ok(None);
+        };
+        let parent = get_retransmit_parent(fanout, index, &shuffled);
+        let (_, children) =
+            get_retransmit_peers(fanout, |k| k == node_index, shuffled.iter().copied());
+        Ok(Some(RetransmitTree {
+            index,
+            root_distance: get_root_distance(index, fanout),
+            parent: parent.map(|k| *self.nodes[k].pubkey()),
+            children: children.map(|k| *self.nodes[k].pubkey()).collect(),
+        }))
+    }
 }
 
 pub fn new_cluster_nodes<T: 'static>(
@@ -590,11 +679,6 @@ impl From<&GossipContactInfo> for ContactInfo {
     }
 }
 
-#[inline]
-pub(crate) fn get_broadcast_protocol(_: &ShredId) -> Protocol {
-    Protocol::UDP
-}
-
 #[inline]
 fn get_root_distance(index: usize, fanout: usize) -> usize {
     if index == 0 {
@@ -658,7 +742,7 @@ pub fn make_test_cluster<R: Rng>(
     (nodes, stakes, cluster_info)
 }
 
-pub(crate) fn get_data_plane_fanout(shred_slot: Slot, root_bank: &Bank) -> usize {
+pub fn get_data_plane_fanout(shred_slot: Slot, root_bank: &Bank) -> usize {
     if check_feature_activation(
         &feature_set::disable_turbine_fanout_experiments::id(),
         shred_slot,
@@ -715,7 +799,9 @@ pub fn check_feature_activation(feature: &Pubkey, shred_slot: Slot, root_bank: &
 mod tests {
     use {
         super::*,
+        assert_matches::assert_matches,
         itertools::Itertools,
+        solana_ledger::shred::ShredType,
         std::{fmt::Debug, hash::Hash},
         test_case::test_case,
     };
@@ -759,6 +845,67 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_get_retransmit_tree() {
+        let mut rng = rand::thread_rng();
+        let (_nodes, stakes, cluster_info) = make_test_cluster(&mut rng, 1_000, None);
+        let cluster_nodes =
+            new_cluster_nodes::<RetransmitStage>(&cluster_info, ClusterType::Development, &stakes);
+        let this_node = cluster_info.id();
+        let slot_leader = stakes
+            .keys()
+            .copied()
+            .find(|pubkey| pubkey != &this_node)
+            .unwrap();
+        let shred = ShredId::new(rng.gen(), rng.gen(), ShredType::Data);
+        let fanout = 20;
+        let tree = cluster_nodes
+            .get_retransmit_tree(&slot_leader, &shred, fanout, &this_node)
+            .unwrap()
+            .unwrap();
+        // Children are the nodes this node retransmits to.
+        let (root_distance, addrs) = cluster_nodes
+            .get_retransmit_addrs(&slot_leader, &shred, fanout, &SocketAddrSpace::Unspecified)
+            .unwrap();
+        assert_eq!(tree.root_distance, root_distance);
+        let children_addrs: Vec<_> = tree
+            .children
+            .iter()
+            .filter_map(|pubkey| {
+                cluster_nodes.nodes[cluster_nodes.index[pubkey]]
+                    .contact_info()?
+                    .tvu(Protocol::UDP)
+            })
+            .collect();
+        assert_eq!(children_addrs, addrs);
+        if cluster_nodes.nodes[cluster_nodes.index[&this_node]].stake > 0 {
+            assert_eq!(
+                tree.parent,
+                cluster_nodes
+                    .get_retransmit_parent(&slot_leader, &shred, fanout)
+                    .unwrap()
+            );
+        }
+        // This node is the parent of each of its children.
+        for child in &tree.children {
+            let child_tree = cluster_nodes
+                .get_retransmit_tree(&slot_leader, &shred, fanout, child)
+                .unwrap()
+                .unwrap();
+            assert_eq!(child_tree.parent, Some(this_node));
+        }
+        assert_matches!(
+            cluster_nodes.get_retransmit_tree(&slot_leader, &shred, fanout, &slot_leader),
+            Err(Error::Loopback { .. })
+        );
+        assert_eq!(
+            cluster_nodes
+                .get_retransmit_tree(&slot_leader, &shred, fanout, &Pubkey::new_unique())
+                .unwrap(),
+            None
+        );
+    }
+
     #[test]
     fn test_cluster_nodes_broadcast() {
         let mut rng = rand::thread_rng();
@@ -1034,4 +1181,39 @@ mod tests {
         }
         assert!(unique_pubkeys.is_empty());
     }
+
+    #[test]
+    fn test_tvu_shred_addrs() {
+        let tvu_quic = SocketAddr::from(([127, 0, 0, 1], 8001));
+        let tvu_udp = SocketAddr::from(([127, 0, 0, 1], 8002));
+        let node = ContactInfo {
+            pubkey: Pubkey::new_unique(),
+            wallclock: timestamp(),
+            tvu_quic: Some(tvu_quic),
+            tvu_udp: Some(tvu_udp),
+        };
+        assert_eq!(
+            node.tvu_shred_addrs(/*use_quic:*/ true, &SocketAddrSpace::Unspecified),
+            (Some(tvu_quic), Some(tvu_udp))
+        );
+        assert_eq!(
+            node.tvu_shred_addrs(/*use_quic:*/ false, &SocketAddrSpace::Unspecified),
+            (None, Some(tvu_udp))
+        );
+        // Loopback addresses are not allowed in a global address space.
+        assert_eq!(
+            node.tvu_shred_addrs(/*use_quic:*/ true, &SocketAddrSpace::Global),
+            (None, None)
+        );
+        // Nodes which do not advertise a QUIC TVU address are sent shreds
+        // over UDP.
+        let node = ContactInfo {
+            tvu_quic: None,
+            ..node
+        };
+        assert_eq!(
+            node.tvu_shred_addrs(/*use_quic:*/ true, &SocketAddrSpace::Unspecified),
+            (None, Some(tvu_udp))
+        );
+    }
 }
@@ -4,10 +4,11 @@ use {
     crate::cluster_nodes::{self, ClusterNodes, ClusterNodesCache, Error, MAX_NUM_TURBINE_HOPS},
     bytes::Bytes,
     crossbeam_channel::{Receiver, RecvError},
+    itertools::{Either, Itertools},
     lru::LruCache,
     rand::Rng,
     rayon::{prelude::*, ThreadPool, ThreadPoolBuilder},
-    solana_gossip::{cluster_info::ClusterInfo, contact_info::Protocol},
+    solana_gossip::cluster_info::ClusterInfo,
     solana_ledger::{
         leader_schedule_cache::LeaderScheduleCache,
         shred::{self, ShredId},
@@ -69,6 +70,8 @@ struct RetransmitStats {
     since: Instant,
     num_nodes: AtomicUsize,
     num_addrs_failed: AtomicUsize,
+    // Number of shreds sent over UDP because the QUIC endpoint was congested.
+    num_addrs_quic_fallback: AtomicUsize,
     num_loopback_errs: AtomicUsize,
     num_shreds: usize,
     num_shreds_skipped: AtomicUsize,
@@ -107,6 +110,11 @@ impl RetransmitStats {
             ("num_small_batches", self.num_small_batches, i64),
             ("num_nodes", *self.num_nodes.get_mut(), i64),
             ("num_addrs_failed", *self.num_addrs_failed.get_mut(), i64),
+            (
+                "num_addrs_quic_fallback",
+                *self.num_addrs_quic_fallback.get_mut(),
+                i64
+            ),
             ("num_loopback_errs", *self.num_loopback_errs.get_mut(), i64),
             ("num_shreds", self.num_shreds, i64),
             (
@@ -187,7 +195,7 @@ fn retransmit(
     cluster_info: &ClusterInfo,
     retransmit_receiver: &Receiver<Vec<shred::Payload>>,
     retransmit_sockets: &[UdpSocket],
-    quic_endpoint_sender: &AsyncSender<(SocketAddr, Bytes)>,
+    quic_endpoint_sender: Option<&AsyncSender<(SocketAddr, Bytes)>>,
     stats: &mut RetransmitStats,
     cluster_nodes_cache: &ClusterNodesCache<RetransmitStage>,
     shred_deduper: &mut ShredDeduper,
@@ -310,7 +318,7 @@ fn retransmit_shred(
     cache: &HashMap<Slot, (/*leader:*/ Pubkey, Arc<ClusterNodes<RetransmitStage>>)>,
     socket_addr_space: &SocketAddrSpace,
     socket: &UdpSocket,
-    quic_endpoint_sender: &AsyncSender<(SocketAddr, Bytes)>,
+    quic_endpoint_sender: Option<&AsyncSender<(SocketAddr, Bytes)>>,
     stats: &RetransmitStats,
 ) -> Option<(
     Slot,  // Shred slot.
@@ -325,8 +333,8 @@ fn retransmit_shred(
     }
     let mut compute_turbine_peers = Measure::start("turbine_start");
     let data_plane_fanout = cluster_nodes::get_data_plane_fanout(key.slot(), root_bank);
-    let (root_distance, addrs) = cluster_nodes
-        .get_retransmit_addrs(slot_leader, &key, data_plane_fanout, socket_addr_space)
+    let (root_distance, nodes) = cluster_nodes
+        .get_retransmit_nodes(slot_leader, &key, data_plane_fanout)
         .inspect_err(|err| match err {
             Error::Loopback { .. } => {
                 error!("retransmit_shred: {err}");
@@ -334,31 +342,45 @@ fn retransmit_shred(
             }
         })
         .ok()?;
+    let (quic_addrs, mut addrs): (Vec<_>, Vec<_>) = nodes
+        .into_iter()
+        .filter_map(|node| {
+            match node.tvu_shred_addrs(quic_endpoint_sender.is_some(), socket_addr_space) {
+                (Some(tvu_quic), tvu_udp) => Some(Either::Left((tvu_quic, tvu_udp))),
+                (None, Some(tvu_udp)) => Some(Either::Right(tvu_udp)),
+                (None, None) => None,
+            }
+        })
+        .partition_map(std::convert::identity);
     compute_turbine_peers.stop();
     stats
         .compute_turbine_peers_total
         .fetch_add(compute_turbine_peers.as_us(), Ordering::Relaxed);
 
     let mut retransmit_time = Measure::start("retransmit_to");
-    let num_addrs = addrs.len();
-    let num_nodes = match cluster_nodes::get_broadcast_protocol(&key) {
-        Protocol::QUIC => {
+    let num_addrs = quic_addrs.len() + addrs.len();
+    let num_nodes = match quic_endpoint_sender {
+        Some(quic_endpoint_sender) if !quic_addrs.is_empty() => {
             let shred = Bytes::from(shred::Payload::unwrap_or_clone(shred));
-            addrs
-                .into_iter()
-                .filter_map(|addr| quic_endpoint_sender.try_send((addr, shred.clone())).ok())
-                .count()
-        }
-        Protocol::UDP => match multi_target_send(socket, shred, &addrs) {
-            Ok(()) => addrs.len(),
-            Err(SendPktsError::IoError(ioerr, num_failed)) => {
-                error!(
-                    "retransmit_to multi_target_send error: {ioerr:?}, {num_failed}/{} packets failed",
-                    addrs.len(),
-                );
-                addrs.len() - num_failed
+            let mut num_nodes = 0;
+            for (tvu_quic, tvu_udp) in quic_addrs {
+                // If the QUIC endpoint can not keep up, fall back to UDP
+                // instead of dropping the shred.
+                if quic_endpoint_sender
+                    .try_send((tvu_quic, shred.clone()))
+                    .is_ok()
+                {
+                    num_nodes += 1;
+                } else if let Some(tvu_udp) = tvu_udp {
+                    stats
+                        .num_addrs_quic_fallback
+                        .fetch_add(1, Ordering::Relaxed);
+                    addrs.push(tvu_udp);
+                }
             }
-        },
+            num_nodes + send_shred_udp(socket, &shred, &addrs)
+        }
+        _ => send_shred_udp(socket, shred, &addrs),
     };
     retransmit_time.stop();
     stats
@@ -371,6 +393,21 @@ fn retransmit_shred(
     Some((key.slot(), root_distance, num_nodes))
 }
 
+// Sends the shred over UDP to each of the addresses, returning the number of
+// addresses it was sent to.
+fn send_shred_udp(socket: &UdpSocket, shred: impl AsRef<[u8]>, addrs: &[SocketAddr]) -> usize {
+    match multi_target_send(socket, shred, addrs) {
+        Ok(()) => addrs.len(),
+        Err(SendPktsError::IoError(ioerr, num_failed)) => {
+            error!(
+                "retransmit_to multi_target_send error: {ioerr:?}, {num_failed}/{} packets failed",
+                addrs.len(),
+            );
+            addrs.len() - num_failed
+        }
+    }
+}
+
 /// Service to retransmit messages received from other peers in turbine.
 pub struct RetransmitStage {
     retransmit_thread_handle: JoinHandle<()>,
@@ -381,6 +418,8 @@ impl RetransmitStage {
     ///
     /// Key arguments:
     /// * `retransmit_sockets` - Sockets to use for transmission of shreds
+    /// * `quic_endpoint_sender` - Sender to the QUIC endpoint shreds are sent over to the nodes
+    ///   which support it, or `None` if shreds are only sent over UDP
     /// * `max_slots` - Structure to keep track of the Turbine progress
     /// * `bank_forks` - Reference to the BankForks structure
     /// * `leader_schedule_cache` - The leader schedule to verify shreds
@@ -391,7 +430,7 @@ impl RetransmitStage {
         leader_schedule_cache: Arc<LeaderScheduleCache>,
         cluster_info: Arc<ClusterInfo>,
         retransmit_sockets: Arc<Vec<UdpSocket>>,
-        quic_endpoint_sender: AsyncSender<(SocketAddr, Bytes)>,
+        quic_endpoint_sender: Option<AsyncSender<(SocketAddr, Bytes)>>,
         retransmit_receiver: Receiver<Vec<shred::Payload>>,
         max_slots: Arc<MaxSlots>,
         rpc_subscriptions: Option<Arc<RpcSubscriptions>>,
@@ -428,7 +467,7 @@ impl RetransmitStage {
                     &cluster_info,
                     &retransmit_receiver,
                     &retransmit_sockets,
-                    &quic_endpoint_sender,
+                    quic_endpoint_sender.as_ref(),
                     &mut stats,
                     &cluster_nodes_cache,
                     &mut shred_deduper,
@@ -480,6 +519,7 @@ impl RetransmitStats {
             since: now,
             num_nodes: AtomicUsize::default(),
             num_addrs_failed: AtomicUsize::default(),
+            num_addrs_quic_fallback: AtomicUsize::default(),
             num_loopback_errs: AtomicUsize::default(),
             num_shreds: 0usize,
             num_shreds_skipped: AtomicUsize::default(),
@@ -26,7 +26,7 @@ use {
     solana_ledger::blockstore_processor::{
         execute_batch, TransactionBatchWithIndexes, TransactionStatusSender,
     },
-    solana_poh::poh_recorder::{RecordTransactionsSummary, TransactionRecorder},
+    solana_poh::poh_recorder::TransactionRecorder,
     solana_pubkey::Pubkey,
     solana_runtime::{
         installed_scheduler_pool::{
@@ -36,6 +36,7 @@ use {
             UninstalledScheduler, UninstalledSchedulerBox,
         },
         prioritization_fee_cache::PrioritizationFeeCache,
+        transaction_recorder::RecordTransactions,
         vote_sender_types::ReplayVoteSender,
     },
     solana_runtime_transaction::runtime_transaction::RuntimeTransaction,
@@ -636,19 +637,11 @@ impl TaskHandler for DefaultTaskHandler {
                     Err(error.clone())?;
                 };
 
-                let RecordTransactionsSummary {
-                    result,
-                    starting_transaction_index,
-                    ..
-                } = handler_context
-                    .transaction_recorder
-                    .as_ref()
-                    .unwrap()
-                    .record_transactions(bank.slot(), vec![transaction.to_versioned_transaction()]);
-                match result {
-                    Ok(()) => Ok(starting_transaction_index),
-                    Err(_) => Err(TransactionError::CommitCancelled),
-                }
+                RecordTransactions::record_transactions(
+                    handler_context.transaction_recorder.as_ref().unwrap(),
+                    bank.slot(),
+                    vec![transaction.to_versioned_transaction()],
+                )
             }),
         };
 
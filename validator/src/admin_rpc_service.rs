@@ -1,4 +1,6 @@
 use {
+    crate::log_filter::LogFilter,
+    base64::{prelude::BASE64_STANDARD, Engine},
     crossbeam_channel::Sender,
     jsonrpc_core::{BoxFuture, ErrorCode, MetaIoHandler, Metadata, Result},
     jsonrpc_core_client::{transports::ipc, RpcError},
@@ -13,30 +15,62 @@ use {
         admin_rpc_post_init::AdminRpcRequestMetadataPostInit,
         consensus::{tower_storage::TowerStorage, Tower},
         repair::repair_service,
-        validator::ValidatorStartProgress,
+        validator::{is_snapshot_config_valid, mark_clean_shutdown, ValidatorStartProgress},
     },
-    solana_geyser_plugin_manager::GeyserPluginManagerRequest,
-    solana_gossip::contact_info::{ContactInfo, Protocol, SOCKET_ADDR_UNSPECIFIED},
+    solana_geyser_plugin_manager::{GeyserPluginManagerRequest, GeyserPluginStatus},
+    solana_gossip::{
+        cluster_info::ClusterInfo,
+        contact_info::{ContactInfo, Protocol, SOCKET_ADDR_UNSPECIFIED},
+        crds::Cursor,
+        custom_crds_value::{
+            is_valid_namespace, CustomValue, MAX_CUSTOM_VALUE_DATA_SIZE,
+            MAX_CUSTOM_VALUE_NAMESPACE_LEN,
+        },
+        peer_scores::BlacklistedPeer,
+    },
+    solana_ledger::{
+        blockstore::MAX_DATA_SHREDS_PER_SLOT,
+        leader_schedule_utils,
+        shred::{ShredId, ShredType},
+    },
+    solana_offchain_message::OffchainMessage,
     solana_rpc::rpc::verify_pubkey,
     solana_rpc_client_api::{config::RpcAccountIndex, custom_error::RpcCustomError},
+    solana_runtime::snapshot_bank_utils::DISABLED_SNAPSHOT_ARCHIVE_INTERVAL,
     solana_sdk::{
+        clock::Slot,
         exit::Exit,
         pubkey::Pubkey,
         signature::{read_keypair_file, Keypair, Signer},
     },
+    solana_streamer::socket_stats::{self, SocketReceiveStatsSnapshot},
+    solana_turbine::{
+        cluster_nodes::{get_data_plane_fanout, new_cluster_nodes},
+        retransmit_stage::RetransmitStage,
+    },
     std::{
-        collections::{HashMap, HashSet},
+        collections::{BTreeMap, HashMap, HashSet},
         env, error,
         fmt::{self, Display},
         net::SocketAddr,
         path::{Path, PathBuf},
-        sync::{Arc, RwLock},
+        str::FromStr,
+        sync::{Arc, Mutex, RwLock},
         thread::{self, Builder},
-        time::{Duration, SystemTime},
+        time::{Duration, SystemTime, UNIX_EPOCH},
     },
     tokio::runtime::Runtime,
 };
 
+// Caps the slots repaired by a single `repairShredsFromPeer` request.
+const MAX_REPAIR_SHREDS_FROM_PEER_SLOTS: u64 = 1_000;
+// Caps the shreds requested by a single `repairShredsFromPeer` request.
+const MAX_REPAIR_SHREDS_FROM_PEER_SHREDS: usize = 10_000;
+// Caps how long a single `repairShredsFromPeer` request waits for the repairs.
+const MAX_REPAIR_SHREDS_FROM_PEER_TIMEOUT_MS: u64 = 60_000;
+// How often a scheduled exit checks whether it is due.
+const SCHEDULED_EXIT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
 #[derive(Clone)]
 pub struct AdminRpcRequestMetadata {
     pub rpc_addr: Option<SocketAddr>,
@@ -48,10 +82,45 @@ pub struct AdminRpcRequestMetadata {
     pub staked_nodes_overrides: Arc<RwLock<HashMap<Pubkey, u64>>>,
     pub post_init: Arc<RwLock<Option<AdminRpcRequestMetadataPostInit>>>,
     pub rpc_to_plugin_manager_sender: Option<Sender<GeyserPluginManagerRequest>>,
+    pub scheduled_exit: Arc<Mutex<ScheduledExit>>,
+    pub log_filter: Arc<RwLock<LogFilter>>,
+    pub gossip_custom_values: Arc<Mutex<GossipCustomValues>>,
 }
 
 impl Metadata for AdminRpcRequestMetadata {}
 
+/// Custom gossip values returned by `gossipCustomValues`, read from the crds
+/// table incrementally
+#[derive(Default)]
+pub struct GossipCustomValues {
+    /// Cursor into the crds table, and the latest value of each node, by
+    /// namespace
+    namespaces: HashMap<String, (Cursor, HashMap<Pubkey, CustomValue>)>,
+}
+
+impl GossipCustomValues {
+    /// Reads the values inserted in `namespace` since the previous call, and
+    /// returns the latest value of each node, sorted by node
+    fn update(&mut self, cluster_info: &ClusterInfo, namespace: &str) -> Vec<CustomValue> {
+        let (cursor, values) = self.namespaces.entry(namespace.to_string()).or_default();
+        for value in cluster_info.get_custom_values(namespace, cursor) {
+            values.insert(value.from, value);
+        }
+        let mut values: Vec<_> = values.values().cloned().collect();
+        values.sort_unstable_by_key(|value| value.from);
+        values
+    }
+}
+
+/// The exit scheduled with `scheduleExit`, if any
+#[derive(Debug, Default)]
+pub struct ScheduledExit {
+    /// Incremented whenever the scheduled exit is replaced or cancelled, so
+    /// that the thread waiting for the previous one stops
+    generation: u64,
+    scheduled: Option<AdminRpcScheduledExit>,
+}
+
 impl AdminRpcRequestMetadata {
     fn with_post_init<F, R>(&self, func: F) -> Result<R>
     where
@@ -75,13 +144,32 @@ pub struct AdminRpcContactInfo {
     pub tvu_quic: SocketAddr,
     pub serve_repair_quic: SocketAddr,
     pub tpu: SocketAddr,
+    pub tpu_quic: SocketAddr,
     pub tpu_forwards: SocketAddr,
+    pub tpu_forwards_quic: SocketAddr,
     pub tpu_vote: SocketAddr,
+    pub tpu_vote_quic: SocketAddr,
     pub rpc: SocketAddr,
     pub rpc_pubsub: SocketAddr,
     pub serve_repair: SocketAddr,
     pub last_updated_timestamp: u64,
     pub shred_version: u16,
+    /// Client version, with its commit, feature set and client id
+    pub version: String,
+    /// First 4 bytes of the feature set identifier
+    pub feature_set: u32,
+}
+
+/// Contact info signed by the validator identity, returned by `signedContactInfo`
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcSignedContactInfo {
+    pub contact_info: AdminRpcContactInfo,
+    /// Base64 encoded off-chain message wrapping the compact JSON serialization of
+    /// `contact_info`; these are the exact bytes covered by `signature`
+    pub message: String,
+    /// Signature of `message` by the validator identity
+    pub signature: String,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -89,6 +177,121 @@ pub struct AdminRpcRepairWhitelist {
     pub whitelist: Vec<Pubkey>,
 }
 
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AdminRpcWriteFence {
+    pub accounts: Vec<Pubkey>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcRetransmitTree {
+    pub node: String,
+    pub slot_leader: String,
+    pub fanout: usize,
+    pub root_distance: usize,
+    pub parent: Option<String>,
+    pub children: Vec<String>,
+}
+
+/// Outcome of the repair of one slot requested with `repairShredsFromPeer`
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcSlotRepair {
+    pub slot: u64,
+    /// Indexes of the data shreds that were missing and requested
+    pub requested: Vec<u64>,
+    /// Indexes of the requested data shreds still missing when the repair timed out
+    pub missing: Vec<u64>,
+    pub is_full: bool,
+}
+
+/// A custom value published through gossip, returned by `gossipCustomValues`
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcGossipCustomValue {
+    pub from: String,
+    pub wallclock: u64,
+    pub data: Vec<u8>,
+}
+
+/// A gossip peer returned by `gossipBlacklist`
+#[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcBlacklistedPeer {
+    pub pubkey: String,
+    /// Seconds left before the ban expires, zero if it has already expired
+    pub remaining_secs: u64,
+    /// Number of times the peer has been banned in a row
+    pub num_bans: u32,
+}
+
+/// The log filter returned by `logFilter`
+#[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcLogFilter {
+    /// The base filter, in the `RUST_LOG` format
+    pub filter: String,
+    /// Levels that take precedence over the base filter for these modules
+    /// and their submodules, persisted across restarts
+    pub module_levels: BTreeMap<String, String>,
+    /// The most records per second logged by these modules and their
+    /// submodules, persisted across restarts
+    #[serde(default)]
+    pub sample_rates: BTreeMap<String, u64>,
+}
+
+/// When a scheduled exit happens; exactly one of the fields is set
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcScheduledExit {
+    /// Exit once the validator has a bank for this slot
+    pub at_slot: Option<Slot>,
+    /// Exit at this unix timestamp, in seconds
+    pub at_unix_timestamp: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcAccountsMaintenanceStatus {
+    pub paused: bool,
+    pub clean_active: bool,
+    pub shrink_active: bool,
+    pub io_limit_bytes_per_second: Option<u64>,
+    pub io_bytes_consumed: u64,
+    pub io_throttled_us: u64,
+}
+
+/// The receive statistics of a UDP socket, returned by `socketReceiveStats`
+#[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcSocketReceiveStats {
+    pub addr: SocketAddr,
+    pub name: String,
+    pub packets_received: u64,
+    pub packet_batches_received: u64,
+    /// None if the OS does not report the packets it drops
+    pub packets_dropped_by_os: Option<u64>,
+    pub packets_dropped_in_process: u64,
+    pub mean_processing_time_us: u64,
+    pub max_processing_time_us: u64,
+    pub channel_len: usize,
+}
+
+/// The settings that `reloadConfig` can change while the validator runs.
+///
+/// In a request, None leaves a setting unchanged. In a response, None means the
+/// setting does not apply to this validator, e.g. the snapshot intervals when
+/// snapshots are disabled.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminRpcReloadableConfig {
+    pub full_snapshot_interval_slots: Option<Slot>,
+    /// DISABLED_SNAPSHOT_ARCHIVE_INTERVAL disables incremental snapshots
+    pub incremental_snapshot_interval_slots: Option<Slot>,
+    pub accounts_db_write_cache_limit_bytes: Option<u64>,
+    pub rpc_max_inflight_transactions_per_fee_payer: Option<usize>,
+}
+
 impl From<ContactInfo> for AdminRpcContactInfo {
     fn from(node: ContactInfo) -> Self {
         macro_rules! unwrap_socket {
@@ -107,13 +310,65 @@ impl From<ContactInfo> for AdminRpcContactInfo {
             tvu_quic: unwrap_socket!(tvu, Protocol::QUIC),
             serve_repair_quic: unwrap_socket!(serve_repair, Protocol::QUIC),
             tpu: unwrap_socket!(tpu, Protocol::UDP),
+            tpu_quic: unwrap_socket!(tpu, Protocol::QUIC),
             tpu_forwards: unwrap_socket!(tpu_forwards, Protocol::UDP),
+            tpu_forwards_quic: unwrap_socket!(tpu_forwards, Protocol::QUIC),
             tpu_vote: unwrap_socket!(tpu_vote, Protocol::UDP),
+            tpu_vote_quic: unwrap_socket!(tpu_vote, Protocol::QUIC),
             rpc: unwrap_socket!(rpc),
             rpc_pubsub: unwrap_socket!(rpc_pubsub),
             serve_repair: unwrap_socket!(serve_repair, Protocol::UDP),
             shred_version: node.shred_version(),
+            version: format!("{:?}", node.version()),
+            feature_set: node.version().feature_set,
+        }
+    }
+}
+
+impl From<SocketReceiveStatsSnapshot> for AdminRpcSocketReceiveStats {
+    fn from(stats: SocketReceiveStatsSnapshot) -> Self {
+        Self {
+            addr: stats.addr,
+            name: stats.name.to_string(),
+            packets_received: stats.packets_received,
+            packet_batches_received: stats.packet_batches_received,
+            packets_dropped_by_os: stats.packets_dropped_by_os,
+            packets_dropped_in_process: stats.packets_dropped_in_process,
+            mean_processing_time_us: stats.mean_processing_time_us,
+            max_processing_time_us: stats.max_processing_time_us,
+            channel_len: stats.channel_len,
+        }
+    }
+}
+
+impl From<CustomValue> for AdminRpcGossipCustomValue {
+    fn from(value: CustomValue) -> Self {
+        Self {
+            from: value.from.to_string(),
+            wallclock: value.wallclock,
+            data: value.data,
+        }
+    }
+}
+
+impl From<BlacklistedPeer> for AdminRpcBlacklistedPeer {
+    fn from(peer: BlacklistedPeer) -> Self {
+        Self {
+            pubkey: peer.pubkey.to_string(),
+            remaining_secs: peer.remaining.as_secs(),
+            num_bans: peer.num_bans,
+        }
+    }
+}
+
+impl Display for AdminRpcBlacklistedPeer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.remaining_secs == 0 {
+            write!(f, "{}: ban expired", self.pubkey)?;
+        } else {
+            write!(f, "{}: banned for {}s", self.pubkey, self.remaining_secs)?;
         }
+        writeln!(f, ", {} ban(s) in a row", self.num_bans)
     }
 }
 
@@ -124,13 +379,27 @@ impl Display for AdminRpcContactInfo {
         writeln!(f, "TVU: {}", self.tvu)?;
         writeln!(f, "TVU QUIC: {}", self.tvu_quic)?;
         writeln!(f, "TPU: {}", self.tpu)?;
+        writeln!(f, "TPU QUIC: {}", self.tpu_quic)?;
         writeln!(f, "TPU Forwards: {}", self.tpu_forwards)?;
+        writeln!(f, "TPU Forwards QUIC: {}", self.tpu_forwards_quic)?;
         writeln!(f, "TPU Votes: {}", self.tpu_vote)?;
+        writeln!(f, "TPU Votes QUIC: {}", self.tpu_vote_quic)?;
         writeln!(f, "RPC: {}", self.rpc)?;
         writeln!(f, "RPC Pubsub: {}", self.rpc_pubsub)?;
         writeln!(f, "Serve Repair: {}", self.serve_repair)?;
+        writeln!(f, "Serve Repair QUIC: {}", self.serve_repair_quic)?;
         writeln!(f, "Last Updated Timestamp: {}", self.last_updated_timestamp)?;
-        writeln!(f, "Shred Version: {}", self.shred_version)
+        writeln!(f, "Shred Version: {}", self.shred_version)?;
+        writeln!(f, "Version: {}", self.version)?;
+        writeln!(f, "Feature Set: {}", self.feature_set)
+    }
+}
+
+impl Display for AdminRpcSignedContactInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.contact_info)?;
+        writeln!(f, "Signed Message: {}", self.message)?;
+        writeln!(f, "Signature: {}", self.signature)
     }
 }
 
@@ -140,6 +409,143 @@ impl Display for AdminRpcRepairWhitelist {
     }
 }
 
+impl Display for AdminRpcWriteFence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.accounts.is_empty() {
+            return writeln!(f, "Write fenced accounts: none");
+        }
+        writeln!(f, "Write fenced accounts:")?;
+        for account in &self.accounts {
+            writeln!(f, "  {account}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for AdminRpcLogFilter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Filter: {}", self.filter)?;
+        if self.module_levels.is_empty() {
+            writeln!(f, "Module levels: none")?;
+        } else {
+            writeln!(f, "Module levels:")?;
+            for (module, level) in &self.module_levels {
+                writeln!(f, "  {module}={level}")?;
+            }
+        }
+        if self.sample_rates.is_empty() {
+            return writeln!(f, "Sample rates: none");
+        }
+        writeln!(f, "Sample rates:")?;
+        for (module, max_records_per_second) in &self.sample_rates {
+            writeln!(f, "  {module}={max_records_per_second} records per second")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for AdminRpcRetransmitTree {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Node: {}", self.node)?;
+        writeln!(f, "Slot leader: {}", self.slot_leader)?;
+        writeln!(f, "Fanout: {}", self.fanout)?;
+        writeln!(f, "Root distance: {}", self.root_distance)?;
+        match &self.parent {
+            Some(parent) => writeln!(f, "Parent: {parent}")?,
+            None => writeln!(f, "Parent: none, the node is the root of the tree")?,
+        }
+        writeln!(f, "Children ({}):", self.children.len())?;
+        for child in &self.children {
+            writeln!(f, "  {child}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for AdminRpcScheduledExit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (self.at_slot, self.at_unix_timestamp) {
+            (Some(slot), _) => writeln!(f, "Exit scheduled at slot {slot}"),
+            (None, Some(timestamp)) => {
+                let now = unix_timestamp();
+                writeln!(
+                    f,
+                    "Exit scheduled at unix timestamp {timestamp}, in {}s",
+                    timestamp.saturating_sub(now)
+                )
+            }
+            (None, None) => writeln!(f, "No exit scheduled"),
+        }
+    }
+}
+
+impl Display for AdminRpcAccountsMaintenanceStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Paused: {}", self.paused)?;
+        writeln!(f, "Clean active: {}", self.clean_active)?;
+        writeln!(f, "Shrink active: {}", self.shrink_active)?;
+        match self.io_limit_bytes_per_second {
+            Some(limit) => writeln!(f, "IO limit: {limit} bytes/s")?,
+            None => writeln!(f, "IO limit: unlimited")?,
+        }
+        writeln!(f, "IO consumed: {} bytes", self.io_bytes_consumed)?;
+        writeln!(
+            f,
+            "IO throttled: {:?}",
+            Duration::from_micros(self.io_throttled_us)
+        )
+    }
+}
+
+impl Display for AdminRpcSocketReceiveStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{} ({}):", self.addr, self.name)?;
+        writeln!(
+            f,
+            "  Received: {} packets in {} batches",
+            self.packets_received, self.packet_batches_received
+        )?;
+        match self.packets_dropped_by_os {
+            Some(dropped) => writeln!(f, "  Dropped by the OS: {dropped} packets")?,
+            None => writeln!(f, "  Dropped by the OS: not reported")?,
+        }
+        writeln!(
+            f,
+            "  Dropped in process: {} packets",
+            self.packets_dropped_in_process
+        )?;
+        writeln!(
+            f,
+            "  Processing time: {:?} mean, {:?} max",
+            Duration::from_micros(self.mean_processing_time_us),
+            Duration::from_micros(self.max_processing_time_us)
+        )?;
+        writeln!(f, "  Queued: {} batches", self.channel_len)
+    }
+}
+
+impl Display for AdminRpcReloadableConfig {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.full_snapshot_interval_slots {
+            Some(slots) => writeln!(f, "Full snapshot interval: {slots} slots")?,
+            None => writeln!(f, "Full snapshot interval: snapshots disabled")?,
+        }
+        match self.incremental_snapshot_interval_slots {
+            Some(slots) if slots != DISABLED_SNAPSHOT_ARCHIVE_INTERVAL => {
+                writeln!(f, "Incremental snapshot interval: {slots} slots")?
+            }
+            _ => writeln!(f, "Incremental snapshot interval: disabled")?,
+        }
+        if let Some(bytes) = self.accounts_db_write_cache_limit_bytes {
+            writeln!(f, "Accounts write cache limit: {bytes} bytes")?;
+        }
+        match self.rpc_max_inflight_transactions_per_fee_payer {
+            Some(max) => writeln!(f, "RPC max inflight transactions per fee payer: {max}"),
+            None => writeln!(f, "RPC max inflight transactions per fee payer: unlimited"),
+        }
+    }
+}
+
 #[rpc]
 pub trait AdminRpc {
     type Metadata;
@@ -147,6 +553,20 @@ pub trait AdminRpc {
     #[rpc(meta, name = "exit")]
     fn exit(&self, meta: Self::Metadata) -> Result<()>;
 
+    #[rpc(meta, name = "scheduleExit")]
+    fn schedule_exit(
+        &self,
+        meta: Self::Metadata,
+        at_slot: Option<Slot>,
+        after_secs: Option<u64>,
+    ) -> Result<AdminRpcScheduledExit>;
+
+    #[rpc(meta, name = "scheduledExit")]
+    fn scheduled_exit(&self, meta: Self::Metadata) -> Result<Option<AdminRpcScheduledExit>>;
+
+    #[rpc(meta, name = "cancelScheduledExit")]
+    fn cancel_scheduled_exit(&self, meta: Self::Metadata) -> Result<Option<AdminRpcScheduledExit>>;
+
     #[rpc(meta, name = "reloadPlugin")]
     fn reload_plugin(
         &self,
@@ -164,11 +584,33 @@ pub trait AdminRpc {
     #[rpc(meta, name = "listPlugins")]
     fn list_plugins(&self, meta: Self::Metadata) -> BoxFuture<Result<Vec<String>>>;
 
+    #[rpc(meta, name = "pluginStatus")]
+    fn plugin_status(&self, meta: Self::Metadata) -> BoxFuture<Result<Vec<GeyserPluginStatus>>>;
+
     #[rpc(meta, name = "rpcAddress")]
     fn rpc_addr(&self, meta: Self::Metadata) -> Result<Option<SocketAddr>>;
 
-    #[rpc(name = "setLogFilter")]
-    fn set_log_filter(&self, filter: String) -> Result<()>;
+    #[rpc(meta, name = "setLogFilter")]
+    fn set_log_filter(&self, meta: Self::Metadata, filter: String) -> Result<()>;
+
+    #[rpc(meta, name = "setModuleLogLevel")]
+    fn set_module_log_level(
+        &self,
+        meta: Self::Metadata,
+        module: String,
+        level: Option<String>,
+    ) -> Result<()>;
+
+    #[rpc(meta, name = "setModuleLogSampleRate")]
+    fn set_module_log_sample_rate(
+        &self,
+        meta: Self::Metadata,
+        module: String,
+        max_records_per_second: Option<u64>,
+    ) -> Result<()>;
+
+    #[rpc(meta, name = "logFilter")]
+    fn log_filter(&self, meta: Self::Metadata) -> Result<AdminRpcLogFilter>;
 
     #[rpc(meta, name = "startTime")]
     fn start_time(&self, meta: Self::Metadata) -> Result<SystemTime>;
@@ -208,6 +650,30 @@ pub trait AdminRpc {
     #[rpc(meta, name = "contactInfo")]
     fn contact_info(&self, meta: Self::Metadata) -> Result<AdminRpcContactInfo>;
 
+    #[rpc(meta, name = "signedContactInfo")]
+    fn signed_contact_info(&self, meta: Self::Metadata) -> Result<AdminRpcSignedContactInfo>;
+
+    #[rpc(meta, name = "publishGossipCustomValue")]
+    fn publish_gossip_custom_value(
+        &self,
+        meta: Self::Metadata,
+        namespace: String,
+        data: Vec<u8>,
+    ) -> Result<()>;
+
+    #[rpc(meta, name = "gossipCustomValues")]
+    fn gossip_custom_values(
+        &self,
+        meta: Self::Metadata,
+        namespace: String,
+    ) -> Result<Vec<AdminRpcGossipCustomValue>>;
+
+    #[rpc(meta, name = "gossipBlacklist")]
+    fn gossip_blacklist(&self, meta: Self::Metadata) -> Result<Vec<AdminRpcBlacklistedPeer>>;
+
+    #[rpc(meta, name = "gossipUnban")]
+    fn gossip_unban(&self, meta: Self::Metadata, pubkey: Pubkey) -> Result<bool>;
+
     #[rpc(meta, name = "repairShredFromPeer")]
     fn repair_shred_from_peer(
         &self,
@@ -217,12 +683,39 @@ pub trait AdminRpc {
         shred_index: u64,
     ) -> Result<()>;
 
+    #[rpc(meta, name = "repairShredsFromPeer")]
+    fn repair_shreds_from_peer(
+        &self,
+        meta: Self::Metadata,
+        pubkey: Option<Pubkey>,
+        start_slot: u64,
+        end_slot: u64,
+        shred_index_ranges: Vec<(u64, u64)>,
+        timeout_ms: u64,
+    ) -> Result<Vec<AdminRpcSlotRepair>>;
+
     #[rpc(meta, name = "repairWhitelist")]
     fn repair_whitelist(&self, meta: Self::Metadata) -> Result<AdminRpcRepairWhitelist>;
 
+    #[rpc(meta, name = "retransmitTree")]
+    fn retransmit_tree(
+        &self,
+        meta: Self::Metadata,
+        slot: u64,
+        shred_index: u32,
+        coding: bool,
+        node: Option<Pubkey>,
+    ) -> Result<AdminRpcRetransmitTree>;
+
     #[rpc(meta, name = "setRepairWhitelist")]
     fn set_repair_whitelist(&self, meta: Self::Metadata, whitelist: Vec<Pubkey>) -> Result<()>;
 
+    #[rpc(meta, name = "writeFencedAccounts")]
+    fn write_fenced_accounts(&self, meta: Self::Metadata) -> Result<AdminRpcWriteFence>;
+
+    #[rpc(meta, name = "setWriteFencedAccounts")]
+    fn set_write_fenced_accounts(&self, meta: Self::Metadata, accounts: Vec<Pubkey>) -> Result<()>;
+
     #[rpc(meta, name = "getSecondaryIndexKeySize")]
     fn get_secondary_index_key_size(
         &self,
@@ -243,6 +736,43 @@ pub trait AdminRpc {
         meta: Self::Metadata,
         public_tpu_forwards_addr: SocketAddr,
     ) -> Result<()>;
+
+    #[rpc(meta, name = "setPublicRpcAddress")]
+    fn set_public_rpc_address(
+        &self,
+        meta: Self::Metadata,
+        public_rpc_addr: SocketAddr,
+    ) -> Result<()>;
+
+    #[rpc(meta, name = "accountsMaintenanceStatus")]
+    fn accounts_maintenance_status(
+        &self,
+        meta: Self::Metadata,
+    ) -> Result<AdminRpcAccountsMaintenanceStatus>;
+
+    #[rpc(meta, name = "pauseAccountsMaintenance")]
+    fn pause_accounts_maintenance(&self, meta: Self::Metadata) -> Result<()>;
+
+    #[rpc(meta, name = "resumeAccountsMaintenance")]
+    fn resume_accounts_maintenance(&self, meta: Self::Metadata) -> Result<()>;
+
+    #[rpc(meta, name = "setAccountsMaintenanceIoLimit")]
+    fn set_accounts_maintenance_io_limit(
+        &self,
+        meta: Self::Metadata,
+        bytes_per_second: Option<u64>,
+    ) -> Result<()>;
+
+    #[rpc(meta, name = "socketReceiveStats")]
+    fn socket_receive_stats(&self, meta: Self::Metadata)
+        -> Result<Vec<AdminRpcSocketReceiveStats>>;
+
+    #[rpc(meta, name = "reloadConfig")]
+    fn reload_config(
+        &self,
+        meta: Self::Metadata,
+        config: AdminRpcReloadableConfig,
+    ) -> Result<AdminRpcReloadableConfig>;
 }
 
 pub struct AdminRpcImpl;
@@ -260,25 +790,94 @@ impl AdminRpc for AdminRpcImpl {
                 thread::sleep(Duration::from_millis(100));
 
                 warn!("validator exit requested");
-                meta.validator_exit.write().unwrap().exit();
-
-                // TODO: Debug why Exit doesn't always cause the validator to fully exit
-                // (rocksdb background processing or some other stuck thread perhaps?).
-                //
-                // If the process is still alive after five seconds, exit harder
-                thread::sleep(Duration::from_secs(
-                    env::var("SOLANA_VALIDATOR_EXIT_TIMEOUT")
-                        .ok()
-                        .and_then(|x| x.parse().ok())
-                        .unwrap_or(5),
-                ));
-                warn!("validator exit timeout");
-                std::process::exit(0);
+                exit_validator(&meta);
             })
             .unwrap();
         Ok(())
     }
 
+    fn schedule_exit(
+        &self,
+        meta: Self::Metadata,
+        at_slot: Option<Slot>,
+        after_secs: Option<u64>,
+    ) -> Result<AdminRpcScheduledExit> {
+        debug!("schedule_exit admin rpc request received");
+
+        let scheduled_exit = match (at_slot, after_secs) {
+            (Some(slot), None) => {
+                // Waiting for a slot requires the bank forks
+                meta.with_post_init(|_| Ok(()))?;
+                AdminRpcScheduledExit {
+                    at_slot: Some(slot),
+                    at_unix_timestamp: None,
+                }
+            }
+            (None, Some(secs)) => AdminRpcScheduledExit {
+                at_slot: None,
+                at_unix_timestamp: Some(unix_timestamp().saturating_add(secs)),
+            },
+            _ => {
+                return Err(jsonrpc_core::error::Error::invalid_params(
+                    "Specify either a slot or a number of seconds to exit after",
+                ))
+            }
+        };
+
+        let generation = {
+            let mut state = meta.scheduled_exit.lock().unwrap();
+            state.generation += 1;
+            state.scheduled = Some(scheduled_exit.clone());
+            state.generation
+        };
+        warn!("validator exit scheduled: {scheduled_exit:?}");
+
+        let result = scheduled_exit.clone();
+        thread::Builder::new()
+            .name("solSchedExit".into())
+            .spawn(move || loop {
+                thread::sleep(SCHEDULED_EXIT_POLL_INTERVAL);
+                if meta.scheduled_exit.lock().unwrap().generation != generation {
+                    // Cancelled, or replaced by a later request
+                    return;
+                }
+                let is_due = match scheduled_exit.at_slot {
+                    Some(slot) => meta
+                        .with_post_init(|post_init| {
+                            Ok(post_init.bank_forks.read().unwrap().highest_slot() >= slot)
+                        })
+                        .unwrap_or(false),
+                    None => scheduled_exit
+                        .at_unix_timestamp
+                        .is_some_and(|timestamp| unix_timestamp() >= timestamp),
+                };
+                if is_due {
+                    warn!("scheduled validator exit is due: {scheduled_exit:?}");
+                    exit_validator(&meta);
+                    return;
+                }
+            })
+            .unwrap();
+        Ok(result)
+    }
+
+    fn scheduled_exit(&self, meta: Self::Metadata) -> Result<Option<AdminRpcScheduledExit>> {
+        debug!("scheduled_exit admin rpc request received");
+        Ok(meta.scheduled_exit.lock().unwrap().scheduled.clone())
+    }
+
+    fn cancel_scheduled_exit(&self, meta: Self::Metadata) -> Result<Option<AdminRpcScheduledExit>> {
+        debug!("cancel_scheduled_exit admin rpc request received");
+
+        let mut state = meta.scheduled_exit.lock().unwrap();
+        state.generation += 1;
+        let cancelled = state.scheduled.take();
+        if let Some(scheduled_exit) = &cancelled {
+            warn!("scheduled validator exit cancelled: {scheduled_exit:?}");
+        }
+        Ok(cancelled)
+    }
+
     fn reload_plugin(
         &self,
         meta: Self::Metadata,
@@ -394,17 +993,106 @@ impl AdminRpc for AdminRpcImpl {
         })
     }
 
+    fn plugin_status(&self, meta: Self::Metadata) -> BoxFuture<Result<Vec<GeyserPluginStatus>>> {
+        Box::pin(async move {
+            // Construct channel for plugin to respond to this particular rpc request instance
+            let (response_sender, response_receiver) = oneshot_channel();
+
+            // Send request to plugin manager
+            if let Some(ref rpc_to_manager_sender) = meta.rpc_to_plugin_manager_sender {
+                rpc_to_manager_sender
+                    .send(GeyserPluginManagerRequest::PluginStatuses { response_sender })
+                    .expect("GeyerPluginService should never drop request receiver");
+            } else {
+                return Err(jsonrpc_core::Error {
+                    code: ErrorCode::InvalidRequest,
+                    message: "No geyser plugin service".to_string(),
+                    data: None,
+                });
+            }
+
+            // Await response from plugin manager
+            response_receiver
+                .await
+                .expect("GeyerPluginService's oneshot sender shouldn't drop early")
+        })
+    }
+
     fn rpc_addr(&self, meta: Self::Metadata) -> Result<Option<SocketAddr>> {
         debug!("rpc_addr admin rpc request received");
         Ok(meta.rpc_addr)
     }
 
-    fn set_log_filter(&self, filter: String) -> Result<()> {
+    fn set_log_filter(&self, meta: Self::Metadata, filter: String) -> Result<()> {
         debug!("set_log_filter admin rpc request received");
-        solana_logger::setup_with(&filter);
+        meta.log_filter.write().unwrap().set_filter(filter);
         Ok(())
     }
 
+    fn set_module_log_level(
+        &self,
+        meta: Self::Metadata,
+        module: String,
+        level: Option<String>,
+    ) -> Result<()> {
+        debug!("set_module_log_level admin rpc request received");
+        verify_log_module(&module)?;
+        let level = level
+            .map(|level| {
+                LevelFilter::from_str(&level).map_err(|_| {
+                    jsonrpc_core::error::Error::invalid_params(format!("invalid level: {level}"))
+                })
+            })
+            .transpose()?;
+        meta.log_filter
+            .write()
+            .unwrap()
+            .set_module_level(module, level)
+            .map_err(|err| {
+                jsonrpc_core::error::Error::invalid_params(format!(
+                    "failed to persist module log level: {err}"
+                ))
+            })
+    }
+
+    fn set_module_log_sample_rate(
+        &self,
+        meta: Self::Metadata,
+        module: String,
+        max_records_per_second: Option<u64>,
+    ) -> Result<()> {
+        debug!("set_module_log_sample_rate admin rpc request received");
+        verify_log_module(&module)?;
+        if max_records_per_second == Some(0) {
+            return Err(jsonrpc_core::error::Error::invalid_params(
+                "the sample rate must be positive, set the module's level to off instead",
+            ));
+        }
+        meta.log_filter
+            .write()
+            .unwrap()
+            .set_sample_rate(module, max_records_per_second)
+            .map_err(|err| {
+                jsonrpc_core::error::Error::invalid_params(format!(
+                    "failed to set module log sample rate: {err}"
+                ))
+            })
+    }
+
+    fn log_filter(&self, meta: Self::Metadata) -> Result<AdminRpcLogFilter> {
+        debug!("log_filter admin rpc request received");
+        let log_filter = meta.log_filter.read().unwrap();
+        Ok(AdminRpcLogFilter {
+            filter: log_filter.filter().to_string(),
+            module_levels: log_filter
+                .module_levels()
+                .iter()
+                .map(|(module, level)| (module.clone(), level.as_str().to_lowercase()))
+                .collect(),
+            sample_rates: log_filter.sample_rates().clone(),
+        })
+    }
+
     fn start_time(&self, meta: Self::Metadata) -> Result<SystemTime> {
         debug!("start_time admin rpc request received");
         Ok(meta.start_time)
@@ -502,6 +1190,90 @@ impl AdminRpc for AdminRpcImpl {
         meta.with_post_init(|post_init| Ok(post_init.cluster_info.my_contact_info().into()))
     }
 
+    fn signed_contact_info(&self, meta: Self::Metadata) -> Result<AdminRpcSignedContactInfo> {
+        meta.with_post_init(|post_init| {
+            let contact_info = AdminRpcContactInfo::from(post_init.cluster_info.my_contact_info());
+            let message = serde_json::to_vec(&contact_info)
+                .map_err(|err| err.to_string())
+                .and_then(|json| OffchainMessage::new(0, &json).map_err(|err| err.to_string()))
+                .and_then(|message| message.serialize().map_err(|err| err.to_string()))
+                .map_err(|err| {
+                    error!("Failed to build the contact info message: {err}");
+                    jsonrpc_core::error::Error::internal_error()
+                })?;
+            let signature = post_init.cluster_info.keypair().sign_message(&message);
+            Ok(AdminRpcSignedContactInfo {
+                contact_info,
+                message: BASE64_STANDARD.encode(message),
+                signature: signature.to_string(),
+            })
+        })
+    }
+
+    fn publish_gossip_custom_value(
+        &self,
+        meta: Self::Metadata,
+        namespace: String,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        if !is_valid_namespace(&namespace) {
+            return Err(jsonrpc_core::error::Error::invalid_params(format!(
+                "invalid namespace {namespace:?}: expected 1 to \
+                 {MAX_CUSTOM_VALUE_NAMESPACE_LEN} ASCII alphanumeric, '-', '_', '.' or '/' \
+                 characters"
+            )));
+        }
+        if data.len() > MAX_CUSTOM_VALUE_DATA_SIZE {
+            return Err(jsonrpc_core::error::Error::invalid_params(format!(
+                "data is {} bytes, larger than the maximum of {MAX_CUSTOM_VALUE_DATA_SIZE}",
+                data.len()
+            )));
+        }
+        meta.with_post_init(|post_init| {
+            post_init
+                .cluster_info
+                .push_custom_value(&namespace, data)
+                .map_err(|err| jsonrpc_core::error::Error::invalid_params(format!("{err}")))
+        })
+    }
+
+    fn gossip_custom_values(
+        &self,
+        meta: Self::Metadata,
+        namespace: String,
+    ) -> Result<Vec<AdminRpcGossipCustomValue>> {
+        meta.with_post_init(|post_init| {
+            let mut gossip_custom_values = meta.gossip_custom_values.lock().unwrap();
+            Ok(gossip_custom_values
+                .update(&post_init.cluster_info, &namespace)
+                .into_iter()
+                .map(AdminRpcGossipCustomValue::from)
+                .collect())
+        })
+    }
+
+    fn gossip_blacklist(&self, meta: Self::Metadata) -> Result<Vec<AdminRpcBlacklistedPeer>> {
+        meta.with_post_init(|post_init| {
+            Ok(post_init
+                .cluster_info
+                .gossip_blacklist()
+                .into_iter()
+                .map(AdminRpcBlacklistedPeer::from)
+                .collect())
+        })
+    }
+
+    fn gossip_unban(&self, meta: Self::Metadata, pubkey: Pubkey) -> Result<bool> {
+        debug!("gossip_unban request received");
+        meta.with_post_init(|post_init| {
+            let unbanned = post_init.cluster_info.gossip_unban(&pubkey);
+            if unbanned {
+                info!("Lifted the gossip ban of {pubkey}");
+            }
+            Ok(unbanned)
+        })
+    }
+
     fn repair_shred_from_peer(
         &self,
         meta: Self::Metadata,
@@ -525,6 +1297,67 @@ impl AdminRpc for AdminRpcImpl {
         })
     }
 
+    fn repair_shreds_from_peer(
+        &self,
+        meta: Self::Metadata,
+        pubkey: Option<Pubkey>,
+        start_slot: u64,
+        end_slot: u64,
+        shred_index_ranges: Vec<(u64, u64)>,
+        timeout_ms: u64,
+    ) -> Result<Vec<AdminRpcSlotRepair>> {
+        debug!("repair_shreds_from_peer request received");
+
+        if end_slot < start_slot || end_slot - start_slot >= MAX_REPAIR_SHREDS_FROM_PEER_SLOTS {
+            return Err(jsonrpc_core::error::Error::invalid_params(format!(
+                "Invalid slot range; at most {MAX_REPAIR_SHREDS_FROM_PEER_SLOTS} slots may be \
+                 repaired at once"
+            )));
+        }
+        if timeout_ms > MAX_REPAIR_SHREDS_FROM_PEER_TIMEOUT_MS {
+            return Err(jsonrpc_core::error::Error::invalid_params(format!(
+                "Invalid timeout; repairs may be waited on for at most \
+                 {MAX_REPAIR_SHREDS_FROM_PEER_TIMEOUT_MS} ms"
+            )));
+        }
+        let shred_indexes: Vec<_> = shred_index_ranges
+            .into_iter()
+            .map(|(start, end)| {
+                if start < end && end <= MAX_DATA_SHREDS_PER_SLOT as u64 {
+                    Ok(start..end)
+                } else {
+                    Err(jsonrpc_core::error::Error::invalid_params(format!(
+                        "Invalid shred index range: {start}..{end}"
+                    )))
+                }
+            })
+            .collect::<Result<_>>()?;
+
+        // Release the lock on the post init data while waiting for the repairs.
+        let post_init = meta.with_post_init(|post_init| Ok(post_init.clone()))?;
+        let slot_repairs = repair_service::RepairService::repair_shreds_from_peer(
+            post_init.cluster_info,
+            post_init.cluster_slots,
+            &post_init.blockstore,
+            pubkey,
+            start_slot..=end_slot,
+            &shred_indexes,
+            &post_init.repair_socket,
+            post_init.outstanding_repair_requests,
+            MAX_REPAIR_SHREDS_FROM_PEER_SHREDS,
+            Duration::from_millis(timeout_ms),
+        );
+        Ok(slot_repairs
+            .into_iter()
+            .map(|slot_repair| AdminRpcSlotRepair {
+                slot: slot_repair.slot,
+                requested: slot_repair.requested,
+                missing: slot_repair.missing,
+                is_full: slot_repair.is_full,
+            })
+            .collect())
+    }
+
     fn repair_whitelist(&self, meta: Self::Metadata) -> Result<AdminRpcRepairWhitelist> {
         debug!("repair_whitelist request received");
 
@@ -540,15 +1373,99 @@ impl AdminRpc for AdminRpcImpl {
         })
     }
 
-    fn set_repair_whitelist(&self, meta: Self::Metadata, whitelist: Vec<Pubkey>) -> Result<()> {
-        debug!("set_repair_whitelist request received");
+    fn retransmit_tree(
+        &self,
+        meta: Self::Metadata,
+        slot: u64,
+        shred_index: u32,
+        coding: bool,
+        node: Option<Pubkey>,
+    ) -> Result<AdminRpcRetransmitTree> {
+        debug!("retransmit_tree request received");
+
+        meta.with_post_init(|post_init| {
+            let root_bank = post_init.bank_forks.read().unwrap().root_bank();
+            let node = node.unwrap_or_else(|| post_init.cluster_info.id());
+            let slot_leader =
+                leader_schedule_utils::slot_leader_at(slot, &root_bank).ok_or_else(|| {
+                    jsonrpc_core::error::Error::invalid_params(format!(
+                        "No leader schedule for slot {slot}"
+                    ))
+                })?;
+            let epoch = root_bank.epoch_schedule().get_epoch(slot);
+            let stakes = root_bank.epoch_staked_nodes(epoch).ok_or_else(|| {
+                jsonrpc_core::error::Error::invalid_params(format!(
+                    "No epoch stakes for slot {slot}"
+                ))
+            })?;
+            let cluster_nodes = new_cluster_nodes::<RetransmitStage>(
+                &post_init.cluster_info,
+                root_bank.cluster_type(),
+                &stakes,
+            );
+            let fanout = get_data_plane_fanout(slot, &root_bank);
+            let shred_type = if coding {
+                ShredType::Code
+            } else {
+                ShredType::Data
+            };
+            let shred = ShredId::new(slot, shred_index, shred_type);
+            let tree = cluster_nodes
+                .get_retransmit_tree(&slot_leader, &shred, fanout, &node)
+                .map_err(|err| jsonrpc_core::error::Error::invalid_params(format!("{err}")))?
+                .ok_or_else(|| {
+                    jsonrpc_core::error::Error::invalid_params(format!("Unknown node {node}"))
+                })?;
+            Ok(AdminRpcRetransmitTree {
+                node: node.to_string(),
+                slot_leader: slot_leader.to_string(),
+                fanout,
+                root_distance: tree.root_distance,
+                parent: tree.parent.as_ref().map(Pubkey::to_string),
+                children: tree.children.iter().map(Pubkey::to_string).collect(),
+            })
+        })
+    }
+
+    fn set_repair_whitelist(&self, meta: Self::Metadata, whitelist: Vec<Pubkey>) -> Result<()> {
+        debug!("set_repair_whitelist request received");
+
+        let whitelist: HashSet<Pubkey> = whitelist.into_iter().collect();
+        meta.with_post_init(|post_init| {
+            *post_init.repair_whitelist.write().unwrap() = whitelist;
+            warn!(
+                "Repair whitelist set to {:?}",
+                &post_init.repair_whitelist.read().unwrap()
+            );
+            Ok(())
+        })
+    }
+
+    fn write_fenced_accounts(&self, meta: Self::Metadata) -> Result<AdminRpcWriteFence> {
+        debug!("write_fenced_accounts request received");
+
+        meta.with_post_init(|post_init| {
+            let mut accounts: Vec<_> = post_init
+                .write_fenced_accounts
+                .read()
+                .unwrap()
+                .iter()
+                .copied()
+                .collect();
+            accounts.sort_unstable();
+            Ok(AdminRpcWriteFence { accounts })
+        })
+    }
+
+    fn set_write_fenced_accounts(&self, meta: Self::Metadata, accounts: Vec<Pubkey>) -> Result<()> {
+        debug!("set_write_fenced_accounts request received");
 
-        let whitelist: HashSet<Pubkey> = whitelist.into_iter().collect();
+        let accounts: HashSet<Pubkey> = accounts.into_iter().collect();
         meta.with_post_init(|post_init| {
-            *post_init.repair_whitelist.write().unwrap() = whitelist;
+            *post_init.write_fenced_accounts.write().unwrap() = accounts;
             warn!(
-                "Repair whitelist set to {:?}",
-                &post_init.repair_whitelist.read().unwrap()
+                "Write fenced accounts set to {:?}",
+                &post_init.write_fenced_accounts.read().unwrap()
             );
             Ok(())
         })
@@ -679,6 +1596,216 @@ impl AdminRpc for AdminRpcImpl {
             Ok(())
         })
     }
+
+    fn set_public_rpc_address(
+        &self,
+        meta: Self::Metadata,
+        public_rpc_addr: SocketAddr,
+    ) -> Result<()> {
+        debug!("set_public_rpc_address rpc request received: {public_rpc_addr}");
+
+        meta.with_post_init(|post_init| {
+            post_init
+                .cluster_info
+                .my_contact_info()
+                .rpc()
+                .ok_or_else(|| {
+                    error!(
+                        "The public RPC address isn't being published. The node is likely running \
+                     with --private-rpc or without --rpc-port."
+                    );
+                    jsonrpc_core::error::Error::internal_error()
+                })?;
+            post_init
+                .cluster_info
+                .set_rpc(public_rpc_addr)
+                .map_err(|err| {
+                    error!("Failed to set public RPC address to {public_rpc_addr}: {err}");
+                    jsonrpc_core::error::Error::internal_error()
+                })?;
+            warn!(
+                "Public RPC address set to {:?}",
+                post_init.cluster_info.my_contact_info().rpc(),
+            );
+            Ok(())
+        })
+    }
+
+    fn accounts_maintenance_status(
+        &self,
+        meta: Self::Metadata,
+    ) -> Result<AdminRpcAccountsMaintenanceStatus> {
+        debug!("accounts_maintenance_status request received");
+
+        meta.with_post_init(|post_init| {
+            let bank = post_init.bank_forks.read().unwrap().root_bank();
+            let accounts_db = &bank.accounts().accounts_db;
+            let io_status = accounts_db.background_io_budget().status();
+            Ok(AdminRpcAccountsMaintenanceStatus {
+                paused: io_status.paused,
+                clean_active: accounts_db.is_clean_active(),
+                shrink_active: accounts_db.is_shrink_active(),
+                io_limit_bytes_per_second: io_status.bytes_per_second,
+                io_bytes_consumed: io_status.bytes_consumed,
+                io_throttled_us: io_status.throttled_us,
+            })
+        })
+    }
+
+    fn pause_accounts_maintenance(&self, meta: Self::Metadata) -> Result<()> {
+        debug!("pause_accounts_maintenance request received");
+
+        meta.with_post_init(|post_init| {
+            let bank = post_init.bank_forks.read().unwrap().root_bank();
+            bank.accounts().accounts_db.background_io_budget().pause();
+            warn!("Accounts clean and shrink paused");
+            Ok(())
+        })
+    }
+
+    fn resume_accounts_maintenance(&self, meta: Self::Metadata) -> Result<()> {
+        debug!("resume_accounts_maintenance request received");
+
+        meta.with_post_init(|post_init| {
+            let bank = post_init.bank_forks.read().unwrap().root_bank();
+            bank.accounts().accounts_db.background_io_budget().resume();
+            warn!("Accounts clean and shrink resumed");
+            Ok(())
+        })
+    }
+
+    fn set_accounts_maintenance_io_limit(
+        &self,
+        meta: Self::Metadata,
+        bytes_per_second: Option<u64>,
+    ) -> Result<()> {
+        debug!("set_accounts_maintenance_io_limit request received: {bytes_per_second:?}");
+
+        meta.with_post_init(|post_init| {
+            let bank = post_init.bank_forks.read().unwrap().root_bank();
+            bank.accounts()
+                .accounts_db
+                .background_io_budget()
+                .set_limit(bytes_per_second);
+            warn!("Accounts clean and shrink IO limit set to {bytes_per_second:?} bytes/s");
+            Ok(())
+        })
+    }
+
+    fn socket_receive_stats(
+        &self,
+        _meta: Self::Metadata,
+    ) -> Result<Vec<AdminRpcSocketReceiveStats>> {
+        debug!("socket_receive_stats request received");
+
+        Ok(socket_stats::socket_receive_stats()
+            .into_iter()
+            .map(AdminRpcSocketReceiveStats::from)
+            .collect())
+    }
+
+    fn reload_config(
+        &self,
+        meta: Self::Metadata,
+        config: AdminRpcReloadableConfig,
+    ) -> Result<AdminRpcReloadableConfig> {
+        debug!("reload_config request received: {config:?}");
+
+        meta.with_post_init(|post_init| {
+            let (root_bank, accounts_hash_interval_slots) = {
+                let bank_forks = post_init.bank_forks.read().unwrap();
+                (
+                    bank_forks.root_bank(),
+                    bank_forks.accounts_hash_interval_slots,
+                )
+            };
+
+            // Validate everything before applying anything, so that a rejected request
+            // leaves the running config untouched
+            let mut snapshot_config = post_init.snapshot_config.write().unwrap();
+            let mut new_snapshot_config = snapshot_config.clone();
+            if let Some(slots) = config.full_snapshot_interval_slots {
+                new_snapshot_config.full_snapshot_archive_interval_slots = slots;
+            }
+            if let Some(slots) = config.incremental_snapshot_interval_slots {
+                new_snapshot_config.incremental_snapshot_archive_interval_slots = slots;
+            }
+            let snapshot_intervals_changed = (
+                snapshot_config.full_snapshot_archive_interval_slots,
+                snapshot_config.incremental_snapshot_archive_interval_slots,
+            ) != (
+                new_snapshot_config.full_snapshot_archive_interval_slots,
+                new_snapshot_config.incremental_snapshot_archive_interval_slots,
+            );
+            if snapshot_intervals_changed {
+                if !snapshot_config.should_generate_snapshots() {
+                    return Err(jsonrpc_core::error::Error::invalid_params(
+                        "Snapshot generation is disabled",
+                    ));
+                }
+                if !is_snapshot_config_valid(&new_snapshot_config, accounts_hash_interval_slots) {
+                    return Err(jsonrpc_core::error::Error::invalid_params(format!(
+                        "Invalid snapshot intervals: the full snapshot interval must be greater \
+                         than the incremental one, and both must be multiples of the accounts \
+                         hash interval ({accounts_hash_interval_slots} slots)"
+                    )));
+                }
+            }
+            if let Some(limit) = config.accounts_db_write_cache_limit_bytes {
+                if limit == 0 {
+                    return Err(jsonrpc_core::error::Error::invalid_params(
+                        "The accounts write cache limit must be greater than zero",
+                    ));
+                }
+            }
+            let inflight_limiter = post_init.rpc_fee_payer_inflight_limiter.as_ref();
+            if let Some(max) = config.rpc_max_inflight_transactions_per_fee_payer {
+                if inflight_limiter.is_none() {
+                    return Err(jsonrpc_core::error::Error::invalid_params(
+                        "The RPC fee payer inflight limit was not enabled at startup",
+                    ));
+                }
+                if max == 0 {
+                    return Err(jsonrpc_core::error::Error::invalid_params(
+                        "The RPC max inflight transactions per fee payer must be greater than \
+                         zero",
+                    ));
+                }
+            }
+
+            if snapshot_intervals_changed {
+                warn!(
+                    "Snapshot intervals changed to {} full, {} incremental slots",
+                    new_snapshot_config.full_snapshot_archive_interval_slots,
+                    new_snapshot_config.incremental_snapshot_archive_interval_slots,
+                );
+                *snapshot_config = new_snapshot_config;
+            }
+            let accounts_db = &root_bank.accounts().accounts_db;
+            if let Some(limit) = config.accounts_db_write_cache_limit_bytes {
+                accounts_db.set_write_cache_limit_bytes(Some(limit));
+                warn!("Accounts write cache limit changed to {limit} bytes");
+            }
+            if let (Some(max), Some(inflight_limiter)) = (
+                config.rpc_max_inflight_transactions_per_fee_payer,
+                inflight_limiter,
+            ) {
+                inflight_limiter.set_max_inflight_per_fee_payer(max);
+                warn!("RPC max inflight transactions per fee payer changed to {max}");
+            }
+
+            let should_generate_snapshots = snapshot_config.should_generate_snapshots();
+            Ok(AdminRpcReloadableConfig {
+                full_snapshot_interval_slots: should_generate_snapshots
+                    .then_some(snapshot_config.full_snapshot_archive_interval_slots),
+                incremental_snapshot_interval_slots: should_generate_snapshots
+                    .then_some(snapshot_config.incremental_snapshot_archive_interval_slots),
+                accounts_db_write_cache_limit_bytes: Some(accounts_db.write_cache_limit_bytes()),
+                rpc_max_inflight_transactions_per_fee_payer: inflight_limiter
+                    .map(|inflight_limiter| inflight_limiter.max_inflight_per_fee_payer()),
+            })
+        })
+    }
 }
 
 impl AdminRpcImpl {
@@ -734,6 +1861,64 @@ impl AdminRpcImpl {
     }
 }
 
+/// Signals the validator to exit, and exits the process if it is still
+/// running after a timeout
+fn exit_validator(meta: &AdminRpcRequestMetadata) {
+    meta.validator_exit.write().unwrap().exit();
+
+    // TODO: Debug why Exit doesn't always cause the validator to fully exit
+    // (rocksdb background processing or some other stuck thread perhaps?).
+    //
+    // If the process is still alive after five seconds, exit harder
+    thread::sleep(Duration::from_secs(
+        env::var("SOLANA_VALIDATOR_EXIT_TIMEOUT")
+            .ok()
+            .and_then(|x| x.parse().ok())
+            .unwrap_or(5),
+    ));
+    warn!("validator exit timeout");
+    // The exit was requested, so a thread stuck on the way out does not make this
+    // an unclean shutdown. Before post init the validator has not started yet,
+    // and a marker left by an earlier crash must stay until it is acted upon
+    if let Some(post_init) = meta.post_init.read().unwrap().as_ref() {
+        mark_clean_shutdown(post_init.blockstore.ledger_path());
+    }
+    std::process::exit(0);
+}
+
+/// Exit the validator on SIGTERM the same way the `exit` method does
+#[cfg(unix)]
+pub fn exit_on_sigterm(metadata: AdminRpcRequestMetadata) -> std::io::Result<()> {
+    let mut signals = signal_hook::iterator::Signals::new([signal_hook::consts::SIGTERM])?;
+    thread::Builder::new()
+        .name("solSigTerm".to_string())
+        .spawn(move || {
+            if signals.forever().next().is_some() {
+                warn!("SIGTERM received, exiting the validator");
+                exit_validator(&metadata);
+            }
+        })?;
+    Ok(())
+}
+
+fn unix_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+/// Rejects modules that would change other modules' settings once
+/// formatted in the `RUST_LOG` format
+fn verify_log_module(module: &str) -> Result<()> {
+    if module.is_empty() || module.contains([',', '=', '/']) {
+        return Err(jsonrpc_core::error::Error::invalid_params(format!(
+            "invalid module: {module}"
+        )));
+    }
+    Ok(())
+}
+
 fn rpc_account_index_from_account_index(account_index: &AccountIndex) -> RpcAccountIndex {
     match account_index {
         AccountIndex::ProgramId => RpcAccountIndex::ProgramId,
@@ -864,7 +2049,7 @@ pub fn load_staked_nodes_overrides(
 mod tests {
     use {
         super::*,
-        serde_json::Value,
+        serde_json::{json, Value},
         solana_accounts_db::{
             accounts_db::{AccountsDbConfig, ACCOUNTS_DB_CONFIG_FOR_TESTING},
             accounts_index::AccountSecondaryIndexes,
@@ -876,29 +2061,43 @@ mod tests {
         solana_gossip::cluster_info::{ClusterInfo, Node},
         solana_inline_spl::token,
         solana_ledger::{
+            blockstore::Blockstore,
             create_new_tmp_ledger,
             genesis_utils::{
                 create_genesis_config, create_genesis_config_with_leader, GenesisConfigInfo,
             },
         },
-        solana_net_utils::bind_to_unspecified,
+        solana_net_utils::{bind_to_localhost, bind_to_unspecified},
+        solana_perf::recycler::Recycler,
         solana_rpc::rpc::create_validator_exit,
         solana_runtime::{
             bank::{Bank, BankTestConfig},
             bank_forks::BankForks,
+            snapshot_config::SnapshotConfig,
         },
         solana_sdk::{
             account::{Account, AccountSharedData},
             pubkey::Pubkey,
+            signature::Signature,
             system_program,
         },
-        solana_streamer::socket::SocketAddrSpace,
+        solana_send_transaction_service::inflight_limiter::FeePayerInflightLimiter,
+        solana_streamer::{
+            socket::SocketAddrSpace,
+            streamer::{self, StreamerReceiveStats},
+        },
         solana_tpu_client::tpu_client::DEFAULT_TPU_ENABLE_UDP,
         spl_token_2022::{
             solana_program::{program_option::COption, program_pack::Pack},
             state::{Account as TokenAccount, AccountState as TokenAccountState, Mint},
         },
-        std::{collections::HashSet, fs::remove_dir_all, sync::atomic::AtomicBool},
+        std::{
+            collections::HashSet,
+            fs::remove_dir_all,
+            sync::atomic::{AtomicBool, Ordering},
+            time::Instant,
+        },
+        tempfile::TempDir,
     };
 
     #[derive(Default)]
@@ -910,6 +2109,7 @@ mod tests {
         io: MetaIoHandler<AdminRpcRequestMetadata>,
         meta: AdminRpcRequestMetadata,
         bank_forks: Arc<RwLock<BankForks>>,
+        _ledger_path: TempDir,
     }
 
     impl RpcHandler {
@@ -939,6 +2139,8 @@ mod tests {
             let vote_account = vote_keypair.pubkey();
             let start_progress = Arc::new(RwLock::new(ValidatorStartProgress::default()));
             let repair_whitelist = Arc::new(RwLock::new(HashSet::new()));
+            let write_fenced_accounts = Arc::new(RwLock::new(HashSet::new()));
+            let ledger_path = tempfile::tempdir().unwrap();
             let meta = AdminRpcRequestMetadata {
                 rpc_addr: None,
                 start_time: SystemTime::now(),
@@ -951,6 +2153,7 @@ mod tests {
                     bank_forks: bank_forks.clone(),
                     vote_account,
                     repair_whitelist,
+                    write_fenced_accounts,
                     notifies: Vec::new(),
                     repair_socket: Arc::new(bind_to_unspecified().unwrap()),
                     outstanding_repair_requests: Arc::<
@@ -959,9 +2162,15 @@ mod tests {
                     cluster_slots: Arc::new(
                         solana_core::cluster_slots_service::cluster_slots::ClusterSlots::default(),
                     ),
+                    blockstore: Arc::new(Blockstore::open(ledger_path.path()).unwrap()),
+                    snapshot_config: Arc::new(RwLock::new(SnapshotConfig::default())),
+                    rpc_fee_payer_inflight_limiter: None,
                 }))),
                 staked_nodes_overrides: Arc::new(RwLock::new(HashMap::new())),
                 rpc_to_plugin_manager_sender: None,
+                scheduled_exit: Arc::default(),
+                log_filter: Arc::default(),
+                gossip_custom_values: Arc::default(),
             };
             let mut io = MetaIoHandler::default();
             io.extend_with(AdminRpcImpl.to_delegate());
@@ -970,6 +2179,7 @@ mod tests {
                 io,
                 meta,
                 bank_forks,
+                _ledger_path: ledger_path,
             }
         }
 
@@ -1297,6 +2507,602 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_repair_shreds_from_peer() {
+        let rpc = RpcHandler::start_with_config(TestConfig::default());
+        let RpcHandler { io, meta, .. } = rpc;
+        let repair = |params: &str| {
+            let req = format!(
+                r#"{{"jsonrpc":"2.0","id":1,"method":"repairShredsFromPeer","params":{params}}}"#
+            );
+            let res = io.handle_request_sync(&req, meta.clone());
+            serde_json::from_str::<Value>(&res.expect("actual response"))
+                .expect("actual response deserialization")
+        };
+
+        // Slots missing from the blockstore are requested from shred 0, but no
+        // peer has them.
+        let response = repair("[null, 5, 6, [], 10]");
+        let slot_repairs =
+            serde_json::from_value::<Vec<AdminRpcSlotRepair>>(response["result"].clone()).unwrap();
+        assert_eq!(slot_repairs.len(), 2);
+        for (slot_repair, slot) in slot_repairs.iter().zip(5..) {
+            assert_eq!(slot_repair.slot, slot);
+            assert_eq!(slot_repair.requested, vec![0]);
+            assert_eq!(slot_repair.missing, vec![0]);
+            assert!(!slot_repair.is_full);
+        }
+
+        let response = repair("[null, 5, 6, [[3, 6]], 10]");
+        let slot_repairs =
+            serde_json::from_value::<Vec<AdminRpcSlotRepair>>(response["result"].clone()).unwrap();
+        assert_eq!(slot_repairs[0].requested, vec![3, 4, 5]);
+
+        for params in [
+            "[null, 6, 5, [], 10]",
+            "[null, 0, 1000, [], 10]",
+            "[null, 5, 6, [[3, 3]], 10]",
+            "[null, 5, 6, [[0, 32769]], 10]",
+            "[null, 5, 6, [], 60001]",
+        ] {
+            let response = repair(params);
+            assert_eq!(
+                response["error"]["code"],
+                ErrorCode::InvalidParams.code(),
+                "{params}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_gossip_custom_values() {
+        let rpc = RpcHandler::start_with_config(TestConfig::default());
+        let RpcHandler { io, meta, .. } = rpc;
+        let node_id = meta
+            .post_init
+            .read()
+            .unwrap()
+            .as_ref()
+            .unwrap()
+            .cluster_info
+            .id();
+        let request = |method: &str, params: &str| {
+            let req =
+                format!(r#"{{"jsonrpc":"2.0","id":1,"method":"{method}","params":{params}}}"#);
+            let res = io.handle_request_sync(&req, meta.clone());
+            serde_json::from_str::<Value>(&res.expect("actual response"))
+                .expect("actual response deserialization")
+        };
+
+        let response = request("gossipCustomValues", r#"["maintenance"]"#);
+        assert_eq!(response["result"], Value::Array(vec![]));
+
+        let response = request("publishGossipCustomValue", r#"["maintenance", [1, 2, 3]]"#);
+        assert_eq!(response["result"], Value::Null);
+        let response = request("gossipCustomValues", r#"["maintenance"]"#);
+        let values =
+            serde_json::from_value::<Vec<AdminRpcGossipCustomValue>>(response["result"].clone())
+                .unwrap();
+        assert_eq!(values.len(), 1);
+        assert_eq!(values[0].from, node_id.to_string());
+        assert_eq!(values[0].data, vec![1, 2, 3]);
+        // Values read by previous calls are still returned, with their updates
+        let response = request("publishGossipCustomValue", r#"["maintenance", [4]]"#);
+        assert_eq!(response["result"], Value::Null);
+        let response = request("gossipCustomValues", r#"["maintenance"]"#);
+        let values =
+            serde_json::from_value::<Vec<AdminRpcGossipCustomValue>>(response["result"].clone())
+                .unwrap();
+        assert_eq!(values.len(), 1);
+        assert_eq!(values[0].data, vec![4]);
+        let response = request("gossipCustomValues", r#"["other"]"#);
+        assert_eq!(response["result"], Value::Array(vec![]));
+
+        let large_data = serde_json::to_string(&vec![0u8; MAX_CUSTOM_VALUE_DATA_SIZE + 1]).unwrap();
+        for params in [
+            r#"["", []]"#.to_string(),
+            r#"["not valid", []]"#.to_string(),
+            format!(r#"["maintenance", {large_data}]"#),
+        ] {
+            let response = request("publishGossipCustomValue", &params);
+            assert_eq!(
+                response["error"]["code"],
+                ErrorCode::InvalidParams.code(),
+                "{params}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_signed_contact_info() {
+        let rpc = RpcHandler::start_with_config(TestConfig::default());
+        let RpcHandler { io, meta, .. } = rpc;
+        let node_id = meta
+            .post_init
+            .read()
+            .unwrap()
+            .as_ref()
+            .unwrap()
+            .cluster_info
+            .id();
+        let req = r#"{"jsonrpc":"2.0","id":1,"method":"signedContactInfo","params":[]}"#;
+        let res = io.handle_request_sync(req, meta.clone());
+        let response = serde_json::from_str::<Value>(&res.expect("actual response"))
+            .expect("actual response deserialization");
+        assert_eq!(response["result"]["contactInfo"]["id"], node_id.to_string());
+        assert_eq!(
+            response["result"]["contactInfo"]["feature_set"],
+            solana_version::Version::default().feature_set
+        );
+
+        let signed_contact_info =
+            serde_json::from_value::<AdminRpcSignedContactInfo>(response["result"].clone())
+                .unwrap();
+        let message = BASE64_STANDARD
+            .decode(&signed_contact_info.message)
+            .unwrap();
+        let json = serde_json::to_vec(&signed_contact_info.contact_info).unwrap();
+        assert_eq!(
+            message,
+            OffchainMessage::new(0, &json).unwrap().serialize().unwrap()
+        );
+        assert_eq!(
+            OffchainMessage::deserialize(&message)
+                .unwrap()
+                .get_message(),
+            &json
+        );
+        let signature = Signature::from_str(&signed_contact_info.signature).unwrap();
+        assert!(signature.verify(node_id.as_ref(), &message));
+        // Not valid as a plain signature over the JSON
+        assert!(!signature.verify(node_id.as_ref(), &json));
+    }
+
+    #[test]
+    fn test_gossip_blacklist() {
+        let rpc = RpcHandler::start_with_config(TestConfig::default());
+        let RpcHandler { io, meta, .. } = rpc;
+        let request = |method: &str, params: &str| {
+            let req =
+                format!(r#"{{"jsonrpc":"2.0","id":1,"method":"{method}","params":{params}}}"#);
+            let res = io.handle_request_sync(&req, meta.clone());
+            serde_json::from_str::<Value>(&res.expect("actual response"))
+                .expect("actual response deserialization")
+        };
+
+        let response = request("gossipBlacklist", "[]");
+        assert_eq!(response["result"], Value::Array(vec![]));
+        let params = json!([Pubkey::new_unique().to_string()]).to_string();
+        let response = request("gossipUnban", &params);
+        assert_eq!(response["result"], Value::Bool(false));
+        let response = request("gossipUnban", r#"["not a pubkey"]"#);
+        assert!(response["error"].is_object());
+    }
+
+    #[test]
+    fn test_write_fenced_accounts() {
+        let rpc = RpcHandler::start_with_config(TestConfig::default());
+        let RpcHandler { io, meta, .. } = rpc;
+        let request = |method: &str, params: &str| {
+            let req =
+                format!(r#"{{"jsonrpc":"2.0","id":1,"method":"{method}","params":{params}}}"#);
+            let res = io.handle_request_sync(&req, meta.clone());
+            serde_json::from_str::<Value>(&res.expect("actual response"))
+                .expect("actual response deserialization")
+        };
+
+        let response = request("writeFencedAccounts", "[]");
+        assert_eq!(response["result"], json!({ "accounts": [] }));
+
+        let mut accounts = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+        accounts.sort_unstable();
+        let params = json!([[accounts[1].to_string(), accounts[0].to_string()]]).to_string();
+        let response = request("setWriteFencedAccounts", &params);
+        assert_eq!(response["result"], Value::Null);
+        let response = request("writeFencedAccounts", "[]");
+        let write_fence =
+            serde_json::from_value::<AdminRpcWriteFence>(response["result"].clone()).unwrap();
+        assert_eq!(write_fence.accounts, accounts);
+        assert_eq!(
+            *meta
+                .post_init
+                .read()
+                .unwrap()
+                .as_ref()
+                .unwrap()
+                .write_fenced_accounts
+                .read()
+                .unwrap(),
+            accounts.iter().copied().collect::<HashSet<_>>()
+        );
+
+        let response = request("setWriteFencedAccounts", "[[]]");
+        assert_eq!(response["result"], Value::Null);
+        let response = request("writeFencedAccounts", "[]");
+        assert_eq!(response["result"], json!({ "accounts": [] }));
+    }
+
+    #[test]
+    fn test_set_public_rpc_address() {
+        let rpc = RpcHandler::start_with_config(TestConfig::default());
+        let RpcHandler { io, meta, .. } = rpc;
+        let cluster_info = meta
+            .post_init
+            .read()
+            .unwrap()
+            .as_ref()
+            .unwrap()
+            .cluster_info
+            .clone();
+        let request = |public_rpc_addr: &str| {
+            let method = "setPublicRpcAddress";
+            let req = format!(
+                r#"{{"jsonrpc":"2.0","id":1,"method":"{method}","params":["{public_rpc_addr}"]}}"#
+            );
+            let res = io.handle_request_sync(&req, meta.clone());
+            serde_json::from_str::<Value>(&res.expect("actual response"))
+                .expect("actual response deserialization")
+        };
+
+        // The node does not advertise an RPC address, so there is nothing to override
+        let response = request("127.0.0.1:8899");
+        assert_eq!(response["error"]["code"], ErrorCode::InternalError.code());
+        assert_eq!(cluster_info.my_contact_info().rpc(), None);
+
+        cluster_info
+            .set_rpc(SocketAddr::from(([127, 0, 0, 1], 8899)))
+            .unwrap();
+        let response = request("10.0.0.1:443");
+        assert_eq!(response["result"], Value::Null);
+        assert_eq!(
+            cluster_info.my_contact_info().rpc(),
+            Some(SocketAddr::from(([10, 0, 0, 1], 443)))
+        );
+    }
+
+    #[test]
+    fn test_module_log_levels() {
+        let rpc = RpcHandler::start_with_config(TestConfig::default());
+        let RpcHandler { io, meta, .. } = rpc;
+        let request = |method: &str, params: Value| {
+            let req = json!({"jsonrpc": "2.0", "id": 1, "method": method, "params": params});
+            let res = io.handle_request_sync(&req.to_string(), meta.clone());
+            serde_json::from_str::<Value>(&res.expect("actual response"))
+                .expect("actual response deserialization")
+        };
+        let log_filter = || {
+            serde_json::from_value::<AdminRpcLogFilter>(
+                request("logFilter", json!([]))["result"].clone(),
+            )
+            .unwrap()
+        };
+
+        let result = request(
+            "setModuleLogLevel",
+            json!(["solana_core::replay_stage", "DEBUG"]),
+        );
+        assert_eq!(result["result"], Value::Null, "{result}");
+        let result = request("setModuleLogLevel", json!(["solana_metrics", "off"]));
+        assert_eq!(result["result"], Value::Null, "{result}");
+        let result = request("setLogFilter", json!(["warn"]));
+        assert_eq!(result["result"], Value::Null, "{result}");
+        assert_eq!(
+            log_filter(),
+            AdminRpcLogFilter {
+                filter: "warn".to_string(),
+                module_levels: BTreeMap::from([
+                    ("solana_core::replay_stage".to_string(), "debug".to_string()),
+                    ("solana_metrics".to_string(), "off".to_string()),
+                ]),
+                sample_rates: BTreeMap::default(),
+            }
+        );
+        assert_eq!(
+            meta.log_filter.read().unwrap().to_string(),
+            "warn,solana_core::replay_stage=debug,solana_metrics=off"
+        );
+
+        for params in [
+            json!(["solana_core", "loud"]),
+            json!(["", "info"]),
+            json!(["solana_core,solana_gossip", "info"]),
+        ] {
+            let result = request("setModuleLogLevel", params);
+            assert_eq!(
+                result["error"]["code"],
+                ErrorCode::InvalidParams.code(),
+                "{result}"
+            );
+        }
+
+        let result = request("setModuleLogLevel", json!(["solana_metrics", null]));
+        assert_eq!(result["result"], Value::Null, "{result}");
+        assert_eq!(
+            log_filter().module_levels,
+            BTreeMap::from([("solana_core::replay_stage".to_string(), "debug".to_string())])
+        );
+
+        // Sampling needs the validator's logger, which tests don't install
+        for params in [
+            json!(["solana_metrics", 10]),
+            json!(["solana_metrics", 0]),
+            json!(["solana_core,solana_gossip", 10]),
+        ] {
+            let result = request("setModuleLogSampleRate", params);
+            assert_eq!(
+                result["error"]["code"],
+                ErrorCode::InvalidParams.code(),
+                "{result}"
+            );
+        }
+        let result = request("setModuleLogSampleRate", json!(["solana_metrics", null]));
+        assert_eq!(result["result"], Value::Null, "{result}");
+        assert!(log_filter().sample_rates.is_empty());
+    }
+
+    #[test]
+    fn test_schedule_exit() {
+        let rpc = RpcHandler::start_with_config(TestConfig::default());
+        let RpcHandler { io, meta, .. } = rpc;
+        let request = |method: &str, params: Value| {
+            let req = json!({"jsonrpc": "2.0", "id": 1, "method": method, "params": params});
+            let res = io.handle_request_sync(&req.to_string(), meta.clone());
+            serde_json::from_str::<Value>(&res.expect("actual response"))
+                .expect("actual response deserialization")
+        };
+        let scheduled_exit = || {
+            serde_json::from_value::<Option<AdminRpcScheduledExit>>(
+                request("scheduledExit", json!([]))["result"].clone(),
+            )
+            .unwrap()
+        };
+        assert_eq!(scheduled_exit(), None);
+
+        // Far enough in the future that the test never exits
+        let result = request("scheduleExit", json!([null, 3600]));
+        let at_unix_timestamp = result["result"]["atUnixTimestamp"].as_u64().unwrap();
+        assert!(at_unix_timestamp >= unix_timestamp() + 3590);
+        assert_eq!(
+            scheduled_exit(),
+            Some(AdminRpcScheduledExit {
+                at_slot: None,
+                at_unix_timestamp: Some(at_unix_timestamp),
+            })
+        );
+
+        // A later request replaces the scheduled exit
+        let expected = AdminRpcScheduledExit {
+            at_slot: Some(Slot::MAX),
+            at_unix_timestamp: None,
+        };
+        let result = request("scheduleExit", json!([Slot::MAX, null]));
+        assert_eq!(
+            serde_json::from_value::<AdminRpcScheduledExit>(result["result"].clone()).unwrap(),
+            expected
+        );
+        assert_eq!(scheduled_exit(), Some(expected.clone()));
+
+        let result = request("scheduleExit", json!([1, 1]));
+        assert_eq!(
+            result["error"]["code"],
+            ErrorCode::InvalidParams.code(),
+            "{result}"
+        );
+        assert_eq!(scheduled_exit(), Some(expected.clone()));
+
+        let result = request("cancelScheduledExit", json!([]));
+        assert_eq!(
+            serde_json::from_value::<Option<AdminRpcScheduledExit>>(result["result"].clone())
+                .unwrap(),
+            Some(expected)
+        );
+        assert_eq!(scheduled_exit(), None);
+        let result = request("cancelScheduledExit", json!([]));
+        assert_eq!(result["result"], Value::Null);
+    }
+
+    #[test]
+    fn test_accounts_maintenance() {
+        let rpc = RpcHandler::start_with_config(TestConfig::default());
+        let bank = rpc.root_bank();
+        let RpcHandler { io, meta, .. } = rpc;
+        let get_status = || {
+            let req = r#"{"jsonrpc":"2.0","id":1,"method":"accountsMaintenanceStatus"}"#;
+            let res = io.handle_request_sync(req, meta.clone());
+            let result: Value = serde_json::from_str(&res.expect("actual response"))
+                .expect("actual response deserialization");
+            serde_json::from_value::<AdminRpcAccountsMaintenanceStatus>(result["result"].clone())
+                .unwrap()
+        };
+
+        let status = get_status();
+        assert!(!status.paused);
+        assert_eq!(status.io_limit_bytes_per_second, None);
+
+        let req = r#"{"jsonrpc":"2.0","id":1,"method":"pauseAccountsMaintenance"}"#;
+        io.handle_request_sync(req, meta.clone()).unwrap();
+        assert!(get_status().paused);
+        assert!(bank
+            .accounts()
+            .accounts_db
+            .background_io_budget()
+            .is_paused());
+
+        let req = r#"{"jsonrpc":"2.0","id":1,"method":"setAccountsMaintenanceIoLimit","params":[1000000]}"#;
+        io.handle_request_sync(req, meta.clone()).unwrap();
+        assert_eq!(get_status().io_limit_bytes_per_second, Some(1_000_000));
+
+        let req =
+            r#"{"jsonrpc":"2.0","id":1,"method":"setAccountsMaintenanceIoLimit","params":[null]}"#;
+        io.handle_request_sync(req, meta.clone()).unwrap();
+        assert_eq!(get_status().io_limit_bytes_per_second, None);
+
+        let req = r#"{"jsonrpc":"2.0","id":1,"method":"resumeAccountsMaintenance"}"#;
+        io.handle_request_sync(req, meta.clone()).unwrap();
+        assert!(!get_status().paused);
+    }
+
+    #[test]
+    fn test_socket_receive_stats() {
+        let RpcHandler { io, meta, .. } = RpcHandler::start_with_config(TestConfig::default());
+        let get_stats = |addr| {
+            let req = r#"{"jsonrpc":"2.0","id":1,"method":"socketReceiveStats"}"#;
+            let res = io.handle_request_sync(req, meta.clone());
+            let result: Value = serde_json::from_str(&res.expect("actual response"))
+                .expect("actual response deserialization");
+            serde_json::from_value::<Vec<AdminRpcSocketReceiveStats>>(result["result"].clone())
+                .unwrap()
+                .into_iter()
+                .find(|stats| stats.addr == addr)
+        };
+
+        let socket = Arc::new(bind_to_localhost().unwrap());
+        let addr = socket.local_addr().unwrap();
+        assert_eq!(get_stats(addr), None);
+
+        let exit = Arc::new(AtomicBool::new(false));
+        let (packet_batch_sender, packet_batch_receiver) = crossbeam_channel::unbounded();
+        let t_receiver = streamer::receiver(
+            "solRcvrTest".to_string(),
+            socket,
+            exit.clone(),
+            packet_batch_sender,
+            Recycler::default(),
+            Arc::new(StreamerReceiveStats::new("test_socket_receive_stats")),
+            Duration::from_millis(1),
+            true,
+            None,
+            false,
+        );
+        let sender = bind_to_localhost().unwrap();
+        for _ in 0..10 {
+            sender.send_to(&[0; 64], addr).unwrap();
+        }
+        let mut num_packets = 0;
+        while num_packets < 10 {
+            num_packets += packet_batch_receiver
+                .recv_timeout(Duration::from_secs(5))
+                .unwrap()
+                .len();
+        }
+        // Stats are recorded once batches are handed over
+        let start = Instant::now();
+        let stats = loop {
+            let stats = get_stats(addr).unwrap();
+            if stats.packets_received == 10 || start.elapsed() > Duration::from_secs(5) {
+                break stats;
+            }
+            thread::sleep(Duration::from_millis(10));
+        };
+        assert_eq!(stats.name, "test_socket_receive_stats");
+        assert_eq!(stats.packets_received, 10);
+        assert_eq!(stats.packets_dropped_in_process, 0);
+        if cfg!(target_os = "linux") {
+            assert_eq!(stats.packets_dropped_by_os, Some(0));
+        }
+
+        exit.store(true, Ordering::Relaxed);
+        t_receiver.join().unwrap();
+    }
+
+    #[test]
+    fn test_reload_config() {
+        let rpc = RpcHandler::start_with_config(TestConfig::default());
+        rpc.bank_forks
+            .write()
+            .unwrap()
+            .set_accounts_hash_interval_slots(100);
+        let bank = rpc.root_bank();
+        let RpcHandler { io, meta, .. } = rpc;
+        let reload_config = |config: Value| {
+            let req = json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "reloadConfig",
+                "params": [config],
+            });
+            let res = io.handle_request_sync(&req.to_string(), meta.clone());
+            let result: Value = serde_json::from_str(&res.expect("actual response"))
+                .expect("actual response deserialization");
+            match result.get("result") {
+                Some(config) => {
+                    Ok(serde_json::from_value::<AdminRpcReloadableConfig>(config.clone()).unwrap())
+                }
+                None => Err(result["error"]["message"].as_str().unwrap().to_string()),
+            }
+        };
+        let snapshot_intervals = || {
+            let post_init = meta.post_init.read().unwrap();
+            let snapshot_config = post_init.as_ref().unwrap().snapshot_config.read().unwrap();
+            (
+                snapshot_config.full_snapshot_archive_interval_slots,
+                snapshot_config.incremental_snapshot_archive_interval_slots,
+            )
+        };
+
+        // An empty request changes nothing and returns the current settings
+        let config = reload_config(json!({})).unwrap();
+        let default_snapshot_config = SnapshotConfig::default();
+        assert_eq!(
+            config,
+            AdminRpcReloadableConfig {
+                full_snapshot_interval_slots: Some(
+                    default_snapshot_config.full_snapshot_archive_interval_slots
+                ),
+                incremental_snapshot_interval_slots: Some(
+                    default_snapshot_config.incremental_snapshot_archive_interval_slots
+                ),
+                accounts_db_write_cache_limit_bytes: Some(
+                    bank.accounts().accounts_db.write_cache_limit_bytes()
+                ),
+                rpc_max_inflight_transactions_per_fee_payer: None,
+            }
+        );
+
+        let config = reload_config(json!({
+            "fullSnapshotIntervalSlots": 1000,
+            "incrementalSnapshotIntervalSlots": 200,
+            "accountsDbWriteCacheLimitBytes": 1_000_000,
+        }))
+        .unwrap();
+        assert_eq!(config.full_snapshot_interval_slots, Some(1000));
+        assert_eq!(config.incremental_snapshot_interval_slots, Some(200));
+        assert_eq!(config.accounts_db_write_cache_limit_bytes, Some(1_000_000));
+        assert_eq!(snapshot_intervals(), (1000, 200));
+        assert_eq!(
+            bank.accounts().accounts_db.write_cache_limit_bytes(),
+            1_000_000
+        );
+
+        // A rejected request applies none of its settings
+        assert!(reload_config(json!({
+            "incrementalSnapshotIntervalSlots": 150,
+            "accountsDbWriteCacheLimitBytes": 2_000_000,
+        }))
+        .is_err());
+        assert!(reload_config(json!({"incrementalSnapshotIntervalSlots": 1000})).is_err());
+        assert!(reload_config(json!({"rpcMaxInflightTransactionsPerFeePayer": 10})).is_err());
+        assert_eq!(snapshot_intervals(), (1000, 200));
+        assert_eq!(
+            bank.accounts().accounts_db.write_cache_limit_bytes(),
+            1_000_000
+        );
+
+        // The inflight limit can only be changed if it was enabled at startup
+        let inflight_limiter = Arc::new(FeePayerInflightLimiter::new(5));
+        meta.post_init
+            .write()
+            .unwrap()
+            .as_mut()
+            .unwrap()
+            .rpc_fee_payer_inflight_limiter = Some(inflight_limiter.clone());
+        let config = reload_config(json!({"rpcMaxInflightTransactionsPerFeePayer": 10})).unwrap();
+        assert_eq!(config.rpc_max_inflight_transactions_per_fee_payer, Some(10));
+        assert_eq!(inflight_limiter.max_inflight_per_fee_payer(), 10);
+        assert!(reload_config(json!({"rpcMaxInflightTransactionsPerFeePayer": 0})).is_err());
+        assert_eq!(inflight_limiter.max_inflight_per_fee_payer(), 10);
+    }
+
     // This test checks that the rpc call to `set_identity` works a expected with
     // Bank but without validator.
     #[test]
@@ -1381,6 +3187,9 @@ mod tests {
                 post_init: post_init.clone(),
                 staked_nodes_overrides: Arc::new(RwLock::new(HashMap::new())),
                 rpc_to_plugin_manager_sender: None,
+                scheduled_exit: Arc::default(),
+                log_filter: Arc::default(),
+                gossip_custom_values: Arc::default(),
             };
 
             let _validator = Validator::new(
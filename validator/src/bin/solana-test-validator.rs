@@ -407,6 +407,9 @@ fn main() {
             post_init: admin_service_post_init,
             tower_storage: tower_storage.clone(),
             rpc_to_plugin_manager_sender,
+            scheduled_exit: Arc::default(),
+            log_filter: Arc::default(),
+            gossip_custom_values: Arc::default(),
         },
     );
     let dashboard = if output == Output::Dashboard {
@@ -3,7 +3,7 @@ use {
     log::*,
     rand::{seq::SliceRandom, thread_rng, Rng},
     rayon::prelude::*,
-    solana_core::validator::{ValidatorConfig, ValidatorStartProgress},
+    solana_core::validator::{SnapshotBankHashSource, ValidatorConfig, ValidatorStartProgress},
     solana_download_utils::{download_snapshot_archive, DownloadProgressRecord},
     solana_genesis_utils::download_then_check_genesis_hash,
     solana_gossip::{
@@ -27,10 +27,11 @@ use {
     },
     solana_streamer::socket::SocketAddrSpace,
     std::{
-        collections::{hash_map::RandomState, HashMap, HashSet},
+        collections::{hash_map::RandomState, BTreeMap, HashMap, HashSet},
         net::{SocketAddr, TcpListener, TcpStream, UdpSocket},
         path::Path,
         process::exit,
+        str::FromStr,
         sync::{
             atomic::{AtomicBool, Ordering},
             Arc, RwLock,
@@ -57,6 +58,14 @@ pub const MAX_RPC_CONNECTIONS_EVALUATED_PER_ITERATION: usize = 32;
 
 pub const PING_TIMEOUT: Duration = Duration::from_secs(2);
 
+/// Number of slots, ending at the slot of the bank loaded at startup, whose bank hashes are
+/// requested from each snapshot verification RPC node
+const SNAPSHOT_VERIFICATION_SLOTS: usize = 32;
+/// How many times to query the snapshot verification RPC nodes before giving up, and how long to
+/// wait in between
+const SNAPSHOT_VERIFICATION_ATTEMPTS: usize = 10;
+const SNAPSHOT_VERIFICATION_RETRY_DELAY: Duration = Duration::from_secs(5);
+
 #[derive(Debug)]
 pub struct RpcBootstrapConfig {
     pub no_genesis_fetch: bool,
@@ -809,6 +818,114 @@ fn get_rpc_nodes(
     }
 }
 
+/// Bank hashes agreed on by a quorum of independent RPC nodes, so that the bank the validator
+/// starts from is verified once loaded instead of being trusted because its snapshot hash matches
+/// the one gossiped by a known validator
+pub struct RpcQuorumBankHashSource {
+    rpc_clients: Vec<(String, RpcClient)>,
+    /// Number of RPC nodes which must report the same bank hash for it to be trusted
+    quorum: usize,
+}
+
+impl RpcQuorumBankHashSource {
+    pub fn new(rpc_urls: Vec<String>, quorum: usize) -> Self {
+        let rpc_clients = rpc_urls
+            .into_iter()
+            .map(|rpc_url| {
+                let rpc_client = RpcClient::new(rpc_url.clone());
+                (rpc_url, rpc_client)
+            })
+            .collect();
+        Self {
+            rpc_clients,
+            quorum,
+        }
+    }
+}
+
+impl SnapshotBankHashSource for RpcQuorumBankHashSource {
+    fn bank_hashes(&self, slot: Slot) -> Result<BTreeMap<Slot, Hash>, String> {
+        let start_slot = slot.saturating_sub(SNAPSHOT_VERIFICATION_SLOTS.saturating_sub(1) as Slot);
+        for attempt in 1..=SNAPSHOT_VERIFICATION_ATTEMPTS {
+            let reported_bank_hashes: Vec<_> = self
+                .rpc_clients
+                .iter()
+                .map(|(rpc_url, rpc_client)| {
+                    rpc_client
+                        .get_slot_hashes(start_slot, SNAPSHOT_VERIFICATION_SLOTS)
+                        .map_err(|err| err.to_string())
+                        .and_then(|slot_hashes| {
+                            slot_hashes
+                                .into_iter()
+                                .map(|slot_hash| {
+                                    Hash::from_str(&slot_hash.hash)
+                                        .map(|hash| (slot_hash.slot, hash))
+                                        .map_err(|err| err.to_string())
+                                })
+                                .collect::<Result<Vec<_>, _>>()
+                        })
+                        .unwrap_or_else(|err| {
+                            warn!("Failed to get bank hashes from {rpc_url}: {err}");
+                            vec![]
+                        })
+                })
+                .collect();
+            let bank_hashes = get_quorum_bank_hashes(&reported_bank_hashes, self.quorum);
+            if let Some(hash) = bank_hashes.get(&slot) {
+                info!(
+                    "{} of {} RPC nodes agree on bank hash {hash} for slot {slot}",
+                    reported_bank_hashes
+                        .iter()
+                        .filter(|bank_hashes| bank_hashes.contains(&(slot, *hash)))
+                        .count(),
+                    self.rpc_clients.len(),
+                );
+                return Ok(bank_hashes);
+            }
+            warn!(
+                "Snapshot verification RPC nodes do not agree on a bank hash for slot {slot}, \
+                 attempt {attempt} of {SNAPSHOT_VERIFICATION_ATTEMPTS}"
+            );
+            if attempt < SNAPSHOT_VERIFICATION_ATTEMPTS {
+                std::thread::sleep(SNAPSHOT_VERIFICATION_RETRY_DELAY);
+            }
+        }
+        Err(format!(
+            "fewer than {} RPC nodes agree on the bank hash of slot {slot}",
+            self.quorum
+        ))
+    }
+}
+
+/// Returns the bank hashes reported by at least `quorum` of the sources. Slots for which more
+/// than one bank hash reaches the quorum are left out.
+fn get_quorum_bank_hashes(
+    reported_bank_hashes: &[Vec<(Slot, Hash)>],
+    quorum: usize,
+) -> BTreeMap<Slot, Hash> {
+    let mut counts = HashMap::<(Slot, Hash), usize>::new();
+    for slot_hash in reported_bank_hashes
+        .iter()
+        .flat_map(|bank_hashes| bank_hashes.iter().copied().unique())
+    {
+        *counts.entry(slot_hash).or_default() += 1;
+    }
+    counts
+        .into_iter()
+        .filter(|(_, count)| *count >= quorum)
+        .map(|(slot_hash, _)| slot_hash)
+        .into_group_map()
+        .into_iter()
+        .filter_map(|(slot, hashes)| match hashes.as_slice() {
+            [hash] => Some((slot, *hash)),
+            _ => {
+                warn!("Conflicting bank hashes reached the quorum for slot {slot}: {hashes:?}");
+                None
+            }
+        })
+        .collect()
+}
+
 /// Get the Slot and Hash of the local snapshot with the highest slot.  Can be either a full
 /// snapshot or an incremental snapshot.
 fn get_highest_local_snapshot_hash(
@@ -1696,4 +1813,31 @@ mod tests {
             assert_eq!(expected, actual);
         }
     }
+
+    #[test]
+    fn test_get_quorum_bank_hashes() {
+        let (hash1, hash2, hash3) = (Hash::new_unique(), Hash::new_unique(), Hash::new_unique());
+        let reported_bank_hashes = vec![
+            vec![(1, hash1), (2, hash2), (3, hash3)],
+            // Repeated reports from one source count once
+            vec![(1, hash1), (1, hash1), (3, hash3)],
+            vec![(2, hash3), (3, hash3)],
+            vec![],
+        ];
+
+        assert_eq!(
+            get_quorum_bank_hashes(&reported_bank_hashes, 3),
+            BTreeMap::from([(3, hash3)]),
+        );
+        assert_eq!(
+            get_quorum_bank_hashes(&reported_bank_hashes, 2),
+            BTreeMap::from([(1, hash1), (3, hash3)]),
+        );
+        // Slot 2 has two bank hashes with one report each, so it is left out
+        assert_eq!(
+            get_quorum_bank_hashes(&reported_bank_hashes, 1),
+            BTreeMap::from([(1, hash1), (3, hash3)]),
+        );
+        assert!(get_quorum_bank_hashes(&reported_bank_hashes, 4).is_empty());
+    }
 }
@@ -14,11 +14,20 @@ use {
             is_parsable, is_pubkey, is_pubkey_or_keypair, is_slot, is_url_or_moniker,
         },
     },
-    solana_core::banking_trace::BANKING_TRACE_DIR_DEFAULT_BYTE_LIMIT,
+    solana_core::{
+        accounts_replication_service::DEFAULT_ACCOUNTS_REPLICATION_CATCH_UP_SLOTS,
+        banking_stage::PrioGraphSchedulerConfig,
+        banking_trace::BANKING_TRACE_DIR_DEFAULT_BYTE_LIMIT,
+    },
     solana_faucet::faucet::{self, FAUCET_PORT},
     solana_net_utils::{MINIMUM_VALIDATOR_PORT_RANGE_WIDTH, VALIDATOR_PORT_RANGE},
     solana_rayon_threadlimit::get_thread_count,
-    solana_rpc::{rpc::MAX_REQUEST_BODY_SIZE, rpc_pubsub_service::PubSubConfig},
+    solana_rpc::{
+        rpc::{
+            DEFAULT_PREFLIGHT_CACHE_CAPACITY, DEFAULT_PREFLIGHT_CACHE_TTL_MS, MAX_REQUEST_BODY_SIZE,
+        },
+        rpc_pubsub_service::PubSubConfig,
+    },
     solana_rpc_client_api::request::{DELINQUENT_VALIDATOR_SLOT_DISTANCE, MAX_MULTIPLE_ACCOUNTS},
     solana_runtime::{
         snapshot_bank_utils::{
@@ -67,18 +76,24 @@ pub fn app<'a>(version: &'a str, default_args: &'a DefaultArgs) -> App<'a, 'a> {
         .subcommand(commands::exit::command(default_args))
         .subcommand(commands::authorized_voter::command(default_args))
         .subcommand(commands::contact_info::command(default_args))
+        .subcommand(commands::gossip_blacklist::command(default_args))
         .subcommand(commands::repair_shred_from_peer::command(default_args))
         .subcommand(commands::repair_whitelist::command(default_args))
+        .subcommand(commands::retransmit_tree::command(default_args))
+        .subcommand(commands::accounts_maintenance::command(default_args))
         .subcommand(
             SubCommand::with_name("init").about("Initialize the ledger directory then exit"),
         )
         .subcommand(commands::monitor::command(default_args))
         .subcommand(SubCommand::with_name("run").about("Run the validator"))
         .subcommand(commands::plugin::command(default_args))
+        .subcommand(commands::reload_config::command(default_args))
         .subcommand(commands::set_identity::command(default_args))
         .subcommand(commands::set_log_filter::command(default_args))
+        .subcommand(commands::socket_stats::command(default_args))
         .subcommand(commands::staked_nodes_overrides::command(default_args))
         .subcommand(commands::wait_for_restart_window::command(default_args))
+        .subcommand(commands::write_fence::command(default_args))
         .subcommand(commands::set_public_address::command(default_args));
 
     commands::run::add_args(app, default_args)
@@ -384,6 +399,7 @@ pub struct DefaultArgs {
     pub rpc_send_transaction_retry_ms: String,
     pub rpc_send_transaction_batch_ms: String,
     pub rpc_send_transaction_leader_forward_count: String,
+    pub rpc_send_transaction_leader_prefetch_count: String,
     pub rpc_send_transaction_service_max_retries: String,
     pub rpc_send_transaction_batch_size: String,
     pub rpc_send_transaction_retry_pool_max_size: String,
@@ -395,9 +411,13 @@ pub struct DefaultArgs {
     pub rpc_bigtable_app_profile_id: String,
     pub rpc_bigtable_max_message_size: String,
     pub rpc_max_request_body_size: String,
+    pub rpc_preflight_cache_ttl_ms: String,
+    pub rpc_preflight_cache_capacity: String,
     pub rpc_pubsub_worker_threads: String,
     pub rpc_pubsub_notification_threads: String,
 
+    pub accounts_replication_catch_up_slots: String,
+
     pub maximum_local_snapshot_age: String,
     pub maximum_full_snapshot_archives_to_retain: String,
     pub maximum_incremental_snapshot_archives_to_retain: String,
@@ -441,6 +461,9 @@ pub struct DefaultArgs {
     pub wait_for_restart_window_max_delinquent_stake: String,
 
     pub banking_trace_dir_byte_limit: String,
+    pub banking_stage_spill_limit_mb: String,
+    pub banking_stage_look_ahead_window_size: String,
+    pub banking_stage_age_priority_weight: String,
 
     pub wen_restart_path: String,
 
@@ -480,6 +503,9 @@ impl DefaultArgs {
             rpc_send_transaction_leader_forward_count: default_send_transaction_service_config
                 .leader_forward_count
                 .to_string(),
+            rpc_send_transaction_leader_prefetch_count: default_send_transaction_service_config
+                .leader_prefetch_count
+                .to_string(),
             rpc_send_transaction_service_max_retries: default_send_transaction_service_config
                 .service_max_retries
                 .to_string(),
@@ -500,6 +526,8 @@ impl DefaultArgs {
                 .to_string(),
             rpc_pubsub_worker_threads: "4".to_string(),
             rpc_pubsub_notification_threads: get_thread_count().to_string(),
+            accounts_replication_catch_up_slots: DEFAULT_ACCOUNTS_REPLICATION_CATCH_UP_SLOTS
+                .to_string(),
             maximum_full_snapshot_archives_to_retain: DEFAULT_MAX_FULL_SNAPSHOT_ARCHIVES_TO_RETAIN
                 .to_string(),
             maximum_incremental_snapshot_archives_to_retain:
@@ -535,11 +563,20 @@ impl DefaultArgs {
             tpu_max_streams_per_ms: DEFAULT_MAX_STREAMS_PER_MS.to_string(),
             num_quic_endpoints: DEFAULT_QUIC_ENDPOINTS.to_string(),
             rpc_max_request_body_size: MAX_REQUEST_BODY_SIZE.to_string(),
+            rpc_preflight_cache_ttl_ms: DEFAULT_PREFLIGHT_CACHE_TTL_MS.to_string(),
+            rpc_preflight_cache_capacity: DEFAULT_PREFLIGHT_CACHE_CAPACITY.to_string(),
             exit_min_idle_time: "10".to_string(),
             exit_max_delinquent_stake: "5".to_string(),
             wait_for_restart_window_min_idle_time: "10".to_string(),
             wait_for_restart_window_max_delinquent_stake: "5".to_string(),
             banking_trace_dir_byte_limit: BANKING_TRACE_DIR_DEFAULT_BYTE_LIMIT.to_string(),
+            banking_stage_spill_limit_mb: "1024".to_string(),
+            banking_stage_look_ahead_window_size: PrioGraphSchedulerConfig::default()
+                .look_ahead_window_size
+                .to_string(),
+            banking_stage_age_priority_weight: PrioGraphSchedulerConfig::default()
+                .age_priority_weight
+                .to_string(),
             wen_restart_path: "wen_restart_progress.proto".to_string(),
             thread_args: DefaultThreadArgs::default(),
         }
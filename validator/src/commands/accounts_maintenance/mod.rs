@@ -0,0 +1,124 @@
+use {
+    crate::{admin_rpc_service, cli::DefaultArgs},
+    clap::{value_t_or_exit, App, AppSettings, Arg, ArgMatches, SubCommand},
+    solana_clap_utils::input_validators::is_parsable,
+    std::{path::Path, process::exit},
+};
+
+pub fn command(_default_args: &DefaultArgs) -> App<'_, '_> {
+    SubCommand::with_name("accounts-maintenance")
+        .about("Manage the validator's background accounts clean and shrink")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .setting(AppSettings::InferSubcommands)
+        .subcommand(
+            SubCommand::with_name("status")
+                .about("Display the state of background accounts clean and shrink")
+                .arg(
+                    Arg::with_name("output")
+                        .long("output")
+                        .takes_value(true)
+                        .value_name("MODE")
+                        .possible_values(&["json", "json-compact"])
+                        .help("Output display mode"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("pause")
+                .about("Stop starting new background accounts clean and shrink passes")
+                .after_help(
+                    "Note: accounts maintenance changes only apply to the currently running \
+                     validator instance",
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("resume")
+                .about("Resume background accounts clean and shrink")
+                .after_help(
+                    "Note: accounts maintenance changes only apply to the currently running \
+                     validator instance",
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("set-io-limit")
+                .about("Set the disk bandwidth available to background accounts clean and shrink")
+                .setting(AppSettings::ArgRequiredElseHelp)
+                .arg(
+                    Arg::with_name("bytes_per_second")
+                        .index(1)
+                        .value_name("BYTES_PER_SECOND")
+                        .takes_value(true)
+                        .validator(is_parsable::<u64>)
+                        .required_unless("unlimited")
+                        .help("Bytes per second available to background clean and shrink"),
+                )
+                .arg(
+                    Arg::with_name("unlimited")
+                        .long("unlimited")
+                        .conflicts_with("bytes_per_second")
+                        .help("Remove the limit on background clean and shrink IO"),
+                )
+                .after_help(
+                    "Note: accounts maintenance changes only apply to the currently running \
+                     validator instance",
+                ),
+        )
+}
+
+pub fn execute(matches: &ArgMatches, ledger_path: &Path) {
+    match matches.subcommand() {
+        ("status", Some(subcommand_matches)) => {
+            let output_mode = subcommand_matches.value_of("output");
+            let admin_client = admin_rpc_service::connect(ledger_path);
+            let status = admin_rpc_service::runtime()
+                .block_on(async move { admin_client.await?.accounts_maintenance_status().await })
+                .unwrap_or_else(|err| {
+                    eprintln!("Accounts maintenance status query failed: {err}");
+                    exit(1);
+                });
+            if let Some(mode) = output_mode {
+                match mode {
+                    "json" => println!("{}", serde_json::to_string_pretty(&status).unwrap()),
+                    "json-compact" => print!("{}", serde_json::to_string(&status).unwrap()),
+                    _ => unreachable!(),
+                }
+            } else {
+                print!("{status}");
+            }
+        }
+        ("pause", _) => {
+            let admin_client = admin_rpc_service::connect(ledger_path);
+            admin_rpc_service::runtime()
+                .block_on(async move { admin_client.await?.pause_accounts_maintenance().await })
+                .unwrap_or_else(|err| {
+                    eprintln!("pauseAccountsMaintenance request failed: {err}");
+                    exit(1);
+                });
+        }
+        ("resume", _) => {
+            let admin_client = admin_rpc_service::connect(ledger_path);
+            admin_rpc_service::runtime()
+                .block_on(async move { admin_client.await?.resume_accounts_maintenance().await })
+                .unwrap_or_else(|err| {
+                    eprintln!("resumeAccountsMaintenance request failed: {err}");
+                    exit(1);
+                });
+        }
+        ("set-io-limit", Some(subcommand_matches)) => {
+            let bytes_per_second = (!subcommand_matches.is_present("unlimited"))
+                .then(|| value_t_or_exit!(subcommand_matches, "bytes_per_second", u64));
+            let admin_client = admin_rpc_service::connect(ledger_path);
+            admin_rpc_service::runtime()
+                .block_on(async move {
+                    admin_client
+                        .await?
+                        .set_accounts_maintenance_io_limit(bytes_per_second)
+                        .await
+                })
+                .unwrap_or_else(|err| {
+                    eprintln!("setAccountsMaintenanceIoLimit request failed: {err}");
+                    exit(1);
+                });
+        }
+        _ => unreachable!(),
+    }
+}
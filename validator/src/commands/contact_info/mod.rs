@@ -1,7 +1,8 @@
 use {
     crate::{admin_rpc_service, cli::DefaultArgs, commands::FromClapArgMatches},
     clap::{App, Arg, ArgMatches, SubCommand},
-    std::{path::Path, process::exit},
+    serde::Serialize,
+    std::{fmt::Display, path::Path, process::exit},
 };
 
 const COMMAND: &str = "contact-info";
@@ -9,12 +10,14 @@ const COMMAND: &str = "contact-info";
 #[derive(Debug, PartialEq)]
 pub struct ContactInfoArgs {
     pub output: Option<String>,
+    pub sign: bool,
 }
 
 impl FromClapArgMatches for ContactInfoArgs {
     fn from_clap_arg_match(matches: &ArgMatches) -> Self {
         ContactInfoArgs {
             output: matches.value_of("output").map(String::from),
+            sign: matches.is_present("sign"),
         }
     }
 }
@@ -30,22 +33,42 @@ pub fn command(_default_args: &DefaultArgs) -> App<'_, '_> {
                 .possible_values(&["json", "json-compact"])
                 .help("Output display mode"),
         )
+        .arg(Arg::with_name("sign").long("sign").takes_value(false).help(
+            "Sign the contact info with the validator identity, for registration with \
+             external services. The compact JSON serialization of the contact info is \
+             signed as an off-chain message, which is returned along with the signature",
+        ))
 }
 
 pub fn execute(matches: &ArgMatches, ledger_path: &Path) {
     let contact_info_args = ContactInfoArgs::from_clap_arg_match(matches);
 
     let admin_client = admin_rpc_service::connect(ledger_path);
-    let contact_info = admin_rpc_service::runtime()
-        .block_on(async move { admin_client.await?.contact_info().await })
-        .unwrap_or_else(|err| {
-            eprintln!("Contact info query failed: {err}");
-            exit(1);
-        });
-    if let Some(mode) = contact_info_args.output {
-        match mode.as_str() {
-            "json" => println!("{}", serde_json::to_string_pretty(&contact_info).unwrap()),
-            "json-compact" => print!("{}", serde_json::to_string(&contact_info).unwrap()),
+    let output = contact_info_args.output.as_deref();
+    if contact_info_args.sign {
+        let signed_contact_info = admin_rpc_service::runtime()
+            .block_on(async move { admin_client.await?.signed_contact_info().await })
+            .unwrap_or_else(|err| {
+                eprintln!("Signed contact info query failed: {err}");
+                exit(1);
+            });
+        print_contact_info(&signed_contact_info, output);
+    } else {
+        let contact_info = admin_rpc_service::runtime()
+            .block_on(async move { admin_client.await?.contact_info().await })
+            .unwrap_or_else(|err| {
+                eprintln!("Contact info query failed: {err}");
+                exit(1);
+            });
+        print_contact_info(&contact_info, output);
+    }
+}
+
+fn print_contact_info<T: Display + Serialize>(contact_info: &T, output: Option<&str>) {
+    if let Some(mode) = output {
+        match mode {
+            "json" => println!("{}", serde_json::to_string_pretty(contact_info).unwrap()),
+            "json-compact" => print!("{}", serde_json::to_string(contact_info).unwrap()),
             _ => unreachable!(),
         }
     } else {
@@ -69,6 +92,7 @@ mod tests {
             vec![COMMAND, "--output", "json"],
             ContactInfoArgs {
                 output: Some("json".to_string()),
+                sign: false,
             },
         );
     }
@@ -80,6 +104,7 @@ mod tests {
             vec![COMMAND, "--output", "json-compact"],
             ContactInfoArgs {
                 output: Some("json-compact".to_string()),
+                sign: false,
             },
         );
     }
@@ -89,7 +114,22 @@ mod tests {
         verify_args_struct_by_command(
             command(&DefaultArgs::default()),
             vec![COMMAND],
-            ContactInfoArgs { output: None },
+            ContactInfoArgs {
+                output: None,
+                sign: false,
+            },
+        );
+    }
+
+    #[test]
+    fn verify_args_struct_by_command_contact_info_sign() {
+        verify_args_struct_by_command(
+            command(&DefaultArgs::default()),
+            vec![COMMAND, "--sign", "--output", "json"],
+            ContactInfoArgs {
+                output: Some("json".to_string()),
+                sign: true,
+            },
         );
     }
 
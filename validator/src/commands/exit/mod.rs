@@ -1,8 +1,9 @@
 use {
     crate::{admin_rpc_service, cli::DefaultArgs, commands},
-    clap::{value_t_or_exit, App, Arg, ArgMatches, SubCommand},
+    clap::{value_t, value_t_or_exit, App, Arg, ArgMatches, SubCommand},
     solana_clap_utils::input_validators::{is_parsable, is_valid_percentage},
-    std::{path::Path, process::exit},
+    solana_sdk::clock::Slot,
+    std::{path::Path, process::exit, time::Duration},
 };
 
 pub fn command(default_args: &DefaultArgs) -> App<'_, '_> {
@@ -54,9 +55,91 @@ pub fn command(default_args: &DefaultArgs) -> App<'_, '_> {
                 .long("skip-health-check")
                 .help("Skip health check"),
         )
+        .arg(
+            Arg::with_name("at_slot")
+                .long("at-slot")
+                .takes_value(true)
+                .validator(is_parsable::<Slot>)
+                .value_name("SLOT")
+                .conflicts_with("after_duration")
+                .help(
+                    "Schedule the validator to exit once it reaches this slot, instead of \
+                     waiting for a restart window",
+                ),
+        )
+        .arg(
+            Arg::with_name("after_duration")
+                .long("after-duration")
+                .takes_value(true)
+                .validator(|value| parse_duration(&value).map(|_| ()))
+                .value_name("DURATION")
+                .help(
+                    "Schedule the validator to exit after this duration, in seconds or with an \
+                     s, m or h suffix, instead of waiting for a restart window",
+                ),
+        )
+        .after_help(
+            "A scheduled exit replaces any exit scheduled before, and is cancelled with \
+             `exit cancel`",
+        )
+        .subcommand(SubCommand::with_name("cancel").about("Cancel a scheduled exit"))
+}
+
+fn parse_duration(value: &str) -> Result<Duration, String> {
+    let (number, multiplier) = match value.char_indices().last() {
+        Some((index, 's')) => (&value[..index], 1),
+        Some((index, 'm')) => (&value[..index], 60),
+        Some((index, 'h')) => (&value[..index], 60 * 60),
+        _ => (value, 1),
+    };
+    number
+        .parse::<u64>()
+        .ok()
+        .and_then(|number| number.checked_mul(multiplier))
+        .map(Duration::from_secs)
+        .ok_or_else(|| format!("Unable to parse duration: {value}"))
+}
+
+fn cancel(ledger_path: &Path) {
+    let admin_client = admin_rpc_service::connect(ledger_path);
+    let cancelled = admin_rpc_service::runtime()
+        .block_on(async move { admin_client.await?.cancel_scheduled_exit().await })
+        .unwrap_or_else(|err| {
+            println!("cancelScheduledExit request failed: {err}");
+            exit(1);
+        });
+    match cancelled {
+        Some(scheduled_exit) => print!("Cancelled: {scheduled_exit}"),
+        None => println!("No exit was scheduled"),
+    }
+}
+
+fn schedule(ledger_path: &Path, at_slot: Option<Slot>, after: Option<Duration>) {
+    let after_secs = after.map(|after| after.as_secs());
+    let admin_client = admin_rpc_service::connect(ledger_path);
+    let scheduled_exit = admin_rpc_service::runtime()
+        .block_on(async move { admin_client.await?.schedule_exit(at_slot, after_secs).await })
+        .unwrap_or_else(|err| {
+            println!("scheduleExit request failed: {err}");
+            exit(1);
+        });
+    print!("{scheduled_exit}");
 }
 
 pub fn execute(matches: &ArgMatches, ledger_path: &Path) {
+    if let ("cancel", Some(_)) = matches.subcommand() {
+        cancel(ledger_path);
+        return;
+    }
+    let at_slot = value_t!(matches, "at_slot", Slot).ok();
+    let after = matches
+        .value_of("after_duration")
+        .map(|value| parse_duration(value).unwrap());
+    if at_slot.is_some() || after.is_some() {
+        schedule(ledger_path, at_slot, after);
+        return;
+    }
+
     let min_idle_time = value_t_or_exit!(matches, "min_idle_time", usize);
     let force = matches.is_present("force");
     let monitor = matches.is_present("monitor");
@@ -92,3 +175,20 @@ pub fn execute(matches: &ArgMatches, ledger_path: &Path) {
         commands::monitor::execute(matches, ledger_path);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("90"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse_duration("90s"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse_duration("15m"), Ok(Duration::from_secs(15 * 60)));
+        assert_eq!(parse_duration("2h"), Ok(Duration::from_secs(2 * 60 * 60)));
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("m").is_err());
+        assert!(parse_duration("-1").is_err());
+        assert!(parse_duration("1d").is_err());
+    }
+}
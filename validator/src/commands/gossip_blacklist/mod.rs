@@ -0,0 +1,82 @@
+use {
+    crate::{admin_rpc_service, cli::DefaultArgs},
+    clap::{value_t_or_exit, App, AppSettings, Arg, ArgMatches, SubCommand},
+    solana_clap_utils::input_validators::is_pubkey,
+    solana_sdk::pubkey::Pubkey,
+    std::{path::Path, process::exit},
+};
+
+pub fn command(_default_args: &DefaultArgs) -> App<'_, '_> {
+    SubCommand::with_name("gossip-blacklist")
+        .about("Manage the gossip peers the validator blacklisted for misbehaving")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .setting(AppSettings::InferSubcommands)
+        .subcommand(
+            SubCommand::with_name("get")
+                .about("Display the peers on the validator's gossip blacklist, or recently so")
+                .arg(
+                    Arg::with_name("output")
+                        .long("output")
+                        .takes_value(true)
+                        .value_name("MODE")
+                        .possible_values(&["json", "json-compact"])
+                        .help("Output display mode"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("remove")
+                .about("Lift the ban of a gossip peer and clear its misbehavior score")
+                .arg(
+                    Arg::with_name("pubkey")
+                        .index(1)
+                        .required(true)
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .takes_value(true)
+                        .help("Identity of the peer"),
+                ),
+        )
+}
+
+pub fn execute(matches: &ArgMatches, ledger_path: &Path) {
+    match matches.subcommand() {
+        ("get", Some(subcommand_matches)) => {
+            let output_mode = subcommand_matches.value_of("output");
+            let admin_client = admin_rpc_service::connect(ledger_path);
+            let blacklist = admin_rpc_service::runtime()
+                .block_on(async move { admin_client.await?.gossip_blacklist().await })
+                .unwrap_or_else(|err| {
+                    eprintln!("Gossip blacklist query failed: {err}");
+                    exit(1);
+                });
+            if let Some(mode) = output_mode {
+                match mode {
+                    "json" => println!("{}", serde_json::to_string_pretty(&blacklist).unwrap()),
+                    "json-compact" => print!("{}", serde_json::to_string(&blacklist).unwrap()),
+                    _ => unreachable!(),
+                }
+            } else if blacklist.is_empty() {
+                println!("Gossip blacklist: none");
+            } else {
+                println!("Gossip blacklist:");
+                for peer in blacklist {
+                    print!("  {peer}");
+                }
+            }
+        }
+        ("remove", Some(subcommand_matches)) => {
+            let pubkey = value_t_or_exit!(subcommand_matches, "pubkey", Pubkey);
+            let admin_client = admin_rpc_service::connect(ledger_path);
+            let unbanned = admin_rpc_service::runtime()
+                .block_on(async move { admin_client.await?.gossip_unban(pubkey).await })
+                .unwrap_or_else(|err| {
+                    eprintln!("gossipUnban request failed: {err}");
+                    exit(1);
+                });
+            if !unbanned {
+                println!("{pubkey} is not on the gossip blacklist");
+            }
+        }
+        _ => unreachable!(),
+    }
+}
@@ -1,16 +1,22 @@
+pub mod accounts_maintenance;
 pub mod authorized_voter;
 pub mod contact_info;
 pub mod exit;
+pub mod gossip_blacklist;
 pub mod monitor;
 pub mod plugin;
+pub mod reload_config;
 pub mod repair_shred_from_peer;
 pub mod repair_whitelist;
+pub mod retransmit_tree;
 pub mod run;
 pub mod set_identity;
 pub mod set_log_filter;
 pub mod set_public_address;
+pub mod socket_stats;
 pub mod staked_nodes_overrides;
 pub mod wait_for_restart_window;
+pub mod write_fence;
 
 pub trait FromClapArgMatches {
     fn from_clap_arg_match(matches: &clap::ArgMatches) -> Self;
@@ -10,6 +10,10 @@ pub fn command(_default_args: &DefaultArgs) -> App<'_, '_> {
         .setting(AppSettings::SubcommandRequiredElseHelp)
         .setting(AppSettings::InferSubcommands)
         .subcommand(SubCommand::with_name("list").about("List all current running gesyer plugins"))
+        .subcommand(
+            SubCommand::with_name("status")
+                .about("Show the interface version and health of each loaded geyser plugin"),
+        )
         .subcommand(
             SubCommand::with_name("unload")
                 .about(
@@ -56,6 +60,34 @@ pub fn execute(matches: &ArgMatches, ledger_path: &Path) {
                 println!("There are currently no plugins loaded");
             }
         }
+        ("status", _) => {
+            let admin_client = admin_rpc_service::connect(ledger_path);
+            let statuses = admin_rpc_service::runtime()
+                .block_on(async move { admin_client.await?.plugin_status().await })
+                .unwrap_or_else(|err| {
+                    println!("Failed to get plugin status: {err}");
+                    exit(1);
+                });
+            if statuses.is_empty() {
+                println!("There are currently no plugins loaded");
+            }
+            for status in statuses {
+                let interface_version = status
+                    .interface_version
+                    .map(|version| version.to_string())
+                    .unwrap_or_else(|| "unknown".to_string());
+                match status.failure {
+                    Some(failure) => println!(
+                        "{}: failed (interface version {interface_version}): {failure}",
+                        status.name
+                    ),
+                    None => println!(
+                        "{}: running (interface version {interface_version})",
+                        status.name
+                    ),
+                }
+            }
+        }
         ("unload", Some(subcommand_matches)) => {
             if let Ok(name) = value_t!(subcommand_matches, "name", String) {
                 let admin_client = admin_rpc_service::connect(ledger_path);
@@ -0,0 +1,195 @@
+use {
+    crate::{
+        admin_rpc_service::{self, AdminRpcReloadableConfig},
+        cli::DefaultArgs,
+        commands::FromClapArgMatches,
+    },
+    clap::{value_t, App, AppSettings, Arg, ArgMatches, SubCommand},
+    solana_clap_utils::input_validators::is_parsable,
+    std::{path::Path, process::exit},
+};
+
+const COMMAND: &str = "reload-config";
+
+const MB: u64 = 1024 * 1024;
+
+#[derive(Debug, PartialEq)]
+pub struct ReloadConfigArgs {
+    pub config: AdminRpcReloadableConfig,
+    pub output: Option<String>,
+}
+
+impl FromClapArgMatches for ReloadConfigArgs {
+    fn from_clap_arg_match(matches: &ArgMatches) -> Self {
+        ReloadConfigArgs {
+            config: AdminRpcReloadableConfig {
+                full_snapshot_interval_slots: value_t!(
+                    matches,
+                    "full_snapshot_interval_slots",
+                    u64
+                )
+                .ok(),
+                incremental_snapshot_interval_slots: value_t!(
+                    matches,
+                    "snapshot_interval_slots",
+                    u64
+                )
+                .ok(),
+                accounts_db_write_cache_limit_bytes: value_t!(
+                    matches,
+                    "accounts_db_cache_limit_mb",
+                    u64
+                )
+                .ok()
+                .map(|mb| mb * MB),
+                rpc_max_inflight_transactions_per_fee_payer: value_t!(
+                    matches,
+                    "rpc_max_inflight_transactions_per_fee_payer",
+                    usize
+                )
+                .ok(),
+            },
+            output: matches.value_of("output").map(String::from),
+        }
+    }
+}
+
+pub fn command(_default_args: &DefaultArgs) -> App<'_, '_> {
+    SubCommand::with_name(COMMAND)
+        .about("Change selected settings of the running validator without restarting it")
+        .setting(AppSettings::ArgRequiredElseHelp)
+        .arg(
+            Arg::with_name("snapshot_interval_slots")
+                .long("snapshot-interval-slots")
+                .alias("incremental-snapshot-interval-slots")
+                .value_name("NUMBER")
+                .takes_value(true)
+                .validator(is_parsable::<u64>)
+                .help("Number of slots between generating incremental snapshots"),
+        )
+        .arg(
+            Arg::with_name("full_snapshot_interval_slots")
+                .long("full-snapshot-interval-slots")
+                .value_name("NUMBER")
+                .takes_value(true)
+                .validator(is_parsable::<u64>)
+                .help("Number of slots between generating full snapshots"),
+        )
+        .arg(
+            Arg::with_name("accounts_db_cache_limit_mb")
+                .long("accounts-db-cache-limit-mb")
+                .value_name("MEGABYTES")
+                .takes_value(true)
+                .validator(is_parsable::<u64>)
+                .help(
+                    "How large the write cache for account data can become before it is \
+                     flushed more aggressively",
+                ),
+        )
+        .arg(
+            Arg::with_name("rpc_max_inflight_transactions_per_fee_payer")
+                .long("rpc-max-inflight-transactions-per-fee-payer")
+                .value_name("NUMBER")
+                .takes_value(true)
+                .validator(is_parsable::<usize>)
+                .help(
+                    "Reject sendTransaction requests from a fee payer that already has this \
+                     many transactions being sent. Only if the limit was set at startup",
+                ),
+        )
+        .arg(
+            Arg::with_name("output")
+                .long("output")
+                .takes_value(true)
+                .value_name("MODE")
+                .possible_values(&["json", "json-compact"])
+                .help("Output display mode"),
+        )
+        .after_help(
+            "Note: the changes only apply to the currently running validator instance. \
+             Settings that are not given are left unchanged.",
+        )
+}
+
+pub fn execute(matches: &ArgMatches, ledger_path: &Path) {
+    let ReloadConfigArgs { config, output } = ReloadConfigArgs::from_clap_arg_match(matches);
+
+    let admin_client = admin_rpc_service::connect(ledger_path);
+    let config = admin_rpc_service::runtime()
+        .block_on(async move { admin_client.await?.reload_config(config).await })
+        .unwrap_or_else(|err| {
+            eprintln!("Reload config request failed: {err}");
+            exit(1);
+        });
+    if let Some(mode) = output {
+        match mode.as_str() {
+            "json" => println!("{}", serde_json::to_string_pretty(&config).unwrap()),
+            "json-compact" => print!("{}", serde_json::to_string(&config).unwrap()),
+            _ => unreachable!(),
+        }
+    } else {
+        print!("{config}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::commands::tests::{
+            verify_args_struct_by_command, verify_args_struct_by_command_is_error,
+        },
+    };
+
+    #[test]
+    fn verify_args_struct_by_command_reload_config_all() {
+        verify_args_struct_by_command(
+            command(&DefaultArgs::default()),
+            vec![
+                COMMAND,
+                "--full-snapshot-interval-slots",
+                "50000",
+                "--incremental-snapshot-interval-slots",
+                "1000",
+                "--accounts-db-cache-limit-mb",
+                "4096",
+                "--rpc-max-inflight-transactions-per-fee-payer",
+                "10",
+                "--output",
+                "json",
+            ],
+            ReloadConfigArgs {
+                config: AdminRpcReloadableConfig {
+                    full_snapshot_interval_slots: Some(50_000),
+                    incremental_snapshot_interval_slots: Some(1000),
+                    accounts_db_write_cache_limit_bytes: Some(4096 * MB),
+                    rpc_max_inflight_transactions_per_fee_payer: Some(10),
+                },
+                output: Some("json".to_string()),
+            },
+        );
+    }
+
+    #[test]
+    fn verify_args_struct_by_command_reload_config_some() {
+        verify_args_struct_by_command(
+            command(&DefaultArgs::default()),
+            vec![COMMAND, "--snapshot-interval-slots", "500"],
+            ReloadConfigArgs {
+                config: AdminRpcReloadableConfig {
+                    incremental_snapshot_interval_slots: Some(500),
+                    ..AdminRpcReloadableConfig::default()
+                },
+                output: None,
+            },
+        );
+    }
+
+    #[test]
+    fn verify_args_struct_by_command_reload_config_invalid() {
+        verify_args_struct_by_command_is_error::<ReloadConfigArgs>(
+            command(&DefaultArgs::default()),
+            vec![COMMAND, "--accounts-db-cache-limit-mb", "lots"],
+        );
+    }
+}
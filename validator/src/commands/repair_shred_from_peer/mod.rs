@@ -1,14 +1,19 @@
 use {
     crate::{admin_rpc_service, cli::DefaultArgs},
-    clap::{value_t, value_t_or_exit, App, Arg, ArgMatches, SubCommand},
+    clap::{value_t, value_t_or_exit, values_t, App, Arg, ArgMatches, SubCommand},
     solana_clap_utils::input_validators::{is_parsable, is_pubkey},
     solana_sdk::pubkey::Pubkey,
     std::{path::Path, process::exit},
 };
 
+const DEFAULT_TIMEOUT_SECONDS: &str = "10";
+
 pub fn command(_default_args: &DefaultArgs) -> App<'_, '_> {
     SubCommand::with_name("repair-shred-from-peer")
-        .about("Request a repair from the specified validator")
+        .about(
+            "Request repairs of missing shreds from the specified validator, and report which \
+             slots are complete once they land",
+        )
         .arg(
             Arg::with_name("pubkey")
                 .long("pubkey")
@@ -16,40 +21,150 @@ pub fn command(_default_args: &DefaultArgs) -> App<'_, '_> {
                 .required(false)
                 .takes_value(true)
                 .validator(is_pubkey)
-                .help("Identity pubkey of the validator to repair from"),
+                .help(
+                    "Identity pubkey of the validator to repair from [default: a sample of \
+                     validators having the slot]",
+                ),
         )
         .arg(
             Arg::with_name("slot")
                 .long("slot")
                 .value_name("SLOT")
                 .takes_value(true)
+                .required(true)
                 .validator(is_parsable::<u64>)
-                .help("Slot to repair"),
+                .help("Slot to repair, or first slot of the range to repair"),
+        )
+        .arg(
+            Arg::with_name("end_slot")
+                .long("end-slot")
+                .value_name("SLOT")
+                .takes_value(true)
+                .validator(is_parsable::<u64>)
+                .help("Last slot of the range to repair [default: --slot]"),
         )
         .arg(
             Arg::with_name("shred")
                 .long("shred")
                 .value_name("SHRED")
                 .takes_value(true)
+                .multiple(true)
+                .validator(is_shred_index_range)
+                .help(
+                    "Index of a data shred to repair, or inclusive range of indexes such as \
+                     10-20. May be specified multiple times [default: all missing data shreds]",
+                ),
+        )
+        .arg(
+            Arg::with_name("timeout")
+                .long("timeout")
+                .value_name("SECONDS")
+                .takes_value(true)
+                .default_value(DEFAULT_TIMEOUT_SECONDS)
                 .validator(is_parsable::<u64>)
-                .help("Shred to repair"),
+                .help("How long to wait for the repaired shreds to land, at most 60 seconds"),
+        )
+        .arg(
+            Arg::with_name("output")
+                .long("output")
+                .takes_value(true)
+                .value_name("MODE")
+                .possible_values(&["json", "json-compact"])
+                .help("Output display mode"),
         )
 }
 
+/// Parses a shred index, or an inclusive range of shred indexes, into a half-open range
+fn parse_shred_index_range(value: &str) -> Result<(u64, u64), String> {
+    let (start, end) = value.split_once('-').unwrap_or((value, value));
+    let start = start
+        .parse::<u64>()
+        .map_err(|err| format!("invalid shred index {start}: {err}"))?;
+    let end = end
+        .parse::<u64>()
+        .map_err(|err| format!("invalid shred index {end}: {err}"))?;
+    if end < start {
+        return Err(format!("invalid shred index range {value}"));
+    }
+    Ok((start, end + 1))
+}
+
+fn is_shred_index_range(value: String) -> Result<(), String> {
+    parse_shred_index_range(&value).map(|_| ())
+}
+
 pub fn execute(matches: &ArgMatches, ledger_path: &Path) {
     let pubkey = value_t!(matches, "pubkey", Pubkey).ok();
-    let slot = value_t_or_exit!(matches, "slot", u64);
-    let shred_index = value_t_or_exit!(matches, "shred", u64);
+    let start_slot = value_t_or_exit!(matches, "slot", u64);
+    let end_slot = value_t!(matches, "end_slot", u64).unwrap_or(start_slot);
+    let shred_index_ranges: Vec<_> = values_t!(matches, "shred", String)
+        .unwrap_or_default()
+        .iter()
+        .map(|value| parse_shred_index_range(value).unwrap())
+        .collect();
+    let timeout_ms = value_t_or_exit!(matches, "timeout", u64).saturating_mul(1000);
     let admin_client = admin_rpc_service::connect(ledger_path);
-    admin_rpc_service::runtime()
+    let slot_repairs = admin_rpc_service::runtime()
         .block_on(async move {
             admin_client
                 .await?
-                .repair_shred_from_peer(pubkey, slot, shred_index)
+                .repair_shreds_from_peer(
+                    pubkey,
+                    start_slot,
+                    end_slot,
+                    shred_index_ranges,
+                    timeout_ms,
+                )
                 .await
         })
         .unwrap_or_else(|err| {
             println!("repair shred from peer failed: {err}");
             exit(1);
         });
+
+    match matches.value_of("output") {
+        Some("json") => println!("{}", serde_json::to_string_pretty(&slot_repairs).unwrap()),
+        Some("json-compact") => println!("{}", serde_json::to_string(&slot_repairs).unwrap()),
+        _ => {
+            for slot_repair in &slot_repairs {
+                let status = if slot_repair.is_full {
+                    "full"
+                } else {
+                    "incomplete"
+                };
+                print!(
+                    "slot {}: {status}, requested {} shreds",
+                    slot_repair.slot,
+                    slot_repair.requested.len(),
+                );
+                if slot_repair.missing.is_empty() {
+                    println!();
+                } else {
+                    println!(", still missing {:?}", slot_repair.missing);
+                }
+            }
+        }
+    }
+
+    if slot_repairs
+        .iter()
+        .any(|slot_repair| !slot_repair.missing.is_empty())
+    {
+        exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_shred_index_range() {
+        assert_eq!(parse_shred_index_range("5"), Ok((5, 6)));
+        assert_eq!(parse_shred_index_range("10-20"), Ok((10, 21)));
+        assert_eq!(parse_shred_index_range("7-7"), Ok((7, 8)));
+        assert!(parse_shred_index_range("20-10").is_err());
+        assert!(parse_shred_index_range("a-10").is_err());
+        assert!(parse_shred_index_range("").is_err());
+    }
 }
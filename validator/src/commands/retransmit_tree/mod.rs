@@ -0,0 +1,84 @@
+use {
+    crate::{admin_rpc_service, cli::DefaultArgs},
+    clap::{value_t, value_t_or_exit, App, Arg, ArgMatches, SubCommand},
+    solana_clap_utils::input_validators::{is_parsable, is_pubkey},
+    solana_sdk::pubkey::Pubkey,
+    std::{path::Path, process::exit},
+};
+
+pub fn command(_default_args: &DefaultArgs) -> App<'_, '_> {
+    SubCommand::with_name("retransmit-tree")
+        .about(
+            "Display the parent and children of a node in the turbine retransmit tree of a \
+             shred, as computed by this validator",
+        )
+        .arg(
+            Arg::with_name("slot")
+                .long("slot")
+                .value_name("SLOT")
+                .takes_value(true)
+                .required(true)
+                .validator(is_parsable::<u64>)
+                .help("Slot of the shred"),
+        )
+        .arg(
+            Arg::with_name("shred")
+                .long("shred")
+                .value_name("SHRED")
+                .takes_value(true)
+                .required(true)
+                .validator(is_parsable::<u32>)
+                .help("Index of the shred"),
+        )
+        .arg(
+            Arg::with_name("coding")
+                .long("coding")
+                .takes_value(false)
+                .help("The shred is a coding shred rather than a data shred"),
+        )
+        .arg(
+            Arg::with_name("node")
+                .long("node")
+                .value_name("PUBKEY")
+                .takes_value(true)
+                .validator(is_pubkey)
+                .help(
+                    "Identity pubkey of the node to compute the tree for [default: this validator]",
+                ),
+        )
+        .arg(
+            Arg::with_name("output")
+                .long("output")
+                .takes_value(true)
+                .value_name("MODE")
+                .possible_values(&["json", "json-compact"])
+                .help("Output display mode"),
+        )
+}
+
+pub fn execute(matches: &ArgMatches, ledger_path: &Path) {
+    let slot = value_t_or_exit!(matches, "slot", u64);
+    let shred_index = value_t_or_exit!(matches, "shred", u32);
+    let coding = matches.is_present("coding");
+    let node = value_t!(matches, "node", Pubkey).ok();
+    let admin_client = admin_rpc_service::connect(ledger_path);
+    let retransmit_tree = admin_rpc_service::runtime()
+        .block_on(async move {
+            admin_client
+                .await?
+                .retransmit_tree(slot, shred_index, coding, node)
+                .await
+        })
+        .unwrap_or_else(|err| {
+            eprintln!("Retransmit tree query failed: {err}");
+            exit(1);
+        });
+    match matches.value_of("output") {
+        Some("json") => println!(
+            "{}",
+            serde_json::to_string_pretty(&retransmit_tree).unwrap()
+        ),
+        Some("json-compact") => print!("{}", serde_json::to_string(&retransmit_tree).unwrap()),
+        _ => print!("{retransmit_tree}"),
+    }
+}
@@ -1,6 +1,7 @@
 use {
     crate::cli::{hash_validator, port_range_validator, port_validator, DefaultArgs},
     clap::{App, Arg},
+    solana_accounts_db::accounts_file::AccountsFileProvider,
     solana_clap_utils::{
         hidden_unless_forced,
         input_validators::{
@@ -253,6 +254,56 @@ pub fn add_args<'a>(app: App<'a, 'a>, default_args: &'a DefaultArgs) -> App<'a,
             .validator(solana_net_utils::is_host_port)
             .help("Enable the JSON RPC 'requestAirdrop' API with this faucet address."),
     )
+    .arg(
+        Arg::with_name("accounts_replication_addr")
+            .long("accounts-replication-address")
+            .value_name("HOST:PORT")
+            .takes_value(true)
+            .validator(solana_net_utils::is_host_port)
+            .help(
+                "Stream the accounts changed in each frozen bank to replica nodes connecting to \
+                 this address. Replicas keep a read-only copy of the accounts without replaying \
+                 the ledger",
+            ),
+    )
+    .arg(
+        Arg::with_name("accounts_replication_catch_up_slots")
+            .long("accounts-replication-catch-up-slots")
+            .value_name("NUMBER")
+            .takes_value(true)
+            .validator(is_parsable::<usize>)
+            .default_value(&default_args.accounts_replication_catch_up_slots)
+            .requires("accounts_replication_addr")
+            .help(
+                "Number of rooted slots whose accounts are kept for replicas to catch up on \
+                 when they connect. Replicas further behind must restart from a newer snapshot",
+            ),
+    )
+    .arg(
+        Arg::with_name("accounts_replica_source")
+            .long("accounts-replica-source")
+            .value_name("HOST:PORT")
+            .takes_value(true)
+            .validator(solana_net_utils::is_host_port)
+            .requires_all(&["accounts_replica_source_identity", "no_voting"])
+            .help(
+                "Run as a replica of the validator streaming its accounts from this address, \
+                 taking its banks from that stream instead of replaying the ledger. The \
+                 replica must start from a snapshot of that validator",
+            ),
+    )
+    .arg(
+        Arg::with_name("accounts_replica_source_identity")
+            .long("accounts-replica-source-identity")
+            .value_name("PUBKEY")
+            .takes_value(true)
+            .validator(is_pubkey)
+            .requires("accounts_replica_source")
+            .help(
+                "Identity of the validator at --accounts-replica-source. Messages not signed by \
+                 it are rejected",
+            ),
+    )
     .arg(
         Arg::with_name("account_paths")
             .long("accounts")
@@ -385,6 +436,36 @@ pub fn add_args<'a>(app: App<'a, 'a>, default_args: &'a DefaultArgs) -> App<'a,
                  --rpc-bind-address / --rpc-port]",
             ),
     )
+    .arg(
+        Arg::with_name("nat_traversal")
+            .long("nat-traversal")
+            .value_name("METHOD")
+            .takes_value(true)
+            .possible_values(&["auto", "nat-pmp", "upnp"])
+            .help(
+                "Ask the NAT gateway to forward the validator's ports and advertise the \
+                 external addresses in gossip. Useful for validators running behind a home \
+                 router without manual port forwarding",
+            ),
+    )
+    .arg(
+        Arg::with_name("nat_pmp_gateway")
+            .long("nat-pmp-gateway")
+            .value_name("IP_ADDRESS")
+            .takes_value(true)
+            .requires("nat_traversal")
+            .validator(is_parsable::<std::net::Ipv4Addr>)
+            .help("NAT-PMP gateway address [default: the default route gateway]"),
+    )
+    .arg(
+        Arg::with_name("nat_lease_duration")
+            .long("nat-lease-duration")
+            .value_name("SECONDS")
+            .takes_value(true)
+            .requires("nat_traversal")
+            .validator(is_parsable::<u64>)
+            .help("Lifetime to request for NAT port mappings [default: 3600]"),
+    )
     .arg(
         Arg::with_name("dynamic_port_range")
             .long("dynamic-port-range")
@@ -475,6 +556,16 @@ pub fn add_args<'a>(app: App<'a, 'a>, default_args: &'a DefaultArgs) -> App<'a,
                  increases priority, positive value decreases priority.",
             ),
     )
+    .arg(
+        Arg::with_name("snapshot_archive_manifest")
+            .long("snapshot-archive-manifest")
+            .takes_value(false)
+            .help(
+                "Include a manifest of per-file BLAKE3 hashes in generated snapshot archives, \
+                 which is verified when the archive is unpacked. Archives with a manifest can not \
+                 be unpacked by validators running v2.2 or older.",
+            ),
+    )
     .arg(
         Arg::with_name("minimal_snapshot_download_speed")
             .long("minimal-snapshot-download-speed")
@@ -715,6 +806,31 @@ pub fn add_args<'a>(app: App<'a, 'a>, default_args: &'a DefaultArgs) -> App<'a,
             .requires("known_validators")
             .help("Use the RPC service of known validators only"),
     )
+    .arg(
+        Arg::with_name("snapshot_verification_rpc_urls")
+            .long("snapshot-verification-rpc-url")
+            .takes_value(true)
+            .value_name("RPC_URL")
+            .multiple(true)
+            .help(
+                "Verify the snapshot the validator starts from against the bank hashes reported \
+                 by the JSON RPC endpoint at RPC_URL. May be specified multiple times; the \
+                 validator exits if fewer than --snapshot-verification-quorum endpoints agree on \
+                 the bank hash of the snapshot slot",
+            ),
+    )
+    .arg(
+        Arg::with_name("snapshot_verification_quorum")
+            .long("snapshot-verification-quorum")
+            .takes_value(true)
+            .value_name("NUMBER")
+            .validator(is_parsable::<usize>)
+            .requires("snapshot_verification_rpc_urls")
+            .help(
+                "Number of --snapshot-verification-rpc-url endpoints which must agree on a bank \
+                 hash [default: a majority of them]",
+            ),
+    )
     .arg(
         Arg::with_name("repair_validators")
             .long("repair-validator")
@@ -753,6 +869,66 @@ pub fn add_args<'a>(app: App<'a, 'a>, default_args: &'a DefaultArgs) -> App<'a,
                  push/pull from from validators outside this set. [default: all validators]",
             ),
     )
+    .arg(
+        Arg::with_name("gossip_egress_limit")
+            .long("gossip-egress-limit")
+            .value_name("BYTES_PER_SECOND")
+            .takes_value(true)
+            .validator(is_parsable::<u64>)
+            .help(
+                "Maximum bytes per second sent by gossip to all peers combined. Messages \
+                 exceeding the limit are deferred, and dropped if they wait for too long. \
+                 [default: unlimited]",
+            ),
+    )
+    .arg(
+        Arg::with_name("gossip_egress_peer_limit")
+            .long("gossip-egress-peer-limit")
+            .value_name("BYTES_PER_SECOND")
+            .takes_value(true)
+            .validator(is_parsable::<u64>)
+            .help(
+                "Maximum bytes per second sent by gossip to each peer. Messages exceeding the \
+                 limit are deferred, and dropped if they wait for too long. [default: unlimited]",
+            ),
+    )
+    .arg(
+        Arg::with_name("gossip_ban_threshold")
+            .long("gossip-ban-threshold")
+            .value_name("SCORE")
+            .takes_value(true)
+            .validator(is_parsable::<u64>)
+            .help(
+                "Blacklist gossip peers once their misbehavior score reaches this. Protocol \
+                 violations score 5 and exceeding the packet limit 10. Offenses only count \
+                 against peers which answered a ping from the address they were received from, \
+                 in packets whose signatures verify, and staked nodes and entrypoints are never \
+                 blacklisted. Bans start at a minute and double with each repeat offense, \
+                 up to an hour. See the gossip-blacklist command to lift them. \
+                 [default: never blacklist]",
+            ),
+    )
+    .arg(
+        Arg::with_name("gossip_peer_packet_limit")
+            .long("gossip-peer-packet-limit")
+            .value_name("PACKETS_PER_SECOND")
+            .takes_value(true)
+            .validator(is_parsable::<u64>)
+            .requires("gossip_ban_threshold")
+            .help(
+                "Maximum packets per second received by gossip from each peer before it is \
+                 penalized for excessive bandwidth. [default: unlimited]",
+            ),
+    )
+    .arg(
+        Arg::with_name("gossip_allowlisted_peers")
+            .long("gossip-allowlisted-peer")
+            .value_name("PUBKEY")
+            .takes_value(true)
+            .multiple(true)
+            .validator(is_pubkey)
+            .help("Identity of a gossip peer which is never blacklisted"),
+    )
     .arg(
         Arg::with_name("tpu_coalesce_ms")
             .long("tpu-coalesce-ms")
@@ -789,6 +965,18 @@ pub fn add_args<'a>(app: App<'a, 'a>, default_args: &'a DefaultArgs) -> App<'a,
             .validator(is_parsable::<usize>)
             .help("Controls the TPU connection pool size per remote address"),
     )
+    .arg(
+        Arg::with_name("tpu_client_zero_rtt")
+            .long("tpu-client-zero-rtt")
+            .takes_value(false)
+            .conflicts_with("tpu_disable_quic")
+            .help(
+                "Resume QUIC sessions with 0-RTT when sending transactions to a leader \
+                 connected to before, saving a handshake round trip each leader rotation. \
+                 Transactions sent as early data can be replayed to the leader, which \
+                 deduplicates them by signature.",
+            ),
+    )
     .arg(
         Arg::with_name("tpu_max_connections_per_ipaddr_per_minute")
             .long("tpu-max-connections-per-ipaddr-per-minute")
@@ -806,6 +994,16 @@ pub fn add_args<'a>(app: App<'a, 'a>, default_args: &'a DefaultArgs) -> App<'a,
             .hidden(hidden_unless_forced())
             .help("Controls if to use QUIC to send votes."),
     )
+    .arg(
+        Arg::with_name("turbine_use_quic")
+            .long("turbine-use-quic")
+            .takes_value(false)
+            .hidden(hidden_unless_forced())
+            .help(
+                "Send shreds over QUIC to the nodes which advertise a QUIC TVU address, \
+                 falling back to UDP if the QUIC endpoint is congested.",
+            ),
+    )
     .arg(
         Arg::with_name("tpu_max_connections_per_peer")
             .long("tpu-max-connections-per-peer")
@@ -1086,6 +1284,18 @@ pub fn add_args<'a>(app: App<'a, 'a>, default_args: &'a DefaultArgs) -> App<'a,
                  service.",
             ),
     )
+    .arg(
+        Arg::with_name("rpc_send_transaction_leader_prefetch_count")
+            .long("rpc-send-leader-prefetch-count")
+            .value_name("NUMBER")
+            .takes_value(true)
+            .validator(is_parsable::<u64>)
+            .default_value(&default_args.rpc_send_transaction_leader_prefetch_count)
+            .help(
+                "The number of leaders after the ones transactions sent via rpc service are \
+                 forwarded to, whose QUIC connections are opened ahead of their slots.",
+            ),
+    )
     .arg(
         Arg::with_name("rpc_send_transaction_default_max_retries")
             .long("rpc-send-default-max-retries")
@@ -1160,6 +1370,74 @@ pub fn add_args<'a>(app: App<'a, 'a>, default_args: &'a DefaultArgs) -> App<'a,
             .default_value(&default_args.rpc_max_request_body_size)
             .help("The maximum request body size accepted by rpc service"),
     )
+    .arg(
+        Arg::with_name("rpc_preflight_cache_ttl_ms")
+            .long("rpc-preflight-cache-ttl-ms")
+            .value_name("MILLISECONDS")
+            .takes_value(true)
+            .validator(is_parsable::<u64>)
+            .default_value(&default_args.rpc_preflight_cache_ttl_ms)
+            .help(
+                "How long the result of a sendTransaction preflight simulation is reused for an \
+                 identical transaction. 0 disables the preflight cache",
+            ),
+    )
+    .arg(
+        Arg::with_name("rpc_preflight_cache_capacity")
+            .long("rpc-preflight-cache-capacity")
+            .value_name("NUMBER")
+            .takes_value(true)
+            .validator(is_parsable::<usize>)
+            .default_value(&default_args.rpc_preflight_cache_capacity)
+            .help("The maximum number of cached sendTransaction preflight results"),
+    )
+    .arg(
+        Arg::with_name("rpc_max_inflight_transactions_per_fee_payer")
+            .long("rpc-max-inflight-transactions-per-fee-payer")
+            .value_name("NUMBER")
+            .takes_value(true)
+            .validator(is_parsable::<usize>)
+            .help(
+                "Reject sendTransaction requests from a fee payer that already has this many \
+                 transactions being sent by the send transaction service [default: no limit]",
+            ),
+    )
+    .arg(
+        Arg::with_name("epoch_stakes_retention")
+            .long("epoch-stakes-retention")
+            .value_name("EPOCHS")
+            .takes_value(true)
+            .validator(is_parsable::<u64>)
+            .help(
+                "Number of past epochs whose stake and vote account snapshots are kept in \
+                 memory and served by getVoteAccountEpochStakes. Values below the number \
+                 needed for the leader schedule are ignored",
+            ),
+    )
+    .arg(
+        Arg::with_name("intra_batch_execution_threads")
+            .long("intra-batch-execution-threads")
+            .value_name("NUMBER")
+            .takes_value(true)
+            .validator(is_parsable::<usize>)
+            .help(
+                "Max number of threads of the replay thread pool executing the transactions of \
+                 a batch which do not lock any of the same accounts in parallel [default: \
+                 execute batches serially]",
+            ),
+    )
+    .arg(
+        Arg::with_name("rpc_non_circulating_account")
+            .long("rpc-non-circulating-account")
+            .validator(is_pubkey)
+            .value_name("PUBKEY")
+            .multiple(true)
+            .takes_value(true)
+            .help(
+                "Report the balance of this account as non-circulating supply, in addition to \
+                 the mainnet-beta non-circulating accounts. May be specified multiple times",
+            ),
+    )
     .arg(
         Arg::with_name("geyser_plugin_config")
             .long("geyser-plugin-config")
@@ -1341,6 +1619,18 @@ pub fn add_args<'a>(app: App<'a, 'a>, default_args: &'a DefaultArgs) -> App<'a,
             .possible_values(&["mmap", "file"])
             .help("Access account storages using this method")
     )
+    .arg(
+        Arg::with_name("accounts_db_storage_format")
+            .long("accounts-db-storage-format")
+            .value_name("FORMAT")
+            .takes_value(true)
+            .possible_values(AccountsFileProvider::cli_names())
+            .help(
+                "Write new account storage files in this format. Existing storages in either \
+                 format are always readable.",
+            )
+            .hidden(hidden_unless_forced()),
+    )
     .arg(
         Arg::with_name("accounts_db_ancient_append_vecs")
             .long("accounts-db-ancient-append-vecs")
@@ -1391,6 +1681,17 @@ pub fn add_args<'a>(app: App<'a, 'a>, default_args: &'a DefaultArgs) -> App<'a,
                  the cache is flushed more aggressively.",
             ),
     )
+    .arg(
+        Arg::with_name("accounts_db_background_io_limit")
+            .long("accounts-db-background-io-limit")
+            .value_name("BYTES_PER_SECOND")
+            .validator(is_parsable::<u64>)
+            .takes_value(true)
+            .help(
+                "Disk bandwidth available to background accounts clean and shrink. \
+                 [default: unlimited]",
+            ),
+    )
     .arg(
         Arg::with_name("accounts_db_read_cache_limit_mb")
             .long("accounts-db-read-cache-limit-mb")
@@ -1550,6 +1851,46 @@ pub fn add_args<'a>(app: App<'a, 'a>, default_args: &'a DefaultArgs) -> App<'a,
                 was created before we started creating ours.",
             ),
     )
+    .arg(
+        Arg::with_name("leader_prewarm")
+            .long("leader-prewarm")
+            .takes_value(false)
+            .help(
+                "Shortly before each leader window, load the accounts most written to in \
+                recent blocks and compile the most used programs, so that the first \
+                transactions of the leader block do not wait on cold loads",
+            ),
+    )
+    .arg(
+        Arg::with_name("leader_prewarm_lead_slots")
+            .long("leader-prewarm-lead-slots")
+            .value_name("SLOTS")
+            .takes_value(true)
+            .requires("leader_prewarm")
+            .validator(is_parsable::<u64>)
+            .help("Number of slots ahead of the leader window to start pre-warming [default: 4]"),
+    )
+    .arg(
+        Arg::with_name("leader_prewarm_max_accounts")
+            .long("leader-prewarm-max-accounts")
+            .value_name("COUNT")
+            .takes_value(true)
+            .requires("leader_prewarm")
+            .validator(is_parsable::<usize>)
+            .help("Maximum number of accounts to load ahead of the leader window [default: 1024]"),
+    )
+    .arg(
+        Arg::with_name("leader_prewarm_max_programs")
+            .long("leader-prewarm-max-programs")
+            .value_name("COUNT")
+            .takes_value(true)
+            .requires("leader_prewarm")
+            .validator(is_parsable::<usize>)
+            .help(
+                "Maximum number of programs to compile ahead of the leader window \
+                [default: 128]",
+            ),
+    )
     .arg(
         Arg::with_name("block_verification_method")
             .long("block-verification-method")
@@ -1574,6 +1915,83 @@ pub fn add_args<'a>(app: App<'a, 'a>, default_args: &'a DefaultArgs) -> App<'a,
             .possible_values(TransactionStructure::cli_names())
             .help(TransactionStructure::cli_message()),
     )
+    .arg(
+        Arg::with_name("banking_stage_spill_path")
+            .long("banking-stage-spill-path")
+            .value_name("DIR")
+            .takes_value(true)
+            .help(
+                "Spill buffered transactions exceeding the banking stage capacity to a file in \
+                 this directory instead of dropping them. Spilled transactions are reloaded in \
+                 priority order once there is room again. [default: disabled]",
+            ),
+    )
+    .arg(
+        Arg::with_name("banking_stage_spill_limit_mb")
+            .long("banking-stage-spill-limit-mb")
+            .value_name("MEGABYTES")
+            .takes_value(true)
+            .requires("banking_stage_spill_path")
+            .validator(is_parsable::<u64>)
+            .default_value(&default_args.banking_stage_spill_limit_mb)
+            .help(
+                "Maximum size of spilled transactions. Once reached, the lowest priority \
+                 transactions are dropped.",
+            ),
+    )
+    .arg(
+        Arg::with_name("banking_stage_look_ahead_window_size")
+            .long("banking-stage-look-ahead-window-size")
+            .value_name("COUNT")
+            .takes_value(true)
+            .validator(|s| is_within_range(s, 1..))
+            .default_value(&default_args.banking_stage_look_ahead_window_size)
+            .help(
+                "Number of buffered transactions the central-scheduler block production method \
+                 looks ahead at to avoid scheduling conflicting transactions on different \
+                 threads.",
+            ),
+    )
+    .arg(
+        Arg::with_name("banking_stage_max_transactions_per_account")
+            .long("banking-stage-max-transactions-per-account")
+            .value_name("COUNT")
+            .takes_value(true)
+            .validator(|s| is_within_range(s, 1..))
+            .help(
+                "Maximum number of transactions writing to the same account that the \
+                 central-scheduler block production method schedules in one pass, leaving room \
+                 for transactions on other accounts. [default: unlimited]",
+            ),
+    )
+    .arg(
+        Arg::with_name("banking_stage_age_priority_weight")
+            .long("banking-stage-age-priority-weight")
+            .value_name("PRIORITY")
+            .takes_value(true)
+            .validator(is_parsable::<u64>)
+            .default_value(&default_args.banking_stage_age_priority_weight)
+            .help(
+                "Priority the central-scheduler block production method adds to buffered \
+                 transactions for every millisecond they have waited, when ordering the \
+                 transactions it looks ahead at. 0 orders transactions by fee alone.",
+            ),
+    )
+    .arg(
+        Arg::with_name("write_fenced_account")
+            .long("write-fenced-account")
+            .validator(is_pubkey)
+            .value_name("PUBKEY")
+            .multiple(true)
+            .takes_value(true)
+            .help(
+                "Account whose writes are kept out of the blocks produced by this validator, \
+                 such as a critical program upgrade authority. Transactions write locking the \
+                 account are dropped by the banking stage. This is a local policy: blocks from \
+                 other leaders that write the account are replayed as usual. May be specified \
+                 multiple times, and changed at runtime with `agave-validator write-fence`.",
+            ),
+    )
     .arg(
         Arg::with_name("unified_scheduler_handler_threads")
             .long("unified-scheduler-handler-threads")
@@ -4,7 +4,9 @@ use {
         admin_rpc_service::{load_staked_nodes_overrides, StakedNodesOverrides},
         bootstrap,
         cli::{self},
-        ledger_lockfile, lock_ledger, redirect_stderr_to_file,
+        ledger_lockfile, lock_ledger,
+        log_filter::LogFilter,
+        redirect_stderr_to_file,
     },
     clap::{crate_name, value_t, value_t_or_exit, values_t, values_t_or_exit, ArgMatches},
     crossbeam_channel::unbounded,
@@ -12,7 +14,7 @@ use {
     rand::{seq::SliceRandom, thread_rng},
     solana_accounts_db::{
         accounts_db::{AccountShrinkThreshold, AccountsDb, AccountsDbConfig, CreateAncientStorage},
-        accounts_file::StorageAccess,
+        accounts_file::{AccountsFileProvider, StorageAccess},
         accounts_index::{
             AccountIndex, AccountSecondaryIndexes, AccountSecondaryIndexesIncludeExclude,
             AccountsIndexConfig, IndexLimitMb, ScanFilter,
@@ -22,10 +24,15 @@ use {
             create_and_canonicalize_directory,
         },
     },
-    solana_clap_utils::input_parsers::{keypair_of, keypairs_of, pubkey_of, value_of, values_of},
+    solana_clap_utils::input_parsers::{
+        keypair_of, keypairs_of, pubkey_of, pubkeys_of, value_of, values_of,
+    },
     solana_core::{
+        accounts_replication_service::{AccountsReplicaConfig, AccountsReplicationConfig},
+        banking_stage::{PrioGraphSchedulerConfig, TransactionSpillConfig},
         banking_trace::DISABLED_BAKING_TRACE_DIR,
         consensus::tower_storage,
+        leader_prewarm_service::LeaderPrewarmConfig,
         system_monitor_service::SystemMonitorService,
         tpu::DEFAULT_TPU_COALESCE,
         validator::{
@@ -37,6 +44,9 @@ use {
     solana_gossip::{
         cluster_info::{Node, NodeConfig},
         contact_info::ContactInfo,
+        egress_budget::GossipBandwidthConfig,
+        peer_scores::GossipPeerScoresConfig,
+        port_mapping_service::PortMappingConfig,
     },
     solana_ledger::{
         blockstore_cleanup_service::{DEFAULT_MAX_LEDGER_SHREDS, DEFAULT_MIN_MAX_LEDGER_SHREDS},
@@ -46,6 +56,7 @@ use {
         },
         use_snapshot_archives_at_startup::{self, UseSnapshotArchivesAtStartup},
     },
+    solana_net_utils::nat::NatTraversalMethod,
     solana_perf::recycler::enable_recycler_warming,
     solana_poh::poh_service,
     solana_rpc::{
@@ -53,6 +64,8 @@ use {
         rpc_pubsub_service::PubSubConfig,
     },
     solana_runtime::{
+        bank::BankConfig,
+        non_circulating_supply::NonCirculatingAccounts,
         runtime_config::RuntimeConfig,
         snapshot_bank_utils::DISABLED_SNAPSHOT_ARCHIVE_INTERVAL,
         snapshot_config::{SnapshotConfig, SnapshotUsage},
@@ -132,7 +145,19 @@ pub fn execute(
         }
     };
     let use_progress_bar = logfile.is_none();
+    let log_filter = LogFilter::load(ledger_path);
+    // The validator's logger samples chatty modules. It logs to stderr, so
+    // logging to a file needs stderr to be redirected, which is unix only.
+    let logger_installed = (logfile.is_none() || cfg!(unix))
+        && log_filter
+            .install_logger()
+            .map_err(|err| eprintln!("Unable to install the validator's logger: {err}"))
+            .is_ok();
     let _logger_thread = redirect_stderr_to_file(logfile);
+    // Setting up the redirection resets the level of the installed logger
+    if logger_installed || !log_filter.module_levels().is_empty() {
+        log_filter.apply();
+    }
 
     info!("{} {}", crate_name!(), solana_version);
     info!("Starting validator with: {:#?}", std::env::args_os());
@@ -170,6 +195,21 @@ pub fn execute(
 
     let init_complete_file = matches.value_of("init_complete_file");
 
+    let snapshot_verification_rpc_urls =
+        values_t!(matches, "snapshot_verification_rpc_urls", String).unwrap_or_default();
+    let snapshot_verification_quorum = value_t!(matches, "snapshot_verification_quorum", usize)
+        .unwrap_or(snapshot_verification_rpc_urls.len() / 2 + 1);
+    if !snapshot_verification_rpc_urls.is_empty()
+        && !(1..=snapshot_verification_rpc_urls.len()).contains(&snapshot_verification_quorum)
+    {
+        clap::Error::with_description(
+            "--snapshot-verification-quorum must be between 1 and the number of \
+             --snapshot-verification-rpc-url arguments",
+            clap::ErrorKind::InvalidValue,
+        )
+        .exit();
+    }
+
     let rpc_bootstrap_config = bootstrap::RpcBootstrapConfig {
         no_genesis_fetch: matches.is_present("no_genesis_fetch"),
         no_snapshot_fetch: matches.is_present("no_snapshot_fetch"),
@@ -269,6 +309,15 @@ pub fn execute(
         .pop()
         .unwrap();
 
+    let mut non_circulating_accounts = NonCirculatingAccounts::default();
+    if matches.is_present("rpc_non_circulating_account") {
+        non_circulating_accounts.extend(values_t_or_exit!(
+            matches,
+            "rpc_non_circulating_account",
+            Pubkey
+        ));
+    }
+
     let debug_keys: Option<Arc<HashSet<_>>> = if matches.is_present("debug_key") {
         Some(Arc::new(
             values_t_or_exit!(matches, "debug_key", Pubkey)
@@ -317,6 +366,19 @@ pub fn execute(
     };
 
     let contact_debug_interval = value_t_or_exit!(matches, "contact_debug_interval", u64);
+    let gossip_bandwidth = GossipBandwidthConfig {
+        max_bytes_per_second: value_t!(matches, "gossip_egress_limit", u64).ok(),
+        max_peer_bytes_per_second: value_t!(matches, "gossip_egress_peer_limit", u64).ok(),
+    };
+    let gossip_peer_scores = GossipPeerScoresConfig {
+        ban_threshold: value_t!(matches, "gossip_ban_threshold", u64).ok(),
+        max_packets_per_second: value_t!(matches, "gossip_peer_packet_limit", u64).ok(),
+        allowlist: pubkeys_of(matches, "gossip_allowlisted_peers")
+            .unwrap_or_default()
+            .into_iter()
+            .collect(),
+        ..GossipPeerScoresConfig::default()
+    };
 
     let account_indexes = process_account_indexes(matches);
 
@@ -333,6 +395,7 @@ pub fn execute(
     };
 
     let tpu_connection_pool_size = value_t_or_exit!(matches, "tpu_connection_pool_size", usize);
+    let tpu_client_zero_rtt = matches.is_present("tpu_client_zero_rtt");
 
     let shrink_ratio = value_t_or_exit!(matches, "accounts_shrink_ratio", f64);
     if !(0.0..=1.0).contains(&shrink_ratio) {
@@ -507,6 +570,8 @@ pub fn execute(
             }
         })
         .unwrap_or_default();
+    let accounts_file_provider =
+        value_t!(matches, "accounts_db_storage_format", AccountsFileProvider).unwrap_or_default();
 
     let scan_filter_for_shrinking = matches
         .value_of("accounts_db_scan_filter_for_shrinking")
@@ -551,6 +616,7 @@ pub fn execute(
         test_skip_rewrites_but_include_in_bank_hash: matches
             .is_present("accounts_db_test_skip_rewrites"),
         storage_access,
+        accounts_file_provider,
         scan_filter_for_shrinking,
         enable_experimental_accumulator_hash: matches
             .is_present("accounts_db_experimental_accumulator_hash"),
@@ -561,6 +627,8 @@ pub fn execute(
         num_clean_threads: Some(accounts_db_clean_threads),
         num_foreground_threads: Some(accounts_db_foreground_threads),
         num_hash_threads: Some(accounts_db_hash_threads),
+        background_io_bytes_per_second: value_t!(matches, "accounts_db_background_io_limit", u64)
+            .ok(),
         ..AccountsDbConfig::default()
     };
 
@@ -646,6 +714,36 @@ pub fn execute(
 
     let full_api = matches.is_present("full_rpc_api");
 
+    let port_mapping_config = matches.is_present("nat_traversal").then(|| {
+        let method = match value_t_or_exit!(matches, "nat_traversal", NatTraversalMethod) {
+            NatTraversalMethod::NatPmp { .. } => NatTraversalMethod::NatPmp {
+                gateway: value_t!(matches, "nat_pmp_gateway", Ipv4Addr).ok(),
+            },
+            method => method,
+        };
+        let mut config = PortMappingConfig {
+            method,
+            ..PortMappingConfig::default()
+        };
+        if let Ok(lease_duration) = value_t!(matches, "nat_lease_duration", u64) {
+            config.lease_duration = Duration::from_secs(lease_duration);
+        }
+        config
+    });
+
+    let leader_prewarm_config = matches.is_present("leader_prewarm").then(|| {
+        let default = LeaderPrewarmConfig::default();
+        LeaderPrewarmConfig {
+            lead_slots: value_t!(matches, "leader_prewarm_lead_slots", u64)
+                .unwrap_or(default.lead_slots),
+            max_accounts: value_t!(matches, "leader_prewarm_max_accounts", usize)
+                .unwrap_or(default.max_accounts),
+            max_programs: value_t!(matches, "leader_prewarm_max_programs", usize)
+                .unwrap_or(default.max_programs),
+            ..default
+        }
+    });
+
     let mut validator_config = ValidatorConfig {
         require_tower: matches.is_present("require_tower"),
         tower_storage,
@@ -689,6 +787,19 @@ pub fn execute(
                 usize
             )),
             skip_preflight_health_check: matches.is_present("skip_preflight_health_check"),
+            preflight_cache_ttl_ms: value_t_or_exit!(matches, "rpc_preflight_cache_ttl_ms", u64),
+            preflight_cache_capacity: value_t_or_exit!(
+                matches,
+                "rpc_preflight_cache_capacity",
+                usize
+            ),
+            non_circulating_accounts,
+            max_inflight_transactions_per_fee_payer: value_t!(
+                matches,
+                "rpc_max_inflight_transactions_per_fee_payer",
+                usize
+            )
+            .ok(),
         },
         on_start_geyser_plugin_config_files,
         geyser_plugin_always_enabled: matches.is_present("geyser_plugin_always_enabled"),
@@ -701,6 +812,24 @@ pub fn execute(
                 // https://github.com/solana-labs/solana/issues/12250
             )
         }),
+        accounts_replication_config: matches
+            .value_of("accounts_replication_addr")
+            .map(|address| AccountsReplicationConfig {
+                addr: solana_net_utils::parse_host_port(address)
+                    .expect("failed to parse accounts replication address"),
+                catch_up_slots: value_t_or_exit!(
+                    matches,
+                    "accounts_replication_catch_up_slots",
+                    usize
+                ),
+            }),
+        accounts_replica_config: matches.value_of("accounts_replica_source").map(|address| {
+            AccountsReplicaConfig {
+                source: solana_net_utils::parse_host_port(address)
+                    .expect("failed to parse accounts replica source address"),
+                source_identity: pubkey_of(matches, "accounts_replica_source_identity").unwrap(),
+            }
+        }),
         pubsub_config: PubSubConfig {
             enable_block_subscription: matches.is_present("rpc_pubsub_enable_block_subscription"),
             enable_vote_subscription: matches.is_present("rpc_pubsub_enable_vote_subscription"),
@@ -736,6 +865,8 @@ pub fn execute(
             || matches.is_present("skip_startup_ledger_verification")),
         debug_keys,
         contact_debug_interval,
+        gossip_bandwidth,
+        gossip_peer_scores,
         send_transaction_service_config: send_transaction_service::Config {
             retry_rate_ms: rpc_send_retry_rate_ms,
             leader_forward_count,
@@ -758,6 +889,12 @@ pub fn execute(
                 usize
             ),
             tpu_peers: rpc_send_transaction_tpu_peers,
+            status_sender: None,
+            leader_prefetch_count: value_t_or_exit!(
+                matches,
+                "rpc_send_transaction_leader_prefetch_count",
+                u64
+            ),
         },
         no_poh_speed_test: matches.is_present("no_poh_speed_test"),
         no_os_memory_stats_reporting: matches.is_present("no_os_memory_stats_reporting"),
@@ -779,6 +916,10 @@ pub fn execute(
             log_messages_bytes_limit: value_of(matches, "log_messages_bytes_limit"),
             ..RuntimeConfig::default()
         },
+        bank_config: BankConfig {
+            intra_batch_execution_threads: value_of(matches, "intra_batch_execution_threads"),
+            epoch_stakes_retention: value_of(matches, "epoch_stakes_retention"),
+        },
         staked_nodes_overrides: staked_nodes_overrides.clone(),
         use_snapshot_archives_at_startup: value_t_or_exit!(
             matches,
@@ -790,10 +931,13 @@ pub fn execute(
         replay_forks_threads,
         replay_transactions_threads,
         tvu_shred_sigverify_threads: tvu_sigverify_threads,
+        turbine_use_quic: matches.is_present("turbine_use_quic"),
         delay_leader_block_for_pending_fork: matches
             .is_present("delay_leader_block_for_pending_fork"),
         wen_restart_proto_path: value_t!(matches, "wen_restart", PathBuf).ok(),
         wen_restart_coordinator: value_t!(matches, "wen_restart_coordinator", Pubkey).ok(),
+        port_mapping_config,
+        leader_prewarm_config,
         ..ValidatorConfig::default()
     };
 
@@ -996,6 +1140,7 @@ pub fn execute(
         maximum_incremental_snapshot_archives_to_retain,
         accounts_hash_debug_verify: validator_config.accounts_db_test_hash_calculation,
         packager_thread_niceness_adj: snapshot_packager_niceness_adj,
+        archive_manifest: matches.is_present("snapshot_archive_manifest"),
     };
 
     // The accounts hash interval shall match the snapshot interval
@@ -1064,6 +1209,42 @@ pub fn execute(
     )
     .unwrap_or_default();
     validator_config.enable_block_production_forwarding = staked_nodes_overrides_path.is_some();
+    validator_config.transaction_spill_config =
+        matches
+            .value_of("banking_stage_spill_path")
+            .map(|path| TransactionSpillConfig {
+                path: PathBuf::from(path),
+                max_bytes: value_t_or_exit!(matches, "banking_stage_spill_limit_mb", u64)
+                    .saturating_mul(MB as u64),
+            });
+    validator_config.prio_graph_scheduler_config = PrioGraphSchedulerConfig {
+        look_ahead_window_size: value_t_or_exit!(
+            matches,
+            "banking_stage_look_ahead_window_size",
+            usize
+        ),
+        max_transactions_per_account_per_pass: value_t!(
+            matches,
+            "banking_stage_max_transactions_per_account",
+            usize
+        )
+        .unwrap_or(usize::MAX),
+        age_priority_weight: value_t_or_exit!(matches, "banking_stage_age_priority_weight", u64),
+        ..PrioGraphSchedulerConfig::default()
+    };
+    validator_config.write_fenced_accounts = Arc::new(RwLock::new(
+        pubkeys_of(matches, "write_fenced_account")
+            .unwrap_or_default()
+            .into_iter()
+            .collect(),
+    ));
+    if !snapshot_verification_rpc_urls.is_empty() {
+        validator_config.snapshot_bank_hash_source =
+            Some(Arc::new(bootstrap::RpcQuorumBankHashSource::new(
+                snapshot_verification_rpc_urls,
+                snapshot_verification_quorum,
+            )));
+    }
     validator_config.unified_scheduler_handler_threads =
         value_t!(matches, "unified_scheduler_handler_threads", usize).ok();
 
@@ -1095,20 +1276,25 @@ pub fn execute(
         } else {
             (None, None)
         };
-    admin_rpc_service::run(
-        &ledger_path,
-        admin_rpc_service::AdminRpcRequestMetadata {
-            rpc_addr: validator_config.rpc_addrs.map(|(rpc_addr, _)| rpc_addr),
-            start_time: std::time::SystemTime::now(),
-            validator_exit: validator_config.validator_exit.clone(),
-            start_progress: start_progress.clone(),
-            authorized_voter_keypairs: authorized_voter_keypairs.clone(),
-            post_init: admin_service_post_init.clone(),
-            tower_storage: validator_config.tower_storage.clone(),
-            staked_nodes_overrides,
-            rpc_to_plugin_manager_sender,
-        },
-    );
+    let admin_rpc_metadata = admin_rpc_service::AdminRpcRequestMetadata {
+        rpc_addr: validator_config.rpc_addrs.map(|(rpc_addr, _)| rpc_addr),
+        start_time: std::time::SystemTime::now(),
+        validator_exit: validator_config.validator_exit.clone(),
+        start_progress: start_progress.clone(),
+        authorized_voter_keypairs: authorized_voter_keypairs.clone(),
+        post_init: admin_service_post_init.clone(),
+        tower_storage: validator_config.tower_storage.clone(),
+        staked_nodes_overrides,
+        rpc_to_plugin_manager_sender,
+        scheduled_exit: Arc::default(),
+        log_filter: Arc::new(RwLock::new(log_filter)),
+        gossip_custom_values: Arc::default(),
+    };
+    #[cfg(unix)]
+    if let Err(err) = admin_rpc_service::exit_on_sigterm(admin_rpc_metadata.clone()) {
+        warn!("Failed to install the SIGTERM handler: {err}");
+    }
+    admin_rpc_service::run(&ledger_path, admin_rpc_metadata);
 
     let gossip_host: IpAddr = matches
         .value_of("gossip_host")
@@ -1342,6 +1528,7 @@ pub fn execute(
             vote_use_quic,
             tpu_connection_pool_size,
             tpu_enable_udp,
+            tpu_client_zero_rtt,
             tpu_quic_server_config,
             tpu_fwd_quic_server_config,
             vote_quic_server_config,
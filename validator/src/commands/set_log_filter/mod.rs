@@ -1,7 +1,8 @@
 use {
     crate::{admin_rpc_service, cli::DefaultArgs},
-    clap::{value_t_or_exit, App, Arg, ArgMatches, SubCommand},
-    std::{path::Path, process::exit},
+    clap::{value_t, values_t, App, Arg, ArgGroup, ArgMatches, SubCommand},
+    log::LevelFilter,
+    std::{path::Path, process::exit, str::FromStr},
 };
 
 pub fn command(_default_args: &DefaultArgs) -> App<'_, '_> {
@@ -13,16 +14,196 @@ pub fn command(_default_args: &DefaultArgs) -> App<'_, '_> {
                 .index(1)
                 .help("New filter using the same format as the RUST_LOG environment variable"),
         )
-        .after_help("Note: the new filter only applies to the currently running validator instance")
+        .arg(
+            Arg::with_name("module")
+                .long("module")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1)
+                .value_name("MODULE=LEVEL")
+                .validator(|value| parse_module_level(&value).map(|_| ()))
+                .help("Log the module and its submodules at this level, regardless of the filter"),
+        )
+        .arg(
+            Arg::with_name("reset_module")
+                .long("reset-module")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1)
+                .value_name("MODULE")
+                .help("Log the module according to the filter again"),
+        )
+        .arg(
+            Arg::with_name("sample")
+                .long("sample")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1)
+                .value_name("MODULE=RECORDS_PER_SECOND")
+                .validator(|value| parse_sample_rate(&value).map(|_| ()))
+                .help(
+                    "Log at most this many records per second from the module and its \
+                     submodules, dropping the rest",
+                ),
+        )
+        .arg(
+            Arg::with_name("reset_sample")
+                .long("reset-sample")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1)
+                .value_name("MODULE")
+                .help("Log every record of the module again"),
+        )
+        .arg(
+            Arg::with_name("show")
+                .long("show")
+                .takes_value(false)
+                .help("Display the log filter after applying any changes"),
+        )
+        .arg(
+            Arg::with_name("output")
+                .long("output")
+                .takes_value(true)
+                .value_name("MODE")
+                .possible_values(&["json", "json-compact"])
+                .requires("show")
+                .help("Output display mode"),
+        )
+        .group(
+            ArgGroup::with_name("log_filter_changes")
+                .args(&[
+                    "filter",
+                    "module",
+                    "reset_module",
+                    "sample",
+                    "reset_sample",
+                    "show",
+                ])
+                .multiple(true)
+                .required(true),
+        )
+        .after_help(
+            "Note: the new filter only applies to the currently running validator instance, \
+             whereas module levels and sample rates also apply after a restart",
+        )
+}
+
+fn parse_module_level(value: &str) -> Result<(String, LevelFilter), String> {
+    let (module, level) = value
+        .split_once('=')
+        .ok_or_else(|| format!("Expected MODULE=LEVEL: {value}"))?;
+    if module.is_empty() || module.contains(',') {
+        return Err(format!("Invalid module: {module}"));
+    }
+    let level = LevelFilter::from_str(level).map_err(|_| format!("Invalid level: {level}"))?;
+    Ok((module.to_string(), level))
+}
+
+fn parse_sample_rate(value: &str) -> Result<(String, u64), String> {
+    let (module, max_records_per_second) = value
+        .split_once('=')
+        .ok_or_else(|| format!("Expected MODULE=RECORDS_PER_SECOND: {value}"))?;
+    if module.is_empty() || module.contains(',') {
+        return Err(format!("Invalid module: {module}"));
+    }
+    match max_records_per_second.parse() {
+        Ok(0) | Err(_) => Err(format!(
+            "Invalid records per second: {max_records_per_second}"
+        )),
+        Ok(max_records_per_second) => Ok((module.to_string(), max_records_per_second)),
+    }
 }
 
 pub fn execute(matches: &ArgMatches, ledger_path: &Path) {
-    let filter = value_t_or_exit!(matches, "filter", String);
+    let filter = value_t!(matches, "filter", String).ok();
+    let module_levels = values_t!(matches, "module", String)
+        .unwrap_or_default()
+        .iter()
+        .map(|value| parse_module_level(value).unwrap())
+        .map(|(module, level)| (module, Some(level.as_str().to_lowercase())));
+    let reset_modules = values_t!(matches, "reset_module", String)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|module| (module, None));
+    let module_levels: Vec<_> = reset_modules.chain(module_levels).collect();
+    let sample_rates = values_t!(matches, "sample", String)
+        .unwrap_or_default()
+        .iter()
+        .map(|value| parse_sample_rate(value).unwrap())
+        .map(|(module, max_records_per_second)| (module, Some(max_records_per_second)));
+    let reset_samples = values_t!(matches, "reset_sample", String)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|module| (module, None));
+    let sample_rates: Vec<_> = reset_samples.chain(sample_rates).collect();
+    let show = matches.is_present("show");
+
     let admin_client = admin_rpc_service::connect(ledger_path);
-    admin_rpc_service::runtime()
-        .block_on(async move { admin_client.await?.set_log_filter(filter).await })
+    let log_filter = admin_rpc_service::runtime()
+        .block_on(async move {
+            let admin_client = admin_client.await?;
+            if let Some(filter) = filter {
+                admin_client.set_log_filter(filter).await?;
+            }
+            for (module, level) in module_levels {
+                admin_client.set_module_log_level(module, level).await?;
+            }
+            for (module, max_records_per_second) in sample_rates {
+                admin_client
+                    .set_module_log_sample_rate(module, max_records_per_second)
+                    .await?;
+            }
+            if show {
+                admin_client.log_filter().await.map(Some)
+            } else {
+                Ok(None)
+            }
+        })
         .unwrap_or_else(|err| {
             println!("set log filter failed: {err}");
             exit(1);
         });
+
+    if let Some(log_filter) = log_filter {
+        match matches.value_of("output") {
+            Some("json") => println!("{}", serde_json::to_string_pretty(&log_filter).unwrap()),
+            Some("json-compact") => print!("{}", serde_json::to_string(&log_filter).unwrap()),
+            _ => print!("{log_filter}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_module_level() {
+        assert_eq!(
+            parse_module_level("solana_core::replay_stage=debug"),
+            Ok(("solana_core::replay_stage".to_string(), LevelFilter::Debug))
+        );
+        assert_eq!(
+            parse_module_level("solana_metrics=OFF"),
+            Ok(("solana_metrics".to_string(), LevelFilter::Off))
+        );
+        assert!(parse_module_level("solana_core").is_err());
+        assert!(parse_module_level("=debug").is_err());
+        assert!(parse_module_level("solana_core=loud").is_err());
+        assert!(parse_module_level("solana_core,solana_gossip=info").is_err());
+    }
+
+    #[test]
+    fn test_parse_sample_rate() {
+        assert_eq!(
+            parse_sample_rate("solana_metrics=10"),
+            Ok(("solana_metrics".to_string(), 10))
+        );
+        assert!(parse_sample_rate("solana_metrics").is_err());
+        assert!(parse_sample_rate("=10").is_err());
+        assert!(parse_sample_rate("solana_metrics=0").is_err());
+        assert!(parse_sample_rate("solana_metrics=many").is_err());
+        assert!(parse_sample_rate("solana_core,solana_gossip=10").is_err());
+    }
 }
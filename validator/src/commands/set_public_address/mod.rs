@@ -23,9 +23,17 @@ pub fn command(_default_args: &DefaultArgs) -> App<'_, '_> {
                 .validator(solana_net_utils::is_host_port)
                 .help("TPU Forwards address to advertise in gossip"),
         )
+        .arg(
+            Arg::with_name("rpc_addr")
+                .long("rpc")
+                .value_name("HOST:PORT")
+                .takes_value(true)
+                .validator(solana_net_utils::is_host_port)
+                .help("RPC address to advertise in gossip"),
+        )
         .group(
             ArgGroup::with_name("set_public_address_details")
-                .args(&["tpu_addr", "tpu_forwards_addr"])
+                .args(&["tpu_addr", "tpu_forwards_addr", "rpc_addr"])
                 .required(true)
                 .multiple(true),
         )
@@ -45,6 +53,7 @@ pub fn execute(matches: &ArgMatches, ledger_path: &Path) {
     };
     let tpu_addr = parse_arg_addr("tpu_addr", "tpu");
     let tpu_forwards_addr = parse_arg_addr("tpu_forwards_addr", "tpu-forwards");
+    let rpc_addr = parse_arg_addr("rpc_addr", "rpc");
 
     macro_rules! set_public_address {
         ($public_addr:expr, $set_public_address:ident, $request:literal) => {
@@ -67,4 +76,5 @@ pub fn execute(matches: &ArgMatches, ledger_path: &Path) {
         set_public_tpu_forwards_address,
         "setPublicTpuForwardsAddress"
     );
+    set_public_address!(rpc_addr, set_public_rpc_address, "setPublicRpcAddress");
 }
@@ -0,0 +1,97 @@
+use {
+    crate::{admin_rpc_service, cli::DefaultArgs, commands::FromClapArgMatches},
+    clap::{App, Arg, ArgMatches, SubCommand},
+    std::{path::Path, process::exit},
+};
+
+const COMMAND: &str = "socket-stats";
+
+#[derive(Debug, PartialEq)]
+pub struct SocketStatsArgs {
+    pub output: Option<String>,
+}
+
+impl FromClapArgMatches for SocketStatsArgs {
+    fn from_clap_arg_match(matches: &ArgMatches) -> Self {
+        SocketStatsArgs {
+            output: matches.value_of("output").map(String::from),
+        }
+    }
+}
+
+pub fn command(_default_args: &DefaultArgs) -> App<'_, '_> {
+    SubCommand::with_name(COMMAND)
+        .about(
+            "Display the packets received and dropped on each UDP socket read by the validator, \
+             since it started",
+        )
+        .arg(
+            Arg::with_name("output")
+                .long("output")
+                .takes_value(true)
+                .value_name("MODE")
+                .possible_values(&["json", "json-compact"])
+                .help("Output display mode"),
+        )
+}
+
+pub fn execute(matches: &ArgMatches, ledger_path: &Path) {
+    let socket_stats_args = SocketStatsArgs::from_clap_arg_match(matches);
+
+    let admin_client = admin_rpc_service::connect(ledger_path);
+    let socket_stats = admin_rpc_service::runtime()
+        .block_on(async move { admin_client.await?.socket_receive_stats().await })
+        .unwrap_or_else(|err| {
+            eprintln!("Socket stats query failed: {err}");
+            exit(1);
+        });
+    if let Some(mode) = socket_stats_args.output {
+        match mode.as_str() {
+            "json" => println!("{}", serde_json::to_string_pretty(&socket_stats).unwrap()),
+            "json-compact" => print!("{}", serde_json::to_string(&socket_stats).unwrap()),
+            _ => unreachable!(),
+        }
+    } else {
+        for stats in socket_stats {
+            print!("{stats}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::commands::tests::{
+            verify_args_struct_by_command, verify_args_struct_by_command_is_error,
+        },
+    };
+
+    #[test]
+    fn verify_args_struct_by_command_socket_stats_output_json() {
+        verify_args_struct_by_command(
+            command(&DefaultArgs::default()),
+            vec![COMMAND, "--output", "json"],
+            SocketStatsArgs {
+                output: Some("json".to_string()),
+            },
+        );
+    }
+
+    #[test]
+    fn verify_args_struct_by_command_socket_stats_output_default() {
+        verify_args_struct_by_command(
+            command(&DefaultArgs::default()),
+            vec![COMMAND],
+            SocketStatsArgs { output: None },
+        );
+    }
+
+    #[test]
+    fn verify_args_struct_by_command_socket_stats_output_invalid() {
+        verify_args_struct_by_command_is_error::<SocketStatsArgs>(
+            command(&DefaultArgs::default()),
+            vec![COMMAND, "--output", "invalid_output_type"],
+        );
+    }
+}
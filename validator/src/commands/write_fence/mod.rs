@@ -0,0 +1,118 @@
+use {
+    crate::{admin_rpc_service, cli::DefaultArgs},
+    clap::{values_t_or_exit, App, AppSettings, Arg, ArgMatches, SubCommand},
+    solana_clap_utils::input_validators::is_pubkey,
+    solana_sdk::pubkey::Pubkey,
+    std::{collections::HashSet, path::Path, process::exit},
+};
+
+pub fn command(_default_args: &DefaultArgs) -> App<'_, '_> {
+    SubCommand::with_name("write-fence")
+        .about("Manage the accounts whose writes the validator keeps out of its blocks")
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .setting(AppSettings::InferSubcommands)
+        .subcommand(
+            SubCommand::with_name("get")
+                .about("Display the validator's write fenced accounts")
+                .arg(
+                    Arg::with_name("output")
+                        .long("output")
+                        .takes_value(true)
+                        .value_name("MODE")
+                        .possible_values(&["json", "json-compact"])
+                        .help("Output display mode"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("set")
+                .about("Set the validator's write fenced accounts")
+                .setting(AppSettings::ArgRequiredElseHelp)
+                .arg(
+                    Arg::with_name("account")
+                        .long("account")
+                        .validator(is_pubkey)
+                        .value_name("PUBKEY")
+                        .multiple(true)
+                        .takes_value(true)
+                        .help("Account whose writes are kept out of the validator's blocks"),
+                )
+                .after_help(
+                    "Note: write fence changes only apply to the currently running validator instance",
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("remove-all")
+                .about("Clear the validator's write fenced accounts")
+                .after_help(
+                    "Note: write fence changes only apply to the currently running validator instance",
+                ),
+        )
+}
+
+pub fn execute(matches: &ArgMatches, ledger_path: &Path) {
+    match matches.subcommand() {
+        ("get", Some(subcommand_matches)) => {
+            let output_mode = subcommand_matches.value_of("output");
+            let admin_client = admin_rpc_service::connect(ledger_path);
+            let write_fence = admin_rpc_service::runtime()
+                .block_on(async move { admin_client.await?.write_fenced_accounts().await })
+                .unwrap_or_else(|err| {
+                    eprintln!("Write fence query failed: {err}");
+                    exit(1);
+                });
+            if let Some(mode) = output_mode {
+                match mode {
+                    "json" => println!("{}", serde_json::to_string_pretty(&write_fence).unwrap()),
+                    "json-compact" => {
+                        print!("{}", serde_json::to_string(&write_fence).unwrap())
+                    }
+                    _ => unreachable!(),
+                }
+            } else {
+                print!("{write_fence}");
+            }
+        }
+        ("set", Some(subcommand_matches)) => {
+            let accounts = if subcommand_matches.is_present("account") {
+                let accounts: HashSet<_> = values_t_or_exit!(subcommand_matches, "account", Pubkey)
+                    .into_iter()
+                    .collect();
+                accounts.into_iter().collect::<Vec<_>>()
+            } else {
+                return;
+            };
+            set_write_fenced_accounts(ledger_path, accounts).unwrap_or_else(|err| {
+                eprintln!("{err}");
+                exit(1);
+            });
+        }
+        ("remove-all", _) => {
+            set_write_fenced_accounts(ledger_path, Vec::default()).unwrap_or_else(|err| {
+                eprintln!("{err}");
+                exit(1);
+            });
+        }
+        _ => unreachable!(),
+    }
+}
+
+fn set_write_fenced_accounts(
+    ledger_path: &Path,
+    accounts: Vec<Pubkey>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let admin_client = admin_rpc_service::connect(ledger_path);
+    admin_rpc_service::runtime()
+        .block_on(async move {
+            admin_client
+                .await?
+                .set_write_fenced_accounts(accounts)
+                .await
+        })
+        .map_err(|err| {
+            std::io::Error::new(
+                std::io::ErrorKind::Other,
+                format!("setWriteFencedAccounts request failed: {err}"),
+            )
+        })?;
+    Ok(())
+}
@@ -0,0 +1,496 @@
+//! The validator's log filter, which can be adjusted per module at runtime
+//! through the admin RPC service.
+//!
+//! The filter is composed of a base filter, taken from `RUST_LOG` at startup
+//! and replaced by `setLogFilter`, and of per-module levels that take
+//! precedence over it. Chatty modules can also be sampled, capping how many
+//! records per second they log. The per-module levels and sample rates are
+//! persisted in the ledger directory so that they survive a restart.
+//!
+//! Sampling needs the validator's own logger, installed with
+//! [`LogFilter::install_logger`] in place of `solana_logger`'s.
+
+use {
+    log::*,
+    serde::{Deserialize, Serialize},
+    std::{
+        collections::BTreeMap,
+        env, fmt, fs, io,
+        path::{Path, PathBuf},
+        str::FromStr,
+        sync::{
+            atomic::{AtomicU64, Ordering},
+            OnceLock, RwLock,
+        },
+        time::{SystemTime, UNIX_EPOCH},
+    },
+};
+
+const MODULE_LOG_LEVELS_FILE: &str = "module-log-levels.json";
+
+/// The validator's logger, once installed
+static LOGGER: OnceLock<&'static SampledLogger> = OnceLock::new();
+
+#[derive(Debug)]
+pub struct LogFilter {
+    filter: String,
+    module_levels: BTreeMap<String, LevelFilter>,
+    /// The most records per second logged by these modules and their
+    /// submodules
+    sample_rates: BTreeMap<String, u64>,
+    /// Where the module settings are persisted, if anywhere
+    path: Option<PathBuf>,
+}
+
+/// The module settings, as persisted in the ledger directory
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct PersistedModuleSettings {
+    #[serde(default)]
+    levels: BTreeMap<String, String>,
+    #[serde(default)]
+    max_records_per_second: BTreeMap<String, u64>,
+}
+
+impl Default for LogFilter {
+    fn default() -> Self {
+        Self {
+            filter: env::var("RUST_LOG").unwrap_or_else(|_| solana_logger::DEFAULT_FILTER.into()),
+            module_levels: BTreeMap::default(),
+            sample_rates: BTreeMap::default(),
+            path: None,
+        }
+    }
+}
+
+impl LogFilter {
+    /// Returns the log filter with the module settings persisted in
+    /// `ledger_path`
+    pub fn load(ledger_path: &Path) -> Self {
+        let path = ledger_path.join(MODULE_LOG_LEVELS_FILE);
+        let (module_levels, sample_rates) = match fs::read(&path) {
+            Ok(data) => parse_module_settings(&data).unwrap_or_else(|err| {
+                warn!("Ignoring invalid module log settings in {path:?}: {err}");
+                Default::default()
+            }),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Default::default(),
+            Err(err) => {
+                warn!("Unable to read module log settings from {path:?}: {err}");
+                Default::default()
+            }
+        };
+        Self {
+            module_levels,
+            sample_rates,
+            path: Some(path),
+            ..Self::default()
+        }
+    }
+
+    /// Installs the validator's logger, which applies this filter and
+    /// samples the modules with a sample rate. Must be called before any
+    /// other logger is set up, `solana_logger`'s included.
+    pub fn install_logger(&self) -> Result<(), SetLoggerError> {
+        let logger = Box::leak(Box::new(SampledLogger::new(self)));
+        log::set_logger(logger)?;
+        log::set_max_level(logger.max_level());
+        let _ = LOGGER.set(logger);
+        Ok(())
+    }
+
+    pub fn filter(&self) -> &str {
+        &self.filter
+    }
+
+    pub fn module_levels(&self) -> &BTreeMap<String, LevelFilter> {
+        &self.module_levels
+    }
+
+    pub fn sample_rates(&self) -> &BTreeMap<String, u64> {
+        &self.sample_rates
+    }
+
+    /// Replaces the base filter, keeping the module levels
+    pub fn set_filter(&mut self, filter: String) {
+        self.filter = filter;
+        self.apply();
+    }
+
+    /// Sets the level of `module`, or removes it with `None` so that the
+    /// base filter applies to the module again
+    pub fn set_module_level(
+        &mut self,
+        module: String,
+        level: Option<LevelFilter>,
+    ) -> io::Result<()> {
+        match level {
+            Some(level) => self.module_levels.insert(module, level),
+            None => self.module_levels.remove(&module),
+        };
+        self.apply();
+        self.persist()
+    }
+
+    /// Caps the records per second logged by `module` and its submodules,
+    /// or removes the cap with `None`. Fails if the validator's logger
+    /// isn't installed, as no other logger samples.
+    pub fn set_sample_rate(
+        &mut self,
+        module: String,
+        max_records_per_second: Option<u64>,
+    ) -> io::Result<()> {
+        match max_records_per_second {
+            Some(max_records_per_second) => {
+                if LOGGER.get().is_none() {
+                    return Err(io::Error::new(
+                        io::ErrorKind::Unsupported,
+                        "log sampling requires the validator's logger",
+                    ));
+                }
+                self.sample_rates.insert(module, max_records_per_second)
+            }
+            None => self.sample_rates.remove(&module),
+        };
+        self.apply();
+        self.persist()
+    }
+
+    /// Makes this the filter of the process's logger
+    pub fn apply(&self) {
+        match LOGGER.get() {
+            Some(logger) => logger.update(self),
+            None => solana_logger::setup_with(&self.to_string()),
+        }
+    }
+
+    fn persist(&self) -> io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        if self.module_levels.is_empty() && self.sample_rates.is_empty() {
+            return match fs::remove_file(path) {
+                Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+                _ => Ok(()),
+            };
+        }
+        let settings = PersistedModuleSettings {
+            levels: self
+                .module_levels
+                .iter()
+                .map(|(module, level)| (module.clone(), level.as_str().to_lowercase()))
+                .collect(),
+            max_records_per_second: self.sample_rates.clone(),
+        };
+        fs::write(path, serde_json::to_vec_pretty(&settings)?)
+    }
+}
+
+/// Formats the filter in the `RUST_LOG` format, with the module levels
+/// following the base filter so that they take precedence
+impl fmt::Display for LogFilter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.filter)?;
+        for (module, level) in &self.module_levels {
+            write!(f, ",{module}={}", level.as_str().to_lowercase())?;
+        }
+        Ok(())
+    }
+}
+
+type ModuleSettings = (BTreeMap<String, LevelFilter>, BTreeMap<String, u64>);
+
+fn parse_module_settings(data: &[u8]) -> Result<ModuleSettings, String> {
+    let PersistedModuleSettings {
+        levels,
+        max_records_per_second,
+    } = serde_json::from_slice(data).map_err(|err| err.to_string())?;
+    let module_levels = levels
+        .into_iter()
+        .map(|(module, level)| {
+            let level = LevelFilter::from_str(&level)
+                .map_err(|_| format!("invalid level for {module}: {level}"))?;
+            Ok((module, level))
+        })
+        .collect::<Result<_, String>>()?;
+    Ok((module_levels, max_records_per_second))
+}
+
+/// Caps the records per second logged by a module, counting them in
+/// one-second windows
+#[derive(Debug)]
+struct ModuleSampler {
+    module: String,
+    max_records_per_second: u64,
+    /// The second the current window started at
+    window: AtomicU64,
+    /// Records seen in the current window
+    count: AtomicU64,
+    /// Records dropped and not yet reported
+    dropped: AtomicU64,
+}
+
+impl ModuleSampler {
+    fn new(module: String, max_records_per_second: u64) -> Self {
+        Self {
+            module,
+            max_records_per_second,
+            window: AtomicU64::default(),
+            count: AtomicU64::default(),
+            dropped: AtomicU64::default(),
+        }
+    }
+
+    /// Whether records of `target` are sampled by this module
+    fn matches(&self, target: &str) -> bool {
+        target
+            .strip_prefix(self.module.as_str())
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+    }
+
+    /// Returns whether to log a record at `now_secs` and, once a new window
+    /// starts, how many records were dropped in the previous ones
+    fn sample(&self, now_secs: u64) -> (bool, u64) {
+        let window = self.window.load(Ordering::Relaxed);
+        let mut dropped = 0;
+        if now_secs > window
+            && self
+                .window
+                .compare_exchange(window, now_secs, Ordering::Relaxed, Ordering::Relaxed)
+                .is_ok()
+        {
+            self.count.store(0, Ordering::Relaxed);
+            dropped = self.dropped.swap(0, Ordering::Relaxed);
+        }
+        if self.count.fetch_add(1, Ordering::Relaxed) < self.max_records_per_second {
+            (true, dropped)
+        } else {
+            self.dropped.fetch_add(1, Ordering::Relaxed);
+            (false, dropped)
+        }
+    }
+}
+
+/// Logs like `solana_logger`, except for the records of sampled modules
+/// beyond their rate, which are dropped and counted
+#[derive(Debug)]
+struct SampledLogger {
+    logger: RwLock<env_logger::Logger>,
+    /// Sorted by decreasing module length, so that the most specific module
+    /// samples a record
+    samplers: RwLock<Vec<ModuleSampler>>,
+}
+
+impl SampledLogger {
+    fn new(log_filter: &LogFilter) -> Self {
+        Self {
+            logger: RwLock::new(build_logger(log_filter)),
+            samplers: RwLock::new(build_samplers(log_filter)),
+        }
+    }
+
+    fn max_level(&self) -> LevelFilter {
+        self.logger.read().unwrap().filter()
+    }
+
+    fn update(&self, log_filter: &LogFilter) {
+        let logger = build_logger(log_filter);
+        log::set_max_level(logger.filter());
+        *self.logger.write().unwrap() = logger;
+        *self.samplers.write().unwrap() = build_samplers(log_filter);
+    }
+}
+
+impl Log for SampledLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.logger.read().unwrap().enabled(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        let logger = self.logger.read().unwrap();
+        if !logger.matches(record) {
+            return;
+        }
+        let samplers = self.samplers.read().unwrap();
+        if let Some(sampler) = samplers
+            .iter()
+            .find(|sampler| sampler.matches(record.target()))
+        {
+            let now_secs = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+            let (keep, dropped) = sampler.sample(now_secs);
+            if dropped > 0 {
+                logger.log(
+                    &Record::builder()
+                        .args(format_args!(
+                            "dropped {dropped} records of {} beyond its sample rate of {} per \
+                             second",
+                            sampler.module, sampler.max_records_per_second
+                        ))
+                        .level(Level::Warn)
+                        .target(record.target())
+                        .build(),
+                );
+            }
+            if !keep {
+                return;
+            }
+        }
+        logger.log(record);
+    }
+
+    fn flush(&self) {
+        self.logger.read().unwrap().flush();
+    }
+}
+
+fn build_logger(log_filter: &LogFilter) -> env_logger::Logger {
+    env_logger::Builder::new()
+        .parse_filters(&log_filter.to_string())
+        .format_timestamp_nanos()
+        .build()
+}
+
+fn build_samplers(log_filter: &LogFilter) -> Vec<ModuleSampler> {
+    let mut samplers: Vec<_> = log_filter
+        .sample_rates
+        .iter()
+        .map(|(module, max_records_per_second)| {
+            ModuleSampler::new(module.clone(), *max_records_per_second)
+        })
+        .collect();
+    samplers.sort_by_key(|sampler| std::cmp::Reverse(sampler.module.len()));
+    samplers
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, tempfile::TempDir};
+
+    #[test]
+    fn test_module_levels_persisted() {
+        let ledger_path = TempDir::new().unwrap();
+        let mut log_filter = LogFilter::load(ledger_path.path());
+        log_filter.filter = "info".to_string();
+        assert_eq!(log_filter.to_string(), "info");
+
+        log_filter
+            .set_module_level(
+                "solana_core::replay_stage".to_string(),
+                Some(LevelFilter::Debug),
+            )
+            .unwrap();
+        log_filter
+            .set_module_level("solana_metrics".to_string(), Some(LevelFilter::Off))
+            .unwrap();
+        assert_eq!(
+            log_filter.to_string(),
+            "info,solana_core::replay_stage=debug,solana_metrics=off"
+        );
+
+        // Module levels survive a restart, whereas the base filter doesn't
+        log_filter.set_filter("warn".to_string());
+        let mut restarted_log_filter = LogFilter::load(ledger_path.path());
+        assert_eq!(
+            restarted_log_filter.module_levels(),
+            log_filter.module_levels()
+        );
+        assert_ne!(restarted_log_filter.filter(), "warn");
+
+        restarted_log_filter
+            .set_module_level("solana_metrics".to_string(), None)
+            .unwrap();
+        restarted_log_filter
+            .set_module_level("solana_core::replay_stage".to_string(), None)
+            .unwrap();
+        assert!(!ledger_path.path().join(MODULE_LOG_LEVELS_FILE).exists());
+        assert!(LogFilter::load(ledger_path.path())
+            .module_levels()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_sample_rates_persisted() {
+        let ledger_path = TempDir::new().unwrap();
+        let mut log_filter = LogFilter::load(ledger_path.path());
+        // Without the validator's logger, modules can't be sampled
+        assert_eq!(
+            log_filter
+                .set_sample_rate("solana_metrics".to_string(), Some(10))
+                .unwrap_err()
+                .kind(),
+            io::ErrorKind::Unsupported
+        );
+        assert!(!ledger_path.path().join(MODULE_LOG_LEVELS_FILE).exists());
+
+        log_filter
+            .sample_rates
+            .insert("solana_metrics".to_string(), 10);
+        log_filter
+            .set_module_level("solana_core".to_string(), Some(LevelFilter::Trace))
+            .unwrap();
+        let restarted_log_filter = LogFilter::load(ledger_path.path());
+        assert_eq!(
+            restarted_log_filter.sample_rates(),
+            &BTreeMap::from([("solana_metrics".to_string(), 10)])
+        );
+        assert_eq!(
+            restarted_log_filter.module_levels(),
+            &BTreeMap::from([("solana_core".to_string(), LevelFilter::Trace)])
+        );
+    }
+
+    #[test]
+    fn test_module_sampler() {
+        let sampler = ModuleSampler::new("solana_metrics".to_string(), 2);
+        assert!(sampler.matches("solana_metrics"));
+        assert!(sampler.matches("solana_metrics::metrics"));
+        assert!(!sampler.matches("solana_metrics_extra"));
+        assert!(!sampler.matches("solana_core"));
+
+        assert_eq!(sampler.sample(100), (true, 0));
+        assert_eq!(sampler.sample(100), (true, 0));
+        assert_eq!(sampler.sample(100), (false, 0));
+        assert_eq!(sampler.sample(100), (false, 0));
+        // A new window reports the records dropped in the previous one
+        assert_eq!(sampler.sample(101), (true, 2));
+        assert_eq!(sampler.sample(101), (true, 0));
+        assert_eq!(sampler.sample(101), (false, 0));
+        assert_eq!(sampler.sample(105), (true, 1));
+    }
+
+    #[test]
+    fn test_samplers_most_specific_first() {
+        let log_filter = LogFilter {
+            sample_rates: BTreeMap::from([
+                ("solana_core".to_string(), 100),
+                ("solana_core::replay_stage".to_string(), 1),
+            ]),
+            ..LogFilter::default()
+        };
+        let samplers = build_samplers(&log_filter);
+        let sampler = samplers
+            .iter()
+            .find(|sampler| sampler.matches("solana_core::replay_stage"))
+            .unwrap();
+        assert_eq!(sampler.max_records_per_second, 1);
+        let sampler = samplers
+            .iter()
+            .find(|sampler| sampler.matches("solana_core::banking_stage"))
+            .unwrap();
+        assert_eq!(sampler.max_records_per_second, 100);
+    }
+
+    #[test]
+    fn test_invalid_module_levels_ignored() {
+        let ledger_path = TempDir::new().unwrap();
+        fs::write(
+            ledger_path.path().join(MODULE_LOG_LEVELS_FILE),
+            r#"{"levels": {"solana_core": "loud"}}"#,
+        )
+        .unwrap();
+        assert!(LogFilter::load(ledger_path.path())
+            .module_levels()
+            .is_empty());
+    }
+}
@@ -70,15 +70,33 @@ pub fn main() {
         ("wait-for-restart-window", Some(subcommand_matches)) => {
             commands::wait_for_restart_window::execute(subcommand_matches, &ledger_path);
         }
+        ("gossip-blacklist", Some(subcommand_matches)) => {
+            commands::gossip_blacklist::execute(subcommand_matches, &ledger_path);
+        }
         ("repair-shred-from-peer", Some(subcommand_matches)) => {
             commands::repair_shred_from_peer::execute(subcommand_matches, &ledger_path);
         }
         ("repair-whitelist", Some(repair_whitelist_subcommand_matches)) => {
             commands::repair_whitelist::execute(repair_whitelist_subcommand_matches, &ledger_path);
         }
+        ("retransmit-tree", Some(subcommand_matches)) => {
+            commands::retransmit_tree::execute(subcommand_matches, &ledger_path);
+        }
+        ("accounts-maintenance", Some(subcommand_matches)) => {
+            commands::accounts_maintenance::execute(subcommand_matches, &ledger_path);
+        }
         ("set-public-address", Some(subcommand_matches)) => {
             commands::set_public_address::execute(subcommand_matches, &ledger_path);
         }
+        ("reload-config", Some(subcommand_matches)) => {
+            commands::reload_config::execute(subcommand_matches, &ledger_path);
+        }
+        ("socket-stats", Some(subcommand_matches)) => {
+            commands::socket_stats::execute(subcommand_matches, &ledger_path);
+        }
+        ("write-fence", Some(subcommand_matches)) => {
+            commands::write_fence::execute(subcommand_matches, &ledger_path);
+        }
         _ => unreachable!(),
     };
 }